@@ -9,6 +9,10 @@
 import init, {
     gw_version,
     gw_health_check,
+    gw_build_profile,
+    gw_build_features,
+    gw_last_error,
+    gw_content_version,
     gw_create_test_battle,
     gw_preview_damage,
     gw_generate_battle_terrain,
@@ -20,7 +24,11 @@ import init, {
     gw_get_board_slots,
     gw_get_refresh_cost,
     gw_get_xp_cost,
+    gw_get_shop_odds,
+    gw_get_template_odds,
+    gw_find_unit_odds,
     gw_get_shop_info,
+    gw_get_shop_spells,
     gw_refresh_shop,
     gw_toggle_freeze,
     gw_buy_monster,
@@ -34,12 +42,20 @@ import init, {
     gw_deploy_from_bench,
     gw_recall_to_bench,
     gw_swap_positions,
+    gw_tavern_action,
+    gw_encode_board_code,
+    gw_decode_board_code,
+    gw_get_tavern_run_summary,
+    gw_get_ghost_run_score,
+    gw_collection_stats,
+    gw_craft_foil,
     gw_game_mode_name,
     gw_tavern_phase_name,
     GwPlayer,
     GwCard,
     GwBattle,
 } from 'game-wasm';
+import type { ClContentVersion } from './network/cl_network_types';
 
 // WASM 模块是否已初始化
 let wasmInitialized = false;
@@ -59,8 +75,19 @@ export interface ClWasmCard {
     cost: number;
     base_damage: number;
     base_defense?: number;
-    target_type?: 'SingleEnemy' | 'AllEnemies' | 'self' | 'SingleAlly' | 'AllAllies' | 'None';
+    target_type?: 'SingleEnemy' | 'AllEnemies' | 'SelfTarget' | 'SingleAlly' | 'AllAllies' | 'None';
     effects: ClWasmEffect[];
+    /** 闪卡外观 (纯装饰，对手可见) */
+    foil?: boolean;
+}
+
+/** 收藏统计 (对应 GcCollectionStats) */
+export interface ClCollectionStats {
+    total_templates: number;
+    owned_templates: number;
+    foil_templates: number;
+    owned_percent: number;
+    foil_percent: number;
 }
 
 /** 效果类型 */
@@ -191,6 +218,8 @@ export interface ClTavernMonster {
     hp: number;
     buy_price: number;
     sell_price: number;
+    /** 皮肤 ID (纯装饰，默认外观时缺省) */
+    skin?: string;
 }
 
 /** 商店槽位 */
@@ -198,6 +227,73 @@ export interface ClTavernShopSlot {
     index: number;
     monster: ClTavernMonster | null;
     frozen: boolean;
+    /** 调整前的基础价格 (高于 monster.buy_price 时为折扣) */
+    base_price: number;
+}
+
+/** 酒馆法术 (购买即结算) */
+export interface ClTavernSpell {
+    spell_id: string;
+    name: string;
+    description: string;
+    tier: 'Tier1' | 'Tier2' | 'Tier3' | 'Tier4' | 'Tier5';
+    cost: number;
+    effect: { type: 'Buff' | 'BuffBoard' | 'Discover' | 'Gold'; data: Record<string, unknown> };
+}
+
+/** 单个稀有度的商店概率 */
+export interface ClTierOdds {
+    tier: 'Tier1' | 'Tier2' | 'Tier3' | 'Tier4' | 'Tier5';
+    percent: number;
+    next_level_percent: number;
+    delta: number;
+    refresh_chance: number;
+}
+
+/** 商店概率预览 */
+export interface ClShopOddsPreview {
+    level: number;
+    is_max_level: boolean;
+    odds: ClTierOdds[];
+    expected_tier: number;
+    next_level_expected_tier: number;
+    expected_cost: number;
+}
+
+/** 指定模板的刷新概率 */
+export interface ClTemplateOdds {
+    template_id: string;
+    name: string;
+    tier: ClTierOdds['tier'];
+    tier_templates: number;
+    slot_chance: number;
+    refresh_chance: number;
+    /** 期望刷新花费 (当前等级无法出现时为 null) */
+    expected_refresh_cost: number | null;
+}
+
+/** 流程得分明细 */
+export interface ClRunScoreBreakdown {
+    rounds_survived: number;
+    survival_points: number;
+    gold_spent: number;
+    board_power: number;
+    economy_points: number;
+    board_stars: number;
+    composition_points: number;
+    damage_dealt: number;
+    damage_points: number;
+}
+
+/** 流程结算摘要 (结算界面) */
+export interface ClRunScore {
+    mode: 'Tavern' | 'Adventure';
+    player_id: string;
+    /** 最终名次 (冒险为 null) */
+    placement: number | null;
+    wins: number;
+    breakdown: ClRunScoreBreakdown;
+    total: number;
 }
 
 /** 可合并组 */
@@ -232,7 +328,7 @@ export async function cl_initWasm(): Promise<void> {
     wasmInitialized = true;
     
     const version = gw_version();
-    console.log(`✅ WASM 模块加载成功! 版本: ${version}`);
+    console.log(`✅ WASM 模块加载成功! 版本: ${version} (${gw_build_profile()})`);
     
     // 健康检查
     if (!gw_health_check()) {
@@ -257,6 +353,36 @@ export function cl_getWasmVersion(): string {
     return gw_version();
 }
 
+/**
+ * WASM 构建是否包含指定功能 (精简版只含战斗与酒馆)
+ */
+export function cl_hasWasmFeature(feature: 'maps' | 'boss' | 'adventure'): boolean {
+    if (!wasmInitialized) {
+        throw new Error('WASM 未初始化');
+    }
+    return gw_build_features().includes(feature);
+}
+
+/**
+ * WASM 最近一次错误 (导出函数返回 null 或失败时查询原因)
+ */
+export function cl_getWasmLastError(): string | undefined {
+    if (!wasmInitialized) {
+        return undefined;
+    }
+    return gw_last_error();
+}
+
+/**
+ * 本地内容版本 (seasonId 为服务器 /api/content/version 返回的 season_id)
+ */
+export function cl_getContentVersion(seasonId: string): ClContentVersion {
+    if (!wasmInitialized) {
+        throw new Error('WASM 未初始化');
+    }
+    return JSON.parse(gw_content_version(seasonId)) as ClContentVersion;
+}
+
 // =============================================================================
 // 战斗相关
 // =============================================================================
@@ -281,6 +407,16 @@ export function cl_createBattle(battleId: string): GwBattle {
     return new GwBattle(battleId);
 }
 
+/**
+ * 创建沙盒战斗 (练习模式，可任意编辑状态)
+ */
+export function cl_createSandboxBattle(battleId: string): GwBattle {
+    if (!wasmInitialized) {
+        throw new Error('WASM 未初始化');
+    }
+    return GwBattle.newSandbox(battleId);
+}
+
 /**
  * 预览伤害计算
  */
@@ -295,6 +431,32 @@ export function cl_previewDamage(
     return gw_preview_damage(attackerAttack, targetDefense, cardDamage) as ClWasmDamageResult;
 }
 
+/** 收藏统计 (拥有/闪卡完成度)，失败时返回 null */
+export function cl_collectionStats(collectionJson: string): ClCollectionStats | null {
+    if (!wasmInitialized) {
+        return null;
+    }
+    try {
+        return gw_collection_stats(collectionJson) as ClCollectionStats | null;
+    } catch (e) {
+        console.error('收藏统计失败:', e);
+        return null;
+    }
+}
+
+/** 用尘制作闪卡 (成功时 data 为更新后的收藏 JSON) */
+export function cl_craftFoil(collectionJson: string, templateId: string): ClOperationResult {
+    if (!wasmInitialized) {
+        return { success: false, error: 'WASM 未初始化' };
+    }
+    try {
+        return gw_craft_foil(collectionJson, templateId) as ClOperationResult;
+    } catch (e) {
+        console.error('制作闪卡失败:', e);
+        return { success: false, error: String(e) };
+    }
+}
+
 // =============================================================================
 // 卡牌创建
 // =============================================================================
@@ -360,7 +522,7 @@ export interface ClWasmBoss {
     defense: number;
     max_rage: number;
     current_rage: number;
-    rage_per_damage: number;
+    rage_per_mille: number;
     skills: ClWasmBossSkill[];
     rage_skill: ClWasmBossSkill;
     state: ClWasmBossState;
@@ -435,6 +597,9 @@ export interface ClWasmTerrainModifier {
     healing_bonus_percent: number;
 }
 
+/** 调色板 (色盲友好配色，颜色由 game-core 提供) */
+export type ClColorPalette = 'standard' | 'deuteranopia' | 'protanopia';
+
 /** 战斗地形生成结果 */
 export interface ClWasmBattleTerrainResult {
     player_terrain: string;
@@ -443,6 +608,8 @@ export interface ClWasmBattleTerrainResult {
     enemy_terrain_name: string;
     player_color: number[];
     enemy_color: number[];
+    player_pattern: string;
+    enemy_pattern: string;
 }
 
 /**
@@ -509,17 +676,19 @@ export function cl_calculateDirectAttack(
  * @param worldTerrain 世界地形类型（如 "Grassland", "Forest" 等）
  * @param enemyType 敌人类型
  * @param seed 随机种子
+ * @param palette 调色板（无障碍设置）
  */
 export function cl_generateBattleTerrain(
     worldTerrain: string,
     enemyType: string,
-    seed: number
+    seed: number,
+    palette: ClColorPalette = 'standard'
 ): ClWasmBattleTerrainResult | null {
     if (!wasmInitialized) {
         return null;
     }
     try {
-        const result = gw_generate_battle_terrain(worldTerrain, enemyType, seed);
+        const result = gw_generate_battle_terrain(worldTerrain, enemyType, seed, palette);
         return result as ClWasmBattleTerrainResult;
     } catch (e) {
         console.error('地形生成失败:', e);
@@ -683,6 +852,39 @@ export function cl_getXpCost(): number {
     return gw_get_xp_cost();
 }
 
+/** 获取商店概率预览 (各稀有度概率、升级变化、期望值) */
+export function cl_getShopOdds(level: number): ClShopOddsPreview | null {
+    if (!wasmInitialized) return null;
+    try {
+        return gw_get_shop_odds(level) as ClShopOddsPreview;
+    } catch (e) {
+        console.error('获取商店概率失败:', e);
+        return null;
+    }
+}
+
+/** 指定模板在下一次刷新中的概率 */
+export function cl_getTemplateOdds(shopJson: string, poolJson: string, templateId: string): ClTemplateOdds | null {
+    if (!wasmInitialized) return null;
+    try {
+        return gw_get_template_odds(shopJson, poolJson, templateId) as ClTemplateOdds | null;
+    } catch (e) {
+        console.error('获取模板概率失败:', e);
+        return null;
+    }
+}
+
+/** 找怪兽: 按名称或模板ID搜索刷新概率 */
+export function cl_findUnitOdds(shopJson: string, poolJson: string, query: string): ClTemplateOdds[] {
+    if (!wasmInitialized) return [];
+    try {
+        return (gw_find_unit_odds(shopJson, poolJson, query) as ClTemplateOdds[] | null) ?? [];
+    } catch (e) {
+        console.error('搜索怪兽概率失败:', e);
+        return [];
+    }
+}
+
 // =============================================================================
 // 酒馆模式 - 商店操作
 // =============================================================================
@@ -698,6 +900,17 @@ export function cl_getShopInfo(shopJson: string): ClTavernShopSlot[] {
     }
 }
 
+/** 获取商店法术槽位 (购买使用 cl_tavernAction 的 BuySpell) */
+export function cl_getShopSpells(shopJson: string): Array<ClTavernSpell | null> {
+    if (!wasmInitialized) return [];
+    try {
+        return (gw_get_shop_spells(shopJson) as Array<ClTavernSpell | null> | null) ?? [];
+    } catch (e) {
+        console.error('获取商店法术失败:', e);
+        return [];
+    }
+}
+
 /** 刷新商店 */
 export function cl_refreshShop(
     economyJson: string,
@@ -884,6 +1097,80 @@ export function cl_recallToBench(
     }
 }
 
+/** 手牌区错误码 (与 game-core GcError 对应) */
+export const CL_ERR_BENCH_FULL = 6001;
+
+/** 执行酒馆座位操作 (失败时 data 为 {code}) */
+export function cl_tavernAction(
+    seatJson: string,
+    poolJson: string,
+    action: object,
+    randomRolls: number[]
+): ClOperationResult {
+    if (!wasmInitialized) {
+        return { success: false, error: 'WASM 未初始化' };
+    }
+    try {
+        return gw_tavern_action(
+            seatJson,
+            poolJson,
+            JSON.stringify(action),
+            JSON.stringify(randomRolls)
+        ) as ClOperationResult;
+    } catch (e) {
+        console.error('酒馆操作失败:', e);
+        return { success: false, error: String(e) };
+    }
+}
+
+/** 生成阵容分享码 (成功时 data 为分享码) */
+export function cl_encodeBoardCode(arenaJson: string, benchJson: string): ClOperationResult {
+    if (!wasmInitialized) {
+        return { success: false, error: 'WASM 未初始化' };
+    }
+    try {
+        return gw_encode_board_code(arenaJson, benchJson) as ClOperationResult;
+    } catch (e) {
+        console.error('生成阵容分享码失败:', e);
+        return { success: false, error: String(e) };
+    }
+}
+
+/** 解析阵容分享码 (成功时 data 为 {composition, arena, bench}) */
+export function cl_decodeBoardCode(code: string, poolJson: string): ClOperationResult {
+    if (!wasmInitialized) {
+        return { success: false, error: 'WASM 未初始化' };
+    }
+    try {
+        return gw_decode_board_code(code, poolJson) as ClOperationResult;
+    } catch (e) {
+        console.error('解析阵容分享码失败:', e);
+        return { success: false, error: String(e) };
+    }
+}
+
+/** 酒馆对局结算摘要 (按名次排序) */
+export function cl_getTavernRunSummary(gameJson: string): ClRunScore[] {
+    if (!wasmInitialized) return [];
+    try {
+        return (gw_get_tavern_run_summary(gameJson) as ClRunScore[] | null) ?? [];
+    } catch (e) {
+        console.error('计算酒馆结算失败:', e);
+        return [];
+    }
+}
+
+/** 冒险/单人流程结算 (花费与伤害由调用方累计) */
+export function cl_getGhostRunScore(runJson: string, goldSpent: number, damageDealt: number): ClRunScore | null {
+    if (!wasmInitialized) return null;
+    try {
+        return gw_get_ghost_run_score(runJson, goldSpent, damageDealt) as ClRunScore | null;
+    } catch (e) {
+        console.error('计算流程结算失败:', e);
+        return null;
+    }
+}
+
 /** 战场内换位 */
 export function cl_swapPositions(arenaJson: string, slotA: number, slotB: number): ClOperationResult {
     if (!wasmInitialized) {
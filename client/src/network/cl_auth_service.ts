@@ -31,6 +31,13 @@ export interface ClUserInfo {
     username: string;
 }
 
+/** 账号删除申请 (对应 GcAccountDeletion，时间为 Unix 秒) */
+export interface ClAccountDeletion {
+    player_id: string;
+    requested_at: number;
+    purge_at: number;
+}
+
 /** 认证状态 */
 export type ClAuthState = 'idle' | 'loading' | 'authenticated' | 'error';
 
@@ -253,6 +260,46 @@ class ClAuthService {
         }
     }
 
+    /** 导出账号全部数据 (JSON 归档) */
+    async exportAccount(): Promise<Blob | null> {
+        if (!this._token) {
+            return null;
+        }
+
+        try {
+            const response = await fetch(`${this._baseUrl}/api/account/export`, {
+                method: 'GET',
+                headers: this.getAuthHeaders(),
+            });
+            return response.ok ? await response.blob() : null;
+        } catch (e) {
+            console.warn('[ClAuthService] 导出账号数据失败:', e);
+            return null;
+        }
+    }
+
+    /** 查询 / 申请 / 撤销账号删除 (申请后宽限期内可撤销) */
+    async accountDeletion(action: 'get' | 'request' | 'cancel'): Promise<ClAccountDeletion | null> {
+        if (!this._token) {
+            return null;
+        }
+
+        const method = { get: 'GET', request: 'POST', cancel: 'DELETE' }[action];
+        try {
+            const response = await fetch(`${this._baseUrl}/api/account/deletion`, {
+                method,
+                headers: this.getAuthHeaders(),
+            });
+            if (!response.ok || action === 'cancel') {
+                return null;
+            }
+            return await response.json();
+        } catch (e) {
+            console.warn('[ClAuthService] 账号删除请求失败:', e);
+            return null;
+        }
+    }
+
     /** 获取带认证的请求头 */
     getAuthHeaders(): Record<string, string> {
         if (!this._token) {
@@ -20,12 +20,37 @@
 import {
     ClMessageType,
     ClPlayCardRequest,
+    ClUseSkillRequest,
+    ClStartChannelRequest,
     ClGameStartedEvent,
     ClGameStateEvent,
     ClTurnStartEvent,
     ClCardPlayedEvent,
     ClTurnEndedEvent,
     ClGameEndedEvent,
+    ClPlayerMatchStats,
+    ClMatchReward,
+    ClMatchRewardsEvent,
+    ClPublicAction,
+    ClActionFeedEvent,
+    ClPage,
+    ClPageRequest,
+    ClBattleWindow,
+    ClBattleWindowEvent,
+    ClHandPageEvent,
+    ClCombatLogPageEvent,
+    ClPing,
+    ClPingIntent,
+    ClPingTarget,
+    ClTeamPingEvent,
+    ClPauseStatus,
+    ClPauseStatusEvent,
+    ClSeatControlEvent,
+    ClOnboardingFlag,
+    ClOnboardingProgress,
+    ClOnboardingUpdatedEvent,
+    ClResyncEvent,
+    ClWinProbability,
     ClErrorResponse,
 } from './cl_network_types';
 import { ClWebSocketCore, cl_getWebSocketCore } from './cl_websocket_core';
@@ -58,7 +83,18 @@ export interface ClBattleServiceCallbacks {
     onTurnStart?: (playerId: string, isMyTurn: boolean) => void;
     onCardPlayed?: (playerId: string, cardId: string, isMyCard: boolean) => void;
     onTurnEnd?: (playerId: string) => void;
-    onGameEnd?: (winnerId: string | null, isWinner: boolean) => void;
+    onGameEnd?: (winnerId: string | null, isWinner: boolean, stats: ClPlayerMatchStats[]) => void;
+    onMatchRewards?: (myReward: ClMatchReward | null, rewards: ClMatchReward[]) => void;
+    onWinProbability?: (probabilities: ClWinProbability[]) => void;
+    onActionFeed?: (entries: ClPublicAction[]) => void;
+    onBattleWindow?: (window: ClBattleWindow) => void;
+    onHandPage?: (ownerId: string, page: ClPage<unknown>) => void;
+    onCombatLogPage?: (page: ClPage<unknown>) => void;
+    onTeamPing?: (ping: ClPing) => void;
+    onPauseStatus?: (status: ClPauseStatus) => void;
+    onSeatControl?: (playerId: string, bot: boolean) => void;
+    onOnboardingUpdated?: (completed: ClOnboardingFlag[], progress: ClOnboardingProgress) => void;
+    onResync?: (dropped: number) => void;
     
     // 错误
     onError?: (code: string, message: string) => void;
@@ -127,6 +163,40 @@ export class ClBattleService {
         return this.wsCore.send(ClMessageType.PlayCard, request);
     }
 
+    /**
+     * 使用英雄技能 (每回合一次)
+     */
+    useSkill(skillId: string, targetId?: string): boolean {
+        if (this.phase !== ClBattlePhase.MyTurn) {
+            console.error('❌ 不是你的回合');
+            return false;
+        }
+        
+        const request: ClUseSkillRequest = {
+            skill_id: skillId,
+            target_id: targetId,
+        };
+        
+        return this.wsCore.send(ClMessageType.UseSkill, request);
+    }
+
+    /**
+     * 开始引导 (多回合后结算，受伤会被打断)
+     */
+    startChannel(templateId: string, targetId?: string): boolean {
+        if (this.phase !== ClBattlePhase.MyTurn) {
+            console.error('❌ 不是你的回合');
+            return false;
+        }
+        
+        const request: ClStartChannelRequest = {
+            template_id: templateId,
+            target_id: targetId,
+        };
+        
+        return this.wsCore.send(ClMessageType.StartChannel, request);
+    }
+
     /**
      * 结束回合
      */
@@ -139,6 +209,73 @@ export class ClBattleService {
         return this.wsCore.send(ClMessageType.EndTurn);
     }
 
+    /**
+     * 查询公开行动记录 (历史面板，结果经 onActionFeed 返回)
+     */
+    requestActionFeed(fromTurn?: number, toTurn?: number): boolean {
+        return this.wsCore.send(ClMessageType.GetActionFeed, { from_turn: fromTurn, to_turn: toTurn });
+    }
+
+    /**
+     * 查询战斗窗口 (视野内玩家的单位与最近日志，结果经 onBattleWindow 返回)
+     */
+    requestBattleWindow(playerIds: string[] = [], logTail = 0): boolean {
+        return this.wsCore.send(ClMessageType.GetBattleWindow, { player_ids: playerIds, log_tail: logTail });
+    }
+
+    /**
+     * 分页查询手牌 (ownerId 缺省为自己，结果经 onHandPage 返回)
+     */
+    requestHandPage(page: ClPageRequest = {}, ownerId?: string): boolean {
+        return this.wsCore.send(ClMessageType.GetHandPage, { owner_id: ownerId, page });
+    }
+
+    /**
+     * 分页查询战斗日志 (offset 缺省为最近一页，结果经 onCombatLogPage 返回)
+     */
+    requestCombatLog(page: ClPageRequest = {}): boolean {
+        return this.wsCore.send(ClMessageType.GetCombatLog, { page });
+    }
+
+    /**
+     * 发送组队标记 (只转发给队友，服务器限流，超出的标记直接丢弃)
+     */
+    sendPing(target: ClPingTarget, intent: ClPingIntent): boolean {
+        if (this.phase === ClBattlePhase.Idle || this.phase === ClBattlePhase.GameOver) {
+            return false;
+        }
+        
+        return this.wsCore.send(ClMessageType.SendPing, { target, intent });
+    }
+
+    /**
+     * 申请暂停 (其他玩家同意后对局冻结)
+     */
+    requestPause(): boolean {
+        return this.wsCore.send(ClMessageType.RequestPause);
+    }
+
+    /**
+     * 同意其他玩家的暂停申请
+     */
+    acceptPause(): boolean {
+        return this.wsCore.send(ClMessageType.AcceptPause);
+    }
+
+    /**
+     * 拒绝或撤回暂停申请
+     */
+    declinePause(): boolean {
+        return this.wsCore.send(ClMessageType.DeclinePause);
+    }
+
+    /**
+     * 继续暂停中的对局
+     */
+    resumeBattle(): boolean {
+        return this.wsCore.send(ClMessageType.ResumeBattle);
+    }
+
     /**
      * 投降
      */
@@ -236,12 +373,18 @@ export class ClBattleService {
             this.setPhase(isMyTurn ? ClBattlePhase.MyTurn : ClBattlePhase.OpponentTurn);
             
             this.callbacks.onGameStart?.(this.battleState!);
+            if (data.win_probability?.length) {
+                this.callbacks.onWinProbability?.(data.win_probability);
+            }
         });
 
         // 状态更新
         this.wsCore.on<ClGameStateEvent>(ClMessageType.GameState, (data) => {
             this.battleState = JSON.parse(data.battle_state);
             this.callbacks.onStateUpdate?.(this.battleState!);
+            if (data.win_probability?.length) {
+                this.callbacks.onWinProbability?.(data.win_probability);
+            }
         });
 
         // 回合开始
@@ -280,8 +423,57 @@ export class ClBattleService {
             console.log(`🏆 游戏结束! 获胜者: ${data.winner_id || '平局'}`);
             this.setPhase(ClBattlePhase.GameOver);
             
-            const isWinner = data.winner_id === this.localPlayerId;
-            this.callbacks.onGameEnd?.(data.winner_id, isWinner);
+            // 组队时队友获胜同样算作胜利
+            const winners = data.winner_ids ?? (data.winner_id ? [data.winner_id] : []);
+            const isWinner = winners.includes(this.localPlayerId);
+            this.callbacks.onGameEnd?.(data.winner_id, isWinner, data.stats ?? []);
+        });
+
+        // 对局奖励 (结算界面显示)
+        this.wsCore.on<ClMatchRewardsEvent>(ClMessageType.MatchRewards, (data) => {
+            const myReward = data.rewards.find(r => r.player_id === this.localPlayerId) ?? null;
+            this.callbacks.onMatchRewards?.(myReward, data.rewards);
+        });
+
+        // 公开行动记录 (历史面板)
+        this.wsCore.on<ClActionFeedEvent>(ClMessageType.ActionFeed, (data) => {
+            this.callbacks.onActionFeed?.(data.entries);
+        });
+
+        // 分页与窗口查询
+        this.wsCore.on<ClBattleWindowEvent>(ClMessageType.BattleWindow, (data) => {
+            this.callbacks.onBattleWindow?.(data.window);
+        });
+        this.wsCore.on<ClHandPageEvent>(ClMessageType.HandPage, (data) => {
+            this.callbacks.onHandPage?.(data.owner_id, data.page);
+        });
+        this.wsCore.on<ClCombatLogPageEvent>(ClMessageType.CombatLogPage, (data) => {
+            this.callbacks.onCombatLogPage?.(data.page);
+        });
+
+        // 座位托管 (断线玩家由机器人代打)
+        this.wsCore.on<ClSeatControlEvent>(ClMessageType.SeatControl, (data) => {
+            this.callbacks.onSeatControl?.(data.player_id, data.bot);
+        });
+
+        // 新手引导有新完成的步骤 (只发给本人)
+        this.wsCore.on<ClOnboardingUpdatedEvent>(ClMessageType.OnboardingUpdated, (data) => {
+            this.callbacks.onOnboardingUpdated?.(data.completed, data.progress);
+        });
+
+        // 连接丢失消息，随后的快照消息按正常流程覆盖本地状态
+        this.wsCore.on<ClResyncEvent>(ClMessageType.Resync, (data) => {
+            this.callbacks.onResync?.(data.dropped);
+        });
+
+        // 队友的标记
+        this.wsCore.on<ClTeamPingEvent>(ClMessageType.TeamPing, (data) => {
+            this.callbacks.onTeamPing?.(data.ping);
+        });
+
+        // 暂停状态 (申请、暂停、继续)
+        this.wsCore.on<ClPauseStatusEvent>(ClMessageType.PauseStatus, (data) => {
+            this.callbacks.onPauseStatus?.(data.status);
         });
 
         // 错误
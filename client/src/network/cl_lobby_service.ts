@@ -7,9 +7,10 @@
  * 
  * 职责:
  * 1. 用户登录/登出
- * 2. 房间列表获取
+ * 2. 房间列表获取 (订阅大厅后由服务器推送增量)
  * 3. 房间创建/加入/离开
  * 4. 玩家准备状态
+ * 5. 暂存对局的列表与恢复
  * 
  * 设计原则:
  * - 高层业务逻辑封装
@@ -20,16 +21,29 @@
 import {
     ClMessageType,
     ClLoginRequest,
+    ClAppearance,
     ClCreateRoomRequest,
     ClJoinRoomRequest,
+    ClResumeRoomRequest,
     ClLoginSuccessResponse,
+    ClContentVersion,
+    ClContentVersionWarningEvent,
+    ClWorldTime,
+    ClWorldTimeEvent,
+    ClRegionWeather,
+    ClWeatherEvent,
     ClRoomCreatedResponse,
     ClRoomJoinedResponse,
     ClPlayerInfo,
     ClPlayerJoinedEvent,
     ClPlayerLeftEvent,
+    ClBattleFormat,
+    ClRoomSettingsEvent,
     ClRoomListResponse,
     ClRoomSummary,
+    ClLobbyDeltasEvent,
+    ClParkedRoomInfo,
+    ClParkedRoomsResponse,
     ClErrorResponse,
 } from './cl_network_types';
 import { ClWebSocketCore, cl_getWebSocketCore } from './cl_websocket_core';
@@ -57,20 +71,30 @@ export interface ClLobbyCallbacks {
     // 登录
     onLoginSuccess?: (playerId: string) => void;
     onLoginFailed?: (error: string) => void;
+    onContentVersionWarning?: (components: string[]) => void;
+    
+    // 世界时间 (昼夜)
+    onWorldTime?: (time: ClWorldTime) => void;
+    
+    // 区域天气 (渲染天气效果)
+    onWeather?: (regions: ClRegionWeather[]) => void;
     
     // 房间列表
     onRoomListUpdate?: (rooms: ClRoomSummary[]) => void;
+    onParkedRoomsUpdate?: (rooms: ClParkedRoomInfo[]) => void;
     
     // 房间事件
     onRoomCreated?: (roomId: string) => void;
     onRoomJoined?: (roomId: string, players: ClPlayerInfo[]) => void;
     onRoomLeft?: () => void;
+    onRoomResumed?: (roomId: string, players: ClPlayerInfo[]) => void;
     
     // 房间内事件
     onPlayerJoined?: (playerId: string, name: string) => void;
     onPlayerLeft?: (playerId: string) => void;
     onPlayerReady?: (playerId: string) => void;
     onPlayerUnready?: (playerId: string) => void;
+    onRoomSettings?: (settings: ClRoomSettingsEvent) => void;
     
     // 游戏开始（转交给战斗服务）
     onGameStarting?: () => void;
@@ -91,6 +115,7 @@ export class ClLobbyService {
     // 玩家信息
     private playerId: string = '';
     private playerName: string = '';
+    private contentVersion: ClContentVersion | undefined;
     
     // 房间信息
     private currentRoomId: string | null = null;
@@ -109,7 +134,7 @@ export class ClLobbyService {
     /**
      * 连接并登录
      */
-    async connectAndLogin(playerId: string, playerName: string): Promise<void> {
+    async connectAndLogin(playerId: string, playerName: string, appearance?: ClAppearance, slot?: number): Promise<void> {
         this.playerId = playerId;
         this.playerName = playerName;
         
@@ -118,23 +143,33 @@ export class ClLobbyService {
         try {
             await this.wsCore.connect();
             this.setState(ClLobbyState.NotLoggedIn);
-            this.login(playerId, playerName);
+            this.login(playerId, playerName, appearance, slot);
         } catch (error) {
             this.setState(ClLobbyState.Offline);
             throw error;
         }
     }
 
+    /**
+     * 设置登录时上报的内容版本 (服务器据此检测过期的 WASM 缓存)
+     */
+    setContentVersion(version: ClContentVersion | undefined): void {
+        this.contentVersion = version;
+    }
+
     /**
      * 登录（连接后调用）
      */
-    login(playerId: string, playerName: string): void {
+    login(playerId: string, playerName: string, appearance?: ClAppearance, slot?: number): void {
         this.playerId = playerId;
         this.playerName = playerName;
         
         const request: ClLoginRequest = {
             player_id: playerId,
             name: playerName,
+            appearance,
+            slot,
+            content_version: this.contentVersion,
         };
         
         this.wsCore.send(ClMessageType.Login, request);
@@ -160,6 +195,20 @@ export class ClLobbyService {
         this.wsCore.send(ClMessageType.GetRoomList);
     }
 
+    /**
+     * 订阅大厅 (服务器先下发完整列表，之后推送增量；再次调用即重新同步)
+     */
+    subscribeLobby(): void {
+        this.wsCore.send(ClMessageType.SubscribeLobby);
+    }
+
+    /**
+     * 取消订阅大厅
+     */
+    unsubscribeLobby(): void {
+        this.wsCore.send(ClMessageType.UnsubscribeLobby);
+    }
+
     /**
      * 创建房间
      */
@@ -185,7 +234,22 @@ export class ClLobbyService {
     }
 
     /**
-     * 离开房间
+     * 查询可恢复的暂存对局
+     */
+    refreshParkedRooms(): void {
+        this.wsCore.send(ClMessageType.ListParkedRooms);
+    }
+
+    /**
+     * 恢复暂存对局 (成功后服务器推送 GameStarted)
+     */
+    resumeRoom(roomId: string): void {
+        const request: ClResumeRoomRequest = { room_id: roomId };
+        this.wsCore.send(ClMessageType.ResumeRoom, request);
+    }
+
+    /**
+     * 离开房间 (对局进行中时服务器保留最近一次回合存档)
      */
     leaveRoom(): void {
         this.wsCore.send(ClMessageType.LeaveRoom);
@@ -209,6 +273,20 @@ export class ClLobbyService {
         this.wsCore.send(ClMessageType.Unready);
     }
 
+    /**
+     * 设置赛制（房主，决斗 / 自由混战 / 2v2）
+     */
+    setRoomFormat(format: ClBattleFormat): void {
+        this.wsCore.send(ClMessageType.SetRoomFormat, { format });
+    }
+
+    /**
+     * 选择队伍（2v2，队伍 0 或 1）
+     */
+    setTeam(team: number): void {
+        this.wsCore.send(ClMessageType.SetTeam, { team });
+    }
+
     /**
      * 开始游戏（房主）
      */
@@ -301,6 +379,22 @@ export class ClLobbyService {
             this.callbacks.onLoginSuccess?.(data.player_id);
         });
 
+        // 内容版本过期
+        this.wsCore.on<ClContentVersionWarningEvent>(ClMessageType.ContentVersionWarning, (data) => {
+            console.warn(`⚠️ 客户端内容已过期: ${data.components.join(', ')}`);
+            this.callbacks.onContentVersionWarning?.(data.components);
+        });
+
+        // 世界时间 (登录后与昼夜切换时)
+        this.wsCore.on<ClWorldTimeEvent>(ClMessageType.WorldTime, (data) => {
+            this.callbacks.onWorldTime?.(data.time);
+        });
+
+        // 区域天气 (登录后与天气变化时)
+        this.wsCore.on<ClWeatherEvent>(ClMessageType.Weather, (data) => {
+            this.callbacks.onWeather?.(data.regions);
+        });
+
         // 登录失败
         this.wsCore.on<ClErrorResponse>(ClMessageType.LoginFailed, (data) => {
             console.error(`❌ 登录失败: ${data.message}`);
@@ -313,6 +407,23 @@ export class ClLobbyService {
             this.callbacks.onRoomListUpdate?.(data.rooms);
         });
 
+        // 大厅增量
+        this.wsCore.on<ClLobbyDeltasEvent>(ClMessageType.LobbyDeltas, (data) => {
+            for (const delta of data.deltas) {
+                if (delta.kind === 'RoomClosed') {
+                    this.roomList = this.roomList.filter(r => r.id !== delta.room_id);
+                    continue;
+                }
+                const index = this.roomList.findIndex(r => r.id === delta.room.id);
+                if (index >= 0) {
+                    this.roomList[index] = delta.room;
+                } else {
+                    this.roomList.push(delta.room);
+                }
+            }
+            this.callbacks.onRoomListUpdate?.([...this.roomList]);
+        });
+
         // 房间创建成功
         this.wsCore.on<ClRoomCreatedResponse>(ClMessageType.RoomCreated, (data) => {
             console.log(`🏠 房间创建成功: ${data.room_id}`);
@@ -336,6 +447,20 @@ export class ClLobbyService {
             this.callbacks.onRoomJoined?.(data.room_id, data.players);
         });
 
+        // 恢复暂存对局
+        this.wsCore.on<ClRoomJoinedResponse>(ClMessageType.RoomResumed, (data) => {
+            console.log(`♻️ 恢复对局: ${data.room_id}`);
+            this.currentRoomId = data.room_id;
+            this.roomPlayers = data.players;
+            this.setState(ClLobbyState.InRoom);
+            this.callbacks.onRoomResumed?.(data.room_id, data.players);
+        });
+
+        // 暂存对局列表
+        this.wsCore.on<ClParkedRoomsResponse>(ClMessageType.ParkedRooms, (data) => {
+            this.callbacks.onParkedRoomsUpdate?.(data.rooms);
+        });
+
         // 玩家加入
         this.wsCore.on<ClPlayerJoinedEvent>(ClMessageType.PlayerJoined, (data) => {
             console.log(`👋 玩家加入: ${data.name}`);
@@ -369,6 +494,11 @@ export class ClLobbyService {
             this.callbacks.onPlayerUnready?.(data.player_id);
         });
 
+        // 房间赛制与队伍
+        this.wsCore.on<ClRoomSettingsEvent>(ClMessageType.RoomSettings, (data) => {
+            this.callbacks.onRoomSettings?.(data);
+        });
+
         // 游戏开始
         this.wsCore.on(ClMessageType.GameStarted, () => {
             console.log('⚔️ 游戏开始!');
@@ -16,7 +16,8 @@ export type ClMcpHistoryAction =
     | ClMcpHistoryDelete
     | ClMcpHistoryMove
     | ClMcpHistoryClearArea
-    | ClMcpHistorySpawnBatch;
+    | ClMcpHistorySpawnBatch
+    | ClMcpHistorySpawnGroup;
 
 /**
  * 生成实体的历史记录
@@ -77,6 +78,18 @@ export interface ClMcpHistorySpawnBatch {
     entityIds: string[];
 }
 
+/**
+ * 放置组合预制体的历史记录
+ */
+export interface ClMcpHistorySpawnGroup {
+    type: 'spawn_group';
+    instanceId: string;
+    entities: Array<{
+        prefabId: string;
+        position: Vector3;
+    }>;
+}
+
 /**
  * MCP 命令历史管理器
  */
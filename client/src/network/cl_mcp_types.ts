@@ -39,6 +39,31 @@ export interface ClMcpSpawnBatch {
     center: { x: number; y: number };
     radius: number;
     count: number;
+    /** 服务器挑选的位置 (缺省时在半径内随机放置) */
+    positions?: { x: number; y: number }[];
+}
+
+/** 组合预制体放置后的实体 (绝对位置) */
+export interface ClMcpGroupEntity {
+    entity_type: string;
+    prefab_id: string;
+    position: { x: number; y: number };
+    rotation: number;
+    scale: number;
+}
+
+export interface ClMcpSpawnGroup {
+    group_id: string;
+    instance_id: string;
+    origin: { x: number; y: number };
+    rotation: number;
+    entities: ClMcpGroupEntity[];
+}
+
+export interface ClMcpExpandGroup {
+    instance_id: string;
+    /** 实例中的实体 (拆开后按单个实体编辑) */
+    entities?: ClMcpGroupEntity[];
 }
 
 export interface ClMcpUndo {
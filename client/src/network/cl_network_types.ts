@@ -33,16 +33,33 @@ export enum ClMessageType {
     JoinRoom = 'JoinRoom',
     LeaveRoom = 'LeaveRoom',
     GetRoomList = 'GetRoomList',
+    SubscribeLobby = 'SubscribeLobby',
+    UnsubscribeLobby = 'UnsubscribeLobby',
+    ResumeRoom = 'ResumeRoom',
+    ListParkedRooms = 'ListParkedRooms',
     
     // 房间内
     Ready = 'Ready',
     Unready = 'Unready',
+    SetRoomFormat = 'SetRoomFormat',
+    SetTeam = 'SetTeam',
     StartGame = 'StartGame',
     
     // 游戏内
     PlayCard = 'PlayCard',
+    UseSkill = 'UseSkill',
+    StartChannel = 'StartChannel',
     EndTurn = 'EndTurn',
     Surrender = 'Surrender',
+    GetActionFeed = 'GetActionFeed',
+    GetBattleWindow = 'GetBattleWindow',
+    GetHandPage = 'GetHandPage',
+    GetCombatLog = 'GetCombatLog',
+    SendPing = 'SendPing',
+    RequestPause = 'RequestPause',
+    AcceptPause = 'AcceptPause',
+    DeclinePause = 'DeclinePause',
+    ResumeBattle = 'ResumeBattle',
     
     // 心跳
     Ping = 'Ping',
@@ -57,29 +74,45 @@ export enum ClMessageType {
     // 认证响应
     LoginSuccess = 'LoginSuccess',
     LoginFailed = 'LoginFailed',
+    ContentVersionWarning = 'ContentVersionWarning',
     
     // 大厅响应
     RoomList = 'RoomList',
+    LobbyDeltas = 'LobbyDeltas',
     RoomCreated = 'RoomCreated',
     RoomJoined = 'RoomJoined',
+    RoomResumed = 'RoomResumed',
+    ParkedRooms = 'ParkedRooms',
     
     // 房间事件
     PlayerJoined = 'PlayerJoined',
     PlayerLeft = 'PlayerLeft',
     PlayerReady = 'PlayerReady',
     PlayerUnready = 'PlayerUnready',
+    RoomSettings = 'RoomSettings',
+    SeatControl = 'SeatControl',
     
     // 游戏事件
     GameStarted = 'GameStarted',
     GameState = 'GameState',
     TurnStart = 'TurnStart',
     CardPlayed = 'CardPlayed',
+    SkillUsed = 'SkillUsed',
     TurnEnded = 'TurnEnded',
+    ActionFeed = 'ActionFeed',
+    BattleWindow = 'BattleWindow',
+    HandPage = 'HandPage',
+    CombatLogPage = 'CombatLogPage',
+    TeamPing = 'TeamPing',
+    PauseStatus = 'PauseStatus',
     GameEnded = 'GameEnded',
+    MatchRewards = 'MatchRewards',
+    OnboardingUpdated = 'OnboardingUpdated',
     
     // 通用
     Error = 'Error',
     Pong = 'Pong',
+    Resync = 'Resync',
     
     // =========================================================================
     // 世界同步 (多人探索)
@@ -104,6 +137,8 @@ export enum ClMessageType {
     
     // 世界状态
     WorldState = 'WorldState',
+    WorldTime = 'WorldTime',
+    Weather = 'Weather',
 }
 
 // =============================================================================
@@ -123,10 +158,31 @@ export interface ClMessage<T = unknown> {
 // 请求数据类型 (客户端 -> 服务器)
 // =============================================================================
 
+/** 角色外观 (对应 GcAppearance) */
+export interface ClAppearance {
+    palette_id: number;
+    hair_id: number;
+    face_id: number;
+}
+
 export interface ClLoginRequest {
     player_id: string;
     name: string;
     token?: string; // 可选的认证 token
+    appearance?: ClAppearance; // 角色外观，房间内其他玩家可见
+    slot?: number; // 存档槽位，对局奖励发放到该槽位
+    content_version?: ClContentVersion; // 本地 WASM 的内容版本 (cl_getContentVersion)
+}
+
+/** 内容版本 (与 game-core GcContentVersion 对应) */
+export interface ClContentVersion {
+    schema_version: number;
+    season_id: string;
+    cards: string;
+    monsters: string;
+    structures: string;
+    talents: string;
+    maps: string;
 }
 
 export interface ClCreateRoomRequest {
@@ -140,12 +196,27 @@ export interface ClJoinRoomRequest {
     password?: string;
 }
 
+export interface ClResumeRoomRequest {
+    room_id: string;
+}
+
 export interface ClPlayCardRequest {
     card_id: string;
     target_id?: string;
     target_position?: number;
 }
 
+export interface ClUseSkillRequest {
+    skill_id: string;
+    target_id?: string;
+}
+
+/** 按模板开始引导 (对应 GC_CHANNEL_* 模板 ID，如 pyroblast / meditation) */
+export interface ClStartChannelRequest {
+    template_id: string;
+    target_id?: string;
+}
+
 // =============================================================================
 // 响应数据类型 (服务器 -> 客户端)
 // =============================================================================
@@ -155,6 +226,120 @@ export interface ClLoginSuccessResponse {
     session_id?: string;
 }
 
+/** 内容版本过期 (登录成功后收到，应提示刷新页面) */
+export interface ClContentVersionWarningEvent {
+    components: string[];
+    server: ClContentVersion;
+}
+
+/** 昼夜时段 */
+export type ClDayPhase = 'Dawn' | 'Day' | 'Dusk' | 'Night';
+
+/** 世界时间 (登录后收到，时段切换时全服广播；两次广播之间按 time_ratio 本地推算) */
+export interface ClWorldTime {
+    day: number;
+    hour: number;
+    minute: number;
+    phase: ClDayPhase;
+    time_ratio: number;      // 游戏秒 / 现实秒
+    next_phase_at: number;   // Unix 秒
+}
+
+export interface ClWorldTimeEvent {
+    time: ClWorldTime;
+}
+
+/** 天气 */
+export type ClWeather = 'Clear' | 'Rain' | 'Storm' | 'Snow';
+
+/** 区域天气 (区域 ID 为地图 ID；climate 为大世界地形) */
+export interface ClRegionWeather {
+    region_id: string;
+    climate: string;
+    weather: ClWeather;
+    next_update_at: number;  // Unix 秒，届时天气可能不变
+}
+
+/** 天气 (登录后收到全部区域，之后只广播天气变化的区域) */
+export interface ClWeatherEvent {
+    regions: ClRegionWeather[];
+}
+
+/** 回放中的玩家 */
+export interface ClReplayPlayer {
+    id: string;
+    name: string;
+    profession?: string;
+}
+
+/** 分享回放摘要 (POST /api/replays 与 GET /api/replays 返回) */
+export interface ClReplaySummary {
+    code: string;             // 8 位分享码
+    mode: 'YuGiOhStyle' | 'TavernStyle';
+    players: ClReplayPlayer[];
+    winner_id: string | null;
+    turns: number;
+    event_count: number;
+    size_bytes: number;
+    public: boolean;
+    uploaded_at: number;      // Unix 秒
+    expires_at: number;       // Unix 秒
+}
+
+/** 幽灵流程类型 */
+export type ClGhostMode = 'Tavern' | 'Adventure';
+
+/** 幽灵阵容单位 */
+export interface ClGhostUnit {
+    template_id: string;
+    star: number;
+    atk: number;
+    hp: number;
+    position: { row: 'Front' | 'Back'; slot: number };
+}
+
+/** 幽灵回合摘要 */
+export interface ClGhostRound {
+    round: number;
+    units: ClGhostUnit[];
+    health: number;
+    won: boolean | null;      // null 为平局
+}
+
+/** 幽灵 (POST /api/ghosts 上传，GET /api/ghosts/:player_id 获取) */
+export interface ClGhostRun {
+    format_version: number;
+    mode: ClGhostMode;
+    player_id: string;
+    player_name: string;
+    rounds: ClGhostRound[];
+    score: number | null;
+}
+
+export type ClGhostStanding = 'Ahead' | 'Even' | 'Behind';
+
+/** 与幽灵竞赛的逐回合差值 (自己 - 幽灵) */
+export interface ClGhostRoundDelta {
+    round: number;
+    health_delta: number;
+    power_delta: number;
+    stars_delta: number;
+    wins_delta: number;
+    won: boolean | null;
+    ghost_won: boolean | null;
+    standing: ClGhostStanding;
+}
+
+/** 竞赛结果 (POST /api/ghosts/:player_id/race 返回) */
+export interface ClGhostRace {
+    ghost_player_id: string;
+    ghost_player_name: string;
+    rounds: ClGhostRoundDelta[];
+    score_delta: number;
+    standing: ClGhostStanding;
+    ghost_score: number | null;
+}
+
 export interface ClRoomCreatedResponse {
     room_id: string;
 }
@@ -169,6 +354,7 @@ export interface ClPlayerInfo {
     name: string;
     ready: boolean;
     is_host: boolean;
+    appearance?: ClAppearance;
 }
 
 export interface ClRoomListResponse {
@@ -180,13 +366,43 @@ export interface ClRoomSummary {
     name: string;
     player_count: number;
     max_players: number;
-    has_password: boolean;
-    status: 'waiting' | 'playing';
+    has_password?: boolean;
+    status?: 'waiting' | 'playing';
+    game_started?: boolean;
+}
+
+/**
+ * 大厅房间增量 (订阅大厅后服务器推送，节流合并)
+ */
+export type ClLobbyDelta =
+    | { kind: 'RoomCreated'; room: ClRoomSummary }
+    | { kind: 'RoomUpdated'; room: ClRoomSummary }
+    | { kind: 'RoomClosed'; room_id: string };
+
+export interface ClLobbyDeltasEvent {
+    deltas: ClLobbyDelta[];
+}
+
+/**
+ * 暂存对局 (休闲对局每回合自动存档，玩家离开后可继续)
+ */
+export interface ClParkedRoomInfo {
+    room_id: string;
+    name: string;
+    player_ids: string[];
+    turn: number;
+    saved_at: number;    // Unix 秒
+    expires_at: number;  // Unix 秒，过期后无法恢复
+}
+
+export interface ClParkedRoomsResponse {
+    rooms: ClParkedRoomInfo[];
 }
 
 export interface ClPlayerJoinedEvent {
     player_id: string;
     name: string;
+    appearance?: ClAppearance;
 }
 
 export interface ClPlayerLeftEvent {
@@ -194,12 +410,35 @@ export interface ClPlayerLeftEvent {
     reason?: string;
 }
 
+/** 牌区视图 (对手的牌区只有数量) */
+export interface ClZoneView {
+    count: number;
+    cards?: unknown[];
+}
+
+export interface ClPlayerZones {
+    player_id: string;
+    hand: ClZoneView;
+    deck: ClZoneView;
+    discard: ClZoneView;
+}
+
+/** 胜率估算 (对应 GcWinProbability，千分比) */
+export interface ClWinProbability {
+    player_id: string;
+    per_mille: number;
+}
+
 export interface ClGameStartedEvent {
-    battle_state: string; // JSON 序列化的战斗状态
+    battle_state: string; // JSON 序列化的战斗状态 (按接收者脱敏)
+    zones: ClPlayerZones[];
+    win_probability?: ClWinProbability[]; // 服务器开启胜率广播时附带
 }
 
 export interface ClGameStateEvent {
     battle_state: string;
+    zones: ClPlayerZones[];
+    win_probability?: ClWinProbability[];
 }
 
 export interface ClTurnStartEvent {
@@ -214,18 +453,246 @@ export interface ClCardPlayedEvent {
     result?: string;
 }
 
+export interface ClSkillUsedEvent {
+    player_id: string;
+    skill_id: string;
+    result: string; // JSON 序列化的技能结果
+}
+
 export interface ClTurnEndedEvent {
     player_id: string;
 }
 
+/** 战斗赛制 (对应 GcBattleFormat) */
+export type ClBattleFormat = 'Duel' | 'FreeForAll' | 'TwoVsTwo';
+
+/** 房间赛制与队伍 (玩家 ID -> 队伍 0/1，仅 2v2) */
+export interface ClRoomSettingsEvent {
+    format: ClBattleFormat;
+    teams: Record<string, number>;
+}
+
+/** 座位托管状态 (断线超过宽限期由机器人接管，重连后交还) */
+export interface ClSeatControlEvent {
+    player_id: string;
+    bot: boolean;
+}
+
+/** 对局统计 (对应 GcPlayerMatchStats) */
+export interface ClPlayerMatchStats {
+    player_id: string;
+    team?: number;
+    damage_dealt: number;
+    healing_done: number;
+    kills: number;
+    deaths: number;
+    assists: number;
+}
+
 export interface ClGameEndedEvent {
     winner_id: string | null;
+    /** 获胜方全部玩家 (2v2 时包含队友) */
+    winner_ids?: string[];
+    /** 对局统计 (按座位顺序) */
+    stats?: ClPlayerMatchStats[];
     reason: 'victory' | 'surrender' | 'disconnect' | 'timeout';
 }
 
+/** 对局奖励 (对应 GcMatchReward) */
+export interface ClMatchReward {
+    player_id: string;
+    source: 'Tavern' | 'CardBattle';
+    placement?: number;
+    won?: boolean;
+    grant: { gold: number; xp: number };
+}
+
+export interface ClMatchRewardsEvent {
+    rewards: ClMatchReward[];
+}
+
+/** 新手引导标记 (对应 GcOnboardingFlag) */
+export type ClOnboardingFlag =
+    | 'first_card_played'
+    | 'first_card_deployed'
+    | 'first_skill_used'
+    | 'first_battle_finished'
+    | 'first_battle_won'
+    | 'first_monster_bought'
+    | 'first_monster_deployed'
+    | 'first_merge'
+    | 'first_tavern_finished'
+    | 'first_tavern_win';
+
+/** 新手引导进度 (对应 GcOnboardingProgress) */
+export interface ClOnboardingProgress {
+    completed: ClOnboardingFlag[];
+}
+
+/** 单个模式的引导清单 (对应 GcOnboardingChecklist，GET /api/player/:id/onboarding 返回) */
+export interface ClOnboardingChecklist {
+    mode: 'CardBattle' | 'Tavern';
+    steps: { flag: ClOnboardingFlag; done: boolean }[];
+    next: ClOnboardingFlag | null;
+}
+
+export interface ClOnboardingUpdatedEvent {
+    completed: ClOnboardingFlag[];
+    progress: ClOnboardingProgress;
+}
+
+/** 连接丢失了消息 (服务器随后推送房间、战斗、座位等完整状态快照，应整体刷新) */
+export interface ClResyncEvent {
+    dropped: number;
+}
+
+/** 公开行动 (对应 GcPublicActionKind，隐藏信息已脱敏) */
+export type ClPublicActionKind =
+    | { type: 'PhaseChanged'; data: { phase: string } }
+    | { type: 'CardPlayed'; data: { card_name: string; card_type: string; cost: number; target_id: string } }
+    | { type: 'SkillUsed'; data: { skill_id: string; target_id: string } }
+    | { type: 'ChannelStarted'; data: { template_id: string; target_id: string } }
+    | { type: 'CardAcquired'; data: { card_name: string } }
+    | { type: 'PoolRefreshed' }
+    | { type: 'CardDeployed'; data: { card_name: string; slot_index: number } }
+    | { type: 'CombatResolved' }
+    | { type: 'TurnStarted' }
+    | { type: 'CardReceived' };
+
+/** 公开行动记录条目 (对应 GcPublicAction) */
+export interface ClPublicAction {
+    seq: number;
+    event_index: number;
+    turn: number;
+    player_id: string | null;
+    kind: ClPublicActionKind;
+    hp_changes?: { player_id: string; before: number; after: number }[];
+}
+
+/** 公开行动记录 (行动后增量推送，或查询的回合区间) */
+export interface ClActionFeedEvent {
+    entries: ClPublicAction[];
+}
+
+/** 分页请求 (对应 GcPageRequest，offset 缺省为最后 limit 条，limit 为 0 时取默认值) */
+export interface ClPageRequest {
+    offset?: number | null;
+    limit?: number;
+}
+
+/** 一页数据 (对应 GcPage) */
+export interface ClPage<T> {
+    items: T[];
+    offset: number;
+    total: number;
+}
+
+/** 战斗窗口中的玩家概要 (对应 GcWindowPlayer) */
+export interface ClWindowPlayer {
+    player_id: string;
+    name: string;
+    team: number | null;
+    state: string;
+    hp: number;
+    max_hp: number;
+    hand_count: number;
+    deck_count: number;
+    discard_count: number;
+    in_view: boolean;
+}
+
+/** 战斗窗口中的战场单位 (对应 GcWindowUnit) */
+export interface ClWindowUnit {
+    owner_id: string;
+    slot_index: number;
+    card: unknown;
+    remaining_hp: number;
+    can_attack: boolean;
+}
+
+/** 战斗窗口 (对应 GcBattleWindow，低端设备代替完整状态) */
+export interface ClBattleWindow {
+    battle_id: string;
+    turn: number;
+    phase: string;
+    current_player_id: string | null;
+    winner_ids: string[];
+    players: ClWindowPlayer[];
+    units: ClWindowUnit[];
+    recent_log: ClPage<unknown>;
+}
+
+export interface ClBattleWindowEvent {
+    window: ClBattleWindow;
+}
+
+/** 手牌的一页 (他人手牌 items 为空，total 为数量) */
+export interface ClHandPageEvent {
+    owner_id: string;
+    page: ClPage<unknown>;
+}
+
+export interface ClCombatLogPageEvent {
+    page: ClPage<unknown>;
+}
+
+/** 标记意图 (对应 GcPingIntent) */
+export type ClPingIntent = 'Attack' | 'Defend' | 'Caution' | 'Look';
+
+/** 标记对象 (对应 GcPingTarget) */
+export type ClPingTarget =
+    | { kind: 'Player'; player_id: string }
+    | { kind: 'BattlefieldSlot'; player_id: string; slot: number };
+
+export interface ClSendPingRequest {
+    target: ClPingTarget;
+    intent: ClPingIntent;
+}
+
+/** 队友的标记 (对应 GcPing) */
+export interface ClPing {
+    sender_id: string;
+    target: ClPingTarget;
+    intent: ClPingIntent;
+    turn: number;
+}
+
+export interface ClTeamPingEvent {
+    ping: ClPing;
+}
+
+/** 对局暂停状态 (对应 GcPauseStatus) */
+export interface ClPauseStatus {
+    paused: boolean;
+    /** 待同意申请的申请者 */
+    requested_by: string | null;
+    /** 进行中暂停的申请者 */
+    paused_by: string | null;
+    /** 剩余暂停额度 (秒) */
+    remaining_secs: number;
+    /** 暂停中时: 额度用尽、自动继续的时间 (Unix 秒) */
+    resumes_at: number | null;
+}
+
+export interface ClPauseStatusEvent {
+    status: ClPauseStatus;
+}
+
 export interface ClErrorResponse {
     code: string;
     message: string;
+    /** 消息解析失败时的字段路径诊断 (code 为 PARSE_ERROR) */
+    diagnostic?: ClJsonDiagnostic;
+}
+
+/** JSON 解析诊断 (对应 GcJsonDiagnostic) */
+export interface ClJsonDiagnostic {
+    input: string;
+    path: string;
+    message: string;
+    expected: string | null;
+    line: number;
+    column: number;
 }
 
 // =============================================================================
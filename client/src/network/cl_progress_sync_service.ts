@@ -9,6 +9,7 @@
  */
 
 import { cl_getAuthService } from './cl_auth_service';
+import type { ClAppearance } from './cl_network_types';
 
 // =============================================================================
 // 类型定义
@@ -22,8 +23,66 @@ export interface ClPlayerProgress {
     current_map: string;
     game_flags: Record<string, boolean>;
     statistics: ClPlayerStatistics;
+    /** 存档难度 (省略为 Normal) */
+    difficulty?: ClDifficulty;
+    /** 敌人等级随玩家缩放 */
+    level_scaling?: boolean;
 }
 
+/** 难度预设 (对应 GcDifficulty) */
+export type ClDifficulty = 'Story' | 'Normal' | 'Hard';
+
+/** 存档槽位摘要 (对应 GcSaveSlotSummary) */
+export interface ClSaveSlotSummary {
+    slot: number;
+    name: string | null;
+    profession: string | null;
+    level: number;
+    current_map: string | null;
+    difficulty: ClDifficulty;
+    /** 最后保存时间 (Unix 秒) */
+    last_save_at: number | null;
+}
+
+/** 角色 (对应 GcCharacter) */
+export interface ClCharacter {
+    name: string;
+    profession: string;
+    appearance: ClAppearance;
+}
+
+/** 每个账号的最大存档槽位数 (对应 GC_MAX_SAVE_SLOTS) */
+export const CL_MAX_SAVE_SLOTS = 3;
+
+/** 设置格式版本 (对应 GC_SETTINGS_SCHEMA_VERSION) */
+export const CL_SETTINGS_SCHEMA_VERSION = 1;
+
+/** 界面设置值 (对应 GcSettingValue) */
+export type ClSettingValue = boolean | number | string;
+
+/** 账号设置 (对应 GcPlayerSettings) */
+export interface ClPlayerSettings {
+    schema_version: number;
+    /** 动作 → 按键列表 */
+    keybinds: Record<string, string[]>;
+    /** 界面偏好 */
+    ui: Record<string, ClSettingValue>;
+}
+
+/** 带版本号的设置记录 (对应 GcSettingsRecord) */
+export interface ClSettingsRecord {
+    /** 版本号 (每次保存加 1，从未保存为 0) */
+    revision: number;
+    /** 最后保存时间 (Unix 秒) */
+    updated_at: number;
+    settings: ClPlayerSettings;
+}
+
+/** 保存设置的结果: 成功返回新记录，版本冲突返回服务器当前版本号 */
+export type ClSaveSettingsResult =
+    | { ok: true; record: ClSettingsRecord }
+    | { ok: false; conflict: number | null; message: string };
+
 /** 玩家统计数据 */
 export interface ClPlayerStatistics {
     battles_won: number;
@@ -43,6 +102,7 @@ class ClProgressSyncService {
     private _autoSaveInterval: number | null = null;
     private _lastProgress: ClPlayerProgress | null = null;
     private _isDirty: boolean = false;
+    private _slot: number = 0;
 
     constructor() {
         this._baseUrl = this._getApiBaseUrl();
@@ -76,6 +136,11 @@ class ClProgressSyncService {
         };
     }
 
+    /** 带槽位参数的玩家数据地址 */
+    private _playerUrl(playerId: string, path: string): string {
+        return `${this._baseUrl}/api/player/${playerId}/${path}?slot=${this._slot}`;
+    }
+
     /** 获取当前玩家 ID */
     private _getPlayerId(): string | null {
         const authService = cl_getAuthService();
@@ -95,7 +160,7 @@ class ClProgressSyncService {
         }
 
         try {
-            const response = await fetch(`${this._baseUrl}/api/player/${playerId}/progress`, {
+            const response = await fetch(this._playerUrl(playerId, 'progress'), {
                 method: 'GET',
                 headers: this._getAuthHeaders(),
             });
@@ -117,6 +182,8 @@ class ClProgressSyncService {
                     current_map: data.current_map,
                     game_flags: data.game_flags || {},
                     statistics: data.statistics || this._getDefaultStatistics(),
+                    difficulty: data.difficulty || 'Normal',
+                    level_scaling: data.level_scaling ?? false,
                 };
                 console.log('[ClProgressSync] 进度加载成功:', this._lastProgress);
                 return this._lastProgress;
@@ -137,7 +204,7 @@ class ClProgressSyncService {
         }
 
         try {
-            const response = await fetch(`${this._baseUrl}/api/player/${playerId}/progress`, {
+            const response = await fetch(this._playerUrl(playerId, 'progress'), {
                 method: 'POST',
                 headers: this._getAuthHeaders(),
                 body: JSON.stringify(progress),
@@ -157,6 +224,210 @@ class ClProgressSyncService {
         }
     }
 
+    // =========================================================================
+    // 存档槽位
+    // =========================================================================
+
+    /** 当前存档槽位 */
+    get slot(): number {
+        return this._slot;
+    }
+
+    /** 切换存档槽位 (先保存当前槽位未保存的更改) */
+    async selectSlot(slot: number): Promise<boolean> {
+        if (slot < 0 || slot >= CL_MAX_SAVE_SLOTS) {
+            console.warn(`[ClProgressSync] 存档槽位 ${slot} 无效`);
+            return false;
+        }
+        await this.flushIfDirty();
+        this._slot = slot;
+        this._lastProgress = null;
+        this._isDirty = false;
+        return true;
+    }
+
+    /** 列出账号的存档槽位 */
+    async listSlots(): Promise<ClSaveSlotSummary[]> {
+        const playerId = this._getPlayerId();
+        if (!playerId) {
+            return [];
+        }
+
+        try {
+            const response = await fetch(`${this._baseUrl}/api/player/${playerId}/slots`, {
+                method: 'GET',
+                headers: this._getAuthHeaders(),
+            });
+            if (!response.ok) {
+                throw new Error(`加载存档槽位失败: ${response.status}`);
+            }
+            return await response.json();
+        } catch (e) {
+            console.warn('[ClProgressSync] 加载存档槽位失败:', e);
+            return [];
+        }
+    }
+
+    /** 删除存档槽位 */
+    async deleteSlot(slot: number): Promise<boolean> {
+        const playerId = this._getPlayerId();
+        if (!playerId) {
+            return false;
+        }
+
+        try {
+            const response = await fetch(`${this._baseUrl}/api/player/${playerId}/slots/${slot}`, {
+                method: 'DELETE',
+                headers: this._getAuthHeaders(),
+            });
+            if (!response.ok) {
+                throw new Error(`删除存档槽位失败: ${response.status}`);
+            }
+            if (slot === this._slot) {
+                this._lastProgress = null;
+                this._isDirty = false;
+            }
+            return true;
+        } catch (e) {
+            console.warn('[ClProgressSync] 删除存档槽位失败:', e);
+            return false;
+        }
+    }
+
+    /** 复制存档槽位 (覆盖目标槽位) */
+    async copySlot(from: number, to: number): Promise<boolean> {
+        const playerId = this._getPlayerId();
+        if (!playerId) {
+            return false;
+        }
+
+        try {
+            const response = await fetch(`${this._baseUrl}/api/player/${playerId}/slots/${from}/copy`, {
+                method: 'POST',
+                headers: this._getAuthHeaders(),
+                body: JSON.stringify({ to }),
+            });
+            if (!response.ok) {
+                throw new Error(`复制存档槽位失败: ${response.status}`);
+            }
+            return true;
+        } catch (e) {
+            console.warn('[ClProgressSync] 复制存档槽位失败:', e);
+            return false;
+        }
+    }
+
+    // =========================================================================
+    // 角色
+    // =========================================================================
+
+    /** 加载当前槽位的角色 (未创建时为 null) */
+    async loadCharacter(): Promise<ClCharacter | null> {
+        const playerId = this._getPlayerId();
+        if (!playerId) {
+            return null;
+        }
+
+        try {
+            const response = await fetch(this._playerUrl(playerId, 'character'), {
+                method: 'GET',
+                headers: this._getAuthHeaders(),
+            });
+            if (!response.ok) {
+                throw new Error(`加载角色失败: ${response.status}`);
+            }
+            return await response.json();
+        } catch (e) {
+            console.warn('[ClProgressSync] 加载角色失败:', e);
+            return null;
+        }
+    }
+
+    /** 在当前槽位创建角色 (名称不合规或槽位已有角色时返回错误信息) */
+    async createCharacter(character: ClCharacter): Promise<string | null> {
+        return this._postCharacter('character', character);
+    }
+
+    /** 重命名当前槽位的角色 (失败时返回错误信息) */
+    async renameCharacter(name: string): Promise<string | null> {
+        return this._postCharacter('character/rename', { name });
+    }
+
+    /** 提交角色请求，成功返回 null，失败返回服务器错误信息 */
+    private async _postCharacter(path: string, body: unknown): Promise<string | null> {
+        const playerId = this._getPlayerId();
+        if (!playerId) {
+            return '未登录';
+        }
+
+        try {
+            const response = await fetch(this._playerUrl(playerId, path), {
+                method: 'POST',
+                headers: this._getAuthHeaders(),
+                body: JSON.stringify(body),
+            });
+            if (!response.ok) {
+                const error = await response.json().catch(() => null);
+                return error?.error?.message || `请求失败: ${response.status}`;
+            }
+            return null;
+        } catch (e) {
+            console.warn('[ClProgressSync] 角色请求失败:', e);
+            return String(e);
+        }
+    }
+
+    // =========================================================================
+    // 设置
+    // =========================================================================
+
+    /** 加载账号设置 (跨设备同步) */
+    async loadSettings(): Promise<ClSettingsRecord | null> {
+        const playerId = this._getPlayerId();
+        if (!playerId) {
+            return null;
+        }
+
+        try {
+            const response = await fetch(`${this._baseUrl}/api/player/${playerId}/settings`, {
+                method: 'GET',
+                headers: this._getAuthHeaders(),
+            });
+            if (!response.ok) {
+                throw new Error(`加载设置失败: ${response.status}`);
+            }
+            return await response.json();
+        } catch (e) {
+            console.warn('[ClProgressSync] 加载设置失败:', e);
+            return null;
+        }
+    }
+
+    /** 保存账号设置 (revision 为加载时的版本号，其他设备已保存时返回冲突) */
+    async saveSettings(revision: number, settings: ClPlayerSettings): Promise<ClSaveSettingsResult> {
+        const playerId = this._getPlayerId();
+        if (!playerId) {
+            return { ok: false, conflict: null, message: '未登录' };
+        }
+
+        try {
+            const response = await fetch(`${this._baseUrl}/api/player/${playerId}/settings`, {
+                method: 'PUT',
+                headers: this._getAuthHeaders(),
+                body: JSON.stringify({ revision, settings }),
+            });
+            if (!response.ok) {
+                const error = await response.json().catch(() => null);
+                const conflict = response.status === 409 ? (error?.error?.revision ?? null) : null;
+                return { ok: false, conflict, message: error?.error?.message || `保存设置失败: ${response.status}` };
+            }
+            return { ok: true, record: await response.json() };
+        } catch (e) {
+            console.warn('[ClProgressSync] 保存设置失败:', e);
+            return { ok: false, conflict: null, message: String(e) };
+        }
+    }
+
     /** 标记进度已更改 (等待自动保存) */
     markDirty(progress: ClPlayerProgress): void {
         this._lastProgress = progress;
@@ -212,6 +483,8 @@ class ClProgressSyncService {
             current_map: 'main_world',
             game_flags: {},
             statistics: this._getDefaultStatistics(),
+            difficulty: 'Normal',
+            level_scaling: false,
         };
     }
 }
@@ -8,6 +8,8 @@
  * 文档: 文档/04-client.md
  */
 
+import type { ClAppearance } from './cl_network_types';
+
 // =============================================================================
 // 同步消息类型
 // =============================================================================
@@ -71,6 +73,7 @@ export interface ClPlayerStatusData {
     playerId: string;
     name: string;
     level: number;
+    appearance?: ClAppearance;
     hp: number;
     maxHp: number;
     status: 'idle' | 'moving' | 'in_battle' | 'offline';
@@ -13,6 +13,7 @@
 
 import { ClWebSocketCore } from './cl_websocket_core';
 import { ClMessageType } from './cl_network_types';
+import type { ClAppearance } from './cl_network_types';
 import {
     ClPlayerPositionData,
     ClPlayerMoveData,
@@ -82,6 +83,7 @@ export interface ClRemotePlayer {
     id: string;
     name: string;
     level: number;
+    appearance?: ClAppearance;
     
     // 位置
     position: { x: number; y: number; z: number };
@@ -314,6 +316,7 @@ export class ClWorldSyncService {
                 id: data.playerId,
                 name: data.name,
                 level: data.level,
+                appearance: data.appearance,
                 position: { x: 0, y: 0, z: 0 },
                 rotationY: 0,
                 targetPosition: { x: 0, y: 0, z: 0 },
@@ -326,6 +329,7 @@ export class ClWorldSyncService {
         } else {
             player.name = data.name;
             player.level = data.level;
+            player.appearance = data.appearance ?? player.appearance;
             player.status = data.status;
             
             if (data.status === 'offline') {
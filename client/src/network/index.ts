@@ -46,23 +46,78 @@ export type {
     ClMessage,
     ClNetworkConfig,
     ClLoginRequest,
+    ClAppearance,
     ClCreateRoomRequest,
     ClJoinRoomRequest,
     ClPlayCardRequest,
+    ClUseSkillRequest,
+    ClStartChannelRequest,
     ClLoginSuccessResponse,
+    ClContentVersion,
+    ClContentVersionWarningEvent,
+    ClDayPhase,
+    ClWorldTime,
+    ClWorldTimeEvent,
+    ClWeather,
+    ClRegionWeather,
+    ClWeatherEvent,
+    ClReplayPlayer,
+    ClReplaySummary,
+    ClGhostMode,
+    ClGhostUnit,
+    ClGhostRound,
+    ClGhostRun,
+    ClGhostStanding,
+    ClGhostRoundDelta,
+    ClGhostRace,
     ClRoomCreatedResponse,
     ClRoomJoinedResponse,
     ClPlayerInfo,
     ClRoomListResponse,
     ClRoomSummary,
+    ClParkedRoomInfo,
+    ClLobbyDelta,
     ClPlayerJoinedEvent,
     ClPlayerLeftEvent,
+    ClZoneView,
+    ClPlayerZones,
     ClGameStartedEvent,
     ClGameStateEvent,
     ClTurnStartEvent,
     ClCardPlayedEvent,
+    ClSkillUsedEvent,
     ClTurnEndedEvent,
     ClGameEndedEvent,
+    ClBattleFormat,
+    ClRoomSettingsEvent,
+    ClSeatControlEvent,
+    ClPlayerMatchStats,
+    ClMatchReward,
+    ClMatchRewardsEvent,
+    ClOnboardingFlag,
+    ClOnboardingProgress,
+    ClOnboardingChecklist,
+    ClOnboardingUpdatedEvent,
+    ClResyncEvent,
+    ClPublicActionKind,
+    ClPublicAction,
+    ClActionFeedEvent,
+    ClPageRequest,
+    ClPage,
+    ClWindowPlayer,
+    ClWindowUnit,
+    ClBattleWindow,
+    ClBattleWindowEvent,
+    ClHandPageEvent,
+    ClCombatLogPageEvent,
+    ClPingIntent,
+    ClPingTarget,
+    ClSendPingRequest,
+    ClPing,
+    ClTeamPingEvent,
+    ClPauseStatus,
+    ClPauseStatusEvent,
+    ClWinProbability,
     ClErrorResponse,
 } from './cl_network_types';
 
@@ -101,6 +156,7 @@ export type {
     ClAuthResponse,
     ClUserInfo,
     ClAuthState,
+    ClAccountDeletion,
 } from './cl_auth_service';
 
 // =============================================================================
@@ -109,11 +165,20 @@ export type {
 
 export {
     cl_getProgressSyncService,
+    CL_MAX_SAVE_SLOTS,
+    CL_SETTINGS_SCHEMA_VERSION,
 } from './cl_progress_sync_service';
 
 export type {
     ClPlayerProgress,
     ClPlayerStatistics,
+    ClDifficulty,
+    ClSaveSlotSummary,
+    ClCharacter,
+    ClSettingValue,
+    ClPlayerSettings,
+    ClSettingsRecord,
+    ClSaveSettingsResult,
 } from './cl_progress_sync_service';
 
 // =============================================================================
@@ -194,6 +259,9 @@ export type {
     ClMcpMoveEntity,
     ClMcpClearArea,
     ClMcpSpawnBatch,
+    ClMcpGroupEntity,
+    ClMcpSpawnGroup,
+    ClMcpExpandGroup,
     ClMcpUndo,
 } from './cl_mcp_types';
 
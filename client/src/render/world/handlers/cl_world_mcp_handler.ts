@@ -7,6 +7,7 @@
  * 
  * 职责：
  * - 处理 AI 代理发送的 MCP 命令
+ * - 记录已放置的组合预制体实例 (拆开后成员按单个实体编辑)
  * - 管理命令历史（撤销/重做）
  * - 委托给具体的子系统执行命令
  */
@@ -18,7 +19,10 @@ import {
     ClMcpDeleteEntity, 
     ClMcpMoveEntity,
     ClMcpClearArea, 
-    ClMcpSpawnBatch 
+    ClMcpSpawnBatch,
+    ClMcpSpawnGroup,
+    ClMcpExpandGroup,
+    ClMcpGroupEntity
 } from '../../../network';
 import { 
     getMcpHistory, 
@@ -26,7 +30,8 @@ import {
     ClMcpHistoryDelete, 
     ClMcpHistoryMove,
     ClMcpHistoryClearArea, 
-    ClMcpHistorySpawnBatch 
+    ClMcpHistorySpawnBatch,
+    ClMcpHistorySpawnGroup
 } from '../../../network/cl_mcp_history';
 import { ClLevelLoader } from '../core/cl_level_loader';
 import { ClEditorManager } from '../editor/cl_editor_manager';
@@ -38,6 +43,8 @@ export class ClWorldMcpHandler {
     private scene: Scene;
     private levelLoader: ClLevelLoader | null = null;
     private editorManager: ClEditorManager | null = null;
+    /** 已放置、尚未拆开的组实例 (实例 ID -> 成员) */
+    private groupInstances: Map<string, ClMcpGroupEntity[]> = new Map();
     
     constructor(scene: Scene) {
         this.scene = scene;
@@ -80,6 +87,12 @@ export class ClWorldMcpHandler {
             case 'SpawnBatch':
                 this.handleSpawnBatch(command.data as ClMcpSpawnBatch, history);
                 break;
+            case 'SpawnGroup':
+                this.handleSpawnGroup(command.data as ClMcpSpawnGroup, history);
+                break;
+            case 'ExpandGroup':
+                this.handleExpandGroup(command.data as ClMcpExpandGroup);
+                break;
             case 'Undo':
                 this.handleUndo();
                 break;
@@ -225,10 +238,11 @@ export class ClWorldMcpHandler {
         
         for (let i = 0; i < data.count; i++) {
             const prefabId = data.prefab_ids[Math.floor(Math.random() * data.prefab_ids.length)];
+            const planned = data.positions?.[i];
             const angle = Math.random() * Math.PI * 2;
             const distance = Math.random() * data.radius;
-            const x = centerX + Math.cos(angle) * distance;
-            const z = centerZ + Math.sin(angle) * distance;
+            const x = planned ? planned.x : centerX + Math.cos(angle) * distance;
+            const z = planned ? planned.y : centerZ + Math.sin(angle) * distance;
             const rotation = Math.random() * Math.PI * 2;
             const scale = 0.8 + Math.random() * 0.4;
             
@@ -253,6 +267,50 @@ export class ClWorldMcpHandler {
         console.log(`🌲 批量生成: 创建了 ${data.count} 个 ${data.entity_type}`);
     }
     
+    /**
+     * 处理放置组合预制体
+     */
+    private handleSpawnGroup(data: ClMcpSpawnGroup, history: ReturnType<typeof getMcpHistory>): void {
+        if (!this.levelLoader) return;
+        
+        const historyAction: ClMcpHistorySpawnGroup = {
+            type: 'spawn_group',
+            instanceId: data.instance_id,
+            entities: []
+        };
+        for (const entity of data.entities) {
+            const position = new Vector3(entity.position.x, 0, entity.position.y);
+            historyAction.entities.push({ prefabId: entity.prefab_id, position: position.clone() });
+            this.levelLoader.spawnEntity(
+                entity.entity_type as any,
+                entity.prefab_id,
+                position,
+                new Vector3(0, entity.rotation, 0),
+                new Vector3(entity.scale, entity.scale, entity.scale)
+            );
+        }
+        history.push(historyAction);
+        this.groupInstances.set(data.instance_id, data.entities);
+        
+        console.log(`🏕️ 放置组合预制体 ${data.group_id}: ${data.entities.length} 个实体 (${data.instance_id})`);
+    }
+    
+    /**
+     * 处理拆开组实例 (成员保留在场景中，之后按单个实体编辑)
+     */
+    private handleExpandGroup(data: ClMcpExpandGroup): void {
+        const entities = this.groupInstances.get(data.instance_id) ?? data.entities ?? [];
+        this.groupInstances.delete(data.instance_id);
+        console.log(`🧩 拆开组实例 ${data.instance_id}: ${entities.length} 个独立实体`);
+    }
+    
+    /**
+     * 已放置、尚未拆开的组实例
+     */
+    getGroupInstances(): ReadonlyMap<string, ClMcpGroupEntity[]> {
+        return this.groupInstances;
+    }
+    
     /**
      * 处理撤销
      */
@@ -306,6 +364,16 @@ export class ClWorldMcpHandler {
                 console.warn('⚠️ 批量生成的撤销建议使用 clear_area 命令');
                 break;
             }
+            case 'spawn_group': {
+                action.entities.forEach(entity => {
+                    const mesh = this.findMeshNearPosition(entity.prefabId, entity.position);
+                    if (mesh) {
+                        this.editorManager?.deleteById(mesh.name);
+                    }
+                });
+                this.groupInstances.delete(action.instanceId);
+                break;
+            }
         }
     }
     
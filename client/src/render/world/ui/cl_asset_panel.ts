@@ -11,12 +11,42 @@ import {
     TextBlock, 
     Control,
     Button,
-    ScrollViewer
+    ScrollViewer,
+    InputText,
+    Image
 } from "@babylonjs/gui";
 import { Scene, Vector3 } from "@babylonjs/core";
 import { ClAssetManager } from "../cl_asset_manager";
 import { ClLevelLoader } from "../core/cl_level_loader";
 
+/** /api/assets 返回的资源 */
+interface ClAssetEntry {
+    name: string;
+    type: string;
+    path: string;
+    /** 预制体分类 (仅预制体) */
+    category?: string;
+    /** 用户标签 */
+    tags?: string[];
+    /** 缩略图地址 (上传的贴图与模型) */
+    thumbnail?: string;
+    /** 模型元数据 (上传的模型) */
+    model?: { vertices: number; triangles: number };
+}
+
+/**
+ * 把搜索框文本拆成搜索词与标签 (`#标签` 为标签过滤)
+ */
+function clParseAssetSearch(text: string): URLSearchParams {
+    const words = text.trim().split(/\s+/).filter(w => w.length > 0);
+    const tags = words.filter(w => w.startsWith('#') && w.length > 1).map(w => w.slice(1));
+    const terms = words.filter(w => !w.startsWith('#'));
+    const params = new URLSearchParams();
+    if (terms.length > 0) params.set('q', terms.join(' '));
+    if (tags.length > 0) params.set('tags', tags.join(','));
+    return params;
+}
+
 /**
  * 资源面板 - 负责显示和加载可用资源
  */
@@ -29,6 +59,10 @@ export class ClAssetPanel {
     private onCreateSectionTitle: ((parent: StackPanel, text: string, color: string) => void) | null = null;
     private onRefreshHierarchy: (() => void) | null = null;
     private onSpawnUploadedModel: ((filename: string) => Promise<void>) | null = null;
+    
+    // 搜索
+    private searchTimer: ReturnType<typeof setTimeout> | null = null;
+    private searchSerial = 0;
 
     constructor(
         scene: Scene,
@@ -61,6 +95,17 @@ export class ClAssetPanel {
             this.onCreateSectionTitle(parent, "📦 资源库", "#7CFC00");
         }
 
+        const searchInput = new InputText("assetSearch");
+        searchInput.width = "100%";
+        searchInput.height = "32px";
+        searchInput.color = "white";
+        searchInput.background = "#222222";
+        searchInput.focusedBackground = "#2a2a2a";
+        searchInput.placeholderText = "搜索名称或标签 (#标签 过滤)";
+        searchInput.placeholderColor = "#777777";
+        searchInput.fontSize = 13;
+        parent.addControl(searchInput);
+
         const assetScrollView = new ScrollViewer("assetScroll");
         assetScrollView.width = "100%";
         assetScrollView.height = "400px";
@@ -79,9 +124,97 @@ export class ClAssetPanel {
         assetListPanel.paddingBottom = "5px";
         assetScrollView.addControl(assetListPanel);
 
+        const searchResultsPanel = new StackPanel();
+        searchResultsPanel.isVertical = true;
+        searchResultsPanel.width = "100%";
+        searchResultsPanel.paddingTop = "5px";
+        searchResultsPanel.isVisible = false;
+
+        searchInput.onTextChangedObservable.add(() => {
+            if (this.searchTimer) clearTimeout(this.searchTimer);
+            this.searchTimer = setTimeout(() => {
+                const params = clParseAssetSearch(searchInput.text);
+                const searching = params.toString().length > 0;
+                // ScrollViewer 只能容纳一个子控件，搜索时切换为结果列表
+                assetScrollView.removeControl(searching ? assetListPanel : searchResultsPanel);
+                assetScrollView.addControl(searching ? searchResultsPanel : assetListPanel);
+                searchResultsPanel.isVisible = searching;
+                if (searching) {
+                    this.searchAssets(params, searchResultsPanel);
+                }
+            }, 250);
+        });
+
         this.loadAssetList(assetListPanel);
     }
 
+    /**
+     * 按搜索词与标签查询资源 (服务器排序)
+     */
+    private async searchAssets(params: URLSearchParams, panel: StackPanel): Promise<void> {
+        const serial = ++this.searchSerial;
+        let assets: ClAssetEntry[] = [];
+        try {
+            const response = await fetch(`/api/assets?${params.toString()}`);
+            if (response.ok) {
+                assets = await response.json();
+            }
+        } catch (e) {
+            console.warn("Failed to search assets", e);
+        }
+        // 较早的请求晚到时丢弃
+        if (serial !== this.searchSerial) return;
+
+        panel.clearControls();
+        if (assets.length === 0) {
+            const empty = new TextBlock();
+            empty.text = "没有匹配的资源";
+            empty.color = "#888888";
+            empty.height = "30px";
+            empty.fontSize = 12;
+            panel.addControl(empty);
+            return;
+        }
+
+        assets.forEach(asset => {
+            const tags = asset.tags && asset.tags.length > 0 ? `  #${asset.tags.join(' #')}` : '';
+            const label = asset.category ? `${asset.name} (${asset.category})${tags}` : `${asset.name}${tags}`;
+            const btn = this.createAssetButton("search_" + asset.name, label, asset.thumbnail);
+            btn.width = "100%";
+            btn.height = asset.thumbnail ? "48px" : "30px";
+            btn.color = "white";
+            btn.background = asset.type === 'prefab' ? "#2a4a2a" : "#444444";
+            btn.paddingBottom = "2px";
+            btn.fontSize = 12;
+            btn.horizontalAlignment = Control.HORIZONTAL_ALIGNMENT_LEFT;
+
+            btn.onPointerClickObservable.add(async () => {
+                if (asset.type === 'prefab') {
+                    await this.spawnNatureAsset(asset.name, asset.name);
+                } else {
+                    await this.spawnAsset(asset);
+                }
+            });
+
+            panel.addControl(btn);
+        });
+    }
+
+    /**
+     * 创建资源按钮 (有缩略图时显示预览，不必加载完整模型)
+     */
+    private createAssetButton(name: string, label: string, thumbnail?: string): Button {
+        if (!thumbnail) {
+            return Button.CreateSimpleButton(name, label);
+        }
+        const btn = Button.CreateImageButton(name, label, `/${thumbnail}`);
+        if (btn.image) {
+            btn.image.width = "44px";
+            btn.image.stretch = Image.STRETCH_UNIFORM;
+        }
+        return btn;
+    }
+
     /**
      * 加载资源列表
      */
@@ -98,12 +231,13 @@ export class ClAssetPanel {
             separator.fontSize = 12;
             panel.addControl(separator);
             
-            let assets: Array<{name: string, type: string, path: string}> = [];
+            let assets: ClAssetEntry[] = [];
             
             try {
                 const response = await fetch('/api/assets');
                 if (response.ok) {
-                    assets = await response.json();
+                    // 预制体已在自然素材分类中列出
+                    assets = (await response.json() as ClAssetEntry[]).filter(a => a.type !== 'prefab');
                 }
             } catch (e) {
                 console.warn("Failed to fetch assets, using defaults");
@@ -117,9 +251,10 @@ export class ClAssetPanel {
             }
             
             assets.forEach(asset => {
-                const btn = Button.CreateSimpleButton("asset_" + asset.name, asset.name);
+                const label = asset.model ? `${asset.name} (${asset.model.triangles} 面)` : asset.name;
+                const btn = this.createAssetButton("asset_" + asset.name, label, asset.thumbnail);
                 btn.width = "100%";
-                btn.height = "30px";
+                btn.height = asset.thumbnail ? "48px" : "30px";
                 btn.color = "white";
                 btn.background = asset.type === 'enemy' || asset.name.includes('enemy') ? "#8B0000" : "#444444";
                 btn.paddingBottom = "2px";
@@ -279,9 +414,14 @@ export class ClAssetPanel {
     /**
      * 生成资源
      */
-    private async spawnAsset(asset: {name: string, type: string, path: string}): Promise<void> {
+    private async spawnAsset(asset: ClAssetEntry): Promise<void> {
         console.log(`📦 生成资源: ${asset.name}`);
         
+        if (asset.type === 'texture') {
+            console.warn(`🖼️ 贴图不能直接放置到场景中: ${asset.name}`);
+            return;
+        }
+        
         if (asset.path.includes("uploads")) {
             if (this.onSpawnUploadedModel) {
                 await this.onSpawnUploadedModel(asset.name);
@@ -246,6 +246,18 @@ export class ClFileOperations {
                 body: JSON.stringify(mapData)
             });
             
+            // 地图内容不合法: 列出问题，不回退到本地下载
+            if (response.status === 400 || response.status === 422) {
+                const body = await response.json().catch(() => null);
+                const error = body?.error;
+                const issues: { path: string; message: string }[] = error?.issues
+                    ?? (error?.diagnostic ? [{ path: error.diagnostic.path, message: error.diagnostic.message }] : []);
+                console.error("❌ 地图校验失败:", issues);
+                const lines = issues.slice(0, 5).map(i => `${i.path}: ${i.message}`).join("\n");
+                alert(`地图保存失败: ${error?.message ?? response.statusText}\n${lines}`);
+                return { success: false, savedLocally: false, error: error?.message ?? response.statusText };
+            }
+            
             if (!response.ok) {
                 throw new Error(`Save failed: ${response.statusText}`);
             }
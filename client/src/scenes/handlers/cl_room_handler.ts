@@ -82,7 +82,7 @@ export class ClRoomHandler {
                         name: r.name,
                         playerCount: r.player_count,
                         maxPlayers: r.max_players,
-                        status: r.status === 'waiting' ? 'waiting' : 'playing'
+                        status: r.game_started || r.status === 'playing' ? 'playing' : 'waiting'
                     }));
                     this.lobbyUI?.updateRoomList(roomData);
                 },
@@ -185,8 +185,8 @@ export class ClRoomHandler {
         
         // 使用大厅服务获取房间列表
         if (this.lobbyService) {
-            // 发送请求到服务器
-            this.lobbyService.refreshRoomList();
+            // 订阅大厅: 服务器下发完整列表，之后推送增量 (重复订阅即重新同步)
+            this.lobbyService.subscribeLobby();
             
             // 同时显示本地缓存的列表(如果有)
             const cachedRooms = this.lobbyService.getRoomList();
@@ -196,7 +196,7 @@ export class ClRoomHandler {
                     name: r.name,
                     playerCount: r.player_count,
                     maxPlayers: r.max_players,
-                    status: r.status === 'waiting' ? 'waiting' : 'playing'
+                    status: r.game_started || r.status === 'playing' ? 'playing' : 'waiting'
                 }));
                 this.lobbyUI?.updateRoomList(roomData);
             }
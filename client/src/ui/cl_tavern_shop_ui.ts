@@ -295,8 +295,12 @@ export class ClTavernShopUI {
                 panel.addControl(statsText);
                 
                 // 价格
-                const priceText = new GUI.TextBlock(`monster_price_${i}`, `💰${m.buy_price}`);
-                priceText.color = '#FFD700';
+                const onSale = slot.base_price > m.buy_price;
+                const priceText = new GUI.TextBlock(
+                    `monster_price_${i}`,
+                    onSale ? `💰${m.buy_price} (原${slot.base_price})` : `💰${m.buy_price}`,
+                );
+                priceText.color = onSale ? '#7CFC00' : '#FFD700';
                 priceText.fontSize = 14;
                 priceText.top = '15px';
                 panel.addControl(priceText);
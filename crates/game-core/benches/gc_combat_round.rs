@@ -0,0 +1,86 @@
+//! 自动战斗回合基准测试 (含分配次数统计)
+//!
+//! 运行: `cargo bench -p game-core --bench gc_combat_round`
+//! 启动时先打印每回合堆分配次数，再进行计时。
+//! `run_combat_round` 每回合新建缓冲区，`run_combat_round_with` 复用同一个 `GcCombatScratch`。
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use game_core::*;
+
+/// 统计分配次数的全局分配器
+struct GcCountingAllocator;
+
+static GC_ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for GcCountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        GC_ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: GcCountingAllocator = GcCountingAllocator;
+
+/// 双方前后排都站满、生命足够打很多回合的沙盘
+fn create_arena() -> GcBattleArena {
+    let mut arena = GcBattleArena::new(GcTerrainType::Plain, GcTerrainType::Plain);
+    for slot in 0..5 {
+        let monster = |id: String, reach| {
+            let mut m = GcMonster::new(&id, "怪兽", 1, GcMonsterAttribute::Earth, 10, 5, 100_000);
+            m.reach = reach;
+            m
+        };
+        arena.player_monsters[slot] = Some(monster(format!("p{}", slot), GcAttackReach::Melee));
+        arena.player_back_row[slot] = Some(monster(format!("pb{}", slot), GcAttackReach::Ranged));
+        arena.enemy_monsters[slot] = Some(monster(format!("e{}", slot), GcAttackReach::Melee));
+        arena.enemy_back_row[slot] = Some(monster(format!("eb{}", slot), GcAttackReach::Ranged));
+    }
+    arena
+}
+
+/// 每回合平均分配次数
+fn allocations_per_round(rounds: usize, mut round: impl FnMut(&mut GcBattleArena)) -> usize {
+    let mut arena = create_arena();
+    let before = GC_ALLOCATIONS.load(Ordering::Relaxed);
+    for _ in 0..rounds {
+        round(&mut arena);
+    }
+    (GC_ALLOCATIONS.load(Ordering::Relaxed) - before) / rounds
+}
+
+fn bench_combat_round(c: &mut Criterion) {
+    let fresh = allocations_per_round(100, |arena| {
+        arena.run_combat_round(None);
+    });
+    let mut scratch = GcCombatScratch::gc_new();
+    let reused = allocations_per_round(100, |arena| {
+        arena.run_combat_round_with(None, &mut scratch);
+    });
+    println!("run_combat_round: 每回合 {} 次分配", fresh);
+    println!("run_combat_round_with (复用缓冲区): 每回合 {} 次分配", reused);
+
+    let mut group = c.benchmark_group("combat_round");
+    group.bench_function("fresh_scratch", |b| {
+        b.iter_batched(create_arena, |mut arena| arena.run_combat_round(None), BatchSize::SmallInput);
+    });
+    group.bench_function("reused_scratch", |b| {
+        let mut scratch = GcCombatScratch::gc_new();
+        b.iter_batched(
+            create_arena,
+            |mut arena| arena.run_combat_round_with(None, &mut scratch),
+            BatchSize::SmallInput,
+        );
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_combat_round);
+criterion_main!(benches);
@@ -0,0 +1,57 @@
+//! 回合战斗基准测试
+//!
+//! 对比旧实现 (克隆双方战场再写回) 与当前原地借用实现，战场越大差距越明显。
+//! 运行: `cargo bench -p game-core --bench gc_turn_combat`
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use game_core::*;
+
+/// 创建双方战场都铺满卡牌的战斗
+fn create_battle(slot_count: usize) -> GcBattleState {
+    let config = GcBattlefieldConfig { slot_count, deploy_cost: 0 };
+    let mut players = vec![GcPlayer::gc_new("p1", "玩家1"), GcPlayer::gc_new("p2", "玩家2")];
+    for player in &mut players {
+        player.stats.max_hp = u32::MAX;
+        player.stats.hp = u32::MAX;
+        player.battlefield = GcBattlefield::gc_new(config.clone());
+        for slot in 0..slot_count {
+            let card = GcCard::gc_new_attack(format!("{}_{}", player.id, slot), "攻击", 1, 5);
+            player.battlefield.gc_deploy_to_slot(slot, card).expect("槽位可用");
+        }
+    }
+    let mut state = GcBattleState::gc_new("bench", players);
+    state.phase = GcBattlePhase::Playing;
+    state
+}
+
+/// 旧实现: 克隆双方战场后战斗，再写回
+fn clone_turn_combat(state: &mut GcBattleState) -> (u32, u32) {
+    state.players[0].battlefield.gc_on_turn_start();
+    let mut player_bf = state.players[0].battlefield.clone();
+    let mut opponent_bf = state.players[1].battlefield.clone();
+
+    let player_combat = player_bf.gc_attack_battlefield(&mut opponent_bf);
+    opponent_bf.gc_on_turn_start();
+    let opponent_combat = opponent_bf.gc_attack_battlefield(&mut player_bf);
+
+    state.players[0].battlefield = player_bf;
+    state.players[1].battlefield = opponent_bf;
+    (player_combat.player_damage, opponent_combat.player_damage)
+}
+
+fn bench_turn_combat(c: &mut Criterion) {
+    let mut group = c.benchmark_group("turn_combat");
+    for slot_count in [5, 50, 500] {
+        let battle = create_battle(slot_count);
+        group.bench_with_input(BenchmarkId::new("clone", slot_count), &battle, |b, battle| {
+            b.iter_batched(|| battle.clone(), |mut state| clone_turn_combat(&mut state), BatchSize::SmallInput);
+        });
+        group.bench_with_input(BenchmarkId::new("in_place", slot_count), &battle, |b, battle| {
+            b.iter_batched(|| battle.clone(), |mut state| state.gc_execute_turn_combat("p1"), BatchSize::SmallInput);
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_turn_combat);
+criterion_main!(benches);
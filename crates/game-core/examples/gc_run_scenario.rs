@@ -0,0 +1,48 @@
+//! 场景脚本运行器 (供策划验证卡牌行为)
+//!
+//! 用法: `cargo run -p game-core --example gc_run_scenario -- scenarios/knight_attack.scenario [...]`
+
+use std::process::ExitCode;
+
+use game_core::gc_run_scenario;
+
+fn main() -> ExitCode {
+    let paths: Vec<String> = std::env::args().skip(1).collect();
+    if paths.is_empty() {
+        eprintln!("用法: gc_run_scenario <脚本路径>...");
+        return ExitCode::FAILURE;
+    }
+
+    let mut all_passed = true;
+    for path in &paths {
+        let script = match std::fs::read_to_string(path) {
+            Ok(script) => script,
+            Err(e) => {
+                eprintln!("❌ {}: 无法读取 ({})", path, e);
+                all_passed = false;
+                continue;
+            }
+        };
+        match gc_run_scenario(&script) {
+            Ok(report) if report.gc_passed() => {
+                println!("✅ {}: {} 项断言通过", path, report.checks);
+            }
+            Ok(report) => {
+                all_passed = false;
+                println!("❌ {}: {} 项断言，{} 项失败", path, report.checks, report.failures.len());
+                for failure in &report.failures {
+                    println!("   第 {} 行 `{}`: {}", failure.line, failure.source, failure.message);
+                }
+                if report.aborted {
+                    println!("   (操作失败，脚本已中止)");
+                }
+            }
+            Err(e) => {
+                all_passed = false;
+                println!("❌ {}: {}", path, e);
+            }
+        }
+    }
+
+    if all_passed { ExitCode::SUCCESS } else { ExitCode::FAILURE }
+}
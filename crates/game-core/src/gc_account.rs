@@ -0,0 +1,126 @@
+//! 账号数据导出与删除 (数据保护请求)
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! ## 规则
+//! - 删除申请先进入宽限期 (`GcAccountDeletion`)，期间可以撤销；到期后由服务器清除账号的全部存档数据
+//! - 对局历史等多人共享的记录不删除，而是把玩家 ID 替换为匿名 ID (`gc_anonymized_player_id`)
+//! - 匿名 ID 由玩家 ID 稳定推导 (同一玩家的多条记录仍可关联为同一匿名玩家)，不可反推
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// 默认删除宽限期 (秒): 14 天
+pub const GC_ACCOUNT_DELETION_GRACE_SECS: u64 = 14 * 24 * 3600;
+
+/// 匿名玩家 ID 前缀
+pub const GC_ANONYMIZED_PREFIX: &str = "deleted-";
+
+// =============================================================================
+// 删除申请
+// =============================================================================
+
+/// 账号删除申请
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcAccountDeletion {
+    /// 账号 (玩家) ID
+    pub player_id: String,
+    /// 申请时间 (Unix 秒)
+    pub requested_at: u64,
+    /// 清除时间 (Unix 秒)，之前可以撤销
+    pub purge_at: u64,
+}
+
+impl GcAccountDeletion {
+    /// 创建删除申请
+    pub fn gc_schedule(player_id: impl Into<String>, now: u64, grace_secs: u64) -> Self {
+        Self {
+            player_id: player_id.into(),
+            requested_at: now,
+            purge_at: now.saturating_add(grace_secs),
+        }
+    }
+
+    /// 是否已到清除时间
+    pub fn gc_is_due(&self, now: u64) -> bool {
+        now >= self.purge_at
+    }
+
+    /// 距清除的剩余时间 (秒)
+    pub fn gc_remaining_secs(&self, now: u64) -> u64 {
+        self.purge_at.saturating_sub(now)
+    }
+}
+
+// =============================================================================
+// 匿名化
+// =============================================================================
+
+/// 玩家的匿名 ID (`deleted-` + 16 位十六进制，FNV-1a 64 位)
+pub fn gc_anonymized_player_id(player_id: &str) -> String {
+    let hash = player_id.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    });
+    format!("{}{:016x}", GC_ANONYMIZED_PREFIX, hash)
+}
+
+/// 把 JSON 中等于玩家 ID 的字符串值与对象键替换为匿名 ID，返回替换次数
+pub fn gc_anonymize_json(value: &mut Value, player_id: &str, alias: &str) -> usize {
+    match value {
+        Value::String(s) if s == player_id => {
+            *s = alias.to_string();
+            1
+        }
+        Value::Array(items) => items.iter_mut().map(|v| gc_anonymize_json(v, player_id, alias)).sum(),
+        Value::Object(map) => {
+            let mut count = 0;
+            if let Some(inner) = map.remove(player_id) {
+                map.insert(alias.to_string(), inner);
+                count += 1;
+            }
+            count + map.values_mut().map(|v| gc_anonymize_json(v, player_id, alias)).sum::<usize>()
+        }
+        _ => 0,
+    }
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_deletion_grace_period() {
+        let deletion = GcAccountDeletion::gc_schedule("p1", 1000, 500);
+        assert_eq!(deletion.purge_at, 1500);
+        assert!(!deletion.gc_is_due(1499));
+        assert_eq!(deletion.gc_remaining_secs(1200), 300);
+        assert!(deletion.gc_is_due(1500));
+        assert_eq!(deletion.gc_remaining_secs(2000), 0);
+    }
+
+    #[test]
+    fn test_anonymize_match_result() {
+        let alias = gc_anonymized_player_id("p1");
+        assert!(alias.starts_with(GC_ANONYMIZED_PREFIX));
+        assert_eq!(alias, gc_anonymized_player_id("p1"));
+        assert_ne!(alias, gc_anonymized_player_id("p2"));
+
+        let mut result = json!({
+            "placements": [["p1", 1], ["p2", 2]],
+            "damage": { "p1": 120, "p2": 80 },
+            "note": "p1 获胜",
+        });
+        assert_eq!(gc_anonymize_json(&mut result, "p1", &alias), 2);
+        assert_eq!(result["placements"][0][0], json!(alias));
+        assert_eq!(result["damage"][alias.as_str()], json!(120));
+        assert!(result["damage"].get("p1").is_none());
+        assert_eq!(result["note"], json!("p1 获胜"));
+    }
+}
@@ -0,0 +1,317 @@
+//! 公开行动记录 (历史面板数据)
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! ## 规则
+//! - 每场事件溯源战斗维护一份公开行动记录，随事件记录同步追加，撤销事件时移除对应条目
+//! - 只记录所有人可见的信息: 打出/部署/获取的卡牌 (已公开)、技能、战场战斗、回合推进、生命变化
+//! - 隐藏信息脱敏: 服务器发牌只记录"获得一张牌"，不含卡牌内容；能量、沙盒、费用修正等内部事件不记录
+//! - 条目序号 (`seq`) 单调递增且不复用，客户端按序号增量拉取；也可按回合区间查询
+
+use serde::{Deserialize, Serialize};
+
+use crate::{gc_apply_battle_event, GcBattleEvent, GcBattlePhase, GcBattleState, GcCard, GcCardType, GcError};
+
+// =============================================================================
+// 条目
+// =============================================================================
+
+/// 公开行动类型
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data")]
+pub enum GcPublicActionKind {
+    /// 战斗阶段变化
+    PhaseChanged { phase: GcBattlePhase },
+    /// 出牌 (打出的牌对所有人公开)
+    CardPlayed { card_name: String, card_type: GcCardType, cost: u32, target_id: String },
+    /// 使用英雄技能
+    SkillUsed { skill_id: String, target_id: String },
+    /// 开始引导 (引导模板与目标公开)
+    ChannelStarted { template_id: String, target_id: String },
+    /// 从公共卡池获取卡牌 (展示区的牌对所有人公开)
+    CardAcquired { card_name: String },
+    /// 刷新公共卡池
+    PoolRefreshed,
+    /// 部署卡牌到战场
+    CardDeployed { card_name: String, slot_index: usize },
+    /// 回合结束战场战斗 (伤害见生命变化)
+    CombatResolved,
+    /// 新回合开始 (行动玩家为条目的玩家)
+    TurnStarted,
+    /// 获得一张牌 (内容隐藏)
+    CardReceived,
+}
+
+/// 玩家生命变化
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcFeedHpChange {
+    pub player_id: String,
+    pub before: u32,
+    pub after: u32,
+}
+
+/// 公开行动记录条目
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcPublicAction {
+    /// 序号 (从 1 开始，单调递增)
+    pub seq: u64,
+    /// 对应事件在事件列表中的序号
+    pub event_index: usize,
+    /// 发生的回合
+    pub turn: u32,
+    /// 行动玩家
+    pub player_id: Option<String>,
+    /// 行动内容
+    pub kind: GcPublicActionKind,
+    /// 行动造成的玩家生命变化
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hp_changes: Vec<GcFeedHpChange>,
+}
+
+// =============================================================================
+// 事件应用前的公开信息
+// =============================================================================
+
+/// 事件应用前需要记住的公开信息 (卡牌离开手牌/展示区后无法再查到)
+#[derive(Clone, Debug)]
+pub struct GcFeedProbe {
+    turn: u32,
+    hp: Vec<(String, u32)>,
+    card: Option<(String, GcCardType, u32)>,
+}
+
+impl GcFeedProbe {
+    /// 在事件应用前采集
+    pub fn gc_capture(state: &GcBattleState, event: &GcBattleEvent) -> Self {
+        let card = match event {
+            GcBattleEvent::CardPlayed { player_id, card_id, .. }
+            | GcBattleEvent::CardDeployed { player_id, card_id, .. } => state.gc_find_player(player_id)
+                .and_then(|p| p.hand.iter().find(|c| &c.id == card_id)),
+            GcBattleEvent::CardAcquired { card_id, .. } => return Self::gc_capture_pool_card(state, card_id),
+            _ => None,
+        };
+        Self::gc_with_card(state, card)
+    }
+
+    /// 采集不涉及卡牌的事件 (回合推进、刷新卡池等)
+    pub fn gc_capture_state(state: &GcBattleState) -> Self {
+        Self::gc_with_card(state, None)
+    }
+
+    /// 采集从展示区获取卡牌 (事件携带的卡池在获取后才生成)
+    pub fn gc_capture_pool_card(state: &GcBattleState, card_id: &str) -> Self {
+        Self::gc_with_card(state, state.card_pool.display.iter().find(|c| c.id == card_id))
+    }
+
+    fn gc_with_card(state: &GcBattleState, card: Option<&GcCard>) -> Self {
+        Self {
+            turn: state.turn,
+            hp: state.players.iter().map(|p| (p.id.clone(), p.stats.hp)).collect(),
+            card: card.map(|c| (c.name.clone(), c.card_type.clone(), c.cost)),
+        }
+    }
+
+    /// 卡牌名称 (找不到时退回卡牌 ID)
+    fn gc_card_name(&self, card_id: &str) -> String {
+        self.card.as_ref().map_or_else(|| card_id.to_string(), |(name, _, _)| name.clone())
+    }
+}
+
+/// 将事件描述为公开行动 (内部事件返回 None)
+fn gc_describe_event(probe: &GcFeedProbe, event: &GcBattleEvent, after: &GcBattleState) -> Option<(Option<String>, GcPublicActionKind)> {
+    let described = match event {
+        GcBattleEvent::PhaseChanged { phase } => (None, GcPublicActionKind::PhaseChanged { phase: phase.clone() }),
+        GcBattleEvent::CardPlayed { player_id, card_id, target_id } => {
+            let (card_type, cost) = probe.card.as_ref()
+                .map_or((GcCardType::Attack, 0), |(_, card_type, cost)| (card_type.clone(), *cost));
+            let kind = GcPublicActionKind::CardPlayed {
+                card_name: probe.gc_card_name(card_id),
+                card_type,
+                cost,
+                target_id: target_id.clone(),
+            };
+            (Some(player_id.clone()), kind)
+        }
+        GcBattleEvent::SkillUsed { player_id, skill_id, target_id } => {
+            let kind = GcPublicActionKind::SkillUsed { skill_id: skill_id.clone(), target_id: target_id.clone() };
+            (Some(player_id.clone()), kind)
+        }
+        GcBattleEvent::ChannelStarted { player_id, template_id, target_id } => {
+            let kind = GcPublicActionKind::ChannelStarted { template_id: template_id.clone(), target_id: target_id.clone() };
+            (Some(player_id.clone()), kind)
+        }
+        GcBattleEvent::CardAcquired { player_id, card_id, .. } => {
+            (Some(player_id.clone()), GcPublicActionKind::CardAcquired { card_name: probe.gc_card_name(card_id) })
+        }
+        GcBattleEvent::PoolRefreshed { player_id, .. } => (Some(player_id.clone()), GcPublicActionKind::PoolRefreshed),
+        GcBattleEvent::CardDeployed { player_id, card_id, slot_index } => {
+            let kind = GcPublicActionKind::CardDeployed { card_name: probe.gc_card_name(card_id), slot_index: *slot_index };
+            (Some(player_id.clone()), kind)
+        }
+        GcBattleEvent::TurnCombatResolved { player_id } => (Some(player_id.clone()), GcPublicActionKind::CombatResolved),
+        GcBattleEvent::TurnAdvanced => {
+            (after.gc_current_player_id().map(str::to_string), GcPublicActionKind::TurnStarted)
+        }
+        GcBattleEvent::CardGiven { player_id, .. } => (Some(player_id.clone()), GcPublicActionKind::CardReceived),
+        GcBattleEvent::Genesis { .. }
+        | GcBattleEvent::EnergySet { .. }
+        | GcBattleEvent::SandboxEdited { .. }
+        | GcBattleEvent::CostModifierAdded { .. }
+        | GcBattleEvent::CostModifiersRemoved { .. } => return None,
+    };
+    Some(described)
+}
+
+// =============================================================================
+// 行动记录
+// =============================================================================
+
+/// 公开行动记录
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcActionFeed {
+    /// 条目 (按序号升序)
+    entries: Vec<GcPublicAction>,
+    /// 已分配的最大序号
+    last_seq: u64,
+}
+
+impl GcActionFeed {
+    /// 从事件列表构建 (事件必须能依次折叠)
+    pub fn gc_from_events(events: &[GcBattleEvent]) -> Result<Self, GcError> {
+        gc_fold_battle_events_with_feed(events).map(|(_, feed)| feed)
+    }
+
+    /// 记录一个已应用的事件 (`probe` 为应用前采集)
+    pub fn gc_observe(&mut self, probe: GcFeedProbe, event_index: usize, event: &GcBattleEvent, after: &GcBattleState) {
+        let Some((player_id, kind)) = gc_describe_event(&probe, event, after) else {
+            return;
+        };
+        let hp_changes = after.players.iter()
+            .filter_map(|p| {
+                let before = probe.hp.iter().find(|(id, _)| id == &p.id).map(|(_, hp)| *hp)?;
+                (before != p.stats.hp).then(|| GcFeedHpChange { player_id: p.id.clone(), before, after: p.stats.hp })
+            })
+            .collect();
+        let turn = match kind {
+            GcPublicActionKind::TurnStarted => after.turn,
+            _ => probe.turn,
+        };
+        self.last_seq += 1;
+        self.entries.push(GcPublicAction { seq: self.last_seq, event_index, turn, player_id, kind, hp_changes });
+    }
+
+    /// 移除对应事件序号不小于 `event_count` 的条目 (撤销事件后调用，序号不复用)
+    pub fn gc_truncate_events(&mut self, event_count: usize) {
+        self.entries.retain(|e| e.event_index < event_count);
+    }
+
+    /// 全部条目
+    pub fn gc_entries(&self) -> &[GcPublicAction] {
+        &self.entries
+    }
+
+    /// 序号大于 `seq` 的条目 (增量广播，`seq` 为 0 时返回全部)
+    pub fn gc_since(&self, seq: u64) -> &[GcPublicAction] {
+        let start = self.entries.partition_point(|e| e.seq <= seq);
+        &self.entries[start..]
+    }
+
+    /// 回合区间内的条目 (闭区间)
+    pub fn gc_turn_range(&self, from_turn: u32, to_turn: u32) -> Vec<&GcPublicAction> {
+        self.entries.iter().filter(|e| (from_turn..=to_turn).contains(&e.turn)).collect()
+    }
+
+    /// 已分配的最大序号
+    pub fn gc_last_seq(&self) -> u64 {
+        self.last_seq
+    }
+}
+
+/// 折叠事件列表，同时构建公开行动记录
+pub(crate) fn gc_fold_battle_events_with_feed(events: &[GcBattleEvent]) -> Result<(GcBattleState, GcActionFeed), GcError> {
+    let (first, rest) = events.split_first().ok_or(GcError::GcBattleNotStarted)?;
+    let GcBattleEvent::Genesis { state } = first else {
+        return Err(GcError::GcInvalidAction("第一个事件必须是初始快照".to_string()));
+    };
+    let mut state = (**state).clone();
+    let mut feed = GcActionFeed::default();
+    for (index, event) in rest.iter().enumerate() {
+        let probe = GcFeedProbe::gc_capture(&state, event);
+        gc_apply_battle_event(&mut state, event)?;
+        feed.gc_observe(probe, index + 1, event, &state);
+    }
+    Ok((state, feed))
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GcEventSourcedBattle, GcPlayer};
+
+    fn create_battle() -> GcEventSourcedBattle {
+        let mut p1 = GcPlayer::gc_new("p1", "玩家1");
+        p1.hand.push(GcCard::gc_new_attack("c1", "打击", 1, 10));
+        let mut battle = GcEventSourcedBattle::gc_new("feed", vec![p1, GcPlayer::gc_new("p2", "玩家2")]);
+        battle.gc_record(GcBattleEvent::PhaseChanged { phase: GcBattlePhase::Playing }).unwrap();
+        battle
+    }
+
+    #[test]
+    fn test_feed_records_public_actions_and_redacts_hidden_cards() {
+        let mut battle = create_battle();
+        assert!(battle.gc_play_card("p1", "c1", "p2").success);
+        battle.gc_next_turn();
+        let secret = GcCard::gc_new_attack("secret", "秘密武器", 3, 99);
+        battle.gc_record(GcBattleEvent::CardGiven { player_id: "p2".to_string(), card: Box::new(secret) }).unwrap();
+        battle.gc_record(GcBattleEvent::EnergySet { player_id: "p2".to_string(), energy: 3 }).unwrap();
+
+        let feed = battle.gc_action_feed();
+        let kinds: Vec<&GcPublicActionKind> = feed.gc_entries().iter().map(|e| &e.kind).collect();
+        assert_eq!(kinds.len(), 4);
+        assert!(matches!(kinds[1], GcPublicActionKind::CardPlayed { card_name, .. } if card_name == "打击"));
+        assert_eq!(kinds[3], &GcPublicActionKind::CardReceived);
+
+        let played = &feed.gc_entries()[1];
+        assert_eq!(played.player_id.as_deref(), Some("p1"));
+        assert_eq!(played.hp_changes.len(), 1);
+        assert!(played.hp_changes[0].after < played.hp_changes[0].before);
+
+        let started = &feed.gc_entries()[2];
+        assert_eq!((started.player_id.as_deref(), started.turn), (Some("p2"), battle.gc_state().turn));
+
+        // 序列化后不含隐藏卡牌
+        let json = serde_json::to_string(feed).unwrap();
+        assert!(!json.contains("秘密武器") && !json.contains("secret"));
+    }
+
+    #[test]
+    fn test_feed_queries_and_undo() {
+        let mut battle = create_battle();
+        battle.gc_play_card("p1", "c1", "p2");
+        battle.gc_next_turn();
+        battle.gc_next_turn();
+
+        let feed = battle.gc_action_feed();
+        let first_turn = feed.gc_entries()[0].turn;
+        assert_eq!(feed.gc_turn_range(first_turn, first_turn).len(), 2);
+        assert_eq!(feed.gc_turn_range(first_turn + 1, u32::MAX).len(), 2);
+        assert_eq!(feed.gc_since(2).len(), 2);
+        assert!(feed.gc_since(feed.gc_last_seq()).is_empty());
+
+        // 重建的记录与在线维护的一致
+        let rebuilt = GcActionFeed::gc_from_events(battle.gc_events()).unwrap();
+        assert_eq!(&rebuilt, feed);
+
+        // 撤销移除条目，序号不复用
+        battle.gc_undo();
+        assert_eq!(battle.gc_action_feed().gc_entries().len(), 3);
+        battle.gc_next_turn();
+        assert_eq!(battle.gc_action_feed().gc_entries().last().map(|e| e.seq), Some(5));
+    }
+}
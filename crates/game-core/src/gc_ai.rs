@@ -0,0 +1,135 @@
+//! 托管机器人 (断线玩家的座位由服务器代打)
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! ## 规则
+//! - 只选择联机座位能发出的操作: 出牌 (`PlayCard`) 与结束回合 (`EndTurn`)
+//! - 困难与普通先用 `gc_search_lethal` (不含英雄技能) 找本回合的斩杀序列，找到时按序列出牌收尾
+//! - 候选出牌来自 `gc_suggest_action` 的排序结果，难度决定选第几条:
+//!   困难选最佳，普通选次佳 (能直接获胜时仍选获胜)，简单选最差且不找斩杀
+//! - 出牌后局面分不高于结束回合时不出牌；没有可出的牌时结束回合
+//! - 不是该玩家的回合或战斗已结束时不行动 (None)；结果只由战斗状态决定，可复现
+
+use serde::{Deserialize, Serialize};
+
+use crate::{gc_search_lethal, gc_suggest_action, GcAction, GcBattleState, GcHintReason, GcLethalSearch};
+
+// =============================================================================
+// 难度
+// =============================================================================
+
+/// 机器人难度
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GcBotDifficulty {
+    /// 简单
+    Easy,
+    /// 普通
+    #[default]
+    Normal,
+    /// 困难
+    Hard,
+}
+
+impl GcBotDifficulty {
+    /// 从名称解析 (`easy` / `normal` / `hard`，不区分大小写)
+    pub fn gc_from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "easy" => Some(Self::Easy),
+            "normal" => Some(Self::Normal),
+            "hard" => Some(Self::Hard),
+            _ => None,
+        }
+    }
+}
+
+// =============================================================================
+// 决策
+// =============================================================================
+
+/// 机器人的下一步操作 (出牌或结束回合)
+pub fn gc_bot_next_action(state: &GcBattleState, player_id: &str, difficulty: GcBotDifficulty) -> Option<GcAction> {
+    if difficulty != GcBotDifficulty::Easy {
+        let search = GcLethalSearch { use_skills: false, ..Default::default() };
+        if let Some(first) = gc_search_lethal(state, player_id, search).actions.into_iter().next() {
+            return Some(first);
+        }
+    }
+
+    let hints = gc_suggest_action(state, player_id);
+    let end_turn = hints.last()?;
+    let plays: Vec<_> = hints.iter()
+        .filter(|h| matches!(h.action, GcAction::PlayCard { .. }))
+        .filter(|h| h.reason == GcHintReason::Lethal || h.score > end_turn.score)
+        .collect();
+
+    let lethal = plays.first().is_some_and(|h| h.reason == GcHintReason::Lethal);
+    let choice = match difficulty {
+        _ if plays.is_empty() => None,
+        GcBotDifficulty::Hard => plays.first(),
+        GcBotDifficulty::Normal if lethal => plays.first(),
+        GcBotDifficulty::Normal => plays.get(1).or(plays.first()),
+        GcBotDifficulty::Easy => plays.last(),
+    };
+    Some(choice.map_or_else(|| end_turn.action.clone(), |h| h.action.clone()))
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GcCard, GcPlayer};
+
+    fn battle() -> GcBattleState {
+        let mut state = GcBattleState::gc_new("b1", vec![GcPlayer::gc_new("p1", "玩家1"), GcPlayer::gc_new("p2", "玩家2")]);
+        state.players[0].hand.push(GcCard::gc_new_attack("weak", "轻击", 1, 5));
+        state.players[0].hand.push(GcCard::gc_new_attack("big", "重击", 1, 30));
+        state
+    }
+
+    fn played(action: Option<GcAction>) -> Option<String> {
+        match action? {
+            GcAction::PlayCard { card_id, .. } => Some(card_id),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_difficulty_picks_ranked_play() {
+        let state = battle();
+        assert_eq!(played(gc_bot_next_action(&state, "p1", GcBotDifficulty::Hard)).as_deref(), Some("big"));
+        assert_eq!(played(gc_bot_next_action(&state, "p1", GcBotDifficulty::Normal)).as_deref(), Some("weak"));
+        assert_eq!(played(gc_bot_next_action(&state, "p1", GcBotDifficulty::Easy)).as_deref(), Some("weak"));
+        assert!(gc_bot_next_action(&state, "p2", GcBotDifficulty::Hard).is_none());
+
+        // 普通难度不会错过斩杀
+        let mut lethal = battle();
+        lethal.players[1].stats.hp = 20;
+        assert_eq!(played(gc_bot_next_action(&lethal, "p1", GcBotDifficulty::Normal)).as_deref(), Some("big"));
+    }
+
+    #[test]
+    fn test_finishes_with_multi_card_lethal() {
+        // 单张都不够、两张一起刚好斩杀: 普通难度按斩杀序列出牌，简单难度不找斩杀
+        let mut state = battle();
+        let search = GcLethalSearch { use_skills: false, ..Default::default() };
+        state.players[1].stats.hp = gc_search_lethal(&state, "p1", search).max_damage;
+        let lethal = gc_search_lethal(&state, "p1", search);
+        assert_eq!(lethal.actions.len(), 2);
+
+        assert_eq!(gc_bot_next_action(&state, "p1", GcBotDifficulty::Normal).as_ref(), lethal.actions.first());
+        assert_eq!(played(gc_bot_next_action(&state, "p1", GcBotDifficulty::Easy)).as_deref(), Some("weak"));
+    }
+
+    #[test]
+    fn test_ends_turn_without_playable_cards() {
+        let mut state = battle();
+        state.players[0].hand.clear();
+        let action = gc_bot_next_action(&state, "p1", GcBotDifficulty::Hard);
+        assert!(matches!(action, Some(GcAction::EndTurn { player_id }) if player_id == "p1"));
+    }
+}
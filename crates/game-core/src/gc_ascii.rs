@@ -0,0 +1,174 @@
+//! 战斗与沙盘的 ASCII 渲染 (调试用)
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! ## 规则
+//! - 与 `GcMap::gc_to_ascii` 相同，只用于终端调试与日志，不参与游戏逻辑
+//! - 手牌按序号列出 (`[0]`、`[1]` ...)，终端客户端的命令用序号或卡牌 ID 引用
+//! - 沙盘位置写作 `f0`~`f4` (前排) 与 `b0`~`b4` (后排)，可被 `gc_parse_arena_position` 解析
+
+use std::fmt::Write;
+
+use crate::{GcArenaPosition, GcArenaRow, GcBattleArena, GcBattleState, GcMonster, GcPlayerState};
+
+/// 沙盘位置标记 (`f0` / `b3`)
+pub fn gc_arena_position_label(pos: GcArenaPosition) -> String {
+    let row = match pos.row {
+        GcArenaRow::Front => 'f',
+        GcArenaRow::Back => 'b',
+    };
+    format!("{}{}", row, pos.slot)
+}
+
+/// 解析沙盘位置标记 (不区分大小写，槽位 0~4)
+pub fn gc_parse_arena_position(text: &str) -> Option<GcArenaPosition> {
+    let text = text.trim().to_ascii_lowercase();
+    let (row, slot) = text.split_at_checked(1)?;
+    let slot: u8 = slot.parse().ok().filter(|s| *s < 5)?;
+    match row {
+        "f" => Some(GcArenaPosition::front(slot)),
+        "b" => Some(GcArenaPosition::back(slot)),
+        _ => None,
+    }
+}
+
+/// 单个怪兽的一行描述
+fn gc_monster_line(monster: &GcMonster) -> String {
+    format!(
+        "{} ★{} 攻{} 生命{}/{} [{}]",
+        monster.name, monster.star, monster.base_atk, monster.current_hp, monster.max_hp, monster.id
+    )
+}
+
+impl GcBattleArena {
+    /// 转换为 ASCII 字符串 (调试用，敌方在上、我方在下，靠近中线的为前排)
+    pub fn gc_to_ascii(&self) -> String {
+        let mut out = String::new();
+        let side = |out: &mut String, title: &str, terrain: &str, rows: [(&str, GcArenaRow, &[Option<GcMonster>; 5]); 2]| {
+            let _ = writeln!(out, "{} ({})", title, terrain);
+            for (label, row, slots) in rows {
+                let mut empty = true;
+                for (slot, monster) in slots.iter().enumerate() {
+                    if let Some(monster) = monster {
+                        let pos = GcArenaPosition { row, slot: slot as u8 };
+                        let _ = writeln!(out, "  {} {} {}", label, gc_arena_position_label(pos), gc_monster_line(monster));
+                        empty = false;
+                    }
+                }
+                if empty {
+                    let _ = writeln!(out, "  {} (空)", label);
+                }
+            }
+        };
+
+        side(
+            &mut out,
+            "敌方",
+            self.enemy_terrain.name(),
+            [("后排", GcArenaRow::Back, &self.enemy_back_row), ("前排", GcArenaRow::Front, &self.enemy_monsters)],
+        );
+        out.push_str("  ----------------\n");
+        side(
+            &mut out,
+            "我方",
+            self.player_terrain.name(),
+            [("前排", GcArenaRow::Front, &self.player_monsters), ("后排", GcArenaRow::Back, &self.player_back_row)],
+        );
+        out
+    }
+}
+
+impl GcBattleState {
+    /// 转换为 ASCII 字符串 (调试用；指定 `viewer` 时只列出该玩家的手牌，其他玩家只显示数量)
+    pub fn gc_to_ascii(&self, viewer: Option<&str>) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "== 战斗 {} | 回合 {} | {:?} ==", self.id, self.turn, self.phase);
+        let current = self.gc_current_player_id();
+
+        for player in &self.players {
+            let marker = if current == Some(player.id.as_str()) { '>' } else { ' ' };
+            let state = match player.state {
+                GcPlayerState::Alive => "存活",
+                GcPlayerState::Dead => "死亡",
+                GcPlayerState::Stunned => "眩晕",
+                GcPlayerState::Disconnected => "断线",
+            };
+            let stats = &player.stats;
+            let _ = writeln!(
+                out,
+                "{} {} {} [{}] 生命 {}/{} 能量 {}/{} 手牌 {} 牌库 {} 弃牌 {}",
+                marker,
+                player.id,
+                player.name,
+                state,
+                stats.hp,
+                stats.max_hp,
+                stats.energy,
+                stats.max_energy,
+                player.hand.len(),
+                player.deck.len(),
+                player.discard.len(),
+            );
+            if viewer.is_none_or(|v| v == player.id) {
+                for (i, card) in player.hand.iter().enumerate() {
+                    let _ = writeln!(
+                        out,
+                        "    [{}] {} 费用{} 伤害{} 防御{} ({})",
+                        i, card.name, card.cost, card.base_damage, card.base_defense, card.id
+                    );
+                }
+            }
+        }
+
+        if let Some(winner) = &self.winner_id {
+            let _ = writeln!(out, "胜者: {}", winner);
+        }
+        out
+    }
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GcCard, GcMonsterAttribute, GcPlayer, GcTerrainType};
+
+    #[test]
+    fn test_arena_position_labels() {
+        for pos in [GcArenaPosition::front(0), GcArenaPosition::back(4)] {
+            assert_eq!(gc_parse_arena_position(&gc_arena_position_label(pos)), Some(pos));
+        }
+        assert_eq!(gc_parse_arena_position(" B2 "), Some(GcArenaPosition::back(2)));
+        assert_eq!(gc_parse_arena_position("f5"), None);
+        assert_eq!(gc_parse_arena_position("x1"), None);
+        assert_eq!(gc_parse_arena_position(""), None);
+    }
+
+    #[test]
+    fn test_battle_and_arena_ascii() {
+        let mut p1 = GcPlayer::gc_new("p1", "玩家1");
+        p1.hand.push(GcCard::gc_new_attack("c1", "打击", 1, 10));
+        let mut p2 = GcPlayer::gc_new("p2", "玩家2");
+        p2.hand.push(GcCard::gc_new_attack("secret", "秘密", 1, 99));
+        let state = GcBattleState::gc_new("ascii", vec![p1, p2]);
+
+        let text = state.gc_to_ascii(Some("p1"));
+        assert!(text.contains("> p1 玩家1"));
+        assert!(text.contains("[0] 打击 费用1 伤害10"));
+        assert!(!text.contains("secret"));
+        assert!(state.gc_to_ascii(None).contains("secret"));
+
+        let mut arena = GcBattleArena::new(GcTerrainType::Plain, GcTerrainType::Forest);
+        let slime = GcMonster::new_with_template("m1", "slime", "史莱姆", 1, GcMonsterAttribute::Water, 50, 30, 60);
+        arena.player_back_row[2] = Some(slime);
+        let text = arena.gc_to_ascii();
+        assert!(text.contains("后排 b2 史莱姆"));
+        assert!(text.contains("前排 (空)"));
+        assert!(text.find("敌方") < text.find("我方"));
+    }
+}
@@ -0,0 +1,284 @@
+//! 资源标签与搜索 (编辑器资源库、MCP 素材查询)
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/12-MCP-API.md
+//!
+//! 给资源 (上传的模型、内置资源、预制体) 打用户自定义标签，并按名称与标签做全文搜索，
+//! 资源很多时编辑器与 MCP 代理都能快速找到需要的素材
+//!
+//! ## 规则
+//! - 标签按 `gc_normalize_text` 规范化后转为小写，1-32 个字符，不能包含 `,` (查询参数用逗号分隔)
+//! - 每个资源最多 16 个标签；标签清空后资源从注册表移除
+//! - 搜索词按空白拆分，每个词都要出现在资源名称、附加文本 (例如分类) 或某个标签中 (不区分大小写)
+//! - 标签过滤要求资源带有全部指定标签
+//! - 匹配结果按得分排序: 名称完全相同 > 名称前缀 > 名称包含 > 只匹配标签或附加文本
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::gc_normalize_text;
+
+/// 标签长度上限 (字符)
+pub const GC_ASSET_TAG_MAX_LEN: usize = 32;
+
+/// 每个资源的标签数量上限
+pub const GC_ASSET_MAX_TAGS: usize = 16;
+
+// =============================================================================
+// 标签
+// =============================================================================
+
+/// 规范化标签
+pub fn gc_normalize_asset_tag(tag: &str) -> Result<String, String> {
+    let tag = gc_normalize_text(tag).to_lowercase();
+    let len = tag.chars().count();
+    if len == 0 || len > GC_ASSET_TAG_MAX_LEN {
+        return Err(format!("标签长度应为 1-{} 个字符: {:?}", GC_ASSET_TAG_MAX_LEN, tag));
+    }
+    if tag.contains(',') {
+        return Err(format!("标签不能包含逗号: {:?}", tag));
+    }
+    Ok(tag)
+}
+
+/// 规范化一组标签 (去重)
+fn gc_normalize_asset_tags(tags: &[String]) -> Result<BTreeSet<String>, String> {
+    tags.iter().map(|t| gc_normalize_asset_tag(t)).collect()
+}
+
+/// 标签使用次数
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcAssetTagCount {
+    /// 标签
+    pub tag: String,
+    /// 带有该标签的资源数
+    pub count: usize,
+}
+
+/// 资源标签注册表 (资源名称 -> 标签)
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcAssetTagRegistry {
+    /// 资源名称 -> 标签 (只保存有标签的资源)
+    pub assets: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl GcAssetTagRegistry {
+    /// 资源的标签 (按字母排序)
+    pub fn gc_tags(&self, asset: &str) -> Vec<String> {
+        self.assets.get(asset).map(|tags| tags.iter().cloned().collect()).unwrap_or_default()
+    }
+
+    /// 替换资源的标签，返回规范化后的标签 (空列表表示移除全部标签)
+    pub fn gc_set_tags(&mut self, asset: &str, tags: &[String]) -> Result<Vec<String>, String> {
+        if asset.trim().is_empty() {
+            return Err("资源名称不能为空".to_string());
+        }
+        let tags = gc_normalize_asset_tags(tags)?;
+        if tags.len() > GC_ASSET_MAX_TAGS {
+            return Err(format!("每个资源最多 {} 个标签", GC_ASSET_MAX_TAGS));
+        }
+        if tags.is_empty() {
+            self.assets.remove(asset);
+        } else {
+            self.assets.insert(asset.to_string(), tags);
+        }
+        Ok(self.gc_tags(asset))
+    }
+
+    /// 增删资源的标签，返回修改后的标签
+    pub fn gc_edit_tags(&mut self, asset: &str, add: &[String], remove: &[String]) -> Result<Vec<String>, String> {
+        let remove = gc_normalize_asset_tags(remove)?;
+        let mut tags = self.assets.get(asset).cloned().unwrap_or_default();
+        tags.extend(gc_normalize_asset_tags(add)?);
+        tags.retain(|t| !remove.contains(t));
+        self.gc_set_tags(asset, &tags.into_iter().collect::<Vec<_>>())
+    }
+
+    /// 重命名标签 (所有资源)，返回受影响的资源
+    pub fn gc_rename_tag(&mut self, from: &str, to: &str) -> Result<Vec<String>, String> {
+        let from = gc_normalize_asset_tag(from)?;
+        let to = gc_normalize_asset_tag(to)?;
+        let mut changed = Vec::new();
+        for (asset, tags) in self.assets.iter_mut() {
+            if tags.remove(&from) {
+                tags.insert(to.clone());
+                changed.push(asset.clone());
+            }
+        }
+        Ok(changed)
+    }
+
+    /// 删除标签 (所有资源)，返回受影响的资源
+    pub fn gc_delete_tag(&mut self, tag: &str) -> Result<Vec<String>, String> {
+        let tag = gc_normalize_asset_tag(tag)?;
+        let mut changed = Vec::new();
+        for (asset, tags) in self.assets.iter_mut() {
+            if tags.remove(&tag) {
+                changed.push(asset.clone());
+            }
+        }
+        self.assets.retain(|_, tags| !tags.is_empty());
+        Ok(changed)
+    }
+
+    /// 全部标签及使用次数 (次数多的在前，同次数按字母排序)
+    pub fn gc_tag_counts(&self) -> Vec<GcAssetTagCount> {
+        let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+        for tag in self.assets.values().flatten() {
+            *counts.entry(tag.as_str()).or_default() += 1;
+        }
+        let mut counts: Vec<GcAssetTagCount> = counts
+            .into_iter()
+            .map(|(tag, count)| GcAssetTagCount { tag: tag.to_string(), count })
+            .collect();
+        counts.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.tag.cmp(&b.tag)));
+        counts
+    }
+}
+
+// =============================================================================
+// 搜索
+// =============================================================================
+
+/// 资源查询
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcAssetQuery {
+    /// 搜索词 (按空白拆分，全部命中才匹配)
+    #[serde(default)]
+    pub q: String,
+    /// 必须带有的标签
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+impl GcAssetQuery {
+    /// 由搜索词与逗号分隔的标签构建 (HTTP 查询参数、MCP 参数)
+    pub fn gc_parse(q: Option<&str>, tags: Option<&str>) -> Self {
+        Self {
+            q: q.unwrap_or_default().to_string(),
+            tags: tags
+                .unwrap_or_default()
+                .split(',')
+                .filter(|t| !t.trim().is_empty())
+                .map(|t| t.to_string())
+                .collect(),
+        }
+    }
+
+    /// 是否没有任何条件
+    pub fn gc_is_empty(&self) -> bool {
+        self.q.trim().is_empty() && self.tags.iter().all(|t| t.trim().is_empty())
+    }
+
+    /// 匹配得分 (不匹配时返回 None)
+    ///
+    /// `extra` 为可搜索的附加文本，例如预制体分类
+    pub fn gc_score(&self, name: &str, extra: &[&str], tags: &[String]) -> Option<u32> {
+        for required in &self.tags {
+            let required = gc_normalize_asset_tag(required).ok()?;
+            if !tags.contains(&required) {
+                return None;
+            }
+        }
+
+        let name = name.to_lowercase();
+        let query = gc_normalize_text(&self.q).to_lowercase();
+        let terms: Vec<&str> = query.split_whitespace().collect();
+        if terms.is_empty() {
+            return Some(1);
+        }
+        let extra: Vec<String> = extra.iter().map(|e| e.to_lowercase()).collect();
+        let mut score = 0;
+        for term in terms {
+            score += if name == term {
+                100
+            } else if name.starts_with(term) {
+                50
+            } else if name.contains(term) {
+                20
+            } else if tags.iter().any(|t| t == term) {
+                15
+            } else if tags.iter().chain(&extra).any(|t| t.contains(term)) {
+                5
+            } else {
+                return None;
+            };
+        }
+        Some(score)
+    }
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tags(list: &[&str]) -> Vec<String> {
+        list.iter().map(|t| t.to_string()).collect()
+    }
+
+    #[test]
+    fn test_tags_are_normalized_and_limited() {
+        let mut registry = GcAssetTagRegistry::default();
+        let set = registry.gc_set_tags("tent.glb", &tags(&[" Camp ", "camp", "营地"]));
+        assert_eq!(set, Ok(tags(&["camp", "营地"])));
+        assert!(registry.gc_set_tags("tent.glb", &tags(&["a,b"])).is_err());
+        assert!(registry.gc_set_tags("tent.glb", &tags(&[""])).is_err());
+
+        let many: Vec<String> = (0..=GC_ASSET_MAX_TAGS).map(|i| format!("t{}", i)).collect();
+        assert!(registry.gc_set_tags("tent.glb", &many).is_err());
+        assert_eq!(registry.gc_tags("tent.glb"), tags(&["camp", "营地"]));
+
+        let edited = registry.gc_edit_tags("tent.glb", &tags(&["Cloth"]), &tags(&["营地"]));
+        assert_eq!(edited, Ok(tags(&["camp", "cloth"])));
+        assert_eq!(registry.gc_edit_tags("tent.glb", &[], &tags(&["camp", "cloth"])), Ok(Vec::new()));
+        assert!(registry.assets.is_empty());
+    }
+
+    #[test]
+    fn test_rename_delete_and_counts() {
+        let mut registry = GcAssetTagRegistry::default();
+        let _ = registry.gc_set_tags("pine_1", &tags(&["forest", "tall"]));
+        let _ = registry.gc_set_tags("pine_2", &tags(&["forest"]));
+        let _ = registry.gc_set_tags("rock_1", &tags(&["stone"]));
+
+        assert_eq!(
+            registry.gc_tag_counts(),
+            vec![
+                GcAssetTagCount { tag: "forest".to_string(), count: 2 },
+                GcAssetTagCount { tag: "stone".to_string(), count: 1 },
+                GcAssetTagCount { tag: "tall".to_string(), count: 1 },
+            ]
+        );
+        assert_eq!(registry.gc_rename_tag("Forest", "woods"), Ok(tags(&["pine_1", "pine_2"])));
+        assert_eq!(registry.gc_tags("pine_1"), tags(&["tall", "woods"]));
+        assert_eq!(registry.gc_delete_tag("stone"), Ok(tags(&["rock_1"])));
+        assert!(!registry.assets.contains_key("rock_1"));
+    }
+
+    #[test]
+    fn test_query_matches_names_and_tags() {
+        let pine = tags(&["forest", "conifer"]);
+        let query = GcAssetQuery::gc_parse(Some("pine"), None);
+        assert!(query.gc_score("pine_1", &[], &[]) > query.gc_score("big_pine", &[], &[]));
+        assert_eq!(GcAssetQuery::gc_parse(Some("pine_1"), None).gc_score("Pine_1", &[], &[]), Some(100));
+
+        // 每个词都要命中: 名称、标签或附加文本
+        let query = GcAssetQuery::gc_parse(Some("FOREST pine"), None);
+        assert!(query.gc_score("pine_1", &[], &pine).is_some());
+        assert!(query.gc_score("rock_1", &[], &pine).is_none());
+        assert!(GcAssetQuery::gc_parse(Some("tree"), None).gc_score("pine_1", &["trees"], &[]).is_some());
+
+        // 标签过滤要求全部标签
+        let filter = GcAssetQuery::gc_parse(None, Some("forest, Conifer"));
+        assert_eq!(filter.tags.len(), 2);
+        assert_eq!(filter.gc_score("pine_1", &[], &pine), Some(1));
+        assert!(filter.gc_score("oak_1", &[], &tags(&["forest"])).is_none());
+        assert!(GcAssetQuery::default().gc_is_empty());
+    }
+}
@@ -6,10 +6,13 @@
 
 use serde::{Deserialize, Serialize};
 use crate::{
-    GcBattleId, GcPlayerId, GcPlayer, GcCard, GcConfig,
+    GcBattleId, GcPlayerId, GcPlayer, GcCard, GcCardType, GcTargetType, GcConfig,
     GcDamageResult, GcEffectResult, GcError,
-    GcCardPool, GcCardPoolConfig,
-    GcBattlefieldCombatResult,
+    GcCardPool, GcCardPoolConfig, gc_execute_pool_effects,
+    GcBattlefieldCombatResult, GcTerrainType,
+    GcChannelAction, GcCombatLogEvent, gc_tick_channels, gc_check_channel_interrupts,
+    gc_resolve_damage, gc_percent_of, gc_damage_cue,
+    GcCostModifier, gc_effective_card_cost, gc_consume_cost_modifiers, gc_expire_cost_modifiers,
 };
 
 // =============================================================================
@@ -143,6 +146,30 @@ pub struct GcBattleState {
     
     /// 每回合行动力上限
     pub action_points_per_turn: u32,
+    
+    /// 引导中的多回合行动
+    #[serde(default)]
+    pub channels: Vec<GcChannelAction>,
+    
+    /// 战斗日志
+    #[serde(default)]
+    pub combat_log: Vec<GcCombatLogEvent>,
+    
+    /// 当前玩家本回合已打出的卡牌类型 (用于连击判定)
+    #[serde(default)]
+    pub played_this_turn: Vec<GcCardType>,
+    
+    /// 沙盒模式 (允许任意编辑状态，见 `gc_sandbox`)
+    #[serde(default)]
+    pub sandbox: bool,
+    
+    /// 强制地形 (沙盒设置，None 表示默认)
+    #[serde(default)]
+    pub forced_terrain: Option<GcTerrainType>,
+    
+    /// 卡牌费用修正 (见 `gc_card_cost`)
+    #[serde(default)]
+    pub cost_modifiers: Vec<GcCostModifier>,
 }
 
 impl GcBattleState {
@@ -161,6 +188,12 @@ impl GcBattleState {
             winner_id: None,
             card_pool,
             action_points_per_turn: 5,
+            channels: Vec::new(),
+            combat_log: Vec::new(),
+            played_this_turn: Vec::new(),
+            sandbox: false,
+            forced_terrain: None,
+            cost_modifiers: Vec::new(),
         }
     }
     
@@ -184,6 +217,12 @@ impl GcBattleState {
             winner_id: None,
             card_pool,
             action_points_per_turn: action_points,
+            channels: Vec::new(),
+            combat_log: Vec::new(),
+            played_this_turn: Vec::new(),
+            sandbox: false,
+            forced_terrain: None,
+            cost_modifiers: Vec::new(),
         }
     }
     
@@ -222,14 +261,47 @@ impl GcBattleState {
         self.phase == GcBattlePhase::Finished
     }
     
-    /// 检查战斗是否应该结束
+    /// 两名玩家是否为友方 (同一玩家，或同属一支队伍)
+    pub fn gc_are_allies(&self, a: &str, b: &str) -> bool {
+        if a == b {
+            return true;
+        }
+        let team_of = |id: &str| self.gc_find_player(id).and_then(|p| p.team);
+        matches!((team_of(a), team_of(b)), (Some(x), Some(y)) if x == y)
+    }
+    
+    /// 按行动顺序在该玩家之后的第一个可行动敌方玩家 (默认目标与战场攻击对象)
+    pub fn gc_next_enemy_of(&self, player_id: &str) -> Option<&GcPlayer> {
+        let index = self.players.iter().position(|p| p.id == player_id)?;
+        let player_count = self.players.len();
+        (1..player_count)
+            .map(|i| &self.players[(index + i) % player_count])
+            .find(|p| p.gc_can_act() && !self.gc_are_allies(player_id, &p.id))
+    }
+    
+    /// 获胜方的全部玩家 (组队时包含已阵亡的队友；平局或未结束时为空)
+    pub fn gc_winner_ids(&self) -> Vec<String> {
+        let Some(winner_id) = self.winner_id.as_deref() else {
+            return Vec::new();
+        };
+        self.players
+            .iter()
+            .filter(|p| self.gc_are_allies(winner_id, &p.id))
+            .map(|p| p.id.clone())
+            .collect()
+    }
+    
+    /// 检查战斗是否应该结束 (存活玩家全部属于同一方)
     pub fn gc_check_battle_end(&mut self) {
         let alive_players: Vec<_> = self.players
             .iter()
             .filter(|p| p.gc_can_act())
             .collect();
         
-        if alive_players.len() <= 1 {
+        let one_side_left = alive_players
+            .iter()
+            .all(|p| self.gc_are_allies(&alive_players[0].id, &p.id));
+        if alive_players.is_empty() || one_side_left {
             self.phase = GcBattlePhase::Finished;
             self.winner_id = alive_players.first().map(|p| p.id.clone());
         }
@@ -237,15 +309,22 @@ impl GcBattleState {
     
     /// 进入下一回合
     pub fn gc_next_turn(&mut self) {
+        // 结束当前玩家回合的费用修正
+        if let Some(player_id) = self.gc_current_player_id().map(str::to_string) {
+            gc_expire_cost_modifiers(self, &player_id);
+        }
+        
         // 寻找下一个可行动的玩家
         let player_count = self.players.len();
         for i in 1..=player_count {
             let next_index = (self.current_player_index + i) % player_count;
             if self.players[next_index].gc_can_act() {
-                // 重置当前玩家的行动力
+                // 重置当前玩家的行动力与技能冷却
                 self.players[next_index].stats.gc_reset_action_points();
+                self.players[next_index].hero_skills.gc_on_turn_start();
                 
                 self.current_player_index = next_index;
+                self.played_this_turn.clear();
                 
                 // 如果回到第一个玩家，增加回合数
                 if next_index <= self.current_player_index {
@@ -253,6 +332,10 @@ impl GcBattleState {
                 }
                 
                 self.phase = GcBattlePhase::DrawCard;
+                
+                // 推进该玩家的引导行动
+                let player_id = self.players[next_index].id.clone();
+                gc_tick_channels(self, &player_id);
                 return;
             }
         }
@@ -382,40 +465,32 @@ impl GcBattleState {
     }
     
     /// 执行回合结束战斗 (双方战场卡牌互相攻击)
+    /// 多人战斗时攻击行动顺序中的下一个敌方玩家
     /// 返回战斗结果
     pub fn gc_execute_turn_combat(&mut self, current_player_id: &str) -> Option<GcEndTurnResult> {
-        // 找到双方玩家索引
+        // 找到双方玩家索引 (没有可攻击的敌方时不结算)
         let current_idx = self.players.iter().position(|p| p.id == current_player_id)?;
-        let opponent_idx = (current_idx + 1) % self.players.len();
-        
-        if opponent_idx == current_idx {
-            return None; // 只有一个玩家
-        }
+        let opponent_id = self.gc_next_enemy_of(current_player_id)?.id.clone();
+        let opponent_idx = self.players.iter().position(|p| p.id == opponent_id)?;
         
         // 启用当前玩家战场卡牌的攻击能力
-        self.players[current_idx].battlefield.gc_on_turn_start();
-        
-        // 分离出两个战场进行战斗
-        // 由于 Rust 借用规则，需要克隆来处理
-        let mut player_bf = self.players[current_idx].battlefield.clone();
-        let mut opponent_bf = self.players[opponent_idx].battlefield.clone();
+        let (player, opponent) = gc_pair_mut(&mut self.players, current_idx, opponent_idx);
+        let player_bf = &mut player.battlefield;
+        let opponent_bf = &mut opponent.battlefield;
+        player_bf.gc_on_turn_start();
         
-        // 当前玩家攻击对手
-        let player_combat = player_bf.gc_attack_battlefield(&mut opponent_bf);
+        // 当前玩家攻击对手 (两个战场原地借用，不克隆)
+        let player_combat = player_bf.gc_attack_battlefield(opponent_bf);
         let opponent_damage = player_combat.player_damage;
         
         // 对手反击 (如果对手战场有可攻击的卡牌)
         opponent_bf.gc_on_turn_start(); // 启用对手攻击
-        let opponent_combat = opponent_bf.gc_attack_battlefield(&mut player_bf);
+        let opponent_combat = opponent_bf.gc_attack_battlefield(player_bf);
         let player_damage = opponent_combat.player_damage;
         
-        // 更新战场状态
-        self.players[current_idx].battlefield = player_bf;
-        self.players[opponent_idx].battlefield = opponent_bf;
-        
         // 应用伤害到玩家
-        self.players[current_idx].stats.gc_take_damage(player_damage);
-        self.players[opponent_idx].stats.gc_take_damage(opponent_damage);
+        player.stats.gc_take_damage(player_damage);
+        opponent.stats.gc_take_damage(opponent_damage);
         
         // 检查战斗是否结束
         self.gc_check_battle_end();
@@ -435,6 +510,18 @@ impl GcBattleState {
 // 核心战斗函数
 // =============================================================================
 
+/// 同时可变借用两名不同的玩家 (用 split_at_mut 避免克隆)
+fn gc_pair_mut(players: &mut [GcPlayer], a: usize, b: usize) -> (&mut GcPlayer, &mut GcPlayer) {
+    assert_ne!(a, b, "不能同时借用同一名玩家");
+    if a < b {
+        let (left, right) = players.split_at_mut(b);
+        (&mut left[a], &mut right[0])
+    } else {
+        let (left, right) = players.split_at_mut(a);
+        (&mut right[0], &mut left[b])
+    }
+}
+
 /// 验证出牌操作
 pub fn gc_validate_play_card(
     state: &GcBattleState,
@@ -460,8 +547,8 @@ pub fn gc_validate_play_card(
     let card = player.gc_find_card_in_hand(card_id)
         .ok_or(GcError::GcCardNotInHand)?;
     
-    // 5. 检查能量是否足够
-    if player.stats.energy < card.cost {
+    // 5. 检查能量是否足够 (按修正后的实际费用)
+    if player.stats.energy < gc_effective_card_cost(state, player_id, card) {
         return Err(GcError::GcNotEnoughEnergy);
     }
     
@@ -473,22 +560,35 @@ pub fn gc_validate_play_card(
         if !target.gc_can_act() {
             return Err(GcError::GcInvalidTarget);
         }
+        
+        // 组队时单体攻击不能指向友方，单体增益不能指向敌方
+        let allied = state.gc_are_allies(player_id, target_id);
+        let wrong_side = match card.target_type {
+            GcTargetType::SingleEnemy => allied && player_id != target_id,
+            GcTargetType::SingleAlly => !allied,
+            _ => false,
+        };
+        if wrong_side {
+            return Err(GcError::GcInvalidTarget);
+        }
     }
     
     Ok(())
 }
 
 /// 计算伤害
+///
+/// - `combo_bonus`: 连击触发时的额外伤害
 pub fn gc_calculate_damage(
     attacker: &GcPlayer,
     target: &GcPlayer,
     card: &GcCard,
+    combo_bonus: u32,
 ) -> GcDamageResult {
-    let base_damage = card.base_damage + attacker.stats.attack;
-    let defense_reduction = (target.stats.defense as f32 * 0.3) as u32;
-    let final_damage = base_damage.saturating_sub(defense_reduction);
+    let base_damage = card.base_damage.saturating_add(combo_bonus).saturating_add(attacker.stats.attack);
+    let defense_reduction = gc_percent_of(target.stats.defense, 30);
     
-    GcDamageResult::new(base_damage, defense_reduction, final_damage)
+    gc_resolve_damage(base_damage, defense_reduction, card.damage_type, &target.resistances)
 }
 
 /// 执行出牌操作
@@ -509,48 +609,98 @@ pub fn gc_execute_play_card(
         .cloned()
         .unwrap();
     
-    // 计算伤害
-    let damage_result = {
-        let attacker = state.gc_find_player(player_id).unwrap();
-        let target = state.gc_find_player(target_id).unwrap();
-        gc_calculate_damage(attacker, target, &card)
-    };
-    
-    // 应用伤害
-    let target_killed = {
-        let target = state.gc_find_player_mut(target_id).unwrap();
-        target.stats.gc_take_damage(damage_result.final_damage);
-        !target.stats.gc_is_alive()
-    };
-    
-    if target_killed {
-        let target = state.gc_find_player_mut(target_id).unwrap();
-        target.state = crate::GcPlayerState::Dead;
+    // 实际费用 (消耗修正前计算)
+    let cost = gc_effective_card_cost(state, player_id, &card);
+    
+    // 连击判定 (基于本回合之前的出牌)
+    let combo_bonus = card.gc_combo_bonus(&state.played_this_turn);
+    
+    let mut effects = Vec::new();
+    let mut damage_dealt = 0;
+    let mut target_killed = false;
+    if card.target_type == GcTargetType::SingleAlly {
+        // 友方单体牌: 治疗目标 (base_damage 为治疗量)，不造成伤害
+        let healed = state.gc_find_player_mut(target_id)
+            .map(|target| target.stats.gc_heal(card.base_damage))
+            .unwrap_or(0);
+        state.combat_log.push(GcCombatLogEvent::Heal {
+            turn: state.turn,
+            source_id: player_id.to_string(),
+            target_id: target_id.to_string(),
+            amount: healed,
+        });
+        effects.push(GcEffectResult::new("治疗", target_id, healed as i32, format!("恢复 {} 点生命", healed)));
+    } else {
+        // 计算伤害
+        let damage_result = {
+            let attacker = state.gc_find_player(player_id).unwrap();
+            let target = state.gc_find_player(target_id).unwrap();
+            gc_calculate_damage(attacker, target, &card, combo_bonus)
+        };
+        
+        // 应用伤害
+        let (killed, target_max_hp) = {
+            let target = state.gc_find_player_mut(target_id).unwrap();
+            target.stats.gc_take_damage(damage_result.final_damage);
+            (!target.stats.gc_is_alive(), target.stats.max_hp)
+        };
+        state.combat_log.push(GcCombatLogEvent::Damage {
+            turn: state.turn,
+            source_id: player_id.to_string(),
+            target_id: target_id.to_string(),
+            result: damage_result.clone(),
+            breakdown: damage_result.gc_breakdown(),
+            cue: gc_damage_cue(&damage_result, target_max_hp, killed),
+        });
+        
+        if killed {
+            let target = state.gc_find_player_mut(target_id).unwrap();
+            target.state = crate::GcPlayerState::Dead;
+        }
+        
+        effects.push(GcEffectResult::new(
+            "伤害",
+            target_id,
+            damage_result.final_damage as i32,
+            format!("造成 {} 点伤害 ({})", damage_result.final_damage, damage_result.gc_breakdown()),
+        ));
+        if combo_bonus > 0 {
+            effects.push(GcEffectResult::new(
+                "连击",
+                target_id,
+                combo_bonus as i32,
+                format!("连击触发，伤害 +{}", combo_bonus),
+            ));
+        }
+        damage_dealt = damage_result.final_damage;
+        target_killed = killed;
     }
     
     // 消耗能量并移除卡牌
     {
         let player = state.gc_find_player_mut(player_id).unwrap();
-        player.stats.energy = player.stats.energy.saturating_sub(card.cost);
+        player.stats.energy = player.stats.energy.saturating_sub(cost);
         if let Some(used_card) = player.gc_remove_card_from_hand(card_id) {
             player.discard.push(used_card);
         }
     }
+    gc_consume_cost_modifiers(state, player_id, &card);
+    state.played_this_turn.push(card.card_type.clone());
+    
+    // 卡池效果 (查看/检索)
+    let pool_effects = match state.players.iter_mut().find(|p| p.id == player_id) {
+        Some(player) => gc_execute_pool_effects(&mut state.card_pool, player, &card.effects),
+        None => Vec::new(),
+    };
     
-    // 检查战斗是否结束
+    // 检查引导打断与战斗是否结束
+    gc_check_channel_interrupts(state);
     state.gc_check_battle_end();
     
     // 构造效果结果
-    let effects = vec![
-        GcEffectResult::new(
-            "伤害",
-            target_id,
-            damage_result.final_damage as i32,
-            format!("造成 {} 点伤害", damage_result.final_damage),
-        ),
-    ];
+    effects.extend(pool_effects);
     
-    GcPlayCardResult::success(card, damage_result.final_damage, effects, target_killed)
+    GcPlayCardResult::success(card, damage_dealt, effects, target_killed)
 }
 
 // =============================================================================
@@ -605,4 +755,44 @@ mod tests {
         let target = battle.gc_find_player("p2").unwrap();
         assert!(target.stats.hp < GcConfig::DEFAULT_HP);
     }
+
+    #[test]
+    fn test_gc_execute_turn_combat_in_place() {
+        let mut battle = create_test_battle();
+        let card = GcCard::gc_new_attack("bf1", "哨兵", 1, 10);
+        battle.players[1].battlefield.gc_deploy_to_slot(0, card).unwrap();
+        
+        // 后一名玩家发起战斗，双方战场原地借用
+        let result = battle.gc_execute_turn_combat("p2").unwrap();
+        assert!(result.opponent_damage_taken > 0);
+        assert_eq!(battle.players[0].stats.hp, GcConfig::DEFAULT_HP - result.opponent_damage_taken);
+        assert_eq!(battle.players[1].battlefield.gc_deployed_count(), 1);
+    }
+
+    #[test]
+    fn test_gc_combo_bonus_from_play_history() {
+        use crate::{GcCardCombo, GcComboCondition};
+
+        let mut battle = create_test_battle();
+        let combo_card = GcCard::gc_new_attack("c2", "追击", 1, 10)
+            .gc_with_combo(GcCardCombo::new(GcComboCondition::PlayedType(GcCardType::Attack), 8));
+        battle.players[0].hand.push(combo_card.clone());
+
+        let base = {
+            let p1 = battle.gc_find_player("p1").unwrap();
+            let p2 = battle.gc_find_player("p2").unwrap();
+            gc_calculate_damage(p1, p2, &combo_card, 0).final_damage
+        };
+
+        assert!(gc_execute_play_card(&mut battle, "p1", "c1", "p2").success);
+        assert_eq!(battle.played_this_turn, vec![GcCardType::Attack]);
+
+        let result = gc_execute_play_card(&mut battle, "p1", "c2", "p2");
+        assert!(result.success);
+        assert_eq!(result.damage_dealt, base + 8);
+        assert!(result.effects_triggered.iter().any(|e| e.effect_name == "连击"));
+
+        battle.gc_next_turn();
+        assert!(battle.played_this_turn.is_empty());
+    }
 }
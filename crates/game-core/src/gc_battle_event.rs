@@ -0,0 +1,465 @@
+//! 事件溯源战斗
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! ## 规则
+//! - 权威数据是有序的领域事件列表，战斗状态由事件依次折叠 (fold) 得到
+//! - 第一个事件必须是 `Genesis` (创建战斗或从旧状态迁移时的完整快照)
+//! - 操作先校验，成功后才记录事件；失败的操作不留下事件
+//! - 含随机结果的事件 (卡池洗牌、服务器发牌) 直接携带结果，重放时不再产生随机数
+//! - 回放、撤销、审计、增量同步都基于事件列表: 重放前缀即得历史状态，发送新增事件即可同步
+//! - 随事件维护公开行动记录 (`GcActionFeed`)，供历史面板和解说使用
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    GcBattlePhase, GcBattleState, GcCard, GcCardPool, GcCardPoolConfig, GcCostModifier, GcEndTurnResult,
+    GcError, GcPlayCardResult, GcPlayer, GcSandboxCommand, GcSkillUseResult, GcActionFeed, GcFeedProbe,
+    gc_add_cost_modifier, gc_apply_sandbox_command, gc_execute_play_card, gc_execute_use_skill, gc_start_channel_from_template,
+    gc_remove_cost_modifiers_from, gc_fold_battle_events_with_feed,
+};
+
+// =============================================================================
+// 领域事件
+// =============================================================================
+
+/// 战斗领域事件
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data")]
+pub enum GcBattleEvent {
+    /// 初始快照 (创建战斗、从旧状态迁移)
+    Genesis { state: Box<GcBattleState> },
+    /// 战斗阶段变化
+    PhaseChanged { phase: GcBattlePhase },
+    /// 出牌
+    CardPlayed { player_id: String, card_id: String, target_id: String },
+    /// 使用英雄技能
+    SkillUsed { player_id: String, skill_id: String, target_id: String },
+    /// 按模板开始引导
+    ChannelStarted { player_id: String, template_id: String, target_id: String },
+    /// 从公共卡池获取卡牌 (携带补充后的卡池)
+    CardAcquired { player_id: String, card_id: String, card_pool: Box<GcCardPool> },
+    /// 刷新公共卡池 (携带刷新后的卡池)
+    PoolRefreshed { player_id: String, card_pool: Box<GcCardPool> },
+    /// 部署卡牌到战场
+    CardDeployed { player_id: String, card_id: String, slot_index: usize },
+    /// 回合结束战场战斗
+    TurnCombatResolved { player_id: String },
+    /// 进入下一回合
+    TurnAdvanced,
+    /// 玩家获得卡牌 (卡牌由外部生成)
+    CardGiven { player_id: String, card: Box<GcCard> },
+    /// 设置玩家能量
+    EnergySet { player_id: String, energy: u32 },
+    /// 沙盒编辑
+    SandboxEdited { command: GcSandboxCommand },
+    /// 添加卡牌费用修正
+    CostModifierAdded { modifier: GcCostModifier },
+    /// 移除指定来源的费用修正 (光环结束)
+    CostModifiersRemoved { source: String },
+}
+
+/// 将一个事件应用到状态 (折叠的单步)
+pub fn gc_apply_battle_event(state: &mut GcBattleState, event: &GcBattleEvent) -> Result<(), GcError> {
+    match event {
+        GcBattleEvent::Genesis { state: genesis } => {
+            *state = (**genesis).clone();
+        }
+        GcBattleEvent::PhaseChanged { phase } => {
+            state.phase = phase.clone();
+        }
+        GcBattleEvent::CardPlayed { player_id, card_id, target_id } => {
+            let result = gc_execute_play_card(state, player_id, card_id, target_id);
+            if !result.success {
+                return Err(GcError::GcInvalidAction(result.error.unwrap_or_default()));
+            }
+        }
+        GcBattleEvent::SkillUsed { player_id, skill_id, target_id } => {
+            let result = gc_execute_use_skill(state, player_id, skill_id, target_id);
+            if !result.success {
+                return Err(GcError::GcInvalidAction(result.error.unwrap_or_default()));
+            }
+        }
+        GcBattleEvent::ChannelStarted { player_id, template_id, target_id } => {
+            gc_start_channel_from_template(state, player_id, template_id, target_id)?;
+        }
+        GcBattleEvent::CardAcquired { player_id, card_id, card_pool } => {
+            state.gc_acquire_card_from_pool(player_id, card_id)?;
+            state.card_pool = (**card_pool).clone();
+        }
+        GcBattleEvent::PoolRefreshed { player_id, card_pool } => {
+            state.gc_refresh_pool(player_id)?;
+            state.card_pool = (**card_pool).clone();
+        }
+        GcBattleEvent::CardDeployed { player_id, card_id, slot_index } => {
+            state.gc_deploy_card(player_id, card_id, *slot_index)?;
+        }
+        GcBattleEvent::TurnCombatResolved { player_id } => {
+            state.gc_execute_turn_combat(player_id).ok_or(GcError::GcPlayerNotFound)?;
+        }
+        GcBattleEvent::TurnAdvanced => {
+            state.gc_next_turn();
+        }
+        GcBattleEvent::CardGiven { player_id, card } => {
+            let player = state.gc_find_player_mut(player_id).ok_or(GcError::GcPlayerNotFound)?;
+            player.hand.push((**card).clone());
+        }
+        GcBattleEvent::EnergySet { player_id, energy } => {
+            let player = state.gc_find_player_mut(player_id).ok_or(GcError::GcPlayerNotFound)?;
+            player.stats.energy = *energy;
+        }
+        GcBattleEvent::SandboxEdited { command } => {
+            gc_apply_sandbox_command(state, command)?;
+        }
+        GcBattleEvent::CostModifierAdded { modifier } => {
+            gc_add_cost_modifier(state, modifier.clone())?;
+        }
+        GcBattleEvent::CostModifiersRemoved { source } => {
+            gc_remove_cost_modifiers_from(state, source);
+        }
+    }
+    Ok(())
+}
+
+/// 折叠事件列表得到状态
+pub fn gc_fold_battle_events(events: &[GcBattleEvent]) -> Result<GcBattleState, GcError> {
+    let (first, rest) = events.split_first().ok_or(GcError::GcBattleNotStarted)?;
+    let GcBattleEvent::Genesis { state } = first else {
+        return Err(GcError::GcInvalidAction("第一个事件必须是初始快照".to_string()));
+    };
+    let mut state = (**state).clone();
+    for event in rest {
+        gc_apply_battle_event(&mut state, event)?;
+    }
+    Ok(state)
+}
+
+// =============================================================================
+// 事件溯源战斗
+// =============================================================================
+
+/// 事件溯源战斗 (事件列表 + 折叠后的当前状态缓存)
+#[derive(Clone, Debug)]
+pub struct GcEventSourcedBattle {
+    /// 事件 (从旧到新)
+    events: Vec<GcBattleEvent>,
+    /// 当前状态 (等于折叠全部事件的结果)
+    state: GcBattleState,
+    /// 公开行动记录
+    feed: GcActionFeed,
+}
+
+impl GcEventSourcedBattle {
+    /// 创建新战斗 (兼容 `GcBattleState::gc_new`)
+    pub fn gc_new(id: impl Into<String>, players: Vec<GcPlayer>) -> Self {
+        Self::gc_from_state(GcBattleState::gc_new(id, players))
+    }
+
+    /// 创建带自定义配置的战斗 (兼容 `GcBattleState::gc_new_with_config`)
+    pub fn gc_new_with_config(
+        id: impl Into<String>,
+        players: Vec<GcPlayer>,
+        pool_config: GcCardPoolConfig,
+        action_points: u32,
+    ) -> Self {
+        Self::gc_from_state(GcBattleState::gc_new_with_config(id, players, pool_config, action_points))
+    }
+
+    /// 从已有状态迁移 (状态作为初始快照)
+    pub fn gc_from_state(state: GcBattleState) -> Self {
+        Self {
+            events: vec![GcBattleEvent::Genesis { state: Box::new(state.clone()) }],
+            state,
+            feed: GcActionFeed::default(),
+        }
+    }
+
+    /// 从事件列表重建
+    pub fn gc_from_events(events: Vec<GcBattleEvent>) -> Result<Self, GcError> {
+        let (state, feed) = gc_fold_battle_events_with_feed(&events)?;
+        Ok(Self { events, state, feed })
+    }
+
+    /// 当前状态
+    pub fn gc_state(&self) -> &GcBattleState {
+        &self.state
+    }
+
+    /// 取出当前状态
+    pub fn gc_into_state(self) -> GcBattleState {
+        self.state
+    }
+
+    /// 全部事件
+    pub fn gc_events(&self) -> &[GcBattleEvent] {
+        &self.events
+    }
+
+    /// 指定序号之后的事件 (增量同步)
+    pub fn gc_events_since(&self, index: usize) -> &[GcBattleEvent] {
+        self.events.get(index..).unwrap_or(&[])
+    }
+
+    /// 公开行动记录
+    pub fn gc_action_feed(&self) -> &GcActionFeed {
+        &self.feed
+    }
+
+    /// 事件数量
+    pub fn gc_len(&self) -> usize {
+        self.events.len()
+    }
+
+    /// 是否为空 (至少有初始快照，总是 false)
+    pub fn gc_is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// 应用前 `count` 个事件后的状态 (回放、审计)
+    pub fn gc_state_at(&self, count: usize) -> Result<GcBattleState, GcError> {
+        gc_fold_battle_events(&self.events[..count.min(self.events.len())])
+    }
+
+    /// 记录事件 (应用失败时不记录)
+    pub fn gc_record(&mut self, event: GcBattleEvent) -> Result<(), GcError> {
+        let probe = GcFeedProbe::gc_capture(&self.state, &event);
+        let mut next = self.state.clone();
+        gc_apply_battle_event(&mut next, &event)?;
+        self.state = next;
+        self.gc_push(probe, event);
+        Ok(())
+    }
+
+    /// 追加已应用到当前状态的事件，并更新公开行动记录
+    fn gc_push(&mut self, probe: GcFeedProbe, event: GcBattleEvent) {
+        self.feed.gc_observe(probe, self.events.len(), &event, &self.state);
+        self.events.push(event);
+    }
+
+    /// 撤销最后一个事件 (初始快照不可撤销)，返回被撤销的事件
+    pub fn gc_undo(&mut self) -> Option<GcBattleEvent> {
+        if self.events.len() <= 1 {
+            return None;
+        }
+        let event = self.events.pop()?;
+        match gc_fold_battle_events(&self.events) {
+            Ok(state) => {
+                self.state = state;
+                self.feed.gc_truncate_events(self.events.len());
+                Some(event)
+            }
+            Err(_) => {
+                self.events.push(event);
+                None
+            }
+        }
+    }
+
+    // =========================================================================
+    // 操作 (校验成功后记录事件)
+    // =========================================================================
+
+    /// 出牌
+    pub fn gc_play_card(&mut self, player_id: &str, card_id: &str, target_id: &str) -> GcPlayCardResult {
+        let event = GcBattleEvent::CardPlayed {
+            player_id: player_id.to_string(),
+            card_id: card_id.to_string(),
+            target_id: target_id.to_string(),
+        };
+        let probe = GcFeedProbe::gc_capture(&self.state, &event);
+        let result = gc_execute_play_card(&mut self.state, player_id, card_id, target_id);
+        if result.success {
+            self.gc_push(probe, event);
+        }
+        result
+    }
+
+    /// 使用英雄技能
+    pub fn gc_use_skill(&mut self, player_id: &str, skill_id: &str, target_id: &str) -> GcSkillUseResult {
+        let event = GcBattleEvent::SkillUsed {
+            player_id: player_id.to_string(),
+            skill_id: skill_id.to_string(),
+            target_id: target_id.to_string(),
+        };
+        let probe = GcFeedProbe::gc_capture(&self.state, &event);
+        let result = gc_execute_use_skill(&mut self.state, player_id, skill_id, target_id);
+        if result.success {
+            self.gc_push(probe, event);
+        }
+        result
+    }
+
+    /// 按模板开始引导
+    pub fn gc_start_channel(&mut self, player_id: &str, template_id: &str, target_id: &str) -> Result<(), GcError> {
+        let event = GcBattleEvent::ChannelStarted {
+            player_id: player_id.to_string(),
+            template_id: template_id.to_string(),
+            target_id: target_id.to_string(),
+        };
+        let probe = GcFeedProbe::gc_capture(&self.state, &event);
+        gc_start_channel_from_template(&mut self.state, player_id, template_id, target_id)?;
+        self.gc_push(probe, event);
+        Ok(())
+    }
+
+    /// 从公共卡池获取卡牌
+    pub fn gc_acquire_card_from_pool(&mut self, player_id: &str, card_id: &str) -> Result<GcCard, GcError> {
+        let probe = GcFeedProbe::gc_capture_pool_card(&self.state, card_id);
+        let card = self.state.gc_acquire_card_from_pool(player_id, card_id)?;
+        let event = GcBattleEvent::CardAcquired {
+            player_id: player_id.to_string(),
+            card_id: card_id.to_string(),
+            card_pool: Box::new(self.state.card_pool.clone()),
+        };
+        self.gc_push(probe, event);
+        Ok(card)
+    }
+
+    /// 刷新公共卡池
+    pub fn gc_refresh_pool(&mut self, player_id: &str) -> Result<(), GcError> {
+        let probe = GcFeedProbe::gc_capture_state(&self.state);
+        self.state.gc_refresh_pool(player_id)?;
+        let event = GcBattleEvent::PoolRefreshed {
+            player_id: player_id.to_string(),
+            card_pool: Box::new(self.state.card_pool.clone()),
+        };
+        self.gc_push(probe, event);
+        Ok(())
+    }
+
+    /// 部署卡牌到战场
+    pub fn gc_deploy_card(&mut self, player_id: &str, card_id: &str, slot_index: usize) -> Result<(), GcError> {
+        let event = GcBattleEvent::CardDeployed {
+            player_id: player_id.to_string(),
+            card_id: card_id.to_string(),
+            slot_index,
+        };
+        let probe = GcFeedProbe::gc_capture(&self.state, &event);
+        self.state.gc_deploy_card(player_id, card_id, slot_index)?;
+        self.gc_push(probe, event);
+        Ok(())
+    }
+
+    /// 回合结束战场战斗
+    pub fn gc_execute_turn_combat(&mut self, player_id: &str) -> Option<GcEndTurnResult> {
+        let event = GcBattleEvent::TurnCombatResolved { player_id: player_id.to_string() };
+        let probe = GcFeedProbe::gc_capture(&self.state, &event);
+        let result = self.state.gc_execute_turn_combat(player_id)?;
+        self.gc_push(probe, event);
+        Some(result)
+    }
+
+    /// 进入下一回合
+    pub fn gc_next_turn(&mut self) {
+        let probe = GcFeedProbe::gc_capture_state(&self.state);
+        self.state.gc_next_turn();
+        self.gc_push(probe, GcBattleEvent::TurnAdvanced);
+    }
+}
+
+impl From<GcBattleState> for GcEventSourcedBattle {
+    fn from(state: GcBattleState) -> Self {
+        Self::gc_from_state(state)
+    }
+}
+
+impl From<GcEventSourcedBattle> for GcBattleState {
+    fn from(battle: GcEventSourcedBattle) -> Self {
+        battle.gc_into_state()
+    }
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GcConfig;
+
+    fn create_battle() -> GcEventSourcedBattle {
+        let mut p1 = GcPlayer::gc_new("p1", "玩家1");
+        p1.hand.push(GcCard::gc_new_attack("c1", "打击", 1, 10));
+        p1.hand.push(GcCard::gc_new_attack("c2", "重击", 1, 20));
+        let mut battle = GcEventSourcedBattle::gc_new("events", vec![p1, GcPlayer::gc_new("p2", "玩家2")]);
+        battle.gc_record(GcBattleEvent::PhaseChanged { phase: GcBattlePhase::Playing }).unwrap();
+        battle
+    }
+
+    fn to_json(state: &GcBattleState) -> String {
+        serde_json::to_string(state).unwrap_or_default()
+    }
+
+    #[test]
+    fn test_fold_matches_live_state() {
+        let mut battle = create_battle();
+        assert!(battle.gc_play_card("p1", "c1", "p2").success);
+        assert!(!battle.gc_play_card("p1", "missing", "p2").success);
+        let card_id = battle.gc_state().gc_get_pool_display()[0].id.clone();
+        battle.gc_acquire_card_from_pool("p1", &card_id).unwrap();
+        battle.gc_next_turn();
+
+        // 失败的出牌不留事件
+        assert_eq!(battle.gc_len(), 5);
+
+        // 通过 JSON 传输事件后重放，得到相同状态
+        let json = serde_json::to_string(battle.gc_events()).unwrap();
+        let events: Vec<GcBattleEvent> = serde_json::from_str(&json).unwrap();
+        let replayed = GcEventSourcedBattle::gc_from_events(events).unwrap();
+        assert_eq!(to_json(replayed.gc_state()), to_json(battle.gc_state()));
+    }
+
+    #[test]
+    fn test_undo_and_history() {
+        let mut battle = create_battle();
+        battle.gc_play_card("p1", "c1", "p2");
+        battle.gc_play_card("p1", "c2", "p2");
+        let hp = battle.gc_state().players[1].stats.hp;
+        assert!(hp < GcConfig::DEFAULT_HP);
+
+        assert!(matches!(battle.gc_undo(), Some(GcBattleEvent::CardPlayed { .. })));
+        assert!(battle.gc_state().players[1].stats.hp > hp);
+        assert_eq!(battle.gc_state().players[0].hand.len(), 1);
+
+        assert_eq!(battle.gc_state_at(1).unwrap().phase, GcBattlePhase::Starting);
+        assert_eq!(battle.gc_events_since(2).len(), 1);
+    }
+
+    #[test]
+    fn test_channel_resolves_through_turn_advance() {
+        let mut battle = create_battle();
+        assert!(battle.gc_start_channel("p1", "missing", "p2").is_err());
+        battle.gc_start_channel("p1", crate::GC_CHANNEL_PYROBLAST, "p2").unwrap();
+        assert_eq!(battle.gc_state().channels.len(), 1);
+
+        // p2 回合 → p1 回合 (剩 1) → p2 回合 → p1 回合 (完成)
+        for _ in 0..4 {
+            battle.gc_next_turn();
+        }
+        assert!(battle.gc_state().channels.is_empty());
+        assert_eq!(battle.gc_state().players[1].stats.hp, GcConfig::DEFAULT_HP - 30);
+        assert!(matches!(
+            battle.gc_state().combat_log.last(),
+            Some(crate::GcCombatLogEvent::ChannelComplete { .. })
+        ));
+
+        let replayed = GcEventSourcedBattle::gc_from_events(battle.gc_events().to_vec()).unwrap();
+        assert_eq!(to_json(replayed.gc_state()), to_json(battle.gc_state()));
+    }
+
+    #[test]
+    fn test_invalid_event_is_rejected() {
+        let mut battle = create_battle();
+        let event = GcBattleEvent::CardPlayed {
+            player_id: "p2".to_string(),
+            card_id: "c1".to_string(),
+            target_id: "p1".to_string(),
+        };
+        assert!(battle.gc_record(event).is_err());
+        assert_eq!(battle.gc_len(), 2);
+        assert!(GcEventSourcedBattle::gc_from_events(vec![GcBattleEvent::TurnAdvanced]).is_err());
+    }
+}
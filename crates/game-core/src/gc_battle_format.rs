@@ -0,0 +1,258 @@
+//! 多人战斗赛制 (决斗 / 自由混战 / 2v2)
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! ## 规则
+//! - 决斗 2 人；自由混战 3-4 人，各自为战；2v2 固定 4 人分两队
+//! - 非组队赛制会清除玩家的队伍标记，所有其他玩家都是敌方
+//! - 2v2 未指定队伍的玩家按加入顺序补进人数较少的队伍 (队伍 0 / 1)，每队必须恰好 2 人
+//! - 组队时座位交替排列 (A1 B1 A2 B2)，保证行动顺序敌我交替
+//! - 队伍共同胜负: 一队全员无法行动即整队落败 (`GcBattleState::gc_teams`)
+//! - 出牌目标、战场攻击与胜负判定见 `GcBattleState::gc_are_allies` / `gc_next_enemy_of` / `gc_winner_ids`
+
+use serde::{Deserialize, Serialize};
+
+use crate::{GcBattleState, GcError, GcPlayer, GcPlayerId};
+
+// =============================================================================
+// 常量
+// =============================================================================
+
+/// 单场战斗最多玩家数
+pub const GC_MAX_BATTLE_PLAYERS: usize = 4;
+
+/// 2v2 的队伍数
+pub const GC_BATTLE_TEAM_COUNT: u32 = 2;
+
+// =============================================================================
+// 赛制
+// =============================================================================
+
+/// 战斗赛制
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum GcBattleFormat {
+    /// 1v1 决斗
+    #[default]
+    Duel,
+    /// 3-4 人自由混战
+    FreeForAll,
+    /// 2v2 组队
+    TwoVsTwo,
+}
+
+impl GcBattleFormat {
+    /// 允许的玩家人数 (最少, 最多)
+    pub fn gc_player_range(&self) -> (usize, usize) {
+        match self {
+            Self::Duel => (2, 2),
+            Self::FreeForAll => (3, GC_MAX_BATTLE_PLAYERS),
+            Self::TwoVsTwo => (4, 4),
+        }
+    }
+
+    /// 最多玩家数 (房间容量)
+    pub fn gc_max_players(&self) -> usize {
+        self.gc_player_range().1
+    }
+
+    /// 是否组队
+    pub fn gc_uses_teams(&self) -> bool {
+        matches!(self, Self::TwoVsTwo)
+    }
+
+    /// 按人数推断赛制 (4 人且带队伍标记时为 2v2)
+    pub fn gc_infer(players: &[GcPlayer]) -> Self {
+        match players.len() {
+            0..=2 => Self::Duel,
+            4 if players.iter().any(|p| p.team.is_some()) => Self::TwoVsTwo,
+            _ => Self::FreeForAll,
+        }
+    }
+}
+
+// =============================================================================
+// 座位安排
+// =============================================================================
+
+/// 按赛制校验人数与队伍并安排座位 (即行动顺序)
+pub fn gc_arrange_seats(format: GcBattleFormat, mut players: Vec<GcPlayer>) -> Result<Vec<GcPlayer>, GcError> {
+    let (min, max) = format.gc_player_range();
+    if players.len() < min || players.len() > max {
+        return Err(GcError::GcInvalidAction(format!(
+            "{:?} 需要 {}-{} 名玩家，当前 {} 名", format, min, max, players.len()
+        )));
+    }
+
+    if !format.gc_uses_teams() {
+        for player in &mut players {
+            player.team = None;
+        }
+        return Ok(players);
+    }
+
+    // 未指定队伍的玩家补进人数较少的队伍
+    let mut sizes = [0usize; GC_BATTLE_TEAM_COUNT as usize];
+    for team in players.iter().filter_map(|p| p.team) {
+        if team >= GC_BATTLE_TEAM_COUNT {
+            return Err(GcError::GcInvalidAction(format!("无效的队伍: {}", team)));
+        }
+        sizes[team as usize] += 1;
+    }
+    for player in players.iter_mut().filter(|p| p.team.is_none()) {
+        let team = if sizes[0] <= sizes[1] { 0 } else { 1 };
+        sizes[team] += 1;
+        player.team = Some(team as u32);
+    }
+    if sizes.iter().any(|&size| size != players.len() / 2) {
+        return Err(GcError::GcInvalidAction(format!("队伍人数不均: {:?}", sizes)));
+    }
+
+    // 两队交替入座，先手方为第一个玩家所在的队伍
+    let first_team = players[0].team;
+    let (mut first, mut second): (Vec<_>, Vec<_>) = players.into_iter().partition(|p| p.team == first_team);
+    let mut seats = Vec::with_capacity(first.len() + second.len());
+    while !first.is_empty() {
+        seats.push(first.remove(0));
+        seats.push(second.remove(0));
+    }
+    Ok(seats)
+}
+
+// =============================================================================
+// 队伍
+// =============================================================================
+
+/// 队伍概况 (由玩家的队伍标记汇总)
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcBattleTeam {
+    /// 队伍编号
+    pub team: u32,
+    /// 队员 (按座位顺序)
+    pub player_ids: Vec<GcPlayerId>,
+    /// 全员无法行动 (队伍共同落败)
+    pub eliminated: bool,
+}
+
+impl GcBattleState {
+    /// 玩家所在队伍 (不分队伍时为 None)
+    pub fn gc_team_of(&self, player_id: &str) -> Option<u32> {
+        self.gc_find_player(player_id).and_then(|p| p.team)
+    }
+
+    /// 各队伍概况 (按队伍编号排序；不分队伍时为空)
+    pub fn gc_teams(&self) -> Vec<GcBattleTeam> {
+        let mut teams: Vec<GcBattleTeam> = Vec::new();
+        for player in &self.players {
+            let Some(team) = player.team else {
+                continue;
+            };
+            let index = match teams.iter().position(|t| t.team == team) {
+                Some(index) => index,
+                None => {
+                    teams.push(GcBattleTeam { team, player_ids: Vec::new(), eliminated: true });
+                    teams.len() - 1
+                }
+            };
+            teams[index].player_ids.push(player.id.clone());
+            teams[index].eliminated &= !player.gc_can_act();
+        }
+        teams.sort_by_key(|t| t.team);
+        teams
+    }
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{gc_validate_play_card, GcBattlePhase, GcCard, GcPlayerState};
+
+    fn players(count: usize) -> Vec<GcPlayer> {
+        (0..count).map(|i| GcPlayer::gc_new(format!("p{}", i), format!("玩家{}", i))).collect()
+    }
+
+    fn ids(players: &[GcPlayer]) -> Vec<&str> {
+        players.iter().map(|p| p.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_player_counts_per_format() {
+        assert!(gc_arrange_seats(GcBattleFormat::Duel, players(2)).is_ok());
+        assert!(gc_arrange_seats(GcBattleFormat::Duel, players(3)).is_err());
+        assert!(gc_arrange_seats(GcBattleFormat::FreeForAll, players(2)).is_err());
+        assert!(gc_arrange_seats(GcBattleFormat::FreeForAll, players(4)).is_ok());
+        assert!(gc_arrange_seats(GcBattleFormat::TwoVsTwo, players(3)).is_err());
+
+        let mut teamed = players(4);
+        teamed[0].team = Some(0);
+        let seated = gc_arrange_seats(GcBattleFormat::FreeForAll, teamed).unwrap();
+        assert!(seated.iter().all(|p| p.team.is_none()));
+    }
+
+    #[test]
+    fn test_two_vs_two_fills_and_interleaves_teams() {
+        let mut list = players(4);
+        list[0].team = Some(1);
+        list[1].team = Some(1);
+        let seated = gc_arrange_seats(GcBattleFormat::TwoVsTwo, list).unwrap();
+        assert_eq!(ids(&seated), vec!["p0", "p2", "p1", "p3"]);
+        let teams: Vec<_> = seated.iter().map(|p| p.team).collect();
+        assert_eq!(teams, vec![Some(1), Some(0), Some(1), Some(0)]);
+
+        let mut uneven = players(4);
+        for player in uneven.iter_mut().take(3) {
+            player.team = Some(0);
+        }
+        assert!(gc_arrange_seats(GcBattleFormat::TwoVsTwo, uneven).is_err());
+    }
+
+    #[test]
+    fn test_team_victory_and_enemy_routing() {
+        let seated = gc_arrange_seats(GcBattleFormat::TwoVsTwo, players(4)).unwrap();
+        let order = ids(&seated).into_iter().map(str::to_string).collect::<Vec<_>>();
+        let mut state = GcBattleState::gc_new("b1", seated);
+        state.phase = GcBattlePhase::Playing;
+
+        // 下一个敌人跳过队友与阵亡的敌人
+        assert!(state.gc_are_allies(&order[0], &order[2]));
+        assert_eq!(state.gc_next_enemy_of(&order[0]).map(|p| p.id.as_str()), Some(order[1].as_str()));
+        state.gc_find_player_mut(&order[1]).unwrap().state = GcPlayerState::Dead;
+        assert_eq!(state.gc_next_enemy_of(&order[0]).map(|p| p.id.as_str()), Some(order[3].as_str()));
+        state.gc_check_battle_end();
+        assert!(!state.gc_is_finished());
+
+        // 敌队仍有存活队员时未落败
+        let teams = state.gc_teams();
+        assert_eq!(teams.len(), 2);
+        assert!(teams.iter().all(|t| !t.eliminated && t.player_ids.len() == 2));
+
+        // 敌队全灭后整队获胜，包括已阵亡的队友
+        state.gc_find_player_mut(&order[2]).unwrap().state = GcPlayerState::Dead;
+        state.gc_find_player_mut(&order[3]).unwrap().state = GcPlayerState::Dead;
+        state.gc_check_battle_end();
+        assert!(state.gc_is_finished());
+        assert_eq!(state.winner_id.as_deref(), Some(order[0].as_str()));
+        assert_eq!(state.gc_winner_ids(), vec![order[0].clone(), order[2].clone()]);
+        let losing_team = state.gc_team_of(&order[1]).unwrap();
+        assert!(state.gc_teams().iter().any(|t| t.team == losing_team && t.eliminated));
+    }
+
+    #[test]
+    fn test_single_enemy_cards_cannot_target_teammates() {
+        let seated = gc_arrange_seats(GcBattleFormat::TwoVsTwo, players(4)).unwrap();
+        let order = ids(&seated).into_iter().map(str::to_string).collect::<Vec<_>>();
+        let mut state = GcBattleState::gc_new("b1", seated);
+        state.phase = GcBattlePhase::Playing;
+        state.players[0].hand.push(GcCard::gc_new_attack("atk", "打击", 1, 10));
+
+        let validate = |target: &str| gc_validate_play_card(&state, &order[0], "atk", target);
+        assert!(matches!(validate(&order[2]), Err(GcError::GcInvalidTarget)));
+        assert!(validate(&order[1]).is_ok());
+        assert!(validate(&order[3]).is_ok());
+    }
+}
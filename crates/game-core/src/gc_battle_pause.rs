@@ -0,0 +1,254 @@
+//! 对局暂停 (休闲对局双方同意的暂停与继续)
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! 任一玩家申请暂停，其他玩家同意后对局冻结: 计时停止、拒绝一切对局操作，直到继续或暂停额度用尽
+//!
+//! ## 规则
+//! - 每个房间一份暂停额度 (`budget_secs`)，每次暂停的时长从额度中扣除；额度用尽后不能再申请
+//! - 申请在 `GC_PAUSE_REQUEST_TTL_SECS` 内有效，同一时间只有一个申请；申请者本人不能同意
+//! - 暂停中任一玩家都可以继续；暂停时长达到剩余额度时自动继续
+//! - 暂停期间的时长不计入回合计时与断线托管计时 (由服务器按继续时返回的时长顺延)
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// 默认每个房间的暂停额度 (秒)
+pub const GC_PAUSE_BUDGET_SECS: u64 = 300;
+
+/// 暂停申请有效期 (秒)
+pub const GC_PAUSE_REQUEST_TTL_SECS: u64 = 30;
+
+// =============================================================================
+// 暂停状态
+// =============================================================================
+
+/// 待同意的暂停申请
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcPauseRequest {
+    /// 申请者
+    pub player_id: String,
+    /// 申请时间 (Unix 秒)
+    pub requested_at: u64,
+}
+
+/// 进行中的暂停
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcActivePause {
+    /// 申请者
+    pub requested_by: String,
+    /// 同意者
+    pub accepted_by: String,
+    /// 开始时间 (Unix 秒)
+    pub started_at: u64,
+}
+
+/// 房间的暂停状态
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcPauseState {
+    /// 剩余暂停额度 (秒，不含进行中的暂停)
+    pub budget_secs: u64,
+    /// 待同意的申请
+    #[serde(default)]
+    pub request: Option<GcPauseRequest>,
+    /// 进行中的暂停
+    #[serde(default)]
+    pub paused: Option<GcActivePause>,
+}
+
+impl Default for GcPauseState {
+    fn default() -> Self {
+        Self::gc_new(GC_PAUSE_BUDGET_SECS)
+    }
+}
+
+/// 暂停操作失败
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum GcPauseError {
+    /// 对局已暂停
+    #[error("对局已暂停")]
+    GcAlreadyPaused,
+    /// 对局未暂停
+    #[error("对局未暂停")]
+    GcNotPaused,
+    /// 暂停额度已用尽
+    #[error("暂停额度已用尽")]
+    GcBudgetExhausted,
+    /// 已有其他玩家的申请
+    #[error("已有待同意的暂停申请")]
+    GcRequestPending,
+    /// 没有待同意的申请
+    #[error("没有待同意的暂停申请")]
+    GcNoRequest,
+    /// 不能同意自己的申请
+    #[error("不能同意自己的暂停申请")]
+    GcOwnRequest,
+}
+
+/// 暂停状态摘要 (广播给客户端)
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcPauseStatus {
+    /// 是否暂停中
+    pub paused: bool,
+    /// 待同意申请的申请者
+    #[serde(default)]
+    pub requested_by: Option<String>,
+    /// 进行中暂停的申请者
+    #[serde(default)]
+    pub paused_by: Option<String>,
+    /// 剩余暂停额度 (秒，已扣除进行中的暂停)
+    pub remaining_secs: u64,
+    /// 暂停中时: 额度用尽、自动继续的时间 (Unix 秒)
+    #[serde(default)]
+    pub resumes_at: Option<u64>,
+}
+
+impl GcPauseState {
+    /// 指定额度的暂停状态
+    pub fn gc_new(budget_secs: u64) -> Self {
+        Self { budget_secs, request: None, paused: None }
+    }
+
+    /// 是否暂停中
+    pub fn gc_is_paused(&self) -> bool {
+        self.paused.is_some()
+    }
+
+    /// 剩余额度 (已扣除进行中的暂停)
+    pub fn gc_remaining_secs(&self, now: u64) -> u64 {
+        match &self.paused {
+            Some(pause) => self.budget_secs.saturating_sub(now.saturating_sub(pause.started_at)),
+            None => self.budget_secs,
+        }
+    }
+
+    /// 当前有效的申请 (已过期的不算)
+    pub fn gc_pending_request(&self, now: u64) -> Option<&GcPauseRequest> {
+        self.request.as_ref()
+            .filter(|r| now.saturating_sub(r.requested_at) < GC_PAUSE_REQUEST_TTL_SECS)
+    }
+
+    /// 申请暂停 (重复申请刷新申请时间)
+    pub fn gc_request(&mut self, player_id: &str, now: u64) -> Result<(), GcPauseError> {
+        if self.gc_is_paused() {
+            return Err(GcPauseError::GcAlreadyPaused);
+        }
+        if self.budget_secs == 0 {
+            return Err(GcPauseError::GcBudgetExhausted);
+        }
+        if self.gc_pending_request(now).is_some_and(|r| r.player_id != player_id) {
+            return Err(GcPauseError::GcRequestPending);
+        }
+        self.request = Some(GcPauseRequest { player_id: player_id.to_string(), requested_at: now });
+        Ok(())
+    }
+
+    /// 同意暂停，对局立即冻结
+    pub fn gc_accept(&mut self, player_id: &str, now: u64) -> Result<(), GcPauseError> {
+        if self.gc_is_paused() {
+            return Err(GcPauseError::GcAlreadyPaused);
+        }
+        let request = self.gc_pending_request(now).ok_or(GcPauseError::GcNoRequest)?;
+        if request.player_id == player_id {
+            return Err(GcPauseError::GcOwnRequest);
+        }
+        self.paused = Some(GcActivePause {
+            requested_by: request.player_id.clone(),
+            accepted_by: player_id.to_string(),
+            started_at: now,
+        });
+        self.request = None;
+        Ok(())
+    }
+
+    /// 拒绝或撤回申请
+    pub fn gc_decline(&mut self, now: u64) -> Result<(), GcPauseError> {
+        self.gc_pending_request(now).ok_or(GcPauseError::GcNoRequest)?;
+        self.request = None;
+        Ok(())
+    }
+
+    /// 继续对局，返回本次暂停的时长 (秒，不超过剩余额度)
+    pub fn gc_resume(&mut self, now: u64) -> Result<u64, GcPauseError> {
+        let pause = self.paused.take().ok_or(GcPauseError::GcNotPaused)?;
+        let elapsed = now.saturating_sub(pause.started_at).min(self.budget_secs);
+        self.budget_secs -= elapsed;
+        Ok(elapsed)
+    }
+
+    /// 额度用尽时自动继续，返回本次暂停的时长；同时清除过期申请
+    pub fn gc_tick(&mut self, now: u64) -> Option<u64> {
+        if self.request.is_some() && self.gc_pending_request(now).is_none() {
+            self.request = None;
+        }
+        if self.gc_is_paused() && self.gc_remaining_secs(now) == 0 {
+            return self.gc_resume(now).ok();
+        }
+        None
+    }
+
+    /// 状态摘要
+    pub fn gc_status(&self, now: u64) -> GcPauseStatus {
+        let remaining_secs = self.gc_remaining_secs(now);
+        GcPauseStatus {
+            paused: self.gc_is_paused(),
+            requested_by: self.gc_pending_request(now).map(|r| r.player_id.clone()),
+            paused_by: self.paused.as_ref().map(|p| p.requested_by.clone()),
+            remaining_secs,
+            resumes_at: self.paused.as_ref().map(|_| now + remaining_secs),
+        }
+    }
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mutual_consent_pause_and_resume() {
+        let mut pause = GcPauseState::gc_new(120);
+
+        // 申请者本人不能同意，其他玩家的申请需先处理
+        pause.gc_request("p1", 100).expect("申请");
+        assert_eq!(pause.gc_accept("p1", 101), Err(GcPauseError::GcOwnRequest));
+        assert_eq!(pause.gc_request("p2", 101), Err(GcPauseError::GcRequestPending));
+        assert_eq!(pause.gc_status(101).requested_by.as_deref(), Some("p1"));
+
+        pause.gc_accept("p2", 105).expect("同意");
+        assert!(pause.gc_is_paused());
+        assert_eq!(pause.gc_request("p2", 106), Err(GcPauseError::GcAlreadyPaused));
+        let status = pause.gc_status(125);
+        assert_eq!((status.paused_by.as_deref(), status.remaining_secs, status.resumes_at), (Some("p1"), 100, Some(225)));
+
+        // 继续后扣除暂停时长
+        assert_eq!(pause.gc_resume(145), Ok(40));
+        assert_eq!(pause.budget_secs, 80);
+        assert_eq!(pause.gc_resume(146), Err(GcPauseError::GcNotPaused));
+
+        // 过期的申请不能再同意
+        pause.gc_request("p2", 200).expect("申请");
+        assert_eq!(pause.gc_accept("p1", 200 + GC_PAUSE_REQUEST_TTL_SECS), Err(GcPauseError::GcNoRequest));
+        pause.gc_request("p1", 300).expect("过期后可重新申请");
+        pause.gc_decline(301).expect("拒绝");
+        assert!(pause.request.is_none());
+    }
+
+    #[test]
+    fn test_budget_elapses_automatically() {
+        let mut pause = GcPauseState::gc_new(60);
+        pause.gc_request("p1", 0).expect("申请");
+        pause.gc_accept("p2", 10).expect("同意");
+
+        assert_eq!(pause.gc_tick(69), None);
+        assert_eq!(pause.gc_tick(500), Some(60));
+        assert!(!pause.gc_is_paused());
+        assert_eq!(pause.budget_secs, 0);
+        assert_eq!(pause.gc_request("p1", 501), Err(GcPauseError::GcBudgetExhausted));
+    }
+}
@@ -7,9 +7,11 @@
 //! 地形由三个因素决定:
 //! 1. 大世界当前位置的地形 (基础权重)
 //! 2. 遭遇敌人的类型 (修正权重)
-//! 3. 随机因子 (最终抽取)
+//! 3. 遭遇位置的海拔与当时的天气 (修正权重)
+//! 4. 随机因子 (最终抽取)
 
 use serde::{Deserialize, Serialize};
+use crate::{gc_signed_percent_of, GcColorPalette, GcModifierBucket, GcPercentStack, GcWeather};
 
 // =============================================================================
 // 地形类型
@@ -55,18 +57,47 @@ impl GcTerrainType {
         }
     }
 
-    /// 获取地形颜色 (用于前端渲染，RGBA 格式)
-    pub fn color(&self) -> (u8, u8, u8, u8) {
+    /// 获取地形颜色 (用于前端渲染，RGBA 格式，按调色板)
+    pub fn color(&self, palette: GcColorPalette) -> (u8, u8, u8, u8) {
+        match palette {
+            GcColorPalette::Standard => match self {
+                GcTerrainType::Plain => (144, 238, 144, 255),    // 浅绿
+                GcTerrainType::Volcano => (255, 69, 0, 255),     // 红橙
+                GcTerrainType::Glacier => (173, 216, 230, 255),  // 浅蓝
+                GcTerrainType::Ocean => (0, 105, 148, 255),      // 深蓝
+                GcTerrainType::Swamp => (85, 107, 47, 255),      // 暗橄榄绿
+                GcTerrainType::Shadow => (48, 25, 52, 255),      // 暗紫
+                GcTerrainType::Holy => (255, 255, 224, 255),     // 浅黄
+                GcTerrainType::Forest => (34, 139, 34, 255),     // 森林绿
+                GcTerrainType::Mountain => (139, 137, 137, 255), // 灰色
+            },
+            GcColorPalette::Deuteranopia | GcColorPalette::Protanopia => match self {
+                GcTerrainType::Plain => (240, 228, 66, 255),     // 黄
+                GcTerrainType::Volcano if palette == GcColorPalette::Protanopia => (230, 159, 0, 255), // 橙
+                GcTerrainType::Volcano => (213, 94, 0, 255),     // 朱红
+                GcTerrainType::Glacier => (86, 180, 233, 255),   // 天蓝
+                GcTerrainType::Ocean => (0, 114, 178, 255),      // 蓝
+                GcTerrainType::Swamp => (204, 121, 167, 255),    // 红紫
+                GcTerrainType::Shadow => (40, 40, 40, 255),      // 近黑
+                GcTerrainType::Holy => (255, 255, 255, 255),     // 白
+                GcTerrainType::Forest => (0, 158, 115, 255),     // 蓝绿
+                GcTerrainType::Mountain => (128, 128, 128, 255), // 灰色
+            },
+        }
+    }
+
+    /// 地形图案 ID (不依赖颜色的纹理提示)
+    pub fn pattern(&self) -> &'static str {
         match self {
-            GcTerrainType::Plain => (144, 238, 144, 255),    // 浅绿
-            GcTerrainType::Volcano => (255, 69, 0, 255),     // 红橙
-            GcTerrainType::Glacier => (173, 216, 230, 255),  // 浅蓝
-            GcTerrainType::Ocean => (0, 105, 148, 255),      // 深蓝
-            GcTerrainType::Swamp => (85, 107, 47, 255),      // 暗橄榄绿
-            GcTerrainType::Shadow => (48, 25, 52, 255),      // 暗紫
-            GcTerrainType::Holy => (255, 255, 224, 255),     // 浅黄
-            GcTerrainType::Forest => (34, 139, 34, 255),     // 森林绿
-            GcTerrainType::Mountain => (139, 137, 137, 255), // 灰色
+            GcTerrainType::Plain => "solid",
+            GcTerrainType::Volcano => "cracks",
+            GcTerrainType::Glacier => "crosshatch",
+            GcTerrainType::Ocean => "waves",
+            GcTerrainType::Swamp => "dots",
+            GcTerrainType::Shadow => "diagonal",
+            GcTerrainType::Holy => "rays",
+            GcTerrainType::Forest => "triangles",
+            GcTerrainType::Mountain => "zigzag",
         }
     }
 
@@ -124,16 +155,42 @@ impl GcMonsterAttribute {
         }
     }
 
-    /// 获取属性颜色 (用于前端显示)
-    pub fn color(&self) -> (u8, u8, u8, u8) {
+    /// 获取属性颜色 (用于前端显示，按调色板)
+    pub fn color(&self, palette: GcColorPalette) -> (u8, u8, u8, u8) {
+        match palette {
+            GcColorPalette::Standard => match self {
+                GcMonsterAttribute::None => (128, 128, 128, 255),  // 灰色
+                GcMonsterAttribute::Fire => (255, 69, 0, 255),     // 红橙
+                GcMonsterAttribute::Water => (30, 144, 255, 255),  // 道奇蓝
+                GcMonsterAttribute::Wind => (144, 238, 144, 255),  // 浅绿
+                GcMonsterAttribute::Earth => (139, 90, 43, 255),   // 棕色
+                GcMonsterAttribute::Light => (255, 255, 0, 255),   // 黄色
+                GcMonsterAttribute::Dark => (75, 0, 130, 255),     // 靛蓝
+            },
+            GcColorPalette::Deuteranopia | GcColorPalette::Protanopia => match self {
+                GcMonsterAttribute::None => (128, 128, 128, 255),  // 灰色
+                GcMonsterAttribute::Fire if palette == GcColorPalette::Protanopia => (230, 159, 0, 255), // 橙
+                GcMonsterAttribute::Fire => (213, 94, 0, 255),     // 朱红
+                GcMonsterAttribute::Water => (0, 114, 178, 255),   // 蓝
+                GcMonsterAttribute::Wind => (86, 180, 233, 255),   // 天蓝
+                GcMonsterAttribute::Earth if palette == GcColorPalette::Protanopia => (204, 121, 167, 255), // 红紫
+                GcMonsterAttribute::Earth => (230, 159, 0, 255),   // 橙
+                GcMonsterAttribute::Light => (240, 228, 66, 255),  // 黄
+                GcMonsterAttribute::Dark => (40, 40, 40, 255),     // 近黑
+            },
+        }
+    }
+
+    /// 属性图标 ID (不依赖颜色的符号提示)
+    pub fn pattern(&self) -> &'static str {
         match self {
-            GcMonsterAttribute::None => (128, 128, 128, 255),  // 灰色
-            GcMonsterAttribute::Fire => (255, 69, 0, 255),     // 红橙
-            GcMonsterAttribute::Water => (30, 144, 255, 255),  // 道奇蓝
-            GcMonsterAttribute::Wind => (144, 238, 144, 255),  // 浅绿
-            GcMonsterAttribute::Earth => (139, 90, 43, 255),   // 棕色
-            GcMonsterAttribute::Light => (255, 255, 0, 255),   // 黄色
-            GcMonsterAttribute::Dark => (75, 0, 130, 255),     // 靛蓝
+            GcMonsterAttribute::None => "none",
+            GcMonsterAttribute::Fire => "flame",
+            GcMonsterAttribute::Water => "droplet",
+            GcMonsterAttribute::Wind => "swirl",
+            GcMonsterAttribute::Earth => "square",
+            GcMonsterAttribute::Light => "star",
+            GcMonsterAttribute::Dark => "crescent",
         }
     }
 }
@@ -157,6 +214,9 @@ pub struct GcTerrainModifier {
     pub damage_taken_percent: i32,
     /// 回复效果修正百分比
     pub healing_bonus_percent: i32,
+    /// 速度修正百分比 (影响先攻顺序)
+    #[serde(default)]
+    pub speed_percent: i32,
 }
 
 impl GcTerrainModifier {
@@ -165,21 +225,39 @@ impl GcTerrainModifier {
         Self::default()
     }
 
+    /// 攻击力修正 (地形分桶)
+    pub fn atk_stack(&self) -> GcPercentStack {
+        GcPercentStack::gc_new().gc_with(GcModifierBucket::Terrain, self.atk_percent)
+    }
+
+    /// 防御力修正 (地形分桶)
+    pub fn def_stack(&self) -> GcPercentStack {
+        GcPercentStack::gc_new().gc_with(GcModifierBucket::Terrain, self.def_percent)
+    }
+
+    /// 速度修正 (地形分桶)
+    pub fn speed_stack(&self) -> GcPercentStack {
+        GcPercentStack::gc_new().gc_with(GcModifierBucket::Terrain, self.speed_percent)
+    }
+
     /// 应用攻击力修正
     pub fn apply_atk(&self, base_atk: u32) -> u32 {
-        let modified = base_atk as i64 * (100 + self.atk_percent as i64) / 100;
-        modified.max(1) as u32
+        self.atk_stack().gc_apply(base_atk).max(1)
     }
 
     /// 应用防御力修正
     pub fn apply_def(&self, base_def: u32) -> u32 {
-        let modified = base_def as i64 * (100 + self.def_percent as i64) / 100;
-        modified.max(0) as u32
+        self.def_stack().gc_apply(base_def)
+    }
+
+    /// 应用速度修正
+    pub fn apply_speed(&self, base_speed: u32) -> u32 {
+        self.speed_stack().gc_apply(base_speed)
     }
 
     /// 计算每回合 HP 变化量
     pub fn calc_hp_change(&self, max_hp: u32) -> i32 {
-        (max_hp as i64 * self.hp_per_turn_percent as i64 / 100) as i32
+        gc_signed_percent_of(max_hp, self.hp_per_turn_percent)
     }
 }
 
@@ -226,6 +304,7 @@ pub fn gc_get_terrain_modifier(terrain: GcTerrainType, attribute: GcMonsterAttri
         // 沼泽 - 所有怪兽持续伤害
         (Swamp, _) => GcTerrainModifier {
             hp_per_turn_percent: -5,
+            speed_percent: -20,
             ..Default::default()
         },
 
@@ -262,6 +341,7 @@ pub fn gc_get_terrain_modifier(terrain: GcTerrainType, attribute: GcMonsterAttri
         },
         (Mountain, Wind) => GcTerrainModifier {
             dodge_bonus: 1000, // 10% 闪避 (万分比)
+            speed_percent: 20,
             ..Default::default()
         },
 
@@ -384,20 +464,52 @@ pub fn gc_apply_enemy_modifier(weights: &mut Vec<GcTerrainWeight>, enemy_type: G
         Boss => (Shadow, 20), // Boss 倾向暗域
     };
 
-    // 查找并增加权重，或添加新项
-    let mut found = false;
-    for w in weights.iter_mut() {
-        if w.terrain == boost_terrain {
-            w.weight += boost_amount;
-            found = true;
-            break;
+    gc_boost_terrain_weight(weights, boost_terrain, boost_amount);
+}
+
+/// 高地海拔阈值 (地图海拔图层)
+pub const GC_HIGHLAND_ELEVATION: i16 = 50;
+/// 雪线海拔阈值
+pub const GC_SNOWLINE_ELEVATION: i16 = 100;
+/// 低洼海拔阈值
+pub const GC_LOWLAND_ELEVATION: i16 = -10;
+
+/// 根据地图海拔修正地形权重 (高处偏山岳/冰原，低洼处偏海洋/沼泽)
+pub fn gc_apply_elevation_modifier(weights: &mut Vec<GcTerrainWeight>, elevation: i16) {
+    use GcTerrainType::*;
+
+    if elevation >= GC_HIGHLAND_ELEVATION {
+        gc_boost_terrain_weight(weights, Mountain, 30);
+    }
+    if elevation >= GC_SNOWLINE_ELEVATION {
+        gc_boost_terrain_weight(weights, Glacier, 20);
+    }
+    if elevation <= GC_LOWLAND_ELEVATION {
+        gc_boost_terrain_weight(weights, Ocean, 20);
+        gc_boost_terrain_weight(weights, Swamp, 20);
+    }
+}
+
+/// 根据天气修正地形权重 (雨天偏沼泽、暴风雨偏海洋、下雪偏冰原)
+pub fn gc_apply_weather_modifier(weights: &mut Vec<GcTerrainWeight>, weather: GcWeather) {
+    use GcTerrainType::*;
+
+    match weather {
+        GcWeather::Clear => {}
+        GcWeather::Rain => {
+            gc_boost_terrain_weight(weights, Swamp, 20);
+            gc_boost_terrain_weight(weights, Ocean, 10);
         }
+        GcWeather::Storm => gc_boost_terrain_weight(weights, Ocean, 30),
+        GcWeather::Snow => gc_boost_terrain_weight(weights, Glacier, 40),
     }
-    if !found {
-        weights.push(GcTerrainWeight {
-            terrain: boost_terrain,
-            weight: boost_amount,
-        });
+}
+
+/// 增加地形权重，不存在时添加新项
+fn gc_boost_terrain_weight(weights: &mut Vec<GcTerrainWeight>, terrain: GcTerrainType, amount: u32) {
+    match weights.iter_mut().find(|w| w.terrain == terrain) {
+        Some(w) => w.weight += amount,
+        None => weights.push(GcTerrainWeight { terrain, weight: amount }),
     }
 }
 
@@ -415,7 +527,8 @@ pub fn gc_select_terrain(weights: &[GcTerrainWeight], random_value: u32) -> GcTe
         return GcTerrainType::Plain;
     }
 
-    let threshold = random_value % total;
+    // 0-99 按比例映射到总权重，修正后总权重超过 100 时追加的地形也能被选中
+    let threshold = (random_value % 100) * total / 100;
     let mut accumulated = 0;
 
     for w in weights {
@@ -442,9 +555,32 @@ pub fn gc_generate_battle_terrain(
     world_terrain: GcWorldTerrainType,
     enemy_type: GcEnemyType,
     random_seed: u64,
+) -> (GcTerrainType, GcTerrainType) {
+    gc_generate_battle_terrain_at(world_terrain, enemy_type, 0, random_seed)
+}
+
+/// 生成战斗地形 (考虑遭遇位置的海拔图层)
+pub fn gc_generate_battle_terrain_at(
+    world_terrain: GcWorldTerrainType,
+    enemy_type: GcEnemyType,
+    elevation: i16,
+    random_seed: u64,
+) -> (GcTerrainType, GcTerrainType) {
+    gc_generate_battle_terrain_in(world_terrain, enemy_type, elevation, GcWeather::Clear, random_seed)
+}
+
+/// 生成战斗地形 (考虑海拔与遭遇时的天气；晴天与 `gc_generate_battle_terrain_at` 相同)
+pub fn gc_generate_battle_terrain_in(
+    world_terrain: GcWorldTerrainType,
+    enemy_type: GcEnemyType,
+    elevation: i16,
+    weather: GcWeather,
+    random_seed: u64,
 ) -> (GcTerrainType, GcTerrainType) {
     let mut weights = gc_get_base_terrain_weights(world_terrain);
     gc_apply_enemy_modifier(&mut weights, enemy_type);
+    gc_apply_elevation_modifier(&mut weights, elevation);
+    gc_apply_weather_modifier(&mut weights, weather);
 
     // 使用种子生成两个随机值
     let player_random = (random_seed % 100) as u32;
@@ -514,4 +650,27 @@ mod tests {
         assert!(volcano_weight.is_some());
         assert_eq!(volcano_weight.unwrap().weight, 30);
     }
+
+    #[test]
+    fn test_elevation_modifier() {
+        let weight_of = |weights: &[GcTerrainWeight], terrain| {
+            weights.iter().find(|w| w.terrain == terrain).map(|w| w.weight)
+        };
+        let mut flat = gc_get_base_terrain_weights(GcWorldTerrainType::Grassland);
+        gc_apply_elevation_modifier(&mut flat, 0);
+        assert_eq!(flat.len(), 3);
+
+        let mut peak = gc_get_base_terrain_weights(GcWorldTerrainType::Grassland);
+        gc_apply_elevation_modifier(&mut peak, GC_SNOWLINE_ELEVATION);
+        assert_eq!(weight_of(&peak, GcTerrainType::Mountain), Some(40));
+        assert_eq!(weight_of(&peak, GcTerrainType::Glacier), Some(20));
+
+        let mut marsh = gc_get_base_terrain_weights(GcWorldTerrainType::Grassland);
+        gc_apply_elevation_modifier(&mut marsh, -20);
+        assert_eq!(weight_of(&marsh, GcTerrainType::Swamp), Some(20));
+        assert_eq!(
+            gc_generate_battle_terrain_at(GcWorldTerrainType::Grassland, GcEnemyType::Normal, 0, 42),
+            gc_generate_battle_terrain(GcWorldTerrainType::Grassland, GcEnemyType::Normal, 42),
+        );
+    }
 }
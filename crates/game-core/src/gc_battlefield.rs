@@ -17,7 +17,7 @@ use crate::GcCard;
 // =============================================================================
 
 /// 战场配置
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct GcBattlefieldConfig {
     /// 槽位数量
     pub slot_count: usize,
@@ -74,7 +74,7 @@ pub struct GcBattlefieldCombatResult {
 // =============================================================================
 
 /// 战场槽位状态
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct GcBattlefieldSlot {
     /// 槽位索引 (0-4)
     pub index: usize,
@@ -149,7 +149,7 @@ impl GcBattlefieldSlot {
 // =============================================================================
 
 /// 玩家战场
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct GcBattlefield {
     /// 配置
     pub config: GcBattlefieldConfig,
@@ -336,21 +336,136 @@ impl GcBattlefield {
 // 战斗沙盘 (南北双方 + 地形)
 // =============================================================================
 
-use crate::{GcTerrainType, GcMonster};
+use crate::{
+    GcTerrainType, GcMonster, GcAttackOutcome, GcAttackReach, GcCombatCue, GcCompanionAura, GcConfig, GcStructure,
+    GcStructureTargetRule, GcStructureTickResult, GcStructureTurnEffect, gc_execute_monster_exchange,
+    GcInternedId, gc_intern, gc_get_terrain_modifier,
+};
+
+/// 沙盘行
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GcArenaRow {
+    /// 前排 (近战，建筑只能放在前排)
+    #[default]
+    Front,
+    /// 后排 (远程)
+    Back,
+}
+
+/// 沙盘位置 (行 + 槽位)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcArenaPosition {
+    pub row: GcArenaRow,
+    pub slot: u8,
+}
+
+impl GcArenaPosition {
+    /// 前排位置
+    pub fn front(slot: u8) -> Self {
+        Self { row: GcArenaRow::Front, slot }
+    }
+
+    /// 后排位置
+    pub fn back(slot: u8) -> Self {
+        Self { row: GcArenaRow::Back, slot }
+    }
+}
+
+/// 沙盘阵营
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GcArenaSide {
+    /// 玩家 (南方)
+    Player,
+    /// 敌人 (北方)
+    Enemy,
+}
+
+/// 先攻顺序条目
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GcInitiativeEntry {
+    /// 所属阵营
+    pub side: GcArenaSide,
+    /// 所在位置
+    pub position: GcArenaPosition,
+    /// 怪兽ID (序列化为字符串)
+    pub monster_id: GcInternedId,
+    /// 怪兽名称
+    pub name: String,
+    /// 最终速度 (含地形与光环修正)
+    pub speed: u32,
+}
+
+/// 自动战斗中的一次行动
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GcArenaCombatAction {
+    /// 行动方
+    pub side: GcArenaSide,
+    /// 攻击结果 (槽位相对于行动方)
+    pub outcome: GcAttackOutcome,
+}
+
+/// 自动战斗回合结果
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct GcArenaCombatResult {
+    /// 本回合先攻顺序 (供 UI 显示时间轴)
+    pub initiative: Vec<GcInitiativeEntry>,
+    /// 按顺序执行的行动
+    pub actions: Vec<GcArenaCombatAction>,
+    /// 回合开始时玩家方建筑效果 (target_slot = None 的炮塔伤害由调用方扣除敌方生命)
+    #[serde(default)]
+    pub player_structures: Vec<GcStructureTickResult>,
+    /// 回合开始时敌方建筑效果
+    #[serde(default)]
+    pub enemy_structures: Vec<GcStructureTickResult>,
+}
+
+/// 战斗临时缓冲区
+/// 
+/// 自动战斗每次攻击都要计算可攻击位置；多轮战斗 (模拟、AI 搜索) 复用同一个缓冲区，
+/// 攻击路径上不再为目标列表分配内存
+#[derive(Clone, Debug, Default)]
+pub struct GcCombatScratch {
+    /// 可攻击的敌方位置
+    targets: Vec<GcArenaPosition>,
+}
+
+impl GcCombatScratch {
+    /// 创建缓冲区 (预留一方全部位置的容量)
+    pub fn gc_new() -> Self {
+        Self { targets: Vec::with_capacity(10) }
+    }
+}
 
 /// 战斗沙盘 - 支持南北双方对战
+/// 
+/// 每方分前后两排，各 5 个槽位。旧版单排存档的怪兽槽位即为前排。
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct GcBattleArena {
     /// 玩家区域地形 (南方)
     pub player_terrain: GcTerrainType,
     /// 敌人区域地形 (北方)
     pub enemy_terrain: GcTerrainType,
-    /// 玩家怪兽槽位 (5个)
+    /// 玩家前排怪兽槽位 (5个)
     pub player_monsters: [Option<GcMonster>; 5],
-    /// 敌人怪兽槽位 (5个)
+    /// 敌人前排怪兽槽位 (5个)
     pub enemy_monsters: [Option<GcMonster>; 5],
     /// 本回合是否已普通召唤
     pub normal_summon_used: bool,
+    /// 玩家建筑槽位 (与怪兽共用槽位)
+    #[serde(default)]
+    pub player_structures: [Option<GcStructure>; 5],
+    /// 敌人建筑槽位 (与怪兽共用槽位)
+    #[serde(default)]
+    pub enemy_structures: [Option<GcStructure>; 5],
+    /// 攻击目标规则
+    #[serde(default)]
+    pub target_rule: GcStructureTargetRule,
+    /// 玩家后排怪兽槽位 (5个)
+    #[serde(default)]
+    pub player_back_row: [Option<GcMonster>; 5],
+    /// 敌人后排怪兽槽位 (5个)
+    #[serde(default)]
+    pub enemy_back_row: [Option<GcMonster>; 5],
 }
 
 impl Default for GcBattleArena {
@@ -361,6 +476,11 @@ impl Default for GcBattleArena {
             player_monsters: [None, None, None, None, None],
             enemy_monsters: [None, None, None, None, None],
             normal_summon_used: false,
+            player_structures: [None, None, None, None, None],
+            enemy_structures: [None, None, None, None, None],
+            target_rule: GcStructureTargetRule::default(),
+            player_back_row: [None, None, None, None, None],
+            enemy_back_row: [None, None, None, None, None],
         }
     }
 }
@@ -398,7 +518,7 @@ impl GcBattleArena {
         if slot >= 5 {
             return Err("无效槽位".to_string());
         }
-        if self.player_monsters[slot as usize].is_some() {
+        if !self.is_player_slot_free(slot) {
             return Err("槽位已占用".to_string());
         }
         monster.slot = Some(slot);
@@ -410,7 +530,7 @@ impl GcBattleArena {
     /// 回合开始 - 启用攻击
     pub fn on_turn_start(&mut self) {
         self.normal_summon_used = false;
-        for monster in self.player_monsters.iter_mut().flatten() {
+        for monster in self.player_monsters.iter_mut().chain(self.player_back_row.iter_mut()).flatten() {
             monster.can_attack = true;
         }
     }
@@ -451,25 +571,7 @@ impl GcBattleArena {
         monster_id: &str,
         slot: u8,
     ) -> Result<(), String> {
-        if slot >= 5 {
-            return Err("无效槽位".to_string());
-        }
-        
-        if self.player_monsters[slot as usize].is_some() {
-            return Err("槽位已占用".to_string());
-        }
-        
-        // 从手牌区找到怪兽
-        let idx = bench.iter()
-            .position(|m| m.id == monster_id)
-            .ok_or_else(|| "手牌区没有该怪兽".to_string())?;
-        
-        let mut monster = bench.remove(idx);
-        monster.slot = Some(slot);
-        monster.can_attack = false; // 刚部署不能攻击
-        
-        self.player_monsters[slot as usize] = Some(monster);
-        Ok(())
+        self.deploy_from_bench_to(bench, monster_id, GcArenaPosition::front(slot))
     }
     
     /// 从战场撤回怪兽到手牌区
@@ -485,20 +587,7 @@ impl GcBattleArena {
         bench: &mut Vec<GcMonster>,
         slot: u8,
     ) -> Option<GcMonster> {
-        if slot >= 5 {
-            return None;
-        }
-        
-        if let Some(mut monster) = self.player_monsters[slot as usize].take() {
-            monster.slot = None;
-            monster.can_attack = false;
-            // 恢复满血 (撤回时)
-            monster.current_hp = monster.effective_max_hp();
-            bench.push(monster.clone());
-            Some(monster)
-        } else {
-            None
-        }
+        self.recall_from(bench, GcArenaPosition::front(slot))
     }
     
     /// 战场内换位
@@ -515,8 +604,14 @@ impl GcBattleArena {
             return Ok(());
         }
         
-        // 交换槽位内容
+        // 交换槽位内容 (建筑随槽位一起交换)
         self.player_monsters.swap(slot_a as usize, slot_b as usize);
+        self.player_structures.swap(slot_a as usize, slot_b as usize);
+        for (i, structure) in self.player_structures.iter_mut().enumerate() {
+            if let Some(s) = structure {
+                s.slot = Some(i as u8);
+            }
+        }
         
         // 更新怪兽的slot字段
         if let Some(ref mut monster) = self.player_monsters[slot_a as usize] {
@@ -531,29 +626,24 @@ impl GcBattleArena {
     
     /// 获取第一个空槽位
     pub fn first_empty_slot(&self) -> Option<u8> {
-        for (i, slot) in self.player_monsters.iter().enumerate() {
-            if slot.is_none() {
-                return Some(i as u8);
-            }
-        }
-        None
+        (0..5u8).find(|&i| self.is_player_slot_free(i))
     }
     
-    /// 获取玩家怪兽数量
+    /// 获取玩家怪兽数量 (前后排)
     pub fn player_monster_count(&self) -> usize {
-        self.player_monsters.iter().filter(|m| m.is_some()).count()
+        self.player_monsters.iter().chain(self.player_back_row.iter()).filter(|m| m.is_some()).count()
     }
     
-    /// 获取敌方怪兽数量
+    /// 获取敌方怪兽数量 (前后排)
     pub fn enemy_monster_count(&self) -> usize {
-        self.enemy_monsters.iter().filter(|m| m.is_some()).count()
+        self.enemy_monsters.iter().chain(self.enemy_back_row.iter()).filter(|m| m.is_some()).count()
     }
     
     /// 战场是否已满 (考虑槽位限制)
     pub fn is_player_side_full(&self, max_slots: u8) -> bool {
         let max = (max_slots as usize).min(5);
         for i in 0..max {
-            if self.player_monsters[i].is_none() {
+            if self.is_player_slot_free(i as u8) {
                 return false;
             }
         }
@@ -583,6 +673,570 @@ impl GcBattleArena {
         }
         None
     }
+    
+    // =========================================================================
+    // 建筑
+    // =========================================================================
+    
+    /// 玩家槽位是否空闲 (无怪兽且无建筑)
+    pub fn is_player_slot_free(&self, slot: u8) -> bool {
+        let i = slot as usize;
+        i < 5 && self.player_monsters[i].is_none() && self.player_structures[i].is_none()
+    }
+    
+    /// 敌方槽位是否空闲 (无怪兽且无建筑)
+    pub fn is_enemy_slot_free(&self, slot: u8) -> bool {
+        let i = slot as usize;
+        i < 5 && self.enemy_monsters[i].is_none() && self.enemy_structures[i].is_none()
+    }
+    
+    /// 在玩家区域放置建筑
+    pub fn place_player_structure(&mut self, slot: u8, mut structure: GcStructure) -> Result<(), String> {
+        if slot >= 5 {
+            return Err("无效槽位".to_string());
+        }
+        if !self.is_player_slot_free(slot) {
+            return Err("槽位已占用".to_string());
+        }
+        structure.slot = Some(slot);
+        self.player_structures[slot as usize] = Some(structure);
+        Ok(())
+    }
+    
+    /// 在敌方区域放置建筑
+    pub fn place_enemy_structure(&mut self, slot: u8, mut structure: GcStructure) -> Result<(), String> {
+        if slot >= 5 {
+            return Err("无效槽位".to_string());
+        }
+        if !self.is_enemy_slot_free(slot) {
+            return Err("槽位已占用".to_string());
+        }
+        structure.slot = Some(slot);
+        self.enemy_structures[slot as usize] = Some(structure);
+        Ok(())
+    }
+    
+    /// 获取玩家可攻击的敌方前排槽位 (遵循目标规则)
+    pub fn enemy_targetable_slots(&self) -> Vec<u8> {
+        if self.target_rule == GcStructureTargetRule::WallsFirst {
+            let walls: Vec<u8> = (0..5u8)
+                .filter(|&i| self.enemy_structures[i as usize].as_ref().is_some_and(|s| s.is_wall()))
+                .collect();
+            if !walls.is_empty() {
+                return walls;
+            }
+        }
+        
+        // 已阵亡但尚未清理的怪兽不可作为目标
+        (0..5u8)
+            .filter(|&i| !self.is_enemy_slot_free(i))
+            .filter(|&i| self.enemy_monsters[i as usize].as_ref().is_none_or(|m| m.is_alive()))
+            .collect()
+    }
+    
+    /// 玩家前排怪兽攻击敌方前排槽位
+    /// 
+    /// # 参数
+    /// - `attacker_slot`: 玩家怪兽槽位
+    /// - `target_slot`: 敌方槽位 (None = 直接攻击，仅在敌方无可攻击目标时允许)
+    pub fn player_attack(&mut self, attacker_slot: u8, target_slot: Option<u8>) -> Result<GcAttackOutcome, String> {
+        self.player_attack_at(
+            GcArenaPosition::front(attacker_slot),
+            target_slot.map(GcArenaPosition::front),
+        )
+    }
+    
+    // =========================================================================
+    // 前后排
+    // =========================================================================
+    
+    /// 获取一方某一排的怪兽槽位
+    fn row_monsters(&self, is_player: bool, row: GcArenaRow) -> &[Option<GcMonster>; 5] {
+        match (is_player, row) {
+            (true, GcArenaRow::Front) => &self.player_monsters,
+            (true, GcArenaRow::Back) => &self.player_back_row,
+            (false, GcArenaRow::Front) => &self.enemy_monsters,
+            (false, GcArenaRow::Back) => &self.enemy_back_row,
+        }
+    }
+    
+    /// 获取一方某一排的怪兽槽位 (可变)
+    fn row_monsters_mut(&mut self, is_player: bool, row: GcArenaRow) -> &mut [Option<GcMonster>; 5] {
+        match (is_player, row) {
+            (true, GcArenaRow::Front) => &mut self.player_monsters,
+            (true, GcArenaRow::Back) => &mut self.player_back_row,
+            (false, GcArenaRow::Front) => &mut self.enemy_monsters,
+            (false, GcArenaRow::Back) => &mut self.enemy_back_row,
+        }
+    }
+    
+    /// 获取指定位置的玩家怪兽
+    pub fn player_monster_at(&self, pos: GcArenaPosition) -> Option<&GcMonster> {
+        self.row_monsters(true, pos.row).get(pos.slot as usize)?.as_ref()
+    }
+    
+    /// 获取指定位置的敌方怪兽
+    pub fn enemy_monster_at(&self, pos: GcArenaPosition) -> Option<&GcMonster> {
+        self.row_monsters(false, pos.row).get(pos.slot as usize)?.as_ref()
+    }
+    
+    /// 玩家位置是否空闲 (前排同时检查建筑)
+    pub fn is_player_position_free(&self, pos: GcArenaPosition) -> bool {
+        match pos.row {
+            GcArenaRow::Front => self.is_player_slot_free(pos.slot),
+            GcArenaRow::Back => self.player_back_row.get(pos.slot as usize).is_some_and(|m| m.is_none()),
+        }
+    }
+    
+    /// 从手牌区部署怪兽到指定位置
+    pub fn deploy_from_bench_to(
+        &mut self,
+        bench: &mut Vec<GcMonster>,
+        monster_id: &str,
+        pos: GcArenaPosition,
+    ) -> Result<(), String> {
+        if pos.slot >= 5 {
+            return Err("无效槽位".to_string());
+        }
+        
+        if !self.is_player_position_free(pos) {
+            return Err("槽位已占用".to_string());
+        }
+        
+        // 从手牌区找到怪兽
+        let idx = bench.iter()
+            .position(|m| m.id == monster_id)
+            .ok_or_else(|| "手牌区没有该怪兽".to_string())?;
+        
+        let mut monster = bench.remove(idx);
+        monster.slot = Some(pos.slot);
+        monster.can_attack = false; // 刚部署不能攻击
+        
+        self.row_monsters_mut(true, pos.row)[pos.slot as usize] = Some(monster);
+        Ok(())
+    }
+    
+    /// 从指定位置撤回怪兽到手牌区 (恢复满血)
+    pub fn recall_from(&mut self, bench: &mut Vec<GcMonster>, pos: GcArenaPosition) -> Option<GcMonster> {
+        if pos.slot >= 5 {
+            return None;
+        }
+        
+        let mut monster = self.row_monsters_mut(true, pos.row)[pos.slot as usize].take()?;
+        monster.slot = None;
+        monster.can_attack = false;
+        monster.current_hp = monster.effective_max_hp();
+        bench.push(monster.clone());
+        Some(monster)
+    }
+    
+    /// 移动玩家怪兽到另一位置 (目标有怪兽时互换)
+    /// 
+    /// 前排之间的移动等同于 `swap_positions`；建筑不能移动到后排
+    pub fn move_player_monster(&mut self, from: GcArenaPosition, to: GcArenaPosition) -> Result<(), String> {
+        if from.slot >= 5 || to.slot >= 5 {
+            return Err("无效槽位".to_string());
+        }
+        if from.row == GcArenaRow::Front && to.row == GcArenaRow::Front {
+            return self.swap_positions(from.slot, to.slot);
+        }
+        if from == to {
+            return Ok(());
+        }
+        
+        let has_structure = |pos: GcArenaPosition| {
+            pos.row == GcArenaRow::Front && self.player_structures[pos.slot as usize].is_some()
+        };
+        if has_structure(from) || has_structure(to) {
+            return Err("建筑无法移动到后排".to_string());
+        }
+        
+        let moving = self.row_monsters_mut(true, from.row)[from.slot as usize].take();
+        let displaced = self.row_monsters_mut(true, to.row)[to.slot as usize].take();
+        self.row_monsters_mut(true, from.row)[from.slot as usize] = displaced.map(|mut m| {
+            m.slot = Some(from.slot);
+            m
+        });
+        self.row_monsters_mut(true, to.row)[to.slot as usize] = moving.map(|mut m| {
+            m.slot = Some(to.slot);
+            m
+        });
+        Ok(())
+    }
+    
+    /// 获取指定攻击距离可攻击的敌方位置
+    /// 
+    /// - 城墙优先: 敌方有城墙时只能攻击城墙
+    /// - 近战: 敌方前排有单位 (怪兽或建筑) 时只能攻击前排
+    /// - 远程: 可攻击任意排
+    pub fn enemy_targetable_positions(&self, reach: GcAttackReach) -> Vec<GcArenaPosition> {
+        let mut targets = Vec::new();
+        self.collect_enemy_targets(reach, &mut targets);
+        targets
+    }
+    
+    /// 将可攻击的敌方位置写入缓冲区 (先清空，不产生中间分配)
+    fn collect_enemy_targets(&self, reach: GcAttackReach, out: &mut Vec<GcArenaPosition>) {
+        out.clear();
+        if self.enemy_walls_block() {
+            out.extend((0..5u8)
+                .filter(|&i| self.enemy_structures[i as usize].as_ref().is_some_and(|s| s.is_wall()))
+                .map(GcArenaPosition::front));
+            return;
+        }
+        
+        // 已阵亡但尚未清理的怪兽不可作为目标
+        out.extend((0..5u8)
+            .filter(|&i| !self.is_enemy_slot_free(i))
+            .filter(|&i| self.enemy_monsters[i as usize].as_ref().is_none_or(|m| m.is_alive()))
+            .map(GcArenaPosition::front));
+        if reach == GcAttackReach::Melee && !out.is_empty() {
+            return;
+        }
+        
+        out.extend((0..5u8)
+            .filter(|&i| self.enemy_back_row[i as usize].as_ref().is_some_and(|m| m.is_alive()))
+            .map(GcArenaPosition::back));
+    }
+    
+    /// 敌方城墙是否阻挡攻击
+    fn enemy_walls_block(&self) -> bool {
+        self.target_rule == GcStructureTargetRule::WallsFirst
+            && self.enemy_structures.iter().flatten().any(|s| s.is_wall())
+    }
+    
+    /// 自动选择攻击目标 (可攻击位置中ATK最低者，建筑视为 0)
+    pub fn auto_target_for(&self, attacker: GcArenaPosition) -> Option<GcArenaPosition> {
+        let reach = self.player_monster_at(attacker)?.reach;
+        self.lowest_atk_target(&self.enemy_targetable_positions(reach))
+    }
+    
+    /// 在可攻击位置中选出ATK最低者
+    fn lowest_atk_target(&self, targetable: &[GcArenaPosition]) -> Option<GcArenaPosition> {
+        targetable.iter()
+            .copied()
+            .min_by_key(|&pos| {
+                self.enemy_monster_at(pos)
+                    .map(|m| m.effective_atk(self.enemy_terrain))
+                    .unwrap_or(0)
+            })
+    }
+    
+    /// 自动攻击 (自动选择目标，无目标时直接攻击)
+    pub fn player_auto_attack(&mut self, attacker: GcArenaPosition) -> Result<GcAttackOutcome, String> {
+        self.player_auto_attack_with(attacker, &mut GcCombatScratch::default())
+    }
+    
+    /// 自动攻击 (使用复用缓冲区，可攻击位置只计算一次)
+    pub fn player_auto_attack_with(
+        &mut self,
+        attacker: GcArenaPosition,
+        scratch: &mut GcCombatScratch,
+    ) -> Result<GcAttackOutcome, String> {
+        self.collect_attacker_targets(attacker, scratch);
+        let target = self.lowest_atk_target(&scratch.targets);
+        self.attack_with_targets(attacker, target, &scratch.targets)
+    }
+    
+    /// 玩家怪兽攻击敌方位置
+    /// 
+    /// # 参数
+    /// - `attacker`: 玩家怪兽位置 (后排近战怪兽无法攻击)
+    /// - `target`: 敌方位置 (None = 直接攻击，仅在敌方无可攻击目标时允许)
+    pub fn player_attack_at(
+        &mut self,
+        attacker: GcArenaPosition,
+        target: Option<GcArenaPosition>,
+    ) -> Result<GcAttackOutcome, String> {
+        self.player_attack_at_with(attacker, target, &mut GcCombatScratch::default())
+    }
+    
+    /// 玩家怪兽攻击敌方位置 (使用复用缓冲区)
+    pub fn player_attack_at_with(
+        &mut self,
+        attacker: GcArenaPosition,
+        target: Option<GcArenaPosition>,
+        scratch: &mut GcCombatScratch,
+    ) -> Result<GcAttackOutcome, String> {
+        self.collect_attacker_targets(attacker, scratch);
+        self.attack_with_targets(attacker, target, &scratch.targets)
+    }
+    
+    /// 计算攻击者可攻击的敌方位置 (攻击位置无怪兽时为空，由攻击校验报错)
+    fn collect_attacker_targets(&self, attacker: GcArenaPosition, scratch: &mut GcCombatScratch) {
+        match self.player_monster_at(attacker) {
+            Some(monster) => self.collect_enemy_targets(monster.reach, &mut scratch.targets),
+            None => scratch.targets.clear(),
+        }
+    }
+    
+    /// 按已计算的可攻击位置执行攻击
+    fn attack_with_targets(
+        &mut self,
+        attacker: GcArenaPosition,
+        target: Option<GcArenaPosition>,
+        targetable: &[GcArenaPosition],
+    ) -> Result<GcAttackOutcome, String> {
+        if attacker.slot >= 5 {
+            return Err("无效槽位".to_string());
+        }
+        let reach = self.player_monster_at(attacker)
+            .ok_or_else(|| "攻击槽位没有怪兽".to_string())?
+            .reach;
+        if attacker.row == GcArenaRow::Back && reach == GcAttackReach::Melee {
+            return Err("近战怪兽在后排无法攻击".to_string());
+        }
+        let walls_block = self.enemy_walls_block();
+        let player_terrain = self.player_terrain;
+        let enemy_terrain = self.enemy_terrain;
+        
+        let attacker_monster = self.row_monsters_mut(true, attacker.row)[attacker.slot as usize].as_mut()
+            .ok_or_else(|| "攻击槽位没有怪兽".to_string())?;
+        if !attacker_monster.can_attack {
+            return Err("该怪兽本回合无法攻击".to_string());
+        }
+        let atk = attacker_monster.effective_atk(player_terrain);
+        let attacker_name = attacker_monster.name.clone();
+        let element = attacker_monster.damage_type;
+        
+        let target = match target {
+            None if targetable.is_empty() => {
+                attacker_monster.can_attack = false;
+                return Ok(GcAttackOutcome {
+                    attacker_slot: attacker.slot,
+                    target_slot: None,
+                    attacker_name,
+                    target_name: None,
+                    damage: atk,
+                    target_destroyed: false,
+                    attacker_destroyed: false,
+                    player_damage: atk,
+                    attacker_row: attacker.row,
+                    target_row: GcArenaRow::Front,
+                    cue: GcCombatCue::gc_new(atk, GcConfig::DEFAULT_HP, element),
+                });
+            }
+            None => return Err("敌方仍有可攻击目标".to_string()),
+            Some(t) if !targetable.contains(&t) => {
+                return Err(if walls_block { "必须先攻击城墙" } else { "近战怪兽必须先攻击前排" }.to_string());
+            }
+            Some(t) => t,
+        };
+        attacker_monster.can_attack = false;
+        let slot = target.slot as usize;
+        
+        // 攻击建筑 (建筑只在前排)
+        if target.row == GcArenaRow::Front {
+            if let Some(structure) = self.enemy_structures[slot].as_mut() {
+                let before = structure.current_hp;
+                let destroyed = structure.take_hit(atk);
+                let outcome = GcAttackOutcome {
+                    attacker_slot: attacker.slot,
+                    target_slot: Some(target.slot),
+                    attacker_name,
+                    target_name: Some(structure.name.clone()),
+                    damage: before - structure.current_hp,
+                    target_destroyed: destroyed,
+                    attacker_destroyed: false,
+                    player_damage: 0,
+                    attacker_row: attacker.row,
+                    target_row: target.row,
+                    cue: GcCombatCue::gc_new(before - structure.current_hp, structure.max_hp, element)
+                        .gc_with_lethal(destroyed),
+                };
+                if destroyed {
+                    self.enemy_structures[slot] = None;
+                }
+                return Ok(outcome);
+            }
+        }
+        
+        // 攻击怪兽 (阵亡由 gc_process_deaths 处理)
+        let defender = match target.row {
+            GcArenaRow::Front => self.enemy_monsters[slot].as_mut(),
+            GcArenaRow::Back => self.enemy_back_row[slot].as_mut(),
+        }.ok_or_else(|| "目标槽位为空".to_string())?;
+        let attacker_monster = match attacker.row {
+            GcArenaRow::Front => self.player_monsters[attacker.slot as usize].as_mut(),
+            GcArenaRow::Back => self.player_back_row[attacker.slot as usize].as_mut(),
+        }.ok_or_else(|| "攻击槽位没有怪兽".to_string())?;
+        let (damage, _) = gc_execute_monster_exchange(attacker_monster, defender, player_terrain, enemy_terrain);
+        
+        Ok(GcAttackOutcome {
+            attacker_slot: attacker.slot,
+            target_slot: Some(target.slot),
+            attacker_name,
+            target_name: Some(defender.name.clone()),
+            damage,
+            target_destroyed: !defender.is_alive(),
+            attacker_destroyed: !attacker_monster.is_alive(),
+            player_damage: 0,
+            attacker_row: attacker.row,
+            target_row: target.row,
+            cue: GcCombatCue::gc_new(damage, defender.max_hp, element).gc_with_lethal(!defender.is_alive()),
+        })
+    }
+    
+    // =========================================================================
+    // 先攻
+    // =========================================================================
+    
+    /// 计算先攻顺序
+    /// 
+    /// 速度高者先行动；同速时按槽位从小到大，同槽位时前排优先、玩家优先
+    pub fn initiative_order(&self, player_aura: Option<&GcCompanionAura>) -> Vec<GcInitiativeEntry> {
+        let mut entries = Vec::new();
+        
+        for side in [GcArenaSide::Player, GcArenaSide::Enemy] {
+            let is_player = side == GcArenaSide::Player;
+            let terrain = if is_player { self.player_terrain } else { self.enemy_terrain };
+            for row in [GcArenaRow::Front, GcArenaRow::Back] {
+                for (i, monster) in self.row_monsters(is_player, row).iter().enumerate() {
+                    let Some(m) = monster.as_ref().filter(|m| m.is_alive()) else {
+                        continue;
+                    };
+                    // 地形与光环分属不同分桶，合并后一次取整
+                    let mut stack = gc_get_terrain_modifier(terrain, m.attribute).speed_stack();
+                    if let Some(aura) = player_aura.filter(|_| is_player) {
+                        stack = stack.gc_merge(&aura.speed_stack());
+                    }
+                    let speed = stack.gc_apply(m.speed);
+                    entries.push(GcInitiativeEntry {
+                        side,
+                        position: GcArenaPosition { row, slot: i as u8 },
+                        monster_id: gc_intern(&m.id),
+                        name: m.name.clone(),
+                        speed,
+                    });
+                }
+            }
+        }
+        
+        // 稳定排序: 同速同槽位时保持 玩家前排 → 玩家后排 → 敌方前排 → 敌方后排
+        entries.sort_by_key(|e| (std::cmp::Reverse(e.speed), e.position.slot));
+        entries
+    }
+    
+    /// 执行一轮自动战斗
+    /// 
+    /// 回合开始时先触发双方建筑效果，随后双方存活怪兽按先攻顺序依次自动攻击，行动前已阵亡的怪兽跳过。
+    /// 阵亡怪兽留在槽位中，由 gc_process_deaths 统一清理；直接攻击的伤害由调用方结算。
+    pub fn run_combat_round(&mut self, player_aura: Option<&GcCompanionAura>) -> GcArenaCombatResult {
+        self.run_combat_round_with(player_aura, &mut GcCombatScratch::default())
+    }
+    
+    /// 执行一轮自动战斗 (使用复用缓冲区，多轮战斗传入同一个 scratch)
+    pub fn run_combat_round_with(
+        &mut self,
+        player_aura: Option<&GcCompanionAura>,
+        scratch: &mut GcCombatScratch,
+    ) -> GcArenaCombatResult {
+        let (player_structures, enemy_structures) = self.tick_structures();
+        let initiative = self.initiative_order(player_aura);
+        
+        for monster in self.player_monsters.iter_mut()
+            .chain(self.player_back_row.iter_mut())
+            .chain(self.enemy_monsters.iter_mut())
+            .chain(self.enemy_back_row.iter_mut())
+            .flatten()
+        {
+            monster.can_attack = true;
+        }
+        
+        let mut actions = Vec::with_capacity(initiative.len());
+        for entry in &initiative {
+            let is_player = entry.side == GcArenaSide::Player;
+            let alive = self.row_monsters(is_player, entry.position.row)[entry.position.slot as usize]
+                .as_ref()
+                .is_some_and(|m| m.is_alive());
+            if !alive {
+                continue;
+            }
+            
+            // 敌方行动时交换双方，复用玩家视角的攻击逻辑
+            if !is_player {
+                self.swap_sides();
+            }
+            let outcome = self.player_auto_attack_with(entry.position, scratch);
+            if !is_player {
+                self.swap_sides();
+            }
+            
+            if let Ok(outcome) = outcome {
+                actions.push(GcArenaCombatAction { side: entry.side, outcome });
+            }
+        }
+        
+        GcArenaCombatResult { initiative, actions, player_structures, enemy_structures }
+    }
+    
+    /// 交换南北双方
+    fn swap_sides(&mut self) {
+        std::mem::swap(&mut self.player_terrain, &mut self.enemy_terrain);
+        std::mem::swap(&mut self.player_monsters, &mut self.enemy_monsters);
+        std::mem::swap(&mut self.player_back_row, &mut self.enemy_back_row);
+        std::mem::swap(&mut self.player_structures, &mut self.enemy_structures);
+    }
+    
+    /// 触发双方建筑的每回合效果
+    /// 
+    /// 炮塔在对方无怪兽时攻击玩家本体 (target_slot = None)，由调用方扣除玩家生命
+    pub fn tick_structures(&mut self) -> (Vec<GcStructureTickResult>, Vec<GcStructureTickResult>) {
+        let player_results = Self::tick_side(
+            &self.player_structures,
+            &mut self.player_monsters,
+            &mut self.enemy_monsters,
+            self.enemy_terrain,
+        );
+        let enemy_results = Self::tick_side(
+            &self.enemy_structures,
+            &mut self.enemy_monsters,
+            &mut self.player_monsters,
+            self.player_terrain,
+        );
+        (player_results, enemy_results)
+    }
+    
+    /// 触发单方建筑效果
+    fn tick_side(
+        structures: &[Option<GcStructure>; 5],
+        allies: &mut [Option<GcMonster>; 5],
+        enemies: &mut [Option<GcMonster>; 5],
+        enemy_terrain: GcTerrainType,
+    ) -> Vec<GcStructureTickResult> {
+        let mut results = Vec::new();
+        
+        for structure in structures.iter().flatten() {
+            match structure.turn_effect {
+                GcStructureTurnEffect::None => {}
+                GcStructureTurnEffect::HealAllies(amount) => {
+                    for monster in allies.iter_mut().flatten() {
+                        monster.current_hp = monster.current_hp.saturating_add(amount).min(monster.effective_max_hp());
+                    }
+                    results.push(GcStructureTickResult {
+                        structure_id: structure.id.clone(),
+                        target_slot: None,
+                        value: amount,
+                        description: format!("{} 治疗己方全体 {} 点", structure.name, amount),
+                    });
+                }
+                GcStructureTurnEffect::DamageEnemy(amount) => {
+                    let target = crate::gc_auto_select_target(enemies, enemy_terrain);
+                    if let Some(monster) = target.and_then(|t| enemies[t as usize].as_mut()) {
+                        monster.take_damage(amount);
+                    }
+                    results.push(GcStructureTickResult {
+                        structure_id: structure.id.clone(),
+                        target_slot: target,
+                        value: amount,
+                        description: format!("{} 射击造成 {} 点伤害", structure.name, amount),
+                    });
+                }
+            }
+        }
+        
+        results
+    }
 }
 
 // =============================================================================
@@ -649,4 +1303,178 @@ mod tests {
         assert!(destroyed);
         assert!(bf.slots[0].gc_is_empty());
     }
+
+    fn create_arena_with_wall() -> GcBattleArena {
+        let mut arena = GcBattleArena::new(GcTerrainType::Plain, GcTerrainType::Plain);
+        arena.target_rule = crate::GcStructureTargetRule::WallsFirst;
+        let wall = crate::gc_get_structure_template("wooden_wall")
+            .map(|t| t.gc_create_instance("wall1"))
+            .expect("默认模板应存在");
+        arena.place_enemy_structure(0, wall).ok();
+        arena.enemy_monsters[1] = Some(GcMonster::new("e1", "哥布林", 1, crate::GcMonsterAttribute::Earth, 60, 20, 50));
+        let mut attacker = GcMonster::new("p1", "灰狼", 3, crate::GcMonsterAttribute::Earth, 90, 40, 80);
+        attacker.can_attack = true;
+        arena.player_monsters[0] = Some(attacker);
+        arena
+    }
+
+    #[test]
+    fn test_arena_walls_first() {
+        let mut arena = create_arena_with_wall();
+        assert_eq!(arena.enemy_targetable_slots(), vec![0]);
+        assert!(arena.player_attack(0, Some(1)).is_err());
+
+        let outcome = arena.player_attack(0, Some(0)).expect("应能攻击城墙");
+        assert_eq!(outcome.damage, 70);
+        assert!(!outcome.target_destroyed);
+        assert!(arena.enemy_structures[0].is_some());
+    }
+
+    #[test]
+    fn test_arena_structure_occupies_slot() {
+        let mut arena = GcBattleArena::default();
+        let totem = crate::gc_get_structure_template("healing_totem")
+            .map(|t| t.gc_create_instance("t1"))
+            .expect("默认模板应存在");
+        assert!(arena.place_player_structure(0, totem).is_ok());
+        assert_eq!(arena.first_empty_slot(), Some(1));
+
+        let monster = GcMonster::new("m1", "史莱姆", 1, crate::GcMonsterAttribute::Water, 50, 30, 60);
+        assert!(arena.summon_player_monster(0, monster).is_err());
+    }
+
+    #[test]
+    fn test_arena_tick_structures() {
+        let mut arena = create_arena_with_wall();
+        let turret = crate::gc_get_structure_template("arrow_turret")
+            .map(|t| t.gc_create_instance("tur1"))
+            .expect("默认模板应存在");
+        arena.place_player_structure(1, turret).ok();
+
+        let (player_results, _) = arena.tick_structures();
+        assert_eq!(player_results.len(), 1);
+        assert_eq!(player_results[0].target_slot, Some(1));
+        assert_eq!(arena.enemy_monsters[1].as_ref().map(|m| m.current_hp), Some(35));
+    }
+
+    #[test]
+    fn test_combat_round_ticks_structures() {
+        let mut arena = create_arena_with_wall();
+        let turret = crate::gc_get_structure_template("arrow_turret")
+            .map(|t| t.gc_create_instance("tur1"))
+            .expect("默认模板应存在");
+        arena.place_player_structure(1, turret).ok();
+
+        let result = arena.run_combat_round(None);
+        assert_eq!(result.player_structures.len(), 1);
+        assert_eq!(result.player_structures[0].target_slot, Some(1));
+        assert!(result.enemy_structures.is_empty());
+        // 灰狼优先攻击木墙，哥布林只承受炮塔伤害
+        assert_eq!(arena.enemy_monsters[1].as_ref().map(|m| m.current_hp), Some(35));
+    }
+
+    fn create_two_row_arena() -> GcBattleArena {
+        let mut arena = GcBattleArena::default();
+        arena.enemy_monsters[0] = Some(GcMonster::new("e1", "哥布林", 1, crate::GcMonsterAttribute::Earth, 60, 20, 50));
+        arena.enemy_back_row[2] = Some(GcMonster::new("e2", "火精灵", 2, crate::GcMonsterAttribute::Fire, 40, 10, 40));
+        let mut melee = GcMonster::new("m1", "灰狼", 3, crate::GcMonsterAttribute::Earth, 90, 40, 80);
+        melee.can_attack = true;
+        let mut ranged = GcMonster::new("r1", "鹰身女妖", 3, crate::GcMonsterAttribute::Wind, 70, 20, 60);
+        ranged.reach = GcAttackReach::Ranged;
+        ranged.can_attack = true;
+        arena.player_monsters[0] = Some(melee);
+        arena.player_back_row[0] = Some(ranged);
+        arena
+    }
+
+    #[test]
+    fn test_arena_melee_must_target_front_row() {
+        let mut arena = create_two_row_arena();
+        assert_eq!(arena.enemy_targetable_positions(GcAttackReach::Melee), vec![GcArenaPosition::front(0)]);
+        assert!(arena.player_attack_at(GcArenaPosition::front(0), Some(GcArenaPosition::back(2))).is_err());
+
+        // 远程可越过前排攻击后排
+        let outcome = arena.player_attack_at(GcArenaPosition::back(0), Some(GcArenaPosition::back(2)))
+            .expect("远程应能攻击后排");
+        assert_eq!(outcome.target_row, GcArenaRow::Back);
+        assert!(outcome.target_destroyed);
+    }
+
+    #[test]
+    fn test_arena_back_row_deploy_and_move() {
+        let mut arena = GcBattleArena::default();
+        let mut bench = vec![GcMonster::new("m1", "史莱姆", 1, crate::GcMonsterAttribute::Water, 50, 30, 60)];
+        assert!(arena.deploy_from_bench_to(&mut bench, "m1", GcArenaPosition::back(1)).is_ok());
+        assert_eq!(arena.player_monster_count(), 1);
+
+        // 近战在后排不能攻击
+        arena.on_turn_start();
+        assert!(arena.player_attack_at(GcArenaPosition::back(1), None).is_err());
+
+        assert!(arena.move_player_monster(GcArenaPosition::back(1), GcArenaPosition::front(3)).is_ok());
+        assert_eq!(arena.player_monster_at(GcArenaPosition::front(3)).and_then(|m| m.slot), Some(3));
+        assert!(arena.recall_from(&mut bench, GcArenaPosition::front(3)).is_some());
+        assert_eq!(bench.len(), 1);
+    }
+
+    #[test]
+    fn test_arena_single_row_json_migrates_to_front() {
+        let mut legacy = serde_json::to_value(GcBattleArena::default()).unwrap_or_default();
+        if let Some(obj) = legacy.as_object_mut() {
+            obj.remove("player_back_row");
+            obj.remove("enemy_back_row");
+        }
+        let arena: Result<GcBattleArena, _> = serde_json::from_value(legacy);
+        assert!(arena.is_ok_and(|a| a.player_back_row.iter().all(|m| m.is_none())));
+    }
+
+    #[test]
+    fn test_initiative_order_by_speed_with_slot_tiebreak() {
+        let mut arena = create_two_row_arena();
+        if let Some(m) = arena.enemy_back_row[2].as_mut() {
+            m.speed = 20;
+        }
+        let order = arena.initiative_order(None);
+        let ids: Vec<String> = order.iter().map(|e| e.monster_id.to_string()).collect();
+        // e2 最快；其余同速，按槽位 → 前排 → 玩家优先
+        assert_eq!(ids, vec!["e2", "m1", "r1", "e1"]);
+
+        let aura = GcCompanionAura { speed_percent: 50, ..Default::default() };
+        let order = arena.initiative_order(Some(&aura));
+        assert_eq!(order[1].speed, 15);
+    }
+
+    #[test]
+    fn test_terrain_modifies_speed() {
+        let mut monster = GcMonster::new("w1", "鹰身女妖", 3, crate::GcMonsterAttribute::Wind, 70, 20, 60);
+        monster.speed = 10;
+        assert_eq!(monster.effective_speed(GcTerrainType::Mountain), 12);
+        assert_eq!(monster.effective_speed(GcTerrainType::Swamp), 8);
+    }
+
+    #[test]
+    fn test_combat_round_follows_initiative() {
+        let mut arena = create_two_row_arena();
+        let result = arena.run_combat_round(None);
+        assert_eq!(result.initiative.len(), 4);
+        assert!(!result.actions.is_empty());
+        assert_eq!(result.actions[0].side, GcArenaSide::Player);
+        assert_eq!(result.actions[0].outcome.attacker_name, "灰狼");
+    }
+
+    #[test]
+    fn test_combat_round_with_reused_scratch_matches() {
+        let mut fresh = create_two_row_arena();
+        let mut reused = create_two_row_arena();
+        let mut scratch = GcCombatScratch::gc_new();
+        for _ in 0..3 {
+            let a = serde_json::to_string(&fresh.run_combat_round(None)).unwrap_or_default();
+            let b = serde_json::to_string(&reused.run_combat_round_with(None, &mut scratch)).unwrap_or_default();
+            assert_eq!(a, b);
+        }
+        assert_eq!(
+            serde_json::to_string(&fresh).unwrap_or_default(),
+            serde_json::to_string(&reused).unwrap_or_default(),
+        );
+    }
 }
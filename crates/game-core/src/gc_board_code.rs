@@ -0,0 +1,380 @@
+//! 阵容分享码 (酒馆阵容导入/导出)
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! ## 规则
+//! - 分享码只包含阵容与手牌区的模板ID、星级、金色等级与位置，不含生命等运行时数据
+//! - 二进制格式: 版本 → 模板ID表 (去重) → 单位 (位置, 模板索引, 星级<<4 | 金色等级) → 16 位校验和
+//! - 文本为 Crockford Base32 (与回放分享码同一字符表)，解码前按 `gc_normalize_share_code` 规范化
+//! - 解码只检查格式；`gc_validate` 再按怪兽池检查模板、星级与手牌区容量，客户端可仅凭分享码渲染阵容
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::gc_replay::GC_REPLAY_CODE_ALPHABET;
+use crate::{
+    gc_arena_position_label, gc_normalize_share_code, GcArenaPosition, GcArenaRow, GcBattleArena, GcMonster,
+    GcMonsterPool, GC_MAX_BENCH_CAPACITY,
+};
+
+// =============================================================================
+// 常量
+// =============================================================================
+
+/// 分享码格式版本
+pub const GC_BOARD_CODE_VERSION: u8 = 1;
+
+/// 手牌区位置编码 (0-4 前排, 5-9 后排)
+const GC_BOARD_CODE_BENCH: u8 = 10;
+
+/// 每排槽位数
+const GC_BOARD_CODE_ROW_SLOTS: u8 = 5;
+
+// =============================================================================
+// 阵容
+// =============================================================================
+
+/// 单位所在位置
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data")]
+pub enum GcBoardCodeLocation {
+    /// 阵容位置
+    Board(GcArenaPosition),
+    /// 手牌区
+    Bench,
+}
+
+impl GcBoardCodeLocation {
+    fn gc_to_byte(self) -> u8 {
+        match self {
+            Self::Board(pos) => match pos.row {
+                GcArenaRow::Front => pos.slot,
+                GcArenaRow::Back => GC_BOARD_CODE_ROW_SLOTS + pos.slot,
+            },
+            Self::Bench => GC_BOARD_CODE_BENCH,
+        }
+    }
+
+    fn gc_from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0..=4 => Some(Self::Board(GcArenaPosition::front(byte))),
+            5..=9 => Some(Self::Board(GcArenaPosition::back(byte - GC_BOARD_CODE_ROW_SLOTS))),
+            GC_BOARD_CODE_BENCH => Some(Self::Bench),
+            _ => None,
+        }
+    }
+}
+
+/// 分享码中的单位
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcBoardCodeUnit {
+    /// 模板ID
+    pub template_id: String,
+    /// 星级
+    pub star: u8,
+    /// 金色等级 (最多 15)
+    pub golden_level: u8,
+    /// 位置
+    pub location: GcBoardCodeLocation,
+}
+
+/// 可分享的阵容 (阵容 + 手牌区)
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcBoardComposition {
+    /// 单位 (阵容在前，手牌区保持原顺序)
+    pub units: Vec<GcBoardCodeUnit>,
+}
+
+/// 分享码错误
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum GcBoardCodeError {
+    /// 含有非法字符
+    #[error("分享码含有非法字符: {0}")]
+    GcInvalidCharacter(char),
+    /// 数据损坏 (截断或校验和不符)
+    #[error("分享码已损坏")]
+    GcCorrupted,
+    /// 版本不支持
+    #[error("不支持的分享码版本: {0}")]
+    GcUnsupportedVersion(u8),
+    /// 阵容过大无法编码
+    #[error("阵容过大，无法生成分享码")]
+    GcTooLarge,
+    /// 怪兽池中没有该模板
+    #[error("未知的怪兽模板: {0}")]
+    GcUnknownTemplate(String),
+    /// 星级不合法
+    #[error("{template_id} 的星级不合法: {star}")]
+    GcInvalidStar { template_id: String, star: u8 },
+    /// 位置重复
+    #[error("阵容位置重复: {0}")]
+    GcDuplicatePosition(String),
+    /// 手牌区超出上限
+    #[error("手牌区怪兽过多: {count} 只，上限 {max} 只")]
+    GcBenchTooLarge { count: usize, max: usize },
+}
+
+impl GcBoardComposition {
+    /// 由阵容与手牌区生成
+    pub fn gc_from_board(board: &GcBattleArena, bench: &[GcMonster]) -> Self {
+        let unit = |monster: &GcMonster, location| GcBoardCodeUnit {
+            template_id: monster.template_id.clone(),
+            star: monster.star,
+            golden_level: monster.golden_level,
+            location,
+        };
+        let mut units = Vec::new();
+        for (row, monsters) in [(GcArenaRow::Front, &board.player_monsters), (GcArenaRow::Back, &board.player_back_row)] {
+            for (slot, monster) in monsters.iter().enumerate() {
+                if let Some(monster) = monster {
+                    let pos = GcArenaPosition { row, slot: slot as u8 };
+                    units.push(unit(monster, GcBoardCodeLocation::Board(pos)));
+                }
+            }
+        }
+        units.extend(bench.iter().map(|m| unit(m, GcBoardCodeLocation::Bench)));
+        Self { units }
+    }
+
+    /// 编码为分享码
+    pub fn gc_encode(&self) -> Result<String, GcBoardCodeError> {
+        let mut templates: Vec<&str> = Vec::new();
+        for unit in &self.units {
+            if !templates.contains(&unit.template_id.as_str()) {
+                templates.push(&unit.template_id);
+            }
+        }
+        let count = |n: usize| u8::try_from(n).map_err(|_| GcBoardCodeError::GcTooLarge);
+
+        let mut bytes = vec![GC_BOARD_CODE_VERSION, count(templates.len())?];
+        for template_id in &templates {
+            bytes.push(count(template_id.len())?);
+            bytes.extend_from_slice(template_id.as_bytes());
+        }
+        bytes.push(count(self.units.len())?);
+        for unit in &self.units {
+            let index = templates.iter().position(|t| *t == unit.template_id).unwrap_or(0);
+            bytes.push(unit.location.gc_to_byte());
+            bytes.push(index as u8);
+            bytes.push((unit.star.min(15) << 4) | unit.golden_level.min(15));
+        }
+        bytes.extend_from_slice(&gc_board_code_checksum(&bytes).to_be_bytes());
+        Ok(gc_base32_encode(&bytes))
+    }
+
+    /// 解码分享码 (只检查格式，不检查模板是否存在)
+    pub fn gc_decode(code: &str) -> Result<Self, GcBoardCodeError> {
+        let bytes = gc_base32_decode(&gc_normalize_share_code(code))?;
+        let (payload, checksum) = bytes.split_last_chunk::<2>().ok_or(GcBoardCodeError::GcCorrupted)?;
+        if gc_board_code_checksum(payload) != u16::from_be_bytes(*checksum) {
+            return Err(GcBoardCodeError::GcCorrupted);
+        }
+
+        let mut reader = payload.iter().copied();
+        let mut next = || reader.next().ok_or(GcBoardCodeError::GcCorrupted);
+        let version = next()?;
+        if version != GC_BOARD_CODE_VERSION {
+            return Err(GcBoardCodeError::GcUnsupportedVersion(version));
+        }
+
+        let mut templates = Vec::new();
+        for _ in 0..next()? {
+            let len = next()?;
+            let raw: Vec<u8> = (0..len).map(|_| next()).collect::<Result<_, _>>()?;
+            templates.push(String::from_utf8(raw).map_err(|_| GcBoardCodeError::GcCorrupted)?);
+        }
+
+        let mut units = Vec::new();
+        for _ in 0..next()? {
+            let location = GcBoardCodeLocation::gc_from_byte(next()?).ok_or(GcBoardCodeError::GcCorrupted)?;
+            let template_id = templates.get(usize::from(next()?)).ok_or(GcBoardCodeError::GcCorrupted)?.clone();
+            let grade = next()?;
+            units.push(GcBoardCodeUnit { template_id, star: grade >> 4, golden_level: grade & 0x0f, location });
+        }
+        if next().is_ok() {
+            return Err(GcBoardCodeError::GcCorrupted);
+        }
+        Ok(Self { units })
+    }
+
+    /// 按怪兽池校验 (模板存在、星级 1-3、位置不重复、手牌区不超过上限)
+    pub fn gc_validate(&self, pool: &GcMonsterPool) -> Result<(), GcBoardCodeError> {
+        let mut occupied: Vec<GcArenaPosition> = Vec::new();
+        for unit in &self.units {
+            if pool.find_template(&unit.template_id).is_none() {
+                return Err(GcBoardCodeError::GcUnknownTemplate(unit.template_id.clone()));
+            }
+            if !(1..=3).contains(&unit.star) {
+                return Err(GcBoardCodeError::GcInvalidStar { template_id: unit.template_id.clone(), star: unit.star });
+            }
+            if let GcBoardCodeLocation::Board(pos) = unit.location {
+                if occupied.contains(&pos) {
+                    return Err(GcBoardCodeError::GcDuplicatePosition(gc_arena_position_label(pos)));
+                }
+                occupied.push(pos);
+            }
+        }
+        let bench = self.units.iter().filter(|u| u.location == GcBoardCodeLocation::Bench).count();
+        if bench > GC_MAX_BENCH_CAPACITY {
+            return Err(GcBoardCodeError::GcBenchTooLarge { count: bench, max: GC_MAX_BENCH_CAPACITY });
+        }
+        Ok(())
+    }
+
+    /// 还原为阵容与手牌区 (先校验；怪兽ID为 `code_<序号>`，满血)
+    pub fn gc_to_board(&self, pool: &GcMonsterPool) -> Result<(GcBattleArena, Vec<GcMonster>), GcBoardCodeError> {
+        self.gc_validate(pool)?;
+        let mut board = GcBattleArena::default();
+        let mut bench = Vec::new();
+        for (i, unit) in self.units.iter().enumerate() {
+            let mut monster = pool.spawn_template(&unit.template_id, &format!("code_{}", i))
+                .ok_or_else(|| GcBoardCodeError::GcUnknownTemplate(unit.template_id.clone()))?;
+            monster.star = unit.star;
+            monster.golden_level = unit.golden_level;
+            monster.current_hp = monster.effective_max_hp();
+            match unit.location {
+                GcBoardCodeLocation::Board(pos) => {
+                    monster.slot = Some(pos.slot);
+                    let row = match pos.row {
+                        GcArenaRow::Front => &mut board.player_monsters,
+                        GcArenaRow::Back => &mut board.player_back_row,
+                    };
+                    row[usize::from(pos.slot)] = Some(monster);
+                }
+                GcBoardCodeLocation::Bench => bench.push(monster),
+            }
+        }
+        Ok((board, bench))
+    }
+}
+
+// =============================================================================
+// 编码工具
+// =============================================================================
+
+/// 16 位校验和 (FNV-1a 折叠)
+fn gc_board_code_checksum(bytes: &[u8]) -> u16 {
+    let mut hash: u32 = 0x811c_9dc5;
+    for byte in bytes {
+        hash ^= u32::from(*byte);
+        hash = hash.wrapping_mul(0x0100_0193);
+    }
+    ((hash >> 16) ^ (hash & 0xffff)) as u16
+}
+
+/// 字节转 Base32 (每 5 位一个字符，末尾不足补 0)
+fn gc_base32_encode(bytes: &[u8]) -> String {
+    let mut out = String::new();
+    let (mut buffer, mut bits) = (0u32, 0u32);
+    for byte in bytes {
+        buffer = (buffer << 8) | u32::from(*byte);
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            out.push(GC_REPLAY_CODE_ALPHABET[((buffer >> bits) & 31) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        out.push(GC_REPLAY_CODE_ALPHABET[((buffer << (5 - bits)) & 31) as usize] as char);
+    }
+    out
+}
+
+/// Base32 转字节 (丢弃末尾不足 8 位的补位)
+fn gc_base32_decode(text: &str) -> Result<Vec<u8>, GcBoardCodeError> {
+    let mut out = Vec::new();
+    let (mut buffer, mut bits) = (0u32, 0u32);
+    for c in text.chars() {
+        let value = GC_REPLAY_CODE_ALPHABET.iter()
+            .position(|a| *a as char == c)
+            .ok_or(GcBoardCodeError::GcInvalidCharacter(c))?;
+        buffer = (buffer << 5) | value as u32;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((buffer >> bits) as u8);
+        }
+    }
+    Ok(out)
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(pool: &GcMonsterPool) -> (GcBattleArena, Vec<GcMonster>) {
+        let ids: Vec<String> = pool.templates().iter().take(3).map(|t| t.template_id.clone()).collect();
+        let mut board = GcBattleArena::default();
+        let mut front = pool.spawn_template(&ids[0], "a").expect("模板");
+        front.star = 3;
+        front.golden_level = 2;
+        board.player_monsters[2] = Some(front);
+        board.player_back_row[4] = pool.spawn_template(&ids[1], "b");
+        let bench = vec![pool.spawn_template(&ids[2], "c").expect("模板"), pool.spawn_template(&ids[0], "d").expect("模板")];
+        (board, bench)
+    }
+
+    #[test]
+    fn test_round_trip_and_render() {
+        let pool = GcMonsterPool::with_defaults();
+        let (board, bench) = sample(&pool);
+        let composition = GcBoardComposition::gc_from_board(&board, &bench);
+        assert_eq!(composition.units.len(), 4);
+
+        let code = composition.gc_encode().expect("编码");
+        assert!(code.chars().all(|c| GC_REPLAY_CODE_ALPHABET.contains(&(c as u8))));
+        let decoded = GcBoardComposition::gc_decode(&code.to_lowercase()).expect("解码");
+        assert_eq!(decoded, composition);
+
+        let (rendered, rendered_bench) = decoded.gc_to_board(&pool).expect("还原");
+        let front = rendered.player_monster_at(GcArenaPosition::front(2)).expect("前排");
+        assert_eq!((front.template_id.as_str(), front.star, front.golden_level), (bench[1].template_id.as_str(), 3, 2));
+        assert_eq!(front.current_hp, front.effective_max_hp());
+        assert!(rendered.player_monster_at(GcArenaPosition::back(4)).is_some());
+        assert_eq!(rendered_bench.iter().map(|m| m.template_id.clone()).collect::<Vec<_>>(),
+            bench.iter().map(|m| m.template_id.clone()).collect::<Vec<_>>());
+
+        assert_eq!(GcBoardComposition::default().gc_encode().map(|c| GcBoardComposition::gc_decode(&c)),
+            Ok(Ok(GcBoardComposition::default())));
+    }
+
+    #[test]
+    fn test_decode_rejects_corruption() {
+        let pool = GcMonsterPool::with_defaults();
+        let (board, bench) = sample(&pool);
+        let code = GcBoardComposition::gc_from_board(&board, &bench).gc_encode().expect("编码");
+
+        let mut tampered: Vec<char> = code.chars().collect();
+        tampered[4] = if tampered[4] == 'A' { 'B' } else { 'A' };
+        assert_eq!(GcBoardComposition::gc_decode(&tampered.iter().collect::<String>()), Err(GcBoardCodeError::GcCorrupted));
+        assert_eq!(GcBoardComposition::gc_decode(&code[..code.len() - 4]), Err(GcBoardCodeError::GcCorrupted));
+        assert_eq!(GcBoardComposition::gc_decode("ab!c"), Err(GcBoardCodeError::GcInvalidCharacter('!')));
+        assert_eq!(GcBoardComposition::gc_decode(""), Err(GcBoardCodeError::GcCorrupted));
+    }
+
+    #[test]
+    fn test_validate_against_pool() {
+        let pool = GcMonsterPool::with_defaults();
+        let template_id = pool.templates()[0].template_id.clone();
+        let unit = |location, star| GcBoardCodeUnit { template_id: template_id.clone(), star, golden_level: 0, location };
+        let front = GcBoardCodeLocation::Board(GcArenaPosition::front(0));
+
+        let duplicate = GcBoardComposition { units: vec![unit(front, 1), unit(front, 2)] };
+        assert_eq!(duplicate.gc_validate(&pool), Err(GcBoardCodeError::GcDuplicatePosition("f0".to_string())));
+
+        let bad_star = GcBoardComposition { units: vec![unit(front, 0)] };
+        assert!(matches!(bad_star.gc_to_board(&pool), Err(GcBoardCodeError::GcInvalidStar { star: 0, .. })));
+
+        let crowded = GcBoardComposition { units: vec![unit(GcBoardCodeLocation::Bench, 1); GC_MAX_BENCH_CAPACITY + 1] };
+        assert!(matches!(crowded.gc_validate(&pool), Err(GcBoardCodeError::GcBenchTooLarge { .. })));
+
+        let unknown = GcBoardComposition { units: vec![GcBoardCodeUnit { template_id: "ghost".to_string(), ..unit(front, 1) }] };
+        assert_eq!(unknown.gc_validate(&GcMonsterPool::with_defaults()), Err(GcBoardCodeError::GcUnknownTemplate("ghost".to_string())));
+    }
+}
@@ -9,9 +9,12 @@
 //! - 怒气系统
 //! - 复活机制 (周本)
 //! - Boss 技能
+//! - 仇恨表 (单体技能攻击仇恨最高的玩家)
 
 use serde::{Deserialize, Serialize};
 
+use crate::{GcDifficultySettings, GcThreatTable, gc_per_mille_of, gc_percent_of};
+
 // =============================================================================
 // Boss 类型与状态
 // =============================================================================
@@ -192,8 +195,8 @@ pub struct GcBoss {
     pub max_rage: u32,
     /// 当前怒气值
     pub current_rage: u32,
-    /// 每点伤害增加的怒气
-    pub rage_per_damage: f32,
+    /// 每 1000 点伤害增加的怒气 (千分比，500 = 每点伤害 0.5 怒气)
+    pub rage_per_mille: u32,
     
     // 技能
     /// 普通技能
@@ -216,6 +219,9 @@ pub struct GcBoss {
     // 目标选择
     /// 被嫁祸后优先攻击的组织 ID
     pub target_organization: Option<String>,
+    /// 仇恨表
+    #[serde(default)]
+    pub threat: GcThreatTable,
     
     // 掉落
     /// 掉落物列表
@@ -232,7 +238,7 @@ impl GcBoss {
         base_attack: u32,
         defense: u32,
         max_rage: u32,
-        rage_per_damage: f32,
+        rage_per_mille: u32,
     ) -> Self {
         Self {
             id: id.to_string(),
@@ -246,7 +252,7 @@ impl GcBoss {
             defense,
             max_rage,
             current_rage: 0,
-            rage_per_damage,
+            rage_per_mille,
             skills: Vec::new(),
             rage_skill: GcBossSkill::gc_new_rage_skill(
                 "default_rage",
@@ -260,10 +266,24 @@ impl GcBoss {
             max_revives: 0,
             attack_boost_per_revive: 0,
             target_organization: None,
+            threat: GcThreatTable::gc_new(),
             drops: Vec::new(),
         }
     }
     
+    /// 应用副本难度: 缩放生命、攻击、防御与掉落数量
+    pub fn gc_apply_difficulty(&mut self, difficulty: &GcDifficultySettings) {
+        self.max_hp = difficulty.gc_scale_enemy_stat(self.max_hp).max(1);
+        self.current_hp = difficulty.gc_scale_enemy_stat(self.current_hp).min(self.max_hp);
+        self.base_attack = difficulty.gc_scale_enemy_stat(self.base_attack);
+        self.current_attack = difficulty.gc_scale_enemy_stat(self.current_attack);
+        self.defense = difficulty.gc_scale_enemy_stat(self.defense);
+        for drop in &mut self.drops {
+            drop.min_quantity = difficulty.gc_scale_loot(drop.min_quantity);
+            drop.max_quantity = difficulty.gc_scale_loot(drop.max_quantity).max(drop.min_quantity);
+        }
+    }
+    
     /// 设置描述
     pub fn gc_with_description(mut self, description: &str) -> Self {
         self.description = description.to_string();
@@ -333,7 +353,7 @@ impl GcBoss {
         self.current_hp = self.current_hp.saturating_sub(actual_damage);
         
         // 增加怒气
-        let rage_gain = (actual_damage as f32 * self.rage_per_damage) as u32;
+        let rage_gain = gc_per_mille_of(actual_damage, self.rage_per_mille);
         self.gc_add_rage(rage_gain);
         
         // 检查是否需要复活
@@ -366,7 +386,7 @@ impl GcBoss {
         self.current_hp = self.current_hp.saturating_sub(actual_damage);
         
         // 增加怒气
-        let rage_gain = (actual_damage as f32 * self.rage_per_damage) as u32;
+        let rage_gain = gc_per_mille_of(actual_damage, self.rage_per_mille);
         self.gc_add_rage(rage_gain);
         
         // 检查死亡
@@ -395,8 +415,8 @@ impl GcBoss {
         
         // 攻击力增加 5% (每次复活累积)
         // attack_boost_per_revive 应设为 5
-        let boost = self.base_attack * self.attack_boost_per_revive / 100;
-        self.current_attack = self.base_attack + boost * self.revive_count;
+        let boost = gc_percent_of(self.base_attack, self.attack_boost_per_revive);
+        self.current_attack = self.base_attack.saturating_add(boost.saturating_mul(self.revive_count));
         
         // 进入狂怒状态
         self.state = GcBossState::Enraged;
@@ -413,8 +433,8 @@ impl GcBoss {
         self.current_hp = self.max_hp;
         
         // 增加攻击力
-        let boost = self.base_attack * self.attack_boost_per_revive / 100;
-        self.current_attack = self.base_attack + boost * self.revive_count;
+        let boost = gc_percent_of(self.base_attack, self.attack_boost_per_revive);
+        self.current_attack = self.base_attack.saturating_add(boost.saturating_mul(self.revive_count));
         
         // 进入狂怒状态
         self.state = GcBossState::Enraged;
@@ -461,11 +481,60 @@ impl GcBoss {
             .max_by_key(|s| s.cooldown)
     }
     
-    /// 回合结束处理 (减少技能冷却)
+    /// 回合结束处理 (减少技能冷却，仇恨衰减)
     pub fn gc_on_turn_end(&mut self) {
         for skill in &mut self.skills {
             skill.gc_tick();
         }
+        self.threat.gc_on_turn_end();
+    }
+    
+    // =========================================================================
+    // 仇恨
+    // =========================================================================
+    
+    /// 受到玩家伤害 (记录仇恨)
+    /// 返回 (实际伤害, 是否死亡)
+    pub fn gc_take_damage_from(&mut self, player_id: &str, damage: u32) -> (u32, bool) {
+        let (actual_damage, died) = self.gc_take_damage(damage);
+        self.threat.gc_on_damage(player_id, actual_damage);
+        (actual_damage, died)
+    }
+    
+    /// 记录玩家治疗产生的仇恨
+    pub fn gc_record_heal(&mut self, player_id: &str, amount: u32) {
+        self.threat.gc_on_heal(player_id, amount);
+    }
+    
+    /// 被玩家嘲讽
+    pub fn gc_taunt(&mut self, player_id: &str, turns: u32) {
+        self.threat.gc_taunt(player_id, turns);
+    }
+    
+    /// 规划本回合行动 (Boss AI)
+    /// 
+    /// - 单体技能: 攻击存活玩家中仇恨最高者
+    /// - 组织/全体技能: 目标为所有存活玩家 (组织过滤由调用方按阵型处理)
+    pub fn gc_plan_action(&self, alive_players: &[String]) -> Option<GcBossActionResult> {
+        let skill = self.gc_select_skill()?;
+        let is_rage_skill = self.gc_is_rage_full();
+        
+        let target_players = match skill.target_type {
+            GcSkillTargetType::Single => {
+                vec![self.threat.gc_select_target(alive_players)?.to_string()]
+            }
+            GcSkillTargetType::Organization | GcSkillTargetType::All => alive_players.to_vec(),
+        };
+        
+        Some(GcBossActionResult {
+            skill_name: skill.name.clone(),
+            skill_description: skill.description.clone(),
+            target_type: skill.target_type.clone(),
+            target_organization: self.target_organization.clone(),
+            target_players,
+            damage: skill.damage,
+            is_rage_skill,
+        })
     }
     
     /// 设置嫁祸目标
@@ -516,7 +585,7 @@ pub fn gc_create_shadow_lurker() -> GcBoss {
         25,
         10,
         100,
-        0.5,
+        500,
     );
     boss = boss.gc_with_description("在地底深处游荡的凶兽，专门伏击落单的幸存者");
     
@@ -559,7 +628,7 @@ pub fn gc_create_abyssal_titan() -> GcBoss {
         40,
         25,
         150,
-        0.3,
+        300,
     );
     boss = boss.gc_with_description("从地底深渊爬出的巨型凶兽，是毁灭地表文明的元凶之一");
     
@@ -622,6 +691,11 @@ mod tests {
         assert_eq!(boss.max_hp, 500);
         assert_eq!(boss.current_hp, 500);
         assert!(boss.gc_is_alive());
+        
+        let mut hard = gc_create_shadow_lurker();
+        hard.gc_apply_difficulty(&GcDifficultySettings::gc_new(crate::GcDifficulty::Hard, false));
+        assert_eq!((hard.max_hp, hard.current_hp), (750, 750));
+        assert_eq!(hard.current_attack, boss.current_attack * 3 / 2);
     }
     
     #[test]
@@ -681,4 +755,20 @@ mod tests {
         let skill = boss.gc_select_skill();
         assert!(skill.is_some());
     }
+    
+    #[test]
+    fn test_gc_boss_targets_highest_threat() {
+        let mut boss = gc_create_shadow_lurker();
+        boss.skills.retain(|s| s.target_type == GcSkillTargetType::Single);
+        let players = vec!["p1".to_string(), "p2".to_string()];
+        
+        boss.gc_take_damage_from("p1", 40);
+        boss.gc_take_damage_from("p2", 80);
+        let action = boss.gc_plan_action(&players).expect("应有可用技能");
+        assert_eq!(action.target_players, vec!["p2".to_string()]);
+        
+        boss.gc_taunt("p1", 2);
+        let action = boss.gc_plan_action(&players).expect("应有可用技能");
+        assert_eq!(action.target_players, vec!["p1".to_string()]);
+    }
 }
@@ -0,0 +1,328 @@
+//! 怪兽捕获系统
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! ## 捕获规则
+//! - 仅在 PvE 战斗中对敌方怪兽使用 (由调用方保证)
+//! - 成功率 = 稀有度基础概率 × 残血系数 + 道具加成
+//! - 无论成功与否，捕获道具都会被消耗
+//! - 判定: 随机数 (0-255) 按比例换算到 0-99 后与成功率比较 (`roll × 100 / 256 < 成功率`)，没有取模偏差
+//! - 捕获成功的怪兽恢复满HP，进入玩家手牌区(备战席)，同时转化为伙伴加入伙伴收藏 (同一怪兽不重复)
+
+use serde::{Deserialize, Serialize};
+
+use crate::{GcBattleArena, GcCompanion, GcMonster, GcMonsterTier, GcPlayer};
+
+// =============================================================================
+// 常量
+// =============================================================================
+
+/// 捕获成功率下限 (%)
+pub const GC_CAPTURE_MIN_CHANCE: u32 = 1;
+
+/// 捕获成功率上限 (%)，大师球除外
+pub const GC_CAPTURE_MAX_CHANCE: u32 = 95;
+
+// =============================================================================
+// 捕获道具
+// =============================================================================
+
+/// 捕获道具类型
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum GcCaptureItemType {
+    /// 普通捕获网
+    BasicNet,
+    /// 高级捕获网
+    GreatNet,
+    /// 大师捕获网 (必定成功)
+    MasterNet,
+}
+
+impl GcCaptureItemType {
+    /// 背包中的消耗品ID
+    pub fn item_id(&self) -> &'static str {
+        match self {
+            GcCaptureItemType::BasicNet => "capture_basic_net",
+            GcCaptureItemType::GreatNet => "capture_great_net",
+            GcCaptureItemType::MasterNet => "capture_master_net",
+        }
+    }
+
+    /// 道具名称
+    pub fn name(&self) -> &'static str {
+        match self {
+            GcCaptureItemType::BasicNet => "捕获网",
+            GcCaptureItemType::GreatNet => "高级捕获网",
+            GcCaptureItemType::MasterNet => "大师捕获网",
+        }
+    }
+
+    /// 成功率加成 (%)
+    pub fn bonus_percent(&self) -> u32 {
+        match self {
+            GcCaptureItemType::BasicNet => 0,
+            GcCaptureItemType::GreatNet => 15,
+            GcCaptureItemType::MasterNet => 100,
+        }
+    }
+
+    /// 是否必定成功
+    pub fn guaranteed(&self) -> bool {
+        matches!(self, GcCaptureItemType::MasterNet)
+    }
+}
+
+// =============================================================================
+// 成功率计算
+// =============================================================================
+
+/// 稀有度基础捕获率 (%)
+pub fn gc_capture_base_chance(tier: GcMonsterTier) -> u32 {
+    match tier {
+        GcMonsterTier::Tier1 => 60,
+        GcMonsterTier::Tier2 => 45,
+        GcMonsterTier::Tier3 => 30,
+        GcMonsterTier::Tier4 => 15,
+        GcMonsterTier::Tier5 => 5,
+    }
+}
+
+/// 计算捕获成功率 (%)
+///
+/// 满血时残血系数为 1.0，HP 越低系数越高，濒死时接近 2.0
+pub fn gc_capture_chance(monster: &GcMonster, tier: GcMonsterTier, item: GcCaptureItemType) -> u32 {
+    if item.guaranteed() {
+        return 100;
+    }
+
+    let max_hp = monster.effective_max_hp().max(1);
+    let hp_percent = (monster.current_hp.min(max_hp) * 100) / max_hp;
+
+    // 残血系数 (百分比): 100 ~ 200
+    let hp_factor = 200 - hp_percent;
+    let chance = gc_capture_base_chance(tier) * hp_factor / 100 + item.bonus_percent();
+
+    chance.clamp(GC_CAPTURE_MIN_CHANCE, GC_CAPTURE_MAX_CHANCE)
+}
+
+// =============================================================================
+// 捕获操作
+// =============================================================================
+
+/// 捕获结果
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GcCaptureResult {
+    /// 是否捕获成功
+    pub success: bool,
+    /// 本次成功率 (%)
+    pub chance: u32,
+    /// 是否消耗了道具
+    pub item_consumed: bool,
+    /// 捕获到的怪兽ID
+    pub captured_id: Option<String>,
+    /// 新获得的伙伴ID
+    #[serde(default)]
+    pub companion_id: Option<String>,
+    /// 错误信息 (操作无效时)
+    pub error: Option<String>,
+}
+
+impl GcCaptureResult {
+    /// 操作无效 (未消耗道具)
+    pub fn invalid(error: &str) -> Self {
+        Self {
+            success: false,
+            chance: 0,
+            item_consumed: false,
+            captured_id: None,
+            companion_id: None,
+            error: Some(error.to_string()),
+        }
+    }
+}
+
+/// 随机数是否落在成功率内 (按比例换算，各成功率的实际概率与标称值相差不到 1%)
+pub fn gc_capture_roll_succeeds(roll: u8, chance: u32) -> bool {
+    (roll as u32 * 100 / 256) < chance
+}
+
+/// 尝试捕获敌方怪兽
+///
+/// - `enemy_slot`: 敌方怪兽槽位 (0-4)
+/// - `tier`: 目标怪兽稀有度 (来自模板)
+/// - `roll`: 调用方提供的随机数 (0-255)
+pub fn gc_attempt_capture(
+    arena: &mut GcBattleArena,
+    player: &mut GcPlayer,
+    enemy_slot: u8,
+    tier: GcMonsterTier,
+    item: GcCaptureItemType,
+    roll: u8,
+) -> GcCaptureResult {
+    let idx = enemy_slot as usize;
+    if idx >= arena.enemy_monsters.len() {
+        return GcCaptureResult::invalid("无效的槽位");
+    }
+
+    let chance = match &arena.enemy_monsters[idx] {
+        Some(monster) if monster.is_alive() => gc_capture_chance(monster, tier, item),
+        Some(_) => return GcCaptureResult::invalid("目标已阵亡"),
+        None => return GcCaptureResult::invalid("该槽位没有怪兽"),
+    };
+
+    if player.gc_is_bench_full() {
+        return GcCaptureResult::invalid("手牌区已满");
+    }
+
+    let inventory = match player.inventory.as_mut() {
+        Some(inv) => inv,
+        None => return GcCaptureResult::invalid("玩家没有背包"),
+    };
+    if let Err(e) = inventory.gc_consume(item.item_id()) {
+        return GcCaptureResult::invalid(&e);
+    }
+
+    let success = gc_capture_roll_succeeds(roll, chance);
+    let mut captured_id = None;
+    let mut companion_id = None;
+
+    if success {
+        if let Some(mut monster) = arena.enemy_monsters[idx].take() {
+            monster.current_hp = monster.effective_max_hp();
+            monster.slot = None;
+            monster.can_attack = false;
+            captured_id = Some(monster.id.clone());
+            let companion = GcCompanion::from_captured(&monster);
+            let id = companion.id.clone();
+            if player.companions.add(companion) {
+                companion_id = Some(id);
+            }
+            player.gc_add_to_bench(monster);
+        }
+    }
+
+    GcCaptureResult {
+        success,
+        chance,
+        item_consumed: true,
+        captured_id,
+        companion_id,
+        error: None,
+    }
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GcInventory, GcMonsterAttribute, GcTerrainType};
+
+    fn create_enemy(hp: u32) -> GcMonster {
+        let mut m = GcMonster::new_with_template(
+            "wild_slime", "slime", "史莱姆", 1, GcMonsterAttribute::Water, 10, 5, 20,
+        );
+        m.current_hp = hp;
+        m
+    }
+
+    fn create_player(nets: u32) -> GcPlayer {
+        let mut player = GcPlayer::gc_new("p1", "玩家");
+        let mut inv = GcInventory::gc_new("p1", 10);
+        inv.gc_add_consumable(GcCaptureItemType::BasicNet.item_id(), nets);
+        player.inventory = Some(inv);
+        player
+    }
+
+    #[test]
+    fn test_capture_chance_scales_with_hp() {
+        let full = create_enemy(20);
+        let low = create_enemy(2);
+
+        let full_chance = gc_capture_chance(&full, GcMonsterTier::Tier1, GcCaptureItemType::BasicNet);
+        let low_chance = gc_capture_chance(&low, GcMonsterTier::Tier1, GcCaptureItemType::BasicNet);
+
+        assert_eq!(full_chance, 60);
+        assert!(low_chance > full_chance);
+        assert!(low_chance <= GC_CAPTURE_MAX_CHANCE);
+    }
+
+    #[test]
+    fn test_capture_chance_rarity() {
+        let m = create_enemy(20);
+        let common = gc_capture_chance(&m, GcMonsterTier::Tier1, GcCaptureItemType::BasicNet);
+        let legend = gc_capture_chance(&m, GcMonsterTier::Tier5, GcCaptureItemType::BasicNet);
+        assert!(common > legend);
+        assert_eq!(gc_capture_chance(&m, GcMonsterTier::Tier5, GcCaptureItemType::MasterNet), 100);
+    }
+
+    #[test]
+    fn test_capture_roll_distribution_matches_chance() {
+        for chance in [1, 25, 60, 95, 100] {
+            let successes = (0..=255u8).filter(|&roll| gc_capture_roll_succeeds(roll, chance)).count() as f64;
+            let actual = successes * 100.0 / 256.0;
+            assert!((actual - chance as f64).abs() < 1.0, "成功率 {}% 实际 {:.2}%", chance, actual);
+        }
+        // 60% 不再因取模偏到 68.75%
+        assert_eq!((0..=255u8).filter(|&roll| gc_capture_roll_succeeds(roll, 60)).count(), 154);
+    }
+
+    #[test]
+    fn test_capture_success_moves_to_bench() {
+        let mut arena = GcBattleArena::new(GcTerrainType::Plain, GcTerrainType::Plain);
+        arena.enemy_monsters[0] = Some(create_enemy(5));
+        let mut player = create_player(1);
+
+        let result = gc_attempt_capture(
+            &mut arena, &mut player, 0, GcMonsterTier::Tier1, GcCaptureItemType::BasicNet, 0,
+        );
+
+        assert!(result.success);
+        assert!(result.item_consumed);
+        assert!(arena.enemy_monsters[0].is_none());
+        assert_eq!(player.gc_bench_count(), 1);
+        assert_eq!(player.bench[0].current_hp, player.bench[0].effective_max_hp());
+        assert_eq!(player.inventory.as_ref().map(|i| i.gc_consumable_count("capture_basic_net")), Some(0));
+
+        // 捕获的怪兽同时成为伙伴
+        assert_eq!(result.companion_id.as_deref(), Some("companion_wild_slime"));
+        assert!(player.companions.equip("companion_wild_slime").is_ok());
+    }
+
+    #[test]
+    fn test_capture_fail_consumes_item() {
+        let mut arena = GcBattleArena::new(GcTerrainType::Plain, GcTerrainType::Plain);
+        arena.enemy_monsters[0] = Some(create_enemy(20));
+        let mut player = create_player(2);
+
+        let result = gc_attempt_capture(
+            &mut arena, &mut player, 0, GcMonsterTier::Tier5, GcCaptureItemType::BasicNet, 99,
+        );
+
+        assert!(!result.success);
+        assert!(result.item_consumed);
+        assert!(arena.enemy_monsters[0].is_some());
+        assert_eq!(player.gc_bench_count(), 0);
+        assert_eq!(player.inventory.as_ref().map(|i| i.gc_consumable_count("capture_basic_net")), Some(1));
+    }
+
+    #[test]
+    fn test_capture_without_item() {
+        let mut arena = GcBattleArena::new(GcTerrainType::Plain, GcTerrainType::Plain);
+        arena.enemy_monsters[0] = Some(create_enemy(5));
+        let mut player = create_player(0);
+
+        let result = gc_attempt_capture(
+            &mut arena, &mut player, 0, GcMonsterTier::Tier1, GcCaptureItemType::GreatNet, 0,
+        );
+
+        assert!(!result.success);
+        assert!(!result.item_consumed);
+        assert!(result.error.is_some());
+        assert!(arena.enemy_monsters[0].is_some());
+    }
+}
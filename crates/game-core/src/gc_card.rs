@@ -5,7 +5,7 @@
 //! 文档: 文档/01-game-core.md
 
 use serde::{Deserialize, Serialize};
-use crate::{GcCardId, GcCardTemplateId, GcEffect};
+use crate::{GcCardId, GcCardTemplateId, GcDamageType, GcEffect, GcLocale};
 
 // =============================================================================
 // 卡牌类型
@@ -64,8 +64,8 @@ pub enum GcTargetType {
     SingleEnemy,
     /// 所有敌人
     AllEnemies,
-    /// 自己
-    #[serde(rename = "self")]
+    /// 自己 (旧版存档为 "self")
+    #[serde(alias = "self")]
     SelfTarget,
     /// 单个友方
     SingleAlly,
@@ -81,12 +81,50 @@ impl Default for GcTargetType {
     }
 }
 
+// =============================================================================
+// 连击
+// =============================================================================
+
+/// 连击条件 (基于本回合已打出的卡牌)
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value")]
+pub enum GcComboCondition {
+    /// 本回合已打出过指定类型的卡牌
+    PlayedType(GcCardType),
+    /// 本回合已打出至少 N 张卡牌
+    PlayedCount(u32),
+}
+
+/// 连击加成 (如 "连击: 本回合已打出攻击牌时伤害 +8")
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcCardCombo {
+    /// 触发条件
+    pub condition: GcComboCondition,
+    /// 额外伤害
+    pub bonus_damage: u32,
+}
+
+impl GcCardCombo {
+    /// 创建连击加成
+    pub fn new(condition: GcComboCondition, bonus_damage: u32) -> Self {
+        Self { condition, bonus_damage }
+    }
+
+    /// 根据本回合出牌记录判断是否触发
+    pub fn gc_is_triggered(&self, played_this_turn: &[GcCardType]) -> bool {
+        match &self.condition {
+            GcComboCondition::PlayedType(card_type) => played_this_turn.contains(card_type),
+            GcComboCondition::PlayedCount(count) => played_this_turn.len() as u32 >= *count,
+        }
+    }
+}
+
 // =============================================================================
 // 卡牌
 // =============================================================================
 
 /// 卡牌实例
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct GcCard {
     /// 实例唯一 ID
     pub id: GcCardId,
@@ -118,8 +156,20 @@ pub struct GcCard {
     /// 目标类型
     pub target_type: GcTargetType,
     
+    /// 伤害类型
+    #[serde(default)]
+    pub damage_type: GcDamageType,
+    
+    /// 连击加成
+    #[serde(default)]
+    pub combo: Option<GcCardCombo>,
+    
     /// 效果列表
     pub effects: Vec<GcEffect>,
+    
+    /// 闪卡外观 (纯装饰，不影响数值；随卡组与战斗状态同步，对手可见)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub foil: bool,
 }
 
 impl GcCard {
@@ -141,7 +191,10 @@ impl GcCard {
             base_damage: damage,
             base_defense: 0,
             target_type: GcTargetType::SingleEnemy,
+            damage_type: GcDamageType::Physical,
+            combo: None,
             effects: Vec::new(),
+            foil: false,
         }
     }
     
@@ -163,7 +216,10 @@ impl GcCard {
             base_damage: 0,
             base_defense: defense,
             target_type: GcTargetType::SelfTarget,
+            damage_type: GcDamageType::Physical,
+            combo: None,
             effects: Vec::new(),
+            foil: false,
         }
     }
     
@@ -178,17 +234,34 @@ impl GcCard {
             id: id.into(),
             template_id: "heal".to_string(),
             name: name.into(),
-            description: format!("恢复 {} 点生命值", heal_amount),
+            description: "恢复 {heal} 点生命值".to_string(),
             card_type: GcCardType::Skill,
             rarity: GcCardRarity::Common,
             cost,
             base_damage: heal_amount, // 用 base_damage 存储治疗量
             base_defense: 0,
             target_type: GcTargetType::SelfTarget,
+            damage_type: GcDamageType::Physical,
+            combo: None,
             effects: Vec::new(),
+            foil: false,
         }
     }
     
+    /// 创建友方单体治疗卡牌 (组队时可以治疗队友)
+    pub fn gc_new_ally_heal(
+        id: impl Into<String>,
+        name: impl Into<String>,
+        cost: u32,
+        heal_amount: u32,
+    ) -> Self {
+        let mut card = Self::gc_new_heal(id, name, cost, heal_amount);
+        card.template_id = "ally_heal".to_string();
+        card.description = "为一名友方恢复 {heal} 点生命值".to_string();
+        card.target_type = GcTargetType::SingleAlly;
+        card
+    }
+    
     /// 创建特殊卡牌
     pub fn gc_new_special(
         id: impl Into<String>,
@@ -206,10 +279,32 @@ impl GcCard {
             base_damage: 0,
             base_defense: 0,
             target_type: GcTargetType::None,
+            damage_type: GcDamageType::Physical,
+            combo: None,
             effects: Vec::new(),
+            foil: false,
         }
     }
     
+    /// 设置连击加成
+    pub fn gc_with_combo(mut self, combo: GcCardCombo) -> Self {
+        self.combo = Some(combo);
+        self
+    }
+    
+    /// 本回合出牌记录下的连击额外伤害 (未触发为 0)
+    pub fn gc_combo_bonus(&self, played_this_turn: &[GcCardType]) -> u32 {
+        self.combo.as_ref()
+            .filter(|c| c.gc_is_triggered(played_this_turn))
+            .map(|c| c.bonus_damage)
+            .unwrap_or(0)
+    }
+    
+    /// 效果列表描述 (数值按语言格式化，效果之间用 "，" 分隔)
+    pub fn gc_effects_description(&self, locale: GcLocale) -> String {
+        self.effects.iter().map(|e| e.gc_description_in(locale)).collect::<Vec<_>>().join("，")
+    }
+    
     /// 是否需要选择目标
     pub fn gc_needs_target(&self) -> bool {
         matches!(
@@ -253,6 +348,14 @@ pub struct GcCardTemplate {
     /// 目标类型
     pub target_type: GcTargetType,
     
+    /// 伤害类型
+    #[serde(default)]
+    pub damage_type: GcDamageType,
+    
+    /// 连击加成
+    #[serde(default)]
+    pub combo: Option<GcCardCombo>,
+    
     /// 效果列表
     pub effects: Vec<GcEffect>,
 }
@@ -271,7 +374,10 @@ impl GcCardTemplate {
             base_damage: self.base_damage,
             base_defense: self.base_defense,
             target_type: self.target_type.clone(),
+            damage_type: self.damage_type,
+            combo: self.combo.clone(),
             effects: self.effects.clone(),
+            foil: false,
         }
     }
 }
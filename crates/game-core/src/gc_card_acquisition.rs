@@ -91,8 +91,11 @@ pub struct GcAcquisitionSlot {
     pub item_id: String,
     /// 物品名称
     pub name: String,
-    /// 获取费用
+    /// 获取费用 (经定价管线计算后的实际价格)
     pub cost: u32,
+    /// 调整前的基础费用 (高于 `cost` 时界面显示折扣)
+    #[serde(default)]
+    pub base_cost: u32,
     /// 是否被冻结 (酒馆模式)
     pub frozen: bool,
     /// 稀有度/星级
@@ -109,12 +112,19 @@ impl GcAcquisitionSlot {
             item_id: item_id.to_string(),
             name: name.to_string(),
             cost,
+            base_cost: cost,
             frozen: false,
             tier,
             description: None,
         }
     }
     
+    /// 设置基础费用
+    pub fn with_base_cost(mut self, base_cost: u32) -> Self {
+        self.base_cost = base_cost;
+        self
+    }
+    
     /// 设置冻结状态
     pub fn with_frozen(mut self, frozen: bool) -> Self {
         self.frozen = frozen;
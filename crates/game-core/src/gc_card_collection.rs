@@ -0,0 +1,217 @@
+//! 卡牌收藏与闪卡
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! ## 规则
+//! - 收藏按卡牌模板记录拥有张数，其中一部分可以是闪卡 (`foil_count <= count`)
+//! - 闪卡是纯装饰外观，与卡牌强度无关；用尘 (dust) 把一张普通副本制作为闪卡，价格按稀有度
+//! - 卡组的 `foils` 记录以闪卡外观出场的副本 (按模板计数)，生成卡牌实例时带上 `GcCard::foil`，
+//!   战斗状态同步给对手时可见
+//! - 收藏统计: 拥有模板数、闪卡模板数与完成度 (整数百分比，按全部卡牌模板计算)
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{gc_get_card_template, GcCardRarity, GcCardTemplateId, GcDeck, GC_CARD_TEMPLATE_IDS};
+
+// =============================================================================
+// 闪卡价格
+// =============================================================================
+
+/// 制作一张闪卡所需的尘
+pub fn gc_foil_craft_cost(rarity: &GcCardRarity) -> u32 {
+    match rarity {
+        GcCardRarity::Common => 400,
+        GcCardRarity::Rare => 800,
+        GcCardRarity::Epic => 1600,
+        GcCardRarity::Legendary => 3200,
+    }
+}
+
+// =============================================================================
+// 收藏
+// =============================================================================
+
+/// 单个模板的拥有情况
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcOwnedCard {
+    /// 拥有张数 (含闪卡)
+    pub count: u32,
+    /// 其中闪卡张数
+    #[serde(default)]
+    pub foil_count: u32,
+}
+
+/// 卡牌收藏
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcCardCollection {
+    /// 模板 ID -> 拥有情况
+    #[serde(default)]
+    pub cards: BTreeMap<GcCardTemplateId, GcOwnedCard>,
+    /// 尘
+    #[serde(default)]
+    pub dust: u32,
+}
+
+/// 收藏统计
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcCollectionStats {
+    /// 全部卡牌模板数
+    pub total_templates: u32,
+    /// 拥有的模板数
+    pub owned_templates: u32,
+    /// 拥有闪卡的模板数
+    pub foil_templates: u32,
+    /// 收藏完成度 (百分比)
+    pub owned_percent: u32,
+    /// 闪卡完成度 (百分比)
+    pub foil_percent: u32,
+}
+
+impl GcCardCollection {
+    /// 模板的拥有情况 (未拥有时为 0 张)
+    pub fn gc_owned(&self, template_id: &str) -> GcOwnedCard {
+        self.cards.get(template_id).copied().unwrap_or_default()
+    }
+
+    /// 获得卡牌 (普通外观)
+    pub fn gc_add_cards(&mut self, template_id: &str, count: u32) -> Result<(), String> {
+        if gc_get_card_template(template_id).is_none() {
+            return Err(format!("未知卡牌: {}", template_id));
+        }
+        let owned = self.cards.entry(template_id.to_string()).or_default();
+        owned.count = owned.count.saturating_add(count);
+        Ok(())
+    }
+
+    /// 把一张普通副本制作为闪卡，返回花费的尘
+    pub fn gc_craft_foil(&mut self, template_id: &str) -> Result<u32, String> {
+        let template = gc_get_card_template(template_id).ok_or_else(|| format!("未知卡牌: {}", template_id))?;
+        let owned = self.gc_owned(template_id);
+        if owned.count <= owned.foil_count {
+            return Err(format!("没有可制作为闪卡的 {}", template.name));
+        }
+        let cost = gc_foil_craft_cost(&template.rarity);
+        if self.dust < cost {
+            return Err(format!("尘不足 (需要 {}，拥有 {})", cost, self.dust));
+        }
+        self.dust -= cost;
+        if let Some(owned) = self.cards.get_mut(template_id) {
+            owned.foil_count += 1;
+        }
+        Ok(cost)
+    }
+
+    /// 校验卡组的闪卡外观不超过拥有的闪卡张数
+    pub fn gc_validate_deck_foils(&self, deck: &GcDeck) -> Result<(), String> {
+        for (template_id, foils) in gc_count_templates(&deck.foils) {
+            if foils > self.gc_owned(template_id).foil_count {
+                return Err(format!("闪卡 {} 不足 {} 张", template_id, foils));
+            }
+        }
+        Ok(())
+    }
+
+    /// 卡组中的副本尽量使用拥有的闪卡外观 (覆盖 `deck.foils`)
+    pub fn gc_apply_foils(&self, deck: &mut GcDeck) {
+        let copies = gc_count_templates(deck.cards.iter().chain(deck.sideboard.iter()));
+        deck.foils = copies.into_iter()
+            .flat_map(|(template_id, count)| {
+                let foils = count.min(self.gc_owned(template_id).foil_count);
+                std::iter::repeat_n(template_id.clone(), foils as usize)
+            })
+            .collect();
+    }
+
+    /// 收藏统计
+    pub fn gc_stats(&self) -> GcCollectionStats {
+        let total = GC_CARD_TEMPLATE_IDS.len() as u32;
+        let owned = GC_CARD_TEMPLATE_IDS.iter().filter(|id| self.gc_owned(id).count > 0).count() as u32;
+        let foil = GC_CARD_TEMPLATE_IDS.iter().filter(|id| self.gc_owned(id).foil_count > 0).count() as u32;
+        let percent = |n: u32| (n * 100).checked_div(total).unwrap_or(0);
+        GcCollectionStats {
+            total_templates: total,
+            owned_templates: owned,
+            foil_templates: foil,
+            owned_percent: percent(owned),
+            foil_percent: percent(foil),
+        }
+    }
+}
+
+/// 按模板计数
+fn gc_count_templates<'a>(ids: impl IntoIterator<Item = &'a GcCardTemplateId>) -> BTreeMap<&'a GcCardTemplateId, u32> {
+    let mut counts = BTreeMap::new();
+    for id in ids {
+        *counts.entry(id).or_insert(0) += 1;
+    }
+    counts
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collection() -> GcCardCollection {
+        let mut collection = GcCardCollection { dust: 1000, ..Default::default() };
+        collection.gc_add_cards("card_knight_attack", 2).unwrap();
+        collection.gc_add_cards("card_knight_skill", 1).unwrap();
+        collection
+    }
+
+    #[test]
+    fn test_craft_foil_costs_dust_and_needs_normal_copy() {
+        let mut collection = collection();
+        assert!(collection.gc_add_cards("missing", 1).is_err());
+
+        let rarity = gc_get_card_template("card_knight_attack").map(|c| c.rarity).unwrap();
+        let cost = collection.gc_craft_foil("card_knight_attack").unwrap();
+        assert_eq!(cost, gc_foil_craft_cost(&rarity));
+        assert_eq!(collection.dust, 1000 - cost);
+        assert_eq!(collection.gc_owned("card_knight_attack"), GcOwnedCard { count: 2, foil_count: 1 });
+
+        // 没有普通副本或尘不足时不扣尘
+        assert!(collection.gc_craft_foil("card_knight_ult").is_err());
+        collection.dust = 0;
+        assert!(collection.gc_craft_foil("card_knight_attack").is_err());
+        assert_eq!(collection.gc_owned("card_knight_attack").foil_count, 1);
+    }
+
+    #[test]
+    fn test_deck_foils_flow_into_card_instances() {
+        let mut collection = collection();
+        collection.gc_craft_foil("card_knight_attack").unwrap();
+
+        let cards = ["card_knight_attack", "card_knight_attack", "card_knight_skill"];
+        let mut deck = GcDeck::gc_new("d1", "骑士", cards.iter().map(|c| c.to_string()).collect());
+        collection.gc_apply_foils(&mut deck);
+        assert_eq!(deck.foils, vec!["card_knight_attack".to_string()]);
+        assert!(collection.gc_validate_deck_foils(&deck).is_ok());
+
+        let built = deck.gc_build_cards("p1");
+        assert_eq!(built.iter().filter(|c| c.foil).count(), 1);
+        let json = serde_json::to_string(&built).unwrap();
+        assert_eq!(json.matches("\"foil\":true").count(), 1);
+
+        deck.foils.push("card_knight_skill".to_string());
+        assert!(collection.gc_validate_deck_foils(&deck).is_err());
+    }
+
+    #[test]
+    fn test_collection_stats() {
+        let mut collection = collection();
+        collection.gc_craft_foil("card_knight_attack").unwrap();
+        let stats = collection.gc_stats();
+        let total = GC_CARD_TEMPLATE_IDS.len() as u32;
+        assert_eq!((stats.total_templates, stats.owned_templates, stats.foil_templates), (total, 2, 1));
+        assert_eq!(stats.owned_percent, 200 / total);
+        assert_eq!(stats.foil_percent, 100 / total);
+    }
+}
@@ -0,0 +1,249 @@
+//! 卡牌费用修正 (减费、加费、光环)
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! ## 费用规则
+//! - 实际费用 = 卡牌基础费用 + 所有生效修正之和，不低于 0
+//! - 修正只作用于所属玩家，可限定卡牌类型 ("下一张攻击牌费用 -1")
+//! - 出牌校验与扣除能量都使用实际费用
+//! - `NextCard` 修正在命中的卡牌打出后消耗；`ThisTurn` 在所属玩家回合结束时移除；
+//!   `Turns(n)` 每个所属玩家回合结束 -1，归零时移除；`Aura` 持续到来源被移除
+
+use serde::{Deserialize, Serialize};
+
+use crate::{GcBattleState, GcCard, GcCardType, GcError, GcPlayerId};
+
+// =============================================================================
+// 费用修正
+// =============================================================================
+
+/// 修正生效范围
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data")]
+pub enum GcCostModifierScope {
+    /// 所有卡牌
+    #[default]
+    Any,
+    /// 指定类型的卡牌
+    CardType(GcCardType),
+}
+
+impl GcCostModifierScope {
+    /// 是否作用于卡牌
+    pub fn gc_matches(&self, card: &GcCard) -> bool {
+        match self {
+            GcCostModifierScope::Any => true,
+            GcCostModifierScope::CardType(card_type) => card.card_type == *card_type,
+        }
+    }
+}
+
+/// 修正持续方式
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data")]
+pub enum GcCostModifierDuration {
+    /// 命中的下一张卡牌打出后消耗
+    NextCard,
+    /// 持续到所属玩家本回合结束
+    ThisTurn,
+    /// 持续所属玩家的若干个回合
+    Turns(u32),
+    /// 光环: 持续到来源被移除
+    Aura,
+}
+
+/// 费用修正
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcCostModifier {
+    /// 修正 ID
+    pub id: String,
+    /// 所属玩家 ID
+    pub player_id: GcPlayerId,
+    /// 来源 (卡牌、技能、战场单位 ID)
+    pub source: String,
+    /// 费用变化 (负数为减费)
+    pub delta: i32,
+    /// 生效范围
+    pub scope: GcCostModifierScope,
+    /// 持续方式
+    pub duration: GcCostModifierDuration,
+}
+
+impl GcCostModifier {
+    /// 创建费用修正 (作用于所有卡牌)
+    pub fn gc_new(
+        id: &str,
+        player_id: &str,
+        source: &str,
+        delta: i32,
+        duration: GcCostModifierDuration,
+    ) -> Self {
+        Self {
+            id: id.to_string(),
+            player_id: player_id.to_string(),
+            source: source.to_string(),
+            delta,
+            scope: GcCostModifierScope::Any,
+            duration,
+        }
+    }
+
+    /// 限定卡牌类型
+    pub fn gc_for_card_type(mut self, card_type: GcCardType) -> Self {
+        self.scope = GcCostModifierScope::CardType(card_type);
+        self
+    }
+
+    /// 是否作用于玩家的卡牌
+    pub fn gc_applies_to(&self, player_id: &str, card: &GcCard) -> bool {
+        self.player_id == player_id && self.scope.gc_matches(card)
+    }
+}
+
+/// 手牌费用视图 (供 UI 显示实时费用)
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcCardCostView {
+    /// 卡牌 ID
+    pub card_id: String,
+    /// 基础费用
+    pub base_cost: u32,
+    /// 实际费用
+    pub cost: u32,
+}
+
+// =============================================================================
+// 费用计算
+// =============================================================================
+
+/// 计算玩家打出卡牌的实际费用
+pub fn gc_effective_card_cost(state: &GcBattleState, player_id: &str, card: &GcCard) -> u32 {
+    let delta = state.cost_modifiers
+        .iter()
+        .filter(|m| m.gc_applies_to(player_id, card))
+        .fold(0i64, |sum, m| sum + m.delta as i64);
+    (card.cost as i64 + delta).clamp(0, u32::MAX as i64) as u32
+}
+
+/// 玩家手牌的实际费用
+pub fn gc_hand_costs(state: &GcBattleState, player_id: &str) -> Vec<GcCardCostView> {
+    let Some(player) = state.gc_find_player(player_id) else {
+        return Vec::new();
+    };
+    player.hand
+        .iter()
+        .map(|card| GcCardCostView {
+            card_id: card.id.clone(),
+            base_cost: card.cost,
+            cost: gc_effective_card_cost(state, player_id, card),
+        })
+        .collect()
+}
+
+// =============================================================================
+// 修正操作
+// =============================================================================
+
+/// 添加费用修正 (同 ID 的修正会被替换)
+pub fn gc_add_cost_modifier(state: &mut GcBattleState, modifier: GcCostModifier) -> Result<(), GcError> {
+    if state.gc_find_player(&modifier.player_id).is_none() {
+        return Err(GcError::GcPlayerNotFound);
+    }
+    state.cost_modifiers.retain(|m| m.id != modifier.id);
+    state.cost_modifiers.push(modifier);
+    Ok(())
+}
+
+/// 移除指定来源的全部修正 (光环来源离场时调用)，返回移除数量
+pub fn gc_remove_cost_modifiers_from(state: &mut GcBattleState, source: &str) -> usize {
+    let before = state.cost_modifiers.len();
+    state.cost_modifiers.retain(|m| m.source != source);
+    before - state.cost_modifiers.len()
+}
+
+/// 卡牌打出后消耗命中的 `NextCard` 修正
+pub fn gc_consume_cost_modifiers(state: &mut GcBattleState, player_id: &str, card: &GcCard) {
+    state.cost_modifiers.retain(|m| {
+        !(m.duration == GcCostModifierDuration::NextCard && m.gc_applies_to(player_id, card))
+    });
+}
+
+/// 所属玩家回合结束时推进修正持续时间
+pub fn gc_expire_cost_modifiers(state: &mut GcBattleState, player_id: &str) {
+    state.cost_modifiers.retain_mut(|m| {
+        if m.player_id != player_id {
+            return true;
+        }
+        match &mut m.duration {
+            GcCostModifierDuration::ThisTurn => false,
+            GcCostModifierDuration::Turns(turns) => {
+                *turns = turns.saturating_sub(1);
+                *turns > 0
+            }
+            GcCostModifierDuration::NextCard | GcCostModifierDuration::Aura => true,
+        }
+    });
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{gc_execute_play_card, gc_validate_play_card, GcPlayer};
+
+    fn create_battle() -> GcBattleState {
+        let mut p1 = GcPlayer::gc_new("p1", "玩家1");
+        p1.hand.push(GcCard::gc_new_attack("a1", "重击", 3, 10));
+        p1.hand.push(GcCard::gc_new_attack("a2", "重击", 3, 10));
+        p1.hand.push(GcCard::gc_new_defense("d1", "格挡", 2, 5));
+        p1.stats.energy = 2;
+        let p2 = GcPlayer::gc_new("p2", "玩家2");
+        GcBattleState::gc_new("b1", vec![p1, p2])
+    }
+
+    #[test]
+    fn test_next_attack_discount_is_consumed() {
+        let mut battle = create_battle();
+        assert!(gc_validate_play_card(&battle, "p1", "a1", "p2").is_err());
+
+        let discount = GcCostModifier::gc_new("m1", "p1", "战吼", -1, GcCostModifierDuration::NextCard)
+            .gc_for_card_type(GcCardType::Attack);
+        assert!(gc_add_cost_modifier(&mut battle, discount).is_ok());
+        let costs = gc_hand_costs(&battle, "p1");
+        assert_eq!(costs.iter().map(|c| c.cost).collect::<Vec<_>>(), vec![2, 2, 2]);
+        assert_eq!(costs[0].base_cost, 3);
+
+        assert!(gc_execute_play_card(&mut battle, "p1", "a1", "p2").success);
+        assert_eq!(battle.gc_find_player("p1").map(|p| p.stats.energy), Some(0));
+        assert!(battle.cost_modifiers.is_empty());
+        assert_eq!(gc_hand_costs(&battle, "p1")[0].cost, 3);
+    }
+
+    #[test]
+    fn test_modifiers_expire_with_owner_turns() {
+        let mut battle = create_battle();
+        for (id, duration) in [
+            ("turn", GcCostModifierDuration::ThisTurn),
+            ("two", GcCostModifierDuration::Turns(2)),
+            ("aura", GcCostModifierDuration::Aura),
+        ] {
+            assert!(gc_add_cost_modifier(&mut battle, GcCostModifier::gc_new(id, "p1", id, -1, duration)).is_ok());
+        }
+        let card = GcCard::gc_new_attack("x", "x", 2, 1);
+        assert_eq!(gc_effective_card_cost(&battle, "p1", &card), 0);
+        assert_eq!(gc_effective_card_cost(&battle, "p2", &card), 2);
+
+        gc_expire_cost_modifiers(&mut battle, "p2");
+        assert_eq!(battle.cost_modifiers.len(), 3);
+        gc_expire_cost_modifiers(&mut battle, "p1");
+        assert_eq!(battle.cost_modifiers.len(), 2);
+        gc_expire_cost_modifiers(&mut battle, "p1");
+        assert_eq!(battle.cost_modifiers.len(), 1);
+        assert_eq!(gc_remove_cost_modifiers_from(&mut battle, "aura"), 1);
+        assert!(gc_add_cost_modifier(&mut battle, GcCostModifier::gc_new("m", "p9", "s", 1, GcCostModifierDuration::Aura)).is_err());
+    }
+}
@@ -9,11 +9,17 @@
 //! - 玩家消耗行动力获取卡牌
 //! - 卡池自动补充
 //!
+//! ## 生命周期
+//! - 抽牌堆 → 展示区 → (刷新) 弃牌堆；抽牌堆空时弃牌堆按 `shuffle_seed` 与洗牌次数确定性地洗回
+//!   (事件溯源重放出牌时结果一致)
+//! - 放逐 (`gc_banish_card`) 的卡牌永久移出卡池，不再参与洗牌
+//! - 卡牌效果可查看抽牌堆顶 (`gc_peek_top`) 或按类型检索 (`gc_tutor`)，见 `gc_execute_pool_effects`
+//!
 //! ## 抽卡接口
 //! 实现 `GcCardAcquisition` trait，支持运行时动态切换抽卡方式
 
 use serde::{Deserialize, Serialize};
-use crate::GcCard;
+use crate::{gc_simulation_rng, GcCard, GcCardType, GcEffect, GcEffectResult, GcEffectType, GcPlayer};
 use crate::gc_card_acquisition::{
     GcCardAcquisition, GcAcquisitionContext, GcAcquisitionSlot, GcAcquisitionResult
 };
@@ -25,7 +31,7 @@ use rand::thread_rng;
 // =============================================================================
 
 /// 卡池配置
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct GcCardPoolConfig {
     /// 公共展示区大小
     pub display_size: usize,
@@ -56,7 +62,7 @@ impl Default for GcCardPoolConfig {
 // =============================================================================
 
 /// 公共卡池
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct GcCardPool {
     /// 配置
     pub config: GcCardPoolConfig,
@@ -69,6 +75,27 @@ pub struct GcCardPool {
     
     /// 弃牌堆
     pub discard_pile: Vec<GcCard>,
+    
+    /// 放逐区 (永久移出卡池)
+    #[serde(default)]
+    pub banished: Vec<GcCard>,
+    
+    /// 弃牌堆洗回时的随机种子
+    #[serde(default)]
+    pub shuffle_seed: u64,
+    
+    /// 弃牌堆已洗回的次数 (与种子一起决定洗牌结果)
+    #[serde(default)]
+    pub reshuffle_count: u32,
+}
+
+/// 卡池各区域的卡牌数量
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcCardPoolCounts {
+    pub draw_pile: usize,
+    pub display: usize,
+    pub discard_pile: usize,
+    pub banished: usize,
 }
 
 impl GcCardPool {
@@ -79,6 +106,9 @@ impl GcCardPool {
             draw_pile: Vec::new(),
             display: Vec::new(),
             discard_pile: Vec::new(),
+            banished: Vec::new(),
+            shuffle_seed: 0,
+            reshuffle_count: 0,
         }
     }
 
@@ -94,25 +124,49 @@ impl GcCardPool {
         self.gc_refill_display();
     }
 
+    /// 初始化卡池 (确定性洗牌，之后弃牌堆洗回也使用该种子)
+    pub fn gc_initialize_seeded(&mut self, seed: u64) {
+        self.shuffle_seed = seed;
+        self.reshuffle_count = 0;
+        self.draw_pile = gc_generate_card_pool(self.config.initial_pool_size);
+        self.draw_pile.shuffle(&mut gc_simulation_rng(seed));
+        self.gc_refill_display();
+    }
+
     /// 洗牌
     pub fn gc_shuffle_draw_pile(&mut self) {
         let mut rng = thread_rng();
         self.draw_pile.shuffle(&mut rng);
     }
 
+    /// 弃牌堆洗回抽牌堆 (确定性；弃牌堆为空时返回 false)
+    pub fn gc_reshuffle_discard(&mut self) -> bool {
+        if self.discard_pile.is_empty() {
+            return false;
+        }
+        self.draw_pile.append(&mut self.discard_pile);
+        let seed = self.shuffle_seed.wrapping_add(u64::from(self.reshuffle_count));
+        self.draw_pile.shuffle(&mut gc_simulation_rng(seed));
+        self.reshuffle_count += 1;
+        true
+    }
+
+    /// 从抽牌堆顶抽一张 (抽牌堆空时先洗回弃牌堆)
+    pub fn gc_draw_top(&mut self) -> Option<GcCard> {
+        if self.draw_pile.is_empty() {
+            self.gc_reshuffle_discard();
+        }
+        self.draw_pile.pop()
+    }
+
     /// 补充展示区
     pub fn gc_refill_display(&mut self) {
         while self.display.len() < self.config.display_size {
-            if let Some(card) = self.draw_pile.pop() {
-                self.display.push(card);
-            } else if !self.discard_pile.is_empty() {
-                // 弃牌堆洗入抽牌堆
-                self.draw_pile.append(&mut self.discard_pile);
-                self.gc_shuffle_draw_pile();
-            } else {
+            let Some(card) = self.gc_draw_top() else {
                 // 没有更多卡牌了
                 break;
-            }
+            };
+            self.display.push(card);
         }
     }
 
@@ -152,6 +206,51 @@ impl GcCardPool {
     pub fn gc_discard_pile_count(&self) -> usize {
         self.discard_pile.len()
     }
+
+    /// 放逐区数量
+    pub fn gc_banished_count(&self) -> usize {
+        self.banished.len()
+    }
+
+    /// 各区域数量
+    pub fn gc_counts(&self) -> GcCardPoolCounts {
+        GcCardPoolCounts {
+            draw_pile: self.draw_pile.len(),
+            display: self.display.len(),
+            discard_pile: self.discard_pile.len(),
+            banished: self.banished.len(),
+        }
+    }
+
+    /// 放逐卡牌 (依次查找展示区、抽牌堆、弃牌堆；展示区空位会补充)
+    pub fn gc_banish_card(&mut self, card_id: &str) -> Option<GcCard> {
+        let card = if let Some(index) = self.display.iter().position(|c| c.id == card_id) {
+            let card = self.display.remove(index);
+            self.gc_refill_display();
+            card
+        } else if let Some(index) = self.draw_pile.iter().position(|c| c.id == card_id) {
+            self.draw_pile.remove(index)
+        } else {
+            let index = self.discard_pile.iter().position(|c| c.id == card_id)?;
+            self.discard_pile.remove(index)
+        };
+        self.banished.push(card.clone());
+        Some(card)
+    }
+
+    /// 查看抽牌堆顶的卡牌 (从顶到底，不足时返回剩余全部；不触发洗牌)
+    pub fn gc_peek_top(&self, count: usize) -> Vec<&GcCard> {
+        self.draw_pile.iter().rev().take(count).collect()
+    }
+
+    /// 按类型检索: 从抽牌堆顶向下找第一张该类型的卡牌，没有时查找弃牌堆
+    pub fn gc_tutor(&mut self, card_type: &GcCardType) -> Option<GcCard> {
+        if let Some(index) = self.draw_pile.iter().rposition(|c| &c.card_type == card_type) {
+            return Some(self.draw_pile.remove(index));
+        }
+        let index = self.discard_pile.iter().position(|c| &c.card_type == card_type)?;
+        Some(self.discard_pile.remove(index))
+    }
 }
 
 // =============================================================================
@@ -230,6 +329,49 @@ impl GcCardAcquisition for GcCardPool {
     }
 }
 
+// =============================================================================
+// 卡池效果
+// =============================================================================
+
+/// 执行与公共卡池交互的卡牌效果 (出牌时调用，其他效果类型跳过)
+///
+/// - `PeekPool`: 查看抽牌堆顶 N 张，结果说明中列出卡名
+/// - `TutorPool`: 检索一张指定类型的卡牌加入出牌者手牌
+pub fn gc_execute_pool_effects(
+    pool: &mut GcCardPool,
+    player: &mut GcPlayer,
+    effects: &[GcEffect],
+) -> Vec<GcEffectResult> {
+    let mut results = Vec::new();
+    for effect in effects {
+        match &effect.effect_type {
+            GcEffectType::PeekPool => {
+                let top = pool.gc_peek_top(effect.value.max(0) as usize);
+                let names: Vec<&str> = top.iter().map(|c| c.name.as_str()).collect();
+                results.push(GcEffectResult::new(
+                    &effect.name,
+                    &player.id,
+                    names.len() as i32,
+                    format!("卡池顶部: {}", if names.is_empty() { "(空)".to_string() } else { names.join("、") }),
+                ));
+            }
+            GcEffectType::TutorPool(card_type) => {
+                let result = match pool.gc_tutor(card_type) {
+                    Some(card) => {
+                        let result = GcEffectResult::new(&effect.name, &player.id, 1, format!("检索到 {}", card.name));
+                        player.hand.push(card);
+                        result
+                    }
+                    None => GcEffectResult::new(&effect.name, &player.id, 0, format!("卡池中没有 {:?} 类型的卡牌", card_type)),
+                };
+                results.push(result);
+            }
+            _ => {}
+        }
+    }
+    results
+}
+
 // =============================================================================
 // 卡牌生成
 // =============================================================================
@@ -329,7 +471,6 @@ fn gc_heal_card_name(heal: usize) -> &'static str {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::GcCardType;
 
     #[test]
     fn test_gc_card_pool_new() {
@@ -371,4 +512,83 @@ mod tests {
         assert!(attack_count >= 15); // 至少 30%
         assert!(defense_count >= 10); // 至少 20%
     }
+
+    #[test]
+    fn test_gc_reshuffle_is_deterministic() {
+        let mut a = GcCardPool::gc_default();
+        a.gc_initialize_seeded(7);
+        let mut b = a.clone();
+
+        for pool in [&mut a, &mut b] {
+            // 每次刷新前清空抽牌堆，刷新时都要洗回弃牌堆
+            for _ in 0..2 {
+                pool.discard_pile.append(&mut pool.draw_pile);
+                pool.gc_refresh_display();
+            }
+        }
+        assert_eq!(a.reshuffle_count, 2);
+        let ids = |p: &GcCardPool| p.display.iter().chain(&p.draw_pile).map(|c| c.id.clone()).collect::<Vec<_>>();
+        assert_eq!(ids(&a), ids(&b));
+
+        // 总数守恒
+        let counts = a.gc_counts();
+        assert_eq!(counts.draw_pile + counts.display + counts.discard_pile, 50);
+        assert!(!GcCardPool::gc_default().gc_reshuffle_discard());
+    }
+
+    #[test]
+    fn test_gc_banish_peek_and_tutor() {
+        let mut pool = GcCardPool::gc_default();
+        pool.gc_initialize_seeded(1);
+
+        let shown = pool.display[0].id.clone();
+        assert_eq!(pool.gc_banish_card(&shown).map(|c| c.id), Some(shown.clone()));
+        assert_eq!(pool.display.len(), 5);
+        assert_eq!(pool.gc_counts(), GcCardPoolCounts { draw_pile: 44, display: 5, discard_pile: 0, banished: 1 });
+        assert!(pool.gc_banish_card(&shown).is_none());
+
+        // 放逐的卡牌不会被洗回
+        pool.gc_refresh_display();
+        pool.discard_pile.append(&mut pool.draw_pile);
+        pool.gc_reshuffle_discard();
+        assert!(pool.draw_pile.iter().all(|c| c.id != shown));
+
+        let top: Vec<String> = pool.gc_peek_top(3).iter().map(|c| c.id.clone()).collect();
+        assert_eq!(top.len(), 3);
+        assert_eq!(pool.draw_pile.last().map(|c| c.id.clone()), top.first().cloned());
+
+        let mut player = GcPlayer::gc_new("p1", "玩家1");
+        let effects = [GcEffect::gc_peek_pool(3), GcEffect::gc_tutor_pool(GcCardType::Defense), GcEffect::gc_draw(1)];
+        let results = gc_execute_pool_effects(&mut pool, &mut player, &effects);
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].actual_value, 3);
+        assert_eq!(player.hand.len(), 1);
+        assert_eq!(player.hand[0].card_type, GcCardType::Defense);
+
+        let results = gc_execute_pool_effects(&mut GcCardPool::gc_default(), &mut player, &effects[1..2]);
+        assert_eq!(results[0].actual_value, 0);
+    }
+
+    #[test]
+    fn test_gc_tutor_effect_replays_through_events() {
+        use crate::{gc_fold_battle_events, GcBattleState, GcEventSourcedBattle};
+
+        let mut p1 = GcPlayer::gc_new("p1", "玩家1");
+        let mut card = GcCard::gc_new_attack("tutor", "寻觅", 1, 5);
+        card.effects.push(GcEffect::gc_tutor_pool(GcCardType::Attack));
+        p1.hand.push(card);
+        let mut state = GcBattleState::gc_new("b", vec![p1, GcPlayer::gc_new("p2", "玩家2")]);
+        state.card_pool = GcCardPool::gc_default();
+        state.card_pool.gc_initialize_seeded(3);
+
+        let mut battle = GcEventSourcedBattle::gc_from_state(state);
+        let result = battle.gc_play_card("p1", "tutor", "p2");
+        assert!(result.success);
+        assert!(result.effects_triggered.iter().any(|e| e.effect_name == "检索" && e.actual_value == 1));
+        let hand = &battle.gc_state().players[0].hand;
+        assert_eq!(hand.len(), 1);
+
+        let replayed = gc_fold_battle_events(battle.gc_events()).unwrap();
+        assert_eq!(replayed.players[0].hand[0].id, hand[0].id);
+    }
 }
@@ -6,7 +6,19 @@
 //!
 //! 定义所有卡牌的模板数据
 
-use crate::{GcCard, GcCardType, GcCardRarity, GcTargetType, GcEffect, GcEffectType};
+use crate::{
+    GcCard, GcCardType, GcCardRarity, GcTargetType, GcEffect, GcEffectType, GcDamageType,
+    GcCardCombo, GcComboCondition,
+};
+
+/// 所有卡牌模板ID (新增模板时同步添加，内容版本校验按此列表计算)
+pub const GC_CARD_TEMPLATE_IDS: [&str; 15] = [
+    "card_knight_attack", "card_knight_skill", "card_knight_ult",
+    "card_swordsman_attack", "card_swordsman_skill", "card_swordsman_ult",
+    "card_warlock_attack", "card_warlock_skill", "card_warlock_ult",
+    "card_gunner_attack", "card_gunner_skill", "card_gunner_ult",
+    "card_assassin_attack", "card_assassin_skill", "card_assassin_ult",
+];
 
 /// 获取卡牌模板
 pub fn gc_get_card_template(template_id: &str) -> Option<GcCard> {
@@ -18,13 +30,15 @@ pub fn gc_get_card_template(template_id: &str) -> Option<GcCard> {
             id: "temp_id".to_string(), // 实际使用时会被替换
             template_id: template_id.to_string(),
             name: "盾击".to_string(),
-            description: "造成 8 点物理伤害，获得 5 点格挡".to_string(),
+            description: "造成 {damage} 点物理伤害，获得 {block} 点格挡".to_string(),
             card_type: GcCardType::Attack,
             rarity: GcCardRarity::Common,
             cost: 1,
             base_damage: 8,
             base_defense: 5,
             target_type: GcTargetType::SingleEnemy,
+            damage_type: GcDamageType::Physical,
+            combo: None,
             effects: vec![
                 GcEffect {
                     effect_type: GcEffectType::PhysicalDamage,
@@ -41,18 +55,21 @@ pub fn gc_get_card_template(template_id: &str) -> Option<GcCard> {
                     name: "Effect".to_string(),
                 },
             ],
+            foil: false,
         }),
         "card_knight_skill" => Some(GcCard {
             id: "temp_id".to_string(),
             template_id: template_id.to_string(),
             name: "嘲讽怒吼".to_string(),
-            description: "嘲讽所有敌人，获得 15 点格挡".to_string(),
+            description: "嘲讽所有敌人，获得 {block} 点格挡".to_string(),
             card_type: GcCardType::Skill,
             rarity: GcCardRarity::Rare,
             cost: 2,
             base_damage: 0,
             base_defense: 15,
             target_type: GcTargetType::AllEnemies,
+            damage_type: GcDamageType::Physical,
+            combo: None,
             effects: vec![
                 GcEffect {
                     effect_type: GcEffectType::Taunt,
@@ -69,18 +86,21 @@ pub fn gc_get_card_template(template_id: &str) -> Option<GcCard> {
                     name: "Effect".to_string(),
                 },
             ],
+            foil: false,
         }),
         "card_knight_ult" => Some(GcCard {
             id: "temp_id".to_string(),
             template_id: template_id.to_string(),
             name: "圣光守护".to_string(),
-            description: "获得 30 点格挡，所有队友获得 15 点格挡".to_string(),
+            description: "获得 {block} 点格挡，所有队友获得 {effect1} 点格挡".to_string(),
             card_type: GcCardType::Skill, // 大招也是技能牌
             rarity: GcCardRarity::Legendary,
             cost: 3,
             base_damage: 0,
             base_defense: 30,
             target_type: GcTargetType::AllAllies,
+            damage_type: GcDamageType::Holy,
+            combo: None,
             effects: vec![
                 GcEffect {
                     effect_type: GcEffectType::GainBlock,
@@ -97,6 +117,7 @@ pub fn gc_get_card_template(template_id: &str) -> Option<GcCard> {
                     name: "Effect".to_string(),
                 },
             ],
+            foil: false,
         }),
 
         // =============================================================================
@@ -106,13 +127,15 @@ pub fn gc_get_card_template(template_id: &str) -> Option<GcCard> {
             id: "temp_id".to_string(),
             template_id: template_id.to_string(),
             name: "斩击".to_string(),
-            description: "造成 12 点物理伤害".to_string(),
+            description: "造成 {damage} 点物理伤害".to_string(),
             card_type: GcCardType::Attack,
             rarity: GcCardRarity::Common,
             cost: 1,
             base_damage: 12,
             base_defense: 0,
             target_type: GcTargetType::SingleEnemy,
+            damage_type: GcDamageType::Physical,
+            combo: None,
             effects: vec![
                 GcEffect {
                     effect_type: GcEffectType::PhysicalDamage,
@@ -122,18 +145,21 @@ pub fn gc_get_card_template(template_id: &str) -> Option<GcCard> {
                     name: "Effect".to_string(),
                 },
             ],
+            foil: false,
         }),
         "card_swordsman_skill" => Some(GcCard {
             id: "temp_id".to_string(),
             template_id: template_id.to_string(),
             name: "旋风斩".to_string(),
-            description: "对所有敌人造成 8 点物理伤害".to_string(),
+            description: "对所有敌人造成 {damage} 点物理伤害".to_string(),
             card_type: GcCardType::Attack,
             rarity: GcCardRarity::Rare,
             cost: 2,
             base_damage: 8,
             base_defense: 0,
             target_type: GcTargetType::AllEnemies,
+            damage_type: GcDamageType::Physical,
+            combo: None,
             effects: vec![
                 GcEffect {
                     effect_type: GcEffectType::PhysicalDamage,
@@ -143,18 +169,21 @@ pub fn gc_get_card_template(template_id: &str) -> Option<GcCard> {
                     name: "Effect".to_string(),
                 },
             ],
+            foil: false,
         }),
         "card_swordsman_ult" => Some(GcCard {
             id: "temp_id".to_string(),
             template_id: template_id.to_string(),
             name: "破空斩".to_string(),
-            description: "造成 30 点物理伤害，无视防御".to_string(),
+            description: "造成 {damage} 点物理伤害，无视防御。连击: 本回合已打出 {combo_count} 张牌时伤害 +{combo}".to_string(),
             card_type: GcCardType::Attack,
             rarity: GcCardRarity::Legendary,
             cost: 3,
             base_damage: 30,
             base_defense: 0,
             target_type: GcTargetType::SingleEnemy,
+            damage_type: GcDamageType::Physical,
+            combo: Some(GcCardCombo::new(GcComboCondition::PlayedCount(2), 15)),
             effects: vec![
                 GcEffect {
                     effect_type: GcEffectType::PhysicalDamage,
@@ -164,6 +193,7 @@ pub fn gc_get_card_template(template_id: &str) -> Option<GcCard> {
                     name: "Effect".to_string(),
                 },
             ],
+            foil: false,
         }),
 
         // =============================================================================
@@ -173,13 +203,15 @@ pub fn gc_get_card_template(template_id: &str) -> Option<GcCard> {
             id: "temp_id".to_string(),
             template_id: template_id.to_string(),
             name: "暗影箭".to_string(),
-            description: "造成 10 点魔法伤害".to_string(),
+            description: "造成 {damage} 点魔法伤害".to_string(),
             card_type: GcCardType::Attack,
             rarity: GcCardRarity::Common,
             cost: 1,
             base_damage: 10,
             base_defense: 0,
             target_type: GcTargetType::SingleEnemy,
+            damage_type: GcDamageType::Shadow,
+            combo: None,
             effects: vec![
                 GcEffect {
                     effect_type: GcEffectType::MagicDamage,
@@ -189,18 +221,21 @@ pub fn gc_get_card_template(template_id: &str) -> Option<GcCard> {
                     name: "Effect".to_string(),
                 },
             ],
+            foil: false,
         }),
         "card_warlock_skill" => Some(GcCard {
             id: "temp_id".to_string(),
             template_id: template_id.to_string(),
             name: "腐蚀术".to_string(),
-            description: "造成 5 点伤害，施加 3 层中毒".to_string(),
+            description: "造成 {damage} 点伤害，施加 {poison} 层中毒".to_string(),
             card_type: GcCardType::Skill,
             rarity: GcCardRarity::Rare,
             cost: 1,
             base_damage: 5,
             base_defense: 0,
             target_type: GcTargetType::SingleEnemy,
+            damage_type: GcDamageType::Shadow,
+            combo: None,
             effects: vec![
                 GcEffect {
                     effect_type: GcEffectType::MagicDamage,
@@ -217,18 +252,21 @@ pub fn gc_get_card_template(template_id: &str) -> Option<GcCard> {
                     name: "Effect".to_string(),
                 },
             ],
+            foil: false,
         }),
         "card_warlock_ult" => Some(GcCard {
             id: "temp_id".to_string(),
             template_id: template_id.to_string(),
             name: "混乱之雨".to_string(),
-            description: "对所有敌人造成 20 点魔法伤害，施加 2 层虚弱".to_string(),
+            description: "对所有敌人造成 {damage} 点魔法伤害，施加 {weak} 层虚弱".to_string(),
             card_type: GcCardType::Skill,
             rarity: GcCardRarity::Legendary,
             cost: 3,
             base_damage: 20,
             base_defense: 0,
             target_type: GcTargetType::AllEnemies,
+            damage_type: GcDamageType::Shadow,
+            combo: None,
             effects: vec![
                 GcEffect {
                     effect_type: GcEffectType::MagicDamage,
@@ -245,6 +283,7 @@ pub fn gc_get_card_template(template_id: &str) -> Option<GcCard> {
                     name: "Effect".to_string(),
                 },
             ],
+            foil: false,
         }),
 
         // =============================================================================
@@ -254,13 +293,15 @@ pub fn gc_get_card_template(template_id: &str) -> Option<GcCard> {
             id: "temp_id".to_string(),
             template_id: template_id.to_string(),
             name: "射击".to_string(),
-            description: "造成 10 点物理伤害".to_string(),
+            description: "造成 {damage} 点物理伤害".to_string(),
             card_type: GcCardType::Attack,
             rarity: GcCardRarity::Common,
             cost: 1,
             base_damage: 10,
             base_defense: 0,
             target_type: GcTargetType::SingleEnemy,
+            damage_type: GcDamageType::Physical,
+            combo: None,
             effects: vec![
                 GcEffect {
                     effect_type: GcEffectType::PhysicalDamage,
@@ -270,18 +311,21 @@ pub fn gc_get_card_template(template_id: &str) -> Option<GcCard> {
                     name: "Effect".to_string(),
                 },
             ],
+            foil: false,
         }),
         "card_gunner_skill" => Some(GcCard {
             id: "temp_id".to_string(),
             template_id: template_id.to_string(),
             name: "快速射击".to_string(),
-            description: "造成 3 次 5 点物理伤害".to_string(),
+            description: "造成 {hits} 次 {damage} 点物理伤害".to_string(),
             card_type: GcCardType::Attack,
             rarity: GcCardRarity::Rare,
             cost: 2,
             base_damage: 5,
             base_defense: 0,
             target_type: GcTargetType::SingleEnemy,
+            damage_type: GcDamageType::Physical,
+            combo: None,
             effects: vec![
                 GcEffect {
                     effect_type: GcEffectType::PhysicalDamage,
@@ -305,18 +349,21 @@ pub fn gc_get_card_template(template_id: &str) -> Option<GcCard> {
                     name: "Effect".to_string(),
                 },
             ],
+            foil: false,
         }),
         "card_gunner_ult" => Some(GcCard {
             id: "temp_id".to_string(),
             template_id: template_id.to_string(),
             name: "爆头".to_string(),
-            description: "造成 40 点物理伤害，必定暴击".to_string(),
+            description: "造成 {damage} 点物理伤害，必定暴击".to_string(),
             card_type: GcCardType::Attack,
             rarity: GcCardRarity::Legendary,
             cost: 3,
             base_damage: 40,
             base_defense: 0,
             target_type: GcTargetType::SingleEnemy,
+            damage_type: GcDamageType::Physical,
+            combo: None,
             effects: vec![
                 GcEffect {
                     effect_type: GcEffectType::PhysicalDamage,
@@ -326,6 +373,7 @@ pub fn gc_get_card_template(template_id: &str) -> Option<GcCard> {
                     name: "Effect".to_string(),
                 },
             ],
+            foil: false,
         }),
 
         // =============================================================================
@@ -335,13 +383,15 @@ pub fn gc_get_card_template(template_id: &str) -> Option<GcCard> {
             id: "temp_id".to_string(),
             template_id: template_id.to_string(),
             name: "刺击".to_string(),
-            description: "造成 12 点物理伤害".to_string(),
+            description: "造成 {damage} 点物理伤害".to_string(),
             card_type: GcCardType::Attack,
             rarity: GcCardRarity::Common,
             cost: 1,
             base_damage: 12,
             base_defense: 0,
             target_type: GcTargetType::SingleEnemy,
+            damage_type: GcDamageType::Physical,
+            combo: None,
             effects: vec![
                 GcEffect {
                     effect_type: GcEffectType::PhysicalDamage,
@@ -351,18 +401,21 @@ pub fn gc_get_card_template(template_id: &str) -> Option<GcCard> {
                     name: "Effect".to_string(),
                 },
             ],
+            foil: false,
         }),
         "card_assassin_skill" => Some(GcCard {
             id: "temp_id".to_string(),
             template_id: template_id.to_string(),
             name: "毒刃".to_string(),
-            description: "造成 8 点伤害，施加 5 层中毒".to_string(),
+            description: "造成 {damage} 点伤害，施加 {poison} 层中毒。连击: 本回合已打出攻击牌时伤害 +{combo}".to_string(),
             card_type: GcCardType::Skill,
             rarity: GcCardRarity::Rare,
             cost: 1,
             base_damage: 8,
             base_defense: 0,
             target_type: GcTargetType::SingleEnemy,
+            damage_type: GcDamageType::Physical,
+            combo: Some(GcCardCombo::new(GcComboCondition::PlayedType(GcCardType::Attack), 8)),
             effects: vec![
                 GcEffect {
                     effect_type: GcEffectType::PhysicalDamage,
@@ -379,18 +432,21 @@ pub fn gc_get_card_template(template_id: &str) -> Option<GcCard> {
                     name: "Effect".to_string(),
                 },
             ],
+            foil: false,
         }),
         "card_assassin_ult" => Some(GcCard {
             id: "temp_id".to_string(),
             template_id: template_id.to_string(),
             name: "瞬狱影杀阵".to_string(),
-            description: "对随机敌人造成 5 次 8 点伤害".to_string(),
+            description: "对随机敌人造成 {hits} 次 {damage} 点伤害".to_string(),
             card_type: GcCardType::Attack,
             rarity: GcCardRarity::Legendary,
             cost: 3,
             base_damage: 8,
             base_defense: 0,
             target_type: GcTargetType::AllEnemies, // 实际上是随机目标，这里简化
+            damage_type: GcDamageType::Physical,
+            combo: None,
             effects: vec![
                 GcEffect {
                     effect_type: GcEffectType::PhysicalDamage,
@@ -428,6 +484,7 @@ pub fn gc_get_card_template(template_id: &str) -> Option<GcCard> {
                     name: "Effect".to_string(),
                 },
             ],
+            foil: false,
         }),
 
         _ => None,
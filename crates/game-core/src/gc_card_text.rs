@@ -0,0 +1,229 @@
+//! 卡牌描述模板 (数值按卡牌数据实时代入)
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! ## 规则
+//! - 卡牌描述 (`description`) 是模板，`{占位符}` 在查询时由卡牌数据与出牌者属性代入，显示的数字与实际结算一致
+//! - 伤害按结算公式代入: 基础伤害 + 出牌者攻击力 (攻击力已包含天赋、伙伴光环、组织加成)，不含目标防御与抗性
+//! - 未知占位符原样保留，`gc_card_text_unknown_keys` 供内容校验使用；不含占位符的旧描述渲染结果不变
+//! - 渲染结果列出每个数值的基础值与实际值，界面可以高亮被加成的数字
+//!
+//! ## 占位符
+//! | 占位符 | 数值 |
+//! |--------|------|
+//! | `{damage}` | 单段伤害 (基础伤害 + 攻击力) |
+//! | `{block}` | 格挡 (基础防御) |
+//! | `{heal}` | 治疗量 (治疗牌的基础伤害字段) |
+//! | `{hits}` | 伤害段数 (伤害类效果的数量，至少 1) |
+//! | `{poison}` / `{weak}` | 中毒 / 虚弱层数 |
+//! | `{combo}` | 连击额外伤害 |
+//! | `{combo_count}` | 连击所需的本回合出牌数 |
+//! | `{effectN}` | 第 N 个效果的数值 (从 0 开始) |
+
+use serde::{Deserialize, Serialize};
+
+use crate::{GcCard, GcComboCondition, GcEffectType, GcPlayer};
+
+// =============================================================================
+// 渲染上下文
+// =============================================================================
+
+/// 渲染上下文 (出牌者的加成)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcCardTextContext {
+    /// 攻击力 (计入伤害)
+    pub attack_bonus: u32,
+}
+
+impl GcCardTextContext {
+    /// 按玩家当前属性创建 (战斗中的实际攻击力)
+    pub fn gc_for_player(player: &GcPlayer) -> Self {
+        Self { attack_bonus: player.stats.attack }
+    }
+}
+
+// =============================================================================
+// 渲染结果
+// =============================================================================
+
+/// 描述中的一个数值
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcCardTextValue {
+    /// 占位符
+    pub key: String,
+    /// 卡牌本身的数值
+    pub base: i64,
+    /// 计入加成后的数值 (显示的数字)
+    pub value: i64,
+}
+
+impl GcCardTextValue {
+    /// 是否被加成修改
+    pub fn gc_is_modified(&self) -> bool {
+        self.base != self.value
+    }
+}
+
+/// 渲染后的卡牌描述
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcCardText {
+    /// 代入数值后的文本
+    pub text: String,
+    /// 代入的数值 (按首次出现顺序，同一占位符只列一次)
+    pub values: Vec<GcCardTextValue>,
+}
+
+// =============================================================================
+// 渲染
+// =============================================================================
+
+/// 是否为伤害类效果
+fn gc_is_damage_effect(effect_type: &GcEffectType) -> bool {
+    matches!(effect_type, GcEffectType::Damage | GcEffectType::PhysicalDamage | GcEffectType::MagicDamage)
+}
+
+/// 第一个匹配效果的数值
+fn gc_effect_value(card: &GcCard, types: &[GcEffectType]) -> Option<i64> {
+    card.effects.iter()
+        .find(|e| types.contains(&e.effect_type))
+        .map(|e| i64::from(e.value))
+}
+
+/// 占位符的 (基础值, 实际值)；未知或卡牌没有对应数据时为 None
+fn gc_card_text_value(card: &GcCard, key: &str, ctx: &GcCardTextContext) -> Option<(i64, i64)> {
+    let same = |value: i64| Some((value, value));
+    match key {
+        "damage" => {
+            let base = i64::from(card.base_damage);
+            Some((base, base + i64::from(ctx.attack_bonus)))
+        }
+        "block" => same(i64::from(card.base_defense)),
+        "heal" => same(i64::from(card.base_damage)),
+        "hits" => {
+            let hits = card.effects.iter().filter(|e| gc_is_damage_effect(&e.effect_type)).count().max(1);
+            same(hits as i64)
+        }
+        "poison" => same(gc_effect_value(card, &[GcEffectType::Poison, GcEffectType::ApplyPoison])?),
+        "weak" => same(gc_effect_value(card, &[GcEffectType::Weak, GcEffectType::ApplyWeak])?),
+        "combo" => same(i64::from(card.combo.as_ref()?.bonus_damage)),
+        "combo_count" => match card.combo.as_ref()?.condition {
+            GcComboCondition::PlayedCount(count) => same(i64::from(count)),
+            GcComboCondition::PlayedType(_) => None,
+        },
+        _ => {
+            let index: usize = key.strip_prefix("effect")?.parse().ok()?;
+            same(i64::from(card.effects.get(index)?.value))
+        }
+    }
+}
+
+/// 依次处理描述中的占位符: `on_key` 返回替换文本 (None 时原样保留)
+fn gc_expand(template: &str, mut on_key: impl FnMut(&str) -> Option<String>) -> String {
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        output.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        match after.find('}') {
+            Some(end) => {
+                let key = &after[..end];
+                match on_key(key) {
+                    Some(text) => output.push_str(&text),
+                    None => output.push_str(&rest[start..start + end + 2]),
+                }
+                rest = &after[end + 1..];
+            }
+            None => {
+                output.push_str(&rest[start..]);
+                rest = "";
+            }
+        }
+    }
+    output.push_str(rest);
+    output
+}
+
+/// 渲染卡牌描述
+pub fn gc_render_card_text(card: &GcCard, ctx: &GcCardTextContext) -> GcCardText {
+    let mut values: Vec<GcCardTextValue> = Vec::new();
+    let text = gc_expand(&card.description, |key| {
+        let (base, value) = gc_card_text_value(card, key, ctx)?;
+        if !values.iter().any(|v| v.key == key) {
+            values.push(GcCardTextValue { key: key.to_string(), base, value });
+        }
+        Some(value.to_string())
+    });
+    GcCardText { text, values }
+}
+
+/// 描述中无法代入的占位符 (内容校验用)
+pub fn gc_card_text_unknown_keys(card: &GcCard) -> Vec<String> {
+    let mut unknown = Vec::new();
+    gc_expand(&card.description, |key| {
+        if gc_card_text_value(card, key, &GcCardTextContext::default()).is_none() {
+            unknown.push(key.to_string());
+        }
+        None
+    });
+    unknown
+}
+
+/// 把一组卡牌的描述替换为渲染后的文本 (发给客户端的视图使用)
+pub fn gc_render_card_descriptions(cards: &mut [GcCard], ctx: &GcCardTextContext) {
+    for card in cards {
+        card.description = gc_render_card_text(card, ctx).text;
+    }
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{gc_get_card_template, GcCardCombo, GC_CARD_TEMPLATE_IDS};
+
+    #[test]
+    fn test_render_substitutes_live_values() {
+        let mut card = GcCard::gc_new_attack("c1", "重击", 1, 8).gc_with_combo(GcCardCombo::new(GcComboCondition::PlayedCount(2), 4));
+        card.description = "造成 {damage} 点伤害。连击: 已打出 {combo_count} 张牌时 +{combo}".to_string();
+
+        let plain = gc_render_card_text(&card, &GcCardTextContext::default());
+        assert_eq!(plain.text, "造成 8 点伤害。连击: 已打出 2 张牌时 +4");
+        assert!(plain.values.iter().all(|v| !v.gc_is_modified()));
+
+        let boosted = gc_render_card_text(&card, &GcCardTextContext { attack_bonus: 3 });
+        assert_eq!(boosted.text, "造成 11 点伤害。连击: 已打出 2 张牌时 +4");
+        assert_eq!(boosted.values[0], GcCardTextValue { key: "damage".to_string(), base: 8, value: 11 });
+        assert!(boosted.values[0].gc_is_modified());
+    }
+
+    #[test]
+    fn test_unknown_placeholders_are_kept() {
+        let mut card = GcCard::gc_new_defense("c2", "格挡", 1, 5);
+        card.description = "获得 {block} 点格挡 {unknown} {effect3} {".to_string();
+        let rendered = gc_render_card_text(&card, &GcCardTextContext::default());
+        assert_eq!(rendered.text, "获得 5 点格挡 {unknown} {effect3} {");
+        assert_eq!(gc_card_text_unknown_keys(&card), vec!["unknown".to_string(), "effect3".to_string()]);
+    }
+
+    #[test]
+    fn test_templates_render_without_leftovers() {
+        for id in GC_CARD_TEMPLATE_IDS {
+            let card = gc_get_card_template(id).unwrap();
+            assert!(gc_card_text_unknown_keys(&card).is_empty(), "{}: {}", id, card.description);
+            assert!(!gc_render_card_text(&card, &GcCardTextContext::default()).text.contains('{'), "{}", id);
+        }
+
+        let gunner = gc_get_card_template("card_gunner_skill").unwrap();
+        assert_eq!(gc_render_card_text(&gunner, &GcCardTextContext::default()).text, "造成 3 次 5 点物理伤害");
+        let knight = gc_get_card_template("card_knight_ult").unwrap();
+        assert_eq!(
+            gc_render_card_text(&knight, &GcCardTextContext { attack_bonus: 10 }).text,
+            "获得 30 点格挡，所有队友获得 15 点格挡",
+        );
+    }
+}
@@ -0,0 +1,420 @@
+//! 引导/多回合行动系统
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! ## 引导规则
+//! - 引导行动需要持续若干回合才能完成 (引导法术、建造战场单位)
+//! - 每名玩家同一时间只能引导一个行动
+//! - 开始引导时扣除能量；伤害引导只能指向敌方 (不能指向自己或队友)
+//! - 引导者的回合开始时进度 -1，归零时结算
+//! - 引导者受到伤害 (可配置) 或死亡时引导被打断
+//! - 开始/推进/完成/打断都会写入战斗日志
+//! - 对局中只能按模板 ID 发起引导 (`gc_start_channel_from_template`)，数值由模板决定，不信任客户端
+
+use serde::{Deserialize, Serialize};
+
+use crate::{GcBattleState, GcCard, GcCombatLogEvent, GcError, GcPlayerId, GcPlayerState};
+
+// =============================================================================
+// 引导效果
+// =============================================================================
+
+/// 引导完成时的效果
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data")]
+pub enum GcChannelEffect {
+    /// 对目标造成伤害
+    Damage { target_id: GcPlayerId, amount: u32 },
+    /// 恢复引导者生命
+    Heal { amount: u32 },
+    /// 在引导者战场指定槽位建造单位
+    Construct { slot: usize, card: GcCard },
+}
+
+/// 引导中的行动
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GcChannelAction {
+    /// 行动 ID
+    pub id: String,
+    /// 引导者 ID
+    pub caster_id: GcPlayerId,
+    /// 行动名称
+    pub name: String,
+    /// 总回合数
+    pub total_turns: u32,
+    /// 剩余回合数
+    pub remaining_turns: u32,
+    /// 受到伤害时是否被打断
+    pub interrupt_on_damage: bool,
+    /// 上次检查时引导者的生命值
+    pub caster_hp: u32,
+    /// 开始引导时消耗的能量
+    #[serde(default)]
+    pub energy_cost: u32,
+    /// 完成效果
+    pub effect: GcChannelEffect,
+}
+
+impl GcChannelAction {
+    /// 创建引导行动 (caster_hp 在开始引导时填入)
+    pub fn gc_new(
+        id: &str,
+        caster_id: &str,
+        name: &str,
+        turns: u32,
+        interrupt_on_damage: bool,
+        effect: GcChannelEffect,
+    ) -> Self {
+        Self {
+            id: id.to_string(),
+            caster_id: caster_id.to_string(),
+            name: name.to_string(),
+            total_turns: turns.max(1),
+            remaining_turns: turns.max(1),
+            interrupt_on_damage,
+            caster_hp: 0,
+            energy_cost: 0,
+            effect,
+        }
+    }
+
+    /// 设置能量消耗
+    pub fn gc_with_energy_cost(mut self, energy_cost: u32) -> Self {
+        self.energy_cost = energy_cost;
+        self
+    }
+}
+
+// =============================================================================
+// 引导模板
+// =============================================================================
+
+/// 炎爆术: 消耗 2 能量，引导 2 回合后对敌方目标造成伤害，受伤打断
+pub const GC_CHANNEL_PYROBLAST: &str = "pyroblast";
+/// 冥想: 消耗 1 能量，引导 2 回合后恢复自身生命，受伤打断
+pub const GC_CHANNEL_MEDITATION: &str = "meditation";
+
+/// 按模板创建引导行动 (行动 ID 由模板、引导者与回合生成)
+pub fn gc_create_channel_from_template(
+    template_id: &str,
+    caster_id: &str,
+    target_id: &str,
+    turn: u32,
+) -> Result<GcChannelAction, GcError> {
+    let id = format!("{}_{}_t{}", template_id, caster_id, turn);
+    match template_id {
+        GC_CHANNEL_PYROBLAST => Ok(GcChannelAction::gc_new(
+            &id, caster_id, "炎爆术", 2, true,
+            GcChannelEffect::Damage { target_id: target_id.to_string(), amount: 30 },
+        ).gc_with_energy_cost(2)),
+        GC_CHANNEL_MEDITATION => Ok(GcChannelAction::gc_new(
+            &id, caster_id, "冥想", 2, true,
+            GcChannelEffect::Heal { amount: 25 },
+        ).gc_with_energy_cost(1)),
+        _ => Err(GcError::GcInvalidAction(format!("未知的引导: {}", template_id))),
+    }
+}
+
+// =============================================================================
+// 打断原因
+// =============================================================================
+
+/// 引导打断原因
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GcChannelInterruptReason {
+    /// 引导者受到伤害
+    Damaged,
+    /// 引导者死亡
+    CasterDied,
+}
+
+// =============================================================================
+// 引导操作
+// =============================================================================
+
+/// 开始引导
+pub fn gc_start_channel(state: &mut GcBattleState, mut action: GcChannelAction) -> Result<(), GcError> {
+    if state.gc_is_finished() {
+        return Err(GcError::GcBattleEnded);
+    }
+
+    if state.gc_current_player_id() != Some(action.caster_id.as_str()) {
+        return Err(GcError::GcNotYourTurn);
+    }
+
+    if state.channels.iter().any(|c| c.caster_id == action.caster_id) {
+        return Err(GcError::GcInvalidAction("已有正在引导的行动".to_string()));
+    }
+
+    let caster = state.gc_find_player(&action.caster_id)
+        .ok_or(GcError::GcPlayerNotFound)?;
+
+    if caster.stats.energy < action.energy_cost {
+        return Err(GcError::GcNotEnoughEnergy);
+    }
+
+    if let GcChannelEffect::Damage { target_id, .. } = &action.effect {
+        let target = state.gc_find_player(target_id)
+            .ok_or(GcError::GcInvalidTarget)?;
+        if !target.gc_can_act() || state.gc_are_allies(&action.caster_id, target_id) {
+            return Err(GcError::GcInvalidTarget);
+        }
+    }
+
+    if let GcChannelEffect::Construct { slot, .. } = &action.effect {
+        let slot = caster.battlefield.gc_get_slot(*slot)
+            .ok_or(GcError::GcInvalidSlot)?;
+        if !slot.gc_is_empty() {
+            return Err(GcError::GcSlotOccupied);
+        }
+    }
+
+    action.caster_hp = caster.stats.hp;
+    if let Some(caster) = state.gc_find_player_mut(&action.caster_id) {
+        caster.stats.energy -= action.energy_cost;
+    }
+    state.combat_log.push(GcCombatLogEvent::ChannelStart {
+        turn: state.turn,
+        action_id: action.id.clone(),
+        caster_id: action.caster_id.clone(),
+        name: action.name.clone(),
+        turns: action.total_turns,
+    });
+    state.channels.push(action);
+
+    Ok(())
+}
+
+/// 按模板开始引导 (对局入口: 服务器消息、事件重放、WASM)
+pub fn gc_start_channel_from_template(
+    state: &mut GcBattleState,
+    caster_id: &str,
+    template_id: &str,
+    target_id: &str,
+) -> Result<(), GcError> {
+    let action = gc_create_channel_from_template(template_id, caster_id, target_id, state.turn)?;
+    gc_start_channel(state, action)
+}
+
+/// 检查引导打断 (引导者受伤或死亡)
+///
+/// 返回被打断的行动 ID
+pub fn gc_check_channel_interrupts(state: &mut GcBattleState) -> Vec<String> {
+    let mut interrupted = Vec::new();
+    let mut kept = Vec::new();
+
+    for mut action in std::mem::take(&mut state.channels) {
+        let reason = match state.gc_find_player(&action.caster_id) {
+            Some(p) if p.state == GcPlayerState::Dead || !p.stats.gc_is_alive() => Some(GcChannelInterruptReason::CasterDied),
+            None => Some(GcChannelInterruptReason::CasterDied),
+            Some(p) if action.interrupt_on_damage && p.stats.hp < action.caster_hp => Some(GcChannelInterruptReason::Damaged),
+            Some(p) => {
+                // 治疗后以新生命值为基准
+                action.caster_hp = p.stats.hp;
+                None
+            }
+        };
+
+        match reason {
+            Some(reason) => {
+                state.combat_log.push(GcCombatLogEvent::ChannelInterrupt {
+                    turn: state.turn,
+                    action_id: action.id.clone(),
+                    reason,
+                });
+                interrupted.push(action.id);
+            }
+            None => kept.push(action),
+        }
+    }
+
+    state.channels = kept;
+    interrupted
+}
+
+/// 推进指定玩家的引导 (在该玩家回合开始时调用)
+///
+/// 返回完成的行动 ID
+pub fn gc_tick_channels(state: &mut GcBattleState, player_id: &str) -> Vec<String> {
+    gc_check_channel_interrupts(state);
+
+    let mut completed = Vec::new();
+    let mut kept = Vec::new();
+
+    for mut action in std::mem::take(&mut state.channels) {
+        if action.caster_id != player_id {
+            kept.push(action);
+            continue;
+        }
+
+        action.remaining_turns = action.remaining_turns.saturating_sub(1);
+        if action.remaining_turns > 0 {
+            state.combat_log.push(GcCombatLogEvent::ChannelTick {
+                turn: state.turn,
+                action_id: action.id.clone(),
+                remaining_turns: action.remaining_turns,
+            });
+            kept.push(action);
+            continue;
+        }
+
+        let description = gc_resolve_channel(state, &action);
+        state.combat_log.push(GcCombatLogEvent::ChannelComplete {
+            turn: state.turn,
+            action_id: action.id.clone(),
+            description,
+        });
+        completed.push(action.id);
+    }
+
+    state.channels.extend(kept);
+    state.gc_check_battle_end();
+    completed
+}
+
+/// 结算引导效果，返回描述文本
+fn gc_resolve_channel(state: &mut GcBattleState, action: &GcChannelAction) -> String {
+    match &action.effect {
+        GcChannelEffect::Damage { target_id, amount } => {
+            match state.gc_find_player_mut(target_id) {
+                Some(target) if target.gc_can_act() => {
+                    let dealt = target.stats.gc_take_damage(*amount);
+                    if !target.stats.gc_is_alive() {
+                        target.state = GcPlayerState::Dead;
+                    }
+                    format!("{} 造成 {} 点伤害", action.name, dealt)
+                }
+                _ => format!("{} 目标已失效", action.name),
+            }
+        }
+        GcChannelEffect::Heal { amount } => {
+            let healed = state.gc_find_player_mut(&action.caster_id)
+                .map(|p| p.stats.gc_heal(*amount))
+                .unwrap_or(0);
+            format!("{} 恢复 {} 点生命", action.name, healed)
+        }
+        GcChannelEffect::Construct { slot, card } => {
+            let deployed = state.gc_find_player_mut(&action.caster_id)
+                .map(|p| p.battlefield.gc_deploy_to_slot(*slot, card.clone()).is_ok())
+                .unwrap_or(false);
+            if deployed {
+                format!("{} 建造完成", action.name)
+            } else {
+                format!("{} 建造失败: 槽位不可用", action.name)
+            }
+        }
+    }
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GcPlayer;
+
+    fn create_battle() -> GcBattleState {
+        let p1 = GcPlayer::gc_new("p1", "玩家1");
+        let p2 = GcPlayer::gc_new("p2", "玩家2");
+        GcBattleState::gc_new("b1", vec![p1, p2])
+    }
+
+    fn fireball(turns: u32) -> GcChannelAction {
+        GcChannelAction::gc_new(
+            "c1", "p1", "炎爆术", turns, true,
+            GcChannelEffect::Damage { target_id: "p2".to_string(), amount: 30 },
+        )
+    }
+
+    #[test]
+    fn test_channel_completes_after_turns() {
+        let mut battle = create_battle();
+        assert!(gc_start_channel(&mut battle, fireball(2)).is_ok());
+        assert!(gc_start_channel(&mut battle, fireball(2)).is_err());
+
+        assert!(gc_tick_channels(&mut battle, "p1").is_empty());
+        assert_eq!(battle.channels[0].remaining_turns, 1);
+
+        assert_eq!(gc_tick_channels(&mut battle, "p1"), vec!["c1".to_string()]);
+        assert!(battle.channels.is_empty());
+        assert_eq!(battle.gc_find_player("p2").map(|p| p.stats.hp), Some(70));
+        assert!(matches!(battle.combat_log.last(), Some(GcCombatLogEvent::ChannelComplete { .. })));
+    }
+
+    #[test]
+    fn test_channel_interrupted_by_damage() {
+        let mut battle = create_battle();
+        gc_start_channel(&mut battle, fireball(2)).ok();
+
+        if let Some(p1) = battle.gc_find_player_mut("p1") {
+            p1.stats.gc_take_damage(5);
+        }
+
+        assert_eq!(gc_check_channel_interrupts(&mut battle), vec!["c1".to_string()]);
+        assert!(battle.channels.is_empty());
+        assert!(matches!(
+            battle.combat_log.last(),
+            Some(GcCombatLogEvent::ChannelInterrupt { reason: GcChannelInterruptReason::Damaged, .. })
+        ));
+    }
+
+    #[test]
+    fn test_channel_construct() {
+        let mut battle = create_battle();
+        let card = GcCard::gc_new_attack("tower", "箭塔", 0, 5);
+        let action = GcChannelAction::gc_new(
+            "c2", "p1", "建造箭塔", 1, false,
+            GcChannelEffect::Construct { slot: 0, card },
+        );
+        assert!(gc_start_channel(&mut battle, action).is_ok());
+
+        // 不因伤害打断
+        if let Some(p1) = battle.gc_find_player_mut("p1") {
+            p1.stats.gc_take_damage(5);
+        }
+        gc_tick_channels(&mut battle, "p1");
+
+        let built = battle.gc_find_player("p1")
+            .and_then(|p| p.battlefield.gc_get_slot(0))
+            .map(|s| !s.gc_is_empty());
+        assert_eq!(built, Some(true));
+    }
+
+    #[test]
+    fn test_damage_channel_rejects_self_and_allies() {
+        let mut battle = create_battle();
+        let at_self = gc_create_channel_from_template(GC_CHANNEL_PYROBLAST, "p1", "p1", 1).expect("模板应存在");
+        assert!(matches!(gc_start_channel(&mut battle, at_self), Err(GcError::GcInvalidTarget)));
+
+        for (player, team) in battle.players.iter_mut().zip([1, 1]) {
+            player.team = Some(team);
+        }
+        let at_ally = gc_create_channel_from_template(GC_CHANNEL_PYROBLAST, "p1", "p2", 1).expect("模板应存在");
+        assert!(matches!(gc_start_channel(&mut battle, at_ally), Err(GcError::GcInvalidTarget)));
+        assert!(battle.channels.is_empty());
+    }
+
+    #[test]
+    fn test_template_channel_costs_energy() {
+        let mut battle = create_battle();
+        let energy = |battle: &GcBattleState| battle.gc_find_player("p1").map(|p| p.stats.energy);
+        let before = energy(&battle).unwrap_or_default();
+
+        assert!(gc_start_channel_from_template(&mut battle, "p1", GC_CHANNEL_PYROBLAST, "p2").is_ok());
+        assert_eq!(energy(&battle), Some(before - 2));
+
+        battle.channels.clear();
+        if let Some(p1) = battle.gc_find_player_mut("p1") {
+            p1.stats.energy = 0;
+        }
+        assert!(matches!(
+            gc_start_channel_from_template(&mut battle, "p1", GC_CHANNEL_MEDITATION, "p1"),
+            Err(GcError::GcNotEnoughEnergy)
+        ));
+        assert!(battle.channels.is_empty());
+    }
+}
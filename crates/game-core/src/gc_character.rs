@@ -0,0 +1,216 @@
+//! 角色创建 (名称、职业、外观)
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! ## 角色规则
+//! - 名称规范化 (NFKC、去除首尾空白等，见 `gc_text`) 后 2~12 个字符，只允许文字、数字和下划线
+//! - 敏感词由调用方提供的 `GcNameFilter` 判断 (服务器加载屏蔽词表)
+//! - 外观参数为配色、发型、脸型的编号，超出范围视为无效
+//! - 外观随房间与大世界的玩家信息下发，其他玩家据此显示角色
+
+use serde::{Deserialize, Serialize};
+
+use crate::{gc_normalize_text, GcProfessionType};
+
+/// 角色名称最少字符数
+pub const GC_CHARACTER_NAME_MIN: usize = 2;
+
+/// 角色名称最多字符数
+pub const GC_CHARACTER_NAME_MAX: usize = 12;
+
+/// 可选配色数量
+pub const GC_APPEARANCE_PALETTES: u32 = 8;
+
+/// 可选发型数量
+pub const GC_APPEARANCE_HAIRS: u32 = 12;
+
+/// 可选脸型数量
+pub const GC_APPEARANCE_FACES: u32 = 6;
+
+// =============================================================================
+// 名称过滤
+// =============================================================================
+
+/// 名称过滤钩子 (敏感词检查)
+pub trait GcNameFilter {
+    /// 名称是否允许使用
+    fn gc_is_allowed(&self, name: &str) -> bool;
+}
+
+/// 不做过滤
+pub struct GcNoNameFilter;
+
+impl GcNameFilter for GcNoNameFilter {
+    fn gc_is_allowed(&self, _name: &str) -> bool {
+        true
+    }
+}
+
+/// 屏蔽词表 (名称包含任一屏蔽词即拒绝，不区分大小写)
+#[derive(Clone, Debug, Default)]
+pub struct GcWordBlocklist {
+    words: Vec<String>,
+}
+
+impl GcWordBlocklist {
+    /// 创建屏蔽词表 (忽略空词)
+    pub fn gc_new<S: AsRef<str>>(words: &[S]) -> Self {
+        Self {
+            words: words
+                .iter()
+                .map(|w| w.as_ref().trim().to_lowercase())
+                .filter(|w| !w.is_empty())
+                .collect(),
+        }
+    }
+
+    /// 屏蔽词数量
+    pub fn gc_len(&self) -> usize {
+        self.words.len()
+    }
+}
+
+impl GcNameFilter for GcWordBlocklist {
+    fn gc_is_allowed(&self, name: &str) -> bool {
+        let name = name.to_lowercase();
+        !self.words.iter().any(|w| name.contains(w.as_str()))
+    }
+}
+
+/// 校验角色名称，返回规范化 (`gc_normalize_text`) 后的名称
+pub fn gc_validate_character_name(name: &str, filter: &dyn GcNameFilter) -> Result<String, String> {
+    let name = gc_normalize_text(name);
+    let name = name.as_str();
+    let len = name.chars().count();
+    if !(GC_CHARACTER_NAME_MIN..=GC_CHARACTER_NAME_MAX).contains(&len) {
+        return Err(format!(
+            "角色名称需要 {}~{} 个字符",
+            GC_CHARACTER_NAME_MIN, GC_CHARACTER_NAME_MAX
+        ));
+    }
+    if !name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        return Err("角色名称只能包含文字、数字和下划线".to_string());
+    }
+    if !filter.gc_is_allowed(name) {
+        return Err("角色名称包含不允许的词语".to_string());
+    }
+    Ok(name.to_string())
+}
+
+// =============================================================================
+// 外观
+// =============================================================================
+
+/// 角色外观参数
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct GcAppearance {
+    /// 配色编号
+    pub palette_id: u32,
+    /// 发型编号
+    pub hair_id: u32,
+    /// 脸型编号
+    #[serde(default)]
+    pub face_id: u32,
+}
+
+impl GcAppearance {
+    /// 创建外观
+    pub fn gc_new(palette_id: u32, hair_id: u32, face_id: u32) -> Self {
+        Self { palette_id, hair_id, face_id }
+    }
+
+    /// 校验编号范围
+    pub fn gc_validate(&self) -> Result<(), String> {
+        if self.palette_id >= GC_APPEARANCE_PALETTES {
+            return Err(format!("配色编号 {} 无效", self.palette_id));
+        }
+        if self.hair_id >= GC_APPEARANCE_HAIRS {
+            return Err(format!("发型编号 {} 无效", self.hair_id));
+        }
+        if self.face_id >= GC_APPEARANCE_FACES {
+            return Err(format!("脸型编号 {} 无效", self.face_id));
+        }
+        Ok(())
+    }
+}
+
+// =============================================================================
+// 角色
+// =============================================================================
+
+/// 角色 (每个存档槽位一个)
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcCharacter {
+    /// 角色名称
+    pub name: String,
+    /// 职业
+    pub profession: GcProfessionType,
+    /// 外观
+    #[serde(default)]
+    pub appearance: GcAppearance,
+}
+
+impl GcCharacter {
+    /// 创建角色 (校验名称与外观，名称去除首尾空白)
+    pub fn gc_new(
+        name: &str,
+        profession: GcProfessionType,
+        appearance: GcAppearance,
+        filter: &dyn GcNameFilter,
+    ) -> Result<Self, String> {
+        let name = gc_validate_character_name(name, filter)?;
+        appearance.gc_validate()?;
+        Ok(Self { name, profession, appearance })
+    }
+
+    /// 重命名
+    pub fn gc_rename(&mut self, name: &str, filter: &dyn GcNameFilter) -> Result<(), String> {
+        self.name = gc_validate_character_name(name, filter)?;
+        Ok(())
+    }
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_character_name_rules() {
+        assert_eq!(gc_validate_character_name("  勇者_01 ", &GcNoNameFilter), Ok("勇者_01".to_string()));
+        assert!(gc_validate_character_name("a", &GcNoNameFilter).is_err());
+        assert!(gc_validate_character_name("abcdefghijklm", &GcNoNameFilter).is_err());
+        assert!(gc_validate_character_name("a b", &GcNoNameFilter).is_err());
+        assert!(gc_validate_character_name("勇者!", &GcNoNameFilter).is_err());
+
+        let blocklist = GcWordBlocklist::gc_new(&["Admin", " "]);
+        assert_eq!(blocklist.gc_len(), 1);
+        assert!(gc_validate_character_name("TheADMIN", &blocklist).is_err());
+        assert!(gc_validate_character_name("Hero", &blocklist).is_ok());
+    }
+
+    #[test]
+    fn test_create_and_rename_character() {
+        let appearance = GcAppearance::gc_new(2, 5, 1);
+        let mut character = GcCharacter::gc_new("Alice", GcProfessionType::Knight, appearance, &GcNoNameFilter)
+            .unwrap_or_else(|_| GcCharacter { name: String::new(), profession: GcProfessionType::Gunner, appearance });
+        assert_eq!(character.name, "Alice");
+        assert!(character.gc_rename("x", &GcNoNameFilter).is_err());
+        assert!(character.gc_rename("Bob", &GcNoNameFilter).is_ok());
+        assert_eq!(character.name, "Bob");
+
+        let bad = GcAppearance::gc_new(GC_APPEARANCE_PALETTES, 0, 0);
+        assert!(GcCharacter::gc_new("Alice", GcProfessionType::Knight, bad, &GcNoNameFilter).is_err());
+        assert!(GcAppearance::gc_new(0, GC_APPEARANCE_HAIRS, 0).gc_validate().is_err());
+
+        let json = r#"{"name":"Bob","profession":"Warlock"}"#;
+        let parsed: GcCharacter = serde_json::from_str(json).unwrap_or(character);
+        assert_eq!(parsed.profession, GcProfessionType::Warlock);
+        assert_eq!(parsed.appearance, GcAppearance::default());
+    }
+}
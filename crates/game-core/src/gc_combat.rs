@@ -3,7 +3,7 @@
 //! 战斗伤害计算、攻击目标选择
 
 use serde::{Deserialize, Serialize};
-use crate::{GcMonster, GcTerrainType};
+use crate::{GcArenaRow, GcCombatCue, GcConfig, GcMonster, GcTerrainType};
 
 /// 攻击结果
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -24,6 +24,15 @@ pub struct GcAttackOutcome {
     pub attacker_destroyed: bool,
     /// 对玩家的直接伤害
     pub player_damage: u32,
+    /// 攻击者所在排
+    #[serde(default)]
+    pub attacker_row: GcArenaRow,
+    /// 目标所在排
+    #[serde(default)]
+    pub target_row: GcArenaRow,
+    /// 表现提示 (冲击强度、元素、震屏)
+    #[serde(default)]
+    pub cue: GcCombatCue,
 }
 
 /// 计算怪兽攻击结果
@@ -68,6 +77,10 @@ pub fn gc_calculate_attack(
                 target_destroyed,
                 attacker_destroyed,
                 player_damage: if target_destroyed { damage } else { 0 },
+                attacker_row: GcArenaRow::Front,
+                target_row: GcArenaRow::Front,
+                cue: GcCombatCue::gc_new(damage, def_monster.max_hp, attacker.damage_type)
+                    .gc_with_lethal(target_destroyed),
             }
         }
         None => {
@@ -81,6 +94,9 @@ pub fn gc_calculate_attack(
                 target_destroyed: false,
                 attacker_destroyed: false,
                 player_damage: atk,
+                attacker_row: GcArenaRow::Front,
+                target_row: GcArenaRow::Front,
+                cue: GcCombatCue::gc_new(atk, GcConfig::DEFAULT_HP, attacker.damage_type),
             }
         }
     }
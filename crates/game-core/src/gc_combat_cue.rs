@@ -0,0 +1,185 @@
+//! 战斗表现提示 (音效/特效/震屏)
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! ## 规则
+//! - 提示由战斗结算直接生成并随战斗日志/攻击结果下发，界面与回放使用同一份数据
+//! - 冲击强度按伤害占目标最大生命的比例分级: <10% 轻、<25% 中、<50% 重、其余为巨大
+//! - 震屏建议由冲击强度决定，暴击或致命一击再提升一级
+//! - 元素取伤害类型；目标抗性为正时标记"被抵抗"，为负时标记"弱点"
+
+use serde::{Deserialize, Serialize};
+
+use crate::{GcDamageResult, GcDamageType};
+
+// =============================================================================
+// 冲击强度与震屏
+// =============================================================================
+
+/// 冲击强度
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum GcImpactTier {
+    /// 轻
+    #[default]
+    Light,
+    /// 中
+    Medium,
+    /// 重
+    Heavy,
+    /// 巨大
+    Massive,
+}
+
+impl GcImpactTier {
+    /// 按伤害占参考生命的比例分级 (参考生命为 0 时按 1 处理)
+    pub fn gc_from_damage(damage: u32, reference_hp: u32) -> Self {
+        let percent = damage as u64 * 100 / reference_hp.max(1) as u64;
+        match percent {
+            0..=9 => Self::Light,
+            10..=24 => Self::Medium,
+            25..=49 => Self::Heavy,
+            _ => Self::Massive,
+        }
+    }
+}
+
+/// 震屏建议
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum GcScreenShake {
+    /// 不震屏
+    #[default]
+    None,
+    /// 轻微
+    Small,
+    /// 中等
+    Medium,
+    /// 强烈
+    Large,
+}
+
+impl GcScreenShake {
+    /// 冲击强度对应的震屏
+    pub fn gc_for_impact(tier: GcImpactTier) -> Self {
+        match tier {
+            GcImpactTier::Light => Self::None,
+            GcImpactTier::Medium => Self::Small,
+            GcImpactTier::Heavy => Self::Medium,
+            GcImpactTier::Massive => Self::Large,
+        }
+    }
+
+    /// 提升一级 (已是最高时不变)
+    pub fn gc_escalate(self) -> Self {
+        match self {
+            Self::None => Self::Small,
+            Self::Small => Self::Medium,
+            Self::Medium | Self::Large => Self::Large,
+        }
+    }
+}
+
+// =============================================================================
+// 表现提示
+// =============================================================================
+
+/// 一次命中的表现提示
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcCombatCue {
+    /// 冲击强度
+    pub impact: GcImpactTier,
+    /// 元素 (伤害类型)
+    pub element: GcDamageType,
+    /// 震屏建议
+    pub screen_shake: GcScreenShake,
+    /// 暴击
+    #[serde(default)]
+    pub critical: bool,
+    /// 致命一击 (目标被击倒/摧毁)
+    #[serde(default)]
+    pub lethal: bool,
+    /// 被抵抗 (目标抗性为正)
+    #[serde(default)]
+    pub resisted: bool,
+    /// 命中弱点 (目标抗性为负)
+    #[serde(default)]
+    pub weakness: bool,
+}
+
+impl GcCombatCue {
+    /// 由伤害数值与元素生成提示
+    pub fn gc_new(damage: u32, reference_hp: u32, element: GcDamageType) -> Self {
+        let impact = GcImpactTier::gc_from_damage(damage, reference_hp);
+        Self {
+            impact,
+            element,
+            screen_shake: GcScreenShake::gc_for_impact(impact),
+            ..Default::default()
+        }
+    }
+
+    /// 标记致命一击 (震屏提升一级)
+    pub fn gc_with_lethal(mut self, lethal: bool) -> Self {
+        if lethal && !self.lethal {
+            self.lethal = true;
+            self.screen_shake = self.screen_shake.gc_escalate();
+        }
+        self
+    }
+
+    /// 标记暴击 (震屏提升一级)
+    pub fn gc_with_critical(mut self, critical: bool) -> Self {
+        if critical && !self.critical {
+            self.critical = true;
+            self.screen_shake = self.screen_shake.gc_escalate();
+        }
+        self
+    }
+}
+
+/// 由伤害结算结果生成提示 (卡牌、英雄技能等玩家伤害)
+pub fn gc_damage_cue(result: &GcDamageResult, target_max_hp: u32, lethal: bool) -> GcCombatCue {
+    let mut cue = GcCombatCue::gc_new(result.final_damage, target_max_hp, result.damage_type)
+        .gc_with_critical(result.is_critical)
+        .gc_with_lethal(lethal);
+    cue.resisted = result.resistance_percent > 0;
+    cue.weakness = result.resistance_percent < 0;
+    cue
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{gc_resolve_damage, GcResistances};
+
+    #[test]
+    fn test_impact_tiers() {
+        assert_eq!(GcImpactTier::gc_from_damage(9, 100), GcImpactTier::Light);
+        assert_eq!(GcImpactTier::gc_from_damage(10, 100), GcImpactTier::Medium);
+        assert_eq!(GcImpactTier::gc_from_damage(30, 100), GcImpactTier::Heavy);
+        assert_eq!(GcImpactTier::gc_from_damage(80, 100), GcImpactTier::Massive);
+        assert_eq!(GcImpactTier::gc_from_damage(5, 0), GcImpactTier::Massive);
+    }
+
+    #[test]
+    fn test_damage_cue_from_result() {
+        let weak = GcResistances { fire: -50, ..Default::default() };
+        let result = gc_resolve_damage(20, 0, GcDamageType::Fire, &weak);
+        let cue = gc_damage_cue(&result, 100, false);
+        assert_eq!(cue.element, GcDamageType::Fire);
+        assert_eq!((cue.impact, cue.screen_shake), (GcImpactTier::Heavy, GcScreenShake::Medium));
+        assert!(cue.weakness && !cue.resisted);
+
+        // 致命一击震屏提升一级，且不会重复提升
+        let lethal = gc_damage_cue(&result, 100, true).gc_with_lethal(true);
+        assert_eq!(lethal.screen_shake, GcScreenShake::Large);
+
+        let resisted = gc_resolve_damage(20, 0, GcDamageType::Frost, &GcResistances { frost: 50, ..Default::default() });
+        assert!(gc_damage_cue(&resisted, 100, false).resisted);
+    }
+}
@@ -0,0 +1,48 @@
+//! 战斗日志
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! ## 规则
+//! - 战斗中的引导、伤害、治疗按发生顺序追加到 `GcBattleState::combat_log`
+//! - 日志只追加不修改，统计 (伤害统计、对局数据) 和分页查询都基于它
+
+use serde::{Deserialize, Serialize};
+
+use crate::{GcChannelInterruptReason, GcCombatCue, GcDamageResult, GcPlayerId};
+
+// =============================================================================
+// 日志事件
+// =============================================================================
+
+/// 战斗日志事件
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data")]
+pub enum GcCombatLogEvent {
+    /// 开始引导
+    ChannelStart { turn: u32, action_id: String, caster_id: GcPlayerId, name: String, turns: u32 },
+    /// 引导推进
+    ChannelTick { turn: u32, action_id: String, remaining_turns: u32 },
+    /// 引导完成
+    ChannelComplete { turn: u32, action_id: String, description: String },
+    /// 引导被打断
+    ChannelInterrupt { turn: u32, action_id: String, reason: GcChannelInterruptReason },
+    /// 造成伤害 (含类型与减免明细，以及界面表现提示)
+    Damage {
+        turn: u32,
+        source_id: GcPlayerId,
+        target_id: GcPlayerId,
+        result: GcDamageResult,
+        breakdown: String,
+        #[serde(default)]
+        cue: GcCombatCue,
+    },
+    /// 治疗 (友方单体牌与治疗技能，用于统计治疗量与助攻)
+    Heal {
+        turn: u32,
+        source_id: GcPlayerId,
+        target_id: GcPlayerId,
+        amount: u32,
+    },
+}
@@ -0,0 +1,353 @@
+//! 紧凑沙盘状态 (AI 搜索用)
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! ## 规则
+//! - 沙盘 30 个位置用位图表示: 每方 15 位 (前排 0-4、后排 5-9、建筑 10-14)，敌方偏移 15
+//! - 搜索中会变化的数据 (生命、能否攻击、占位) 放在 `GcCompactArena`，整体 `Copy`，可直接哈希
+//! - 不变的数据 (名称、模板、星级等) 放在 `GcCompactCatalog`，一次搜索内共享
+//! - 编码时预先算好每个怪兽在己方地形下的攻击力，评估只做整数运算
+//! - `gc_decode(gc_encode(arena))` 与原沙盘等价 (见测试)
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use crate::{GcArenaSide, GcBattleArena, GcMonster, GcStructure, GcStructureTargetRule, GcTerrainType};
+
+// =============================================================================
+// 常量
+// =============================================================================
+
+/// 每方每排槽位数
+const GC_COMPACT_SLOTS: usize = 5;
+
+/// 每方位置数 (前排、后排、建筑)
+const GC_COMPACT_SIDE_POSITIONS: usize = GC_COMPACT_SLOTS * 3;
+
+/// 沙盘位置总数
+pub const GC_COMPACT_POSITIONS: usize = GC_COMPACT_SIDE_POSITIONS * 2;
+
+/// 空位的目录索引
+const GC_COMPACT_EMPTY: u16 = u16::MAX;
+
+// =============================================================================
+// 紧凑状态
+// =============================================================================
+
+/// 位置类别
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GcCompactLane {
+    /// 前排怪兽
+    Front,
+    /// 后排怪兽
+    Back,
+    /// 建筑
+    Structure,
+}
+
+/// 位置索引 (0-29)
+pub fn gc_compact_position(side: GcArenaSide, lane: GcCompactLane, slot: usize) -> usize {
+    let side_offset = match side {
+        GcArenaSide::Player => 0,
+        GcArenaSide::Enemy => GC_COMPACT_SIDE_POSITIONS,
+    };
+    let lane_offset = match lane {
+        GcCompactLane::Front => 0,
+        GcCompactLane::Back => GC_COMPACT_SLOTS,
+        GcCompactLane::Structure => GC_COMPACT_SLOTS * 2,
+    };
+    side_offset + lane_offset + slot
+}
+
+/// 单个位置上的单位
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct GcCompactUnit {
+    /// 目录索引 (怪兽或建筑目录，按位置类别区分)
+    pub catalog: u16,
+    /// 当前生命
+    pub hp: u32,
+    /// 己方地形下的攻击力 (建筑为 0)
+    pub atk: u32,
+}
+
+impl GcCompactUnit {
+    const EMPTY: Self = Self { catalog: GC_COMPACT_EMPTY, hp: 0, atk: 0 };
+}
+
+/// 紧凑沙盘
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct GcCompactArena {
+    /// 占位位图
+    pub occupied: u32,
+    /// 可攻击位图 (仅怪兽)
+    pub ready: u32,
+    /// 各位置单位
+    pub units: [GcCompactUnit; GC_COMPACT_POSITIONS],
+    /// 双方地形 (玩家、敌人)
+    pub terrain: [GcTerrainType; 2],
+    /// 是否必须先攻击城墙
+    pub walls_first: bool,
+    /// 本回合是否已普通召唤
+    pub normal_summon_used: bool,
+}
+
+impl GcCompactArena {
+    /// 指定位置是否有单位
+    pub fn gc_is_occupied(&self, position: usize) -> bool {
+        self.occupied & (1 << position) != 0
+    }
+
+    /// 指定位置的怪兽是否可攻击
+    pub fn gc_is_ready(&self, position: usize) -> bool {
+        self.ready & (1 << position) != 0
+    }
+
+    /// 一方的占位位图
+    pub fn gc_side_mask(&self, side: GcArenaSide) -> u32 {
+        let mask = (1u32 << GC_COMPACT_SIDE_POSITIONS) - 1;
+        match side {
+            GcArenaSide::Player => self.occupied & mask,
+            GcArenaSide::Enemy => (self.occupied >> GC_COMPACT_SIDE_POSITIONS) & mask,
+        }
+    }
+
+    /// 局面评估 (玩家视角): 双方 (生命 + 攻击) 之差
+    pub fn gc_evaluate(&self) -> i64 {
+        let mut bits = self.occupied;
+        let mut score = 0i64;
+        while bits != 0 {
+            let position = bits.trailing_zeros() as usize;
+            bits &= bits - 1;
+            let unit = &self.units[position];
+            let value = unit.hp as i64 + unit.atk as i64;
+            if position < GC_COMPACT_SIDE_POSITIONS {
+                score += value;
+            } else {
+                score -= value;
+            }
+        }
+        score
+    }
+
+    /// 置换表键
+    pub fn gc_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+/// 完整沙盘的局面评估 (与 `GcCompactArena::gc_evaluate` 等价)
+pub fn gc_evaluate_arena(arena: &GcBattleArena) -> i64 {
+    let side_score = |monsters: [&[Option<GcMonster>; 5]; 2], structures: &[Option<GcStructure>; 5], terrain| {
+        let monster_score: i64 = monsters.iter()
+            .flat_map(|row| row.iter().flatten())
+            .map(|m| m.current_hp as i64 + m.effective_atk(terrain) as i64)
+            .sum();
+        let structure_score: i64 = structures.iter().flatten().map(|s| s.current_hp as i64).sum();
+        monster_score + structure_score
+    };
+    side_score([&arena.player_monsters, &arena.player_back_row], &arena.player_structures, arena.player_terrain)
+        - side_score([&arena.enemy_monsters, &arena.enemy_back_row], &arena.enemy_structures, arena.enemy_terrain)
+}
+
+// =============================================================================
+// 目录与转换
+// =============================================================================
+
+/// 不变数据目录 (一次搜索共享)
+#[derive(Clone, Debug, Default)]
+pub struct GcCompactCatalog {
+    /// 怪兽原型
+    pub monsters: Vec<GcMonster>,
+    /// 建筑原型
+    pub structures: Vec<GcStructure>,
+}
+
+/// 去掉会变化的字段后的怪兽签名
+fn gc_monster_signature(monster: &GcMonster) -> serde_json::Value {
+    let mut normalized = monster.clone();
+    normalized.current_hp = 0;
+    normalized.can_attack = false;
+    serde_json::to_value(normalized).unwrap_or_default()
+}
+
+/// 去掉会变化的字段后的建筑签名
+fn gc_structure_signature(structure: &GcStructure) -> serde_json::Value {
+    let mut normalized = structure.clone();
+    normalized.current_hp = 0;
+    serde_json::to_value(normalized).unwrap_or_default()
+}
+
+impl GcCompactCatalog {
+    /// 创建空目录
+    pub fn gc_new() -> Self {
+        Self::default()
+    }
+
+    fn gc_intern_monster(&mut self, monster: &GcMonster) -> u16 {
+        let signature = gc_monster_signature(monster);
+        if let Some(index) = self.monsters.iter()
+            .position(|m| m.id == monster.id && gc_monster_signature(m) == signature)
+        {
+            return index as u16;
+        }
+        self.monsters.push(monster.clone());
+        (self.monsters.len() - 1) as u16
+    }
+
+    fn gc_intern_structure(&mut self, structure: &GcStructure) -> u16 {
+        let signature = gc_structure_signature(structure);
+        if let Some(index) = self.structures.iter()
+            .position(|s| s.id == structure.id && gc_structure_signature(s) == signature)
+        {
+            return index as u16;
+        }
+        self.structures.push(structure.clone());
+        (self.structures.len() - 1) as u16
+    }
+
+    /// 编码沙盘
+    pub fn gc_encode(&mut self, arena: &GcBattleArena) -> GcCompactArena {
+        let mut compact = GcCompactArena {
+            occupied: 0,
+            ready: 0,
+            units: [GcCompactUnit::EMPTY; GC_COMPACT_POSITIONS],
+            terrain: [arena.player_terrain, arena.enemy_terrain],
+            walls_first: arena.target_rule == GcStructureTargetRule::WallsFirst,
+            normal_summon_used: arena.normal_summon_used,
+        };
+
+        let sides = [
+            (GcArenaSide::Player, arena.player_terrain, &arena.player_monsters, &arena.player_back_row, &arena.player_structures),
+            (GcArenaSide::Enemy, arena.enemy_terrain, &arena.enemy_monsters, &arena.enemy_back_row, &arena.enemy_structures),
+        ];
+        for (side, terrain, front, back, structures) in sides {
+            for (lane, row) in [(GcCompactLane::Front, front), (GcCompactLane::Back, back)] {
+                for (slot, monster) in row.iter().enumerate() {
+                    let Some(monster) = monster else { continue };
+                    let position = gc_compact_position(side, lane, slot);
+                    compact.occupied |= 1 << position;
+                    if monster.can_attack {
+                        compact.ready |= 1 << position;
+                    }
+                    compact.units[position] = GcCompactUnit {
+                        catalog: self.gc_intern_monster(monster),
+                        hp: monster.current_hp,
+                        atk: monster.effective_atk(terrain),
+                    };
+                }
+            }
+            for (slot, structure) in structures.iter().enumerate() {
+                let Some(structure) = structure else { continue };
+                let position = gc_compact_position(side, GcCompactLane::Structure, slot);
+                compact.occupied |= 1 << position;
+                compact.units[position] = GcCompactUnit {
+                    catalog: self.gc_intern_structure(structure),
+                    hp: structure.current_hp,
+                    atk: 0,
+                };
+            }
+        }
+        compact
+    }
+
+    fn gc_decode_monster(&self, compact: &GcCompactArena, position: usize) -> Option<GcMonster> {
+        if !compact.gc_is_occupied(position) {
+            return None;
+        }
+        let unit = compact.units[position];
+        let mut monster = self.monsters.get(unit.catalog as usize)?.clone();
+        monster.current_hp = unit.hp;
+        monster.can_attack = compact.gc_is_ready(position);
+        Some(monster)
+    }
+
+    fn gc_decode_structure(&self, compact: &GcCompactArena, position: usize) -> Option<GcStructure> {
+        if !compact.gc_is_occupied(position) {
+            return None;
+        }
+        let unit = compact.units[position];
+        let mut structure = self.structures.get(unit.catalog as usize)?.clone();
+        structure.current_hp = unit.hp;
+        Some(structure)
+    }
+
+    /// 解码为完整沙盘
+    pub fn gc_decode(&self, compact: &GcCompactArena) -> GcBattleArena {
+        let monsters = |side, lane| std::array::from_fn(|slot| {
+            self.gc_decode_monster(compact, gc_compact_position(side, lane, slot))
+        });
+        let structures = |side| std::array::from_fn(|slot| {
+            self.gc_decode_structure(compact, gc_compact_position(side, GcCompactLane::Structure, slot))
+        });
+
+        GcBattleArena {
+            player_terrain: compact.terrain[0],
+            enemy_terrain: compact.terrain[1],
+            player_monsters: monsters(GcArenaSide::Player, GcCompactLane::Front),
+            enemy_monsters: monsters(GcArenaSide::Enemy, GcCompactLane::Front),
+            normal_summon_used: compact.normal_summon_used,
+            player_structures: structures(GcArenaSide::Player),
+            enemy_structures: structures(GcArenaSide::Enemy),
+            target_rule: if compact.walls_first { GcStructureTargetRule::WallsFirst } else { GcStructureTargetRule::Free },
+            player_back_row: monsters(GcArenaSide::Player, GcCompactLane::Back),
+            enemy_back_row: monsters(GcArenaSide::Enemy, GcCompactLane::Back),
+        }
+    }
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GcMonsterAttribute, gc_get_structure_template};
+
+    fn create_arena() -> GcBattleArena {
+        let mut arena = GcBattleArena::new(GcTerrainType::Volcano, GcTerrainType::Ocean);
+        let mut fire = GcMonster::new("m1", "火蜥蜴", 3, GcMonsterAttribute::Fire, 80, 40, 70);
+        fire.can_attack = true;
+        fire.star = 2;
+        arena.player_monsters[1] = Some(fire);
+        arena.player_back_row[3] = Some(GcMonster::new("m2", "史莱姆", 1, GcMonsterAttribute::Water, 50, 30, 60));
+        let mut enemy = GcMonster::new("e1", "水精", 2, GcMonsterAttribute::Water, 60, 20, 90);
+        enemy.current_hp = 35;
+        arena.enemy_monsters[0] = Some(enemy);
+        arena.enemy_structures[2] = Some(gc_get_structure_template("stone_wall").expect("模板存在").gc_create_instance("w1"));
+        arena.target_rule = GcStructureTargetRule::WallsFirst;
+        arena
+    }
+
+    #[test]
+    fn test_round_trip_is_equivalent() {
+        let arena = create_arena();
+        let mut catalog = GcCompactCatalog::gc_new();
+        let compact = catalog.gc_encode(&arena);
+        let decoded = catalog.gc_decode(&compact);
+
+        assert_eq!(serde_json::to_value(&decoded).unwrap(), serde_json::to_value(&arena).unwrap());
+        assert_eq!(compact.gc_evaluate(), gc_evaluate_arena(&arena));
+        assert_eq!(compact.gc_side_mask(GcArenaSide::Player).count_ones(), 2);
+        assert!(compact.gc_is_ready(gc_compact_position(GcArenaSide::Player, GcCompactLane::Front, 1)));
+    }
+
+    #[test]
+    fn test_hash_tracks_dynamic_state() {
+        let mut arena = create_arena();
+        let mut catalog = GcCompactCatalog::gc_new();
+        let before = catalog.gc_encode(&arena);
+        assert_eq!(before.gc_hash(), catalog.gc_encode(&arena).gc_hash());
+
+        // 同一只怪兽受伤后复用目录条目，但状态哈希不同
+        arena.enemy_monsters[0].as_mut().unwrap().current_hp = 10;
+        let after = catalog.gc_encode(&arena);
+        assert_eq!(catalog.monsters.len(), 3);
+        assert_ne!(before.gc_hash(), after.gc_hash());
+        assert_eq!(after.gc_evaluate(), gc_evaluate_arena(&arena));
+    }
+}
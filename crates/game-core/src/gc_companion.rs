@@ -0,0 +1,275 @@
+//! 伙伴系统
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! ## 伙伴规则
+//! - 伙伴独立于战场怪兽，不占用槽位、不参与战斗
+//! - 同时只能装备一个伙伴
+//! - 战斗光环: 小幅提升攻击/防御/生命/怪兽速度 (百分比)
+//! - 闲置加成: 提升 RPG 层金币/经验获取 (百分比)，服务器发放对局奖励时按装备的伙伴计算
+//! - 获取途径: 捕获的怪兽转化 (`gc_attempt_capture` 成功时自动加入)、成就奖励
+
+use serde::{Deserialize, Serialize};
+
+use crate::{GcModifierBucket, GcMonster, GcMonsterAttribute, GcPercentStack, GcRewardGrant, gc_apply_bonus_percent};
+
+// =============================================================================
+// 伙伴加成
+// =============================================================================
+
+/// 伙伴战斗光环 (百分比加成)
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcCompanionAura {
+    /// 攻击力加成 (%)
+    pub atk_percent: u32,
+    /// 防御力加成 (%)
+    pub def_percent: u32,
+    /// 最大生命加成 (%)
+    pub hp_percent: u32,
+    /// 怪兽速度加成 (%)
+    #[serde(default)]
+    pub speed_percent: u32,
+}
+
+impl GcCompanionAura {
+    /// 攻击加成 (光环分桶)
+    pub fn atk_stack(&self) -> GcPercentStack {
+        GcPercentStack::gc_new().gc_with_bonus(GcModifierBucket::Aura, self.atk_percent)
+    }
+
+    /// 防御加成 (光环分桶)
+    pub fn def_stack(&self) -> GcPercentStack {
+        GcPercentStack::gc_new().gc_with_bonus(GcModifierBucket::Aura, self.def_percent)
+    }
+
+    /// 生命加成 (光环分桶)
+    pub fn hp_stack(&self) -> GcPercentStack {
+        GcPercentStack::gc_new().gc_with_bonus(GcModifierBucket::Aura, self.hp_percent)
+    }
+
+    /// 速度加成 (光环分桶)
+    pub fn speed_stack(&self) -> GcPercentStack {
+        GcPercentStack::gc_new().gc_with_bonus(GcModifierBucket::Aura, self.speed_percent)
+    }
+
+    /// 应用攻击加成
+    pub fn apply_atk(&self, value: u32) -> u32 {
+        self.atk_stack().gc_apply(value)
+    }
+
+    /// 应用防御加成
+    pub fn apply_def(&self, value: u32) -> u32 {
+        self.def_stack().gc_apply(value)
+    }
+
+    /// 应用生命加成
+    pub fn apply_hp(&self, value: u32) -> u32 {
+        self.hp_stack().gc_apply(value)
+    }
+
+    /// 应用速度加成
+    pub fn apply_speed(&self, value: u32) -> u32 {
+        self.speed_stack().gc_apply(value)
+    }
+}
+
+/// 伙伴闲置加成 (大地图)
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcCompanionIdleBonus {
+    /// 金币获取加成 (%)
+    pub gold_percent: u32,
+    /// 经验获取加成 (%)
+    pub xp_percent: u32,
+}
+
+// =============================================================================
+// 伙伴
+// =============================================================================
+
+/// 伙伴来源
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GcCompanionSource {
+    /// 捕获的怪兽 (模板ID)
+    Captured(String),
+    /// 成就奖励 (成就ID)
+    Achievement(String),
+}
+
+/// 伙伴
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GcCompanion {
+    /// 伙伴ID
+    pub id: String,
+    /// 名称
+    pub name: String,
+    /// 来源
+    pub source: GcCompanionSource,
+    /// 战斗光环
+    pub aura: GcCompanionAura,
+    /// 闲置加成
+    pub idle_bonus: GcCompanionIdleBonus,
+}
+
+impl GcCompanion {
+    /// 由捕获的怪兽转化为伙伴
+    ///
+    /// 光环类型由怪兽属性决定，星级越高加成越大
+    pub fn from_captured(monster: &GcMonster) -> Self {
+        let power = monster.star.clamp(1, 3) as u32;
+        let mut aura = GcCompanionAura::default();
+        let mut idle_bonus = GcCompanionIdleBonus::default();
+
+        match monster.attribute {
+            GcMonsterAttribute::Fire | GcMonsterAttribute::Dark => aura.atk_percent = 2 * power,
+            GcMonsterAttribute::Earth | GcMonsterAttribute::Water => aura.def_percent = 2 * power,
+            GcMonsterAttribute::Light => aura.hp_percent = 2 * power,
+            GcMonsterAttribute::Wind => aura.speed_percent = 5 * power,
+            GcMonsterAttribute::None => idle_bonus.xp_percent = 3 * power,
+        }
+        idle_bonus.gold_percent = 5;
+
+        Self {
+            id: format!("companion_{}", monster.id),
+            name: monster.name.clone(),
+            source: GcCompanionSource::Captured(monster.template_id.clone()),
+            aura,
+            idle_bonus,
+        }
+    }
+
+    /// 成就奖励伙伴
+    pub fn from_achievement(achievement_id: &str, name: &str, aura: GcCompanionAura, idle_bonus: GcCompanionIdleBonus) -> Self {
+        Self {
+            id: format!("companion_{}", achievement_id),
+            name: name.to_string(),
+            source: GcCompanionSource::Achievement(achievement_id.to_string()),
+            aura,
+            idle_bonus,
+        }
+    }
+}
+
+// =============================================================================
+// 伙伴收藏
+// =============================================================================
+
+/// 玩家拥有的伙伴
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct GcCompanionCollection {
+    /// 拥有的伙伴
+    pub owned: Vec<GcCompanion>,
+    /// 当前装备的伙伴ID
+    pub equipped_id: Option<String>,
+}
+
+impl GcCompanionCollection {
+    /// 添加伙伴 (重复ID时忽略)
+    pub fn add(&mut self, companion: GcCompanion) -> bool {
+        if self.owned.iter().any(|c| c.id == companion.id) {
+            return false;
+        }
+        self.owned.push(companion);
+        true
+    }
+
+    /// 装备伙伴
+    pub fn equip(&mut self, companion_id: &str) -> Result<(), String> {
+        if !self.owned.iter().any(|c| c.id == companion_id) {
+            return Err("未拥有该伙伴".to_string());
+        }
+        self.equipped_id = Some(companion_id.to_string());
+        Ok(())
+    }
+
+    /// 卸下伙伴
+    pub fn unequip(&mut self) {
+        self.equipped_id = None;
+    }
+
+    /// 当前装备的伙伴
+    pub fn equipped(&self) -> Option<&GcCompanion> {
+        let id = self.equipped_id.as_ref()?;
+        self.owned.iter().find(|c| &c.id == id)
+    }
+
+    /// 当前战斗光环 (未装备时无加成)
+    pub fn active_aura(&self) -> GcCompanionAura {
+        self.equipped().map(|c| c.aura.clone()).unwrap_or_default()
+    }
+
+    /// 应用闲置金币加成
+    pub fn apply_idle_gold(&self, gold: u32) -> u32 {
+        let percent = self.equipped().map(|c| c.idle_bonus.gold_percent).unwrap_or(0);
+        gc_apply_bonus_percent(gold, percent)
+    }
+
+    /// 应用闲置经验加成
+    pub fn apply_idle_xp(&self, xp: u32) -> u32 {
+        let percent = self.equipped().map(|c| c.idle_bonus.xp_percent).unwrap_or(0);
+        gc_apply_bonus_percent(xp, percent)
+    }
+
+    /// 对一份 RPG 奖励应用闲置金币与经验加成
+    pub fn apply_idle_bonus(&self, grant: GcRewardGrant) -> GcRewardGrant {
+        GcRewardGrant::gc_new(self.apply_idle_gold(grant.gold), self.apply_idle_xp(grant.xp))
+    }
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GcPlayer, GcProfessionType};
+
+    fn create_fire_monster() -> GcMonster {
+        GcMonster::new_with_template("m1", "fire_sprite", "火精灵", 2, GcMonsterAttribute::Fire, 70, 10, 40)
+    }
+
+    #[test]
+    fn test_companion_from_captured() {
+        let companion = GcCompanion::from_captured(&create_fire_monster());
+        assert_eq!(companion.aura.atk_percent, 2);
+        assert_eq!(companion.idle_bonus.gold_percent, 5);
+        assert_eq!(companion.source, GcCompanionSource::Captured("fire_sprite".to_string()));
+    }
+
+    #[test]
+    fn test_collection_equip() {
+        let mut collection = GcCompanionCollection::default();
+        assert!(collection.equip("companion_m1").is_err());
+
+        let companion = GcCompanion::from_captured(&create_fire_monster());
+        assert!(collection.add(companion.clone()));
+        assert!(!collection.add(companion));
+        assert!(collection.equip("companion_m1").is_ok());
+
+        assert_eq!(collection.apply_idle_gold(100), 105);
+        assert_eq!(collection.active_aura().apply_atk(100), 102);
+
+        let boosted = collection.apply_idle_bonus(GcRewardGrant::gc_new(100, 100));
+        assert_eq!(boosted, GcRewardGrant::gc_new(105, 100));
+
+        collection.unequip();
+        assert_eq!(collection.apply_idle_gold(100), 100);
+    }
+
+    #[test]
+    fn test_companion_aura_in_player_stats() {
+        let mut player = GcPlayer::gc_new("p1", "玩家");
+        player.gc_init_rpg(GcProfessionType::Knight);
+        let base_attack = player.stats.attack;
+
+        let aura = GcCompanionAura { atk_percent: 10, ..Default::default() };
+        let companion = GcCompanion::from_achievement("first_boss", "小精灵", aura, GcCompanionIdleBonus::default());
+        player.companions.add(companion);
+        player.companions.equip("companion_first_boss").ok();
+        player.gc_update_rpg_stats();
+
+        assert_eq!(player.stats.attack, base_attack * 110 / 100);
+    }
+}
@@ -0,0 +1,212 @@
+//! 内容版本 (客户端与服务器的规则一致性校验)
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! ## 校验规则
+//! - 线上格式版本 `GC_SCHEMA_VERSION`: 消息或存档结构不兼容时递增，不一致时必须拒绝
+//! - 内容哈希: 对各类模板清单 (卡牌、怪兽池、建筑、天赋、地图) 的规范化 JSON 做 FNV-1a，
+//!   键按字母排序，与 HashMap 的遍历顺序无关，跨平台稳定
+//! - 赛季轮换会改变卡牌与怪兽池，因此哈希按激活的赛季计算
+//! - 内容不一致说明客户端缓存了旧的 WASM，由服务器决定警告或拒绝
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    gc_create_assassin_talent_tree, gc_create_boss_arena_map, gc_create_forest_map,
+    gc_create_gunner_talent_tree, gc_create_knight_talent_tree, gc_create_swordsman_talent_tree,
+    gc_create_town_map, gc_create_warlock_talent_tree, gc_default_structure_templates,
+    gc_get_card_template, GcMonsterPool, GcSeason, GC_CARD_TEMPLATE_IDS,
+};
+
+/// 线上格式版本 (消息、存档结构不兼容时递增)
+pub const GC_SCHEMA_VERSION: u32 = 1;
+
+// =============================================================================
+// 内容版本
+// =============================================================================
+
+/// 内容版本清单
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcContentVersion {
+    /// 线上格式版本
+    pub schema_version: u32,
+    /// 计算时激活的赛季 ID (无赛季为空)
+    #[serde(default)]
+    pub season_id: String,
+    /// 卡牌模板哈希
+    pub cards: String,
+    /// 怪兽池哈希
+    pub monsters: String,
+    /// 建筑模板哈希
+    pub structures: String,
+    /// 天赋树哈希
+    pub talents: String,
+    /// 地图模板哈希
+    pub maps: String,
+}
+
+/// 与服务器内容版本的比较结果
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GcContentCheck {
+    /// 完全一致
+    Match,
+    /// 线上格式一致，部分内容不一致 (列出不一致的清单名)
+    Stale { components: Vec<String> },
+    /// 线上格式不兼容
+    Incompatible { client_schema: u32, server_schema: u32 },
+}
+
+impl GcContentVersion {
+    /// 按赛季计算内容版本 (None 表示使用全部默认内容)
+    pub fn gc_compute(season: Option<&GcSeason>) -> Self {
+        let cards: Vec<_> = GC_CARD_TEMPLATE_IDS
+            .iter()
+            .filter_map(|id| match season {
+                Some(s) => s.gc_get_card_template(id),
+                None => gc_get_card_template(id),
+            })
+            .collect();
+        let monsters = season.map_or_else(GcMonsterPool::with_defaults, |s| s.gc_monster_pool());
+        let talents = [
+            gc_create_knight_talent_tree(),
+            gc_create_swordsman_talent_tree(),
+            gc_create_warlock_talent_tree(),
+            gc_create_gunner_talent_tree(),
+            gc_create_assassin_talent_tree(),
+        ];
+        let maps = [gc_create_town_map(), gc_create_forest_map(), gc_create_boss_arena_map()];
+
+        Self {
+            schema_version: GC_SCHEMA_VERSION,
+            season_id: season.map(|s| s.id.clone()).unwrap_or_default(),
+            cards: gc_content_hash(&cards),
+            monsters: gc_content_hash(&monsters),
+            structures: gc_content_hash(&gc_default_structure_templates()),
+            talents: gc_content_hash(&talents),
+            maps: gc_content_hash(&maps),
+        }
+    }
+
+    /// 各清单 (名称, 哈希)
+    fn gc_components(&self) -> [(&'static str, &str); 5] {
+        [
+            ("cards", &self.cards),
+            ("monsters", &self.monsters),
+            ("structures", &self.structures),
+            ("talents", &self.talents),
+            ("maps", &self.maps),
+        ]
+    }
+
+    /// 以服务器版本 (self) 检查客户端版本
+    pub fn gc_check(&self, client: &GcContentVersion) -> GcContentCheck {
+        if client.schema_version != self.schema_version {
+            return GcContentCheck::Incompatible {
+                client_schema: client.schema_version,
+                server_schema: self.schema_version,
+            };
+        }
+        let components: Vec<String> = self
+            .gc_components()
+            .iter()
+            .zip(client.gc_components().iter())
+            .filter(|(server, client)| server.1 != client.1)
+            .map(|(server, _)| server.0.to_string())
+            .collect();
+        if components.is_empty() {
+            GcContentCheck::Match
+        } else {
+            GcContentCheck::Stale { components }
+        }
+    }
+}
+
+// =============================================================================
+// 哈希
+// =============================================================================
+
+/// 内容哈希 (规范化 JSON 的 FNV-1a，16 位十六进制)
+pub fn gc_content_hash<T: Serialize>(value: &T) -> String {
+    let mut canonical = String::new();
+    if let Ok(json) = serde_json::to_value(value) {
+        gc_write_canonical(&json, &mut canonical);
+    }
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in canonical.bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    format!("{:016x}", hash)
+}
+
+/// 写出键排序后的 JSON
+fn gc_write_canonical(value: &serde_json::Value, out: &mut String) {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort();
+            out.push('{');
+            for (i, key) in keys.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&serde_json::Value::String((*key).clone()).to_string());
+                out.push(':');
+                gc_write_canonical(&map[*key], out);
+            }
+            out.push('}');
+        }
+        serde_json::Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                gc_write_canonical(item, out);
+            }
+            out.push(']');
+        }
+        other => out.push_str(&other.to_string()),
+    }
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GcSeasonContent, GcSeasonManager};
+
+    #[test]
+    fn test_content_hash_is_stable_and_order_independent() {
+        let a = serde_json::json!({ "b": 1, "a": [1, 2, { "y": true, "x": null }] });
+        let b = serde_json::json!({ "a": [1, 2, { "x": null, "y": true }], "b": 1 });
+        assert_eq!(gc_content_hash(&a), gc_content_hash(&b));
+        assert_ne!(gc_content_hash(&a), gc_content_hash(&serde_json::json!({ "b": 2 })));
+
+        assert_eq!(GcContentVersion::gc_compute(None), GcContentVersion::gc_compute(None));
+        assert!(GC_CARD_TEMPLATE_IDS.iter().all(|id| gc_get_card_template(id).is_some()));
+    }
+
+    #[test]
+    fn test_check_reports_stale_components() {
+        let server = GcContentVersion::gc_compute(None);
+        assert_eq!(server.gc_check(&server), GcContentCheck::Match);
+
+        // 赛季禁用卡牌后，卡牌哈希变化
+        let seasons = GcSeasonManager::with_defaults();
+        let season = seasons.current_season().cloned().map(|s| s.with_content(GcSeasonContent {
+            banned_cards: vec!["card_knight_ult".to_string()],
+            ..Default::default()
+        }));
+        let client = GcContentVersion::gc_compute(season.as_ref());
+        assert_eq!(server.gc_check(&client), GcContentCheck::Stale { components: vec!["cards".to_string()] });
+
+        let old = GcContentVersion { schema_version: 0, ..server.clone() };
+        assert!(matches!(server.gc_check(&old), GcContentCheck::Incompatible { client_schema: 0, .. }));
+    }
+}
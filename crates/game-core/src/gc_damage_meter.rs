@@ -0,0 +1,171 @@
+//! 伤害统计 (团队副本 / 世界 Boss)
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! ## 规则
+//! - 按玩家累计伤害与治疗，时间由调用方提供 (Unix 秒)
+//! - DPS/HPS = 累计量 / 战斗时长 (从第一次记录起，至少 1 秒)
+//! - 快照按伤害降序，并给出每名玩家的伤害占比 (万分比)
+
+use serde::{Deserialize, Serialize};
+
+// =============================================================================
+// 统计数据
+// =============================================================================
+
+/// 单名玩家的统计
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcMeterEntry {
+    /// 玩家ID
+    pub player_id: String,
+    /// 累计伤害
+    pub damage: u64,
+    /// 累计治疗
+    pub healing: u64,
+    /// 攻击次数
+    pub hits: u32,
+}
+
+/// 快照中的一行
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcMeterRow {
+    /// 玩家ID
+    pub player_id: String,
+    /// 累计伤害
+    pub damage: u64,
+    /// 累计治疗
+    pub healing: u64,
+    /// 每秒伤害
+    pub dps: u64,
+    /// 每秒治疗
+    pub hps: u64,
+    /// 伤害占比 (万分比)
+    pub damage_basis_points: u64,
+}
+
+/// 统计快照
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcMeterSnapshot {
+    /// 战斗时长 (秒)
+    pub elapsed_secs: u64,
+    /// 全队伤害
+    pub total_damage: u64,
+    /// 全队治疗
+    pub total_healing: u64,
+    /// 各玩家数据 (按伤害降序)
+    pub rows: Vec<GcMeterRow>,
+}
+
+/// 伤害统计
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct GcDamageMeter {
+    /// 第一次记录的时间
+    pub started_at: Option<u64>,
+    /// 各玩家统计
+    pub entries: Vec<GcMeterEntry>,
+}
+
+impl GcDamageMeter {
+    /// 创建空统计
+    pub fn gc_new() -> Self {
+        Self::default()
+    }
+
+    /// 获取或创建玩家条目
+    fn gc_entry(&mut self, player_id: &str, now: u64) -> &mut GcMeterEntry {
+        self.started_at.get_or_insert(now);
+        let idx = match self.entries.iter().position(|e| e.player_id == player_id) {
+            Some(idx) => idx,
+            None => {
+                self.entries.push(GcMeterEntry {
+                    player_id: player_id.to_string(),
+                    ..Default::default()
+                });
+                self.entries.len() - 1
+            }
+        };
+        &mut self.entries[idx]
+    }
+
+    /// 记录伤害
+    pub fn gc_record_damage(&mut self, player_id: &str, amount: u32, now: u64) {
+        let entry = self.gc_entry(player_id, now);
+        entry.damage += amount as u64;
+        entry.hits += 1;
+    }
+
+    /// 记录治疗
+    pub fn gc_record_heal(&mut self, player_id: &str, amount: u32, now: u64) {
+        self.gc_entry(player_id, now).healing += amount as u64;
+    }
+
+    /// 是否有参与者
+    pub fn gc_has_participant(&self, player_id: &str) -> bool {
+        self.entries.iter().any(|e| e.player_id == player_id)
+    }
+
+    /// 参与者ID
+    pub fn gc_participants(&self) -> Vec<String> {
+        self.entries.iter().map(|e| e.player_id.clone()).collect()
+    }
+
+    /// 生成快照
+    pub fn gc_snapshot(&self, now: u64) -> GcMeterSnapshot {
+        let elapsed_secs = self.started_at
+            .map(|start| now.saturating_sub(start))
+            .unwrap_or(0);
+        let divisor = elapsed_secs.max(1);
+        let total_damage: u64 = self.entries.iter().map(|e| e.damage).sum();
+        let total_healing: u64 = self.entries.iter().map(|e| e.healing).sum();
+
+        let mut rows: Vec<GcMeterRow> = self.entries.iter()
+            .map(|e| GcMeterRow {
+                player_id: e.player_id.clone(),
+                damage: e.damage,
+                healing: e.healing,
+                dps: e.damage / divisor,
+                hps: e.healing / divisor,
+                damage_basis_points: (e.damage * 10_000).checked_div(total_damage).unwrap_or(0),
+            })
+            .collect();
+        rows.sort_by_key(|r| std::cmp::Reverse(r.damage));
+
+        GcMeterSnapshot {
+            elapsed_secs,
+            total_damage,
+            total_healing,
+            rows,
+        }
+    }
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_meter_snapshot() {
+        let mut meter = GcDamageMeter::gc_new();
+        meter.gc_record_damage("dps", 300, 100);
+        meter.gc_record_damage("tank", 100, 105);
+        meter.gc_record_heal("healer", 500, 108);
+        meter.gc_record_damage("dps", 300, 110);
+
+        let snapshot = meter.gc_snapshot(110);
+        assert_eq!(snapshot.elapsed_secs, 10);
+        assert_eq!(snapshot.total_damage, 700);
+        assert_eq!(snapshot.rows[0].player_id, "dps");
+        assert_eq!(snapshot.rows[0].dps, 60);
+        assert_eq!(snapshot.rows[0].damage_basis_points, 8571);
+
+        let healer = snapshot.rows.iter().find(|r| r.player_id == "healer").expect("治疗者存在");
+        assert_eq!(healer.hps, 50);
+        assert_eq!(meter.gc_participants().len(), 3);
+    }
+}
@@ -0,0 +1,190 @@
+//! 伤害类型与抗性
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! ## 规则
+//! - 伤害分为 物理/火焰/冰霜/暗影/神圣 五种类型
+//! - 单位持有抗性表 (百分比): 正数减伤，负数为易伤
+//! - 怪兽抗性由属性决定，玩家抗性来自装备
+//! - 抗性上限 75%，易伤下限 -100%
+//! - 结算顺序: 原始伤害 → 防御减免 → 类型抗性 → 最终伤害
+
+use serde::{Deserialize, Serialize};
+
+use crate::{GcDamageResult, GcMonsterAttribute, gc_apply_percent};
+
+// =============================================================================
+// 常量
+// =============================================================================
+
+/// 抗性上限 (%)
+pub const GC_MAX_RESISTANCE: i32 = 75;
+
+/// 易伤下限 (%)
+pub const GC_MIN_RESISTANCE: i32 = -100;
+
+// =============================================================================
+// 伤害类型
+// =============================================================================
+
+/// 伤害类型
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum GcDamageType {
+    /// 物理
+    #[default]
+    Physical,
+    /// 火焰
+    Fire,
+    /// 冰霜
+    Frost,
+    /// 暗影
+    Shadow,
+    /// 神圣
+    Holy,
+}
+
+impl GcDamageType {
+    /// 显示名称
+    pub fn gc_name(&self) -> &'static str {
+        match self {
+            GcDamageType::Physical => "物理",
+            GcDamageType::Fire => "火焰",
+            GcDamageType::Frost => "冰霜",
+            GcDamageType::Shadow => "暗影",
+            GcDamageType::Holy => "神圣",
+        }
+    }
+}
+
+// =============================================================================
+// 抗性表
+// =============================================================================
+
+/// 抗性表 (百分比，正数减伤，负数易伤)
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcResistances {
+    pub physical: i32,
+    pub fire: i32,
+    pub frost: i32,
+    pub shadow: i32,
+    pub holy: i32,
+}
+
+impl GcResistances {
+    /// 由怪兽属性生成抗性 (只影响元素伤害，物理不变)
+    pub fn gc_from_attribute(attribute: GcMonsterAttribute) -> Self {
+        match attribute {
+            GcMonsterAttribute::Fire => Self { fire: 50, frost: -25, ..Default::default() },
+            GcMonsterAttribute::Water => Self { frost: 50, fire: 25, ..Default::default() },
+            GcMonsterAttribute::Wind => Self { frost: 25, fire: -25, ..Default::default() },
+            GcMonsterAttribute::Earth => Self { fire: 25, frost: -25, ..Default::default() },
+            GcMonsterAttribute::Light => Self { holy: 50, shadow: -50, ..Default::default() },
+            GcMonsterAttribute::Dark => Self { shadow: 50, holy: -50, ..Default::default() },
+            GcMonsterAttribute::None => Self::default(),
+        }
+    }
+
+    /// 获取某类型的抗性 (已限制在上下限内)
+    pub fn gc_get(&self, damage_type: GcDamageType) -> i32 {
+        let raw = match damage_type {
+            GcDamageType::Physical => self.physical,
+            GcDamageType::Fire => self.fire,
+            GcDamageType::Frost => self.frost,
+            GcDamageType::Shadow => self.shadow,
+            GcDamageType::Holy => self.holy,
+        };
+        raw.clamp(GC_MIN_RESISTANCE, GC_MAX_RESISTANCE)
+    }
+
+    /// 合并抗性 (叠加)
+    pub fn gc_merge(&mut self, other: &GcResistances) {
+        self.physical = self.physical.saturating_add(other.physical);
+        self.fire = self.fire.saturating_add(other.fire);
+        self.frost = self.frost.saturating_add(other.frost);
+        self.shadow = self.shadow.saturating_add(other.shadow);
+        self.holy = self.holy.saturating_add(other.holy);
+    }
+
+    /// 对伤害应用抗性
+    pub fn gc_apply(&self, damage: u32, damage_type: GcDamageType) -> u32 {
+        gc_apply_percent(damage, -self.gc_get(damage_type))
+    }
+}
+
+// =============================================================================
+// 统一伤害结算
+// =============================================================================
+
+/// 统一伤害结算: 原始伤害 → 防御减免 → 类型抗性
+pub fn gc_resolve_damage(
+    raw_damage: u32,
+    defense_reduction: u32,
+    damage_type: GcDamageType,
+    resistances: &GcResistances,
+) -> GcDamageResult {
+    let after_defense = raw_damage.saturating_sub(defense_reduction);
+    let final_damage = resistances.gc_apply(after_defense, damage_type);
+
+    let mut result = GcDamageResult::new(raw_damage, raw_damage.saturating_sub(final_damage), final_damage);
+    result.damage_type = damage_type;
+    result.defense_reduction = defense_reduction;
+    result.resistance_percent = resistances.gc_get(damage_type);
+    result
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_attribute_resistances() {
+        let fire = GcResistances::gc_from_attribute(GcMonsterAttribute::Fire);
+        assert_eq!(fire.gc_apply(100, GcDamageType::Fire), 50);
+        assert_eq!(fire.gc_apply(100, GcDamageType::Frost), 125);
+        assert_eq!(fire.gc_apply(100, GcDamageType::Physical), 100);
+    }
+
+    #[test]
+    fn test_resistance_clamped() {
+        let res = GcResistances { shadow: 200, holy: -300, ..Default::default() };
+        assert_eq!(res.gc_get(GcDamageType::Shadow), GC_MAX_RESISTANCE);
+        assert_eq!(res.gc_apply(100, GcDamageType::Holy), 200);
+    }
+
+    #[test]
+    fn test_resolve_damage_breakdown() {
+        let res = GcResistances { fire: 25, ..Default::default() };
+        let result = gc_resolve_damage(50, 10, GcDamageType::Fire, &res);
+        assert_eq!(result.final_damage, 30);
+        assert_eq!(result.reduced_damage, 20);
+        assert_eq!(result.gc_breakdown(), "50 火焰伤害 - 防御 10 - 抗性 25% = 30");
+
+        // 易伤使最终伤害超过原始伤害时，减免记为 0
+        let weak = GcResistances { frost: -50, ..Default::default() };
+        let result = gc_resolve_damage(40, 0, GcDamageType::Frost, &weak);
+        assert_eq!((result.final_damage, result.reduced_damage), (60, 0));
+    }
+
+    #[test]
+    fn test_monster_battle_uses_resistance() {
+        use crate::{GcMonster, GcTerrainType, gc_calculate_battle_damage};
+
+        let mut attacker = GcMonster::new("a", "火精灵", 2, GcMonsterAttribute::Fire, 100, 10, 40);
+        attacker.damage_type = GcDamageType::Fire;
+        let water = GcMonster::new("w", "史莱姆", 1, GcMonsterAttribute::Water, 50, 20, 60);
+        let wind = GcMonster::new("h", "鹰身女妖", 2, GcMonsterAttribute::Wind, 50, 20, 60);
+
+        let result = gc_calculate_battle_damage(&attacker, &water, GcTerrainType::Plain, GcTerrainType::Plain);
+        assert_eq!(result.damage, 60);
+        assert_eq!(result.resistance_percent, 25);
+
+        let result = gc_calculate_battle_damage(&attacker, &wind, GcTerrainType::Plain, GcTerrainType::Plain);
+        assert_eq!(result.damage, 100);
+    }
+}
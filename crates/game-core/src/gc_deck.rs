@@ -0,0 +1,338 @@
+//! 卡组与备牌 (系列赛)
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! ## 规则
+//! - 卡组由主牌 (模板ID列表) 与备牌区组成，备牌区最多 15 张
+//! - 系列赛 (如 BO3) 每局结束后进入换牌阶段
+//! - 换牌阶段每名玩家可用备牌替换主牌，每次最多 3 张，一进一出
+//! - 所有玩家提交换牌后开始下一局；一方先达到胜场要求则系列赛结束
+//! - 闪卡外观 (`foils`) 不超过卡组中对应模板的张数，换牌不影响外观
+
+use serde::{Deserialize, Serialize};
+
+use crate::{GcCard, GcCardTemplateId, gc_get_card_template};
+
+// =============================================================================
+// 常量
+// =============================================================================
+
+/// 备牌区上限
+pub const GC_SIDEBOARD_MAX_SIZE: usize = 15;
+
+/// 换牌阶段每名玩家最多替换的卡牌数
+pub const GC_SIDEBOARD_MAX_SWAPS: usize = 3;
+
+// =============================================================================
+// 卡组
+// =============================================================================
+
+/// 卡组
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcDeck {
+    /// 卡组ID
+    pub id: String,
+    /// 卡组名称
+    pub name: String,
+    /// 主牌 (卡牌模板ID，可重复)
+    pub cards: Vec<GcCardTemplateId>,
+    /// 备牌区
+    #[serde(default)]
+    pub sideboard: Vec<GcCardTemplateId>,
+    /// 以闪卡外观出场的副本 (模板ID，按张数重复；纯装饰)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub foils: Vec<GcCardTemplateId>,
+}
+
+/// 一次换牌: 从主牌移出一张，从备牌区换入一张
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcSideboardSwap {
+    /// 移出主牌的模板ID
+    pub card_out: GcCardTemplateId,
+    /// 从备牌区换入的模板ID
+    pub card_in: GcCardTemplateId,
+}
+
+impl GcDeck {
+    /// 创建卡组 (无备牌)
+    pub fn gc_new(id: &str, name: &str, cards: Vec<GcCardTemplateId>) -> Self {
+        Self {
+            id: id.to_string(),
+            name: name.to_string(),
+            cards,
+            sideboard: Vec::new(),
+            foils: Vec::new(),
+        }
+    }
+
+    /// 验证卡组
+    pub fn gc_validate(&self) -> Result<(), String> {
+        if self.cards.is_empty() {
+            return Err("卡组不能为空".to_string());
+        }
+        if self.sideboard.len() > GC_SIDEBOARD_MAX_SIZE {
+            return Err(format!("备牌区最多 {} 张", GC_SIDEBOARD_MAX_SIZE));
+        }
+        if let Some(unknown) = self.cards.iter()
+            .chain(self.sideboard.iter())
+            .find(|id| gc_get_card_template(id).is_none())
+        {
+            return Err(format!("未知卡牌: {}", unknown));
+        }
+        if let Some(foil) = self.foils.iter().find(|id| {
+            let foils = self.foils.iter().filter(|f| f == id).count();
+            self.cards.iter().chain(self.sideboard.iter()).filter(|c| c == id).count() < foils
+        }) {
+            return Err(format!("闪卡 {} 超过卡组中的张数", foil));
+        }
+        Ok(())
+    }
+
+    /// 应用换牌 (全部成功才生效)
+    pub fn gc_apply_sideboard(&mut self, swaps: &[GcSideboardSwap]) -> Result<(), String> {
+        if swaps.len() > GC_SIDEBOARD_MAX_SWAPS {
+            return Err(format!("每次最多替换 {} 张卡牌", GC_SIDEBOARD_MAX_SWAPS));
+        }
+
+        let mut cards = self.cards.clone();
+        let mut sideboard = self.sideboard.clone();
+        for swap in swaps {
+            let out_idx = cards.iter().position(|c| *c == swap.card_out)
+                .ok_or_else(|| format!("主牌中没有 {}", swap.card_out))?;
+            let in_idx = sideboard.iter().position(|c| *c == swap.card_in)
+                .ok_or_else(|| format!("备牌区中没有 {}", swap.card_in))?;
+            let card_in = sideboard.remove(in_idx);
+            sideboard.push(cards.remove(out_idx));
+            cards.push(card_in);
+        }
+
+        self.cards = cards;
+        self.sideboard = sideboard;
+        Ok(())
+    }
+
+    /// 生成主牌的卡牌实例 (每个模板的前若干张使用闪卡外观)
+    pub fn gc_build_cards(&self, instance_prefix: &str) -> Vec<GcCard> {
+        let mut foils_left = self.foils.clone();
+        self.cards.iter()
+            .enumerate()
+            .filter_map(|(i, template_id)| {
+                let mut card = gc_get_card_template(template_id)?;
+                card.id = format!("{}_{}", instance_prefix, i);
+                if let Some(pos) = foils_left.iter().position(|f| f == template_id) {
+                    foils_left.swap_remove(pos);
+                    card.foil = true;
+                }
+                Some(card)
+            })
+            .collect()
+    }
+}
+
+// =============================================================================
+// 系列赛
+// =============================================================================
+
+/// 系列赛阶段
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GcSeriesPhase {
+    /// 对局中
+    #[default]
+    InGame,
+    /// 局间换牌
+    BetweenGames,
+    /// 系列赛结束
+    Finished,
+}
+
+/// 玩家系列赛战绩
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcSeriesScore {
+    /// 玩家ID
+    pub player_id: String,
+    /// 胜场
+    pub wins: u32,
+    /// 本次换牌阶段是否已提交
+    pub sideboard_submitted: bool,
+}
+
+/// 系列赛 (BO3 等)
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GcSeries {
+    /// 总局数 (奇数)
+    pub best_of: u32,
+    /// 当前局数 (从 1 开始)
+    pub game_number: u32,
+    /// 阶段
+    pub phase: GcSeriesPhase,
+    /// 各玩家战绩
+    pub scores: Vec<GcSeriesScore>,
+}
+
+impl GcSeries {
+    /// 创建系列赛
+    pub fn gc_new(best_of: u32, player_ids: &[String]) -> Self {
+        Self {
+            best_of: best_of.max(1),
+            game_number: 1,
+            phase: GcSeriesPhase::InGame,
+            scores: player_ids.iter()
+                .map(|id| GcSeriesScore {
+                    player_id: id.clone(),
+                    wins: 0,
+                    sideboard_submitted: false,
+                })
+                .collect(),
+        }
+    }
+
+    /// 获胜所需胜场
+    pub fn gc_wins_needed(&self) -> u32 {
+        self.best_of / 2 + 1
+    }
+
+    /// 系列赛胜者
+    pub fn gc_winner(&self) -> Option<&str> {
+        let needed = self.gc_wins_needed();
+        self.scores.iter()
+            .find(|s| s.wins >= needed)
+            .map(|s| s.player_id.as_str())
+    }
+
+    /// 记录一局结果，进入换牌阶段或结束
+    pub fn gc_record_game(&mut self, winner_id: Option<&str>) -> Result<GcSeriesPhase, String> {
+        if self.phase != GcSeriesPhase::InGame {
+            return Err("当前不在对局中".to_string());
+        }
+
+        if let Some(score) = winner_id.and_then(|id| self.scores.iter_mut().find(|s| s.player_id == id)) {
+            score.wins += 1;
+        }
+
+        self.phase = if self.gc_winner().is_some() || self.game_number >= self.best_of {
+            GcSeriesPhase::Finished
+        } else {
+            for score in &mut self.scores {
+                score.sideboard_submitted = false;
+            }
+            GcSeriesPhase::BetweenGames
+        };
+        Ok(self.phase)
+    }
+
+    /// 提交换牌 (可为空表示不换)
+    pub fn gc_submit_sideboard(
+        &mut self,
+        player_id: &str,
+        deck: &mut GcDeck,
+        swaps: &[GcSideboardSwap],
+    ) -> Result<(), String> {
+        if self.phase != GcSeriesPhase::BetweenGames {
+            return Err("当前不是换牌阶段".to_string());
+        }
+        let score = self.scores.iter_mut()
+            .find(|s| s.player_id == player_id)
+            .ok_or("玩家不在系列赛中")?;
+        if score.sideboard_submitted {
+            return Err("本阶段已提交换牌".to_string());
+        }
+
+        deck.gc_apply_sideboard(swaps)?;
+        score.sideboard_submitted = true;
+        Ok(())
+    }
+
+    /// 是否所有玩家都已提交换牌
+    pub fn gc_all_submitted(&self) -> bool {
+        self.scores.iter().all(|s| s.sideboard_submitted)
+    }
+
+    /// 开始下一局
+    pub fn gc_start_next_game(&mut self) -> Result<u32, String> {
+        if self.phase != GcSeriesPhase::BetweenGames {
+            return Err("当前不是换牌阶段".to_string());
+        }
+        if !self.gc_all_submitted() {
+            return Err("还有玩家未提交换牌".to_string());
+        }
+        self.game_number += 1;
+        self.phase = GcSeriesPhase::InGame;
+        Ok(self.game_number)
+    }
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    fn create_deck() -> GcDeck {
+        let mut deck = GcDeck::gc_new("d1", "骑士", ids(&["card_knight_attack", "card_knight_attack", "card_knight_skill"]));
+        deck.sideboard = ids(&["card_knight_ult"]);
+        deck
+    }
+
+    #[test]
+    fn test_deck_validation() {
+        assert!(create_deck().gc_validate().is_ok());
+
+        let mut deck = create_deck();
+        deck.sideboard.push("card_unknown".to_string());
+        assert!(deck.gc_validate().is_err());
+
+        deck.sideboard = vec!["card_knight_ult".to_string(); GC_SIDEBOARD_MAX_SIZE + 1];
+        assert!(deck.gc_validate().is_err());
+
+        // 闪卡外观不能超过卡组中的张数
+        let mut deck = create_deck();
+        deck.foils = vec![deck.cards[0].clone()];
+        assert!(deck.gc_validate().is_ok());
+        deck.foils.push("card_assassin_ult".to_string());
+        assert!(deck.gc_validate().is_err());
+    }
+
+    #[test]
+    fn test_apply_sideboard_is_atomic() {
+        let mut deck = create_deck();
+        let swap = GcSideboardSwap {
+            card_out: "card_knight_attack".to_string(),
+            card_in: "card_knight_ult".to_string(),
+        };
+
+        // 备牌区只有一张 ult，第二次换入失败，整体不生效
+        assert!(deck.gc_apply_sideboard(&[swap.clone(), swap.clone()]).is_err());
+        assert_eq!(deck, create_deck());
+
+        assert!(deck.gc_apply_sideboard(&[swap]).is_ok());
+        assert_eq!(deck.cards.len(), 3);
+        assert!(deck.cards.contains(&"card_knight_ult".to_string()));
+        assert_eq!(deck.sideboard, ids(&["card_knight_attack"]));
+        assert_eq!(deck.gc_build_cards("p1").len(), 3);
+    }
+
+    #[test]
+    fn test_best_of_three_flow() {
+        let mut series = GcSeries::gc_new(3, &ids(&["p1", "p2"]));
+        let mut deck = create_deck();
+
+        assert_eq!(series.gc_record_game(Some("p1")), Ok(GcSeriesPhase::BetweenGames));
+        assert!(series.gc_start_next_game().is_err());
+
+        assert!(series.gc_submit_sideboard("p1", &mut deck, &[]).is_ok());
+        assert!(series.gc_submit_sideboard("p1", &mut deck, &[]).is_err());
+        assert!(series.gc_submit_sideboard("p2", &mut create_deck(), &[]).is_ok());
+        assert_eq!(series.gc_start_next_game(), Ok(2));
+
+        assert_eq!(series.gc_record_game(Some("p1")), Ok(GcSeriesPhase::Finished));
+        assert_eq!(series.gc_winner(), Some("p1"));
+    }
+}
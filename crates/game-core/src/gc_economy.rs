@@ -71,7 +71,7 @@ pub const GC_STREAK_BONUS: [u32; 8] = [
 // =============================================================================
 
 /// 玩家经济状态
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct GcEconomy {
     /// 当前金币
     pub gold: u32,
@@ -5,7 +5,7 @@
 //! 文档: 文档/01-game-core.md
 
 use serde::{Deserialize, Serialize};
-use crate::{GcPlayerId, GcTargetType};
+use crate::{gc_format_number, GcCardType, GcLocale, GcPlayerId, GcTargetType};
 
 // =============================================================================
 // 效果类型
@@ -46,6 +46,10 @@ pub enum GcEffectType {
     ApplyWeak,
     /// 嘲讽
     Taunt,
+    /// 查看公共卡池抽牌堆顶 N 张 (数值为张数)
+    PeekPool,
+    /// 从公共卡池检索一张指定类型的卡牌加入手牌
+    TutorPool(GcCardType),
 }
 
 // =============================================================================
@@ -53,7 +57,7 @@ pub enum GcEffectType {
 // =============================================================================
 
 /// 效果定义
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct GcEffect {
     /// 效果类型
     pub effect_type: GcEffectType,
@@ -116,6 +120,28 @@ impl GcEffect {
         }
     }
     
+    /// 创建查看卡池效果
+    pub fn gc_peek_pool(count: i32) -> Self {
+        Self {
+            effect_type: GcEffectType::PeekPool,
+            value: count,
+            duration: 0,
+            name: "预见".to_string(),
+            target: GcTargetType::SelfTarget,
+        }
+    }
+    
+    /// 创建检索效果
+    pub fn gc_tutor_pool(card_type: GcCardType) -> Self {
+        Self {
+            effect_type: GcEffectType::TutorPool(card_type),
+            value: 1,
+            duration: 0,
+            name: "检索".to_string(),
+            target: GcTargetType::SelfTarget,
+        }
+    }
+    
     /// 创建中毒效果
     pub fn gc_poison(damage: i32, duration: u32) -> Self {
         Self {
@@ -137,6 +163,18 @@ impl GcEffect {
             target: GcTargetType::SingleEnemy,
         }
     }
+    
+    /// 效果描述 (数值按语言格式化): "伤害 1,200"、"中毒 3 (2 回合)"
+    pub fn gc_description_in(&self, locale: GcLocale) -> String {
+        let mut text = self.name.clone();
+        if self.value != 0 {
+            text = format!("{} {}", text, gc_format_number(i64::from(self.value), locale));
+        }
+        if self.duration > 0 {
+            text = format!("{} ({} 回合)", text, gc_format_number(i64::from(self.duration), locale));
+        }
+        text
+    }
 }
 
 // =============================================================================
@@ -220,6 +258,13 @@ impl GcActiveEffect {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_gc_effect_description_formats_values() {
+        assert_eq!(GcEffect::gc_damage(1200).gc_description_in(GcLocale::EnUs), "伤害 1,200");
+        assert_eq!(GcEffect::gc_damage(1200).gc_description_in(GcLocale::DeDe), "伤害 1.200");
+        assert_eq!(GcEffect::gc_stun(2).gc_description_in(GcLocale::ZhCn), "眩晕 (2 回合)");
+    }
+
     #[test]
     fn test_gc_effect_damage() {
         let effect = GcEffect::gc_damage(20);
@@ -0,0 +1,311 @@
+//! 遭遇生成 (根据地图上下文组建敌方队伍)
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! ## 生成规则
+//! - 怪物从刷怪区域的 `monster_ids` 中选取，队伍 1~3 只，Boss 区域固定 1 只
+//! - 等级在区域等级范围内随机，但不超过玩家等级 (区域最低等级除外)
+//! - 敌人类型取自区域，参与战斗地形权重；按位置生成时再叠加海拔修正
+//! - 掉落表引用区域的 `loot_table`，未设置时为 `loot_<区域ID>`
+//! - 相同输入 (世界地形、区域、玩家等级、种子) 在客户端与服务器得到相同结果
+//! - 难度由 `gc_with_difficulty` 在生成后应用 (等级缩放)，属性/掉落/经验倍率随遭遇下发
+//! - 昼夜: 只在当前时段出现怪物的区域生成遭遇，时段等级加成由 `gc_with_time_of_day` 应用
+//! - 天气: 区域配置了该天气的怪物表时从中选取；天气混入随机种子、修正战斗地形权重并附加等级加成
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    gc_generate_battle_terrain_in, gc_simulation_rng, GcDayPhase, GcDifficultySettings, GcEnemyType, GcMap,
+    GcPosition, GcSpawnRegion, GcTerrainType, GcWeather, GcWorldTerrainType,
+};
+
+/// 普通遭遇的最大队伍人数
+pub const GC_ENCOUNTER_MAX_PARTY: usize = 3;
+
+// =============================================================================
+// 遭遇数据
+// =============================================================================
+
+/// 遭遇中的敌人
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcEncounterEnemy {
+    /// 怪物 ID
+    pub monster_id: String,
+    /// 等级
+    pub level: u32,
+}
+
+/// 遭遇 (一场战斗的敌方配置)
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcEncounter {
+    /// 来源刷怪区域 ID
+    pub region_id: String,
+    /// 敌方队伍
+    pub enemies: Vec<GcEncounterEnemy>,
+    /// 敌人类型 (地形权重)
+    pub enemy_type: GcEnemyType,
+    /// 玩家区域战斗地形
+    pub player_terrain: GcTerrainType,
+    /// 敌人区域战斗地形
+    pub enemy_terrain: GcTerrainType,
+    /// 掉落表引用
+    pub loot_table: String,
+    /// 生成种子
+    pub seed: u64,
+    /// 难度设置 (战斗与结算按此缩放敌人属性、掉落与经验)
+    #[serde(default)]
+    pub difficulty: GcDifficultySettings,
+    /// 遭遇发生的时段 (未按时段生成时为空)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_of_day: Option<GcDayPhase>,
+    /// 遭遇发生时的天气 (未按天气生成时为空)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weather: Option<GcWeather>,
+}
+
+impl GcEncounter {
+    /// 应用存档的难度设置 (开启等级缩放时敌人不低于玩家等级)
+    pub fn gc_with_difficulty(mut self, difficulty: GcDifficultySettings, player_level: u32) -> Self {
+        for enemy in &mut self.enemies {
+            enemy.level = difficulty.gc_scale_enemy_level(enemy.level, player_level);
+        }
+        self.difficulty = difficulty;
+        self
+    }
+    
+    /// 应用时段影响 (夜晚、黄昏敌人等级提高)
+    pub fn gc_with_time_of_day(mut self, phase: GcDayPhase) -> Self {
+        let bonus = phase.gc_effects().encounter_level_bonus;
+        for enemy in &mut self.enemies {
+            enemy.level += bonus;
+        }
+        self.time_of_day = Some(phase);
+        self
+    }
+}
+
+// =============================================================================
+// 生成
+// =============================================================================
+
+/// 根据区域生成遭遇
+pub fn gc_generate_encounter(
+    world_terrain: GcWorldTerrainType,
+    region: &GcSpawnRegion,
+    player_level: u32,
+    seed: u64,
+) -> GcEncounter {
+    gc_generate_encounter_on(world_terrain, region, player_level, 0, seed)
+}
+
+/// 根据玩家所在位置生成遭遇 (位置不在任何刷怪区域时为 None)
+pub fn gc_generate_encounter_at(
+    map: &GcMap,
+    pos: &GcPosition,
+    world_terrain: GcWorldTerrainType,
+    player_level: u32,
+    seed: u64,
+) -> Option<GcEncounter> {
+    let region = map.gc_spawn_region_at(pos)?;
+    let elevation = map.gc_elevation_at(pos);
+    Some(gc_generate_encounter_on(world_terrain, region, player_level, elevation, seed))
+}
+
+/// 按时段 (与天气) 生成遭遇 (跳过该时段不出现怪物的区域，并应用时段等级加成)
+pub fn gc_generate_encounter_during(
+    map: &GcMap,
+    pos: &GcPosition,
+    world_terrain: GcWorldTerrainType,
+    player_level: u32,
+    phase: GcDayPhase,
+    weather: Option<GcWeather>,
+    seed: u64,
+) -> Option<GcEncounter> {
+    let region = map.gc_active_spawn_region_at(pos, phase)?;
+    let elevation = map.gc_elevation_at(pos);
+    let encounter = gc_generate_encounter_with(world_terrain, region, player_level, elevation, weather, seed);
+    Some(encounter.gc_with_time_of_day(phase))
+}
+
+/// 由地图、位置和盐值派生遭遇种子 (FNV-1a，跨平台稳定)
+pub fn gc_encounter_seed(map_id: &str, pos: &GcPosition, salt: u64) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    let bytes = map_id.bytes()
+        .chain(pos.x.to_le_bytes())
+        .chain(pos.y.to_le_bytes())
+        .chain(salt.to_le_bytes());
+    for byte in bytes {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    hash
+}
+
+/// 根据区域与遭遇位置的海拔生成遭遇 (分块地图等不持有完整 `GcMap` 的调用方使用)
+pub fn gc_generate_encounter_on(
+    world_terrain: GcWorldTerrainType,
+    region: &GcSpawnRegion,
+    player_level: u32,
+    elevation: i16,
+    seed: u64,
+) -> GcEncounter {
+    gc_generate_encounter_with(world_terrain, region, player_level, elevation, None, seed)
+}
+
+/// 按天气生成遭遇 (天气怪物表、地形权重、种子与等级加成)
+pub fn gc_generate_encounter_in(
+    world_terrain: GcWorldTerrainType,
+    region: &GcSpawnRegion,
+    player_level: u32,
+    elevation: i16,
+    weather: GcWeather,
+    seed: u64,
+) -> GcEncounter {
+    gc_generate_encounter_with(world_terrain, region, player_level, elevation, Some(weather), seed)
+}
+
+/// 遭遇生成 (未指定天气时与晴天的怪物表、地形和种子相同，但不记录天气)
+fn gc_generate_encounter_with(
+    world_terrain: GcWorldTerrainType,
+    region: &GcSpawnRegion,
+    player_level: u32,
+    elevation: i16,
+    weather: Option<GcWeather>,
+    seed: u64,
+) -> GcEncounter {
+    let conditions = weather.unwrap_or_default();
+    let mut rng = gc_simulation_rng(conditions.gc_mix_seed(seed));
+    let monster_ids = match weather {
+        Some(weather) => region.gc_monsters_for(weather),
+        None => &region.monster_ids,
+    };
+
+    let min_level = region.min_level.min(region.max_level);
+    let max_level = region.max_level.min(player_level).max(min_level);
+    let party_size = match (region.enemy_type, monster_ids.is_empty()) {
+        (_, true) => 0,
+        (GcEnemyType::Boss, false) => 1,
+        _ => rng.gen_range(1..=GC_ENCOUNTER_MAX_PARTY),
+    };
+    let enemies = (0..party_size)
+        .map(|_| GcEncounterEnemy {
+            monster_id: monster_ids[rng.gen_range(0..monster_ids.len())].clone(),
+            level: rng.gen_range(min_level..=max_level) + conditions.gc_encounter_level_bonus(),
+        })
+        .collect();
+
+    let (player_terrain, enemy_terrain) =
+        gc_generate_battle_terrain_in(world_terrain, region.enemy_type, elevation, conditions, rng.gen());
+
+    GcEncounter {
+        region_id: region.id.clone(),
+        enemies,
+        enemy_type: region.enemy_type,
+        player_terrain,
+        enemy_terrain,
+        loot_table: region.loot_table.clone().unwrap_or_else(|| format!("loot_{}", region.id)),
+        seed,
+        difficulty: GcDifficultySettings::default(),
+        time_of_day: None,
+        weather,
+    }
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{gc_create_forest_map, GcDifficulty};
+
+    #[test]
+    fn test_encounter_is_deterministic_and_respects_region() {
+        let region = GcSpawnRegion::gc_new("r1", "草丛", 0, 0, 5, 5)
+            .gc_with_monsters(&["monster_slime", "monster_goblin"], 2, 6);
+        let a = gc_generate_encounter(GcWorldTerrainType::Grassland, &region, 4, 7);
+        let b = gc_generate_encounter(GcWorldTerrainType::Grassland, &region, 4, 7);
+        assert_eq!(a, b);
+        assert_eq!(a.loot_table, "loot_r1");
+
+        for seed in 0..50 {
+            let encounter = gc_generate_encounter(GcWorldTerrainType::Grassland, &region, 4, seed);
+            assert!((1..=GC_ENCOUNTER_MAX_PARTY).contains(&encounter.enemies.len()));
+            assert!(encounter.enemies.iter().all(|e| (2..=4).contains(&e.level)));
+            assert!(encounter.enemies.iter().all(|e| region.monster_ids.contains(&e.monster_id)));
+        }
+
+        let boss = region.clone().gc_with_enemy_type(GcEnemyType::Boss);
+        let low = gc_generate_encounter(GcWorldTerrainType::Cave, &boss, 1, 3);
+        assert_eq!(low.enemies.len(), 1);
+        assert_eq!(low.enemies[0].level, 2);
+
+        let scaled = gc_generate_encounter(GcWorldTerrainType::Grassland, &region, 9, 7)
+            .gc_with_difficulty(GcDifficultySettings::gc_new(GcDifficulty::Hard, true), 9);
+        assert!(scaled.enemies.iter().all(|e| e.level == 9));
+        assert_eq!(scaled.difficulty.difficulty, GcDifficulty::Hard);
+    }
+
+    #[test]
+    fn test_encounter_at_map_position() {
+        let map = gc_create_forest_map();
+        let pos = GcPosition::gc_new(16, 6);
+        let seed = gc_encounter_seed(&map.id, &pos, 1);
+        assert_eq!(seed, gc_encounter_seed("map_forest", &pos, 1));
+        assert_ne!(seed, gc_encounter_seed("map_forest", &pos, 2));
+
+        let encounter = gc_generate_encounter_at(&map, &pos, GcWorldTerrainType::Woodland, 10, seed);
+        assert_eq!(encounter.as_ref().map(|e| e.region_id.as_str()), Some("region_forest_east"));
+        assert!(encounter.is_some_and(|e| e.enemies.iter().all(|x| x.monster_id == "monster_wolf")));
+        assert!(gc_generate_encounter_at(&map, &GcPosition::gc_new(12, 3), GcWorldTerrainType::Woodland, 10, seed).is_none());
+    }
+
+    #[test]
+    fn test_encounter_varies_by_time_of_day() {
+        let mut map = crate::GcMap::gc_new("m", "夜林", 6, 6);
+        map.spawn_regions.push(
+            GcSpawnRegion::gc_new("night", "夜间", 0, 0, 6, 6)
+                .gc_with_monsters(&["monster_bat"], 3, 3)
+                .gc_with_phases(&[GcDayPhase::Night]),
+        );
+        let pos = GcPosition::gc_new(2, 2);
+        let day = gc_generate_encounter_during(&map, &pos, GcWorldTerrainType::Woodland, 5, GcDayPhase::Day, None, 1);
+        assert!(day.is_none());
+
+        let night = gc_generate_encounter_during(&map, &pos, GcWorldTerrainType::Woodland, 5, GcDayPhase::Night, None, 1).unwrap();
+        assert_eq!(night.time_of_day, Some(GcDayPhase::Night));
+        let bonus = GcDayPhase::Night.gc_effects().encounter_level_bonus;
+        assert!(night.enemies.iter().all(|e| e.level == 3 + bonus));
+    }
+    
+    #[test]
+    fn test_encounter_uses_weather_tables() {
+        let region = GcSpawnRegion::gc_new("lake", "湖畔", 0, 0, 5, 5)
+            .gc_with_monsters(&["monster_slime"], 4, 4)
+            .gc_with_weather_monsters(GcWeather::Storm, &["monster_eel"]);
+
+        // 未指定天气与晴天的怪物、地形一致
+        let plain = gc_generate_encounter_on(GcWorldTerrainType::Waterside, &region, 9, 0, 5);
+        let clear = gc_generate_encounter_in(GcWorldTerrainType::Waterside, &region, 9, 0, GcWeather::Clear, 5);
+        assert_eq!(plain.enemies, clear.enemies);
+        assert_eq!((plain.player_terrain, plain.enemy_terrain), (clear.player_terrain, clear.enemy_terrain));
+        assert_eq!((plain.weather, clear.weather), (None, Some(GcWeather::Clear)));
+
+        let storm = gc_generate_encounter_in(GcWorldTerrainType::Waterside, &region, 9, 0, GcWeather::Storm, 5);
+        assert_eq!(storm.seed, 5);
+        assert!(storm.enemies.iter().all(|e| e.monster_id == "monster_eel" && e.level == 5));
+        let rain = gc_generate_encounter_in(GcWorldTerrainType::Waterside, &region, 9, 0, GcWeather::Rain, 5);
+        assert!(rain.enemies.iter().all(|e| e.monster_id == "monster_slime"));
+
+        // 雪天战场偏向冰原
+        let glaciers = (0..100)
+            .map(|seed| gc_generate_encounter_in(GcWorldTerrainType::Grassland, &region, 9, 0, GcWeather::Snow, seed))
+            .filter(|e| e.player_terrain == GcTerrainType::Glacier)
+            .count();
+        assert!(glaciers > 0);
+    }
+}
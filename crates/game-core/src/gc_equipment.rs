@@ -11,7 +11,7 @@
 //! - 职业限制
 
 use serde::{Deserialize, Serialize};
-use crate::{GcBaseStats, GcCombatStats, GcProfessionType};
+use crate::{GcBaseStats, GcCombatStats, GcModifierBucket, GcPercentStack, GcProfessionType, GC_PERCENT};
 
 // =============================================================================
 // 装备枚举
@@ -80,16 +80,31 @@ impl GcRarity {
         }
     }
     
-    /// 获取属性倍率
-    pub fn gc_multiplier(&self) -> f32 {
+    /// 获取属性倍率 (百分比，100 = 1.0x)
+    pub fn gc_multiplier_percent(&self) -> u32 {
         match self {
-            GcRarity::Common => 1.0,
-            GcRarity::Uncommon => 1.2,
-            GcRarity::Rare => 1.5,
-            GcRarity::Epic => 2.0,
-            GcRarity::Legendary => 3.0,
+            GcRarity::Common => 100,
+            GcRarity::Uncommon => 120,
+            GcRarity::Rare => 150,
+            GcRarity::Epic => 200,
+            GcRarity::Legendary => 300,
         }
     }
+    
+    /// 稀有度加成 (装备分桶)
+    pub fn gc_stack(&self) -> GcPercentStack {
+        GcPercentStack::gc_new().gc_with_bonus(GcModifierBucket::Equipment, self.gc_multiplier_percent() - GC_PERCENT)
+    }
+
+    /// 应用稀有度倍率
+    pub fn gc_apply_multiplier(&self, value: u32) -> u32 {
+        self.gc_stack().gc_apply(value)
+    }
+
+    /// 获取属性倍率 (仅用于显示)
+    pub fn gc_multiplier(&self) -> f32 {
+        self.gc_multiplier_percent() as f32 / 100.0
+    }
 }
 
 // =============================================================================
@@ -97,7 +112,7 @@ impl GcRarity {
 // =============================================================================
 
 /// 装备物品
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct GcEquipment {
     /// 唯一 ID (实例 ID)
     pub id: String,
@@ -261,5 +276,9 @@ mod tests {
     fn test_gc_rarity_multiplier() {
         assert_eq!(GcRarity::Common.gc_multiplier(), 1.0);
         assert_eq!(GcRarity::Legendary.gc_multiplier(), 3.0);
+        assert_eq!(GcRarity::Uncommon.gc_apply_multiplier(15), 18);
+        // 稀有度与天赋分属不同分桶: 15 × 1.5 × 1.2
+        let stack = GcRarity::Rare.gc_stack().gc_with(GcModifierBucket::Talent, 20);
+        assert_eq!(stack.gc_apply(15), 27);
     }
 }
@@ -90,6 +90,38 @@ pub enum GcError {
     #[error("目标已死亡")]
     GcTargetDead,
     
+    // =========================================================================
+    // 技能相关错误 (5xxx)
+    // =========================================================================
+    
+    /// 技能不存在
+    #[error("技能不存在")]
+    GcSkillNotFound,
+    
+    /// 技能冷却中
+    #[error("技能冷却中")]
+    GcSkillOnCooldown,
+    
+    /// 本回合已使用过技能
+    #[error("本回合已使用过技能")]
+    GcSkillAlreadyUsed,
+    
+    // =========================================================================
+    // 酒馆相关错误 (6xxx)
+    // =========================================================================
+    
+    /// 手牌区已满
+    #[error("手牌区已满")]
+    GcBenchFull,
+    
+    /// 商店槽位为空
+    #[error("商店槽位为空")]
+    GcShopSlotEmpty,
+    
+    /// 金币不足
+    #[error("金币不足")]
+    GcNotEnoughGold,
+    
     // =========================================================================
     // 通用错误 (9xxx)
     // =========================================================================
@@ -131,6 +163,16 @@ impl GcError {
             GcError::GcInvalidTarget => 4001,
             GcError::GcTargetDead => 4002,
             
+            // 技能相关 5xxx
+            GcError::GcSkillNotFound => 5001,
+            GcError::GcSkillOnCooldown => 5002,
+            GcError::GcSkillAlreadyUsed => 5003,
+            
+            // 酒馆相关 6xxx
+            GcError::GcBenchFull => 6001,
+            GcError::GcShopSlotEmpty => 6002,
+            GcError::GcNotEnoughGold => 6003,
+            
             // 通用 9xxx
             GcError::GcInvalidAction(_) => 9001,
             GcError::GcInternalError(_) => 9999,
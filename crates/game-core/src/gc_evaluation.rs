@@ -0,0 +1,134 @@
+//! 卡牌对战局面评估 (胜率估算、AI 与提示共用)
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! ## 评估规则
+//! - 与 `gc_evaluate_arena` 相同的物量评估: 生命 + 防御 + 战场攻防 + 手牌与能量，全部整数运算
+//! - 局面分 = 己方评估 - 最强对手评估；已淘汰的玩家评估为 0
+//! - 胜率为千分比: 500 + 500 × 分 / (|分| + `GC_EVAL_SCALE`)，双人对局双方胜率之和恰为 1000
+//! - 战斗结束时胜者 1000、其余 0
+//! - 浮点只在 WASM 显示层换算 (见 `gc_math`)
+
+use serde::{Deserialize, Serialize};
+
+use crate::{GcBattleState, GcPlayer, GcPlayerState, GC_PER_MILLE};
+
+/// 胜率曲线的缩放 (局面分等于此值时胜率 75%)
+pub const GC_EVAL_SCALE: i64 = 60;
+
+/// 每张手牌的估值
+const GC_EVAL_CARD_VALUE: i64 = 4;
+
+/// 每点能量的估值
+const GC_EVAL_ENERGY_VALUE: i64 = 2;
+
+/// 战场攻击力的权重 (每回合都会造成伤害)
+const GC_EVAL_BOARD_ATTACK_WEIGHT: i64 = 2;
+
+// =============================================================================
+// 评估
+// =============================================================================
+
+/// 玩家胜率 (观战面板、动量条)
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcWinProbability {
+    /// 玩家 ID
+    pub player_id: String,
+    /// 胜率 (千分比)
+    pub per_mille: u32,
+}
+
+/// 单名玩家的物量评估
+pub fn gc_evaluate_player(player: &GcPlayer) -> i64 {
+    if player.state == GcPlayerState::Dead || !player.stats.gc_is_alive() {
+        return 0;
+    }
+    player.stats.hp as i64
+        + player.stats.defense as i64
+        + player.battlefield.gc_total_attack() as i64 * GC_EVAL_BOARD_ATTACK_WEIGHT
+        + player.battlefield.gc_total_defense() as i64
+        + player.hand.len() as i64 * GC_EVAL_CARD_VALUE
+        + player.stats.energy as i64 * GC_EVAL_ENERGY_VALUE
+}
+
+/// 局面评估 (玩家视角): 己方评估减去最强对手评估，玩家不存在时为 0
+pub fn gc_evaluate_battle(state: &GcBattleState, player_id: &str) -> i64 {
+    let Some(player) = state.gc_find_player(player_id) else {
+        return 0;
+    };
+    let strongest_opponent = state.players
+        .iter()
+        .filter(|p| p.id != player_id)
+        .map(gc_evaluate_player)
+        .max()
+        .unwrap_or(0);
+    gc_evaluate_player(player) - strongest_opponent
+}
+
+/// 局面分换算为胜率 (千分比)
+pub fn gc_score_to_per_mille(score: i64) -> u32 {
+    let half = GC_PER_MILLE as i64 / 2;
+    (half + half * score / (score.abs() + GC_EVAL_SCALE)) as u32
+}
+
+/// 玩家胜率 (千分比)
+pub fn gc_win_probability(state: &GcBattleState, player_id: &str) -> u32 {
+    if let Some(winner_id) = &state.winner_id {
+        return if winner_id == player_id { GC_PER_MILLE } else { 0 };
+    }
+    gc_score_to_per_mille(gc_evaluate_battle(state, player_id))
+}
+
+/// 全部玩家的胜率
+pub fn gc_win_probabilities(state: &GcBattleState) -> Vec<GcWinProbability> {
+    state.players
+        .iter()
+        .map(|p| GcWinProbability {
+            player_id: p.id.clone(),
+            per_mille: gc_win_probability(state, &p.id),
+        })
+        .collect()
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GcCard;
+
+    fn create_battle() -> GcBattleState {
+        GcBattleState::gc_new("b1", vec![GcPlayer::gc_new("p1", "玩家1"), GcPlayer::gc_new("p2", "玩家2")])
+    }
+
+    #[test]
+    fn test_even_battle_is_fifty_fifty() {
+        let battle = create_battle();
+        assert_eq!(gc_evaluate_battle(&battle, "p1"), 0);
+        assert_eq!(gc_win_probability(&battle, "p1"), 500);
+        assert_eq!(gc_score_to_per_mille(GC_EVAL_SCALE), 750);
+        assert_eq!(gc_score_to_per_mille(-GC_EVAL_SCALE), 250);
+        assert_eq!(gc_win_probability(&battle, "nobody"), 500);
+    }
+
+    #[test]
+    fn test_advantage_shifts_probability() {
+        let mut battle = create_battle();
+        battle.players[1].stats.hp /= 2;
+        if let Some(slot) = battle.players[0].battlefield.gc_get_slot_mut(0) {
+            slot.gc_deploy(GcCard::gc_new_attack("a1", "重击", 1, 10));
+        }
+        let probabilities = gc_win_probabilities(&battle);
+        assert!(probabilities[0].per_mille > 500);
+        assert_eq!(probabilities[0].per_mille + probabilities[1].per_mille, GC_PER_MILLE);
+        assert!(gc_evaluate_battle(&battle, "p1") > 0);
+
+        battle.winner_id = Some("p2".to_string());
+        assert_eq!(gc_win_probability(&battle, "p1"), 0);
+        assert_eq!(gc_win_probability(&battle, "p2"), GC_PER_MILLE);
+    }
+}
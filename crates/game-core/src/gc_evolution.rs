@@ -0,0 +1,233 @@
+//! 怪兽进化系统
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! ## 进化规则
+//! - 进化路线定义在怪兽模板中 (如 史莱姆 → 史莱姆王)
+//! - 需要玩家等级达到要求，部分路线需要消耗催化道具
+//! - 进化后替换模板属性，保留实例ID、星级、金色等级
+//! - 与三合一升星相互独立，可叠加
+
+use serde::{Deserialize, Serialize};
+
+use crate::{GcInventory, GcMonster, GcMonsterPool, GcMonsterTemplate, GcPlayer};
+
+// =============================================================================
+// 进化路线
+// =============================================================================
+
+/// 进化路线
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcEvolutionPath {
+    /// 进化目标模板ID
+    pub target_template_id: String,
+    /// 所需玩家等级
+    pub required_level: u32,
+    /// 催化道具ID (背包消耗品)
+    pub catalyst_item: Option<String>,
+}
+
+impl GcEvolutionPath {
+    /// 创建进化路线
+    pub fn new(target_template_id: &str, required_level: u32, catalyst_item: Option<&str>) -> Self {
+        Self {
+            target_template_id: target_template_id.to_string(),
+            required_level,
+            catalyst_item: catalyst_item.map(|s| s.to_string()),
+        }
+    }
+}
+
+// =============================================================================
+// 进化验证
+// =============================================================================
+
+/// 查找怪兽到目标模板的进化路线
+pub fn gc_find_evolution_path<'a>(
+    monster: &GcMonster,
+    pool: &'a GcMonsterPool,
+    target_template_id: &str,
+) -> Option<&'a GcEvolutionPath> {
+    pool.find_template(&monster.template_id)?
+        .evolutions
+        .iter()
+        .find(|p| p.target_template_id == target_template_id)
+}
+
+/// 验证进化条件
+///
+/// 返回进化目标模板
+pub fn gc_validate_evolution<'a>(
+    monster: &GcMonster,
+    pool: &'a GcMonsterPool,
+    target_template_id: &str,
+    player_level: u32,
+    inventory: Option<&GcInventory>,
+) -> Result<&'a GcMonsterTemplate, String> {
+    let path = gc_find_evolution_path(monster, pool, target_template_id)
+        .ok_or("该怪兽无法进化为目标形态")?;
+
+    if player_level < path.required_level {
+        return Err(format!("需要玩家等级 {}", path.required_level));
+    }
+
+    if let Some(catalyst) = &path.catalyst_item {
+        let count = inventory.map(|inv| inv.gc_consumable_count(catalyst)).unwrap_or(0);
+        if count == 0 {
+            return Err("缺少进化催化道具".to_string());
+        }
+    }
+
+    pool.find_template(target_template_id)
+        .ok_or_else(|| "进化目标模板不存在".to_string())
+}
+
+/// 列出怪兽当前可用的进化目标
+pub fn gc_available_evolutions(
+    monster: &GcMonster,
+    pool: &GcMonsterPool,
+    player_level: u32,
+    inventory: Option<&GcInventory>,
+) -> Vec<String> {
+    let Some(template) = pool.find_template(&monster.template_id) else {
+        return Vec::new();
+    };
+
+    template.evolutions.iter()
+        .filter(|p| gc_validate_evolution(monster, pool, &p.target_template_id, player_level, inventory).is_ok())
+        .map(|p| p.target_template_id.clone())
+        .collect()
+}
+
+// =============================================================================
+// 进化执行
+// =============================================================================
+
+/// 将怪兽替换为目标模板属性 (保留ID、星级、金色等级)
+fn gc_apply_template(monster: &mut GcMonster, template: &GcMonsterTemplate) {
+    monster.template_id = template.template_id.clone();
+    monster.name = template.name.clone();
+    monster.level = template.level;
+    monster.attribute = template.attribute;
+    monster.base_atk = template.base_atk;
+    monster.base_def = template.base_def;
+    monster.max_hp = template.base_hp;
+    monster.reach = template.reach;
+    monster.speed = template.speed;
+    monster.damage_type = template.damage_type;
+    monster.current_hp = monster.effective_max_hp();
+}
+
+/// 进化怪兽
+///
+/// 验证通过后消耗催化道具并替换模板属性
+pub fn gc_evolve_monster(
+    monster: &mut GcMonster,
+    pool: &GcMonsterPool,
+    target_template_id: &str,
+    player_level: u32,
+    inventory: Option<&mut GcInventory>,
+) -> Result<(), String> {
+    let template = gc_validate_evolution(monster, pool, target_template_id, player_level, inventory.as_deref())?;
+
+    if let Some(catalyst) = gc_find_evolution_path(monster, pool, target_template_id)
+        .and_then(|p| p.catalyst_item.as_ref())
+    {
+        inventory.ok_or("缺少进化催化道具")?.gc_consume(catalyst)?;
+    }
+
+    gc_apply_template(monster, template);
+    Ok(())
+}
+
+/// 进化玩家手牌区(备战席)中的怪兽
+pub fn gc_evolve_bench_monster(
+    player: &mut GcPlayer,
+    monster_id: &str,
+    pool: &GcMonsterPool,
+    target_template_id: &str,
+) -> Result<(), String> {
+    let player_level = player.level;
+    let monster = player.bench.iter_mut()
+        .find(|m| m.id == monster_id)
+        .ok_or("怪兽不在手牌区")?;
+
+    gc_evolve_monster(monster, pool, target_template_id, player_level, player.inventory.as_mut())
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GcMonsterAttribute;
+
+    fn create_slime() -> GcMonster {
+        GcMonster::new_with_template("s1", "slime", "史莱姆", 1, GcMonsterAttribute::Water, 50, 30, 60)
+    }
+
+    #[test]
+    fn test_evolution_requires_level() {
+        let pool = GcMonsterPool::with_defaults();
+        let mut inv = GcInventory::gc_new("p1", 10);
+        inv.gc_add_consumable("evo_slime_crown", 1);
+
+        let result = gc_validate_evolution(&create_slime(), &pool, "king_slime", 1, Some(&inv));
+        assert!(result.is_err());
+        assert!(gc_validate_evolution(&create_slime(), &pool, "king_slime", 3, Some(&inv)).is_ok());
+    }
+
+    #[test]
+    fn test_evolution_requires_catalyst() {
+        let pool = GcMonsterPool::with_defaults();
+        let mut monster = create_slime();
+        let mut inv = GcInventory::gc_new("p1", 10);
+
+        assert!(gc_evolve_monster(&mut monster, &pool, "king_slime", 5, Some(&mut inv)).is_err());
+        assert_eq!(monster.template_id, "slime");
+    }
+
+    #[test]
+    fn test_evolve_monster_transforms_stats() {
+        let pool = GcMonsterPool::with_defaults();
+        let mut monster = create_slime();
+        monster.star = 2;
+        let mut inv = GcInventory::gc_new("p1", 10);
+        inv.gc_add_consumable("evo_slime_crown", 1);
+
+        assert!(gc_evolve_monster(&mut monster, &pool, "king_slime", 3, Some(&mut inv)).is_ok());
+        assert_eq!(monster.template_id, "king_slime");
+        assert_eq!(monster.id, "s1");
+        assert_eq!(monster.star, 2);
+        assert_eq!(monster.base_atk, 90);
+        assert_eq!(monster.current_hp, monster.effective_max_hp());
+        assert_eq!(inv.gc_consumable_count("evo_slime_crown"), 0);
+    }
+
+    #[test]
+    fn test_evolution_only_not_in_shop() {
+        let pool = GcMonsterPool::with_defaults();
+        assert!(pool.templates_by_tier(crate::GcMonsterTier::Tier2)
+            .iter()
+            .all(|t| t.template_id != "king_slime"));
+    }
+
+    #[test]
+    fn test_evolve_bench_monster() {
+        let pool = GcMonsterPool::with_defaults();
+        let mut player = GcPlayer::gc_new("p1", "玩家");
+        player.level = 3;
+        let mut inv = GcInventory::gc_new("p1", 10);
+        inv.gc_add_consumable("evo_slime_crown", 1);
+        player.inventory = Some(inv);
+        player.gc_add_to_bench(create_slime());
+
+        assert_eq!(gc_available_evolutions(&player.bench[0], &pool, 3, player.inventory.as_ref()), vec!["king_slime"]);
+        assert!(gc_evolve_bench_monster(&mut player, "s1", &pool, "king_slime").is_ok());
+        assert_eq!(player.bench[0].name, "史莱姆王");
+    }
+}
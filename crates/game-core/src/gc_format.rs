@@ -0,0 +1,217 @@
+//! 数值格式化 (本地化)
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! ## 规则
+//! - 语言由 locale ID 指定 (`zh-CN`、`en-US`、`ja`、`de-DE`、`fr` ...)，只看语言部分；未知语言使用默认的简体中文
+//! - 千分位与小数点按语言区分: 英/中/日 `12,345.6`，德 `12.345,6`，法 `12 345,6` (窄不换行空格)
+//! - 紧凑数字保留一位小数并向下取整，不会把 999,999 显示成 "1000K"；中日按万/亿分级
+//! - 时长只显示最大的两个非零单位 (天/时/分/秒)
+//! - 只用整数运算，WASM 与原生服务器输出一致
+
+use serde::{Deserialize, Serialize};
+
+// =============================================================================
+// 语言
+// =============================================================================
+
+/// 格式化语言
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum GcLocale {
+    /// 简体中文
+    #[default]
+    ZhCn,
+    /// 英语
+    EnUs,
+    /// 日语
+    JaJp,
+    /// 德语
+    DeDe,
+    /// 法语
+    FrFr,
+}
+
+impl GcLocale {
+    /// 从 locale ID 解析 (`en-US`、`en_GB`、`ZH` 均可)
+    pub fn gc_from_id(id: &str) -> Self {
+        let language = id.split(['-', '_']).next().unwrap_or_default().to_ascii_lowercase();
+        match language.as_str() {
+            "en" => Self::EnUs,
+            "ja" => Self::JaJp,
+            "de" => Self::DeDe,
+            "fr" => Self::FrFr,
+            _ => Self::ZhCn,
+        }
+    }
+
+    /// 标准 locale ID
+    pub fn gc_id(&self) -> &'static str {
+        match self {
+            Self::ZhCn => "zh-CN",
+            Self::EnUs => "en-US",
+            Self::JaJp => "ja-JP",
+            Self::DeDe => "de-DE",
+            Self::FrFr => "fr-FR",
+        }
+    }
+
+    /// (千分位分隔符, 小数点)
+    fn gc_separators(&self) -> (&'static str, char) {
+        match self {
+            Self::ZhCn | Self::EnUs | Self::JaJp => (",", '.'),
+            Self::DeDe => (".", ','),
+            Self::FrFr => ("\u{202f}", ','),
+        }
+    }
+
+    /// 数值与单位之间的分隔 (百分号、紧凑单位、时长单位)
+    fn gc_unit_gap(&self) -> &'static str {
+        match self {
+            Self::ZhCn | Self::EnUs | Self::JaJp => "",
+            Self::DeDe | Self::FrFr => "\u{a0}",
+        }
+    }
+
+    /// 紧凑单位 (除数, 后缀)，从小到大
+    fn gc_compact_units(&self) -> &'static [(u64, &'static str)] {
+        match self {
+            Self::ZhCn => &[(10_000, "万"), (100_000_000, "亿")],
+            Self::JaJp => &[(10_000, "万"), (100_000_000, "億")],
+            Self::EnUs => &[(1_000, "K"), (1_000_000, "M"), (1_000_000_000, "B")],
+            Self::DeDe => &[(1_000, "Tsd."), (1_000_000, "Mio."), (1_000_000_000, "Mrd.")],
+            Self::FrFr => &[(1_000, "k"), (1_000_000, "M"), (1_000_000_000, "Md")],
+        }
+    }
+
+    /// 时长单位 (天, 时, 分, 秒)
+    fn gc_duration_units(&self) -> [&'static str; 4] {
+        match self {
+            Self::ZhCn => ["天", "小时", "分钟", "秒"],
+            Self::JaJp => ["日", "時間", "分", "秒"],
+            Self::EnUs => ["d", "h", "m", "s"],
+            Self::DeDe => ["T.", "Std.", "Min.", "Sek."],
+            Self::FrFr => ["j", "h", "min", "s"],
+        }
+    }
+}
+
+// =============================================================================
+// 格式化
+// =============================================================================
+
+/// 带千分位的整数部分
+fn gc_group_digits(value: u64, separator: &str) -> String {
+    let digits = value.to_string();
+    let mut out = String::with_capacity(digits.len() + digits.len() / 3 * separator.len());
+    for (i, ch) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            out.push_str(separator);
+        }
+        out.push(ch);
+    }
+    out
+}
+
+/// 整数 (带千分位): 12345 → "12,345" / "12.345"
+pub fn gc_format_number(value: i64, locale: GcLocale) -> String {
+    let (group, _) = locale.gc_separators();
+    let sign = if value < 0 { "-" } else { "" };
+    format!("{}{}", sign, gc_group_digits(value.unsigned_abs(), group))
+}
+
+/// 紧凑数字: 12500 → "12.5K" / "1.2万" / "12,5 Tsd."，小于最小单位时同 `gc_format_number`
+pub fn gc_format_compact(value: i64, locale: GcLocale) -> String {
+    let abs = value.unsigned_abs();
+    let Some(&(divisor, suffix)) = locale.gc_compact_units().iter().rev().find(|(d, _)| abs >= *d) else {
+        return gc_format_number(value, locale);
+    };
+    let (group, decimal) = locale.gc_separators();
+    let tenths = abs / (divisor / 10);
+    let sign = if value < 0 { "-" } else { "" };
+    let mut out = format!("{}{}", sign, gc_group_digits(tenths / 10, group));
+    if !tenths.is_multiple_of(10) {
+        out.push(decimal);
+        out.push(char::from(b'0' + (tenths % 10) as u8));
+    }
+    out.push_str(locale.gc_unit_gap());
+    out.push_str(suffix);
+    out
+}
+
+/// 百分比: 25 → "25%" / "25 %"
+pub fn gc_format_percent(percent: i32, locale: GcLocale) -> String {
+    format!("{}{}%", gc_format_number(i64::from(percent), locale), locale.gc_unit_gap())
+}
+
+/// 时长 (秒): 3900 → "1h 5m" / "1小时5分钟"，只显示最大的两个非零单位
+pub fn gc_format_duration(seconds: u64, locale: GcLocale) -> String {
+    let units = locale.gc_duration_units();
+    let parts = [seconds / 86_400, seconds % 86_400 / 3_600, seconds % 3_600 / 60, seconds % 60];
+    let gap = locale.gc_unit_gap();
+    let between = match locale {
+        GcLocale::ZhCn | GcLocale::JaJp => "",
+        _ => " ",
+    };
+    let mut shown: Vec<String> = parts.iter().zip(units)
+        .skip_while(|(amount, _)| **amount == 0)
+        .take(2)
+        .filter(|(amount, _)| **amount > 0)
+        .map(|(amount, unit)| format!("{}{}{}", gc_format_number(*amount as i64, locale), gap, unit))
+        .collect();
+    if shown.is_empty() {
+        shown.push(format!("0{}{}", gap, units[3]));
+    }
+    shown.join(between)
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locale_ids_and_numbers() {
+        assert_eq!(GcLocale::gc_from_id("en-GB"), GcLocale::EnUs);
+        assert_eq!(GcLocale::gc_from_id("DE_at"), GcLocale::DeDe);
+        assert_eq!(GcLocale::gc_from_id("xx"), GcLocale::ZhCn);
+        assert_eq!(GcLocale::gc_from_id(GcLocale::JaJp.gc_id()), GcLocale::JaJp);
+
+        assert_eq!(gc_format_number(1_234_567, GcLocale::EnUs), "1,234,567");
+        assert_eq!(gc_format_number(-1_234, GcLocale::DeDe), "-1.234");
+        assert_eq!(gc_format_number(999, GcLocale::FrFr), "999");
+        assert_eq!(gc_format_number(12_345, GcLocale::FrFr), "12\u{202f}345");
+        assert_eq!(gc_format_percent(25, GcLocale::ZhCn), "25%");
+        assert_eq!(gc_format_percent(-5, GcLocale::DeDe), "-5\u{a0}%");
+    }
+
+    #[test]
+    fn test_compact_numbers_truncate() {
+        assert_eq!(gc_format_compact(12_500, GcLocale::EnUs), "12.5K");
+        assert_eq!(gc_format_compact(12_000, GcLocale::EnUs), "12K");
+        assert_eq!(gc_format_compact(999_999, GcLocale::EnUs), "999.9K");
+        assert_eq!(gc_format_compact(-2_500_000, GcLocale::EnUs), "-2.5M");
+        assert_eq!(gc_format_compact(950, GcLocale::EnUs), "950");
+        assert_eq!(gc_format_compact(1_200_000_000_000, GcLocale::EnUs), "1,200B");
+
+        assert_eq!(gc_format_compact(12_500, GcLocale::ZhCn), "1.2万");
+        assert_eq!(gc_format_compact(9_999, GcLocale::ZhCn), "9,999");
+        assert_eq!(gc_format_compact(350_000_000, GcLocale::JaJp), "3.5億");
+        assert_eq!(gc_format_compact(12_500, GcLocale::DeDe), "12,5\u{a0}Tsd.");
+    }
+
+    #[test]
+    fn test_durations_show_two_largest_units() {
+        assert_eq!(gc_format_duration(3_900, GcLocale::EnUs), "1h 5m");
+        assert_eq!(gc_format_duration(3_600, GcLocale::EnUs), "1h");
+        assert_eq!(gc_format_duration(90_061, GcLocale::EnUs), "1d 1h");
+        assert_eq!(gc_format_duration(45, GcLocale::ZhCn), "45秒");
+        assert_eq!(gc_format_duration(3_900, GcLocale::ZhCn), "1小时5分钟");
+        assert_eq!(gc_format_duration(0, GcLocale::JaJp), "0秒");
+        assert_eq!(gc_format_duration(3_900, GcLocale::DeDe), "1\u{a0}Std. 5\u{a0}Min.");
+    }
+}
@@ -0,0 +1,335 @@
+//! 怪兽融合系统
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! ## 融合规则
+//! - 两只指定模板的不同怪兽按配方融合为新模板 (区别于同模板三合一)
+//! - 配方与素材顺序无关
+//! - 结果属性 = 目标模板 + 素材平均值的10%，再叠加 ±10% 随机浮动
+//! - 浮动由调用方提供的随机数 (0-255) 按比例换算 (`roll × 21 / 256`)，产物 ID 也由调用方提供，同样的输入得到同样的结果
+//! - 首次成功融合某配方时记录为"已发现"
+
+use serde::{Deserialize, Serialize};
+
+use crate::{GcMonster, GcMonsterPool, GcPlayer, gc_percent_of};
+
+// =============================================================================
+// 常量
+// =============================================================================
+
+/// 素材属性继承比例 (%)
+pub const GC_FUSION_INHERIT_PERCENT: u32 = 10;
+
+/// 属性随机浮动范围 (±%)
+pub const GC_FUSION_VARIANCE_PERCENT: u32 = 10;
+
+// =============================================================================
+// 融合配方
+// =============================================================================
+
+/// 融合配方
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcFusionRecipe {
+    /// 配方ID
+    pub recipe_id: String,
+    /// 素材A模板ID
+    pub parent_a: String,
+    /// 素材B模板ID
+    pub parent_b: String,
+    /// 融合结果模板ID
+    pub result_template_id: String,
+}
+
+impl GcFusionRecipe {
+    /// 创建配方
+    pub fn new(recipe_id: &str, parent_a: &str, parent_b: &str, result_template_id: &str) -> Self {
+        Self {
+            recipe_id: recipe_id.to_string(),
+            parent_a: parent_a.to_string(),
+            parent_b: parent_b.to_string(),
+            result_template_id: result_template_id.to_string(),
+        }
+    }
+
+    /// 是否匹配两个素材模板 (与顺序无关)
+    pub fn matches(&self, template_a: &str, template_b: &str) -> bool {
+        (self.parent_a == template_a && self.parent_b == template_b)
+            || (self.parent_a == template_b && self.parent_b == template_a)
+    }
+}
+
+/// 融合配方表
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct GcFusionRegistry {
+    recipes: Vec<GcFusionRecipe>,
+}
+
+impl GcFusionRegistry {
+    /// 创建空配方表
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 创建带默认配方的配方表
+    pub fn with_defaults() -> Self {
+        let mut registry = Self::new();
+        registry.add_recipe(GcFusionRecipe::new("fusion_goblin_bomber", "goblin", "fire_sprite", "goblin_bomber"));
+        registry.add_recipe(GcFusionRecipe::new("fusion_griffin", "wolf", "harpy", "griffin"));
+        registry
+    }
+
+    /// 添加配方
+    pub fn add_recipe(&mut self, recipe: GcFusionRecipe) {
+        self.recipes.push(recipe);
+    }
+
+    /// 获取所有配方 (只读)
+    pub fn recipes(&self) -> &[GcFusionRecipe] {
+        &self.recipes
+    }
+
+    /// 查找两个素材模板对应的配方
+    pub fn find_recipe(&self, template_a: &str, template_b: &str) -> Option<&GcFusionRecipe> {
+        self.recipes.iter().find(|r| r.matches(template_a, template_b))
+    }
+}
+
+// =============================================================================
+// 配方发现记录
+// =============================================================================
+
+/// 已发现的配方
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct GcFusionDiscovery {
+    discovered: Vec<String>,
+}
+
+impl GcFusionDiscovery {
+    /// 创建空记录
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 记录配方，首次发现返回 true
+    pub fn record(&mut self, recipe_id: &str) -> bool {
+        if self.is_discovered(recipe_id) {
+            return false;
+        }
+        self.discovered.push(recipe_id.to_string());
+        true
+    }
+
+    /// 配方是否已发现
+    pub fn is_discovered(&self, recipe_id: &str) -> bool {
+        self.discovered.iter().any(|r| r == recipe_id)
+    }
+
+    /// 已发现配方数量
+    pub fn count(&self) -> usize {
+        self.discovered.len()
+    }
+
+    /// 已发现的配方ID列表
+    pub fn discovered(&self) -> &[String] {
+        &self.discovered
+    }
+}
+
+// =============================================================================
+// 融合操作
+// =============================================================================
+
+/// 融合结果
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GcFusionResult {
+    /// 融合产物
+    pub monster: GcMonster,
+    /// 使用的配方ID
+    pub recipe_id: String,
+    /// 是否首次发现该配方
+    pub newly_discovered: bool,
+}
+
+/// 不浮动的随机数 (随机数不足时使用)
+pub const GC_FUSION_NEUTRAL_ROLL: u8 = 128;
+
+/// 按随机数浮动属性值 (±GC_FUSION_VARIANCE_PERCENT，随机数按比例换算，没有取模偏差)
+fn gc_apply_variance(value: u32, roll: u8) -> u32 {
+    let range = GC_FUSION_VARIANCE_PERCENT * 2 + 1;
+    let percent = 100 - GC_FUSION_VARIANCE_PERCENT + (roll as u32 * range / 256);
+    gc_percent_of(value, percent).max(1)
+}
+
+/// 融合两只怪兽
+///
+/// - `rolls`: 调用方提供的随机数 (至少3个: 攻击/防御/生命)，不足时视为无浮动
+/// - `result_id`: 融合产物的 ID
+pub fn gc_fuse_monsters(
+    a: &GcMonster,
+    b: &GcMonster,
+    registry: &GcFusionRegistry,
+    pool: &GcMonsterPool,
+    discovery: &mut GcFusionDiscovery,
+    rolls: &[u8],
+    result_id: &str,
+) -> Result<GcFusionResult, String> {
+    if a.id == b.id {
+        return Err("不能与自身融合".to_string());
+    }
+    if a.template_id == b.template_id {
+        return Err("相同模板请使用三合一升星".to_string());
+    }
+
+    let recipe = registry.find_recipe(&a.template_id, &b.template_id)
+        .ok_or("没有对应的融合配方")?;
+    let template = pool.find_template(&recipe.result_template_id)
+        .ok_or("融合目标模板不存在")?;
+
+    let inherit = |x: u32, y: u32| (x + y) / 2 * GC_FUSION_INHERIT_PERCENT / 100;
+    let roll = |i: usize| rolls.get(i).copied().unwrap_or(GC_FUSION_NEUTRAL_ROLL);
+
+    let atk = gc_apply_variance(template.base_atk + inherit(a.base_atk, b.base_atk), roll(0));
+    let def = gc_apply_variance(template.base_def + inherit(a.base_def, b.base_def), roll(1));
+    let hp = gc_apply_variance(template.base_hp + inherit(a.max_hp, b.max_hp), roll(2));
+
+    let mut monster = GcMonster::new_with_template(
+        result_id,
+        &template.template_id,
+        &template.name,
+        template.level,
+        template.attribute,
+        atk,
+        def,
+        hp,
+    );
+    monster.reach = template.reach;
+    monster.speed = template.speed;
+    monster.damage_type = template.damage_type;
+
+    Ok(GcFusionResult {
+        monster,
+        recipe_id: recipe.recipe_id.clone(),
+        newly_discovered: discovery.record(&recipe.recipe_id),
+    })
+}
+
+/// 融合玩家手牌区(备战席)中的两只怪兽
+///
+/// 成功后移除素材，产物加入手牌区
+#[allow(clippy::too_many_arguments)]
+pub fn gc_fuse_bench_monsters(
+    player: &mut GcPlayer,
+    id_a: &str,
+    id_b: &str,
+    registry: &GcFusionRegistry,
+    pool: &GcMonsterPool,
+    discovery: &mut GcFusionDiscovery,
+    rolls: &[u8],
+    result_id: &str,
+) -> Result<GcFusionResult, String> {
+    let a = player.gc_find_in_bench(id_a).ok_or("素材A不在手牌区")?;
+    let b = player.gc_find_in_bench(id_b).ok_or("素材B不在手牌区")?;
+
+    let result = gc_fuse_monsters(a, b, registry, pool, discovery, rolls, result_id)?;
+
+    player.gc_remove_from_bench(id_a);
+    player.gc_remove_from_bench(id_b);
+    player.gc_add_to_bench(result.monster.clone());
+
+    Ok(result)
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GcMonsterAttribute;
+
+    fn create_goblin() -> GcMonster {
+        GcMonster::new_with_template("g1", "goblin", "哥布林", 1, GcMonsterAttribute::Earth, 60, 20, 50)
+    }
+
+    fn create_sprite() -> GcMonster {
+        GcMonster::new_with_template("f1", "fire_sprite", "火精灵", 2, GcMonsterAttribute::Fire, 70, 10, 40)
+    }
+
+    #[test]
+    fn test_recipe_order_independent() {
+        let registry = GcFusionRegistry::with_defaults();
+        assert!(registry.find_recipe("goblin", "fire_sprite").is_some());
+        assert!(registry.find_recipe("fire_sprite", "goblin").is_some());
+        assert!(registry.find_recipe("goblin", "slime").is_none());
+    }
+
+    #[test]
+    fn test_fuse_monsters_stats() {
+        let registry = GcFusionRegistry::with_defaults();
+        let pool = GcMonsterPool::with_defaults();
+        let mut discovery = GcFusionDiscovery::new();
+
+        // 浮动为 0 (roll = 128)
+        let neutral = [GC_FUSION_NEUTRAL_ROLL; 3];
+        let result = gc_fuse_monsters(&create_goblin(), &create_sprite(), &registry, &pool, &mut discovery, &neutral, "fused_1")
+            .expect("融合应成功");
+        assert_eq!(result.monster.id, "fused_1");
+        assert_eq!(result.monster.template_id, "goblin_bomber");
+        assert_eq!(result.monster.base_atk, 110 + 6);
+        assert!(result.newly_discovered);
+
+        // 最大浮动 (roll = 255 -> +10%)
+        let result = gc_fuse_monsters(&create_goblin(), &create_sprite(), &registry, &pool, &mut discovery, &[255, 0, 255], "fused_2")
+            .expect("融合应成功");
+        assert_eq!(result.monster.base_atk, 116 * 110 / 100);
+        assert!(!result.newly_discovered);
+        assert_eq!(discovery.count(), 1);
+    }
+
+    #[test]
+    fn test_fuse_same_template_rejected() {
+        let registry = GcFusionRegistry::with_defaults();
+        let pool = GcMonsterPool::with_defaults();
+        let mut discovery = GcFusionDiscovery::new();
+        let mut other = create_goblin();
+        other.id = "g2".to_string();
+
+        assert!(gc_fuse_monsters(&create_goblin(), &other, &registry, &pool, &mut discovery, &[], "fused").is_err());
+    }
+
+    #[test]
+    fn test_fuse_bench_monsters() {
+        let registry = GcFusionRegistry::with_defaults();
+        let pool = GcMonsterPool::with_defaults();
+        let mut discovery = GcFusionDiscovery::new();
+        let mut player = GcPlayer::gc_new("p1", "玩家");
+        player.gc_add_to_bench(create_goblin());
+        player.gc_add_to_bench(create_sprite());
+
+        let result = gc_fuse_bench_monsters(&mut player, "g1", "f1", &registry, &pool, &mut discovery, &[5, 5, 5], "fused_1");
+        assert!(result.is_ok());
+        assert_eq!(player.gc_bench_count(), 1);
+        assert_eq!(player.bench[0].template_id, "goblin_bomber");
+        assert!(discovery.is_discovered("fusion_goblin_bomber"));
+    }
+
+    #[test]
+    fn test_variance_is_unbiased_and_deterministic() {
+        // 21 档浮动各占 12-13 个随机数
+        let mut counts = [0u32; 21];
+        for roll in 0..=255u8 {
+            counts[(gc_apply_variance(1000, roll) / 10 - 90) as usize] += 1;
+        }
+        assert!(counts.iter().all(|&c| c == 12 || c == 13), "{:?}", counts);
+
+        let registry = GcFusionRegistry::with_defaults();
+        let pool = GcMonsterPool::with_defaults();
+        let fuse = || gc_fuse_monsters(&create_goblin(), &create_sprite(), &registry, &pool, &mut GcFusionDiscovery::new(), &[7, 99, 200], "f")
+            .expect("融合应成功")
+            .monster;
+        assert_eq!(serde_json::to_value(fuse()).unwrap(), serde_json::to_value(fuse()).unwrap());
+    }
+}
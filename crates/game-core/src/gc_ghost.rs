@@ -0,0 +1,417 @@
+//! 幽灵数据 (异步竞赛: 挑战好友的成绩)
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! ## 规则
+//! - 单人酒馆/冒险流程每回合记录一条紧凑摘要: 阵容 (模板、星级、攻击、生命、位置)、生命、胜负
+//! - 流程结束时计算最终得分: 胜场 × 100 + 最后回合 × 10 + 剩余生命
+//! - 竞赛时逐回合比较双方 (只比较双方都记录过的回合)，报告生命、战力、星级与得分差值
+//! - 每回合的领先判定依次比较: 累计胜场、生命、阵容战力
+
+use std::cmp::Ordering;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{GcArenaPosition, GcArenaRow, GcBattleArena, GcMonster, GcTavernSeat};
+
+/// 幽灵格式版本
+pub const GC_GHOST_FORMAT_VERSION: u32 = 1;
+
+/// 单个幽灵最多记录的回合数
+pub const GC_GHOST_MAX_ROUNDS: usize = 60;
+
+/// 每回合最多记录的单位数 (前排 5 + 后排 5)
+pub const GC_GHOST_MAX_UNITS: usize = 10;
+
+// =============================================================================
+// 幽灵数据
+// =============================================================================
+
+/// 流程类型
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum GcGhostMode {
+    /// 单人酒馆
+    #[default]
+    Tavern,
+    /// 冒险
+    Adventure,
+}
+
+/// 阵容单位 (紧凑)
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcGhostUnit {
+    /// 怪兽模板 ID
+    pub template_id: String,
+    /// 星级
+    pub star: u8,
+    /// 攻击
+    pub atk: u32,
+    /// 最大生命
+    pub hp: u32,
+    /// 位置
+    pub position: GcArenaPosition,
+}
+
+impl GcGhostUnit {
+    /// 由怪兽生成
+    pub fn gc_from_monster(monster: &GcMonster, position: GcArenaPosition) -> Self {
+        Self {
+            template_id: monster.template_id.clone(),
+            star: monster.star,
+            atk: monster.base_atk,
+            hp: monster.max_hp,
+            position,
+        }
+    }
+}
+
+/// 提取玩家方阵容 (前排在前)
+pub fn gc_ghost_units(board: &GcBattleArena) -> Vec<GcGhostUnit> {
+    let front = board.player_monsters.iter().enumerate().map(|(i, m)| (m, GcArenaPosition::front(i as u8)));
+    let back = board.player_back_row.iter().enumerate().map(|(i, m)| (m, GcArenaPosition::back(i as u8)));
+    front
+        .chain(back)
+        .filter_map(|(monster, pos)| monster.as_ref().map(|m| GcGhostUnit::gc_from_monster(m, pos)))
+        .collect()
+}
+
+/// 一回合的摘要
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcGhostRound {
+    /// 回合 (从 1 开始)
+    pub round: u32,
+    /// 战斗阵容
+    pub units: Vec<GcGhostUnit>,
+    /// 回合结束时的生命
+    pub health: u32,
+    /// 战斗结果 (None 为平局)
+    #[serde(default)]
+    pub won: Option<bool>,
+}
+
+impl GcGhostRound {
+    /// 阵容战力 (攻击 + 生命之和)
+    pub fn gc_power(&self) -> u32 {
+        self.units.iter().fold(0u32, |sum, u| sum.saturating_add(u.atk).saturating_add(u.hp))
+    }
+
+    /// 阵容星级之和
+    pub fn gc_stars(&self) -> u32 {
+        self.units.iter().map(|u| u.star as u32).sum()
+    }
+}
+
+/// 幽灵校验失败
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum GcGhostError {
+    /// 格式版本不支持
+    #[error("不支持的幽灵格式版本: {0}")]
+    GcUnsupportedVersion(u32),
+    /// 没有任何回合
+    #[error("幽灵没有记录任何回合")]
+    GcEmpty,
+    /// 回合过多
+    #[error("幽灵回合过多: {count} 个，上限 {max} 个")]
+    GcTooManyRounds { count: usize, max: usize },
+    /// 回合顺序错误或单位过多
+    #[error("第 {0} 回合数据不合法")]
+    GcInvalidRound(u32),
+    /// 流程未结束
+    #[error("只能上传已结束的流程")]
+    GcNotFinished,
+    /// 得分与回合数据不一致
+    #[error("得分不一致: 记录 {recorded}，应为 {expected}")]
+    GcScoreMismatch { recorded: u32, expected: u32 },
+}
+
+/// 一次单人流程的幽灵
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcGhostRun {
+    /// 格式版本
+    pub format_version: u32,
+    /// 流程类型
+    pub mode: GcGhostMode,
+    /// 玩家 ID
+    pub player_id: String,
+    /// 玩家名称
+    pub player_name: String,
+    /// 每回合摘要 (回合递增)
+    pub rounds: Vec<GcGhostRound>,
+    /// 最终得分 (流程结束后才有)
+    #[serde(default)]
+    pub score: Option<u32>,
+}
+
+impl GcGhostRun {
+    /// 开始记录
+    pub fn gc_new(mode: GcGhostMode, player_id: &str, player_name: &str) -> Self {
+        Self {
+            format_version: GC_GHOST_FORMAT_VERSION,
+            mode,
+            player_id: player_id.to_string(),
+            player_name: player_name.to_string(),
+            rounds: Vec::new(),
+            score: None,
+        }
+    }
+
+    /// 记录一回合 (同一回合重复记录时覆盖)
+    pub fn gc_record_round(&mut self, round: GcGhostRound) {
+        self.rounds.retain(|r| r.round != round.round);
+        let index = self.rounds.partition_point(|r| r.round < round.round);
+        self.rounds.insert(index, round);
+    }
+
+    /// 记录酒馆座位的本回合结果 (阵容取座位当前阵容)
+    pub fn gc_record_tavern_seat(&mut self, round: u32, seat: &GcTavernSeat, won: Option<bool>) {
+        self.gc_record_round(GcGhostRound {
+            round,
+            units: gc_ghost_units(&seat.board),
+            health: seat.health,
+            won,
+        });
+    }
+
+    /// 某回合的摘要
+    pub fn gc_round(&self, round: u32) -> Option<&GcGhostRound> {
+        self.rounds.iter().find(|r| r.round == round)
+    }
+
+    /// 截至某回合 (含) 的胜场
+    pub fn gc_wins_through(&self, round: u32) -> u32 {
+        self.rounds.iter().filter(|r| r.round <= round && r.won == Some(true)).count() as u32
+    }
+
+    /// 截至某回合 (含) 的得分
+    pub fn gc_score_through(&self, round: u32) -> u32 {
+        let Some(last) = self.rounds.iter().rev().find(|r| r.round <= round) else {
+            return 0;
+        };
+        (self.gc_wins_through(round) * 100)
+            .saturating_add(last.round.saturating_mul(10))
+            .saturating_add(last.health)
+    }
+
+    /// 结束流程并计算最终得分
+    pub fn gc_finish(&mut self) -> u32 {
+        let score = self.gc_score_through(u32::MAX);
+        self.score = Some(score);
+        score
+    }
+
+    /// 校验进行中的流程 (版本、回合顺序与数量)
+    pub fn gc_validate_progress(&self) -> Result<(), GcGhostError> {
+        if self.format_version != GC_GHOST_FORMAT_VERSION {
+            return Err(GcGhostError::GcUnsupportedVersion(self.format_version));
+        }
+        if self.rounds.len() > GC_GHOST_MAX_ROUNDS {
+            return Err(GcGhostError::GcTooManyRounds { count: self.rounds.len(), max: GC_GHOST_MAX_ROUNDS });
+        }
+        let mut previous = 0;
+        for round in &self.rounds {
+            let back_rows = round.units.iter().filter(|u| u.position.row == GcArenaRow::Back).count();
+            if round.round <= previous || round.units.len() > GC_GHOST_MAX_UNITS || back_rows > GC_GHOST_MAX_UNITS / 2 {
+                return Err(GcGhostError::GcInvalidRound(round.round));
+            }
+            previous = round.round;
+        }
+        Ok(())
+    }
+
+    /// 校验上传的幽灵 (须已结束且得分与回合数据一致)
+    pub fn gc_validate(&self) -> Result<(), GcGhostError> {
+        self.gc_validate_progress()?;
+        if self.rounds.is_empty() {
+            return Err(GcGhostError::GcEmpty);
+        }
+        let recorded = self.score.ok_or(GcGhostError::GcNotFinished)?;
+        let expected = self.gc_score_through(u32::MAX);
+        if recorded != expected {
+            return Err(GcGhostError::GcScoreMismatch { recorded, expected });
+        }
+        Ok(())
+    }
+}
+
+// =============================================================================
+// 竞赛比较
+// =============================================================================
+
+/// 领先情况
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GcGhostStanding {
+    /// 领先
+    Ahead,
+    /// 持平
+    Even,
+    /// 落后
+    Behind,
+}
+
+impl From<Ordering> for GcGhostStanding {
+    fn from(ordering: Ordering) -> Self {
+        match ordering {
+            Ordering::Greater => Self::Ahead,
+            Ordering::Equal => Self::Even,
+            Ordering::Less => Self::Behind,
+        }
+    }
+}
+
+/// 一回合的差值 (自己 - 幽灵)
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcGhostRoundDelta {
+    /// 回合
+    pub round: u32,
+    /// 生命差
+    pub health_delta: i64,
+    /// 阵容战力差
+    pub power_delta: i64,
+    /// 阵容星级差
+    pub stars_delta: i64,
+    /// 累计胜场差
+    pub wins_delta: i64,
+    /// 自己本回合战斗结果
+    pub won: Option<bool>,
+    /// 幽灵本回合战斗结果
+    pub ghost_won: Option<bool>,
+    /// 领先情况
+    pub standing: GcGhostStanding,
+}
+
+/// 与幽灵的竞赛结果
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcGhostRace {
+    /// 幽灵玩家 ID
+    pub ghost_player_id: String,
+    /// 幽灵玩家名称
+    pub ghost_player_name: String,
+    /// 逐回合差值
+    pub rounds: Vec<GcGhostRoundDelta>,
+    /// 最近比较回合的得分差 (自己的得分 - 幽灵同回合的得分)
+    pub score_delta: i64,
+    /// 当前领先情况 (最近比较的回合，没有可比较回合时为持平)
+    pub standing: GcGhostStanding,
+    /// 幽灵的最终得分
+    pub ghost_score: Option<u32>,
+}
+
+/// 与幽灵逐回合比较 (自己的流程可以尚未结束)
+pub fn gc_race_ghost(mine: &GcGhostRun, ghost: &GcGhostRun) -> GcGhostRace {
+    let rounds: Vec<GcGhostRoundDelta> = mine
+        .rounds
+        .iter()
+        .filter_map(|own| ghost.gc_round(own.round).map(|theirs| (own, theirs)))
+        .map(|(own, theirs)| {
+            let wins = (mine.gc_wins_through(own.round), ghost.gc_wins_through(own.round));
+            let standing = wins
+                .0
+                .cmp(&wins.1)
+                .then(own.health.cmp(&theirs.health))
+                .then(own.gc_power().cmp(&theirs.gc_power()));
+            GcGhostRoundDelta {
+                round: own.round,
+                health_delta: own.health as i64 - theirs.health as i64,
+                power_delta: own.gc_power() as i64 - theirs.gc_power() as i64,
+                stars_delta: own.gc_stars() as i64 - theirs.gc_stars() as i64,
+                wins_delta: wins.0 as i64 - wins.1 as i64,
+                won: own.won,
+                ghost_won: theirs.won,
+                standing: standing.into(),
+            }
+        })
+        .collect();
+
+    let (score_delta, standing) = rounds
+        .last()
+        .map(|last| {
+            let delta = mine.gc_score_through(last.round) as i64 - ghost.gc_score_through(last.round) as i64;
+            (delta, last.standing)
+        })
+        .unwrap_or((0, GcGhostStanding::Even));
+
+    GcGhostRace {
+        ghost_player_id: ghost.player_id.clone(),
+        ghost_player_name: ghost.player_name.clone(),
+        rounds,
+        score_delta,
+        standing,
+        ghost_score: ghost.score,
+    }
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GcMonsterAttribute, GcTavernSeat};
+
+    fn round(round: u32, health: u32, won: Option<bool>, atk: u32) -> GcGhostRound {
+        let unit = GcGhostUnit {
+            template_id: "slime".to_string(),
+            star: 1,
+            atk,
+            hp: 10,
+            position: GcArenaPosition::front(0),
+        };
+        GcGhostRound { round, units: vec![unit], health, won }
+    }
+
+    #[test]
+    fn test_record_and_validate() {
+        let mut seat = GcTavernSeat::gc_new("p1", "玩家");
+        seat.player.bench.push(GcMonster::new_with_template("m1", "slime", "史莱姆", 1, GcMonsterAttribute::Water, 50, 30, 60));
+        assert!(seat.gc_deploy("m1", GcArenaPosition::back(2)).is_ok());
+
+        let mut run = GcGhostRun::gc_new(GcGhostMode::Tavern, "p1", "玩家");
+        run.gc_record_tavern_seat(2, &seat, Some(false));
+        run.gc_record_tavern_seat(1, &seat, Some(true));
+        run.gc_record_tavern_seat(2, &seat, Some(true));
+        assert_eq!(run.rounds.iter().map(|r| r.round).collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(run.rounds[0].units[0].position, GcArenaPosition::back(2));
+        assert_eq!(run.gc_validate(), Err(GcGhostError::GcNotFinished));
+
+        // 2 胜 × 100 + 第 2 回合 × 10 + 40 生命
+        assert_eq!(run.gc_finish(), 260);
+        assert_eq!(run.gc_validate(), Ok(()));
+
+        let json = serde_json::to_string(&run).unwrap();
+        let mut parsed: GcGhostRun = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, run);
+        parsed.score = Some(999);
+        assert!(matches!(parsed.gc_validate(), Err(GcGhostError::GcScoreMismatch { .. })));
+    }
+
+    #[test]
+    fn test_race_reports_deltas() {
+        let mut ghost = GcGhostRun::gc_new(GcGhostMode::Tavern, "friend", "好友");
+        ghost.gc_record_round(round(1, 40, Some(true), 20));
+        ghost.gc_record_round(round(2, 35, Some(false), 30));
+        ghost.gc_record_round(round(3, 35, Some(true), 40));
+        ghost.gc_finish();
+
+        // 自己的流程进行到第 2 回合
+        let mut mine = GcGhostRun::gc_new(GcGhostMode::Tavern, "me", "我");
+        mine.gc_record_round(round(1, 38, Some(false), 25));
+        mine.gc_record_round(round(2, 38, Some(true), 30));
+
+        let race = gc_race_ghost(&mine, &ghost);
+        assert_eq!(race.rounds.len(), 2);
+        assert_eq!(race.rounds[0].standing, GcGhostStanding::Behind);
+        assert_eq!((race.rounds[0].health_delta, race.rounds[0].power_delta), (-2, 5));
+        // 第 2 回合胜场持平，生命领先
+        assert_eq!(race.rounds[1].wins_delta, 0);
+        assert_eq!(race.standing, GcGhostStanding::Ahead);
+        assert_eq!(race.score_delta, 3);
+        assert_eq!(race.ghost_score, Some(100 * 2 + 30 + 35));
+
+        let empty = GcGhostRun::gc_new(GcGhostMode::Tavern, "new", "新人");
+        assert_eq!(gc_race_ghost(&empty, &ghost).standing, GcGhostStanding::Even);
+    }
+}
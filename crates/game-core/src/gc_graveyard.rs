@@ -16,7 +16,7 @@ use crate::GcMonster;
 // =============================================================================
 
 /// 墓地
-#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
 pub struct GcGraveyard {
     /// 墓地中的怪兽列表 (按死亡顺序)
     monsters: Vec<GcMonster>,
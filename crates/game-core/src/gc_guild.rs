@@ -0,0 +1,361 @@
+//! 组织金库与共享升级
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! ## 规则
+//! - 每个组织拥有一个金库，成员捐献金币进入金库，并记录个人捐献 (贡献榜)
+//! - 金库可购买组织升级，每级为全体成员提供小幅加成 (如经验 +2%)
+//! - 只有会长与干部可以动用金库；只有会长可以任免干部
+//! - 第一个加入的成员成为会长
+//! - 加成在战斗属性合成时应用 (见 `GcPlayer::gc_update_rpg_stats`)
+
+use serde::{Deserialize, Serialize};
+
+use crate::{GcModifierBucket, GcOrganization, GcPercentStack, gc_apply_bonus_percent};
+
+// =============================================================================
+// 常量
+// =============================================================================
+
+/// 单次捐献上限
+pub const GC_GUILD_MAX_DONATION: u64 = 10_000;
+
+/// 升级最高等级
+pub const GC_GUILD_UPGRADE_MAX_LEVEL: u32 = 5;
+
+// =============================================================================
+// 成员与职位
+// =============================================================================
+
+/// 组织职位
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GcGuildRole {
+    /// 会长
+    Leader,
+    /// 干部
+    Officer,
+    /// 成员
+    #[default]
+    Member,
+}
+
+impl GcGuildRole {
+    /// 是否可以动用金库
+    pub fn gc_can_spend(&self) -> bool {
+        matches!(self, GcGuildRole::Leader | GcGuildRole::Officer)
+    }
+}
+
+/// 金库成员
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcGuildMember {
+    /// 玩家ID
+    pub player_id: String,
+    /// 职位
+    pub role: GcGuildRole,
+    /// 累计捐献
+    pub donated: u64,
+}
+
+// =============================================================================
+// 升级与加成
+// =============================================================================
+
+/// 组织升级
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GcGuildUpgrade {
+    /// 智慧: 经验获取
+    Wisdom,
+    /// 勇武: 攻击力
+    Valor,
+    /// 坚壁: 防御力
+    Bulwark,
+    /// 活力: 最大生命
+    Vigor,
+}
+
+impl GcGuildUpgrade {
+    /// 名称
+    pub fn gc_name(&self) -> &str {
+        match self {
+            GcGuildUpgrade::Wisdom => "智慧",
+            GcGuildUpgrade::Valor => "勇武",
+            GcGuildUpgrade::Bulwark => "坚壁",
+            GcGuildUpgrade::Vigor => "活力",
+        }
+    }
+
+    /// 每级加成 (%)
+    pub fn gc_percent_per_level(&self) -> u32 {
+        match self {
+            GcGuildUpgrade::Wisdom => 2,
+            GcGuildUpgrade::Valor | GcGuildUpgrade::Bulwark | GcGuildUpgrade::Vigor => 1,
+        }
+    }
+
+    /// 升到指定等级的花费
+    pub fn gc_cost(&self, level: u32) -> u64 {
+        let base = match self {
+            GcGuildUpgrade::Wisdom => 500,
+            GcGuildUpgrade::Valor | GcGuildUpgrade::Bulwark | GcGuildUpgrade::Vigor => 800,
+        };
+        base * level as u64 * level as u64
+    }
+}
+
+/// 组织升级等级
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcGuildUpgradeLevel {
+    /// 升级
+    pub upgrade: GcGuildUpgrade,
+    /// 当前等级
+    pub level: u32,
+}
+
+/// 组织加成 (百分比，全体成员生效)
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcGuildBuffs {
+    /// 经验获取加成 (%)
+    pub xp_percent: u32,
+    /// 攻击力加成 (%)
+    pub atk_percent: u32,
+    /// 防御力加成 (%)
+    pub def_percent: u32,
+    /// 最大生命加成 (%)
+    pub hp_percent: u32,
+}
+
+impl GcGuildBuffs {
+    /// 应用经验加成
+    pub fn apply_xp(&self, value: u32) -> u32 {
+        gc_apply_bonus_percent(value, self.xp_percent)
+    }
+
+    /// 攻击加成 (组织分桶)
+    pub fn atk_stack(&self) -> GcPercentStack {
+        GcPercentStack::gc_new().gc_with_bonus(GcModifierBucket::Guild, self.atk_percent)
+    }
+
+    /// 防御加成 (组织分桶)
+    pub fn def_stack(&self) -> GcPercentStack {
+        GcPercentStack::gc_new().gc_with_bonus(GcModifierBucket::Guild, self.def_percent)
+    }
+
+    /// 生命加成 (组织分桶)
+    pub fn hp_stack(&self) -> GcPercentStack {
+        GcPercentStack::gc_new().gc_with_bonus(GcModifierBucket::Guild, self.hp_percent)
+    }
+
+    /// 应用攻击加成
+    pub fn apply_atk(&self, value: u32) -> u32 {
+        self.atk_stack().gc_apply(value)
+    }
+
+    /// 应用防御加成
+    pub fn apply_def(&self, value: u32) -> u32 {
+        self.def_stack().gc_apply(value)
+    }
+
+    /// 应用生命加成
+    pub fn apply_hp(&self, value: u32) -> u32 {
+        self.hp_stack().gc_apply(value)
+    }
+}
+
+// =============================================================================
+// 金库
+// =============================================================================
+
+/// 组织金库
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GcGuildTreasury {
+    /// 所属组织
+    pub organization: GcOrganization,
+    /// 余额
+    pub balance: u64,
+    /// 已购买的升级
+    pub upgrades: Vec<GcGuildUpgradeLevel>,
+    /// 成员
+    pub members: Vec<GcGuildMember>,
+}
+
+impl GcGuildTreasury {
+    /// 创建空金库
+    pub fn gc_new(organization: GcOrganization) -> Self {
+        Self {
+            organization,
+            balance: 0,
+            upgrades: Vec::new(),
+            members: Vec::new(),
+        }
+    }
+
+    /// 获取成员
+    pub fn gc_member(&self, player_id: &str) -> Option<&GcGuildMember> {
+        self.members.iter().find(|m| m.player_id == player_id)
+    }
+
+    /// 加入组织 (第一个成员成为会长)，返回职位
+    pub fn gc_join(&mut self, player_id: &str) -> GcGuildRole {
+        if let Some(member) = self.gc_member(player_id) {
+            return member.role;
+        }
+        let role = if self.members.is_empty() { GcGuildRole::Leader } else { GcGuildRole::Member };
+        self.members.push(GcGuildMember {
+            player_id: player_id.to_string(),
+            role,
+            donated: 0,
+        });
+        role
+    }
+
+    /// 任免职位 (仅会长，不能任命第二个会长)
+    pub fn gc_set_role(&mut self, actor_id: &str, target_id: &str, role: GcGuildRole) -> Result<(), String> {
+        if self.gc_member(actor_id).map(|m| m.role) != Some(GcGuildRole::Leader) {
+            return Err("只有会长可以任免职位".to_string());
+        }
+        if role == GcGuildRole::Leader || actor_id == target_id {
+            return Err("不能变更会长".to_string());
+        }
+        let target = self.members.iter_mut()
+            .find(|m| m.player_id == target_id)
+            .ok_or("目标不是组织成员")?;
+        target.role = role;
+        Ok(())
+    }
+
+    /// 捐献金币，返回金库余额
+    pub fn gc_donate(&mut self, player_id: &str, amount: u64) -> Result<u64, String> {
+        if amount == 0 || amount > GC_GUILD_MAX_DONATION {
+            return Err(format!("单次捐献需在 1 到 {} 之间", GC_GUILD_MAX_DONATION));
+        }
+        let member = self.members.iter_mut()
+            .find(|m| m.player_id == player_id)
+            .ok_or("不是组织成员")?;
+        member.donated += amount;
+        self.balance += amount;
+        Ok(self.balance)
+    }
+
+    /// 存入非捐献收入 (如领地产出)，返回金库余额
+    pub fn gc_deposit(&mut self, amount: u64) -> u64 {
+        self.balance += amount;
+        self.balance
+    }
+
+    /// 升级当前等级
+    pub fn gc_upgrade_level(&self, upgrade: GcGuildUpgrade) -> u32 {
+        self.upgrades.iter()
+            .find(|u| u.upgrade == upgrade)
+            .map(|u| u.level)
+            .unwrap_or(0)
+    }
+
+    /// 购买升级 (需要动用金库权限)，返回新等级
+    pub fn gc_purchase_upgrade(&mut self, player_id: &str, upgrade: GcGuildUpgrade) -> Result<u32, String> {
+        let role = self.gc_member(player_id).map(|m| m.role).ok_or("不是组织成员")?;
+        if !role.gc_can_spend() {
+            return Err("没有动用金库的权限".to_string());
+        }
+
+        let level = self.gc_upgrade_level(upgrade) + 1;
+        if level > GC_GUILD_UPGRADE_MAX_LEVEL {
+            return Err(format!("{} 已达最高等级", upgrade.gc_name()));
+        }
+        let cost = upgrade.gc_cost(level);
+        if self.balance < cost {
+            return Err(format!("金库余额不足，需要 {}", cost));
+        }
+
+        self.balance -= cost;
+        match self.upgrades.iter_mut().find(|u| u.upgrade == upgrade) {
+            Some(entry) => entry.level = level,
+            None => self.upgrades.push(GcGuildUpgradeLevel { upgrade, level }),
+        }
+        Ok(level)
+    }
+
+    /// 当前升级提供的成员加成
+    pub fn gc_buffs(&self) -> GcGuildBuffs {
+        let mut buffs = GcGuildBuffs::default();
+        for entry in &self.upgrades {
+            let percent = entry.upgrade.gc_percent_per_level() * entry.level;
+            match entry.upgrade {
+                GcGuildUpgrade::Wisdom => buffs.xp_percent += percent,
+                GcGuildUpgrade::Valor => buffs.atk_percent += percent,
+                GcGuildUpgrade::Bulwark => buffs.def_percent += percent,
+                GcGuildUpgrade::Vigor => buffs.hp_percent += percent,
+            }
+        }
+        buffs
+    }
+
+    /// 贡献榜 (按累计捐献降序)
+    pub fn gc_leaderboard(&self) -> Vec<GcGuildMember> {
+        let mut ranking = self.members.clone();
+        ranking.sort_by_key(|m| std::cmp::Reverse(m.donated));
+        ranking
+    }
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_treasury() -> GcGuildTreasury {
+        let mut treasury = GcGuildTreasury::gc_new(GcOrganization::IronBlood);
+        assert_eq!(treasury.gc_join("leader"), GcGuildRole::Leader);
+        assert_eq!(treasury.gc_join("p1"), GcGuildRole::Member);
+        treasury
+    }
+
+    #[test]
+    fn test_donate_and_leaderboard() {
+        let mut treasury = create_treasury();
+        assert_eq!(treasury.gc_donate("p1", 800), Ok(800));
+        assert_eq!(treasury.gc_donate("leader", 300), Ok(1100));
+        assert!(treasury.gc_donate("stranger", 100).is_err());
+        assert!(treasury.gc_donate("p1", GC_GUILD_MAX_DONATION + 1).is_err());
+
+        let ranking = treasury.gc_leaderboard();
+        assert_eq!(ranking[0].player_id, "p1");
+        assert_eq!(ranking[1].donated, 300);
+    }
+
+    #[test]
+    fn test_purchase_requires_permission() {
+        let mut treasury = create_treasury();
+        treasury.gc_donate("p1", 2500).unwrap();
+
+        assert!(treasury.gc_purchase_upgrade("p1", GcGuildUpgrade::Wisdom).is_err());
+        assert!(treasury.gc_set_role("p1", "p1", GcGuildRole::Officer).is_err());
+        assert!(treasury.gc_set_role("leader", "p1", GcGuildRole::Officer).is_ok());
+
+        assert_eq!(treasury.gc_purchase_upgrade("p1", GcGuildUpgrade::Wisdom), Ok(1));
+        assert_eq!(treasury.gc_purchase_upgrade("p1", GcGuildUpgrade::Wisdom), Ok(2));
+        assert_eq!(treasury.balance, 0);
+        assert!(treasury.gc_purchase_upgrade("p1", GcGuildUpgrade::Wisdom).is_err());
+
+        let buffs = treasury.gc_buffs();
+        assert_eq!(buffs.xp_percent, 4);
+        assert_eq!(buffs.apply_xp(100), 104);
+    }
+
+    #[test]
+    fn test_buffs_apply_to_player_stats() {
+        let mut player = crate::GcPlayer::gc_new("p1", "玩家1");
+        let buffs = GcGuildBuffs { atk_percent: 10, hp_percent: 5, ..Default::default() };
+
+        player.gc_set_guild_buffs(buffs.clone());
+        player.gc_set_guild_buffs(buffs);
+        assert_eq!(player.stats.attack, 11);
+        assert_eq!(player.stats.max_hp, 105);
+        assert_eq!(player.stats.hp, 105);
+    }
+}
@@ -0,0 +1,181 @@
+//! 手牌/牌库/弃牌堆查看 (隐私级别)
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! ## 隐私规则
+//! - 玩家本人 (`Owner`) 可以看到自己手牌、牌库、弃牌堆的数量与内容
+//! - 对手与观战者 (`Opponent`) 只能看到数量
+//! - 服务器广播的战斗状态按接收者脱敏: 其他玩家的牌区内容被清空，数量见 `GcPlayerZones`
+//! - 可见卡牌的描述按牌主当前属性渲染 (`gc_render_card_descriptions`)，显示的数字与实际结算一致
+
+use serde::{Deserialize, Serialize};
+
+use crate::{gc_render_card_descriptions, GcBattleState, GcCard, GcCardTextContext, GcPlayer, GcPlayerId};
+
+// =============================================================================
+// 视图
+// =============================================================================
+
+/// 查看者隐私级别
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GcZonePrivacy {
+    /// 本人视角 (数量 + 内容)
+    Owner,
+    /// 对手/观战视角 (只有数量)
+    #[default]
+    Opponent,
+}
+
+/// 单个牌区视图
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct GcZoneView {
+    /// 卡牌数量
+    pub count: usize,
+    /// 卡牌内容 (对手视角为 None)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cards: Option<Vec<GcCard>>,
+}
+
+impl GcZoneView {
+    /// 按隐私级别生成视图
+    pub fn gc_new(cards: &[GcCard], privacy: GcZonePrivacy) -> Self {
+        Self {
+            count: cards.len(),
+            cards: (privacy == GcZonePrivacy::Owner).then(|| cards.to_vec()),
+        }
+    }
+
+    /// 按隐私级别生成视图，可见卡牌的描述按上下文渲染
+    pub fn gc_rendered(cards: &[GcCard], privacy: GcZonePrivacy, ctx: &GcCardTextContext) -> Self {
+        let mut view = Self::gc_new(cards, privacy);
+        if let Some(cards) = &mut view.cards {
+            gc_render_card_descriptions(cards, ctx);
+        }
+        view
+    }
+}
+
+/// 玩家牌区视图
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GcPlayerZones {
+    /// 玩家 ID
+    pub player_id: GcPlayerId,
+    /// 手牌
+    pub hand: GcZoneView,
+    /// 牌库
+    pub deck: GcZoneView,
+    /// 弃牌堆
+    pub discard: GcZoneView,
+}
+
+impl GcPlayerZones {
+    /// 按隐私级别生成玩家牌区视图
+    pub fn gc_new(player: &GcPlayer, privacy: GcZonePrivacy) -> Self {
+        let ctx = GcCardTextContext::gc_for_player(player);
+        Self {
+            player_id: player.id.clone(),
+            hand: GcZoneView::gc_rendered(&player.hand, privacy, &ctx),
+            deck: GcZoneView::gc_rendered(&player.deck, privacy, &ctx),
+            discard: GcZoneView::gc_rendered(&player.discard, privacy, &ctx),
+        }
+    }
+}
+
+// =============================================================================
+// 查询与脱敏
+// =============================================================================
+
+/// 查看者对某名玩家牌区的隐私级别
+pub fn gc_zone_privacy(viewer_id: Option<&str>, owner_id: &str) -> GcZonePrivacy {
+    if viewer_id == Some(owner_id) {
+        GcZonePrivacy::Owner
+    } else {
+        GcZonePrivacy::Opponent
+    }
+}
+
+/// 查看者看到的全部玩家牌区 (viewer_id 为 None 表示观战者)
+pub fn gc_battle_zones(state: &GcBattleState, viewer_id: Option<&str>) -> Vec<GcPlayerZones> {
+    state.players
+        .iter()
+        .map(|p| GcPlayerZones::gc_new(p, gc_zone_privacy(viewer_id, &p.id)))
+        .collect()
+}
+
+/// 按查看者脱敏的战斗状态: 清空其他玩家的手牌、牌库、弃牌堆内容，本人卡牌的描述按当前属性渲染
+pub fn gc_redact_battle_state(state: &GcBattleState, viewer_id: Option<&str>) -> GcBattleState {
+    let mut redacted = state.clone();
+    for player in &mut redacted.players {
+        if gc_zone_privacy(viewer_id, &player.id) == GcZonePrivacy::Opponent {
+            player.hand.clear();
+            player.deck.clear();
+            player.discard.clear();
+        } else {
+            let ctx = GcCardTextContext::gc_for_player(player);
+            gc_render_card_descriptions(&mut player.hand, &ctx);
+            gc_render_card_descriptions(&mut player.deck, &ctx);
+            gc_render_card_descriptions(&mut player.discard, &ctx);
+        }
+    }
+    redacted
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_battle() -> GcBattleState {
+        let mut p1 = GcPlayer::gc_new("p1", "玩家1");
+        p1.hand.push(GcCard::gc_new_attack("c1", "重击", 1, 20));
+        p1.deck.push(GcCard::gc_new_attack("c2", "重击", 1, 20));
+        let mut p2 = GcPlayer::gc_new("p2", "玩家2");
+        p2.hand.push(GcCard::gc_new_defense("c3", "格挡", 1, 5));
+        p2.discard.push(GcCard::gc_new_defense("c4", "格挡", 1, 5));
+        GcBattleState::gc_new("b1", vec![p1, p2])
+    }
+
+    #[test]
+    fn test_owner_sees_contents_opponent_sees_counts() {
+        let battle = create_battle();
+        let zones = gc_battle_zones(&battle, Some("p1"));
+        assert_eq!(zones[0].hand.cards.as_ref().map(|c| c[0].id.as_str()), Some("c1"));
+        assert_eq!(zones[0].deck.count, 1);
+        assert_eq!(zones[1].hand.count, 1);
+        assert!(zones[1].hand.cards.is_none());
+        assert_eq!(zones[1].discard.count, 1);
+
+        let json = serde_json::to_string(&zones[1]).unwrap_or_default();
+        assert!(!json.contains("c3"));
+        assert!(gc_battle_zones(&battle, None).iter().all(|z| z.hand.cards.is_none()));
+    }
+
+    #[test]
+    fn test_redacted_state_hides_other_players() {
+        let battle = create_battle();
+        let redacted = gc_redact_battle_state(&battle, Some("p2"));
+        assert!(redacted.players[0].hand.is_empty());
+        assert!(redacted.players[0].deck.is_empty());
+        assert_eq!(redacted.players[1].hand.len(), 1);
+        assert_eq!(redacted.players[1].discard.len(), 1);
+        assert_eq!(battle.players[0].hand.len(), 1);
+    }
+
+    #[test]
+    fn test_visible_descriptions_use_owner_attack() {
+        let mut battle = create_battle();
+        battle.players[0].stats.attack = 4;
+        battle.players[0].hand[0].description = "造成 {damage} 点伤害".to_string();
+
+        let zones = gc_battle_zones(&battle, Some("p1"));
+        assert_eq!(zones[0].hand.cards.as_ref().unwrap()[0].description, "造成 24 点伤害");
+        let redacted = gc_redact_battle_state(&battle, Some("p1"));
+        assert_eq!(redacted.players[0].hand[0].description, "造成 24 点伤害");
+        assert_eq!(battle.players[0].hand[0].description, "造成 {damage} 点伤害");
+    }
+}
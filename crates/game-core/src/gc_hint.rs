@@ -0,0 +1,157 @@
+//! 行动提示 (新手提示按钮、教程引导)
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! ## 提示规则
+//! - 枚举当前玩家的出牌、部署、拿牌与结束回合，用 `gc_preview_action` 在状态副本上试执行一步
+//! - 用预览给出的局面分为每一步打分 (深度 1)，不合法的操作直接跳过
+//! - 能直接获胜的出牌排在最前，其余按局面分从高到低，同分保持枚举顺序
+//! - 结束回合始终作为兜底建议，分数为当前局面分
+//! - 不是该玩家的回合或战斗已结束时没有提示
+
+use serde::{Deserialize, Serialize};
+
+use crate::{gc_evaluate_battle, gc_preview_action, GcAction, GcBattleState};
+
+// =============================================================================
+// 提示
+// =============================================================================
+
+/// 提示理由 (客户端按代码显示本地化文案)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GcHintReason {
+    /// 这一步可以直接获胜
+    Lethal,
+    /// 对敌人造成伤害
+    Damage,
+    /// 在战场部署卡牌
+    DevelopBoard,
+    /// 从卡池补充手牌
+    RefillHand,
+    /// 没有更好的操作，结束回合
+    EndTurn,
+}
+
+/// 单条行动建议
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GcHint {
+    /// 建议的操作
+    pub action: GcAction,
+    /// 理由代码
+    pub reason: GcHintReason,
+    /// 执行后的局面分
+    pub score: i64,
+}
+
+// =============================================================================
+// 建议
+// =============================================================================
+
+/// 为玩家生成排序后的行动建议 (第一条为最佳)
+pub fn gc_suggest_action(state: &GcBattleState, player_id: &str) -> Vec<GcHint> {
+    if state.gc_is_finished() || state.gc_current_player_id() != Some(player_id) {
+        return Vec::new();
+    }
+    let Some(player) = state.gc_find_player(player_id) else {
+        return Vec::new();
+    };
+
+    let mut hints = Vec::new();
+
+    // 出牌 (对每个可行动的对手)
+    for card in &player.hand {
+        for target in state.players.iter().filter(|p| p.id != player_id && p.gc_can_act()) {
+            let action = GcAction::PlayCard {
+                player_id: player_id.to_string(),
+                card_id: card.id.clone(),
+                target_id: target.id.clone(),
+            };
+            let preview = gc_preview_action(state, &action);
+            if !preview.gc_is_applied() {
+                continue;
+            }
+            let reason = if preview.gc_is_lethal() { GcHintReason::Lethal } else { GcHintReason::Damage };
+            hints.push(GcHint { action, reason, score: preview.score });
+        }
+    }
+
+    // 部署 (槽位等价，只尝试第一个空槽位)
+    if let Some(&slot_index) = player.battlefield.gc_get_empty_slots().first() {
+        for card in &player.hand {
+            let action = GcAction::DeployCard {
+                player_id: player_id.to_string(),
+                card_id: card.id.clone(),
+                slot_index,
+            };
+            let preview = gc_preview_action(state, &action);
+            if preview.gc_is_applied() {
+                hints.push(GcHint { action, reason: GcHintReason::DevelopBoard, score: preview.score });
+            }
+        }
+    }
+
+    // 从卡池拿牌
+    for card in state.gc_get_pool_display() {
+        let action = GcAction::AcquireCard {
+            player_id: player_id.to_string(),
+            card_id: card.id.clone(),
+        };
+        let preview = gc_preview_action(state, &action);
+        if preview.gc_is_applied() {
+            hints.push(GcHint { action, reason: GcHintReason::RefillHand, score: preview.score });
+        }
+    }
+
+    // 稳定排序: 获胜优先，其次局面分
+    hints.sort_by(|a, b| {
+        (b.reason == GcHintReason::Lethal, b.score).cmp(&(a.reason == GcHintReason::Lethal, a.score))
+    });
+
+    // 结束回合兜底
+    hints.push(GcHint {
+        action: GcAction::EndTurn { player_id: player_id.to_string() },
+        reason: GcHintReason::EndTurn,
+        score: gc_evaluate_battle(state, player_id),
+    });
+
+    hints
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GcCard, GcPlayer};
+
+    fn create_battle() -> GcBattleState {
+        GcBattleState::gc_new("b1", vec![GcPlayer::gc_new("p1", "玩家1"), GcPlayer::gc_new("p2", "玩家2")])
+    }
+
+    #[test]
+    fn test_no_hints_out_of_turn() {
+        let battle = create_battle();
+        assert!(gc_suggest_action(&battle, "p2").is_empty());
+
+        let hints = gc_suggest_action(&battle, "p1");
+        assert!(matches!(hints.last().map(|h| h.reason), Some(GcHintReason::EndTurn)));
+    }
+
+    #[test]
+    fn test_lethal_ranked_first() {
+        let mut battle = create_battle();
+        battle.players[1].stats.hp = 1;
+        battle.players[0].hand.push(GcCard::gc_new_attack("weak", "轻击", 1, 5));
+        battle.players[0].hand.push(GcCard::gc_new_attack("big", "重击", 3, 30));
+
+        let hints = gc_suggest_action(&battle, "p1");
+        assert_eq!(hints[0].reason, GcHintReason::Lethal);
+        assert!(matches!(&hints[0].action, GcAction::PlayCard { target_id, .. } if target_id == "p2"));
+        assert!(hints.iter().any(|h| h.reason == GcHintReason::DevelopBoard));
+        assert!(hints.iter().any(|h| h.reason == GcHintReason::RefillHand));
+    }
+}
@@ -0,0 +1,310 @@
+//! 战斗状态历史 (写时共享快照)
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! ## 规则
+//! - 快照把战斗状态拆成若干组件 (每名玩家、卡池、引导、战斗日志)，每个组件用 `Arc` 保存
+//! - 记录新快照时，与上一个快照内容相同的组件直接共享 `Arc`，不再复制
+//! - 内容是否相同直接按 `PartialEq` 比较，不做序列化或哈希 (避免指纹碰撞导致错误共享)
+//! - 快照不可变，恢复时才克隆出完整的 `GcBattleState`
+//! - 历史支持撤销/重做；在中间位置记录新快照会丢弃其后的重做分支
+
+use std::sync::Arc;
+
+use crate::{
+    GcBattleId, GcBattlePhase, GcBattleState, GcCardPool, GcCardType, GcChannelAction,
+    GcCombatLogEvent, GcCostModifier, GcPlayer, GcPlayerId, GcTerrainType,
+};
+
+// =============================================================================
+// 共享组件
+// =============================================================================
+
+/// 可共享的快照组件
+#[derive(Clone, Debug)]
+struct GcSharedPart<T> {
+    value: Arc<T>,
+}
+
+impl<T: PartialEq + Clone> GcSharedPart<T> {
+    /// 内容与上一份相同则共享，否则复制一份
+    fn gc_capture(value: &T, previous: Option<&GcSharedPart<T>>) -> Self {
+        match previous {
+            Some(previous) if *previous.value == *value => previous.clone(),
+            _ => Self { value: Arc::new(value.clone()) },
+        }
+    }
+}
+
+// =============================================================================
+// 快照
+// =============================================================================
+
+/// 不可变战斗快照
+#[derive(Clone, Debug)]
+pub struct GcBattleSnapshot {
+    id: GcBattleId,
+    turn: u32,
+    current_player_index: usize,
+    phase: GcBattlePhase,
+    turn_time_limit: u32,
+    winner_id: Option<GcPlayerId>,
+    action_points_per_turn: u32,
+    played_this_turn: Vec<GcCardType>,
+    sandbox: bool,
+    forced_terrain: Option<GcTerrainType>,
+    cost_modifiers: Vec<GcCostModifier>,
+    players: Vec<GcSharedPart<GcPlayer>>,
+    card_pool: GcSharedPart<GcCardPool>,
+    channels: GcSharedPart<Vec<GcChannelAction>>,
+    combat_log: GcSharedPart<Vec<GcCombatLogEvent>>,
+}
+
+impl GcBattleSnapshot {
+    /// 记录快照，尽量与上一个快照共享组件
+    pub fn gc_capture(state: &GcBattleState, previous: Option<&GcBattleSnapshot>) -> Self {
+        let players = state.players.iter()
+            .enumerate()
+            .map(|(i, player)| {
+                let previous = previous.and_then(|p| p.players.get(i));
+                GcSharedPart::gc_capture(player, previous)
+            })
+            .collect();
+
+        Self {
+            id: state.id.clone(),
+            turn: state.turn,
+            current_player_index: state.current_player_index,
+            phase: state.phase.clone(),
+            turn_time_limit: state.turn_time_limit,
+            winner_id: state.winner_id.clone(),
+            action_points_per_turn: state.action_points_per_turn,
+            played_this_turn: state.played_this_turn.clone(),
+            sandbox: state.sandbox,
+            forced_terrain: state.forced_terrain,
+            cost_modifiers: state.cost_modifiers.clone(),
+            players,
+            card_pool: GcSharedPart::gc_capture(&state.card_pool, previous.map(|p| &p.card_pool)),
+            channels: GcSharedPart::gc_capture(&state.channels, previous.map(|p| &p.channels)),
+            combat_log: GcSharedPart::gc_capture(&state.combat_log, previous.map(|p| &p.combat_log)),
+        }
+    }
+
+    /// 恢复为完整战斗状态
+    pub fn gc_restore(&self) -> GcBattleState {
+        GcBattleState {
+            id: self.id.clone(),
+            turn: self.turn,
+            current_player_index: self.current_player_index,
+            players: self.players.iter().map(|p| (*p.value).clone()).collect(),
+            phase: self.phase.clone(),
+            turn_time_limit: self.turn_time_limit,
+            winner_id: self.winner_id.clone(),
+            card_pool: (*self.card_pool.value).clone(),
+            action_points_per_turn: self.action_points_per_turn,
+            channels: (*self.channels.value).clone(),
+            combat_log: (*self.combat_log.value).clone(),
+            played_this_turn: self.played_this_turn.clone(),
+            sandbox: self.sandbox,
+            forced_terrain: self.forced_terrain,
+            cost_modifiers: self.cost_modifiers.clone(),
+        }
+    }
+
+    /// 内容是否与另一个快照完全相同 (所有组件共享且基础字段相等)
+    pub fn gc_same_as(&self, other: &GcBattleSnapshot) -> bool {
+        self.players.len() == other.players.len()
+            && self.gc_shared_parts_with(other) == self.players.len() + 3
+            && self.id == other.id
+            && self.turn == other.turn
+            && self.current_player_index == other.current_player_index
+            && self.phase == other.phase
+            && self.turn_time_limit == other.turn_time_limit
+            && self.winner_id == other.winner_id
+            && self.action_points_per_turn == other.action_points_per_turn
+            && self.played_this_turn == other.played_this_turn
+            && self.sandbox == other.sandbox
+            && self.forced_terrain == other.forced_terrain
+            && self.cost_modifiers == other.cost_modifiers
+    }
+
+    /// 与另一个快照共享的组件数量
+    pub fn gc_shared_parts_with(&self, other: &GcBattleSnapshot) -> usize {
+        let players = self.players.iter()
+            .zip(&other.players)
+            .filter(|(a, b)| Arc::ptr_eq(&a.value, &b.value))
+            .count();
+        players
+            + Arc::ptr_eq(&self.card_pool.value, &other.card_pool.value) as usize
+            + Arc::ptr_eq(&self.channels.value, &other.channels.value) as usize
+            + Arc::ptr_eq(&self.combat_log.value, &other.combat_log.value) as usize
+    }
+}
+
+// =============================================================================
+// 历史
+// =============================================================================
+
+/// 战斗历史 (撤销/重做、回放、AI 搜索)
+#[derive(Clone, Debug, Default)]
+pub struct GcBattleHistory {
+    /// 快照 (从旧到新)
+    snapshots: Vec<GcBattleSnapshot>,
+    /// 当前所在快照
+    cursor: usize,
+    /// 最多保留的快照数 (0 表示不限)
+    capacity: usize,
+}
+
+impl GcBattleHistory {
+    /// 创建历史 (capacity 为 0 表示不限数量)
+    pub fn gc_new(capacity: usize) -> Self {
+        Self {
+            snapshots: Vec::new(),
+            cursor: 0,
+            capacity,
+        }
+    }
+
+    /// 快照数量
+    pub fn gc_len(&self) -> usize {
+        self.snapshots.len()
+    }
+
+    /// 是否为空
+    pub fn gc_is_empty(&self) -> bool {
+        self.snapshots.is_empty()
+    }
+
+    /// 获取指定快照
+    pub fn gc_get(&self, index: usize) -> Option<&GcBattleSnapshot> {
+        self.snapshots.get(index)
+    }
+
+    /// 记录当前状态 (丢弃重做分支)，与当前快照相同时不记录，返回是否记录
+    pub fn gc_record(&mut self, state: &GcBattleState) -> bool {
+        let current = self.snapshots.get(self.cursor);
+        let snapshot = GcBattleSnapshot::gc_capture(state, current);
+        if current.is_some_and(|c| snapshot.gc_same_as(c)) {
+            return false;
+        }
+        if !self.snapshots.is_empty() {
+            self.snapshots.truncate(self.cursor + 1);
+        }
+        self.snapshots.push(snapshot);
+        if self.capacity > 0 && self.snapshots.len() > self.capacity {
+            self.snapshots.remove(0);
+        }
+        self.cursor = self.snapshots.len() - 1;
+        true
+    }
+
+    /// 是否可以撤销
+    pub fn gc_can_undo(&self) -> bool {
+        self.cursor > 0
+    }
+
+    /// 是否可以重做
+    pub fn gc_can_redo(&self) -> bool {
+        self.cursor + 1 < self.snapshots.len()
+    }
+
+    /// 撤销，返回上一个状态
+    pub fn gc_undo(&mut self) -> Option<GcBattleState> {
+        if !self.gc_can_undo() {
+            return None;
+        }
+        self.cursor -= 1;
+        Some(self.snapshots[self.cursor].gc_restore())
+    }
+
+    /// 重做，返回下一个状态
+    pub fn gc_redo(&mut self) -> Option<GcBattleState> {
+        if !self.gc_can_redo() {
+            return None;
+        }
+        self.cursor += 1;
+        Some(self.snapshots[self.cursor].gc_restore())
+    }
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_battle() -> GcBattleState {
+        GcBattleState::gc_new("history", vec![
+            GcPlayer::gc_new("p1", "玩家1"),
+            GcPlayer::gc_new("p2", "玩家2"),
+        ])
+    }
+
+    #[test]
+    fn test_unchanged_parts_are_shared() {
+        let mut state = create_battle();
+        let first = GcBattleSnapshot::gc_capture(&state, None);
+
+        state.players[1].stats.hp -= 10;
+        let second = GcBattleSnapshot::gc_capture(&state, Some(&first));
+
+        // p1、卡池、引导、日志共享，只有 p2 被复制
+        assert_eq!(second.gc_shared_parts_with(&first), 4);
+        assert_eq!(second.gc_restore().players[1].stats.hp, first.gc_restore().players[1].stats.hp - 10);
+    }
+
+    #[test]
+    fn test_small_changes_are_never_shared() {
+        let mut state = create_battle();
+        let first = GcBattleSnapshot::gc_capture(&state, None);
+
+        // 只改动一个深层字段也必须复制，恢复后能看到改动
+        state.players[0].stats.energy += 1;
+        let second = GcBattleSnapshot::gc_capture(&state, Some(&first));
+        assert_eq!(second.gc_shared_parts_with(&first), 4);
+        assert_eq!(second.gc_restore().players[0].stats.energy, state.players[0].stats.energy);
+        assert!(!second.gc_same_as(&first));
+    }
+
+    #[test]
+    fn test_undo_redo_and_branching() {
+        let mut state = create_battle();
+        let mut history = GcBattleHistory::gc_new(0);
+        history.gc_record(&state);
+        assert!(!history.gc_record(&state));
+        state.turn = 2;
+        history.gc_record(&state);
+        state.turn = 3;
+        history.gc_record(&state);
+
+        assert_eq!(history.gc_undo().map(|s| s.turn), Some(2));
+        assert_eq!(history.gc_undo().map(|s| s.turn), Some(1));
+        assert!(history.gc_undo().is_none());
+        assert_eq!(history.gc_redo().map(|s| s.turn), Some(2));
+
+        // 在中间记录会丢弃重做分支
+        state.turn = 9;
+        history.gc_record(&state);
+        assert!(!history.gc_can_redo());
+        assert_eq!(history.gc_len(), 3);
+        assert_eq!(history.gc_undo().map(|s| s.turn), Some(2));
+    }
+
+    #[test]
+    fn test_capacity_drops_oldest() {
+        let mut state = create_battle();
+        let mut history = GcBattleHistory::gc_new(2);
+        for turn in 1..=4 {
+            state.turn = turn;
+            history.gc_record(&state);
+        }
+        assert_eq!(history.gc_len(), 2);
+        assert_eq!(history.gc_undo().map(|s| s.turn), Some(3));
+        assert!(!history.gc_can_undo());
+    }
+}
@@ -0,0 +1,184 @@
+//! ID 驻留 (模板ID、实体ID)
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! ## 规则
+//! - `GcInternedId` 是 u32 句柄，复制与比较不分配内存，供核心热路径使用
+//! - 句柄只在当前进程内有效，序列化时一律写出原始字符串，反序列化时重新驻留
+//! - 全局注册表只增不减；相同字符串总是得到相同句柄
+//! - 句柄的数值取决于驻留顺序，不能用于排序结果或持久化
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, OnceLock, RwLock};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+// =============================================================================
+// 句柄
+// =============================================================================
+
+/// 驻留ID句柄
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct GcInternedId(u32);
+
+impl GcInternedId {
+    /// 驻留字符串ID (全局注册表)
+    pub fn gc_new(id: &str) -> Self {
+        gc_intern(id)
+    }
+
+    /// 句柄序号
+    pub fn gc_index(self) -> u32 {
+        self.0
+    }
+
+    /// 原始字符串
+    pub fn gc_as_str(self) -> Arc<str> {
+        gc_registry_read(|registry| registry.gc_resolve(self).cloned()).unwrap_or_else(|| Arc::from(""))
+    }
+}
+
+impl fmt::Display for GcInternedId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.gc_as_str())
+    }
+}
+
+impl PartialEq<str> for GcInternedId {
+    fn eq(&self, other: &str) -> bool {
+        gc_intern_lookup(other) == Some(*self)
+    }
+}
+
+impl PartialEq<&str> for GcInternedId {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
+
+impl Serialize for GcInternedId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.gc_as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for GcInternedId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let id = String::deserialize(deserializer)?;
+        Ok(gc_intern(&id))
+    }
+}
+
+// =============================================================================
+// 注册表
+// =============================================================================
+
+/// ID 注册表
+#[derive(Clone, Debug, Default)]
+pub struct GcIdRegistry {
+    /// 字符串 → 句柄
+    handles: HashMap<Arc<str>, GcInternedId>,
+    /// 句柄 → 字符串
+    names: Vec<Arc<str>>,
+}
+
+impl GcIdRegistry {
+    /// 创建空注册表
+    pub fn gc_new() -> Self {
+        Self::default()
+    }
+
+    /// 驻留字符串 (已存在时返回原句柄)
+    pub fn gc_intern(&mut self, id: &str) -> GcInternedId {
+        if let Some(handle) = self.handles.get(id) {
+            return *handle;
+        }
+        let handle = GcInternedId(self.names.len() as u32);
+        let name: Arc<str> = Arc::from(id);
+        self.names.push(name.clone());
+        self.handles.insert(name, handle);
+        handle
+    }
+
+    /// 查找已驻留的字符串 (不插入)
+    pub fn gc_get(&self, id: &str) -> Option<GcInternedId> {
+        self.handles.get(id).copied()
+    }
+
+    /// 句柄对应的字符串
+    pub fn gc_resolve(&self, handle: GcInternedId) -> Option<&Arc<str>> {
+        self.names.get(handle.0 as usize)
+    }
+
+    /// 已驻留数量
+    pub fn gc_len(&self) -> usize {
+        self.names.len()
+    }
+
+    /// 是否为空
+    pub fn gc_is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+}
+
+/// 全局注册表
+fn gc_global_registry() -> &'static RwLock<GcIdRegistry> {
+    static REGISTRY: OnceLock<RwLock<GcIdRegistry>> = OnceLock::new();
+    REGISTRY.get_or_init(|| RwLock::new(GcIdRegistry::gc_new()))
+}
+
+/// 只读访问全局注册表 (锁中毒时继续使用内部数据，注册表只增不减)
+fn gc_registry_read<R>(f: impl FnOnce(&GcIdRegistry) -> R) -> R {
+    let registry = gc_global_registry().read().unwrap_or_else(|e| e.into_inner());
+    f(&registry)
+}
+
+/// 驻留字符串ID (全局注册表)
+pub fn gc_intern(id: &str) -> GcInternedId {
+    if let Some(handle) = gc_intern_lookup(id) {
+        return handle;
+    }
+    let mut registry = gc_global_registry().write().unwrap_or_else(|e| e.into_inner());
+    registry.gc_intern(id)
+}
+
+/// 查找已驻留的字符串ID (不插入)
+pub fn gc_intern_lookup(id: &str) -> Option<GcInternedId> {
+    gc_registry_read(|registry| registry.gc_get(id))
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_intern_and_resolve() {
+        let mut registry = GcIdRegistry::gc_new();
+        let a = registry.gc_intern("card_knight_attack");
+        let b = registry.gc_intern("monster_slime");
+        assert_ne!(a, b);
+        assert_eq!(registry.gc_intern("card_knight_attack"), a);
+        assert_eq!(registry.gc_len(), 2);
+        assert_eq!(registry.gc_resolve(b).map(|s| &**s), Some("monster_slime"));
+        assert!(registry.gc_get("unknown").is_none());
+    }
+
+    #[test]
+    fn test_serializes_as_string() {
+        let id = gc_intern("intern_test_goblin");
+        assert_eq!(id, "intern_test_goblin");
+        assert_eq!(id.to_string(), "intern_test_goblin");
+
+        let json = serde_json::to_string(&id).unwrap_or_default();
+        assert_eq!(json, "\"intern_test_goblin\"");
+        let parsed: GcInternedId = serde_json::from_str(&json).unwrap_or_else(|_| gc_intern(""));
+        assert_eq!(parsed, id);
+    }
+}
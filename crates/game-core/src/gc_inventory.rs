@@ -9,6 +9,8 @@
 //! - 装备栏管理
 //! - 属性统计
 
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
 use crate::{
@@ -21,7 +23,7 @@ use crate::{
 // =============================================================================
 
 /// 已装备物品
-#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
 pub struct GcEquippedItems {
     /// 武器
     pub weapon: Option<GcEquipment>,
@@ -102,7 +104,7 @@ impl GcEquippedItems {
 // =============================================================================
 
 /// 玩家背包
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct GcInventory {
     /// 玩家 ID
     pub player_id: String,
@@ -112,6 +114,9 @@ pub struct GcInventory {
     pub equipped: GcEquippedItems,
     /// 最大容量
     pub capacity: usize,
+    /// 消耗品 (物品ID -> 数量，不占用背包容量)
+    #[serde(default)]
+    pub consumables: HashMap<String, u32>,
 }
 
 impl GcInventory {
@@ -122,6 +127,7 @@ impl GcInventory {
             items: Vec::new(),
             equipped: GcEquippedItems::default(),
             capacity,
+            consumables: HashMap::new(),
         }
     }
     
@@ -191,6 +197,30 @@ impl GcInventory {
         }
     }
     
+    /// 添加消耗品
+    pub fn gc_add_consumable(&mut self, item_id: &str, count: u32) {
+        *self.consumables.entry(item_id.to_string()).or_insert(0) += count;
+    }
+    
+    /// 获取消耗品数量
+    pub fn gc_consumable_count(&self, item_id: &str) -> u32 {
+        self.consumables.get(item_id).copied().unwrap_or(0)
+    }
+    
+    /// 消耗一个消耗品
+    pub fn gc_consume(&mut self, item_id: &str) -> Result<(), String> {
+        match self.consumables.get_mut(item_id) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                if *count == 0 {
+                    self.consumables.remove(item_id);
+                }
+                Ok(())
+            }
+            _ => Err("消耗品不足".to_string()),
+        }
+    }
+    
     /// 获取所有装备提供的属性加成 (基础 + 战斗)
     pub fn gc_get_total_stats(&self) -> (GcBaseStats, GcCombatStats) {
         (
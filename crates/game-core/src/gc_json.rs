@@ -0,0 +1,120 @@
+//! JSON 输入校验 (带字段路径的诊断)
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! ## 诊断规则
+//! - 所有外部 JSON 输入 (WASM 参数、服务器请求体、WebSocket 消息) 经 `gc_parse_json` 解析
+//! - 失败时报告输入名、出错字段路径 (如 `slots[2].atk`，根节点为 `.`)、原因与期望的类型
+//! - 行列号指向 JSON 文本中出错的位置，便于在编辑器中定位
+//! - 诊断可序列化，客户端按字段路径高亮表单
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+// =============================================================================
+// 诊断
+// =============================================================================
+
+/// JSON 解析诊断
+#[derive(Clone, Debug, Error, PartialEq, Eq, Serialize, Deserialize)]
+#[error("{input} 解析失败: {path}: {message}")]
+pub struct GcJsonDiagnostic {
+    /// 输入名 (参数名、请求体、消息类型)
+    pub input: String,
+    /// 出错字段路径 (根节点为 ".")
+    pub path: String,
+    /// 原因 (serde 原始描述，不含行列号)
+    pub message: String,
+    /// 期望的类型或取值
+    pub expected: Option<String>,
+    /// 出错位置的行号 (从 1 开始，未知时为 0)
+    pub line: usize,
+    /// 出错位置的列号 (从 1 开始，未知时为 0)
+    pub column: usize,
+}
+
+impl GcJsonDiagnostic {
+    /// 从 serde 错误生成诊断
+    fn gc_from_error(input: &str, path: String, inner: serde_json::Error) -> Self {
+        let (line, column) = (inner.line(), inner.column());
+        let mut message = inner.to_string();
+        let location = format!(" at line {} column {}", line, column);
+        if let Some(stripped) = message.strip_suffix(&location) {
+            message = stripped.to_string();
+        }
+        let expected = message
+            .split_once("expected ")
+            .map(|(_, expected)| expected.to_string());
+        Self {
+            input: input.to_string(),
+            path,
+            message,
+            expected,
+            line,
+            column,
+        }
+    }
+}
+
+// =============================================================================
+// 解析
+// =============================================================================
+
+/// 解析 JSON 文本 (input 为输入名，出现在诊断中)
+pub fn gc_parse_json<T: DeserializeOwned>(input: &str, json: &str) -> Result<T, GcJsonDiagnostic> {
+    let mut deserializer = serde_json::Deserializer::from_str(json);
+    let value = serde_path_to_error::deserialize(&mut deserializer)
+        .map_err(|e| GcJsonDiagnostic::gc_from_error(input, e.path().to_string(), e.into_inner()))?;
+    // 末尾不能有多余内容
+    deserializer.end()
+        .map_err(|e| GcJsonDiagnostic::gc_from_error(input, ".".to_string(), e))?;
+    Ok(value)
+}
+
+/// 解析已读入的 JSON 值
+pub fn gc_parse_json_value<T: DeserializeOwned>(
+    input: &str,
+    value: serde_json::Value,
+) -> Result<T, GcJsonDiagnostic> {
+    serde_path_to_error::deserialize(value)
+        .map_err(|e| GcJsonDiagnostic::gc_from_error(input, e.path().to_string(), e.into_inner()))
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GcEconomy, GcParkPolicy};
+
+    #[test]
+    fn test_diagnostic_reports_field_path() {
+        let json = r#"{"idle_ttl_secs":60,"max_age_secs":"lots"}"#;
+        let result: Result<GcParkPolicy, _> = gc_parse_json("policy_json", json);
+        let diagnostic = result.err();
+        assert_eq!(diagnostic.as_ref().map(|d| d.path.as_str()), Some("max_age_secs"));
+        assert_eq!(diagnostic.as_ref().and_then(|d| d.expected.as_deref()), Some("u64"));
+        assert_eq!(diagnostic.as_ref().map(|d| d.line), Some(1));
+
+        let text = diagnostic.map(|d| d.to_string()).unwrap_or_default();
+        assert!(text.starts_with("policy_json 解析失败: max_age_secs: invalid type"));
+    }
+
+    #[test]
+    fn test_syntax_and_trailing_errors() {
+        let broken: Result<GcEconomy, _> = gc_parse_json("economy_json", "{\n  \"gold\": ");
+        assert_eq!(broken.err().map(|d| (d.path, d.line)), Some(("gold".to_string(), 2)));
+
+        let trailing: Result<u32, _> = gc_parse_json("count", "3 4");
+        assert!(trailing.is_err());
+
+        let value = serde_json::json!({ "items": [1, "x"] });
+        let nested: Result<std::collections::HashMap<String, Vec<u32>>, _> = gc_parse_json_value("body", value);
+        assert_eq!(nested.err().map(|d| d.path), Some("items[1]".to_string()));
+    }
+}
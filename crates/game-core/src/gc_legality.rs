@@ -0,0 +1,258 @@
+//! 操作合法性说明 (按钮禁用原因、本地化提示)
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! ## 说明规则
+//! - 与 `gc_validate_play_card` / `gc_deploy_card` 等校验的检查项一致，但返回结构化原因而非错误字符串
+//! - 收集全部不满足的条件 (例如能量与目标同时不满足)，空列表表示操作合法
+//! - 战斗结束、不是该玩家回合、玩家不存在时只返回这一条原因
+//! - 每条原因可转换为 `GcError`，客户端按错误码与参数拼接本地化文案
+
+use serde::{Deserialize, Serialize};
+
+use crate::{gc_effective_card_cost, GcAction, GcBattleState, GcConfig, GcError, GcPlayer};
+
+// =============================================================================
+// 原因
+// =============================================================================
+
+/// 操作不合法的原因
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "code")]
+pub enum GcLegalityReason {
+    /// 战斗已结束
+    BattleEnded,
+    /// 不是该玩家的回合
+    NotYourTurn { current_player_id: Option<String> },
+    /// 玩家不存在
+    PlayerNotFound { player_id: String },
+    /// 卡牌不在手中
+    CardNotInHand { card_id: String },
+    /// 能量不足
+    NotEnoughEnergy { need: u32, have: u32 },
+    /// 行动力不足
+    NotEnoughActionPoints { need: u32, have: u32 },
+    /// 目标无效 (不存在或已无法行动)
+    InvalidTarget { target_id: String },
+    /// 槽位超出范围
+    InvalidSlot { slot_index: usize, slot_count: usize },
+    /// 槽位已被占用
+    SlotOccupied { slot_index: usize, card_id: String, card_name: String },
+    /// 手牌已满
+    HandFull { max: usize },
+    /// 卡牌不在卡池展示区
+    CardNotInPool { card_id: String },
+    /// 技能不存在
+    SkillNotFound { skill_id: String },
+    /// 本回合已使用过技能
+    SkillAlreadyUsed,
+    /// 技能冷却中
+    SkillOnCooldown { turns_left: u32 },
+}
+
+impl GcLegalityReason {
+    /// 对应的核心错误 (用于错误码)
+    pub fn gc_error(&self) -> GcError {
+        match self {
+            Self::BattleEnded => GcError::GcBattleEnded,
+            Self::NotYourTurn { .. } => GcError::GcNotYourTurn,
+            Self::PlayerNotFound { .. } => GcError::GcPlayerNotFound,
+            Self::CardNotInHand { .. } => GcError::GcCardNotInHand,
+            Self::NotEnoughEnergy { .. } => GcError::GcNotEnoughEnergy,
+            Self::NotEnoughActionPoints { .. } => GcError::GcNotEnoughActionPoints,
+            Self::InvalidTarget { .. } => GcError::GcInvalidTarget,
+            Self::InvalidSlot { .. } => GcError::GcInvalidSlot,
+            Self::SlotOccupied { .. } => GcError::GcSlotOccupied,
+            Self::HandFull { .. } => GcError::GcHandFull,
+            Self::CardNotInPool { .. } => GcError::GcCardNotInPool,
+            Self::SkillNotFound { .. } => GcError::GcSkillNotFound,
+            Self::SkillAlreadyUsed => GcError::GcSkillAlreadyUsed,
+            Self::SkillOnCooldown { .. } => GcError::GcSkillOnCooldown,
+        }
+    }
+}
+
+// =============================================================================
+// 说明
+// =============================================================================
+
+/// 说明操作为何不合法 (空列表表示合法)
+pub fn gc_explain_legality(state: &GcBattleState, action: &GcAction) -> Vec<GcLegalityReason> {
+    let player_id = action.gc_player_id();
+
+    let Some(player) = state.gc_find_player(player_id) else {
+        return vec![GcLegalityReason::PlayerNotFound { player_id: player_id.to_string() }];
+    };
+    if state.gc_is_finished() {
+        return vec![GcLegalityReason::BattleEnded];
+    }
+    // 投降不要求轮到自己
+    if let GcAction::Surrender { .. } = action {
+        return Vec::new();
+    }
+    if state.gc_current_player_id() != Some(player_id) {
+        return vec![GcLegalityReason::NotYourTurn {
+            current_player_id: state.gc_current_player_id().map(str::to_string),
+        }];
+    }
+
+    let mut reasons = Vec::new();
+    match action {
+        GcAction::PlayCard { card_id, target_id, .. } => match player.gc_find_card_in_hand(card_id) {
+            Some(card) => {
+                let need = gc_effective_card_cost(state, player_id, card);
+                if player.stats.energy < need {
+                    reasons.push(GcLegalityReason::NotEnoughEnergy { need, have: player.stats.energy });
+                }
+                if card.gc_needs_target() {
+                    gc_check_target(state, None, target_id, &mut reasons);
+                }
+            }
+            None => reasons.push(GcLegalityReason::CardNotInHand { card_id: card_id.clone() }),
+        },
+        GcAction::UseSkill { skill_id, target_id, .. } => {
+            if player.hero_skills.used_this_turn {
+                reasons.push(GcLegalityReason::SkillAlreadyUsed);
+            }
+            match player.hero_skills.gc_find(skill_id) {
+                Some(skill) => {
+                    if !skill.gc_is_ready() {
+                        reasons.push(GcLegalityReason::SkillOnCooldown { turns_left: skill.current_cooldown });
+                    }
+                    if player.stats.energy < skill.energy_cost {
+                        reasons.push(GcLegalityReason::NotEnoughEnergy {
+                            need: skill.energy_cost,
+                            have: player.stats.energy,
+                        });
+                    }
+                    if skill.effect.needs_target() {
+                        gc_check_target(state, Some(player_id), target_id, &mut reasons);
+                    }
+                }
+                None => reasons.push(GcLegalityReason::SkillNotFound { skill_id: skill_id.clone() }),
+            }
+        }
+        GcAction::DeployCard { card_id, slot_index, .. } => {
+            gc_check_action_points(player, 1, &mut reasons);
+            match player.battlefield.gc_get_slot(*slot_index) {
+                Some(slot) => {
+                    if let Some(card) = &slot.card {
+                        reasons.push(GcLegalityReason::SlotOccupied {
+                            slot_index: *slot_index,
+                            card_id: card.id.clone(),
+                            card_name: card.name.clone(),
+                        });
+                    }
+                }
+                None => reasons.push(GcLegalityReason::InvalidSlot {
+                    slot_index: *slot_index,
+                    slot_count: player.battlefield.slots.len(),
+                }),
+            }
+            if player.gc_find_card_in_hand(card_id).is_none() {
+                reasons.push(GcLegalityReason::CardNotInHand { card_id: card_id.clone() });
+            }
+        }
+        GcAction::AcquireCard { card_id, .. } => {
+            gc_check_action_points(player, state.card_pool.config.acquire_cost, &mut reasons);
+            if player.gc_is_hand_full() {
+                reasons.push(GcLegalityReason::HandFull { max: GcConfig::MAX_HAND_SIZE });
+            }
+            if !state.gc_get_pool_display().iter().any(|c| &c.id == card_id) {
+                reasons.push(GcLegalityReason::CardNotInPool { card_id: card_id.clone() });
+            }
+        }
+        GcAction::EndTurn { .. } | GcAction::Surrender { .. } => {}
+    }
+    reasons
+}
+
+/// 检查行动力
+fn gc_check_action_points(player: &GcPlayer, need: u32, reasons: &mut Vec<GcLegalityReason>) {
+    if !player.stats.gc_has_action_points(need) {
+        reasons.push(GcLegalityReason::NotEnoughActionPoints { need, have: player.stats.action_points });
+    }
+}
+
+/// 检查目标 (exclude 为不能选择的玩家，例如技能不能以自己为目标)
+fn gc_check_target(
+    state: &GcBattleState,
+    exclude: Option<&str>,
+    target_id: &str,
+    reasons: &mut Vec<GcLegalityReason>,
+) {
+    let valid = state
+        .gc_find_player(target_id)
+        .is_some_and(|t| t.gc_can_act() && exclude != Some(t.id.as_str()));
+    if !valid {
+        reasons.push(GcLegalityReason::InvalidTarget { target_id: target_id.to_string() });
+    }
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GcCard;
+
+    fn create_battle() -> GcBattleState {
+        GcBattleState::gc_new("b1", vec![GcPlayer::gc_new("p1", "玩家1"), GcPlayer::gc_new("p2", "玩家2")])
+    }
+
+    #[test]
+    fn test_explain_play_card() {
+        let mut battle = create_battle();
+        battle.players[0].stats.energy = 1;
+        battle.players[0].hand.push(GcCard::gc_new_attack("fire", "火球", 3, 10));
+
+        let play = |target: &str| GcAction::PlayCard {
+            player_id: "p1".to_string(),
+            card_id: "fire".to_string(),
+            target_id: target.to_string(),
+        };
+        assert_eq!(
+            gc_explain_legality(&battle, &play("ghost")),
+            vec![
+                GcLegalityReason::NotEnoughEnergy { need: 3, have: 1 },
+                GcLegalityReason::InvalidTarget { target_id: "ghost".to_string() },
+            ]
+        );
+
+        battle.players[0].stats.energy = 3;
+        assert!(gc_explain_legality(&battle, &play("p2")).is_empty());
+
+        let out_of_turn = GcAction::EndTurn { player_id: "p2".to_string() };
+        assert_eq!(
+            gc_explain_legality(&battle, &out_of_turn),
+            vec![GcLegalityReason::NotYourTurn { current_player_id: Some("p1".to_string()) }]
+        );
+        assert!(gc_explain_legality(&battle, &GcAction::Surrender { player_id: "p2".to_string() }).is_empty());
+    }
+
+    #[test]
+    fn test_explain_deploy_slot_occupied() {
+        let mut battle = create_battle();
+        if let Some(slot) = battle.players[0].battlefield.gc_get_slot_mut(0) {
+            slot.gc_deploy(GcCard::gc_new_attack("wolf", "野狼", 1, 4));
+        }
+        let deploy = GcAction::DeployCard {
+            player_id: "p1".to_string(),
+            card_id: "missing".to_string(),
+            slot_index: 0,
+        };
+        let reasons = gc_explain_legality(&battle, &deploy);
+        assert_eq!(
+            reasons[0],
+            GcLegalityReason::SlotOccupied { slot_index: 0, card_id: "wolf".to_string(), card_name: "野狼".to_string() }
+        );
+        assert_eq!(reasons[1].gc_error().gc_code(), 3001);
+
+        let json = serde_json::to_string(&reasons[1]).unwrap_or_default();
+        assert_eq!(json, r#"{"code":"CardNotInHand","card_id":"missing"}"#);
+    }
+}
@@ -0,0 +1,288 @@
+//! 斩杀检测 (本回合能否获胜、可造成的最大伤害)
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! ## 搜索规则
+//! - 在状态副本上搜索本回合的操作序列: 出牌、英雄技能 (可关闭)，最后可以结束回合触发战场攻击
+//! - 能量、行动力与技能冷却由合法性检查与执行器约束，不合法的分支直接跳过
+//! - 战斗因此结束且操作者一方获胜即为斩杀；逐层加深搜索，返回步数最少的序列
+//! - 同一节点上数值相同的手牌只尝试一张；部署与拿牌不参与搜索 (新部署的卡牌本回合不能攻击)
+//! - 搜索深度与节点数有上限，超出时 `complete = false`，此时没有找到斩杀不代表一定没有
+//! - `max_damage` 为搜索到的序列中对敌方造成的最大伤害总和 (威胁范围)
+
+use serde::{Deserialize, Serialize};
+
+use crate::gc_preview::gc_apply_action;
+use crate::{gc_explain_legality, GcAction, GcBattleState};
+
+// =============================================================================
+// 常量
+// =============================================================================
+
+/// 默认最大搜索深度 (操作步数)
+pub const GC_LETHAL_MAX_DEPTH: usize = 6;
+
+/// 默认最大搜索节点数
+pub const GC_LETHAL_MAX_NODES: usize = 4000;
+
+// =============================================================================
+// 搜索参数与结果
+// =============================================================================
+
+/// 搜索参数
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcLethalSearch {
+    /// 最大搜索深度
+    pub max_depth: usize,
+    /// 最大搜索节点数
+    pub max_nodes: usize,
+    /// 是否使用英雄技能
+    pub use_skills: bool,
+}
+
+impl Default for GcLethalSearch {
+    fn default() -> Self {
+        Self { max_depth: GC_LETHAL_MAX_DEPTH, max_nodes: GC_LETHAL_MAX_NODES, use_skills: true }
+    }
+}
+
+/// 斩杀检测结果
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcLethalCheck {
+    /// 玩家 ID
+    pub player_id: String,
+    /// 本回合能否获胜
+    pub lethal: bool,
+    /// 斩杀序列 (按顺序执行；没有斩杀时为空)
+    pub actions: Vec<GcAction>,
+    /// 本回合可对敌方造成的最大伤害总和
+    pub max_damage: u32,
+    /// 搜索的节点数
+    pub nodes: usize,
+    /// 是否搜索完整 (未触及深度或节点上限)
+    pub complete: bool,
+}
+
+impl GcLethalCheck {
+    /// 斩杀序列用到的手牌 (界面高亮)
+    pub fn gc_card_ids(&self) -> Vec<&str> {
+        self.actions.iter()
+            .filter_map(|a| match a {
+                GcAction::PlayCard { card_id, .. } => Some(card_id.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+// =============================================================================
+// 搜索
+// =============================================================================
+
+struct GcLethalSearcher<'a> {
+    root: &'a GcBattleState,
+    player_id: &'a str,
+    search: GcLethalSearch,
+    path: Vec<GcAction>,
+    nodes: usize,
+    truncated: bool,
+    max_damage: u32,
+}
+
+impl GcLethalSearcher<'_> {
+    /// 本回合可选的操作
+    fn gc_candidates(&self, state: &GcBattleState) -> Vec<GcAction> {
+        let Some(player) = state.gc_find_player(self.player_id) else {
+            return Vec::new();
+        };
+        let targets: Vec<&str> = state.players.iter()
+            .filter(|p| p.gc_can_act() && !state.gc_are_allies(self.player_id, &p.id))
+            .map(|p| p.id.as_str())
+            .collect();
+
+        let mut actions = Vec::new();
+        let mut seen = Vec::new();
+        for card in &player.hand {
+            let key = (card.name.as_str(), card.cost, card.base_damage, card.base_defense);
+            if seen.contains(&key) {
+                continue;
+            }
+            seen.push(key);
+            for target in &targets {
+                actions.push(GcAction::PlayCard {
+                    player_id: self.player_id.to_string(),
+                    card_id: card.id.clone(),
+                    target_id: target.to_string(),
+                });
+            }
+        }
+        if self.search.use_skills && !player.hero_skills.used_this_turn {
+            for skill in player.hero_skills.skills.iter().filter(|s| s.gc_is_ready()) {
+                let targets = if skill.effect.needs_target() { targets.clone() } else { vec![self.player_id] };
+                for target in targets {
+                    actions.push(GcAction::UseSkill {
+                        player_id: self.player_id.to_string(),
+                        skill_id: skill.id.clone(),
+                        target_id: target.to_string(),
+                    });
+                }
+            }
+        }
+        if player.battlefield.slots.iter().any(|s| s.card.is_some()) {
+            actions.push(GcAction::EndTurn { player_id: self.player_id.to_string() });
+        }
+        actions
+    }
+
+    /// 对敌方造成的伤害总和 (相对搜索起点)
+    fn gc_damage(&self, state: &GcBattleState) -> u32 {
+        self.root.players.iter()
+            .filter(|p| !self.root.gc_are_allies(self.player_id, &p.id))
+            .filter_map(|p| Some(p.stats.hp.saturating_sub(state.gc_find_player(&p.id)?.stats.hp)))
+            .sum()
+    }
+
+    /// 深度受限搜索，找到斩杀时返回 true (序列留在 path 中)
+    fn gc_dfs(&mut self, state: &GcBattleState, depth: usize) -> bool {
+        if depth == 0 {
+            self.truncated = true;
+            return false;
+        }
+        for action in self.gc_candidates(state) {
+            if self.nodes >= self.search.max_nodes {
+                self.truncated = true;
+                return false;
+            }
+            self.nodes += 1;
+            if !gc_explain_legality(state, &action).is_empty() {
+                continue;
+            }
+            let mut next = state.clone();
+            if gc_apply_action(&mut next, &action).is_err() {
+                continue;
+            }
+            self.max_damage = self.max_damage.max(self.gc_damage(&next));
+
+            let end_turn = matches!(action, GcAction::EndTurn { .. });
+            self.path.push(action);
+            if next.gc_is_finished() {
+                if next.gc_winner_ids().iter().any(|id| id == self.player_id) {
+                    return true;
+                }
+            } else if !end_turn && self.gc_dfs(&next, depth - 1) {
+                return true;
+            }
+            self.path.pop();
+        }
+        false
+    }
+}
+
+/// 按参数搜索斩杀
+pub fn gc_search_lethal(state: &GcBattleState, player_id: &str, search: GcLethalSearch) -> GcLethalCheck {
+    let mut check = GcLethalCheck { player_id: player_id.to_string(), ..Default::default() };
+    if state.gc_is_finished() || state.gc_current_player_id() != Some(player_id) {
+        check.complete = true;
+        return check;
+    }
+
+    let mut searcher = GcLethalSearcher {
+        root: state,
+        player_id,
+        search,
+        path: Vec::new(),
+        nodes: 0,
+        truncated: false,
+        max_damage: 0,
+    };
+    // 逐层加深: 先找到的序列步数最少
+    for depth in 1..=search.max_depth.max(1) {
+        searcher.truncated = false;
+        if searcher.gc_dfs(state, depth) {
+            check.lethal = true;
+            check.actions = std::mem::take(&mut searcher.path);
+            break;
+        }
+        if !searcher.truncated || searcher.nodes >= search.max_nodes {
+            break;
+        }
+    }
+    check.max_damage = searcher.max_damage;
+    check.nodes = searcher.nodes;
+    check.complete = check.lethal || !searcher.truncated;
+    check
+}
+
+/// 搜索本回合的斩杀 (默认参数)
+pub fn gc_find_lethal(state: &GcBattleState, player_id: &str) -> GcLethalCheck {
+    gc_search_lethal(state, player_id, GcLethalSearch::default())
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{gc_preview_action, GcCard, GcPlayer};
+
+    fn create_battle() -> GcBattleState {
+        let mut state = GcBattleState::gc_new("b1", vec![GcPlayer::gc_new("p1", "玩家1"), GcPlayer::gc_new("p2", "玩家2")]);
+        state.players[0].stats.energy = 3;
+        state.players[0].hand.push(GcCard::gc_new_attack("a1", "轻击", 1, 6));
+        state.players[0].hand.push(GcCard::gc_new_attack("a2", "轻击", 1, 6));
+        state.players[0].hand.push(GcCard::gc_new_attack("big", "重击", 2, 12));
+        state
+    }
+
+    #[test]
+    fn test_finds_shortest_lethal_sequence() {
+        let mut state = create_battle();
+        let no_skills = GcLethalSearch { use_skills: false, ..Default::default() };
+        let big = GcAction::PlayCard { player_id: "p1".to_string(), card_id: "big".to_string(), target_id: "p2".to_string() };
+        let big_damage = gc_preview_action(&state, &big).damage_dealt;
+        state.players[1].stats.hp = big_damage;
+
+        let check = gc_search_lethal(&state, "p1", no_skills);
+        assert!(check.lethal);
+        assert!(check.complete);
+        assert_eq!(check.actions, vec![big]);
+        assert_eq!(check.gc_card_ids(), vec!["big"]);
+        assert_eq!(state.players[1].stats.hp, big_damage);
+
+        // 原状态不变，可以按序列执行
+        let mut replay = state.clone();
+        for action in &check.actions {
+            assert!(gc_apply_action(&mut replay, action).is_ok());
+        }
+        assert_eq!(replay.gc_winner_ids(), vec!["p1".to_string()]);
+    }
+
+    #[test]
+    fn test_no_lethal_reports_threat() {
+        let state = create_battle();
+        let check = gc_search_lethal(&state, "p1", GcLethalSearch { use_skills: false, ..Default::default() });
+        assert!(!check.lethal);
+        assert!(check.actions.is_empty());
+        assert!(check.complete);
+        assert!(check.max_damage > 0);
+        assert!(check.max_damage < state.players[1].stats.hp);
+
+        // 不是自己的回合
+        let other = gc_find_lethal(&state, "p2");
+        assert!(!other.lethal);
+        assert_eq!(other.nodes, 0);
+    }
+
+    #[test]
+    fn test_node_budget_marks_incomplete() {
+        let state = create_battle();
+        let check = gc_search_lethal(&state, "p1", GcLethalSearch { max_nodes: 2, ..Default::default() });
+        assert!(!check.lethal);
+        assert!(!check.complete);
+        assert_eq!(check.nodes, 2);
+    }
+}
@@ -7,12 +7,19 @@
 //! - Lv.1-2: 3 个战场槽位
 //! - Lv.3-4: 4 个战场槽位
 //! - Lv.5+:  5 个战场槽位
+//! - 手牌区(备战席)默认 8 格，可通过效果扩容至 12 格
 //!
 //! ## 稀有度权重
 //! 玩家等级越高，商店出现高星怪兽的概率越高
+//!
+//! ## 找怪兽
+//! 指定模板的刷新命中率 = 稀有度概率 ÷ 该稀有度可出现的模板数 (同稀有度模板等概率)，
+//! 期望刷新花费按几何分布计算 (期望刷新次数 × 刷新费用)
 
 use serde::{Deserialize, Serialize};
 
+use crate::{GcMonsterPool, GC_MAX_LEVEL, GC_REFRESH_COST};
+
 // =============================================================================
 // 槽位常量
 // =============================================================================
@@ -23,8 +30,11 @@ pub const GC_MIN_BOARD_SLOTS: u8 = 3;
 /// 最大战场槽位数
 pub const GC_MAX_BOARD_SLOTS: u8 = 5;
 
-/// 手牌区(备战席)无上限
-pub const GC_BENCH_UNLIMITED: bool = true;
+/// 手牌区(备战席)默认容量
+pub const GC_DEFAULT_BENCH_CAPACITY: usize = 8;
+
+/// 手牌区(备战席)扩容上限
+pub const GC_MAX_BENCH_CAPACITY: usize = 12;
 
 // =============================================================================
 // 稀有度/星级定义
@@ -119,6 +129,33 @@ impl GcTierWeights {
         
         GcMonsterTier::Tier1 // 默认
     }
+    
+    /// 指定稀有度的概率 (%)
+    pub fn percent_of(&self, tier: GcMonsterTier) -> u8 {
+        self.weights[tier as usize - 1]
+    }
+    
+    /// 单个商店槽位的期望稀有度
+    pub fn expected_tier(&self) -> f32 {
+        self.weights.iter()
+            .enumerate()
+            .map(|(i, &w)| (i + 1) as f32 * w as f32)
+            .sum::<f32>() / 100.0
+    }
+    
+    /// 单个商店槽位的期望购买价格
+    pub fn expected_cost(&self) -> f32 {
+        self.weights.iter()
+            .enumerate()
+            .map(|(i, &w)| GcMonsterTier::from_u8((i + 1) as u8).cost() as f32 * w as f32)
+            .sum::<f32>() / 100.0
+    }
+    
+    /// 一次刷新 (slots 个槽位) 中至少出现一只该稀有度的概率 (%)
+    pub fn refresh_chance(&self, tier: GcMonsterTier, slots: u8) -> f32 {
+        let miss = 1.0 - self.percent_of(tier) as f32 / 100.0;
+        (1.0 - miss.powi(slots as i32)) * 100.0
+    }
 }
 
 /// 各等级的稀有度权重表
@@ -138,6 +175,143 @@ pub fn gc_get_tier_weights(level: u8) -> GcTierWeights {
     }
 }
 
+// =============================================================================
+// 商店概率预览
+// =============================================================================
+
+/// 单个稀有度的商店概率 (供 UI 显示 "3费: 25%")
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GcTierOdds {
+    /// 稀有度
+    pub tier: GcMonsterTier,
+    /// 当前等级概率 (%)
+    pub percent: u8,
+    /// 下一等级概率 (%)
+    pub next_level_percent: u8,
+    /// 升级后的变化 (百分点)
+    pub delta: i16,
+    /// 一次刷新中至少出现一只的概率 (%)
+    pub refresh_chance: f32,
+}
+
+/// 商店概率预览
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GcShopOddsPreview {
+    /// 当前等级
+    pub level: u8,
+    /// 是否已满级 (下一等级数据与当前相同)
+    pub is_max_level: bool,
+    /// 各稀有度概率
+    pub odds: Vec<GcTierOdds>,
+    /// 当前等级单槽期望稀有度
+    pub expected_tier: f32,
+    /// 下一等级单槽期望稀有度
+    pub next_level_expected_tier: f32,
+    /// 当前等级单槽期望价格
+    pub expected_cost: f32,
+}
+
+/// 获取各稀有度概率及升级后的变化
+pub fn gc_get_tier_odds(level: u8) -> Vec<GcTierOdds> {
+    let current = gc_get_tier_weights(level);
+    let next = gc_get_tier_weights(level.saturating_add(1).min(GC_MAX_LEVEL));
+    let slots = gc_get_shop_slots(level);
+
+    (1..=5u8)
+        .map(GcMonsterTier::from_u8)
+        .map(|tier| {
+            let percent = current.percent_of(tier);
+            let next_level_percent = next.percent_of(tier);
+            GcTierOdds {
+                tier,
+                percent,
+                next_level_percent,
+                delta: next_level_percent as i16 - percent as i16,
+                refresh_chance: current.refresh_chance(tier, slots),
+            }
+        })
+        .collect()
+}
+
+/// 获取商店概率预览 (当前/下一等级概率与期望值)
+pub fn gc_get_shop_odds_preview(level: u8) -> GcShopOddsPreview {
+    let is_max_level = level >= GC_MAX_LEVEL;
+    let current = gc_get_tier_weights(level);
+    let next = gc_get_tier_weights(level.saturating_add(1).min(GC_MAX_LEVEL));
+
+    GcShopOddsPreview {
+        level,
+        is_max_level,
+        odds: gc_get_tier_odds(level),
+        expected_tier: current.expected_tier(),
+        next_level_expected_tier: next.expected_tier(),
+        expected_cost: current.expected_cost(),
+    }
+}
+
+// =============================================================================
+// 找怪兽 (指定模板的刷新概率)
+// =============================================================================
+
+/// 指定模板的刷新概率 (供 "找怪兽" 搜索与刷新决策)
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GcTemplateOdds {
+    /// 模板ID
+    pub template_id: String,
+    /// 名称
+    pub name: String,
+    /// 稀有度
+    pub tier: GcMonsterTier,
+    /// 同稀有度可出现的模板数
+    pub tier_templates: u32,
+    /// 单个槽位出现的概率 (%)
+    pub slot_chance: f32,
+    /// 一次刷新中至少出现一只的概率 (%)
+    pub refresh_chance: f32,
+    /// 期望刷新花费 (金币，当前等级无法出现时为 None)
+    pub expected_refresh_cost: Option<f32>,
+}
+
+/// 期望刷新花费: 每次刷新命中率为 `refresh_chance` (%) 时，直到命中的期望金币
+pub fn gc_expected_refresh_cost(refresh_chance: f32, refresh_cost: u32) -> Option<f32> {
+    if refresh_chance <= 0.0 {
+        return None;
+    }
+    Some(100.0 / refresh_chance.min(100.0) * refresh_cost as f32)
+}
+
+/// 指定模板在下一次刷新中的概率
+///
+/// `open_slots` 为会被刷新的槽位数 (未冻结)。模板不存在或仅能通过进化获得时返回 None
+pub fn gc_get_template_odds(pool: &GcMonsterPool, template_id: &str, level: u8, open_slots: u8) -> Option<GcTemplateOdds> {
+    let template = pool.find_template(template_id).filter(|t| !t.evolution_only)?;
+    let tier_templates = pool.templates_by_tier(template.tier).len() as u32;
+    let slot_chance = gc_get_tier_weights(level).percent_of(template.tier) as f32 / tier_templates as f32;
+    let miss = 1.0 - slot_chance / 100.0;
+    let refresh_chance = (1.0 - miss.powi(open_slots as i32)) * 100.0;
+
+    Some(GcTemplateOdds {
+        template_id: template.template_id.clone(),
+        name: template.name.clone(),
+        tier: template.tier,
+        tier_templates,
+        slot_chance,
+        refresh_chance,
+        expected_refresh_cost: gc_expected_refresh_cost(refresh_chance, GC_REFRESH_COST),
+    })
+}
+
+/// 找怪兽: 按名称或模板ID (不区分大小写的子串) 搜索，返回各模板的刷新概率，命中率高的在前
+pub fn gc_find_unit_odds(pool: &GcMonsterPool, query: &str, level: u8, open_slots: u8) -> Vec<GcTemplateOdds> {
+    let query = query.trim().to_lowercase();
+    let mut odds: Vec<GcTemplateOdds> = pool.templates().iter()
+        .filter(|t| t.name.to_lowercase().contains(&query) || t.template_id.to_lowercase().contains(&query))
+        .filter_map(|t| gc_get_template_odds(pool, &t.template_id, level, open_slots))
+        .collect();
+    odds.sort_by(|a, b| b.refresh_chance.total_cmp(&a.refresh_chance).then_with(|| a.template_id.cmp(&b.template_id)));
+    odds
+}
+
 // =============================================================================
 // 等级信息结构
 // =============================================================================
@@ -203,4 +377,69 @@ mod tests {
         assert_eq!(weights.select_tier(50), GcMonsterTier::Tier2);  // 50-79 -> T2
         assert_eq!(weights.select_tier(80), GcMonsterTier::Tier3);  // 80-99 -> T3
     }
+    
+    #[test]
+    fn test_tier_odds_deltas() {
+        let odds = gc_get_tier_odds(3);
+        assert_eq!(odds[1].percent, 25);
+        assert_eq!(odds[2].next_level_percent, 15);
+        assert_eq!(odds[0].delta, -20);
+        assert!((odds[0].refresh_chance - 100.0 * (1.0 - 0.25f32.powi(5))).abs() < 0.01);
+        
+        // 满级时下一等级与当前相同
+        assert!(gc_get_tier_odds(GC_MAX_LEVEL).iter().all(|o| o.delta == 0));
+    }
+    
+    #[test]
+    fn test_shop_odds_preview_expectations() {
+        let preview = gc_get_shop_odds_preview(1);
+        assert!(!preview.is_max_level);
+        assert!((preview.expected_tier - 1.0).abs() < 0.001);
+        assert!((preview.expected_cost - 1.0).abs() < 0.001);
+        
+        let preview = gc_get_shop_odds_preview(2);
+        assert!((preview.next_level_expected_tier - 1.25).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_template_odds_and_refresh_cost() {
+        let pool = GcMonsterPool::with_defaults();
+        let tier1 = pool.templates_by_tier(GcMonsterTier::Tier1);
+        let template_id = tier1[0].template_id.clone();
+
+        let odds = gc_get_template_odds(&pool, &template_id, 1, 5).expect("模板存在");
+        assert_eq!(odds.tier_templates as usize, tier1.len());
+        assert!((odds.slot_chance - 100.0 / tier1.len() as f32).abs() < 0.001);
+        let expected = (1.0 - (1.0 - odds.slot_chance / 100.0).powi(5)) * 100.0;
+        assert!((odds.refresh_chance - expected).abs() < 0.001);
+        assert!(odds.expected_refresh_cost.is_some());
+
+        // 冻结槽位越多命中率越低；全部冻结则无法命中
+        let fewer = gc_get_template_odds(&pool, &template_id, 1, 2).expect("模板存在");
+        assert!(fewer.refresh_chance < odds.refresh_chance);
+        assert_eq!(gc_get_template_odds(&pool, &template_id, 1, 0).and_then(|o| o.expected_refresh_cost), None);
+
+        // 当前等级不出现的稀有度
+        let tier3 = pool.templates_by_tier(GcMonsterTier::Tier3)[0].template_id.clone();
+        let odds = gc_get_template_odds(&pool, &tier3, 1, 5).expect("模板存在");
+        assert_eq!(odds.refresh_chance, 0.0);
+        assert!(gc_get_template_odds(&pool, "no_such_template", 1, 5).is_none());
+
+        assert_eq!(gc_expected_refresh_cost(50.0, 2), Some(4.0));
+        assert_eq!(gc_expected_refresh_cost(0.0, 2), None);
+    }
+
+    #[test]
+    fn test_find_unit_odds_sorted_by_chance() {
+        let pool = GcMonsterPool::with_defaults();
+        let all = gc_find_unit_odds(&pool, "", 4, 5);
+        assert!(!all.is_empty());
+        assert!(all.windows(2).all(|w| w[0].refresh_chance >= w[1].refresh_chance));
+        assert!(all.iter().all(|o| !pool.find_template(&o.template_id).is_some_and(|t| t.evolution_only)));
+
+        let name = all[0].name.clone();
+        let found = gc_find_unit_odds(&pool, &name, 4, 5);
+        assert!(found.iter().any(|o| o.template_id == all[0].template_id));
+        assert!(gc_find_unit_odds(&pool, "不存在的怪兽", 4, 5).is_empty());
+    }
 }
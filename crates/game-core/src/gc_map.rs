@@ -8,9 +8,18 @@
 //! - 瓦片类型 (草地、水、墙壁等)
 //! - 坐标系统
 //! - 地图结构 (二维网格)
+//! - 可选图层: 海拔、移动消耗、刷怪区域 (未设置时不写入 JSON)
+
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BinaryHeap, HashMap};
 
 use serde::{Deserialize, Serialize};
 
+use crate::{GcDayPhase, GcEnemyType, GcWeather};
+
+/// 未设置移动消耗图层时，可通行瓦片的默认消耗
+pub const GC_DEFAULT_MOVEMENT_COST: u32 = 1;
+
 // =============================================================================
 // 坐标系统
 // =============================================================================
@@ -189,6 +198,12 @@ pub struct GcTile {
     pub explored: bool,
     /// 是否可见 (战争迷雾)
     pub visible: bool,
+    /// 海拔图层 (影响战斗地形生成，未设置视为 0)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub elevation: Option<i16>,
+    /// 移动消耗图层 (寻路用，未设置时使用默认消耗)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub movement_cost: Option<u32>,
 }
 
 impl GcTile {
@@ -199,6 +214,8 @@ impl GcTile {
             entity_id: None,
             explored: false,
             visible: false,
+            elevation: None,
+            movement_cost: None,
         }
     }
     
@@ -209,6 +226,8 @@ impl GcTile {
             entity_id: Some(entity_id.to_string()),
             explored: false,
             visible: false,
+            elevation: None,
+            movement_cost: None,
         }
     }
     
@@ -221,6 +240,140 @@ impl GcTile {
     pub fn gc_is_interactable(&self) -> bool {
         self.tile_type.gc_is_interactable()
     }
+    
+    /// 海拔 (未设置为 0)
+    pub fn gc_elevation(&self) -> i16 {
+        self.elevation.unwrap_or(0)
+    }
+    
+    /// 进入该瓦片的移动消耗 (不可通行为 None)
+    pub fn gc_movement_cost(&self) -> Option<u32> {
+        if !self.gc_is_walkable() {
+            return None;
+        }
+        Some(self.movement_cost.unwrap_or(GC_DEFAULT_MOVEMENT_COST).max(1))
+    }
+}
+
+// =============================================================================
+// 刷怪区域
+// =============================================================================
+
+fn gc_default_region_level() -> u32 {
+    1
+}
+
+/// 命名刷怪区域 (遭遇生成器按区域挑选怪物)
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GcSpawnRegion {
+    /// 区域 ID
+    pub id: String,
+    /// 区域名称
+    pub name: String,
+    /// 左上角 X
+    pub x: i32,
+    /// 左上角 Y
+    pub y: i32,
+    /// 宽度
+    pub width: usize,
+    /// 高度
+    pub height: usize,
+    /// 敌人类型 (影响战斗地形权重)
+    #[serde(default)]
+    pub enemy_type: GcEnemyType,
+    /// 可出现的怪物 ID
+    #[serde(default)]
+    pub monster_ids: Vec<String>,
+    /// 最低等级
+    #[serde(default = "gc_default_region_level")]
+    pub min_level: u32,
+    /// 最高等级
+    #[serde(default = "gc_default_region_level")]
+    pub max_level: u32,
+    /// 掉落表引用 (未设置时为 `loot_<区域ID>`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub loot_table: Option<String>,
+    /// 出现怪物的时段 (为空表示全天)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub active_phases: Vec<GcDayPhase>,
+    /// 特定天气下的怪物表 (未配置的天气使用 `monster_ids`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub weather_monsters: BTreeMap<GcWeather, Vec<String>>,
+}
+
+impl GcSpawnRegion {
+    /// 创建刷怪区域
+    pub fn gc_new(id: &str, name: &str, x: i32, y: i32, width: usize, height: usize) -> Self {
+        Self {
+            id: id.to_string(),
+            name: name.to_string(),
+            x,
+            y,
+            width,
+            height,
+            enemy_type: GcEnemyType::Normal,
+            monster_ids: Vec::new(),
+            min_level: 1,
+            max_level: 1,
+            loot_table: None,
+            active_phases: Vec::new(),
+            weather_monsters: BTreeMap::new(),
+        }
+    }
+    
+    /// 设置怪物与等级范围
+    pub fn gc_with_monsters(mut self, monster_ids: &[&str], min_level: u32, max_level: u32) -> Self {
+        self.monster_ids = monster_ids.iter().map(|id| id.to_string()).collect();
+        self.min_level = min_level.min(max_level);
+        self.max_level = max_level.max(min_level);
+        self
+    }
+    
+    /// 设置敌人类型
+    pub fn gc_with_enemy_type(mut self, enemy_type: GcEnemyType) -> Self {
+        self.enemy_type = enemy_type;
+        self
+    }
+    
+    /// 限定出现怪物的时段
+    pub fn gc_with_phases(mut self, phases: &[GcDayPhase]) -> Self {
+        self.active_phases = phases.to_vec();
+        self
+    }
+    
+    /// 设置某种天气下的怪物表
+    pub fn gc_with_weather_monsters(mut self, weather: GcWeather, monster_ids: &[&str]) -> Self {
+        self.weather_monsters.insert(weather, monster_ids.iter().map(|id| id.to_string()).collect());
+        self
+    }
+    
+    /// 该天气下可出现的怪物
+    pub fn gc_monsters_for(&self, weather: GcWeather) -> &[String] {
+        match self.weather_monsters.get(&weather) {
+            Some(ids) if !ids.is_empty() => ids,
+            _ => &self.monster_ids,
+        }
+    }
+    
+    /// 该时段是否出现怪物
+    pub fn gc_is_active(&self, phase: GcDayPhase) -> bool {
+        self.active_phases.is_empty() || self.active_phases.contains(&phase)
+    }
+    
+    /// 位置是否在区域内
+    pub fn gc_contains(&self, pos: &GcPosition) -> bool {
+        let (dx, dy) = (pos.x as i64 - self.x as i64, pos.y as i64 - self.y as i64);
+        dx >= 0 && dy >= 0 && (dx as u64) < self.width as u64 && (dy as u64) < self.height as u64
+    }
+}
+
+/// 寻路结果
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcPath {
+    /// 途经位置 (不含起点，含终点)
+    pub steps: Vec<GcPosition>,
+    /// 总移动消耗
+    pub cost: u32,
 }
 
 // =============================================================================
@@ -242,6 +395,9 @@ pub struct GcMap {
     pub tiles: Vec<Vec<GcTile>>,
     /// 出生点
     pub spawn_point: GcPosition,
+    /// 刷怪区域图层
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub spawn_regions: Vec<GcSpawnRegion>,
 }
 
 impl GcMap {
@@ -262,6 +418,7 @@ impl GcMap {
             height,
             tiles,
             spawn_point: GcPosition::gc_new(width as i32 / 2, height as i32 / 2),
+            spawn_regions: Vec::new(),
         }
     }
     
@@ -349,6 +506,106 @@ impl GcMap {
         }
     }
     
+    /// 设置海拔 (None 清除)
+    pub fn gc_set_elevation(&mut self, pos: &GcPosition, elevation: Option<i16>) -> bool {
+        if let Some(tile) = self.gc_get_tile_mut(pos) {
+            tile.elevation = elevation;
+            true
+        } else {
+            false
+        }
+    }
+    
+    /// 设置移动消耗 (None 恢复默认)
+    pub fn gc_set_movement_cost(&mut self, pos: &GcPosition, movement_cost: Option<u32>) -> bool {
+        if let Some(tile) = self.gc_get_tile_mut(pos) {
+            tile.movement_cost = movement_cost;
+            true
+        } else {
+            false
+        }
+    }
+    
+    /// 填充矩形区域的海拔
+    pub fn gc_fill_elevation(&mut self, x: i32, y: i32, w: usize, h: usize, elevation: Option<i16>) {
+        for dy in 0..h {
+            for dx in 0..w {
+                self.gc_set_elevation(&GcPosition::gc_new(x + dx as i32, y + dy as i32), elevation);
+            }
+        }
+    }
+    
+    /// 位置海拔 (越界为 0)
+    pub fn gc_elevation_at(&self, pos: &GcPosition) -> i16 {
+        self.gc_get_tile(pos).map(|t| t.gc_elevation()).unwrap_or(0)
+    }
+    
+    /// 添加刷怪区域 (同 ID 替换)
+    pub fn gc_set_spawn_region(&mut self, region: GcSpawnRegion) {
+        if let Some(existing) = self.spawn_regions.iter_mut().find(|r| r.id == region.id) {
+            *existing = region;
+        } else {
+            self.spawn_regions.push(region);
+        }
+    }
+    
+    /// 移除刷怪区域
+    pub fn gc_remove_spawn_region(&mut self, region_id: &str) -> bool {
+        let before = self.spawn_regions.len();
+        self.spawn_regions.retain(|r| r.id != region_id);
+        self.spawn_regions.len() != before
+    }
+    
+    /// 包含该位置的第一个刷怪区域
+    pub fn gc_spawn_region_at(&self, pos: &GcPosition) -> Option<&GcSpawnRegion> {
+        self.spawn_regions.iter().find(|r| r.gc_contains(pos))
+    }
+    
+    /// 包含该位置、且在该时段出现怪物的第一个刷怪区域
+    pub fn gc_active_spawn_region_at(&self, pos: &GcPosition, phase: GcDayPhase) -> Option<&GcSpawnRegion> {
+        self.spawn_regions.iter().find(|r| r.gc_contains(pos) && r.gc_is_active(phase))
+    }
+    
+    /// 按移动消耗寻找最短路径 (Dijkstra，四方向)
+    pub fn gc_find_path(&self, from: &GcPosition, to: &GcPosition) -> Option<GcPath> {
+        self.gc_get_tile(from)?;
+        self.gc_get_tile(to)?.gc_movement_cost()?;
+        
+        let mut best: HashMap<GcPosition, u32> = HashMap::from([(*from, 0)]);
+        let mut came_from: HashMap<GcPosition, GcPosition> = HashMap::new();
+        // (消耗, y, x) 保证相同消耗时展开顺序固定
+        let mut open = BinaryHeap::from([Reverse((0u32, from.y, from.x))]);
+        
+        while let Some(Reverse((cost, y, x))) = open.pop() {
+            let pos = GcPosition::gc_new(x, y);
+            if pos == *to {
+                let mut steps = vec![pos];
+                while let Some(prev) = came_from.get(steps.last()?) {
+                    steps.push(*prev);
+                }
+                steps.pop();
+                steps.reverse();
+                return Some(GcPath { steps, cost });
+            }
+            if best.get(&pos).is_some_and(|&c| c < cost) {
+                continue;
+            }
+            for direction in [GcDirection::Up, GcDirection::Down, GcDirection::Left, GcDirection::Right] {
+                let next = pos.gc_move(direction);
+                let Some(step_cost) = self.gc_get_tile(&next).and_then(|t| t.gc_movement_cost()) else {
+                    continue;
+                };
+                let next_cost = cost.saturating_add(step_cost);
+                if best.get(&next).is_none_or(|&c| next_cost < c) {
+                    best.insert(next, next_cost);
+                    came_from.insert(next, pos);
+                    open.push(Reverse((next_cost, next.y, next.x)));
+                }
+            }
+        }
+        None
+    }
+    
     /// 转换为 ASCII 字符串 (调试用)
     pub fn gc_to_ascii(&self) -> String {
         let mut result = String::new();
@@ -433,6 +690,43 @@ mod tests {
         assert_eq!(tile.entity_id, Some("npc_shop_1".to_string()));
     }
     
+    #[test]
+    fn test_gc_map_layers_serialization() {
+        let mut map = GcMap::gc_new("test", "测试地图", 3, 1);
+        let plain = serde_json::to_string(&map).unwrap_or_default();
+        assert!(!plain.contains("elevation") && !plain.contains("spawn_regions"));
+        
+        map.gc_set_elevation(&GcPosition::gc_new(1, 0), Some(12));
+        map.gc_set_movement_cost(&GcPosition::gc_new(2, 0), Some(3));
+        map.gc_set_spawn_region(GcSpawnRegion::gc_new("r1", "草丛", 0, 0, 2, 1)
+            .gc_with_monsters(&["monster_slime"], 1, 3));
+        let json = serde_json::to_string(&map).unwrap_or_default();
+        let loaded: GcMap = serde_json::from_str(&json).unwrap_or_else(|_| GcMap::gc_new("x", "x", 1, 1));
+        assert_eq!(loaded.gc_elevation_at(&GcPosition::gc_new(1, 0)), 12);
+        assert_eq!(loaded.gc_elevation_at(&GcPosition::gc_new(0, 0)), 0);
+        assert_eq!(loaded.tiles[0][2].gc_movement_cost(), Some(3));
+        assert_eq!(loaded.gc_spawn_region_at(&GcPosition::gc_new(1, 0)).map(|r| r.id.as_str()), Some("r1"));
+        assert!(loaded.gc_spawn_region_at(&GcPosition::gc_new(2, 0)).is_none());
+    }
+    
+    #[test]
+    fn test_gc_map_find_path_uses_movement_cost() {
+        let mut map = GcMap::gc_new("test", "测试地图", 5, 3);
+        // 中间一行是沼泽 (消耗 5)，绕行上方更便宜
+        map.gc_set_movement_cost(&GcPosition::gc_new(2, 1), Some(5));
+        map.gc_set_tile(&GcPosition::gc_new(2, 2), GcTileType::Wall);
+        
+        let path = map.gc_find_path(&GcPosition::gc_new(0, 1), &GcPosition::gc_new(4, 1));
+        assert_eq!(path.as_ref().map(|p| p.cost), Some(6));
+        assert!(path.is_some_and(|p| !p.steps.contains(&GcPosition::gc_new(2, 1))));
+        
+        map.gc_set_tile(&GcPosition::gc_new(2, 0), GcTileType::Water);
+        let through = map.gc_find_path(&GcPosition::gc_new(0, 1), &GcPosition::gc_new(4, 1));
+        assert_eq!(through.map(|p| (p.cost, p.steps.len())), Some((8, 4)));
+        map.gc_set_tile(&GcPosition::gc_new(2, 1), GcTileType::Wall);
+        assert!(map.gc_find_path(&GcPosition::gc_new(0, 1), &GcPosition::gc_new(4, 1)).is_none());
+    }
+    
     #[test]
     fn test_gc_map_fill_rect() {
         let mut map = GcMap::gc_new("test", "测试地图", 10, 10);
@@ -0,0 +1,391 @@
+//! 地图分块 (大地图按需加载)
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! ## 分块规则
+//! - 地图按 `chunk_size` × `chunk_size` (默认 32) 切块，边缘块可能更小
+//! - 先发送 `GcMapHeader` (尺寸、出生点、块大小)，再按玩家位置逐块请求 `GcMapChunk`
+//! - 客户端只持有已加载的块，未加载位置视为不可通行
+//! - 修改瓦片会把所在块标记为脏块，保存时只写出脏块
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{GcMap, GcPosition, GcSpawnRegion, GcTile, GcTileType};
+
+/// 默认块大小 (格)
+pub const GC_CHUNK_SIZE: usize = 32;
+
+// =============================================================================
+// 块坐标与块数据
+// =============================================================================
+
+/// 块坐标 (以块为单位)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct GcChunkCoord {
+    pub cx: i32,
+    pub cy: i32,
+}
+
+impl GcChunkCoord {
+    /// 创建块坐标
+    pub fn gc_new(cx: i32, cy: i32) -> Self {
+        Self { cx, cy }
+    }
+}
+
+/// 地图块
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GcMapChunk {
+    /// 块坐标
+    pub coord: GcChunkCoord,
+    /// 块宽度 (边缘块可能小于块大小)
+    pub width: usize,
+    /// 块高度
+    pub height: usize,
+    /// 瓦片 (按行存储: tiles[y * width + x])
+    pub tiles: Vec<GcTile>,
+}
+
+impl GcMapChunk {
+    /// 块内坐标的瓦片
+    pub fn gc_get(&self, local_x: usize, local_y: usize) -> Option<&GcTile> {
+        if local_x >= self.width || local_y >= self.height {
+            return None;
+        }
+        self.tiles.get(local_y * self.width + local_x)
+    }
+
+    fn gc_get_mut(&mut self, local_x: usize, local_y: usize) -> Option<&mut GcTile> {
+        if local_x >= self.width || local_y >= self.height {
+            return None;
+        }
+        self.tiles.get_mut(local_y * self.width + local_x)
+    }
+}
+
+/// 地图头信息 (不含瓦片)
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GcMapHeader {
+    /// 地图 ID
+    pub id: String,
+    /// 地图名称
+    pub name: String,
+    /// 地图宽度
+    pub width: usize,
+    /// 地图高度
+    pub height: usize,
+    /// 出生点
+    pub spawn_point: GcPosition,
+    /// 块大小
+    pub chunk_size: usize,
+    /// 刷怪区域图层 (随头信息下发)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub spawn_regions: Vec<GcSpawnRegion>,
+}
+
+impl GcMapHeader {
+    /// 横向块数
+    pub fn gc_chunks_x(&self) -> i32 {
+        self.width.div_ceil(self.chunk_size) as i32
+    }
+
+    /// 纵向块数
+    pub fn gc_chunks_y(&self) -> i32 {
+        self.height.div_ceil(self.chunk_size) as i32
+    }
+
+    /// 块坐标是否在地图范围内
+    pub fn gc_is_valid_chunk(&self, coord: GcChunkCoord) -> bool {
+        coord.cx >= 0 && coord.cy >= 0 && coord.cx < self.gc_chunks_x() && coord.cy < self.gc_chunks_y()
+    }
+
+    /// 位置所在的块
+    pub fn gc_chunk_of(&self, pos: &GcPosition) -> GcChunkCoord {
+        let size = self.chunk_size as i32;
+        GcChunkCoord::gc_new(pos.x.div_euclid(size), pos.y.div_euclid(size))
+    }
+
+    /// 添加刷怪区域 (同 ID 替换)
+    pub fn gc_set_spawn_region(&mut self, region: GcSpawnRegion) {
+        self.spawn_regions.retain(|r| r.id != region.id);
+        self.spawn_regions.push(region);
+    }
+
+    /// 移除刷怪区域
+    pub fn gc_remove_spawn_region(&mut self, region_id: &str) -> bool {
+        let before = self.spawn_regions.len();
+        self.spawn_regions.retain(|r| r.id != region_id);
+        self.spawn_regions.len() != before
+    }
+
+    /// 位置周围 `radius` 块内的全部有效块 (按行优先)
+    pub fn gc_chunks_around(&self, pos: &GcPosition, radius: u32) -> Vec<GcChunkCoord> {
+        let center = self.gc_chunk_of(pos);
+        let r = radius.min(i32::MAX as u32) as i32;
+        let mut coords = Vec::new();
+        for cy in center.cy.saturating_sub(r)..=center.cy.saturating_add(r) {
+            for cx in center.cx.saturating_sub(r)..=center.cx.saturating_add(r) {
+                let coord = GcChunkCoord::gc_new(cx, cy);
+                if self.gc_is_valid_chunk(coord) {
+                    coords.push(coord);
+                }
+            }
+        }
+        coords
+    }
+}
+
+// =============================================================================
+// 分块地图
+// =============================================================================
+
+/// 分块地图 (可只加载部分块)
+#[derive(Clone, Debug)]
+pub struct GcChunkedMap {
+    /// 头信息
+    pub header: GcMapHeader,
+    /// 已加载的块
+    chunks: BTreeMap<GcChunkCoord, GcMapChunk>,
+    /// 修改过、尚未保存的块
+    dirty: BTreeSet<GcChunkCoord>,
+}
+
+impl GcChunkedMap {
+    /// 只有头信息的空地图 (客户端按需加载块)
+    pub fn gc_from_header(header: GcMapHeader) -> Self {
+        Self {
+            header,
+            chunks: BTreeMap::new(),
+            dirty: BTreeSet::new(),
+        }
+    }
+
+    /// 把完整地图切块 (块大小为 0 时使用默认值)
+    pub fn gc_from_map(map: &GcMap, chunk_size: usize) -> Self {
+        let chunk_size = if chunk_size == 0 { GC_CHUNK_SIZE } else { chunk_size };
+        let mut chunked = Self::gc_from_header(GcMapHeader {
+            id: map.id.clone(),
+            name: map.name.clone(),
+            width: map.width,
+            height: map.height,
+            spawn_point: map.spawn_point,
+            chunk_size,
+            spawn_regions: map.spawn_regions.clone(),
+        });
+        for cy in 0..chunked.header.gc_chunks_y() {
+            for cx in 0..chunked.header.gc_chunks_x() {
+                let coord = GcChunkCoord::gc_new(cx, cy);
+                let x0 = cx as usize * chunk_size;
+                let y0 = cy as usize * chunk_size;
+                let width = chunk_size.min(map.width - x0);
+                let height = chunk_size.min(map.height - y0);
+                let tiles = (y0..y0 + height)
+                    .flat_map(|y| map.tiles[y][x0..x0 + width].iter().cloned())
+                    .collect();
+                chunked.chunks.insert(coord, GcMapChunk { coord, width, height, tiles });
+            }
+        }
+        chunked
+    }
+
+    /// 拼回完整地图 (有未加载的块时返回 None)
+    pub fn gc_to_map(&self) -> Option<GcMap> {
+        let header = &self.header;
+        let mut tiles = Vec::with_capacity(header.height);
+        for y in 0..header.height {
+            let mut row = Vec::with_capacity(header.width);
+            for x in 0..header.width {
+                row.push(self.gc_get_tile(&GcPosition::gc_new(x as i32, y as i32))?.clone());
+            }
+            tiles.push(row);
+        }
+        Some(GcMap {
+            id: header.id.clone(),
+            name: header.name.clone(),
+            width: header.width,
+            height: header.height,
+            tiles,
+            spawn_point: header.spawn_point,
+            spawn_regions: header.spawn_regions.clone(),
+        })
+    }
+
+    /// 加载块 (替换已有块，块坐标无效时拒绝)
+    pub fn gc_load_chunk(&mut self, chunk: GcMapChunk) -> Result<(), String> {
+        if !self.header.gc_is_valid_chunk(chunk.coord) {
+            return Err("块坐标超出地图范围".to_string());
+        }
+        if chunk.tiles.len() != chunk.width * chunk.height {
+            return Err("块瓦片数量与尺寸不一致".to_string());
+        }
+        self.dirty.remove(&chunk.coord);
+        self.chunks.insert(chunk.coord, chunk);
+        Ok(())
+    }
+
+    /// 卸载块 (远离玩家时释放内存，脏块保留)
+    pub fn gc_unload_chunk(&mut self, coord: GcChunkCoord) -> bool {
+        if self.dirty.contains(&coord) {
+            return false;
+        }
+        self.chunks.remove(&coord).is_some()
+    }
+
+    /// 块是否已加载
+    pub fn gc_is_loaded(&self, coord: GcChunkCoord) -> bool {
+        self.chunks.contains_key(&coord)
+    }
+
+    /// 获取块
+    pub fn gc_chunk(&self, coord: GcChunkCoord) -> Option<&GcMapChunk> {
+        self.chunks.get(&coord)
+    }
+
+    /// 已加载的块数量
+    pub fn gc_loaded_count(&self) -> usize {
+        self.chunks.len()
+    }
+
+    /// 获取瓦片 (所在块未加载时为 None)
+    pub fn gc_get_tile(&self, pos: &GcPosition) -> Option<&GcTile> {
+        let (coord, lx, ly) = self.gc_locate(pos)?;
+        self.chunks.get(&coord)?.gc_get(lx, ly)
+    }
+
+    /// 检查位置是否可通行 (未加载视为不可通行)
+    pub fn gc_can_walk(&self, pos: &GcPosition) -> bool {
+        self.gc_get_tile(pos).map(|t| t.gc_is_walkable()).unwrap_or(false)
+    }
+
+    /// 设置瓦片类型 (标记脏块)
+    pub fn gc_set_tile(&mut self, pos: &GcPosition, tile_type: GcTileType) -> bool {
+        self.gc_edit_tile(pos, |tile| tile.tile_type = tile_type)
+    }
+
+    /// 放置实体 (标记脏块)
+    pub fn gc_place_entity(&mut self, pos: &GcPosition, tile_type: GcTileType, entity_id: &str) -> bool {
+        self.gc_edit_tile(pos, |tile| {
+            tile.tile_type = tile_type;
+            tile.entity_id = Some(entity_id.to_string());
+        })
+    }
+
+    /// 设置海拔 (标记脏块)
+    pub fn gc_set_elevation(&mut self, pos: &GcPosition, elevation: Option<i16>) -> bool {
+        self.gc_edit_tile(pos, |tile| tile.elevation = elevation)
+    }
+
+    /// 设置移动消耗 (标记脏块)
+    pub fn gc_set_movement_cost(&mut self, pos: &GcPosition, movement_cost: Option<u32>) -> bool {
+        self.gc_edit_tile(pos, |tile| tile.movement_cost = movement_cost)
+    }
+
+    /// 脏块坐标
+    pub fn gc_dirty_chunks(&self) -> Vec<GcChunkCoord> {
+        self.dirty.iter().copied().collect()
+    }
+
+    /// 取出脏块用于保存，并清除脏标记
+    pub fn gc_take_dirty_chunks(&mut self) -> Vec<GcMapChunk> {
+        let dirty = std::mem::take(&mut self.dirty);
+        dirty.into_iter().filter_map(|coord| self.chunks.get(&coord).cloned()).collect()
+    }
+
+    /// 位置对应的块与块内坐标
+    fn gc_locate(&self, pos: &GcPosition) -> Option<(GcChunkCoord, usize, usize)> {
+        if pos.x < 0 || pos.y < 0 || pos.x as usize >= self.header.width || pos.y as usize >= self.header.height {
+            return None;
+        }
+        let size = self.header.chunk_size;
+        let coord = self.header.gc_chunk_of(pos);
+        Some((coord, pos.x as usize % size, pos.y as usize % size))
+    }
+
+    fn gc_edit_tile(&mut self, pos: &GcPosition, edit: impl FnOnce(&mut GcTile)) -> bool {
+        let Some((coord, lx, ly)) = self.gc_locate(pos) else {
+            return false;
+        };
+        let Some(tile) = self.chunks.get_mut(&coord).and_then(|c| c.gc_get_mut(lx, ly)) else {
+            return false;
+        };
+        edit(tile);
+        self.dirty.insert(coord);
+        true
+    }
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_map() -> GcMap {
+        let mut map = GcMap::gc_new("big", "大地图", 70, 40);
+        map.gc_set_tile(&GcPosition::gc_new(65, 35), GcTileType::Wall);
+        map.gc_place_entity(&GcPosition::gc_new(33, 1), GcTileType::Npc, "npc_1");
+        map
+    }
+
+    #[test]
+    fn test_split_and_reassemble() {
+        let map = create_map();
+        let chunked = GcChunkedMap::gc_from_map(&map, GC_CHUNK_SIZE);
+        assert_eq!((chunked.header.gc_chunks_x(), chunked.header.gc_chunks_y()), (3, 2));
+        assert_eq!(chunked.gc_loaded_count(), 6);
+
+        let edge = chunked.gc_chunk(GcChunkCoord::gc_new(2, 1)).map(|c| (c.width, c.height));
+        assert_eq!(edge, Some((6, 8)));
+        assert!(!chunked.gc_can_walk(&GcPosition::gc_new(65, 35)));
+        assert_eq!(
+            chunked.gc_get_tile(&GcPosition::gc_new(33, 1)).and_then(|t| t.entity_id.as_deref()),
+            Some("npc_1"),
+        );
+
+        let rebuilt = chunked.gc_to_map().map(|m| m.gc_to_ascii());
+        assert_eq!(rebuilt, Some(map.gc_to_ascii()));
+    }
+
+    #[test]
+    fn test_streaming_loads_chunks_around_player() {
+        let map = create_map();
+        let full = GcChunkedMap::gc_from_map(&map, GC_CHUNK_SIZE);
+        let mut client = GcChunkedMap::gc_from_header(full.header.clone());
+        assert!(!client.gc_can_walk(&GcPosition::gc_new(1, 1)));
+
+        let around = client.header.gc_chunks_around(&GcPosition::gc_new(1, 1), 1);
+        assert_eq!(around, vec![
+            GcChunkCoord::gc_new(0, 0), GcChunkCoord::gc_new(1, 0),
+            GcChunkCoord::gc_new(0, 1), GcChunkCoord::gc_new(1, 1),
+        ]);
+        for coord in around {
+            let chunk = full.gc_chunk(coord).cloned();
+            assert!(chunk.map(|c| client.gc_load_chunk(c).is_ok()).unwrap_or(false));
+        }
+        assert!(client.gc_can_walk(&GcPosition::gc_new(1, 1)));
+        assert!(client.gc_get_tile(&GcPosition::gc_new(65, 35)).is_none());
+        assert!(client.gc_to_map().is_none());
+    }
+
+    #[test]
+    fn test_dirty_chunk_tracking() {
+        let mut chunked = GcChunkedMap::gc_from_map(&create_map(), 16);
+        assert!(chunked.gc_set_tile(&GcPosition::gc_new(17, 2), GcTileType::Water));
+        assert!(chunked.gc_place_entity(&GcPosition::gc_new(18, 3), GcTileType::Chest, "chest_1"));
+        assert!(!chunked.gc_set_tile(&GcPosition::gc_new(-1, 0), GcTileType::Water));
+        assert_eq!(chunked.gc_dirty_chunks(), vec![GcChunkCoord::gc_new(1, 0)]);
+        assert!(!chunked.gc_unload_chunk(GcChunkCoord::gc_new(1, 0)));
+
+        let saved = chunked.gc_take_dirty_chunks();
+        assert_eq!(saved.len(), 1);
+        assert_eq!(saved[0].gc_get(1, 2).map(|t| t.tile_type), Some(GcTileType::Water));
+        assert!(chunked.gc_dirty_chunks().is_empty());
+        assert!(chunked.gc_unload_chunk(GcChunkCoord::gc_new(1, 0)));
+    }
+}
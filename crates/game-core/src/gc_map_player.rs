@@ -68,6 +68,25 @@ impl GcMapPlayer {
         }
     }
     
+    /// 移动并检测进入格子的交互 (撞墙时只改变朝向)
+    pub fn gc_move(&mut self, direction: GcDirection, map: &GcMap) -> GcMoveResult {
+        self.direction = direction;
+        let new_pos = self.position.gc_move(direction);
+        if !map.gc_can_walk(&new_pos) {
+            return GcMoveResult {
+                success: false,
+                new_position: self.position,
+                interaction: None,
+            };
+        }
+        self.position = new_pos;
+        GcMoveResult {
+            success: true,
+            new_position: new_pos,
+            interaction: gc_tile_interaction(map, &new_pos),
+        }
+    }
+    
     /// 传送到指定位置
     pub fn gc_teleport(&mut self, map_id: &str, position: GcPosition) {
         self.current_map_id = map_id.to_string();
@@ -116,6 +135,19 @@ pub enum GcInteraction {
     Chest { chest_id: String },
 }
 
+/// 位置上的交互 (可交互瓦片且带实体 ID)
+pub fn gc_tile_interaction(map: &GcMap, pos: &GcPosition) -> Option<GcInteraction> {
+    let tile = map.gc_get_tile(pos).filter(|t| t.gc_is_interactable())?;
+    let entity_id = tile.entity_id.clone()?;
+    match tile.tile_type {
+        GcTileType::Portal => Some(GcInteraction::Portal { portal_id: entity_id }),
+        GcTileType::Npc => Some(GcInteraction::Npc { npc_id: entity_id }),
+        GcTileType::Monster => Some(GcInteraction::Monster { monster_id: entity_id }),
+        GcTileType::Chest => Some(GcInteraction::Chest { chest_id: entity_id }),
+        _ => None,
+    }
+}
+
 // =============================================================================
 // 地图世界管理器
 // =============================================================================
@@ -197,18 +229,7 @@ impl GcWorld {
         
         // 检查交互
         let interaction = if can_walk {
-            map.gc_get_tile(&new_pos)
-                .filter(|t| t.gc_is_interactable())
-                .and_then(|t| {
-                    let entity_id = t.entity_id.clone()?;
-                    Some(match t.tile_type {
-                        GcTileType::Portal => GcInteraction::Portal { portal_id: entity_id },
-                        GcTileType::Npc => GcInteraction::Npc { npc_id: entity_id },
-                        GcTileType::Monster => GcInteraction::Monster { monster_id: entity_id },
-                        GcTileType::Chest => GcInteraction::Chest { chest_id: entity_id },
-                        _ => return None,
-                    })
-                })
+            gc_tile_interaction(map, &new_pos)
         } else {
             None
         };
@@ -337,4 +358,21 @@ mod tests {
         assert!(result.success);
         assert!(matches!(result.interaction, Some(GcInteraction::Npc { .. })));
     }
+    
+    #[test]
+    fn test_gc_map_player_move_reports_interaction() {
+        let map = create_test_map();
+        let mut player = GcMapPlayer::gc_new("player1", "test_map", GcPosition::gc_new(7, 6));
+        
+        let result = player.gc_move(GcDirection::Down, &map);
+        assert!(result.success);
+        assert!(matches!(result.interaction, Some(GcInteraction::Npc { ref npc_id }) if npc_id == "npc_shop"));
+        
+        // 撞墙: 位置不变，朝向改变
+        player.gc_teleport("test_map", GcPosition::gc_new(4, 2));
+        let result = player.gc_move(GcDirection::Down, &map);
+        assert!(!result.success);
+        assert_eq!(result.new_position, GcPosition::gc_new(4, 2));
+        assert_eq!(player.direction, GcDirection::Down);
+    }
 }
@@ -9,7 +9,7 @@
 //! - 森林 (普通怪物)
 //! - 副本入口 (Boss 战)
 
-use crate::{GcMap, GcPosition, GcTileType, GcWorld};
+use crate::{GcMap, GcPosition, GcSpawnRegion, GcTileType, GcWorld};
 
 // =============================================================================
 // 主城地图
@@ -108,6 +108,24 @@ pub fn gc_create_forest_map() -> GcMap {
         map.gc_place_entity(&GcPosition::gc_new(x, y), GcTileType::Monster, id);
     }
     
+    // 刷怪区域: 小溪西侧是史莱姆与哥布林，东侧山坡是狼群
+    map.gc_set_spawn_region(
+        GcSpawnRegion::gc_new("region_forest_west", "林间空地", 0, 0, 12, 20)
+            .gc_with_monsters(&["monster_slime", "monster_goblin"], 1, 3),
+    );
+    map.gc_set_spawn_region(
+        GcSpawnRegion::gc_new("region_forest_east", "狼嚎山坡", 13, 0, 12, 20)
+            .gc_with_monsters(&["monster_wolf"], 3, 5),
+    );
+    
+    // 海拔: 东侧山坡抬高，小溪低洼；溪边泥地难走
+    map.gc_fill_elevation(13, 0, 12, 20, Some(60));
+    map.gc_fill_elevation(12, 0, 1, 20, Some(-15));
+    for y in [9, 11] {
+        map.gc_set_movement_cost(&GcPosition::gc_new(11, y), Some(2));
+        map.gc_set_movement_cost(&GcPosition::gc_new(13, y), Some(2));
+    }
+    
     // 宝箱
     map.gc_place_entity(&GcPosition::gc_new(22, 2), GcTileType::Chest, "chest_forest_1");
     map.gc_place_entity(&GcPosition::gc_new(2, 18), GcTileType::Chest, "chest_forest_2");
@@ -218,6 +236,12 @@ mod tests {
         let monster_pos = GcPosition::gc_new(5, 5);
         let tile = map.gc_get_tile(&monster_pos).unwrap();
         assert_eq!(tile.tile_type, GcTileType::Monster);
+        
+        // 检查刷怪区域与海拔图层
+        let region = map.gc_spawn_region_at(&monster_pos).map(|r| r.id.as_str());
+        assert_eq!(region, Some("region_forest_west"));
+        assert_eq!(map.gc_elevation_at(&GcPosition::gc_new(16, 6)), 60);
+        assert!(map.gc_find_path(&map.spawn_point, &GcPosition::gc_new(20, 13)).is_some());
     }
     
     #[test]
@@ -0,0 +1,340 @@
+//! 地图上传校验
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! ## 校验规则
+//! - 上传内容有两种: 编辑器场景 (实体列表) 与瓦片地图 (`GcMap`，含 `tiles` 字段)
+//! - 先按对应结构反序列化，结构错误返回带字段路径的 `GcJsonDiagnostic`
+//! - 再检查内容: 实体 ID 唯一、坐标为 3 个有限数值、缩放为正数、瓦片行列与宽高一致等
+//! - 地图 ID 只允许字母、数字、`_`、`-` (最长 64)，杜绝 `../` 之类的路径穿越
+//! - 编辑器场景的字段名沿用客户端的 camelCase 写法 (`ambientColor`)，作为别名接受
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{gc_parse_json_value, GcJsonDiagnostic, GcMap};
+
+/// 地图 ID 最大长度
+pub const GC_MAP_ID_MAX_LEN: usize = 64;
+
+// =============================================================================
+// 编辑器场景
+// =============================================================================
+
+/// 编辑器场景实体类型
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GcSceneEntityType {
+    #[serde(alias = "tree")]
+    Tree,
+    #[serde(alias = "structure")]
+    Structure,
+    #[serde(alias = "enemy")]
+    Enemy,
+    #[serde(alias = "npc")]
+    Npc,
+    #[serde(alias = "waypoint")]
+    Waypoint,
+}
+
+/// 实体缩放 (统一缩放或 [x, y, z])
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum GcSceneScale {
+    Uniform(f64),
+    Axes(Vec<f64>),
+}
+
+/// 编辑器场景实体
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GcSceneEntity {
+    /// 实体类型
+    #[serde(rename = "type")]
+    pub entity_type: GcSceneEntityType,
+    /// 实体 ID (场景内唯一)
+    pub id: String,
+    /// 预制体名称
+    pub prefab: String,
+    /// 位置 [x, y, z]
+    pub position: Vec<f64>,
+    /// 旋转 [x, y, z]
+    #[serde(default)]
+    pub rotation: Option<Vec<f64>>,
+    /// 缩放
+    #[serde(default)]
+    pub scale: Option<GcSceneScale>,
+    /// 额外属性 (按实体类型由客户端解释)
+    #[serde(default)]
+    pub properties: Option<serde_json::Value>,
+}
+
+/// 编辑器场景环境设置
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GcSceneSettings {
+    /// 场景尺寸
+    pub size: f64,
+    /// 天空盒
+    pub skybox: String,
+    /// 环境光颜色 [r, g, b]
+    #[serde(alias = "ambientColor")]
+    pub ambient_color: Vec<f64>,
+    /// 雾浓度
+    #[serde(alias = "fogDensity")]
+    pub fog_density: f64,
+    /// 雾颜色 [r, g, b]
+    #[serde(default, alias = "fogColor")]
+    pub fog_color: Option<Vec<f64>>,
+    /// 太阳光强度
+    #[serde(default, alias = "sunIntensity")]
+    pub sun_intensity: Option<f64>,
+    /// 太阳方向 [x, y, z]
+    #[serde(default, alias = "sunDirection")]
+    pub sun_direction: Option<Vec<f64>>,
+}
+
+/// 编辑器场景 (地图编辑器保存的格式)
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GcEditorScene {
+    /// 地图 ID (缺省为 "map_default")
+    #[serde(default = "gc_default_scene_id")]
+    pub id: String,
+    /// 地图名称
+    pub name: String,
+    /// 格式版本
+    pub version: String,
+    /// 环境设置
+    pub settings: GcSceneSettings,
+    /// 实体列表
+    pub entities: Vec<GcSceneEntity>,
+}
+
+fn gc_default_scene_id() -> String {
+    "map_default".to_string()
+}
+
+// =============================================================================
+// 校验结果
+// =============================================================================
+
+/// 内容问题 (字段路径 + 原因)
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcMapIssue {
+    /// 字段路径，如 `entities[3].position`
+    pub path: String,
+    /// 原因
+    pub message: String,
+}
+
+/// 上传校验失败
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum GcMapUploadError {
+    /// 结构不符 (字段缺失、类型错误)
+    #[error("{0}")]
+    GcInvalidJson(GcJsonDiagnostic),
+    /// 内容不合法
+    #[error("地图校验失败: {} 处问题", .0.len())]
+    GcInvalidContent(Vec<GcMapIssue>),
+}
+
+/// 通过校验的上传内容
+#[derive(Clone, Debug)]
+pub enum GcMapUpload {
+    /// 编辑器场景
+    Scene(GcEditorScene),
+    /// 瓦片地图
+    Tiles(GcMap),
+}
+
+impl GcMapUpload {
+    /// 地图 ID
+    pub fn gc_id(&self) -> &str {
+        match self {
+            GcMapUpload::Scene(scene) => &scene.id,
+            GcMapUpload::Tiles(map) => &map.id,
+        }
+    }
+}
+
+// =============================================================================
+// 校验
+// =============================================================================
+
+/// 地图 ID 是否可用作文件名
+pub fn gc_is_valid_map_id(id: &str) -> bool {
+    !id.is_empty()
+        && id.len() <= GC_MAP_ID_MAX_LEN
+        && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// 校验上传的地图 JSON
+pub fn gc_validate_map_upload(value: serde_json::Value) -> Result<GcMapUpload, GcMapUploadError> {
+    let upload = if value.get("tiles").is_some() {
+        GcMapUpload::Tiles(gc_parse_json_value("map", value).map_err(GcMapUploadError::GcInvalidJson)?)
+    } else {
+        GcMapUpload::Scene(gc_parse_json_value("scene", value).map_err(GcMapUploadError::GcInvalidJson)?)
+    };
+
+    let mut issues = Vec::new();
+    if !gc_is_valid_map_id(upload.gc_id()) {
+        gc_issue(&mut issues, "id", "地图 ID 只能包含字母、数字、_ 和 -，且不超过 64 个字符");
+    }
+    match &upload {
+        GcMapUpload::Scene(scene) => gc_check_scene(scene, &mut issues),
+        GcMapUpload::Tiles(map) => gc_check_tiles(map, &mut issues),
+    }
+
+    if issues.is_empty() {
+        Ok(upload)
+    } else {
+        Err(GcMapUploadError::GcInvalidContent(issues))
+    }
+}
+
+fn gc_issue(issues: &mut Vec<GcMapIssue>, path: &str, message: &str) {
+    issues.push(GcMapIssue {
+        path: path.to_string(),
+        message: message.to_string(),
+    });
+}
+
+/// 检查 [x, y, z] / [r, g, b] 向量
+fn gc_check_vec3(issues: &mut Vec<GcMapIssue>, path: &str, values: &[f64]) {
+    if values.len() != 3 {
+        gc_issue(issues, path, &format!("需要 3 个数值，实际 {} 个", values.len()));
+    } else if values.iter().any(|v| !v.is_finite()) {
+        gc_issue(issues, path, "数值必须是有限数");
+    }
+}
+
+fn gc_check_scene(scene: &GcEditorScene, issues: &mut Vec<GcMapIssue>) {
+    let settings = &scene.settings;
+    if !(settings.size.is_finite() && settings.size > 0.0) {
+        gc_issue(issues, "settings.size", "场景尺寸必须为正数");
+    }
+    if !(settings.fog_density.is_finite() && settings.fog_density >= 0.0) {
+        gc_issue(issues, "settings.fog_density", "雾浓度不能为负数");
+    }
+    gc_check_vec3(issues, "settings.ambient_color", &settings.ambient_color);
+    if let Some(color) = &settings.fog_color {
+        gc_check_vec3(issues, "settings.fog_color", color);
+    }
+    if let Some(direction) = &settings.sun_direction {
+        gc_check_vec3(issues, "settings.sun_direction", direction);
+    }
+
+    let mut ids = HashSet::new();
+    for (i, entity) in scene.entities.iter().enumerate() {
+        let path = format!("entities[{}]", i);
+        if entity.id.is_empty() {
+            gc_issue(issues, &format!("{}.id", path), "实体 ID 不能为空");
+        } else if !ids.insert(entity.id.as_str()) {
+            gc_issue(issues, &format!("{}.id", path), &format!("实体 ID 重复: {}", entity.id));
+        }
+        if entity.prefab.is_empty() {
+            gc_issue(issues, &format!("{}.prefab", path), "预制体名称不能为空");
+        }
+        gc_check_vec3(issues, &format!("{}.position", path), &entity.position);
+        if let Some(rotation) = &entity.rotation {
+            gc_check_vec3(issues, &format!("{}.rotation", path), rotation);
+        }
+        match &entity.scale {
+            Some(GcSceneScale::Uniform(s)) if !(s.is_finite() && *s > 0.0) => {
+                gc_issue(issues, &format!("{}.scale", path), "缩放必须为正数");
+            }
+            Some(GcSceneScale::Axes(axes)) => {
+                gc_check_vec3(issues, &format!("{}.scale", path), axes);
+                if axes.iter().any(|s| *s <= 0.0) {
+                    gc_issue(issues, &format!("{}.scale", path), "缩放必须为正数");
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn gc_check_tiles(map: &GcMap, issues: &mut Vec<GcMapIssue>) {
+    if map.width == 0 || map.height == 0 {
+        gc_issue(issues, "width", "地图宽高必须大于 0");
+    }
+    if map.tiles.len() != map.height {
+        gc_issue(issues, "tiles", &format!("行数 {} 与高度 {} 不一致", map.tiles.len(), map.height));
+    }
+    for (y, row) in map.tiles.iter().enumerate() {
+        if row.len() != map.width {
+            gc_issue(issues, &format!("tiles[{}]", y), &format!("列数 {} 与宽度 {} 不一致", row.len(), map.width));
+        }
+    }
+    if !map.gc_is_valid(&map.spawn_point) {
+        gc_issue(issues, "spawn_point", "出生点不在地图范围内");
+    }
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn scene_json() -> serde_json::Value {
+        json!({
+            "id": "forest_01",
+            "name": "森林",
+            "version": "1.0.0",
+            "settings": { "size": 500, "skybox": "sky_day", "ambientColor": [0.8, 0.8, 0.9], "fogDensity": 0.002 },
+            "entities": [
+                { "type": "tree", "id": "t1", "prefab": "tree_pine", "position": [1, 0, 2], "scale": 1.5 },
+                { "type": "enemy", "id": "e1", "prefab": "slime", "position": [5, 0, 5], "properties": { "level": 3 } }
+            ]
+        })
+    }
+
+    #[test]
+    fn test_accepts_editor_scene_and_tile_map() {
+        let scene = gc_validate_map_upload(scene_json());
+        assert_eq!(scene.as_ref().map(|u| u.gc_id()), Ok("forest_01"));
+
+        let map = serde_json::to_value(GcMap::gc_new("town", "小镇", 4, 3)).unwrap_or_default();
+        let tiles = gc_validate_map_upload(map);
+        assert!(matches!(tiles, Ok(GcMapUpload::Tiles(_))));
+    }
+
+    #[test]
+    fn test_reports_invalid_entities_and_ids() {
+        let mut value = scene_json();
+        value["id"] = json!("../../etc/passwd");
+        value["entities"][1]["id"] = json!("t1");
+        value["entities"][1]["position"] = json!([1, 2]);
+        value["entities"][0]["scale"] = json!(-1);
+
+        let paths: Vec<String> = match gc_validate_map_upload(value) {
+            Err(GcMapUploadError::GcInvalidContent(issues)) => issues.into_iter().map(|i| i.path).collect(),
+            _ => Vec::new(),
+        };
+        assert_eq!(paths, vec!["id", "entities[0].scale", "entities[1].id", "entities[1].position"]);
+
+        assert!(gc_is_valid_map_id("map_default"));
+        assert!(!gc_is_valid_map_id("a/b"));
+        assert!(!gc_is_valid_map_id(""));
+    }
+
+    #[test]
+    fn test_structure_errors_have_field_paths() {
+        let mut value = scene_json();
+        value["entities"][1]["type"] = json!("dragon");
+        let diagnostic = match gc_validate_map_upload(value) {
+            Err(GcMapUploadError::GcInvalidJson(d)) => Some(d),
+            _ => None,
+        };
+        assert_eq!(diagnostic.map(|d| d.path), Some("entities[1].type".to_string()));
+
+        let mut map = serde_json::to_value(GcMap::gc_new("town", "小镇", 4, 3)).unwrap_or_default();
+        map["width"] = json!(5);
+        assert!(matches!(gc_validate_map_upload(map), Err(GcMapUploadError::GcInvalidContent(_))));
+    }
+}
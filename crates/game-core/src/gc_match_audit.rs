@@ -0,0 +1,261 @@
+//! 对局审计记录 (竞技对局的争议处理与反作弊复核)
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! 竞技对局结束时生成不可变的审计记录: 行动日志哈希链、随机种子、内容版本与结果摘要，
+//! 之后可凭回放 (`GcReplay`) 复核对局是否被篡改
+//!
+//! ## 规则
+//! - 哈希链: 第 i 个事件的哈希 = 内容哈希 (上一个哈希, 事件)，首个事件的上一个哈希为空串；
+//!   任意事件被修改、插入或删除，从该位置起的哈希全部改变
+//! - 随机种子取自事件中的卡池洗牌种子 (初始快照与获取、刷新卡池事件)，按首次出现顺序去重
+//! - 记录封存时计算封印哈希 (`seal`，覆盖除封印外的全部字段)，任何字段被改动都无法通过校验
+//! - 校验 (`gc_verify_match`) 先验封印，再重放回放事件，逐项比对玩家、胜者、回合、哈希链与种子
+//! - 内容版本只做记录: 内容更新后旧对局仍可校验，复核时据此判断对局使用的规则
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{
+    gc_content_hash, GcBattleEvent, GcBattleState, GcContentVersion, GcEventSourcedBattle, GcGameMode, GcReplay,
+    GcReplayError, GcReplayPlayer,
+};
+
+/// 审计记录格式版本
+pub const GC_MATCH_AUDIT_VERSION: u32 = 1;
+
+// =============================================================================
+// 审计记录
+// =============================================================================
+
+/// 对局审计记录 (封存后不可修改)
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcMatchAudit {
+    /// 格式版本
+    pub format_version: u32,
+    /// 战斗 ID
+    pub battle_id: String,
+    /// 游戏模式
+    pub mode: GcGameMode,
+    /// 玩家
+    pub players: Vec<GcReplayPlayer>,
+    /// 胜者
+    #[serde(default)]
+    pub winner_id: Option<String>,
+    /// 回合数
+    pub turns: u32,
+    /// 行动日志哈希链 (每个事件一项)
+    pub chain: Vec<String>,
+    /// 随机种子 (卡池洗牌)
+    pub rng_seeds: Vec<u64>,
+    /// 对局使用的内容版本
+    pub content: GcContentVersion,
+    /// 记录时间 (Unix 秒)
+    pub recorded_at: u64,
+    /// 封印哈希
+    pub seal: String,
+}
+
+/// 审计校验失败
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum GcMatchAuditError {
+    /// 格式版本不支持
+    #[error("不支持的审计记录版本: {0}")]
+    GcUnsupportedVersion(u32),
+    /// 对局未结束
+    #[error("只能为已结束的对局生成审计记录")]
+    GcNotFinished,
+    /// 封印不符 (记录被改动)
+    #[error("审计记录封印不符")]
+    GcSealMismatch,
+    /// 回放无法通过校验
+    #[error("回放无效: {0}")]
+    GcInvalidReplay(#[from] GcReplayError),
+    /// 结果摘要不一致
+    #[error("审计记录与回放不一致: {0}")]
+    GcMismatch(String),
+    /// 哈希链在某个事件处分叉
+    #[error("行动日志在第 {index} 个事件处不一致")]
+    GcChainMismatch { index: usize },
+}
+
+impl GcMatchAudit {
+    /// 由已结束的事件溯源战斗生成并封存审计记录
+    pub fn gc_record(
+        battle: &GcEventSourcedBattle,
+        mode: GcGameMode,
+        content: &GcContentVersion,
+        now: u64,
+    ) -> Result<Self, GcMatchAuditError> {
+        let replay = GcReplay::gc_from_battle(battle, mode).map_err(|_| GcMatchAuditError::GcNotFinished)?;
+        let mut audit = Self {
+            format_version: GC_MATCH_AUDIT_VERSION,
+            battle_id: battle.gc_state().id.clone(),
+            mode,
+            players: replay.players,
+            winner_id: replay.winner_id,
+            turns: replay.turns,
+            chain: gc_event_hash_chain(&replay.events),
+            rng_seeds: gc_event_rng_seeds(&replay.events),
+            content: content.clone(),
+            recorded_at: now,
+            seal: String::new(),
+        };
+        audit.seal = audit.gc_compute_seal();
+        Ok(audit)
+    }
+
+    /// 行动日志末端哈希 (无事件时为空)
+    pub fn gc_head(&self) -> Option<&str> {
+        self.chain.last().map(String::as_str)
+    }
+
+    /// 计算封印 (不含封印字段本身)
+    pub fn gc_compute_seal(&self) -> String {
+        gc_content_hash(&Self { seal: String::new(), ..self.clone() })
+    }
+}
+
+// =============================================================================
+// 哈希链与种子
+// =============================================================================
+
+/// 事件哈希链
+pub fn gc_event_hash_chain(events: &[GcBattleEvent]) -> Vec<String> {
+    let mut previous = String::new();
+    events.iter()
+        .map(|event| {
+            previous = gc_content_hash(&(&previous, event));
+            previous.clone()
+        })
+        .collect()
+}
+
+/// 事件中出现的卡池洗牌种子 (按首次出现顺序去重)
+pub fn gc_event_rng_seeds(events: &[GcBattleEvent]) -> Vec<u64> {
+    let mut seeds = Vec::new();
+    for event in events {
+        let seed = match event {
+            GcBattleEvent::Genesis { state } => state.card_pool.shuffle_seed,
+            GcBattleEvent::CardAcquired { card_pool, .. } | GcBattleEvent::PoolRefreshed { card_pool, .. } => {
+                card_pool.shuffle_seed
+            }
+            _ => continue,
+        };
+        if !seeds.contains(&seed) {
+            seeds.push(seed);
+        }
+    }
+    seeds
+}
+
+// =============================================================================
+// 校验
+// =============================================================================
+
+/// 凭回放复核审计记录，通过时返回重放后的最终状态
+pub fn gc_verify_match(audit: &GcMatchAudit, replay: &GcReplay) -> Result<GcBattleState, GcMatchAuditError> {
+    if audit.format_version != GC_MATCH_AUDIT_VERSION {
+        return Err(GcMatchAuditError::GcUnsupportedVersion(audit.format_version));
+    }
+    if audit.seal != audit.gc_compute_seal() {
+        return Err(GcMatchAuditError::GcSealMismatch);
+    }
+    let state = replay.gc_verify(usize::MAX)?;
+
+    let mismatch = |field: &str| Err(GcMatchAuditError::GcMismatch(field.to_string()));
+    if state.id != audit.battle_id {
+        return mismatch("战斗 ID");
+    }
+    if replay.mode != audit.mode {
+        return mismatch("游戏模式");
+    }
+    if replay.players != audit.players {
+        return mismatch("玩家");
+    }
+    if replay.winner_id != audit.winner_id {
+        return mismatch("胜者");
+    }
+    if replay.turns != audit.turns {
+        return mismatch("回合数");
+    }
+
+    let chain = gc_event_hash_chain(&replay.events);
+    if let Some(index) = (0..chain.len().max(audit.chain.len())).find(|&i| chain.get(i) != audit.chain.get(i)) {
+        return Err(GcMatchAuditError::GcChainMismatch { index });
+    }
+    if gc_event_rng_seeds(&replay.events) != audit.rng_seeds {
+        return mismatch("随机种子");
+    }
+    Ok(state)
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GcBattlePhase, GcCard, GcPlayer, GcProfessionType};
+
+    fn finished_battle() -> GcEventSourcedBattle {
+        let mut p1 = GcPlayer::gc_new("p1", "玩家1");
+        p1.gc_init_rpg(GcProfessionType::Warlock);
+        p1.hand.push(GcCard::gc_new_attack("c1", "终结", 1, 500));
+        let mut state = GcBattleState::gc_new("battle_audit", vec![p1, GcPlayer::gc_new("p2", "玩家2")]);
+        state.card_pool.shuffle_seed = 42;
+        let mut battle = GcEventSourcedBattle::gc_from_state(state);
+        battle.gc_record(GcBattleEvent::PhaseChanged { phase: GcBattlePhase::Playing }).expect("开始对局");
+        assert!(battle.gc_play_card("p1", "c1", "p2").success);
+        battle
+    }
+
+    fn content() -> GcContentVersion {
+        GcContentVersion::gc_compute(None)
+    }
+
+    #[test]
+    fn test_audit_verifies_against_replay() {
+        let battle = finished_battle();
+        let audit = GcMatchAudit::gc_record(&battle, GcGameMode::YuGiOhStyle, &content(), 1000).expect("生成审计");
+        assert_eq!(audit.chain.len(), 3);
+        assert_eq!(audit.rng_seeds, vec![42]);
+        assert_eq!(audit.winner_id.as_deref(), Some("p1"));
+
+        let replay = GcReplay::gc_from_battle(&battle, GcGameMode::YuGiOhStyle).expect("回放");
+        assert!(gc_verify_match(&audit, &replay).is_ok());
+
+        // 未结束的对局不生成审计
+        let ongoing = GcEventSourcedBattle::gc_new("b", vec![GcPlayer::gc_new("a", "A")]);
+        assert_eq!(
+            GcMatchAudit::gc_record(&ongoing, GcGameMode::YuGiOhStyle, &content(), 0).err(),
+            Some(GcMatchAuditError::GcNotFinished)
+        );
+    }
+
+    #[test]
+    fn test_tampering_is_detected() {
+        let battle = finished_battle();
+        let audit = GcMatchAudit::gc_record(&battle, GcGameMode::YuGiOhStyle, &content(), 1000).expect("生成审计");
+        let replay = GcReplay::gc_from_battle(&battle, GcGameMode::YuGiOhStyle).expect("回放");
+
+        // 改动记录本身: 封印失效
+        let mut forged = audit.clone();
+        forged.winner_id = Some("p2".to_string());
+        assert_eq!(gc_verify_match(&forged, &replay).err(), Some(GcMatchAuditError::GcSealMismatch));
+
+        // 在回放中插入事件: 哈希链在该事件处分叉
+        let mut edited = replay.clone();
+        edited.events.insert(2, GcBattleEvent::EnergySet { player_id: "p1".to_string(), energy: 9 });
+        assert!(edited.gc_verify(100).is_ok());
+        assert_eq!(gc_verify_match(&audit, &edited).err(), Some(GcMatchAuditError::GcChainMismatch { index: 2 }));
+
+        // 回放结果与记录不符
+        let mut other = replay.clone();
+        other.mode = GcGameMode::TavernStyle;
+        assert!(matches!(gc_verify_match(&audit, &other), Err(GcMatchAuditError::GcMismatch(_))));
+    }
+}
@@ -0,0 +1,179 @@
+//! 对局统计 (伤害、治疗、击杀与助攻)
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! ## 规则
+//! - 统计由战斗日志 (`combat_log`) 推导，不额外保存状态；撤销与回放后结果一致
+//! - 致命一击 (`cue.lethal`) 计 1 次击杀与 1 次死亡；击杀自己或队友不计击杀
+//! - 助攻: 击杀前 `GC_ASSIST_WINDOW_TURNS` 回合内对被击杀者造成过伤害，或治疗过击杀者的其他玩家；
+//!   必须是被击杀者的敌方，每次击杀每人最多 1 次助攻
+//! - 结果按座位顺序排列，附带队伍编号
+
+use serde::{Deserialize, Serialize};
+
+use crate::{GcBattleState, GcCombatLogEvent, GcPlayerId};
+
+// =============================================================================
+// 常量
+// =============================================================================
+
+/// 助攻有效的回合窗口 (含击杀所在回合)
+pub const GC_ASSIST_WINDOW_TURNS: u32 = 2;
+
+// =============================================================================
+// 统计
+// =============================================================================
+
+/// 单名玩家的对局统计
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcPlayerMatchStats {
+    /// 玩家 ID
+    pub player_id: GcPlayerId,
+    /// 队伍 (不分队伍时为 None)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub team: Option<u32>,
+    /// 造成的伤害
+    pub damage_dealt: u64,
+    /// 治疗量
+    pub healing_done: u64,
+    /// 击杀
+    pub kills: u32,
+    /// 死亡
+    pub deaths: u32,
+    /// 助攻
+    pub assists: u32,
+}
+
+/// 一次伤害或治疗的贡献记录
+struct GcContribution<'a> {
+    turn: u32,
+    source_id: &'a str,
+    target_id: &'a str,
+}
+
+/// 从战斗日志汇总对局统计 (按座位顺序)
+pub fn gc_match_stats(state: &GcBattleState) -> Vec<GcPlayerMatchStats> {
+    let mut stats: Vec<GcPlayerMatchStats> = state.players.iter()
+        .map(|p| GcPlayerMatchStats { player_id: p.id.clone(), team: p.team, ..Default::default() })
+        .collect();
+    let index_of = |id: &str| state.players.iter().position(|p| p.id == id);
+    let mut hits: Vec<GcContribution> = Vec::new();
+    let mut heals: Vec<GcContribution> = Vec::new();
+
+    for event in &state.combat_log {
+        match event {
+            GcCombatLogEvent::Damage { turn, source_id, target_id, result, cue, .. } => {
+                if let Some(index) = index_of(source_id) {
+                    stats[index].damage_dealt += u64::from(result.final_damage);
+                }
+                hits.push(GcContribution { turn: *turn, source_id, target_id });
+                if !cue.lethal {
+                    continue;
+                }
+
+                if let Some(index) = index_of(target_id) {
+                    stats[index].deaths += 1;
+                }
+                if source_id != target_id && !state.gc_are_allies(source_id, target_id) {
+                    if let Some(index) = index_of(source_id) {
+                        stats[index].kills += 1;
+                    }
+                }
+
+                // 助攻: 窗口内伤害过被击杀者或治疗过击杀者的其他敌方玩家
+                let window_start = turn.saturating_sub(GC_ASSIST_WINDOW_TURNS - 1);
+                let mut assisters: Vec<&str> = hits.iter()
+                    .filter(|c| c.turn >= window_start && c.target_id == target_id)
+                    .chain(heals.iter().filter(|c| c.turn >= window_start && c.target_id == source_id))
+                    .map(|c| c.source_id)
+                    .filter(|id| *id != source_id && !state.gc_are_allies(id, target_id))
+                    .collect();
+                assisters.sort_unstable();
+                assisters.dedup();
+                for id in assisters {
+                    if let Some(index) = index_of(id) {
+                        stats[index].assists += 1;
+                    }
+                }
+            }
+            GcCombatLogEvent::Heal { turn, source_id, target_id, amount } => {
+                if let Some(index) = index_of(source_id) {
+                    stats[index].healing_done += u64::from(*amount);
+                }
+                heals.push(GcContribution { turn: *turn, source_id, target_id });
+            }
+            _ => {}
+        }
+    }
+    stats
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{gc_arrange_seats, gc_execute_play_card, GcBattleFormat, GcBattlePhase, GcCard, GcPlayer};
+
+    /// 2v2 对局 (座位 p0 p1 p2 p3，p0/p2 一队)，出牌者的回合由测试直接指定
+    fn team_battle() -> GcBattleState {
+        let players = (0..4).map(|i| GcPlayer::gc_new(format!("p{}", i), format!("玩家{}", i))).collect();
+        let seated = gc_arrange_seats(GcBattleFormat::TwoVsTwo, players).unwrap();
+        let mut state = GcBattleState::gc_new("b1", seated);
+        state.phase = GcBattlePhase::Playing;
+        state
+    }
+
+    fn play(state: &mut GcBattleState, seat: usize, card: GcCard, target: &str) {
+        state.current_player_index = seat;
+        state.players[seat].stats.energy = 10;
+        let card_id = card.id.clone();
+        state.players[seat].hand.push(card);
+        let player_id = state.players[seat].id.clone();
+        let result = gc_execute_play_card(state, &player_id, &card_id, target);
+        assert!(result.success, "{:?}", result.error);
+    }
+
+    #[test]
+    fn test_ally_heal_and_assists() {
+        let mut state = team_battle();
+        state.players[0].stats.hp = 50;
+        state.players[1].stats.hp = 40;
+
+        // p2 治疗队友 p0；p2 先打 p1 一下，p0 完成击杀
+        play(&mut state, 2, GcCard::gc_new_ally_heal("heal", "治疗", 1, 15), "p0");
+        play(&mut state, 2, GcCard::gc_new_attack("a1", "打击", 1, 5), "p1");
+        play(&mut state, 0, GcCard::gc_new_attack("a2", "重击", 1, 100), "p1");
+        assert!(!state.players[1].gc_can_act());
+
+        let stats = gc_match_stats(&state);
+        let p0 = &stats[0];
+        let p2 = &stats[2];
+        assert_eq!((p0.kills, p0.assists), (1, 0));
+        assert_eq!((p2.kills, p2.assists), (0, 1));
+        assert_eq!(stats[1].deaths, 1);
+        assert!(p2.damage_dealt > 0);
+        assert_eq!(p2.healing_done, 15);
+        assert_eq!(p0.team, p2.team);
+    }
+
+    #[test]
+    fn test_assist_window_and_teammates() {
+        let mut state = team_battle();
+        state.players[1].stats.hp = 40;
+
+        // 窗口外的伤害不计助攻；单体攻击牌不能指向队友
+        play(&mut state, 2, GcCard::gc_new_attack("a1", "打击", 1, 5), "p1");
+        state.turn += GC_ASSIST_WINDOW_TURNS;
+        play(&mut state, 0, GcCard::gc_new_attack("a2", "重击", 1, 100), "p1");
+        assert_eq!(gc_match_stats(&state)[2].assists, 0);
+
+        state.players[0].hand.push(GcCard::gc_new_attack("a3", "打击", 1, 5));
+        let result = gc_execute_play_card(&mut state, "p0", "a3", "p2");
+        assert!(!result.success);
+    }
+}
@@ -0,0 +1,260 @@
+//! 定点数学 (无浮点)
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! ## 规则
+//! - 影响战斗结果的计算一律使用整数: 倍率用百分比 (100 = 1.0x) 或千分比 (1000 = 1.0x) 表示
+//! - 中间结果用 u64/i64 计算，结果向下取整，WASM 与原生服务器得到完全相同的数值
+//! - 浮点只允许用于显示 (进度条、概率展示)，不能回写到战斗状态
+//! - 属性运算不会 panic 或回绕: 结果饱和到 [0, u32::MAX]，需要发现溢出时使用 `gc_checked_*`
+//! - 百分比加成统一叠加: 同一分桶内相加，不同分桶之间相乘，最后只取整一次 (见 `GcPercentStack`)
+//! - 星级倍率属于基础值缩放，先于所有分桶计算
+
+use serde::{Deserialize, Serialize};
+
+// =============================================================================
+// 常量
+// =============================================================================
+
+/// 百分比基数 (100 = 1.0x)
+pub const GC_PERCENT: u32 = 100;
+
+/// 千分比基数 (1000 = 1.0x)
+pub const GC_PER_MILLE: u32 = 1000;
+
+// =============================================================================
+// 定点运算
+// =============================================================================
+
+/// 按比例缩放: value × numerator / denominator (向下取整，分母为 0 时为 0)
+pub fn gc_scale(value: u32, numerator: u32, denominator: u32) -> u32 {
+    if denominator == 0 {
+        return 0;
+    }
+    (value as u64 * numerator as u64 / denominator as u64).min(u32::MAX as u64) as u32
+}
+
+/// 取百分比: value × percent%
+pub fn gc_percent_of(value: u32, percent: u32) -> u32 {
+    gc_scale(value, percent, GC_PERCENT)
+}
+
+/// 取千分比: value × per_mille‰
+pub fn gc_per_mille_of(value: u32, per_mille: u32) -> u32 {
+    gc_scale(value, per_mille, GC_PER_MILLE)
+}
+
+/// 应用百分比增减: value × (100 + delta)% (不低于 0)
+pub fn gc_apply_percent(value: u32, delta_percent: i32) -> u32 {
+    gc_checked_apply_percent(value, delta_percent).unwrap_or(u32::MAX)
+}
+
+/// 应用加成百分比: value × (100 + percent)% (光环、组织加成等无符号百分比)
+pub fn gc_apply_bonus_percent(value: u32, percent: u32) -> u32 {
+    gc_scale(value, GC_PERCENT.saturating_add(percent), GC_PERCENT)
+}
+
+/// 取带符号百分比: value × percent% (用于每回合生命变化等有正负的量，饱和到 i32)
+pub fn gc_signed_percent_of(value: u32, percent: i32) -> i32 {
+    (value as i64 * percent as i64 / GC_PERCENT as i64).clamp(i32::MIN as i64, i32::MAX as i64) as i32
+}
+
+// =============================================================================
+// 饱和与检查运算
+// =============================================================================
+
+/// 带符号增减 (饱和，不低于 0)
+pub fn gc_add_signed(value: u32, delta: i32) -> u32 {
+    if delta >= 0 {
+        value.saturating_add(delta as u32)
+    } else {
+        value.saturating_sub(delta.unsigned_abs())
+    }
+}
+
+/// 带上限的加法叠加: 在 value 上叠加 delta，结果限制在 [0, cap]
+pub fn gc_add_signed_capped(value: u32, delta: i32, cap: u32) -> u32 {
+    gc_add_signed(value, delta).min(cap)
+}
+
+/// 检查版本的百分比增减 (结果超过 u32 时返回 None，低于 0 时为 0)
+pub fn gc_checked_apply_percent(value: u32, delta_percent: i32) -> Option<u32> {
+    let factor = (GC_PERCENT as i128 + delta_percent as i128).max(0);
+    u32::try_from(value as i128 * factor / GC_PERCENT as i128).ok()
+}
+
+/// 检查版本的按比例缩放 (分母为 0 或结果超过 u32 时返回 None)
+pub fn gc_checked_scale(value: u32, numerator: u32, denominator: u32) -> Option<u32> {
+    if denominator == 0 {
+        return None;
+    }
+    u32::try_from(value as u64 * numerator as u64 / denominator as u64).ok()
+}
+
+// =============================================================================
+// 百分比叠加
+// =============================================================================
+
+/// 百分比加成分桶
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum GcModifierBucket {
+    /// 地形
+    Terrain,
+    /// 天赋
+    Talent,
+    /// 装备 (稀有度倍率)
+    Equipment,
+    /// 伙伴光环
+    Aura,
+    /// 组织加成
+    Guild,
+}
+
+/// 百分比加成叠加器
+///
+/// 同一分桶内的加成相加，不同分桶之间相乘，最后只向下取整一次，结果与添加顺序无关。
+///
+/// ```
+/// use game_core::{GcModifierBucket, GcPercentStack};
+///
+/// // 两个天赋 +10% 同桶相加为 +20%，再与光环 +50% 相乘: 100 × 1.2 × 1.5
+/// let stack = GcPercentStack::gc_new()
+///     .gc_with(GcModifierBucket::Talent, 10)
+///     .gc_with(GcModifierBucket::Aura, 50)
+///     .gc_with(GcModifierBucket::Talent, 10);
+/// assert_eq!(stack.gc_bucket(GcModifierBucket::Talent), 20);
+/// assert_eq!(stack.gc_apply(100), 180);
+/// ```
+///
+/// 一个分桶合计达到 -100% 时结果为 0:
+///
+/// ```
+/// use game_core::{GcModifierBucket, GcPercentStack};
+///
+/// let stack = GcPercentStack::gc_new()
+///     .gc_with(GcModifierBucket::Terrain, -60)
+///     .gc_with(GcModifierBucket::Terrain, -60)
+///     .gc_with(GcModifierBucket::Guild, 300);
+/// assert_eq!(stack.gc_apply(1000), 0);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcPercentStack {
+    /// 各分桶的百分比合计 (按 GcModifierBucket 顺序)
+    percents: [i32; 5],
+}
+
+impl GcPercentStack {
+    /// 创建空叠加器
+    pub fn gc_new() -> Self {
+        Self::default()
+    }
+
+    /// 在分桶中加入百分比加成 (同桶相加，饱和)
+    pub fn gc_add(&mut self, bucket: GcModifierBucket, percent: i32) {
+        let slot = &mut self.percents[bucket as usize];
+        *slot = slot.saturating_add(percent);
+    }
+
+    /// 加入百分比加成 (链式)
+    pub fn gc_with(mut self, bucket: GcModifierBucket, percent: i32) -> Self {
+        self.gc_add(bucket, percent);
+        self
+    }
+
+    /// 加入无符号加成百分比 (光环、组织加成，超过 i32 时饱和)
+    pub fn gc_with_bonus(self, bucket: GcModifierBucket, percent: u32) -> Self {
+        self.gc_with(bucket, i32::try_from(percent).unwrap_or(i32::MAX))
+    }
+
+    /// 合并另一个叠加器 (逐分桶相加)
+    pub fn gc_merge(mut self, other: &GcPercentStack) -> Self {
+        for (slot, percent) in self.percents.iter_mut().zip(other.percents) {
+            *slot = slot.saturating_add(percent);
+        }
+        self
+    }
+
+    /// 分桶合计
+    pub fn gc_bucket(&self, bucket: GcModifierBucket) -> i32 {
+        self.percents[bucket as usize]
+    }
+
+    /// 应用到数值: value × Π(100 + 分桶合计)% (各分桶不低于 0，结果饱和)
+    pub fn gc_apply(&self, value: u32) -> u32 {
+        let mut numerator = value as i128;
+        let mut denominator: i128 = 1;
+        for percent in self.percents.iter().filter(|&&p| p != 0) {
+            let factor = (GC_PERCENT as i128 + *percent as i128).max(0);
+            match numerator.checked_mul(factor) {
+                Some(next) => numerator = next,
+                None => return u32::MAX,
+            }
+            denominator *= GC_PERCENT as i128;
+        }
+        (numerator / denominator).min(u32::MAX as i128) as u32
+    }
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scale_matches_exact_fractions() {
+        assert_eq!(gc_percent_of(33, 450), 148);
+        assert_eq!(gc_percent_of(10, 30), 3);
+        assert_eq!(gc_per_mille_of(999, 10), 9);
+        assert_eq!(gc_scale(7, 1, 0), 0);
+        assert_eq!(gc_percent_of(u32::MAX, 300), u32::MAX);
+    }
+
+    #[test]
+    fn test_apply_percent() {
+        assert_eq!(gc_apply_percent(100, 20), 120);
+        assert_eq!(gc_apply_percent(100, -10), 90);
+        assert_eq!(gc_apply_percent(100, -150), 0);
+        // 0.1 无法用二进制浮点精确表示，整数运算没有误差
+        assert_eq!(gc_apply_percent(70, 10), 77);
+    }
+
+    #[test]
+    fn test_extreme_values_saturate() {
+        assert_eq!(gc_apply_percent(u32::MAX, 20), u32::MAX);
+        assert_eq!(gc_apply_percent(u32::MAX, -100), 0);
+        assert_eq!(gc_apply_percent(u32::MAX, i32::MAX), u32::MAX);
+        assert_eq!(gc_apply_percent(100, i32::MIN), 0);
+        assert_eq!(gc_apply_bonus_percent(u32::MAX, u32::MAX), u32::MAX);
+        assert_eq!(gc_signed_percent_of(u32::MAX, -100), i32::MIN);
+        assert_eq!(gc_add_signed(u32::MAX, i32::MAX), u32::MAX);
+        assert_eq!(gc_add_signed(5, i32::MIN), 0);
+        assert_eq!(gc_add_signed_capped(90, 30, 100), 100);
+        assert_eq!(gc_checked_apply_percent(u32::MAX, 1), None);
+        assert_eq!(gc_checked_apply_percent(u32::MAX, -100), Some(0));
+        assert_eq!(gc_checked_scale(1, 1, 0), None);
+    }
+
+    #[test]
+    fn test_percent_stack_additive_within_multiplicative_across() {
+        let a = GcPercentStack::gc_new()
+            .gc_with(GcModifierBucket::Terrain, 20)
+            .gc_with(GcModifierBucket::Aura, 10)
+            .gc_with(GcModifierBucket::Terrain, -10);
+        let b = GcPercentStack::gc_new()
+            .gc_with(GcModifierBucket::Aura, 10)
+            .gc_with(GcModifierBucket::Terrain, -10)
+            .gc_with(GcModifierBucket::Terrain, 20);
+        assert_eq!(a, b);
+        // 9 × 1.1 × 1.1 = 10.89，只在最后取整一次 (逐步取整会得到 9)
+        assert_eq!(a.gc_apply(9), 10);
+        assert_eq!(gc_apply_percent(gc_apply_percent(9, 10), 10), 9);
+        assert_eq!(a.gc_merge(&b).gc_bucket(GcModifierBucket::Terrain), 20);
+        assert_eq!(GcPercentStack::gc_new().gc_apply(7), 7);
+        assert_eq!(GcPercentStack::gc_new().gc_with_bonus(GcModifierBucket::Guild, u32::MAX).gc_apply(u32::MAX), u32::MAX);
+    }
+}
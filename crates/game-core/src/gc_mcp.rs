@@ -5,12 +5,18 @@
 //! 文档: 文档/12-MCP-API.md
 //!
 //! 定义 AI 代理与游戏服务器交互的命令结构
+//!
+//! ## 权限规则
+//! - 每个代理持有一个 API Key，Key 带有权限级别 (只读 / 编辑世界 / 管理)
+//! - 工具按是否修改世界划分所需级别，未知工具一律拒绝
+//! - Key 可额外限定工具白名单，白名单为空表示不限
+//! - 试运行 (dry_run) 不修改世界，只需要只读权限
 
 use serde::{Deserialize, Serialize};
-use crate::GcPosition;
+use crate::{GcMcpGroupEntity, GcPosition};
 
 /// MCP 操作命令
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 #[serde(tag = "type", content = "data")]
 pub enum GcMcpCommand {
     /// 生成实体
@@ -53,6 +59,9 @@ pub enum GcMcpCommand {
         radius: f32,
         /// 数量
         count: u32,
+        /// 服务器挑选的位置 (为空时由客户端在半径内随机放置)
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        positions: Vec<GcPosition>,
     },
     
     /// 清空区域
@@ -63,6 +72,30 @@ pub enum GcMcpCommand {
         radius: f32,
     },
     
+    /// 放置组合预制体 (一次生成组内全部实体)
+    SpawnGroup {
+        /// 组 ID
+        group_id: String,
+        /// 组实例 ID (服务器校验时分配)
+        #[serde(default)]
+        instance_id: String,
+        /// 组原点
+        origin: GcPosition,
+        /// 旋转 (弧度)
+        rotation: f32,
+        /// 各成员的绝对位置
+        entities: Vec<GcMcpGroupEntity>,
+    },
+
+    /// 把组实例拆回独立实体
+    ExpandGroup {
+        /// 组实例 ID
+        instance_id: String,
+        /// 实例中的实体 (服务器校验时填入)
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        entities: Vec<GcMcpGroupEntity>,
+    },
+    
     /// 撤销上一步操作
     Undo,
     
@@ -82,3 +115,204 @@ pub struct GcMcpResponse {
     /// 返回数据
     pub data: Option<serde_json::Value>,
 }
+
+// =============================================================================
+// 权限
+// =============================================================================
+
+/// 只读工具 (不修改世界)
+pub const GC_MCP_READ_TOOLS: [&str; 3] = ["list_prefabs", "get_world_info", "list_groups"];
+
+/// 修改世界的工具
+pub const GC_MCP_WRITE_TOOLS: [&str; 14] = [
+    "spawn_entity",
+    "delete_entity",
+    "move_entity",
+    "clear_area",
+    "spawn_batch",
+    "build_area",
+    "undo",
+    "set_tile_layer",
+    "set_spawn_region",
+    "remove_spawn_region",
+    "save_group",
+    "place_group",
+    "expand_group",
+    "tag_asset",
+];
+
+/// 支持试运行 (dry_run) 的工具
+pub const GC_MCP_DRY_RUN_TOOLS: [&str; 6] =
+    ["spawn_entity", "spawn_batch", "build_area", "move_entity", "clear_area", "place_group"];
+
+/// MCP 权限级别 (高级别包含低级别的全部权限)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum GcMcpScope {
+    /// 只读 (查询素材、世界信息)
+    #[serde(alias = "read")]
+    Read,
+    /// 编辑世界
+    #[serde(alias = "write")]
+    Write,
+    /// 管理 (编辑世界 + 查看审计日志)
+    #[serde(alias = "admin")]
+    Admin,
+}
+
+impl GcMcpScope {
+    /// 从配置文本解析 (read / write / admin)
+    pub fn gc_parse(text: &str) -> Option<Self> {
+        match text.trim().to_ascii_lowercase().as_str() {
+            "read" => Some(Self::Read),
+            "write" => Some(Self::Write),
+            "admin" => Some(Self::Admin),
+            _ => None,
+        }
+    }
+}
+
+/// 调用工具所需的权限级别 (未知工具返回 None)
+pub fn gc_mcp_tool_scope(tool: &str) -> Option<GcMcpScope> {
+    if GC_MCP_READ_TOOLS.contains(&tool) {
+        Some(GcMcpScope::Read)
+    } else if GC_MCP_WRITE_TOOLS.contains(&tool) {
+        Some(GcMcpScope::Write)
+    } else {
+        None
+    }
+}
+
+/// 调用工具所需的权限级别 (试运行只需要只读权限)
+pub fn gc_mcp_required_scope(tool: &str, dry_run: bool) -> Option<GcMcpScope> {
+    if dry_run && GC_MCP_DRY_RUN_TOOLS.contains(&tool) {
+        return Some(GcMcpScope::Read);
+    }
+    gc_mcp_tool_scope(tool)
+}
+
+/// MCP API Key
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcMcpKey {
+    /// Key 标识 (写入审计日志，不是密钥本身)
+    pub id: String,
+    /// 密钥
+    #[serde(skip_serializing)]
+    pub secret: String,
+    /// 权限级别
+    pub scope: GcMcpScope,
+    /// 工具白名单 (为空表示按权限级别不限)
+    #[serde(default)]
+    pub tools: Vec<String>,
+}
+
+impl GcMcpKey {
+    /// 是否允许调用该工具
+    pub fn gc_allows(&self, tool: &str) -> bool {
+        self.gc_allows_call(tool, false)
+    }
+
+    /// 是否允许以该方式调用工具 (dry_run 为试运行)
+    pub fn gc_allows_call(&self, tool: &str, dry_run: bool) -> bool {
+        let Some(required) = gc_mcp_required_scope(tool, dry_run) else {
+            return false;
+        };
+        self.scope >= required && (self.tools.is_empty() || self.tools.iter().any(|t| t == tool))
+    }
+}
+
+/// 解析 Key 列表
+///
+/// 格式: `id:secret:scope[:tool1,tool2]`，多个 Key 用 `;` 分隔，例如
+/// `builder:s3cret:write;viewer:abc:read:list_prefabs`
+pub fn gc_parse_mcp_keys(spec: &str) -> Result<Vec<GcMcpKey>, String> {
+    let mut keys: Vec<GcMcpKey> = Vec::new();
+    for entry in spec.split(';').map(str::trim).filter(|e| !e.is_empty()) {
+        let parts: Vec<&str> = entry.splitn(4, ':').map(str::trim).collect();
+        if parts.len() < 3 || parts[0].is_empty() || parts[1].is_empty() {
+            return Err(format!("Key 格式应为 id:secret:scope[:tools]，实际: {}", parts[0]));
+        }
+        let scope = GcMcpScope::gc_parse(parts[2])
+            .ok_or_else(|| format!("Key {} 的权限级别无效: {}", parts[0], parts[2]))?;
+        let tools: Vec<String> = parts
+            .get(3)
+            .map(|list| list.split(',').map(str::trim).filter(|t| !t.is_empty()).map(String::from).collect())
+            .unwrap_or_default();
+        if let Some(unknown) = tools.iter().find(|t| gc_mcp_tool_scope(t).is_none()) {
+            return Err(format!("Key {} 的白名单含未知工具: {}", parts[0], unknown));
+        }
+        if keys.iter().any(|k| k.id == parts[0] || k.secret == parts[1]) {
+            return Err(format!("Key {} 的 ID 或密钥重复", parts[0]));
+        }
+        keys.push(GcMcpKey { id: parts[0].to_string(), secret: parts[1].to_string(), scope, tools });
+    }
+    Ok(keys)
+}
+
+// =============================================================================
+// 审计
+// =============================================================================
+
+/// 工具调用结果
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GcMcpAuditOutcome {
+    /// 执行成功
+    Ok,
+    /// 权限不足被拒绝
+    Denied,
+    /// 执行失败
+    Failed { message: String },
+}
+
+/// 工具调用审计记录
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GcMcpAuditEntry {
+    /// Unix 时间戳 (秒)
+    pub timestamp: u64,
+    /// 调用者 Key 标识
+    pub key_id: String,
+    /// 工具名
+    pub tool: String,
+    /// 调用参数
+    pub arguments: serde_json::Value,
+    /// 结果
+    pub outcome: GcMcpAuditOutcome,
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scope_and_allowlist() {
+        let viewer = GcMcpKey { id: "v".into(), secret: "x".into(), scope: GcMcpScope::Read, tools: vec![] };
+        assert!(viewer.gc_allows("list_prefabs"));
+        assert!(!viewer.gc_allows("spawn_entity"));
+        assert!(!viewer.gc_allows("unknown_tool"));
+        assert!(viewer.gc_allows_call("spawn_entity", true));
+        assert!(!viewer.gc_allows_call("undo", true));
+
+        let builder = GcMcpKey { scope: GcMcpScope::Write, tools: vec!["spawn_entity".into()], ..viewer };
+        assert!(builder.gc_allows("spawn_entity"));
+        assert!(!builder.gc_allows("delete_entity"));
+        assert!(!builder.gc_allows("list_prefabs"));
+    }
+
+    #[test]
+    fn test_parse_mcp_keys() {
+        let keys = gc_parse_mcp_keys("builder:s3cret:write; viewer:abc:read:list_prefabs,get_world_info;").unwrap();
+        assert_eq!(keys.len(), 2);
+        assert_eq!(keys[0].scope, GcMcpScope::Write);
+        assert!(keys[0].tools.is_empty());
+        assert_eq!(keys[1].tools, vec!["list_prefabs", "get_world_info"]);
+        assert!(gc_parse_mcp_keys("").unwrap().is_empty());
+
+        assert!(gc_parse_mcp_keys("a:b").is_err());
+        assert!(gc_parse_mcp_keys("a:b:root").is_err());
+        assert!(gc_parse_mcp_keys("a:b:read:nuke_world").is_err());
+        assert!(gc_parse_mcp_keys("a:b:read;a:c:write").is_err());
+    }
+}
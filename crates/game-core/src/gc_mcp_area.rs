@@ -0,0 +1,350 @@
+//! MCP 区域布局配方
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/12-MCP-API.md
+//!
+//! `build_area(style, center, radius)` 把一句风格描述 (例如 "dense pine forest with a clearing")
+//! 解析为配方，按预制体注册表展开成若干层批量生成，代理一次调用即可铺满一片区域
+//!
+//! ## 规则
+//! - 风格描述按关键词匹配配方 (森林、松林、枯木林、花园、草地、乱石滩)，可叠加修饰词
+//! - 修饰词: dense/茂密 提高密度，sparse/稀疏 降低密度，clearing/空地 在中心留出空地
+//! - 每层从注册表的分类中按前缀挑选预制体，注册表里没有的层会被跳过并记入报告
+//! - 各层依次走世界规则校验，前一层的位置计入后一层的密度，结果是确定的 (试运行即执行结果)
+
+use serde::{Deserialize, Serialize};
+
+use std::collections::BTreeMap;
+
+use crate::{GcChunkedMap, GcMcpCommand, GcMcpValidation, GcMcpWorld, GcMcpZone, GcPosition};
+
+/// 预制体注册表 (分类 -> 预制体 ID 列表)
+pub type GcPrefabRegistry = BTreeMap<String, Vec<String>>;
+
+/// 区域半径上限
+pub const GC_MCP_AREA_MAX_RADIUS: f32 = 50.0;
+
+/// 空地占区域半径的比例
+const GC_MCP_CLEARING_RATIO: f32 = 0.35;
+
+// =============================================================================
+// 配方
+// =============================================================================
+
+/// 配方中的一层
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GcMcpAreaLayer {
+    /// 实体类型
+    pub entity_type: String,
+    /// 注册表分类
+    pub category: String,
+    /// 预制体 ID 前缀 (为空表示整个分类)
+    #[serde(default)]
+    pub prefixes: Vec<String>,
+    /// 基础密度 (每 100 平方单位的实体数)
+    pub density: f32,
+}
+
+impl GcMcpAreaLayer {
+    fn gc_new(entity_type: &str, category: &str, prefixes: &[&str], density: f32) -> Self {
+        Self {
+            entity_type: entity_type.to_string(),
+            category: category.to_string(),
+            prefixes: prefixes.iter().map(|p| p.to_string()).collect(),
+            density,
+        }
+    }
+
+    /// 在注册表中挑选该层可用的预制体
+    pub fn gc_resolve(&self, registry: &GcPrefabRegistry) -> Vec<String> {
+        registry
+            .get(&self.category)
+            .map(|ids| {
+                ids.iter()
+                    .filter(|id| self.prefixes.is_empty() || self.prefixes.iter().any(|p| id.starts_with(p.as_str())))
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// 布局配方
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GcMcpAreaRecipe {
+    /// 配方 ID
+    pub id: String,
+    /// 匹配的关键词
+    pub keywords: Vec<String>,
+    /// 各层 (按顺序生成，先铺主体再点缀)
+    pub layers: Vec<GcMcpAreaLayer>,
+}
+
+impl GcMcpAreaRecipe {
+    fn gc_new(id: &str, keywords: &[&str], layers: Vec<GcMcpAreaLayer>) -> Self {
+        Self {
+            id: id.to_string(),
+            keywords: keywords.iter().map(|k| k.to_string()).collect(),
+            layers,
+        }
+    }
+}
+
+/// 内置配方 (越具体的配方越靠前，先匹配)
+pub fn gc_mcp_area_recipes() -> Vec<GcMcpAreaRecipe> {
+    vec![
+        GcMcpAreaRecipe::gc_new("pine_forest", &["pine", "松"], vec![
+            GcMcpAreaLayer::gc_new("tree", "trees", &["pine_"], 3.0),
+            GcMcpAreaLayer::gc_new("plant", "plants", &["fern_"], 1.5),
+            GcMcpAreaLayer::gc_new("rock", "rocks", &["rock_medium_"], 0.4),
+            GcMcpAreaLayer::gc_new("plant", "mushrooms", &[], 0.4),
+        ]),
+        GcMcpAreaRecipe::gc_new("dead_forest", &["dead", "spooky", "枯", "死"], vec![
+            GcMcpAreaLayer::gc_new("tree", "trees", &["dead_tree_", "twisted_tree_"], 2.0),
+            GcMcpAreaLayer::gc_new("rock", "rocks", &["pebble_"], 1.0),
+            GcMcpAreaLayer::gc_new("plant", "mushrooms", &[], 0.6),
+        ]),
+        GcMcpAreaRecipe::gc_new("forest", &["forest", "wood", "grove", "森林", "树林"], vec![
+            GcMcpAreaLayer::gc_new("tree", "trees", &["common_tree_", "pine_"], 2.5),
+            GcMcpAreaLayer::gc_new("plant", "bushes", &[], 1.2),
+            GcMcpAreaLayer::gc_new("grass", "grass", &[], 1.5),
+            GcMcpAreaLayer::gc_new("plant", "mushrooms", &[], 0.3),
+        ]),
+        GcMcpAreaRecipe::gc_new("garden", &["garden", "flower", "花"], vec![
+            GcMcpAreaLayer::gc_new("flower", "flowers", &[], 4.0),
+            GcMcpAreaLayer::gc_new("plant", "bushes", &["bush_flowers"], 1.0),
+            GcMcpAreaLayer::gc_new("plant", "plants", &["clover_", "plant_"], 1.5),
+        ]),
+        GcMcpAreaRecipe::gc_new("rocky", &["rock", "stone", "boulder", "石"], vec![
+            GcMcpAreaLayer::gc_new("rock", "rocks", &["rock_medium_"], 1.2),
+            GcMcpAreaLayer::gc_new("rock", "rocks", &["pebble_"], 2.5),
+            GcMcpAreaLayer::gc_new("grass", "grass", &["grass_wispy_"], 1.0),
+        ]),
+        GcMcpAreaRecipe::gc_new("meadow", &["meadow", "grass", "field", "草"], vec![
+            GcMcpAreaLayer::gc_new("grass", "grass", &[], 4.0),
+            GcMcpAreaLayer::gc_new("flower", "flowers", &["flower_"], 1.0),
+            GcMcpAreaLayer::gc_new("plant", "plants", &["clover_"], 1.0),
+        ]),
+    ]
+}
+
+// =============================================================================
+// 风格解析
+// =============================================================================
+
+/// 解析后的风格
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GcMcpAreaStyle {
+    /// 原始描述
+    pub text: String,
+    /// 匹配的配方
+    pub recipe: GcMcpAreaRecipe,
+    /// 密度倍率
+    pub density_scale: f32,
+    /// 是否在中心留出空地
+    pub clearing: bool,
+}
+
+/// 解析风格描述 (未匹配到配方时返回可用配方列表)
+pub fn gc_parse_area_style(text: &str) -> Result<GcMcpAreaStyle, String> {
+    let lower = text.to_lowercase();
+    let has = |words: &[&str]| words.iter().any(|w| lower.contains(w));
+    let recipes = gc_mcp_area_recipes();
+    let recipe = recipes
+        .iter()
+        .find(|r| r.keywords.iter().any(|k| lower.contains(k.as_str())))
+        .cloned()
+        .ok_or_else(|| {
+            let ids: Vec<&str> = recipes.iter().map(|r| r.id.as_str()).collect();
+            format!("无法识别的风格: {} (可用: {})", text, ids.join(", "))
+        })?;
+
+    let density_scale = if has(&["very dense", "非常茂密"]) {
+        2.0
+    } else if has(&["dense", "thick", "lush", "茂密", "密"]) {
+        1.5
+    } else if has(&["sparse", "scattered", "稀疏", "零星"]) {
+        0.5
+    } else {
+        1.0
+    };
+
+    Ok(GcMcpAreaStyle {
+        text: text.to_string(),
+        recipe,
+        density_scale,
+        clearing: has(&["clearing", "glade", "空地"]),
+    })
+}
+
+// =============================================================================
+// 布局规划
+// =============================================================================
+
+/// 区域布局报告 (试运行直接返回给代理)
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GcMcpAreaPlan {
+    /// 使用的配方 ID
+    pub recipe: String,
+    /// 密度倍率
+    pub density_scale: f32,
+    /// 空地半径 (没有空地为 0)
+    pub clearing_radius: f32,
+    /// 各层的校验结果 (命令附带服务器挑选的位置，可直接执行)
+    pub layers: Vec<GcMcpValidation>,
+    /// 注册表中没有可用预制体而跳过的层 (实体类型/分类)
+    #[serde(default)]
+    pub missing_layers: Vec<String>,
+    /// 请求生成的实体总数
+    pub requested: u32,
+    /// 预计新增的实体总数
+    pub added: u32,
+}
+
+impl GcMcpAreaPlan {
+    /// 是否可以执行 (至少能放下一个实体)
+    pub fn gc_is_valid(&self) -> bool {
+        self.added > 0
+    }
+
+    /// 需要执行的命令 (跳过一个都放不下的层)
+    pub fn gc_commands(&self) -> impl Iterator<Item = &GcMcpCommand> {
+        self.layers.iter().filter(|layer| layer.valid).map(|layer| &layer.command)
+    }
+}
+
+impl GcMcpWorld {
+    /// 按风格规划区域布局 (不修改世界，执行时依次应用 gc_commands)
+    pub fn gc_plan_area(
+        &self,
+        style: &GcMcpAreaStyle,
+        center: &GcPosition,
+        radius: f32,
+        registry: &GcPrefabRegistry,
+        map: Option<&GcChunkedMap>,
+    ) -> GcMcpAreaPlan {
+        let radius = radius.clamp(1.0, GC_MCP_AREA_MAX_RADIUS);
+        let clearing_radius = if style.clearing { radius * GC_MCP_CLEARING_RATIO } else { 0.0 };
+        let area = std::f32::consts::PI * (radius.powi(2) - clearing_radius.powi(2));
+
+        // 在副本上逐层应用，前一层的位置计入后一层的密度
+        let mut scratch = self.clone();
+        if clearing_radius > 0.0 {
+            scratch.rules.blocked_zones.push(GcMcpZone {
+                id: "clearing".to_string(),
+                center: *center,
+                radius: clearing_radius,
+            });
+        }
+
+        let mut plan = GcMcpAreaPlan {
+            recipe: style.recipe.id.clone(),
+            density_scale: style.density_scale,
+            clearing_radius,
+            layers: Vec::new(),
+            missing_layers: Vec::new(),
+            requested: 0,
+            added: 0,
+        };
+        for layer in &style.recipe.layers {
+            let prefab_ids = layer.gc_resolve(registry);
+            if prefab_ids.is_empty() {
+                plan.missing_layers.push(format!("{}/{}", layer.entity_type, layer.category));
+                continue;
+            }
+            let count = (layer.density * style.density_scale * area / 100.0).round() as u32;
+            if count == 0 {
+                continue;
+            }
+            let validation = scratch.gc_validate(
+                &GcMcpCommand::SpawnBatch {
+                    entity_type: layer.entity_type.clone(),
+                    prefab_ids,
+                    center: *center,
+                    radius,
+                    count,
+                    positions: Vec::new(),
+                },
+                map,
+            );
+            if validation.valid {
+                scratch.gc_apply(&validation.command);
+            }
+            plan.requested += validation.requested;
+            plan.added += validation.added;
+            plan.layers.push(validation);
+        }
+        plan
+    }
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn registry() -> GcPrefabRegistry {
+        let mut registry = GcPrefabRegistry::new();
+        registry.insert("trees".into(), vec!["pine_1".into(), "pine_2".into(), "common_tree_1".into()]);
+        registry.insert("plants".into(), vec!["fern_1".into(), "clover_1".into()]);
+        registry.insert("rocks".into(), vec!["rock_medium_1".into(), "pebble_round_1".into()]);
+        registry
+    }
+
+    #[test]
+    fn test_parse_area_style() {
+        let style = gc_parse_area_style("dense pine forest with a clearing").unwrap();
+        assert_eq!(style.recipe.id, "pine_forest");
+        assert_eq!(style.density_scale, 1.5);
+        assert!(style.clearing);
+
+        let style = gc_parse_area_style("稀疏的花园").unwrap();
+        assert_eq!(style.recipe.id, "garden");
+        assert_eq!(style.density_scale, 0.5);
+        assert!(!style.clearing);
+
+        assert_eq!(gc_parse_area_style("a forest").unwrap().recipe.id, "forest");
+        assert!(gc_parse_area_style("volcano").unwrap_err().contains("pine_forest"));
+    }
+
+    #[test]
+    fn test_resolve_layer_prefabs() {
+        let pines = GcMcpAreaLayer::gc_new("tree", "trees", &["pine_"], 1.0);
+        assert_eq!(pines.gc_resolve(&registry()), vec!["pine_1", "pine_2"]);
+        let mushrooms = GcMcpAreaLayer::gc_new("plant", "mushrooms", &[], 1.0);
+        assert!(mushrooms.gc_resolve(&registry()).is_empty());
+    }
+
+    #[test]
+    fn test_plan_area_layers_and_clearing() {
+        let world = GcMcpWorld::default();
+        let style = gc_parse_area_style("dense pine forest with a clearing").unwrap();
+        let center = GcPosition::gc_new(40, 40);
+        let plan = world.gc_plan_area(&style, &center, 12.0, &registry(), None);
+
+        assert!(plan.gc_is_valid());
+        assert_eq!(plan.missing_layers, vec!["plant/mushrooms"]);
+        assert_eq!(plan.layers.len(), 3);
+        assert_eq!(plan.added, plan.layers.iter().map(|l| l.added).sum::<u32>());
+
+        // 空地内没有实体，全部命令都只用注册表里的预制体
+        for command in plan.gc_commands() {
+            let GcMcpCommand::SpawnBatch { prefab_ids, positions, .. } = command else {
+                panic!("应为批量生成");
+            };
+            assert!(prefab_ids.iter().all(|id| registry().values().flatten().any(|r| r == id)));
+            assert!(positions.iter().all(|p| {
+                let (dx, dy) = ((p.x - center.x) as f32, (p.y - center.y) as f32);
+                (dx * dx + dy * dy).sqrt() > plan.clearing_radius
+            }));
+        }
+
+        // 规划不修改世界，同样的输入得到同样的结果
+        assert!(world.placements.is_empty());
+        assert_eq!(world.gc_plan_area(&style, &center, 12.0, &registry(), None), plan);
+    }
+}
@@ -0,0 +1,299 @@
+//! MCP 组合预制体 (营地等多实体组合)
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/12-MCP-API.md
+//!
+//! 把多个预制体组合成一个命名的可复用组 (例如营地 = 帐篷 + 篝火 + 木箱)，保存在服务器，
+//! 代理或编辑器一次 `place_group` 即可整体放置
+//!
+//! ## 规则
+//! - 组内成员记录相对组原点的偏移、旋转 (弧度，与 `spawn_entity` 相同) 与缩放
+//! - 放置时按组的旋转绕原点转动偏移 (与客户端绕 Y 轴旋转的方向一致)，坐标四舍五入到整数
+//! - 放置要求每个成员都通过世界规则校验，任何一个不通过则整组不放置
+//! - 放置后记录为组实例，`expand_group` 把实例拆回独立实体 (返回各实体的绝对位置，之后按单个实体编辑)
+//! - 组 ID 只能包含小写字母、数字、`_` 与 `-`；成员数量有上限
+
+use serde::{Deserialize, Serialize};
+
+use crate::{GcMcpCommand, GcPosition};
+
+/// 组内成员数量上限
+pub const GC_PREFAB_GROUP_MAX_MEMBERS: usize = 64;
+
+/// 组 ID 长度上限
+pub const GC_PREFAB_GROUP_MAX_ID_LEN: usize = 64;
+
+// =============================================================================
+// 组定义
+// =============================================================================
+
+/// 组内成员 (相对组原点)
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GcPrefabGroupMember {
+    /// 实体类型
+    pub entity_type: String,
+    /// 预制体 ID
+    pub prefab_id: String,
+    /// 相对组原点的偏移
+    pub offset: GcPosition,
+    /// 旋转 (弧度)
+    #[serde(default)]
+    pub rotation: f32,
+    /// 缩放
+    #[serde(default = "gc_default_scale")]
+    pub scale: f32,
+}
+
+fn gc_default_scale() -> f32 {
+    1.0
+}
+
+/// 组合预制体
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GcPrefabGroup {
+    /// 组 ID
+    pub id: String,
+    /// 显示名称
+    pub name: String,
+    /// 成员
+    pub members: Vec<GcPrefabGroupMember>,
+    /// 最后保存的 Key
+    #[serde(default)]
+    pub updated_by: String,
+    /// 最后保存时间 (Unix 秒)
+    #[serde(default)]
+    pub updated_at: u64,
+}
+
+/// 放置后的实体 (绝对位置)
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GcMcpGroupEntity {
+    /// 实体类型
+    pub entity_type: String,
+    /// 预制体 ID
+    pub prefab_id: String,
+    /// 位置
+    pub position: GcPosition,
+    /// 旋转 (弧度)
+    pub rotation: f32,
+    /// 缩放
+    pub scale: f32,
+}
+
+impl GcMcpGroupEntity {
+    /// 作为单个实体生成的命令
+    pub fn gc_spawn_command(&self) -> GcMcpCommand {
+        GcMcpCommand::SpawnEntity {
+            entity_type: self.entity_type.clone(),
+            prefab_id: self.prefab_id.clone(),
+            position: self.position,
+            rotation: self.rotation,
+            scale: self.scale,
+        }
+    }
+}
+
+/// 按旋转转动偏移 (绕 Y 轴，x 对应场景 x，y 对应场景 z)
+fn gc_rotate(offset: &GcPosition, rotation: f32) -> GcPosition {
+    let (sin, cos) = rotation.sin_cos();
+    let (x, y) = (offset.x as f32, offset.y as f32);
+    GcPosition::gc_new((x * cos + y * sin).round() as i32, (y * cos - x * sin).round() as i32)
+}
+
+impl GcPrefabGroup {
+    /// 由已放置的实体组合 (位置换算为相对 origin 的偏移)
+    pub fn gc_compose(id: &str, name: &str, origin: &GcPosition, entities: &[GcMcpGroupEntity]) -> Self {
+        Self {
+            id: id.to_string(),
+            name: name.to_string(),
+            members: entities
+                .iter()
+                .map(|e| GcPrefabGroupMember {
+                    entity_type: e.entity_type.clone(),
+                    prefab_id: e.prefab_id.clone(),
+                    offset: GcPosition::gc_new(e.position.x - origin.x, e.position.y - origin.y),
+                    rotation: e.rotation,
+                    scale: e.scale,
+                })
+                .collect(),
+            updated_by: String::new(),
+            updated_at: 0,
+        }
+    }
+
+    /// 检查组定义
+    pub fn gc_validate(&self) -> Result<(), String> {
+        let id_ok = !self.id.is_empty()
+            && self.id.len() <= GC_PREFAB_GROUP_MAX_ID_LEN
+            && self.id.bytes().all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'_' || b == b'-');
+        if !id_ok {
+            return Err(format!("组 ID 无效: {:?} (小写字母、数字、_ 与 -，最长 {})", self.id, GC_PREFAB_GROUP_MAX_ID_LEN));
+        }
+        if self.name.trim().is_empty() {
+            return Err("组名称不能为空".to_string());
+        }
+        if self.members.is_empty() || self.members.len() > GC_PREFAB_GROUP_MAX_MEMBERS {
+            return Err(format!("组成员数量应为 1-{}，实际 {}", GC_PREFAB_GROUP_MAX_MEMBERS, self.members.len()));
+        }
+        for (i, member) in self.members.iter().enumerate() {
+            if member.prefab_id.is_empty() || member.entity_type.is_empty() {
+                return Err(format!("第 {} 个成员缺少预制体或实体类型", i + 1));
+            }
+            if !(member.scale.is_finite() && member.scale > 0.0 && member.rotation.is_finite()) {
+                return Err(format!("第 {} 个成员的旋转或缩放无效", i + 1));
+            }
+        }
+        Ok(())
+    }
+
+    /// 成员离原点的最远距离
+    pub fn gc_radius(&self) -> f32 {
+        self.members
+            .iter()
+            .map(|m| ((m.offset.x as f32).powi(2) + (m.offset.y as f32).powi(2)).sqrt())
+            .fold(0.0, f32::max)
+    }
+
+    /// 在 origin 处按 rotation 放置，返回各成员的绝对位置
+    pub fn gc_place(&self, origin: &GcPosition, rotation: f32) -> Vec<GcMcpGroupEntity> {
+        self.members
+            .iter()
+            .map(|m| {
+                let offset = gc_rotate(&m.offset, rotation);
+                GcMcpGroupEntity {
+                    entity_type: m.entity_type.clone(),
+                    prefab_id: m.prefab_id.clone(),
+                    position: GcPosition::gc_new(origin.x + offset.x, origin.y + offset.y),
+                    rotation: m.rotation + rotation,
+                    scale: m.scale,
+                }
+            })
+            .collect()
+    }
+
+    /// 放置命令 (实例 ID 由世界记录在校验时分配)
+    pub fn gc_place_command(&self, origin: &GcPosition, rotation: f32) -> GcMcpCommand {
+        GcMcpCommand::SpawnGroup {
+            group_id: self.id.clone(),
+            instance_id: String::new(),
+            origin: *origin,
+            rotation,
+            entities: self.gc_place(origin, rotation),
+        }
+    }
+}
+
+// =============================================================================
+// 组实例
+// =============================================================================
+
+/// 世界中已放置的组实例
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GcMcpGroupInstance {
+    /// 实例 ID
+    pub instance_id: String,
+    /// 组 ID
+    pub group_id: String,
+    /// 原点
+    pub origin: GcPosition,
+    /// 旋转 (弧度)
+    pub rotation: f32,
+    /// 放置的实体
+    pub entities: Vec<GcMcpGroupEntity>,
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GcMcpWorld;
+
+    fn campsite() -> GcPrefabGroup {
+        let member = |prefab: &str, x: i32, y: i32| GcPrefabGroupMember {
+            entity_type: "structure".to_string(),
+            prefab_id: prefab.to_string(),
+            offset: GcPosition::gc_new(x, y),
+            rotation: 0.0,
+            scale: 1.0,
+        };
+        GcPrefabGroup {
+            id: "campsite".to_string(),
+            name: "营地".to_string(),
+            members: vec![member("campfire", 0, 0), member("tent", 3, 0), member("crate", 0, 2)],
+            updated_by: String::new(),
+            updated_at: 0,
+        }
+    }
+
+    #[test]
+    fn test_place_applies_relative_transform() {
+        let group = campsite();
+        assert!(group.gc_validate().is_ok());
+        assert_eq!(group.gc_radius(), 3.0);
+
+        let placed = group.gc_place(&GcPosition::gc_new(20, 30), std::f32::consts::FRAC_PI_2);
+        let positions: Vec<GcPosition> = placed.iter().map(|e| e.position).collect();
+        assert_eq!(positions, vec![GcPosition::gc_new(20, 30), GcPosition::gc_new(20, 27), GcPosition::gc_new(22, 30)]);
+        assert!(placed.iter().all(|e| e.rotation == std::f32::consts::FRAC_PI_2));
+
+        // 由放置结果重新组合 (不旋转) 得到同样的偏移
+        let flat = group.gc_place(&GcPosition::gc_new(-5, 8), 0.0);
+        let composed = GcPrefabGroup::gc_compose("campsite", "营地", &GcPosition::gc_new(-5, 8), &flat);
+        assert_eq!(composed.members, group.members);
+    }
+
+    #[test]
+    fn test_validate_rejects_bad_groups() {
+        let mut group = campsite();
+        group.id = "Camp Site".to_string();
+        assert!(group.gc_validate().is_err());
+
+        let mut empty = campsite();
+        empty.members.clear();
+        assert!(empty.gc_validate().is_err());
+
+        let mut scaled = campsite();
+        scaled.members[1].scale = 0.0;
+        assert!(scaled.gc_validate().is_err());
+    }
+
+    #[test]
+    fn test_world_places_and_expands_instances() {
+        let mut world = GcMcpWorld::default();
+        let group = campsite();
+
+        let placed = world.gc_validate(&group.gc_place_command(&GcPosition::gc_new(20, 20), 0.0), None);
+        assert!(placed.valid);
+        assert_eq!(placed.added, 3);
+        let GcMcpCommand::SpawnGroup { instance_id, .. } = &placed.command else {
+            panic!("应为组放置");
+        };
+        assert_eq!(instance_id, "campsite_1");
+        world.gc_apply(&placed.command);
+        assert_eq!(world.placements.len(), 3);
+        assert_eq!(world.groups.len(), 1);
+
+        // 任一成员不合法则整组不放置
+        let blocked = world.gc_validate(&group.gc_place_command(&GcPosition::gc_new(99, 0), 0.0), None);
+        assert!(!blocked.valid);
+        assert_eq!(blocked.issues[0].code, "out_of_bounds");
+
+        // 拆开实例: 命令附带各实体的绝对位置，实例记录移除，实体分布保留
+        let expand = world.gc_validate(&GcMcpCommand::ExpandGroup { instance_id: "campsite_1".to_string(), entities: Vec::new() }, None);
+        assert!(expand.valid);
+        let GcMcpCommand::ExpandGroup { entities, .. } = &expand.command else {
+            panic!("应为拆开组");
+        };
+        assert_eq!(entities.len(), 3);
+        world.gc_apply(&expand.command);
+        assert!(world.groups.is_empty());
+        assert_eq!(world.placements.len(), 3);
+
+        let missing = world.gc_validate(&GcMcpCommand::ExpandGroup { instance_id: "campsite_1".to_string(), entities: Vec::new() }, None);
+        assert_eq!(missing.issues[0].code, "unknown_group_instance");
+    }
+}
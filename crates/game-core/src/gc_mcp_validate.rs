@@ -0,0 +1,558 @@
+//! MCP 世界编辑校验
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/12-MCP-API.md
+//!
+//! ## 校验规则
+//! - 边界: 目标位置必须在世界边界内；指定地图时使用该地图的实际尺寸
+//! - 禁止区域: 出生点周围等区域不允许放置实体
+//! - 密度: 服务器记录 MCP 生成过的实体分布，生成后局部密度不得超过上限
+//! - 可通行: 指定地图时，目标位置必须是可通行瓦片
+//! - 批量生成由服务器逐个挑选位置，不满足规则的跳过，返回部分成功报告
+//! - 试运行 (dry_run) 与正式执行使用同一套校验，且位置挑选是确定的，试运行结果即执行结果
+
+use std::collections::BTreeMap;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use crate::{GcChunkedMap, GcMapHeader, GcMcpCommand, GcMcpGroupInstance, GcPosition};
+
+/// 出生点周围的禁止放置半径
+pub const GC_MCP_SPAWN_CLEARANCE: f32 = 3.0;
+
+/// 批量生成时每个实体的选位尝试次数
+const GC_MCP_PLACE_ATTEMPTS: u32 = 3;
+
+// =============================================================================
+// 规则
+// =============================================================================
+
+/// 禁止放置的圆形区域
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GcMcpZone {
+    /// 区域标识
+    pub id: String,
+    /// 中心
+    pub center: GcPosition,
+    /// 半径
+    pub radius: f32,
+}
+
+/// 世界编辑规则
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GcMcpWorldRules {
+    /// 最小 X
+    pub min_x: i32,
+    /// 最小 Y
+    pub min_y: i32,
+    /// 最大 X
+    pub max_x: i32,
+    /// 最大 Y
+    pub max_y: i32,
+    /// 密度上限 (每 100 平方单位的实体数)
+    pub max_density: f32,
+    /// 统计局部密度的半径
+    pub density_radius: f32,
+    /// 单次批量生成的数量上限
+    pub max_batch_count: u32,
+    /// 禁止放置的区域
+    #[serde(default)]
+    pub blocked_zones: Vec<GcMcpZone>,
+}
+
+impl Default for GcMcpWorldRules {
+    fn default() -> Self {
+        Self {
+            min_x: -100,
+            min_y: -100,
+            max_x: 100,
+            max_y: 100,
+            max_density: 10.0,
+            density_radius: 5.0,
+            max_batch_count: 200,
+            blocked_zones: vec![GcMcpZone {
+                id: "spawn_point".to_string(),
+                center: GcPosition::gc_new(0, 0),
+                radius: GC_MCP_SPAWN_CLEARANCE,
+            }],
+        }
+    }
+}
+
+impl GcMcpWorldRules {
+    /// 换成地图的实际边界与出生点 (密度规则与其他禁止区域不变)
+    pub fn gc_with_map(&self, header: &GcMapHeader) -> Self {
+        let mut blocked_zones: Vec<GcMcpZone> =
+            self.blocked_zones.iter().filter(|zone| zone.id != "spawn_point").cloned().collect();
+        blocked_zones.push(GcMcpZone {
+            id: "spawn_point".to_string(),
+            center: header.spawn_point,
+            radius: GC_MCP_SPAWN_CLEARANCE,
+        });
+        Self {
+            min_x: 0,
+            min_y: 0,
+            max_x: header.width as i32 - 1,
+            max_y: header.height as i32 - 1,
+            blocked_zones,
+            ..self.clone()
+        }
+    }
+
+    /// 圆形区域是否完全在边界内
+    pub fn gc_contains(&self, center: &GcPosition, radius: f32) -> bool {
+        let (x, y) = (center.x as f32, center.y as f32);
+        x - radius >= self.min_x as f32
+            && x + radius <= self.max_x as f32
+            && y - radius >= self.min_y as f32
+            && y + radius <= self.max_y as f32
+    }
+
+    /// 位置所在的禁止区域
+    pub fn gc_blocked_zone(&self, pos: &GcPosition) -> Option<&GcMcpZone> {
+        self.blocked_zones.iter().find(|zone| gc_distance(&zone.center, pos) <= zone.radius)
+    }
+}
+
+// =============================================================================
+// 世界记录
+// =============================================================================
+
+/// 一次生成的实体分布 (单个实体半径为 0)
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GcMcpPlacement {
+    /// 中心
+    pub center: GcPosition,
+    /// 分布半径
+    pub radius: f32,
+    /// 实体数量
+    pub count: u32,
+}
+
+/// 服务器记录的 MCP 世界 (实体由客户端生成，这里只记录分布用于密度估算)
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct GcMcpWorld {
+    /// 编辑规则
+    pub rules: GcMcpWorldRules,
+    /// 已生成的实体分布
+    pub placements: Vec<GcMcpPlacement>,
+    /// 已放置、尚未拆开的组实例
+    #[serde(default)]
+    pub groups: Vec<GcMcpGroupInstance>,
+    /// 已分配的组实例数 (生成实例 ID)
+    #[serde(default)]
+    pub group_serial: u64,
+}
+
+/// 执行前的校验结果 (试运行直接返回给代理)
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GcMcpValidation {
+    /// 是否可以执行 (批量生成至少能放下一个)
+    pub valid: bool,
+    /// 违反的规则
+    pub issues: Vec<GcMcpIssue>,
+    /// 实际执行的命令 (批量生成附带服务器挑选的位置)
+    pub command: GcMcpCommand,
+    /// 请求生成的实体数
+    pub requested: u32,
+    /// 预计新增的实体数
+    pub added: u32,
+    /// 预计移除的实体数 (估算)
+    pub removed: u32,
+    /// 批量生成中被跳过的数量 (按规则代码)
+    #[serde(default)]
+    pub skipped: BTreeMap<String, u32>,
+    /// 执行后目标区域的密度 (每 100 平方单位)
+    pub density_after: f32,
+}
+
+/// 违反的规则
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GcMcpIssue {
+    /// 规则代码 (out_of_bounds, blocked_zone, not_walkable, too_dense, too_many, unknown_group_instance)
+    pub code: String,
+    /// 说明
+    pub message: String,
+}
+
+impl GcMcpIssue {
+    fn gc_new(code: &str, message: String) -> Self {
+        Self { code: code.to_string(), message }
+    }
+}
+
+/// 规则代码的说明
+fn gc_issue_label(code: &str) -> &'static str {
+    match code {
+        "out_of_bounds" => "超出世界边界",
+        "blocked_zone" => "位于禁止区域",
+        "not_walkable" => "不可通行",
+        "too_dense" => "密度超过上限",
+        "too_many" => "超过单次数量上限",
+        _ => "不满足规则",
+    }
+}
+
+/// 圆面积 (半径不足 1 时按 1 计算)
+fn gc_area(radius: f32) -> f32 {
+    std::f32::consts::PI * radius.max(1.0).powi(2)
+}
+
+/// 两点距离
+fn gc_distance(a: &GcPosition, b: &GcPosition) -> f32 {
+    (((a.x - b.x) as f32).powi(2) + ((a.y - b.y) as f32).powi(2)).sqrt()
+}
+
+impl GcMcpWorld {
+    /// 估算圆形区域内的实体数 (批量生成按均匀分布折算)
+    pub fn gc_count_within(&self, center: &GcPosition, radius: f32) -> f32 {
+        self.placements
+            .iter()
+            .filter(|p| gc_distance(&p.center, center) <= radius + p.radius)
+            .map(|p| {
+                if p.radius <= radius {
+                    p.count as f32
+                } else {
+                    p.count as f32 * (radius / p.radius).powi(2)
+                }
+            })
+            .sum()
+    }
+
+    /// 区域密度 (每 100 平方单位，extra 为额外计入的实体数)
+    pub fn gc_density(&self, center: &GcPosition, radius: f32, extra: u32) -> f32 {
+        (self.gc_count_within(center, radius) + extra as f32) * 100.0 / gc_area(radius)
+    }
+
+    /// 检查单个位置 (pending 为同一命令中已挑选的位置，density 为 false 时不检查密度)
+    fn gc_check_point(
+        &self,
+        rules: &GcMcpWorldRules,
+        pos: &GcPosition,
+        map: Option<&GcChunkedMap>,
+        pending: &[GcPosition],
+        density: bool,
+    ) -> Vec<GcMcpIssue> {
+        let mut issues = Vec::new();
+        if !rules.gc_contains(pos, 0.0) {
+            issues.push(GcMcpIssue::gc_new(
+                "out_of_bounds",
+                format!(
+                    "({}, {}) 超出世界边界 [{}, {}] x [{}, {}]",
+                    pos.x, pos.y, rules.min_x, rules.max_x, rules.min_y, rules.max_y
+                ),
+            ));
+        }
+        if let Some(zone) = rules.gc_blocked_zone(pos) {
+            issues.push(GcMcpIssue::gc_new(
+                "blocked_zone",
+                format!("({}, {}) 位于禁止区域 {} (半径 {})", pos.x, pos.y, zone.id, zone.radius),
+            ));
+        }
+        if let Some(map) = map {
+            if !map.gc_can_walk(pos) {
+                issues.push(GcMcpIssue::gc_new(
+                    "not_walkable",
+                    format!("({}, {}) 在地图 {} 上不可通行", pos.x, pos.y, map.header.id),
+                ));
+            }
+        }
+        if density {
+            let nearby = pending.iter().filter(|p| gc_distance(p, pos) <= rules.density_radius).count() as u32;
+            let after = self.gc_density(pos, rules.density_radius, nearby + 1);
+            if after > rules.max_density {
+                issues.push(GcMcpIssue::gc_new(
+                    "too_dense",
+                    format!("({}, {}) 生成后密度 {:.1} 超过上限 {:.1} (每 100 平方单位)", pos.x, pos.y, after, rules.max_density),
+                ));
+            }
+        }
+        issues
+    }
+
+    /// 校验命令 (指定地图时使用地图边界并检查可通行)
+    pub fn gc_validate(&self, command: &GcMcpCommand, map: Option<&GcChunkedMap>) -> GcMcpValidation {
+        let rules = match map {
+            Some(map) => self.rules.gc_with_map(&map.header),
+            None => self.rules.clone(),
+        };
+        let mut validation = GcMcpValidation {
+            valid: true,
+            issues: Vec::new(),
+            command: command.clone(),
+            requested: 0,
+            added: 0,
+            removed: 0,
+            skipped: BTreeMap::new(),
+            density_after: 0.0,
+        };
+
+        match command {
+            GcMcpCommand::SpawnEntity { position, .. } => {
+                validation.issues = self.gc_check_point(&rules, position, map, &[], true);
+                validation.requested = 1;
+                validation.added = 1;
+                validation.density_after = self.gc_density(position, rules.density_radius, 1);
+            }
+            GcMcpCommand::MoveEntity { position, .. } => {
+                validation.issues = self.gc_check_point(&rules, position, map, &[], false);
+            }
+            GcMcpCommand::ClearArea { center, radius } => {
+                if !rules.gc_contains(center, 0.0) {
+                    validation.issues = self.gc_check_point(&rules, center, None, &[], false);
+                }
+                validation.removed = self.gc_count_within(center, *radius).round() as u32;
+            }
+            GcMcpCommand::SpawnGroup { group_id, instance_id, origin, rotation, entities } => {
+                let mut pending: Vec<GcPosition> = Vec::new();
+                for entity in entities {
+                    validation.issues.extend(self.gc_check_point(&rules, &entity.position, map, &pending, true));
+                    pending.push(entity.position);
+                }
+                validation.requested = entities.len() as u32;
+                validation.added = entities.len() as u32;
+                validation.density_after = self.gc_density(origin, rules.density_radius, validation.added);
+                validation.command = GcMcpCommand::SpawnGroup {
+                    group_id: group_id.clone(),
+                    instance_id: if instance_id.is_empty() {
+                        format!("{}_{}", group_id, self.group_serial + 1)
+                    } else {
+                        instance_id.clone()
+                    },
+                    origin: *origin,
+                    rotation: *rotation,
+                    entities: entities.clone(),
+                };
+            }
+            GcMcpCommand::ExpandGroup { instance_id, .. } => match self.gc_group(instance_id) {
+                Some(instance) => {
+                    validation.command = GcMcpCommand::ExpandGroup {
+                        instance_id: instance_id.clone(),
+                        entities: instance.entities.clone(),
+                    };
+                }
+                None => validation.issues.push(GcMcpIssue::gc_new(
+                    "unknown_group_instance",
+                    format!("组实例不存在: {}", instance_id),
+                )),
+            },
+            GcMcpCommand::SpawnBatch { entity_type, prefab_ids, center, radius, count, .. } => {
+                let positions = self.gc_plan_batch(&rules, center, *radius, *count, map, &mut validation.skipped);
+                for (code, skipped) in &validation.skipped {
+                    validation.issues.push(GcMcpIssue::gc_new(
+                        code,
+                        format!("{} 个实体{}，已跳过", skipped, gc_issue_label(code)),
+                    ));
+                }
+                validation.requested = *count;
+                validation.added = positions.len() as u32;
+                validation.density_after =
+                    self.gc_density(center, radius.max(rules.density_radius), validation.added);
+                validation.valid = !positions.is_empty();
+                validation.command = GcMcpCommand::SpawnBatch {
+                    entity_type: entity_type.clone(),
+                    prefab_ids: prefab_ids.clone(),
+                    center: *center,
+                    radius: *radius,
+                    count: positions.len() as u32,
+                    positions,
+                };
+                return validation;
+            }
+            _ => {}
+        }
+
+        validation.valid = validation.issues.is_empty();
+        validation
+    }
+
+    /// 为批量生成挑选位置 (种子由命令与世界状态决定，同样的输入得到同样的位置)
+    fn gc_plan_batch(
+        &self,
+        rules: &GcMcpWorldRules,
+        center: &GcPosition,
+        radius: f32,
+        count: u32,
+        map: Option<&GcChunkedMap>,
+        skipped: &mut BTreeMap<String, u32>,
+    ) -> Vec<GcPosition> {
+        let attempts = count.min(rules.max_batch_count);
+        if count > attempts {
+            skipped.insert("too_many".to_string(), count - attempts);
+        }
+
+        let seed = (center.x as u32 as u64) << 32
+            ^ center.y as u32 as u64
+            ^ (radius.to_bits() as u64) << 16
+            ^ (count as u64) << 48
+            ^ self.placements.len() as u64;
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut positions: Vec<GcPosition> = Vec::new();
+        for _ in 0..attempts {
+            let mut last_code = None;
+            for _ in 0..GC_MCP_PLACE_ATTEMPTS {
+                let angle = rng.gen::<f32>() * std::f32::consts::TAU;
+                let distance = radius.max(0.0) * rng.gen::<f32>().sqrt();
+                let pos = GcPosition::gc_new(
+                    center.x + (angle.cos() * distance).round() as i32,
+                    center.y + (angle.sin() * distance).round() as i32,
+                );
+                match self.gc_check_point(rules, &pos, map, &positions, true).into_iter().next() {
+                    None => {
+                        positions.push(pos);
+                        last_code = None;
+                        break;
+                    }
+                    Some(issue) => last_code = Some(issue.code),
+                }
+            }
+            if let Some(code) = last_code {
+                *skipped.entry(code).or_insert(0) += 1;
+            }
+        }
+        positions
+    }
+
+    /// 记录已执行的命令 (只影响密度估算)
+    pub fn gc_apply(&mut self, command: &GcMcpCommand) {
+        match command {
+            GcMcpCommand::SpawnEntity { position, .. } => {
+                self.placements.push(GcMcpPlacement { center: *position, radius: 0.0, count: 1 });
+            }
+            GcMcpCommand::SpawnBatch { center, radius, count, positions, .. } => {
+                if positions.is_empty() {
+                    self.placements.push(GcMcpPlacement { center: *center, radius: *radius, count: *count });
+                } else {
+                    self.placements.extend(
+                        positions.iter().map(|p| GcMcpPlacement { center: *p, radius: 0.0, count: 1 }),
+                    );
+                }
+            }
+            GcMcpCommand::ClearArea { center, radius } => {
+                self.placements.retain(|p| gc_distance(&p.center, center) > *radius);
+                self.groups.retain(|g| gc_distance(&g.origin, center) > *radius);
+            }
+            GcMcpCommand::SpawnGroup { group_id, instance_id, origin, rotation, entities } => {
+                self.placements.extend(
+                    entities.iter().map(|e| GcMcpPlacement { center: e.position, radius: 0.0, count: 1 }),
+                );
+                self.group_serial += 1;
+                self.groups.push(GcMcpGroupInstance {
+                    instance_id: instance_id.clone(),
+                    group_id: group_id.clone(),
+                    origin: *origin,
+                    rotation: *rotation,
+                    entities: entities.clone(),
+                });
+            }
+            GcMcpCommand::ExpandGroup { instance_id, .. } => {
+                self.groups.retain(|g| g.instance_id != *instance_id);
+            }
+            _ => {}
+        }
+    }
+
+    /// 查找组实例
+    pub fn gc_group(&self, instance_id: &str) -> Option<&GcMcpGroupInstance> {
+        self.groups.iter().find(|g| g.instance_id == instance_id)
+    }
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gc_create_town_map;
+
+    fn spawn(x: i32, y: i32) -> GcMcpCommand {
+        GcMcpCommand::SpawnEntity {
+            entity_type: "tree".to_string(),
+            prefab_id: "pine_1".to_string(),
+            position: GcPosition::gc_new(x, y),
+            rotation: 0.0,
+            scale: 1.0,
+        }
+    }
+
+    fn batch(x: i32, y: i32, radius: f32, count: u32) -> GcMcpCommand {
+        GcMcpCommand::SpawnBatch {
+            entity_type: "tree".to_string(),
+            prefab_ids: vec!["pine_1".to_string()],
+            center: GcPosition::gc_new(x, y),
+            radius,
+            count,
+            positions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_validate_bounds_and_blocked_zone() {
+        let world = GcMcpWorld::default();
+        let ok = world.gc_validate(&spawn(10, 10), None);
+        assert!(ok.valid);
+        assert_eq!(ok.added, 1);
+
+        assert_eq!(world.gc_validate(&spawn(150, 0), None).issues[0].code, "out_of_bounds");
+        assert_eq!(world.gc_validate(&spawn(1, 1), None).issues[0].code, "blocked_zone");
+
+        // 指定地图时使用地图尺寸与出生点，并检查可通行 (营地四周是围墙)
+        let map = GcChunkedMap::gc_from_map(&gc_create_town_map(), 0);
+        let codes: Vec<String> = world.gc_validate(&spawn(0, 0), Some(&map)).issues.into_iter().map(|i| i.code).collect();
+        assert_eq!(codes, vec!["not_walkable"]);
+        assert_eq!(world.gc_validate(&spawn(30, 5), Some(&map)).issues[0].code, "out_of_bounds");
+    }
+
+    #[test]
+    fn test_batch_reports_partial_success() {
+        let world = GcMcpWorld::default();
+        let result = world.gc_validate(&batch(20, 20, 10.0, 500), None);
+        assert!(result.valid);
+        assert_eq!(result.requested, 500);
+        assert_eq!(result.skipped["too_many"], 300);
+        assert!(result.skipped["too_dense"] > 0);
+        let GcMcpCommand::SpawnBatch { count, positions, .. } = &result.command else {
+            panic!("应为批量生成");
+        };
+        assert_eq!(*count, result.added);
+        assert!(positions.iter().all(|p| world.rules.gc_contains(p, 0.0)));
+
+        // 同样的输入得到同样的位置，试运行即执行结果
+        assert_eq!(world.gc_validate(&batch(20, 20, 10.0, 500), None), result);
+
+        // 贴着边界与出生点的批量生成只放置合法位置
+        let edge = world.gc_validate(&batch(98, 0, 8.0, 20), None);
+        assert!(edge.skipped.contains_key("out_of_bounds"));
+        let center = world.gc_validate(&batch(0, 0, 2.0, 5), None);
+        assert!(!center.valid);
+        assert_eq!(center.skipped["blocked_zone"], 5);
+    }
+
+    #[test]
+    fn test_density_tracks_applied_commands() {
+        let mut world = GcMcpWorld::default();
+        for _ in 0..6 {
+            world.gc_apply(&spawn(10, 10));
+        }
+        assert!(world.gc_validate(&spawn(11, 11), None).valid);
+
+        world.gc_apply(&spawn(10, 10));
+        assert_eq!(world.gc_validate(&spawn(11, 11), None).issues[0].code, "too_dense");
+
+        // 清空区域后密度恢复，试运行预估移除数量
+        let clear = GcMcpCommand::ClearArea { center: GcPosition::gc_new(10, 10), radius: 3.0 };
+        assert_eq!(world.gc_validate(&clear, None).removed, 7);
+        world.gc_apply(&clear);
+        assert!(world.gc_validate(&spawn(11, 11), None).valid);
+
+        // 批量生成按实际位置记录
+        let planned = world.gc_validate(&batch(-40, -40, 6.0, 10), None);
+        world.gc_apply(&planned.command);
+        assert_eq!(world.placements.len(), planned.added as usize);
+    }
+}
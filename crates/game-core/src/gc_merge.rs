@@ -17,9 +17,12 @@
 //! - 3只都在战场 → 留在最左槽位
 //! - 混合(战场+手牌) → 留在战场槽位
 //! - 3只都在手牌 → 留在手牌区
+//!
+//! ## 皮肤继承
+//! 沿用留在结果位置的单位的皮肤，它没有皮肤时取其余单位中第一个有皮肤的 (见 `gc_merged_skin`)
 
 use serde::{Deserialize, Serialize};
-use crate::GcMonster;
+use crate::{gc_merged_skin, GcMonster};
 
 // =============================================================================
 // 合成结果
@@ -206,8 +209,9 @@ pub fn gc_merge_monsters(monsters: [GcMonster; 3]) -> GcMergeResult {
         to_golden,
     );
     
-    // 继承槽位
+    // 继承槽位与皮肤
     merged.slot = result_location;
+    merged.skin = gc_merged_skin(&monsters);
     merged.can_attack = false; // 刚合成不能攻击
     
     GcMergeResult {
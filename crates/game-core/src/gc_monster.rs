@@ -15,10 +15,30 @@
 //! - 金色LvN = 3 + N×3 金
 
 use serde::{Deserialize, Serialize};
-use crate::{GcMonsterAttribute, GcTerrainType, gc_get_terrain_modifier};
+use crate::{
+    GcDamageType, GcInternedId, GcMonsterAttribute, GcResistances, GcTerrainType, GC_PERCENT,
+    gc_get_terrain_modifier, gc_intern, gc_percent_of,
+};
+
+/// 默认速度 (旧存档缺少速度字段时使用)
+pub const GC_DEFAULT_MONSTER_SPEED: u32 = 10;
+
+fn gc_default_monster_speed() -> u32 {
+    GC_DEFAULT_MONSTER_SPEED
+}
+
+/// 攻击距离
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GcAttackReach {
+    /// 近战 (只能攻击前排，在后排无法攻击)
+    #[default]
+    Melee,
+    /// 远程 (可攻击任意排)
+    Ranged,
+}
 
 /// 怪兽实体
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct GcMonster {
     /// 唯一ID
     pub id: String,
@@ -48,6 +68,18 @@ pub struct GcMonster {
     pub star: u8,
     /// 金色等级 (0=普通, 1+=金色强化次数)
     pub golden_level: u8,
+    /// 攻击距离
+    #[serde(default)]
+    pub reach: GcAttackReach,
+    /// 速度 (决定先攻顺序)
+    #[serde(default = "gc_default_monster_speed")]
+    pub speed: u32,
+    /// 攻击伤害类型
+    #[serde(default)]
+    pub damage_type: GcDamageType,
+    /// 皮肤 ID (纯装饰，None 为默认外观)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub skin: Option<String>,
 }
 
 impl GcMonster {
@@ -81,6 +113,10 @@ impl GcMonster {
             can_attack: false,
             star: 1,
             golden_level: 0,
+            reach: GcAttackReach::Melee,
+            speed: GC_DEFAULT_MONSTER_SPEED,
+            damage_type: GcDamageType::Physical,
+            skin: None,
         }
     }
     
@@ -109,21 +145,24 @@ impl GcMonster {
     // 星级系统
     // =========================================================================
     
-    /// 计算星级属性倍率
-    /// - 1★ = 1.0x
-    /// - 2★ = 2.0x
-    /// - 3★ = 3.0x
-    /// - 金色LvN = 3.0 × (1 + N × 0.5)
-    pub fn star_multiplier(&self) -> f32 {
-        let base = self.star as f32;
+    /// 计算星级属性倍率 (百分比，100 = 1.0x)
+    /// - 1★ = 100
+    /// - 2★ = 200
+    /// - 3★ = 300
+    /// - 金色LvN = 300 × (1 + N × 0.5) = 300 + N × 150
+    pub fn star_multiplier_percent(&self) -> u32 {
         if self.golden_level > 0 {
-            // 金色单位: 3 × (1 + golden_level × 0.5)
-            3.0 * (1.0 + self.golden_level as f32 * 0.5)
+            300 + self.golden_level as u32 * 150
         } else {
-            base
+            self.star as u32 * GC_PERCENT
         }
     }
     
+    /// 星级属性倍率 (仅用于显示，战斗计算使用 star_multiplier_percent)
+    pub fn star_multiplier(&self) -> f32 {
+        self.star_multiplier_percent() as f32 / GC_PERCENT as f32
+    }
+    
     /// 是否为金色单位
     pub fn is_golden(&self) -> bool {
         self.golden_level > 0
@@ -172,31 +211,36 @@ impl GcMonster {
     
     /// 获取星级加成后的最大HP
     pub fn effective_max_hp(&self) -> u32 {
-        (self.max_hp as f32 * self.star_multiplier()) as u32
+        gc_percent_of(self.max_hp, self.star_multiplier_percent())
     }
 
     /// 获取地形加成后的攻击力 (含星级)
     pub fn effective_atk(&self, terrain: GcTerrainType) -> u32 {
-        let base_with_star = (self.base_atk as f32 * self.star_multiplier()) as u32;
+        let base_with_star = self.starred_atk();
         let modifier = gc_get_terrain_modifier(terrain, self.attribute);
         modifier.apply_atk(base_with_star)
     }
 
     /// 获取地形加成后的防御力 (含星级)
     pub fn effective_def(&self, terrain: GcTerrainType) -> u32 {
-        let base_with_star = (self.base_def as f32 * self.star_multiplier()) as u32;
+        let base_with_star = self.starred_def();
         let modifier = gc_get_terrain_modifier(terrain, self.attribute);
         modifier.apply_def(base_with_star)
     }
     
+    /// 获取地形加成后的速度 (不受星级影响)
+    pub fn effective_speed(&self, terrain: GcTerrainType) -> u32 {
+        gc_get_terrain_modifier(terrain, self.attribute).apply_speed(self.speed)
+    }
+    
     /// 获取纯星级加成后的攻击力 (不含地形)
     pub fn starred_atk(&self) -> u32 {
-        (self.base_atk as f32 * self.star_multiplier()) as u32
+        gc_percent_of(self.base_atk, self.star_multiplier_percent())
     }
     
     /// 获取纯星级加成后的防御力 (不含地形)
     pub fn starred_def(&self) -> u32 {
-        (self.base_def as f32 * self.star_multiplier()) as u32
+        gc_percent_of(self.base_def, self.star_multiplier_percent())
     }
 
     /// 受到伤害
@@ -214,6 +258,11 @@ impl GcMonster {
     pub fn is_alive(&self) -> bool {
         self.current_hp > 0
     }
+
+    /// 伤害类型抗性 (由属性决定)
+    pub fn resistances(&self) -> GcResistances {
+        GcResistances::gc_from_attribute(self.attribute)
+    }
 }
 
 // =============================================================================
@@ -223,10 +272,10 @@ impl GcMonster {
 /// 战斗结果
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct GcBattleResult {
-    /// 攻击者ID
-    pub attacker_id: String,
-    /// 防御者ID
-    pub defender_id: String,
+    /// 攻击者ID (序列化为字符串)
+    pub attacker_id: GcInternedId,
+    /// 防御者ID (序列化为字符串)
+    pub defender_id: GcInternedId,
     /// 攻击者有效攻击力
     pub attacker_atk: u32,
     /// 防御者有效防御力
@@ -239,6 +288,12 @@ pub struct GcBattleResult {
     pub counter_damage: u32,
     /// 攻击者是否死亡
     pub attacker_destroyed: bool,
+    /// 攻击伤害类型
+    #[serde(default)]
+    pub damage_type: GcDamageType,
+    /// 防御者对该类型的抗性 (%，负数为易伤)
+    #[serde(default)]
+    pub resistance_percent: i32,
 }
 
 /// 计算怪兽战斗伤害
@@ -247,6 +302,7 @@ pub struct GcBattleResult {
 /// - 攻击方攻击 > 防御方防御: 造成 (ATK - DEF) 伤害
 /// - 攻击方攻击 < 防御方防御: 攻击方受到 (DEF - ATK) 反击伤害
 /// - 攻击方攻击 = 防御方防御: 双方都不受伤
+/// - 造成的伤害再按防御方对攻击方伤害类型的抗性修正 (反击伤害不受影响)
 pub fn gc_calculate_battle_damage(
     attacker: &GcMonster,
     defender: &GcMonster,
@@ -255,25 +311,53 @@ pub fn gc_calculate_battle_damage(
 ) -> GcBattleResult {
     let atk = attacker.effective_atk(attacker_terrain);
     let def = defender.effective_def(defender_terrain);
-    
-    let (damage, counter_damage) = if atk > def {
-        (atk - def, 0)
-    } else if atk < def {
-        (0, def - atk)
-    } else {
-        (0, 0)
-    };
+    let (damage, counter_damage) = gc_exchange_damage(attacker, defender, atk, def);
     
     GcBattleResult {
-        attacker_id: attacker.id.clone(),
-        defender_id: defender.id.clone(),
+        attacker_id: gc_intern(&attacker.id),
+        defender_id: gc_intern(&defender.id),
         attacker_atk: atk,
         defender_def: def,
         damage,
         defender_destroyed: damage >= defender.current_hp,
         counter_damage,
         attacker_destroyed: counter_damage >= attacker.current_hp,
+        damage_type: attacker.damage_type,
+        resistance_percent: defender.resistances().gc_get(attacker.damage_type),
+    }
+}
+
+/// 按有效攻防计算 (伤害, 反击伤害)
+fn gc_exchange_damage(attacker: &GcMonster, defender: &GcMonster, atk: u32, def: u32) -> (u32, u32) {
+    if atk > def {
+        (defender.resistances().gc_apply(atk - def, attacker.damage_type), 0)
+    } else if atk < def {
+        (0, def - atk)
+    } else {
+        (0, 0)
+    }
+}
+
+/// 执行怪兽战斗，只返回 (伤害, 反击伤害)
+/// 
+/// 与 gc_execute_monster_battle 结算相同，但不构造 GcBattleResult (不复制ID)，供自动战斗热路径使用
+pub fn gc_execute_monster_exchange(
+    attacker: &mut GcMonster,
+    defender: &mut GcMonster,
+    attacker_terrain: GcTerrainType,
+    defender_terrain: GcTerrainType,
+) -> (u32, u32) {
+    let atk = attacker.effective_atk(attacker_terrain);
+    let def = defender.effective_def(defender_terrain);
+    let (damage, counter_damage) = gc_exchange_damage(attacker, defender, atk, def);
+    
+    if damage > 0 {
+        defender.take_damage(damage);
+    }
+    if counter_damage > 0 {
+        attacker.take_damage(counter_damage);
     }
+    (damage, counter_damage)
 }
 
 /// 执行怪兽战斗 (修改双方状态)
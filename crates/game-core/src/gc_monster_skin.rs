@@ -0,0 +1,209 @@
+//! 怪兽皮肤 (纯装饰外观)
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! 玩家拥有的怪兽皮肤与装备选择，酒馆购买时按装备的皮肤给怪兽实例打上外观 ID
+//!
+//! ## 规则
+//! - 皮肤只影响外观，不参与合成判断、战斗与价格计算
+//! - 皮肤 ID 为 1-32 个小写字母、数字、`_` 或 `-`，按怪兽模板归属 (同名皮肤在不同模板下互不相干)
+//! - 只能装备已拥有的皮肤；每个模板最多装备一个，未装备时为默认外观
+//! - 购买时 (`GcTavernSeat::gc_buy`) 按座位的皮肤配置给怪兽设置 `skin`；商店里的怪兽没有皮肤
+//! - 合成继承: 合成结果留在哪个单位的位置，就沿用哪个单位的皮肤 (战场最左槽位优先，其次按合成顺序)；
+//!   该单位没有皮肤时取其余单位中第一个有皮肤的
+//! - 皮肤随怪兽序列化 (阵容、座位同步)，对手可见；玩家的拥有记录只发给本人
+//! - 皮肤只由服务器按规则发放: 酒馆对局第一名获得最终阵容中各模板的冠军皮肤 (`champion`)
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::GcMonster;
+
+/// 皮肤 ID 长度上限
+pub const GC_MONSTER_SKIN_ID_MAX_LEN: usize = 32;
+
+/// 酒馆夺冠解锁的皮肤 ID
+pub const GC_CHAMPION_SKIN_ID: &str = "champion";
+
+// =============================================================================
+// 皮肤 ID
+// =============================================================================
+
+/// 校验皮肤 ID
+pub fn gc_validate_monster_skin_id(skin_id: &str) -> Result<(), String> {
+    let valid_char = |c: char| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-';
+    if skin_id.is_empty() || skin_id.len() > GC_MONSTER_SKIN_ID_MAX_LEN || !skin_id.chars().all(valid_char) {
+        return Err(format!(
+            "皮肤 ID 应为 1-{} 个小写字母、数字、'_' 或 '-': {:?}",
+            GC_MONSTER_SKIN_ID_MAX_LEN, skin_id
+        ));
+    }
+    Ok(())
+}
+
+// =============================================================================
+// 拥有记录
+// =============================================================================
+
+/// 皮肤配置 (模板 ID -> 装备的皮肤 ID)
+pub type GcMonsterSkinLoadout = BTreeMap<String, String>;
+
+/// 玩家的怪兽皮肤 (按账号保存)
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcMonsterSkins {
+    /// 模板 ID -> 拥有的皮肤
+    #[serde(default)]
+    pub owned: BTreeMap<String, BTreeSet<String>>,
+    /// 模板 ID -> 装备的皮肤
+    #[serde(default)]
+    pub equipped: GcMonsterSkinLoadout,
+}
+
+impl GcMonsterSkins {
+    /// 是否拥有皮肤
+    pub fn gc_owns(&self, template_id: &str, skin_id: &str) -> bool {
+        self.owned.get(template_id).is_some_and(|skins| skins.contains(skin_id))
+    }
+
+    /// 获得皮肤，返回是否为新获得
+    pub fn gc_grant(&mut self, template_id: &str, skin_id: &str) -> Result<bool, String> {
+        if template_id.trim().is_empty() {
+            return Err("模板 ID 不能为空".to_string());
+        }
+        gc_validate_monster_skin_id(skin_id)?;
+        Ok(self.owned.entry(template_id.to_string()).or_default().insert(skin_id.to_string()))
+    }
+
+    /// 装备皮肤 (None 表示恢复默认外观)
+    pub fn gc_equip(&mut self, template_id: &str, skin_id: Option<&str>) -> Result<(), String> {
+        match skin_id {
+            Some(skin_id) => {
+                if !self.gc_owns(template_id, skin_id) {
+                    return Err(format!("未拥有皮肤: {}/{}", template_id, skin_id));
+                }
+                self.equipped.insert(template_id.to_string(), skin_id.to_string());
+            }
+            None => {
+                self.equipped.remove(template_id);
+            }
+        }
+        Ok(())
+    }
+
+    /// 模板装备的皮肤
+    pub fn gc_equipped(&self, template_id: &str) -> Option<&str> {
+        self.equipped.get(template_id).map(String::as_str)
+    }
+
+    /// 对局使用的皮肤配置 (只含仍拥有的皮肤)
+    pub fn gc_loadout(&self) -> GcMonsterSkinLoadout {
+        self.equipped.iter()
+            .filter(|(template_id, skin_id)| self.gc_owns(template_id, skin_id))
+            .map(|(template_id, skin_id)| (template_id.clone(), skin_id.clone()))
+            .collect()
+    }
+}
+
+// =============================================================================
+// 购买与合成
+// =============================================================================
+
+/// 按皮肤配置设置怪兽外观 (购买时调用)
+pub fn gc_apply_monster_skin(monster: &mut GcMonster, loadout: &GcMonsterSkinLoadout) {
+    monster.skin = loadout.get(&monster.template_id).cloned();
+}
+
+/// 合成结果继承的皮肤
+///
+/// 结果位置的单位 (战场最左槽位，全部在手牌区时为第一个) 优先，其次按顺序取第一个有皮肤的单位
+pub fn gc_merged_skin(monsters: &[GcMonster]) -> Option<String> {
+    let keeper = monsters.iter()
+        .enumerate()
+        .min_by_key(|(i, m)| (m.slot.is_none(), m.slot, *i))
+        .map(|(i, _)| i)?;
+    monsters[keeper].skin.clone()
+        .or_else(|| monsters.iter().find_map(|m| m.skin.clone()))
+}
+
+// =============================================================================
+// 解锁
+// =============================================================================
+
+/// 酒馆夺冠解锁的皮肤: 冠军最终阵容中每个怪兽模板一个冠军皮肤 (模板, 皮肤)
+pub fn gc_champion_skin_unlocks(board: &[Option<GcMonster>]) -> Vec<(String, String)> {
+    let templates: BTreeSet<&str> = board.iter()
+        .flatten()
+        .map(|m| m.template_id.as_str())
+        .collect();
+    templates.into_iter()
+        .map(|template_id| (template_id.to_string(), GC_CHAMPION_SKIN_ID.to_string()))
+        .collect()
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{gc_merge_monsters, GcMonsterAttribute};
+
+    fn slime(id: &str, slot: Option<u8>, skin: Option<&str>) -> GcMonster {
+        let mut monster = GcMonster::new_with_template(id, "slime", "史莱姆", 1, GcMonsterAttribute::Water, 50, 30, 60);
+        monster.slot = slot;
+        monster.skin = skin.map(str::to_string);
+        monster
+    }
+
+    #[test]
+    fn test_grant_and_equip_owned_skins() {
+        let mut skins = GcMonsterSkins::default();
+        assert!(skins.gc_equip("slime", Some("frost")).is_err());
+        assert_eq!(skins.gc_grant("slime", "frost"), Ok(true));
+        assert_eq!(skins.gc_grant("slime", "frost"), Ok(false));
+        assert!(skins.gc_grant("slime", "Frost!").is_err());
+        assert!(skins.gc_grant("", "frost").is_err());
+
+        // 同名皮肤按模板区分
+        assert!(skins.gc_equip("dragon", Some("frost")).is_err());
+        assert_eq!(skins.gc_equip("slime", Some("frost")), Ok(()));
+        assert_eq!(skins.gc_equipped("slime"), Some("frost"));
+
+        // 失去拥有记录的皮肤不进入对局配置
+        skins.equipped.insert("dragon".to_string(), "gold".to_string());
+        assert_eq!(skins.gc_loadout(), GcMonsterSkinLoadout::from([("slime".to_string(), "frost".to_string())]));
+        assert_eq!(skins.gc_equip("slime", None), Ok(()));
+        assert_eq!(skins.gc_equipped("slime"), None);
+    }
+
+    #[test]
+    fn test_merge_keeps_skin_of_kept_unit() {
+        // 战场最左的单位保留位置与皮肤
+        let merged = gc_merged_skin(&[slime("a", None, Some("frost")), slime("b", Some(3), Some("ember")), slime("c", Some(1), None)]);
+        assert_eq!(merged.as_deref(), Some("frost"));
+        let merged = gc_merged_skin(&[slime("a", None, Some("frost")), slime("b", Some(1), Some("ember")), slime("c", Some(3), None)]);
+        assert_eq!(merged.as_deref(), Some("ember"));
+        assert_eq!(gc_merged_skin(&[slime("a", None, None), slime("b", None, None), slime("c", None, None)]), None);
+
+        // 皮肤不影响能否合成与合成后的属性
+        let plain = gc_merge_monsters([slime("a", None, None), slime("b", None, None), slime("c", None, None)]);
+        let skinned = gc_merge_monsters([slime("a", Some(2), Some("frost")), slime("b", None, None), slime("c", None, Some("ember"))]);
+        assert!(plain.success && skinned.success);
+        let (plain, skinned) = (plain.merged_monster.expect("应合成"), skinned.merged_monster.expect("应合成"));
+        assert_eq!(skinned.skin.as_deref(), Some("frost"));
+        assert_eq!((plain.star, plain.effective_max_hp()), (skinned.star, skinned.effective_max_hp()));
+    }
+
+    #[test]
+    fn test_champion_unlocks_one_skin_per_board_template() {
+        let board = [Some(slime("a", Some(0), None)), None, Some(slime("b", Some(2), Some("frost")))];
+        let unlocks = gc_champion_skin_unlocks(&board);
+        assert_eq!(unlocks, vec![("slime".to_string(), GC_CHAMPION_SKIN_ID.to_string())]);
+        assert!(gc_validate_monster_skin_id(GC_CHAMPION_SKIN_ID).is_ok());
+        assert!(gc_champion_skin_unlocks(&[None, None]).is_empty());
+    }
+}
@@ -0,0 +1,279 @@
+//! 新手引导进度 (按模式的首次体验清单)
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! ## 规则
+//! - 进度是一组已完成的标记 (`first_card_played`、`first_merge` ...)，只增不减，随存档槽位保存
+//! - 标记由玩法事件 (`GcOnboardingEvent`) 推进；卡牌对战事件与酒馆操作可直接转换，客户端不必自己解析战斗状态
+//! - 每个模式有固定顺序的清单，`gc_checklist` 给出每一步是否完成与下一步
+//! - 获胜同时完成"完成一局"，酒馆第 1 名同时完成"完成一局酒馆"
+
+use std::collections::BTreeSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{GcBattleEvent, GcBattleState, GcTavernAction, GcTavernSeat};
+
+// =============================================================================
+// 标记与模式
+// =============================================================================
+
+/// 新手引导标记
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GcOnboardingFlag {
+    /// 第一次出牌
+    FirstCardPlayed,
+    /// 第一次在战场部署卡牌
+    FirstCardDeployed,
+    /// 第一次使用英雄技能
+    FirstSkillUsed,
+    /// 完成第一局卡牌对战
+    FirstBattleFinished,
+    /// 赢得第一局卡牌对战
+    FirstBattleWon,
+    /// 第一次在酒馆购买怪兽
+    FirstMonsterBought,
+    /// 第一次部署怪兽到阵容
+    FirstMonsterDeployed,
+    /// 第一次合成升星
+    FirstMerge,
+    /// 完成第一局酒馆
+    FirstTavernFinished,
+    /// 第一次在酒馆获得第 1 名
+    FirstTavernWin,
+}
+
+/// 引导清单所属模式
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum GcOnboardingMode {
+    /// 卡牌对战
+    CardBattle,
+    /// 酒馆
+    Tavern,
+}
+
+impl GcOnboardingMode {
+    /// 全部模式
+    pub const ALL: [Self; 2] = [Self::CardBattle, Self::Tavern];
+
+    /// 清单步骤 (按引导顺序)
+    pub fn gc_steps(&self) -> &'static [GcOnboardingFlag] {
+        match self {
+            Self::CardBattle => &[
+                GcOnboardingFlag::FirstCardPlayed,
+                GcOnboardingFlag::FirstCardDeployed,
+                GcOnboardingFlag::FirstSkillUsed,
+                GcOnboardingFlag::FirstBattleFinished,
+                GcOnboardingFlag::FirstBattleWon,
+            ],
+            Self::Tavern => &[
+                GcOnboardingFlag::FirstMonsterBought,
+                GcOnboardingFlag::FirstMonsterDeployed,
+                GcOnboardingFlag::FirstMerge,
+                GcOnboardingFlag::FirstTavernFinished,
+                GcOnboardingFlag::FirstTavernWin,
+            ],
+        }
+    }
+}
+
+// =============================================================================
+// 玩法事件
+// =============================================================================
+
+/// 推进引导的玩法事件
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum GcOnboardingEvent {
+    /// 出牌
+    CardPlayed,
+    /// 部署卡牌到战场
+    CardDeployed,
+    /// 使用英雄技能
+    SkillUsed,
+    /// 卡牌对战结束
+    BattleFinished { won: bool },
+    /// 酒馆购买怪兽
+    MonsterBought,
+    /// 酒馆部署怪兽
+    MonsterDeployed,
+    /// 合成升星
+    MonstersMerged,
+    /// 酒馆对局结束
+    TavernFinished { placement: u8 },
+}
+
+impl GcOnboardingEvent {
+    /// 从卡牌对战事件转换 (只看该玩家自己的操作)
+    pub fn gc_from_battle_event(event: &GcBattleEvent, player_id: &str) -> Option<Self> {
+        match event {
+            GcBattleEvent::CardPlayed { player_id: actor, .. } if actor == player_id => Some(Self::CardPlayed),
+            GcBattleEvent::CardDeployed { player_id: actor, .. } if actor == player_id => Some(Self::CardDeployed),
+            GcBattleEvent::SkillUsed { player_id: actor, .. } if actor == player_id => Some(Self::SkillUsed),
+            _ => None,
+        }
+    }
+
+    /// 卡牌对战结束 (战斗未结束或玩家不在对局中时为 None)
+    pub fn gc_from_battle_end(state: &GcBattleState, player_id: &str) -> Option<Self> {
+        if !state.gc_is_finished() || state.gc_find_player(player_id).is_none() {
+            return None;
+        }
+        Some(Self::BattleFinished { won: state.gc_winner_ids().iter().any(|id| id == player_id) })
+    }
+
+    /// 从成功执行的酒馆操作转换
+    pub fn gc_from_tavern_action(action: &GcTavernAction) -> Option<Self> {
+        match action {
+            GcTavernAction::Buy { .. } => Some(Self::MonsterBought),
+            GcTavernAction::Deploy { .. } => Some(Self::MonsterDeployed),
+            _ => None,
+        }
+    }
+
+    /// 从酒馆座位推断 (拥有合成过的怪兽、已确定名次)
+    pub fn gc_from_tavern_seat(seat: &GcTavernSeat) -> Vec<Self> {
+        let mut events = Vec::new();
+        let merged = seat.board.player_monsters.iter().flatten()
+            .chain(seat.board.player_back_row.iter().flatten())
+            .chain(seat.player.bench.iter())
+            .any(|m| m.star > 1 || m.golden_level > 0);
+        if merged {
+            events.push(Self::MonstersMerged);
+        }
+        if let Some(placement) = seat.placement {
+            events.push(Self::TavernFinished { placement });
+        }
+        events
+    }
+
+    /// 事件完成的标记
+    fn gc_flags(&self) -> Vec<GcOnboardingFlag> {
+        match *self {
+            Self::CardPlayed => vec![GcOnboardingFlag::FirstCardPlayed],
+            Self::CardDeployed => vec![GcOnboardingFlag::FirstCardDeployed],
+            Self::SkillUsed => vec![GcOnboardingFlag::FirstSkillUsed],
+            Self::BattleFinished { won: false } => vec![GcOnboardingFlag::FirstBattleFinished],
+            Self::BattleFinished { won: true } => vec![GcOnboardingFlag::FirstBattleFinished, GcOnboardingFlag::FirstBattleWon],
+            Self::MonsterBought => vec![GcOnboardingFlag::FirstMonsterBought],
+            Self::MonsterDeployed => vec![GcOnboardingFlag::FirstMonsterDeployed],
+            Self::MonstersMerged => vec![GcOnboardingFlag::FirstMerge],
+            Self::TavernFinished { placement: 1 } => vec![GcOnboardingFlag::FirstTavernFinished, GcOnboardingFlag::FirstTavernWin],
+            Self::TavernFinished { .. } => vec![GcOnboardingFlag::FirstTavernFinished],
+        }
+    }
+}
+
+// =============================================================================
+// 进度
+// =============================================================================
+
+/// 新手引导进度
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcOnboardingProgress {
+    /// 已完成的标记
+    #[serde(default)]
+    pub completed: BTreeSet<GcOnboardingFlag>,
+}
+
+/// 清单中的一步
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcOnboardingStep {
+    /// 标记
+    pub flag: GcOnboardingFlag,
+    /// 是否完成
+    pub done: bool,
+}
+
+/// 单个模式的引导清单
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcOnboardingChecklist {
+    /// 模式
+    pub mode: GcOnboardingMode,
+    /// 步骤 (按引导顺序)
+    pub steps: Vec<GcOnboardingStep>,
+    /// 下一个未完成的步骤 (全部完成时为 None)
+    pub next: Option<GcOnboardingFlag>,
+}
+
+impl GcOnboardingProgress {
+    /// 标记是否已完成
+    pub fn gc_is_done(&self, flag: GcOnboardingFlag) -> bool {
+        self.completed.contains(&flag)
+    }
+
+    /// 记录玩法事件，返回本次新完成的标记
+    pub fn gc_record(&mut self, event: &GcOnboardingEvent) -> Vec<GcOnboardingFlag> {
+        event.gc_flags().into_iter().filter(|flag| self.completed.insert(*flag)).collect()
+    }
+
+    /// 记录多个事件，返回新完成的标记
+    pub fn gc_record_all<'a>(&mut self, events: impl IntoIterator<Item = &'a GcOnboardingEvent>) -> Vec<GcOnboardingFlag> {
+        events.into_iter().flat_map(|event| self.gc_record(event)).collect()
+    }
+
+    /// 模式的引导清单
+    pub fn gc_checklist(&self, mode: GcOnboardingMode) -> GcOnboardingChecklist {
+        let steps: Vec<GcOnboardingStep> = mode.gc_steps().iter()
+            .map(|&flag| GcOnboardingStep { flag, done: self.gc_is_done(flag) })
+            .collect();
+        let next = steps.iter().find(|s| !s.done).map(|s| s.flag);
+        GcOnboardingChecklist { mode, steps, next }
+    }
+
+    /// 全部模式的引导清单
+    pub fn gc_checklists(&self) -> Vec<GcOnboardingChecklist> {
+        GcOnboardingMode::ALL.iter().map(|mode| self.gc_checklist(*mode)).collect()
+    }
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GcBattlePhase, GcPlayer};
+
+    #[test]
+    fn test_battle_events_complete_flags_once() {
+        let mut progress = GcOnboardingProgress::default();
+        let played = GcBattleEvent::CardPlayed {
+            player_id: "p1".to_string(),
+            card_id: "c1".to_string(),
+            target_id: "p2".to_string(),
+        };
+        assert_eq!(GcOnboardingEvent::gc_from_battle_event(&played, "p2"), None);
+        let event = GcOnboardingEvent::gc_from_battle_event(&played, "p1").unwrap();
+        assert_eq!(progress.gc_record(&event), vec![GcOnboardingFlag::FirstCardPlayed]);
+        assert!(progress.gc_record(&event).is_empty());
+
+        let mut state = GcBattleState::gc_new("b1", vec![GcPlayer::gc_new("p1", "玩家1"), GcPlayer::gc_new("p2", "玩家2")]);
+        assert_eq!(GcOnboardingEvent::gc_from_battle_end(&state, "p1"), None);
+        state.phase = GcBattlePhase::Finished;
+        state.winner_id = Some("p1".to_string());
+        let end = GcOnboardingEvent::gc_from_battle_end(&state, "p1").unwrap();
+        assert_eq!(end, GcOnboardingEvent::BattleFinished { won: true });
+        assert_eq!(progress.gc_record(&end).len(), 2);
+    }
+
+    #[test]
+    fn test_checklist_next_step_and_serde() {
+        let mut progress = GcOnboardingProgress::default();
+        progress.gc_record_all(&[GcOnboardingEvent::MonsterBought, GcOnboardingEvent::TavernFinished { placement: 3 }]);
+
+        let tavern = progress.gc_checklist(GcOnboardingMode::Tavern);
+        assert_eq!(tavern.next, Some(GcOnboardingFlag::FirstMonsterDeployed));
+        assert_eq!(tavern.steps.iter().filter(|s| s.done).count(), 2);
+        assert!(!progress.gc_is_done(GcOnboardingFlag::FirstTavernWin));
+        assert_eq!(progress.gc_checklists().len(), GcOnboardingMode::ALL.len());
+
+        let json = serde_json::to_string(&progress).unwrap();
+        assert_eq!(json, r#"{"completed":["first_monster_bought","first_tavern_finished"]}"#);
+        assert_eq!(serde_json::from_str::<GcOnboardingProgress>("{}").unwrap(), GcOnboardingProgress::default());
+    }
+}
@@ -0,0 +1,339 @@
+//! 分页与窗口访问 (观战、历史、低端设备)
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! 对局越长，战斗日志、领域事件与行动记录越大。这里提供按页、按窗口读取的访问器，
+//! 客户端不必一次反序列化完整状态或完整日志
+//!
+//! ## 规则
+//! - `GcPageRequest.offset` 为 None 时取最后 `limit` 条 (最新的在末尾)，否则从 offset 开始取
+//! - `limit` 为 0 时使用默认值 `GC_PAGE_DEFAULT_LIMIT`，超过 `GC_PAGE_MAX_LIMIT` 时截断
+//! - 越界的 offset 返回空页，`total` 始终是完整长度，客户端据此翻页
+//! - 手牌分页遵循牌区隐私: 非本人只能得到数量 (`items` 为空)，本人卡牌的描述按当前属性渲染
+//! - 战斗窗口只包含视野内玩家的战场单位与最近的战斗日志，其余玩家只有概要 (生命值与牌区数量)
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    gc_render_card_descriptions, gc_zone_privacy, GcActionFeed, GcBattleEvent, GcBattlePhase, GcBattleState, GcCard,
+    GcCardTextContext, GcCombatLogEvent, GcEventSourcedBattle, GcPlayerState, GcPublicAction, GcZonePrivacy,
+};
+
+/// 默认每页条数
+pub const GC_PAGE_DEFAULT_LIMIT: usize = 50;
+
+/// 每页条数上限
+pub const GC_PAGE_MAX_LIMIT: usize = 200;
+
+// =============================================================================
+// 分页
+// =============================================================================
+
+/// 分页请求
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcPageRequest {
+    /// 起始位置 (None 表示取最后 limit 条)
+    #[serde(default)]
+    pub offset: Option<usize>,
+    /// 条数 (0 表示默认值)
+    #[serde(default)]
+    pub limit: usize,
+}
+
+impl GcPageRequest {
+    /// 最后 limit 条
+    pub fn gc_latest(limit: usize) -> Self {
+        Self { offset: None, limit }
+    }
+
+    /// 从 offset 开始的 limit 条
+    pub fn gc_at(offset: usize, limit: usize) -> Self {
+        Self { offset: Some(offset), limit }
+    }
+
+    /// 实际条数
+    pub fn gc_limit(&self) -> usize {
+        match self.limit {
+            0 => GC_PAGE_DEFAULT_LIMIT,
+            limit => limit.min(GC_PAGE_MAX_LIMIT),
+        }
+    }
+}
+
+/// 一页数据
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GcPage<T> {
+    /// 本页条目
+    pub items: Vec<T>,
+    /// 本页第一条在完整列表中的位置
+    pub offset: usize,
+    /// 完整列表长度
+    pub total: usize,
+}
+
+impl<T: Clone> GcPage<T> {
+    /// 从完整列表截取一页
+    pub fn gc_from_slice(items: &[T], request: GcPageRequest) -> Self {
+        let limit = request.gc_limit();
+        let offset = request.offset.unwrap_or_else(|| items.len().saturating_sub(limit)).min(items.len());
+        let end = (offset + limit).min(items.len());
+        Self { items: items[offset..end].to_vec(), offset, total: items.len() }
+    }
+}
+
+impl<T> GcPage<T> {
+    /// 之后是否还有条目
+    pub fn gc_has_more(&self) -> bool {
+        self.offset + self.items.len() < self.total
+    }
+
+    /// 之前是否还有条目
+    pub fn gc_has_previous(&self) -> bool {
+        self.offset > 0
+    }
+}
+
+/// 战斗日志的一页
+pub fn gc_combat_log_page(state: &GcBattleState, request: GcPageRequest) -> GcPage<GcCombatLogEvent> {
+    GcPage::gc_from_slice(&state.combat_log, request)
+}
+
+/// 领域事件的一页 (事件序号即 offset + 下标)
+pub fn gc_battle_event_page(battle: &GcEventSourcedBattle, request: GcPageRequest) -> GcPage<GcBattleEvent> {
+    GcPage::gc_from_slice(battle.gc_events(), request)
+}
+
+/// 公开行动记录的一页
+pub fn gc_action_feed_page(feed: &GcActionFeed, request: GcPageRequest) -> GcPage<GcPublicAction> {
+    GcPage::gc_from_slice(feed.gc_entries(), request)
+}
+
+/// 手牌的一页 (viewer_id 为 None 表示观战者；玩家不存在时返回 None)
+///
+/// 非本人查看时 `items` 为空，`total` 为手牌数量
+pub fn gc_hand_page(
+    state: &GcBattleState,
+    viewer_id: Option<&str>,
+    owner_id: &str,
+    request: GcPageRequest,
+) -> Option<GcPage<GcCard>> {
+    let player = state.gc_find_player(owner_id)?;
+    if gc_zone_privacy(viewer_id, owner_id) == GcZonePrivacy::Opponent {
+        return Some(GcPage { items: Vec::new(), offset: 0, total: player.hand.len() });
+    }
+    let mut page = GcPage::gc_from_slice(&player.hand, request);
+    gc_render_card_descriptions(&mut page.items, &GcCardTextContext::gc_for_player(player));
+    Some(page)
+}
+
+// =============================================================================
+// 战斗窗口
+// =============================================================================
+
+/// 战斗窗口请求
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcBattleWindowRequest {
+    /// 视野内的玩家 (为空表示全部)
+    #[serde(default)]
+    pub player_ids: Vec<String>,
+    /// 附带最近多少条战斗日志 (0 表示不附带)
+    #[serde(default)]
+    pub log_tail: usize,
+}
+
+/// 窗口中的玩家概要
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcWindowPlayer {
+    /// 玩家 ID
+    pub player_id: String,
+    /// 名称
+    pub name: String,
+    /// 队伍
+    pub team: Option<u32>,
+    /// 状态
+    pub state: GcPlayerState,
+    /// 生命值
+    pub hp: u32,
+    /// 最大生命值
+    pub max_hp: u32,
+    /// 手牌数量
+    pub hand_count: usize,
+    /// 牌库数量
+    pub deck_count: usize,
+    /// 弃牌堆数量
+    pub discard_count: usize,
+    /// 是否在视野内 (视野内的玩家附带战场单位)
+    pub in_view: bool,
+}
+
+/// 窗口中的战场单位
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GcWindowUnit {
+    /// 所属玩家 ID
+    pub owner_id: String,
+    /// 槽位索引
+    pub slot_index: usize,
+    /// 卡牌
+    pub card: GcCard,
+    /// 剩余生命值
+    pub remaining_hp: u32,
+    /// 本回合能否攻击
+    pub can_attack: bool,
+}
+
+/// 战斗窗口 (观战与低端设备使用的精简状态)
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GcBattleWindow {
+    /// 战斗 ID
+    pub battle_id: String,
+    /// 当前回合数
+    pub turn: u32,
+    /// 战斗阶段
+    pub phase: GcBattlePhase,
+    /// 当前行动玩家
+    pub current_player_id: Option<String>,
+    /// 获胜方 (战斗结束后)
+    pub winner_ids: Vec<String>,
+    /// 全部玩家的概要
+    pub players: Vec<GcWindowPlayer>,
+    /// 视野内玩家的战场单位
+    pub units: Vec<GcWindowUnit>,
+    /// 最近的战斗日志
+    pub recent_log: GcPage<GcCombatLogEvent>,
+}
+
+/// 生成战斗窗口
+pub fn gc_battle_window(state: &GcBattleState, request: &GcBattleWindowRequest) -> GcBattleWindow {
+    let in_view = |id: &str| request.player_ids.is_empty() || request.player_ids.iter().any(|p| p == id);
+    let players = state.players.iter()
+        .map(|p| GcWindowPlayer {
+            player_id: p.id.clone(),
+            name: p.name.clone(),
+            team: p.team,
+            state: p.state.clone(),
+            hp: p.stats.hp,
+            max_hp: p.stats.max_hp,
+            hand_count: p.hand.len(),
+            deck_count: p.deck.len(),
+            discard_count: p.discard.len(),
+            in_view: in_view(&p.id),
+        })
+        .collect();
+    let units = state.players.iter()
+        .filter(|p| in_view(&p.id))
+        .flat_map(|p| {
+            p.battlefield.slots.iter().filter_map(move |slot| {
+                Some(GcWindowUnit {
+                    owner_id: p.id.clone(),
+                    slot_index: slot.index,
+                    card: slot.card.clone()?,
+                    remaining_hp: slot.remaining_hp,
+                    can_attack: slot.can_attack,
+                })
+            })
+        })
+        .collect();
+    let recent_log = if request.log_tail == 0 {
+        GcPage { items: Vec::new(), offset: state.combat_log.len(), total: state.combat_log.len() }
+    } else {
+        gc_combat_log_page(state, GcPageRequest::gc_latest(request.log_tail))
+    };
+
+    GcBattleWindow {
+        battle_id: state.id.clone(),
+        turn: state.turn,
+        phase: state.phase.clone(),
+        current_player_id: state.gc_current_player_id().map(str::to_string),
+        winner_ids: if state.gc_is_finished() { state.gc_winner_ids() } else { Vec::new() },
+        players,
+        units,
+        recent_log,
+    }
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GcPlayer;
+
+    fn create_battle() -> GcBattleState {
+        let mut p1 = GcPlayer::gc_new("p1", "玩家1");
+        for i in 0..12 {
+            p1.hand.push(GcCard::gc_new_attack(&format!("h{}", i), "轻击", 1, 5));
+        }
+        let mut p2 = GcPlayer::gc_new("p2", "玩家2");
+        p2.hand.push(GcCard::gc_new_defense("d1", "格挡", 1, 5));
+        if let Some(slot) = p2.battlefield.gc_get_slot_mut(1) {
+            slot.gc_deploy(GcCard::gc_new_attack("wolf", "野狼", 1, 4));
+        }
+        let mut battle = GcBattleState::gc_new("b1", vec![p1, p2]);
+        for turn in 1..=30 {
+            battle.combat_log.push(GcCombatLogEvent::ChannelTick { turn, action_id: "a".to_string(), remaining_turns: 1 });
+        }
+        battle
+    }
+
+    #[test]
+    fn test_page_requests_clamp_and_default() {
+        let items: Vec<u32> = (0..10).collect();
+        let latest = GcPage::gc_from_slice(&items, GcPageRequest::gc_latest(3));
+        assert_eq!((latest.items.clone(), latest.offset, latest.total), (vec![7, 8, 9], 7, 10));
+        assert!(latest.gc_has_previous() && !latest.gc_has_more());
+
+        let first = GcPage::gc_from_slice(&items, GcPageRequest::gc_at(0, 4));
+        assert_eq!(first.items, vec![0, 1, 2, 3]);
+        assert!(first.gc_has_more() && !first.gc_has_previous());
+
+        let beyond = GcPage::gc_from_slice(&items, GcPageRequest::gc_at(50, 4));
+        assert!(beyond.items.is_empty());
+        assert_eq!((beyond.offset, beyond.total), (10, 10));
+
+        assert_eq!(GcPageRequest::default().gc_limit(), GC_PAGE_DEFAULT_LIMIT);
+        assert_eq!(GcPageRequest::gc_latest(10_000).gc_limit(), GC_PAGE_MAX_LIMIT);
+        let short = GcPage::gc_from_slice(&items, GcPageRequest::gc_latest(50));
+        assert_eq!((short.items.len(), short.offset), (10, 0));
+    }
+
+    #[test]
+    fn test_hand_page_respects_privacy() {
+        let battle = create_battle();
+        let own = gc_hand_page(&battle, Some("p1"), "p1", GcPageRequest::gc_at(10, 5)).expect("玩家存在");
+        assert_eq!(own.items.iter().map(|c| c.id.as_str()).collect::<Vec<_>>(), vec!["h10", "h11"]);
+        assert_eq!(own.total, 12);
+
+        let hidden = gc_hand_page(&battle, Some("p2"), "p1", GcPageRequest::gc_at(0, 5)).expect("玩家存在");
+        assert!(hidden.items.is_empty());
+        assert_eq!(hidden.total, 12);
+        assert!(gc_hand_page(&battle, None, "p1", GcPageRequest::default()).is_some_and(|p| p.items.is_empty()));
+        assert!(gc_hand_page(&battle, None, "nobody", GcPageRequest::default()).is_none());
+    }
+
+    #[test]
+    fn test_battle_window_limits_units_and_log() {
+        let battle = create_battle();
+        let window = gc_battle_window(&battle, &GcBattleWindowRequest { player_ids: vec!["p1".to_string()], log_tail: 5 });
+        assert_eq!(window.players.len(), 2);
+        assert!(window.players[0].in_view && !window.players[1].in_view);
+        assert_eq!(window.players[0].hand_count, 12);
+        assert!(window.units.is_empty());
+        assert_eq!((window.recent_log.items.len(), window.recent_log.offset, window.recent_log.total), (5, 25, 30));
+        assert_eq!(window.current_player_id.as_deref(), Some("p1"));
+
+        let all = gc_battle_window(&battle, &GcBattleWindowRequest::default());
+        assert_eq!(all.units.len(), 1);
+        assert_eq!((all.units[0].owner_id.as_str(), all.units[0].slot_index, all.units[0].card.id.as_str()), ("p2", 1, "wolf"));
+        assert!(all.recent_log.items.is_empty());
+        assert_eq!(all.recent_log.total, 30);
+
+        // 窗口远小于完整状态
+        let full = serde_json::to_string(&battle).unwrap_or_default().len();
+        let compact = serde_json::to_string(&window).unwrap_or_default().len();
+        assert!(compact * 2 < full);
+    }
+}
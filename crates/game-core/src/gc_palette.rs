@@ -0,0 +1,95 @@
+//! 色盲友好调色板
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! ## 规则
+//! - 颜色辅助函数 (`GcTerrainType::color`、`GcMonsterAttribute::color`) 按调色板返回颜色
+//! - 色盲调色板基于 Okabe-Ito 配色，红绿色盲下仍能区分；第一型 (红色弱) 避免依赖暗红色
+//! - 颜色之外另有图案 ID (`pattern`)，界面可叠加纹理/图标，不只靠颜色区分
+
+use serde::{Deserialize, Serialize};
+
+/// 调色板
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum GcColorPalette {
+    /// 标准
+    #[default]
+    Standard,
+    /// 绿色盲 (第二型)
+    Deuteranopia,
+    /// 红色盲 (第一型)
+    Protanopia,
+}
+
+impl GcColorPalette {
+    /// 全部调色板
+    pub const ALL: [GcColorPalette; 3] = [Self::Standard, Self::Deuteranopia, Self::Protanopia];
+
+    /// 标识 (小写，前端设置使用)
+    pub fn gc_id(&self) -> &'static str {
+        match self {
+            Self::Standard => "standard",
+            Self::Deuteranopia => "deuteranopia",
+            Self::Protanopia => "protanopia",
+        }
+    }
+
+    /// 显示名称
+    pub fn gc_name(&self) -> &'static str {
+        match self {
+            Self::Standard => "标准",
+            Self::Deuteranopia => "绿色盲",
+            Self::Protanopia => "红色盲",
+        }
+    }
+
+    /// 由标识解析 (不区分大小写，未知时为标准调色板)
+    pub fn gc_from_id(id: &str) -> Self {
+        Self::ALL
+            .into_iter()
+            .find(|palette| palette.gc_id().eq_ignore_ascii_case(id))
+            .unwrap_or_default()
+    }
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GcMonsterAttribute, GcTerrainType};
+
+    #[test]
+    fn test_palette_ids_round_trip() {
+        for palette in GcColorPalette::ALL {
+            assert_eq!(GcColorPalette::gc_from_id(palette.gc_id()), palette);
+        }
+        assert_eq!(GcColorPalette::gc_from_id("Protanopia"), GcColorPalette::Protanopia);
+        assert_eq!(GcColorPalette::gc_from_id("unknown"), GcColorPalette::Standard);
+    }
+
+    #[test]
+    fn test_palettes_keep_colors_and_patterns_distinct() {
+        for palette in GcColorPalette::ALL {
+            let terrains: Vec<_> = GcTerrainType::all().iter().map(|t| t.color(palette)).collect();
+            for (i, color) in terrains.iter().enumerate() {
+                assert!(!terrains[i + 1..].contains(color), "{:?} 地形颜色重复", palette);
+            }
+        }
+        let patterns: Vec<_> = GcTerrainType::all().iter().map(|t| t.pattern()).collect();
+        for (i, pattern) in patterns.iter().enumerate() {
+            assert!(!patterns[i + 1..].contains(pattern));
+        }
+
+        // 红色盲调色板不使用暗红色表示火
+        assert_ne!(
+            GcMonsterAttribute::Fire.color(GcColorPalette::Protanopia),
+            GcMonsterAttribute::Fire.color(GcColorPalette::Deuteranopia),
+        );
+        assert_eq!(GcMonsterAttribute::Fire.color(GcColorPalette::Standard), (255, 69, 0, 255));
+    }
+}
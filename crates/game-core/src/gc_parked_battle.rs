@@ -0,0 +1,171 @@
+//! 暂存对局 (休闲对局的"稍后继续")
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! ## 暂存规则
+//! - 服务器在每个回合边界 (开局、结束回合、系列赛下一局开始) 保存对局快照
+//! - 玩家全部离开后房间关闭，快照保留；参与者可凭房间 ID 恢复，回到最近一次存档的回合
+//! - 一局结束即删除快照 (系列赛下一局开始时重新存档)
+//! - 过期策略: 距最后一次存档超过 `idle_ttl_secs`，或距首次存档超过 `max_age_secs`，视为被放弃
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{GcBattleState, GcDeck, GcEventSourcedBattle, GcGuildBuffs, GcSeries};
+
+/// 默认闲置过期时间 (3 天)
+pub const GC_PARK_IDLE_TTL_SECS: u64 = 3 * 24 * 3600;
+
+/// 默认最长保留时间 (14 天)
+pub const GC_PARK_MAX_AGE_SECS: u64 = 14 * 24 * 3600;
+
+// =============================================================================
+// 过期策略
+// =============================================================================
+
+/// 暂存对局过期策略
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcParkPolicy {
+    /// 距最后一次存档的最长闲置时间 (秒)
+    pub idle_ttl_secs: u64,
+    /// 距首次存档的最长保留时间 (秒)
+    pub max_age_secs: u64,
+}
+
+impl Default for GcParkPolicy {
+    fn default() -> Self {
+        Self {
+            idle_ttl_secs: GC_PARK_IDLE_TTL_SECS,
+            max_age_secs: GC_PARK_MAX_AGE_SECS,
+        }
+    }
+}
+
+impl GcParkPolicy {
+    /// 过期时间 (Unix 秒)
+    pub fn gc_expires_at(&self, parked: &GcParkedBattle) -> u64 {
+        parked.saved_at.saturating_add(self.idle_ttl_secs)
+            .min(parked.created_at.saturating_add(self.max_age_secs))
+    }
+
+    /// 是否已过期
+    pub fn gc_is_expired(&self, parked: &GcParkedBattle, now: u64) -> bool {
+        now >= self.gc_expires_at(parked)
+    }
+}
+
+// =============================================================================
+// 对局快照
+// =============================================================================
+
+/// 房间玩家 (恢复时显示名称)
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcParkedPlayer {
+    /// 玩家 ID
+    pub id: String,
+    /// 玩家名称
+    pub name: String,
+}
+
+/// 暂存对局 (回合边界的房间快照)
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GcParkedBattle {
+    /// 房间 ID (恢复时沿用)
+    pub room_id: String,
+    /// 房间名称
+    pub room_name: String,
+    /// 房主 ID
+    pub owner_id: String,
+    /// 对局参与者
+    pub players: Vec<GcParkedPlayer>,
+    /// 战斗状态
+    pub battle: GcBattleState,
+    /// 系列赛
+    #[serde(default)]
+    pub series: Option<GcSeries>,
+    /// 玩家卡组 (系列赛后续对局使用)
+    #[serde(default)]
+    pub decks: HashMap<String, GcDeck>,
+    /// 玩家组织加成
+    #[serde(default)]
+    pub guild_buffs: HashMap<String, GcGuildBuffs>,
+    /// 首次存档时间 (Unix 秒)
+    pub created_at: u64,
+    /// 最后存档时间 (Unix 秒)
+    pub saved_at: u64,
+}
+
+impl GcParkedBattle {
+    /// 玩家是否为对局参与者
+    pub fn gc_has_player(&self, player_id: &str) -> bool {
+        self.players.iter().any(|p| p.id == player_id)
+    }
+
+    /// 参与者 ID 列表
+    pub fn gc_player_ids(&self) -> Vec<String> {
+        self.players.iter().map(|p| p.id.clone()).collect()
+    }
+
+    /// 恢复战斗 (快照作为事件溯源的初始状态)
+    pub fn gc_restore_battle(&self) -> GcEventSourcedBattle {
+        GcEventSourcedBattle::gc_from_state(self.battle.clone())
+    }
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GcPlayer;
+
+    fn create_parked(created_at: u64, saved_at: u64) -> GcParkedBattle {
+        let battle = GcBattleState::gc_new("b1", vec![GcPlayer::gc_new("p1", "玩家1"), GcPlayer::gc_new("p2", "玩家2")]);
+        GcParkedBattle {
+            room_id: "r1".to_string(),
+            room_name: "休闲".to_string(),
+            owner_id: "p1".to_string(),
+            players: battle.players.iter()
+                .map(|p| GcParkedPlayer { id: p.id.clone(), name: p.name.clone() })
+                .collect(),
+            battle,
+            series: None,
+            decks: HashMap::new(),
+            guild_buffs: HashMap::new(),
+            created_at,
+            saved_at,
+        }
+    }
+
+    #[test]
+    fn test_park_expiry_policy() {
+        let policy = GcParkPolicy { idle_ttl_secs: 100, max_age_secs: 1000 };
+        let parked = create_parked(0, 50);
+        assert_eq!(policy.gc_expires_at(&parked), 150);
+        assert!(!policy.gc_is_expired(&parked, 149));
+        assert!(policy.gc_is_expired(&parked, 150));
+
+        // 一直有人行动也会在最长保留时间后过期
+        let old = create_parked(0, 990);
+        assert_eq!(policy.gc_expires_at(&old), 1000);
+    }
+
+    #[test]
+    fn test_restore_parked_battle() {
+        let mut parked = create_parked(10, 10);
+        parked.battle.turn = 7;
+        assert!(parked.gc_has_player("p2"));
+        assert!(!parked.gc_has_player("p3"));
+
+        let json = serde_json::to_string(&parked).unwrap_or_default();
+        let loaded: GcParkedBattle = serde_json::from_str(&json).unwrap_or_else(|_| create_parked(0, 0));
+        let battle = loaded.gc_restore_battle();
+        assert_eq!(battle.gc_state().turn, 7);
+        assert_eq!(loaded.gc_player_ids(), vec!["p1".to_string(), "p2".to_string()]);
+    }
+}
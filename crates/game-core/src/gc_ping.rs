@@ -0,0 +1,184 @@
+//! 组队标记与意图 (Ping)
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! ## 规则
+//! - 标记只能指向战场槽位或玩家，附带预设意图 (集火/保护/小心/注意)，不含自由文本
+//! - 只转发给队友 (发送者除外)；没有队友 (决斗、自由混战) 时拒绝
+//! - 标记的对象必须存在: 玩家可行动，槽位在范围内且有卡牌
+//! - 限流: 每名玩家在 `GC_PING_WINDOW_SECS` 秒内最多 `GC_PING_LIMIT` 次，超出的标记直接丢弃
+//! - 标记不改变战斗状态，也不进入事件日志
+
+use serde::{Deserialize, Serialize};
+
+use crate::{GcBattleState, GcError, GcPlayerId};
+
+// =============================================================================
+// 常量
+// =============================================================================
+
+/// 限流窗口内最多的标记次数
+pub const GC_PING_LIMIT: usize = 3;
+
+/// 限流窗口 (秒)
+pub const GC_PING_WINDOW_SECS: u64 = 5;
+
+// =============================================================================
+// 标记
+// =============================================================================
+
+/// 标记意图
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GcPingIntent {
+    /// 集火 (建议攻击该目标)
+    Attack,
+    /// 保护 (建议支援该目标)
+    Defend,
+    /// 小心 (危险)
+    Caution,
+    /// 注意 (一般提示)
+    Look,
+}
+
+/// 标记对象
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind")]
+pub enum GcPingTarget {
+    /// 玩家 (建议目标)
+    Player { player_id: GcPlayerId },
+    /// 某名玩家的战场槽位
+    BattlefieldSlot { player_id: GcPlayerId, slot: usize },
+}
+
+/// 转发给队友的标记
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcPing {
+    /// 发送者
+    pub sender_id: GcPlayerId,
+    /// 标记对象
+    pub target: GcPingTarget,
+    /// 意图
+    pub intent: GcPingIntent,
+    /// 发送时的回合
+    pub turn: u32,
+}
+
+/// 校验标记并生成转发内容
+pub fn gc_create_ping(
+    state: &GcBattleState,
+    sender_id: &str,
+    target: GcPingTarget,
+    intent: GcPingIntent,
+) -> Result<GcPing, GcError> {
+    if state.gc_is_finished() {
+        return Err(GcError::GcBattleEnded);
+    }
+    state.gc_find_player(sender_id).ok_or(GcError::GcPlayerNotFound)?;
+    if gc_ping_recipients(state, sender_id).is_empty() {
+        return Err(GcError::GcInvalidAction("没有队友可以接收标记".to_string()));
+    }
+
+    match &target {
+        GcPingTarget::Player { player_id } => {
+            let player = state.gc_find_player(player_id).ok_or(GcError::GcInvalidTarget)?;
+            if !player.gc_can_act() {
+                return Err(GcError::GcInvalidTarget);
+            }
+        }
+        GcPingTarget::BattlefieldSlot { player_id, slot } => {
+            let player = state.gc_find_player(player_id).ok_or(GcError::GcInvalidTarget)?;
+            let slot = player.battlefield.gc_get_slot(*slot).ok_or(GcError::GcInvalidSlot)?;
+            if slot.gc_is_empty() {
+                return Err(GcError::GcInvalidTarget);
+            }
+        }
+    }
+
+    Ok(GcPing { sender_id: sender_id.to_string(), target, intent, turn: state.turn })
+}
+
+/// 标记的接收者: 发送者的队友 (不含发送者)
+pub fn gc_ping_recipients(state: &GcBattleState, sender_id: &str) -> Vec<GcPlayerId> {
+    state.players.iter()
+        .filter(|p| p.id != sender_id && state.gc_are_allies(sender_id, &p.id))
+        .map(|p| p.id.clone())
+        .collect()
+}
+
+// =============================================================================
+// 限流
+// =============================================================================
+
+/// 单名玩家的标记限流 (滑动窗口)
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GcPingLimiter {
+    /// 窗口内的发送时间 (Unix 秒)
+    sent_at: Vec<u64>,
+}
+
+impl GcPingLimiter {
+    /// 尝试发送一次标记，超出限额时返回 false
+    pub fn gc_try_acquire(&mut self, now: u64) -> bool {
+        self.sent_at.retain(|&at| now.saturating_sub(at) < GC_PING_WINDOW_SECS);
+        if self.sent_at.len() >= GC_PING_LIMIT {
+            return false;
+        }
+        self.sent_at.push(now);
+        true
+    }
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{gc_arrange_seats, GcBattleFormat, GcBattlePhase, GcCard, GcPlayer};
+
+    fn battle(format: GcBattleFormat, count: usize) -> GcBattleState {
+        let players = (0..count).map(|i| GcPlayer::gc_new(format!("p{}", i), format!("玩家{}", i))).collect();
+        let mut state = GcBattleState::gc_new("b1", gc_arrange_seats(format, players).unwrap());
+        state.phase = GcBattlePhase::Playing;
+        state
+    }
+
+    #[test]
+    fn test_ping_targets_must_exist_and_reach_teammates() {
+        let mut state = battle(GcBattleFormat::TwoVsTwo, 4);
+        assert_eq!(gc_ping_recipients(&state, "p0"), vec!["p2".to_string()]);
+
+        let player = GcPingTarget::Player { player_id: "p1".to_string() };
+        let ping = gc_create_ping(&state, "p0", player, GcPingIntent::Attack).unwrap();
+        assert_eq!((ping.sender_id.as_str(), ping.turn), ("p0", 1));
+
+        let missing = GcPingTarget::Player { player_id: "nobody".to_string() };
+        assert!(matches!(gc_create_ping(&state, "p0", missing, GcPingIntent::Look), Err(GcError::GcInvalidTarget)));
+
+        // 空槽位与越界槽位无效，有卡牌的槽位可以标记
+        let slot = |slot| GcPingTarget::BattlefieldSlot { player_id: "p1".to_string(), slot };
+        assert!(matches!(gc_create_ping(&state, "p0", slot(0), GcPingIntent::Caution), Err(GcError::GcInvalidTarget)));
+        assert!(matches!(gc_create_ping(&state, "p0", slot(99), GcPingIntent::Caution), Err(GcError::GcInvalidSlot)));
+        state.players[1].hand.push(GcCard::gc_new_attack("c1", "打击", 1, 5));
+        state.players[1].gc_deploy_to_battlefield("c1", 0).unwrap();
+        assert!(gc_create_ping(&state, "p0", slot(0), GcPingIntent::Caution).is_ok());
+
+        // 没有队友时拒绝
+        let duel = battle(GcBattleFormat::Duel, 2);
+        let target = GcPingTarget::Player { player_id: "p1".to_string() };
+        assert!(gc_create_ping(&duel, "p0", target, GcPingIntent::Attack).is_err());
+    }
+
+    #[test]
+    fn test_ping_rate_limit_window() {
+        let mut limiter = GcPingLimiter::default();
+        for _ in 0..GC_PING_LIMIT {
+            assert!(limiter.gc_try_acquire(100));
+        }
+        assert!(!limiter.gc_try_acquire(101));
+        assert!(limiter.gc_try_acquire(100 + GC_PING_WINDOW_SECS));
+    }
+}
@@ -8,10 +8,16 @@ use serde::{Deserialize, Serialize};
 use crate::{
     GcCard, GcPlayerId, GcConfig, GcBattlefield,
     GcProfessionType, GcPlayerTalents, GcInventory,
-    GcBaseStats, GcCombatStats, GcProfession,
+    GcBaseStats, GcCombatStats, GcProfession, GcResistances,
     GcMonster, GcEconomy, GcGraveyard, GcGameMode,
+    GcCompanionCollection, GcHeroSkillSet, GcError, GcGuildBuffs,
+    GC_DEFAULT_BENCH_CAPACITY, GC_MAX_BENCH_CAPACITY,
 };
 
+fn gc_default_bench_capacity() -> usize {
+    GC_DEFAULT_BENCH_CAPACITY
+}
+
 // =============================================================================
 // 玩家状态
 // =============================================================================
@@ -40,7 +46,7 @@ impl Default for GcPlayerState {
 // =============================================================================
 
 /// 玩家战斗属性
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct GcPlayerStats {
     /// 当前生命值
     pub hp: u32,
@@ -92,8 +98,8 @@ impl GcPlayerStats {
     
     /// 恢复生命
     pub fn gc_heal(&mut self, amount: u32) -> u32 {
-        let actual = amount.min(self.max_hp - self.hp);
-        self.hp = (self.hp + amount).min(self.max_hp);
+        let actual = amount.min(self.max_hp.saturating_sub(self.hp));
+        self.hp = self.hp.saturating_add(amount).min(self.max_hp);
         actual
     }
     
@@ -128,7 +134,7 @@ impl GcPlayerStats {
 // =============================================================================
 
 /// 玩家数据
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct GcPlayer {
     /// 唯一 ID
     pub id: GcPlayerId,
@@ -162,9 +168,13 @@ pub struct GcPlayer {
     /// 金币经济 (酒馆模式)
     pub economy: GcEconomy,
     
-    /// 手牌区/备战席 (酒馆模式, 怪兽列表, 无上限)
+    /// 手牌区/备战席 (酒馆模式, 怪兽列表)
     pub bench: Vec<GcMonster>,
     
+    /// 手牌区容量
+    #[serde(default = "gc_default_bench_capacity")]
+    pub bench_capacity: usize,
+    
     /// 墓地 (阵亡怪兽)
     pub graveyard: GcGraveyard,
 
@@ -181,6 +191,26 @@ pub struct GcPlayer {
     
     /// 背包与装备
     pub inventory: Option<GcInventory>,
+    
+    /// 伙伴 (提供战斗光环与闲置加成)
+    #[serde(default)]
+    pub companions: GcCompanionCollection,
+    
+    /// 英雄技能栏 (旧存档缺省时与 `gc_new` 一致，为通用技能栏)
+    #[serde(default = "GcHeroSkillSet::gc_basic")]
+    pub hero_skills: GcHeroSkillSet,
+
+    /// 组织升级加成
+    #[serde(default)]
+    pub guild_buffs: GcGuildBuffs,
+
+    /// 伤害类型抗性 (来自装备)
+    #[serde(default)]
+    pub resistances: GcResistances,
+
+    /// 队伍 (2v2 组队时同队为友方；None 表示单人，自由混战与决斗)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub team: Option<u32>,
 }
 
 impl GcPlayer {
@@ -198,11 +228,17 @@ impl GcPlayer {
             game_mode: GcGameMode::default(),
             economy: GcEconomy::default(),
             bench: Vec::new(),
+            bench_capacity: GC_DEFAULT_BENCH_CAPACITY,
             graveyard: GcGraveyard::default(),
             level: 1,
             profession: None,
             talents: None,
             inventory: None,
+            companions: GcCompanionCollection::default(),
+            hero_skills: GcHeroSkillSet::gc_basic(),
+            guild_buffs: GcGuildBuffs::default(),
+            resistances: GcResistances::default(),
+            team: None,
         }
     }
     
@@ -246,11 +282,37 @@ impl GcPlayer {
     pub fn gc_bench_count(&self) -> usize {
         self.bench.len()
     }
+    
+    /// 手牌区是否已满
+    pub fn gc_is_bench_full(&self) -> bool {
+        self.bench.len() >= self.bench_capacity
+    }
+    
+    /// 手牌区剩余空位
+    pub fn gc_bench_free_slots(&self) -> usize {
+        self.bench_capacity.saturating_sub(self.bench.len())
+    }
+    
+    /// 添加怪兽到手牌区 (已满时拒绝)
+    pub fn gc_try_add_to_bench(&mut self, monster: GcMonster) -> Result<(), GcError> {
+        if self.gc_is_bench_full() {
+            return Err(GcError::GcBenchFull);
+        }
+        self.bench.push(monster);
+        Ok(())
+    }
+    
+    /// 扩容手牌区 (不超过上限)，返回扩容后容量
+    pub fn gc_expand_bench(&mut self, amount: usize) -> usize {
+        self.bench_capacity = (self.bench_capacity + amount).min(GC_MAX_BENCH_CAPACITY);
+        self.bench_capacity
+    }
 
     /// 初始化 RPG 系统 (职业、背包)
     pub fn gc_init_rpg(&mut self, profession: GcProfessionType) {
         self.profession = Some(profession);
         self.inventory = Some(GcInventory::gc_new(&self.id, 20));
+        self.hero_skills = GcHeroSkillSet::gc_for_profession(profession);
         // 天赋系统初始化比较复杂，通常单独调用
         self.gc_update_rpg_stats();
     }
@@ -284,6 +346,14 @@ impl GcPlayer {
         self.stats.max_hp = combat_stats.max_hp as u32;
         self.stats.attack = combat_stats.physical_attack.max(combat_stats.magic_attack) as u32;
         self.stats.defense = combat_stats.physical_defense.max(combat_stats.magic_defense) as u32;
+        self.resistances = combat_stats.resistances.clone();
+        
+        // 6. 伙伴战斗光环与组织升级加成分属不同分桶，一次相乘
+        let aura = self.companions.active_aura();
+        let buffs = &self.guild_buffs;
+        self.stats.max_hp = aura.hp_stack().gc_merge(&buffs.hp_stack()).gc_apply(self.stats.max_hp);
+        self.stats.attack = aura.atk_stack().gc_merge(&buffs.atk_stack()).gc_apply(self.stats.attack);
+        self.stats.defense = aura.def_stack().gc_merge(&buffs.def_stack()).gc_apply(self.stats.defense);
         
         // 保持当前生命值不超过最大值
         if self.stats.hp > self.stats.max_hp {
@@ -296,6 +366,30 @@ impl GcPlayer {
         }
     }
     
+    /// 设置组织加成 (有职业时重新合成属性，否则以默认属性为基础)
+    pub fn gc_set_guild_buffs(&mut self, buffs: GcGuildBuffs) {
+        self.guild_buffs = buffs;
+        if self.profession.is_some() {
+            self.gc_update_rpg_stats();
+            return;
+        }
+        let full_hp = self.stats.hp == self.stats.max_hp;
+        self.stats.max_hp = GcConfig::DEFAULT_HP;
+        self.stats.attack = GcConfig::DEFAULT_ATTACK;
+        self.stats.defense = GcConfig::DEFAULT_DEFENSE;
+        self.gc_apply_guild_buffs();
+        if full_hp || self.stats.hp > self.stats.max_hp {
+            self.stats.hp = self.stats.max_hp;
+        }
+    }
+
+    /// 在当前属性上叠加组织加成
+    fn gc_apply_guild_buffs(&mut self) {
+        self.stats.max_hp = self.guild_buffs.apply_hp(self.stats.max_hp);
+        self.stats.attack = self.guild_buffs.apply_atk(self.stats.attack);
+        self.stats.defense = self.guild_buffs.apply_def(self.stats.defense);
+    }
+
     /// 是否可以行动
     pub fn gc_can_act(&self) -> bool {
         self.state == GcPlayerState::Alive && self.stats.gc_is_alive()
@@ -400,6 +494,15 @@ mod tests {
         assert!(player.gc_can_act());
     }
 
+    #[test]
+    fn test_missing_hero_skills_default_to_basic_set() {
+        let mut value = serde_json::to_value(GcPlayer::gc_new("p1", "测试玩家")).unwrap();
+        value.as_object_mut().unwrap().remove("hero_skills");
+        let player: GcPlayer = serde_json::from_value(value).unwrap();
+        assert!(player.hero_skills.gc_find("skill_strike").is_some());
+        assert_eq!(player.hero_skills.skills.len(), GcHeroSkillSet::gc_basic().skills.len());
+    }
+
     #[test]
     fn test_gc_player_stats_damage() {
         let mut stats = GcPlayerStats::default();
@@ -0,0 +1,295 @@
+//! 玩家设置 (按键绑定与界面偏好，跨设备同步)
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! 每个账号一份小型设置数据，服务器保存并校验；修改带版本号 (乐观并发)，防止两台设备互相覆盖
+//!
+//! ## 规则
+//! - 结构: `keybinds` (操作 ID -> 按键列表) 与 `ui` (偏好名 -> 布尔、数字或文本)，不允许其他字段
+//! - 序列化后不超过 `GC_SETTINGS_MAX_BYTES`；条目数、按键数与文本长度有上限
+//! - 格式版本 `schema_version`: 读取时按迁移表逐版本升级到当前版本，旧数据始终可以载入；
+//!   比当前版本新的数据拒绝载入
+//! - 修改时须提供读取到的 `revision`，与当前不一致时拒绝 (返回当前版本号)，成功后版本号 + 1
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::{gc_parse_json, gc_parse_json_value, GcJsonDiagnostic};
+
+/// 设置格式版本
+pub const GC_SETTINGS_SCHEMA_VERSION: u32 = 1;
+
+/// 设置序列化后的最大字节数
+pub const GC_SETTINGS_MAX_BYTES: usize = 16 * 1024;
+
+/// 按键绑定与界面偏好各自的最大条目数
+pub const GC_SETTINGS_MAX_ENTRIES: usize = 128;
+
+/// 每个操作最多绑定的按键数
+pub const GC_SETTINGS_MAX_KEYS: usize = 4;
+
+/// 操作 ID、偏好名与按键名的最大长度 (字符)
+pub const GC_SETTINGS_MAX_NAME_CHARS: usize = 64;
+
+/// 文本偏好值的最大长度 (字符)
+pub const GC_SETTINGS_MAX_TEXT_CHARS: usize = 256;
+
+// =============================================================================
+// 设置
+// =============================================================================
+
+/// 界面偏好值
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum GcSettingValue {
+    /// 开关
+    Bool(bool),
+    /// 数值 (音量、缩放等)
+    Number(f64),
+    /// 文本 (语言、主题等)
+    Text(String),
+}
+
+/// 玩家设置
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GcPlayerSettings {
+    /// 格式版本
+    pub schema_version: u32,
+    /// 按键绑定 (操作 ID -> 按键列表)
+    #[serde(default)]
+    pub keybinds: BTreeMap<String, Vec<String>>,
+    /// 界面偏好
+    #[serde(default)]
+    pub ui: BTreeMap<String, GcSettingValue>,
+}
+
+impl Default for GcPlayerSettings {
+    fn default() -> Self {
+        Self {
+            schema_version: GC_SETTINGS_SCHEMA_VERSION,
+            keybinds: BTreeMap::new(),
+            ui: BTreeMap::new(),
+        }
+    }
+}
+
+/// 设置校验或修改失败
+#[derive(Clone, Debug, Error, PartialEq)]
+pub enum GcSettingsError {
+    /// 数据过大
+    #[error("设置过大: {size} 字节，上限 {max} 字节")]
+    GcTooLarge { size: usize, max: usize },
+    /// JSON 无法解析或结构不符
+    #[error("设置格式错误: {0}")]
+    GcInvalidJson(GcJsonDiagnostic),
+    /// 格式版本比当前版本新
+    #[error("不支持的设置格式版本: {0}")]
+    GcUnsupportedVersion(u32),
+    /// 内容超出限制
+    #[error("设置不合法: {0}")]
+    GcInvalid(String),
+    /// 版本号不一致 (其他设备已修改)
+    #[error("设置已被修改，当前版本号 {current}")]
+    GcRevisionConflict { current: u64 },
+}
+
+impl GcPlayerSettings {
+    /// 校验总大小、条目数与长度
+    pub fn gc_validate(&self) -> Result<(), GcSettingsError> {
+        let invalid = |message: String| Err(GcSettingsError::GcInvalid(message));
+        let name_ok = |name: &str| !name.is_empty() && name.chars().count() <= GC_SETTINGS_MAX_NAME_CHARS;
+
+        if self.schema_version != GC_SETTINGS_SCHEMA_VERSION {
+            return Err(GcSettingsError::GcUnsupportedVersion(self.schema_version));
+        }
+        let size = serde_json::to_vec(self).map(|bytes| bytes.len()).unwrap_or(usize::MAX);
+        if size > GC_SETTINGS_MAX_BYTES {
+            return Err(GcSettingsError::GcTooLarge { size, max: GC_SETTINGS_MAX_BYTES });
+        }
+        if self.keybinds.len() > GC_SETTINGS_MAX_ENTRIES || self.ui.len() > GC_SETTINGS_MAX_ENTRIES {
+            return invalid(format!("条目过多，上限 {} 个", GC_SETTINGS_MAX_ENTRIES));
+        }
+        for (action, keys) in &self.keybinds {
+            if !name_ok(action) {
+                return invalid(format!("操作 ID 不合法: {}", action));
+            }
+            if keys.len() > GC_SETTINGS_MAX_KEYS || !keys.iter().all(|k| name_ok(k)) {
+                return invalid(format!("操作 {} 的按键不合法", action));
+            }
+        }
+        for (name, value) in &self.ui {
+            if !name_ok(name) {
+                return invalid(format!("偏好名不合法: {}", name));
+            }
+            match value {
+                GcSettingValue::Number(n) if !n.is_finite() => return invalid(format!("偏好 {} 不是有效数值", name)),
+                GcSettingValue::Text(text) if text.chars().count() > GC_SETTINGS_MAX_TEXT_CHARS => {
+                    return invalid(format!("偏好 {} 过长", name));
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// =============================================================================
+// 格式迁移
+// =============================================================================
+
+/// 迁移步骤: 第 i 项把版本 i 的数据升级到版本 i + 1
+type GcSettingsMigration = fn(&mut serde_json::Map<String, Value>);
+
+/// 迁移表 (长度等于当前格式版本)
+const GC_SETTINGS_MIGRATIONS: [GcSettingsMigration; GC_SETTINGS_SCHEMA_VERSION as usize] = [gc_migrate_v0_ui];
+
+/// 版本 0 (无版本号): 界面偏好直接写在顶层，移入 `ui`
+fn gc_migrate_v0_ui(object: &mut serde_json::Map<String, Value>) {
+    let loose: Vec<String> = object.keys()
+        .filter(|k| !matches!(k.as_str(), "schema_version" | "keybinds" | "ui"))
+        .cloned()
+        .collect();
+    let mut ui = match object.remove("ui") {
+        Some(Value::Object(ui)) => ui,
+        _ => serde_json::Map::new(),
+    };
+    for key in loose {
+        if let Some(value) = object.remove(&key) {
+            ui.entry(key).or_insert(value);
+        }
+    }
+    object.insert("ui".to_string(), Value::Object(ui));
+}
+
+/// 把任意版本的设置升级到当前版本并校验 (服务器载入旧数据、客户端上传时使用)
+pub fn gc_migrate_settings(value: Value) -> Result<GcPlayerSettings, GcSettingsError> {
+    let Value::Object(mut object) = value else {
+        return gc_parse_json_value("settings", value).map_err(GcSettingsError::GcInvalidJson);
+    };
+    let version = match object.get("schema_version") {
+        None => 0,
+        Some(v) => v.as_u64()
+            .and_then(|v| u32::try_from(v).ok())
+            .ok_or_else(|| GcSettingsError::GcInvalid("格式版本不是整数".to_string()))?,
+    };
+    if version > GC_SETTINGS_SCHEMA_VERSION {
+        return Err(GcSettingsError::GcUnsupportedVersion(version));
+    }
+
+    for migrate in &GC_SETTINGS_MIGRATIONS[version as usize..] {
+        migrate(&mut object);
+    }
+    object.insert("schema_version".to_string(), Value::from(GC_SETTINGS_SCHEMA_VERSION));
+
+    let settings: GcPlayerSettings = gc_parse_json_value("settings", Value::Object(object))
+        .map_err(GcSettingsError::GcInvalidJson)?;
+    settings.gc_validate()?;
+    Ok(settings)
+}
+
+/// 解析上传的设置 JSON (先检查大小，再迁移与校验)
+pub fn gc_parse_settings(json: &str) -> Result<GcPlayerSettings, GcSettingsError> {
+    if json.len() > GC_SETTINGS_MAX_BYTES {
+        return Err(GcSettingsError::GcTooLarge { size: json.len(), max: GC_SETTINGS_MAX_BYTES });
+    }
+    let value: Value = gc_parse_json("settings", json).map_err(GcSettingsError::GcInvalidJson)?;
+    gc_migrate_settings(value)
+}
+
+// =============================================================================
+// 存储记录
+// =============================================================================
+
+/// 服务器保存的设置记录
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct GcSettingsRecord {
+    /// 版本号 (从未保存为 0，每次修改 + 1)
+    pub revision: u64,
+    /// 最后修改时间 (Unix 秒)
+    #[serde(default)]
+    pub updated_at: u64,
+    /// 设置
+    pub settings: GcPlayerSettings,
+}
+
+impl GcSettingsRecord {
+    /// 按读取时的版本号替换设置 (乐观并发)
+    pub fn gc_update(&mut self, expected_revision: u64, settings: GcPlayerSettings, now: u64) -> Result<(), GcSettingsError> {
+        if expected_revision != self.revision {
+            return Err(GcSettingsError::GcRevisionConflict { current: self.revision });
+        }
+        settings.gc_validate()?;
+        self.revision += 1;
+        self.updated_at = now;
+        self.settings = settings;
+        Ok(())
+    }
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_and_validate() {
+        let settings = gc_parse_settings(r#"{
+            "schema_version": 1,
+            "keybinds": { "end_turn": ["Space", "Enter"] },
+            "ui": { "volume": 0.8, "show_hints": false, "language": "zh-CN" }
+        }"#).expect("合法设置");
+        assert_eq!(settings.keybinds["end_turn"], vec!["Space", "Enter"]);
+        assert_eq!(settings.ui["volume"], GcSettingValue::Number(0.8));
+        assert_eq!(settings.ui["show_hints"], GcSettingValue::Bool(false));
+
+        // 未知字段、嵌套对象、过多按键、过大数据都被拒绝
+        assert!(matches!(gc_parse_settings(r#"{"schema_version":1,"extra":1}"#), Err(GcSettingsError::GcInvalidJson(_))));
+        assert!(matches!(gc_parse_settings(r#"{"schema_version":1,"ui":{"a":{"b":1}}}"#), Err(GcSettingsError::GcInvalidJson(_))));
+        let keys = json!({ "schema_version": 1, "keybinds": { "attack": ["A", "B", "C", "D", "E"] } });
+        assert!(matches!(gc_migrate_settings(keys), Err(GcSettingsError::GcInvalid(_))));
+        let huge = format!(r#"{{"schema_version":1,"ui":{{"note":"{}"}}}}"#, "x".repeat(GC_SETTINGS_MAX_BYTES));
+        assert!(matches!(gc_parse_settings(&huge), Err(GcSettingsError::GcTooLarge { .. })));
+        assert_eq!(gc_parse_settings(r#"{"schema_version":9}"#), Err(GcSettingsError::GcUnsupportedVersion(9)));
+    }
+
+    #[test]
+    fn test_migrate_unversioned_blob() {
+        // 版本 0: 偏好写在顶层
+        let old = json!({ "keybinds": { "end_turn": ["Space"] }, "volume": 0.5, "theme": "dark" });
+        let settings = gc_migrate_settings(old).expect("旧数据应可载入");
+        assert_eq!(settings.schema_version, GC_SETTINGS_SCHEMA_VERSION);
+        assert_eq!(settings.ui["volume"], GcSettingValue::Number(0.5));
+        assert_eq!(settings.ui["theme"], GcSettingValue::Text("dark".to_string()));
+        assert_eq!(settings.keybinds.len(), 1);
+    }
+
+    #[test]
+    fn test_optimistic_update() {
+        let mut record = GcSettingsRecord::default();
+        let mut settings = GcPlayerSettings::default();
+        settings.ui.insert("volume".to_string(), GcSettingValue::Number(0.3));
+
+        record.gc_update(0, settings.clone(), 100).expect("首次保存");
+        assert_eq!((record.revision, record.updated_at), (1, 100));
+
+        // 另一台设备按旧版本号保存: 冲突，设置不变
+        assert_eq!(
+            record.gc_update(0, GcPlayerSettings::default(), 101),
+            Err(GcSettingsError::GcRevisionConflict { current: 1 })
+        );
+        assert_eq!(record.settings, settings);
+        record.gc_update(1, GcPlayerSettings::default(), 102).expect("按最新版本号保存");
+        assert_eq!(record.revision, 2);
+    }
+}
@@ -0,0 +1,290 @@
+//! 操作预览 (悬停预览、提示打分)
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! ## 预览规则
+//! - 先用 `gc_explain_legality` 检查，不合法时不试执行，只返回原因
+//! - 合法时在状态副本上执行一步，原状态不变；执行器仍然失败时返回错误信息
+//! - 结束回合只预览本回合的战场攻击结算，不推进到下一名玩家
+//! - 投降按该玩家阵亡处理，再检查战斗是否结束
+//! - 结果列出生命值有变化的玩家、受损或被摧毁的战场卡牌、因此出局的玩家与获胜方
+//! - `score` 为执行后操作者的局面分 (`gc_evaluate_battle`)，提示系统按它排序
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    gc_evaluate_battle, gc_execute_play_card, gc_execute_use_skill, gc_explain_legality, GcAction,
+    GcBattleState, GcLegalityReason, GcPlayerState,
+};
+
+// =============================================================================
+// 预览结果
+// =============================================================================
+
+/// 玩家的生命值变化
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcPreviewPlayer {
+    /// 玩家 ID
+    pub player_id: String,
+    /// 执行前生命值
+    pub hp_before: u32,
+    /// 执行后生命值
+    pub hp_after: u32,
+    /// 是否因此出局
+    pub eliminated: bool,
+}
+
+/// 战场卡牌的变化
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcPreviewUnit {
+    /// 所属玩家 ID
+    pub owner_id: String,
+    /// 槽位索引
+    pub slot_index: usize,
+    /// 卡牌 ID
+    pub card_id: String,
+    /// 卡牌名称
+    pub card_name: String,
+    /// 执行前剩余生命值
+    pub hp_before: u32,
+    /// 执行后剩余生命值 (被摧毁时为 0)
+    pub hp_after: u32,
+    /// 是否被摧毁
+    pub destroyed: bool,
+}
+
+/// 操作预览
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GcActionPreview {
+    /// 预览的操作
+    pub action: GcAction,
+    /// 操作是否合法
+    pub legal: bool,
+    /// 不合法的原因
+    pub reasons: Vec<GcLegalityReason>,
+    /// 执行失败的错误信息 (合法性检查通过但执行器拒绝时)
+    pub error: Option<String>,
+    /// 其他玩家损失的生命值总和
+    pub damage_dealt: u32,
+    /// 操作者损失的生命值
+    pub damage_taken: u32,
+    /// 生命值有变化或出局的玩家
+    pub players: Vec<GcPreviewPlayer>,
+    /// 受损或被摧毁的战场卡牌
+    pub units: Vec<GcPreviewUnit>,
+    /// 因此出局的玩家
+    pub eliminated: Vec<String>,
+    /// 获胜方的全部玩家 (战斗因此结束时)
+    pub winner_ids: Vec<String>,
+    /// 执行后操作者的局面分
+    pub score: i64,
+}
+
+impl GcActionPreview {
+    /// 未执行的预览 (不合法或执行失败)
+    fn gc_rejected(state: &GcBattleState, action: &GcAction, reasons: Vec<GcLegalityReason>, error: Option<String>) -> Self {
+        Self {
+            action: action.clone(),
+            legal: reasons.is_empty(),
+            reasons,
+            error,
+            damage_dealt: 0,
+            damage_taken: 0,
+            players: Vec::new(),
+            units: Vec::new(),
+            eliminated: Vec::new(),
+            winner_ids: Vec::new(),
+            score: gc_evaluate_battle(state, action.gc_player_id()),
+        }
+    }
+
+    /// 是否成功试执行
+    pub fn gc_is_applied(&self) -> bool {
+        self.legal && self.error.is_none()
+    }
+
+    /// 操作者是否因此获胜
+    pub fn gc_is_lethal(&self) -> bool {
+        self.winner_ids.iter().any(|id| id == self.action.gc_player_id())
+    }
+}
+
+// =============================================================================
+// 预览
+// =============================================================================
+
+/// 在副本上执行操作 (返回执行器的错误信息)
+pub(crate) fn gc_apply_action(state: &mut GcBattleState, action: &GcAction) -> Result<(), String> {
+    match action {
+        GcAction::PlayCard { player_id, card_id, target_id } => {
+            let result = gc_execute_play_card(state, player_id, card_id, target_id);
+            if result.success { Ok(()) } else { Err(result.error.unwrap_or_default()) }
+        }
+        GcAction::UseSkill { player_id, skill_id, target_id } => {
+            let result = gc_execute_use_skill(state, player_id, skill_id, target_id);
+            if result.success { Ok(()) } else { Err(result.error.unwrap_or_default()) }
+        }
+        GcAction::DeployCard { player_id, card_id, slot_index } => {
+            state.gc_deploy_card(player_id, card_id, *slot_index).map_err(|e| e.to_string())
+        }
+        GcAction::AcquireCard { player_id, card_id } => {
+            state.gc_acquire_card_from_pool(player_id, card_id).map(|_| ()).map_err(|e| e.to_string())
+        }
+        GcAction::EndTurn { player_id } => {
+            state.gc_execute_turn_combat(player_id);
+            Ok(())
+        }
+        GcAction::Surrender { player_id } => {
+            if let Some(player) = state.gc_find_player_mut(player_id) {
+                player.state = GcPlayerState::Dead;
+            }
+            state.gc_check_battle_end();
+            Ok(())
+        }
+    }
+}
+
+/// 对比执行前后的状态
+fn gc_diff(before: &GcBattleState, after: &GcBattleState, action: &GcAction) -> GcActionPreview {
+    let actor_id = action.gc_player_id();
+    let mut preview = GcActionPreview::gc_rejected(after, action, Vec::new(), None);
+
+    for old in &before.players {
+        let Some(new) = after.gc_find_player(&old.id) else {
+            continue;
+        };
+        let lost = old.stats.hp.saturating_sub(new.stats.hp);
+        if old.id == actor_id {
+            preview.damage_taken = lost;
+        } else {
+            preview.damage_dealt += lost;
+        }
+        let eliminated = old.gc_can_act() && !new.gc_can_act();
+        if eliminated {
+            preview.eliminated.push(old.id.clone());
+        }
+        if old.stats.hp != new.stats.hp || eliminated {
+            preview.players.push(GcPreviewPlayer {
+                player_id: old.id.clone(),
+                hp_before: old.stats.hp,
+                hp_after: new.stats.hp,
+                eliminated,
+            });
+        }
+
+        for slot in &old.battlefield.slots {
+            let Some(card) = &slot.card else {
+                continue;
+            };
+            let remaining = new.battlefield.slots.get(slot.index)
+                .filter(|s| s.card.as_ref().is_some_and(|c| c.id == card.id))
+                .map(|s| s.remaining_hp);
+            let hp_after = remaining.unwrap_or(0);
+            if hp_after < slot.remaining_hp || remaining.is_none() {
+                preview.units.push(GcPreviewUnit {
+                    owner_id: old.id.clone(),
+                    slot_index: slot.index,
+                    card_id: card.id.clone(),
+                    card_name: card.name.clone(),
+                    hp_before: slot.remaining_hp,
+                    hp_after,
+                    destroyed: remaining.is_none(),
+                });
+            }
+        }
+    }
+
+    if !before.gc_is_finished() && after.gc_is_finished() {
+        preview.winner_ids = after.gc_winner_ids();
+    }
+    preview
+}
+
+/// 预览操作的结果 (不修改原状态)
+pub fn gc_preview_action(state: &GcBattleState, action: &GcAction) -> GcActionPreview {
+    let reasons = gc_explain_legality(state, action);
+    if !reasons.is_empty() {
+        return GcActionPreview::gc_rejected(state, action, reasons, None);
+    }
+    let mut next = state.clone();
+    if let Err(error) = gc_apply_action(&mut next, action) {
+        return GcActionPreview::gc_rejected(state, action, Vec::new(), Some(error));
+    }
+    gc_diff(state, &next, action)
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GcCard, GcPlayer};
+
+    fn create_battle() -> GcBattleState {
+        GcBattleState::gc_new("b1", vec![GcPlayer::gc_new("p1", "玩家1"), GcPlayer::gc_new("p2", "玩家2")])
+    }
+
+    #[test]
+    fn test_preview_does_not_mutate() {
+        let mut battle = create_battle();
+        battle.players[0].hand.push(GcCard::gc_new_attack("fire", "火球", 1, 10));
+        let action = GcAction::PlayCard {
+            player_id: "p1".to_string(),
+            card_id: "fire".to_string(),
+            target_id: "p2".to_string(),
+        };
+        let before = serde_json::to_string(&battle).unwrap_or_default();
+
+        let preview = gc_preview_action(&battle, &action);
+        assert!(preview.gc_is_applied());
+        assert!(preview.damage_dealt > 0);
+        assert_eq!(preview.damage_taken, 0);
+        assert_eq!(preview.players.len(), 1);
+        assert_eq!(preview.players[0].hp_before - preview.players[0].hp_after, preview.damage_dealt);
+        assert!(!preview.gc_is_lethal());
+        assert_eq!(serde_json::to_string(&battle).unwrap_or_default(), before);
+
+        // 斩杀
+        battle.players[1].stats.hp = 1;
+        let lethal = gc_preview_action(&battle, &action);
+        assert_eq!(lethal.eliminated, vec!["p2".to_string()]);
+        assert!(lethal.players[0].eliminated);
+        assert_eq!(lethal.winner_ids, vec!["p1".to_string()]);
+        assert!(lethal.gc_is_lethal());
+        assert_eq!(battle.players[1].stats.hp, 1);
+    }
+
+    #[test]
+    fn test_preview_illegal_action() {
+        let battle = create_battle();
+        let preview = gc_preview_action(&battle, &GcAction::EndTurn { player_id: "p2".to_string() });
+        assert!(!preview.legal);
+        assert!(!preview.gc_is_applied());
+        assert_eq!(preview.reasons, vec![GcLegalityReason::NotYourTurn { current_player_id: Some("p1".to_string()) }]);
+        assert!(preview.players.is_empty());
+
+        let surrender = gc_preview_action(&battle, &GcAction::Surrender { player_id: "p2".to_string() });
+        assert_eq!(surrender.eliminated, vec!["p2".to_string()]);
+        assert_eq!(surrender.winner_ids, vec!["p1".to_string()]);
+        assert_eq!(battle.players[1].state, GcPlayerState::Alive);
+    }
+
+    #[test]
+    fn test_preview_end_turn_combat() {
+        let mut battle = create_battle();
+        if let Some(slot) = battle.players[0].battlefield.gc_get_slot_mut(0) {
+            slot.gc_deploy(GcCard::gc_new_attack("wolf", "野狼", 1, 4));
+        }
+        if let Some(slot) = battle.players[1].battlefield.gc_get_slot_mut(0) {
+            slot.gc_deploy(GcCard::gc_new_defense("wall", "石墙", 1, 2));
+        }
+        let preview = gc_preview_action(&battle, &GcAction::EndTurn { player_id: "p1".to_string() });
+        assert!(preview.gc_is_applied());
+        assert!(preview.units.iter().any(|u| u.card_id == "wall" && u.owner_id == "p2" && u.hp_after < u.hp_before));
+        assert_eq!(battle.players[1].battlefield.slots[0].card.as_ref().map(|c| c.id.as_str()), Some("wall"));
+    }
+}
@@ -0,0 +1,423 @@
+//! 回放分享 (回放格式、校验、短码与保留策略)
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! ## 规则
+//! - 回放 = 对局元数据 + 完整领域事件列表 (`GcBattleEvent`，首个为 `Genesis`)
+//! - 上传时重放全部事件: 必须能折叠、对局已结束，元数据 (玩家、胜者、回合数) 必须与重放结果一致
+//! - 分享码为 8 位 Crockford Base32 (不含 I/L/O/U)，由内容哈希生成，冲突时换盐重算
+//! - 回放库按保留策略清理: 超过保留时间或超过条数上限时先删除最旧的
+//! - 只有公开回放出现在列表中，私有回放只能凭分享码获取
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{gc_fold_battle_events, GcBattleEvent, GcBattleState, GcEventSourcedBattle, GcGameMode, GcProfessionType};
+
+/// 回放格式版本
+pub const GC_REPLAY_FORMAT_VERSION: u32 = 1;
+
+/// 分享码长度
+pub const GC_REPLAY_CODE_LEN: usize = 8;
+
+/// 分享码字符表 (Crockford Base32)
+pub(crate) const GC_REPLAY_CODE_ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+/// 列表单次最多返回条数
+pub const GC_REPLAY_LIST_MAX: usize = 50;
+
+// =============================================================================
+// 回放格式
+// =============================================================================
+
+/// 回放中的玩家
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcReplayPlayer {
+    /// 玩家 ID
+    pub id: String,
+    /// 玩家名称
+    pub name: String,
+    /// 职业
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profession: Option<GcProfessionType>,
+}
+
+/// 回放
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GcReplay {
+    /// 格式版本
+    pub format_version: u32,
+    /// 游戏模式
+    pub mode: GcGameMode,
+    /// 玩家
+    pub players: Vec<GcReplayPlayer>,
+    /// 胜者
+    #[serde(default)]
+    pub winner_id: Option<String>,
+    /// 回合数
+    pub turns: u32,
+    /// 领域事件 (首个为初始快照)
+    pub events: Vec<GcBattleEvent>,
+}
+
+/// 回放校验失败
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum GcReplayError {
+    /// 格式版本不支持
+    #[error("不支持的回放格式版本: {0}")]
+    GcUnsupportedVersion(u32),
+    /// 回放数据过大 (字节)
+    #[error("回放过大: {size} 字节，上限 {max} 字节")]
+    GcTooLarge { size: usize, max: usize },
+    /// 事件过多
+    #[error("回放事件过多: {count} 个，上限 {max} 个")]
+    GcTooManyEvents { count: usize, max: usize },
+    /// 事件无法重放
+    #[error("回放事件无法重放: {0}")]
+    GcInvalidEvents(String),
+    /// 对局未结束
+    #[error("只能分享已结束的对局")]
+    GcNotFinished,
+    /// 元数据与重放结果不一致
+    #[error("回放元数据与事件不一致: {0}")]
+    GcMetadataMismatch(String),
+}
+
+impl GcReplay {
+    /// 由已结束的事件溯源战斗生成回放
+    pub fn gc_from_battle(battle: &GcEventSourcedBattle, mode: GcGameMode) -> Result<Self, GcReplayError> {
+        let state = battle.gc_state();
+        if !state.gc_is_finished() {
+            return Err(GcReplayError::GcNotFinished);
+        }
+        Ok(Self {
+            format_version: GC_REPLAY_FORMAT_VERSION,
+            mode,
+            players: gc_replay_players(state),
+            winner_id: state.winner_id.clone(),
+            turns: state.turn,
+            events: battle.gc_events().to_vec(),
+        })
+    }
+
+    /// 重放校验，返回最终状态
+    pub fn gc_verify(&self, max_events: usize) -> Result<GcBattleState, GcReplayError> {
+        if self.format_version != GC_REPLAY_FORMAT_VERSION {
+            return Err(GcReplayError::GcUnsupportedVersion(self.format_version));
+        }
+        if self.events.len() > max_events {
+            return Err(GcReplayError::GcTooManyEvents { count: self.events.len(), max: max_events });
+        }
+        let state = gc_fold_battle_events(&self.events)
+            .map_err(|e| GcReplayError::GcInvalidEvents(e.to_string()))?;
+        if !state.gc_is_finished() {
+            return Err(GcReplayError::GcNotFinished);
+        }
+        if gc_replay_players(&state) != self.players {
+            return Err(GcReplayError::GcMetadataMismatch("玩家".to_string()));
+        }
+        if state.winner_id != self.winner_id {
+            return Err(GcReplayError::GcMetadataMismatch("胜者".to_string()));
+        }
+        if state.turn != self.turns {
+            return Err(GcReplayError::GcMetadataMismatch("回合数".to_string()));
+        }
+        Ok(state)
+    }
+}
+
+/// 从战斗状态提取回放玩家
+fn gc_replay_players(state: &GcBattleState) -> Vec<GcReplayPlayer> {
+    state
+        .players
+        .iter()
+        .map(|p| GcReplayPlayer { id: p.id.clone(), name: p.name.clone(), profession: p.profession })
+        .collect()
+}
+
+// =============================================================================
+// 分享码
+// =============================================================================
+
+/// 由内容与盐值生成分享码 (FNV-1a 哈希，跨平台稳定)
+pub fn gc_replay_share_code(content: &[u8], salt: u64) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in content.iter().copied().chain(salt.to_le_bytes()) {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    (0..GC_REPLAY_CODE_LEN)
+        .map(|i| GC_REPLAY_CODE_ALPHABET[((hash >> (i * 5)) & 31) as usize] as char)
+        .collect()
+}
+
+/// 规范化用户输入的分享码 (去除空白与连字符、转大写，易混字符按 Crockford 规则映射)
+pub fn gc_normalize_share_code(input: &str) -> String {
+    input
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '-')
+        .map(|c| match c.to_ascii_uppercase() {
+            'I' | 'L' => '1',
+            'O' => '0',
+            other => other,
+        })
+        .collect()
+}
+
+// =============================================================================
+// 回放库
+// =============================================================================
+
+/// 回放保留策略
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcReplayPolicy {
+    /// 单个回放的最大字节数
+    pub max_bytes: usize,
+    /// 单个回放的最大事件数
+    pub max_events: usize,
+    /// 保留时间 (秒)
+    pub retention_secs: u64,
+    /// 最多保留条数
+    pub max_entries: usize,
+}
+
+impl Default for GcReplayPolicy {
+    fn default() -> Self {
+        Self {
+            max_bytes: 256 * 1024,
+            max_events: 5000,
+            retention_secs: 7 * 24 * 3600,
+            max_entries: 500,
+        }
+    }
+}
+
+/// 回放摘要 (列表与上传响应)
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcReplaySummary {
+    /// 分享码
+    pub code: String,
+    /// 游戏模式
+    pub mode: GcGameMode,
+    /// 玩家
+    pub players: Vec<GcReplayPlayer>,
+    /// 胜者
+    pub winner_id: Option<String>,
+    /// 回合数
+    pub turns: u32,
+    /// 事件数
+    pub event_count: usize,
+    /// 数据大小 (字节)
+    pub size_bytes: usize,
+    /// 是否公开
+    pub public: bool,
+    /// 上传时间 (Unix 秒)
+    pub uploaded_at: u64,
+    /// 过期时间 (Unix 秒)
+    pub expires_at: u64,
+}
+
+/// 列表筛选条件
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcReplayFilter {
+    /// 游戏模式
+    #[serde(default)]
+    pub mode: Option<GcGameMode>,
+    /// 任一玩家的职业
+    #[serde(default)]
+    pub profession: Option<GcProfessionType>,
+}
+
+impl GcReplayFilter {
+    /// 摘要是否符合条件
+    pub fn gc_matches(&self, summary: &GcReplaySummary) -> bool {
+        self.mode.is_none_or(|mode| summary.mode == mode)
+            && self.profession.is_none_or(|prof| summary.players.iter().any(|p| p.profession == Some(prof)))
+    }
+}
+
+/// 库中的回放
+#[derive(Clone, Debug)]
+struct GcStoredReplay {
+    summary: GcReplaySummary,
+    replay: GcReplay,
+}
+
+/// 回放库 (分享码 -> 回放)
+#[derive(Clone, Debug, Default)]
+pub struct GcReplayLibrary {
+    /// 保留策略
+    pub policy: GcReplayPolicy,
+    entries: BTreeMap<String, GcStoredReplay>,
+}
+
+impl GcReplayLibrary {
+    /// 创建回放库
+    pub fn gc_new(policy: GcReplayPolicy) -> Self {
+        Self { policy, entries: BTreeMap::new() }
+    }
+
+    /// 回放数量
+    pub fn gc_len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// 是否为空
+    pub fn gc_is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// 上传回放 (`size_bytes` 为上传数据的字节数)，成功返回摘要
+    pub fn gc_upload(
+        &mut self,
+        replay: GcReplay,
+        size_bytes: usize,
+        public: bool,
+        now: u64,
+    ) -> Result<GcReplaySummary, GcReplayError> {
+        if size_bytes > self.policy.max_bytes {
+            return Err(GcReplayError::GcTooLarge { size: size_bytes, max: self.policy.max_bytes });
+        }
+        replay.gc_verify(self.policy.max_events)?;
+        self.gc_prune(now);
+
+        let content = serde_json::to_vec(&replay.events).unwrap_or_default();
+        let code = (now..)
+            .map(|salt| gc_replay_share_code(&content, salt))
+            .find(|code| !self.entries.contains_key(code))
+            .unwrap_or_default();
+        let summary = GcReplaySummary {
+            code: code.clone(),
+            mode: replay.mode,
+            players: replay.players.clone(),
+            winner_id: replay.winner_id.clone(),
+            turns: replay.turns,
+            event_count: replay.events.len(),
+            size_bytes,
+            public,
+            uploaded_at: now,
+            expires_at: now.saturating_add(self.policy.retention_secs),
+        };
+        self.entries.insert(code, GcStoredReplay { summary: summary.clone(), replay });
+
+        // 超出条数上限时删除最旧的
+        while self.entries.len() > self.policy.max_entries {
+            let Some(oldest) = self.entries.values().min_by_key(|e| e.summary.uploaded_at).map(|e| e.summary.code.clone()) else {
+                break;
+            };
+            self.entries.remove(&oldest);
+        }
+        Ok(summary)
+    }
+
+    /// 按分享码获取回放 (输入会先规范化，已过期的视为不存在)
+    pub fn gc_get(&self, code: &str, now: u64) -> Option<(&GcReplaySummary, &GcReplay)> {
+        self.entries
+            .get(&gc_normalize_share_code(code))
+            .filter(|e| e.summary.expires_at > now)
+            .map(|e| (&e.summary, &e.replay))
+    }
+
+    /// 最近的公开回放 (新的在前)
+    pub fn gc_list_public(&self, filter: &GcReplayFilter, limit: usize, now: u64) -> Vec<GcReplaySummary> {
+        let mut list: Vec<_> = self
+            .entries
+            .values()
+            .map(|e| &e.summary)
+            .filter(|s| s.public && s.expires_at > now && filter.gc_matches(s))
+            .cloned()
+            .collect();
+        list.sort_by(|a, b| b.uploaded_at.cmp(&a.uploaded_at).then_with(|| a.code.cmp(&b.code)));
+        list.truncate(limit.min(GC_REPLAY_LIST_MAX));
+        list
+    }
+
+    /// 删除过期回放，返回删除数量
+    pub fn gc_prune(&mut self, now: u64) -> usize {
+        let before = self.entries.len();
+        self.entries.retain(|_, e| e.summary.expires_at > now);
+        before - self.entries.len()
+    }
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GcBattlePhase, GcCard, GcPlayer, GcProfessionType};
+
+    fn finished_battle() -> GcEventSourcedBattle {
+        let mut p1 = GcPlayer::gc_new("p1", "玩家1");
+        p1.gc_init_rpg(GcProfessionType::Warlock);
+        p1.hand.push(GcCard::gc_new_attack("c1", "终结", 1, 500));
+        let mut battle = GcEventSourcedBattle::gc_new("replay", vec![p1, GcPlayer::gc_new("p2", "玩家2")]);
+        battle.gc_record(GcBattleEvent::PhaseChanged { phase: GcBattlePhase::Playing }).unwrap();
+        assert!(battle.gc_play_card("p1", "c1", "p2").success);
+        battle
+    }
+
+    #[test]
+    fn test_replay_round_trip_and_verify() {
+        let battle = finished_battle();
+        assert!(battle.gc_state().gc_is_finished());
+        let replay = GcReplay::gc_from_battle(&battle, GcGameMode::YuGiOhStyle).unwrap();
+        let json = serde_json::to_string(&replay).unwrap();
+        let parsed: GcReplay = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.gc_verify(100).unwrap().winner_id.as_deref(), Some("p1"));
+
+        let mut tampered = parsed.clone();
+        tampered.winner_id = Some("p2".to_string());
+        assert!(matches!(tampered.gc_verify(100), Err(GcReplayError::GcMetadataMismatch(_))));
+        assert!(matches!(parsed.gc_verify(1), Err(GcReplayError::GcTooManyEvents { .. })));
+
+        let unfinished = GcEventSourcedBattle::gc_new("x", vec![GcPlayer::gc_new("a", "A")]);
+        assert_eq!(GcReplay::gc_from_battle(&unfinished, GcGameMode::YuGiOhStyle).unwrap_err(), GcReplayError::GcNotFinished);
+    }
+
+    #[test]
+    fn test_share_codes() {
+        let code = gc_replay_share_code(b"events", 1);
+        assert_eq!(code.len(), GC_REPLAY_CODE_LEN);
+        assert!(code.bytes().all(|b| GC_REPLAY_CODE_ALPHABET.contains(&b)));
+        assert_eq!(code, gc_replay_share_code(b"events", 1));
+        assert_ne!(code, gc_replay_share_code(b"events", 2));
+        assert_eq!(gc_normalize_share_code(" ab-cd o1l "), "ABCD011");
+    }
+
+    #[test]
+    fn test_library_limits_filters_and_retention() {
+        let policy = GcReplayPolicy { max_bytes: 10_000, max_events: 100, retention_secs: 100, max_entries: 2 };
+        let mut library = GcReplayLibrary::gc_new(policy);
+        let replay = GcReplay::gc_from_battle(&finished_battle(), GcGameMode::YuGiOhStyle).unwrap();
+
+        assert!(matches!(library.gc_upload(replay.clone(), 20_000, true, 0), Err(GcReplayError::GcTooLarge { .. })));
+
+        let first = library.gc_upload(replay.clone(), 500, true, 10).unwrap();
+        let private = library.gc_upload(replay.clone(), 500, false, 20).unwrap();
+        assert_ne!(first.code, private.code);
+        assert!(library.gc_get(&first.code.to_lowercase(), 30).is_some());
+
+        // 列表只含公开回放，按职业与模式筛选
+        let warlock = GcReplayFilter { profession: Some(GcProfessionType::Warlock), ..Default::default() };
+        assert_eq!(library.gc_list_public(&warlock, 10, 30).len(), 1);
+        let knight = GcReplayFilter { profession: Some(GcProfessionType::Knight), ..Default::default() };
+        assert!(library.gc_list_public(&knight, 10, 30).is_empty());
+        let tavern = GcReplayFilter { mode: Some(GcGameMode::TavernStyle), ..Default::default() };
+        assert!(library.gc_list_public(&tavern, 10, 30).is_empty());
+
+        // 超过条数上限删除最旧的；过期后取不到并被清理
+        library.gc_upload(replay, 500, true, 30).unwrap();
+        assert_eq!(library.gc_len(), 2);
+        assert!(library.gc_get(&first.code, 30).is_none());
+        assert!(library.gc_get(&private.code, 120).is_none());
+        assert_eq!(library.gc_prune(200), 2);
+        assert!(library.gc_is_empty());
+    }
+}
@@ -0,0 +1,206 @@
+//! 跨模式奖励 (酒馆名次、卡牌对战胜负换算为 RPG 金币与经验)
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! ## 换算规则
+//! - 酒馆对局按最终名次查表，名次超出表长度时使用参与奖励
+//! - 卡牌对战每局结算: 胜者 (组队时整队) 获得胜利奖励，其余玩家获得失败奖励 (平局均按失败)
+//! - 换算表可由服务器配置文件替换，`gc_validate` 保证至少有一个名次
+//! - 奖励在服务器对局结束时发放，并随结算摘要下发给客户端
+
+use serde::{Deserialize, Serialize};
+
+// =============================================================================
+// 奖励
+// =============================================================================
+
+/// RPG 层奖励 (金币与经验)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcRewardGrant {
+    /// 金币
+    pub gold: u32,
+    /// 经验
+    pub xp: u32,
+}
+
+impl GcRewardGrant {
+    /// 创建奖励
+    pub fn gc_new(gold: u32, xp: u32) -> Self {
+        Self { gold, xp }
+    }
+
+    /// 是否为空奖励
+    pub fn gc_is_empty(&self) -> bool {
+        self.gold == 0 && self.xp == 0
+    }
+}
+
+/// 奖励来源模式
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GcRewardSource {
+    /// 酒馆对局 (含每周挑战)
+    Tavern,
+    /// 卡牌对战
+    CardBattle,
+}
+
+/// 单名玩家的对局奖励 (结算摘要)
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcMatchReward {
+    /// 玩家 ID
+    pub player_id: String,
+    /// 来源模式
+    pub source: GcRewardSource,
+    /// 酒馆名次
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub placement: Option<u8>,
+    /// 卡牌对战是否获胜
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub won: Option<bool>,
+    /// 获得的奖励
+    pub grant: GcRewardGrant,
+}
+
+// =============================================================================
+// 换算表
+// =============================================================================
+
+/// 奖励换算表
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcRewardTable {
+    /// 酒馆名次奖励 (下标 0 为第 1 名)
+    pub tavern_placements: Vec<GcRewardGrant>,
+    /// 酒馆参与奖励 (名次超出表长度时)
+    #[serde(default)]
+    pub tavern_participation: GcRewardGrant,
+    /// 卡牌对战胜利奖励
+    pub card_battle_win: GcRewardGrant,
+    /// 卡牌对战失败奖励
+    #[serde(default)]
+    pub card_battle_loss: GcRewardGrant,
+}
+
+impl Default for GcRewardTable {
+    fn default() -> Self {
+        Self {
+            tavern_placements: [(100, 60), (70, 45), (50, 35), (40, 30), (25, 20), (20, 15), (15, 10), (10, 10)]
+                .into_iter()
+                .map(|(gold, xp)| GcRewardGrant::gc_new(gold, xp))
+                .collect(),
+            tavern_participation: GcRewardGrant::gc_new(10, 10),
+            card_battle_win: GcRewardGrant::gc_new(40, 30),
+            card_battle_loss: GcRewardGrant::gc_new(10, 15),
+        }
+    }
+}
+
+impl GcRewardTable {
+    /// 校验换算表
+    pub fn gc_validate(&self) -> Result<(), String> {
+        if self.tavern_placements.is_empty() {
+            return Err("酒馆名次奖励不能为空".to_string());
+        }
+        Ok(())
+    }
+
+    /// 酒馆名次对应的奖励 (名次从 1 开始)
+    pub fn gc_tavern_grant(&self, placement: u8) -> GcRewardGrant {
+        (placement as usize)
+            .checked_sub(1)
+            .and_then(|i| self.tavern_placements.get(i))
+            .copied()
+            .unwrap_or(self.tavern_participation)
+    }
+
+    /// 卡牌对战结果对应的奖励
+    pub fn gc_card_battle_grant(&self, won: bool) -> GcRewardGrant {
+        if won {
+            self.card_battle_win
+        } else {
+            self.card_battle_loss
+        }
+    }
+}
+
+// =============================================================================
+// 结算
+// =============================================================================
+
+/// 酒馆对局结束时的奖励 (placements 为玩家最终名次)
+pub fn gc_tavern_rewards(table: &GcRewardTable, placements: &[(String, u8)]) -> Vec<GcMatchReward> {
+    placements
+        .iter()
+        .map(|(player_id, placement)| GcMatchReward {
+            player_id: player_id.clone(),
+            source: GcRewardSource::Tavern,
+            placement: Some(*placement),
+            won: None,
+            grant: table.gc_tavern_grant(*placement),
+        })
+        .collect()
+}
+
+/// 卡牌对战一局结束时的奖励 (组队获胜时整队计为胜方)
+pub fn gc_card_battle_rewards(
+    table: &GcRewardTable,
+    player_ids: &[String],
+    winner_ids: &[String],
+) -> Vec<GcMatchReward> {
+    player_ids
+        .iter()
+        .map(|player_id| {
+            let won = winner_ids.contains(player_id);
+            GcMatchReward {
+                player_id: player_id.clone(),
+                source: GcRewardSource::CardBattle,
+                placement: None,
+                won: Some(won),
+                grant: table.gc_card_battle_grant(won),
+            }
+        })
+        .collect()
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tavern_placement_rewards() {
+        let table = GcRewardTable::default();
+        assert!(table.gc_validate().is_ok());
+        let placements = vec![("p1".to_string(), 1), ("p2".to_string(), 2), ("p9".to_string(), 9)];
+        let rewards = gc_tavern_rewards(&table, &placements);
+        assert_eq!(rewards[0].grant, GcRewardGrant::gc_new(100, 60));
+        assert_eq!(rewards[1].grant, GcRewardGrant::gc_new(70, 45));
+        assert_eq!(rewards[2].grant, table.tavern_participation);
+        assert_eq!(rewards[2].placement, Some(9));
+        assert_eq!(table.gc_tavern_grant(0), table.tavern_participation);
+
+        let empty = GcRewardTable { tavern_placements: Vec::new(), ..GcRewardTable::default() };
+        assert!(empty.gc_validate().is_err());
+    }
+
+    #[test]
+    fn test_card_battle_rewards_and_config() {
+        let json = r#"{"tavern_placements":[{"gold":5,"xp":5}],"card_battle_win":{"gold":30,"xp":20}}"#;
+        let table: GcRewardTable = serde_json::from_str(json).unwrap_or_default();
+        assert_eq!(table.card_battle_win.gold, 30);
+        assert!(table.card_battle_loss.gc_is_empty());
+
+        let players = vec!["p1".to_string(), "p2".to_string()];
+        let rewards = gc_card_battle_rewards(&table, &players, &["p2".to_string()]);
+        assert_eq!(rewards[0].won, Some(false));
+        assert!(rewards[0].grant.gc_is_empty());
+        assert_eq!(rewards[1].grant, GcRewardGrant::gc_new(30, 20));
+
+        let draw = gc_card_battle_rewards(&table, &players, &[]);
+        assert!(draw.iter().all(|r| r.won == Some(false)));
+    }
+}
@@ -0,0 +1,506 @@
+//! 规则集与每周挑战
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! ## 规则
+//! - 规则集由若干修改器组成 (如 "所有怪兽 +1 星"、"刷新免费"、"全场沼泽")
+//! - 每周挑战按周轮换规则集，定义由服务器提供 (JSON)
+//! - 规则集在创建对局时应用；刷新与购买的修改器在酒馆操作时生效
+//! - 每周挑战单独计分: 名次越靠前积分越高，按积分排行
+//! - 难度预设 (剧情/普通/困难) 调整敌人属性、掉落数量与经验，可选敌人等级随玩家缩放；
+//!   由遭遇生成与 Boss 副本应用，每个存档单独选择
+
+use serde::{Deserialize, Serialize};
+
+use crate::{gc_percent_of, GcMonster, GcShopPricing, GcShopVarietyRule, GcTavernGame, GcTerrainType};
+
+// =============================================================================
+// 常量
+// =============================================================================
+
+/// 一周的秒数
+pub const GC_WEEK_SECS: u64 = 7 * 24 * 60 * 60;
+
+/// 怪兽最高星级
+pub const GC_MAX_MONSTER_STAR: u8 = 3;
+
+// =============================================================================
+// 规则集
+// =============================================================================
+
+/// 规则修改器
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data")]
+pub enum GcRulesetModifier {
+    /// 商店购买的怪兽提升星级
+    MonsterStarBonus { amount: u8 },
+    /// 刷新商店免费
+    FreeRefresh,
+    /// 所有阵容使用指定地形
+    ForcedTerrain { terrain: GcTerrainType },
+    /// 开局额外金币
+    StartingGoldBonus { amount: u32 },
+    /// 开局生命值
+    StartingHealth { health: u32 },
+    /// 商店定价 (基础价格表、最低价、促销间隔)
+    ShopPricing { pricing: GcShopPricing },
+    /// 商店防重复 (连续刷新中同一模板的出现次数上限)
+    ShopVariety { rule: GcShopVarietyRule },
+}
+
+/// 规则集
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcRuleset {
+    /// 规则集ID
+    pub id: String,
+    /// 名称
+    pub name: String,
+    /// 说明
+    #[serde(default)]
+    pub description: String,
+    /// 修改器
+    pub modifiers: Vec<GcRulesetModifier>,
+    /// 难度设置
+    #[serde(default)]
+    pub difficulty: GcDifficultySettings,
+}
+
+impl GcRuleset {
+    /// 创建规则集
+    pub fn gc_new(id: &str, name: &str, modifiers: Vec<GcRulesetModifier>) -> Self {
+        Self {
+            id: id.to_string(),
+            name: name.to_string(),
+            description: String::new(),
+            modifiers,
+            difficulty: GcDifficultySettings::default(),
+        }
+    }
+
+    /// 设置难度
+    pub fn gc_with_difficulty(mut self, difficulty: GcDifficultySettings) -> Self {
+        self.difficulty = difficulty;
+        self
+    }
+
+    /// 购买怪兽的星级加成
+    pub fn gc_star_bonus(&self) -> u8 {
+        self.modifiers.iter()
+            .map(|m| match m {
+                GcRulesetModifier::MonsterStarBonus { amount } => *amount,
+                _ => 0,
+            })
+            .sum()
+    }
+
+    /// 刷新是否免费
+    pub fn gc_free_refresh(&self) -> bool {
+        self.modifiers.contains(&GcRulesetModifier::FreeRefresh)
+    }
+
+    /// 强制地形
+    pub fn gc_forced_terrain(&self) -> Option<GcTerrainType> {
+        self.modifiers.iter().find_map(|m| match m {
+            GcRulesetModifier::ForcedTerrain { terrain } => Some(*terrain),
+            _ => None,
+        })
+    }
+
+    /// 对购买的怪兽应用修改器
+    pub fn gc_apply_to_bought(&self, monster: &mut GcMonster) {
+        monster.star = monster.star.saturating_add(self.gc_star_bonus()).min(GC_MAX_MONSTER_STAR);
+    }
+
+    /// 对局创建时应用修改器
+    pub fn gc_apply_to_tavern(&self, game: &mut GcTavernGame) {
+        for seat in &mut game.seats {
+            for modifier in &self.modifiers {
+                match modifier {
+                    GcRulesetModifier::ForcedTerrain { terrain } => seat.board.player_terrain = *terrain,
+                    GcRulesetModifier::StartingGoldBonus { amount } => seat.player.economy.earn(*amount),
+                    GcRulesetModifier::StartingHealth { health } => seat.health = (*health).max(1),
+                    GcRulesetModifier::ShopPricing { pricing } => seat.shop.pricing = pricing.clone(),
+                    GcRulesetModifier::ShopVariety { rule } => seat.shop.variety = Some(*rule),
+                    GcRulesetModifier::MonsterStarBonus { .. } | GcRulesetModifier::FreeRefresh => {}
+                }
+            }
+        }
+        game.ruleset = Some(self.clone());
+    }
+}
+
+// =============================================================================
+// 难度
+// =============================================================================
+
+/// 难度预设
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum GcDifficulty {
+    /// 剧情: 敌人较弱，经验略少
+    Story,
+    /// 普通
+    #[default]
+    Normal,
+    /// 困难: 敌人更强，掉落与经验更多
+    Hard,
+}
+
+impl GcDifficulty {
+    /// 显示名称
+    pub fn gc_name(&self) -> &'static str {
+        match self {
+            GcDifficulty::Story => "剧情",
+            GcDifficulty::Normal => "普通",
+            GcDifficulty::Hard => "困难",
+        }
+    }
+
+    /// 存储用 ID (与 JSON 序列化名称相同)
+    pub fn gc_id(&self) -> &'static str {
+        match self {
+            GcDifficulty::Story => "Story",
+            GcDifficulty::Normal => "Normal",
+            GcDifficulty::Hard => "Hard",
+        }
+    }
+
+    /// 从存储 ID 解析 (未知值为普通)
+    pub fn gc_from_id(id: &str) -> Self {
+        match id {
+            "Story" => GcDifficulty::Story,
+            "Hard" => GcDifficulty::Hard,
+            _ => GcDifficulty::Normal,
+        }
+    }
+
+    /// 敌人属性倍率 (百分比)
+    pub fn gc_enemy_stat_percent(&self) -> u32 {
+        match self {
+            GcDifficulty::Story => 70,
+            GcDifficulty::Normal => 100,
+            GcDifficulty::Hard => 150,
+        }
+    }
+
+    /// 掉落数量倍率 (百分比)
+    pub fn gc_loot_percent(&self) -> u32 {
+        match self {
+            GcDifficulty::Story | GcDifficulty::Normal => 100,
+            GcDifficulty::Hard => 150,
+        }
+    }
+
+    /// 经验倍率 (百分比)
+    pub fn gc_xp_percent(&self) -> u32 {
+        match self {
+            GcDifficulty::Story => 75,
+            GcDifficulty::Normal => 100,
+            GcDifficulty::Hard => 125,
+        }
+    }
+}
+
+/// 难度设置 (每个存档单独选择)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcDifficultySettings {
+    /// 难度预设
+    #[serde(default)]
+    pub difficulty: GcDifficulty,
+    /// 敌人等级是否随玩家等级缩放 (不低于玩家等级)
+    #[serde(default)]
+    pub level_scaling: bool,
+}
+
+impl GcDifficultySettings {
+    /// 创建难度设置
+    pub fn gc_new(difficulty: GcDifficulty, level_scaling: bool) -> Self {
+        Self { difficulty, level_scaling }
+    }
+
+    /// 敌人等级 (开启缩放时提升到玩家等级)
+    pub fn gc_scale_enemy_level(&self, level: u32, player_level: u32) -> u32 {
+        if self.level_scaling {
+            level.max(player_level)
+        } else {
+            level
+        }
+    }
+
+    /// 敌人属性 (攻击、防御、生命)
+    pub fn gc_scale_enemy_stat(&self, value: u32) -> u32 {
+        gc_percent_of(value, self.difficulty.gc_enemy_stat_percent())
+    }
+
+    /// 掉落数量 (原本有掉落时至少 1)
+    pub fn gc_scale_loot(&self, quantity: u32) -> u32 {
+        if quantity == 0 {
+            return 0;
+        }
+        gc_percent_of(quantity, self.difficulty.gc_loot_percent()).max(1)
+    }
+
+    /// 经验
+    pub fn gc_scale_xp(&self, xp: u32) -> u32 {
+        gc_percent_of(xp, self.difficulty.gc_xp_percent())
+    }
+
+    /// 对敌方怪兽应用属性倍率
+    pub fn gc_apply_to_monster(&self, monster: &mut GcMonster) {
+        monster.base_atk = self.gc_scale_enemy_stat(monster.base_atk);
+        monster.base_def = self.gc_scale_enemy_stat(monster.base_def);
+        monster.max_hp = self.gc_scale_enemy_stat(monster.max_hp).max(1);
+        monster.current_hp = self.gc_scale_enemy_stat(monster.current_hp).min(monster.max_hp);
+    }
+}
+
+// =============================================================================
+// 每周轮换
+// =============================================================================
+
+/// 每周挑战轮换 (服务器提供)
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcWeeklyRotation {
+    /// 第 0 周开始时间 (Unix 时间戳)
+    pub epoch_start: u64,
+    /// 按周轮换的规则集
+    pub rulesets: Vec<GcRuleset>,
+}
+
+impl GcWeeklyRotation {
+    /// 验证定义
+    pub fn gc_validate(&self) -> Result<(), String> {
+        if self.rulesets.is_empty() {
+            return Err("每周挑战至少需要一个规则集".to_string());
+        }
+        for (i, ruleset) in self.rulesets.iter().enumerate() {
+            if self.rulesets[..i].iter().any(|r| r.id == ruleset.id) {
+                return Err(format!("规则集ID重复: {}", ruleset.id));
+            }
+            if ruleset.modifiers.is_empty() {
+                return Err(format!("规则集 {} 没有修改器", ruleset.id));
+            }
+        }
+        Ok(())
+    }
+
+    /// 当前周序号 (未到第 0 周时为 0)
+    pub fn gc_week_index(&self, now: u64) -> u64 {
+        now.saturating_sub(self.epoch_start) / GC_WEEK_SECS
+    }
+
+    /// 指定周的规则集
+    pub fn gc_ruleset_for_week(&self, week: u64) -> Option<&GcRuleset> {
+        if self.rulesets.is_empty() {
+            return None;
+        }
+        self.rulesets.get((week % self.rulesets.len() as u64) as usize)
+    }
+
+    /// 本周规则集
+    pub fn gc_current(&self, now: u64) -> Option<&GcRuleset> {
+        self.gc_ruleset_for_week(self.gc_week_index(now))
+    }
+}
+
+/// 默认每周轮换
+pub fn gc_default_weekly_rotation() -> GcWeeklyRotation {
+    GcWeeklyRotation {
+        epoch_start: 0,
+        rulesets: vec![
+            GcRuleset::gc_new("weekly_star_rush", "星光涌动", vec![GcRulesetModifier::MonsterStarBonus { amount: 1 }]),
+            GcRuleset::gc_new("weekly_free_refresh", "免费刷新", vec![GcRulesetModifier::FreeRefresh]),
+            GcRuleset::gc_new("weekly_swamp", "遍地沼泽", vec![
+                GcRulesetModifier::ForcedTerrain { terrain: GcTerrainType::Swamp },
+                GcRulesetModifier::StartingGoldBonus { amount: 3 },
+            ]),
+        ],
+    }
+}
+
+// =============================================================================
+// 每周排行
+// =============================================================================
+
+/// 每周挑战成绩
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcWeeklyEntry {
+    /// 玩家ID
+    pub player_id: String,
+    /// 参与局数
+    pub games: u32,
+    /// 最好名次
+    pub best_placement: u8,
+    /// 累计积分
+    pub points: u32,
+    /// 最高流程得分
+    #[serde(default)]
+    pub best_score: u32,
+}
+
+/// 每周挑战排行
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcWeeklyLeaderboard {
+    /// 周序号
+    pub week: u64,
+    /// 成绩
+    pub entries: Vec<GcWeeklyEntry>,
+}
+
+impl GcWeeklyLeaderboard {
+    /// 进入新的一周时清空
+    pub fn gc_roll_week(&mut self, week: u64) {
+        if self.week != week {
+            self.week = week;
+            self.entries.clear();
+        }
+    }
+
+    /// 记录一局成绩 (积分 = 人数 - 名次 + 1)
+    pub fn gc_record(&mut self, player_id: &str, placement: u8, player_count: usize) {
+        let points = (player_count as u32 + 1).saturating_sub(placement as u32);
+        match self.entries.iter_mut().find(|e| e.player_id == player_id) {
+            Some(entry) => {
+                entry.games += 1;
+                entry.best_placement = entry.best_placement.min(placement);
+                entry.points += points;
+            }
+            None => self.entries.push(GcWeeklyEntry {
+                player_id: player_id.to_string(),
+                games: 1,
+                best_placement: placement,
+                points,
+                best_score: 0,
+            }),
+        }
+    }
+
+    /// 记录流程得分 (只保留最高分，须先记录名次)
+    pub fn gc_record_score(&mut self, player_id: &str, score: u32) {
+        if let Some(entry) = self.entries.iter_mut().find(|e| e.player_id == player_id) {
+            entry.best_score = entry.best_score.max(score);
+        }
+    }
+
+    /// 排行 (积分降序，同分按最好名次，再按最高得分)
+    pub fn gc_ranking(&self) -> Vec<GcWeeklyEntry> {
+        let mut ranking = self.entries.clone();
+        ranking.sort_by(|a, b| {
+            b.points.cmp(&a.points)
+                .then(a.best_placement.cmp(&b.best_placement))
+                .then(b.best_score.cmp(&a.best_score))
+        });
+        ranking
+    }
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GcMonsterAttribute, GcTavernAction};
+
+    fn players() -> Vec<(String, String)> {
+        vec![("p1".to_string(), "玩家1".to_string()), ("p2".to_string(), "玩家2".to_string())]
+    }
+
+    #[test]
+    fn test_rotation_parses_and_rotates() {
+        let json = r#"{"epoch_start":1000,"rulesets":[
+            {"id":"a","name":"A","modifiers":[{"type":"FreeRefresh"}]},
+            {"id":"b","name":"B","modifiers":[{"type":"MonsterStarBonus","data":{"amount":1}}]}
+        ]}"#;
+        let rotation: GcWeeklyRotation = serde_json::from_str(json).unwrap();
+        assert!(rotation.gc_validate().is_ok());
+        assert_eq!(rotation.gc_current(1000).map(|r| r.id.as_str()), Some("a"));
+        assert_eq!(rotation.gc_current(1000 + GC_WEEK_SECS).map(|r| r.id.as_str()), Some("b"));
+        assert_eq!(rotation.gc_current(1000 + 2 * GC_WEEK_SECS).map(|r| r.id.as_str()), Some("a"));
+        assert!(gc_default_weekly_rotation().gc_validate().is_ok());
+    }
+
+    #[test]
+    fn test_modifiers_apply_to_tavern() {
+        let swamp = GcRuleset::gc_new("swamp", "沼泽", vec![
+            GcRulesetModifier::ForcedTerrain { terrain: GcTerrainType::Swamp },
+            GcRulesetModifier::FreeRefresh,
+            GcRulesetModifier::MonsterStarBonus { amount: 1 },
+        ]);
+        let mut game = GcTavernGame::gc_new(&players());
+        swamp.gc_apply_to_tavern(&mut game);
+        assert!(game.seats.iter().all(|s| s.board.player_terrain == GcTerrainType::Swamp));
+
+        // 免费刷新: 没有金币也能刷新
+        game.seats[0].player.economy.gold = 0;
+        assert!(game.gc_apply_action("p1", &GcTavernAction::Refresh, &[1, 2, 3]).is_ok());
+
+        // 购买的怪兽 +1 星
+        let seat = game.gc_seat_mut("p1").unwrap();
+        seat.player.economy.gold = 10;
+        seat.shop.slots[0] = Some(GcMonster::new("m1", "史莱姆", 1, GcMonsterAttribute::Water, 50, 30, 60));
+        let seat = game.gc_apply_action("p1", &GcTavernAction::Buy { slot: 0 }, &[]).unwrap();
+        assert_eq!(seat.player.bench[0].star, 2);
+
+        let rule = GcShopVarietyRule { window: 3, max_appearances: 2 };
+        GcRuleset::gc_new("variety", "多样商店", vec![GcRulesetModifier::ShopVariety { rule }]).gc_apply_to_tavern(&mut game);
+        assert!(game.seats.iter().all(|s| s.shop.variety == Some(rule)));
+    }
+
+    #[test]
+    fn test_weekly_leaderboard() {
+        let mut board = GcWeeklyLeaderboard::default();
+        board.gc_roll_week(3);
+        board.gc_record("p1", 2, 4);
+        board.gc_record("p2", 1, 4);
+        board.gc_record("p1", 1, 4);
+
+        let ranking = board.gc_ranking();
+        assert_eq!(ranking[0].player_id, "p1");
+        assert_eq!(ranking[0].points, 7);
+        assert_eq!(ranking[0].best_placement, 1);
+
+        // 同积分同名次时按最高得分
+        board.gc_record("p3", 1, 2);
+        board.gc_record("p4", 1, 2);
+        board.gc_record_score("p4", 900);
+        board.gc_record_score("p4", 300);
+        board.gc_record_score("nobody", 100);
+        let ranking = board.gc_ranking();
+        assert_eq!((ranking[2].player_id.as_str(), ranking[2].best_score), ("p4", 900));
+        assert_eq!(ranking[3].player_id, "p3");
+        assert_eq!(board.entries.len(), 4);
+
+        board.gc_roll_week(4);
+        assert!(board.entries.is_empty());
+    }
+
+    #[test]
+    fn test_difficulty_presets() {
+        let story = GcDifficultySettings::gc_new(GcDifficulty::Story, false);
+        let hard = GcDifficultySettings::gc_new(GcDifficulty::Hard, true);
+        assert_eq!(story.gc_scale_enemy_stat(100), 70);
+        assert_eq!(hard.gc_scale_enemy_stat(100), 150);
+        assert_eq!(story.gc_scale_loot(1), 1);
+        assert_eq!(hard.gc_scale_loot(3), 4);
+        assert_eq!(story.gc_scale_xp(100), 75);
+        assert_eq!(story.gc_scale_enemy_level(2, 8), 2);
+        assert_eq!(hard.gc_scale_enemy_level(2, 8), 8);
+        assert_eq!(hard.gc_scale_enemy_level(10, 8), 10);
+
+        let mut monster = GcMonster::new("m1", "史莱姆", 1, GcMonsterAttribute::Water, 50, 30, 60);
+        story.gc_apply_to_monster(&mut monster);
+        assert_eq!((monster.base_atk, monster.base_def, monster.max_hp, monster.current_hp), (35, 21, 42, 42));
+
+        let ruleset: GcRuleset = serde_json::from_str(r#"{"id":"r","name":"r","modifiers":[]}"#)
+            .unwrap_or_default();
+        assert_eq!(ruleset.difficulty, GcDifficultySettings::default());
+        for difficulty in [GcDifficulty::Story, GcDifficulty::Normal, GcDifficulty::Hard] {
+            let json = serde_json::to_string(&difficulty).unwrap_or_default();
+            assert_eq!(json, format!("\"{}\"", difficulty.gc_id()));
+            assert_eq!(GcDifficulty::gc_from_id(difficulty.gc_id()), difficulty);
+        }
+    }
+}
@@ -0,0 +1,203 @@
+//! 流程结算与得分
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! 酒馆对局/冒险流程结束时的结算摘要: 存活回合、经济效率、阵容强度、造成伤害四项得分及明细
+//!
+//! ## 规则
+//! - 存活得分 = 存活回合 × 50
+//! - 经济得分 = 最终阵容战力 × 10 / 花费金币 (未花费金币时按 1 计)，上限 500
+//! - 阵容得分 = 最终阵容星级之和 × 20 + 阵容战力 / 10
+//! - 伤害得分 = 对对手造成的伤害 × 5
+//! - 总分为四项之和；阵容取座位最终阵容 (冒险取最后一回合记录的阵容)
+//! - 酒馆座位在对局中累计统计 (`GcRunStats`)；冒险的伤害与花费由调用方提供
+
+use serde::{Deserialize, Serialize};
+
+use crate::{gc_ghost_units, GcGhostMode, GcGhostRun, GcGhostUnit, GcTavernGame};
+
+/// 每存活一回合的得分
+pub const GC_RUN_SCORE_PER_ROUND: u32 = 50;
+
+/// 经济得分上限
+pub const GC_RUN_SCORE_ECONOMY_MAX: u32 = 500;
+
+/// 每颗星的阵容得分
+pub const GC_RUN_SCORE_PER_STAR: u32 = 20;
+
+/// 每点伤害的得分
+pub const GC_RUN_SCORE_PER_DAMAGE: u32 = 5;
+
+// =============================================================================
+// 流程统计
+// =============================================================================
+
+/// 流程累计统计 (酒馆座位随对局更新)
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcRunStats {
+    /// 存活回合 (参与结算的回合数)
+    pub rounds_survived: u32,
+    /// 胜场
+    pub wins: u32,
+    /// 花费金币 (购买、刷新、法术)
+    pub gold_spent: u32,
+    /// 对对手造成的伤害
+    pub damage_dealt: u32,
+}
+
+// =============================================================================
+// 得分
+// =============================================================================
+
+/// 得分明细
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcRunScoreBreakdown {
+    /// 存活回合
+    pub rounds_survived: u32,
+    /// 存活得分
+    pub survival_points: u32,
+    /// 花费金币
+    pub gold_spent: u32,
+    /// 最终阵容战力 (攻击 + 生命之和)
+    pub board_power: u32,
+    /// 经济得分
+    pub economy_points: u32,
+    /// 最终阵容星级之和
+    pub board_stars: u32,
+    /// 阵容得分
+    pub composition_points: u32,
+    /// 造成伤害
+    pub damage_dealt: u32,
+    /// 伤害得分
+    pub damage_points: u32,
+}
+
+/// 流程结算摘要
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcRunScore {
+    /// 流程类型
+    pub mode: GcGhostMode,
+    /// 玩家 ID
+    pub player_id: String,
+    /// 最终名次 (冒险或未结束时为空)
+    #[serde(default)]
+    pub placement: Option<u8>,
+    /// 胜场
+    pub wins: u32,
+    /// 得分明细
+    pub breakdown: GcRunScoreBreakdown,
+    /// 总分
+    pub total: u32,
+}
+
+impl GcRunScore {
+    /// 按统计与最终阵容计算得分
+    pub fn gc_compute(mode: GcGhostMode, player_id: &str, stats: &GcRunStats, units: &[GcGhostUnit]) -> Self {
+        let board_power = units.iter().fold(0u32, |sum, u| sum.saturating_add(u.atk).saturating_add(u.hp));
+        let board_stars: u32 = units.iter().map(|u| u.star as u32).sum();
+
+        let breakdown = GcRunScoreBreakdown {
+            rounds_survived: stats.rounds_survived,
+            survival_points: stats.rounds_survived.saturating_mul(GC_RUN_SCORE_PER_ROUND),
+            gold_spent: stats.gold_spent,
+            board_power,
+            economy_points: (board_power.saturating_mul(10) / stats.gold_spent.max(1)).min(GC_RUN_SCORE_ECONOMY_MAX),
+            board_stars,
+            composition_points: board_stars.saturating_mul(GC_RUN_SCORE_PER_STAR).saturating_add(board_power / 10),
+            damage_dealt: stats.damage_dealt,
+            damage_points: stats.damage_dealt.saturating_mul(GC_RUN_SCORE_PER_DAMAGE),
+        };
+        let total = breakdown.survival_points
+            .saturating_add(breakdown.economy_points)
+            .saturating_add(breakdown.composition_points)
+            .saturating_add(breakdown.damage_points);
+
+        Self {
+            mode,
+            player_id: player_id.to_string(),
+            placement: None,
+            wins: stats.wins,
+            breakdown,
+            total,
+        }
+    }
+
+    /// 酒馆座位的结算 (取座位最终阵容与名次)
+    pub fn gc_from_tavern(game: &GcTavernGame, player_id: &str) -> Option<Self> {
+        let seat = game.gc_seat(player_id)?;
+        let mut score = Self::gc_compute(GcGhostMode::Tavern, player_id, &seat.run_stats, &gc_ghost_units(&seat.board));
+        score.placement = seat.placement;
+        Some(score)
+    }
+
+    /// 酒馆对局所有座位的结算 (按名次排序，未定名次的在后)
+    pub fn gc_from_tavern_game(game: &GcTavernGame) -> Vec<Self> {
+        let mut scores: Vec<Self> = game.seats.iter()
+            .filter_map(|s| Self::gc_from_tavern(game, &s.player.id))
+            .collect();
+        scores.sort_by_key(|s| (s.placement.is_none(), s.placement));
+        scores
+    }
+
+    /// 冒险/单人流程的结算 (回合与胜场取自幽灵记录)
+    ///
+    /// - `gold_spent`、`damage_dealt`: 流程中累计的花费与伤害
+    pub fn gc_from_ghost_run(run: &GcGhostRun, gold_spent: u32, damage_dealt: u32) -> Self {
+        let last = run.rounds.last();
+        let stats = GcRunStats {
+            rounds_survived: last.map_or(0, |r| r.round),
+            wins: run.gc_wins_through(u32::MAX),
+            gold_spent,
+            damage_dealt,
+        };
+        let units = last.map(|r| r.units.as_slice()).unwrap_or_default();
+        Self::gc_compute(run.mode, &run.player_id, &stats, units)
+    }
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GcArenaPosition, GcGhostRound};
+
+    fn unit(star: u8, atk: u32, hp: u32) -> GcGhostUnit {
+        GcGhostUnit { template_id: "slime".to_string(), star, atk, hp, position: GcArenaPosition::front(0) }
+    }
+
+    #[test]
+    fn test_score_breakdown() {
+        let stats = GcRunStats { rounds_survived: 8, wins: 5, gold_spent: 40, damage_dealt: 12 };
+        let score = GcRunScore::gc_compute(GcGhostMode::Tavern, "p1", &stats, &[unit(2, 60, 140), unit(1, 30, 70)]);
+        let b = &score.breakdown;
+        assert_eq!(b.survival_points, 400);
+        assert_eq!(b.board_power, 300);
+        assert_eq!(b.economy_points, 75);
+        assert_eq!((b.board_stars, b.composition_points), (3, 90));
+        assert_eq!(b.damage_points, 60);
+        assert_eq!(score.total, 400 + 75 + 90 + 60);
+
+        // 未花费金币时经济得分封顶
+        let free = GcRunScore::gc_compute(GcGhostMode::Tavern, "p1", &GcRunStats::default(), &[unit(3, 900, 900)]);
+        assert_eq!(free.breakdown.economy_points, GC_RUN_SCORE_ECONOMY_MAX);
+        assert_eq!(GcRunScore::gc_compute(GcGhostMode::Tavern, "p1", &GcRunStats::default(), &[]).total, 0);
+    }
+
+    #[test]
+    fn test_adventure_score_from_ghost_run() {
+        let mut run = GcGhostRun::gc_new(GcGhostMode::Adventure, "p1", "玩家");
+        run.gc_record_round(GcGhostRound { round: 1, units: vec![unit(1, 30, 70)], health: 40, won: Some(true) });
+        run.gc_record_round(GcGhostRound { round: 2, units: vec![unit(2, 60, 140)], health: 30, won: Some(false) });
+
+        let score = GcRunScore::gc_from_ghost_run(&run, 20, 4);
+        assert_eq!(score.mode, GcGhostMode::Adventure);
+        assert_eq!((score.wins, score.breakdown.rounds_survived), (1, 2));
+        assert_eq!(score.breakdown.board_stars, 2);
+        assert_eq!(score.placement, None);
+    }
+}
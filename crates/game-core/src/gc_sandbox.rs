@@ -0,0 +1,187 @@
+//! 练习沙盒 (任意编辑战斗状态)
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! ## 规则
+//! - 只有 `sandbox` 标记为 true 的战斗才接受沙盒命令，正式对局一律拒绝
+//! - 命令通过安全接口修改状态 (生命、能量、手牌、地形、跳过回合)，不直接改 JSON
+//! - 数值会被限制在合法范围内 (生命不超过上限，手牌不超过上限)
+//! - 修改生命后重新检查战斗是否结束
+
+use serde::{Deserialize, Serialize};
+
+use crate::{GcBattlePhase, GcBattleState, GcConfig, GcError, GcPlayerState, GcTerrainType, gc_get_card_template};
+
+// =============================================================================
+// 沙盒命令
+// =============================================================================
+
+/// 沙盒命令
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data")]
+pub enum GcSandboxCommand {
+    /// 设置生命值 (超过上限时同时提高上限)
+    SetHp { player_id: String, hp: u32 },
+    /// 设置能量
+    SetEnergy { player_id: String, energy: u32 },
+    /// 设置行动力
+    SetActionPoints { player_id: String, action_points: u32 },
+    /// 将任意模板卡牌加入手牌
+    AddCardToHand { player_id: String, template_id: String },
+    /// 清空手牌
+    ClearHand { player_id: String },
+    /// 强制地形 (None 恢复默认)
+    ForceTerrain { terrain: Option<GcTerrainType> },
+    /// 跳过回合 (不结算战场战斗)
+    SkipTurn { count: u32 },
+}
+
+/// 执行沙盒命令
+pub fn gc_apply_sandbox_command(state: &mut GcBattleState, command: &GcSandboxCommand) -> Result<(), GcError> {
+    if !state.sandbox {
+        return Err(GcError::GcInvalidAction("非沙盒战斗不能编辑状态".to_string()));
+    }
+
+    match command {
+        GcSandboxCommand::SetHp { player_id, hp } => {
+            let player = state.gc_find_player_mut(player_id).ok_or(GcError::GcPlayerNotFound)?;
+            player.stats.max_hp = player.stats.max_hp.max(*hp);
+            player.stats.hp = *hp;
+            if *hp > 0 && player.state == GcPlayerState::Dead {
+                player.state = GcPlayerState::Alive;
+            }
+            gc_sandbox_refresh_result(state);
+        }
+        GcSandboxCommand::SetEnergy { player_id, energy } => {
+            let player = state.gc_find_player_mut(player_id).ok_or(GcError::GcPlayerNotFound)?;
+            player.stats.max_energy = player.stats.max_energy.max(*energy);
+            player.stats.energy = *energy;
+        }
+        GcSandboxCommand::SetActionPoints { player_id, action_points } => {
+            let player = state.gc_find_player_mut(player_id).ok_or(GcError::GcPlayerNotFound)?;
+            player.stats.max_action_points = player.stats.max_action_points.max(*action_points);
+            player.stats.action_points = *action_points;
+        }
+        GcSandboxCommand::AddCardToHand { player_id, template_id } => {
+            let player = state.gc_find_player_mut(player_id).ok_or(GcError::GcPlayerNotFound)?;
+            if player.gc_is_hand_full() {
+                return Err(GcError::GcHandFull);
+            }
+            let mut card = gc_get_card_template(template_id).ok_or(GcError::GcCardNotFound)?;
+            let serial = player.hand.len() + player.deck.len() + player.discard.len();
+            card.id = format!("sandbox_{}_{}_{}", player_id, template_id, serial);
+            player.hand.push(card);
+        }
+        GcSandboxCommand::ClearHand { player_id } => {
+            let player = state.gc_find_player_mut(player_id).ok_or(GcError::GcPlayerNotFound)?;
+            player.hand.clear();
+        }
+        GcSandboxCommand::ForceTerrain { terrain } => {
+            state.forced_terrain = *terrain;
+        }
+        GcSandboxCommand::SkipTurn { count } => {
+            if state.players.is_empty() {
+                return Err(GcError::GcBattleNotStarted);
+            }
+            for _ in 0..*count {
+                gc_sandbox_skip_turn(state);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// 跳过当前回合: 切换到下一名玩家并恢复其能量、行动力并抽牌
+fn gc_sandbox_skip_turn(state: &mut GcBattleState) {
+    state.current_player_index = (state.current_player_index + 1) % state.players.len();
+    if state.current_player_index == 0 {
+        state.turn += 1;
+    }
+    state.played_this_turn.clear();
+
+    let player = &mut state.players[state.current_player_index];
+    player.stats.energy = player.stats.max_energy;
+    player.stats.gc_reset_action_points();
+    player.gc_draw_cards(GcConfig::DRAW_PER_TURN);
+}
+
+/// 生命变化后重新判定胜负 (沙盒可以把结束的战斗"复活")
+fn gc_sandbox_refresh_result(state: &mut GcBattleState) {
+    if state.phase == GcBattlePhase::Finished {
+        state.phase = GcBattlePhase::Playing;
+        state.winner_id = None;
+    }
+    if state.players.len() >= 2 {
+        state.gc_check_battle_end();
+    }
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GcPlayer;
+
+    fn create_sandbox() -> GcBattleState {
+        let mut state = GcBattleState::gc_new("sandbox", vec![
+            GcPlayer::gc_new("p1", "玩家1"),
+            GcPlayer::gc_new("p2", "玩家2"),
+        ]);
+        state.phase = GcBattlePhase::Playing;
+        state.sandbox = true;
+        state
+    }
+
+    #[test]
+    fn test_rejected_outside_sandbox() {
+        let mut state = create_sandbox();
+        state.sandbox = false;
+        let command = GcSandboxCommand::SetHp { player_id: "p1".to_string(), hp: 1 };
+        assert!(gc_apply_sandbox_command(&mut state, &command).is_err());
+        assert_eq!(state.players[0].stats.hp, GcConfig::DEFAULT_HP);
+    }
+
+    #[test]
+    fn test_set_hp_and_revive() {
+        let mut state = create_sandbox();
+        let kill = GcSandboxCommand::SetHp { player_id: "p2".to_string(), hp: 0 };
+        gc_apply_sandbox_command(&mut state, &kill).unwrap();
+        assert!(state.gc_is_finished());
+        assert_eq!(state.winner_id.as_deref(), Some("p1"));
+
+        let revive = GcSandboxCommand::SetHp { player_id: "p2".to_string(), hp: 500 };
+        gc_apply_sandbox_command(&mut state, &revive).unwrap();
+        assert!(!state.gc_is_finished());
+        assert_eq!(state.players[1].stats.max_hp, 500);
+    }
+
+    #[test]
+    fn test_add_card_terrain_and_skip() {
+        let mut state = create_sandbox();
+        let add = GcSandboxCommand::AddCardToHand {
+            player_id: "p1".to_string(),
+            template_id: "card_knight_attack".to_string(),
+        };
+        gc_apply_sandbox_command(&mut state, &add).unwrap();
+        gc_apply_sandbox_command(&mut state, &add).unwrap();
+        assert_eq!(state.players[0].hand.len(), 2);
+        assert_ne!(state.players[0].hand[0].id, state.players[0].hand[1].id);
+
+        let unknown = GcSandboxCommand::AddCardToHand { player_id: "p1".to_string(), template_id: "nope".to_string() };
+        assert!(gc_apply_sandbox_command(&mut state, &unknown).is_err());
+
+        let json = r#"{"type":"ForceTerrain","data":{"terrain":"Swamp"}}"#;
+        let terrain: GcSandboxCommand = serde_json::from_str(json).unwrap();
+        gc_apply_sandbox_command(&mut state, &terrain).unwrap();
+        assert_eq!(state.forced_terrain, Some(GcTerrainType::Swamp));
+
+        gc_apply_sandbox_command(&mut state, &GcSandboxCommand::SkipTurn { count: 2 }).unwrap();
+        assert_eq!(state.current_player_index, 0);
+        assert_eq!(state.turn, 2);
+    }
+}
@@ -0,0 +1,123 @@
+//! 存档槽位 (同一账号多角色)
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! ## 槽位规则
+//! - 每个账号最多 `GC_MAX_SAVE_SLOTS` 个槽位，编号从 0 开始
+//! - 背包、职业、进度等存档数据本身不含槽位信息，槽位只作为存储键的一部分
+//! - 槽位 0 为默认槽位 (未指定槽位的旧接口读写槽位 0)
+//! - 复制槽位会覆盖目标槽位的全部数据
+
+use serde::{Deserialize, Serialize};
+
+use crate::{GcDifficulty, GcProfessionType};
+
+/// 每个账号的最大存档槽位数
+pub const GC_MAX_SAVE_SLOTS: u32 = 3;
+
+/// 默认槽位
+pub const GC_DEFAULT_SAVE_SLOT: u32 = 0;
+
+// =============================================================================
+// 槽位摘要
+// =============================================================================
+
+/// 存档槽位摘要 (选择角色界面显示)
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcSaveSlotSummary {
+    /// 槽位编号
+    pub slot: u32,
+    /// 角色名称 (尚未创建角色时为 None)
+    #[serde(default)]
+    pub name: Option<String>,
+    /// 职业 (未选择职业时为 None)
+    #[serde(default)]
+    pub profession: Option<GcProfessionType>,
+    /// 等级
+    pub level: u32,
+    /// 当前地图 (尚未保存进度时为 None)
+    #[serde(default)]
+    pub current_map: Option<String>,
+    /// 存档难度
+    #[serde(default)]
+    pub difficulty: GcDifficulty,
+    /// 最后保存时间 (Unix 秒)
+    #[serde(default)]
+    pub last_save_at: Option<i64>,
+}
+
+impl GcSaveSlotSummary {
+    /// 创建空槽位摘要
+    pub fn gc_new(slot: u32) -> Self {
+        Self {
+            slot,
+            name: None,
+            profession: None,
+            level: 1,
+            current_map: None,
+            difficulty: GcDifficulty::default(),
+            last_save_at: None,
+        }
+    }
+}
+
+// =============================================================================
+// 校验
+// =============================================================================
+
+/// 校验槽位编号
+pub fn gc_validate_save_slot(slot: u32) -> Result<u32, String> {
+    if slot < GC_MAX_SAVE_SLOTS {
+        Ok(slot)
+    } else {
+        Err(format!("存档槽位 {} 无效 (0~{})", slot, GC_MAX_SAVE_SLOTS - 1))
+    }
+}
+
+/// 校验槽位复制 (来源与目标不能相同)
+pub fn gc_validate_save_slot_copy(from: u32, to: u32) -> Result<(), String> {
+    gc_validate_save_slot(from)?;
+    gc_validate_save_slot(to)?;
+    if from == to {
+        return Err("来源槽位与目标槽位相同".to_string());
+    }
+    Ok(())
+}
+
+/// 第一个未使用的槽位 (全部占用时为 None)
+pub fn gc_first_free_save_slot(used: &[GcSaveSlotSummary]) -> Option<u32> {
+    (0..GC_MAX_SAVE_SLOTS).find(|slot| used.iter().all(|s| s.slot != *slot))
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_save_slots() {
+        assert_eq!(gc_validate_save_slot(GC_DEFAULT_SAVE_SLOT), Ok(0));
+        assert!(gc_validate_save_slot(GC_MAX_SAVE_SLOTS).is_err());
+        assert!(gc_validate_save_slot_copy(0, 1).is_ok());
+        assert!(gc_validate_save_slot_copy(1, 1).is_err());
+        assert!(gc_validate_save_slot_copy(0, GC_MAX_SAVE_SLOTS).is_err());
+    }
+
+    #[test]
+    fn test_first_free_slot() {
+        let mut used = vec![GcSaveSlotSummary::gc_new(0), GcSaveSlotSummary::gc_new(2)];
+        assert_eq!(gc_first_free_save_slot(&used), Some(1));
+        used.push(GcSaveSlotSummary::gc_new(1));
+        assert_eq!(gc_first_free_save_slot(&used), None);
+
+        let json = r#"{"slot":1,"level":4}"#;
+        let summary: GcSaveSlotSummary = serde_json::from_str(json).unwrap_or_else(|_| GcSaveSlotSummary::gc_new(9));
+        assert_eq!(summary.slot, 1);
+        assert_eq!(summary.difficulty, GcDifficulty::Normal);
+    }
+}
@@ -0,0 +1,508 @@
+//! 场景脚本 (卡牌行为验证)
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! ## 规则
+//! - 一行一条语句，也可用 `;` 分隔；`#` 之后为注释
+//! - 语句:
+//!   - `players p1 p2` 声明玩家 (可省略，默认 p1 p2，只能出现在第一条)
+//!   - `p1 plays card_knight_attack at p2` 出牌 (手牌中没有该模板时自动生成一张；省略 `at` 时打第一个存活的对手)
+//!   - `give p1 card_knight_attack` 加入手牌
+//!   - `set p1.hp = 50` 设置属性
+//!   - `p1 ends turn` 结束回合 (结算战场战斗、恢复能量并抽牌)
+//!   - `expect p2.hp == 80` 断言，比较符: `== != < <= > >=`
+//! - 可断言: `<玩家>.hp|max_hp|attack|defense|energy|action_points|hand|deck|discard`、`turn`、`winner` (`none` 表示无)
+//! - 操作失败会中止执行；断言失败只记录，继续执行后续语句
+
+use serde::{Deserialize, Serialize};
+
+use crate::{GcBattlePhase, GcBattleState, GcConfig, GcPlayer, gc_execute_play_card, gc_get_card_template};
+
+// =============================================================================
+// 语法
+// =============================================================================
+
+/// 玩家属性
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GcScenarioField {
+    Hp,
+    MaxHp,
+    Attack,
+    Defense,
+    Energy,
+    ActionPoints,
+    Hand,
+    Deck,
+    Discard,
+}
+
+impl GcScenarioField {
+    /// 从名称解析
+    pub fn gc_parse(name: &str) -> Option<Self> {
+        match name {
+            "hp" => Some(Self::Hp),
+            "max_hp" => Some(Self::MaxHp),
+            "attack" => Some(Self::Attack),
+            "defense" => Some(Self::Defense),
+            "energy" => Some(Self::Energy),
+            "action_points" => Some(Self::ActionPoints),
+            "hand" => Some(Self::Hand),
+            "deck" => Some(Self::Deck),
+            "discard" => Some(Self::Discard),
+            _ => None,
+        }
+    }
+
+    /// 是否可以通过 `set` 修改 (牌堆数量只读)
+    pub fn gc_is_settable(&self) -> bool {
+        !matches!(self, Self::Hand | Self::Deck | Self::Discard)
+    }
+}
+
+/// 断言/赋值的对象
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GcScenarioPath {
+    /// 玩家属性
+    Player { player_id: String, field: GcScenarioField },
+    /// 当前回合数
+    Turn,
+    /// 获胜者
+    Winner,
+}
+
+/// 比较符
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GcScenarioOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl GcScenarioOp {
+    fn gc_parse(token: &str) -> Option<Self> {
+        match token {
+            "==" => Some(Self::Eq),
+            "!=" => Some(Self::Ne),
+            "<" => Some(Self::Lt),
+            "<=" => Some(Self::Le),
+            ">" => Some(Self::Gt),
+            ">=" => Some(Self::Ge),
+            _ => None,
+        }
+    }
+
+    fn gc_symbol(&self) -> &str {
+        match self {
+            Self::Eq => "==",
+            Self::Ne => "!=",
+            Self::Lt => "<",
+            Self::Le => "<=",
+            Self::Gt => ">",
+            Self::Ge => ">=",
+        }
+    }
+
+    fn gc_compare(&self, actual: u64, expected: u64) -> bool {
+        match self {
+            Self::Eq => actual == expected,
+            Self::Ne => actual != expected,
+            Self::Lt => actual < expected,
+            Self::Le => actual <= expected,
+            Self::Gt => actual > expected,
+            Self::Ge => actual >= expected,
+        }
+    }
+}
+
+/// 场景动作
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GcScenarioAction {
+    /// 出牌
+    Play { player_id: String, template_id: String, target_id: Option<String> },
+    /// 加入手牌
+    Give { player_id: String, template_id: String },
+    /// 设置玩家属性
+    Set { player_id: String, field: GcScenarioField, value: u32 },
+    /// 结束回合
+    EndTurn { player_id: String },
+    /// 数值断言
+    Expect { path: GcScenarioPath, op: GcScenarioOp, value: u64 },
+    /// 获胜者断言 (None 表示没有获胜者)
+    ExpectWinner { player_id: Option<String> },
+}
+
+/// 一条语句
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcScenarioStep {
+    /// 行号 (从 1 开始)
+    pub line: usize,
+    /// 原文
+    pub source: String,
+    /// 动作
+    pub action: GcScenarioAction,
+}
+
+/// 场景脚本
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcScenario {
+    /// 玩家ID
+    pub players: Vec<String>,
+    /// 语句
+    pub steps: Vec<GcScenarioStep>,
+}
+
+// =============================================================================
+// 解析
+// =============================================================================
+
+/// 解析 `p1.hp` 形式的属性
+fn gc_parse_player_field(token: &str) -> Result<(String, GcScenarioField), String> {
+    let (player_id, field) = token.split_once('.')
+        .ok_or_else(|| format!("应为 <玩家>.<属性>: {}", token))?;
+    let field = GcScenarioField::gc_parse(field)
+        .ok_or_else(|| format!("未知属性: {}", field))?;
+    Ok((player_id.to_string(), field))
+}
+
+/// 解析一条语句
+fn gc_parse_statement(tokens: &[&str]) -> Result<GcScenarioAction, String> {
+    match tokens {
+        ["give", player_id, template_id] => Ok(GcScenarioAction::Give {
+            player_id: player_id.to_string(),
+            template_id: template_id.to_string(),
+        }),
+        ["set", path, "=", value] => {
+            let (player_id, field) = gc_parse_player_field(path)?;
+            if !field.gc_is_settable() {
+                return Err(format!("属性不可修改: {}", path));
+            }
+            let value = value.parse().map_err(|_| format!("无效数值: {}", value))?;
+            Ok(GcScenarioAction::Set { player_id, field, value })
+        }
+        [player_id, "ends", "turn"] => Ok(GcScenarioAction::EndTurn { player_id: player_id.to_string() }),
+        [player_id, "plays", template_id] => Ok(GcScenarioAction::Play {
+            player_id: player_id.to_string(),
+            template_id: template_id.to_string(),
+            target_id: None,
+        }),
+        [player_id, "plays", template_id, "at", target_id] => Ok(GcScenarioAction::Play {
+            player_id: player_id.to_string(),
+            template_id: template_id.to_string(),
+            target_id: Some(target_id.to_string()),
+        }),
+        ["expect", "winner", op, value] => {
+            let op = GcScenarioOp::gc_parse(op).ok_or_else(|| format!("未知比较符: {}", op))?;
+            if op != GcScenarioOp::Eq {
+                return Err("winner 只支持 ==".to_string());
+            }
+            let player_id = (*value != "none").then(|| value.to_string());
+            Ok(GcScenarioAction::ExpectWinner { player_id })
+        }
+        ["expect", path, op, value] => {
+            let path = match *path {
+                "turn" => GcScenarioPath::Turn,
+                _ => {
+                    let (player_id, field) = gc_parse_player_field(path)?;
+                    GcScenarioPath::Player { player_id, field }
+                }
+            };
+            let op = GcScenarioOp::gc_parse(op).ok_or_else(|| format!("未知比较符: {}", op))?;
+            let value = value.parse().map_err(|_| format!("无效数值: {}", value))?;
+            Ok(GcScenarioAction::Expect { path, op, value })
+        }
+        _ => Err("无法识别的语句".to_string()),
+    }
+}
+
+impl GcScenario {
+    /// 解析脚本，错误信息带行号
+    pub fn gc_parse(script: &str) -> Result<Self, String> {
+        let mut scenario = GcScenario {
+            players: vec!["p1".to_string(), "p2".to_string()],
+            steps: Vec::new(),
+        };
+
+        for (index, raw_line) in script.lines().enumerate() {
+            let line = index + 1;
+            let code = raw_line.split('#').next().unwrap_or_default();
+            for statement in code.split(';').map(str::trim).filter(|s| !s.is_empty()) {
+                let tokens: Vec<&str> = statement.split_whitespace().collect();
+                if tokens[0] == "players" {
+                    if !scenario.steps.is_empty() {
+                        return Err(format!("第 {} 行: players 必须是第一条语句", line));
+                    }
+                    if tokens.len() < 3 {
+                        return Err(format!("第 {} 行: 至少需要两名玩家", line));
+                    }
+                    scenario.players = tokens[1..].iter().map(|s| s.to_string()).collect();
+                    continue;
+                }
+                let action = gc_parse_statement(&tokens)
+                    .map_err(|e| format!("第 {} 行: {} ({})", line, e, statement))?;
+                scenario.steps.push(GcScenarioStep {
+                    line,
+                    source: statement.to_string(),
+                    action,
+                });
+            }
+        }
+        Ok(scenario)
+    }
+
+    /// 创建场景对应的战斗
+    pub fn gc_create_battle(&self) -> GcBattleState {
+        let players = self.players.iter()
+            .map(|id| GcPlayer::gc_new(id.clone(), id.clone()))
+            .collect();
+        let mut state = GcBattleState::gc_new("scenario", players);
+        state.phase = GcBattlePhase::Playing;
+        state
+    }
+
+    /// 执行脚本
+    pub fn gc_run(&self) -> GcScenarioReport {
+        let mut state = self.gc_create_battle();
+        let mut report = GcScenarioReport::default();
+
+        for (index, step) in self.steps.iter().enumerate() {
+            match gc_run_step(&mut state, &step.action, index) {
+                Ok(None) => {}
+                Ok(Some(check)) => {
+                    report.checks += 1;
+                    if let Err(message) = check {
+                        report.failures.push(GcScenarioFailure::gc_from_step(step, message));
+                    }
+                }
+                Err(message) => {
+                    report.failures.push(GcScenarioFailure::gc_from_step(step, message));
+                    report.aborted = true;
+                    break;
+                }
+            }
+        }
+
+        report.state = Some(state);
+        report
+    }
+}
+
+/// 解析并执行脚本
+pub fn gc_run_scenario(script: &str) -> Result<GcScenarioReport, String> {
+    Ok(GcScenario::gc_parse(script)?.gc_run())
+}
+
+// =============================================================================
+// 执行
+// =============================================================================
+
+/// 执行结果中的一条失败
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcScenarioFailure {
+    /// 行号
+    pub line: usize,
+    /// 原文
+    pub source: String,
+    /// 原因
+    pub message: String,
+}
+
+impl GcScenarioFailure {
+    fn gc_from_step(step: &GcScenarioStep, message: String) -> Self {
+        Self {
+            line: step.line,
+            source: step.source.clone(),
+            message,
+        }
+    }
+}
+
+/// 执行报告
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct GcScenarioReport {
+    /// 执行的断言数
+    pub checks: usize,
+    /// 失败
+    pub failures: Vec<GcScenarioFailure>,
+    /// 是否因操作失败而中止
+    pub aborted: bool,
+    /// 结束时的战斗状态
+    pub state: Option<GcBattleState>,
+}
+
+impl GcScenarioReport {
+    /// 是否全部通过
+    pub fn gc_passed(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+/// 读取数值
+fn gc_read_path(state: &GcBattleState, path: &GcScenarioPath) -> Result<u64, String> {
+    let (player_id, field) = match path {
+        GcScenarioPath::Turn => return Ok(state.turn as u64),
+        GcScenarioPath::Winner => return Err("winner 不是数值".to_string()),
+        GcScenarioPath::Player { player_id, field } => (player_id, field),
+    };
+    let player = state.gc_find_player(player_id)
+        .ok_or_else(|| format!("玩家不存在: {}", player_id))?;
+    let value = match field {
+        GcScenarioField::Hp => player.stats.hp as u64,
+        GcScenarioField::MaxHp => player.stats.max_hp as u64,
+        GcScenarioField::Attack => player.stats.attack as u64,
+        GcScenarioField::Defense => player.stats.defense as u64,
+        GcScenarioField::Energy => player.stats.energy as u64,
+        GcScenarioField::ActionPoints => player.stats.action_points as u64,
+        GcScenarioField::Hand => player.hand.len() as u64,
+        GcScenarioField::Deck => player.deck.len() as u64,
+        GcScenarioField::Discard => player.discard.len() as u64,
+    };
+    Ok(value)
+}
+
+/// 生成模板卡牌实例
+fn gc_scenario_card(template_id: &str, index: usize) -> Result<crate::GcCard, String> {
+    let mut card = gc_get_card_template(template_id)
+        .ok_or_else(|| format!("未知卡牌模板: {}", template_id))?;
+    card.id = format!("scenario_{}_{}", template_id, index);
+    Ok(card)
+}
+
+/// 执行一条语句: 操作失败返回 Err，断言返回 Some(结果)
+fn gc_run_step(
+    state: &mut GcBattleState,
+    action: &GcScenarioAction,
+    index: usize,
+) -> Result<Option<Result<(), String>>, String> {
+    match action {
+        GcScenarioAction::Give { player_id, template_id } => {
+            let card = gc_scenario_card(template_id, index)?;
+            let player = state.gc_find_player_mut(player_id)
+                .ok_or_else(|| format!("玩家不存在: {}", player_id))?;
+            player.hand.push(card);
+        }
+        GcScenarioAction::Set { player_id, field, value } => {
+            let player = state.gc_find_player_mut(player_id)
+                .ok_or_else(|| format!("玩家不存在: {}", player_id))?;
+            let stats = &mut player.stats;
+            match field {
+                GcScenarioField::Hp => {
+                    stats.max_hp = stats.max_hp.max(*value);
+                    stats.hp = *value;
+                }
+                GcScenarioField::MaxHp => {
+                    stats.max_hp = *value;
+                    stats.hp = stats.hp.min(*value);
+                }
+                GcScenarioField::Attack => stats.attack = *value,
+                GcScenarioField::Defense => stats.defense = *value,
+                GcScenarioField::Energy => stats.energy = *value,
+                GcScenarioField::ActionPoints => stats.action_points = *value,
+                GcScenarioField::Hand | GcScenarioField::Deck | GcScenarioField::Discard => {
+                    return Err("属性不可修改".to_string());
+                }
+            }
+        }
+        GcScenarioAction::Play { player_id, template_id, target_id } => {
+            let player = state.gc_find_player(player_id)
+                .ok_or_else(|| format!("玩家不存在: {}", player_id))?;
+            let card_id = match player.hand.iter().find(|c| &c.template_id == template_id) {
+                Some(card) => card.id.clone(),
+                None => {
+                    let card = gc_scenario_card(template_id, index)?;
+                    let card_id = card.id.clone();
+                    state.gc_find_player_mut(player_id)
+                        .ok_or_else(|| format!("玩家不存在: {}", player_id))?
+                        .hand.push(card);
+                    card_id
+                }
+            };
+            let target_id = match target_id {
+                Some(target_id) => target_id.clone(),
+                None => state.players.iter()
+                    .find(|p| &p.id != player_id && p.gc_can_act())
+                    .map(|p| p.id.clone())
+                    .unwrap_or_default(),
+            };
+            let result = gc_execute_play_card(state, player_id, &card_id, &target_id);
+            if !result.success {
+                return Err(result.error.unwrap_or_else(|| "出牌失败".to_string()));
+            }
+        }
+        GcScenarioAction::EndTurn { player_id } => {
+            if state.gc_current_player_id() != Some(player_id.as_str()) {
+                return Err(format!("不是 {} 的回合", player_id));
+            }
+            state.gc_execute_turn_combat(player_id);
+            if !state.gc_is_finished() {
+                state.gc_next_turn();
+                if let Some(player) = state.gc_current_player_mut() {
+                    player.stats.energy = player.stats.max_energy;
+                    player.gc_draw_cards(GcConfig::DRAW_PER_TURN);
+                }
+            }
+        }
+        GcScenarioAction::Expect { path, op, value } => {
+            let actual = gc_read_path(state, path)?;
+            let check = if op.gc_compare(actual, *value) {
+                Ok(())
+            } else {
+                Err(format!("期望 {} {}，实际为 {}", op.gc_symbol(), value, actual))
+            };
+            return Ok(Some(check));
+        }
+        GcScenarioAction::ExpectWinner { player_id } => {
+            let check = if &state.winner_id == player_id {
+                Ok(())
+            } else {
+                Err(format!("期望获胜者 {:?}，实际为 {:?}", player_id, state.winner_id))
+            };
+            return Ok(Some(check));
+        }
+    }
+    Ok(None)
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_errors_have_line_numbers() {
+        let error = GcScenario::gc_parse("p1 plays card_knight_attack at p2\nexpect p2.mana == 3").unwrap_err();
+        assert!(error.starts_with("第 2 行"), "{}", error);
+        assert!(GcScenario::gc_parse("give p1 x\nplayers a b").is_err());
+        assert!(GcScenario::gc_parse("set p1.hand = 3").is_err());
+    }
+
+    #[test]
+    fn test_run_reports_failures() {
+        let report = gc_run_scenario("
+            # 盾击: 8 + 攻击 10 - 防御 5 * 0.3 = 17
+            p1 plays card_knight_attack at p2; expect p2.hp == 83
+            expect p1.discard == 1
+            expect p2.hp == 1   # 故意失败
+            expect turn == 1
+        ").unwrap();
+        assert_eq!(report.checks, 4);
+        assert_eq!(report.failures.len(), 1);
+        assert_eq!(report.failures[0].line, 5);
+        assert!(!report.aborted);
+    }
+
+    #[test]
+    fn test_failed_action_aborts() {
+        let report = gc_run_scenario("p2 plays card_knight_attack at p1\nexpect p1.hp == 100").unwrap();
+        assert!(report.aborted);
+        assert_eq!(report.checks, 0);
+        assert!(!report.gc_passed());
+    }
+}
@@ -11,9 +11,16 @@
 //! - 每个赛季限定可用的游戏模式
 //! - 战斗开始前选择模式，战斗中不可切换
 //! - 部分赛季可能只开放单一模式
+//!
+//! ## 内容轮换
+//! - 赛季可限定可用的怪兽模板与卡牌 (允许列表为空表示全部可用)
+//! - 禁用列表优先于允许列表
+//! - 怪兽池与卡牌模板加载时按当前赛季过滤，对局开始时校验卡组
 
 use serde::{Deserialize, Serialize};
 
+use crate::{GcCard, GcDeck, GcMonsterPool, gc_get_card_template};
+
 // =============================================================================
 // 游戏模式
 // =============================================================================
@@ -82,6 +89,44 @@ impl Default for GcGameMode {
     }
 }
 
+// =============================================================================
+// 赛季内容
+// =============================================================================
+
+/// 赛季内容轮换 (怪兽模板与卡牌的允许/禁用列表)
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcSeasonContent {
+    /// 允许的怪兽模板ID (空 = 全部允许)
+    #[serde(default)]
+    pub allowed_monsters: Vec<String>,
+    /// 禁用的怪兽模板ID
+    #[serde(default)]
+    pub banned_monsters: Vec<String>,
+    /// 允许的卡牌模板ID (空 = 全部允许)
+    #[serde(default)]
+    pub allowed_cards: Vec<String>,
+    /// 禁用的卡牌模板ID
+    #[serde(default)]
+    pub banned_cards: Vec<String>,
+}
+
+impl GcSeasonContent {
+    /// 检查ID是否通过允许/禁用列表
+    fn gc_passes(id: &str, allowed: &[String], banned: &[String]) -> bool {
+        !banned.iter().any(|b| b == id) && (allowed.is_empty() || allowed.iter().any(|a| a == id))
+    }
+    
+    /// 怪兽模板是否可用
+    pub fn gc_is_monster_allowed(&self, template_id: &str) -> bool {
+        Self::gc_passes(template_id, &self.allowed_monsters, &self.banned_monsters)
+    }
+    
+    /// 卡牌模板是否可用
+    pub fn gc_is_card_allowed(&self, template_id: &str) -> bool {
+        Self::gc_passes(template_id, &self.allowed_cards, &self.banned_cards)
+    }
+}
+
 // =============================================================================
 // 赛季定义
 // =============================================================================
@@ -105,6 +150,9 @@ pub struct GcSeason {
     pub end_time: u64,
     /// 是否为当前激活的赛季
     pub is_active: bool,
+    /// 内容轮换
+    #[serde(default)]
+    pub content: GcSeasonContent,
 }
 
 impl GcSeason {
@@ -127,6 +175,39 @@ impl GcSeason {
             start_time,
             end_time,
             is_active: false,
+            content: GcSeasonContent::default(),
+        }
+    }
+    
+    /// 设置内容轮换
+    pub fn with_content(mut self, content: GcSeasonContent) -> Self {
+        self.content = content;
+        self
+    }
+    
+    /// 按赛季过滤后的怪兽池
+    pub fn gc_monster_pool(&self) -> GcMonsterPool {
+        let mut pool = GcMonsterPool::with_defaults();
+        pool.retain_templates(|t| self.content.gc_is_monster_allowed(&t.template_id));
+        pool
+    }
+    
+    /// 按赛季加载卡牌模板 (已轮换出或被禁用的返回 None)
+    pub fn gc_get_card_template(&self, template_id: &str) -> Option<GcCard> {
+        if !self.content.gc_is_card_allowed(template_id) {
+            return None;
+        }
+        gc_get_card_template(template_id)
+    }
+    
+    /// 校验卡组是否符合本赛季 (对局开始时)
+    pub fn gc_validate_deck(&self, deck: &GcDeck) -> Result<(), String> {
+        match deck.cards.iter()
+            .chain(deck.sideboard.iter())
+            .find(|id| !self.content.gc_is_card_allowed(id))
+        {
+            Some(id) => Err(format!("卡牌 {} 在赛季 {} 中不可用", id, self.name)),
+            None => Ok(()),
         }
     }
     
@@ -200,7 +281,7 @@ impl GcSeasonManager {
             u64::MAX, // 永久
         ));
         
-        // 赛季2: 仅酒馆模式
+        // 赛季2: 仅酒馆模式 (轮换出远古巨龙)
         self.add_season(GcSeason::new(
             "s2_tavern",
             "酒馆狂欢",
@@ -209,9 +290,12 @@ impl GcSeasonManager {
             vec![GcGameMode::TavernStyle],
             0,
             u64::MAX,
-        ));
+        ).with_content(GcSeasonContent {
+            banned_monsters: vec!["ancient_dragon".to_string()],
+            ..Default::default()
+        }));
         
-        // 赛季3: 仅决斗模式
+        // 赛季3: 仅决斗模式 (禁用术士终极技)
         self.add_season(GcSeason::new(
             "s3_duel",
             "决斗者之路",
@@ -220,7 +304,10 @@ impl GcSeasonManager {
             vec![GcGameMode::YuGiOhStyle],
             0,
             u64::MAX,
-        ));
+        ).with_content(GcSeasonContent {
+            banned_cards: vec!["card_warlock_ult".to_string()],
+            ..Default::default()
+        }));
         
         // 默认激活第一个赛季
         if !self.seasons.is_empty() {
@@ -412,6 +499,29 @@ mod tests {
         assert!(!manager.is_mode_available(GcGameMode::YuGiOhStyle));
     }
     
+    #[test]
+    fn test_season_content_rotation() {
+        let season = GcSeason::new("t", "测试", "", "", vec![GcGameMode::TavernStyle], 0, u64::MAX)
+            .with_content(GcSeasonContent {
+                allowed_monsters: vec!["slime".to_string(), "goblin".to_string()],
+                banned_monsters: vec!["goblin".to_string()],
+                banned_cards: vec!["card_knight_ult".to_string()],
+                ..Default::default()
+            });
+        
+        let pool = season.gc_monster_pool();
+        assert_eq!(pool.templates().len(), 1);
+        assert!(pool.find_template("slime").is_some());
+        
+        assert!(season.gc_get_card_template("card_knight_ult").is_none());
+        assert!(season.gc_get_card_template("card_knight_attack").is_some());
+        
+        let mut deck = GcDeck::gc_new("d", "骑士", vec!["card_knight_attack".to_string()]);
+        assert!(season.gc_validate_deck(&deck).is_ok());
+        deck.cards.push("card_knight_ult".to_string());
+        assert!(season.gc_validate_deck(&deck).is_err());
+    }
+    
     #[test]
     fn test_phase() {
         let phase = GcPhase::Tavern(GcTavernPhase::Shopping);
@@ -0,0 +1,238 @@
+//! 商店定价 (动态价格与促销)
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! ## 规则
+//! - 价格管线: 星级基础价 (配置表) → 价格修改器 (按加入顺序叠加) → 不低于最低价
+//! - 修改器来源: 促销、英雄技能、遗物、规则集；作用范围为全部、指定怪兽、星级或模板
+//! - 持续时间: 本回合 (回合开始时清除) / 下一次购买 (命中的购买后消耗) / 永久
+//! - 促销槽位: 每隔 `sale_every_rounds` 回合，回合开始时商店中一只怪兽本回合降价 `sale_discount`
+//! - 定价配置可由规则集 (`GcRulesetModifier::ShopPricing`) 下发，默认价格为 1★=1金、2★=2金、3★+=3金，不促销
+
+use serde::{Deserialize, Serialize};
+
+use crate::{gc_monster_buy_price, GcMonster};
+
+// =============================================================================
+// 定价配置
+// =============================================================================
+
+/// 商店定价配置
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GcShopPricing {
+    /// 各星级基础价格 (索引 = 星级 - 1，超出时取最后一项；为空时使用默认价格)
+    pub star_prices: Vec<u32>,
+    /// 最低价格
+    pub min_price: u32,
+    /// 促销间隔回合数 (0 = 不促销)
+    pub sale_every_rounds: u32,
+    /// 促销降价金额
+    pub sale_discount: u32,
+}
+
+impl Default for GcShopPricing {
+    fn default() -> Self {
+        Self {
+            star_prices: vec![1, 2, 3],
+            min_price: 0,
+            sale_every_rounds: 0,
+            sale_discount: 1,
+        }
+    }
+}
+
+impl GcShopPricing {
+    /// 星级基础价格
+    pub fn gc_base_price(&self, star: u8) -> u32 {
+        let index = usize::from(star.max(1) - 1).min(self.star_prices.len().saturating_sub(1));
+        self.star_prices.get(index).copied().unwrap_or_else(|| gc_monster_buy_price(star))
+    }
+
+    /// 该回合是否有促销
+    pub fn gc_is_sale_round(&self, round: u32) -> bool {
+        self.sale_every_rounds > 0 && round.is_multiple_of(self.sale_every_rounds)
+    }
+}
+
+// =============================================================================
+// 价格修改器
+// =============================================================================
+
+/// 价格修改来源
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GcPriceSource {
+    /// 促销
+    Sale,
+    /// 英雄技能
+    HeroPower,
+    /// 遗物
+    Relic,
+    /// 规则集
+    Ruleset,
+}
+
+/// 价格修改范围
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data")]
+pub enum GcPriceScope {
+    /// 所有怪兽
+    All,
+    /// 指定怪兽实例
+    Monster { monster_id: String },
+    /// 指定星级
+    Star { star: u8 },
+    /// 指定模板
+    Template { template_id: String },
+}
+
+impl GcPriceScope {
+    /// 是否作用于该怪兽
+    pub fn gc_matches(&self, monster: &GcMonster) -> bool {
+        match self {
+            Self::All => true,
+            Self::Monster { monster_id } => &monster.id == monster_id,
+            Self::Star { star } => monster.star == *star,
+            Self::Template { template_id } => &monster.template_id == template_id,
+        }
+    }
+}
+
+/// 价格修改持续时间
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GcPriceDuration {
+    /// 本回合
+    ThisTurn,
+    /// 下一次命中的购买
+    NextPurchase,
+    /// 永久
+    Permanent,
+}
+
+/// 价格修改器
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcPriceModifier {
+    /// 来源
+    pub source: GcPriceSource,
+    /// 作用范围
+    pub scope: GcPriceScope,
+    /// 价格变化 (负数为降价)
+    pub delta: i32,
+    /// 持续时间
+    pub duration: GcPriceDuration,
+}
+
+impl GcPriceModifier {
+    /// 创建修改器
+    pub fn gc_new(source: GcPriceSource, scope: GcPriceScope, delta: i32, duration: GcPriceDuration) -> Self {
+        Self { source, scope, delta, duration }
+    }
+
+    /// 促销: 指定怪兽本回合降价
+    pub fn gc_sale(monster_id: &str, discount: u32) -> Self {
+        Self::gc_new(
+            GcPriceSource::Sale,
+            GcPriceScope::Monster { monster_id: monster_id.to_string() },
+            -(discount.min(i32::MAX as u32) as i32),
+            GcPriceDuration::ThisTurn,
+        )
+    }
+}
+
+// =============================================================================
+// 价格管线
+// =============================================================================
+
+/// 单项价格调整 (供界面展示)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcPriceAdjustment {
+    pub source: GcPriceSource,
+    pub delta: i32,
+}
+
+/// 计算后的价格
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcShopPrice {
+    /// 基础价格
+    pub base: u32,
+    /// 最终价格
+    pub price: u32,
+    /// 生效的调整
+    pub adjustments: Vec<GcPriceAdjustment>,
+}
+
+impl GcShopPrice {
+    /// 是否低于基础价格
+    pub fn gc_is_discounted(&self) -> bool {
+        self.price < self.base
+    }
+}
+
+/// 计算怪兽价格
+pub fn gc_price_monster(pricing: &GcShopPricing, modifiers: &[GcPriceModifier], monster: &GcMonster) -> GcShopPrice {
+    let base = pricing.gc_base_price(monster.star);
+    let adjustments: Vec<GcPriceAdjustment> = modifiers.iter()
+        .filter(|m| m.scope.gc_matches(monster))
+        .map(|m| GcPriceAdjustment { source: m.source, delta: m.delta })
+        .collect();
+    let total = adjustments.iter().fold(i64::from(base), |acc, a| acc + i64::from(a.delta));
+    let price = total.clamp(i64::from(pricing.min_price), i64::from(u32::MAX)) as u32;
+    GcShopPrice { base, price, adjustments }
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GcMonsterAttribute;
+
+    fn monster(id: &str, star: u8) -> GcMonster {
+        let mut m = GcMonster::new_with_template(id, "slime", "史莱姆", 1, GcMonsterAttribute::Water, 50, 30, 60);
+        m.star = star;
+        m
+    }
+
+    #[test]
+    fn test_base_prices_follow_config() {
+        let pricing = GcShopPricing::default();
+        assert_eq!((1..=4).map(|s| pricing.gc_base_price(s)).collect::<Vec<_>>(), vec![1, 2, 3, 3]);
+
+        let pricing = GcShopPricing { star_prices: vec![2, 4], ..Default::default() };
+        assert_eq!(pricing.gc_base_price(3), 4);
+        let empty = GcShopPricing { star_prices: Vec::new(), ..Default::default() };
+        assert_eq!(empty.gc_base_price(2), gc_monster_buy_price(2));
+
+        let pricing = GcShopPricing { sale_every_rounds: 3, ..Default::default() };
+        assert!(!pricing.gc_is_sale_round(2));
+        assert!(pricing.gc_is_sale_round(6));
+        assert!(!GcShopPricing::default().gc_is_sale_round(6));
+    }
+
+    #[test]
+    fn test_pipeline_stacks_modifiers_and_clamps() {
+        let pricing = GcShopPricing { min_price: 1, ..Default::default() };
+        let modifiers = vec![
+            GcPriceModifier::gc_sale("m1", 1),
+            GcPriceModifier::gc_new(GcPriceSource::Relic, GcPriceScope::Star { star: 3 }, -1, GcPriceDuration::Permanent),
+            GcPriceModifier::gc_new(GcPriceSource::HeroPower, GcPriceScope::All, -5, GcPriceDuration::NextPurchase),
+        ];
+
+        let price = gc_price_monster(&pricing, &modifiers[..2], &monster("m1", 3));
+        assert_eq!((price.base, price.price), (3, 1));
+        assert_eq!(price.adjustments.len(), 2);
+        assert!(price.gc_is_discounted());
+
+        // 不受其他怪兽的促销影响；降价不低于最低价
+        let price = gc_price_monster(&pricing, &modifiers, &monster("m2", 2));
+        assert_eq!(price.price, 1);
+        assert_eq!(price.adjustments, vec![GcPriceAdjustment { source: GcPriceSource::HeroPower, delta: -5 }]);
+
+        let surcharge = GcPriceModifier::gc_new(GcPriceSource::Ruleset, GcPriceScope::All, 2, GcPriceDuration::Permanent);
+        assert_eq!(gc_price_monster(&pricing, &[surcharge], &monster("m3", 1)).price, 3);
+    }
+}
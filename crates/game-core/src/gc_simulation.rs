@@ -0,0 +1,87 @@
+//! 批量模拟 (平衡测试 / 服务器 AI)
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! ## 规则
+//! - 每个模拟相互独立，只拿到自己的种子；同一种子在任何线程上结果相同
+//! - 启用 `rayon` 特性时并行执行 (仅原生构建)，否则顺序执行；结果始终按种子顺序返回
+//! - 模拟内的随机数应来自 `gc_simulation_rng(seed)`，不要使用线程全局随机源
+//!   (例如 `GcCardPool::gc_initialize` 会用 `thread_rng` 洗牌，确定性模拟需自行构造卡池)
+//! - WASM 构建不能开启 `rayon` 特性 (编译期报错)
+
+#[cfg(all(feature = "rayon", target_arch = "wasm32"))]
+compile_error!("`rayon` 特性只用于原生构建，WASM 构建请关闭");
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+/// 由种子创建模拟专用随机源
+pub fn gc_simulation_rng(seed: u64) -> StdRng {
+    StdRng::seed_from_u64(seed)
+}
+
+/// 为批量模拟生成种子 (base, base+1, ...)
+pub fn gc_simulation_seeds(base: u64, count: usize) -> Vec<u64> {
+    (0..count as u64).map(|i| base.wrapping_add(i)).collect()
+}
+
+/// 对每个种子运行一次模拟，结果按种子顺序返回
+#[cfg(feature = "rayon")]
+pub fn gc_run_simulations<T, F>(seeds: &[u64], simulate: F) -> Vec<T>
+where
+    T: Send,
+    F: Fn(u64) -> T + Sync + Send,
+{
+    use rayon::prelude::*;
+    seeds.par_iter().map(|&seed| simulate(seed)).collect()
+}
+
+/// 对每个种子运行一次模拟，结果按种子顺序返回
+#[cfg(not(feature = "rayon"))]
+pub fn gc_run_simulations<T, F>(seeds: &[u64], simulate: F) -> Vec<T>
+where
+    T: Send,
+    F: Fn(u64) -> T + Sync + Send,
+{
+    seeds.iter().map(|&seed| simulate(seed)).collect()
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GcBattleArena, GcBattleState, GcPlayer, GcTavernGame, gc_run_scenario};
+    use rand::Rng;
+
+    fn gc_assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn test_core_states_are_send_sync() {
+        gc_assert_send_sync::<GcBattleState>();
+        gc_assert_send_sync::<GcPlayer>();
+        gc_assert_send_sync::<GcBattleArena>();
+        gc_assert_send_sync::<GcTavernGame>();
+    }
+
+    #[test]
+    fn test_results_are_deterministic_per_seed() {
+        let seeds = gc_simulation_seeds(42, 32);
+        let simulate = |seed: u64| {
+            let mut rng = gc_simulation_rng(seed);
+            let hp: u32 = rng.gen_range(10..60);
+            let report = gc_run_scenario(&format!("set p2.hp = {}\np1 plays card_knight_attack", hp)).unwrap();
+            report.state.map(|s| s.players[1].stats.hp).unwrap_or_default()
+        };
+
+        let first = gc_run_simulations(&seeds, simulate);
+        let second = gc_run_simulations(&seeds, simulate);
+        assert_eq!(first, second);
+        assert_eq!(first.len(), 32);
+        assert_eq!(first[0], simulate(42));
+    }
+}
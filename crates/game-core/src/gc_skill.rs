@@ -0,0 +1,394 @@
+//! 英雄技能系统
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! ## 技能规则
+//! - 英雄技能独立于卡牌，不占手牌
+//! - 使用时消耗能量，并进入冷却 (回合数)
+//! - 每回合最多使用一次英雄技能
+//! - 回合开始时冷却 -1，并重置使用次数
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    GcBattleState, GcCombatLogEvent, GcDamageType, GcEffectResult, GcError, GcPlayerState,
+    GcProfessionType, gc_check_channel_interrupts, gc_damage_cue, gc_resolve_damage,
+};
+
+// =============================================================================
+// 技能定义
+// =============================================================================
+
+/// 英雄技能效果
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value")]
+pub enum GcHeroSkillEffect {
+    /// 对目标造成伤害 (叠加攻击力的一半)
+    Damage(u32),
+    /// 恢复自身生命
+    Heal(u32),
+    /// 恢复自身能量
+    RestoreEnergy(u32),
+}
+
+impl GcHeroSkillEffect {
+    /// 是否需要敌方目标
+    pub fn needs_target(&self) -> bool {
+        matches!(self, GcHeroSkillEffect::Damage(_))
+    }
+}
+
+/// 英雄技能
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GcHeroSkill {
+    /// 技能 ID
+    pub id: String,
+    /// 技能名称
+    pub name: String,
+    /// 技能描述
+    pub description: String,
+    /// 能量消耗
+    pub energy_cost: u32,
+    /// 冷却时间 (回合数)
+    pub cooldown: u32,
+    /// 当前冷却
+    pub current_cooldown: u32,
+    /// 技能效果
+    pub effect: GcHeroSkillEffect,
+    /// 伤害类型 (仅伤害技能)
+    #[serde(default)]
+    pub damage_type: GcDamageType,
+}
+
+impl GcHeroSkill {
+    /// 创建技能
+    pub fn gc_new(id: &str, name: &str, description: &str, energy_cost: u32, cooldown: u32, effect: GcHeroSkillEffect) -> Self {
+        Self {
+            id: id.to_string(),
+            name: name.to_string(),
+            description: description.to_string(),
+            energy_cost,
+            cooldown,
+            current_cooldown: 0,
+            effect,
+            damage_type: GcDamageType::Physical,
+        }
+    }
+
+    /// 设置伤害类型
+    pub fn gc_with_damage_type(mut self, damage_type: GcDamageType) -> Self {
+        self.damage_type = damage_type;
+        self
+    }
+
+    /// 是否冷却完毕
+    pub fn gc_is_ready(&self) -> bool {
+        self.current_cooldown == 0
+    }
+}
+
+// =============================================================================
+// 技能栏
+// =============================================================================
+
+/// 玩家英雄技能栏
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct GcHeroSkillSet {
+    /// 技能列表
+    pub skills: Vec<GcHeroSkill>,
+    /// 本回合是否已使用技能
+    pub used_this_turn: bool,
+}
+
+impl GcHeroSkillSet {
+    /// 通用技能栏 (无职业)
+    pub fn gc_basic() -> Self {
+        Self {
+            skills: vec![
+                GcHeroSkill::gc_new("skill_strike", "强击", "造成 10 点伤害", 1, 2, GcHeroSkillEffect::Damage(10)),
+            ],
+            used_this_turn: false,
+        }
+    }
+
+    /// 职业技能栏
+    pub fn gc_for_profession(profession: GcProfessionType) -> Self {
+        let signature = match profession {
+            GcProfessionType::Knight => GcHeroSkill::gc_new(
+                "skill_holy_guard", "圣光守护", "恢复 20 点生命", 2, 3, GcHeroSkillEffect::Heal(20),
+            ),
+            GcProfessionType::Swordsman => GcHeroSkill::gc_new(
+                "skill_whirlwind", "旋风斩", "造成 18 点伤害", 2, 2, GcHeroSkillEffect::Damage(18),
+            ),
+            GcProfessionType::Warlock => GcHeroSkill::gc_new(
+                "skill_mana_siphon", "法力虹吸", "恢复 2 点能量", 0, 3, GcHeroSkillEffect::RestoreEnergy(2),
+            ),
+            GcProfessionType::Gunner => GcHeroSkill::gc_new(
+                "skill_snipe", "狙击", "造成 25 点伤害", 3, 3, GcHeroSkillEffect::Damage(25),
+            ),
+            GcProfessionType::Assassin => GcHeroSkill::gc_new(
+                "skill_backstab", "背刺", "造成 14 点伤害", 1, 2, GcHeroSkillEffect::Damage(14),
+            ),
+        };
+
+        let mut set = Self::gc_basic();
+        set.skills.push(signature);
+        set
+    }
+
+    /// 查找技能
+    pub fn gc_find(&self, skill_id: &str) -> Option<&GcHeroSkill> {
+        self.skills.iter().find(|s| s.id == skill_id)
+    }
+
+    /// 查找技能 (可变)
+    pub fn gc_find_mut(&mut self, skill_id: &str) -> Option<&mut GcHeroSkill> {
+        self.skills.iter_mut().find(|s| s.id == skill_id)
+    }
+
+    /// 回合开始: 冷却 -1，重置使用次数
+    pub fn gc_on_turn_start(&mut self) {
+        self.used_this_turn = false;
+        for skill in &mut self.skills {
+            skill.current_cooldown = skill.current_cooldown.saturating_sub(1);
+        }
+    }
+}
+
+// =============================================================================
+// 技能使用
+// =============================================================================
+
+/// 技能使用结果
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GcSkillUseResult {
+    /// 是否成功
+    pub success: bool,
+    /// 错误信息 (失败时)
+    pub error: Option<String>,
+    /// 使用的技能 ID
+    pub skill_id: Option<String>,
+    /// 触发的效果
+    pub effects_triggered: Vec<GcEffectResult>,
+    /// 目标是否死亡
+    pub target_killed: bool,
+}
+
+impl GcSkillUseResult {
+    /// 创建失败结果
+    pub fn fail(error: impl Into<String>) -> Self {
+        Self {
+            success: false,
+            error: Some(error.into()),
+            skill_id: None,
+            effects_triggered: Vec::new(),
+            target_killed: false,
+        }
+    }
+}
+
+/// 验证技能使用
+pub fn gc_validate_use_skill(
+    state: &GcBattleState,
+    player_id: &str,
+    skill_id: &str,
+    target_id: &str,
+) -> Result<(), GcError> {
+    if state.gc_is_finished() {
+        return Err(GcError::GcBattleEnded);
+    }
+
+    if state.gc_current_player_id() != Some(player_id) {
+        return Err(GcError::GcNotYourTurn);
+    }
+
+    let player = state.gc_find_player(player_id)
+        .ok_or(GcError::GcPlayerNotFound)?;
+
+    if player.hero_skills.used_this_turn {
+        return Err(GcError::GcSkillAlreadyUsed);
+    }
+
+    let skill = player.hero_skills.gc_find(skill_id)
+        .ok_or(GcError::GcSkillNotFound)?;
+
+    if !skill.gc_is_ready() {
+        return Err(GcError::GcSkillOnCooldown);
+    }
+
+    if player.stats.energy < skill.energy_cost {
+        return Err(GcError::GcNotEnoughEnergy);
+    }
+
+    if skill.effect.needs_target() {
+        let target = state.gc_find_player(target_id)
+            .ok_or(GcError::GcInvalidTarget)?;
+
+        // 伤害技能只能指向敌方 (组队时不能指向队友)
+        if state.gc_are_allies(player_id, &target.id) || !target.gc_can_act() {
+            return Err(GcError::GcInvalidTarget);
+        }
+    }
+
+    Ok(())
+}
+
+/// 执行技能使用
+pub fn gc_execute_use_skill(
+    state: &mut GcBattleState,
+    player_id: &str,
+    skill_id: &str,
+    target_id: &str,
+) -> GcSkillUseResult {
+    if let Err(e) = gc_validate_use_skill(state, player_id, skill_id, target_id) {
+        return GcSkillUseResult::fail(e.to_string());
+    }
+
+    // 扣除能量、进入冷却、标记已使用
+    let (effect, damage_type, attack) = match state.gc_find_player_mut(player_id) {
+        Some(player) => {
+            let attack = player.stats.attack;
+            let Some(skill) = player.hero_skills.gc_find_mut(skill_id) else {
+                return GcSkillUseResult::fail(GcError::GcSkillNotFound.to_string());
+            };
+            skill.current_cooldown = skill.cooldown;
+            let effect = skill.effect.clone();
+            let damage_type = skill.damage_type;
+            let cost = skill.energy_cost;
+            player.stats.energy = player.stats.energy.saturating_sub(cost);
+            player.hero_skills.used_this_turn = true;
+            (effect, damage_type, attack)
+        }
+        None => return GcSkillUseResult::fail(GcError::GcPlayerNotFound.to_string()),
+    };
+
+    let mut target_killed = false;
+    let effect_result = match effect {
+        GcHeroSkillEffect::Damage(power) => {
+            let Some(target) = state.gc_find_player_mut(target_id) else {
+                return GcSkillUseResult::fail(GcError::GcInvalidTarget.to_string());
+            };
+            let damage = gc_resolve_damage(
+                power + attack / 2,
+                target.stats.defense * 3 / 10,
+                damage_type,
+                &target.resistances,
+            );
+            let dealt = target.stats.gc_take_damage(damage.final_damage);
+            if !target.stats.gc_is_alive() {
+                target.state = GcPlayerState::Dead;
+                target_killed = true;
+            }
+            let breakdown = damage.gc_breakdown();
+            let cue = gc_damage_cue(&damage, target.stats.max_hp, target_killed);
+            state.combat_log.push(GcCombatLogEvent::Damage {
+                turn: state.turn,
+                source_id: player_id.to_string(),
+                target_id: target_id.to_string(),
+                result: damage,
+                breakdown: breakdown.clone(),
+                cue,
+            });
+            GcEffectResult::new("伤害", target_id, dealt as i32, format!("造成 {} 点伤害 ({})", dealt, breakdown))
+        }
+        GcHeroSkillEffect::Heal(amount) => {
+            let healed = state.gc_find_player_mut(player_id)
+                .map(|p| p.stats.gc_heal(amount))
+                .unwrap_or(0);
+            state.combat_log.push(GcCombatLogEvent::Heal {
+                turn: state.turn,
+                source_id: player_id.to_string(),
+                target_id: player_id.to_string(),
+                amount: healed,
+            });
+            GcEffectResult::new("治疗", player_id, healed as i32, format!("恢复 {} 点生命", healed))
+        }
+        GcHeroSkillEffect::RestoreEnergy(amount) => {
+            if let Some(player) = state.gc_find_player_mut(player_id) {
+                player.stats.energy = (player.stats.energy + amount).min(player.stats.max_energy);
+            }
+            GcEffectResult::new("能量", player_id, amount as i32, format!("恢复 {} 点能量", amount))
+        }
+    };
+
+    gc_check_channel_interrupts(state);
+    state.gc_check_battle_end();
+
+    GcSkillUseResult {
+        success: true,
+        error: None,
+        skill_id: Some(skill_id.to_string()),
+        effects_triggered: vec![effect_result],
+        target_killed,
+    }
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GcPlayer;
+
+    fn create_battle() -> GcBattleState {
+        let p1 = GcPlayer::gc_new("p1", "玩家1");
+        let p2 = GcPlayer::gc_new("p2", "玩家2");
+        GcBattleState::gc_new("b1", vec![p1, p2])
+    }
+
+    #[test]
+    fn test_use_skill_damage_and_cooldown() {
+        let mut battle = create_battle();
+        let hp_before = battle.gc_find_player("p2").map(|p| p.stats.hp).unwrap_or(0);
+
+        let result = gc_execute_use_skill(&mut battle, "p1", "skill_strike", "p2");
+        assert!(result.success);
+
+        let p2_hp = battle.gc_find_player("p2").map(|p| p.stats.hp).unwrap_or(0);
+        assert!(p2_hp < hp_before);
+
+        let skill = battle.gc_find_player("p1").and_then(|p| p.hero_skills.gc_find("skill_strike")).cloned();
+        assert_eq!(skill.map(|s| s.current_cooldown), Some(2));
+    }
+
+    #[test]
+    fn test_skill_once_per_turn() {
+        let mut battle = create_battle();
+        if let Some(p) = battle.gc_find_player_mut("p1") {
+            p.hero_skills = GcHeroSkillSet::gc_for_profession(GcProfessionType::Warlock);
+        }
+
+        assert!(gc_execute_use_skill(&mut battle, "p1", "skill_strike", "p2").success);
+        assert!(matches!(
+            gc_validate_use_skill(&battle, "p1", "skill_mana_siphon", "p1"),
+            Err(GcError::GcSkillAlreadyUsed)
+        ));
+    }
+
+    #[test]
+    fn test_skill_cooldown_ticks_on_turn_start() {
+        let mut set = GcHeroSkillSet::gc_basic();
+        set.skills[0].current_cooldown = 2;
+        set.used_this_turn = true;
+
+        set.gc_on_turn_start();
+        assert_eq!(set.skills[0].current_cooldown, 1);
+        assert!(!set.used_this_turn);
+    }
+
+    #[test]
+    fn test_skill_not_enough_energy() {
+        let mut battle = create_battle();
+        if let Some(p) = battle.gc_find_player_mut("p1") {
+            p.stats.energy = 0;
+        }
+        assert!(matches!(
+            gc_validate_use_skill(&battle, "p1", "skill_strike", "p2"),
+            Err(GcError::GcNotEnoughEnergy)
+        ));
+    }
+}
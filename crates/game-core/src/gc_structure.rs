@@ -0,0 +1,211 @@
+//! 战场建筑/障碍物
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! ## 建筑规则
+//! - 建筑 (城墙、图腾、炮塔) 占用战斗沙盘槽位，但不是怪兽
+//! - 建筑有独立的生命值与防御力，不会攻击、不会反击
+//! - 回合开始时触发建筑的每回合效果
+//! - 目标规则可配置: 城墙优先时，必须先摧毁城墙才能攻击其他单位
+
+use serde::{Deserialize, Serialize};
+
+// =============================================================================
+// 建筑类型
+// =============================================================================
+
+/// 建筑类型
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GcStructureKind {
+    /// 城墙 (阻挡攻击)
+    Wall,
+    /// 图腾 (辅助)
+    Totem,
+    /// 炮塔 (每回合射击)
+    Turret,
+}
+
+/// 建筑每回合效果
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value")]
+pub enum GcStructureTurnEffect {
+    /// 无效果
+    None,
+    /// 治疗己方所有怪兽
+    HealAllies(u32),
+    /// 对敌方ATK最低的怪兽造成伤害 (无怪兽时攻击玩家)
+    DamageEnemy(u32),
+}
+
+/// 攻击目标规则
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GcStructureTargetRule {
+    /// 可自由选择目标
+    #[default]
+    Free,
+    /// 敌方有城墙时必须先攻击城墙
+    WallsFirst,
+}
+
+// =============================================================================
+// 建筑模板与实例
+// =============================================================================
+
+/// 建筑模板 (可从 JSON 加载)
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GcStructureTemplate {
+    pub template_id: String,
+    pub name: String,
+    pub kind: GcStructureKind,
+    pub hp: u32,
+    pub def: u32,
+    pub turn_effect: GcStructureTurnEffect,
+}
+
+impl GcStructureTemplate {
+    /// 创建建筑实例
+    pub fn gc_create_instance(&self, instance_id: &str) -> GcStructure {
+        GcStructure {
+            id: instance_id.to_string(),
+            template_id: self.template_id.clone(),
+            name: self.name.clone(),
+            kind: self.kind,
+            current_hp: self.hp,
+            max_hp: self.hp,
+            def: self.def,
+            turn_effect: self.turn_effect.clone(),
+            slot: None,
+        }
+    }
+}
+
+/// 建筑实例
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GcStructure {
+    pub id: String,
+    pub template_id: String,
+    pub name: String,
+    pub kind: GcStructureKind,
+    pub current_hp: u32,
+    pub max_hp: u32,
+    pub def: u32,
+    pub turn_effect: GcStructureTurnEffect,
+    /// 所在槽位
+    pub slot: Option<u8>,
+}
+
+impl GcStructure {
+    /// 受到攻击 (攻击力减去防御力)，返回是否被摧毁
+    pub fn take_hit(&mut self, atk: u32) -> bool {
+        let damage = atk.saturating_sub(self.def);
+        self.current_hp = self.current_hp.saturating_sub(damage);
+        self.current_hp == 0
+    }
+
+    /// 是否存活
+    pub fn is_alive(&self) -> bool {
+        self.current_hp > 0
+    }
+
+    /// 是否阻挡攻击
+    pub fn is_wall(&self) -> bool {
+        self.kind == GcStructureKind::Wall
+    }
+}
+
+/// 建筑每回合效果结果
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GcStructureTickResult {
+    /// 建筑ID
+    pub structure_id: String,
+    /// 效果目标槽位 (None = 玩家本体或全体)
+    pub target_slot: Option<u8>,
+    /// 效果数值
+    pub value: u32,
+    /// 描述
+    pub description: String,
+}
+
+// =============================================================================
+// 默认模板
+// =============================================================================
+
+/// 获取默认建筑模板
+pub fn gc_default_structure_templates() -> Vec<GcStructureTemplate> {
+    vec![
+        GcStructureTemplate {
+            template_id: "wooden_wall".to_string(),
+            name: "木墙".to_string(),
+            kind: GcStructureKind::Wall,
+            hp: 80,
+            def: 20,
+            turn_effect: GcStructureTurnEffect::None,
+        },
+        GcStructureTemplate {
+            template_id: "stone_wall".to_string(),
+            name: "石墙".to_string(),
+            kind: GcStructureKind::Wall,
+            hp: 150,
+            def: 50,
+            turn_effect: GcStructureTurnEffect::None,
+        },
+        GcStructureTemplate {
+            template_id: "healing_totem".to_string(),
+            name: "治疗图腾".to_string(),
+            kind: GcStructureKind::Totem,
+            hp: 40,
+            def: 0,
+            turn_effect: GcStructureTurnEffect::HealAllies(10),
+        },
+        GcStructureTemplate {
+            template_id: "arrow_turret".to_string(),
+            name: "箭塔".to_string(),
+            kind: GcStructureKind::Turret,
+            hp: 60,
+            def: 10,
+            turn_effect: GcStructureTurnEffect::DamageEnemy(15),
+        },
+    ]
+}
+
+/// 从 JSON 加载建筑模板
+pub fn gc_structure_templates_from_json(json: &str) -> Result<Vec<GcStructureTemplate>, String> {
+    serde_json::from_str(json).map_err(|e| format!("建筑模板解析失败: {}", e))
+}
+
+/// 按模板ID查找默认建筑模板
+pub fn gc_get_structure_template(template_id: &str) -> Option<GcStructureTemplate> {
+    gc_default_structure_templates()
+        .into_iter()
+        .find(|t| t.template_id == template_id)
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_structure_take_hit() {
+        let mut wall = gc_get_structure_template("wooden_wall")
+            .map(|t| t.gc_create_instance("w1"))
+            .expect("默认模板应存在");
+        assert!(!wall.take_hit(60));
+        assert_eq!(wall.current_hp, 40);
+        assert!(wall.take_hit(100));
+        assert!(!wall.is_alive());
+    }
+
+    #[test]
+    fn test_structure_templates_json_roundtrip() {
+        let json = serde_json::to_string(&gc_default_structure_templates()).unwrap_or_default();
+        let loaded = gc_structure_templates_from_json(&json);
+        assert_eq!(loaded.map(|t| t.len()), Ok(4));
+        assert!(gc_structure_templates_from_json("not json").is_err());
+    }
+}
@@ -11,7 +11,10 @@
 //! - 天赋解锁条件
 
 use serde::{Deserialize, Serialize};
-use crate::{GcBaseStats, GcCombatStats, GcProfessionType, GcStatType};
+use crate::{
+    GcBaseStats, GcCombatStats, GcLocale, GcModifierBucket, GcPercentStack, GcProfessionType, GcStatType, gc_add_signed,
+    gc_add_signed_capped, gc_apply_percent, gc_format_number, gc_format_percent,
+};
 
 // =============================================================================
 // 天赋效果类型
@@ -64,15 +67,22 @@ pub enum GcTalentEffect {
 impl GcTalentEffect {
     /// 获取效果描述
     pub fn gc_description(&self) -> String {
+        self.gc_description_in(GcLocale::default())
+    }
+
+    /// 获取效果描述 (数值按语言格式化)
+    pub fn gc_description_in(&self, locale: GcLocale) -> String {
+        let fmt_number = |value: i32| gc_format_number(i64::from(value), locale);
+        let fmt_percent = |value: i32| gc_format_percent(value, locale);
         match self {
             GcTalentEffect::AddBaseStat { stat_type, value } => {
-                format!("{} +{}", stat_type.gc_name(), value)
+                format!("{} +{}", stat_type.gc_name(), fmt_number(*value))
             }
             GcTalentEffect::AddCombatStatPercent { stat_name, percent } => {
-                format!("{} +{}%", stat_name, percent)
+                format!("{} +{}", stat_name, fmt_percent(*percent))
             }
             GcTalentEffect::AddCombatStatFlat { stat_name, value } => {
-                format!("{} +{}", stat_name, value)
+                format!("{} +{}", stat_name, fmt_number(*value))
             }
             GcTalentEffect::UnlockSkill { skill_id } => {
                 format!("解锁技能: {}", skill_id)
@@ -84,10 +94,10 @@ impl GcTalentEffect {
                 description.clone()
             }
             GcTalentEffect::DamageBonus { damage_type, percent } => {
-                format!("{}伤害 +{}%", damage_type, percent)
+                format!("{}伤害 +{}", damage_type, fmt_percent(*percent))
             }
             GcTalentEffect::ResistanceBonus { damage_type, percent } => {
-                format!("{}抗性 +{}%", damage_type, percent)
+                format!("{}抗性 +{}", damage_type, fmt_percent(*percent))
             }
         }
     }
@@ -104,28 +114,22 @@ impl GcTalentEffect {
         match self {
             GcTalentEffect::AddCombatStatFlat { stat_name, value } => {
                 match stat_name.as_str() {
-                    "max_hp" => stats.max_hp = (stats.max_hp as i32 + value).max(0) as u32,
-                    "physical_attack" => stats.physical_attack = (stats.physical_attack as i32 + value).max(0) as u32,
-                    "magic_attack" => stats.magic_attack = (stats.magic_attack as i32 + value).max(0) as u32,
-                    "physical_defense" => stats.physical_defense = (stats.physical_defense as i32 + value).max(0) as u32,
-                    "magic_defense" => stats.magic_defense = (stats.magic_defense as i32 + value).max(0) as u32,
-                    "crit_rate" => stats.crit_rate = (stats.crit_rate as i32 + value).max(0).min(100) as u32,
-                    "crit_damage" => stats.crit_damage = (stats.crit_damage as i32 + value).max(0) as u32,
-                    "dodge_rate" => stats.dodge_rate = (stats.dodge_rate as i32 + value).max(0).min(100) as u32,
-                    "healing_bonus" => stats.healing_bonus = (stats.healing_bonus as i32 + value).max(0) as u32,
-                    "cooldown_reduction" => stats.cooldown_reduction = (stats.cooldown_reduction as i32 + value).max(0).min(80) as u32,
+                    "max_hp" => stats.max_hp = gc_add_signed(stats.max_hp, *value),
+                    "physical_attack" => stats.physical_attack = gc_add_signed(stats.physical_attack, *value),
+                    "magic_attack" => stats.magic_attack = gc_add_signed(stats.magic_attack, *value),
+                    "physical_defense" => stats.physical_defense = gc_add_signed(stats.physical_defense, *value),
+                    "magic_defense" => stats.magic_defense = gc_add_signed(stats.magic_defense, *value),
+                    "crit_rate" => stats.crit_rate = gc_add_signed_capped(stats.crit_rate, *value, 100),
+                    "crit_damage" => stats.crit_damage = gc_add_signed(stats.crit_damage, *value),
+                    "dodge_rate" => stats.dodge_rate = gc_add_signed_capped(stats.dodge_rate, *value, 100),
+                    "healing_bonus" => stats.healing_bonus = gc_add_signed(stats.healing_bonus, *value),
+                    "cooldown_reduction" => stats.cooldown_reduction = gc_add_signed_capped(stats.cooldown_reduction, *value, 80),
                     _ => {}
                 }
             }
             GcTalentEffect::AddCombatStatPercent { stat_name, percent } => {
-                let multiplier = *percent as f32 / 100.0;
-                match stat_name.as_str() {
-                    "max_hp" => stats.max_hp = (stats.max_hp as f32 * (1.0 + multiplier)) as u32,
-                    "physical_attack" => stats.physical_attack = (stats.physical_attack as f32 * (1.0 + multiplier)) as u32,
-                    "magic_attack" => stats.magic_attack = (stats.magic_attack as f32 * (1.0 + multiplier)) as u32,
-                    "physical_defense" => stats.physical_defense = (stats.physical_defense as f32 * (1.0 + multiplier)) as u32,
-                    "magic_defense" => stats.magic_defense = (stats.magic_defense as f32 * (1.0 + multiplier)) as u32,
-                    _ => {}
+                if let Some(stat) = gc_percent_stat_mut(stats, stat_name) {
+                    *stat = gc_apply_percent(*stat, *percent);
                 }
             }
             _ => {}
@@ -133,6 +137,48 @@ impl GcTalentEffect {
     }
 }
 
+/// 可按百分比加成的战斗属性
+fn gc_percent_stat_mut<'a>(stats: &'a mut GcCombatStats, stat_name: &str) -> Option<&'a mut u32> {
+    match stat_name {
+        "max_hp" => Some(&mut stats.max_hp),
+        "physical_attack" => Some(&mut stats.physical_attack),
+        "magic_attack" => Some(&mut stats.magic_attack),
+        "physical_defense" => Some(&mut stats.physical_defense),
+        "magic_defense" => Some(&mut stats.magic_defense),
+        _ => None,
+    }
+}
+
+/// 批量应用天赋效果到战斗属性
+///
+/// 先按顺序应用固定值，再把同一属性的百分比加成放进天赋分桶相加，一次取整，
+/// 结果与天赋的点选顺序无关。
+fn gc_apply_effects_to_combat_stats<'a>(
+    effects: impl IntoIterator<Item = &'a GcTalentEffect>,
+    stats: &mut GcCombatStats,
+) {
+    let mut stacks: Vec<(&str, GcPercentStack)> = Vec::new();
+    for effect in effects {
+        match effect {
+            GcTalentEffect::AddCombatStatPercent { stat_name, percent } => {
+                match stacks.iter_mut().find(|(name, _)| *name == stat_name.as_str()) {
+                    Some((_, stack)) => stack.gc_add(GcModifierBucket::Talent, *percent),
+                    None => stacks.push((
+                        stat_name,
+                        GcPercentStack::gc_new().gc_with(GcModifierBucket::Talent, *percent),
+                    )),
+                }
+            }
+            _ => effect.gc_apply_to_combat_stats(stats),
+        }
+    }
+    for (stat_name, stack) in stacks {
+        if let Some(stat) = gc_percent_stat_mut(stats, stat_name) {
+            *stat = stack.gc_apply(*stat);
+        }
+    }
+}
+
 // =============================================================================
 // 天赋节点
 // =============================================================================
@@ -149,7 +195,7 @@ pub enum GcTalentNodeType {
 }
 
 /// 天赋节点
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct GcTalentNode {
     /// 节点 ID
     pub id: String,
@@ -301,7 +347,7 @@ impl GcTalentNode {
 // =============================================================================
 
 /// 天赋树
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct GcTalentTree {
     /// 树 ID
     pub id: String,
@@ -433,9 +479,7 @@ impl GcTalentTree {
     
     /// 应用所有效果到战斗属性
     pub fn gc_apply_to_combat_stats(&self, stats: &mut GcCombatStats) {
-        for effect in self.gc_collect_all_effects() {
-            effect.gc_apply_to_combat_stats(stats);
-        }
+        gc_apply_effects_to_combat_stats(self.gc_collect_all_effects(), stats);
     }
 }
 
@@ -444,7 +488,7 @@ impl GcTalentTree {
 // =============================================================================
 
 /// 玩家天赋状态
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct GcPlayerTalents {
     /// 玩家 ID
     pub player_id: String,
@@ -547,9 +591,7 @@ impl GcPlayerTalents {
     
     /// 应用所有天赋效果到战斗属性
     pub fn gc_apply_to_combat_stats(&self, stats: &mut GcCombatStats) {
-        for tree in &self.trees {
-            tree.gc_apply_to_combat_stats(stats);
-        }
+        gc_apply_effects_to_combat_stats(self.gc_collect_all_effects(), stats);
     }
 }
 
@@ -594,6 +636,15 @@ mod tests {
         assert_eq!(effects.len(), 1);
     }
     
+    #[test]
+    fn test_gc_talent_description_locale() {
+        let effect = GcTalentEffect::DamageBonus { damage_type: "物理".to_string(), percent: 15 };
+        assert_eq!(effect.gc_description(), "物理伤害 +15%");
+        assert_eq!(effect.gc_description_in(GcLocale::FrFr), "物理伤害 +15\u{a0}%");
+        let flat = GcTalentEffect::AddCombatStatFlat { stat_name: "max_hp".to_string(), value: 1500 };
+        assert_eq!(flat.gc_description_in(GcLocale::DeDe), "max_hp +1.500");
+    }
+    
     #[test]
     fn test_gc_talent_tree() {
         let mut tree = GcTalentTree::gc_new("tree_knight", "骑士天赋", Some(GcProfessionType::Knight));
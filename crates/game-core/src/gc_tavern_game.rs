@@ -0,0 +1,916 @@
+//! 酒馆对局 (多人)
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! ## 规则
+//! - 每名玩家拥有独立的座位: 玩家数据 (经济/手牌区)、商店、阵容
+//! - 手牌区有容量上限，已满时拒绝购买与撤回 (错误码 6001)
+//! - 手牌区超出容量时 (如容量被效果缩减) 必须先出售多余怪兽
+//! - 扩容效果可将手牌区扩大至上限
+//!
+//! ## 生命与淘汰
+//! - 玩家初始 40 点生命、5 点护甲，护甲先于生命承受伤害
+//! - 战斗失败受到伤害 = 回合基础伤害 + 对手存活怪兽星级之和
+//! - 前 3 回合不会被淘汰 (生命最低保留 1)
+//! - 生命归零即淘汰，按淘汰先后记录名次 (8 人局为 第8 ~ 第1)；
+//!   同回合淘汰时溢出伤害越高名次越靠后
+//! - 仅剩一名存活玩家时其获得第 1 名，对局结束
+//! - 购物阶段结束时记录阵容快照，战斗使用快照；淘汰玩家的快照作为幽灵参战
+//!
+//! ## 皮肤
+//! - 座位带有玩家的皮肤配置 (开局时由服务器按拥有记录设置)，购买的怪兽按配置设置外观
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    GcArenaPosition, GcBattleArena, GcBoardSnapshot, GcError, GcMonster, GcMonsterPool, GcMonsterSkinLoadout,
+    GcPairingCandidate, GcPlayer, GcRuleset, GcRunStats, GcTavernPairing, GcTavernShop, GcTavernSpellOutcome,
+    GC_TAVERN_OPPONENT_MEMORY, gc_apply_monster_skin, gc_pair_opponents, gc_resolve_tavern_spell, gc_sell_monster, gc_validate_tavern_spell,
+};
+
+// =============================================================================
+// 常量
+// =============================================================================
+
+/// 初始生命
+pub const GC_TAVERN_STARTING_HEALTH: u32 = 40;
+
+/// 初始护甲
+pub const GC_TAVERN_STARTING_ARMOR: u32 = 5;
+
+/// 不会被淘汰的回合数 (含)
+pub const GC_TAVERN_IMMORTAL_ROUNDS: u32 = 3;
+
+/// 单场自动战斗最大轮数 (超过判平局)
+pub const GC_TAVERN_MAX_COMBAT_ROUNDS: u32 = 30;
+
+fn gc_default_tavern_health() -> u32 {
+    GC_TAVERN_STARTING_HEALTH
+}
+
+fn gc_default_tavern_armor() -> u32 {
+    GC_TAVERN_STARTING_ARMOR
+}
+
+// =============================================================================
+// 玩家操作
+// =============================================================================
+
+/// 酒馆操作 (购物阶段)
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data")]
+pub enum GcTavernAction {
+    /// 刷新商店
+    Refresh,
+    /// 购买商店槽位中的怪兽
+    Buy { slot: usize },
+    /// 购买并立即结算法术 (`target` 为需要目标的法术指定友方怪兽ID)
+    BuySpell { slot: usize, target: Option<String> },
+    /// 出售手牌区怪兽
+    Sell { monster_id: String },
+    /// 部署手牌区怪兽到阵容
+    Deploy { monster_id: String, position: GcArenaPosition },
+    /// 从阵容撤回到手牌区
+    Recall { position: GcArenaPosition },
+    /// 冻结/解冻商店槽位
+    ToggleFreeze { slot: usize },
+    /// 扩容手牌区
+    ExpandBench { amount: usize },
+}
+
+/// 强制出售候选
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcBenchSellPrompt {
+    /// 怪兽ID
+    pub monster_id: String,
+    /// 名称
+    pub name: String,
+    /// 出售价格
+    pub sell_price: u32,
+}
+
+/// 手牌区超出容量 (需玩家选择出售)
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcBenchOverflow {
+    /// 需要出售的数量
+    pub excess: usize,
+    /// 可出售的怪兽
+    pub candidates: Vec<GcBenchSellPrompt>,
+}
+
+// =============================================================================
+// 座位
+// =============================================================================
+
+/// 玩家座位
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GcTavernSeat {
+    /// 玩家 (经济、手牌区)
+    pub player: GcPlayer,
+    /// 商店
+    pub shop: GcTavernShop,
+    /// 阵容 (使用玩家一侧)
+    pub board: GcBattleArena,
+    /// 生命
+    #[serde(default = "gc_default_tavern_health")]
+    pub health: u32,
+    /// 护甲 (先于生命承受伤害)
+    #[serde(default = "gc_default_tavern_armor")]
+    pub armor: u32,
+    /// 最终名次 (淘汰或获胜后确定)
+    #[serde(default)]
+    pub placement: Option<u8>,
+    /// 最近对手 (最近的在最后)
+    #[serde(default)]
+    pub recent_opponents: Vec<String>,
+    /// 上回合是否对战幽灵
+    #[serde(default)]
+    pub fought_ghost: bool,
+    /// 皮肤配置 (模板 ID -> 皮肤 ID，购买时使用)
+    #[serde(default, skip_serializing_if = "GcMonsterSkinLoadout::is_empty")]
+    pub skin_loadout: GcMonsterSkinLoadout,
+    /// 流程统计 (结算得分用)
+    #[serde(default)]
+    pub run_stats: GcRunStats,
+}
+
+impl GcTavernSeat {
+    /// 创建座位
+    pub fn gc_new(player_id: &str, name: &str) -> Self {
+        Self {
+            player: GcPlayer::gc_new_tavern(player_id, name),
+            shop: GcTavernShop::new(),
+            board: GcBattleArena::default(),
+            health: GC_TAVERN_STARTING_HEALTH,
+            armor: GC_TAVERN_STARTING_ARMOR,
+            placement: None,
+            recent_opponents: Vec::new(),
+            fought_ghost: false,
+            skin_loadout: GcMonsterSkinLoadout::new(),
+            run_stats: GcRunStats::default(),
+        }
+    }
+
+    /// 是否仍在对局中 (未被淘汰)
+    pub fn gc_is_alive(&self) -> bool {
+        self.health > 0
+    }
+
+    /// 记录本回合对手
+    fn gc_record_opponent(&mut self, opponent_id: &str, ghost: bool) {
+        self.recent_opponents.push(opponent_id.to_string());
+        if self.recent_opponents.len() > GC_TAVERN_OPPONENT_MEMORY {
+            self.recent_opponents.remove(0);
+        }
+        self.fought_ghost = ghost;
+    }
+
+    /// 承受战斗伤害
+    ///
+    /// 护甲先吸收伤害；不死回合内生命最低保留 1
+    pub fn gc_take_damage(&mut self, amount: u32, round: u32) -> GcTavernDamage {
+        let absorbed = amount.min(self.armor);
+        self.armor -= absorbed;
+        let to_health = amount - absorbed;
+        let overkill = to_health.saturating_sub(self.health);
+
+        let floor = if round <= GC_TAVERN_IMMORTAL_ROUNDS { self.health.min(1) } else { 0 };
+        self.health = self.health.saturating_sub(to_health).max(floor);
+
+        GcTavernDamage {
+            player_id: self.player.id.clone(),
+            damage: amount,
+            absorbed,
+            health: self.health,
+            eliminated: !self.gc_is_alive(),
+            overkill,
+        }
+    }
+
+    /// 手牌区超出容量时返回强制出售提示
+    pub fn gc_bench_overflow(&self) -> Option<GcBenchOverflow> {
+        let excess = self.player.bench.len().checked_sub(self.player.bench_capacity)?;
+        if excess == 0 {
+            return None;
+        }
+        Some(GcBenchOverflow {
+            excess,
+            candidates: self.player.bench.iter()
+                .map(|m| GcBenchSellPrompt {
+                    monster_id: m.id.clone(),
+                    name: m.name.clone(),
+                    sell_price: m.sell_price(),
+                })
+                .collect(),
+        })
+    }
+
+    /// 购买怪兽到手牌区
+    pub fn gc_buy(&mut self, slot: usize) -> Result<GcMonster, GcError> {
+        if self.player.gc_is_bench_full() {
+            return Err(GcError::GcBenchFull);
+        }
+        let price = self.shop.slot_price(slot)
+            .ok_or(GcError::GcShopSlotEmpty)?
+            .price;
+        if !self.player.economy.can_afford(price) {
+            return Err(GcError::GcNotEnoughGold);
+        }
+
+        let mut monster = self.shop.buy(slot, &mut self.player.economy)
+            .ok_or(GcError::GcShopSlotEmpty)?;
+        gc_apply_monster_skin(&mut monster, &self.skin_loadout);
+        self.player.gc_try_add_to_bench(monster.clone())?;
+        Ok(monster)
+    }
+
+    /// 购买法术并立即结算 (目标不合法时不扣金币)
+    ///
+    /// - `roll`: 发现怪兽使用的随机数
+    pub fn gc_buy_spell(
+        &mut self,
+        slot: usize,
+        target: Option<&str>,
+        pool: &GcMonsterPool,
+        roll: u8,
+    ) -> Result<GcTavernSpellOutcome, GcError> {
+        let spell = self.shop.get_spell(slot).ok_or(GcError::GcShopSlotEmpty)?;
+        gc_validate_tavern_spell(spell, target, &self.player, &self.board)?;
+        if !self.player.economy.can_afford(spell.cost) {
+            return Err(GcError::GcNotEnoughGold);
+        }
+
+        let spell = self.shop.buy_spell(slot, &mut self.player.economy)
+            .ok_or(GcError::GcShopSlotEmpty)?;
+        gc_resolve_tavern_spell(&spell, target, &mut self.player, &mut self.board, pool, roll)
+    }
+
+    /// 出售手牌区怪兽，返回获得的金币
+    pub fn gc_sell(&mut self, monster_id: &str) -> Result<u32, GcError> {
+        let monster = self.player.gc_remove_from_bench(monster_id)
+            .ok_or_else(|| GcError::GcInvalidAction("手牌区没有该怪兽".to_string()))?;
+        Ok(gc_sell_monster(&monster, &mut self.player.economy))
+    }
+
+    /// 部署手牌区怪兽
+    pub fn gc_deploy(&mut self, monster_id: &str, position: GcArenaPosition) -> Result<(), GcError> {
+        self.board.deploy_from_bench_to(&mut self.player.bench, monster_id, position)
+            .map_err(GcError::GcInvalidAction)
+    }
+
+    /// 撤回阵容怪兽到手牌区
+    pub fn gc_recall(&mut self, position: GcArenaPosition) -> Result<GcMonster, GcError> {
+        if self.player.gc_is_bench_full() {
+            return Err(GcError::GcBenchFull);
+        }
+        self.board.recall_from(&mut self.player.bench, position)
+            .ok_or(GcError::GcInvalidSlot)
+    }
+
+    /// 执行酒馆操作
+    ///
+    /// - `rolls`: 刷新商店所需随机数 (调用方提供)
+    pub fn gc_apply_action(
+        &mut self,
+        action: &GcTavernAction,
+        pool: &GcMonsterPool,
+        rolls: &[u8],
+    ) -> Result<(), GcError> {
+        // 超出容量时只允许出售
+        if self.gc_bench_overflow().is_some() && !matches!(action, GcTavernAction::Sell { .. }) {
+            return Err(GcError::GcBenchFull);
+        }
+
+        let gold_before = self.player.economy.gold;
+        match action {
+            GcTavernAction::Refresh => {
+                if !self.shop.refresh(&mut self.player.economy, pool, rolls) {
+                    return Err(GcError::GcNotEnoughGold);
+                }
+            }
+            GcTavernAction::Buy { slot } => {
+                self.gc_buy(*slot)?;
+            }
+            GcTavernAction::BuySpell { slot, target } => {
+                let roll = rolls.first().copied().unwrap_or(0);
+                self.gc_buy_spell(*slot, target.as_deref(), pool, roll)?;
+            }
+            GcTavernAction::Sell { monster_id } => {
+                self.gc_sell(monster_id)?;
+            }
+            GcTavernAction::Deploy { monster_id, position } => {
+                self.gc_deploy(monster_id, *position)?;
+            }
+            GcTavernAction::Recall { position } => {
+                self.gc_recall(*position)?;
+            }
+            GcTavernAction::ToggleFreeze { slot } => {
+                if !self.shop.toggle_freeze(*slot) {
+                    return Err(GcError::GcShopSlotEmpty);
+                }
+            }
+            GcTavernAction::ExpandBench { amount } => {
+                self.player.gc_expand_bench(*amount);
+            }
+        }
+        let spent = gold_before.saturating_sub(self.player.economy.gold);
+        self.run_stats.gold_spent = self.run_stats.gold_spent.saturating_add(spent);
+        Ok(())
+    }
+}
+
+// =============================================================================
+// 战斗伤害
+// =============================================================================
+
+/// 一场战斗的结果 (平局不产生结果)
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcTavernCombatOutcome {
+    /// 胜者ID
+    pub winner_id: String,
+    /// 败者ID
+    pub loser_id: String,
+    /// 胜者存活怪兽星级之和
+    pub surviving_stars: u32,
+}
+
+/// 玩家受到的战斗伤害
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcTavernDamage {
+    /// 玩家ID
+    pub player_id: String,
+    /// 总伤害
+    pub damage: u32,
+    /// 护甲吸收
+    pub absorbed: u32,
+    /// 剩余生命
+    pub health: u32,
+    /// 是否被淘汰
+    pub eliminated: bool,
+    /// 溢出伤害 (同回合淘汰时决定名次)
+    pub overkill: u32,
+}
+
+/// 回合结算报告
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcTavernRoundReport {
+    /// 结算的回合
+    pub round: u32,
+    /// 本回合对战配对
+    #[serde(default)]
+    pub pairings: Vec<GcTavernPairing>,
+    /// 本回合伤害
+    pub damages: Vec<GcTavernDamage>,
+    /// 本回合确定的名次 (玩家ID, 名次)
+    pub placements: Vec<(String, u8)>,
+    /// 对局胜者 (对局结束时)
+    pub winner_id: Option<String>,
+}
+
+/// 回合基础伤害
+pub fn gc_round_base_damage(round: u32) -> u32 {
+    match round {
+        0..=3 => 2,
+        4..=6 => 4,
+        7..=10 => 6,
+        _ => 8,
+    }
+}
+
+/// 战斗失败受到的伤害
+pub fn gc_tavern_combat_damage(round: u32, surviving_stars: u32) -> u32 {
+    gc_round_base_damage(round) + surviving_stars
+}
+
+/// 一组槽位中存活怪兽的星级之和
+fn gc_alive_stars<'a>(slots: impl Iterator<Item = &'a Option<GcMonster>>) -> u32 {
+    slots.flatten()
+        .filter(|m| m.is_alive())
+        .map(|m| m.star as u32)
+        .sum()
+}
+
+/// 两名玩家阵容进行自动战斗
+///
+/// `a_board` 作为南方 (玩家侧)，`b_board` 的玩家侧放到北方；平局返回 None
+pub fn gc_resolve_board_combat(
+    a_id: &str,
+    a_board: &GcBattleArena,
+    b_id: &str,
+    b_board: &GcBattleArena,
+) -> Option<GcTavernCombatOutcome> {
+    let mut arena = a_board.clone();
+    arena.enemy_terrain = b_board.player_terrain;
+    arena.enemy_monsters = b_board.player_monsters.clone();
+    arena.enemy_back_row = b_board.player_back_row.clone();
+    arena.enemy_structures = b_board.player_structures.clone();
+
+    let a_stars = |ar: &GcBattleArena| gc_alive_stars(ar.player_monsters.iter().chain(ar.player_back_row.iter()));
+    let b_stars = |ar: &GcBattleArena| gc_alive_stars(ar.enemy_monsters.iter().chain(ar.enemy_back_row.iter()));
+
+    for _ in 0..GC_TAVERN_MAX_COMBAT_ROUNDS {
+        if a_stars(&arena) == 0 || b_stars(&arena) == 0 {
+            break;
+        }
+        arena.run_combat_round(None);
+    }
+
+    match (a_stars(&arena), b_stars(&arena)) {
+        (0, 0) => None,
+        (stars, 0) => Some(GcTavernCombatOutcome {
+            winner_id: a_id.to_string(),
+            loser_id: b_id.to_string(),
+            surviving_stars: stars,
+        }),
+        (0, stars) => Some(GcTavernCombatOutcome {
+            winner_id: b_id.to_string(),
+            loser_id: a_id.to_string(),
+            surviving_stars: stars,
+        }),
+        _ => None,
+    }
+}
+
+// =============================================================================
+// 对局
+// =============================================================================
+
+/// 酒馆对局
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GcTavernGame {
+    /// 当前回合 (从 1 开始)
+    pub round: u32,
+    /// 玩家座位
+    pub seats: Vec<GcTavernSeat>,
+    /// 公共怪兽池
+    pub pool: GcMonsterPool,
+    /// 阵容快照 (每名玩家最近一次购物阶段结束时的阵容)
+    #[serde(default)]
+    pub snapshots: Vec<GcBoardSnapshot>,
+    /// 规则集 (每周挑战等)
+    #[serde(default)]
+    pub ruleset: Option<GcRuleset>,
+}
+
+impl GcTavernGame {
+    /// 创建对局
+    ///
+    /// - `players`: (玩家ID, 名称) 列表
+    pub fn gc_new(players: &[(String, String)]) -> Self {
+        Self {
+            round: 1,
+            seats: players.iter()
+                .map(|(id, name)| GcTavernSeat::gc_new(id, name))
+                .collect(),
+            pool: GcMonsterPool::with_defaults(),
+            snapshots: Vec::new(),
+            ruleset: None,
+        }
+    }
+
+    /// 为所有座位免费刷新商店 (回合开始)
+    pub fn gc_fill_shops(&mut self, rolls: &[u8]) {
+        for seat in &mut self.seats {
+            seat.shop.free_refresh(&self.pool, rolls);
+        }
+    }
+
+    /// 新回合购物阶段开始: 存活玩家领取收入并免费刷新商店
+    pub fn gc_begin_shopping(&mut self, rolls: &[u8]) {
+        for seat in self.seats.iter_mut().filter(|s| s.gc_is_alive()) {
+            seat.player.economy.start_new_turn();
+            seat.player.economy.collect_income();
+            seat.shop.free_refresh(&self.pool, rolls);
+            seat.shop.start_turn(self.round);
+        }
+    }
+
+    /// 查找座位
+    pub fn gc_seat(&self, player_id: &str) -> Option<&GcTavernSeat> {
+        self.seats.iter().find(|s| s.player.id == player_id)
+    }
+
+    /// 查找座位 (可变)
+    pub fn gc_seat_mut(&mut self, player_id: &str) -> Option<&mut GcTavernSeat> {
+        self.seats.iter_mut().find(|s| s.player.id == player_id)
+    }
+
+    /// 执行玩家的酒馆操作 (规则集的刷新/购买修改器在此生效)
+    pub fn gc_apply_action(
+        &mut self,
+        player_id: &str,
+        action: &GcTavernAction,
+        rolls: &[u8],
+    ) -> Result<&GcTavernSeat, GcError> {
+        let pool = &self.pool;
+        let ruleset = self.ruleset.as_ref();
+        let seat = self.seats.iter_mut()
+            .find(|s| s.player.id == player_id)
+            .ok_or(GcError::GcPlayerNotFound)?;
+        if !seat.gc_is_alive() {
+            return Err(GcError::GcPlayerCannotAct);
+        }
+
+        match (action, ruleset) {
+            (GcTavernAction::Refresh, Some(ruleset)) if ruleset.gc_free_refresh() => {
+                if seat.gc_bench_overflow().is_some() {
+                    return Err(GcError::GcBenchFull);
+                }
+                seat.shop.free_refresh(pool, rolls);
+            }
+            (GcTavernAction::Buy { slot }, Some(ruleset)) => {
+                let bought_id = seat.shop.get_slot(*slot).map(|m| m.id.clone());
+                seat.gc_apply_action(action, pool, rolls)?;
+                if let Some(monster) = seat.player.bench.iter_mut().find(|m| Some(&m.id) == bought_id.as_ref()) {
+                    ruleset.gc_apply_to_bought(monster);
+                }
+            }
+            _ => seat.gc_apply_action(action, pool, rolls)?,
+        }
+        Ok(seat)
+    }
+
+    /// 存活玩家数
+    pub fn gc_alive_count(&self) -> usize {
+        self.seats.iter().filter(|s| s.gc_is_alive()).count()
+    }
+
+    /// 对局是否结束 (存活玩家不超过 1 名)
+    pub fn gc_is_finished(&self) -> bool {
+        self.gc_alive_count() <= 1
+    }
+
+    /// 记录玩家阵容快照 (购物阶段结束时)
+    pub fn gc_snapshot_board(&mut self, player_id: &str) -> Result<(), GcError> {
+        let seat = self.gc_seat(player_id).ok_or(GcError::GcPlayerNotFound)?;
+        let snapshot = GcBoardSnapshot {
+            player_id: player_id.to_string(),
+            round: self.round,
+            board: seat.board.clone(),
+        };
+        self.snapshots.retain(|s| s.player_id != player_id);
+        self.snapshots.push(snapshot);
+        Ok(())
+    }
+
+    /// 参战阵容: 优先使用快照，没有快照时使用当前阵容
+    fn gc_combat_board(&self, player_id: &str) -> Option<&GcBattleArena> {
+        self.snapshots.iter()
+            .find(|s| s.player_id == player_id)
+            .map(|s| &s.board)
+            .or_else(|| self.gc_seat(player_id).map(|s| &s.board))
+    }
+
+    /// 计算本回合对战配对 (幽灵为有快照的已淘汰玩家，最近淘汰的优先)
+    pub fn gc_pair_round(&self, rolls: &[u8]) -> Vec<GcTavernPairing> {
+        let candidates: Vec<GcPairingCandidate> = self.seats.iter()
+            .filter(|s| s.gc_is_alive())
+            .map(|s| GcPairingCandidate {
+                player_id: s.player.id.clone(),
+                recent_opponents: s.recent_opponents.clone(),
+                fought_ghost: s.fought_ghost,
+            })
+            .collect();
+
+        let mut dead: Vec<&GcTavernSeat> = self.seats.iter()
+            .filter(|s| !s.gc_is_alive())
+            .filter(|s| self.snapshots.iter().any(|snap| snap.player_id == s.player.id))
+            .collect();
+        dead.sort_by_key(|s| s.placement.unwrap_or(u8::MAX));
+        let ghosts: Vec<String> = dead.iter().map(|s| s.player.id.clone()).collect();
+
+        gc_pair_opponents(&candidates, &ghosts, rolls)
+    }
+
+    /// 执行配对战斗并记录对手
+    ///
+    /// 幽灵获胜时主场玩家受到伤害；幽灵落败不产生伤害
+    pub fn gc_run_pairings(&mut self, pairings: &[GcTavernPairing]) -> Vec<GcTavernCombatOutcome> {
+        let mut outcomes = Vec::new();
+        for pairing in pairings {
+            let (Some(home), Some(away)) = (
+                self.gc_combat_board(&pairing.home_id),
+                self.gc_combat_board(&pairing.away_id),
+            ) else {
+                continue;
+            };
+            if let Some(outcome) = gc_resolve_board_combat(&pairing.home_id, home, &pairing.away_id, away) {
+                outcomes.push(outcome);
+            }
+
+            if let Some(seat) = self.gc_seat_mut(&pairing.home_id) {
+                seat.gc_record_opponent(&pairing.away_id, pairing.ghost);
+            }
+            if !pairing.ghost {
+                if let Some(seat) = self.gc_seat_mut(&pairing.away_id) {
+                    seat.gc_record_opponent(&pairing.home_id, false);
+                }
+            }
+        }
+        outcomes
+    }
+
+    /// 进行一回合战斗: 配对、战斗、结算伤害与淘汰
+    pub fn gc_play_round(&mut self, rolls: &[u8]) -> GcTavernRoundReport {
+        let pairings = self.gc_pair_round(rolls);
+        let outcomes = self.gc_run_pairings(&pairings);
+        let mut report = self.gc_resolve_round(&outcomes);
+        report.pairings = pairings;
+        report
+    }
+
+    /// 结算本回合所有战斗，进入下一回合
+    pub fn gc_resolve_round(&mut self, outcomes: &[GcTavernCombatOutcome]) -> GcTavernRoundReport {
+        let round = self.round;
+        let alive_before = self.gc_alive_count();
+        let mut report = GcTavernRoundReport { round, ..Default::default() };
+
+        for seat in self.seats.iter_mut().filter(|s| s.gc_is_alive()) {
+            seat.run_stats.rounds_survived += 1;
+        }
+
+        for outcome in outcomes {
+            let Some(seat) = self.gc_seat_mut(&outcome.loser_id).filter(|s| s.gc_is_alive()) else {
+                continue;
+            };
+            let damage = gc_tavern_combat_damage(round, outcome.surviving_stars);
+            report.damages.push(seat.gc_take_damage(damage, round));
+            // 幽灵获胜不计入已淘汰玩家的统计
+            if let Some(winner) = self.gc_seat_mut(&outcome.winner_id).filter(|s| s.gc_is_alive()) {
+                winner.run_stats.wins += 1;
+                winner.run_stats.damage_dealt = winner.run_stats.damage_dealt.saturating_add(damage);
+            }
+        }
+
+        // 同回合淘汰: 溢出伤害越高名次越靠后
+        let mut eliminated: Vec<&GcTavernDamage> = report.damages.iter().filter(|d| d.eliminated).collect();
+        eliminated.sort_by_key(|d| std::cmp::Reverse(d.overkill));
+        let mut placements: Vec<(String, u8)> = eliminated.iter()
+            .enumerate()
+            .map(|(i, d)| (d.player_id.clone(), (alive_before - i) as u8))
+            .collect();
+
+        if self.gc_alive_count() == 1 {
+            if let Some(winner) = self.seats.iter().find(|s| s.gc_is_alive()) {
+                placements.push((winner.player.id.clone(), 1));
+                report.winner_id = Some(winner.player.id.clone());
+            }
+        }
+
+        for (player_id, placement) in &placements {
+            if let Some(seat) = self.gc_seat_mut(player_id) {
+                seat.placement = Some(*placement);
+            }
+        }
+        report.placements = placements;
+
+        if !self.gc_is_finished() {
+            self.round += 1;
+        }
+        report
+    }
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GcMonsterAttribute, GcRunScore, GC_MAX_BENCH_CAPACITY, GC_RUN_SCORE_PER_DAMAGE};
+
+    fn slime(id: &str) -> GcMonster {
+        GcMonster::new_with_template(id, "slime", "史莱姆", 1, GcMonsterAttribute::Water, 50, 30, 60)
+    }
+
+    fn create_seat() -> GcTavernSeat {
+        let mut seat = GcTavernSeat::gc_new("p1", "玩家");
+        seat.player.economy.gold = 10;
+        seat.shop.slots[0] = Some(slime("shop_1"));
+        seat
+    }
+
+    #[test]
+    fn test_buy_rejected_when_bench_full() {
+        let mut seat = create_seat();
+        seat.player.bench_capacity = 1;
+        seat.player.bench.push(slime("b1"));
+
+        let result = seat.gc_buy(0);
+        assert!(matches!(result, Err(GcError::GcBenchFull)));
+        assert_eq!(result.err().map(|e| e.gc_code()), Some(6001));
+        assert_eq!(seat.player.economy.gold, 10);
+        assert!(seat.shop.slots[0].is_some());
+
+        assert_eq!(seat.player.gc_expand_bench(100), GC_MAX_BENCH_CAPACITY);
+        assert!(seat.gc_buy(0).is_ok());
+        assert_eq!(seat.player.gc_bench_count(), 2);
+    }
+
+    #[test]
+    fn test_buy_applies_equipped_skin() {
+        let mut seat = create_seat();
+        seat.shop.slots[1] = Some(GcMonster::new_with_template("shop_2", "dragon", "龙", 4, GcMonsterAttribute::Fire, 90, 60, 120));
+        seat.skin_loadout.insert("slime".to_string(), "frost".to_string());
+
+        let bought = seat.gc_buy(0).expect("购买");
+        assert_eq!(bought.skin.as_deref(), Some("frost"));
+        assert_eq!(seat.player.bench[0].skin.as_deref(), Some("frost"));
+        assert_eq!(seat.gc_buy(1).expect("购买").skin, None);
+
+        // 皮肤随座位序列化，对手可见
+        let json = serde_json::to_value(&seat).expect("序列化");
+        assert_eq!(json["player"]["bench"][0]["skin"], "frost");
+        assert!(json["player"]["bench"][1].get("skin").is_none());
+    }
+
+    #[test]
+    fn test_recall_rejected_when_bench_full() {
+        let mut seat = create_seat();
+        seat.player.bench.push(slime("b1"));
+        assert!(seat.gc_deploy("b1", GcArenaPosition::front(0)).is_ok());
+
+        seat.player.bench_capacity = 0;
+        assert!(matches!(seat.gc_recall(GcArenaPosition::front(0)), Err(GcError::GcBenchFull)));
+
+        seat.player.bench_capacity = 1;
+        assert!(seat.gc_recall(GcArenaPosition::front(0)).is_ok());
+    }
+
+    #[test]
+    fn test_buy_spell_resolves_immediately() {
+        let tonic = crate::gc_default_tavern_spells().into_iter().find(|s| s.spell_id == "spell_tonic").expect("默认法术");
+        let mut game = GcTavernGame::gc_new(&[("p1".to_string(), "玩家".to_string())]);
+        let seat = game.gc_seat_mut("p1").expect("座位存在");
+        seat.player.economy.gold = 5;
+        seat.player.bench.push(slime("b1"));
+        seat.shop.spell_slots[0] = Some(tonic.clone());
+
+        // 目标不合法: 不扣金币，法术保留
+        let invalid = GcTavernAction::BuySpell { slot: 0, target: Some("nobody".to_string()) };
+        assert!(matches!(game.gc_apply_action("p1", &invalid, &[]), Err(GcError::GcInvalidTarget)));
+        let seat = game.gc_seat("p1").expect("座位存在");
+        assert_eq!(seat.player.economy.gold, 5);
+        assert!(seat.shop.get_spell(0).is_some());
+
+        let buy = GcTavernAction::BuySpell { slot: 0, target: Some("b1".to_string()) };
+        let seat = game.gc_apply_action("p1", &buy, &[]).expect("购买法术");
+        assert_eq!(seat.player.economy.gold, 5 - tonic.cost);
+        assert_eq!(seat.player.bench[0].base_atk, 60);
+        assert!(seat.shop.get_spell(0).is_none());
+        assert!(matches!(game.gc_apply_action("p1", &buy, &[]), Err(GcError::GcShopSlotEmpty)));
+    }
+
+    #[test]
+    fn test_overflow_forces_sell() {
+        let mut game = GcTavernGame::gc_new(&[("p1".to_string(), "玩家".to_string())]);
+        let seat = game.gc_seat_mut("p1").expect("座位存在");
+        seat.player.bench.push(slime("b1"));
+        seat.player.bench.push(slime("b2"));
+        seat.player.bench_capacity = 1;
+
+        let overflow = seat.gc_bench_overflow().expect("应超出容量");
+        assert_eq!(overflow.excess, 1);
+        assert_eq!(overflow.candidates.len(), 2);
+
+        let refresh = game.gc_apply_action("p1", &GcTavernAction::Refresh, &[]);
+        assert!(matches!(refresh, Err(GcError::GcBenchFull)));
+
+        let sell = GcTavernAction::Sell { monster_id: "b2".to_string() };
+        assert!(game.gc_apply_action("p1", &sell, &[]).is_ok());
+        assert!(game.gc_seat("p1").and_then(|s| s.gc_bench_overflow()).is_none());
+    }
+
+    fn create_game(count: usize) -> GcTavernGame {
+        let players: Vec<(String, String)> = (1..=count)
+            .map(|i| (format!("p{}", i), format!("玩家{}", i)))
+            .collect();
+        GcTavernGame::gc_new(&players)
+    }
+
+    fn loss(winner: &str, loser: &str, stars: u32) -> GcTavernCombatOutcome {
+        GcTavernCombatOutcome {
+            winner_id: winner.to_string(),
+            loser_id: loser.to_string(),
+            surviving_stars: stars,
+        }
+    }
+
+    #[test]
+    fn test_damage_armor_and_immortal_rounds() {
+        let mut game = create_game(2);
+        if let Some(seat) = game.gc_seat_mut("p2") {
+            seat.health = 3;
+        }
+
+        let report = game.gc_resolve_round(&[loss("p1", "p2", 10)]);
+        assert_eq!(report.damages[0].damage, 12);
+        assert_eq!(report.damages[0].absorbed, GC_TAVERN_STARTING_ARMOR);
+        assert_eq!(report.damages[0].health, 1);
+        assert!(!report.damages[0].eliminated);
+        assert_eq!(game.round, 2);
+
+        game.round = GC_TAVERN_IMMORTAL_ROUNDS + 1;
+        let report = game.gc_resolve_round(&[loss("p1", "p2", 0)]);
+        assert!(report.damages[0].eliminated);
+        assert_eq!(report.winner_id.as_deref(), Some("p1"));
+        assert!(game.gc_is_finished());
+        assert_eq!(game.gc_seat("p2").and_then(|s| s.placement), Some(2));
+        assert!(matches!(
+            game.gc_apply_action("p2", &GcTavernAction::Refresh, &[]),
+            Err(GcError::GcPlayerCannotAct)
+        ));
+    }
+
+    #[test]
+    fn test_same_round_placements_by_overkill() {
+        let mut game = create_game(4);
+        game.round = 10;
+        for id in ["p3", "p4"] {
+            if let Some(seat) = game.gc_seat_mut(id) {
+                seat.health = 1;
+                seat.armor = 0;
+            }
+        }
+
+        let report = game.gc_resolve_round(&[loss("p1", "p3", 2), loss("p2", "p4", 9)]);
+        assert_eq!(report.placements, vec![("p4".to_string(), 4), ("p3".to_string(), 3)]);
+        assert!(report.winner_id.is_none());
+        assert_eq!(game.gc_alive_count(), 2);
+        assert_eq!(game.round, 11);
+    }
+
+    #[test]
+    fn test_ghost_pairing_uses_snapshot() {
+        let mut game = create_game(4);
+        game.round = 10;
+        if let Some(seat) = game.gc_seat_mut("p4") {
+            seat.board.player_monsters[0] = Some(slime("ghost_slime"));
+        }
+        assert!(game.gc_snapshot_board("p4").is_ok());
+        if let Some(seat) = game.gc_seat_mut("p4") {
+            seat.health = 0;
+            seat.placement = Some(4);
+            seat.board = GcBattleArena::default();
+        }
+
+        let pairings = game.gc_pair_round(&[]);
+        assert_eq!(pairings.len(), 2);
+        let ghost = pairings.iter().find(|p| p.ghost).expect("应有幽灵对局");
+        assert_eq!(ghost.away_id, "p4");
+
+        let outcomes = game.gc_run_pairings(&pairings);
+        assert!(outcomes.iter().any(|o| o.winner_id == "p4" && o.loser_id == ghost.home_id));
+        let home = game.gc_seat(&ghost.home_id).expect("座位存在");
+        assert!(home.fought_ghost);
+        assert_eq!(home.recent_opponents, vec!["p4".to_string()]);
+
+        let report = game.gc_resolve_round(&outcomes);
+        assert!(report.damages.iter().all(|d| d.player_id != "p4"));
+        assert_eq!(game.gc_seat("p4").map(|s| s.run_stats.clone()), Some(GcRunStats::default()));
+    }
+
+    #[test]
+    fn test_run_stats_and_final_score() {
+        let mut game = create_game(2);
+        if let Some(seat) = game.gc_seat_mut("p1") {
+            seat.player.economy.gold = 10;
+        }
+        assert!(game.gc_apply_action("p1", &GcTavernAction::Refresh, &[]).is_ok());
+        let refresh_cost = 10 - game.gc_seat("p1").map_or(0, |s| s.player.economy.gold);
+        assert!(refresh_cost > 0);
+
+        game.round = GC_TAVERN_IMMORTAL_ROUNDS + 1;
+        if let Some(seat) = game.gc_seat_mut("p2") {
+            seat.health = 1;
+            seat.armor = 0;
+        }
+        let report = game.gc_resolve_round(&[loss("p1", "p2", 1)]);
+        assert!(game.gc_is_finished());
+
+        let stats = &game.gc_seat("p1").expect("座位存在").run_stats;
+        assert_eq!((stats.rounds_survived, stats.wins, stats.gold_spent), (1, 1, refresh_cost));
+        assert_eq!(stats.damage_dealt, report.damages[0].damage);
+
+        let scores = GcRunScore::gc_from_tavern_game(&game);
+        assert_eq!(scores.iter().map(|s| s.placement).collect::<Vec<_>>(), vec![Some(1), Some(2)]);
+        assert_eq!(scores[0].breakdown.damage_points, stats.damage_dealt * GC_RUN_SCORE_PER_DAMAGE);
+        assert_eq!(scores[1].breakdown.damage_dealt, 0);
+    }
+
+    #[test]
+    fn test_board_combat_counts_surviving_stars() {
+        let mut a = GcBattleArena::default();
+        let mut monster = slime("a1");
+        monster.star = 2;
+        a.player_monsters[0] = Some(monster);
+        let b = GcBattleArena::default();
+
+        let outcome = gc_resolve_board_combat("p1", &a, "p2", &b);
+        assert_eq!(outcome, Some(loss("p1", "p2", 2)));
+        assert!(gc_resolve_board_combat("p1", &b, "p2", &b).is_none());
+    }
+}
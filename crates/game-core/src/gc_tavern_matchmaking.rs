@@ -0,0 +1,226 @@
+//! 酒馆对手匹配
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! ## 匹配规则
+//! - 每回合存活玩家两两配对，尽量避开最近交手过的对手 (越近代价越高)
+//! - 存活人数为奇数时，一名玩家对战幽灵 (已淘汰玩家的阵容快照)
+//! - 幽灵优先选择最近被淘汰的玩家；上回合刚打过幽灵的玩家尽量不再轮到
+//! - 同代价时按随机数打乱后的顺序决定 (随机数由调用方提供)
+
+use serde::{Deserialize, Serialize};
+
+use crate::GcBattleArena;
+
+// =============================================================================
+// 常量
+// =============================================================================
+
+/// 记录的最近对手数量
+pub const GC_TAVERN_OPPONENT_MEMORY: usize = 3;
+
+// =============================================================================
+// 匹配数据
+// =============================================================================
+
+/// 匹配候选 (存活玩家)
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcPairingCandidate {
+    /// 玩家ID
+    pub player_id: String,
+    /// 最近对手 (最近的在最后)
+    pub recent_opponents: Vec<String>,
+    /// 上回合是否对战幽灵
+    pub fought_ghost: bool,
+}
+
+/// 一组对战
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcTavernPairing {
+    /// 主场玩家ID
+    pub home_id: String,
+    /// 客场玩家ID (幽灵时为已淘汰玩家)
+    pub away_id: String,
+    /// 客场是否为幽灵
+    pub ghost: bool,
+}
+
+/// 阵容快照 (购物阶段结束时记录，淘汰后作为幽灵)
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GcBoardSnapshot {
+    /// 玩家ID
+    pub player_id: String,
+    /// 记录时的回合
+    pub round: u32,
+    /// 阵容
+    pub board: GcBattleArena,
+}
+
+// =============================================================================
+// 匹配
+// =============================================================================
+
+/// 与某对手重复交手的代价 (越近越高，未交手为 0)
+fn gc_repeat_cost(candidate: &GcPairingCandidate, opponent_id: &str) -> u32 {
+    candidate.recent_opponents.iter()
+        .rev()
+        .position(|id| id == opponent_id)
+        .map(|i| GC_TAVERN_OPPONENT_MEMORY.saturating_sub(i) as u32)
+        .unwrap_or(0)
+}
+
+/// 按随机数打乱顺序
+fn gc_shuffle<T>(items: &mut [T], rolls: &[u8]) {
+    if rolls.is_empty() {
+        return;
+    }
+    for i in (1..items.len()).rev() {
+        let j = rolls[i % rolls.len()] as usize % (i + 1);
+        items.swap(i, j);
+    }
+}
+
+/// 在剩余玩家中寻找总代价最小的配对 (8 人以内穷举)
+fn gc_best_matching(remaining: &[&GcPairingCandidate]) -> (u32, Vec<(usize, usize)>) {
+    let Some(first) = remaining.first() else {
+        return (0, Vec::new());
+    };
+
+    let mut best: Option<(u32, Vec<(usize, usize)>)> = None;
+    for j in 1..remaining.len() {
+        let other = remaining[j];
+        let cost = gc_repeat_cost(first, &other.player_id) + gc_repeat_cost(other, &first.player_id);
+
+        let rest: Vec<&GcPairingCandidate> = remaining.iter()
+            .enumerate()
+            .filter(|(k, _)| *k != 0 && *k != j)
+            .map(|(_, c)| *c)
+            .collect();
+        let (rest_cost, rest_pairs) = gc_best_matching(&rest);
+
+        let total = cost + rest_cost;
+        if best.as_ref().is_none_or(|(c, _)| total < *c) {
+            // 子问题的下标映射回当前列表
+            let index_map: Vec<usize> = (1..remaining.len()).filter(|k| *k != j).collect();
+            let mut pairs = vec![(0, j)];
+            pairs.extend(rest_pairs.iter().map(|(a, b)| (index_map[*a], index_map[*b])));
+            best = Some((total, pairs));
+            if total == 0 {
+                break;
+            }
+        }
+    }
+    best.unwrap_or((0, Vec::new()))
+}
+
+/// 计算本回合对战配对
+///
+/// - `candidates`: 存活玩家
+/// - `ghosts`: 可作为幽灵的已淘汰玩家 (最近淘汰的在前)
+/// - `rolls`: 打乱顺序所需随机数
+pub fn gc_pair_opponents(
+    candidates: &[GcPairingCandidate],
+    ghosts: &[String],
+    rolls: &[u8],
+) -> Vec<GcTavernPairing> {
+    let mut order: Vec<&GcPairingCandidate> = candidates.iter().collect();
+    gc_shuffle(&mut order, rolls);
+
+    let mut pairings = Vec::new();
+
+    // 奇数人: 一名玩家对战幽灵
+    if order.len() % 2 == 1 {
+        let idx = order.iter().position(|c| !c.fought_ghost).unwrap_or(0);
+        let player = order.remove(idx);
+        if let Some(ghost) = ghosts.iter().min_by_key(|g| gc_repeat_cost(player, g)) {
+            pairings.push(GcTavernPairing {
+                home_id: player.player_id.clone(),
+                away_id: ghost.clone(),
+                ghost: true,
+            });
+        }
+    }
+
+    let (_, pairs) = gc_best_matching(&order);
+    for (a, b) in pairs {
+        pairings.push(GcTavernPairing {
+            home_id: order[a].player_id.clone(),
+            away_id: order[b].player_id.clone(),
+            ghost: false,
+        });
+    }
+    pairings
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(id: &str, recent: &[&str]) -> GcPairingCandidate {
+        GcPairingCandidate {
+            player_id: id.to_string(),
+            recent_opponents: recent.iter().map(|s| s.to_string()).collect(),
+            fought_ghost: false,
+        }
+    }
+
+    fn opponent_of<'a>(pairings: &'a [GcTavernPairing], id: &str) -> Option<&'a str> {
+        pairings.iter().find_map(|p| {
+            if p.home_id == id {
+                Some(p.away_id.as_str())
+            } else if p.away_id == id && !p.ghost {
+                Some(p.home_id.as_str())
+            } else {
+                None
+            }
+        })
+    }
+
+    #[test]
+    fn test_avoids_recent_opponents() {
+        let candidates = vec![
+            candidate("p1", &["p2"]),
+            candidate("p2", &["p1"]),
+            candidate("p3", &["p4"]),
+            candidate("p4", &["p3"]),
+        ];
+
+        for rolls in [vec![], vec![0, 1, 2, 3], vec![7, 3, 9, 1]] {
+            let pairings = gc_pair_opponents(&candidates, &[], &rolls);
+            assert_eq!(pairings.len(), 2);
+            assert_ne!(opponent_of(&pairings, "p1"), Some("p2"));
+            assert_ne!(opponent_of(&pairings, "p3"), Some("p4"));
+        }
+    }
+
+    #[test]
+    fn test_odd_count_fights_ghost() {
+        let mut candidates = vec![
+            candidate("p1", &[]),
+            candidate("p2", &[]),
+            candidate("p3", &[]),
+        ];
+        candidates[0].fought_ghost = true;
+
+        let ghosts = vec!["p8".to_string(), "p7".to_string()];
+        let pairings = gc_pair_opponents(&candidates, &ghosts, &[]);
+        assert_eq!(pairings.len(), 2);
+
+        let ghost = pairings.iter().find(|p| p.ghost).expect("应有幽灵对局");
+        assert_eq!(ghost.home_id, "p2");
+        assert_eq!(ghost.away_id, "p8");
+    }
+
+    #[test]
+    fn test_repeat_allowed_when_unavoidable() {
+        let candidates = vec![candidate("p1", &["p2"]), candidate("p2", &["p1"])];
+        let pairings = gc_pair_opponents(&candidates, &[], &[]);
+        assert_eq!(pairings.len(), 1);
+    }
+}
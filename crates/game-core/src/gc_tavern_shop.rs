@@ -6,17 +6,23 @@
 //! ## 商店规则
 //! - 5 个商店槽位，展示可购买的怪兽
 //! - 刷新商店消耗 2 金币
-//! - 购买价格: 1★=1金, 2★=2金, 3★+=3金
+//! - 购买价格由定价管线计算 (见 `gc_shop_pricing`)，默认 1★=1金, 2★=2金, 3★+=3金
 //! - 可冻结槽位，保留到下回合
 //! - 每回合开始自动刷新（未冻结的槽位）
+//! - 怪兽槽位之外有一行法术槽位 (见 `gc_tavern_spell`)，随刷新更新，购买即结算
+//! - 可选防重复规则: 最近若干次刷新 (含本次) 中同一模板出现次数有上限，超出时改抽同稀有度的其他模板
 //!
 //! ## 抽卡接口
 //! 实现 `GcCardAcquisition` trait，支持运行时动态切换抽卡方式
 
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 use crate::{
-    GcMonster, GcMonsterAttribute, GcEconomy, 
-    gc_get_tier_weights, GcMonsterTier, GC_REFRESH_COST,
+    GcMonster, GcMonsterAttribute, GcEconomy, GcEvolutionPath, GcAttackReach, GcDamageType, GC_DEFAULT_MONSTER_SPEED,
+    gc_get_tier_weights, gc_get_template_odds, GcMonsterTier, GcTemplateOdds, GC_REFRESH_COST,
+    gc_price_monster, GcPriceDuration, GcPriceModifier, GcShopPrice, GcShopPricing,
+    gc_default_tavern_spells, gc_roll_tavern_spell, GcTavernSpell, GC_SPELL_SLOTS,
     gc_card_acquisition::{
         GcCardAcquisition, GcAcquisitionContext, GcAcquisitionSlot, GcAcquisitionResult
     },
@@ -29,6 +35,21 @@ use crate::{
 /// 商店槽位数量
 pub const GC_SHOP_SLOTS: usize = 5;
 
+// =============================================================================
+// 防重复规则
+// =============================================================================
+
+/// 防重复规则: 最近 `window` 次刷新 (含本次) 中同一模板最多出现 `max_appearances` 次
+///
+/// 只统计刷新新抽出的怪兽 (冻结保留的不计)；同稀有度所有模板都已达上限时不做限制
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcShopVarietyRule {
+    /// 统计的连续刷新次数
+    pub window: u8,
+    /// 窗口内同一模板最多出现次数
+    pub max_appearances: u8,
+}
+
 // =============================================================================
 // 商店结构
 // =============================================================================
@@ -42,6 +63,21 @@ pub struct GcTavernShop {
     pub frozen: [bool; GC_SHOP_SLOTS],
     /// 商店等级 (影响怪兽稀有度)
     pub shop_level: u8,
+    /// 定价配置
+    #[serde(default)]
+    pub pricing: GcShopPricing,
+    /// 生效中的价格修改器
+    #[serde(default)]
+    pub price_modifiers: Vec<GcPriceModifier>,
+    /// 防重复规则 (None = 不限制)
+    #[serde(default)]
+    pub variety: Option<GcShopVarietyRule>,
+    /// 最近几次刷新抽出的模板ID (启用防重复规则时记录，最新的在后)
+    #[serde(default)]
+    pub offer_history: Vec<Vec<String>>,
+    /// 法术槽位
+    #[serde(default)]
+    pub spell_slots: [Option<GcTavernSpell>; GC_SPELL_SLOTS],
 }
 
 impl Default for GcTavernShop {
@@ -50,6 +86,11 @@ impl Default for GcTavernShop {
             slots: [None, None, None, None, None],
             frozen: [false; GC_SHOP_SLOTS],
             shop_level: 1,
+            pricing: GcShopPricing::default(),
+            price_modifiers: Vec::new(),
+            variety: None,
+            offer_history: Vec::new(),
+            spell_slots: Default::default(),
         }
     }
 }
@@ -109,6 +150,9 @@ impl GcTavernShop {
     /// 刷新未冻结的槽位
     fn refresh_unfrozen(&mut self, pool: &GcMonsterPool, random_rolls: &[u8]) {
         let weights = gc_get_tier_weights(self.shop_level);
+        let variety = self.variety;
+        let mut appearances = self.recent_appearances();
+        let mut offered = Vec::new();
         
         for (i, slot) in self.slots.iter_mut().enumerate() {
             if self.frozen[i] {
@@ -119,12 +163,54 @@ impl GcTavernShop {
             let roll = random_rolls.get(i).copied().unwrap_or(0);
             let tier = weights.select_tier(roll);
             
-            // 从池中抽取怪兽
-            *slot = pool.get_random_monster(tier, random_rolls.get(i + GC_SHOP_SLOTS).copied().unwrap_or(0));
+            // 从池中抽取怪兽 (防重复规则跳过窗口内已达上限的模板)
+            let pick = random_rolls.get(i + GC_SHOP_SLOTS).copied().unwrap_or(0);
+            *slot = match variety {
+                Some(rule) => pool.get_random_monster_where(tier, pick, |template_id| {
+                    appearances.get(template_id).copied().unwrap_or(0) < rule.max_appearances
+                }),
+                None => pool.get_random_monster(tier, pick),
+            };
+            if let Some(monster) = slot {
+                *appearances.entry(monster.template_id.clone()).or_default() += 1;
+                offered.push(monster.template_id.clone());
+            }
+        }
+        
+        // 法术槽位使用怪兽之后的随机数
+        for (i, slot) in self.spell_slots.iter_mut().enumerate() {
+            let roll = random_rolls.get(GC_SHOP_SLOTS * 2 + i).copied().unwrap_or(0);
+            *slot = gc_roll_tavern_spell(pool.spells(), self.shop_level, roll);
         }
         
         // 刷新后解除冻结
         self.frozen = [false; GC_SHOP_SLOTS];
+        self.record_offers(offered);
+    }
+    
+    /// 防重复窗口内 (不含本次) 各模板已出现次数
+    fn recent_appearances(&self) -> HashMap<String, u8> {
+        let mut appearances = HashMap::new();
+        let Some(rule) = self.variety else {
+            return appearances;
+        };
+        let keep = usize::from(rule.window.saturating_sub(1));
+        for template_id in self.offer_history.iter().rev().take(keep).flatten() {
+            *appearances.entry(template_id.clone()).or_insert(0u8) += 1;
+        }
+        appearances
+    }
+    
+    /// 记录本次刷新抽出的模板 (只保留防重复窗口需要的历史)
+    fn record_offers(&mut self, offered: Vec<String>) {
+        let Some(rule) = self.variety else {
+            self.offer_history.clear();
+            return;
+        };
+        self.offer_history.push(offered);
+        let keep = usize::from(rule.window.saturating_sub(1));
+        let excess = self.offer_history.len().saturating_sub(keep);
+        self.offer_history.drain(..excess);
     }
     
     /// 购买怪兽
@@ -136,8 +222,7 @@ impl GcTavernShop {
             return None;
         }
         
-        let monster = self.slots[slot_index].as_ref()?;
-        let price = monster.buy_price();
+        let price = self.slot_price(slot_index)?.price;
         
         if !economy.can_afford(price) {
             return None;
@@ -146,10 +231,64 @@ impl GcTavernShop {
         economy.spend(price);
         let purchased = self.slots[slot_index].take();
         self.frozen[slot_index] = false; // 购买后解除冻结
+        if let Some(monster) = &purchased {
+            self.consume_purchase_modifiers(monster);
+        }
         
         purchased
     }
     
+    /// 购买法术 (结算由调用方负责，见 `gc_resolve_tavern_spell`)
+    pub fn buy_spell(&mut self, slot_index: usize, economy: &mut GcEconomy) -> Option<GcTavernSpell> {
+        let cost = self.get_spell(slot_index)?.cost;
+        if !economy.can_afford(cost) {
+            return None;
+        }
+        economy.spend(cost);
+        self.spell_slots[slot_index].take()
+    }
+    
+    /// 获取法术槽位
+    pub fn get_spell(&self, index: usize) -> Option<&GcTavernSpell> {
+        self.spell_slots.get(index).and_then(|s| s.as_ref())
+    }
+    
+    // =========================================================================
+    // 定价
+    // =========================================================================
+    
+    /// 槽位怪兽的当前价格 (经定价管线计算)
+    pub fn slot_price(&self, slot_index: usize) -> Option<GcShopPrice> {
+        let monster = self.get_slot(slot_index)?;
+        Some(gc_price_monster(&self.pricing, &self.price_modifiers, monster))
+    }
+    
+    /// 添加价格修改器 (英雄技能、遗物等)
+    pub fn add_price_modifier(&mut self, modifier: GcPriceModifier) {
+        self.price_modifiers.push(modifier);
+    }
+    
+    /// 购物回合开始 (商店刷新之后调用): 清除本回合修改器，促销回合为一只怪兽降价
+    pub fn start_turn(&mut self, round: u32) {
+        self.price_modifiers.retain(|m| m.duration != GcPriceDuration::ThisTurn);
+        
+        if !self.pricing.gc_is_sale_round(round) {
+            return;
+        }
+        let stocked: Vec<&GcMonster> = self.slots.iter().flatten().collect();
+        if stocked.is_empty() {
+            return;
+        }
+        let target = stocked[round as usize % stocked.len()];
+        let sale = GcPriceModifier::gc_sale(&target.id, self.pricing.sale_discount);
+        self.price_modifiers.push(sale);
+    }
+    
+    /// 购买后消耗命中的 "下一次购买" 修改器
+    fn consume_purchase_modifiers(&mut self, monster: &GcMonster) {
+        self.price_modifiers.retain(|m| !(m.duration == GcPriceDuration::NextPurchase && m.scope.gc_matches(monster)));
+    }
+    
     /// 冻结/解冻槽位
     pub fn toggle_freeze(&mut self, slot_index: usize) -> bool {
         if slot_index >= GC_SHOP_SLOTS {
@@ -199,6 +338,16 @@ impl GcTavernShop {
             .collect()
     }
     
+    /// 下一次刷新会替换的槽位数 (未冻结)
+    pub fn open_slot_count(&self) -> u8 {
+        self.frozen.iter().filter(|&&f| !f).count() as u8
+    }
+    
+    /// 指定模板在下一次刷新中的概率 (按商店等级与未冻结槽位)
+    pub fn template_odds(&self, pool: &GcMonsterPool, template_id: &str) -> Option<GcTemplateOdds> {
+        gc_get_template_odds(pool, template_id, self.shop_level, self.open_slot_count())
+    }
+    
     /// 是否有可购买的怪兽
     pub fn has_available(&self) -> bool {
         self.slots.iter().any(|s| s.is_some())
@@ -216,9 +365,20 @@ impl GcTavernShop {
 // 实现 GcCardAcquisition trait
 // =============================================================================
 
+/// 酒馆商店中可获取的物品
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data")]
+pub enum GcTavernOffer {
+    /// 怪兽
+    Monster(GcMonster),
+    /// 法术 (获取后由调用方结算)
+    Spell(GcTavernSpell),
+}
+
 /// 酒馆商店适配器 (持有怪兽池引用用于刷新)
 /// 
-/// 由于 trait 需要独立工作，这个结构体包装了 GcTavernShop 并持有 GcMonsterPool
+/// 由于 trait 需要独立工作，这个结构体包装了 GcTavernShop 并持有 GcMonsterPool。
+/// 槽位 `0..GC_SHOP_SLOTS` 为怪兽，其后 `GC_SPELL_SLOTS` 个为法术
 #[derive(Clone, Debug)]
 pub struct GcTavernShopAdapter {
     /// 酒馆商店
@@ -253,23 +413,30 @@ impl GcTavernShopAdapter {
 }
 
 impl GcCardAcquisition for GcTavernShopAdapter {
-    type Item = GcMonster;
+    type Item = GcTavernOffer;
     
     fn acquire(&mut self, slot_index: usize, ctx: &mut GcAcquisitionContext) -> GcAcquisitionResult<Self::Item> {
-        // 检查槽位有效性
-        if slot_index >= GC_SHOP_SLOTS {
-            return GcAcquisitionResult::failure("无效槽位");
+        // 法术槽位
+        if let Some(spell_index) = slot_index.checked_sub(GC_SHOP_SLOTS) {
+            let cost = match self.shop.get_spell(spell_index) {
+                Some(spell) => spell.cost,
+                None => return GcAcquisitionResult::failure("该槽位没有法术"),
+            };
+            if !ctx.spend(cost) {
+                return GcAcquisitionResult::failure("金币不足");
+            }
+            return match self.shop.spell_slots[spell_index].take() {
+                Some(spell) => GcAcquisitionResult::success(GcTavernOffer::Spell(spell), cost),
+                None => GcAcquisitionResult::failure("购买失败"),
+            };
         }
         
-        // 检查槽位是否有怪兽
-        let monster = match self.shop.slots[slot_index].as_ref() {
-            Some(m) => m,
+        // 检查槽位是否有怪兽并获取价格
+        let price = match self.shop.slot_price(slot_index) {
+            Some(p) => p.price,
             None => return GcAcquisitionResult::failure("该槽位没有怪兽"),
         };
         
-        // 获取价格
-        let price = monster.buy_price();
-        
         // 检查金币
         if !ctx.can_afford(price) {
             return GcAcquisitionResult::failure("金币不足");
@@ -281,20 +448,23 @@ impl GcCardAcquisition for GcTavernShopAdapter {
         // 获取怪兽
         let purchased = self.shop.slots[slot_index].take();
         self.shop.frozen[slot_index] = false;
+        if let Some(monster) = &purchased {
+            self.shop.consume_purchase_modifiers(monster);
+        }
         
         match purchased {
-            Some(m) => GcAcquisitionResult::success(m, price),
+            Some(m) => GcAcquisitionResult::success(GcTavernOffer::Monster(m), price),
             None => GcAcquisitionResult::failure("购买失败"),
         }
     }
     
     fn can_acquire(&self, slot_index: usize, ctx: &GcAcquisitionContext) -> bool {
-        if slot_index >= GC_SHOP_SLOTS {
-            return false;
+        if let Some(spell_index) = slot_index.checked_sub(GC_SHOP_SLOTS) {
+            return self.shop.get_spell(spell_index).is_some_and(|spell| ctx.can_afford(spell.cost));
         }
         
-        match self.shop.slots[slot_index].as_ref() {
-            Some(monster) => ctx.can_afford(monster.buy_price()),
+        match self.shop.slot_price(slot_index) {
+            Some(price) => ctx.can_afford(price.price),
             None => false,
         }
     }
@@ -311,19 +481,29 @@ impl GcCardAcquisition for GcTavernShopAdapter {
                             format!("{}★", monster.star)
                         };
                         
+                        let price = gc_price_monster(&self.shop.pricing, &self.shop.price_modifiers, monster);
                         GcAcquisitionSlot::new(
                             i,
                             &monster.id,
                             &monster.name,
-                            monster.buy_price(),
+                            price.price,
                             monster.star,
                         )
+                        .with_base_cost(price.base)
                         .with_frozen(self.shop.frozen[i])
                         .with_description(&star_str)
                     },
                     None => GcAcquisitionSlot::new(i, "", "空", 0, 0),
                 }
             })
+            .chain(self.shop.spell_slots.iter().enumerate().map(|(i, opt)| {
+                let index = GC_SHOP_SLOTS + i;
+                match opt {
+                    Some(spell) => GcAcquisitionSlot::new(index, &spell.spell_id, &spell.name, spell.cost, spell.tier as u8)
+                        .with_description(&spell.description),
+                    None => GcAcquisitionSlot::new(index, "", "空", 0, 0),
+                }
+            }))
             .collect()
     }
     
@@ -355,7 +535,7 @@ impl GcCardAcquisition for GcTavernShopAdapter {
     }
     
     fn slot_count(&self) -> usize {
-        GC_SHOP_SLOTS
+        GC_SHOP_SLOTS + GC_SPELL_SLOTS
     }
     
     fn toggle_freeze(&mut self, slot_index: usize) -> bool {
@@ -386,6 +566,25 @@ pub struct GcMonsterTemplate {
     pub base_atk: u32,
     pub base_def: u32,
     pub base_hp: u32,
+    /// 进化路线 (可为空)
+    #[serde(default)]
+    pub evolutions: Vec<GcEvolutionPath>,
+    /// 仅能通过进化/融合获得 (不出现在商店)
+    #[serde(default)]
+    pub evolution_only: bool,
+    /// 攻击距离
+    #[serde(default)]
+    pub reach: GcAttackReach,
+    /// 速度
+    #[serde(default = "gc_default_template_speed")]
+    pub speed: u32,
+    /// 攻击伤害类型
+    #[serde(default)]
+    pub damage_type: GcDamageType,
+}
+
+fn gc_default_template_speed() -> u32 {
+    GC_DEFAULT_MONSTER_SPEED
 }
 
 /// 怪兽池 (所有可抽取的怪兽模板)
@@ -393,6 +592,9 @@ pub struct GcMonsterTemplate {
 pub struct GcMonsterPool {
     /// 按稀有度分类的怪兽模板
     templates: Vec<GcMonsterTemplate>,
+    /// 商店法术 (为空时商店不提供法术)
+    #[serde(default)]
+    spells: Vec<GcTavernSpell>,
 }
 
 impl GcMonsterPool {
@@ -401,18 +603,34 @@ impl GcMonsterPool {
         Self::default()
     }
     
-    /// 创建带默认怪兽的池
+    /// 创建带默认怪兽与默认法术的池
     pub fn with_defaults() -> Self {
         let mut pool = Self::new();
         pool.add_default_monsters();
+        pool.spells = gc_default_tavern_spells();
         pool
     }
     
+    /// 添加商店法术
+    pub fn add_spell(&mut self, spell: GcTavernSpell) {
+        self.spells.push(spell);
+    }
+    
+    /// 获取所有商店法术 (只读)
+    pub fn spells(&self) -> &[GcTavernSpell] {
+        &self.spells
+    }
+    
     /// 添加怪兽模板
     pub fn add_template(&mut self, template: GcMonsterTemplate) {
         self.templates.push(template);
     }
     
+    /// 只保留满足条件的模板 (赛季轮换)
+    pub fn retain_templates(&mut self, keep: impl Fn(&GcMonsterTemplate) -> bool) {
+        self.templates.retain(|t| keep(t));
+    }
+    
     /// 添加默认怪兽
     pub fn add_default_monsters(&mut self) {
         // Tier 1 (1费)
@@ -425,6 +643,11 @@ impl GcMonsterPool {
             base_atk: 50,
             base_def: 30,
             base_hp: 60,
+            evolutions: vec![GcEvolutionPath::new("king_slime", 3, Some("evo_slime_crown"))],
+            evolution_only: false,
+            reach: GcAttackReach::Melee,
+            speed: 8,
+            damage_type: GcDamageType::Frost,
         });
         
         self.add_template(GcMonsterTemplate {
@@ -436,6 +659,11 @@ impl GcMonsterPool {
             base_atk: 60,
             base_def: 20,
             base_hp: 50,
+            evolutions: Vec::new(),
+            evolution_only: false,
+            reach: GcAttackReach::Melee,
+            speed: 12,
+            damage_type: GcDamageType::Physical,
         });
         
         self.add_template(GcMonsterTemplate {
@@ -447,6 +675,11 @@ impl GcMonsterPool {
             base_atk: 70,
             base_def: 10,
             base_hp: 40,
+            evolutions: Vec::new(),
+            evolution_only: false,
+            reach: GcAttackReach::Ranged,
+            speed: 13,
+            damage_type: GcDamageType::Fire,
         });
         
         // Tier 2 (2费)
@@ -459,6 +692,11 @@ impl GcMonsterPool {
             base_atk: 90,
             base_def: 40,
             base_hp: 80,
+            evolutions: vec![GcEvolutionPath::new("dire_wolf", 5, Some("evo_moon_stone"))],
+            evolution_only: false,
+            reach: GcAttackReach::Melee,
+            speed: 15,
+            damage_type: GcDamageType::Physical,
         });
         
         self.add_template(GcMonsterTemplate {
@@ -470,6 +708,11 @@ impl GcMonsterPool {
             base_atk: 100,
             base_def: 30,
             base_hp: 70,
+            evolutions: Vec::new(),
+            evolution_only: false,
+            reach: GcAttackReach::Ranged,
+            speed: 16,
+            damage_type: GcDamageType::Physical,
         });
         
         // Tier 3 (3费)
@@ -482,6 +725,11 @@ impl GcMonsterPool {
             base_atk: 80,
             base_def: 120,
             base_hp: 150,
+            evolutions: Vec::new(),
+            evolution_only: false,
+            reach: GcAttackReach::Melee,
+            speed: 5,
+            damage_type: GcDamageType::Physical,
         });
         
         self.add_template(GcMonsterTemplate {
@@ -493,6 +741,11 @@ impl GcMonsterPool {
             base_atk: 140,
             base_def: 60,
             base_hp: 100,
+            evolutions: Vec::new(),
+            evolution_only: false,
+            reach: GcAttackReach::Ranged,
+            speed: 14,
+            damage_type: GcDamageType::Fire,
         });
         
         // Tier 4 (4费 -> 3金购买)
@@ -505,6 +758,11 @@ impl GcMonsterPool {
             base_atk: 180,
             base_def: 100,
             base_hp: 200,
+            evolutions: vec![GcEvolutionPath::new("ancient_dragon", 8, Some("evo_dragon_heart"))],
+            evolution_only: false,
+            reach: GcAttackReach::Melee,
+            speed: 11,
+            damage_type: GcDamageType::Fire,
         });
         
         // Tier 5 (5费 -> 3金购买)
@@ -517,6 +775,11 @@ impl GcMonsterPool {
             base_atk: 250,
             base_def: 150,
             base_hp: 300,
+            evolutions: Vec::new(),
+            evolution_only: false,
+            reach: GcAttackReach::Melee,
+            speed: 12,
+            damage_type: GcDamageType::Fire,
         });
         
         self.add_template(GcMonsterTemplate {
@@ -528,13 +791,94 @@ impl GcMonsterPool {
             base_atk: 200,
             base_def: 200,
             base_hp: 280,
+            evolutions: Vec::new(),
+            evolution_only: false,
+            reach: GcAttackReach::Melee,
+            speed: 13,
+            damage_type: GcDamageType::Holy,
+        });
+        
+        // 进化形态 (仅能通过进化获得)
+        self.add_template(GcMonsterTemplate {
+            template_id: "king_slime".to_string(),
+            name: "史莱姆王".to_string(),
+            tier: GcMonsterTier::Tier2,
+            level: 3,
+            attribute: GcMonsterAttribute::Water,
+            base_atk: 90,
+            base_def: 70,
+            base_hp: 140,
+            evolutions: Vec::new(),
+            evolution_only: true,
+            reach: GcAttackReach::Melee,
+            speed: 8,
+            damage_type: GcDamageType::Frost,
         });
+        
+        self.add_template(GcMonsterTemplate {
+            template_id: "dire_wolf".to_string(),
+            name: "恐狼".to_string(),
+            tier: GcMonsterTier::Tier3,
+            level: 5,
+            attribute: GcMonsterAttribute::Dark,
+            base_atk: 150,
+            base_def: 70,
+            base_hp: 130,
+            evolutions: Vec::new(),
+            evolution_only: true,
+            reach: GcAttackReach::Melee,
+            speed: 17,
+            damage_type: GcDamageType::Physical,
+        });
+        
+        // 融合形态 (仅能通过融合获得)
+        self.add_template(GcMonsterTemplate {
+            template_id: "goblin_bomber".to_string(),
+            name: "爆破哥布林".to_string(),
+            tier: GcMonsterTier::Tier2,
+            level: 3,
+            attribute: GcMonsterAttribute::Fire,
+            base_atk: 110,
+            base_def: 25,
+            base_hp: 70,
+            evolutions: Vec::new(),
+            evolution_only: true,
+            reach: GcAttackReach::Melee,
+            speed: 11,
+            damage_type: GcDamageType::Fire,
+        });
+        
+        self.add_template(GcMonsterTemplate {
+            template_id: "griffin".to_string(),
+            name: "狮鹫".to_string(),
+            tier: GcMonsterTier::Tier3,
+            level: 5,
+            attribute: GcMonsterAttribute::Wind,
+            base_atk: 130,
+            base_def: 70,
+            base_hp: 120,
+            evolutions: Vec::new(),
+            evolution_only: true,
+            reach: GcAttackReach::Ranged,
+            speed: 18,
+            damage_type: GcDamageType::Physical,
+        });
+    }
+    
+    /// 按模板ID查找模板
+    pub fn find_template(&self, template_id: &str) -> Option<&GcMonsterTemplate> {
+        self.templates.iter().find(|t| t.template_id == template_id)
+    }
+    
+    /// 获取所有模板 (只读)
+    pub fn templates(&self) -> &[GcMonsterTemplate] {
+        &self.templates
     }
     
-    /// 获取指定稀有度的怪兽模板列表
+    /// 获取指定稀有度的怪兽模板列表 (不含仅进化形态)
     pub fn templates_by_tier(&self, tier: GcMonsterTier) -> Vec<&GcMonsterTemplate> {
         self.templates.iter()
-            .filter(|t| t.tier == tier)
+            .filter(|t| t.tier == tier && !t.evolution_only)
             .collect()
     }
     
@@ -544,6 +888,19 @@ impl GcMonsterPool {
     /// - `tier`: 稀有度
     /// - `roll`: 随机数 (0-255)
     pub fn get_random_monster(&self, tier: GcMonsterTier, roll: u8) -> Option<GcMonster> {
+        self.get_random_monster_where(tier, roll, |_| true)
+    }
+    
+    /// 随机获取一个允许的怪兽
+    /// 
+    /// 从 `roll` 选中的模板开始依次向后查找第一个 `allowed(template_id)` 的模板；
+    /// 没有允许的模板时退回 `roll` 选中的模板
+    pub fn get_random_monster_where(
+        &self,
+        tier: GcMonsterTier,
+        roll: u8,
+        allowed: impl Fn(&str) -> bool,
+    ) -> Option<GcMonster> {
         let candidates = self.templates_by_tier(tier);
         
         if candidates.is_empty() {
@@ -551,16 +908,26 @@ impl GcMonsterPool {
         }
         
         let idx = roll as usize % candidates.len();
-        let template = &candidates[idx];
+        let template = (0..candidates.len())
+            .map(|offset| candidates[(idx + offset) % candidates.len()])
+            .find(|t| allowed(&t.template_id))
+            .unwrap_or(candidates[idx]);
         
         Some(self.create_monster_from_template(template))
     }
     
+    /// 按模板ID创建指定ID的怪兽实例 (模板不存在时返回 None)
+    pub fn spawn_template(&self, template_id: &str, id: &str) -> Option<GcMonster> {
+        let mut monster = self.create_monster_from_template(self.find_template(template_id)?);
+        monster.id = id.to_string();
+        Some(monster)
+    }
+    
     /// 从模板创建怪兽实例
     fn create_monster_from_template(&self, template: &GcMonsterTemplate) -> GcMonster {
         let id = format!("{}_{}", template.template_id, uuid_simple());
         
-        GcMonster::new_with_template(
+        let mut monster = GcMonster::new_with_template(
             &id,
             &template.template_id,
             &template.name,
@@ -569,7 +936,11 @@ impl GcMonsterPool {
             template.base_atk,
             template.base_def,
             template.base_hp,
-        )
+        );
+        monster.reach = template.reach;
+        monster.speed = template.speed;
+        monster.damage_type = template.damage_type;
+        monster
     }
 }
 
@@ -639,6 +1010,10 @@ mod tests {
         // 冻结第一个槽位
         shop.toggle_freeze(0);
         assert!(shop.is_frozen(0));
+        assert_eq!(shop.open_slot_count(), 4);
+        let template_id = pool.templates_by_tier(GcMonsterTier::Tier1)[0].template_id.clone();
+        let odds = shop.template_odds(&pool, &template_id).expect("模板存在");
+        assert!(odds.refresh_chance < gc_get_template_odds(&pool, &template_id, 1, 5).map_or(0.0, |o| o.refresh_chance));
         
         // 再次刷新
         shop.free_refresh(&pool, &[50, 50, 50, 50, 50, 50, 50, 50, 50, 50]);
@@ -659,4 +1034,143 @@ mod tests {
         let earned = gc_sell_monster(&monster, &mut economy);
         assert_eq!(earned, 2); // 2星卖2金
     }
+    
+    #[test]
+    fn test_sale_round_and_purchase_modifiers() {
+        use crate::{GcPriceScope, GcPriceSource};
+        
+        let mut shop = GcTavernShop::new();
+        shop.pricing.sale_every_rounds = 2;
+        let pool = GcMonsterPool::with_defaults();
+        shop.free_refresh(&pool, &[0; 10]);
+        
+        // 非促销回合: 全部原价
+        shop.start_turn(1);
+        assert!((0..GC_SHOP_SLOTS).all(|i| shop.slot_price(i).is_some_and(|p| !p.gc_is_discounted())));
+        
+        // 促销回合: 恰好一只怪兽降价 1 金
+        shop.start_turn(2);
+        let sale: Vec<usize> = (0..GC_SHOP_SLOTS)
+            .filter(|&i| shop.slot_price(i).is_some_and(|p| p.gc_is_discounted()))
+            .collect();
+        assert_eq!(sale.len(), 1);
+        let price = shop.slot_price(sale[0]).unwrap();
+        assert_eq!(price.price, price.base - 1);
+        
+        // 英雄技能: 下一次购买再降 1 金，购买后消耗；促销按折后价扣费
+        shop.add_price_modifier(GcPriceModifier::gc_new(GcPriceSource::HeroPower, GcPriceScope::All, -1, GcPriceDuration::NextPurchase));
+        let mut economy = GcEconomy::with_gold(10);
+        let other = (0..GC_SHOP_SLOTS).find(|i| *i != sale[0]).unwrap();
+        let other_price = shop.slot_price(other).unwrap();
+        assert!(shop.buy(other, &mut economy).is_some());
+        assert_eq!(economy.gold, 10 - other_price.price);
+        assert_eq!(shop.price_modifiers.len(), 1);
+        assert!(shop.buy(sale[0], &mut economy).is_some());
+        assert_eq!(economy.gold, 10 - other_price.price - price.price);
+        
+        // 新回合清除本回合促销
+        shop.start_turn(3);
+        assert!(shop.price_modifiers.is_empty());
+        
+        // 适配器展示的费用同样经过管线
+        let mut adapter = GcTavernShopAdapter::from_parts(shop, pool);
+        adapter.shop.free_refresh(&adapter.pool, &[0; 10]);
+        adapter.shop.add_price_modifier(GcPriceModifier::gc_new(GcPriceSource::Relic, GcPriceScope::All, 1, GcPriceDuration::Permanent));
+        let slot = &adapter.get_available_slots()[0];
+        assert_eq!(slot.cost, slot.base_cost + 1);
+    }
+    
+    #[test]
+    fn test_spell_row_and_adapter() {
+        let pool = GcMonsterPool::with_defaults();
+        let mut shop = GcTavernShop::new();
+        shop.free_refresh(&pool, &[0; 11]);
+        let spell = shop.get_spell(0).cloned().expect("刷新后有法术");
+        
+        // 没有法术的池不提供法术
+        let mut bare = GcTavernShop::new();
+        bare.free_refresh(&GcMonsterPool::new(), &[0; 11]);
+        assert!(bare.get_spell(0).is_none());
+        
+        let mut adapter = GcTavernShopAdapter::from_parts(shop, pool);
+        assert_eq!(adapter.slot_count(), GC_SHOP_SLOTS + GC_SPELL_SLOTS);
+        let slots = adapter.get_available_slots();
+        assert_eq!(slots.len(), GC_SHOP_SLOTS + GC_SPELL_SLOTS);
+        assert_eq!(slots[GC_SHOP_SLOTS].item_id, spell.spell_id);
+        
+        let mut ctx = GcAcquisitionContext::with_gold(10);
+        assert!(adapter.can_acquire(GC_SHOP_SLOTS, &ctx));
+        let result = adapter.acquire(GC_SHOP_SLOTS, &mut ctx);
+        assert!(matches!(result.item, Some(GcTavernOffer::Spell(ref s)) if s.spell_id == spell.spell_id));
+        assert_eq!(ctx.gold, 10 - spell.cost);
+        assert!(!adapter.can_acquire(GC_SHOP_SLOTS, &ctx));
+        assert!(matches!(adapter.acquire(0, &mut ctx).item, Some(GcTavernOffer::Monster(_))));
+    }
+    
+    /// 随机刷新若干次，返回每次抽出的模板ID
+    fn roll_offers(shop: &mut GcTavernShop, pool: &GcMonsterPool, refreshes: usize, seed: u64) -> Vec<Vec<String>> {
+        use rand::Rng;
+        let mut rng = crate::gc_simulation_rng(seed);
+        (0..refreshes).map(|_| {
+            let rolls: Vec<u8> = (0..GC_SHOP_SLOTS * 2).map(|_| rng.gen_range(0..100)).collect();
+            shop.free_refresh(pool, &rolls);
+            shop.slots.iter().flatten().map(|m| m.template_id.clone()).collect()
+        }).collect()
+    }
+    
+    /// 窗口内同一模板的最大出现次数
+    fn max_window_appearances(offers: &[Vec<String>], window: usize) -> u8 {
+        offers.windows(window).map(|w| {
+            let mut counts: HashMap<&str, u8> = HashMap::new();
+            for id in w.iter().flatten() {
+                *counts.entry(id).or_default() += 1;
+            }
+            counts.into_values().max().unwrap_or(0)
+        }).max().unwrap_or(0)
+    }
+    
+    #[test]
+    fn test_variety_rule_statistics() {
+        // 12 个 1 阶模板: 连续两次刷新 (10 个槽位) 总能满足 "每个模板最多 1 次"
+        let mut pool = GcMonsterPool::new();
+        let base = GcMonsterPool::with_defaults().templates_by_tier(GcMonsterTier::Tier1)[0].clone();
+        for i in 0..12 {
+            pool.add_template(GcMonsterTemplate { template_id: format!("t{}", i), ..base.clone() });
+        }
+        let rule = GcShopVarietyRule { window: 2, max_appearances: 1 };
+        
+        let unrestricted = roll_offers(&mut GcTavernShop::new(), &pool, 400, 7);
+        assert!(max_window_appearances(&unrestricted, 2) > 1);
+        
+        let mut shop = GcTavernShop { variety: Some(rule), ..Default::default() };
+        let offers = roll_offers(&mut shop, &pool, 400, 7);
+        assert_eq!(max_window_appearances(&offers, 2), 1);
+        assert!(shop.offer_history.len() <= 1);
+        
+        // 出现频率仍大致均匀 (期望每个模板 400 * 5 / 12 ≈ 167 次)
+        let mut counts: HashMap<&str, u32> = HashMap::new();
+        for id in offers.iter().flatten() {
+            *counts.entry(id).or_default() += 1;
+        }
+        assert_eq!(counts.len(), 12);
+        assert!(counts.values().all(|&c| (100..=240).contains(&c)), "{:?}", counts);
+    }
+    
+    #[test]
+    fn test_variety_rule_with_default_pool() {
+        let pool = GcMonsterPool::with_defaults();
+        let rule = GcShopVarietyRule { window: 1, max_appearances: 2 };
+        let mut shop = GcTavernShop { variety: Some(rule), ..Default::default() };
+        let offers = roll_offers(&mut shop, &pool, 200, 11);
+        assert!(max_window_appearances(&offers, 1) <= 2);
+        assert!(shop.offer_history.is_empty());
+        
+        // 冻结的怪兽不计入，模板都达上限时不限制
+        let rule = GcShopVarietyRule { window: 3, max_appearances: 1 };
+        let mut shop = GcTavernShop { variety: Some(rule), ..Default::default() };
+        shop.toggle_freeze(0);
+        let offers = roll_offers(&mut shop, &pool, 3, 3);
+        assert!(offers.iter().all(|o| o.len() == GC_SHOP_SLOTS));
+        assert_eq!(shop.offer_history.len(), 2);
+    }
 }
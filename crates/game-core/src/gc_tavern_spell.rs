@@ -0,0 +1,296 @@
+//! 酒馆法术 (商店一次性道具)
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! ## 规则
+//! - 商店在怪兽槽位之外有一行法术槽位，随商店刷新 (不可冻结)
+//! - 法术按稀有度出现: 只有当前商店等级能刷出该稀有度怪兽时才会出现同稀有度法术
+//! - 法术为一次性道具: 购买时立即结算，目标不合法时不扣金币
+//! - 效果: 强化一只友方怪兽 (阵容或手牌区)、强化全部阵容怪兽、发现一只指定稀有度怪兽、获得金币
+
+use serde::{Deserialize, Serialize};
+
+use crate::{gc_get_tier_weights, GcBattleArena, GcError, GcMonster, GcMonsterPool, GcMonsterTier, GcPlayer};
+
+// =============================================================================
+// 常量
+// =============================================================================
+
+/// 商店法术槽位数量
+pub const GC_SPELL_SLOTS: usize = 1;
+
+// =============================================================================
+// 法术定义
+// =============================================================================
+
+/// 法术目标要求
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GcSpellTargeting {
+    /// 无需目标
+    None,
+    /// 一只友方怪兽 (阵容或手牌区)
+    FriendlyMonster,
+}
+
+/// 法术效果
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data")]
+pub enum GcTavernSpellEffect {
+    /// 一只友方怪兽 +攻击/+生命
+    Buff { atk: u32, hp: u32 },
+    /// 阵容所有怪兽 +攻击/+生命
+    BuffBoard { atk: u32, hp: u32 },
+    /// 发现一只指定稀有度的怪兽 (放入手牌区)
+    Discover { tier: GcMonsterTier },
+    /// 获得金币
+    Gold { amount: u32 },
+}
+
+impl GcTavernSpellEffect {
+    /// 目标要求
+    pub fn gc_targeting(&self) -> GcSpellTargeting {
+        match self {
+            Self::Buff { .. } => GcSpellTargeting::FriendlyMonster,
+            Self::BuffBoard { .. } | Self::Discover { .. } | Self::Gold { .. } => GcSpellTargeting::None,
+        }
+    }
+}
+
+/// 酒馆法术
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcTavernSpell {
+    /// 法术ID
+    pub spell_id: String,
+    /// 名称
+    pub name: String,
+    /// 描述
+    pub description: String,
+    /// 稀有度 (决定出现的商店等级)
+    pub tier: GcMonsterTier,
+    /// 价格
+    pub cost: u32,
+    /// 效果
+    pub effect: GcTavernSpellEffect,
+}
+
+impl GcTavernSpell {
+    /// 创建法术
+    pub fn gc_new(spell_id: &str, name: &str, description: &str, tier: GcMonsterTier, cost: u32, effect: GcTavernSpellEffect) -> Self {
+        Self {
+            spell_id: spell_id.to_string(),
+            name: name.to_string(),
+            description: description.to_string(),
+            tier,
+            cost,
+            effect,
+        }
+    }
+}
+
+/// 默认法术列表
+pub fn gc_default_tavern_spells() -> Vec<GcTavernSpell> {
+    vec![
+        GcTavernSpell::gc_new("spell_tonic", "强化药剂", "一只友方怪兽 +10 攻击 / +10 生命", GcMonsterTier::Tier1, 1,
+            GcTavernSpellEffect::Buff { atk: 10, hp: 10 }),
+        GcTavernSpell::gc_new("spell_coin", "幸运金币", "获得 2 金币", GcMonsterTier::Tier1, 1,
+            GcTavernSpellEffect::Gold { amount: 2 }),
+        GcTavernSpell::gc_new("spell_war_song", "战歌", "阵容所有怪兽 +5 攻击 / +5 生命", GcMonsterTier::Tier2, 2,
+            GcTavernSpellEffect::BuffBoard { atk: 5, hp: 5 }),
+        GcTavernSpell::gc_new("spell_recruit", "招募令", "发现一只 3 阶怪兽", GcMonsterTier::Tier3, 3,
+            GcTavernSpellEffect::Discover { tier: GcMonsterTier::Tier3 }),
+    ]
+}
+
+/// 按商店等级随机选出一个法术 (`roll` 为 0-255 的随机数)
+pub fn gc_roll_tavern_spell(spells: &[GcTavernSpell], shop_level: u8, roll: u8) -> Option<GcTavernSpell> {
+    let weights = gc_get_tier_weights(shop_level);
+    let candidates: Vec<&GcTavernSpell> = spells.iter()
+        .filter(|s| weights.percent_of(s.tier) > 0)
+        .collect();
+    if candidates.is_empty() {
+        return None;
+    }
+    Some(candidates[roll as usize % candidates.len()].clone())
+}
+
+// =============================================================================
+// 结算
+// =============================================================================
+
+/// 法术结算结果
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GcTavernSpellOutcome {
+    /// 法术ID
+    pub spell_id: String,
+    /// 受影响的怪兽ID
+    pub affected: Vec<String>,
+    /// 发现的怪兽
+    pub discovered: Option<GcMonster>,
+    /// 获得的金币
+    pub gold_gained: u32,
+}
+
+/// 友方怪兽 (阵容前后排与手牌区)
+fn gc_friendly_monsters_mut<'a>(player: &'a mut GcPlayer, board: &'a mut GcBattleArena) -> impl Iterator<Item = &'a mut GcMonster> {
+    board.player_monsters.iter_mut()
+        .chain(board.player_back_row.iter_mut())
+        .flatten()
+        .chain(player.bench.iter_mut())
+}
+
+/// 强化怪兽
+fn gc_buff_monster(monster: &mut GcMonster, atk: u32, hp: u32) {
+    monster.base_atk += atk;
+    monster.max_hp += hp;
+    monster.current_hp += hp;
+}
+
+/// 检查法术能否结算 (购买前调用，不修改状态)
+pub fn gc_validate_tavern_spell(
+    spell: &GcTavernSpell,
+    target: Option<&str>,
+    player: &GcPlayer,
+    board: &GcBattleArena,
+) -> Result<(), GcError> {
+    match spell.effect.gc_targeting() {
+        GcSpellTargeting::FriendlyMonster => {
+            let target = target.ok_or(GcError::GcInvalidTarget)?;
+            let found = board.player_monsters.iter()
+                .chain(board.player_back_row.iter())
+                .flatten()
+                .chain(player.bench.iter())
+                .any(|m| m.id == target);
+            if !found {
+                return Err(GcError::GcInvalidTarget);
+            }
+        }
+        GcSpellTargeting::None => {}
+    }
+    if matches!(spell.effect, GcTavernSpellEffect::Discover { .. }) && player.gc_is_bench_full() {
+        return Err(GcError::GcBenchFull);
+    }
+    Ok(())
+}
+
+/// 结算法术
+///
+/// - `target`: 目标怪兽ID (需要目标的法术)
+/// - `roll`: 发现怪兽使用的随机数
+pub fn gc_resolve_tavern_spell(
+    spell: &GcTavernSpell,
+    target: Option<&str>,
+    player: &mut GcPlayer,
+    board: &mut GcBattleArena,
+    pool: &GcMonsterPool,
+    roll: u8,
+) -> Result<GcTavernSpellOutcome, GcError> {
+    gc_validate_tavern_spell(spell, target, player, board)?;
+
+    let mut outcome = GcTavernSpellOutcome {
+        spell_id: spell.spell_id.clone(),
+        affected: Vec::new(),
+        discovered: None,
+        gold_gained: 0,
+    };
+    match &spell.effect {
+        GcTavernSpellEffect::Buff { atk, hp } => {
+            if let Some(monster) = gc_friendly_monsters_mut(player, board).find(|m| Some(m.id.as_str()) == target) {
+                gc_buff_monster(monster, *atk, *hp);
+                outcome.affected.push(monster.id.clone());
+            }
+        }
+        GcTavernSpellEffect::BuffBoard { atk, hp } => {
+            for monster in board.player_monsters.iter_mut().chain(board.player_back_row.iter_mut()).flatten() {
+                gc_buff_monster(monster, *atk, *hp);
+                outcome.affected.push(monster.id.clone());
+            }
+        }
+        GcTavernSpellEffect::Discover { tier } => {
+            let monster = pool.get_random_monster(*tier, roll).ok_or(GcError::GcCardNotInPool)?;
+            player.gc_try_add_to_bench(monster.clone())?;
+            outcome.affected.push(monster.id.clone());
+            outcome.discovered = Some(monster);
+        }
+        GcTavernSpellEffect::Gold { amount } => {
+            player.economy.earn(*amount);
+            outcome.gold_gained = *amount;
+        }
+    }
+    Ok(outcome)
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GcArenaPosition, GcMonsterAttribute};
+
+    fn spell(spell_id: &str) -> GcTavernSpell {
+        gc_default_tavern_spells().into_iter().find(|s| s.spell_id == spell_id).expect("默认法术")
+    }
+
+    fn slime(id: &str) -> GcMonster {
+        GcMonster::new_with_template(id, "slime", "史莱姆", 1, GcMonsterAttribute::Water, 50, 30, 60)
+    }
+
+    #[test]
+    fn test_spells_follow_shop_level() {
+        let spells = gc_default_tavern_spells();
+        let low: Vec<String> = (0..=255u8).filter_map(|r| gc_roll_tavern_spell(&spells, 1, r)).map(|s| s.spell_id).collect();
+        assert!(low.iter().all(|id| id == "spell_tonic" || id == "spell_coin"));
+        assert!((0..=255u8).filter_map(|r| gc_roll_tavern_spell(&spells, 4, r)).any(|s| s.spell_id == "spell_recruit"));
+        assert!(gc_roll_tavern_spell(&[], 1, 0).is_none());
+    }
+
+    #[test]
+    fn test_resolve_targeted_and_board_spells() {
+        let pool = GcMonsterPool::with_defaults();
+        let mut player = GcPlayer::gc_new_tavern("p1", "玩家1");
+        let mut board = GcBattleArena::default();
+        board.player_monsters[0] = Some(slime("front"));
+        board.player_back_row[1] = Some(slime("back"));
+        player.bench.push(slime("benched"));
+
+        // 目标缺失或不存在时不结算
+        let tonic = spell("spell_tonic");
+        assert!(matches!(gc_resolve_tavern_spell(&tonic, None, &mut player, &mut board, &pool, 0), Err(GcError::GcInvalidTarget)));
+        assert!(gc_validate_tavern_spell(&tonic, Some("nobody"), &player, &board).is_err());
+
+        let outcome = gc_resolve_tavern_spell(&tonic, Some("benched"), &mut player, &mut board, &pool, 0).expect("强化");
+        assert_eq!(outcome.affected, vec!["benched".to_string()]);
+        assert_eq!((player.bench[0].base_atk, player.bench[0].max_hp, player.bench[0].current_hp), (60, 70, 70));
+
+        let outcome = gc_resolve_tavern_spell(&spell("spell_war_song"), None, &mut player, &mut board, &pool, 0).expect("战歌");
+        assert_eq!(outcome.affected.len(), 2);
+        assert_eq!(board.player_monster_at(GcArenaPosition::back(1)).map(|m| m.base_atk), Some(55));
+        assert_eq!(player.bench[0].base_atk, 60);
+    }
+
+    #[test]
+    fn test_resolve_discover_and_gold() {
+        let pool = GcMonsterPool::with_defaults();
+        let mut player = GcPlayer::gc_new_tavern("p1", "玩家1");
+        let mut board = GcBattleArena::default();
+
+        let gold = player.economy.gold;
+        let outcome = gc_resolve_tavern_spell(&spell("spell_coin"), None, &mut player, &mut board, &pool, 0).expect("金币");
+        assert_eq!(outcome.gold_gained, 2);
+        assert_eq!(player.economy.gold, gold + 2);
+
+        let recruit = spell("spell_recruit");
+        let outcome = gc_resolve_tavern_spell(&recruit, None, &mut player, &mut board, &pool, 7).expect("发现");
+        let found = outcome.discovered.expect("发现的怪兽");
+        assert!(pool.find_template(&found.template_id).is_some_and(|t| t.tier == GcMonsterTier::Tier3));
+        assert_eq!(player.bench.len(), 1);
+
+        while !player.gc_is_bench_full() {
+            player.bench.push(slime(&format!("filler{}", player.bench.len())));
+        }
+        assert!(matches!(gc_validate_tavern_spell(&recruit, None, &player, &board), Err(GcError::GcBenchFull)));
+    }
+}
@@ -0,0 +1,421 @@
+//! 领地争夺 (大地图)
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! ## 规则
+//! - 领地是地图上的矩形区域，由组织占领
+//! - 无主领地可直接占领；他人领地需发起争夺，争夺窗口内双方累计积分
+//! - 争夺积分只来自对局: 双方组织成员对战，胜方组织每局获得固定积分
+//! - 窗口结束时积分高者获得领地，平局由防守方保留
+//! - 新占领的领地有保护期，保护期内不能被争夺
+//! - 每次资源结算，占领方 (非争夺中) 获得领地产出，存入组织金库
+//! - 时间由调用方提供 (Unix 秒)
+
+use serde::{Deserialize, Serialize};
+
+use crate::{GcMap, GcOrganization, GcPosition};
+
+// =============================================================================
+// 常量
+// =============================================================================
+
+/// 争夺窗口时长 (秒)
+pub const GC_TERRITORY_CONTEST_SECS: u64 = 30 * 60;
+
+/// 占领后保护期 (秒)
+pub const GC_TERRITORY_PROTECTION_SECS: u64 = 2 * 60 * 60;
+
+/// 单次累计的争夺积分上限
+pub const GC_TERRITORY_MAX_POINTS: u32 = 100;
+
+/// 争夺双方对局中胜方组织每局获得的积分
+pub const GC_TERRITORY_WIN_POINTS: u32 = 10;
+
+// =============================================================================
+// 领地定义
+// =============================================================================
+
+/// 领地区域
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcTerritoryZone {
+    /// 领地ID
+    pub id: String,
+    /// 名称
+    pub name: String,
+    /// 所在地图ID
+    pub map_id: String,
+    /// 左上角
+    pub origin: GcPosition,
+    /// 宽度
+    pub width: u32,
+    /// 高度
+    pub height: u32,
+    /// 每次结算的产出 (金币)
+    pub resource_per_tick: u64,
+}
+
+impl GcTerritoryZone {
+    /// 创建领地
+    pub fn gc_new(id: &str, name: &str, map_id: &str, origin: GcPosition, width: u32, height: u32, resource_per_tick: u64) -> Self {
+        Self {
+            id: id.to_string(),
+            name: name.to_string(),
+            map_id: map_id.to_string(),
+            origin,
+            width,
+            height,
+            resource_per_tick,
+        }
+    }
+
+    /// 坐标是否在领地内
+    pub fn gc_contains(&self, map_id: &str, pos: &GcPosition) -> bool {
+        self.map_id == map_id
+            && pos.x >= self.origin.x
+            && pos.y >= self.origin.y
+            && pos.x < self.origin.x + self.width as i32
+            && pos.y < self.origin.y + self.height as i32
+    }
+
+    /// 领地是否完整落在地图内
+    pub fn gc_fits(&self, map: &GcMap) -> bool {
+        let far = GcPosition::gc_new(
+            self.origin.x + self.width as i32 - 1,
+            self.origin.y + self.height as i32 - 1,
+        );
+        self.map_id == map.id && self.width > 0 && self.height > 0
+            && map.gc_is_valid(&self.origin) && map.gc_is_valid(&far)
+    }
+}
+
+// =============================================================================
+// 占领状态
+// =============================================================================
+
+/// 争夺中的领地
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcTerritoryContest {
+    /// 进攻方
+    pub challenger: GcOrganization,
+    /// 开始时间
+    pub started_at: u64,
+    /// 结束时间
+    pub ends_at: u64,
+    /// 防守方积分
+    pub defender_points: u32,
+    /// 进攻方积分
+    pub challenger_points: u32,
+}
+
+/// 领地占领状态
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcTerritoryControl {
+    /// 领地ID
+    pub zone_id: String,
+    /// 占领方
+    pub owner: Option<GcOrganization>,
+    /// 保护期结束时间
+    pub protected_until: u64,
+    /// 进行中的争夺
+    pub contest: Option<GcTerritoryContest>,
+}
+
+/// 占领/争夺请求的结果
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GcTerritoryClaim {
+    /// 无主领地，直接占领
+    Claimed,
+    /// 发起争夺，窗口结束时间
+    ContestStarted { ends_at: u64 },
+}
+
+/// 领地易主
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcTerritoryChange {
+    /// 领地ID
+    pub zone_id: String,
+    /// 原占领方
+    pub previous: Option<GcOrganization>,
+    /// 现占领方
+    pub owner: Option<GcOrganization>,
+}
+
+/// 地图覆盖层中的一块领地
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcTerritoryOverlay {
+    /// 领地
+    pub zone: GcTerritoryZone,
+    /// 占领方
+    pub owner: Option<GcOrganization>,
+    /// 占领方颜色 (RGB)
+    pub color: Option<(u8, u8, u8)>,
+    /// 进行中的争夺
+    pub contest: Option<GcTerritoryContest>,
+    /// 保护期结束时间
+    pub protected_until: u64,
+}
+
+// =============================================================================
+// 领地图层
+// =============================================================================
+
+/// 全部领地与占领状态
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct GcTerritoryMap {
+    /// 领地定义
+    pub zones: Vec<GcTerritoryZone>,
+    /// 占领状态 (与 zones 一一对应)
+    pub controls: Vec<GcTerritoryControl>,
+}
+
+impl GcTerritoryMap {
+    /// 由领地定义创建 (全部无主)
+    pub fn gc_new(zones: Vec<GcTerritoryZone>) -> Self {
+        let controls = zones.iter()
+            .map(|z| GcTerritoryControl {
+                zone_id: z.id.clone(),
+                owner: None,
+                protected_until: 0,
+                contest: None,
+            })
+            .collect();
+        Self { zones, controls }
+    }
+
+    /// 获取占领状态 (可变)
+    fn gc_control_mut(&mut self, zone_id: &str) -> Result<&mut GcTerritoryControl, String> {
+        self.controls.iter_mut()
+            .find(|c| c.zone_id == zone_id)
+            .ok_or_else(|| format!("领地不存在: {}", zone_id))
+    }
+
+    /// 坐标所在领地
+    pub fn gc_zone_at(&self, map_id: &str, pos: &GcPosition) -> Option<&GcTerritoryZone> {
+        self.zones.iter().find(|z| z.gc_contains(map_id, pos))
+    }
+
+    /// 组织占领的领地数
+    pub fn gc_owned_count(&self, organization: &GcOrganization) -> usize {
+        self.controls.iter().filter(|c| c.owner.as_ref() == Some(organization)).count()
+    }
+
+    /// 占领或发起争夺
+    pub fn gc_claim(&mut self, zone_id: &str, organization: GcOrganization, now: u64) -> Result<GcTerritoryClaim, String> {
+        let control = self.gc_control_mut(zone_id)?;
+        match &control.owner {
+            None => {
+                control.owner = Some(organization);
+                control.protected_until = now + GC_TERRITORY_PROTECTION_SECS;
+                Ok(GcTerritoryClaim::Claimed)
+            }
+            Some(owner) if *owner == organization => Err("已占领该领地".to_string()),
+            Some(_) => {
+                if control.contest.is_some() {
+                    return Err("该领地正在争夺中".to_string());
+                }
+                if now < control.protected_until {
+                    return Err("该领地处于保护期".to_string());
+                }
+                let ends_at = now + GC_TERRITORY_CONTEST_SECS;
+                control.contest = Some(GcTerritoryContest {
+                    challenger: organization,
+                    started_at: now,
+                    ends_at,
+                    defender_points: 0,
+                    challenger_points: 0,
+                });
+                Ok(GcTerritoryClaim::ContestStarted { ends_at })
+            }
+        }
+    }
+
+    /// 为争夺中的一方累计积分
+    pub fn gc_add_contest_points(&mut self, zone_id: &str, organization: &GcOrganization, points: u32, now: u64) -> Result<(), String> {
+        if points > GC_TERRITORY_MAX_POINTS {
+            return Err(format!("单次积分不能超过 {}", GC_TERRITORY_MAX_POINTS));
+        }
+        let control = self.gc_control_mut(zone_id)?;
+        let is_defender = control.owner.as_ref() == Some(organization);
+        let contest = control.contest.as_mut()
+            .filter(|c| now < c.ends_at)
+            .ok_or("该领地不在争夺窗口内")?;
+
+        if is_defender {
+            contest.defender_points += points;
+        } else if contest.challenger == *organization {
+            contest.challenger_points += points;
+        } else {
+            return Err("未参与该领地的争夺".to_string());
+        }
+        Ok(())
+    }
+
+    /// 计入一局对局结果: 胜方与败方组织正在争夺同一领地时为胜方加分，返回计分的领地ID
+    ///
+    /// 同时出现在胜败两方的组织 (如混编队伍) 不计分
+    pub fn gc_record_match_result(&mut self, winners: &[GcOrganization], losers: &[GcOrganization], now: u64) -> Vec<String> {
+        let (winners, losers): (Vec<&GcOrganization>, Vec<&GcOrganization>) = (
+            winners.iter().filter(|o| !losers.contains(o)).collect(),
+            losers.iter().filter(|o| !winners.contains(o)).collect(),
+        );
+        let mut scored = Vec::new();
+        for control in &mut self.controls {
+            let Some(owner) = control.owner.as_ref() else {
+                continue;
+            };
+            let Some(contest) = control.contest.as_mut().filter(|c| now < c.ends_at) else {
+                continue;
+            };
+            if winners.contains(&owner) && losers.contains(&&contest.challenger) {
+                contest.defender_points += GC_TERRITORY_WIN_POINTS;
+            } else if winners.contains(&&contest.challenger) && losers.contains(&owner) {
+                contest.challenger_points += GC_TERRITORY_WIN_POINTS;
+            } else {
+                continue;
+            }
+            scored.push(control.zone_id.clone());
+        }
+        scored
+    }
+
+    /// 结算已结束的争夺，返回易主的领地
+    pub fn gc_resolve_contests(&mut self, now: u64) -> Vec<GcTerritoryChange> {
+        let mut changes = Vec::new();
+        for control in &mut self.controls {
+            let Some(contest) = control.contest.take_if(|c| now >= c.ends_at) else {
+                continue;
+            };
+            if contest.challenger_points > contest.defender_points {
+                changes.push(GcTerritoryChange {
+                    zone_id: control.zone_id.clone(),
+                    previous: control.owner.replace(contest.challenger.clone()),
+                    owner: Some(contest.challenger),
+                });
+                control.protected_until = now + GC_TERRITORY_PROTECTION_SECS;
+            }
+        }
+        changes
+    }
+
+    /// 资源结算: 各组织本次获得的产出 (争夺中的领地不产出)
+    pub fn gc_resource_tick(&self) -> Vec<(GcOrganization, u64)> {
+        let mut income: Vec<(GcOrganization, u64)> = Vec::new();
+        for (zone, control) in self.zones.iter().zip(&self.controls) {
+            let Some(owner) = control.owner.as_ref().filter(|_| control.contest.is_none()) else {
+                continue;
+            };
+            match income.iter_mut().find(|(org, _)| org == owner) {
+                Some((_, total)) => *total += zone.resource_per_tick,
+                None => income.push((owner.clone(), zone.resource_per_tick)),
+            }
+        }
+        income
+    }
+
+    /// 地图覆盖层 (用于客户端绘制领地)
+    pub fn gc_overlay(&self, map_id: &str) -> Vec<GcTerritoryOverlay> {
+        self.zones.iter()
+            .zip(&self.controls)
+            .filter(|(zone, _)| zone.map_id == map_id)
+            .map(|(zone, control)| GcTerritoryOverlay {
+                zone: zone.clone(),
+                owner: control.owner.clone(),
+                color: control.owner.as_ref().map(|o| o.gc_color()),
+                contest: control.contest.clone(),
+                protected_until: control.protected_until,
+            })
+            .collect()
+    }
+}
+
+// =============================================================================
+// 预设领地
+// =============================================================================
+
+/// 默认领地: 迷雾森林按四个象限划分
+pub fn gc_create_default_territories() -> GcTerritoryMap {
+    GcTerritoryMap::gc_new(vec![
+        GcTerritoryZone::gc_new("zone_forest_nw", "雾隐林地", "map_forest", GcPosition::gc_new(0, 0), 12, 10, 40),
+        GcTerritoryZone::gc_new("zone_forest_ne", "古树高地", "map_forest", GcPosition::gc_new(12, 0), 13, 10, 50),
+        GcTerritoryZone::gc_new("zone_forest_sw", "泥沼洼地", "map_forest", GcPosition::gc_new(0, 10), 12, 10, 30),
+        GcTerritoryZone::gc_new("zone_forest_se", "遗迹深处", "map_forest", GcPosition::gc_new(12, 10), 13, 10, 60),
+    ])
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gc_create_forest_map;
+
+    #[test]
+    fn test_default_zones_fit_map() {
+        let territories = gc_create_default_territories();
+        let map = gc_create_forest_map();
+        assert!(territories.zones.iter().all(|z| z.gc_fits(&map)));
+
+        let zone = territories.gc_zone_at("map_forest", &GcPosition::gc_new(20, 15));
+        assert_eq!(zone.map(|z| z.id.as_str()), Some("zone_forest_se"));
+        assert!(territories.gc_zone_at("map_town", &GcPosition::gc_new(1, 1)).is_none());
+    }
+
+    #[test]
+    fn test_claim_contest_and_income() {
+        let mut territories = gc_create_default_territories();
+        let zone = "zone_forest_nw";
+
+        assert_eq!(territories.gc_claim(zone, GcOrganization::IronBlood, 0), Ok(GcTerritoryClaim::Claimed));
+        assert!(territories.gc_claim(zone, GcOrganization::HolyLight, 10).is_err());
+        assert_eq!(territories.gc_resource_tick(), vec![(GcOrganization::IronBlood, 40)]);
+
+        let now = GC_TERRITORY_PROTECTION_SECS;
+        let ends_at = now + GC_TERRITORY_CONTEST_SECS;
+        assert_eq!(
+            territories.gc_claim(zone, GcOrganization::HolyLight, now),
+            Ok(GcTerritoryClaim::ContestStarted { ends_at })
+        );
+        assert!(territories.gc_resource_tick().is_empty());
+
+        territories.gc_add_contest_points(zone, &GcOrganization::HolyLight, 50, now + 1).unwrap();
+        territories.gc_add_contest_points(zone, &GcOrganization::IronBlood, 20, now + 2).unwrap();
+        assert!(territories.gc_add_contest_points(zone, &GcOrganization::Wildland, 10, now + 3).is_err());
+
+        assert!(territories.gc_resolve_contests(ends_at - 1).is_empty());
+        let changes = territories.gc_resolve_contests(ends_at);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].owner, Some(GcOrganization::HolyLight));
+        assert_eq!(territories.gc_overlay("map_forest")[0].color, Some(GcOrganization::HolyLight.gc_color()));
+    }
+
+    #[test]
+    fn test_match_results_score_only_contesting_organizations() {
+        let mut territories = gc_create_default_territories();
+        let zone = "zone_forest_ne";
+        territories.gc_claim(zone, GcOrganization::IronBlood, 0).unwrap();
+        let now = GC_TERRITORY_PROTECTION_SECS;
+        territories.gc_claim(zone, GcOrganization::ShadowGuild, now).unwrap();
+
+        let iron = [GcOrganization::IronBlood];
+        let shadow = [GcOrganization::ShadowGuild];
+        let holy = [GcOrganization::HolyLight];
+        assert_eq!(territories.gc_record_match_result(&shadow, &iron, now + 1), vec![zone.to_string()]);
+        assert_eq!(territories.gc_record_match_result(&iron, &shadow, now + 2), vec![zone.to_string()]);
+        assert_eq!(territories.gc_record_match_result(&shadow, &iron, now + 3), vec![zone.to_string()]);
+
+        // 第三方组织、混编队伍与窗口结束后的对局不计分
+        assert!(territories.gc_record_match_result(&holy, &iron, now + 4).is_empty());
+        let mixed = [GcOrganization::IronBlood, GcOrganization::ShadowGuild];
+        assert!(territories.gc_record_match_result(&mixed, &iron, now + 5).is_empty());
+        let ends_at = now + GC_TERRITORY_CONTEST_SECS;
+        assert!(territories.gc_record_match_result(&shadow, &iron, ends_at).is_empty());
+
+        let contest = territories.gc_overlay("map_forest")[1].contest.clone().unwrap();
+        assert_eq!((contest.defender_points, contest.challenger_points), (GC_TERRITORY_WIN_POINTS, 2 * GC_TERRITORY_WIN_POINTS));
+        assert_eq!(territories.gc_resolve_contests(ends_at)[0].owner, Some(GcOrganization::ShadowGuild));
+    }
+}
@@ -0,0 +1,239 @@
+//! 用户文本校验 (名称、房间名、聊天)
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! ## 规则
+//! - 先规范化再校验: Unicode NFKC (全角字母数字转半角)、去除控制字符与零宽/方向控制字符、连续空白合并为一个空格、去除首尾空白
+//! - 每种字段 (`GcTextField`) 有长度范围 (按字符计) 与允许的字符集
+//! - 敏感词由调用方提供的 `GcNameFilter` 判断；同时检查去掉空格后的文本，防止用空格拆开屏蔽词
+//! - 拒绝原因是结构化的 `GcTextError` (字段 + 原因 + 参数)，客户端可按错误码本地化
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use unicode_normalization::UnicodeNormalization;
+
+use crate::{GcJsonDiagnostic, GcNameFilter};
+
+// =============================================================================
+// 字段
+// =============================================================================
+
+/// 用户文本字段
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum GcTextField {
+    /// 玩家名称 (联机登录时的显示名)
+    PlayerName,
+    /// 房间名称
+    RoomName,
+    /// 聊天消息
+    Chat,
+    /// 组织名称
+    OrganizationName,
+    /// 账号用户名
+    Username,
+}
+
+impl GcTextField {
+    /// 长度范围 (字符数，含两端)
+    pub fn gc_limits(&self) -> (usize, usize) {
+        match self {
+            Self::PlayerName => (1, 16),
+            Self::RoomName => (1, 32),
+            Self::Chat => (1, 200),
+            Self::OrganizationName => (2, 24),
+            Self::Username => (3, 32),
+        }
+    }
+
+    /// 显示名称
+    pub fn gc_label(&self) -> &'static str {
+        match self {
+            Self::PlayerName => "玩家名称",
+            Self::RoomName => "房间名称",
+            Self::Chat => "聊天消息",
+            Self::OrganizationName => "组织名称",
+            Self::Username => "用户名",
+        }
+    }
+
+    /// 字符是否允许 (规范化之后)
+    fn gc_allows(&self, c: char) -> bool {
+        match self {
+            Self::PlayerName => c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | ' '),
+            Self::OrganizationName => c.is_alphanumeric() || matches!(c, '_' | ' '),
+            Self::Username => c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'),
+            Self::RoomName | Self::Chat => true,
+        }
+    }
+}
+
+// =============================================================================
+// 拒绝原因
+// =============================================================================
+
+/// 文本被拒绝的原因
+#[derive(Clone, Debug, Error, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "reason")]
+pub enum GcTextRejection {
+    /// 规范化后为空
+    #[error("不能为空")]
+    Empty,
+    /// 太短
+    #[error("至少需要 {min} 个字符 (当前 {len} 个)")]
+    TooShort { min: usize, len: usize },
+    /// 太长
+    #[error("最多 {max} 个字符 (当前 {len} 个)")]
+    TooLong { max: usize, len: usize },
+    /// 包含不允许的字符
+    #[error("包含不允许的字符: {character:?}")]
+    InvalidCharacter { character: char },
+    /// 包含屏蔽词
+    #[error("包含不允许的词语")]
+    Blocked,
+}
+
+/// 文本校验失败
+#[derive(Clone, Debug, Error, PartialEq, Eq, Serialize, Deserialize)]
+#[error("{}{rejection}", field.gc_label())]
+pub struct GcTextError {
+    /// 字段
+    pub field: GcTextField,
+    /// 原因
+    pub rejection: GcTextRejection,
+}
+
+impl GcTextError {
+    /// 错误码 (REST 与 WebSocket 错误共用)
+    pub fn gc_code(&self) -> &'static str {
+        match self.rejection {
+            GcTextRejection::Empty => "TEXT_EMPTY",
+            GcTextRejection::TooShort { .. } => "TEXT_TOO_SHORT",
+            GcTextRejection::TooLong { .. } => "TEXT_TOO_LONG",
+            GcTextRejection::InvalidCharacter { .. } => "TEXT_INVALID_CHARACTER",
+            GcTextRejection::Blocked => "TEXT_BLOCKED",
+        }
+    }
+
+    /// 转换为字段诊断 (`input` 为消息类型或请求体，`path` 为字段路径)
+    pub fn gc_diagnostic(&self, input: &str, path: &str) -> GcJsonDiagnostic {
+        let (min, max) = self.field.gc_limits();
+        GcJsonDiagnostic {
+            input: input.to_string(),
+            path: path.to_string(),
+            message: self.to_string(),
+            expected: Some(format!("{} ({}~{} 个字符)", self.field.gc_label(), min, max)),
+            line: 0,
+            column: 0,
+        }
+    }
+}
+
+// =============================================================================
+// 规范化与校验
+// =============================================================================
+
+/// 零宽与方向控制等不可见的格式字符
+fn gc_is_invisible(c: char) -> bool {
+    matches!(c, '\u{00AD}' | '\u{061C}' | '\u{180E}' | '\u{200B}'..='\u{200F}' | '\u{202A}'..='\u{202E}' | '\u{2060}'..='\u{206F}' | '\u{FEFF}')
+}
+
+/// 规范化文本: NFKC、去除控制与不可见字符、合并空白、去除首尾空白
+pub fn gc_normalize_text(input: &str) -> String {
+    let mut output = String::with_capacity(input.len());
+    let mut pending_space = false;
+    for c in input.nfkc() {
+        if c.is_whitespace() {
+            pending_space = !output.is_empty();
+        } else if !c.is_control() && !gc_is_invisible(c) {
+            if pending_space {
+                output.push(' ');
+                pending_space = false;
+            }
+            output.push(c);
+        }
+    }
+    output
+}
+
+/// 规范化并校验用户文本，返回规范化后的文本
+pub fn gc_sanitize_text(input: &str, field: GcTextField, filter: &dyn GcNameFilter) -> Result<String, GcTextError> {
+    let reject = |rejection| GcTextError { field, rejection };
+    let text = gc_normalize_text(input);
+    let len = text.chars().count();
+    let (min, max) = field.gc_limits();
+
+    if len == 0 {
+        return Err(reject(GcTextRejection::Empty));
+    }
+    if len < min {
+        return Err(reject(GcTextRejection::TooShort { min, len }));
+    }
+    if len > max {
+        return Err(reject(GcTextRejection::TooLong { max, len }));
+    }
+    if let Some(character) = text.chars().find(|c| !field.gc_allows(*c)) {
+        return Err(reject(GcTextRejection::InvalidCharacter { character }));
+    }
+    let compact: String = text.chars().filter(|c| *c != ' ').collect();
+    if !filter.gc_is_allowed(&text) || !filter.gc_is_allowed(&compact) {
+        return Err(reject(GcTextRejection::Blocked));
+    }
+    Ok(text)
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GcNoNameFilter, GcWordBlocklist};
+
+    #[test]
+    fn test_normalize_strips_and_folds() {
+        assert_eq!(gc_normalize_text("  ＡＢＣ１２３  "), "ABC123");
+        assert_eq!(gc_normalize_text("a\u{200B}b\u{202E}c"), "abc");
+        assert_eq!(gc_normalize_text("第一行\n\t第二行\u{0007}"), "第一行 第二行");
+        assert_eq!(gc_normalize_text("\u{3000}房间  名\u{3000}"), "房间 名");
+    }
+
+    #[test]
+    fn test_sanitize_rejections() {
+        let filter = GcWordBlocklist::gc_new(&["admin"]);
+        assert_eq!(gc_sanitize_text(" 玩家1 ", GcTextField::PlayerName, &filter).unwrap(), "玩家1");
+
+        let err = gc_sanitize_text("\u{200B} ", GcTextField::RoomName, &filter).unwrap_err();
+        assert_eq!(err.rejection, GcTextRejection::Empty);
+        assert_eq!(err.gc_code(), "TEXT_EMPTY");
+
+        let err = gc_sanitize_text("ab", GcTextField::Username, &GcNoNameFilter).unwrap_err();
+        assert_eq!(err.rejection, GcTextRejection::TooShort { min: 3, len: 2 });
+
+        let err = gc_sanitize_text(&"长".repeat(17), GcTextField::PlayerName, &GcNoNameFilter).unwrap_err();
+        assert_eq!(err.rejection, GcTextRejection::TooLong { max: 16, len: 17 });
+
+        let err = gc_sanitize_text("a<b>", GcTextField::PlayerName, &GcNoNameFilter).unwrap_err();
+        assert_eq!(err.rejection, GcTextRejection::InvalidCharacter { character: '<' });
+
+        // 全角与空格拆开的屏蔽词同样拒绝
+        for name in ["ＡＤＭＩＮ", "ad min"] {
+            let err = gc_sanitize_text(name, GcTextField::PlayerName, &filter).unwrap_err();
+            assert_eq!(err.rejection, GcTextRejection::Blocked);
+        }
+    }
+
+    #[test]
+    fn test_error_is_structured() {
+        let err = gc_sanitize_text("", GcTextField::RoomName, &GcNoNameFilter).unwrap_err();
+        assert_eq!(err.to_string(), "房间名称不能为空");
+        let json = serde_json::to_value(&err).unwrap();
+        assert_eq!(json, serde_json::json!({ "field": "RoomName", "rejection": { "reason": "Empty" } }));
+
+        let diagnostic = err.gc_diagnostic("CreateRoom", ".name");
+        assert_eq!(diagnostic.path, ".name");
+        assert_eq!(diagnostic.expected.as_deref(), Some("房间名称 (1~32 个字符)"));
+    }
+}
@@ -0,0 +1,225 @@
+//! 仇恨系统 (PvE)
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! ## 仇恨规则
+//! - 每个敌人维护独立的仇恨表，决定单体技能的目标
+//! - 造成伤害: 每点伤害 +1 仇恨
+//! - 治疗: 每点治疗 +50% 仇恨
+//! - 嘲讽: 仇恨立即提升至当前最高值的 110%，并强制锁定目标若干回合
+//! - 回合结束: 所有仇恨衰减 10%，嘲讽剩余回合 -1
+
+use serde::{Deserialize, Serialize};
+
+// =============================================================================
+// 常量
+// =============================================================================
+
+/// 治疗产生的仇恨比例 (%)
+pub const GC_THREAT_HEAL_PERCENT: u32 = 50;
+
+/// 嘲讽后仇恨 = 当前最高仇恨 × 该比例 (%)
+pub const GC_THREAT_TAUNT_PERCENT: u32 = 110;
+
+/// 每回合仇恨衰减比例 (%)
+pub const GC_THREAT_DECAY_PERCENT: u32 = 10;
+
+// =============================================================================
+// 仇恨表
+// =============================================================================
+
+/// 仇恨条目
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcThreatEntry {
+    /// 玩家ID
+    pub player_id: String,
+    /// 仇恨值
+    pub threat: u32,
+}
+
+/// 仇恨快照 (供 UI 显示)
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcThreatSnapshot {
+    /// 玩家ID
+    pub player_id: String,
+    /// 仇恨值
+    pub threat: u32,
+    /// 相对最高仇恨的百分比
+    pub percent_of_top: u32,
+    /// 是否为当前目标
+    pub is_target: bool,
+}
+
+/// 敌人仇恨表
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct GcThreatTable {
+    /// 仇恨条目
+    pub entries: Vec<GcThreatEntry>,
+    /// 嘲讽者ID
+    pub taunted_by: Option<String>,
+    /// 嘲讽剩余回合
+    pub taunt_turns: u32,
+}
+
+impl GcThreatTable {
+    /// 创建空仇恨表
+    pub fn gc_new() -> Self {
+        Self::default()
+    }
+
+    /// 获取玩家仇恨值
+    pub fn gc_threat_of(&self, player_id: &str) -> u32 {
+        self.entries.iter()
+            .find(|e| e.player_id == player_id)
+            .map(|e| e.threat)
+            .unwrap_or(0)
+    }
+
+    /// 增加仇恨
+    pub fn gc_add_threat(&mut self, player_id: &str, amount: u32) {
+        match self.entries.iter_mut().find(|e| e.player_id == player_id) {
+            Some(entry) => entry.threat = entry.threat.saturating_add(amount),
+            None => self.entries.push(GcThreatEntry {
+                player_id: player_id.to_string(),
+                threat: amount,
+            }),
+        }
+    }
+
+    /// 伤害产生仇恨
+    pub fn gc_on_damage(&mut self, player_id: &str, damage: u32) {
+        self.gc_add_threat(player_id, damage);
+    }
+
+    /// 治疗产生仇恨
+    pub fn gc_on_heal(&mut self, player_id: &str, amount: u32) {
+        self.gc_add_threat(player_id, amount * GC_THREAT_HEAL_PERCENT / 100);
+    }
+
+    /// 嘲讽: 仇恨提升至最高值的 110%，并锁定目标
+    pub fn gc_taunt(&mut self, player_id: &str, turns: u32) {
+        let top = self.entries.iter().map(|e| e.threat).max().unwrap_or(0);
+        let spiked = (top * GC_THREAT_TAUNT_PERCENT / 100).max(top + 1);
+        let current = self.gc_threat_of(player_id);
+        if spiked > current {
+            self.gc_add_threat(player_id, spiked - current);
+        }
+        self.taunted_by = Some(player_id.to_string());
+        self.taunt_turns = turns;
+    }
+
+    /// 移除玩家 (死亡或离开)
+    pub fn gc_remove(&mut self, player_id: &str) {
+        self.entries.retain(|e| e.player_id != player_id);
+        if self.taunted_by.as_deref() == Some(player_id) {
+            self.taunted_by = None;
+            self.taunt_turns = 0;
+        }
+    }
+
+    /// 回合结束: 仇恨衰减，嘲讽倒计时
+    pub fn gc_on_turn_end(&mut self) {
+        for entry in &mut self.entries {
+            entry.threat -= entry.threat * GC_THREAT_DECAY_PERCENT / 100;
+        }
+        self.entries.retain(|e| e.threat > 0);
+
+        self.taunt_turns = self.taunt_turns.saturating_sub(1);
+        if self.taunt_turns == 0 {
+            self.taunted_by = None;
+        }
+    }
+
+    /// 选择目标
+    ///
+    /// 嘲讽中且嘲讽者可选时返回嘲讽者；否则返回可选玩家中仇恨最高者
+    /// (同仇恨按 `candidates` 顺序)，均无仇恨时返回第一个候选
+    pub fn gc_select_target<'a>(&self, candidates: &'a [String]) -> Option<&'a str> {
+        if let Some(taunter) = &self.taunted_by {
+            if let Some(c) = candidates.iter().find(|c| *c == taunter) {
+                return Some(c);
+            }
+        }
+
+        let mut best: Option<(&'a str, u32)> = None;
+        for c in candidates {
+            let threat = self.gc_threat_of(c);
+            if best.is_none_or(|(_, t)| threat > t) {
+                best = Some((c, threat));
+            }
+        }
+        best.map(|(id, _)| id)
+    }
+
+    /// 查看当前仇恨 (按仇恨降序)
+    pub fn gc_inspect(&self) -> Vec<GcThreatSnapshot> {
+        let top = self.entries.iter().map(|e| e.threat).max().unwrap_or(0);
+        let ids: Vec<String> = self.entries.iter().map(|e| e.player_id.clone()).collect();
+        let target = self.gc_select_target(&ids).map(|s| s.to_string());
+
+        let mut snapshots: Vec<GcThreatSnapshot> = self.entries.iter()
+            .map(|e| GcThreatSnapshot {
+                player_id: e.player_id.clone(),
+                threat: e.threat,
+                percent_of_top: (e.threat * 100).checked_div(top).unwrap_or(0),
+                is_target: target.as_deref() == Some(e.player_id.as_str()),
+            })
+            .collect();
+        snapshots.sort_by_key(|s| std::cmp::Reverse(s.threat));
+        snapshots
+    }
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_damage_and_heal_threat() {
+        let mut table = GcThreatTable::gc_new();
+        table.gc_on_damage("p1", 100);
+        table.gc_on_heal("p2", 240);
+
+        assert_eq!(table.gc_threat_of("p2"), 120);
+        assert_eq!(table.gc_select_target(&ids(&["p1", "p2"])), Some("p2"));
+    }
+
+    #[test]
+    fn test_taunt_spikes_and_expires() {
+        let mut table = GcThreatTable::gc_new();
+        table.gc_on_damage("dps", 200);
+        table.gc_taunt("tank", 1);
+
+        assert_eq!(table.gc_threat_of("tank"), 220);
+        assert_eq!(table.gc_select_target(&ids(&["dps", "tank"])), Some("tank"));
+
+        table.gc_on_turn_end();
+        assert!(table.taunted_by.is_none());
+        assert_eq!(table.gc_threat_of("dps"), 180);
+    }
+
+    #[test]
+    fn test_inspect_sorted_and_removed() {
+        let mut table = GcThreatTable::gc_new();
+        table.gc_on_damage("p1", 50);
+        table.gc_on_damage("p2", 100);
+
+        let snapshot = table.gc_inspect();
+        assert_eq!(snapshot[0].player_id, "p2");
+        assert!(snapshot[0].is_target);
+        assert_eq!(snapshot[1].percent_of_top, 50);
+
+        table.gc_remove("p2");
+        assert_eq!(table.gc_select_target(&ids(&["p1", "p3"])), Some("p1"));
+    }
+}
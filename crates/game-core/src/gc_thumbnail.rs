@@ -0,0 +1,391 @@
+//! 资源缩略图 (上传模型与贴图的预览)
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! 服务器在上传时生成缩略图，编辑器的资源库不必加载完整模型就能显示预览。
+//! 这里只处理内存中的像素与模型元数据，图片编解码与文件读写在服务器完成
+//!
+//! ## 规则
+//! - 缩略图等比缩放到不超过 `GC_THUMBNAIL_SIZE` (小图不放大)，按源像素面积加权平均
+//! - 模型读取 glTF JSON (`.gltf` 或 `.glb` 的 JSON 块)，统计网格、节点、材质、顶点与三角形数量，
+//!   包围盒取 POSITION 访问器的 min / max 合并 (不计节点变换)
+//! - 模型缩略图是按包围盒比例绘制的斜投影方块，颜色取第一个材质的基础色
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// 缩略图最大边长 (像素)
+pub const GC_THUMBNAIL_SIZE: u32 = 128;
+
+/// GLB 文件头魔数 ("glTF")
+const GC_GLB_MAGIC: u32 = 0x4654_6C67;
+
+/// GLB JSON 块类型 ("JSON")
+const GC_GLB_CHUNK_JSON: u32 = 0x4E4F_534A;
+
+/// 模型缩略图的默认颜色 (没有材质基础色时)
+const GC_MODEL_DEFAULT_COLOR: [f32; 4] = [0.55, 0.6, 0.65, 1.0];
+
+// =============================================================================
+// 图像
+// =============================================================================
+
+/// RGBA8 图像
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GcRgbaImage {
+    /// 宽度
+    pub width: u32,
+    /// 高度
+    pub height: u32,
+    /// 像素 (按行排列，每像素 4 字节)
+    pub pixels: Vec<u8>,
+}
+
+impl GcRgbaImage {
+    /// 纯色图像
+    pub fn gc_filled(width: u32, height: u32, color: [u8; 4]) -> Self {
+        Self { width, height, pixels: color.repeat((width * height) as usize) }
+    }
+
+    /// 由像素数据创建 (长度不符时返回 None)
+    pub fn gc_from_pixels(width: u32, height: u32, pixels: Vec<u8>) -> Option<Self> {
+        (width > 0 && height > 0 && pixels.len() == (width as usize) * (height as usize) * 4)
+            .then_some(Self { width, height, pixels })
+    }
+
+    /// 读取像素
+    pub fn gc_pixel(&self, x: u32, y: u32) -> [u8; 4] {
+        let i = ((y * self.width + x) * 4) as usize;
+        [self.pixels[i], self.pixels[i + 1], self.pixels[i + 2], self.pixels[i + 3]]
+    }
+
+    fn gc_set_pixel(&mut self, x: u32, y: u32, color: [u8; 4]) {
+        let i = ((y * self.width + x) * 4) as usize;
+        self.pixels[i..i + 4].copy_from_slice(&color);
+    }
+
+    /// 等比缩放到不超过 max_size (小图保持原样)
+    pub fn gc_thumbnail(&self, max_size: u32) -> Self {
+        let max_size = max_size.max(1);
+        if self.width <= max_size && self.height <= max_size {
+            return self.clone();
+        }
+        let scale = max_size as f64 / self.width.max(self.height) as f64;
+        let width = ((self.width as f64 * scale).round() as u32).max(1);
+        let height = ((self.height as f64 * scale).round() as u32).max(1);
+        let (sx, sy) = (self.width as f64 / width as f64, self.height as f64 / height as f64);
+
+        let mut output = Self::gc_filled(width, height, [0; 4]);
+        for y in 0..height {
+            let (y0, y1) = (y as f64 * sy, (y + 1) as f64 * sy);
+            for x in 0..width {
+                let (x0, x1) = (x as f64 * sx, (x + 1) as f64 * sx);
+                let mut sum = [0.0f64; 4];
+                let mut area = 0.0;
+                for py in y0.floor() as u32..(y1.ceil() as u32).min(self.height) {
+                    let wy = (y1.min(py as f64 + 1.0) - y0.max(py as f64)).max(0.0);
+                    for px in x0.floor() as u32..(x1.ceil() as u32).min(self.width) {
+                        let w = wy * (x1.min(px as f64 + 1.0) - x0.max(px as f64)).max(0.0);
+                        let pixel = self.gc_pixel(px, py);
+                        // 颜色按透明度加权，避免透明像素的颜色渗入边缘
+                        let alpha = pixel[3] as f64 / 255.0;
+                        for c in 0..3 {
+                            sum[c] += pixel[c] as f64 * alpha * w;
+                        }
+                        sum[3] += alpha * w;
+                        area += w;
+                    }
+                }
+                let color = if sum[3] > 0.0 {
+                    [
+                        (sum[0] / sum[3]).round() as u8,
+                        (sum[1] / sum[3]).round() as u8,
+                        (sum[2] / sum[3]).round() as u8,
+                        (sum[3] / area * 255.0).round() as u8,
+                    ]
+                } else {
+                    [0; 4]
+                };
+                output.gc_set_pixel(x, y, color);
+            }
+        }
+        output
+    }
+}
+
+// =============================================================================
+// 模型元数据
+// =============================================================================
+
+/// 模型元数据 (资源清单与缩略图使用)
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct GcModelInfo {
+    /// 网格数
+    pub meshes: usize,
+    /// 节点数
+    pub nodes: usize,
+    /// 材质数
+    pub materials: usize,
+    /// 顶点数
+    pub vertices: usize,
+    /// 三角形数
+    pub triangles: usize,
+    /// 包围盒最小点 (没有顶点时为 0)
+    pub bounds_min: [f32; 3],
+    /// 包围盒最大点
+    pub bounds_max: [f32; 3],
+    /// 第一个材质的基础色
+    #[serde(default)]
+    pub base_color: Option<[f32; 4]>,
+    /// 导出工具
+    #[serde(default)]
+    pub generator: Option<String>,
+}
+
+impl GcModelInfo {
+    /// 包围盒尺寸
+    pub fn gc_size(&self) -> [f32; 3] {
+        [0, 1, 2].map(|i| (self.bounds_max[i] - self.bounds_min[i]).max(0.0))
+    }
+}
+
+/// 读取 glTF JSON 中的元数据
+pub fn gc_model_info_from_gltf(gltf: &Value) -> Result<GcModelInfo, String> {
+    if gltf.get("asset").is_none() {
+        return Err("不是 glTF 文件: 缺少 asset".to_string());
+    }
+    let list = |key: &str| gltf.get(key).and_then(Value::as_array).map(Vec::as_slice).unwrap_or_default();
+    let accessors = list("accessors");
+    let count = |index: Option<&Value>| -> usize {
+        index
+            .and_then(Value::as_u64)
+            .and_then(|i| accessors.get(i as usize))
+            .and_then(|a| a.get("count"))
+            .and_then(Value::as_u64)
+            .unwrap_or(0) as usize
+    };
+
+    let mut info = GcModelInfo {
+        meshes: list("meshes").len(),
+        nodes: list("nodes").len(),
+        materials: list("materials").len(),
+        generator: gltf.pointer("/asset/generator").and_then(Value::as_str).map(str::to_string),
+        ..Default::default()
+    };
+    let mut bounds: Option<([f32; 3], [f32; 3])> = None;
+    for primitive in list("meshes").iter().flat_map(|m| m.get("primitives").and_then(Value::as_array).into_iter().flatten()) {
+        let position = primitive.pointer("/attributes/POSITION");
+        let vertices = count(position);
+        info.vertices += vertices;
+        // 只统计三角形列表 (mode 4，默认值)
+        if primitive.get("mode").and_then(Value::as_u64).unwrap_or(4) == 4 {
+            let indices = primitive.get("indices");
+            info.triangles += if indices.is_some() { count(indices) } else { vertices } / 3;
+        }
+
+        let accessor = position.and_then(Value::as_u64).and_then(|i| accessors.get(i as usize));
+        let read = |key: &str| -> Option<[f32; 3]> {
+            let values = accessor?.get(key)?.as_array()?;
+            let mut point = [0.0; 3];
+            for (i, v) in values.iter().take(3).enumerate() {
+                point[i] = v.as_f64()? as f32;
+            }
+            Some(point)
+        };
+        if let (Some(min), Some(max)) = (read("min"), read("max")) {
+            bounds = Some(match bounds {
+                Some((lo, hi)) => ([0, 1, 2].map(|i| lo[i].min(min[i])), [0, 1, 2].map(|i| hi[i].max(max[i]))),
+                None => (min, max),
+            });
+        }
+    }
+    if let Some((min, max)) = bounds {
+        info.bounds_min = min;
+        info.bounds_max = max;
+    }
+
+    info.base_color = list("materials").first().and_then(|m| {
+        let values = m.pointer("/pbrMetallicRoughness/baseColorFactor")?.as_array()?;
+        let mut color = [1.0; 4];
+        for (i, v) in values.iter().take(4).enumerate() {
+            color[i] = v.as_f64()? as f32;
+        }
+        Some(color)
+    });
+    Ok(info)
+}
+
+/// 读取 GLB 或 glTF 文件的元数据
+pub fn gc_parse_model_info(bytes: &[u8]) -> Result<GcModelInfo, String> {
+    let u32_at = |offset: usize| -> Option<u32> {
+        Some(u32::from_le_bytes(bytes.get(offset..offset + 4)?.try_into().ok()?))
+    };
+    let json = if u32_at(0) == Some(GC_GLB_MAGIC) {
+        if u32_at(4) != Some(2) {
+            return Err(format!("不支持的 GLB 版本: {:?}", u32_at(4)));
+        }
+        let length = u32_at(12).ok_or("GLB 缺少 JSON 块")? as usize;
+        if u32_at(16) != Some(GC_GLB_CHUNK_JSON) {
+            return Err("GLB 第一个块不是 JSON".to_string());
+        }
+        bytes.get(20..20 + length).ok_or("GLB JSON 块长度超出文件")?
+    } else {
+        bytes
+    };
+    let gltf: Value = serde_json::from_slice(json).map_err(|e| format!("glTF JSON 解析失败: {}", e))?;
+    gc_model_info_from_gltf(&gltf)
+}
+
+// =============================================================================
+// 模型缩略图
+// =============================================================================
+
+/// 按比例缩放颜色
+fn gc_shade(color: [f32; 4], factor: f32) -> [u8; 4] {
+    let channel = |c: f32| (c * factor).clamp(0.0, 1.0) * 255.0;
+    [channel(color[0]) as u8, channel(color[1]) as u8, channel(color[2]) as u8, 255]
+}
+
+/// 点是否在凸四边形内 (顶点按顺序给出)
+fn gc_in_quad(point: (f32, f32), quad: &[(f32, f32); 4]) -> bool {
+    let mut sign = 0.0f32;
+    for i in 0..4 {
+        let (a, b) = (quad[i], quad[(i + 1) % 4]);
+        let cross = (b.0 - a.0) * (point.1 - a.1) - (b.1 - a.1) * (point.0 - a.0);
+        if cross.abs() < f32::EPSILON {
+            continue;
+        }
+        if sign != 0.0 && cross.signum() != sign {
+            return false;
+        }
+        sign = cross.signum();
+    }
+    true
+}
+
+/// 模型的占位缩略图: 按包围盒比例绘制的斜投影方块 (正面、顶面、侧面三种明暗)
+pub fn gc_model_thumbnail(info: &GcModelInfo, size: u32) -> GcRgbaImage {
+    let size = size.max(8);
+    let mut image = GcRgbaImage::gc_filled(size, size, [0; 4]);
+    let color = info.base_color.unwrap_or(GC_MODEL_DEFAULT_COLOR);
+
+    // 斜投影: 深度方向向右上偏移一半
+    let [w, h, d] = info.gc_size().map(|v| v.max(0.01));
+    let (span_x, span_y) = (w + d * 0.5, h + d * 0.5);
+    let scale = (size as f32 * 0.8) / span_x.max(span_y);
+    let (ox, oy) = ((size as f32 - span_x * scale) / 2.0, (size as f32 + span_y * scale) / 2.0);
+    let project = |x: f32, y: f32, z: f32| (ox + (x + z * 0.5) * scale, oy - (y + z * 0.5) * scale);
+
+    let front = [project(0.0, 0.0, 0.0), project(w, 0.0, 0.0), project(w, h, 0.0), project(0.0, h, 0.0)];
+    let top = [project(0.0, h, 0.0), project(w, h, 0.0), project(w, h, d), project(0.0, h, d)];
+    let side = [project(w, 0.0, 0.0), project(w, 0.0, d), project(w, h, d), project(w, h, 0.0)];
+    let faces = [(front, gc_shade(color, 1.0)), (top, gc_shade(color, 1.25)), (side, gc_shade(color, 0.7))];
+
+    for y in 0..size {
+        for x in 0..size {
+            let point = (x as f32 + 0.5, y as f32 + 0.5);
+            if let Some((_, shade)) = faces.iter().find(|(quad, _)| gc_in_quad(point, quad)) {
+                image.gc_set_pixel(x, y, *shade);
+            }
+        }
+    }
+    image
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn sample_gltf() -> Value {
+        json!({
+            "asset": { "version": "2.0", "generator": "Blender" },
+            "nodes": [{ "mesh": 0 }, { "mesh": 1 }],
+            "meshes": [
+                { "primitives": [{ "attributes": { "POSITION": 0 }, "indices": 1 }] },
+                { "primitives": [{ "attributes": { "POSITION": 2 } }] }
+            ],
+            "materials": [{ "pbrMetallicRoughness": { "baseColorFactor": [0.2, 0.6, 0.2, 1.0] } }],
+            "accessors": [
+                { "count": 24, "min": [-1.0, 0.0, -0.5], "max": [1.0, 3.0, 0.5] },
+                { "count": 36 },
+                { "count": 6, "min": [-2.0, 0.0, 0.0], "max": [0.0, 1.0, 1.0] }
+            ]
+        })
+    }
+
+    /// 把 glTF JSON 打包为 GLB (只有 JSON 块)
+    fn pack_glb(gltf: &Value) -> Vec<u8> {
+        let mut json = serde_json::to_vec(gltf).unwrap_or_default();
+        while json.len() % 4 != 0 {
+            json.push(b' ');
+        }
+        let mut glb = Vec::new();
+        glb.extend_from_slice(&GC_GLB_MAGIC.to_le_bytes());
+        glb.extend_from_slice(&2u32.to_le_bytes());
+        glb.extend_from_slice(&((20 + json.len()) as u32).to_le_bytes());
+        glb.extend_from_slice(&(json.len() as u32).to_le_bytes());
+        glb.extend_from_slice(&GC_GLB_CHUNK_JSON.to_le_bytes());
+        glb.extend_from_slice(&json);
+        glb
+    }
+
+    #[test]
+    fn test_parse_model_info_from_glb_and_gltf() {
+        let info = gc_parse_model_info(&pack_glb(&sample_gltf())).expect("GLB 应可解析");
+        assert_eq!((info.meshes, info.nodes, info.materials), (2, 2, 1));
+        assert_eq!((info.vertices, info.triangles), (30, 14));
+        assert_eq!(info.bounds_min, [-2.0, 0.0, -0.5]);
+        assert_eq!(info.gc_size(), [3.0, 3.0, 1.5]);
+        assert_eq!(info.base_color, Some([0.2, 0.6, 0.2, 1.0]));
+        assert_eq!(info.generator.as_deref(), Some("Blender"));
+
+        let text = serde_json::to_vec(&sample_gltf()).unwrap_or_default();
+        assert_eq!(gc_parse_model_info(&text), Ok(info));
+        assert!(gc_parse_model_info(b"not a model").is_err());
+        assert!(gc_parse_model_info(&pack_glb(&json!({ "meshes": [] }))).is_err());
+    }
+
+    #[test]
+    fn test_thumbnail_keeps_aspect_and_averages() {
+        // 左半红、右半透明的 400x200 图片
+        let mut pixels = Vec::new();
+        for _ in 0..200 {
+            for x in 0..400 {
+                pixels.extend_from_slice(if x < 200 { &[255, 0, 0, 255] } else { &[0, 0, 255, 0] });
+            }
+        }
+        let image = GcRgbaImage::gc_from_pixels(400, 200, pixels).expect("尺寸一致");
+        let thumb = image.gc_thumbnail(GC_THUMBNAIL_SIZE);
+        assert_eq!((thumb.width, thumb.height), (128, 64));
+        assert_eq!(thumb.gc_pixel(10, 10), [255, 0, 0, 255]);
+        assert_eq!(thumb.gc_pixel(120, 10)[3], 0);
+        // 边界像素一半覆盖: 颜色不混入透明像素的蓝色
+        let edge = thumb.gc_pixel(63, 10);
+        assert_eq!(&edge[..3], &[255, 0, 0]);
+
+        let small = GcRgbaImage::gc_filled(16, 8, [1, 2, 3, 4]);
+        assert_eq!(small.gc_thumbnail(GC_THUMBNAIL_SIZE), small);
+        assert!(GcRgbaImage::gc_from_pixels(2, 2, vec![0; 3]).is_none());
+    }
+
+    #[test]
+    fn test_model_thumbnail_draws_shaded_box() {
+        let info = gc_parse_model_info(&serde_json::to_vec(&sample_gltf()).unwrap_or_default()).expect("可解析");
+        let thumb = gc_model_thumbnail(&info, 64);
+        assert_eq!((thumb.width, thumb.height), (64, 64));
+        // 角落透明，中心为正面颜色
+        assert_eq!(thumb.gc_pixel(0, 0)[3], 0);
+        let center = thumb.gc_pixel(28, 36);
+        assert_eq!(center[3], 255);
+        assert!(center[1] > center[0]);
+        let opaque = thumb.pixels.chunks(4).filter(|p| p[3] == 255).count();
+        assert!(opaque > 64 * 64 / 4);
+    }
+}
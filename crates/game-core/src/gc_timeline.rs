@@ -0,0 +1,224 @@
+//! 战斗时间线 (调试面板的时间回溯)
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! ## 时间线规则
+//! - 每一步记录一个领域事件 (发生了什么) 和该事件之后的快照 (确切状态)
+//! - 快照复用 `GcBattleSnapshot` 的组件共享，未变化的玩家、卡池不会重复保存
+//! - 位置 `index` 指第 index 个事件之后的状态，0 为初始状态
+//! - 游标可任意前后移动，只查看不修改；新事件总是追加在最新一步之后
+//! - 回溯 (`gc_rewind`) 让战斗从游标位置继续，丢弃游标之后的步骤
+//! - 超过容量时丢弃最早的一步 (容量 0 表示不限)
+
+use serde::Serialize;
+
+use crate::{gc_apply_battle_event, GcBattleEvent, GcBattleSnapshot, GcBattleState, GcError, GcEventSourcedBattle};
+
+// =============================================================================
+// 帧
+// =============================================================================
+
+/// 时间线上的一步
+#[derive(Clone, Debug)]
+struct GcTimelineEntry {
+    event: GcBattleEvent,
+    snapshot: GcBattleSnapshot,
+}
+
+/// 某个位置的完整信息 (调试面板显示、导出)
+#[derive(Clone, Debug, Serialize)]
+pub struct GcTimelineFrame {
+    /// 位置
+    pub index: usize,
+    /// 时间线总步数
+    pub len: usize,
+    /// 到达该位置的事件
+    pub event: GcBattleEvent,
+    /// 该位置的确切状态
+    pub state: GcBattleState,
+}
+
+// =============================================================================
+// 时间线
+// =============================================================================
+
+/// 战斗时间线
+#[derive(Clone, Debug, Default)]
+pub struct GcBattleTimeline {
+    /// 步骤 (从旧到新)
+    entries: Vec<GcTimelineEntry>,
+    /// 当前查看的位置
+    cursor: usize,
+    /// 最多保留的步数 (0 表示不限)
+    capacity: usize,
+}
+
+impl GcBattleTimeline {
+    /// 创建空时间线 (capacity 为 0 表示不限数量)
+    pub fn gc_new(capacity: usize) -> Self {
+        Self {
+            entries: Vec::new(),
+            cursor: 0,
+            capacity,
+        }
+    }
+
+    /// 从事件列表构建 (依次折叠，每个事件之后记录快照)
+    pub fn gc_from_events(events: &[GcBattleEvent]) -> Result<Self, GcError> {
+        let mut timeline = Self::gc_new(0);
+        let mut state = match events.first() {
+            Some(GcBattleEvent::Genesis { state }) => (**state).clone(),
+            Some(_) => return Err(GcError::GcInvalidAction("第一个事件必须是初始快照".to_string())),
+            None => return Err(GcError::GcBattleNotStarted),
+        };
+        timeline.gc_record(events[0].clone(), &state);
+        for event in &events[1..] {
+            gc_apply_battle_event(&mut state, event)?;
+            timeline.gc_record(event.clone(), &state);
+        }
+        Ok(timeline)
+    }
+
+    /// 从事件溯源战斗构建
+    pub fn gc_from_battle(battle: &GcEventSourcedBattle) -> Result<Self, GcError> {
+        Self::gc_from_events(battle.gc_events())
+    }
+
+    /// 步数
+    pub fn gc_len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// 是否为空
+    pub fn gc_is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// 当前查看的位置
+    pub fn gc_cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// 记录事件及其之后的状态 (追加到最新一步之后，游标移到最新)
+    pub fn gc_record(&mut self, event: GcBattleEvent, state: &GcBattleState) {
+        let snapshot = GcBattleSnapshot::gc_capture(state, self.entries.last().map(|e| &e.snapshot));
+        self.entries.push(GcTimelineEntry { event, snapshot });
+        if self.capacity > 0 && self.entries.len() > self.capacity {
+            self.entries.remove(0);
+        }
+        self.cursor = self.entries.len() - 1;
+    }
+
+    /// 指定位置的事件
+    pub fn gc_event_at(&self, index: usize) -> Option<&GcBattleEvent> {
+        self.entries.get(index).map(|e| &e.event)
+    }
+
+    /// 指定位置的状态
+    pub fn gc_state_at(&self, index: usize) -> Option<GcBattleState> {
+        self.entries.get(index).map(|e| e.snapshot.gc_restore())
+    }
+
+    /// 指定位置的完整信息 (不移动游标)
+    pub fn gc_frame(&self, index: usize) -> Option<GcTimelineFrame> {
+        let entry = self.entries.get(index)?;
+        Some(GcTimelineFrame {
+            index,
+            len: self.entries.len(),
+            event: entry.event.clone(),
+            state: entry.snapshot.gc_restore(),
+        })
+    }
+
+    /// 移动游标到指定位置 (超出范围时停在最新一步)
+    pub fn gc_seek(&mut self, index: usize) -> Option<GcTimelineFrame> {
+        if self.entries.is_empty() {
+            return None;
+        }
+        self.cursor = index.min(self.entries.len() - 1);
+        self.gc_frame(self.cursor)
+    }
+
+    /// 游标前后移动若干步 (负数后退，停在两端)
+    pub fn gc_step(&mut self, delta: i64) -> Option<GcTimelineFrame> {
+        let target = (self.cursor as i64).saturating_add(delta).max(0) as usize;
+        self.gc_seek(target)
+    }
+
+    /// 回溯到游标位置: 丢弃之后的步骤，返回该位置的状态
+    pub fn gc_rewind(&mut self) -> Option<GcBattleState> {
+        let state = self.gc_state_at(self.cursor)?;
+        self.entries.truncate(self.cursor + 1);
+        Some(state)
+    }
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GcCard, GcPlayer};
+
+    fn create_battle() -> GcEventSourcedBattle {
+        let mut players = vec![GcPlayer::gc_new("p1", "玩家1"), GcPlayer::gc_new("p2", "玩家2")];
+        players[0].stats.energy = 10;
+        players[0].hand.push(GcCard::gc_new_attack("a1", "打击", 1, 10));
+        players[0].hand.push(GcCard::gc_new_attack("a2", "重击", 2, 20));
+        let mut battle = GcEventSourcedBattle::gc_new("b1", players);
+        battle.gc_play_card("p1", "a1", "p2");
+        battle.gc_play_card("p1", "a2", "p2");
+        battle
+    }
+
+    #[test]
+    fn test_seek_matches_event_replay() {
+        let battle = create_battle();
+        let mut timeline = GcBattleTimeline::gc_from_battle(&battle).unwrap_or_default();
+        assert_eq!(timeline.gc_len(), 3);
+        assert_eq!(timeline.gc_cursor(), 2);
+
+        for index in 0..timeline.gc_len() {
+            let expected = battle.gc_state_at(index + 1).map(|s| s.players[1].stats.hp).ok();
+            let frame = timeline.gc_seek(index);
+            assert_eq!(frame.map(|f| f.state.players[1].stats.hp), expected);
+        }
+        assert!(matches!(timeline.gc_event_at(0), Some(GcBattleEvent::Genesis { .. })));
+    }
+
+    #[test]
+    fn test_step_and_branch() {
+        let battle = create_battle();
+        let mut timeline = GcBattleTimeline::gc_from_battle(&battle).unwrap_or_default();
+
+        assert_eq!(timeline.gc_step(-1).map(|f| f.index), Some(1));
+        assert_eq!(timeline.gc_step(-5).map(|f| f.index), Some(0));
+        assert_eq!(timeline.gc_step(9).map(|f| f.index), Some(2));
+
+        // 只查看时新事件追加在末尾
+        timeline.gc_seek(1);
+        let state = battle.gc_state().clone();
+        timeline.gc_record(GcBattleEvent::TurnAdvanced, &state);
+        assert_eq!(timeline.gc_len(), 4);
+        assert_eq!(timeline.gc_cursor(), 3);
+
+        // 回溯到第 1 步后继续，丢弃原来的后续步骤
+        timeline.gc_seek(1);
+        let rewound = timeline.gc_rewind().map(|s| s.players[1].stats.hp);
+        assert_eq!(rewound, battle.gc_state_at(2).map(|s| s.players[1].stats.hp).ok());
+        timeline.gc_record(GcBattleEvent::TurnAdvanced, &state);
+        assert_eq!(timeline.gc_len(), 3);
+        assert!(matches!(timeline.gc_event_at(2), Some(GcBattleEvent::TurnAdvanced)));
+
+        let mut bounded = GcBattleTimeline::gc_new(2);
+        for _ in 0..3 {
+            bounded.gc_record(GcBattleEvent::TurnAdvanced, &state);
+        }
+        assert_eq!(bounded.gc_len(), 2);
+        assert!(GcBattleTimeline::gc_from_events(&[]).is_err());
+    }
+}
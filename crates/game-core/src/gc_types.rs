@@ -6,6 +6,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::{GcDamageType, GcResistances};
+
 // =============================================================================
 // 存档版本系统
 // =============================================================================
@@ -153,7 +155,7 @@ impl GcStatType {
 }
 
 /// 基础属性集合
-#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
 pub struct GcBaseStats {
     pub strength: u32,
     pub agility: u32,
@@ -181,7 +183,7 @@ impl GcBaseStats {
 }
 
 /// 战斗属性集合
-#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
 pub struct GcCombatStats {
     pub attack: u32,
     pub defense: u32,
@@ -197,6 +199,9 @@ pub struct GcCombatStats {
     pub magic_defense: u32,
     pub healing_bonus: u32,
     pub cooldown_reduction: u32,
+    /// 伤害类型抗性 (%)
+    #[serde(default)]
+    pub resistances: GcResistances,
 }
 
 impl GcCombatStats {
@@ -215,6 +220,7 @@ impl GcCombatStats {
         self.magic_defense += other.magic_defense;
         self.healing_bonus += other.healing_bonus;
         self.cooldown_reduction += other.cooldown_reduction;
+        self.resistances.gc_merge(&other.resistances);
     }
 
     pub fn gc_from_base_stats(base: &GcBaseStats, _level: u32) -> Self {
@@ -241,7 +247,7 @@ impl GcCombatStats {
 // =============================================================================
 
 /// 伤害计算结果
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct GcDamageResult {
     /// 原始伤害 (攻击力 + 卡牌伤害)
     pub raw_damage: u32,
@@ -254,6 +260,18 @@ pub struct GcDamageResult {
     
     /// 是否暴击
     pub is_critical: bool,
+
+    /// 伤害类型
+    #[serde(default)]
+    pub damage_type: GcDamageType,
+
+    /// 防御减免部分
+    #[serde(default)]
+    pub defense_reduction: u32,
+
+    /// 目标抗性 (%，负数为易伤)
+    #[serde(default)]
+    pub resistance_percent: i32,
 }
 
 impl GcDamageResult {
@@ -264,39 +282,96 @@ impl GcDamageResult {
             reduced_damage: reduced,
             final_damage: final_dmg,
             is_critical: false,
+            damage_type: GcDamageType::Physical,
+            defense_reduction: reduced,
+            resistance_percent: 0,
         }
     }
+
+    /// 战斗日志用的伤害明细 (如 "50 火焰伤害 - 防御 10 - 抗性 25% = 30")
+    pub fn gc_breakdown(&self) -> String {
+        let resist = match self.resistance_percent {
+            p if p > 0 => format!(" - 抗性 {}%", p),
+            p if p < 0 => format!(" + 易伤 {}%", -p),
+            _ => String::new(),
+        };
+        format!(
+            "{} {}伤害 - 防御 {}{} = {}",
+            self.raw_damage,
+            self.damage_type.gc_name(),
+            self.defense_reduction,
+            resist,
+            self.final_damage
+        )
+    }
 }
 
 // =============================================================================
 // 游戏操作
 // =============================================================================
 
-/// 游戏操作类型
-#[derive(Clone, Debug, Serialize, Deserialize)]
+/// 游戏操作类型 (旧版 snake_case 标签如 "play_card" 仍可解析)
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(tag = "type")]
 pub enum GcAction {
     /// 出牌
-    #[serde(rename = "play_card")]
+    #[serde(alias = "play_card")]
     PlayCard {
         player_id: GcPlayerId,
         card_id: GcCardId,
         target_id: GcPlayerId,
     },
     
+    /// 使用英雄技能
+    #[serde(alias = "use_skill")]
+    UseSkill {
+        player_id: GcPlayerId,
+        skill_id: String,
+        target_id: GcPlayerId,
+    },
+    
+    /// 部署卡牌到战场
+    #[serde(alias = "deploy_card")]
+    DeployCard {
+        player_id: GcPlayerId,
+        card_id: GcCardId,
+        slot_index: usize,
+    },
+    
+    /// 从公共卡池获取卡牌
+    #[serde(alias = "acquire_card")]
+    AcquireCard {
+        player_id: GcPlayerId,
+        card_id: GcCardId,
+    },
+    
     /// 结束回合
-    #[serde(rename = "end_turn")]
+    #[serde(alias = "end_turn")]
     EndTurn {
         player_id: GcPlayerId,
     },
     
     /// 投降
-    #[serde(rename = "surrender")]
+    #[serde(alias = "surrender")]
     Surrender {
         player_id: GcPlayerId,
     },
 }
 
+impl GcAction {
+    /// 执行操作的玩家
+    pub fn gc_player_id(&self) -> &str {
+        match self {
+            Self::PlayCard { player_id, .. }
+            | Self::UseSkill { player_id, .. }
+            | Self::DeployCard { player_id, .. }
+            | Self::AcquireCard { player_id, .. }
+            | Self::EndTurn { player_id }
+            | Self::Surrender { player_id } => player_id,
+        }
+    }
+}
+
 // =============================================================================
 // 测试
 // =============================================================================
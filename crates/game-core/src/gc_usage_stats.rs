@@ -0,0 +1,352 @@
+//! 卡牌/单位使用统计 (平衡性看板与客户端热门展示)
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! 对局结束时为每名玩家生成一条匿名使用样本，汇总任务把样本累计为各段位的选取率与胜率
+//!
+//! ## 规则
+//! - 样本不含玩家 ID: 只有类型 (卡牌/单位)、段位、是否获胜与使用过的模板 (去重)
+//! - 卡牌对战: 样本为对局中打出或部署过的卡牌模板；胜者及其队友为获胜
+//! - 酒馆: 样本为最终阵容中的怪兽模板；名次在前一半 (含) 为获胜
+//! - 段位按角色等级划分: 1-9 青铜、10-19 白银、20-29 黄金、30 以上钻石
+//! - 选取率 = 选取次数 / 该段位的样本数；胜率 = 获胜次数 / 选取次数；未指定段位时合并全部段位
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{gc_ghost_units, GcBattleEvent, GcCard, GcEventSourcedBattle, GcTavernGame};
+
+/// 统计列表单次最多返回条数
+pub const GC_USAGE_LIST_MAX: usize = 100;
+
+// =============================================================================
+// 段位与样本
+// =============================================================================
+
+/// 段位
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum GcRankBracket {
+    /// 青铜 (1-9 级)
+    #[default]
+    Bronze,
+    /// 白银 (10-19 级)
+    Silver,
+    /// 黄金 (20-29 级)
+    Gold,
+    /// 钻石 (30 级以上)
+    Diamond,
+}
+
+impl GcRankBracket {
+    /// 按角色等级划分段位
+    pub fn gc_from_level(level: u32) -> Self {
+        match level {
+            0..=9 => Self::Bronze,
+            10..=19 => Self::Silver,
+            20..=29 => Self::Gold,
+            _ => Self::Diamond,
+        }
+    }
+}
+
+/// 统计类型
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum GcUsageKind {
+    /// 卡牌 (卡牌对战)
+    Card,
+    /// 单位 (酒馆怪兽)
+    Unit,
+}
+
+/// 一名玩家一局的使用样本 (匿名)
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcUsageSample {
+    /// 统计类型
+    pub kind: GcUsageKind,
+    /// 段位
+    pub bracket: GcRankBracket,
+    /// 是否获胜
+    pub won: bool,
+    /// 使用过的模板 ID (去重)
+    pub items: Vec<String>,
+}
+
+/// 卡牌对战的使用样本 (对局须已结束)
+///
+/// - `bracket_of`: 玩家 ID -> 段位
+pub fn gc_card_usage_samples(
+    battle: &GcEventSourcedBattle,
+    bracket_of: impl Fn(&str) -> GcRankBracket,
+) -> Vec<GcUsageSample> {
+    let state = battle.gc_state();
+    if !state.gc_is_finished() {
+        return Vec::new();
+    }
+
+    // 卡牌实例 -> 模板 (取自初始快照、卡池与发放的卡牌)
+    let mut templates: HashMap<&str, &str> = HashMap::new();
+    let mut played: HashMap<&str, Vec<&str>> = HashMap::new();
+    for event in battle.gc_events() {
+        match event {
+            GcBattleEvent::Genesis { state } => {
+                gc_remember_templates(&mut templates, state.players.iter().flat_map(|p| p.hand.iter().chain(&p.deck)));
+                gc_remember_templates(&mut templates, state.card_pool.display.iter().chain(&state.card_pool.draw_pile));
+            }
+            GcBattleEvent::CardAcquired { card_pool, .. } | GcBattleEvent::PoolRefreshed { card_pool, .. } => {
+                gc_remember_templates(&mut templates, card_pool.display.iter().chain(&card_pool.draw_pile));
+            }
+            GcBattleEvent::CardGiven { card, .. } => gc_remember_templates(&mut templates, std::iter::once(card.as_ref())),
+            GcBattleEvent::CardPlayed { player_id, card_id, .. } | GcBattleEvent::CardDeployed { player_id, card_id, .. } => {
+                played.entry(player_id.as_str()).or_default().push(card_id.as_str());
+            }
+            _ => {}
+        }
+    }
+
+    state.players.iter()
+        .map(|player| {
+            let mut items: Vec<String> = played.get(player.id.as_str())
+                .into_iter()
+                .flatten()
+                .filter_map(|card_id| templates.get(card_id).map(|t| t.to_string()))
+                .collect();
+            items.sort();
+            items.dedup();
+            let won = state.winner_id.as_deref()
+                .is_some_and(|w| w == player.id || state.gc_are_allies(w, &player.id));
+            GcUsageSample { kind: GcUsageKind::Card, bracket: bracket_of(&player.id), won, items }
+        })
+        .collect()
+}
+
+/// 记录卡牌实例对应的模板
+fn gc_remember_templates<'a>(templates: &mut HashMap<&'a str, &'a str>, cards: impl Iterator<Item = &'a GcCard>) {
+    for card in cards {
+        templates.insert(card.id.as_str(), card.template_id.as_str());
+    }
+}
+
+/// 酒馆对局的使用样本 (只含已确定名次的座位)
+///
+/// - `bracket_of`: 玩家 ID -> 段位
+pub fn gc_unit_usage_samples(game: &GcTavernGame, bracket_of: impl Fn(&str) -> GcRankBracket) -> Vec<GcUsageSample> {
+    let top_half = game.seats.len().div_ceil(2);
+    game.seats.iter()
+        .filter_map(|seat| {
+            let placement = seat.placement?;
+            let mut items: Vec<String> = gc_ghost_units(&seat.board).into_iter().map(|u| u.template_id).collect();
+            items.sort();
+            items.dedup();
+            Some(GcUsageSample {
+                kind: GcUsageKind::Unit,
+                bracket: bracket_of(&seat.player.id),
+                won: (placement as usize) <= top_half,
+                items,
+            })
+        })
+        .collect()
+}
+
+// =============================================================================
+// 汇总
+// =============================================================================
+
+/// 选取与获胜次数
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcUsageCounter {
+    /// 选取次数
+    pub picks: u64,
+    /// 获胜次数
+    pub wins: u64,
+}
+
+/// 一类统计的汇总表
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcUsageTable {
+    /// 各段位样本数
+    #[serde(default)]
+    pub games: BTreeMap<GcRankBracket, u64>,
+    /// 模板 ID -> 段位 -> 次数
+    #[serde(default)]
+    pub items: BTreeMap<String, BTreeMap<GcRankBracket, GcUsageCounter>>,
+}
+
+/// 选取率与胜率
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GcUsageRate {
+    /// 模板 ID
+    pub id: String,
+    /// 选取次数
+    pub picks: u64,
+    /// 获胜次数
+    pub wins: u64,
+    /// 选取率 (0-1)
+    pub pick_rate: f64,
+    /// 胜率 (0-1)
+    pub win_rate: f64,
+}
+
+impl GcUsageTable {
+    /// 累计一条样本
+    pub fn gc_add(&mut self, sample: &GcUsageSample) {
+        *self.games.entry(sample.bracket).or_default() += 1;
+        for item in &sample.items {
+            let counter = self.items.entry(item.clone()).or_default().entry(sample.bracket).or_default();
+            counter.picks += 1;
+            counter.wins += u64::from(sample.won);
+        }
+    }
+
+    /// 段位样本数 (None 为全部段位)
+    pub fn gc_games(&self, bracket: Option<GcRankBracket>) -> u64 {
+        self.games.iter()
+            .filter(|(b, _)| bracket.is_none_or(|wanted| **b == wanted))
+            .map(|(_, n)| n)
+            .sum()
+    }
+
+    /// 各模板的选取率与胜率 (选取次数降序，同次数按 ID)
+    ///
+    /// - `bracket`: 只统计该段位 (None 为全部段位)
+    pub fn gc_rates(&self, bracket: Option<GcRankBracket>, limit: usize) -> Vec<GcUsageRate> {
+        let in_bracket = |b: &GcRankBracket| bracket.is_none_or(|wanted| *b == wanted);
+        let games = self.gc_games(bracket);
+
+        let mut rates: Vec<GcUsageRate> = self.items.iter()
+            .filter_map(|(id, counters)| {
+                let total = counters.iter()
+                    .filter(|(b, _)| in_bracket(b))
+                    .fold(GcUsageCounter::default(), |sum, (_, c)| GcUsageCounter {
+                        picks: sum.picks + c.picks,
+                        wins: sum.wins + c.wins,
+                    });
+                (total.picks > 0).then(|| GcUsageRate {
+                    id: id.clone(),
+                    picks: total.picks,
+                    wins: total.wins,
+                    pick_rate: total.picks as f64 / games.max(1) as f64,
+                    win_rate: total.wins as f64 / total.picks as f64,
+                })
+            })
+            .collect();
+        rates.sort_by(|a, b| b.picks.cmp(&a.picks).then_with(|| a.id.cmp(&b.id)));
+        rates.truncate(limit.min(GC_USAGE_LIST_MAX));
+        rates
+    }
+}
+
+/// 全部使用统计
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcUsageStats {
+    /// 卡牌
+    #[serde(default)]
+    pub cards: GcUsageTable,
+    /// 单位
+    #[serde(default)]
+    pub units: GcUsageTable,
+    /// 已处理的样本数
+    #[serde(default)]
+    pub samples: u64,
+}
+
+impl GcUsageStats {
+    /// 累计一条样本
+    pub fn gc_add(&mut self, sample: &GcUsageSample) {
+        match sample.kind {
+            GcUsageKind::Card => self.cards.gc_add(sample),
+            GcUsageKind::Unit => self.units.gc_add(sample),
+        }
+        self.samples += 1;
+    }
+
+    /// 某类统计的汇总表
+    pub fn gc_table(&self, kind: GcUsageKind) -> &GcUsageTable {
+        match kind {
+            GcUsageKind::Card => &self.cards,
+            GcUsageKind::Unit => &self.units,
+        }
+    }
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GcBattlePhase, GcMonster, GcMonsterAttribute, GcPlayer, GcProfessionType};
+
+    fn sample(bracket: GcRankBracket, won: bool, items: &[&str]) -> GcUsageSample {
+        GcUsageSample { kind: GcUsageKind::Card, bracket, won, items: items.iter().map(|s| s.to_string()).collect() }
+    }
+
+    #[test]
+    fn test_rates_by_bracket() {
+        assert_eq!(GcRankBracket::gc_from_level(1), GcRankBracket::Bronze);
+        assert_eq!(GcRankBracket::gc_from_level(25), GcRankBracket::Gold);
+        assert_eq!(GcRankBracket::gc_from_level(99), GcRankBracket::Diamond);
+
+        let mut stats = GcUsageStats::default();
+        stats.gc_add(&sample(GcRankBracket::Bronze, true, &["fireball", "shield"]));
+        stats.gc_add(&sample(GcRankBracket::Bronze, false, &["fireball"]));
+        stats.gc_add(&sample(GcRankBracket::Gold, true, &["shield"]));
+        stats.gc_add(&sample(GcRankBracket::Gold, false, &[]));
+        assert_eq!(stats.samples, 4);
+
+        let all = stats.cards.gc_rates(None, 10);
+        assert_eq!(all.iter().map(|r| r.id.as_str()).collect::<Vec<_>>(), vec!["fireball", "shield"]);
+        assert_eq!((all[1].picks, all[1].wins), (2, 2));
+        assert!((all[0].pick_rate - 0.5).abs() < 1e-9 && (all[0].win_rate - 0.5).abs() < 1e-9);
+
+        let gold = stats.cards.gc_rates(Some(GcRankBracket::Gold), 10);
+        assert_eq!(gold.len(), 1);
+        assert!((gold[0].pick_rate - 0.5).abs() < 1e-9);
+        assert!(stats.cards.gc_rates(Some(GcRankBracket::Diamond), 10).is_empty());
+        assert_eq!(stats.cards.gc_rates(None, 1).len(), 1);
+        assert!(stats.units.items.is_empty());
+
+        // 汇总结果可序列化保存
+        let json = serde_json::to_string(&stats).expect("序列化");
+        assert_eq!(serde_json::from_str::<GcUsageStats>(&json).expect("反序列化"), stats);
+    }
+
+    #[test]
+    fn test_samples_from_finished_matches() {
+        let mut p1 = GcPlayer::gc_new("p1", "玩家1");
+        p1.gc_init_rpg(GcProfessionType::Warlock);
+        p1.hand.push(GcCard::gc_new_attack("c1", "终结", 1, 500));
+        let mut battle = GcEventSourcedBattle::gc_new("usage", vec![p1, GcPlayer::gc_new("p2", "玩家2")]);
+        battle.gc_record(GcBattleEvent::PhaseChanged { phase: GcBattlePhase::Playing }).expect("开始对局");
+        assert!(battle.gc_play_card("p1", "c1", "p2").success);
+
+        let template = battle.gc_events().iter().find_map(|e| match e {
+            GcBattleEvent::Genesis { state } => state.players[0].hand.first().map(|c| c.template_id.clone()),
+            _ => None,
+        });
+        let samples = gc_card_usage_samples(&battle, |id| if id == "p1" { GcRankBracket::Gold } else { GcRankBracket::Bronze });
+        assert_eq!(samples.len(), 2);
+        assert_eq!((samples[0].bracket, samples[0].won), (GcRankBracket::Gold, true));
+        assert_eq!(samples[0].items, template.into_iter().collect::<Vec<_>>());
+        assert!(!samples[1].won && samples[1].items.is_empty());
+
+        let mut game = GcTavernGame::gc_new(&[
+            ("p1".to_string(), "玩家1".to_string()),
+            ("p2".to_string(), "玩家2".to_string()),
+            ("p3".to_string(), "玩家3".to_string()),
+        ]);
+        let slime = |id: &str| GcMonster::new_with_template(id, "slime", "史莱姆", 1, GcMonsterAttribute::Water, 50, 30, 60);
+        for (i, seat) in game.seats.iter_mut().enumerate() {
+            seat.placement = Some(i as u8 + 1);
+            seat.board.player_monsters[0] = Some(slime("a"));
+            seat.board.player_monsters[1] = Some(slime("b"));
+        }
+        game.seats[2].placement = None;
+        let samples = gc_unit_usage_samples(&game, |_| GcRankBracket::Bronze);
+        assert_eq!(samples.iter().map(|s| s.won).collect::<Vec<_>>(), vec![true, true]);
+        assert_eq!(samples[0].items, vec!["slime".to_string()]);
+    }
+}
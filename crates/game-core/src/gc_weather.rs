@@ -0,0 +1,216 @@
+//! 区域天气 (随游戏时间演变的晴/雨/暴风雨/雪)
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! ## 规则
+//! - 天气按区域 (地图) 计算，气候由区域的大世界地形决定 (雪原多雪、沙漠少雨、洞穴恒晴)
+//! - 每 6 个游戏小时为一个天气周期，周期开始时按区域与周期的哈希决定是否转变天气
+//! - 天气只由世界时钟、区域 ID 与气候推算，服务器与客户端同输入同结果
+//! - 天气影响玩法: 遭遇等级加成、刷怪区域的天气怪物表、战斗地形权重与遭遇种子
+
+use serde::{Deserialize, Serialize};
+
+use crate::{GcWorldClock, GcWorldTerrainType};
+
+/// 天气周期长度 (游戏秒)
+pub const GC_WEATHER_PERIOD_SECS: u64 = 6 * 3600;
+
+/// 每个周期转变天气的概率 (百分比)
+pub const GC_WEATHER_CHANGE_PERCENT: u64 = 45;
+
+/// 向前查找天气转变的最大周期数 (超过后视为在最早周期转变)
+const GC_WEATHER_LOOKBACK: u64 = 8;
+
+// =============================================================================
+// 天气
+// =============================================================================
+
+/// 天气
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum GcWeather {
+    /// 晴
+    #[default]
+    Clear,
+    /// 雨
+    Rain,
+    /// 暴风雨
+    Storm,
+    /// 雪
+    Snow,
+}
+
+impl GcWeather {
+    /// 全部天气
+    pub const ALL: [GcWeather; 4] = [Self::Clear, Self::Rain, Self::Storm, Self::Snow];
+
+    /// 显示名称
+    pub fn gc_name(&self) -> &'static str {
+        match self {
+            Self::Clear => "晴",
+            Self::Rain => "雨",
+            Self::Storm => "暴风雨",
+            Self::Snow => "雪",
+        }
+    }
+
+    /// 遭遇敌人等级加成 (暴风雨中怪物更强)
+    pub fn gc_encounter_level_bonus(&self) -> u32 {
+        match self {
+            Self::Storm => 1,
+            _ => 0,
+        }
+    }
+
+    /// 混入天气的遭遇种子 (晴天不变，保持与不考虑天气时相同的结果)
+    pub fn gc_mix_seed(&self, seed: u64) -> u64 {
+        match self {
+            Self::Clear => seed,
+            other => seed ^ (*other as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15),
+        }
+    }
+}
+
+/// 气候的天气权重 (按 `GcWeather::ALL` 顺序: 晴/雨/暴风雨/雪)
+pub fn gc_weather_weights(climate: GcWorldTerrainType) -> [u32; 4] {
+    use GcWorldTerrainType::*;
+
+    match climate {
+        Grassland => [55, 30, 10, 5],
+        Desert => [85, 5, 10, 0],
+        Snowfield => [30, 0, 10, 60],
+        Waterside => [40, 40, 20, 0],
+        Highland => [45, 20, 15, 20],
+        Woodland => [45, 40, 10, 5],
+        Cave => [100, 0, 0, 0],
+        Ruins => [50, 25, 20, 5],
+    }
+}
+
+/// 区域与周期的哈希 (FNV-1a，跨平台稳定)
+fn gc_weather_hash(region_id: &str, period: u64, salt: u8) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    let bytes = region_id.bytes().chain(period.to_le_bytes()).chain([salt]);
+    for byte in bytes {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    hash
+}
+
+/// 区域在某个天气周期的天气
+///
+/// 从该周期往前找最近一次转变天气的周期，天气在两次转变之间保持不变。
+pub fn gc_weather_for_period(region_id: &str, climate: GcWorldTerrainType, period: u64) -> GcWeather {
+    let changed_at = (0..GC_WEATHER_LOOKBACK)
+        .map(|back| period.saturating_sub(back))
+        .find(|p| gc_weather_hash(region_id, *p, 0) % 100 < GC_WEATHER_CHANGE_PERCENT)
+        .unwrap_or_else(|| period.saturating_sub(GC_WEATHER_LOOKBACK));
+
+    let weights = gc_weather_weights(climate);
+    let total: u32 = weights.iter().sum();
+    if total == 0 {
+        return GcWeather::Clear;
+    }
+    let mut roll = (gc_weather_hash(region_id, changed_at, 1) % total as u64) as u32;
+    for (weather, weight) in GcWeather::ALL.into_iter().zip(weights) {
+        if roll < weight {
+            return weather;
+        }
+        roll -= weight;
+    }
+    GcWeather::Clear
+}
+
+// =============================================================================
+// 区域天气
+// =============================================================================
+
+/// 天气区域 (一张地图及其气候)
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcWeatherRegion {
+    /// 区域 ID (地图 ID)
+    pub id: String,
+    /// 气候 (大世界地形)
+    #[serde(default)]
+    pub climate: GcWorldTerrainType,
+}
+
+/// 区域在某一时刻的天气
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcRegionWeather {
+    /// 区域 ID
+    pub region_id: String,
+    /// 气候
+    pub climate: GcWorldTerrainType,
+    /// 当前天气
+    pub weather: GcWeather,
+    /// 下一个天气周期开始的现实时间 (Unix 秒，届时天气可能不变)
+    pub next_update_at: u64,
+}
+
+impl GcWeatherRegion {
+    /// 创建天气区域
+    pub fn gc_new(id: &str, climate: GcWorldTerrainType) -> Self {
+        Self { id: id.to_string(), climate }
+    }
+
+    /// 现实时间对应的天气
+    pub fn gc_weather_at(&self, clock: &GcWorldClock, now: u64) -> GcRegionWeather {
+        let game_secs = clock.gc_game_seconds(now);
+        let period = game_secs / GC_WEATHER_PERIOD_SECS;
+        let until_next = (period + 1) * GC_WEATHER_PERIOD_SECS - game_secs;
+
+        GcRegionWeather {
+            region_id: self.id.clone(),
+            climate: self.climate,
+            weather: gc_weather_for_period(&self.id, self.climate, period),
+            next_update_at: now + until_next.div_ceil(clock.time_ratio.max(1) as u64),
+        }
+    }
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_weather_is_deterministic_and_persists() {
+        let a: Vec<_> = (0..200).map(|p| gc_weather_for_period("map_forest", GcWorldTerrainType::Woodland, p)).collect();
+        let b: Vec<_> = (0..200).map(|p| gc_weather_for_period("map_forest", GcWorldTerrainType::Woodland, p)).collect();
+        assert_eq!(a, b);
+        // 天气会演变，但不会每个周期都变
+        let changes = a.windows(2).filter(|w| w[0] != w[1]).count();
+        assert!(changes > 0 && changes < 199);
+        assert!(a.contains(&GcWeather::Clear) && a.contains(&GcWeather::Rain));
+    }
+
+    #[test]
+    fn test_climate_shapes_weather() {
+        let periods = 0..300;
+        assert!(periods.clone().all(|p| gc_weather_for_period("map_cave", GcWorldTerrainType::Cave, p) == GcWeather::Clear));
+        assert!(periods.clone().all(|p| gc_weather_for_period("map_dune", GcWorldTerrainType::Desert, p) != GcWeather::Snow));
+        assert!(periods.clone().any(|p| gc_weather_for_period("map_peak", GcWorldTerrainType::Snowfield, p) == GcWeather::Snow));
+        assert!(periods.clone().all(|p| gc_weather_for_period("map_peak", GcWorldTerrainType::Snowfield, p) != GcWeather::Rain));
+    }
+
+    #[test]
+    fn test_region_weather_follows_clock() {
+        // 倍率 60: 一个天气周期 (6 游戏小时) = 现实 6 分钟
+        let clock = GcWorldClock::gc_new(60);
+        let region = GcWeatherRegion::gc_new("map_forest", GcWorldTerrainType::Woodland);
+        let now = 6 * 60 * 10 + 100;
+        let weather = region.gc_weather_at(&clock, now);
+        assert_eq!(weather.weather, gc_weather_for_period("map_forest", GcWorldTerrainType::Woodland, 10));
+        assert_eq!(weather.next_update_at, 6 * 60 * 11);
+        assert_eq!(region.gc_weather_at(&clock, now + 200).weather, weather.weather);
+
+        assert_eq!(GcWeather::Clear.gc_mix_seed(42), 42);
+        assert_ne!(GcWeather::Snow.gc_mix_seed(42), GcWeather::Rain.gc_mix_seed(42));
+    }
+}
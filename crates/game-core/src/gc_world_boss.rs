@@ -0,0 +1,551 @@
+//! 世界 Boss
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! ## 规则
+//! - 世界 Boss 按固定间隔刷新，每次存在一段时间，超时未击杀则离开
+//! - 全服玩家共享同一血量，服务器汇总伤害并记录每名玩家的贡献
+//! - 伤害由服务器按角色等级与随机数计算 (`gc_world_boss_hit_damage`)，客户端只发起攻击
+//! - 每名玩家两次攻击至少间隔 `GC_WORLD_BOSS_HIT_INTERVAL_SECS` 秒
+//! - 血量低于阶段阈值时进入下一阶段，攻击力提升
+//! - 击杀后按贡献发放奖励: 前 3 名冠军档，其余按伤害占比分为 金/银/铜 档
+//! - 战斗期间记录伤害统计 (DPS/HPS)，结束时随战斗结果一并给出
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    GcBoss, GcBossDrop, GcBossSkill, GcBossType, GcDamageMeter, GcMeterSnapshot, GcSkillTargetType,
+};
+
+// =============================================================================
+// 常量
+// =============================================================================
+
+/// 冠军档名额
+pub const GC_WORLD_BOSS_CHAMPION_SLOTS: usize = 3;
+
+/// 金档所需伤害占比 (万分比)
+pub const GC_WORLD_BOSS_GOLD_BASIS_POINTS: u64 = 500;
+
+/// 银档所需伤害占比 (万分比)
+pub const GC_WORLD_BOSS_SILVER_BASIS_POINTS: u64 = 100;
+
+/// 单次攻击伤害上限 (服务器校验)
+pub const GC_WORLD_BOSS_MAX_HIT: u32 = 5000;
+
+/// 同一玩家两次攻击的最小间隔 (秒)
+pub const GC_WORLD_BOSS_HIT_INTERVAL_SECS: u64 = 1;
+
+/// 每级角色等级增加的基础伤害
+const GC_WORLD_BOSS_DAMAGE_PER_LEVEL: u32 = 12;
+
+/// 基础伤害 (等级 0)
+const GC_WORLD_BOSS_BASE_DAMAGE: u32 = 40;
+
+/// 按角色等级计算一次攻击的伤害
+///
+/// 基础伤害 40 + 等级 × 12，按随机数 (0-255) 浮动 80%-120%，不超过单次上限
+pub fn gc_world_boss_hit_damage(level: u32, roll: u8) -> u32 {
+    let base = level.saturating_mul(GC_WORLD_BOSS_DAMAGE_PER_LEVEL).saturating_add(GC_WORLD_BOSS_BASE_DAMAGE);
+    let percent = 80 + roll as u64 * 40 / 256;
+    (base as u64 * percent / 100).min(GC_WORLD_BOSS_MAX_HIT as u64) as u32
+}
+
+// =============================================================================
+// 刷新计划
+// =============================================================================
+
+/// 世界 Boss 刷新计划
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcWorldBossSchedule {
+    /// 首次刷新时间 (Unix 时间戳)
+    pub first_spawn_at: u64,
+    /// 刷新间隔 (秒)
+    pub interval_secs: u64,
+    /// 存在时长 (秒)
+    pub duration_secs: u64,
+}
+
+impl GcWorldBossSchedule {
+    /// 创建刷新计划
+    pub fn gc_new(first_spawn_at: u64, interval_secs: u64, duration_secs: u64) -> Self {
+        Self {
+            first_spawn_at,
+            interval_secs: interval_secs.max(1),
+            duration_secs: duration_secs.min(interval_secs.max(1)),
+        }
+    }
+
+    /// 当前时间所在刷新窗口的开始时间 (未到首次刷新返回 None)
+    fn gc_window_start(&self, now: u64) -> Option<u64> {
+        let elapsed = now.checked_sub(self.first_spawn_at)?;
+        Some(self.first_spawn_at + elapsed / self.interval_secs * self.interval_secs)
+    }
+
+    /// 当前处于刷新窗口时返回 (开始, 结束)
+    pub fn gc_active_window(&self, now: u64) -> Option<(u64, u64)> {
+        let start = self.gc_window_start(now)?;
+        let end = start + self.duration_secs;
+        (now < end).then_some((start, end))
+    }
+
+    /// 下一次刷新时间 (严格晚于 now)
+    pub fn gc_next_spawn_at(&self, now: u64) -> u64 {
+        match self.gc_window_start(now) {
+            Some(start) => start + self.interval_secs,
+            None => self.first_spawn_at,
+        }
+    }
+}
+
+// =============================================================================
+// 阶段
+// =============================================================================
+
+/// 世界 Boss 阶段
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcWorldBossPhase {
+    /// 阶段名称
+    pub name: String,
+    /// 血量低于该百分比时进入本阶段
+    pub hp_threshold_percent: u32,
+    /// 攻击力加成 (%)
+    pub attack_bonus_percent: u32,
+}
+
+impl GcWorldBossPhase {
+    /// 创建阶段
+    pub fn gc_new(name: &str, hp_threshold_percent: u32, attack_bonus_percent: u32) -> Self {
+        Self {
+            name: name.to_string(),
+            hp_threshold_percent,
+            attack_bonus_percent,
+        }
+    }
+}
+
+// =============================================================================
+// 贡献与奖励
+// =============================================================================
+
+/// 玩家贡献
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcBossContribution {
+    /// 玩家ID
+    pub player_id: String,
+    /// 累计伤害
+    pub damage: u64,
+    /// 攻击次数
+    pub hits: u32,
+    /// 最后一次攻击时间
+    #[serde(default)]
+    pub last_hit_at: u64,
+}
+
+/// 奖励档位
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GcWorldBossRewardTier {
+    /// 冠军 (伤害前 3 名)
+    Champion,
+    /// 金
+    Gold,
+    /// 银
+    Silver,
+    /// 铜 (参与即可)
+    Bronze,
+}
+
+impl GcWorldBossRewardTier {
+    /// 奖励掉落倍数
+    pub fn gc_drop_multiplier(&self) -> u32 {
+        match self {
+            GcWorldBossRewardTier::Champion => 4,
+            GcWorldBossRewardTier::Gold => 3,
+            GcWorldBossRewardTier::Silver => 2,
+            GcWorldBossRewardTier::Bronze => 1,
+        }
+    }
+}
+
+/// 玩家奖励
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcWorldBossReward {
+    /// 玩家ID
+    pub player_id: String,
+    /// 伤害排名 (从 1 开始)
+    pub rank: u32,
+    /// 累计伤害
+    pub damage: u64,
+    /// 伤害占比 (万分比)
+    pub basis_points: u64,
+    /// 奖励档位
+    pub tier: GcWorldBossRewardTier,
+}
+
+// =============================================================================
+// 世界 Boss 事件
+// =============================================================================
+
+/// 一次攻击的结果
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcWorldBossHit {
+    /// 实际伤害
+    pub damage: u32,
+    /// Boss 剩余血量
+    pub boss_hp: u32,
+    /// 进入的新阶段 (索引)
+    pub new_phase: Option<usize>,
+    /// 是否击杀
+    pub killed: bool,
+}
+
+/// 世界 Boss 状态快照 (广播给客户端)
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcWorldBossStatus {
+    /// Boss ID
+    pub boss_id: String,
+    /// Boss 名称
+    pub name: String,
+    /// 当前血量
+    pub current_hp: u32,
+    /// 最大血量
+    pub max_hp: u32,
+    /// 当前阶段索引
+    pub phase: usize,
+    /// 当前阶段名称
+    pub phase_name: String,
+    /// 离开时间 (Unix 时间戳)
+    pub despawn_at: u64,
+    /// 参与人数
+    pub participants: usize,
+    /// 伤害排行 (前 10)
+    pub top: Vec<GcBossContribution>,
+}
+
+/// 战斗结果 (击杀或超时离开)
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcRaidResult {
+    /// Boss ID
+    pub boss_id: String,
+    /// 是否击杀
+    pub killed: bool,
+    /// 奖励 (未击杀为空)
+    pub rewards: Vec<GcWorldBossReward>,
+    /// 最终伤害统计
+    pub meter: GcMeterSnapshot,
+}
+
+/// 一次世界 Boss 事件
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GcWorldBossEvent {
+    /// Boss
+    pub boss: GcBoss,
+    /// 阶段 (按阈值从高到低，第一个为初始阶段)
+    pub phases: Vec<GcWorldBossPhase>,
+    /// 当前阶段索引
+    pub current_phase: usize,
+    /// 刷新时间
+    pub spawned_at: u64,
+    /// 离开时间
+    pub despawn_at: u64,
+    /// 玩家贡献
+    pub contributions: Vec<GcBossContribution>,
+    /// 伤害统计
+    #[serde(default)]
+    pub meter: GcDamageMeter,
+}
+
+impl GcWorldBossEvent {
+    /// 创建事件
+    pub fn gc_new(boss: GcBoss, phases: Vec<GcWorldBossPhase>, spawned_at: u64, despawn_at: u64) -> Self {
+        Self {
+            boss,
+            phases,
+            current_phase: 0,
+            spawned_at,
+            despawn_at,
+            contributions: Vec::new(),
+            meter: GcDamageMeter::gc_new(),
+        }
+    }
+
+    /// 是否已超时离开
+    pub fn gc_is_expired(&self, now: u64) -> bool {
+        now >= self.despawn_at
+    }
+
+    /// 是否可被攻击
+    pub fn gc_is_active(&self, now: u64) -> bool {
+        self.boss.gc_is_alive() && !self.gc_is_expired(now)
+    }
+
+    /// 当前血量百分比
+    fn gc_hp_percent(&self) -> u32 {
+        (self.boss.current_hp as u64 * 100)
+            .checked_div(self.boss.max_hp as u64)
+            .unwrap_or(0) as u32
+    }
+
+    /// 玩家造成伤害 (服务器汇总调用)
+    pub fn gc_apply_damage(&mut self, player_id: &str, damage: u32, now: u64) -> Result<GcWorldBossHit, String> {
+        if !self.gc_is_active(now) {
+            return Err("世界 Boss 不在场".to_string());
+        }
+        if damage > GC_WORLD_BOSS_MAX_HIT {
+            return Err(format!("单次伤害不能超过 {}", GC_WORLD_BOSS_MAX_HIT));
+        }
+        let last_hit_at = self.contributions.iter()
+            .find(|c| c.player_id == player_id)
+            .map(|c| c.last_hit_at);
+        if last_hit_at.is_some_and(|t| now < t + GC_WORLD_BOSS_HIT_INTERVAL_SECS) {
+            return Err("攻击过于频繁".to_string());
+        }
+
+        let (actual, killed) = self.boss.gc_take_damage_from(player_id, damage);
+        self.meter.gc_record_damage(player_id, actual, now);
+
+        match self.contributions.iter_mut().find(|c| c.player_id == player_id) {
+            Some(c) => {
+                c.damage += actual as u64;
+                c.hits += 1;
+                c.last_hit_at = now;
+            }
+            None => self.contributions.push(GcBossContribution {
+                player_id: player_id.to_string(),
+                damage: actual as u64,
+                hits: 1,
+                last_hit_at: now,
+            }),
+        }
+
+        // 阶段推进 (一次伤害可能跨越多个阶段，取最后一个)
+        let hp_percent = self.gc_hp_percent();
+        let mut new_phase = None;
+        while let Some(next) = self.phases.get(self.current_phase + 1) {
+            if killed || hp_percent >= next.hp_threshold_percent {
+                break;
+            }
+            self.current_phase += 1;
+            new_phase = Some(self.current_phase);
+        }
+        if let Some(phase) = new_phase.and_then(|i| self.phases.get(i)) {
+            self.boss.current_attack = self.boss.base_attack * (100 + phase.attack_bonus_percent) / 100;
+        }
+
+        Ok(GcWorldBossHit {
+            damage: actual,
+            boss_hp: self.boss.current_hp,
+            new_phase,
+            killed,
+        })
+    }
+
+    /// 玩家治疗 (计入统计与仇恨)
+    pub fn gc_record_heal(&mut self, player_id: &str, amount: u32, now: u64) -> Result<(), String> {
+        if !self.gc_is_active(now) {
+            return Err("世界 Boss 不在场".to_string());
+        }
+        self.boss.gc_record_heal(player_id, amount);
+        self.meter.gc_record_heal(player_id, amount, now);
+        Ok(())
+    }
+
+    /// 战斗结果
+    pub fn gc_result(&self, now: u64) -> GcRaidResult {
+        GcRaidResult {
+            boss_id: self.boss.id.clone(),
+            killed: !self.boss.gc_is_alive(),
+            rewards: self.gc_rewards(),
+            meter: self.meter.gc_snapshot(now.min(self.despawn_at)),
+        }
+    }
+
+    /// 按伤害降序的贡献
+    pub fn gc_ranking(&self) -> Vec<GcBossContribution> {
+        let mut ranking = self.contributions.clone();
+        ranking.sort_by_key(|c| std::cmp::Reverse(c.damage));
+        ranking
+    }
+
+    /// 状态快照
+    pub fn gc_status(&self) -> GcWorldBossStatus {
+        GcWorldBossStatus {
+            boss_id: self.boss.id.clone(),
+            name: self.boss.name.clone(),
+            current_hp: self.boss.current_hp,
+            max_hp: self.boss.max_hp,
+            phase: self.current_phase,
+            phase_name: self.phases.get(self.current_phase).map(|p| p.name.clone()).unwrap_or_default(),
+            despawn_at: self.despawn_at,
+            participants: self.contributions.len(),
+            top: self.gc_ranking().into_iter().take(10).collect(),
+        }
+    }
+
+    /// 击杀后的奖励 (未击杀返回空)
+    pub fn gc_rewards(&self) -> Vec<GcWorldBossReward> {
+        if self.boss.gc_is_alive() {
+            return Vec::new();
+        }
+
+        let total: u64 = self.contributions.iter().map(|c| c.damage).sum();
+        self.gc_ranking().into_iter()
+            .filter(|c| c.damage > 0)
+            .enumerate()
+            .map(|(i, c)| {
+                let basis_points = (c.damage * 10_000).checked_div(total).unwrap_or(0);
+                let tier = if i < GC_WORLD_BOSS_CHAMPION_SLOTS {
+                    GcWorldBossRewardTier::Champion
+                } else if basis_points >= GC_WORLD_BOSS_GOLD_BASIS_POINTS {
+                    GcWorldBossRewardTier::Gold
+                } else if basis_points >= GC_WORLD_BOSS_SILVER_BASIS_POINTS {
+                    GcWorldBossRewardTier::Silver
+                } else {
+                    GcWorldBossRewardTier::Bronze
+                };
+                GcWorldBossReward {
+                    player_id: c.player_id,
+                    rank: i as u32 + 1,
+                    damage: c.damage,
+                    basis_points,
+                    tier,
+                }
+            })
+            .collect()
+    }
+}
+
+// =============================================================================
+// 预设世界 Boss
+// =============================================================================
+
+/// 创建烬灭古龙 (世界 Boss) 事件
+pub fn gc_create_ember_wyrm_event(spawned_at: u64, despawn_at: u64) -> GcWorldBossEvent {
+    let mut boss = GcBoss::gc_new(
+        "boss_ember_wyrm",
+        "烬灭古龙",
+        GcBossType::World,
+        200_000,
+        120,
+        40,
+        300,
+        10,
+    );
+    boss = boss.gc_with_description("每隔一段时间降临大陆的远古巨龙，需要全服冒险者合力讨伐");
+
+    boss.gc_add_skill(GcBossSkill::gc_new(
+        "skill_ember_breath",
+        "烬灭吐息",
+        "喷吐烈焰，对全体造成伤害",
+        60,
+        GcSkillTargetType::All,
+        3,
+    ));
+
+    boss.gc_add_drop(GcBossDrop {
+        item_id: "wyrm_scale".to_string(),
+        item_name: "古龙鳞片".to_string(),
+        drop_rate: 100,
+        min_quantity: 1,
+        max_quantity: 3,
+    });
+
+    let phases = vec![
+        GcWorldBossPhase::gc_new("苏醒", 100, 0),
+        GcWorldBossPhase::gc_new("焚天", 75, 20),
+        GcWorldBossPhase::gc_new("熔心", 50, 50),
+        GcWorldBossPhase::gc_new("余烬", 25, 100),
+    ];
+
+    GcWorldBossEvent::gc_new(boss, phases, spawned_at, despawn_at)
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schedule_windows() {
+        let schedule = GcWorldBossSchedule::gc_new(1000, 3600, 600);
+
+        assert_eq!(schedule.gc_active_window(999), None);
+        assert_eq!(schedule.gc_next_spawn_at(999), 1000);
+        assert_eq!(schedule.gc_active_window(1500), Some((1000, 1600)));
+        assert_eq!(schedule.gc_active_window(1600), None);
+        assert_eq!(schedule.gc_next_spawn_at(1600), 4600);
+    }
+
+    #[test]
+    fn test_shared_hp_and_phases() {
+        let mut event = gc_create_ember_wyrm_event(0, 600);
+        event.boss.current_hp = 1000;
+        event.boss.max_hp = 1000;
+        event.boss.defense = 0;
+
+        let hit = event.gc_apply_damage("p1", 300, 10).expect("可攻击");
+        assert_eq!(hit.boss_hp, 700);
+        assert_eq!(hit.new_phase, Some(1));
+        assert!(event.boss.current_attack > event.boss.base_attack);
+
+        // 一次跨越两个阶段
+        let hit = event.gc_apply_damage("p2", 500, 10).expect("可攻击");
+        assert_eq!(hit.new_phase, Some(3));
+
+        assert!(event.gc_apply_damage("p1", GC_WORLD_BOSS_MAX_HIT + 1, 10).is_err());
+        assert!(event.gc_apply_damage("p1", 10, 600).is_err());
+
+        let status = event.gc_status();
+        assert_eq!(status.participants, 2);
+        assert_eq!(status.top[0].player_id, "p2");
+    }
+
+    #[test]
+    fn test_hits_are_rate_limited_per_player() {
+        let mut event = gc_create_ember_wyrm_event(0, 600);
+        assert!(event.gc_apply_damage("p1", 100, 10).is_ok());
+        assert!(event.gc_apply_damage("p1", 100, 10).is_err());
+        assert!(event.gc_apply_damage("p2", 100, 10).is_ok());
+        assert!(event.gc_apply_damage("p1", 100, 10 + GC_WORLD_BOSS_HIT_INTERVAL_SECS).is_ok());
+        assert_eq!(event.contributions[0].hits, 2);
+    }
+
+    #[test]
+    fn test_hit_damage_scales_with_level() {
+        assert_eq!(gc_world_boss_hit_damage(0, 0), 32);
+        assert_eq!(gc_world_boss_hit_damage(10, 128), 160);
+        assert!(gc_world_boss_hit_damage(10, 255) < 160 * 120 / 100);
+        assert!(gc_world_boss_hit_damage(20, 0) > gc_world_boss_hit_damage(10, 255));
+        assert_eq!(gc_world_boss_hit_damage(u32::MAX, 255), GC_WORLD_BOSS_MAX_HIT);
+    }
+
+    #[test]
+    fn test_reward_tiers_by_contribution() {
+        let mut event = gc_create_ember_wyrm_event(0, 600);
+        event.boss.max_hp = 10_000;
+        event.boss.current_hp = 10_000;
+        event.boss.defense = 0;
+
+        for (id, damage) in [("a", 4000), ("b", 3000), ("c", 2000), ("d", 900), ("e", 50)] {
+            assert!(event.gc_apply_damage(id, damage, 1).is_ok());
+        }
+        assert!(event.gc_rewards().is_empty());
+        assert!(event.gc_apply_damage("f", 50, 1).expect("可攻击").killed);
+
+        let result = event.gc_result(2);
+        assert!(result.killed);
+        assert_eq!(result.meter.total_damage, 10_000);
+        assert_eq!(result.meter.rows[0].player_id, "a");
+
+        let rewards = result.rewards;
+        assert_eq!(rewards.len(), 6);
+        assert_eq!(rewards[0].tier, GcWorldBossRewardTier::Champion);
+        assert_eq!(rewards[3].tier, GcWorldBossRewardTier::Gold);
+        assert_eq!(rewards[5].tier, GcWorldBossRewardTier::Bronze);
+    }
+}
@@ -0,0 +1,236 @@
+//! 世界时钟 (游戏内时间与昼夜)
+//!
+//! 模块: game-core
+//! 前缀: Gc
+//! 文档: 文档/01-game-core.md
+//!
+//! ## 规则
+//! - 游戏时间 = 现实 Unix 秒 × 时间倍率 + 偏移，服务器与客户端用同一公式，不需要持续同步
+//! - 默认倍率 60 (现实 1 分钟 = 游戏 1 小时，一个游戏日 24 分钟)
+//! - 一天分为四个时段: 黎明 5~7 点、白天 7~18 点、黄昏 18~20 点、夜晚 20~5 点
+//! - 时段影响玩法: 遭遇等级加成、商店价格与营业、刷怪区域只在指定时段出现怪物
+
+use serde::{Deserialize, Serialize};
+
+/// 游戏内一天的秒数
+pub const GC_GAME_DAY_SECS: u64 = 24 * 60 * 60;
+
+/// 默认时间倍率 (游戏秒 / 现实秒)
+pub const GC_DEFAULT_TIME_RATIO: u32 = 60;
+
+// =============================================================================
+// 时段
+// =============================================================================
+
+/// 昼夜时段
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum GcDayPhase {
+    /// 黎明 (5~7 点)
+    Dawn,
+    /// 白天 (7~18 点)
+    #[default]
+    Day,
+    /// 黄昏 (18~20 点)
+    Dusk,
+    /// 夜晚 (20~5 点)
+    Night,
+}
+
+/// 时段对玩法的影响
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcTimeOfDayEffects {
+    /// 遭遇敌人等级加成
+    pub encounter_level_bonus: u32,
+    /// 商店价格 (百分比)
+    pub shop_price_percent: u32,
+    /// 商店是否营业
+    pub shop_open: bool,
+}
+
+impl GcDayPhase {
+    /// 全部时段 (按一天中的顺序)
+    pub const ALL: [GcDayPhase; 4] = [Self::Dawn, Self::Day, Self::Dusk, Self::Night];
+
+    /// 小时所在的时段
+    pub fn gc_from_hour(hour: u32) -> Self {
+        match hour % 24 {
+            5..=6 => Self::Dawn,
+            7..=17 => Self::Day,
+            18..=19 => Self::Dusk,
+            _ => Self::Night,
+        }
+    }
+
+    /// 时段开始的小时
+    pub fn gc_start_hour(&self) -> u32 {
+        match self {
+            Self::Dawn => 5,
+            Self::Day => 7,
+            Self::Dusk => 18,
+            Self::Night => 20,
+        }
+    }
+
+    /// 下一个时段
+    pub fn gc_next(&self) -> Self {
+        match self {
+            Self::Dawn => Self::Day,
+            Self::Day => Self::Dusk,
+            Self::Dusk => Self::Night,
+            Self::Night => Self::Dawn,
+        }
+    }
+
+    /// 显示名称
+    pub fn gc_name(&self) -> &'static str {
+        match self {
+            Self::Dawn => "黎明",
+            Self::Day => "白天",
+            Self::Dusk => "黄昏",
+            Self::Night => "夜晚",
+        }
+    }
+
+    /// 时段对玩法的影响 (夜晚怪物更强、商店关门；黄昏商店加价)
+    pub fn gc_effects(&self) -> GcTimeOfDayEffects {
+        match self {
+            Self::Dawn => GcTimeOfDayEffects { encounter_level_bonus: 0, shop_price_percent: 90, shop_open: true },
+            Self::Day => GcTimeOfDayEffects { encounter_level_bonus: 0, shop_price_percent: 100, shop_open: true },
+            Self::Dusk => GcTimeOfDayEffects { encounter_level_bonus: 1, shop_price_percent: 110, shop_open: true },
+            Self::Night => GcTimeOfDayEffects { encounter_level_bonus: 2, shop_price_percent: 100, shop_open: false },
+        }
+    }
+
+    /// 该时段的商店价格 (打烊时为 None)
+    pub fn gc_shop_price(&self, base_price: u32) -> Option<u32> {
+        let effects = self.gc_effects();
+        effects
+            .shop_open
+            .then(|| (base_price as u64 * effects.shop_price_percent as u64).div_ceil(100) as u32)
+    }
+}
+
+// =============================================================================
+// 时钟
+// =============================================================================
+
+/// 某一时刻的游戏时间
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcWorldTime {
+    /// 第几天 (从 0 开始)
+    pub day: u64,
+    /// 小时 (0~23)
+    pub hour: u32,
+    /// 分钟 (0~59)
+    pub minute: u32,
+    /// 当前时段
+    pub phase: GcDayPhase,
+    /// 时间倍率 (客户端据此本地推算)
+    pub time_ratio: u32,
+    /// 下一个时段开始的现实时间 (Unix 秒)
+    pub next_phase_at: u64,
+}
+
+/// 世界时钟
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcWorldClock {
+    /// 时间倍率 (游戏秒 / 现实秒，至少为 1)
+    pub time_ratio: u32,
+    /// 游戏时间偏移 (秒)
+    #[serde(default)]
+    pub offset_secs: u64,
+}
+
+impl Default for GcWorldClock {
+    fn default() -> Self {
+        Self::gc_new(GC_DEFAULT_TIME_RATIO)
+    }
+}
+
+impl GcWorldClock {
+    /// 创建时钟 (倍率为 0 时按 1 处理)
+    pub fn gc_new(time_ratio: u32) -> Self {
+        Self { time_ratio: time_ratio.max(1), offset_secs: 0 }
+    }
+
+    /// 现实时间对应的游戏秒数
+    pub fn gc_game_seconds(&self, now: u64) -> u64 {
+        now.saturating_mul(self.time_ratio.max(1) as u64).saturating_add(self.offset_secs)
+    }
+
+    /// 现实时间对应的时段
+    pub fn gc_phase_at(&self, now: u64) -> GcDayPhase {
+        GcDayPhase::gc_from_hour(((self.gc_game_seconds(now) % GC_GAME_DAY_SECS) / 3600) as u32)
+    }
+
+    /// 现实时间对应的游戏时间
+    pub fn gc_time_at(&self, now: u64) -> GcWorldTime {
+        let game_secs = self.gc_game_seconds(now);
+        let secs_of_day = game_secs % GC_GAME_DAY_SECS;
+        let hour = (secs_of_day / 3600) as u32;
+        let phase = GcDayPhase::gc_from_hour(hour);
+
+        // 距离下一个时段开始的游戏秒数，换算为现实秒 (向上取整)
+        let next_start = phase.gc_next().gc_start_hour() as u64 * 3600;
+        let until_next = (next_start + GC_GAME_DAY_SECS - secs_of_day) % GC_GAME_DAY_SECS;
+        let ratio = self.time_ratio.max(1) as u64;
+
+        GcWorldTime {
+            day: game_secs / GC_GAME_DAY_SECS,
+            hour,
+            minute: ((secs_of_day % 3600) / 60) as u32,
+            phase,
+            time_ratio: self.time_ratio.max(1),
+            next_phase_at: now + until_next.div_ceil(ratio),
+        }
+    }
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_phase_boundaries() {
+        assert_eq!(GcDayPhase::gc_from_hour(4), GcDayPhase::Night);
+        assert_eq!(GcDayPhase::gc_from_hour(5), GcDayPhase::Dawn);
+        assert_eq!(GcDayPhase::gc_from_hour(12), GcDayPhase::Day);
+        assert_eq!(GcDayPhase::gc_from_hour(19), GcDayPhase::Dusk);
+        assert_eq!(GcDayPhase::gc_from_hour(23), GcDayPhase::Night);
+        for phase in GcDayPhase::ALL {
+            assert_eq!(GcDayPhase::gc_from_hour(phase.gc_start_hour()), phase);
+        }
+    }
+
+    #[test]
+    fn test_clock_time_and_next_phase() {
+        // 倍率 60: 现实 1 分钟 = 游戏 1 小时
+        let clock = GcWorldClock::gc_new(60);
+        let time = clock.gc_time_at(12 * 60 + 30);
+        assert_eq!((time.day, time.hour, time.minute), (0, 12, 30));
+        assert_eq!(time.phase, GcDayPhase::Day);
+        // 18 点黄昏开始: 还差 5.5 游戏小时 = 330 现实秒
+        assert_eq!(time.next_phase_at, 12 * 60 + 30 + 330);
+        assert_eq!(clock.gc_phase_at(time.next_phase_at), GcDayPhase::Dusk);
+
+        // 夜晚跨过午夜，下一个时段是次日黎明
+        let night = clock.gc_time_at(23 * 60);
+        assert_eq!(night.phase, GcDayPhase::Night);
+        assert_eq!(clock.gc_time_at(night.next_phase_at).hour, 5);
+        assert_eq!(clock.gc_time_at(night.next_phase_at).day, 1);
+
+        assert_eq!(GcWorldClock::gc_new(0).time_ratio, 1);
+    }
+
+    #[test]
+    fn test_time_of_day_effects() {
+        assert_eq!(GcDayPhase::Day.gc_shop_price(100), Some(100));
+        assert_eq!(GcDayPhase::Dusk.gc_shop_price(15), Some(17));
+        assert_eq!(GcDayPhase::Night.gc_shop_price(100), None);
+        assert!(GcDayPhase::Night.gc_effects().encounter_level_bonus > GcDayPhase::Day.gc_effects().encounter_level_bonus);
+    }
+}
@@ -26,11 +26,42 @@ mod gc_effect;
 mod gc_error;
 mod gc_boss;
 mod gc_organization;
+mod gc_guild;
+mod gc_territory;
+mod gc_ruleset;
+mod gc_sandbox;
+mod gc_scenario;
+mod gc_history;
+mod gc_compact;
+mod gc_evaluation;
+mod gc_hint;
+mod gc_ai;
+mod gc_legality;
+mod gc_parked_battle;
+mod gc_timeline;
+mod gc_json;
+mod gc_map_upload;
+mod gc_content_version;
+mod gc_simulation;
+mod gc_intern;
+mod gc_battle_event;
+mod gc_action_feed;
+mod gc_replay;
+mod gc_math;
+mod gc_format;
+mod gc_card_cost;
+mod gc_hand_view;
+mod gc_ascii;
 mod gc_map;
+mod gc_map_chunk;
+mod gc_encounter;
 mod gc_map_entity;
 mod gc_map_player;
 mod gc_map_templates;
 mod gc_profession;
+mod gc_save_slot;
+mod gc_character;
+mod gc_rewards;
 mod gc_talent;
 mod gc_talent_templates;
 mod gc_equipment;
@@ -38,11 +69,53 @@ mod gc_inventory;
 mod gc_equipment_templates;
 mod gc_card_templates;
 mod gc_mcp;
+mod gc_mcp_validate;
+mod gc_mcp_area;
+mod gc_mcp_group;
+mod gc_asset_tags;
+mod gc_thumbnail;
+mod gc_world_clock;
+mod gc_weather;
 mod gc_battle_terrain;
+mod gc_battle_format;
+mod gc_match_stats;
+mod gc_ping;
+mod gc_onboarding;
+mod gc_account;
+mod gc_text;
+mod gc_card_text;
+mod gc_preview;
+mod gc_lethal;
+mod gc_paging;
+mod gc_monster_skin;
+mod gc_run_score;
+mod gc_match_audit;
+mod gc_usage_stats;
+mod gc_battle_pause;
+mod gc_player_settings;
+mod gc_palette;
 mod gc_monster;
 mod gc_summon;
 mod gc_turn;
 mod gc_combat;
+mod gc_combat_cue;
+mod gc_capture;
+mod gc_evolution;
+mod gc_fusion;
+mod gc_companion;
+mod gc_skill;
+mod gc_channel;
+mod gc_combat_log;
+mod gc_structure;
+mod gc_threat;
+mod gc_damage_type;
+mod gc_deck;
+mod gc_card_collection;
+mod gc_tavern_game;
+mod gc_tavern_matchmaking;
+mod gc_ghost;
+mod gc_world_boss;
+mod gc_damage_meter;
 
 // 酒馆模式新增模块
 mod gc_economy;
@@ -50,6 +123,9 @@ mod gc_level_system;
 mod gc_merge;
 mod gc_graveyard;
 mod gc_tavern_shop;
+mod gc_shop_pricing;
+mod gc_tavern_spell;
+mod gc_board_code;
 mod gc_season;
 mod gc_card_acquisition;
 
@@ -64,23 +140,96 @@ pub use gc_effect::*;
 pub use gc_error::*;
 pub use gc_boss::*;
 pub use gc_mcp::*;
+pub use gc_mcp_validate::*;
+pub use gc_mcp_area::*;
+pub use gc_mcp_group::*;
+pub use gc_asset_tags::*;
+pub use gc_thumbnail::*;
+pub use gc_world_clock::*;
+pub use gc_weather::*;
 pub use gc_equipment::*;
 pub use gc_inventory::*;
 pub use gc_equipment_templates::*;
 pub use gc_card_templates::*;
 pub use gc_organization::*;
+pub use gc_guild::*;
+pub use gc_territory::*;
+pub use gc_ruleset::*;
+pub use gc_sandbox::*;
+pub use gc_scenario::*;
+pub use gc_history::*;
+pub use gc_compact::*;
+pub use gc_evaluation::*;
+pub use gc_hint::*;
+pub use gc_ai::*;
+pub use gc_legality::*;
+pub use gc_parked_battle::*;
+pub use gc_timeline::*;
+pub use gc_json::*;
+pub use gc_map_upload::*;
+pub use gc_content_version::*;
+pub use gc_simulation::*;
+pub use gc_intern::*;
+pub use gc_battle_event::*;
+pub use gc_action_feed::*;
+pub use gc_replay::*;
+pub use gc_math::*;
+pub use gc_format::*;
+pub use gc_card_cost::*;
+pub use gc_hand_view::*;
+pub use gc_ascii::*;
 pub use gc_map::*;
+pub use gc_map_chunk::*;
+pub use gc_encounter::*;
 pub use gc_map_entity::*;
 pub use gc_map_player::*;
 pub use gc_map_templates::*;
 pub use gc_profession::*;
+pub use gc_save_slot::*;
+pub use gc_character::*;
+pub use gc_rewards::*;
 pub use gc_talent::*;
 pub use gc_talent_templates::*;
 pub use gc_battle_terrain::*;
+pub use gc_battle_format::*;
+pub use gc_match_stats::*;
+pub use gc_ping::*;
+pub use gc_onboarding::*;
+pub use gc_account::*;
+pub use gc_text::*;
+pub use gc_card_text::*;
+pub use gc_preview::*;
+pub use gc_lethal::*;
+pub use gc_paging::*;
+pub use gc_monster_skin::*;
+pub use gc_run_score::*;
+pub use gc_match_audit::*;
+pub use gc_usage_stats::*;
+pub use gc_battle_pause::*;
+pub use gc_player_settings::*;
+pub use gc_palette::*;
 pub use gc_monster::*;
 pub use gc_summon::*;
 pub use gc_turn::*;
 pub use gc_combat::*;
+pub use gc_combat_cue::*;
+pub use gc_capture::*;
+pub use gc_evolution::*;
+pub use gc_fusion::*;
+pub use gc_companion::*;
+pub use gc_skill::*;
+pub use gc_channel::*;
+pub use gc_combat_log::*;
+pub use gc_structure::*;
+pub use gc_threat::*;
+pub use gc_damage_type::*;
+pub use gc_deck::*;
+pub use gc_card_collection::*;
+pub use gc_tavern_game::*;
+pub use gc_tavern_matchmaking::*;
+pub use gc_ghost::*;
+pub use gc_world_boss::*;
+pub use gc_damage_meter::*;
 
 // 酒馆模式导出
 pub use gc_economy::*;
@@ -88,6 +237,9 @@ pub use gc_level_system::*;
 pub use gc_merge::*;
 pub use gc_graveyard::*;
 pub use gc_tavern_shop::*;
+pub use gc_shop_pricing::*;
+pub use gc_tavern_spell::*;
+pub use gc_board_code::*;
 pub use gc_season::*;
 pub use gc_card_acquisition::*;
 
@@ -0,0 +1,98 @@
+//! 跨平台确定性测试
+//!
+//! 在一组覆盖星级、地形、天赋、Boss 怒气与出牌伤害的输入上计算战斗数值，与
+//! `tests/golden/determinism.json` 对比。原生与 WASM 目标运行同一份快照，数值必须逐项一致:
+//! `cargo test -p game-core --test gc_determinism`
+//! `cargo test -p game-core --test gc_determinism --target wasm32-wasip1` (需要 wasmtime 运行器)
+//!
+//! 数值规则有意修改时: `UPDATE_GOLDEN=1 cargo test -p game-core --test gc_determinism`
+
+use std::fs;
+use std::path::PathBuf;
+
+use game_core::*;
+
+const GC_ATTRIBUTES: [GcMonsterAttribute; 7] = [
+    GcMonsterAttribute::Fire,
+    GcMonsterAttribute::Water,
+    GcMonsterAttribute::Earth,
+    GcMonsterAttribute::Wind,
+    GcMonsterAttribute::Light,
+    GcMonsterAttribute::Dark,
+    GcMonsterAttribute::None,
+];
+
+/// 怪兽: 星级 × 金色等级 × 属性 × 地形
+fn monster_values(out: &mut Vec<u32>) {
+    for (star, golden) in [(1, 0), (2, 0), (3, 0), (3, 1), (3, 3)] {
+        for attribute in GC_ATTRIBUTES {
+            let mut monster = GcMonster::new("m", "怪兽", 1, attribute, 33, 17, 71);
+            monster.star = star;
+            monster.golden_level = golden;
+            monster.speed = 13;
+            out.push(monster.effective_max_hp());
+            for &terrain in GcTerrainType::all() {
+                out.push(monster.effective_atk(terrain));
+                out.push(monster.effective_def(terrain));
+                out.push(monster.effective_speed(terrain));
+            }
+        }
+    }
+}
+
+/// 天赋百分比加成
+fn talent_values(out: &mut Vec<u32>) {
+    for percent in [-150, -33, -10, 7, 10, 15, 33, 250] {
+        let mut stats = GcCombatStats { max_hp: 97, physical_attack: 31, ..Default::default() };
+        for stat_name in ["max_hp", "physical_attack"] {
+            GcTalentEffect::AddCombatStatPercent { stat_name: stat_name.to_string(), percent }
+                .gc_apply_to_combat_stats(&mut stats);
+        }
+        out.push(stats.max_hp);
+        out.push(stats.physical_attack);
+    }
+}
+
+/// Boss 怒气
+fn boss_values(out: &mut Vec<u32>) {
+    for mut boss in [gc_create_shadow_lurker(), gc_create_abyssal_titan()] {
+        for damage in [1, 37, 333, 1001] {
+            boss.current_rage = 0;
+            boss.current_hp = boss.max_hp;
+            boss.gc_take_damage(damage);
+            out.push(boss.current_rage);
+        }
+    }
+}
+
+/// 出牌伤害 (防御减免)
+fn card_damage_values(out: &mut Vec<u32>) {
+    let attacker = GcPlayer::gc_new("p1", "玩家1");
+    for defense in [0, 1, 7, 10, 33, 99] {
+        let mut target = GcPlayer::gc_new("p2", "玩家2");
+        target.stats.defense = defense;
+        let card = GcCard::gc_new_attack("c", "打击", 1, 23);
+        out.push(gc_calculate_damage(&attacker, &target, &card, 0).final_damage);
+    }
+}
+
+#[test]
+fn test_combat_math_is_deterministic() {
+    let mut values = Vec::new();
+    monster_values(&mut values);
+    talent_values(&mut values);
+    boss_values(&mut values);
+    card_damage_values(&mut values);
+
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/golden/determinism.json");
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        fs::write(&path, serde_json::to_string(&values).expect("序列化失败") + "\n").expect("写入快照失败");
+        return;
+    }
+
+    let text = fs::read_to_string(&path).expect("缺少快照，请用 UPDATE_GOLDEN=1 生成");
+    let expected: Vec<u32> = serde_json::from_str(&text).expect("快照不是合法 JSON");
+    assert_eq!(values.len(), expected.len(), "输入组合数量已改变");
+    let diff = values.iter().zip(&expected).position(|(a, b)| a != b);
+    assert!(diff.is_none(), "第 {:?} 项战斗数值与快照不一致", diff);
+}
@@ -0,0 +1,65 @@
+//! 协议快照测试 (golden master)
+//!
+//! 把代表性的状态序列化后与 `tests/golden/*.json` 对比，防止线上格式被无意修改而破坏 JS 客户端。
+//!
+//! 格式是有意修改时，更新快照并随代码一起提交:
+//! `UPDATE_GOLDEN=1 cargo test -p game-core --test gc_golden`
+
+use std::fs;
+use std::path::PathBuf;
+
+use game_core::*;
+use serde::Serialize;
+
+/// 与快照对比 (设置 UPDATE_GOLDEN 时改为写入)
+fn gc_assert_golden<T: Serialize>(name: &str, value: &T) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/golden").join(format!("{}.json", name));
+    let actual = serde_json::to_value(value).expect("序列化失败");
+
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        let text = serde_json::to_string_pretty(&actual).expect("格式化失败");
+        fs::write(&path, text + "\n").expect("写入快照失败");
+        return;
+    }
+
+    let text = fs::read_to_string(&path)
+        .unwrap_or_else(|_| panic!("缺少快照 {}，请用 UPDATE_GOLDEN=1 生成", path.display()));
+    let expected: serde_json::Value = serde_json::from_str(&text).expect("快照不是合法 JSON");
+    assert!(
+        actual == expected,
+        "{} 的序列化格式已改变。若为有意修改，请运行 UPDATE_GOLDEN=1 cargo test -p game-core --test gc_golden 并提交快照。\n实际:\n{}",
+        name,
+        serde_json::to_string_pretty(&actual).unwrap_or_default(),
+    );
+}
+
+/// 固定内容的战斗 (不使用随机卡池)
+fn create_battle() -> GcBattleState {
+    let mut state = GcBattleState::gc_new("golden", vec![
+        GcPlayer::gc_new("p1", "玩家1"),
+        GcPlayer::gc_new("p2", "玩家2"),
+    ]);
+    state.card_pool = GcCardPool::gc_new(GcCardPoolConfig::default());
+    state.phase = GcBattlePhase::Playing;
+
+    let mut card = gc_get_card_template("card_knight_attack").expect("模板存在");
+    card.id = "c1".to_string();
+    state.players[0].hand.push(card);
+    let result = gc_execute_play_card(&mut state, "p1", "c1", "p2");
+    assert!(result.success);
+    state
+}
+
+#[test]
+fn test_battle_state_golden() {
+    gc_assert_golden("battle_state", &create_battle());
+}
+
+#[test]
+fn test_tavern_shop_golden() {
+    let mut shop = GcTavernShop::new();
+    shop.slots[0] = Some(GcMonster::new("m1", "史莱姆", 1, GcMonsterAttribute::Water, 50, 30, 60));
+    shop.slots[2] = Some(GcMonster::new("m2", "火蜥蜴", 2, GcMonsterAttribute::Fire, 80, 40, 70));
+    shop.frozen[2] = true;
+    gc_assert_golden("tavern_shop", &shop);
+}
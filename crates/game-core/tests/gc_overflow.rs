@@ -0,0 +1,71 @@
+//! 属性运算极值测试
+//!
+//! 用 u32::MAX 生命/攻击、-100% 修正等极端输入驱动天赋、地形、伤害与光环计算，
+//! 确认结果饱和而不是 panic 或回绕 (debug 构建中溢出会直接 panic)。
+
+use game_core::*;
+
+#[test]
+fn test_monster_stats_at_u32_max() {
+    let mut monster = GcMonster::new("m", "巨兽", 1, GcMonsterAttribute::Fire, u32::MAX, u32::MAX, u32::MAX);
+    monster.star = 3;
+    monster.golden_level = u8::MAX;
+    monster.speed = u32::MAX;
+
+    assert_eq!(monster.effective_max_hp(), u32::MAX);
+    for &terrain in GcTerrainType::all() {
+        assert!(monster.effective_atk(terrain) >= 1);
+        monster.effective_def(terrain);
+        monster.effective_speed(terrain);
+    }
+    assert_eq!(gc_get_terrain_modifier(GcTerrainType::Swamp, GcMonsterAttribute::Fire).calc_hp_change(u32::MAX), -214_748_364);
+
+    let mut defender = monster.clone();
+    defender.base_def = 0;
+    let result = gc_execute_monster_battle(&mut monster, &mut defender, GcTerrainType::Volcano, GcTerrainType::Plain);
+    assert!(result.defender_destroyed);
+}
+
+#[test]
+fn test_player_damage_and_heal_extremes() {
+    let mut target = GcPlayer::gc_new("p2", "玩家2");
+    target.resistances = GcResistances { physical: GC_MIN_RESISTANCE, ..Default::default() };
+    let mut attacker = GcPlayer::gc_new("p1", "玩家1");
+    attacker.stats.attack = u32::MAX;
+    let card = GcCard::gc_new_attack("c", "打击", 1, u32::MAX);
+
+    // -100% 抗性 (双倍伤害) 与 u32::MAX 原始伤害
+    let damage = gc_calculate_damage(&attacker, &target, &card, u32::MAX);
+    assert_eq!(damage.raw_damage, u32::MAX);
+    assert_eq!(damage.final_damage, u32::MAX);
+    assert_eq!(damage.reduced_damage, 0);
+
+    target.stats.max_hp = u32::MAX;
+    target.stats.hp = u32::MAX - 1;
+    assert_eq!(target.stats.gc_heal(u32::MAX), 1);
+    assert_eq!(target.stats.hp, u32::MAX);
+    assert_eq!(target.stats.gc_take_damage(u32::MAX), u32::MAX);
+    assert_eq!(target.stats.hp, 0);
+}
+
+#[test]
+fn test_talent_and_aura_extremes() {
+    let mut stats = GcCombatStats { max_hp: u32::MAX, crit_rate: 90, physical_attack: 50, ..Default::default() };
+    GcTalentEffect::AddCombatStatFlat { stat_name: "max_hp".to_string(), value: i32::MAX }
+        .gc_apply_to_combat_stats(&mut stats);
+    GcTalentEffect::AddCombatStatFlat { stat_name: "crit_rate".to_string(), value: 50 }
+        .gc_apply_to_combat_stats(&mut stats);
+    GcTalentEffect::AddCombatStatPercent { stat_name: "physical_attack".to_string(), percent: -100 }
+        .gc_apply_to_combat_stats(&mut stats);
+    assert_eq!(stats.max_hp, u32::MAX);
+    assert_eq!(stats.crit_rate, 100);
+    assert_eq!(stats.physical_attack, 0);
+
+    let aura = GcCompanionAura { atk_percent: u32::MAX, def_percent: 0, hp_percent: 100, speed_percent: 0 };
+    assert_eq!(aura.apply_atk(u32::MAX), u32::MAX);
+    assert_eq!(aura.apply_hp(u32::MAX), u32::MAX);
+
+    let buffs = GcGuildBuffs { xp_percent: u32::MAX, atk_percent: 10, def_percent: 0, hp_percent: 0 };
+    assert_eq!(buffs.apply_xp(u32::MAX), u32::MAX);
+    assert_eq!(buffs.apply_atk(100), 110);
+}
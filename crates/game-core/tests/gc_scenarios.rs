@@ -0,0 +1,37 @@
+//! 场景脚本集成测试
+//!
+//! 执行 `scenarios/` 目录下的全部 `.scenario` 脚本，任一断言失败即测试失败
+
+use std::fs;
+use std::path::Path;
+
+use game_core::gc_run_scenario;
+
+#[test]
+fn test_all_scenarios_pass() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("scenarios");
+    let mut paths: Vec<_> = fs::read_dir(&dir)
+        .expect("读取 scenarios 目录")
+        .map(|entry| entry.expect("读取目录项").path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "scenario"))
+        .collect();
+    paths.sort();
+    assert!(!paths.is_empty(), "scenarios 目录为空");
+
+    let mut failures = Vec::new();
+    for path in &paths {
+        let script = fs::read_to_string(path).expect("读取脚本");
+        match gc_run_scenario(&script) {
+            Ok(report) => {
+                for failure in &report.failures {
+                    failures.push(format!(
+                        "{}:{}: {} — {}",
+                        path.display(), failure.line, failure.source, failure.message
+                    ));
+                }
+            }
+            Err(error) => failures.push(format!("{}: {}", path.display(), error)),
+        }
+    }
+    assert!(failures.is_empty(), "场景失败:\n{}", failures.join("\n"));
+}
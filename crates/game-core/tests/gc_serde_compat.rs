@@ -0,0 +1,87 @@
+//! 序列化命名兼容测试
+//!
+//! 线上格式约定: 字段名一律 snake_case，枚举变体与标签一律 PascalCase (与 Rust 名称一致)。
+//! 改名时用 `#[serde(alias = "...")]` 保留旧名称，旧存档与旧客户端发来的数据仍能解析。
+//! `cargo test -p game-core --test gc_serde_compat`
+
+use game_core::*;
+use serde::Serialize;
+
+/// 收集 JSON 中所有对象的键
+fn gc_collect_keys(value: &serde_json::Value, path: &str, out: &mut Vec<String>) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, child) in map {
+                out.push(format!("{}.{}", path, key));
+                gc_collect_keys(child, &format!("{}.{}", path, key), out);
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for (i, child) in items.iter().enumerate() {
+                gc_collect_keys(child, &format!("{}[{}]", path, i), out);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// 断言所有键都是 snake_case
+fn gc_assert_snake_case<T: Serialize>(name: &str, value: &T) {
+    let json = serde_json::to_value(value).expect("序列化失败");
+    let mut keys = Vec::new();
+    gc_collect_keys(&json, name, &mut keys);
+    let bad: Vec<&String> = keys
+        .iter()
+        .filter(|path| {
+            let key = path.rsplit('.').next().unwrap_or_default();
+            !key.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+        })
+        .collect();
+    assert!(bad.is_empty(), "{} 含有非 snake_case 字段: {:?}", name, bad);
+}
+
+#[test]
+fn test_exported_types_use_snake_case_fields() {
+    let mut battle = GcBattleState::gc_new("compat", vec![
+        GcPlayer::gc_new("p1", "玩家1"),
+        GcPlayer::gc_new("p2", "玩家2"),
+    ]);
+    battle.card_pool = GcCardPool::gc_new(GcCardPoolConfig::default());
+    let mut card = gc_get_card_template("card_knight_attack").expect("模板存在");
+    card.id = "c1".to_string();
+    battle.players[0].hand.push(card);
+    gc_assert_snake_case("battle", &battle);
+    gc_assert_snake_case("hints", &gc_suggest_action(&battle, "p1"));
+    gc_assert_snake_case("win_probabilities", &gc_win_probabilities(&battle));
+
+    let players = [("p1".to_string(), "玩家1".to_string()), ("p2".to_string(), "玩家2".to_string())];
+    gc_assert_snake_case("tavern", &GcTavernGame::gc_new(&players));
+    gc_assert_snake_case("town_map", &gc_create_town_map());
+    gc_assert_snake_case("economy", &GcEconomy::default());
+}
+
+#[test]
+fn test_legacy_action_tags_still_parse() {
+    let legacy = r#"{"type":"play_card","player_id":"p1","card_id":"c1","target_id":"p2"}"#;
+    let action: GcAction = gc_parse_json("action", legacy).expect("旧标签可解析");
+    assert!(matches!(action, GcAction::PlayCard { .. }));
+
+    // 重新序列化为当前名称
+    let json = serde_json::to_value(&action).expect("序列化失败");
+    assert_eq!(json["type"], "PlayCard");
+
+    for tag in ["use_skill", "deploy_card", "acquire_card", "end_turn", "surrender"] {
+        let text = format!(
+            r#"{{"type":"{}","player_id":"p1","skill_id":"s","card_id":"c","target_id":"p2","slot_index":0}}"#,
+            tag
+        );
+        assert!(gc_parse_json::<GcAction>("action", &text).is_ok(), "旧标签 {} 无法解析", tag);
+    }
+}
+
+#[test]
+fn test_legacy_target_type_still_parses() {
+    let target: GcTargetType = gc_parse_json("target", r#""self""#).expect("旧名称可解析");
+    assert_eq!(target, GcTargetType::SelfTarget);
+    assert_eq!(serde_json::to_string(&target).unwrap_or_default(), r#""SelfTarget""#);
+}
@@ -0,0 +1,8 @@
+-- 添加玩家卡组表 (主牌与备牌区一并以 JSON 存储)
+CREATE TABLE IF NOT EXISTS player_decks (
+    player_id VARCHAR(255) NOT NULL,
+    deck_id VARCHAR(100) NOT NULL,
+    deck_data JSONB NOT NULL,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    PRIMARY KEY (player_id, deck_id)
+);
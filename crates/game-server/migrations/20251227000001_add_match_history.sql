@@ -0,0 +1,10 @@
+-- 添加对局历史表 (结果以 JSON 存储，如团队副本的伤害统计)
+CREATE TABLE IF NOT EXISTS match_history (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    match_type VARCHAR(50) NOT NULL,
+    player_ids TEXT[] NOT NULL DEFAULT '{}',
+    result_data JSONB NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_match_history_player_ids ON match_history USING GIN (player_ids);
@@ -0,0 +1,6 @@
+-- 添加组织金库表 (余额、升级、成员与捐献以 JSON 存储)
+CREATE TABLE IF NOT EXISTS guild_treasuries (
+    organization VARCHAR(50) PRIMARY KEY,
+    treasury_data JSONB NOT NULL,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
@@ -0,0 +1,3 @@
+-- 存档难度设置 (剧情/普通/困难 + 敌人等级缩放)
+ALTER TABLE player_progress ADD COLUMN IF NOT EXISTS difficulty VARCHAR(20) NOT NULL DEFAULT 'Normal';
+ALTER TABLE player_progress ADD COLUMN IF NOT EXISTS level_scaling BOOLEAN NOT NULL DEFAULT FALSE;
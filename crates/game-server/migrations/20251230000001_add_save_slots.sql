@@ -0,0 +1,12 @@
+-- 存档槽位: 档案、背包、进度按 (player_id, slot) 存储，旧数据归入槽位 0
+ALTER TABLE player_profiles ADD COLUMN IF NOT EXISTS slot INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE player_profiles DROP CONSTRAINT IF EXISTS player_profiles_pkey;
+ALTER TABLE player_profiles ADD PRIMARY KEY (player_id, slot);
+
+ALTER TABLE player_inventories ADD COLUMN IF NOT EXISTS slot INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE player_inventories DROP CONSTRAINT IF EXISTS player_inventories_pkey;
+ALTER TABLE player_inventories ADD PRIMARY KEY (player_id, slot);
+
+ALTER TABLE player_progress ADD COLUMN IF NOT EXISTS slot INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE player_progress DROP CONSTRAINT IF EXISTS player_progress_pkey;
+ALTER TABLE player_progress ADD PRIMARY KEY (player_id, slot);
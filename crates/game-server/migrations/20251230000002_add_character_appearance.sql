@@ -0,0 +1,3 @@
+-- 角色名称与外观 (每个存档槽位一个角色)
+ALTER TABLE player_profiles ADD COLUMN IF NOT EXISTS character_name VARCHAR(32);
+ALTER TABLE player_profiles ADD COLUMN IF NOT EXISTS appearance JSONB;
@@ -0,0 +1,2 @@
+-- RPG 金币 (酒馆名次、卡牌对战胜负换算获得)
+ALTER TABLE player_profiles ADD COLUMN IF NOT EXISTS gold BIGINT NOT NULL DEFAULT 0;
@@ -0,0 +1,9 @@
+-- 添加暂存对局表 (休闲对局每个回合边界自动存档，玩家离开后可恢复)
+CREATE TABLE IF NOT EXISTS parked_battles (
+    room_id VARCHAR(64) PRIMARY KEY,
+    player_ids TEXT[] NOT NULL DEFAULT '{}',
+    parked_data JSONB NOT NULL,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_parked_battles_player_ids ON parked_battles USING GIN (player_ids);
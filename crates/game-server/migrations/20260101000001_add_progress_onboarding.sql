@@ -0,0 +1,2 @@
+-- 新手引导进度 (服务器根据对局事件维护，见 GcOnboardingProgress)
+ALTER TABLE player_progress ADD COLUMN IF NOT EXISTS onboarding JSONB NOT NULL DEFAULT '{}';
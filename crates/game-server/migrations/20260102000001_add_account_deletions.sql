@@ -0,0 +1,8 @@
+-- 账号删除申请 (宽限期内可撤销，到期后清除账号数据并匿名化对局历史)
+CREATE TABLE IF NOT EXISTS account_deletions (
+    player_id VARCHAR(255) PRIMARY KEY,
+    requested_at BIGINT NOT NULL,
+    purge_at BIGINT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_account_deletions_purge_at ON account_deletions(purge_at);
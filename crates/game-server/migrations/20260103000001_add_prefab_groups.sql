@@ -0,0 +1,6 @@
+-- 组合预制体 (地图编辑器与 MCP 代理共用的多实体组合，例如营地)
+CREATE TABLE IF NOT EXISTS prefab_groups (
+    group_id VARCHAR(64) PRIMARY KEY,
+    group_data JSONB NOT NULL,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
@@ -0,0 +1,6 @@
+-- 资源标签 (编辑器资源库与 MCP 素材查询共用，资源名称 -> 标签列表)
+CREATE TABLE IF NOT EXISTS asset_tags (
+    asset_name VARCHAR(255) PRIMARY KEY,
+    tags JSONB NOT NULL,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
@@ -0,0 +1,6 @@
+-- 怪兽皮肤 (按账号保存拥有记录与装备选择，见 GcMonsterSkins)
+CREATE TABLE IF NOT EXISTS player_monster_skins (
+    player_id VARCHAR(255) PRIMARY KEY,
+    skins JSONB NOT NULL,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
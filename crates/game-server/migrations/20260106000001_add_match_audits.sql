@@ -0,0 +1,9 @@
+-- 竞技对局审计记录 (对局结束时写入一次，不再修改，见 GcMatchAudit)
+CREATE TABLE IF NOT EXISTS match_audits (
+    battle_id VARCHAR(255) PRIMARY KEY,
+    player_ids TEXT[] NOT NULL,
+    audit JSONB NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_match_audits_players ON match_audits USING GIN (player_ids);
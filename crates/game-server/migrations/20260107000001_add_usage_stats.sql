@@ -0,0 +1,14 @@
+-- 卡牌/单位使用样本 (对局结束时写入，匿名，见 GcUsageSample)
+CREATE TABLE IF NOT EXISTS usage_samples (
+    id BIGSERIAL PRIMARY KEY,
+    sample JSONB NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
+
+-- 使用统计汇总 (汇总任务累计样本，last_sample_id 之前的样本已处理，见 GcUsageStats)
+CREATE TABLE IF NOT EXISTS usage_summaries (
+    name VARCHAR(64) PRIMARY KEY,
+    stats JSONB NOT NULL,
+    last_sample_id BIGINT NOT NULL DEFAULT 0,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
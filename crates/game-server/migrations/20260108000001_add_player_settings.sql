@@ -0,0 +1,7 @@
+-- 玩家设置 (按键绑定与界面偏好，跨设备同步；revision 用于乐观并发，见 GcSettingsRecord)
+CREATE TABLE IF NOT EXISTS player_settings (
+    player_id VARCHAR(255) PRIMARY KEY,
+    settings JSONB NOT NULL,
+    revision BIGINT NOT NULL DEFAULT 0,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
@@ -0,0 +1,6 @@
+-- 伙伴 (按账号保存伙伴收藏与装备选择，见 GcCompanionCollection)
+CREATE TABLE IF NOT EXISTS player_companions (
+    player_id VARCHAR(255) PRIMARY KEY,
+    companions JSONB NOT NULL,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
@@ -0,0 +1,6 @@
+-- 卡牌收藏 (按账号保存拥有张数、闪卡张数与尘，见 GcCardCollection)
+CREATE TABLE IF NOT EXISTS player_card_collections (
+    player_id VARCHAR(255) PRIMARY KEY,
+    collection JSONB NOT NULL,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
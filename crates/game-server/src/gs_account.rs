@@ -0,0 +1,30 @@
+//! 账号删除清理
+//!
+//! 模块: game-server
+//! 前缀: Gs
+//! 文档: 文档/03-game-server.md
+//!
+//! 后台任务定期清除已过宽限期 (`GsConfig::account_deletion_grace_secs`) 的账号。
+
+use std::time::Duration;
+
+use crate::gs_state::{gs_now, GsAppState};
+
+/// 清理间隔 (秒)
+const GS_ACCOUNT_PURGE_SECS: u64 = 3600;
+
+/// 启动账号清除任务
+pub fn gs_spawn_account_purge(state: GsAppState) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(GS_ACCOUNT_PURGE_SECS));
+        
+        loop {
+            interval.tick().await;
+            
+            let purged = state.gs_purge_due_accounts(gs_now()).await;
+            if purged > 0 {
+                tracing::info!("清除到期账号: {} 个", purged);
+            }
+        }
+    });
+}
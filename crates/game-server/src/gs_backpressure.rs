@@ -0,0 +1,178 @@
+//! 连接出站队列与慢消费者处理
+//!
+//! 模块: game-server
+//! 前缀: Gs
+//! 文档: 文档/03-game-server.md
+//!
+//! 每个 WebSocket 连接有一个有界出站队列 (`ws_outbound_queue_cap`)，由独立的写任务发送，
+//! 连接循环不再被慢客户端阻塞:
+//! - 广播接收落后 (`RecvError::Lagged`) 或出站队列已满时，丢弃的消息计入统计，连接标记为待重同步
+//! - 队列空出一半后推送 `Resync` 与完整状态快照 (房间、战斗、酒馆座位、大厅)，客户端据此整体刷新
+//! - 单个连接重同步 `ws_max_resyncs` 次后仍然跟不上，按慢消费者断开
+//! - 开启故障注入 (`gs_chaos`) 时，入队的消息按概率丢弃 (同样触发重同步)，写任务发送前按概率延迟
+
+use std::sync::Arc;
+
+use axum::extract::ws::{Message, WebSocket};
+use futures_util::stream::SplitSink;
+use futures_util::SinkExt;
+use serde::Serialize;
+use tokio::sync::mpsc;
+use tokio::sync::mpsc::error::TrySendError;
+use tokio::task::JoinHandle;
+
+use crate::gs_chaos::GsChaos;
+
+/// 广播背压统计 (累计值，供监控查询)
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct GsBroadcastStats {
+    /// 广播接收落后次数
+    pub lag_events: u64,
+    /// 广播接收落后丢失的消息数
+    pub lagged_messages: u64,
+    /// 出站队列已满丢弃的消息数
+    pub queue_overflows: u64,
+    /// 推送的重同步快照数
+    pub resyncs: u64,
+    /// 按慢消费者断开的连接数
+    pub slow_consumer_disconnects: u64,
+}
+
+/// 入队结果
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GsPush {
+    /// 已入队
+    Queued,
+    /// 队列已满，消息被丢弃 (连接待重同步)
+    Dropped,
+    /// 故障注入丢弃 (连接待重同步)
+    Faulted,
+    /// 写任务已结束 (连接已关闭)
+    Closed,
+}
+
+/// 重同步状态
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GsResync {
+    /// 不需要重同步
+    Idle,
+    /// 队列空位不足一半，稍后再试
+    Deferred,
+    /// 可以推送快照 (上次重同步后丢失的消息数)
+    Due { dropped: u64 },
+    /// 超过重同步次数上限，应断开连接
+    Disconnect,
+}
+
+/// 连接的出站队列
+pub struct GsOutbound {
+    tx: mpsc::Sender<Message>,
+    /// 上次重同步后丢失的消息数
+    dropped: u64,
+    /// 已推送的重同步次数
+    resyncs: u32,
+    /// 重同步次数上限
+    max_resyncs: u32,
+    /// 故障注入
+    chaos: Option<Arc<GsChaos>>,
+}
+
+impl GsOutbound {
+    /// 创建出站队列 (写任务由调用方负责)
+    pub fn gs_new(tx: mpsc::Sender<Message>, max_resyncs: u32) -> Self {
+        Self { tx, dropped: 0, resyncs: 0, max_resyncs, chaos: None }
+    }
+
+    /// 开启故障注入 (入队时按概率丢弃)
+    pub fn gs_with_chaos(mut self, chaos: Option<Arc<GsChaos>>) -> Self {
+        self.chaos = chaos;
+        self
+    }
+
+    /// 创建出站队列并启动写任务
+    pub fn gs_spawn(
+        sender: SplitSink<WebSocket, Message>,
+        cap: usize,
+        max_resyncs: u32,
+        chaos: Option<Arc<GsChaos>>,
+    ) -> (Self, JoinHandle<()>) {
+        let (tx, rx) = mpsc::channel(cap.max(1));
+        let writer = tokio::spawn(gs_write_loop(sender, rx, chaos.clone()));
+        (Self::gs_new(tx, max_resyncs).gs_with_chaos(chaos), writer)
+    }
+
+    /// 文本消息入队 (不等待)
+    pub fn gs_push(&mut self, text: String) -> GsPush {
+        if self.chaos.as_ref().is_some_and(|chaos| chaos.gs_drop_outbound()) {
+            self.dropped += 1;
+            return GsPush::Faulted;
+        }
+        match self.tx.try_send(Message::Text(text)) {
+            Ok(()) => GsPush::Queued,
+            Err(TrySendError::Full(_)) => {
+                self.dropped += 1;
+                GsPush::Dropped
+            }
+            Err(TrySendError::Closed(_)) => GsPush::Closed,
+        }
+    }
+
+    /// 记录广播接收落后丢失的消息
+    pub fn gs_mark_lagged(&mut self, count: u64) {
+        self.dropped += count;
+    }
+
+    /// 是否待重同步
+    pub fn gs_needs_resync(&self) -> bool {
+        self.dropped > 0
+    }
+
+    /// 写任务是否已结束
+    pub fn gs_is_closed(&self) -> bool {
+        self.tx.is_closed()
+    }
+
+    /// 当前的重同步状态
+    pub fn gs_resync_state(&self) -> GsResync {
+        if !self.gs_needs_resync() {
+            GsResync::Idle
+        } else if self.resyncs >= self.max_resyncs {
+            GsResync::Disconnect
+        } else if self.tx.capacity() * 2 < self.tx.max_capacity() {
+            GsResync::Deferred
+        } else {
+            GsResync::Due { dropped: self.dropped }
+        }
+    }
+
+    /// 推送重同步快照 (整个快照放得下时才入队，避免快照本身再被截断)，返回是否已推送
+    pub fn gs_push_resync(&mut self, snapshot: Vec<String>) -> bool {
+        if self.tx.capacity() < snapshot.len() {
+            return false;
+        }
+        for text in snapshot {
+            if self.tx.try_send(Message::Text(text)).is_err() {
+                return false;
+            }
+        }
+        self.dropped = 0;
+        self.resyncs += 1;
+        true
+    }
+}
+
+/// 写任务: 按顺序发送出站队列中的消息，发送失败时结束
+async fn gs_write_loop(
+    mut sender: SplitSink<WebSocket, Message>,
+    mut rx: mpsc::Receiver<Message>,
+    chaos: Option<Arc<GsChaos>>,
+) {
+    while let Some(msg) = rx.recv().await {
+        if let Some(delay) = chaos.as_ref().and_then(|chaos| chaos.gs_latency()) {
+            tokio::time::sleep(delay).await;
+        }
+        if sender.send(msg).await.is_err() {
+            break;
+        }
+    }
+}
@@ -0,0 +1,92 @@
+//! 断线座位的机器人托管
+//!
+//! 模块: game-server
+//! 前缀: Gs
+//! 文档: 文档/03-game-server.md
+//!
+//! 组队或混战对局中断线的玩家保留座位；超过 `bot_takeover_grace_secs` 后由
+//! `gc_bot_next_action` 代打 (难度见 `bot_difficulty`)，局间换牌时直接跳过。
+//! 机器人的操作与玩家消息走同一个处理函数，广播完全一致；玩家重连 (`ResumeRoom`) 后交还控制。
+
+use std::time::Duration;
+
+use game_core::*;
+
+use crate::gs_state::{gs_now, GsAppState};
+use crate::gs_websocket::{gs_handle_message, GsWsMessage};
+
+/// 调度间隔 (秒)
+const GS_BOT_TICK_SECS: u64 = 1;
+
+/// 单个座位每次调度最多执行的操作数 (超出时强制结束回合)
+const GS_BOT_MAX_STEPS: usize = 20;
+
+/// 启动机器人托管任务
+pub fn gs_spawn_bot_driver(state: GsAppState) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(GS_BOT_TICK_SECS));
+        loop {
+            interval.tick().await;
+            gs_bot_sweep(&state).await;
+        }
+    });
+}
+
+/// 接管超过宽限期的座位，并让所有托管座位行动
+pub(crate) async fn gs_bot_sweep(state: &GsAppState) {
+    for (room_id, player_id) in state.gs_update_bot_seats(gs_now()).await {
+        tracing::info!("机器人接管: 房间 {} (玩家 {})", room_id, player_id);
+        let msg = GsWsMessage::SeatControl { player_id, bot: true };
+        state.gs_broadcast_to_room(&room_id, serde_json::to_string(&msg).unwrap_or_default(), vec![]);
+    }
+
+    for (room_id, player_id) in state.gs_bot_seats().await {
+        gs_play_bot_seat(state, &room_id, &player_id).await;
+    }
+}
+
+/// 托管座位的下一条消息 (出牌、结束回合或跳过换牌；无事可做时为 None)
+async fn gs_bot_next_message(state: &GsAppState, room_id: &str, player_id: &str) -> Option<GsWsMessage> {
+    let room = state.gs_get_room(room_id).await?;
+    if !room.bot_seats.iter().any(|id| id == player_id) || room.pause.gc_is_paused() {
+        return None;
+    }
+
+    let battle = room.battle.as_ref().map(GcEventSourcedBattle::gc_state)?;
+    if !battle.gc_is_finished() {
+        return match gc_bot_next_action(battle, player_id, state.config.bot_difficulty)? {
+            GcAction::PlayCard { card_id, target_id, .. } => Some(GsWsMessage::PlayCard { card_id, target_id: Some(target_id) }),
+            _ => Some(GsWsMessage::EndTurn),
+        };
+    }
+
+    let series = room.series.as_ref()?;
+    let pending = series.phase == GcSeriesPhase::BetweenGames
+        && series.scores.iter().any(|s| s.player_id == player_id && !s.sideboard_submitted);
+    pending.then(|| GsWsMessage::SubmitSideboard { swaps: Vec::new() })
+}
+
+/// 以该座位的身份执行操作，直到回合结束或无事可做
+async fn gs_play_bot_seat(state: &GsAppState, room_id: &str, player_id: &str) {
+    let mut bot_id = Some(player_id.to_string());
+    let mut bot_room = Some(room_id.to_string());
+    let mut lobby_subscribed = false;
+
+    for _ in 0..GS_BOT_MAX_STEPS {
+        let Some(msg) = gs_bot_next_message(state, room_id, player_id).await else {
+            return;
+        };
+        let playing = matches!(msg, GsWsMessage::PlayCard { .. });
+        let responses = gs_handle_message(state, &mut bot_id, &mut bot_room, &mut lobby_subscribed, msg).await;
+        if !playing {
+            return;
+        }
+        if !responses.is_empty() {
+            tracing::warn!("机器人出牌失败: 房间 {} (玩家 {}): {:?}", room_id, player_id, responses);
+            break;
+        }
+    }
+
+    // 出牌失败或操作过多时结束回合，避免对局卡住
+    gs_handle_message(state, &mut bot_id, &mut bot_room, &mut lobby_subscribed, GsWsMessage::EndTurn).await;
+}
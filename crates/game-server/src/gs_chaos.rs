@@ -0,0 +1,181 @@
+//! 故障注入 (混沌测试模式，仅开发使用)
+//!
+//! 模块: game-server
+//! 前缀: Gs
+//! 文档: 文档/03-game-server.md
+//!
+//! 按配置随机注入网络与数据库故障，在真实玩家遇到之前演练重连、幂等与重同步路径:
+//! - 丢弃客户端消息: 连接循环收到后直接丢弃，客户端须自行重试
+//! - 丢弃服务器消息: 出站入队时丢弃并计入丢失，连接随后按背压规则推送重同步快照
+//! - 人为延迟: 写任务发送每条消息前等待随机时长 (不超过 `latency_ms`，顺序不变)
+//! - 数据库错误: 每次取连接池时按概率返回注入的错误
+//!
+//! ## 规则
+//! - `CHAOS_ENABLED` 开启，只在调试构建中生效；发布构建忽略并记录警告
+//! - 各概率取值 0-1 (超出范围截断)；0 表示不注入
+//! - 随机序列由 `CHAOS_SEED` 决定，同一种子、同一调用顺序得到相同的故障序列，便于复现
+//! - 重同步快照本身不注入丢弃，否则连接无法恢复
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+/// 故障注入配置
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct GsChaosConfig {
+    /// 是否开启
+    pub enabled: bool,
+    /// 消息丢弃概率 (客户端与服务器消息各自独立判定)
+    pub drop_rate: f64,
+    /// 每条出站消息的最大额外延迟 (毫秒)
+    pub latency_ms: u64,
+    /// 数据库操作失败概率
+    pub db_error_rate: f64,
+    /// 随机种子
+    pub seed: u64,
+}
+
+impl GsChaosConfig {
+    /// 从环境变量加载 (`CHAOS_*`)
+    pub fn gs_from_env() -> Self {
+        let rate = |name: &str| std::env::var(name)
+            .ok()
+            .and_then(|s| s.parse::<f64>().ok())
+            .map_or(0.0, |r| r.clamp(0.0, 1.0));
+        Self {
+            enabled: std::env::var("CHAOS_ENABLED")
+                .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+                .unwrap_or(false),
+            drop_rate: rate("CHAOS_DROP_RATE"),
+            latency_ms: std::env::var("CHAOS_LATENCY_MS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(0),
+            db_error_rate: rate("CHAOS_DB_ERROR_RATE"),
+            seed: std::env::var("CHAOS_SEED")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or_else(|| uuid::Uuid::new_v4().as_u64_pair().0),
+        }
+    }
+}
+
+/// 故障注入统计 (累计值，供监控查询)
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct GsChaosStats {
+    /// 是否开启
+    pub enabled: bool,
+    /// 丢弃的客户端消息数
+    pub dropped_inbound: u64,
+    /// 丢弃的服务器消息数
+    pub dropped_outbound: u64,
+    /// 被延迟的消息数
+    pub delayed: u64,
+    /// 注入的数据库错误数
+    pub db_errors: u64,
+}
+
+/// 故障注入器 (各连接与数据库共享)
+#[derive(Debug)]
+pub struct GsChaos {
+    config: GsChaosConfig,
+    /// 随机数状态 (splitmix64)
+    rng: AtomicU64,
+    dropped_inbound: AtomicU64,
+    dropped_outbound: AtomicU64,
+    delayed: AtomicU64,
+    db_errors: AtomicU64,
+}
+
+impl GsChaos {
+    /// 按配置创建 (不检查开关与构建类型)
+    pub fn gs_new(config: GsChaosConfig) -> Self {
+        Self {
+            rng: AtomicU64::new(config.seed),
+            config,
+            dropped_inbound: AtomicU64::new(0),
+            dropped_outbound: AtomicU64::new(0),
+            delayed: AtomicU64::new(0),
+            db_errors: AtomicU64::new(0),
+        }
+    }
+
+    /// 配置开启且为调试构建时创建
+    pub fn gs_from_config(config: &GsChaosConfig) -> Option<Arc<Self>> {
+        if !config.enabled {
+            return None;
+        }
+        if !cfg!(debug_assertions) {
+            tracing::warn!("⚠️ 发布构建不支持故障注入，已忽略 CHAOS_ENABLED");
+            return None;
+        }
+        tracing::warn!(
+            "🧨 故障注入已开启: 丢弃 {:.0}%，延迟 ≤{}ms，数据库错误 {:.0}%，种子 {}",
+            config.drop_rate * 100.0,
+            config.latency_ms,
+            config.db_error_rate * 100.0,
+            config.seed,
+        );
+        Some(Arc::new(Self::gs_new(config.clone())))
+    }
+
+    /// 下一个 [0, 1) 随机数
+    fn gs_roll(&self) -> f64 {
+        const GAMMA: u64 = 0x9E37_79B9_7F4A_7C15;
+        let mut z = self.rng.fetch_add(GAMMA, Ordering::Relaxed).wrapping_add(GAMMA);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+        (z >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// 是否丢弃这条客户端消息
+    pub fn gs_drop_inbound(&self) -> bool {
+        let drop = self.gs_roll() < self.config.drop_rate;
+        if drop {
+            self.dropped_inbound.fetch_add(1, Ordering::Relaxed);
+        }
+        drop
+    }
+
+    /// 是否丢弃这条服务器消息
+    pub fn gs_drop_outbound(&self) -> bool {
+        let drop = self.gs_roll() < self.config.drop_rate;
+        if drop {
+            self.dropped_outbound.fetch_add(1, Ordering::Relaxed);
+        }
+        drop
+    }
+
+    /// 这条出站消息的额外延迟 (未配置延迟时为 None)
+    pub fn gs_latency(&self) -> Option<Duration> {
+        if self.config.latency_ms == 0 {
+            return None;
+        }
+        let ms = (self.gs_roll() * (self.config.latency_ms + 1) as f64) as u64;
+        self.delayed.fetch_add(1, Ordering::Relaxed);
+        Some(Duration::from_millis(ms.min(self.config.latency_ms)))
+    }
+
+    /// 数据库操作前调用，按概率返回注入的错误
+    pub fn gs_db_fault(&self) -> anyhow::Result<()> {
+        if self.gs_roll() < self.config.db_error_rate {
+            self.db_errors.fetch_add(1, Ordering::Relaxed);
+            anyhow::bail!("故障注入: 数据库错误");
+        }
+        Ok(())
+    }
+
+    /// 统计快照
+    pub fn gs_stats(&self) -> GsChaosStats {
+        GsChaosStats {
+            enabled: true,
+            dropped_inbound: self.dropped_inbound.load(Ordering::Relaxed),
+            dropped_outbound: self.dropped_outbound.load(Ordering::Relaxed),
+            delayed: self.delayed.load(Ordering::Relaxed),
+            db_errors: self.db_errors.load(Ordering::Relaxed),
+        }
+    }
+}
@@ -4,8 +4,19 @@
 //! 前缀: Gs
 //! 文档: 文档/03-game-server.md
 
+use std::path::PathBuf;
+
+use game_core::{
+    gc_parse_mcp_keys, GcBotDifficulty, GcMcpKey, GcParkPolicy, GcReplayPolicy, GC_DEFAULT_TIME_RATIO, GC_PARK_IDLE_TTL_SECS, GC_PARK_MAX_AGE_SECS,
+    GC_ACCOUNT_DELETION_GRACE_SECS, GC_PAUSE_BUDGET_SECS,
+};
 use serde::Deserialize;
 
+use crate::gs_chaos::GsChaosConfig;
+
+/// 默认静态资源根目录 (相对工作目录，开发时为仓库根目录)
+const GS_DEFAULT_ASSET_ROOT: &str = "client/public";
+
 /// 服务器配置
 #[derive(Clone, Debug, Deserialize)]
 pub struct GsConfig {
@@ -23,6 +34,63 @@ pub struct GsConfig {
     
     /// 每房间最大玩家数
     pub max_players_per_room: usize,
+    
+    /// 战斗状态广播附带胜率估算 (观战动量条)
+    pub broadcast_win_probability: bool,
+    
+    /// 暂存对局过期策略
+    pub park_policy: GcParkPolicy,
+    
+    /// 房间内玩家全部离线超过该时间 (秒) 后关闭房间
+    pub room_orphan_ttl_secs: u64,
+    
+    /// 玩家超过该时间 (秒) 没有任何消息 (含心跳) 视为离线
+    pub presence_ttl_secs: u64,
+    
+    /// 静态资源根目录 (其下的 assets/ 通过 /assets 提供)
+    pub asset_root: PathBuf,
+    
+    /// 静态资源缓存时间 (秒)
+    pub asset_max_age_secs: u64,
+    
+    /// 客户端内容版本不一致 (或未上报) 时拒绝登录，否则只警告
+    pub strict_content_version: bool,
+    
+    /// MCP API Key 列表 (为空时拒绝所有 MCP 请求)
+    pub mcp_keys: Vec<GcMcpKey>,
+    
+    /// 世界时钟倍率 (游戏秒 / 现实秒)
+    pub world_time_ratio: u32,
+    
+    /// 回放分享的大小限制与保留策略
+    pub replay_policy: GcReplayPolicy,
+    
+    /// 组队或混战对局中断线超过该时间 (秒) 后由机器人托管座位
+    pub bot_takeover_grace_secs: u64,
+    
+    /// 托管机器人难度
+    pub bot_difficulty: GcBotDifficulty,
+    
+    /// 账号删除宽限期 (秒)，期间可以撤销
+    pub account_deletion_grace_secs: u64,
+    
+    /// 每个 WebSocket 连接的出站队列容量 (条)
+    pub ws_outbound_queue_cap: usize,
+    
+    /// 单个连接最多自动重同步次数，超过后按慢消费者断开
+    pub ws_max_resyncs: u32,
+    
+    /// 心跳超时 (秒)，连接超过该时间未收到任何消息时关闭并按断线处理
+    pub heartbeat_timeout_secs: u64,
+    
+    /// 心跳超时检查间隔 (秒)
+    pub heartbeat_check_secs: u64,
+    
+    /// 每个房间的对局暂停额度 (秒)
+    pub pause_budget_secs: u64,
+    
+    /// 故障注入 (仅开发使用，发布构建忽略)
+    pub chaos: GsChaosConfig,
 }
 
 impl Default for GsConfig {
@@ -33,6 +101,25 @@ impl Default for GsConfig {
             jwt_secret: "dev-secret-change-in-production".to_string(),
             max_rooms: 100,
             max_players_per_room: 4,
+            broadcast_win_probability: false,
+            park_policy: GcParkPolicy::default(),
+            room_orphan_ttl_secs: 300,
+            presence_ttl_secs: 900,
+            asset_root: PathBuf::from(GS_DEFAULT_ASSET_ROOT),
+            asset_max_age_secs: 3600,
+            strict_content_version: false,
+            mcp_keys: Vec::new(),
+            world_time_ratio: GC_DEFAULT_TIME_RATIO,
+            replay_policy: GcReplayPolicy::default(),
+            bot_takeover_grace_secs: 30,
+            bot_difficulty: GcBotDifficulty::default(),
+            account_deletion_grace_secs: GC_ACCOUNT_DELETION_GRACE_SECS,
+            ws_outbound_queue_cap: 256,
+            ws_max_resyncs: 5,
+            heartbeat_timeout_secs: 75,
+            heartbeat_check_secs: 5,
+            pause_budget_secs: GC_PAUSE_BUDGET_SECS,
+            chaos: GsChaosConfig::default(),
         }
     }
 }
@@ -57,6 +144,114 @@ impl GsConfig {
                 .ok()
                 .and_then(|p| p.parse().ok())
                 .unwrap_or(4),
+            broadcast_win_probability: std::env::var("BROADCAST_WIN_PROBABILITY")
+                .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+                .unwrap_or(false),
+            park_policy: GcParkPolicy {
+                idle_ttl_secs: std::env::var("PARK_IDLE_TTL_SECS")
+                    .ok()
+                    .and_then(|s| s.parse().ok())
+                    .unwrap_or(GC_PARK_IDLE_TTL_SECS),
+                max_age_secs: std::env::var("PARK_MAX_AGE_SECS")
+                    .ok()
+                    .and_then(|s| s.parse().ok())
+                    .unwrap_or(GC_PARK_MAX_AGE_SECS),
+            },
+            room_orphan_ttl_secs: std::env::var("ROOM_ORPHAN_TTL_SECS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(300),
+            presence_ttl_secs: std::env::var("PRESENCE_TTL_SECS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(900),
+            asset_root: std::env::var("ASSET_ROOT")
+                .map(PathBuf::from)
+                .unwrap_or_else(|_| PathBuf::from(GS_DEFAULT_ASSET_ROOT)),
+            asset_max_age_secs: std::env::var("ASSET_MAX_AGE_SECS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(3600),
+            strict_content_version: std::env::var("STRICT_CONTENT_VERSION")
+                .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+                .unwrap_or(false),
+            mcp_keys: gs_load_mcp_keys(),
+            world_time_ratio: std::env::var("WORLD_TIME_RATIO")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .filter(|ratio| *ratio > 0)
+                .unwrap_or(GC_DEFAULT_TIME_RATIO),
+            replay_policy: GcReplayPolicy {
+                max_bytes: std::env::var("REPLAY_MAX_BYTES")
+                    .ok()
+                    .and_then(|s| s.parse().ok())
+                    .unwrap_or(GcReplayPolicy::default().max_bytes),
+                max_events: std::env::var("REPLAY_MAX_EVENTS")
+                    .ok()
+                    .and_then(|s| s.parse().ok())
+                    .unwrap_or(GcReplayPolicy::default().max_events),
+                retention_secs: std::env::var("REPLAY_RETENTION_SECS")
+                    .ok()
+                    .and_then(|s| s.parse().ok())
+                    .unwrap_or(GcReplayPolicy::default().retention_secs),
+                max_entries: std::env::var("REPLAY_MAX_STORED")
+                    .ok()
+                    .and_then(|s| s.parse().ok())
+                    .unwrap_or(GcReplayPolicy::default().max_entries),
+            },
+            bot_takeover_grace_secs: std::env::var("BOT_TAKEOVER_GRACE_SECS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(30),
+            bot_difficulty: std::env::var("BOT_DIFFICULTY")
+                .ok()
+                .and_then(|s| GcBotDifficulty::gc_from_name(&s))
+                .unwrap_or_default(),
+            account_deletion_grace_secs: std::env::var("ACCOUNT_DELETION_GRACE_SECS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(GC_ACCOUNT_DELETION_GRACE_SECS),
+            ws_outbound_queue_cap: std::env::var("WS_OUTBOUND_QUEUE_CAP")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .filter(|cap| *cap > 0)
+                .unwrap_or(256),
+            ws_max_resyncs: std::env::var("WS_MAX_RESYNCS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(5),
+            heartbeat_timeout_secs: std::env::var("HEARTBEAT_TIMEOUT_SECS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .filter(|secs| *secs > 0)
+                .unwrap_or(75),
+            heartbeat_check_secs: std::env::var("HEARTBEAT_CHECK_SECS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .filter(|secs| *secs > 0)
+                .unwrap_or(5),
+            pause_budget_secs: std::env::var("PAUSE_BUDGET_SECS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(GC_PAUSE_BUDGET_SECS),
+            chaos: GsChaosConfig::gs_from_env(),
+        }
+    }
+}
+
+/// 载入 MCP API Key (MCP_KEYS，格式见 `gc_parse_mcp_keys`；格式错误时不启用任何 Key)
+fn gs_load_mcp_keys() -> Vec<GcMcpKey> {
+    let spec = std::env::var("MCP_KEYS").unwrap_or_default();
+    match gc_parse_mcp_keys(&spec) {
+        Ok(keys) => {
+            if keys.is_empty() {
+                tracing::warn!("⚠️ 未配置 MCP_KEYS - MCP 接口将拒绝所有请求");
+            }
+            keys
+        }
+        Err(e) => {
+            tracing::error!("❌ MCP_KEYS 无效: {} - MCP 接口将拒绝所有请求", e);
+            Vec::new()
         }
     }
 }
@@ -6,7 +6,8 @@
 
 use sqlx::{postgres::PgPoolOptions, Pool, Postgres};
 use std::env;
-use game_core::{GcInventory, GcProfessionType};
+use std::sync::Arc;
+use game_core::{gc_anonymize_json, gc_anonymized_player_id, GcAccountDeletion, GcAppearance, GcCardCollection, GcCharacter, GcCompanionCollection, GcDeck, GcDifficulty, GcGuildTreasury, GcInventory, GcMatchAudit, GcMonsterSkins, GcUsageSample, GcUsageStats, gc_migrate_settings, GcSettingsRecord, GcOnboardingProgress, GcParkedBattle, GcPrefabGroup, GcProfessionType, GcRewardGrant, GcSaveSlotSummary};
 use argon2::{
     password_hash::{
         rand_core::OsRng,
@@ -16,10 +17,14 @@ use argon2::{
 };
 use uuid::Uuid;
 
+use crate::gs_chaos::GsChaos;
+
 /// 数据库连接池
 #[derive(Clone)]
 pub struct GsDatabase {
     pool: Pool<Postgres>,
+    /// 故障注入 (开发模式)
+    chaos: Option<Arc<GsChaos>>,
 }
 
 impl GsDatabase {
@@ -38,7 +43,21 @@ impl GsDatabase {
             .run(&pool)
             .await?;
             
-        Ok(Self { pool })
+        Ok(Self { pool, chaos: None })
+    }
+
+    /// 开启故障注入: 之后每次取连接池都可能返回注入的错误
+    pub fn gs_with_chaos(mut self, chaos: Option<Arc<GsChaos>>) -> Self {
+        self.chaos = chaos;
+        self
+    }
+
+    /// 连接池 (开启故障注入时按概率返回错误)
+    fn gs_pool(&self) -> anyhow::Result<&Pool<Postgres>> {
+        if let Some(chaos) = &self.chaos {
+            chaos.gs_db_fault()?;
+        }
+        Ok(&self.pool)
     }
 
     /// 创建新用户
@@ -58,7 +77,7 @@ impl GsDatabase {
         )
         .bind(username)
         .bind(password_hash)
-        .fetch_one(&self.pool)
+        .fetch_one(self.gs_pool()?)
         .await?;
         
         Ok(row.0)
@@ -70,7 +89,7 @@ impl GsDatabase {
             "SELECT password_hash, id FROM users WHERE username = $1"
         )
         .bind(username)
-        .fetch_optional(&self.pool)
+        .fetch_optional(self.gs_pool()?)
         .await?;
         
         if let Some((hash, id)) = row {
@@ -85,12 +104,13 @@ impl GsDatabase {
     }
     
     /// 获取玩家背包
-    pub async fn gs_get_inventory(&self, player_id: &str) -> anyhow::Result<Option<GcInventory>> {
+    pub async fn gs_get_inventory(&self, player_id: &str, slot: u32) -> anyhow::Result<Option<GcInventory>> {
         let row: Option<(serde_json::Value,)> = sqlx::query_as(
-            "SELECT inventory_data FROM player_inventories WHERE player_id = $1"
+            "SELECT inventory_data FROM player_inventories WHERE player_id = $1 AND slot = $2"
         )
         .bind(player_id)
-        .fetch_optional(&self.pool)
+        .bind(slot as i32)
+        .fetch_optional(self.gs_pool()?)
         .await?;
         
         if let Some((data,)) = row {
@@ -102,52 +122,41 @@ impl GsDatabase {
     }
     
     /// 保存玩家背包
-    pub async fn gs_save_inventory(&self, player_id: &str, inventory: &GcInventory) -> anyhow::Result<()> {
+    pub async fn gs_save_inventory(&self, player_id: &str, slot: u32, inventory: &GcInventory) -> anyhow::Result<()> {
         let data = serde_json::to_value(inventory)?;
         
         sqlx::query(
             r#"
-            INSERT INTO player_inventories (player_id, inventory_data, updated_at)
-            VALUES ($1, $2, NOW())
-            ON CONFLICT (player_id) 
-            DO UPDATE SET inventory_data = $2, updated_at = NOW()
+            INSERT INTO player_inventories (player_id, slot, inventory_data, updated_at)
+            VALUES ($1, $2, $3, NOW())
+            ON CONFLICT (player_id, slot) 
+            DO UPDATE SET inventory_data = $3, updated_at = NOW()
             "#
         )
         .bind(player_id)
+        .bind(slot as i32)
         .bind(data)
-        .execute(&self.pool)
+        .execute(self.gs_pool()?)
         .await?;
         
         Ok(())
     }
     
     /// 获取玩家职业
-    pub async fn gs_get_profession(&self, player_id: &str) -> anyhow::Result<Option<GcProfessionType>> {
-        let row: Option<(String,)> = sqlx::query_as(
-            "SELECT profession FROM player_profiles WHERE player_id = $1"
+    pub async fn gs_get_profession(&self, player_id: &str, slot: u32) -> anyhow::Result<Option<GcProfessionType>> {
+        let row: Option<(Option<String>,)> = sqlx::query_as(
+            "SELECT profession FROM player_profiles WHERE player_id = $1 AND slot = $2"
         )
         .bind(player_id)
-        .fetch_optional(&self.pool)
+        .bind(slot as i32)
+        .fetch_optional(self.gs_pool()?)
         .await?;
         
-        if let Some((prof_str,)) = row {
-            // 简单的字符串转换，实际可能需要更健壮的转换
-            let prof = match prof_str.as_str() {
-                "Knight" => GcProfessionType::Knight,
-                "Swordsman" => GcProfessionType::Swordsman,
-                "Warlock" => GcProfessionType::Warlock,
-                "Gunner" => GcProfessionType::Gunner,
-                "Assassin" => GcProfessionType::Assassin,
-                _ => return Ok(None),
-            };
-            Ok(Some(prof))
-        } else {
-            Ok(None)
-        }
+        Ok(row.and_then(|(prof_str,)| prof_str).as_deref().and_then(gs_parse_profession))
     }
     
     /// 保存玩家职业
-    pub async fn gs_save_profession(&self, player_id: &str, profession: GcProfessionType) -> anyhow::Result<()> {
+    pub async fn gs_save_profession(&self, player_id: &str, slot: u32, profession: GcProfessionType) -> anyhow::Result<()> {
         let prof_str = match profession {
             GcProfessionType::Knight => "Knight",
             GcProfessionType::Swordsman => "Swordsman",
@@ -158,41 +167,676 @@ impl GsDatabase {
         
         sqlx::query(
             r#"
-            INSERT INTO player_profiles (player_id, profession, updated_at)
-            VALUES ($1, $2, NOW())
-            ON CONFLICT (player_id) 
-            DO UPDATE SET profession = $2, updated_at = NOW()
+            INSERT INTO player_profiles (player_id, slot, profession, updated_at)
+            VALUES ($1, $2, $3, NOW())
+            ON CONFLICT (player_id, slot) 
+            DO UPDATE SET profession = $3, updated_at = NOW()
             "#
         )
         .bind(player_id)
+        .bind(slot as i32)
         .bind(prof_str)
-        .execute(&self.pool)
+        .execute(self.gs_pool()?)
+        .await?;
+        
+        Ok(())
+    }
+    
+    /// 获取存档槽位的角色 (未创建角色时为 None)
+    pub async fn gs_get_character(&self, player_id: &str, slot: u32) -> anyhow::Result<Option<GcCharacter>> {
+        let row: Option<(Option<String>, Option<String>, Option<serde_json::Value>)> = sqlx::query_as(
+            "SELECT character_name, profession, appearance FROM player_profiles WHERE player_id = $1 AND slot = $2"
+        )
+        .bind(player_id)
+        .bind(slot as i32)
+        .fetch_optional(self.gs_pool()?)
+        .await?;
+        
+        let Some((Some(name), Some(prof_str), appearance)) = row else {
+            return Ok(None);
+        };
+        let Some(profession) = gs_parse_profession(&prof_str) else {
+            return Ok(None);
+        };
+        let appearance: GcAppearance = match appearance {
+            Some(data) => serde_json::from_value(data)?,
+            None => GcAppearance::default(),
+        };
+        Ok(Some(GcCharacter { name, profession, appearance }))
+    }
+    
+    /// 创建角色 (槽位已有角色时返回 false)
+    pub async fn gs_create_character(&self, player_id: &str, slot: u32, character: &GcCharacter) -> anyhow::Result<bool> {
+        let appearance = serde_json::to_value(character.appearance)?;
+        
+        let result = sqlx::query(
+            r#"
+            INSERT INTO player_profiles (player_id, slot, character_name, profession, appearance, updated_at)
+            VALUES ($1, $2, $3, $4, $5, NOW())
+            ON CONFLICT (player_id, slot) 
+            DO UPDATE SET character_name = $3, profession = $4, appearance = $5, updated_at = NOW()
+            WHERE player_profiles.character_name IS NULL
+            "#
+        )
+        .bind(player_id)
+        .bind(slot as i32)
+        .bind(&character.name)
+        .bind(format!("{:?}", character.profession))
+        .bind(appearance)
+        .execute(self.gs_pool()?)
+        .await?;
+        
+        Ok(result.rows_affected() > 0)
+    }
+    
+    /// 重命名角色 (槽位没有角色时返回 false)
+    pub async fn gs_rename_character(&self, player_id: &str, slot: u32, name: &str) -> anyhow::Result<bool> {
+        let result = sqlx::query(
+            r#"
+            UPDATE player_profiles SET character_name = $3, updated_at = NOW()
+            WHERE player_id = $1 AND slot = $2 AND character_name IS NOT NULL
+            "#
+        )
+        .bind(player_id)
+        .bind(slot as i32)
+        .bind(name)
+        .execute(self.gs_pool()?)
+        .await?;
+        
+        Ok(result.rows_affected() > 0)
+    }
+    
+    /// 发放对局奖励 (累加到存档槽位的金币与经验)
+    pub async fn gs_grant_reward(&self, player_id: &str, slot: u32, grant: &GcRewardGrant) -> anyhow::Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO player_profiles (player_id, slot, gold, exp, updated_at)
+            VALUES ($1, $2, $3, $4, NOW())
+            ON CONFLICT (player_id, slot) 
+            DO UPDATE SET
+                gold = player_profiles.gold + $3,
+                exp = COALESCE(player_profiles.exp, 0) + $4,
+                updated_at = NOW()
+            "#
+        )
+        .bind(player_id)
+        .bind(slot as i32)
+        .bind(grant.gold as i64)
+        .bind(grant.xp as i64)
+        .execute(self.gs_pool()?)
+        .await?;
+        
+        Ok(())
+    }
+    
+    // =========================================================================
+    // 卡组 API
+    // =========================================================================
+    
+    /// 获取玩家所有卡组 (含备牌区)
+    pub async fn gs_get_decks(&self, player_id: &str) -> anyhow::Result<Vec<GcDeck>> {
+        let rows: Vec<(serde_json::Value,)> = sqlx::query_as(
+            "SELECT deck_data FROM player_decks WHERE player_id = $1 ORDER BY deck_id"
+        )
+        .bind(player_id)
+        .fetch_all(self.gs_pool()?)
+        .await?;
+        
+        rows.into_iter()
+            .map(|(data,)| Ok(serde_json::from_value(data)?))
+            .collect()
+    }
+    
+    /// 保存玩家卡组 (含备牌区)
+    pub async fn gs_save_deck(&self, player_id: &str, deck: &GcDeck) -> anyhow::Result<()> {
+        let data = serde_json::to_value(deck)?;
+        
+        sqlx::query(
+            r#"
+            INSERT INTO player_decks (player_id, deck_id, deck_data, updated_at)
+            VALUES ($1, $2, $3, NOW())
+            ON CONFLICT (player_id, deck_id) 
+            DO UPDATE SET deck_data = $3, updated_at = NOW()
+            "#
+        )
+        .bind(player_id)
+        .bind(&deck.id)
+        .bind(data)
+        .execute(self.gs_pool()?)
+        .await?;
+        
+        Ok(())
+    }
+    
+    // =========================================================================
+    // 组织金库 API
+    // =========================================================================
+    
+    /// 获取所有组织金库
+    pub async fn gs_get_guild_treasuries(&self) -> anyhow::Result<Vec<GcGuildTreasury>> {
+        let rows: Vec<(serde_json::Value,)> = sqlx::query_as(
+            "SELECT treasury_data FROM guild_treasuries"
+        )
+        .fetch_all(self.gs_pool()?)
+        .await?;
+        
+        rows.into_iter()
+            .map(|(data,)| Ok(serde_json::from_value(data)?))
+            .collect()
+    }
+    
+    /// 保存组织金库
+    pub async fn gs_save_guild_treasury(&self, treasury: &GcGuildTreasury) -> anyhow::Result<()> {
+        let data = serde_json::to_value(treasury)?;
+        
+        sqlx::query(
+            r#"
+            INSERT INTO guild_treasuries (organization, treasury_data, updated_at)
+            VALUES ($1, $2, NOW())
+            ON CONFLICT (organization) 
+            DO UPDATE SET treasury_data = $2, updated_at = NOW()
+            "#
+        )
+        .bind(format!("{:?}", treasury.organization))
+        .bind(data)
+        .execute(self.gs_pool()?)
+        .await?;
+        
+        Ok(())
+    }
+
+    /// 捐献: 扣除捐献者存档槽位的金币并保存金库 (同一事务)，金币不足时返回 false 且不做修改
+    pub async fn gs_donate_to_guild(
+        &self,
+        player_id: &str,
+        slot: u32,
+        amount: u64,
+        treasury: &GcGuildTreasury,
+    ) -> anyhow::Result<bool> {
+        let data = serde_json::to_value(treasury)?;
+        let mut tx = self.gs_pool()?.begin().await?;
+
+        let debited = sqlx::query(
+            "UPDATE player_profiles SET gold = gold - $3, updated_at = NOW() \
+             WHERE player_id = $1 AND slot = $2 AND gold >= $3"
+        )
+        .bind(player_id)
+        .bind(slot as i32)
+        .bind(amount as i64)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        if debited == 0 {
+            tx.rollback().await?;
+            return Ok(false);
+        }
+
+        sqlx::query(
+            r#"
+            INSERT INTO guild_treasuries (organization, treasury_data, updated_at)
+            VALUES ($1, $2, NOW())
+            ON CONFLICT (organization)
+            DO UPDATE SET treasury_data = $2, updated_at = NOW()
+            "#
+        )
+        .bind(format!("{:?}", treasury.organization))
+        .bind(data)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        Ok(true)
+    }
+
+    // =========================================================================
+    // 对局历史 API
+    // =========================================================================
+    
+    /// 保存对局结果
+    pub async fn gs_save_match_history(
+        &self,
+        match_type: &str,
+        player_ids: &[String],
+        result: serde_json::Value,
+    ) -> anyhow::Result<()> {
+        sqlx::query(
+            "INSERT INTO match_history (match_type, player_ids, result_data) VALUES ($1, $2, $3)"
+        )
+        .bind(match_type)
+        .bind(player_ids)
+        .bind(result)
+        .execute(self.gs_pool()?)
         .await?;
         
         Ok(())
     }
     
+    // =========================================================================
+    // 对局审计 API
+    // =========================================================================
+    
+    /// 保存对局审计记录 (只写入一次，已存在时保持原记录)
+    pub async fn gs_save_match_audit(&self, audit: &GcMatchAudit) -> anyhow::Result<()> {
+        let player_ids: Vec<String> = audit.players.iter().map(|p| p.id.clone()).collect();
+        sqlx::query(
+            "INSERT INTO match_audits (battle_id, player_ids, audit) VALUES ($1, $2, $3) ON CONFLICT (battle_id) DO NOTHING"
+        )
+        .bind(&audit.battle_id)
+        .bind(&player_ids)
+        .bind(serde_json::to_value(audit)?)
+        .execute(self.gs_pool()?)
+        .await?;
+        
+        Ok(())
+    }
+    
+    /// 获取对局审计记录
+    pub async fn gs_get_match_audit(&self, battle_id: &str) -> anyhow::Result<Option<GcMatchAudit>> {
+        let row: Option<(serde_json::Value,)> = sqlx::query_as(
+            "SELECT audit FROM match_audits WHERE battle_id = $1"
+        )
+        .bind(battle_id)
+        .fetch_optional(self.gs_pool()?)
+        .await?;
+        
+        Ok(match row {
+            Some((data,)) => Some(serde_json::from_value(data)?),
+            None => None,
+        })
+    }
+    
+    // =========================================================================
+    // 使用统计 API
+    // =========================================================================
+    
+    /// 写入使用样本
+    pub async fn gs_save_usage_samples(&self, samples: &[GcUsageSample]) -> anyhow::Result<()> {
+        for sample in samples {
+            sqlx::query("INSERT INTO usage_samples (sample) VALUES ($1)")
+                .bind(serde_json::to_value(sample)?)
+                .execute(self.gs_pool()?)
+                .await?;
+        }
+        
+        Ok(())
+    }
+    
+    /// 读取指定序号之后的使用样本 (按序号升序)
+    pub async fn gs_get_usage_samples_after(&self, after_id: i64, limit: i64) -> anyhow::Result<Vec<(i64, GcUsageSample)>> {
+        let rows: Vec<(i64, serde_json::Value)> = sqlx::query_as(
+            "SELECT id, sample FROM usage_samples WHERE id > $1 ORDER BY id LIMIT $2"
+        )
+        .bind(after_id)
+        .bind(limit)
+        .fetch_all(self.gs_pool()?)
+        .await?;
+        
+        rows.into_iter()
+            .map(|(id, data)| Ok((id, serde_json::from_value(data)?)))
+            .collect()
+    }
+    
+    /// 读取使用统计汇总与已处理到的样本序号
+    pub async fn gs_get_usage_summary(&self, name: &str) -> anyhow::Result<(GcUsageStats, i64)> {
+        let row: Option<(serde_json::Value, i64)> = sqlx::query_as(
+            "SELECT stats, last_sample_id FROM usage_summaries WHERE name = $1"
+        )
+        .bind(name)
+        .fetch_optional(self.gs_pool()?)
+        .await?;
+        
+        match row {
+            Some((data, last_id)) => Ok((serde_json::from_value(data)?, last_id)),
+            None => Ok((GcUsageStats::default(), 0)),
+        }
+    }
+    
+    /// 保存使用统计汇总
+    pub async fn gs_save_usage_summary(&self, name: &str, stats: &GcUsageStats, last_sample_id: i64) -> anyhow::Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO usage_summaries (name, stats, last_sample_id, updated_at)
+            VALUES ($1, $2, $3, NOW())
+            ON CONFLICT (name)
+            DO UPDATE SET stats = $2, last_sample_id = $3, updated_at = NOW()
+            "#
+        )
+        .bind(name)
+        .bind(serde_json::to_value(stats)?)
+        .bind(last_sample_id)
+        .execute(self.gs_pool()?)
+        .await?;
+        
+        Ok(())
+    }
+    
+    // =========================================================================
+    // 暂存对局 API
+    // =========================================================================
+    
+    /// 保存暂存对局 (同一房间覆盖)
+    pub async fn gs_save_parked_battle(&self, parked: &GcParkedBattle) -> anyhow::Result<()> {
+        let data = serde_json::to_value(parked)?;
+        
+        sqlx::query(
+            r#"
+            INSERT INTO parked_battles (room_id, player_ids, parked_data, updated_at)
+            VALUES ($1, $2, $3, NOW())
+            ON CONFLICT (room_id)
+            DO UPDATE SET player_ids = $2, parked_data = $3, updated_at = NOW()
+            "#
+        )
+        .bind(&parked.room_id)
+        .bind(parked.gc_player_ids())
+        .bind(data)
+        .execute(self.gs_pool()?)
+        .await?;
+        
+        Ok(())
+    }
+    
+    /// 获取暂存对局
+    pub async fn gs_get_parked_battle(&self, room_id: &str) -> anyhow::Result<Option<GcParkedBattle>> {
+        let row: Option<(serde_json::Value,)> = sqlx::query_as(
+            "SELECT parked_data FROM parked_battles WHERE room_id = $1"
+        )
+        .bind(room_id)
+        .fetch_optional(self.gs_pool()?)
+        .await?;
+        
+        row.map(|(data,)| Ok(serde_json::from_value(data)?)).transpose()
+    }
+    
+    /// 获取全部暂存对局 (过期清理用)
+    pub async fn gs_list_parked_battles(&self) -> anyhow::Result<Vec<GcParkedBattle>> {
+        let rows: Vec<(serde_json::Value,)> = sqlx::query_as(
+            "SELECT parked_data FROM parked_battles"
+        )
+        .fetch_all(self.gs_pool()?)
+        .await?;
+        
+        rows.into_iter()
+            .map(|(data,)| Ok(serde_json::from_value(data)?))
+            .collect()
+    }
+    
+    /// 删除暂存对局
+    pub async fn gs_delete_parked_battle(&self, room_id: &str) -> anyhow::Result<()> {
+        sqlx::query("DELETE FROM parked_battles WHERE room_id = $1")
+            .bind(room_id)
+            .execute(self.gs_pool()?)
+            .await?;
+        
+        Ok(())
+    }
+    
+    // =========================================================================
+    // 组合预制体 API
+    // =========================================================================
+    
+    /// 保存组合预制体 (同一 ID 覆盖)
+    pub async fn gs_save_prefab_group(&self, group: &GcPrefabGroup) -> anyhow::Result<()> {
+        let data = serde_json::to_value(group)?;
+        
+        sqlx::query(
+            r#"
+            INSERT INTO prefab_groups (group_id, group_data, updated_at)
+            VALUES ($1, $2, NOW())
+            ON CONFLICT (group_id)
+            DO UPDATE SET group_data = $2, updated_at = NOW()
+            "#
+        )
+        .bind(&group.id)
+        .bind(data)
+        .execute(self.gs_pool()?)
+        .await?;
+        
+        Ok(())
+    }
+    
+    /// 获取全部组合预制体
+    pub async fn gs_list_prefab_groups(&self) -> anyhow::Result<Vec<GcPrefabGroup>> {
+        let rows: Vec<(serde_json::Value,)> = sqlx::query_as(
+            "SELECT group_data FROM prefab_groups ORDER BY group_id"
+        )
+        .fetch_all(self.gs_pool()?)
+        .await?;
+        
+        rows.into_iter()
+            .map(|(data,)| Ok(serde_json::from_value(data)?))
+            .collect()
+    }
+    
+    // =========================================================================
+    // 资源标签 API
+    // =========================================================================
+    
+    /// 保存资源的标签 (空列表时删除记录)
+    pub async fn gs_save_asset_tags(&self, asset: &str, tags: &[String]) -> anyhow::Result<()> {
+        if tags.is_empty() {
+            sqlx::query("DELETE FROM asset_tags WHERE asset_name = $1")
+                .bind(asset)
+                .execute(self.gs_pool()?)
+                .await?;
+            return Ok(());
+        }
+        
+        sqlx::query(
+            r#"
+            INSERT INTO asset_tags (asset_name, tags, updated_at)
+            VALUES ($1, $2, NOW())
+            ON CONFLICT (asset_name)
+            DO UPDATE SET tags = $2, updated_at = NOW()
+            "#
+        )
+        .bind(asset)
+        .bind(serde_json::to_value(tags)?)
+        .execute(self.gs_pool()?)
+        .await?;
+        
+        Ok(())
+    }
+    
+    /// 获取全部资源标签
+    pub async fn gs_list_asset_tags(&self) -> anyhow::Result<Vec<(String, Vec<String>)>> {
+        let rows: Vec<(String, serde_json::Value)> = sqlx::query_as(
+            "SELECT asset_name, tags FROM asset_tags ORDER BY asset_name"
+        )
+        .fetch_all(self.gs_pool()?)
+        .await?;
+        
+        rows.into_iter()
+            .map(|(asset, tags)| Ok((asset, serde_json::from_value(tags)?)))
+            .collect()
+    }
+    
+    // =========================================================================
+    // 怪兽皮肤 API
+    // =========================================================================
+    
+    /// 获取玩家的怪兽皮肤 (没有记录时为空)
+    pub async fn gs_get_monster_skins(&self, player_id: &str) -> anyhow::Result<GcMonsterSkins> {
+        let row: Option<(serde_json::Value,)> = sqlx::query_as(
+            "SELECT skins FROM player_monster_skins WHERE player_id = $1"
+        )
+        .bind(player_id)
+        .fetch_optional(self.gs_pool()?)
+        .await?;
+        
+        match row {
+            Some((data,)) => Ok(serde_json::from_value(data)?),
+            None => Ok(GcMonsterSkins::default()),
+        }
+    }
+    
+    /// 保存玩家的怪兽皮肤
+    pub async fn gs_save_monster_skins(&self, player_id: &str, skins: &GcMonsterSkins) -> anyhow::Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO player_monster_skins (player_id, skins, updated_at)
+            VALUES ($1, $2, NOW())
+            ON CONFLICT (player_id)
+            DO UPDATE SET skins = $2, updated_at = NOW()
+            "#
+        )
+        .bind(player_id)
+        .bind(serde_json::to_value(skins)?)
+        .execute(self.gs_pool()?)
+        .await?;
+        
+        Ok(())
+    }
+    
+    // =========================================================================
+    // 伙伴 API
+    // =========================================================================
+    
+    /// 获取玩家的伙伴收藏 (没有记录时为空)
+    pub async fn gs_get_companions(&self, player_id: &str) -> anyhow::Result<GcCompanionCollection> {
+        let row: Option<(serde_json::Value,)> = sqlx::query_as(
+            "SELECT companions FROM player_companions WHERE player_id = $1"
+        )
+        .bind(player_id)
+        .fetch_optional(self.gs_pool()?)
+        .await?;
+        
+        match row {
+            Some((data,)) => Ok(serde_json::from_value(data)?),
+            None => Ok(GcCompanionCollection::default()),
+        }
+    }
+    
+    /// 保存玩家的伙伴收藏
+    pub async fn gs_save_companions(&self, player_id: &str, companions: &GcCompanionCollection) -> anyhow::Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO player_companions (player_id, companions, updated_at)
+            VALUES ($1, $2, NOW())
+            ON CONFLICT (player_id)
+            DO UPDATE SET companions = $2, updated_at = NOW()
+            "#
+        )
+        .bind(player_id)
+        .bind(serde_json::to_value(companions)?)
+        .execute(self.gs_pool()?)
+        .await?;
+        
+        Ok(())
+    }
+    
+    // =========================================================================
+    // 卡牌收藏 API
+    // =========================================================================
+    
+    /// 获取玩家的卡牌收藏 (没有记录时为空)
+    pub async fn gs_get_card_collection(&self, player_id: &str) -> anyhow::Result<GcCardCollection> {
+        let row: Option<(serde_json::Value,)> = sqlx::query_as(
+            "SELECT collection FROM player_card_collections WHERE player_id = $1"
+        )
+        .bind(player_id)
+        .fetch_optional(self.gs_pool()?)
+        .await?;
+        
+        match row {
+            Some((data,)) => Ok(serde_json::from_value(data)?),
+            None => Ok(GcCardCollection::default()),
+        }
+    }
+    
+    /// 保存玩家的卡牌收藏
+    pub async fn gs_save_card_collection(&self, player_id: &str, collection: &GcCardCollection) -> anyhow::Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO player_card_collections (player_id, collection, updated_at)
+            VALUES ($1, $2, NOW())
+            ON CONFLICT (player_id)
+            DO UPDATE SET collection = $2, updated_at = NOW()
+            "#
+        )
+        .bind(player_id)
+        .bind(serde_json::to_value(collection)?)
+        .execute(self.gs_pool()?)
+        .await?;
+        
+        Ok(())
+    }
+    
+    // =========================================================================
+    // 玩家设置 API
+    // =========================================================================
+    
+    /// 获取玩家设置 (旧格式的数据升级到当前格式)
+    pub async fn gs_get_player_settings(&self, player_id: &str) -> anyhow::Result<Option<GcSettingsRecord>> {
+        let row: Option<(serde_json::Value, i64, i64)> = sqlx::query_as(
+            r#"
+            SELECT settings, revision, EXTRACT(EPOCH FROM updated_at)::BIGINT
+            FROM player_settings WHERE player_id = $1
+            "#
+        )
+        .bind(player_id)
+        .fetch_optional(self.gs_pool()?)
+        .await?;
+        
+        row.map(|(data, revision, updated_at)| Ok(GcSettingsRecord {
+            revision: revision as u64,
+            updated_at: updated_at as u64,
+            settings: gc_migrate_settings(data)?,
+        }))
+        .transpose()
+    }
+    
+    /// 保存玩家设置 (数据库中的版本号须等于 `expected_revision`)，返回是否写入
+    pub async fn gs_save_player_settings(
+        &self,
+        player_id: &str,
+        record: &GcSettingsRecord,
+        expected_revision: u64,
+    ) -> anyhow::Result<bool> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO player_settings (player_id, settings, revision, updated_at)
+            VALUES ($1, $2, $3, NOW())
+            ON CONFLICT (player_id)
+            DO UPDATE SET settings = $2, revision = $3, updated_at = NOW()
+            WHERE player_settings.revision = $4
+            "#
+        )
+        .bind(player_id)
+        .bind(serde_json::to_value(&record.settings)?)
+        .bind(record.revision as i64)
+        .bind(expected_revision as i64)
+        .execute(self.gs_pool()?)
+        .await?;
+        
+        Ok(result.rows_affected() == 1)
+    }
+    
     // =========================================================================
     // 玩家进度 API
     // =========================================================================
     
     /// 获取玩家进度
-    pub async fn gs_get_player_progress(&self, player_id: &str) -> anyhow::Result<Option<GsPlayerProgress>> {
+    pub async fn gs_get_player_progress(&self, player_id: &str, slot: u32) -> anyhow::Result<Option<GsPlayerProgress>> {
         let row: Option<GsPlayerProgress> = sqlx::query_as(
             r#"
             SELECT 
                 player_id,
+                slot,
                 world_position_x,
                 world_position_y,
                 world_position_z,
                 current_map,
                 game_flags,
-                statistics
-            FROM player_progress WHERE player_id = $1
+                statistics,
+                difficulty,
+                level_scaling
+            FROM player_progress WHERE player_id = $1 AND slot = $2
             "#
         )
         .bind(player_id)
-        .fetch_optional(&self.pool)
+        .bind(slot as i32)
+        .fetch_optional(self.gs_pool()?)
         .await?;
         
         Ok(row)
@@ -204,10 +848,10 @@ impl GsDatabase {
             r#"
             INSERT INTO player_progress (
                 player_id, world_position_x, world_position_y, world_position_z,
-                current_map, game_flags, statistics, last_save_at
+                current_map, game_flags, statistics, difficulty, level_scaling, slot, last_save_at
             )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, NOW())
-            ON CONFLICT (player_id) 
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, NOW())
+            ON CONFLICT (player_id, slot) 
             DO UPDATE SET 
                 world_position_x = $2,
                 world_position_y = $3,
@@ -215,6 +859,8 @@ impl GsDatabase {
                 current_map = $5,
                 game_flags = $6,
                 statistics = $7,
+                difficulty = $8,
+                level_scaling = $9,
                 last_save_at = NOW()
             "#
         )
@@ -225,21 +871,343 @@ impl GsDatabase {
         .bind(&progress.current_map)
         .bind(&progress.game_flags)
         .bind(&progress.statistics)
-        .execute(&self.pool)
+        .bind(&progress.difficulty)
+        .bind(progress.level_scaling)
+        .bind(progress.slot)
+        .execute(self.gs_pool()?)
+        .await?;
+        
+        Ok(())
+    }
+    
+    /// 获取新手引导进度 (没有存档时为空进度)
+    pub async fn gs_get_onboarding(&self, player_id: &str, slot: u32) -> anyhow::Result<GcOnboardingProgress> {
+        let row: Option<(serde_json::Value,)> = sqlx::query_as(
+            "SELECT onboarding FROM player_progress WHERE player_id = $1 AND slot = $2"
+        )
+        .bind(player_id)
+        .bind(slot as i32)
+        .fetch_optional(self.gs_pool()?)
+        .await?;
+        
+        match row {
+            Some((data,)) => Ok(serde_json::from_value(data)?),
+            None => Ok(GcOnboardingProgress::default()),
+        }
+    }
+    
+    /// 保存新手引导进度 (不影响客户端上传的其它进度字段)
+    pub async fn gs_save_onboarding(&self, player_id: &str, slot: u32, onboarding: &GcOnboardingProgress) -> anyhow::Result<()> {
+        let data = serde_json::to_value(onboarding)?;
+        
+        sqlx::query(
+            r#"
+            INSERT INTO player_progress (player_id, slot, onboarding, last_save_at)
+            VALUES ($1, $2, $3, NOW())
+            ON CONFLICT (player_id, slot) 
+            DO UPDATE SET onboarding = $3
+            "#
+        )
+        .bind(player_id)
+        .bind(slot as i32)
+        .bind(data)
+        .execute(self.gs_pool()?)
+        .await?;
+        
+        Ok(())
+    }
+    
+    // =========================================================================
+    // 存档槽位 API
+    // =========================================================================
+    
+    /// 列出玩家已使用的存档槽位
+    pub async fn gs_list_save_slots(&self, player_id: &str) -> anyhow::Result<Vec<GcSaveSlotSummary>> {
+        let rows: Vec<GsSaveSlotRow> = sqlx::query_as(
+            r#"
+            SELECT
+                s.slot,
+                pp.character_name,
+                pp.profession,
+                pp.level,
+                pr.current_map,
+                pr.difficulty,
+                EXTRACT(EPOCH FROM pr.last_save_at)::BIGINT AS last_save_at
+            FROM (
+                SELECT slot FROM player_profiles WHERE player_id = $1
+                UNION SELECT slot FROM player_inventories WHERE player_id = $1
+                UNION SELECT slot FROM player_progress WHERE player_id = $1
+            ) s
+            LEFT JOIN player_profiles pp ON pp.player_id = $1 AND pp.slot = s.slot
+            LEFT JOIN player_progress pr ON pr.player_id = $1 AND pr.slot = s.slot
+            ORDER BY s.slot
+            "#
+        )
+        .bind(player_id)
+        .fetch_all(self.gs_pool()?)
+        .await?;
+        
+        Ok(rows.into_iter()
+            .map(|row| GcSaveSlotSummary {
+                slot: row.slot.max(0) as u32,
+                name: row.character_name,
+                profession: row.profession.as_deref().and_then(gs_parse_profession),
+                level: row.level.unwrap_or(1).max(1) as u32,
+                current_map: row.current_map,
+                difficulty: row.difficulty.as_deref().map(GcDifficulty::gc_from_id).unwrap_or_default(),
+                last_save_at: row.last_save_at,
+            })
+            .collect())
+    }
+    
+    /// 删除存档槽位 (档案、背包、进度)，返回是否存在数据
+    pub async fn gs_delete_save_slot(&self, player_id: &str, slot: u32) -> anyhow::Result<bool> {
+        let mut tx = self.gs_pool()?.begin().await?;
+        let mut deleted = 0;
+        for table in GS_SLOT_TABLES {
+            deleted += sqlx::query(&format!("DELETE FROM {} WHERE player_id = $1 AND slot = $2", table))
+                .bind(player_id)
+                .bind(slot as i32)
+                .execute(&mut *tx)
+                .await?
+                .rows_affected();
+        }
+        tx.commit().await?;
+        
+        Ok(deleted > 0)
+    }
+    
+    /// 复制存档槽位 (覆盖目标槽位)，来源槽位没有数据时返回 false
+    pub async fn gs_copy_save_slot(&self, player_id: &str, from: u32, to: u32) -> anyhow::Result<bool> {
+        let mut tx = self.gs_pool()?.begin().await?;
+        let mut copied = 0;
+        for table in GS_SLOT_TABLES {
+            sqlx::query(&format!("DELETE FROM {} WHERE player_id = $1 AND slot = $2", table))
+                .bind(player_id)
+                .bind(to as i32)
+                .execute(&mut *tx)
+                .await?;
+        }
+        for (table, columns) in [
+            ("player_profiles", "character_name, appearance, profession, level, exp, gold, created_at, updated_at"),
+            ("player_inventories", "inventory_data, updated_at"),
+            (
+                "player_progress",
+                "world_position_x, world_position_y, world_position_z, current_map, game_flags, \
+                 statistics, difficulty, level_scaling, onboarding, last_save_at, created_at",
+            ),
+        ] {
+            copied += sqlx::query(&format!(
+                "INSERT INTO {table} (player_id, slot, {columns}) \
+                 SELECT player_id, $3, {columns} FROM {table} WHERE player_id = $1 AND slot = $2"
+            ))
+            .bind(player_id)
+            .bind(from as i32)
+            .bind(to as i32)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+        }
+        
+        if copied == 0 {
+            tx.rollback().await?;
+            return Ok(false);
+        }
+        tx.commit().await?;
+        
+        Ok(true)
+    }
+    
+    // =========================================================================
+    // 账号数据 API
+    // =========================================================================
+    
+    /// 导出账号的全部数据 (各表原始行，用户表不含密码哈希)
+    pub async fn gs_export_account(&self, player_id: &str) -> anyhow::Result<serde_json::Map<String, serde_json::Value>> {
+        let mut archive = serde_json::Map::new();
+        
+        let user: Option<(serde_json::Value,)> = sqlx::query_as(
+            "SELECT to_jsonb(u) - 'password_hash' FROM users u WHERE u.id::text = $1"
+        )
+        .bind(player_id)
+        .fetch_optional(self.gs_pool()?)
+        .await?;
+        archive.insert("user".to_string(), user.map(|(data,)| data).unwrap_or_default());
+        
+        for table in GS_ACCOUNT_TABLES {
+            let rows: Vec<(serde_json::Value,)> = sqlx::query_as(&format!(
+                "SELECT to_jsonb(t) FROM {} t WHERE t.player_id = $1", table
+            ))
+            .bind(player_id)
+            .fetch_all(self.gs_pool()?)
+            .await?;
+            archive.insert(table.to_string(), rows.into_iter().map(|(data,)| data).collect());
+        }
+        
+        for table in GS_SHARED_TABLES {
+            let rows: Vec<(serde_json::Value,)> = sqlx::query_as(&format!(
+                "SELECT to_jsonb(t) FROM {} t WHERE $1 = ANY(t.player_ids)", table
+            ))
+            .bind(player_id)
+            .fetch_all(self.gs_pool()?)
+            .await?;
+            archive.insert(table.to_string(), rows.into_iter().map(|(data,)| data).collect());
+        }
+        
+        Ok(archive)
+    }
+    
+    /// 保存账号删除申请
+    pub async fn gs_save_account_deletion(&self, deletion: &GcAccountDeletion) -> anyhow::Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO account_deletions (player_id, requested_at, purge_at)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (player_id) 
+            DO UPDATE SET requested_at = $2, purge_at = $3
+            "#
+        )
+        .bind(&deletion.player_id)
+        .bind(deletion.requested_at as i64)
+        .bind(deletion.purge_at as i64)
+        .execute(self.gs_pool()?)
+        .await?;
+        
+        Ok(())
+    }
+    
+    /// 获取所有账号删除申请
+    pub async fn gs_list_account_deletions(&self) -> anyhow::Result<Vec<GcAccountDeletion>> {
+        let rows: Vec<(String, i64, i64)> = sqlx::query_as(
+            "SELECT player_id, requested_at, purge_at FROM account_deletions"
+        )
+        .fetch_all(self.gs_pool()?)
+        .await?;
+        
+        Ok(rows.into_iter()
+            .map(|(player_id, requested_at, purge_at)| GcAccountDeletion {
+                player_id,
+                requested_at: requested_at.max(0) as u64,
+                purge_at: purge_at.max(0) as u64,
+            })
+            .collect())
+    }
+    
+    /// 撤销账号删除申请，返回是否存在申请
+    pub async fn gs_delete_account_deletion(&self, player_id: &str) -> anyhow::Result<bool> {
+        let deleted = sqlx::query("DELETE FROM account_deletions WHERE player_id = $1")
+            .bind(player_id)
+            .execute(self.gs_pool()?)
+            .await?
+            .rows_affected();
+        
+        Ok(deleted > 0)
+    }
+    
+    /// 清除账号: 删除账号与全部存档、暂存对局、审计记录，对局历史中的玩家 ID 替换为匿名 ID
+    pub async fn gs_purge_account(&self, player_id: &str) -> anyhow::Result<()> {
+        let alias = gc_anonymized_player_id(player_id);
+        let mut tx = self.gs_pool()?.begin().await?;
+        
+        for table in GS_ACCOUNT_TABLES {
+            sqlx::query(&format!("DELETE FROM {} WHERE player_id = $1", table))
+                .bind(player_id)
+                .execute(&mut *tx)
+                .await?;
+        }
+        sqlx::query("DELETE FROM parked_battles WHERE $1 = ANY(player_ids)")
+            .bind(player_id)
+            .execute(&mut *tx)
+            .await?;
+        // 审计记录已封存，无法匿名化，随账号一起删除
+        sqlx::query("DELETE FROM match_audits WHERE $1 = ANY(player_ids)")
+            .bind(player_id)
+            .execute(&mut *tx)
+            .await?;
+        
+        let matches: Vec<(Uuid, serde_json::Value)> = sqlx::query_as(
+            "SELECT id, result_data FROM match_history WHERE $1 = ANY(player_ids)"
+        )
+        .bind(player_id)
+        .fetch_all(&mut *tx)
         .await?;
+        for (id, mut result) in matches {
+            gc_anonymize_json(&mut result, player_id, &alias);
+            sqlx::query(
+                "UPDATE match_history SET player_ids = array_replace(player_ids, $2, $3), result_data = $4 WHERE id = $1"
+            )
+            .bind(id)
+            .bind(player_id)
+            .bind(&alias)
+            .bind(result)
+            .execute(&mut *tx)
+            .await?;
+        }
+        
+        sqlx::query("DELETE FROM account_deletions WHERE player_id = $1")
+            .bind(player_id)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query("DELETE FROM users WHERE id::text = $1")
+            .bind(player_id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
         
         Ok(())
     }
 }
 
+/// 存档槽位摘要查询行
+#[derive(sqlx::FromRow)]
+struct GsSaveSlotRow {
+    slot: i32,
+    character_name: Option<String>,
+    profession: Option<String>,
+    level: Option<i32>,
+    current_map: Option<String>,
+    difficulty: Option<String>,
+    last_save_at: Option<i64>,
+}
+
+/// 按存档槽位存储的表
+const GS_SLOT_TABLES: [&str; 3] = ["player_profiles", "player_inventories", "player_progress"];
+
+/// 账号独有的表 (按 player_id 导出，清除账号时删除)
+const GS_ACCOUNT_TABLES: [&str; 8] = [
+    "player_profiles", "player_inventories", "player_progress", "player_decks", "player_monster_skins", "player_settings",
+    "player_companions", "player_card_collections",
+];
+
+/// 多人共享的表 (按 player_ids 导出)
+const GS_SHARED_TABLES: [&str; 3] = ["match_history", "parked_battles", "match_audits"];
+
+/// 解析存储的职业名称
+fn gs_parse_profession(prof_str: &str) -> Option<GcProfessionType> {
+    match prof_str {
+        "Knight" => Some(GcProfessionType::Knight),
+        "Swordsman" => Some(GcProfessionType::Swordsman),
+        "Warlock" => Some(GcProfessionType::Warlock),
+        "Gunner" => Some(GcProfessionType::Gunner),
+        "Assassin" => Some(GcProfessionType::Assassin),
+        _ => None,
+    }
+}
+
 /// 玩家进度数据
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, sqlx::FromRow)]
 pub struct GsPlayerProgress {
     pub player_id: String,
+    /// 存档槽位
+    pub slot: i32,
     pub world_position_x: f32,
     pub world_position_y: f32,
     pub world_position_z: f32,
     pub current_map: String,
     pub game_flags: serde_json::Value,
     pub statistics: serde_json::Value,
+    /// 存档难度 ("Story" / "Normal" / "Hard")
+    pub difficulty: String,
+    /// 敌人等级随玩家缩放
+    pub level_scaling: bool,
 }
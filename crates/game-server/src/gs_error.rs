@@ -5,10 +5,15 @@
 //! 文档: 文档/03-game-server.md
 
 use axum::{
+    async_trait,
+    body::Bytes,
+    extract::{FromRequest, Request},
     http::StatusCode,
     response::{IntoResponse, Response},
     Json,
 };
+use game_core::{gc_parse_json, GcJsonDiagnostic, GcMapIssue, GcGhostError, GcMapUploadError, GcReplayError, GcSettingsError, GcTextError};
+use serde::de::DeserializeOwned;
 use serde_json::json;
 use thiserror::Error;
 
@@ -31,6 +36,50 @@ pub enum GsError {
     #[error("认证失败: {0}")]
     GsAuthFailed(String),
     
+    /// 权限不足
+    #[error("权限不足: {0}")]
+    GsForbidden(String),
+    
+    /// 请求不符合规则
+    #[error("{0}")]
+    GsBadRequest(String),
+    
+    /// 请求体 JSON 格式错误 (附带字段路径诊断)
+    #[error("{0}")]
+    GsInvalidJson(GcJsonDiagnostic),
+    
+    /// 上传的地图内容不合法 (附带每处问题的字段路径)
+    #[error("地图校验失败: {} 处问题", .0.len())]
+    GsInvalidMap(Vec<GcMapIssue>),
+    
+    /// 回放不存在或已过期
+    #[error("回放不存在: {0}")]
+    GsReplayNotFound(String),
+    
+    /// 上传的回放不合法 (过大、无法重放或元数据不一致)
+    #[error("{0}")]
+    GsInvalidReplay(GcReplayError),
+    
+    /// 幽灵不存在
+    #[error("幽灵不存在: {0}")]
+    GsGhostNotFound(String),
+    
+    /// 上传的幽灵不合法
+    #[error("{0}")]
+    GsInvalidGhost(GcGhostError),
+    
+    /// 对局审计记录不存在
+    #[error("审计记录不存在: {0}")]
+    GsAuditNotFound(String),
+    
+    /// 玩家设置不合法或版本号冲突
+    #[error("{0}")]
+    GsInvalidSettings(GcSettingsError),
+    
+    /// 用户文本 (名称等) 校验失败 (附带结构化原因)
+    #[error("{0}")]
+    GsInvalidText(GcTextError),
+    
     /// 数据库错误
     #[error("数据库错误: {0}")]
     GsDatabaseError(String),
@@ -55,23 +104,104 @@ impl IntoResponse for GsError {
             GsError::GsRoomFull => (StatusCode::CONFLICT, "ROOM_FULL", self.to_string()),
             GsError::GsPlayerNotFound(_) => (StatusCode::NOT_FOUND, "PLAYER_NOT_FOUND", self.to_string()),
             GsError::GsAuthFailed(_) => (StatusCode::UNAUTHORIZED, "AUTH_FAILED", self.to_string()),
+            GsError::GsForbidden(_) => (StatusCode::FORBIDDEN, "FORBIDDEN", self.to_string()),
+            GsError::GsBadRequest(_) => (StatusCode::BAD_REQUEST, "BAD_REQUEST", self.to_string()),
+            GsError::GsInvalidJson(_) => (StatusCode::BAD_REQUEST, "INVALID_JSON", self.to_string()),
+            GsError::GsInvalidMap(_) => (StatusCode::UNPROCESSABLE_ENTITY, "INVALID_MAP", self.to_string()),
+            GsError::GsReplayNotFound(_) => (StatusCode::NOT_FOUND, "REPLAY_NOT_FOUND", self.to_string()),
+            GsError::GsInvalidReplay(GcReplayError::GcTooLarge { .. }) => (StatusCode::PAYLOAD_TOO_LARGE, "PAYLOAD_TOO_LARGE", self.to_string()),
+            GsError::GsInvalidReplay(_) => (StatusCode::UNPROCESSABLE_ENTITY, "INVALID_REPLAY", self.to_string()),
+            GsError::GsGhostNotFound(_) => (StatusCode::NOT_FOUND, "GHOST_NOT_FOUND", self.to_string()),
+            GsError::GsInvalidGhost(_) => (StatusCode::UNPROCESSABLE_ENTITY, "INVALID_GHOST", self.to_string()),
+            GsError::GsAuditNotFound(_) => (StatusCode::NOT_FOUND, "AUDIT_NOT_FOUND", self.to_string()),
+            GsError::GsInvalidSettings(GcSettingsError::GcTooLarge { .. }) => (StatusCode::PAYLOAD_TOO_LARGE, "PAYLOAD_TOO_LARGE", self.to_string()),
+            GsError::GsInvalidSettings(GcSettingsError::GcInvalidJson(_)) => (StatusCode::BAD_REQUEST, "INVALID_JSON", self.to_string()),
+            GsError::GsInvalidSettings(GcSettingsError::GcRevisionConflict { .. }) => (StatusCode::CONFLICT, "SETTINGS_CONFLICT", self.to_string()),
+            GsError::GsInvalidSettings(_) => (StatusCode::UNPROCESSABLE_ENTITY, "INVALID_SETTINGS", self.to_string()),
+            GsError::GsInvalidText(error) => (StatusCode::BAD_REQUEST, error.gc_code(), self.to_string()),
             GsError::GsDatabaseError(_) => (StatusCode::INTERNAL_SERVER_ERROR, "DATABASE_ERROR", "数据库错误".to_string()),
             GsError::GsInternalError(_) => (StatusCode::INTERNAL_SERVER_ERROR, "INTERNAL_ERROR", "内部错误".to_string()),
             GsError::InternalError(_) => (StatusCode::INTERNAL_SERVER_ERROR, "INTERNAL_ERROR", self.to_string()),
             GsError::AuthError(_) => (StatusCode::UNAUTHORIZED, "AUTH_ERROR", self.to_string()),
         };
 
-        let body = json!({
+        let mut body = json!({
             "error": {
                 "code": code,
                 "message": message,
             }
         });
+        match &self {
+            GsError::GsInvalidJson(diagnostic) => body["error"]["diagnostic"] = json!(diagnostic),
+            GsError::GsInvalidMap(issues) => body["error"]["issues"] = json!(issues),
+            GsError::GsInvalidText(error) => body["error"]["rejection"] = json!(error),
+            GsError::GsInvalidSettings(GcSettingsError::GcInvalidJson(diagnostic)) => body["error"]["diagnostic"] = json!(diagnostic),
+            GsError::GsInvalidSettings(GcSettingsError::GcRevisionConflict { current }) => body["error"]["revision"] = json!(current),
+            _ => {}
+        }
 
         (status, Json(body)).into_response()
     }
 }
 
+/// JSON 请求体提取器 (代替 `Json`，解析失败时返回带字段路径的 `GsInvalidJson`)
+pub struct GsJson<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequest<S> for GsJson<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = GsError;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let bytes = Bytes::from_request(req, state)
+            .await
+            .map_err(|e| GsError::GsBadRequest(e.body_text()))?;
+        let text = std::str::from_utf8(&bytes)
+            .map_err(|_| GsError::GsBadRequest("请求体不是 UTF-8 文本".to_string()))?;
+        gc_parse_json("body", text).map(GsJson).map_err(GsError::GsInvalidJson)
+    }
+}
+
+// 从地图上传校验错误转换
+impl From<GcMapUploadError> for GsError {
+    fn from(err: GcMapUploadError) -> Self {
+        match err {
+            GcMapUploadError::GcInvalidJson(diagnostic) => GsError::GsInvalidJson(diagnostic),
+            GcMapUploadError::GcInvalidContent(issues) => GsError::GsInvalidMap(issues),
+        }
+    }
+}
+
+// 从回放校验错误转换
+impl From<GcReplayError> for GsError {
+    fn from(err: GcReplayError) -> Self {
+        GsError::GsInvalidReplay(err)
+    }
+}
+
+// 从幽灵校验错误转换
+impl From<GcGhostError> for GsError {
+    fn from(err: GcGhostError) -> Self {
+        GsError::GsInvalidGhost(err)
+    }
+}
+
+// 从玩家设置错误转换
+impl From<GcSettingsError> for GsError {
+    fn from(err: GcSettingsError) -> Self {
+        GsError::GsInvalidSettings(err)
+    }
+}
+
+impl From<GcTextError> for GsError {
+    fn from(err: GcTextError) -> Self {
+        GsError::GsInvalidText(err)
+    }
+}
+
 // 从 sqlx 错误转换
 impl From<sqlx::Error> for GsError {
     fn from(err: sqlx::Error) -> Self {
@@ -0,0 +1,43 @@
+//! 连接心跳超时
+//!
+//! 模块: game-server
+//! 前缀: Gs
+//! 文档: 文档/03-game-server.md
+//!
+//! 客户端按固定间隔发送 `Ping` (默认 30 秒)；连接上收到的任何消息 (含 WebSocket 控制帧) 都算作心跳。
+//! 超过 `heartbeat_timeout_secs` 没有收到消息的连接视为已断开，不等 TCP 关闭就主动关闭，
+//! 并按断线清理 (离开房间；组队或混战对局保留座位，宽限期后由机器人接管)。
+//! 连接循环每 `heartbeat_check_secs` 检查一次。
+
+use std::time::{Duration, Instant};
+
+/// 单个连接的心跳状态
+#[derive(Clone, Debug)]
+pub struct GsHeartbeat {
+    /// 超时时间
+    timeout: Duration,
+    /// 最后一次收到消息的时间
+    last_seen: Instant,
+}
+
+impl GsHeartbeat {
+    /// 创建心跳状态 (`now` 视为刚收到消息)
+    pub fn gs_new(timeout_secs: u64, now: Instant) -> Self {
+        Self { timeout: Duration::from_secs(timeout_secs), last_seen: now }
+    }
+
+    /// 收到消息
+    pub fn gs_beat(&mut self, now: Instant) {
+        self.last_seen = now;
+    }
+
+    /// 距最后一次收到消息的时间
+    pub fn gs_silence(&self, now: Instant) -> Duration {
+        now.saturating_duration_since(self.last_seen)
+    }
+
+    /// 是否已超时
+    pub fn gs_is_expired(&self, now: Instant) -> bool {
+        self.gs_silence(now) >= self.timeout
+    }
+}
@@ -0,0 +1,1461 @@
+//! 服务器 + 核心端到端集成测试
+//!
+//! 模块: game-server
+//! 前缀: Gs
+//! 文档: 文档/03-game-server.md
+//!
+//! 在进程内创建 `GsAppState` (无数据库、无网络)，模拟 WebSocket 连接循环:
+//! - 客户端消息先序列化为文本再按连接循环的方式解析，交给 `gs_handle_message`
+//! - 广播按 `gs_should_deliver` 分发到各连接的收件箱
+//! - 断言协议消息与服务器保存的结果 (系列赛、暂存对局)，覆盖核心单元测试触及不到的状态流转
+
+use game_core::{gc_anonymized_player_id, gc_parse_json, gc_verify_match, GcGameMode, GcReplay, GcBattleFormat, GcBattleState, GcCard, GcCardCollection, GcCombatLogEvent, GcCompanion, GcMonsterPool, GcDeck, GcEventSourcedBattle, GcGhostMode, GcGhostRound, GcGhostRun, GcGuildRole, GcOnboardingFlag, GcOrganization, GcPageRequest, GcPingIntent, GcProfessionType, GcPingTarget, GcRankBracket, GcRewardGrant, GcRuleset, GcSeries, GcSeriesPhase, GcSettingValue, GcSettingsError, GcTavernAction, gc_create_ember_wyrm_event, gc_world_boss_hit_damage, GC_CHAMPION_SKIN_ID, GC_CHANNEL_MEDITATION, GC_CHANNEL_PYROBLAST, GC_PING_LIMIT, GC_SETTINGS_MAX_BYTES, GC_TERRITORY_PROTECTION_SECS, GC_TERRITORY_WIN_POINTS};
+use std::time::{Duration, Instant};
+
+use axum::body::Bytes;
+use axum::extract::{ws::Message, Path, Query, State};
+use tokio::sync::{broadcast, mpsc};
+
+use crate::gs_backpressure::{GsOutbound, GsPush, GsResync};
+use crate::gs_bot::gs_bot_sweep;
+use crate::gs_chaos::{GsChaos, GsChaosConfig};
+use crate::gs_config::GsConfig;
+use crate::gs_heartbeat::GsHeartbeat;
+use crate::gs_thumbnail::gs_generate_preview;
+use crate::gs_error::{GsError, GsJson};
+use crate::gs_routes::{gs_claim_territory, gs_delete_asset_tag, gs_rename_asset_tag, gs_set_asset_tags, GsAssetTagsRequest, GsRenameAssetTagRequest, gs_upload_ghost, GS_MAX_GHOSTS, gs_equip_monster_skin, gs_copy_save_slot, gs_craft_foil, gs_create_character, gs_rename_character, gs_delete_save_slot, gs_get_card_collection, gs_donate_guild, gs_equip_companion, gs_get_companions, gs_get_card_stats, gs_get_player_settings, gs_get_unit_stats, gs_join_guild, gs_list_assets, gs_put_player_settings, gs_set_guild_role, GsAssetItem, GsAssetListQuery, GsCopySaveSlotRequest, GsCreateCharacterRequest, GsRenameCharacterRequest, GsSaveSlotQuery, GsCraftFoilRequest, GsEquipCompanionRequest, GsEquipSkinRequest, GsGuildDonateRequest, GsGuildRoleRequest, GsUsageStatsQuery};
+use crate::gs_state::{gs_now, GsAppState, GsBroadcastMessage, GsMemoryUser};
+use crate::gs_websocket::{gs_handle_message, gs_resync_snapshot, gs_should_deliver, GsWsMessage};
+
+/// 单局最多推进的回合数 (防止死循环)
+const GS_TEST_MAX_TURNS: usize = 200;
+
+// =============================================================================
+// 测试客户端
+// =============================================================================
+
+/// 进程内连接 (对应一个 WebSocket)
+struct GsTestClient {
+    player_id: Option<String>,
+    room_id: Option<String>,
+    lobby_subscribed: bool,
+    broadcast_rx: broadcast::Receiver<GsBroadcastMessage>,
+    /// 收到的广播 (按到达顺序)
+    inbox: Vec<GsWsMessage>,
+}
+
+impl GsTestClient {
+    fn gs_connect(state: &GsAppState) -> Self {
+        Self {
+            player_id: None,
+            room_id: None,
+            lobby_subscribed: false,
+            broadcast_rx: state.gs_subscribe(),
+            inbox: Vec::new(),
+        }
+    }
+
+    /// 取走收件箱
+    fn gs_take_inbox(&mut self) -> Vec<GsWsMessage> {
+        std::mem::take(&mut self.inbox)
+    }
+
+    /// 收件箱中最近一次战斗状态 (本人视角)
+    fn gs_latest_battle(&self) -> Option<GcBattleState> {
+        self.inbox.iter().rev().find_map(|msg| match msg {
+            GsWsMessage::GameStarted { battle_state, .. } | GsWsMessage::GameState { battle_state, .. } => {
+                Some(gc_parse_json("battle_state", battle_state).expect("战斗状态应可解析"))
+            }
+            _ => None,
+        })
+    }
+}
+
+/// 一桌客户端 (共享同一个服务器状态)
+struct GsTestTable {
+    state: GsAppState,
+    clients: Vec<GsTestClient>,
+}
+
+impl GsTestTable {
+    fn gs_new(count: usize) -> Self {
+        Self::gs_with_config(count, GsConfig::default())
+    }
+
+    fn gs_with_config(count: usize, config: GsConfig) -> Self {
+        let state = GsAppState::gs_in_memory(config);
+        let clients = (0..count).map(|_| GsTestClient::gs_connect(&state)).collect();
+        Self { state, clients }
+    }
+
+    /// 客户端发送消息，返回直接响应；之后所有连接接收广播
+    async fn gs_send(&mut self, index: usize, msg: GsWsMessage) -> Vec<GsWsMessage> {
+        let text = serde_json::to_string(&msg).expect("消息应可序列化");
+        let msg: GsWsMessage = gc_parse_json("message", &text).expect("消息应可解析");
+
+        let client = &mut self.clients[index];
+        if let Some(pid) = &client.player_id {
+            self.state.gs_touch_player(pid).await;
+        }
+        let responses = gs_handle_message(
+            &self.state,
+            &mut client.player_id,
+            &mut client.room_id,
+            &mut client.lobby_subscribed,
+            msg,
+        )
+        .await;
+        // 响应同样经过一次序列化，确认客户端能解析
+        for resp in &responses {
+            let text = serde_json::to_string(resp).expect("响应应可序列化");
+            gc_parse_json::<GsWsMessage>("response", &text).expect("响应应可解析");
+        }
+
+        self.gs_deliver();
+        responses
+    }
+
+    /// 经故障注入发送: 消息被丢弃时返回 None (对应连接循环丢弃客户端消息)
+    async fn gs_send_unreliable(&mut self, index: usize, msg: GsWsMessage) -> Option<Vec<GsWsMessage>> {
+        if self.state.chaos.as_ref().is_some_and(|chaos| chaos.gs_drop_inbound()) {
+            return None;
+        }
+        Some(self.gs_send(index, msg).await)
+    }
+
+    /// 把广播分发到各连接的收件箱
+    fn gs_deliver(&mut self) {
+        for client in &mut self.clients {
+            while let Ok(broadcast) = client.broadcast_rx.try_recv() {
+                let deliver = gs_should_deliver(
+                    &broadcast,
+                    client.player_id.as_deref(),
+                    client.room_id.as_deref(),
+                    client.lobby_subscribed,
+                );
+                if deliver {
+                    let msg = gc_parse_json("broadcast", &broadcast.message).expect("广播应可解析");
+                    client.inbox.push(msg);
+                }
+            }
+        }
+    }
+
+    /// 登录、建房、加入、准备并开始系列赛，返回房间 ID
+    async fn gs_start_match(&mut self) -> String {
+        self.gs_start_match_with(GcBattleFormat::Duel, &[]).await
+    }
+
+    /// 按赛制开始系列赛 (房主建房后设置赛制，各玩家加入后选择队伍，None 为不选)
+    async fn gs_start_match_with(&mut self, format: GcBattleFormat, teams: &[Option<u32>]) -> String {
+        for i in 0..self.clients.len() {
+            let login = GsWsMessage::Login {
+                player_id: format!("p{}", i + 1),
+                name: format!("玩家{}", i + 1),
+                appearance: None,
+                slot: 1,
+                content_version: None,
+            };
+            let responses = self.gs_send(i, login).await;
+            assert!(matches!(responses.first(), Some(GsWsMessage::LoginSuccess { .. })));
+        }
+
+        let responses = self.gs_send(0, GsWsMessage::CreateRoom { name: "集成测试".to_string() }).await;
+        let Some(GsWsMessage::RoomCreated { room_id }) = responses.first().cloned() else {
+            panic!("创建房间失败: {:?}", responses);
+        };
+        if format != GcBattleFormat::Duel {
+            assert!(self.gs_send(0, GsWsMessage::SetRoomFormat { format }).await.is_empty());
+        }
+        for i in 1..self.clients.len() {
+            let responses = self.gs_send(i, GsWsMessage::JoinRoom { room_id: room_id.clone() }).await;
+            assert!(matches!(responses.first(), Some(GsWsMessage::RoomJoined { .. })), "{:?}", responses);
+            if let Some(&Some(team)) = teams.get(i) {
+                assert!(self.gs_send(i, GsWsMessage::SetTeam { team }).await.is_empty());
+            }
+            assert!(self.gs_send(i, GsWsMessage::Ready).await.is_empty());
+        }
+
+        assert!(self.gs_send(0, GsWsMessage::StartGame).await.is_empty());
+        room_id
+    }
+
+    /// 下完一局: 轮到的玩家打出全部手牌后结束回合，直到收到对局结束；返回获胜方与系列赛
+    async fn gs_play_game(&mut self) -> (Vec<String>, GcSeries) {
+        for _ in 0..GS_TEST_MAX_TURNS {
+            let battle = self.clients[0].gs_latest_battle().expect("应收到战斗状态");
+            let current = battle.gc_current_player_id().expect("应有行动玩家").to_string();
+            let index = self.gs_client_index(&current);
+
+            // 行动玩家视角才能看到自己的手牌
+            let hand = self.clients[index].gs_latest_battle().expect("应收到战斗状态")
+                .gc_find_player(&current)
+                .map(|p| p.hand.clone())
+                .unwrap_or_default();
+            for card in hand {
+                let responses = self.gs_send(index, GsWsMessage::PlayCard { card_id: card.id, target_id: None }).await;
+                assert!(responses.is_empty(), "出牌失败: {:?}", responses);
+            }
+
+            if let Some(result) = self.gs_take_game_end() {
+                return result;
+            }
+            let responses = self.gs_send(index, GsWsMessage::EndTurn).await;
+            assert!(responses.is_empty(), "结束回合失败: {:?}", responses);
+        }
+        panic!("对局在 {} 回合内未结束", GS_TEST_MAX_TURNS);
+    }
+
+    /// 若收件箱中有对局结束，取出获胜方与系列赛 (并确认发放了结算)
+    fn gs_take_game_end(&mut self) -> Option<(Vec<String>, GcSeries)> {
+        let inbox = &self.clients[0].inbox;
+        let winner = inbox.iter().find_map(|msg| match msg {
+            GsWsMessage::GameEnded { winner_ids, .. } => Some(winner_ids.clone()),
+            _ => None,
+        })?;
+        assert!(inbox.iter().any(|msg| matches!(msg, GsWsMessage::MatchRewards { rewards } if !rewards.is_empty())));
+        let series = inbox.iter().rev().find_map(|msg| match msg {
+            GsWsMessage::SeriesUpdate { series } => Some(series.clone()),
+            _ => None,
+        })?;
+        for client in &mut self.clients {
+            client.gs_take_inbox();
+        }
+        Some((winner, series))
+    }
+
+    fn gs_client_index(&self, player_id: &str) -> usize {
+        self.clients.iter()
+            .position(|c| c.player_id.as_deref() == Some(player_id))
+            .expect("玩家应已登录")
+    }
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[tokio::test]
+async fn test_full_series_between_two_players() {
+    let mut table = GsTestTable::gs_new(2);
+    let room_id = table.gs_start_match().await;
+
+    // 双方都收到本人视角的开局状态
+    for client in &table.clients {
+        let started = client.inbox.iter().any(|m| matches!(m, GsWsMessage::GameStarted { .. }));
+        assert!(started, "应收到开局状态");
+    }
+
+    // 回合边界自动暂存对局
+    let battle = table.clients[0].gs_latest_battle().expect("应收到战斗状态");
+    let first = battle.gc_current_player_id().expect("应有行动玩家").to_string();
+    let index = table.gs_client_index(&first);
+    assert!(table.gs_send(index, GsWsMessage::EndTurn).await.is_empty());
+    assert!(table.state.parked_battles.read().await.contains_key(&room_id));
+
+    // 不是自己的回合时结束回合被拒绝
+    let responses = table.gs_send(index, GsWsMessage::EndTurn).await;
+    assert!(matches!(responses.first(), Some(GsWsMessage::Error { code, .. }) if code == "END_TURN_FAILED"));
+
+    // 打完整个三局两胜系列赛，局间双方跳过换牌
+    let mut series = None;
+    for _ in 0..3 {
+        let (winners, current) = table.gs_play_game().await;
+        assert_eq!(winners.len(), 1, "对局应有胜者");
+        // 一局结束后暂存存档失效
+        assert!(!table.state.parked_battles.read().await.contains_key(&room_id));
+        let finished = current.phase == GcSeriesPhase::Finished;
+        series = Some(current);
+        if finished {
+            break;
+        }
+        for i in 0..table.clients.len() {
+            let responses = table.gs_send(i, GsWsMessage::SubmitSideboard { swaps: Vec::new() }).await;
+            assert!(!responses.iter().any(|m| matches!(m, GsWsMessage::Error { .. })), "{:?}", responses);
+        }
+        assert!(table.clients[0].gs_latest_battle().is_some(), "下一局应已开始");
+    }
+
+    // 服务器保存的结果与广播一致
+    let series = series.expect("系列赛应已推进");
+    assert_eq!(series.phase, GcSeriesPhase::Finished);
+    assert_eq!(series.scores.iter().map(|s| s.wins).max(), Some(2));
+    let room = table.state.gs_get_room(&room_id).await.expect("房间应仍存在");
+    assert!(!room.game_started);
+    let saved = room.series.expect("房间应保存系列赛");
+    assert_eq!(saved.phase, GcSeriesPhase::Finished);
+    assert_eq!(saved.scores.iter().map(|s| s.wins).collect::<Vec<_>>(), series.scores.iter().map(|s| s.wins).collect::<Vec<_>>());
+    assert!(room.battle.as_ref().is_some_and(|b| b.gc_state().gc_is_finished()));
+    
+    // 新手引导: 双方都完成了一局，系列赛胜者完成首胜
+    let players = table.state.players.read().await;
+    for (player_id, player) in players.iter() {
+        assert!(player.onboarding.gc_is_done(GcOnboardingFlag::FirstBattleFinished));
+        let champion = series.scores.iter().any(|s| &s.player_id == player_id && s.wins == 2);
+        assert!(!champion || player.onboarding.gc_is_done(GcOnboardingFlag::FirstBattleWon));
+    }
+    assert!(players.values().any(|p| p.onboarding.gc_is_done(GcOnboardingFlag::FirstCardPlayed)));
+}
+
+#[tokio::test]
+async fn test_protocol_rejects_out_of_order_messages() {
+    let mut table = GsTestTable::gs_new(2);
+
+    // 未登录
+    let responses = table.gs_send(0, GsWsMessage::CreateRoom { name: "房间".to_string() }).await;
+    assert!(matches!(responses.first(), Some(GsWsMessage::Error { code, .. }) if code == "NOT_LOGGED_IN"));
+
+    let room_id = table.gs_start_match().await;
+
+    // 已开局的房间不能加入；房间外的连接收不到房间广播
+    table.clients.push(GsTestClient::gs_connect(&table.state));
+    let login = |name: &str| GsWsMessage::Login {
+        player_id: "p3".to_string(),
+        name: name.to_string(),
+        appearance: None,
+        slot: 1,
+        content_version: None,
+    };
+    // 名称校验: 拒绝带字段诊断，通过后保存规范化的名称
+    let responses = table.gs_send(2, login("<玩家3>")).await;
+    assert!(matches!(
+        responses.first(),
+        Some(GsWsMessage::Error { code, diagnostic: Some(d), .. }) if code == "TEXT_INVALID_CHARACTER" && d.path == ".name"
+    ));
+    table.gs_send(2, login(" 玩家３\u{200B} ")).await;
+    assert_eq!(table.state.players.read().await.get("p3").map(|p| p.name.clone()).as_deref(), Some("玩家3"));
+    let responses = table.gs_send(2, GsWsMessage::JoinRoom { room_id }).await;
+    assert!(matches!(responses.first(), Some(GsWsMessage::Error { code, .. }) if code == "JOIN_FAILED"));
+    let responses = table.gs_send(2, GsWsMessage::EndTurn).await;
+    assert!(matches!(responses.first(), Some(GsWsMessage::Error { code, .. }) if code == "NOT_IN_ROOM"));
+
+    let first = table.clients[0].gs_latest_battle()
+        .and_then(|b| b.gc_current_player_id().map(str::to_string))
+        .expect("应有行动玩家");
+    let index = table.gs_client_index(&first);
+    assert!(table.gs_send(index, GsWsMessage::EndTurn).await.is_empty());
+    assert!(table.clients[0].inbox.iter().any(|m| matches!(m, GsWsMessage::TurnEnded { .. })));
+    assert!(!table.clients[2].inbox.iter().any(|m| matches!(m, GsWsMessage::TurnEnded { .. })));
+
+    // 行动后增量广播公开行动记录，也可按回合查询
+    assert!(table.clients[0].inbox.iter()
+        .any(|m| matches!(m, GsWsMessage::ActionFeed { entries } if !entries.is_empty())));
+    let responses = table.gs_send(0, GsWsMessage::GetActionFeed { from_turn: None, to_turn: None }).await;
+    assert!(matches!(responses.first(), Some(GsWsMessage::ActionFeed { entries }) if !entries.is_empty()));
+}
+
+#[tokio::test]
+async fn test_paged_queries_return_windows_instead_of_full_state() {
+    let mut table = GsTestTable::gs_new(2);
+    let room_id = table.gs_start_match().await;
+
+    // 测试对局没有卡组: 给行动玩家塞 3 张手牌
+    let battle = table.clients[0].gs_latest_battle().expect("应收到战斗状态");
+    let current = battle.gc_current_player_id().expect("应有行动玩家").to_string();
+    let index = table.gs_client_index(&current);
+    let other = table.clients[1 - index].player_id.clone().expect("应已登录");
+    {
+        let mut rooms = table.state.rooms.write().await;
+        let room = rooms.get_mut(&room_id).expect("房间应存在");
+        let mut state = room.battle.take().expect("应已开局").gc_into_state();
+        let player = state.players.iter_mut().find(|p| p.id == current).expect("应有行动玩家");
+        player.hand = (0..3).map(|i| GcCard::gc_new_attack(format!("c{}", i), "重击", 1, 20)).collect();
+        room.battle = Some(GcEventSourcedBattle::gc_from_state(state));
+    }
+
+    // 自己的手牌分页返回内容，他人的只返回数量
+
+    let page = GcPageRequest::gc_at(0, 2);
+    let responses = table.gs_send(index, GsWsMessage::GetHandPage { owner_id: None, page }).await;
+    let Some(GsWsMessage::HandPage { owner_id, page: own }) = responses.first() else {
+        panic!("应返回手牌分页: {:?}", responses);
+    };
+    assert_eq!(owner_id, &current);
+    assert_eq!((own.items.len(), own.total), (2, 3));
+    assert!(own.gc_has_more());
+
+    let responses = table.gs_send(1 - index, GsWsMessage::GetHandPage { owner_id: Some(current.clone()), page }).await;
+    assert!(matches!(responses.first(), Some(GsWsMessage::HandPage { page, .. }) if page.items.is_empty() && page.total == 3));
+    let responses = table.gs_send(0, GsWsMessage::GetHandPage { owner_id: Some("p9".to_string()), page }).await;
+    assert!(matches!(responses.first(), Some(GsWsMessage::Error { code, .. }) if code == "HAND_PAGE_FAILED"));
+
+    // 窗口只包含视野内的单位，附带最近日志
+    let responses = table.gs_send(0, GsWsMessage::GetBattleWindow { player_ids: vec![other.clone()], log_tail: 5 }).await;
+    let Some(GsWsMessage::BattleWindow { window }) = responses.first() else {
+        panic!("应返回战斗窗口: {:?}", responses);
+    };
+    assert_eq!(window.players.len(), 2);
+    assert_eq!(window.players.iter().filter(|p| p.in_view).count(), 1);
+    assert!(window.units.iter().all(|u| u.owner_id == other));
+    assert!(window.recent_log.items.len() <= 5);
+
+    let responses = table.gs_send(1, GsWsMessage::GetCombatLog { page: GcPageRequest::gc_latest(3) }).await;
+    assert!(matches!(responses.first(), Some(GsWsMessage::CombatLogPage { page }) if page.items.len() <= 3));
+}
+
+#[tokio::test]
+async fn test_two_vs_two_seats_teams_and_team_victory() {
+    let mut table = GsTestTable::gs_new(4);
+
+    // 房主切换赛制后容量变为 4
+    let room_id = table.gs_start_match_with(GcBattleFormat::TwoVsTwo, &[None, Some(1), Some(1), Some(0)]).await;
+    let room = table.state.gs_get_room(&room_id).await.expect("房间应存在");
+    assert_eq!((room.format, room.max_players), (GcBattleFormat::TwoVsTwo, 4));
+    // 加入时自动分队并同步给房间内其他玩家
+    assert_eq!(room.teams.len(), 4);
+    assert!(table.clients[0].inbox.iter().any(|m| matches!(m, GsWsMessage::RoomSettings { teams, .. } if teams.len() == 4)));
+
+    // 座位两队交替；p1 未选队伍，留在自动分配的队伍 0
+    let battle = table.clients[0].gs_latest_battle().expect("应收到战斗状态");
+    let teams: Vec<_> = battle.players.iter().map(|p| p.team).collect();
+    assert_eq!(teams, vec![Some(0), Some(1), Some(0), Some(1)]);
+    assert!(battle.gc_are_allies("p1", "p4"));
+
+    // 标记只转发给队友，超出限流的直接丢弃，不存在的目标报错
+    let ping = |player_id: &str| GsWsMessage::SendPing {
+        target: GcPingTarget::Player { player_id: player_id.to_string() },
+        intent: GcPingIntent::Attack,
+    };
+    let sender = table.gs_client_index("p1");
+    for _ in 0..=GC_PING_LIMIT {
+        assert!(table.gs_send(sender, ping("p2")).await.is_empty());
+    }
+    let pings = |client: &GsTestClient| client.inbox.iter().filter(|m| matches!(m, GsWsMessage::TeamPing { .. })).count();
+    let received: Vec<_> = ["p1", "p2", "p3", "p4"].iter().map(|id| pings(&table.clients[table.gs_client_index(id)])).collect();
+    assert_eq!(received, vec![0, 0, 0, GC_PING_LIMIT]);
+    let invalid = table.gs_send(table.gs_client_index("p4"), ping("nobody")).await;
+    assert!(matches!(invalid.as_slice(), [GsWsMessage::Error { code, .. }] if code == "INVALID_PING"));
+
+    // 获胜方为整支队伍
+    let (winners, _) = table.gs_play_game().await;
+    assert_eq!(winners.len(), 2, "{:?}", winners);
+    assert!(battle.gc_are_allies(&winners[0], &winners[1]));
+}
+
+#[tokio::test]
+async fn test_bot_takes_over_disconnected_seat_until_reconnect() {
+    let config = GsConfig { bot_takeover_grace_secs: 0, ..GsConfig::default() };
+    let mut table = GsTestTable::gs_with_config(3, config);
+    let room_id = table.gs_start_match_with(GcBattleFormat::FreeForAll, &[]).await;
+    let current = |table: &GsTestTable| table.clients[0].gs_latest_battle()
+        .and_then(|b| b.gc_current_player_id().map(str::to_string))
+        .expect("应有行动玩家");
+    assert_eq!(current(&table), "p1");
+
+    // p2 断线后保留座位，宽限期过后由机器人接管
+    table.state.gs_player_disconnect("p2").await;
+    table.clients[1].room_id = None;
+    gs_bot_sweep(&table.state).await;
+    table.gs_deliver();
+    let room = table.state.gs_get_room(&room_id).await.expect("房间应保留");
+    assert!(room.players.iter().any(|p| p.id == "p2"));
+    assert_eq!(room.bot_seats, vec!["p2".to_string()]);
+    assert!(table.clients[0].inbox.iter().any(|m| matches!(m, GsWsMessage::SeatControl { player_id, bot: true } if player_id == "p2")));
+
+    // 轮到 p2 时机器人行动并结束回合
+    assert!(table.gs_send(0, GsWsMessage::EndTurn).await.is_empty());
+    assert_eq!(current(&table), "p2");
+    gs_bot_sweep(&table.state).await;
+    table.gs_deliver();
+    assert_eq!(current(&table), "p3");
+    assert!(table.clients[0].inbox.iter().any(|m| matches!(m, GsWsMessage::TurnEnded { player_id } if player_id == "p2")));
+
+    // 重连后交还控制，机器人不再代打
+    let login = GsWsMessage::Login {
+        player_id: "p2".to_string(),
+        name: "玩家2".to_string(),
+        appearance: None,
+        slot: 1,
+        content_version: None,
+    };
+    table.gs_send(1, login).await;
+    let responses = table.gs_send(1, GsWsMessage::ResumeRoom { room_id: room_id.clone() }).await;
+    assert!(matches!(responses.first(), Some(GsWsMessage::RoomResumed { .. })), "{:?}", responses);
+    assert!(table.clients[0].inbox.iter().any(|m| matches!(m, GsWsMessage::SeatControl { player_id, bot: false } if player_id == "p2")));
+
+    assert!(table.gs_send(2, GsWsMessage::EndTurn).await.is_empty());
+    assert!(table.gs_send(0, GsWsMessage::EndTurn).await.is_empty());
+    gs_bot_sweep(&table.state).await;
+    table.gs_deliver();
+    assert_eq!(current(&table), "p2");
+    let room = table.state.gs_get_room(&room_id).await.expect("房间应保留");
+    assert!(room.bot_seats.is_empty() && room.disconnected_at.is_empty());
+}
+
+#[tokio::test]
+async fn test_non_participant_cannot_reopen_parked_battle() {
+    let mut table = GsTestTable::gs_new(2);
+    let room_id = table.gs_start_match().await;
+    let first = table.clients[0].gs_latest_battle()
+        .and_then(|b| b.gc_current_player_id().map(str::to_string))
+        .expect("应有行动玩家");
+    let index = table.gs_client_index(&first);
+    assert!(table.gs_send(index, GsWsMessage::EndTurn).await.is_empty());
+
+    // 模拟服务器重启: 房间不在内存，只剩暂存对局
+    table.state.rooms.write().await.remove(&room_id);
+    assert!(table.state.parked_battles.read().await.contains_key(&room_id));
+
+    assert!(table.state.gs_resume_room(&room_id, "intruder").await.is_err());
+    assert!(!table.state.rooms.read().await.contains_key(&room_id), "非参与者不应让房间重新出现");
+
+    assert!(table.state.gs_resume_room(&room_id, "p1").await.is_ok());
+    assert!(table.state.rooms.read().await.contains_key(&room_id));
+}
+
+#[tokio::test]
+async fn test_slow_consumer_resyncs_then_disconnects() {
+    let (tx, mut rx) = mpsc::channel::<Message>(4);
+    let mut outbound = GsOutbound::gs_new(tx, 1);
+    for i in 0..4 {
+        assert_eq!(outbound.gs_push(format!("m{}", i)), GsPush::Queued);
+    }
+    assert_eq!(outbound.gs_push("m4".to_string()), GsPush::Dropped);
+    outbound.gs_mark_lagged(2);
+
+    // 队列空出一半前不推送快照
+    assert_eq!(outbound.gs_resync_state(), GsResync::Deferred);
+    rx.recv().await;
+    rx.recv().await;
+    assert_eq!(outbound.gs_resync_state(), GsResync::Due { dropped: 3 });
+    assert!(outbound.gs_push_resync(vec!["resync".to_string(), "state".to_string()]));
+    assert_eq!(outbound.gs_resync_state(), GsResync::Idle);
+
+    // 重同步次数用尽后再次丢消息，按慢消费者断开
+    assert_eq!(outbound.gs_push("m5".to_string()), GsPush::Dropped);
+    assert_eq!(outbound.gs_resync_state(), GsResync::Disconnect);
+
+    drop(rx);
+    assert!(outbound.gs_is_closed());
+    assert_eq!(outbound.gs_push("m6".to_string()), GsPush::Closed);
+}
+
+#[tokio::test]
+async fn test_resync_snapshot_restores_room_and_battle() {
+    let mut table = GsTestTable::gs_new(2);
+    let room_id = table.gs_start_match().await;
+
+    let snapshot = gs_resync_snapshot(&table.state, Some("p2"), Some(&room_id), true, 7).await;
+    assert!(matches!(snapshot.first(), Some(GsWsMessage::Resync { dropped: 7 })), "{:?}", snapshot);
+    assert!(snapshot.iter().any(|m| matches!(m, GsWsMessage::RoomList { .. })));
+    assert!(snapshot.iter().any(|m| matches!(m, GsWsMessage::RoomJoined { players, .. } if players.len() == 2)));
+    assert!(snapshot.iter().any(|m| matches!(m, GsWsMessage::SeriesUpdate { .. })));
+
+    // 战斗状态按本人视角脱敏，与正常推送一致
+    let battle = snapshot.iter().find_map(|m| match m {
+        GsWsMessage::GameState { battle_state, .. } => Some(gc_parse_json::<GcBattleState>("battle_state", battle_state).expect("战斗状态应可解析")),
+        _ => None,
+    }).expect("快照应包含战斗状态");
+    let latest = table.clients[1].gs_latest_battle().expect("应收到过战斗状态");
+    assert_eq!(serde_json::to_value(&battle).unwrap(), serde_json::to_value(&latest).unwrap());
+
+    // 不在房间时只有大厅与标记
+    let snapshot = gs_resync_snapshot(&table.state, Some("p1"), None, false, 1).await;
+    assert_eq!(snapshot.len(), 1);
+}
+
+#[tokio::test]
+async fn test_silent_connection_reaped_mid_battle() {
+    let config = GsConfig { heartbeat_timeout_secs: 60, bot_takeover_grace_secs: 0, ..GsConfig::default() };
+    let mut table = GsTestTable::gs_with_config(3, config);
+    let room_id = table.gs_start_match_with(GcBattleFormat::FreeForAll, &[]).await;
+    let start = Instant::now();
+    let timeout = table.state.config.heartbeat_timeout_secs;
+    let mut heartbeats: Vec<GsHeartbeat> = (0..3).map(|_| GsHeartbeat::gs_new(timeout, start)).collect();
+
+    // p1、p3 每 30 秒发送 Ping，p2 的连接无声断开 (没有 TCP 关闭)
+    for step in 1..=3 {
+        let now = start + Duration::from_secs(30 * step);
+        for index in [0, 2] {
+            assert!(matches!(table.gs_send(index, GsWsMessage::Ping).await.as_slice(), [GsWsMessage::Pong]));
+            heartbeats[index].gs_beat(now);
+        }
+    }
+    let now = start + Duration::from_secs(90);
+    assert!(!heartbeats[0].gs_is_expired(now) && !heartbeats[2].gs_is_expired(now));
+    assert!(!heartbeats[1].gs_is_expired(start + Duration::from_secs(59)));
+    assert!(heartbeats[1].gs_is_expired(now));
+    assert_eq!(heartbeats[1].gs_silence(now), Duration::from_secs(90));
+
+    // 连接循环关闭超时连接后按断线清理: 座位保留，随后由机器人接管，其余玩家继续对局
+    table.state.gs_player_disconnect("p2").await;
+    table.clients[1].room_id = None;
+    let room = table.state.gs_get_room(&room_id).await.expect("房间应保留");
+    assert!(room.disconnected_at.contains_key("p2"));
+    assert!(room.battle.as_ref().is_some_and(|b| !b.gc_state().gc_is_finished()));
+    assert!(table.state.players.read().await.get("p2").is_none());
+
+    gs_bot_sweep(&table.state).await;
+    table.gs_deliver();
+    assert!(table.clients[2].inbox.iter().any(|m| matches!(m, GsWsMessage::SeatControl { player_id, bot: true } if player_id == "p2")));
+}
+
+#[tokio::test]
+async fn test_account_deletion_purges_after_grace_period() {
+    let config = GsConfig { account_deletion_grace_secs: 100, ..GsConfig::default() };
+    let table = GsTestTable::gs_with_config(0, config);
+    let state = &table.state;
+    let user_id = uuid::Uuid::new_v4();
+    let player_id = user_id.to_string();
+    state.memory_users.write().await.insert("alice".to_string(), GsMemoryUser {
+        id: user_id,
+        username: "alice".to_string(),
+        password_hash: "dev_hash_pw".to_string(),
+    });
+    state.weekly_leaderboard.write().await.gc_record(&player_id, 1, 8);
+
+    // 导出包含账号与内存中的排行记录
+    let archive = state.gs_export_account(&player_id).await.expect("导出应成功");
+    assert_eq!(archive["user"]["username"], "alice");
+    assert_eq!(archive["weekly_leaderboard"].as_array().map(Vec::len), Some(1));
+
+    // 重复申请保持原清除时间，撤销后不再清除
+    let deletion = state.gs_request_account_deletion(&player_id).await;
+    assert_eq!(state.gs_request_account_deletion(&player_id).await, deletion);
+    assert!(state.gs_cancel_account_deletion(&player_id).await);
+    assert_eq!(state.gs_purge_due_accounts(deletion.purge_at).await, 0);
+
+    // 宽限期内不清除，到期后删除账号并匿名化排行
+    let deletion = state.gs_request_account_deletion(&player_id).await;
+    assert_eq!(state.gs_purge_due_accounts(deletion.purge_at - 1).await, 0);
+    assert_eq!(state.gs_purge_due_accounts(deletion.purge_at).await, 1);
+    assert!(state.memory_users.read().await.is_empty());
+    assert!(state.gs_account_deletion(&player_id).await.is_none());
+    let leaderboard = state.weekly_leaderboard.read().await;
+    assert_eq!(leaderboard.entries[0].player_id, gc_anonymized_player_id(&player_id));
+}
+
+#[tokio::test]
+async fn test_asset_tags_filter_asset_list() {
+    let table = GsTestTable::gs_new(0);
+    let state = &table.state;
+    let list = |q: Option<&str>, tags: Option<&str>| {
+        let query = GsAssetListQuery { q: q.map(str::to_string), tags: tags.map(str::to_string) };
+        gs_list_assets(State(state.clone()), Query(query))
+    };
+    let names = |assets: Vec<GsAssetItem>| assets.into_iter().map(|a| a.name).collect::<Vec<_>>();
+
+    assert_eq!(state.gs_set_asset_tags("pine_1", &["Forest".to_string(), "tall".to_string()]).await, Ok(vec!["forest".to_string(), "tall".to_string()]));
+    assert_eq!(state.gs_edit_asset_tags("pine_2", &["forest".to_string()], &[]).await, Ok(vec!["forest".to_string()]));
+    assert!(state.gs_set_asset_tags("pine_3", &["a,b".to_string()]).await.is_err());
+
+    // 不带条件时返回全部资源 (含预制体)，并附带标签
+    let all = list(None, None).await.expect("资源列表").0;
+    assert!(all.iter().any(|a| a.name == "pine_1" && a.asset_type == "prefab" && a.tags.len() == 2));
+
+    // 标签过滤要求全部标签；重命名后按新标签查找
+    assert_eq!(state.gs_rename_asset_tag("tall", "giant").await, Ok(vec!["pine_1".to_string()]));
+    assert_eq!(names(list(None, Some("forest,giant")).await.expect("资源列表").0), vec!["pine_1"]);
+    assert_eq!(names(list(Some("forest"), None).await.expect("资源列表").0), vec!["pine_1", "pine_2"]);
+    assert_eq!(names(list(Some("pine_2"), None).await.expect("资源列表").0)[0], "pine_2");
+
+    // 删除标签后不再匹配
+    assert_eq!(state.gs_delete_asset_tag("forest").await.map(|a| a.len()), Ok(2));
+    assert!(list(None, Some("forest")).await.expect("资源列表").0.is_empty());
+    assert_eq!(state.gs_asset_tag_registry().await.gc_tag_counts().len(), 1);
+
+    // 接口修改标签需要登录
+    let editor = gs_auth_headers(uuid::Uuid::new_v4(), "editor");
+    let set = || GsJson(GsAssetTagsRequest { tags: vec!["ruins".to_string()] });
+    let rename = || GsJson(GsRenameAssetTagRequest { from: "giant".to_string(), to: "huge".to_string() });
+    let anonymous = axum::http::HeaderMap::new;
+    assert!(matches!(gs_set_asset_tags(State(state.clone()), Path("pine_2".to_string()), anonymous(), set()).await, Err(GsError::GsAuthFailed(_))));
+    assert!(matches!(gs_rename_asset_tag(State(state.clone()), anonymous(), rename()).await, Err(GsError::GsAuthFailed(_))));
+    assert!(matches!(gs_delete_asset_tag(State(state.clone()), Path("giant".to_string()), anonymous()).await, Err(GsError::GsAuthFailed(_))));
+    assert_eq!(names(list(None, Some("giant")).await.expect("资源列表").0), vec!["pine_1"]);
+
+    assert!(gs_set_asset_tags(State(state.clone()), Path("pine_2".to_string()), editor.clone(), set()).await.is_ok());
+    assert!(gs_rename_asset_tag(State(state.clone()), editor.clone(), rename()).await.is_ok());
+    assert!(gs_delete_asset_tag(State(state.clone()), Path("ruins".to_string()), editor).await.is_ok());
+    assert_eq!(names(list(None, Some("huge")).await.expect("资源列表").0), vec!["pine_1"]);
+    assert!(list(None, Some("ruins")).await.expect("资源列表").0.is_empty());
+}
+
+#[tokio::test]
+async fn test_uploads_get_thumbnails_in_asset_list() {
+    let asset_root = std::env::temp_dir().join(format!("gs_thumbnail_test_{}", uuid::Uuid::new_v4()));
+    let config = GsConfig { asset_root: asset_root.clone(), ..GsConfig::default() };
+    let table = GsTestTable::gs_with_config(0, config);
+    let upload_dir = table.state.config.gs_upload_dir();
+    std::fs::create_dir_all(&upload_dir).expect("创建上传目录");
+
+    // 300x150 的 RGB 贴图
+    let mut texture = Vec::new();
+    {
+        let mut encoder = png::Encoder::new(&mut texture, 300, 150);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().expect("写入 PNG 头");
+        writer.write_image_data(&[200u8; 300 * 150 * 3]).expect("写入像素");
+    }
+    let model = serde_json::to_vec(&serde_json::json!({
+        "asset": { "version": "2.0" },
+        "meshes": [{ "primitives": [{ "attributes": { "POSITION": 0 } }] }],
+        "accessors": [{ "count": 3, "min": [0.0, 0.0, 0.0], "max": [1.0, 2.0, 1.0] }]
+    }))
+    .expect("序列化 glTF");
+
+    for (name, data) in [("grass.png", &texture), ("hut.gltf", &model)] {
+        std::fs::write(upload_dir.join(name), data).expect("保存上传文件");
+        let preview = gs_generate_preview(&upload_dir, name, data).expect("生成缩略图");
+        assert_eq!(preview.thumbnail, Some(format!("assets/uploads/thumbnails/{}.png", name)));
+    }
+    assert!(gs_generate_preview(&upload_dir, "broken.glb", b"not a model").is_err());
+
+    let assets = gs_list_assets(State(table.state.clone()), Query(GsAssetListQuery { q: None, tags: None }))
+        .await
+        .expect("资源列表")
+        .0;
+    let texture_item = assets.iter().find(|a| a.name == "grass.png").expect("贴图在清单中");
+    assert_eq!(texture_item.asset_type, "texture");
+    assert!(texture_item.preview.thumbnail.is_some());
+    assert!(texture_item.preview.model.is_none());
+    let model_item = assets.iter().find(|a| a.name == "hut.gltf").expect("模型在清单中");
+    assert_eq!(model_item.preview.model.as_ref().map(|m| m.vertices), Some(3));
+
+    // 缩略图等比缩小到 128 像素以内
+    let file = std::fs::File::open(upload_dir.join("thumbnails/grass.png.png")).expect("缩略图文件");
+    let reader = png::Decoder::new(file).read_info().expect("缩略图可解码");
+    assert_eq!((reader.info().width, reader.info().height), (128, 64));
+
+    let _ = std::fs::remove_dir_all(asset_root);
+}
+
+#[tokio::test]
+async fn test_equipped_skins_apply_to_tavern_purchases() {
+    let mut table = GsTestTable::gs_new(2);
+    let state = table.state.clone();
+
+    // 只能装备拥有的皮肤
+    assert!(state.gs_equip_monster_skin("p1", "slime", Some("frost")).await.is_err());
+    assert!(state.gs_grant_monster_skin("p1", "slime", "Frost!").await.is_err());
+    state.gs_grant_monster_skin("p1", "slime", "frost").await.expect("发放皮肤");
+    let skins = state.gs_equip_monster_skin("p1", "slime", Some("frost")).await.expect("装备皮肤");
+    assert_eq!(skins.gc_equipped("slime"), Some("frost"));
+
+    // 装备接口只能操作登录用户自己的皮肤
+    let owner = uuid::Uuid::new_v4();
+    state.gs_grant_monster_skin(&owner.to_string(), "slime", "frost").await.expect("发放皮肤");
+    let equip = || GsJson(GsEquipSkinRequest { template_id: "slime".to_string(), skin_id: Some("frost".to_string()) });
+    let path = || Path(owner.to_string());
+    let forged = gs_equip_monster_skin(State(state.clone()), path(), gs_auth_headers(uuid::Uuid::new_v4(), "other"), equip()).await;
+    assert!(matches!(forged, Err(GsError::GsForbidden(_))));
+    assert!(gs_equip_monster_skin(State(state.clone()), path(), gs_auth_headers(owner, "owner"), equip()).await.is_ok());
+
+    for i in 0..2 {
+        let login = GsWsMessage::Login {
+            player_id: format!("p{}", i + 1),
+            name: format!("玩家{}", i + 1),
+            appearance: None,
+            slot: 1,
+            content_version: None,
+        };
+        table.gs_send(i, login).await;
+    }
+    let responses = table.gs_send(0, GsWsMessage::CreateRoom { name: "酒馆".to_string() }).await;
+    let Some(GsWsMessage::RoomCreated { room_id }) = responses.first().cloned() else {
+        panic!("创建房间失败: {:?}", responses);
+    };
+    table.gs_send(1, GsWsMessage::JoinRoom { room_id: room_id.clone() }).await;
+
+    // 开局时座位带上装备的皮肤
+    let responses = table.gs_send(0, GsWsMessage::StartTavern).await;
+    let Some(GsWsMessage::TavernSeat { seat, .. }) = responses.first() else {
+        panic!("应返回座位: {:?}", responses);
+    };
+    assert_eq!(seat.skin_loadout.get("slime").map(String::as_str), Some("frost"));
+
+    // 购买的史莱姆带皮肤，不影响属性
+    {
+        let mut rooms = state.rooms.write().await;
+        let tavern = rooms.get_mut(&room_id).and_then(|r| r.tavern.as_mut()).expect("酒馆对局");
+        let slime = tavern.pool.spawn_template("slime", "shop_slime").expect("史莱姆模板");
+        for id in ["p1", "p2"] {
+            let seat = tavern.gc_seat_mut(id).expect("座位");
+            seat.player.economy.gold = 10;
+            seat.shop.slots[0] = Some(slime.clone());
+        }
+    }
+    let buy = GcTavernAction::Buy { slot: 0 };
+    let responses = table.gs_send(0, GsWsMessage::TavernAction { action: buy.clone() }).await;
+    let Some(GsWsMessage::TavernSeat { seat, .. }) = responses.first() else {
+        panic!("应返回座位: {:?}", responses);
+    };
+    let skinned = seat.player.bench.last().expect("已购买").clone();
+    assert_eq!(skinned.skin.as_deref(), Some("frost"));
+
+    let responses = table.gs_send(1, GsWsMessage::TavernAction { action: buy }).await;
+    let Some(GsWsMessage::TavernSeat { seat, .. }) = responses.first() else {
+        panic!("应返回座位: {:?}", responses);
+    };
+    let plain = seat.player.bench.last().expect("已购买");
+    assert_eq!(plain.skin, None);
+    assert_eq!((plain.base_atk, plain.max_hp), (skinned.base_atk, skinned.max_hp));
+
+    // 皮肤没有发放接口，第一名由服务器解锁最终阵容的冠军皮肤
+    let mut tavern = state.rooms.read().await.get(&room_id).and_then(|r| r.tavern.clone()).expect("酒馆对局");
+    for (id, placement) in [("p1", 2), ("p2", 1)] {
+        let seat = tavern.gc_seat_mut(id).expect("座位");
+        seat.placement = Some(placement);
+        seat.board.player_monsters[0] = seat.player.bench.pop();
+    }
+    state.gs_grant_tavern_rewards(&tavern).await;
+    assert!(state.gs_monster_skins("p2").await.gc_owns("slime", GC_CHAMPION_SKIN_ID));
+    assert!(!state.gs_monster_skins("p1").await.gc_owns("slime", GC_CHAMPION_SKIN_ID));
+}
+
+#[tokio::test]
+async fn test_tavern_run_summary_scores_and_leaderboard() {
+    let mut table = GsTestTable::gs_new(2);
+    let state = table.state.clone();
+    for i in 0..2 {
+        let login = GsWsMessage::Login {
+            player_id: format!("p{}", i + 1),
+            name: format!("玩家{}", i + 1),
+            appearance: None,
+            slot: 1,
+            content_version: None,
+        };
+        table.gs_send(i, login).await;
+    }
+    let responses = table.gs_send(0, GsWsMessage::CreateRoom { name: "酒馆".to_string() }).await;
+    let Some(GsWsMessage::RoomCreated { room_id }) = responses.first().cloned() else {
+        panic!("创建房间失败: {:?}", responses);
+    };
+    table.gs_send(1, GsWsMessage::JoinRoom { room_id: room_id.clone() }).await;
+    table.gs_send(0, GsWsMessage::StartTavern).await;
+
+    // 每周挑战对局: p1 有阵容，p2 残血，下一回合分出胜负
+    {
+        let mut rooms = state.rooms.write().await;
+        let tavern = rooms.get_mut(&room_id).and_then(|r| r.tavern.as_mut()).expect("酒馆对局");
+        tavern.ruleset = Some(GcRuleset::gc_new("test", "测试", vec![]));
+        tavern.round = 10;
+        let slime = tavern.pool.spawn_template("slime", "board_slime").expect("史莱姆模板");
+        tavern.gc_seat_mut("p1").expect("座位").board.player_monsters[0] = Some(slime);
+        let seat = tavern.gc_seat_mut("p2").expect("座位");
+        seat.health = 1;
+        seat.armor = 0;
+    }
+    table.gs_send(0, GsWsMessage::EndShopping).await;
+    table.gs_send(1, GsWsMessage::EndShopping).await;
+
+    let scores = table.clients[1].inbox.iter().find_map(|msg| match msg {
+        GsWsMessage::TavernRunSummary { scores } => Some(scores.clone()),
+        _ => None,
+    }).expect("对局结束应广播结算摘要");
+    assert_eq!(scores.iter().map(|s| s.player_id.as_str()).collect::<Vec<_>>(), vec!["p1", "p2"]);
+    assert_eq!(scores[0].placement, Some(1));
+    assert!(scores[0].breakdown.damage_dealt > 0 && scores[0].breakdown.board_stars > 0);
+    assert_eq!(scores[1].breakdown.damage_dealt, 0);
+
+    // 排行记录最高得分
+    let ranking = state.weekly_leaderboard.read().await.gc_ranking();
+    assert_eq!(ranking[0].player_id, "p1");
+    assert_eq!(ranking[0].best_score, scores[0].total);
+}
+
+#[tokio::test]
+async fn test_finished_game_records_verifiable_audit() {
+    let mut table = GsTestTable::gs_new(2);
+    let room_id = table.gs_start_match().await;
+    let (winners, _) = table.gs_play_game().await;
+
+    // 一局结束后生成审计记录，可凭该局回放复核
+    let room = table.state.gs_get_room(&room_id).await.expect("房间应仍存在");
+    let battle = room.battle.expect("应保存上一局战斗");
+    let audit = table.state.gs_match_audit(&battle.gc_state().id).await.expect("应有审计记录");
+    assert_eq!(audit.winner_id.as_ref(), winners.first());
+    assert_eq!(audit.chain.len(), battle.gc_events().len());
+    assert_eq!(audit.content, *table.state.content_version);
+
+    let replay = GcReplay::gc_from_battle(&battle, GcGameMode::YuGiOhStyle).expect("回放");
+    assert!(gc_verify_match(&audit, &replay).is_ok());
+
+    // 删减事件的回放无法通过复核
+    let mut truncated = replay.clone();
+    truncated.events.truncate(1);
+    assert!(gc_verify_match(&audit, &truncated).is_err());
+    assert!(table.state.gs_match_audit("battle_unknown").await.is_none());
+}
+
+#[tokio::test]
+async fn test_usage_stats_aggregate_finished_games() {
+    let mut table = GsTestTable::gs_new(2);
+    let state = table.state.clone();
+    for i in 0..2 {
+        let login = GsWsMessage::Login {
+            player_id: format!("p{}", i + 1),
+            name: format!("玩家{}", i + 1),
+            appearance: None,
+            slot: 1,
+            content_version: None,
+        };
+        table.gs_send(i, login).await;
+    }
+    let responses = table.gs_send(0, GsWsMessage::CreateRoom { name: "酒馆".to_string() }).await;
+    let Some(GsWsMessage::RoomCreated { room_id }) = responses.first().cloned() else {
+        panic!("创建房间失败: {:?}", responses);
+    };
+    table.gs_send(1, GsWsMessage::JoinRoom { room_id: room_id.clone() }).await;
+    table.gs_send(0, GsWsMessage::StartTavern).await;
+
+    // p1 带着史莱姆获胜，p2 空阵容落败
+    {
+        let mut rooms = state.rooms.write().await;
+        let tavern = rooms.get_mut(&room_id).and_then(|r| r.tavern.as_mut()).expect("酒馆对局");
+        tavern.round = 10;
+        let slime = tavern.pool.spawn_template("slime", "board_slime").expect("史莱姆模板");
+        tavern.gc_seat_mut("p1").expect("座位").board.player_monsters[0] = Some(slime);
+        let seat = tavern.gc_seat_mut("p2").expect("座位");
+        seat.health = 1;
+        seat.armor = 0;
+    }
+    table.gs_send(0, GsWsMessage::EndShopping).await;
+    table.gs_send(1, GsWsMessage::EndShopping).await;
+
+    // 汇总前统计为空，汇总后按段位给出选取率与胜率
+    let query = || GsUsageStatsQuery { bracket: None, limit: 20 };
+    assert!(gs_get_unit_stats(State(state.clone()), Query(query())).await.rates.is_empty());
+    assert_eq!(state.gs_aggregate_usage().await, 2);
+    assert_eq!(state.gs_aggregate_usage().await, 0);
+
+    let units = gs_get_unit_stats(State(state.clone()), Query(query())).await;
+    assert_eq!(units.samples, 2);
+    let slime = units.rates.iter().find(|r| r.id == "slime").expect("史莱姆统计");
+    assert_eq!((slime.picks, slime.wins), (1, 1));
+    assert_eq!((slime.pick_rate, slime.win_rate), (0.5, 1.0));
+
+    let silver = GsUsageStatsQuery { bracket: Some(GcRankBracket::Silver), limit: 20 };
+    let silver = gs_get_unit_stats(State(state.clone()), Query(silver)).await;
+    assert_eq!(silver.samples, 0);
+    assert!(silver.rates.is_empty());
+
+    // 卡牌对战: 每名玩家一条样本
+    let mut table = GsTestTable::gs_new(2);
+    table.gs_start_match().await;
+    table.gs_play_game().await;
+    assert_eq!(table.state.gs_aggregate_usage().await, 2);
+    let cards = gs_get_card_stats(State(table.state.clone()), Query(query())).await;
+    assert_eq!(cards.samples, 2);
+    assert!(cards.rates.iter().all(|r| r.picks <= 2 && r.wins <= r.picks));
+}
+
+#[tokio::test]
+async fn test_mutual_pause_freezes_battle() {
+    let mut table = GsTestTable::gs_new(2);
+    let room_id = table.gs_start_match().await;
+    let battle = table.clients[0].gs_latest_battle().expect("应收到战斗状态");
+    let current = battle.gc_current_player_id().expect("应有行动玩家").to_string();
+    let index = table.gs_client_index(&current);
+    let other = 1 - index;
+    let latest_status = |table: &GsTestTable| table.clients[other].inbox.iter().rev().find_map(|msg| match msg {
+        GsWsMessage::PauseStatus { status } => Some(status.clone()),
+        _ => None,
+    });
+
+    // 申请者本人不能同意，对手同意后暂停生效并广播
+    assert!(table.gs_send(index, GsWsMessage::RequestPause).await.is_empty());
+    assert_eq!(latest_status(&table).and_then(|s| s.requested_by), Some(current.clone()));
+    let responses = table.gs_send(index, GsWsMessage::AcceptPause).await;
+    assert!(matches!(responses.first(), Some(GsWsMessage::Error { code, .. }) if code == "PAUSE_FAILED"));
+    assert!(table.gs_send(other, GsWsMessage::AcceptPause).await.is_empty());
+    let status = latest_status(&table).expect("应广播暂停状态");
+    assert!(status.paused);
+    assert_eq!(status.remaining_secs, table.state.config.pause_budget_secs);
+
+    // 暂停中拒绝对局操作，断线托管计时冻结
+    let responses = table.gs_send(index, GsWsMessage::EndTurn).await;
+    assert!(matches!(responses.first(), Some(GsWsMessage::Error { code, .. }) if code == "END_TURN_FAILED"));
+    let other_id = table.clients[other].player_id.clone().expect("已登录");
+    table.state.rooms.write().await.get_mut(&room_id).expect("房间").disconnected_at.insert(other_id.clone(), 0);
+    assert!(table.state.gs_update_bot_seats(gs_now()).await.is_empty());
+
+    // 继续后可以操作；额度用尽时自动继续，暂停时长不计入断线计时
+    assert!(table.gs_send(other, GsWsMessage::ResumeBattle).await.is_empty());
+    assert!(!latest_status(&table).expect("应广播暂停状态").paused);
+    assert!(table.gs_send(index, GsWsMessage::EndTurn).await.is_empty());
+
+    let now = gs_now();
+    {
+        let mut rooms = table.state.rooms.write().await;
+        let room = rooms.get_mut(&room_id).expect("房间");
+        room.disconnected_at.insert(other_id.clone(), now);
+        room.pause.budget_secs = 20;
+        room.pause.gc_request("p1", now).expect("申请");
+        room.pause.gc_accept("p2", now).expect("同意");
+    }
+    let expired = table.state.gs_expire_pauses(now + 60).await;
+    assert_eq!(expired.len(), 1);
+    assert_eq!(expired[0].1.remaining_secs, 0);
+    let room = table.state.gs_get_room(&room_id).await.expect("房间");
+    assert!(!room.pause.gc_is_paused());
+    assert_eq!(room.disconnected_at.get(&other_id), Some(&(now + 20)));
+
+    // 额度用尽后不能再申请
+    let responses = table.gs_send(index, GsWsMessage::RequestPause).await;
+    assert!(matches!(responses.first(), Some(GsWsMessage::Error { message, .. }) if message == "暂停额度已用尽"));
+}
+
+#[tokio::test]
+async fn test_player_settings_roundtrip_with_revision_check() {
+    let state = GsAppState::gs_in_memory(GsConfig::default());
+    let user = uuid::Uuid::new_v4();
+    let player_id = user.to_string();
+    let put = |body: String| gs_put_player_settings(
+        State(state.clone()),
+        Path(player_id.clone()),
+        gs_auth_headers(user, "p1"),
+        Bytes::from(body),
+    );
+
+    // 从未保存: 默认设置，版本号 0
+    let record = gs_get_player_settings(State(state.clone()), Path(player_id.clone())).await.expect("读取设置").0;
+    assert_eq!(record.revision, 0);
+
+    // 只能保存自己的设置
+    let body = || Bytes::from(r#"{"revision":0,"settings":{"schema_version":1}}"#);
+    let anonymous = gs_put_player_settings(State(state.clone()), Path(player_id.clone()), axum::http::HeaderMap::new(), body()).await;
+    assert!(matches!(anonymous, Err(GsError::GsAuthFailed(_))));
+    let other = gs_auth_headers(uuid::Uuid::new_v4(), "other");
+    let forged = gs_put_player_settings(State(state.clone()), Path(player_id.clone()), other, body()).await;
+    assert!(matches!(forged, Err(GsError::GsForbidden(_))));
+
+    // 旧格式 (无版本号、偏好在顶层) 保存时升级到当前格式
+    let saved = put(r#"{"revision":0,"settings":{"keybinds":{"end_turn":["Space"]},"volume":0.4}}"#.to_string())
+        .await.expect("保存设置").0;
+    assert_eq!(saved.revision, 1);
+    assert_eq!(saved.settings.ui["volume"], GcSettingValue::Number(0.4));
+
+    // 另一台设备按旧版本号保存: 409 冲突，返回当前版本号
+    let stale = put(r#"{"revision":0,"settings":{"schema_version":1}}"#.to_string()).await;
+    assert!(matches!(stale, Err(GsError::GsInvalidSettings(GcSettingsError::GcRevisionConflict { current: 1 }))));
+    let record = gs_get_player_settings(State(state.clone()), Path(player_id.clone())).await.expect("读取设置").0;
+    assert_eq!(record, saved);
+
+    // 结构不符或过大的设置被拒绝
+    let unknown = put(r#"{"revision":1,"settings":{"schema_version":1,"ui":{"a":[1]}}}"#.to_string()).await;
+    assert!(matches!(unknown, Err(GsError::GsInvalidSettings(GcSettingsError::GcInvalidJson(_)))));
+    let huge = format!(r#"{{"revision":1,"settings":{{"ui":{{"note":"{}"}}}}}}"#, "x".repeat(GC_SETTINGS_MAX_BYTES));
+    assert!(matches!(put(huge).await, Err(GsError::GsInvalidSettings(GcSettingsError::GcTooLarge { .. }))));
+}
+
+/// 开启故障注入的配置 (固定种子，故障序列可复现)
+fn gs_chaos_config(drop_rate: f64, seed: u64) -> GsConfig {
+    let chaos = GsChaosConfig { enabled: true, drop_rate, seed, ..GsChaosConfig::default() };
+    GsConfig { chaos, ..GsConfig::default() }
+}
+
+#[tokio::test]
+async fn test_chaos_dropped_broadcasts_recover_via_resync() {
+    let mut table = GsTestTable::gs_with_config(2, gs_chaos_config(0.5, 7));
+    let room_id = table.gs_start_match().await;
+    let chaos = table.state.chaos.clone().expect("调试构建应开启故障注入");
+
+    // p2 的连接: 广播经出站队列发送，其中一部分被注入丢弃
+    let (tx, mut rx) = mpsc::channel::<Message>(64);
+    let mut outbound = GsOutbound::gs_new(tx, 3).gs_with_chaos(Some(chaos.clone()));
+    table.clients[1].gs_take_inbox();
+    let mut sent = Vec::new();
+    let mut faulted = 0;
+    for _ in 0..4 {
+        let current = table.clients[0].gs_latest_battle()
+            .and_then(|b| b.gc_current_player_id().map(str::to_string))
+            .expect("应有行动玩家");
+        let index = table.gs_client_index(&current);
+        assert!(table.gs_send(index, GsWsMessage::EndTurn).await.is_empty());
+        for msg in table.clients[1].gs_take_inbox() {
+            match outbound.gs_push(serde_json::to_string(&msg).unwrap()) {
+                GsPush::Queued => {}
+                GsPush::Faulted => faulted += 1,
+                push => panic!("意外的入队结果: {:?}", push),
+            }
+            sent.push(msg);
+        }
+    }
+    assert!(faulted > 0 && faulted < sent.len() as u64, "丢弃 {} / {}", faulted, sent.len());
+    assert_eq!(chaos.gs_stats().dropped_outbound, faulted);
+
+    // 丢失的消息由重同步快照补齐: 客户端最终看到的战斗状态与无丢失时一致
+    assert_eq!(outbound.gs_resync_state(), GsResync::Due { dropped: faulted });
+    let snapshot = gs_resync_snapshot(&table.state, Some("p2"), Some(&room_id), false, faulted).await;
+    assert!(outbound.gs_push_resync(snapshot.iter().map(|m| serde_json::to_string(m).unwrap()).collect()));
+    let mut received = GsTestClient::gs_connect(&table.state);
+    while let Ok(Message::Text(text)) = rx.try_recv() {
+        received.inbox.push(gc_parse_json("message", &text).expect("消息应可解析"));
+    }
+    let expected = GsTestClient { inbox: sent, ..GsTestClient::gs_connect(&table.state) };
+    assert_eq!(
+        serde_json::to_value(received.gs_latest_battle()).unwrap(),
+        serde_json::to_value(expected.gs_latest_battle()).unwrap()
+    );
+    assert_eq!(outbound.gs_resync_state(), GsResync::Idle);
+}
+
+#[tokio::test]
+async fn test_chaos_dropped_requests_are_safe_to_retry() {
+    let mut table = GsTestTable::gs_with_config(2, gs_chaos_config(0.5, 11));
+    table.gs_start_match().await;
+    let battle = table.clients[0].gs_latest_battle().expect("应收到战斗状态");
+    let current = battle.gc_current_player_id().expect("应有行动玩家").to_string();
+    let index = table.gs_client_index(&current);
+
+    // 客户端收不到确认就重发，直到请求送达
+    let mut attempts = 0;
+    loop {
+        attempts += 1;
+        assert!(attempts <= 32, "请求始终未送达");
+        if let Some(responses) = table.gs_send_unreliable(index, GsWsMessage::EndTurn).await {
+            assert!(responses.is_empty(), "{:?}", responses);
+            break;
+        }
+    }
+    assert!(attempts > 1, "该种子下首个请求应被丢弃");
+    assert!(table.state.chaos.as_ref().is_some_and(|c| c.gs_stats().dropped_inbound >= 1));
+
+    // 已送达的请求被重复发送: 拒绝，回合只推进一次
+    let responses = table.gs_send(index, GsWsMessage::EndTurn).await;
+    assert!(matches!(responses.first(), Some(GsWsMessage::Error { code, .. }) if code == "END_TURN_FAILED"), "{:?}", responses);
+    let after = table.clients[0].gs_latest_battle().expect("应收到战斗状态");
+    assert_eq!(after.turn, battle.turn + 1);
+    assert_ne!(after.gc_current_player_id(), Some(current.as_str()));
+
+    // 出牌重发同理: 第二次找不到这张手牌，只打出一次
+    let next = after.gc_current_player_id().expect("应有行动玩家").to_string();
+    let next_index = table.gs_client_index(&next);
+    let hand = |table: &GsTestTable| table.clients[next_index].gs_latest_battle()
+        .and_then(|b| b.gc_find_player(&next).map(|p| p.hand.clone()))
+        .expect("应有手牌");
+    let before = hand(&table);
+    let play = || GsWsMessage::PlayCard { card_id: before[0].id.clone(), target_id: None };
+    assert!(table.gs_send(next_index, play()).await.is_empty());
+    table.gs_send(next_index, play()).await;
+    let remaining = hand(&table);
+    assert_eq!(remaining.len(), before.len() - 1);
+    assert!(remaining.iter().all(|c| c.id != before[0].id));
+}
+
+#[tokio::test]
+async fn test_chaos_faults_follow_config() {
+    // 关闭时不创建注入器
+    assert!(GsChaos::gs_from_config(&GsChaosConfig::default()).is_none());
+
+    // 同一种子得到相同的故障序列
+    let config = GsChaosConfig { enabled: true, drop_rate: 0.3, latency_ms: 40, db_error_rate: 1.0, seed: 99 };
+    let sequence = |chaos: &GsChaos| (0..32).map(|_| chaos.gs_drop_outbound()).collect::<Vec<_>>();
+    assert_eq!(sequence(&GsChaos::gs_new(config.clone())), sequence(&GsChaos::gs_new(config.clone())));
+
+    // 延迟不超过上限；数据库操作按概率失败
+    let chaos = GsChaos::gs_new(config.clone());
+    assert!((0..32).all(|_| chaos.gs_latency().is_some_and(|d| d <= Duration::from_millis(40))));
+    assert!(chaos.gs_db_fault().is_err());
+    let stats = chaos.gs_stats();
+    assert_eq!((stats.enabled, stats.delayed, stats.db_errors), (true, 32, 1));
+
+    let calm = GsChaos::gs_new(GsChaosConfig { seed: 99, ..GsChaosConfig::default() });
+    assert!(calm.gs_db_fault().is_ok() && !calm.gs_drop_inbound() && calm.gs_latency().is_none());
+}
+
+/// 携带登录令牌的请求头
+fn gs_auth_headers(user_id: uuid::Uuid, username: &str) -> axum::http::HeaderMap {
+    let token = crate::gs_auth::gs_create_token(user_id, username).expect("签发令牌");
+    let mut headers = axum::http::HeaderMap::new();
+    headers.insert(axum::http::header::AUTHORIZATION, format!("Bearer {}", token).parse().expect("请求头"));
+    headers
+}
+
+#[tokio::test]
+async fn test_guild_actions_use_authenticated_caller() {
+    let table = GsTestTable::gs_new(0);
+    let state = table.state.clone();
+    let org = GcOrganization::IronBlood;
+    let leader = uuid::Uuid::new_v4();
+    let member = uuid::Uuid::new_v4();
+
+    // 未登录不能加入或捐献
+    let joined = gs_join_guild(State(state.clone()), Path(org.clone()), axum::http::HeaderMap::new()).await;
+    assert!(matches!(joined, Err(GsError::GsAuthFailed(_))));
+    let donate = GsGuildDonateRequest { amount: 100, slot: 0 };
+    let donated = gs_donate_guild(State(state.clone()), Path(org.clone()), axum::http::HeaderMap::new(), GsJson(donate)).await;
+    assert!(matches!(donated, Err(GsError::GsAuthFailed(_))));
+
+    // 成员身份取自令牌，第一个加入者成为会长
+    for (id, name) in [(leader, "leader"), (member, "member")] {
+        assert!(gs_join_guild(State(state.clone()), Path(org.clone()), gs_auth_headers(id, name)).await.is_ok());
+    }
+    let promote = || GsGuildRoleRequest { target_id: member.to_string(), role: GcGuildRole::Officer };
+    let by_member = gs_set_guild_role(State(state.clone()), Path(org.clone()), gs_auth_headers(member, "member"), GsJson(promote())).await;
+    assert!(by_member.is_err(), "普通成员不能任免职位");
+    let by_leader = gs_set_guild_role(State(state.clone()), Path(org.clone()), gs_auth_headers(leader, "leader"), GsJson(promote())).await;
+    assert!(by_leader.is_ok(), "会长可以任命干部");
+
+    // 捐献必须扣除捐献者金币，无法扣款时金库不入账
+    let donate = GsGuildDonateRequest { amount: 100, slot: 0 };
+    let donated = gs_donate_guild(State(state.clone()), Path(org.clone()), gs_auth_headers(member, "member"), GsJson(donate)).await;
+    assert!(donated.is_err());
+    let treasury = state.gs_get_guild(&org).await;
+    assert_eq!(treasury.balance, 0);
+    assert_eq!(treasury.gc_member(&member.to_string()).map(|m| m.donated), Some(0));
+}
+
+#[tokio::test]
+async fn test_game_outcome_scores_territory_contest() {
+    let mut table = GsTestTable::gs_new(2);
+    let state = table.state.clone();
+    let zone = "zone_forest_nw";
+    for (org, player_id) in [(GcOrganization::IronBlood, "p1"), (GcOrganization::ShadowGuild, "p2")] {
+        state.gs_update_guild(&org, |t| Ok(t.gc_join(player_id))).await.expect("加入组织");
+    }
+    {
+        let mut territory = state.territory.write().await;
+        territory.gc_claim(zone, GcOrganization::IronBlood, 0).expect("占领");
+        territory.gc_claim(zone, GcOrganization::ShadowGuild, GC_TERRITORY_PROTECTION_SECS).expect("发起争夺");
+        territory.controls[0].contest.as_mut().expect("争夺中").ends_at = u64::MAX;
+    }
+
+    // 未登录不能占领，积分也没有上报接口
+    let claimed = gs_claim_territory(State(state.clone()), Path(zone.to_string()), axum::http::HeaderMap::new()).await;
+    assert!(matches!(claimed, Err(GsError::GsAuthFailed(_))));
+
+    // 双方组织成员打完一局，胜方组织获得积分
+    table.gs_start_match().await;
+    let (winner_ids, _) = table.gs_play_game().await;
+    let contest = state.territory.read().await.controls[0].contest.clone().expect("争夺中");
+    let expected = if winner_ids == ["p1"] { (GC_TERRITORY_WIN_POINTS, 0) } else { (0, GC_TERRITORY_WIN_POINTS) };
+    assert_eq!((contest.defender_points, contest.challenger_points), expected);
+}
+
+#[tokio::test]
+async fn test_world_boss_damage_is_server_computed_and_rate_limited() {
+    let mut table = GsTestTable::gs_new(1);
+    let now = gs_now();
+    *table.state.world_boss.write().await = Some(gc_create_ember_wyrm_event(now, now + 600));
+    let login = GsWsMessage::Login {
+        player_id: "p1".to_string(),
+        name: "玩家1".to_string(),
+        appearance: None,
+        slot: 1,
+        content_version: None,
+    };
+    table.gs_send(0, login).await;
+
+    // 伤害取自角色等级 (无数据库时为 1 级)，再经 Boss 防御减免
+    let max_hp = table.state.world_boss.read().await.as_ref().map_or(0, |e| e.boss.max_hp);
+    let responses = table.gs_send(0, GsWsMessage::AttackWorldBoss).await;
+    let Some(GsWsMessage::WorldBossHit { hit }) = responses.first() else {
+        panic!("应返回攻击结果: {:?}", responses);
+    };
+    assert!(hit.damage <= gc_world_boss_hit_damage(1, 255));
+    assert_eq!(hit.boss_hp, max_hp - hit.damage);
+
+    // 间隔内的连续攻击被拒绝
+    let responses = table.gs_send(0, GsWsMessage::AttackWorldBoss).await;
+    assert!(matches!(responses.first(), Some(GsWsMessage::Error { code, .. }) if code == "WORLD_BOSS_UNAVAILABLE"));
+}
+
+/// 直接写入伙伴收藏 (模拟服务器结算的捕获)
+async fn gs_give_companion(state: &GsAppState, player_id: &str, template_id: &str) {
+    let monster = GcMonsterPool::with_defaults().spawn_template(template_id, template_id).expect("模板应存在");
+    state.companions.write().await
+        .entry(player_id.to_string())
+        .or_default()
+        .add(GcCompanion::from_captured(&monster));
+}
+
+#[tokio::test]
+async fn test_equipped_companion_boosts_match_rewards() {
+    let mut table = GsTestTable::gs_new(2);
+    let state = table.state.clone();
+    let user = uuid::Uuid::new_v4();
+
+    // 伙伴接口只对登录用户开放；没有上报捕获的接口，伙伴只能装备已拥有的
+    let listed = gs_get_companions(State(state.clone()), axum::http::HeaderMap::new()).await;
+    assert!(matches!(listed, Err(GsError::GsAuthFailed(_))));
+    let equip = GsEquipCompanionRequest { companion_id: Some("companion_slime".to_string()) };
+    let equipped = gs_equip_companion(State(state.clone()), gs_auth_headers(user, "tamer"), GsJson(equip)).await;
+    assert!(equipped.is_err());
+    gs_give_companion(&state, &user.to_string(), "slime").await;
+    let equip = GsEquipCompanionRequest { companion_id: Some("companion_slime".to_string()) };
+    let equipped = gs_equip_companion(State(state.clone()), gs_auth_headers(user, "tamer"), GsJson(equip)).await;
+    assert!(equipped.is_ok());
+    assert!(state.gs_companions(&user.to_string()).await.equipped().is_some());
+
+    // 装备伙伴的玩家结算时获得闲置金币加成
+    let room_id = table.gs_start_match().await;
+    gs_give_companion(&state, "p1", "slime").await;
+    state.gs_equip_companion("p1", Some("companion_slime")).await.expect("装备伙伴");
+    let rewards = state.gs_grant_card_battle_rewards(&room_id, &["p1".to_string()]).await;
+    let grant = |id: &str| rewards.iter().find(|r| r.player_id == id).map(|r| r.grant).expect("应有奖励");
+    let win = state.reward_table.card_battle_win;
+    assert_eq!(grant("p1"), GcRewardGrant::gc_new(win.gold * 105 / 100, win.xp));
+    assert!(grant("p1").gold > win.gold);
+    assert_eq!(grant("p2"), state.reward_table.card_battle_loss);
+}
+
+#[tokio::test]
+async fn test_channel_started_over_websocket_resolves_on_turn_advance() {
+    let mut table = GsTestTable::gs_new(2);
+    table.gs_start_match().await;
+
+    let battle = table.clients[0].gs_latest_battle().expect("应收到战斗状态");
+    let caster = battle.gc_current_player_id().expect("应有行动玩家").to_string();
+    let caster_index = table.gs_client_index(&caster);
+    let other_index = 1 - caster_index;
+
+    // 未知模板与非自己回合都被拒绝
+    let unknown = GsWsMessage::StartChannel { template_id: "missing".to_string(), target_id: None };
+    let responses = table.gs_send(caster_index, unknown).await;
+    assert!(matches!(responses.first(), Some(GsWsMessage::Error { code, .. }) if code == "START_CHANNEL_FAILED"));
+    let out_of_turn = GsWsMessage::StartChannel { template_id: GC_CHANNEL_MEDITATION.to_string(), target_id: None };
+    let responses = table.gs_send(other_index, out_of_turn).await;
+    assert!(matches!(responses.first(), Some(GsWsMessage::Error { code, .. }) if code == "START_CHANNEL_FAILED"));
+
+    let start = GsWsMessage::StartChannel { template_id: GC_CHANNEL_PYROBLAST.to_string(), target_id: None };
+    assert!(table.gs_send(caster_index, start).await.is_empty());
+    let battle = table.clients[caster_index].gs_latest_battle().expect("应收到战斗状态");
+    assert_eq!(battle.channels.len(), 1);
+
+    // 引导者回合开始两次后结算
+    for index in [caster_index, other_index, caster_index, other_index] {
+        assert!(table.gs_send(index, GsWsMessage::EndTurn).await.is_empty());
+    }
+    let battle = table.clients[caster_index].gs_latest_battle().expect("应收到战斗状态");
+    assert!(battle.channels.is_empty());
+    assert!(battle.combat_log.iter().any(|e| matches!(e, GcCombatLogEvent::ChannelComplete { .. })));
+}
+
+#[tokio::test]
+async fn test_deck_foils_validated_against_stored_collection() {
+    let mut table = GsTestTable::gs_new(1);
+    let state = table.state.clone();
+    let user = uuid::Uuid::new_v4();
+
+    // 收藏接口只对登录用户开放，闪卡由服务器扣尘制作
+    let listed = gs_get_card_collection(State(state.clone()), axum::http::HeaderMap::new()).await;
+    assert!(matches!(listed, Err(GsError::GsAuthFailed(_))));
+    let mut collection = GcCardCollection::default();
+    collection.gc_add_cards("card_knight_attack", 2).expect("模板应存在");
+    collection.dust = 10_000;
+    state.card_collections.write().await.insert(user.to_string(), collection);
+    let craft = || GsJson(GsCraftFoilRequest { template_id: "card_knight_attack".to_string() });
+    assert!(gs_craft_foil(State(state.clone()), gs_auth_headers(user, "collector"), craft()).await.is_ok());
+    let stored = state.gs_card_collection(&user.to_string()).await;
+    assert_eq!(stored.gc_owned("card_knight_attack").foil_count, 1);
+    assert!(stored.dust < 10_000);
+
+    // 卡组的闪卡外观不能超过服务器保存的闪卡张数
+    let login = GsWsMessage::Login { player_id: "p1".to_string(), name: "玩家1".to_string(), appearance: None, slot: 1, content_version: None };
+    table.gs_send(0, login).await;
+    table.gs_send(0, GsWsMessage::CreateRoom { name: "闪卡".to_string() }).await;
+    let mut deck = GcDeck::gc_new("d1", "骑士", vec!["card_knight_attack".to_string(); 2]);
+    deck.foils = vec!["card_knight_attack".to_string()];
+    let responses = table.gs_send(0, GsWsMessage::SetDeck { deck: deck.clone() }).await;
+    assert!(matches!(responses.first(), Some(GsWsMessage::Error { code, .. }) if code == "SET_DECK_FAILED"));
+
+    let stored = state.gs_card_collection(&user.to_string()).await;
+    state.card_collections.write().await.insert("p1".to_string(), stored);
+    let responses = table.gs_send(0, GsWsMessage::SetDeck { deck }).await;
+    assert!(matches!(responses.first(), Some(GsWsMessage::DeckSet { .. })), "{:?}", responses);
+}
+
+#[tokio::test]
+async fn test_save_slot_changes_require_owner() {
+    let state = GsAppState::gs_in_memory(GsConfig::default());
+    let owner = uuid::Uuid::new_v4();
+    let other = uuid::Uuid::new_v4();
+    let path = |slot: u32| Path((owner.to_string(), slot));
+
+    // 未登录或操作他人存档被拒绝，不会走到数据库
+    let deleted = gs_delete_save_slot(State(state.clone()), path(1), axum::http::HeaderMap::new()).await;
+    assert!(matches!(deleted, Err(GsError::GsAuthFailed(_))));
+    let deleted = gs_delete_save_slot(State(state.clone()), path(1), gs_auth_headers(other, "other")).await;
+    assert!(matches!(deleted, Err(GsError::GsForbidden(_))));
+    let copy = || GsJson(GsCopySaveSlotRequest { to: 2 });
+    let copied = gs_copy_save_slot(State(state.clone()), path(1), gs_auth_headers(other, "other"), copy()).await;
+    assert!(matches!(copied, Err(GsError::GsForbidden(_))));
+
+    // 本人通过校验 (内存模式没有数据库)
+    let copied = gs_copy_save_slot(State(state.clone()), path(1), gs_auth_headers(owner, "owner"), copy()).await;
+    assert!(matches!(copied, Err(GsError::GsInternalError(_))));
+}
+
+#[tokio::test]
+async fn test_character_changes_require_owner() {
+    let state = GsAppState::gs_in_memory(GsConfig::default());
+    let owner = uuid::Uuid::new_v4();
+    let other = uuid::Uuid::new_v4();
+    let path = || Path(owner.to_string());
+    let query = || Query(GsSaveSlotQuery { slot: 1 });
+
+    let create = || GsJson(GsCreateCharacterRequest {
+        name: "Alice".to_string(),
+        profession: GcProfessionType::Knight,
+        appearance: Default::default(),
+    });
+    let created = gs_create_character(State(state.clone()), path(), query(), axum::http::HeaderMap::new(), create()).await;
+    assert!(matches!(created, Err(GsError::GsAuthFailed(_))));
+    let created = gs_create_character(State(state.clone()), path(), query(), gs_auth_headers(other, "other"), create()).await;
+    assert!(matches!(created, Err(GsError::GsForbidden(_))));
+
+    let rename = || GsJson(GsRenameCharacterRequest { name: "Mallory".to_string() });
+    let renamed = gs_rename_character(State(state.clone()), path(), query(), gs_auth_headers(other, "other"), rename()).await;
+    assert!(matches!(renamed, Err(GsError::GsForbidden(_))));
+    let renamed = gs_rename_character(State(state.clone()), path(), query(), gs_auth_headers(owner, "owner"), rename()).await;
+    assert!(matches!(renamed, Err(GsError::GsInternalError(_))));
+}
+
+/// 已结束的幽灵流程 (玩家身份与得分按请求体填写)
+fn gs_finished_ghost(player_id: &str, won_rounds: u32) -> GcGhostRun {
+    let mut run = GcGhostRun::gc_new(GcGhostMode::Tavern, player_id, player_id);
+    for round in 1..=won_rounds {
+        run.gc_record_round(GcGhostRound { round, units: Vec::new(), health: 30, won: Some(true) });
+    }
+    run.gc_finish();
+    run
+}
+
+#[tokio::test]
+async fn test_ghost_upload_uses_token_identity_and_is_capped() {
+    let state = GsAppState::gs_in_memory(GsConfig::default());
+    let owner = uuid::Uuid::new_v4();
+    let victim = uuid::Uuid::new_v4().to_string();
+
+    let uploaded = gs_upload_ghost(State(state.clone()), axum::http::HeaderMap::new(), GsJson(gs_finished_ghost(&victim, 3))).await;
+    assert!(matches!(uploaded, Err(GsError::GsAuthFailed(_))));
+
+    // 请求体中的玩家 ID 被忽略，幽灵保存在令牌用户名下
+    let uploaded = gs_upload_ghost(State(state.clone()), gs_auth_headers(owner, "owner"), GsJson(gs_finished_ghost(&victim, 3)))
+        .await.expect("上传应成功");
+    assert!(uploaded.new_best);
+    {
+        let ghosts = state.ghosts.read().await;
+        assert!(!ghosts.contains_key(&(victim.clone(), GcGhostMode::Tavern)));
+        let stored = ghosts.get(&(owner.to_string(), GcGhostMode::Tavern)).expect("应保存在令牌用户名下");
+        assert_eq!(stored.player_name, "owner");
+    }
+
+    // 篡改得分被拒绝
+    let mut forged = gs_finished_ghost(&owner.to_string(), 1);
+    forged.score = Some(10_000);
+    let uploaded = gs_upload_ghost(State(state.clone()), gs_auth_headers(owner, "owner"), GsJson(forged)).await;
+    assert!(matches!(uploaded, Err(GsError::GsInvalidGhost(_))));
+
+    // 达到上限后淘汰得分最低的幽灵
+    {
+        let mut ghosts = state.ghosts.write().await;
+        for i in ghosts.len()..GS_MAX_GHOSTS {
+            let id = format!("filler-{}", i);
+            ghosts.insert((id.clone(), GcGhostMode::Tavern), gs_finished_ghost(&id, 2));
+        }
+        ghosts.insert(("lowest".to_string(), GcGhostMode::Tavern), gs_finished_ghost("lowest", 1));
+        ghosts.remove(&("filler-1".to_string(), GcGhostMode::Tavern));
+    }
+    let newcomer = uuid::Uuid::new_v4();
+    let uploaded = gs_upload_ghost(State(state.clone()), gs_auth_headers(newcomer, "newcomer"), GsJson(gs_finished_ghost("x", 2)))
+        .await.expect("上传应成功");
+    assert!(uploaded.new_best);
+    let ghosts = state.ghosts.read().await;
+    assert_eq!(ghosts.len(), GS_MAX_GHOSTS);
+    assert!(!ghosts.contains_key(&("lowest".to_string(), GcGhostMode::Tavern)));
+    assert!(ghosts.contains_key(&(newcomer.to_string(), GcGhostMode::Tavern)));
+}
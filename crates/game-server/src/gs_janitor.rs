@@ -0,0 +1,134 @@
+//! 房间与在线玩家清理
+//!
+//! 模块: game-server
+//! 前缀: Gs
+//! 文档: 文档/03-game-server.md
+//!
+//! 后台任务定期清理长时间运行后残留的数据，避免房间表与玩家表无限增长:
+//! - 超过 `presence_ttl_secs` 没有任何消息 (含心跳) 的玩家视为离线，按断线处理
+//! - 指向已关闭房间的玩家房间 ID 被清除
+//! - 房间内玩家全部离线超过 `room_orphan_ttl_secs` 后关闭房间 (进行中的对局保留暂存存档)
+//! - 超过保留时间的分享回放被删除
+
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::gs_state::{gs_now, GsAppState};
+
+/// 清理间隔 (秒)
+const GS_JANITOR_TICK_SECS: u64 = 60;
+
+/// 清理统计 (累计值，供监控查询)
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct GsJanitorStats {
+    /// 清理次数
+    pub runs: u64,
+    /// 最后一次清理时间 (Unix 秒)
+    pub last_run_at: u64,
+    /// 关闭的房间数
+    pub rooms_removed: u64,
+    /// 移除的离线玩家数
+    pub players_removed: u64,
+    /// 清除的失效房间 ID 数
+    pub room_refs_cleared: u64,
+    /// 删除的过期回放数
+    pub replays_expired: u64,
+    /// 心跳超时关闭的连接数 (由连接循环记录)
+    pub heartbeat_timeouts: u64,
+    /// 当前房间数
+    pub rooms: usize,
+    /// 当前在线玩家数
+    pub players: usize,
+}
+
+/// 启动清理任务
+pub fn gs_spawn_janitor(state: GsAppState) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(GS_JANITOR_TICK_SECS));
+        // 房间 ID -> 开始无人在线的时间
+        let mut orphaned_since: HashMap<String, u64> = HashMap::new();
+
+        loop {
+            interval.tick().await;
+            gs_janitor_sweep(&state, &mut orphaned_since).await;
+        }
+    });
+}
+
+/// 执行一次清理
+async fn gs_janitor_sweep(state: &GsAppState, orphaned_since: &mut HashMap<String, u64>) {
+    let now = gs_now();
+
+    // 1. 离线玩家按断线处理 (离开房间并移除)
+    let stale_players: Vec<String> = state.players.read().await
+        .values()
+        .filter(|p| p.last_seen.saturating_add(state.config.presence_ttl_secs) <= now)
+        .map(|p| p.id.clone())
+        .collect();
+    for player_id in &stale_players {
+        state.gs_player_disconnect(player_id).await;
+    }
+
+    // 2. 清除指向已关闭房间的房间 ID
+    let room_ids: HashSet<String> = state.rooms.read().await.keys().cloned().collect();
+    let mut room_refs_cleared = 0;
+    for player in state.players.write().await.values_mut() {
+        if player.room_id.as_ref().is_some_and(|id| !room_ids.contains(id)) {
+            player.room_id = None;
+            room_refs_cleared += 1;
+        }
+    }
+
+    // 3. 关闭玩家全部离线超过 TTL 的房间
+    let online: HashSet<String> = state.players.read().await.keys().cloned().collect();
+    let mut removed_rooms = Vec::new();
+    {
+        let mut rooms = state.rooms.write().await;
+        orphaned_since.retain(|id, _| rooms.contains_key(id));
+        for room in rooms.values() {
+            if room.players.iter().any(|p| online.contains(&p.id)) {
+                orphaned_since.remove(&room.id);
+                continue;
+            }
+            let since = *orphaned_since.entry(room.id.clone()).or_insert(now);
+            if now.saturating_sub(since) >= state.config.room_orphan_ttl_secs {
+                removed_rooms.push(room.id.clone());
+            }
+        }
+        for room_id in &removed_rooms {
+            rooms.remove(room_id);
+            orphaned_since.remove(room_id);
+        }
+    }
+
+    // 4. 删除过期的分享回放
+    let replays_expired = state.replays.write().await.gc_prune(now);
+    if replays_expired > 0 {
+        tracing::info!("🧹 清理: 过期回放 {} 个", replays_expired);
+    }
+
+    let rooms = state.rooms.read().await.len();
+    let players = state.players.read().await.len();
+    if !stale_players.is_empty() || room_refs_cleared > 0 || !removed_rooms.is_empty() {
+        tracing::info!(
+            "🧹 清理: 离线玩家 {} 名，失效房间 ID {} 个，无人房间 {} 个 (剩余房间 {}，在线玩家 {})",
+            stale_players.len(),
+            room_refs_cleared,
+            removed_rooms.len(),
+            rooms,
+            players,
+        );
+    }
+
+    let mut stats = state.janitor_stats.write().await;
+    stats.runs += 1;
+    stats.last_run_at = now;
+    stats.rooms_removed += removed_rooms.len() as u64;
+    stats.players_removed += stale_players.len() as u64;
+    stats.room_refs_cleared += room_refs_cleared;
+    stats.replays_expired += replays_expired as u64;
+    stats.rooms = rooms;
+    stats.players = players;
+}
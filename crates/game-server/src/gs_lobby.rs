@@ -0,0 +1,75 @@
+//! 大厅房间列表推送
+//!
+//! 模块: game-server
+//! 前缀: Gs
+//! 文档: 文档/03-game-server.md
+//!
+//! 后台任务定期对比房间列表快照，向订阅大厅的连接推送增量 (创建/更新/关闭)。
+//! 对比间隔即节流窗口，窗口内的多次变化合并为一条消息；订阅时先下发完整列表，
+//! 客户端重新订阅即可重新同步。
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use crate::gs_state::{GsAppState, GsRoom, GS_BROADCAST_LOBBY};
+use crate::gs_websocket::{GsLobbyDelta, GsWsMessage, RoomInfo};
+
+/// 推送间隔 (毫秒)
+const GS_LOBBY_TICK_MS: u64 = 500;
+
+/// 房间列表项
+pub fn gs_room_info(room: &GsRoom) -> RoomInfo {
+    RoomInfo {
+        id: room.id.clone(),
+        name: room.name.clone(),
+        player_count: room.players.len(),
+        max_players: room.max_players,
+        game_started: room.game_started,
+    }
+}
+
+/// 当前房间列表 (按房间 ID 排序)
+pub async fn gs_lobby_snapshot(state: &GsAppState) -> BTreeMap<String, RoomInfo> {
+    state.rooms.read().await
+        .values()
+        .map(|room| (room.id.clone(), gs_room_info(room)))
+        .collect()
+}
+
+/// 对比两次快照，得到增量
+fn gs_lobby_diff(prev: &BTreeMap<String, RoomInfo>, next: &BTreeMap<String, RoomInfo>) -> Vec<GsLobbyDelta> {
+    let mut deltas: Vec<GsLobbyDelta> = next.iter()
+        .filter_map(|(id, room)| match prev.get(id) {
+            None => Some(GsLobbyDelta::Created { room: room.clone() }),
+            Some(old) if old != room => Some(GsLobbyDelta::Updated { room: room.clone() }),
+            Some(_) => None,
+        })
+        .collect();
+    deltas.extend(
+        prev.keys()
+            .filter(|id| !next.contains_key(*id))
+            .map(|id| GsLobbyDelta::Closed { room_id: id.clone() }),
+    );
+    deltas
+}
+
+/// 启动大厅推送任务
+pub fn gs_spawn_lobby_publisher(state: GsAppState) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_millis(GS_LOBBY_TICK_MS));
+        let mut last = BTreeMap::new();
+        
+        loop {
+            interval.tick().await;
+            
+            let current = gs_lobby_snapshot(&state).await;
+            let deltas = gs_lobby_diff(&last, &current);
+            last = current;
+            
+            if !deltas.is_empty() {
+                let msg = GsWsMessage::LobbyDeltas { deltas };
+                state.gs_broadcast_to_room(GS_BROADCAST_LOBBY, serde_json::to_string(&msg).unwrap_or_default(), vec![]);
+            }
+        }
+    });
+}
@@ -5,23 +5,39 @@
 //! 文档: 文档/12-MCP-API.md
 //!
 //! 实现 MCP over SSE 协议，允许 AI 代理控制游戏世界
+//!
+//! 所有 `/mcp` 请求需携带 API Key (`Authorization: Bearer <key>` 或 `X-Api-Key`)，
+//! Key 的权限级别与工具白名单决定可调用的工具，每次工具调用都记录审计日志
 
 use axum::{
-    extract::State,
-    response::{sse::{Event, Sse}, IntoResponse},
+    extract::{Query, Request, State},
+    http::{header, HeaderMap},
+    middleware::{self, Next},
+    response::{sse::{Event, Sse}, Response},
     routing::{get, post},
-    Json, Router,
+    Extension, Json, Router,
 };
 use futures_util::stream::{self, Stream};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
-use std::{convert::Infallible, time::Duration, path::PathBuf};
+use std::{convert::Infallible, time::Duration};
 use tokio_stream::StreamExt as _;
 use tokio::fs;
 use tracing::info;
 
-use crate::gs_state::GsAppState;
-use game_core::{GcMcpCommand, GcPosition};
+use crate::gs_error::GsError;
+use crate::gs_state::{gs_now, GsAppState};
+use game_core::{
+    gc_mcp_tool_scope, gc_parse_area_style, GcMcpAreaPlan, GcMcpAuditEntry, GcMcpAuditOutcome, GcMcpCommand,
+    GcMcpGroupEntity, GcMcpKey, GcMcpScope, GcMcpValidation, GcMcpWorld, GcPosition, GcPrefabGroup,
+    GcPrefabRegistry, GcSpawnRegion, GcAssetQuery,
+};
+
+/// 审计日志保留的最大条数
+const GS_MCP_AUDIT_CAPACITY: usize = 1000;
+
+/// 权限不足的 JSON-RPC 错误码 (服务器自定义区间)
+const GS_MCP_PERMISSION_DENIED: i32 = -32001;
 
 // =============================================================================
 // 数据结构
@@ -61,6 +77,84 @@ pub fn gs_mcp_routes(state: GsAppState) -> Router<GsAppState> {
     Router::new()
         .route("/sse", get(gs_mcp_sse_handler))
         .route("/message", post(gs_mcp_message_handler))
+        .route("/audit", get(gs_mcp_audit_handler))
+        .layer(middleware::from_fn_with_state(state, gs_mcp_auth))
+}
+
+// =============================================================================
+// 认证与审计
+// =============================================================================
+
+/// 校验 API Key，并把对应的 Key 放入请求扩展
+async fn gs_mcp_auth(
+    State(state): State<GsAppState>,
+    mut request: Request,
+    next: Next,
+) -> Result<Response, GsError> {
+    let token = gs_mcp_token(request.headers())
+        .ok_or_else(|| GsError::GsAuthFailed("缺少 MCP API Key".to_string()))?;
+    let key = state
+        .config
+        .mcp_keys
+        .iter()
+        .find(|k| gs_secret_eq(&k.secret, &token))
+        .cloned()
+        .ok_or_else(|| GsError::GsAuthFailed("MCP API Key 无效".to_string()))?;
+    request.extensions_mut().insert(key);
+    Ok(next.run(request).await)
+}
+
+/// 读取请求携带的 Key (Bearer 令牌或 X-Api-Key)
+fn gs_mcp_token(headers: &HeaderMap) -> Option<String> {
+    let bearer = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "));
+    let api_key = headers.get("x-api-key").and_then(|v| v.to_str().ok());
+    bearer.or(api_key).map(|t| t.trim().to_string()).filter(|t| !t.is_empty())
+}
+
+/// 比较密钥 (耗时与内容无关)
+fn gs_secret_eq(expected: &str, actual: &str) -> bool {
+    expected.len() == actual.len()
+        && expected.bytes().zip(actual.bytes()).fold(0u8, |acc, (a, b)| acc | (a ^ b)) == 0
+}
+
+/// 记录一次工具调用
+async fn gs_mcp_audit(state: &GsAppState, key: &GcMcpKey, tool: &str, arguments: Value, outcome: GcMcpAuditOutcome) {
+    info!(target: "mcp_audit", key_id = %key.id, tool, outcome = ?outcome, "MCP 工具调用");
+    let mut audit = state.mcp_audit.write().await;
+    if audit.len() >= GS_MCP_AUDIT_CAPACITY {
+        audit.pop_front();
+    }
+    audit.push_back(GcMcpAuditEntry {
+        timestamp: gs_now(),
+        key_id: key.id.clone(),
+        tool: tool.to_string(),
+        arguments,
+        outcome,
+    });
+}
+
+/// 审计日志查询参数
+#[derive(Debug, Deserialize)]
+struct GsMcpAuditQuery {
+    /// 返回最近的条数 (默认 100)
+    limit: Option<usize>,
+}
+
+/// 查看审计日志 (需要管理权限)
+async fn gs_mcp_audit_handler(
+    State(state): State<GsAppState>,
+    Extension(key): Extension<GcMcpKey>,
+    Query(query): Query<GsMcpAuditQuery>,
+) -> Result<Json<Vec<GcMcpAuditEntry>>, GsError> {
+    if key.scope < GcMcpScope::Admin {
+        return Err(GsError::GsForbidden(format!("Key {} 无权查看审计日志", key.id)));
+    }
+    let audit = state.mcp_audit.read().await;
+    let limit = query.limit.unwrap_or(100).min(audit.len());
+    Ok(Json(audit.iter().skip(audit.len() - limit).cloned().collect()))
 }
 
 /// SSE 连接处理
@@ -91,13 +185,14 @@ async fn gs_mcp_sse_handler(
 /// MCP 消息处理 (JSON-RPC)
 async fn gs_mcp_message_handler(
     State(state): State<GsAppState>,
+    Extension(key): Extension<GcMcpKey>,
     Json(request): Json<JsonRpcRequest>,
 ) -> Json<JsonRpcResponse> {
-    info!("📩 Received MCP request: {:?}", request.method);
+    info!("📩 Received MCP request: {:?} (key: {})", request.method, key.id);
 
     let response = match request.method.as_str() {
-        "tools/list" => gs_handle_list_tools(request.id),
-        "tools/call" => gs_handle_call_tool(state, request.params, request.id).await,
+        "tools/list" => gs_handle_list_tools(&key, request.id),
+        "tools/call" => gs_handle_authorized_call(state, &key, request.params, request.id).await,
         _ => JsonRpcResponse {
             jsonrpc: "2.0".to_string(),
             result: None,
@@ -117,9 +212,44 @@ async fn gs_mcp_message_handler(
 // 工具实现
 // =============================================================================
 
-/// 列出可用工具
-fn gs_handle_list_tools(id: Option<Value>) -> JsonRpcResponse {
-    let tools = json!({
+/// 检查权限后调用工具，并记录审计日志
+async fn gs_handle_authorized_call(
+    state: GsAppState,
+    key: &GcMcpKey,
+    params: Option<Value>,
+    id: Option<Value>,
+) -> JsonRpcResponse {
+    let tool = params.as_ref().and_then(|p| p.get("name")).and_then(|n| n.as_str()).unwrap_or("").to_string();
+    let arguments = params.as_ref().and_then(|p| p.get("arguments")).cloned().unwrap_or(Value::Null);
+    let dry_run = arguments.get("dry_run").and_then(|v| v.as_bool()).unwrap_or(false);
+
+    // 未知工具交给 gs_handle_call_tool 返回 "Tool not found"
+    if gc_mcp_tool_scope(&tool).is_some() && !key.gc_allows_call(&tool, dry_run) {
+        gs_mcp_audit(&state, key, &tool, arguments, GcMcpAuditOutcome::Denied).await;
+        return JsonRpcResponse {
+            jsonrpc: "2.0".to_string(),
+            result: None,
+            error: Some(JsonRpcError {
+                code: GS_MCP_PERMISSION_DENIED,
+                message: format!("Permission denied: key '{}' may not call '{}'", key.id, tool),
+                data: None,
+            }),
+            id,
+        };
+    }
+
+    let response = gs_handle_call_tool(state.clone(), &key.id, params, id).await;
+    let outcome = match &response.error {
+        Some(error) => GcMcpAuditOutcome::Failed { message: error.message.clone() },
+        None => GcMcpAuditOutcome::Ok,
+    };
+    gs_mcp_audit(&state, key, &tool, arguments, outcome).await;
+    response
+}
+
+/// 列出可用工具 (只列出该 Key 允许调用的工具)
+fn gs_handle_list_tools(key: &GcMcpKey, id: Option<Value>) -> JsonRpcResponse {
+    let mut tools = json!({
         "tools": [
             {
                 "name": "spawn_entity",
@@ -133,7 +263,9 @@ fn gs_handle_list_tools(id: Option<Value>) -> JsonRpcResponse {
                         "y": { "type": "number" },
                         "z": { "type": "number", "description": "通常为0，除非在空中" },
                         "rotation": { "type": "number", "default": 0 },
-                        "scale": { "type": "number", "default": 1 }
+                        "scale": { "type": "number", "default": 1 },
+                        "dry_run": { "type": "boolean", "description": "只按世界规则校验并返回预测结果，不执行" },
+                        "map_id": { "type": "string", "description": "可选，按该地图检查可通行" }
                     },
                     "required": ["entity_type", "prefab_id", "x", "y"]
                 }
@@ -157,7 +289,9 @@ fn gs_handle_list_tools(id: Option<Value>) -> JsonRpcResponse {
                     "properties": {
                         "x": { "type": "number" },
                         "y": { "type": "number" },
-                        "radius": { "type": "number" }
+                        "radius": { "type": "number" },
+                        "dry_run": { "type": "boolean", "description": "只按世界规则校验并返回预测结果，不执行" },
+                        "map_id": { "type": "string", "description": "可选，按该地图检查可通行" }
                     },
                     "required": ["x", "y", "radius"]
                 }
@@ -173,7 +307,9 @@ fn gs_handle_list_tools(id: Option<Value>) -> JsonRpcResponse {
                 "inputSchema": {
                     "type": "object",
                     "properties": {
-                        "category": { "type": "string", "description": "可选，筛选分类：trees, bushes, plants, flowers, grass, rocks, paths, mushrooms" }
+                        "category": { "type": "string", "description": "可选，筛选分类：trees, bushes, plants, flowers, grass, rocks, paths, mushrooms" },
+                        "query": { "type": "string", "description": "可选，按 ID、分类与标签全文搜索 (空格分隔的词需全部命中)" },
+                        "tags": { "type": "array", "items": { "type": "string" }, "description": "可选，只返回带有全部这些标签的素材" }
                     }
                 }
             },
@@ -188,14 +324,32 @@ fn gs_handle_list_tools(id: Option<Value>) -> JsonRpcResponse {
                         "center_x": { "type": "number" },
                         "center_y": { "type": "number" },
                         "radius": { "type": "number", "description": "生成区域半径" },
-                        "count": { "type": "integer", "description": "生成数量" }
+                        "count": { "type": "integer", "description": "生成数量" },
+                        "dry_run": { "type": "boolean", "description": "只按世界规则校验并返回预测结果，不执行" },
+                        "map_id": { "type": "string", "description": "可选，按该地图检查可通行" }
                     },
                     "required": ["entity_type", "prefab_ids", "center_x", "center_y", "radius", "count"]
                 }
             },
+            {
+                "name": "build_area",
+                "description": "按一句风格描述铺满一片区域 (例如 \"dense pine forest with a clearing\")，服务器按配方从预制体注册表中挑选素材，分层批量生成",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "style": { "type": "string", "description": "风格描述：pine forest, forest, dead forest, garden, rocky, meadow，可加 dense / sparse / with a clearing" },
+                        "center_x": { "type": "number" },
+                        "center_y": { "type": "number" },
+                        "radius": { "type": "number", "description": "区域半径 (最大 50)" },
+                        "dry_run": { "type": "boolean", "description": "只按世界规则校验并返回预测结果，不执行" },
+                        "map_id": { "type": "string", "description": "可选，按该地图检查可通行" }
+                    },
+                    "required": ["style", "center_x", "center_y", "radius"]
+                }
+            },
             {
                 "name": "get_world_info",
-                "description": "获取当前世界的基本信息，包括地图边界、可用预制体类型等",
+                "description": "获取当前世界的基本信息，包括游戏时间、区域天气、地图边界、可用预制体类型等",
                 "inputSchema": {
                     "type": "object",
                     "properties": {}
@@ -209,13 +363,126 @@ fn gs_handle_list_tools(id: Option<Value>) -> JsonRpcResponse {
                     "properties": {
                         "entity_id": { "type": "string", "description": "实体ID" },
                         "x": { "type": "number", "description": "新X坐标" },
-                        "y": { "type": "number", "description": "新Y坐标（地面高度为0）" }
+                        "y": { "type": "number", "description": "新Y坐标（地面高度为0）" },
+                        "dry_run": { "type": "boolean", "description": "只按世界规则校验并返回预测结果，不执行" },
+                        "map_id": { "type": "string", "description": "可选，按该地图检查可通行" }
                     },
                     "required": ["entity_id", "x", "y"]
                 }
+            },
+            {
+                "name": "set_tile_layer",
+                "description": "设置地图矩形区域的瓦片图层：海拔 (影响战斗地形) 与移动消耗 (影响寻路)。传 null 清除该图层",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "map_id": { "type": "string" },
+                        "x": { "type": "integer" },
+                        "y": { "type": "integer" },
+                        "width": { "type": "integer", "description": "默认 1" },
+                        "height": { "type": "integer", "description": "默认 1" },
+                        "elevation": { "type": ["integer", "null"] },
+                        "movement_cost": { "type": ["integer", "null"], "description": "至少为 1" }
+                    },
+                    "required": ["map_id", "x", "y"]
+                }
+            },
+            {
+                "name": "set_spawn_region",
+                "description": "添加或替换地图的命名刷怪区域 (遭遇生成器按区域挑选怪物与等级)",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "map_id": { "type": "string" },
+                        "region": {
+                            "type": "object",
+                            "description": "{ id, name, x, y, width, height, enemy_type?, monster_ids?, min_level?, max_level? }"
+                        }
+                    },
+                    "required": ["map_id", "region"]
+                }
+            },
+            {
+                "name": "save_group",
+                "description": "把多个预制体组合成命名的可复用组 (例如营地 = 帐篷 + 篝火 + 木箱) 并保存在服务器，同一 group_id 覆盖",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "group_id": { "type": "string", "description": "组 ID (小写字母、数字、_ 与 -)" },
+                        "name": { "type": "string", "description": "显示名称" },
+                        "members": {
+                            "type": "array",
+                            "description": "成员 [{ entity_type, prefab_id, x, y, rotation?, scale? }]，x/y 为相对原点的坐标",
+                            "items": { "type": "object" }
+                        },
+                        "origin_x": { "type": "number", "description": "可选，成员使用世界坐标时的组原点" },
+                        "origin_y": { "type": "number" }
+                    },
+                    "required": ["group_id", "name", "members"]
+                }
+            },
+            {
+                "name": "tag_asset",
+                "description": "给素材 (预制体 ID 或上传的模型文件名) 添加或移除标签，之后可以用 list_prefabs 的 query / tags 查找",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "asset": { "type": "string", "description": "预制体 ID 或模型文件名" },
+                        "add": { "type": "array", "items": { "type": "string" }, "description": "要添加的标签" },
+                        "remove": { "type": "array", "items": { "type": "string" }, "description": "要移除的标签" }
+                    },
+                    "required": ["asset"]
+                }
+            },
+            {
+                "name": "list_groups",
+                "description": "列出服务器保存的组合预制体及其成员",
+                "inputSchema": { "type": "object", "properties": {} }
+            },
+            {
+                "name": "place_group",
+                "description": "在指定位置整体放置组合预制体 (成员按相对位置与旋转展开)，任一成员不满足世界规则则整组不放置",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "group_id": { "type": "string" },
+                        "x": { "type": "number" },
+                        "y": { "type": "number" },
+                        "rotation": { "type": "number", "default": 0, "description": "弧度" },
+                        "dry_run": { "type": "boolean", "description": "只按世界规则校验并返回预测结果，不执行" },
+                        "map_id": { "type": "string", "description": "可选，按该地图检查可通行" }
+                    },
+                    "required": ["group_id", "x", "y"]
+                }
+            },
+            {
+                "name": "expand_group",
+                "description": "把已放置的组实例拆回独立实体，返回各实体的位置，之后可以单独移动或删除",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "instance_id": { "type": "string", "description": "place_group 返回的实例 ID" }
+                    },
+                    "required": ["instance_id"]
+                }
+            },
+            {
+                "name": "remove_spawn_region",
+                "description": "移除地图的刷怪区域",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "map_id": { "type": "string" },
+                        "region_id": { "type": "string" }
+                    },
+                    "required": ["map_id", "region_id"]
+                }
             }
         ]
     });
+    if let Some(list) = tools["tools"].as_array_mut() {
+        list.retain(|tool| tool["name"].as_str().is_some_and(|name| key.gc_allows(name)));
+    }
 
     JsonRpcResponse {
         jsonrpc: "2.0".to_string(),
@@ -225,9 +492,10 @@ fn gs_handle_list_tools(id: Option<Value>) -> JsonRpcResponse {
     }
 }
 
-/// 调用工具
+/// 调用工具 (key_id 记录为组合预制体的保存者)
 async fn gs_handle_call_tool(
     state: GsAppState,
+    key_id: &str,
     params: Option<Value>,
     id: Option<Value>,
 ) -> JsonRpcResponse {
@@ -288,12 +556,21 @@ async fn gs_handle_call_tool(
                 center: GcPosition { x: center_x, y: center_y },
                 radius: args.get("radius").and_then(|v| v.as_f64()).unwrap_or(10.0) as f32,
                 count: args.get("count").and_then(|v| v.as_u64()).unwrap_or(5) as u32,
+                positions: Vec::new(),
             })
         },
         "list_prefabs" => {
             // 直接返回预制体列表，不需要发送到客户端
             let category = args.get("category").and_then(|v| v.as_str());
-            let prefabs = gs_get_prefabs_list(category).await;
+            let query = GcAssetQuery {
+                q: args.get("query").and_then(|v| v.as_str()).unwrap_or("").to_string(),
+                tags: gs_string_list(&args, "tags"),
+            };
+            let prefabs = if query.gc_is_empty() {
+                gs_get_prefabs_list(&state, category).await
+            } else {
+                gs_search_prefabs(&state, category, &query).await
+            };
             return JsonRpcResponse {
                 jsonrpc: "2.0".to_string(),
                 result: Some(json!({ "content": [{ "type": "text", "text": prefabs }] })),
@@ -303,7 +580,7 @@ async fn gs_handle_call_tool(
         },
         "get_world_info" => {
             // 返回世界基本信息
-            let world_info = gs_get_world_info().await;
+            let world_info = gs_get_world_info(&state).await;
             return JsonRpcResponse {
                 jsonrpc: "2.0".to_string(),
                 result: Some(json!({ "content": [{ "type": "text", "text": world_info }] })),
@@ -311,6 +588,83 @@ async fn gs_handle_call_tool(
                 id,
             };
         },
+        "build_area" => {
+            // 一次调用展开为多条批量生成，单独处理
+            return match gs_build_area(&state, &args).await {
+                Ok(result) => JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    result: Some(result),
+                    error: None,
+                    id,
+                },
+                Err(error) => JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    result: None,
+                    error: Some(error),
+                    id,
+                },
+            };
+        },
+        "set_tile_layer" | "set_spawn_region" | "remove_spawn_region" => {
+            // 直接写入服务器分块地图，不需要发送到客户端
+            return match gs_apply_map_layer_tool(&state, name, &args).await {
+                Ok(text) => JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    result: Some(json!({ "content": [{ "type": "text", "text": text }] })),
+                    error: None,
+                    id,
+                },
+                Err(message) => JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    result: None,
+                    error: Some(JsonRpcError { code: -32602, message, data: None }),
+                    id,
+                },
+            };
+        },
+        "save_group" | "list_groups" | "tag_asset" => {
+            // 组定义与标签保存在服务器，不需要发送到客户端
+            let result = if name == "tag_asset" {
+                gs_tag_asset_tool(&state, &args).await
+            } else {
+                gs_prefab_group_tool(&state, key_id, name, &args).await
+            };
+            return match result {
+                Ok(text) => JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    result: Some(json!({ "content": [{ "type": "text", "text": text }] })),
+                    error: None,
+                    id,
+                },
+                Err(message) => JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    result: None,
+                    error: Some(JsonRpcError { code: -32602, message, data: None }),
+                    id,
+                },
+            };
+        },
+        "place_group" => {
+            let group_id = args.get("group_id").and_then(|v| v.as_str()).unwrap_or("");
+            let Some(group) = state.gs_prefab_group(group_id).await else {
+                return JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    result: None,
+                    error: Some(JsonRpcError { code: -32602, message: format!("组合预制体不存在: {}", group_id), data: None }),
+                    id,
+                };
+            };
+            let x = args.get("x").and_then(|v| v.as_f64()).unwrap_or(0.0) as i32;
+            let y = args.get("y").and_then(|v| v.as_f64()).unwrap_or(0.0) as i32;
+            let rotation = args.get("rotation").and_then(|v| v.as_f64()).unwrap_or(0.0) as f32;
+            Some(group.gc_place_command(&GcPosition { x, y }, rotation))
+        },
+        "expand_group" => {
+            Some(GcMcpCommand::ExpandGroup {
+                instance_id: args.get("instance_id").and_then(|v| v.as_str()).unwrap_or("").to_string(),
+                entities: Vec::new(),
+            })
+        },
         "move_entity" => {
             let x = args.get("x").and_then(|v| v.as_f64()).unwrap_or(0.0) as i32;
             let y = args.get("y").and_then(|v| v.as_f64()).unwrap_or(0.0) as i32;
@@ -323,16 +677,54 @@ async fn gs_handle_call_tool(
     };
 
     if let Some(cmd) = command {
-        // 广播命令给所有连接的客户端
-        // 注意：这里我们需要访问 WebSocket 的广播通道
-        // 由于 GsAppState 中没有直接暴露 sender，我们需要在 gs_state.rs 中添加或通过其他方式获取
-        // 暂时假设 state.broadcast_tx 存在 (需要修改 GsAppState)
+        // 按世界规则校验 (试运行与正式执行共用)，校验与记录在同一把写锁内完成
+        let dry_run = args.get("dry_run").and_then(|v| v.as_bool()).unwrap_or(false);
+        let mut world = state.mcp_world.write().await;
+        let validation = match gs_validate_mcp_command(&state, &world, &cmd, &args).await {
+            Ok(validation) => validation,
+            Err(message) => return JsonRpcResponse {
+                jsonrpc: "2.0".to_string(),
+                result: None,
+                error: Some(JsonRpcError { code: -32602, message, data: None }),
+                id,
+            },
+        };
+        if dry_run {
+            // 试运行: 只返回预测结果，不广播
+            return JsonRpcResponse {
+                jsonrpc: "2.0".to_string(),
+                result: Some(json!({
+                    "content": [{ "type": "text", "text": serde_json::to_string_pretty(&validation).unwrap_or_default() }],
+                    "validation": validation,
+                })),
+                error: None,
+                id,
+            };
+        }
+        if !validation.valid {
+            let reason = validation.issues.first().map(|i| i.message.clone()).unwrap_or_default();
+            return JsonRpcResponse {
+                jsonrpc: "2.0".to_string(),
+                result: None,
+                error: Some(JsonRpcError {
+                    code: -32602,
+                    message: format!("World rules rejected command: {}", reason),
+                    data: Some(json!(validation)),
+                }),
+                id,
+            };
+        }
         
+        // 广播命令给所有连接的客户端 (批量生成附带服务器挑选的位置)
         if let Some(tx) = &state.mcp_tx {
-             let _ = tx.send(cmd);
+             let _ = tx.send(validation.command.clone());
+             world.gc_apply(&validation.command);
              JsonRpcResponse {
                 jsonrpc: "2.0".to_string(),
-                result: Some(json!({ "content": [{ "type": "text", "text": "Command executed successfully" }] })),
+                result: Some(json!({
+                    "content": [{ "type": "text", "text": gs_mcp_report(&validation) }],
+                    "validation": validation,
+                })),
                 error: None,
                 id,
             }
@@ -354,10 +746,287 @@ async fn gs_handle_call_tool(
     }
 }
 
+/// 执行结果说明 (批量生成报告实际数量与跳过原因)
+fn gs_mcp_report(validation: &GcMcpValidation) -> String {
+    match &validation.command {
+        GcMcpCommand::SpawnBatch { .. } => {}
+        GcMcpCommand::SpawnGroup { group_id, instance_id, entities, .. } => {
+            return format!("Placed group {} as instance {} ({} entities)", group_id, instance_id, entities.len());
+        }
+        GcMcpCommand::ExpandGroup { instance_id, entities } => {
+            let mut text = format!("Expanded {} into {} entities", instance_id, entities.len());
+            for entity in entities {
+                text.push_str(&format!("\n- {} {} at ({}, {})", entity.entity_type, entity.prefab_id, entity.position.x, entity.position.y));
+            }
+            return text;
+        }
+        _ => return "Command executed successfully".to_string(),
+    }
+    let mut text = format!("Spawned {}/{} entities", validation.added, validation.requested);
+    if !validation.issues.is_empty() {
+        let reasons: Vec<&str> = validation.issues.iter().map(|i| i.message.as_str()).collect();
+        text.push_str(&format!(" (skipped: {})", reasons.join("; ")));
+    }
+    text
+}
+
+/// 按世界规则校验命令 (参数 map_id 指定时使用地图边界并检查可通行)
+async fn gs_validate_mcp_command(
+    state: &GsAppState,
+    world: &GcMcpWorld,
+    cmd: &GcMcpCommand,
+    args: &Value,
+) -> Result<GcMcpValidation, String> {
+    match args.get("map_id").and_then(|v| v.as_str()) {
+        Some(map_id) => {
+            let maps = state.maps.read().await;
+            let map = maps.get(map_id).ok_or_else(|| format!("地图不存在: {}", map_id))?;
+            Ok(world.gc_validate(cmd, Some(map)))
+        }
+        None => Ok(world.gc_validate(cmd, None)),
+    }
+}
+
+// =============================================================================
+// 区域布局
+// =============================================================================
+
+/// 按风格规划并执行区域布局 (试运行只返回规划结果)
+async fn gs_build_area(state: &GsAppState, args: &Value) -> Result<Value, JsonRpcError> {
+    let invalid = |message: String| JsonRpcError { code: -32602, message, data: None };
+    let style = args.get("style").and_then(|v| v.as_str()).ok_or_else(|| invalid("缺少 style".to_string()))?;
+    let style = gc_parse_area_style(style).map_err(invalid)?;
+    let center = GcPosition::gc_new(
+        args.get("center_x").and_then(|v| v.as_f64()).unwrap_or(0.0) as i32,
+        args.get("center_y").and_then(|v| v.as_f64()).unwrap_or(0.0) as i32,
+    );
+    let radius = args.get("radius").and_then(|v| v.as_f64()).unwrap_or(10.0) as f32;
+    let dry_run = args.get("dry_run").and_then(|v| v.as_bool()).unwrap_or(false);
+    let registry = gs_load_prefab_registry(state).await;
+
+    // 规划与记录在同一把写锁内完成
+    let mut world = state.mcp_world.write().await;
+    let plan = match args.get("map_id").and_then(|v| v.as_str()) {
+        Some(map_id) => {
+            let maps = state.maps.read().await;
+            let map = maps.get(map_id).ok_or_else(|| invalid(format!("地图不存在: {}", map_id)))?;
+            world.gc_plan_area(&style, &center, radius, &registry, Some(map))
+        }
+        None => world.gc_plan_area(&style, &center, radius, &registry, None),
+    };
+    if dry_run {
+        return Ok(json!({
+            "content": [{ "type": "text", "text": serde_json::to_string_pretty(&plan).unwrap_or_default() }],
+            "plan": plan,
+        }));
+    }
+    if !plan.gc_is_valid() {
+        return Err(JsonRpcError {
+            code: -32602,
+            message: format!("World rules rejected command: 区域内放不下任何 {} 实体", plan.recipe),
+            data: Some(json!(plan)),
+        });
+    }
+
+    let tx = state.mcp_tx.as_ref().ok_or_else(|| JsonRpcError {
+        code: -32603,
+        message: "Internal error: MCP channel not available".to_string(),
+        data: None,
+    })?;
+    for command in plan.gc_commands() {
+        let _ = tx.send(command.clone());
+        world.gc_apply(command);
+    }
+    Ok(json!({
+        "content": [{ "type": "text", "text": gs_area_report(&plan) }],
+        "plan": plan,
+    }))
+}
+
+/// 区域布局说明 (各层实际数量与缺失的素材)
+fn gs_area_report(plan: &GcMcpAreaPlan) -> String {
+    let mut text = format!("Built {} area: spawned {}/{} entities", plan.recipe, plan.added, plan.requested);
+    for layer in &plan.layers {
+        if let GcMcpCommand::SpawnBatch { entity_type, .. } = &layer.command {
+            text.push_str(&format!("\n- {}: {}/{}", entity_type, layer.added, layer.requested));
+        }
+    }
+    if !plan.missing_layers.is_empty() {
+        text.push_str(&format!("\n(no prefabs for: {})", plan.missing_layers.join(", ")));
+    }
+    text
+}
+
+// =============================================================================
+// 地图图层编辑
+// =============================================================================
+
+/// 执行地图图层工具，修改的块会被标记为脏块等待保存
+async fn gs_apply_map_layer_tool(state: &GsAppState, name: &str, args: &Value) -> Result<String, String> {
+    let map_id = args.get("map_id").and_then(|v| v.as_str()).ok_or("缺少 map_id")?;
+    let mut maps = state.maps.write().await;
+    let map = maps.get_mut(map_id).ok_or_else(|| format!("地图不存在: {}", map_id))?;
+    
+    match name {
+        "set_tile_layer" => {
+            let x = args.get("x").and_then(|v| v.as_i64()).ok_or("缺少 x")? as i32;
+            let y = args.get("y").and_then(|v| v.as_i64()).ok_or("缺少 y")? as i32;
+            let width = args.get("width").and_then(|v| v.as_u64()).unwrap_or(1).min(256) as i32;
+            let height = args.get("height").and_then(|v| v.as_u64()).unwrap_or(1).min(256) as i32;
+            // 字段缺省表示不修改，显式 null 表示清除
+            let elevation = args.get("elevation")
+                .map(|v| v.as_i64().map(|e| e.clamp(i16::MIN as i64, i16::MAX as i64) as i16));
+            let movement_cost = args.get("movement_cost")
+                .map(|v| v.as_u64().map(|c| c.clamp(1, u32::MAX as u64) as u32));
+            if elevation.is_none() && movement_cost.is_none() {
+                return Err("至少需要 elevation 或 movement_cost".to_string());
+            }
+            
+            let mut changed = 0;
+            for ty in y..y.saturating_add(height) {
+                for tx in x..x.saturating_add(width) {
+                    let pos = GcPosition::gc_new(tx, ty);
+                    let mut ok = false;
+                    if let Some(elevation) = elevation {
+                        ok |= map.gc_set_elevation(&pos, elevation);
+                    }
+                    if let Some(movement_cost) = movement_cost {
+                        ok |= map.gc_set_movement_cost(&pos, movement_cost);
+                    }
+                    changed += ok as usize;
+                }
+            }
+            Ok(format!("已更新 {} 个瓦片，待保存块: {}", changed, map.gc_dirty_chunks().len()))
+        },
+        "set_spawn_region" => {
+            let region: GcSpawnRegion = args.get("region")
+                .cloned()
+                .ok_or("缺少 region")
+                .and_then(|v| serde_json::from_value(v).map_err(|_| "region 格式错误"))?;
+            let id = region.id.clone();
+            map.header.gc_set_spawn_region(region);
+            Ok(format!("已设置刷怪区域 {}", id))
+        },
+        _ => {
+            let region_id = args.get("region_id").and_then(|v| v.as_str()).ok_or("缺少 region_id")?;
+            if map.header.gc_remove_spawn_region(region_id) {
+                Ok(format!("已移除刷怪区域 {}", region_id))
+            } else {
+                Err(format!("刷怪区域不存在: {}", region_id))
+            }
+        },
+    }
+}
+
+// =============================================================================
+// 组合预制体
+// =============================================================================
+
+/// 保存或列出组合预制体
+async fn gs_prefab_group_tool(state: &GsAppState, key_id: &str, name: &str, args: &Value) -> Result<String, String> {
+    if name == "list_groups" {
+        let groups = state.gs_list_prefab_groups().await;
+        if groups.is_empty() {
+            return Ok("还没有组合预制体，使用 `save_group` 创建".to_string());
+        }
+        let mut text = String::from("# 组合预制体\n");
+        for group in &groups {
+            text.push_str(&format!("\n## {} (`{}`，{} 个成员，半径 {:.1})\n", group.name, group.id, group.members.len(), group.gc_radius()));
+            for member in &group.members {
+                text.push_str(&format!("- {} {} 偏移 ({}, {})\n", member.entity_type, member.prefab_id, member.offset.x, member.offset.y));
+            }
+        }
+        return Ok(text);
+    }
+
+    let group_id = args.get("group_id").and_then(|v| v.as_str()).ok_or("缺少 group_id")?;
+    let group_name = args.get("name").and_then(|v| v.as_str()).ok_or("缺少 name")?;
+    let members = args.get("members").and_then(|v| v.as_array()).ok_or("缺少 members")?;
+    let entities: Vec<GcMcpGroupEntity> = members
+        .iter()
+        .map(|m| GcMcpGroupEntity {
+            entity_type: m.get("entity_type").and_then(|v| v.as_str()).unwrap_or("structure").to_string(),
+            prefab_id: m.get("prefab_id").and_then(|v| v.as_str()).unwrap_or("").to_string(),
+            position: GcPosition {
+                x: m.get("x").and_then(|v| v.as_f64()).unwrap_or(0.0) as i32,
+                y: m.get("y").and_then(|v| v.as_f64()).unwrap_or(0.0) as i32,
+            },
+            rotation: m.get("rotation").and_then(|v| v.as_f64()).unwrap_or(0.0) as f32,
+            scale: m.get("scale").and_then(|v| v.as_f64()).unwrap_or(1.0) as f32,
+        })
+        .collect();
+    let origin = GcPosition {
+        x: args.get("origin_x").and_then(|v| v.as_f64()).unwrap_or(0.0) as i32,
+        y: args.get("origin_y").and_then(|v| v.as_f64()).unwrap_or(0.0) as i32,
+    };
+    let group = state
+        .gs_save_prefab_group(GcPrefabGroup::gc_compose(group_id, group_name, &origin, &entities), key_id)
+        .await?;
+    Ok(format!("已保存组合预制体 {} ({} 个成员)，使用 `place_group` 放置", group.id, group.members.len()))
+}
+
 // =============================================================================
 // 素材查询
 // =============================================================================
 
+/// 读取字符串数组参数
+fn gs_string_list(args: &Value, key: &str) -> Vec<String> {
+    args.get(key)
+        .and_then(|v| v.as_array())
+        .map(|list| list.iter().filter_map(|v| v.as_str()).map(|v| v.to_string()).collect())
+        .unwrap_or_default()
+}
+
+/// 增删素材标签
+async fn gs_tag_asset_tool(state: &GsAppState, args: &Value) -> Result<String, String> {
+    let asset = args.get("asset").and_then(|v| v.as_str()).ok_or("缺少 asset")?;
+    let tags = state
+        .gs_edit_asset_tags(asset, &gs_string_list(args, "add"), &gs_string_list(args, "remove"))
+        .await?;
+    if tags.is_empty() {
+        return Ok(format!("{} 没有标签", asset));
+    }
+    Ok(format!("{} 的标签: {}", asset, tags.join(", ")))
+}
+
+/// 搜索返回的最大条数
+const GS_PREFAB_SEARCH_LIMIT: usize = 50;
+
+/// 按搜索词与标签查找预制体 (按匹配程度排序)
+async fn gs_search_prefabs(state: &GsAppState, category_filter: Option<&str>, query: &GcAssetQuery) -> String {
+    let registry = gs_load_prefab_registry(state).await;
+    let tags = state.gs_asset_tag_registry().await;
+    let mut matches: Vec<(u32, &str, &str, Vec<String>)> = Vec::new();
+    for (category, prefab_ids) in &registry {
+        if category_filter.is_some_and(|filter| filter != category) {
+            continue;
+        }
+        for prefab_id in prefab_ids {
+            let prefab_tags = tags.gc_tags(prefab_id);
+            if let Some(score) = query.gc_score(prefab_id, &[category.as_str()], &prefab_tags) {
+                matches.push((score, prefab_id, category, prefab_tags));
+            }
+        }
+    }
+    if matches.is_empty() {
+        return "没有匹配的素材，可以去掉部分搜索词或标签后重试".to_string();
+    }
+    matches.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(b.1)));
+
+    let mut result = format!("# 素材搜索结果 ({} 个)\n\n", matches.len());
+    for (_, prefab_id, category, prefab_tags) in matches.iter().take(GS_PREFAB_SEARCH_LIMIT) {
+        result.push_str(&format!("- `{}` ({})", prefab_id, category));
+        if !prefab_tags.is_empty() {
+            result.push_str(&format!(" 标签: {}", prefab_tags.join(", ")));
+        }
+        result.push('\n');
+    }
+    if matches.len() > GS_PREFAB_SEARCH_LIMIT {
+        result.push_str(&format!("\n只显示前 {} 个，请增加搜索词缩小范围\n", GS_PREFAB_SEARCH_LIMIT));
+    }
+    result
+}
+
 /// 预制体分类数据结构
 #[derive(Debug, Serialize, Deserialize)]
 struct PrefabCategory {
@@ -381,10 +1050,49 @@ struct PrefabsData {
     categories: std::collections::HashMap<String, PrefabCategory>,
 }
 
+/// 读取预制体注册表 (配置文件不存在或格式错误时使用内置列表)
+pub(crate) async fn gs_load_prefab_registry(state: &GsAppState) -> GcPrefabRegistry {
+    let prefabs_path = state.config.gs_map_data_dir().join("nature_prefabs.json");
+    let data = fs::read_to_string(&prefabs_path)
+        .await
+        .ok()
+        .and_then(|content| serde_json::from_str::<PrefabsData>(&content).ok());
+    match data {
+        Some(data) => data
+            .categories
+            .into_iter()
+            .map(|(id, category)| (id, category.models.into_iter().map(|m| m.id).collect()))
+            .collect(),
+        None => gs_builtin_prefab_registry(),
+    }
+}
+
+/// 内置预制体注册表 (与内置素材列表一致)
+fn gs_builtin_prefab_registry() -> GcPrefabRegistry {
+    let numbered = |prefix: &str, count: u32| (1..=count).map(|i| format!("{}{}", prefix, i)).collect::<Vec<_>>();
+    let named = |ids: &[&str]| ids.iter().map(|id| id.to_string()).collect::<Vec<_>>();
+    let mut registry = GcPrefabRegistry::new();
+    registry.insert(
+        "trees".to_string(),
+        ["pine_", "common_tree_", "dead_tree_", "twisted_tree_"].iter().flat_map(|p| numbered(p, 5)).collect(),
+    );
+    registry.insert("bushes".to_string(), named(&["bush_common", "bush_flowers"]));
+    registry.insert(
+        "rocks".to_string(),
+        numbered("rock_medium_", 3).into_iter().chain(numbered("pebble_round_", 5)).collect(),
+    );
+    registry.insert("plants".to_string(), named(&["fern_1", "clover_1", "clover_2"]));
+    registry.insert(
+        "flowers".to_string(),
+        named(&["flower_3_group", "flower_4_group"]).into_iter().chain(numbered("petal_", 5)).collect(),
+    );
+    registry
+}
+
 /// 获取预制体列表
-async fn gs_get_prefabs_list(category_filter: Option<&str>) -> String {
+async fn gs_get_prefabs_list(state: &GsAppState, category_filter: Option<&str>) -> String {
     // 尝试读取 nature_prefabs.json
-    let prefabs_path = PathBuf::from("../client/public/assets/data/nature_prefabs.json");
+    let prefabs_path = state.config.gs_map_data_dir().join("nature_prefabs.json");
     
     match fs::read_to_string(&prefabs_path).await {
         Ok(content) => {
@@ -451,10 +1159,38 @@ async fn gs_get_prefabs_list(category_filter: Option<&str>) -> String {
     }
 }
 
-/// 获取世界基本信息
-async fn gs_get_world_info() -> String {
-    r#"# 游戏世界信息
+/// 获取世界基本信息 (含当前游戏时间与区域天气)
+async fn gs_get_world_info(state: &GsAppState) -> String {
+    let time = state.world_clock.gc_time_at(gs_now());
+    let effects = time.phase.gc_effects();
+    let mut info = format!(
+        "# 游戏世界信息\n\n## 当前时间\n- **第 {} 天 {:02}:{:02}** ({})\n- 时间倍率: 现实 1 秒 = 游戏 {} 秒\n- 下个时段开始: Unix {}\n- 遭遇等级加成: +{}，商店{}\n",
+        time.day,
+        time.hour,
+        time.minute,
+        time.phase.gc_name(),
+        time.time_ratio,
+        time.next_phase_at,
+        effects.encounter_level_bonus,
+        if effects.shop_open { format!("价格 {}%", effects.shop_price_percent) } else { "打烊".to_string() },
+    );
+    info.push_str("\n## 区域天气\n");
+    for region in state.gs_weather_now() {
+        info.push_str(&format!(
+            "- **{}** ({:?}): {}，遭遇等级加成 +{}，下次更新: Unix {}\n",
+            region.region_id,
+            region.climate,
+            region.weather.gc_name(),
+            region.weather.gc_encounter_level_bonus(),
+            region.next_update_at,
+        ));
+    }
+    info.push_str(GS_WORLD_INFO_STATIC);
+    info
+}
 
+/// 世界基本信息 (固定部分)
+const GS_WORLD_INFO_STATIC: &str = r#"
 ## 地图边界
 - **X轴范围**: -100 到 +100 (中心为玩家出生点)
 - **Y轴范围**: -100 到 +100 (地面高度为0)
@@ -489,8 +1225,10 @@ async fn gs_get_world_info() -> String {
 
 ## 工具使用技巧
 - 使用 `list_prefabs` 查看所有可用素材
+- 使用 `build_area` 按风格描述一次铺满一片区域 (森林、花园、乱石滩等)
 - 使用 `spawn_batch` 快速生成区域
+- 素材很多时用 `list_prefabs(query=..., tags=[...])` 搜索，`tag_asset` 给常用素材打标签
+- 使用 `save_group` 把常用组合 (营地、哨塔) 保存为组合预制体，`place_group` 一次放置，`expand_group` 拆开后单独编辑
 - 使用 `undo` 撤销错误操作
 - 使用 `move_entity` 微调位置
-"#.to_string()
-}
+"#;
@@ -0,0 +1,30 @@
+//! 暂存对局清理
+//!
+//! 模块: game-server
+//! 前缀: Gs
+//! 文档: 文档/03-game-server.md
+//!
+//! 后台任务定期删除被放弃的暂存对局 (按 `GsConfig::park_policy` 判断过期)。
+
+use std::time::Duration;
+
+use crate::gs_state::GsAppState;
+
+/// 清理间隔 (秒)
+const GS_PARKED_CLEANUP_SECS: u64 = 3600;
+
+/// 启动暂存对局清理任务
+pub fn gs_spawn_parked_battle_cleanup(state: GsAppState) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(GS_PARKED_CLEANUP_SECS));
+        
+        loop {
+            interval.tick().await;
+            
+            let expired = state.gs_expire_parked_battles().await;
+            if expired > 0 {
+                tracing::info!("清理过期暂存对局: {} 个", expired);
+            }
+        }
+    });
+}
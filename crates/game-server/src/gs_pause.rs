@@ -0,0 +1,31 @@
+//! 对局暂停到期
+//!
+//! 模块: game-server
+//! 前缀: Gs
+//! 文档: 文档/03-game-server.md
+//!
+//! 后台任务检查暂停中的房间，暂停额度用尽时自动继续并广播最新暂停状态。
+
+use std::time::Duration;
+
+use crate::gs_state::{gs_now, GsAppState};
+use crate::gs_websocket::gs_broadcast_pause;
+
+/// 检查间隔 (秒)
+const GS_PAUSE_CHECK_SECS: u64 = 1;
+
+/// 启动暂停到期检查任务
+pub fn gs_spawn_pause_watch(state: GsAppState) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(GS_PAUSE_CHECK_SECS));
+        
+        loop {
+            interval.tick().await;
+            
+            for (room_id, status) in state.gs_expire_pauses(gs_now()).await {
+                tracing::info!("暂停额度用尽，对局继续: 房间 {}", room_id);
+                gs_broadcast_pause(&state, &room_id, status);
+            }
+        }
+    });
+}
@@ -5,7 +5,9 @@
 //! 文档: 文档/03-game-server.md
 
 use axum::{
-    extract::{Path, State, Multipart},
+    body::Bytes,
+    extract::{Path, Query, State, Multipart},
+    response::IntoResponse,
     Json,
 };
 use serde::{Deserialize, Serialize};
@@ -17,10 +19,25 @@ use std::fs::OpenOptions;
 use std::io::Write;
 use uuid::Uuid;
 
-use crate::gs_error::GsError;
-use crate::gs_state::{GsAppState, GsMemoryUser};
+use crate::gs_error::{GsError, GsJson};
+use crate::gs_backpressure::GsBroadcastStats;
+use crate::gs_chaos::GsChaosStats;
+use crate::gs_janitor::GsJanitorStats;
+use crate::gs_mcp::gs_load_prefab_registry;
+use crate::gs_thumbnail::{gs_generate_preview, gs_read_preview, GsAssetPreview};
+use crate::gs_state::{gs_now, GsAppState, GsMemoryUser};
+use crate::gs_websocket::GsWsMessage;
 use crate::gs_auth;
-use game_core::{GcInventory, GcProfessionType};
+use game_core::{
+    GcGuildBuffs, GcGuildMember, GcGuildRole, GcGuildTreasury, GcGuildUpgrade, GcInventory,
+    gc_validate_character_name, gc_validate_save_slot, gc_validate_save_slot_copy, GcAppearance, GcCharacter, GcSaveSlotSummary,
+    gc_encounter_seed, gc_validate_map_upload, GcContentVersion, gc_generate_encounter_in, gc_generate_encounter_on, GcChunkCoord, GcDifficulty, GcDifficultySettings, GcEncounter, GcMapChunk, GcWorldTerrainType, GcMapHeader, GcOrganization, GcPosition, GcProfessionType, GcRuleset, GcSeason, GcWeeklyEntry, GC_WEEK_SECS, GcTerritoryChange, GcTerritoryClaim, GcTerritoryOverlay, GcWorldTime, GcRegionWeather,
+    gc_parse_json, gc_verify_match, GcGameMode, GcMatchAudit, GcMonsterSkins, GcCompanionCollection, GcCardCollection, GcReplay, GcReplayError, GcReplayFilter, GcReplaySummary,
+    GcRankBracket, GcUsageKind, GcUsageRate, gc_migrate_settings, GcSettingsError, GcSettingsRecord, GC_SETTINGS_MAX_BYTES,
+    gc_race_ghost, GcGhostMode, GcGhostRace, GcGhostRun, GcOnboardingChecklist, GcOnboardingProgress,
+    GcAccountDeletion, gc_normalize_text, gc_sanitize_text, GcTextField,
+    GcAssetQuery, GcAssetTagCount,
+};
 
 /// 健康检查响应
 #[derive(Serialize)]
@@ -46,6 +63,27 @@ pub async fn gs_version() -> Json<Value> {
     }))
 }
 
+/// 获取房间与在线玩家清理统计
+pub async fn gs_get_janitor_stats(
+    State(state): State<GsAppState>,
+) -> Json<GsJanitorStats> {
+    Json(state.janitor_stats.read().await.clone())
+}
+
+/// 获取广播背压统计
+pub async fn gs_get_broadcast_stats(
+    State(state): State<GsAppState>,
+) -> Json<GsBroadcastStats> {
+    Json(state.broadcast_stats.read().await.clone())
+}
+
+/// 获取故障注入统计 (未开启时 `enabled` 为 false)
+pub async fn gs_get_chaos_stats(
+    State(state): State<GsAppState>,
+) -> Json<GsChaosStats> {
+    Json(state.chaos.as_ref().map(|chaos| chaos.gs_stats()).unwrap_or_default())
+}
+
 // =============================================================================
 // 认证 API
 // =============================================================================
@@ -66,8 +104,11 @@ pub struct GsAuthResponse {
 /// 用户注册
 pub async fn gs_register(
     State(state): State<GsAppState>,
-    Json(payload): Json<GsAuthRequest>,
+    GsJson(payload): GsJson<GsAuthRequest>,
 ) -> Result<Json<GsAuthResponse>, GsError> {
+    let username = gc_sanitize_text(&payload.username, GcTextField::Username, state.name_filter.as_ref())?;
+    let payload = GsAuthRequest { username, ..payload };
+    
     // 优先使用数据库
     if let Some(db) = &state.db {
         let user_id = db.gs_create_user(&payload.username, &payload.password).await
@@ -114,8 +155,11 @@ pub async fn gs_register(
 /// 用户登录
 pub async fn gs_login(
     State(state): State<GsAppState>,
-    Json(payload): Json<GsAuthRequest>,
+    GsJson(payload): GsJson<GsAuthRequest>,
 ) -> Result<Json<GsAuthResponse>, GsError> {
+    // 用户名按注册时的规则规范化 (全角、首尾空白等不影响登录)
+    let payload = GsAuthRequest { username: gc_normalize_text(&payload.username), ..payload };
+    
     // 优先使用数据库
     if let Some(db) = &state.db {
         let user_id = db.gs_verify_user(&payload.username, &payload.password).await
@@ -158,6 +202,16 @@ pub async fn gs_login(
 pub async fn gs_get_current_user(
     headers: axum::http::HeaderMap,
 ) -> Result<Json<Value>, GsError> {
+    let claims = gs_bearer_claims(&headers)?;
+    
+    Ok(Json(json!({
+        "user_id": claims.sub,
+        "username": claims.username,
+    })))
+}
+
+/// 从 Authorization: Bearer 头解析登录用户
+fn gs_bearer_claims(headers: &axum::http::HeaderMap) -> Result<gs_auth::Claims, GsError> {
     let auth_header = headers
         .get(axum::http::header::AUTHORIZATION)
         .and_then(|v| v.to_str().ok())
@@ -168,25 +222,109 @@ pub async fn gs_get_current_user(
     }
     
     let token = &auth_header[7..];
-    let claims = gs_auth::gs_extract_user_from_token(token)?;
+    gs_auth::gs_extract_user_from_token(token)
+}
+
+/// 校验登录用户就是路径中的玩家 (按路径 ID 修改数据的接口只能操作自己的账号)
+fn gs_require_player(headers: &axum::http::HeaderMap, player_id: &str) -> Result<gs_auth::Claims, GsError> {
+    let claims = gs_bearer_claims(headers)?;
+    if claims.sub != player_id {
+        return Err(GsError::GsForbidden("只能操作自己的数据".to_string()));
+    }
+    Ok(claims)
+}
+
+// =============================================================================
+// 账号数据 API (导出与删除，只能操作登录用户自己的账号)
+// =============================================================================
+
+/// 账号数据导出格式版本
+const GS_ACCOUNT_EXPORT_VERSION: u32 = 1;
+
+/// 导出登录用户的全部数据 (JSON 文件下载)
+pub async fn gs_export_account(
+    State(state): State<GsAppState>,
+    headers: axum::http::HeaderMap,
+) -> Result<impl IntoResponse, GsError> {
+    let claims = gs_bearer_claims(&headers)?;
+    let data = state.gs_export_account(&claims.sub).await
+        .map_err(GsError::GsInternalError)?;
     
-    Ok(Json(json!({
-        "user_id": claims.sub,
+    // 日志不按账号存储，只给出可能提及该账号的位置
+    let log_references = json!([
+        {
+            "source": "client_log",
+            "location": gs_get_log_dir().join("client.log"),
+            "note": "前端上传的调试日志，按时间追加，不区分账号",
+        },
+    ]);
+    let archive = json!({
+        "format_version": GS_ACCOUNT_EXPORT_VERSION,
+        "player_id": claims.sub,
         "username": claims.username,
-    })))
+        "exported_at": gs_now(),
+        "data": data,
+        "log_references": log_references,
+    });
+    let disposition = format!("attachment; filename=\"account-{}.json\"", claims.sub);
+    Ok(([(axum::http::header::CONTENT_DISPOSITION, disposition)], Json(archive)))
+}
+
+/// 查询登录用户的删除申请
+pub async fn gs_get_account_deletion(
+    State(state): State<GsAppState>,
+    headers: axum::http::HeaderMap,
+) -> Result<Json<Option<GcAccountDeletion>>, GsError> {
+    let claims = gs_bearer_claims(&headers)?;
+    Ok(Json(state.gs_account_deletion(&claims.sub).await))
+}
+
+/// 申请删除登录用户的账号 (宽限期后清除)
+pub async fn gs_request_account_deletion(
+    State(state): State<GsAppState>,
+    headers: axum::http::HeaderMap,
+) -> Result<Json<GcAccountDeletion>, GsError> {
+    let claims = gs_bearer_claims(&headers)?;
+    Ok(Json(state.gs_request_account_deletion(&claims.sub).await))
+}
+
+/// 撤销删除申请
+pub async fn gs_cancel_account_deletion(
+    State(state): State<GsAppState>,
+    headers: axum::http::HeaderMap,
+) -> Result<Json<Value>, GsError> {
+    let claims = gs_bearer_claims(&headers)?;
+    let cancelled = state.gs_cancel_account_deletion(&claims.sub).await;
+    Ok(Json(json!({ "cancelled": cancelled })))
 }
 
 // =============================================================================
 // 玩家数据 API
 // =============================================================================
 
+/// 存档槽位查询参数 (省略为槽位 0)
+#[derive(Deserialize)]
+pub struct GsSaveSlotQuery {
+    #[serde(default)]
+    pub slot: u32,
+}
+
+impl GsSaveSlotQuery {
+    /// 校验后的槽位
+    fn gs_slot(&self) -> Result<u32, GsError> {
+        gc_validate_save_slot(self.slot).map_err(GsError::GsBadRequest)
+    }
+}
+
 /// 获取玩家背包
 pub async fn gs_get_inventory(
     State(state): State<GsAppState>,
     Path(player_id): Path<String>,
+    Query(query): Query<GsSaveSlotQuery>,
 ) -> Result<Json<Option<GcInventory>>, GsError> {
+    let slot = query.gs_slot()?;
     if let Some(db) = &state.db {
-        let inventory = db.gs_get_inventory(&player_id).await
+        let inventory = db.gs_get_inventory(&player_id, slot).await
             .map_err(|e| GsError::GsInternalError(e.to_string()))?;
         Ok(Json(inventory))
     } else {
@@ -198,10 +336,12 @@ pub async fn gs_get_inventory(
 pub async fn gs_save_inventory(
     State(state): State<GsAppState>,
     Path(player_id): Path<String>,
-    Json(inventory): Json<GcInventory>,
+    Query(query): Query<GsSaveSlotQuery>,
+    GsJson(inventory): GsJson<GcInventory>,
 ) -> Result<Json<Value>, GsError> {
+    let slot = query.gs_slot()?;
     if let Some(db) = &state.db {
-        db.gs_save_inventory(&player_id, &inventory).await
+        db.gs_save_inventory(&player_id, slot, &inventory).await
             .map_err(|e| GsError::GsInternalError(e.to_string()))?;
         Ok(Json(json!({"status": "ok"})))
     } else {
@@ -213,9 +353,11 @@ pub async fn gs_save_inventory(
 pub async fn gs_get_profession(
     State(state): State<GsAppState>,
     Path(player_id): Path<String>,
+    Query(query): Query<GsSaveSlotQuery>,
 ) -> Result<Json<Option<GcProfessionType>>, GsError> {
+    let slot = query.gs_slot()?;
     if let Some(db) = &state.db {
-        let profession = db.gs_get_profession(&player_id).await
+        let profession = db.gs_get_profession(&player_id, slot).await
             .map_err(|e| GsError::GsInternalError(e.to_string()))?;
         Ok(Json(profession))
     } else {
@@ -227,10 +369,12 @@ pub async fn gs_get_profession(
 pub async fn gs_save_profession(
     State(state): State<GsAppState>,
     Path(player_id): Path<String>,
-    Json(profession): Json<GcProfessionType>,
+    Query(query): Query<GsSaveSlotQuery>,
+    GsJson(profession): GsJson<GcProfessionType>,
 ) -> Result<Json<Value>, GsError> {
+    let slot = query.gs_slot()?;
     if let Some(db) = &state.db {
-        db.gs_save_profession(&player_id, profession).await
+        db.gs_save_profession(&player_id, slot, profession).await
             .map_err(|e| GsError::GsInternalError(e.to_string()))?;
         Ok(Json(json!({"status": "ok"})))
     } else {
@@ -238,6 +382,89 @@ pub async fn gs_save_profession(
     }
 }
 
+// =============================================================================
+// 角色 API
+// =============================================================================
+
+/// 创建角色请求
+#[derive(Deserialize)]
+pub struct GsCreateCharacterRequest {
+    pub name: String,
+    pub profession: GcProfessionType,
+    #[serde(default)]
+    pub appearance: GcAppearance,
+}
+
+/// 重命名角色请求
+#[derive(Deserialize)]
+pub struct GsRenameCharacterRequest {
+    pub name: String,
+}
+
+/// 获取存档槽位的角色
+pub async fn gs_get_character(
+    State(state): State<GsAppState>,
+    Path(player_id): Path<String>,
+    Query(query): Query<GsSaveSlotQuery>,
+) -> Result<Json<Option<GcCharacter>>, GsError> {
+    let slot = query.gs_slot()?;
+    if let Some(db) = &state.db {
+        let character = db.gs_get_character(&player_id, slot).await
+            .map_err(|e| GsError::GsInternalError(e.to_string()))?;
+        Ok(Json(character))
+    } else {
+        Ok(Json(None))
+    }
+}
+
+/// 创建角色 (只能在自己的槽位创建，槽位已有角色时失败)
+pub async fn gs_create_character(
+    State(state): State<GsAppState>,
+    Path(player_id): Path<String>,
+    Query(query): Query<GsSaveSlotQuery>,
+    headers: axum::http::HeaderMap,
+    GsJson(req): GsJson<GsCreateCharacterRequest>,
+) -> Result<Json<GcCharacter>, GsError> {
+    gs_require_player(&headers, &player_id)?;
+    let slot = query.gs_slot()?;
+    let character = GcCharacter::gc_new(&req.name, req.profession, req.appearance, state.name_filter.as_ref())
+        .map_err(GsError::GsBadRequest)?;
+    if let Some(db) = &state.db {
+        let created = db.gs_create_character(&player_id, slot, &character).await
+            .map_err(|e| GsError::GsInternalError(e.to_string()))?;
+        if !created {
+            return Err(GsError::GsBadRequest(format!("存档槽位 {} 已有角色", slot)));
+        }
+        Ok(Json(character))
+    } else {
+        Err(GsError::GsInternalError("Database not connected".to_string()))
+    }
+}
+
+/// 重命名角色 (只能重命名自己的)
+pub async fn gs_rename_character(
+    State(state): State<GsAppState>,
+    Path(player_id): Path<String>,
+    Query(query): Query<GsSaveSlotQuery>,
+    headers: axum::http::HeaderMap,
+    GsJson(req): GsJson<GsRenameCharacterRequest>,
+) -> Result<Json<Value>, GsError> {
+    gs_require_player(&headers, &player_id)?;
+    let slot = query.gs_slot()?;
+    let name = gc_validate_character_name(&req.name, state.name_filter.as_ref())
+        .map_err(GsError::GsBadRequest)?;
+    if let Some(db) = &state.db {
+        let renamed = db.gs_rename_character(&player_id, slot, &name).await
+            .map_err(|e| GsError::GsInternalError(e.to_string()))?;
+        if !renamed {
+            return Err(GsError::GsBadRequest(format!("存档槽位 {} 没有角色", slot)));
+        }
+        Ok(Json(json!({"status": "ok", "name": name})))
+    } else {
+        Err(GsError::GsInternalError("Database not connected".to_string()))
+    }
+}
+
 // =============================================================================
 // 玩家进度 API
 // =============================================================================
@@ -251,15 +478,23 @@ pub struct GsPlayerProgressRequest {
     pub current_map: String,
     pub game_flags: serde_json::Value,
     pub statistics: serde_json::Value,
+    /// 存档难度 (省略为普通)
+    #[serde(default)]
+    pub difficulty: GcDifficulty,
+    /// 敌人等级随玩家缩放
+    #[serde(default)]
+    pub level_scaling: bool,
 }
 
 /// 获取玩家进度
 pub async fn gs_get_player_progress(
     State(state): State<GsAppState>,
     Path(player_id): Path<String>,
+    Query(query): Query<GsSaveSlotQuery>,
 ) -> Result<Json<Option<crate::gs_db::GsPlayerProgress>>, GsError> {
+    let slot = query.gs_slot()?;
     if let Some(db) = &state.db {
-        let progress = db.gs_get_player_progress(&player_id).await
+        let progress = db.gs_get_player_progress(&player_id, slot).await
             .map_err(|e| GsError::GsInternalError(e.to_string()))?;
         Ok(Json(progress))
     } else {
@@ -271,17 +506,22 @@ pub async fn gs_get_player_progress(
 pub async fn gs_save_player_progress(
     State(state): State<GsAppState>,
     Path(player_id): Path<String>,
-    Json(req): Json<GsPlayerProgressRequest>,
+    Query(query): Query<GsSaveSlotQuery>,
+    GsJson(req): GsJson<GsPlayerProgressRequest>,
 ) -> Result<Json<Value>, GsError> {
+    let slot = query.gs_slot()?;
     if let Some(db) = &state.db {
         let progress = crate::gs_db::GsPlayerProgress {
             player_id: player_id.clone(),
+            slot: slot as i32,
             world_position_x: req.world_position_x,
             world_position_y: req.world_position_y,
             world_position_z: req.world_position_z,
             current_map: req.current_map,
             game_flags: req.game_flags,
             statistics: req.statistics,
+            difficulty: req.difficulty.gc_id().to_string(),
+            level_scaling: req.level_scaling,
         };
         
         db.gs_save_player_progress(&progress).await
@@ -292,22 +532,413 @@ pub async fn gs_save_player_progress(
     }
 }
 
+/// 新手引导响应 (进度与各模式清单)
+#[derive(Serialize)]
+pub struct GsOnboardingResponse {
+    pub progress: GcOnboardingProgress,
+    pub checklists: Vec<GcOnboardingChecklist>,
+}
+
+/// 获取新手引导进度 (无数据库时取在线玩家的内存进度)
+pub async fn gs_get_onboarding(
+    State(state): State<GsAppState>,
+    Path(player_id): Path<String>,
+    Query(query): Query<GsSaveSlotQuery>,
+) -> Result<Json<GsOnboardingResponse>, GsError> {
+    let slot = query.gs_slot()?;
+    let progress = match &state.db {
+        Some(db) => db.gs_get_onboarding(&player_id, slot).await
+            .map_err(|e| GsError::GsInternalError(e.to_string()))?,
+        None => state.players.read().await
+            .get(&player_id)
+            .filter(|p| p.slot == slot)
+            .map(|p| p.onboarding.clone())
+            .unwrap_or_default(),
+    };
+    let checklists = progress.gc_checklists();
+    Ok(Json(GsOnboardingResponse { progress, checklists }))
+}
+
+// =============================================================================
+// 怪兽皮肤 API
+// =============================================================================
+
+/// 装备皮肤请求 (skin_id 缺省表示恢复默认外观)
+#[derive(Deserialize)]
+pub struct GsEquipSkinRequest {
+    pub template_id: String,
+    #[serde(default)]
+    pub skin_id: Option<String>,
+}
+
+/// 获取玩家的怪兽皮肤 (拥有与装备)
+pub async fn gs_get_monster_skins(
+    State(state): State<GsAppState>,
+    Path(player_id): Path<String>,
+) -> Json<GcMonsterSkins> {
+    Json(state.gs_monster_skins(&player_id).await)
+}
+
+/// 装备怪兽皮肤 (只能装备自己的，下一局酒馆对局生效)
+pub async fn gs_equip_monster_skin(
+    State(state): State<GsAppState>,
+    Path(player_id): Path<String>,
+    headers: axum::http::HeaderMap,
+    GsJson(req): GsJson<GsEquipSkinRequest>,
+) -> Result<Json<GcMonsterSkins>, GsError> {
+    gs_require_player(&headers, &player_id)?;
+    let skins = state.gs_equip_monster_skin(&player_id, &req.template_id, req.skin_id.as_deref()).await
+        .map_err(GsError::GsBadRequest)?;
+    Ok(Json(skins))
+}
+
+// =============================================================================
+// 伙伴 API (只能操作登录用户自己的伙伴)
+// =============================================================================
+
+/// 装备伙伴请求 (companion_id 缺省表示卸下)
+#[derive(Deserialize)]
+pub struct GsEquipCompanionRequest {
+    #[serde(default)]
+    pub companion_id: Option<String>,
+}
+
+/// 获取伙伴收藏
+pub async fn gs_get_companions(
+    State(state): State<GsAppState>,
+    headers: axum::http::HeaderMap,
+) -> Result<Json<GcCompanionCollection>, GsError> {
+    let claims = gs_bearer_claims(&headers)?;
+    Ok(Json(state.gs_companions(&claims.sub).await))
+}
+
+/// 装备伙伴 (之后的对局奖励按其闲置加成计算)
+pub async fn gs_equip_companion(
+    State(state): State<GsAppState>,
+    headers: axum::http::HeaderMap,
+    GsJson(req): GsJson<GsEquipCompanionRequest>,
+) -> Result<Json<GcCompanionCollection>, GsError> {
+    let claims = gs_bearer_claims(&headers)?;
+    let companions = state.gs_equip_companion(&claims.sub, req.companion_id.as_deref()).await
+        .map_err(GsError::GsBadRequest)?;
+    Ok(Json(companions))
+}
+
+// =============================================================================
+// 卡牌收藏 API (只能操作登录用户自己的收藏)
+// =============================================================================
+
+/// 制作闪卡请求
+#[derive(Deserialize)]
+pub struct GsCraftFoilRequest {
+    pub template_id: String,
+}
+
+/// 获取卡牌收藏
+pub async fn gs_get_card_collection(
+    State(state): State<GsAppState>,
+    headers: axum::http::HeaderMap,
+) -> Result<Json<GcCardCollection>, GsError> {
+    let claims = gs_bearer_claims(&headers)?;
+    Ok(Json(state.gs_card_collection(&claims.sub).await))
+}
+
+/// 用尘制作闪卡 (之后设置卡组时按收藏校验闪卡外观)
+pub async fn gs_craft_foil(
+    State(state): State<GsAppState>,
+    headers: axum::http::HeaderMap,
+    GsJson(req): GsJson<GsCraftFoilRequest>,
+) -> Result<Json<GcCardCollection>, GsError> {
+    let claims = gs_bearer_claims(&headers)?;
+    let collection = state.gs_craft_foil(&claims.sub, &req.template_id).await
+        .map_err(GsError::GsBadRequest)?;
+    Ok(Json(collection))
+}
+
+// =============================================================================
+// 玩家设置 API
+// =============================================================================
+
+/// 保存设置请求 (revision 为读取时的版本号，首次保存为 0)
+#[derive(Deserialize)]
+pub struct GsSettingsUpdateRequest {
+    pub revision: u64,
+    /// 设置 (任意格式版本，保存前升级到当前版本)
+    pub settings: Value,
+}
+
+/// 设置接口的错误 (保留设置校验与版本号冲突的原类型)
+fn gs_settings_error(err: anyhow::Error) -> GsError {
+    match err.downcast::<GcSettingsError>() {
+        Ok(err) => err.into(),
+        Err(err) => err.into(),
+    }
+}
+
+/// 获取玩家设置 (从未保存时版本号为 0)
+pub async fn gs_get_player_settings(
+    State(state): State<GsAppState>,
+    Path(player_id): Path<String>,
+) -> Result<Json<GcSettingsRecord>, GsError> {
+    let record = state.gs_player_settings(&player_id).await.map_err(gs_settings_error)?;
+    Ok(Json(record))
+}
+
+/// 保存玩家设置 (只能保存自己的；版本号与服务器不一致时返回 409 与当前版本号)
+pub async fn gs_put_player_settings(
+    State(state): State<GsAppState>,
+    Path(player_id): Path<String>,
+    headers: axum::http::HeaderMap,
+    body: Bytes,
+) -> Result<Json<GcSettingsRecord>, GsError> {
+    gs_require_player(&headers, &player_id)?;
+    // 请求体含外层字段与空白，上限放宽一倍；设置本身的大小在校验时检查
+    if body.len() > 2 * GC_SETTINGS_MAX_BYTES {
+        return Err(GcSettingsError::GcTooLarge { size: body.len(), max: GC_SETTINGS_MAX_BYTES }.into());
+    }
+    let text = std::str::from_utf8(&body)
+        .map_err(|_| GsError::GsBadRequest("请求体不是 UTF-8 文本".to_string()))?;
+    let request: GsSettingsUpdateRequest = gc_parse_json("body", text).map_err(GsError::GsInvalidJson)?;
+    let settings = gc_migrate_settings(request.settings)?;
+    
+    let record = state.gs_update_player_settings(&player_id, request.revision, settings).await
+        .map_err(gs_settings_error)?;
+    Ok(Json(record))
+}
+
+// =============================================================================
+// 存档槽位 API
+// =============================================================================
+
+/// 复制存档槽位请求
+#[derive(Deserialize)]
+pub struct GsCopySaveSlotRequest {
+    /// 目标槽位 (已有数据会被覆盖)
+    pub to: u32,
+}
+
+/// 列出玩家存档槽位
+pub async fn gs_list_save_slots(
+    State(state): State<GsAppState>,
+    Path(player_id): Path<String>,
+) -> Result<Json<Vec<GcSaveSlotSummary>>, GsError> {
+    if let Some(db) = &state.db {
+        let slots = db.gs_list_save_slots(&player_id).await
+            .map_err(|e| GsError::GsInternalError(e.to_string()))?;
+        Ok(Json(slots))
+    } else {
+        Ok(Json(Vec::new()))
+    }
+}
+
+/// 删除存档槽位 (只能删除自己的)
+pub async fn gs_delete_save_slot(
+    State(state): State<GsAppState>,
+    Path((player_id, slot)): Path<(String, u32)>,
+    headers: axum::http::HeaderMap,
+) -> Result<Json<Value>, GsError> {
+    gs_require_player(&headers, &player_id)?;
+    let slot = gc_validate_save_slot(slot).map_err(GsError::GsBadRequest)?;
+    if let Some(db) = &state.db {
+        let deleted = db.gs_delete_save_slot(&player_id, slot).await
+            .map_err(|e| GsError::GsInternalError(e.to_string()))?;
+        Ok(Json(json!({"status": "ok", "deleted": deleted})))
+    } else {
+        Err(GsError::GsInternalError("Database not connected".to_string()))
+    }
+}
+
+/// 复制存档槽位 (只能复制自己的)
+pub async fn gs_copy_save_slot(
+    State(state): State<GsAppState>,
+    Path((player_id, slot)): Path<(String, u32)>,
+    headers: axum::http::HeaderMap,
+    GsJson(req): GsJson<GsCopySaveSlotRequest>,
+) -> Result<Json<Value>, GsError> {
+    gs_require_player(&headers, &player_id)?;
+    gc_validate_save_slot_copy(slot, req.to).map_err(GsError::GsBadRequest)?;
+    if let Some(db) = &state.db {
+        let copied = db.gs_copy_save_slot(&player_id, slot, req.to).await
+            .map_err(|e| GsError::GsInternalError(e.to_string()))?;
+        if !copied {
+            return Err(GsError::GsBadRequest(format!("存档槽位 {} 没有数据", slot)));
+        }
+        Ok(Json(json!({"status": "ok"})))
+    } else {
+        Err(GsError::GsInternalError("Database not connected".to_string()))
+    }
+}
+
+// =============================================================================
+// 组织金库 API
+// =============================================================================
+
+/// 组织金库概况
+#[derive(Serialize)]
+pub struct GsGuildResponse {
+    pub treasury: GcGuildTreasury,
+    pub buffs: GcGuildBuffs,
+    pub leaderboard: Vec<GcGuildMember>,
+}
+
+impl From<GcGuildTreasury> for GsGuildResponse {
+    fn from(treasury: GcGuildTreasury) -> Self {
+        Self {
+            buffs: treasury.gc_buffs(),
+            leaderboard: treasury.gc_leaderboard(),
+            treasury,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct GsGuildDonateRequest {
+    pub amount: u64,
+    /// 扣除金币的存档槽位 (省略为槽位 0)
+    #[serde(default)]
+    pub slot: u32,
+}
+
+#[derive(Deserialize)]
+pub struct GsGuildUpgradeRequest {
+    pub upgrade: GcGuildUpgrade,
+}
+
+#[derive(Deserialize)]
+pub struct GsGuildRoleRequest {
+    pub target_id: String,
+    pub role: GcGuildRole,
+}
+
+/// 获取组织金库、成员加成与贡献榜
+pub async fn gs_get_guild(
+    State(state): State<GsAppState>,
+    Path(organization): Path<GcOrganization>,
+) -> Json<GsGuildResponse> {
+    Json(state.gs_get_guild(&organization).await.into())
+}
+
+/// 加入组织 (登录用户本人)
+pub async fn gs_join_guild(
+    State(state): State<GsAppState>,
+    Path(organization): Path<GcOrganization>,
+    headers: axum::http::HeaderMap,
+) -> Result<Json<GsGuildResponse>, GsError> {
+    let claims = gs_bearer_claims(&headers)?;
+    let (_, treasury) = state.gs_update_guild(&organization, |t| Ok(t.gc_join(&claims.sub))).await
+        .map_err(GsError::GsBadRequest)?;
+    Ok(Json(treasury.into()))
+}
+
+/// 向组织金库捐献 (从登录用户的存档槽位扣除金币)
+pub async fn gs_donate_guild(
+    State(state): State<GsAppState>,
+    Path(organization): Path<GcOrganization>,
+    headers: axum::http::HeaderMap,
+    GsJson(req): GsJson<GsGuildDonateRequest>,
+) -> Result<Json<GsGuildResponse>, GsError> {
+    let claims = gs_bearer_claims(&headers)?;
+    let slot = gc_validate_save_slot(req.slot).map_err(GsError::GsBadRequest)?;
+    let treasury = state.gs_donate_guild(&organization, &claims.sub, slot, req.amount).await
+        .map_err(GsError::GsBadRequest)?;
+    Ok(Json(treasury.into()))
+}
+
+/// 用金库购买组织升级 (会长/干部)
+pub async fn gs_purchase_guild_upgrade(
+    State(state): State<GsAppState>,
+    Path(organization): Path<GcOrganization>,
+    headers: axum::http::HeaderMap,
+    GsJson(req): GsJson<GsGuildUpgradeRequest>,
+) -> Result<Json<GsGuildResponse>, GsError> {
+    let claims = gs_bearer_claims(&headers)?;
+    let (level, treasury) = state.gs_update_guild(&organization, |t| t.gc_purchase_upgrade(&claims.sub, req.upgrade)).await
+        .map_err(GsError::GsBadRequest)?;
+    tracing::info!("{} 升级 {} 到 {} 级", organization.gc_name(), req.upgrade.gc_name(), level);
+    Ok(Json(treasury.into()))
+}
+
+/// 任免组织职位 (会长)
+pub async fn gs_set_guild_role(
+    State(state): State<GsAppState>,
+    Path(organization): Path<GcOrganization>,
+    headers: axum::http::HeaderMap,
+    GsJson(req): GsJson<GsGuildRoleRequest>,
+) -> Result<Json<GsGuildResponse>, GsError> {
+    let claims = gs_bearer_claims(&headers)?;
+    let (_, treasury) = state.gs_update_guild(&organization, |t| t.gc_set_role(&claims.sub, &req.target_id, req.role)).await
+        .map_err(GsError::GsBadRequest)?;
+    Ok(Json(treasury.into()))
+}
+
+// =============================================================================
+// 领地 API
+// =============================================================================
+
+/// 获取地图的领地覆盖层
+pub async fn gs_get_territory_overlay(
+    State(state): State<GsAppState>,
+    Path(map_id): Path<String>,
+) -> Json<Vec<GcTerritoryOverlay>> {
+    Json(state.territory.read().await.gc_overlay(&map_id))
+}
+
+/// 以登录玩家所属组织占领领地或发起争夺
+///
+/// 争夺积分不经接口上报，由双方组织成员之间的对局结果在服务器计入
+pub async fn gs_claim_territory(
+    State(state): State<GsAppState>,
+    Path(zone_id): Path<String>,
+    headers: axum::http::HeaderMap,
+) -> Result<Json<GcTerritoryClaim>, GsError> {
+    let claims = gs_bearer_claims(&headers)?;
+    let organization = state.gs_player_organization(&claims.sub).await
+        .ok_or_else(|| GsError::GsBadRequest("玩家未加入组织".to_string()))?;
+    
+    let claim = state.territory.write().await
+        .gc_claim(&zone_id, organization.clone(), gs_now())
+        .map_err(GsError::GsBadRequest)?;
+    
+    if let GcTerritoryClaim::ContestStarted { ends_at } = claim {
+        let msg = GsWsMessage::TerritoryContestStarted {
+            zone_id: zone_id.clone(),
+            challenger: organization,
+            ends_at,
+        };
+        state.gs_broadcast_all(serde_json::to_string(&msg).unwrap_or_default());
+    } else {
+        let msg = GsWsMessage::TerritoryChanged {
+            changes: vec![GcTerritoryChange {
+                zone_id,
+                previous: None,
+                owner: Some(organization),
+            }],
+        };
+        state.gs_broadcast_all(serde_json::to_string(&msg).unwrap_or_default());
+    }
+    Ok(Json(claim))
+}
+
 /// 上传文件响应
 #[derive(Serialize)]
 pub struct GsUploadResponse {
     pub url: String,
     pub filename: String,
+    /// 缩略图与模型元数据
+    #[serde(flatten)]
+    pub preview: GsAssetPreview,
 }
 
 /// 处理文件上传
 /// 
-/// 接收 multipart/form-data，保存到 assets/uploads 目录
+/// 接收 multipart/form-data，保存到 {ASSET_ROOT}/assets/uploads 目录 (经 /assets/uploads 提供)，
+/// 并为 PNG 贴图与 GLB / glTF 模型生成缩略图
 pub async fn gs_upload_file(
+    State(state): State<GsAppState>,
     mut multipart: Multipart,
 ) -> Result<Json<GsUploadResponse>, GsError> {
     // 确保上传目录存在
-    // 注意：在生产环境中，这个路径应该配置化，并且指向 Nginx 服务的静态文件目录
-    let upload_dir = PathBuf::from("../../client/public/assets/uploads");
+    let upload_dir = state.config.gs_upload_dir();
     if !upload_dir.exists() {
         fs::create_dir_all(&upload_dir).await
             .map_err(|e| GsError::GsInternalError(format!("Failed to create upload dir: {}", e)))?;
@@ -333,10 +964,25 @@ pub async fn gs_upload_file(
             
         file.write_all(&data).await
             .map_err(|e| GsError::GsInternalError(format!("Failed to write file: {}", e)))?;
+        
+        // 缩略图生成失败不影响上传
+        let (dir, name) = (upload_dir.clone(), file_name.clone());
+        let preview = match tokio::task::spawn_blocking(move || gs_generate_preview(&dir, &name, &data)).await {
+            Ok(Ok(preview)) => preview,
+            Ok(Err(e)) => {
+                tracing::warn!("生成缩略图失败 ({}): {}", file_name, e);
+                GsAssetPreview::default()
+            }
+            Err(e) => {
+                tracing::warn!("生成缩略图任务失败 ({}): {}", file_name, e);
+                GsAssetPreview::default()
+            }
+        };
             
         return Ok(Json(GsUploadResponse {
             url: format!("assets/uploads/{}", file_name),
             filename: file_name,
+            preview,
         }));
     }
 
@@ -344,19 +990,23 @@ pub async fn gs_upload_file(
 }
 
 /// 保存地图数据
+/// 
+/// 先按编辑器场景或瓦片地图结构校验，通过后原样写入 (保持客户端的字段写法)
 pub async fn gs_save_map(
-    Json(payload): Json<Value>,
+    State(state): State<GsAppState>,
+    GsJson(payload): GsJson<Value>,
 ) -> Result<Json<Value>, GsError> {
+    let upload = gc_validate_map_upload(payload.clone())?;
+    
     // 确保目录存在
-    let data_dir = PathBuf::from("../../client/public/assets/data");
+    let data_dir = state.config.gs_map_data_dir();
     if !data_dir.exists() {
         fs::create_dir_all(&data_dir).await
             .map_err(|e| GsError::GsInternalError(format!("Failed to create data dir: {}", e)))?;
     }
 
-    // 获取地图ID，默认为 map_default
-    let map_id = payload.get("id").and_then(|v| v.as_str()).unwrap_or("map_default");
-    let file_path = data_dir.join(format!("{}.json", map_id));
+    // 地图 ID 已校验为安全文件名
+    let file_path = data_dir.join(format!("{}.json", upload.gc_id()));
 
     // 写入文件
     let content = serde_json::to_string_pretty(&payload)
@@ -374,6 +1024,109 @@ pub async fn gs_save_map(
     })))
 }
 
+/// 获取分块地图的头信息 (尺寸、出生点、块大小)
+pub async fn gs_get_map_header(
+    State(state): State<GsAppState>,
+    Path(map_id): Path<String>,
+) -> Result<Json<GcMapHeader>, GsError> {
+    state.maps.read().await
+        .get(&map_id)
+        .map(|map| Json(map.header.clone()))
+        .ok_or_else(|| GsError::GsBadRequest(format!("地图不存在: {}", map_id)))
+}
+
+/// 获取单个地图块
+pub async fn gs_get_map_chunk(
+    State(state): State<GsAppState>,
+    Path((map_id, cx, cy)): Path<(String, i32, i32)>,
+) -> Result<Json<GcMapChunk>, GsError> {
+    let maps = state.maps.read().await;
+    let map = maps.get(&map_id)
+        .ok_or_else(|| GsError::GsBadRequest(format!("地图不存在: {}", map_id)))?;
+    map.gc_chunk(GcChunkCoord::gc_new(cx, cy))
+        .map(|chunk| Json(chunk.clone()))
+        .ok_or_else(|| GsError::GsBadRequest(format!("地图块不存在: ({}, {})", cx, cy)))
+}
+
+/// 按位置请求地图块的参数
+#[derive(Deserialize)]
+pub struct GsMapChunksQuery {
+    pub x: i32,
+    pub y: i32,
+    /// 以块为单位的半径 (默认 1，最大 4)
+    #[serde(default = "gs_default_chunk_radius")]
+    pub radius: u32,
+}
+
+fn gs_default_chunk_radius() -> u32 {
+    1
+}
+
+/// 获取玩家位置周围的地图块 (玩家移动时按需加载)
+pub async fn gs_get_map_chunks_around(
+    State(state): State<GsAppState>,
+    Path(map_id): Path<String>,
+    Query(query): Query<GsMapChunksQuery>,
+) -> Result<Json<Vec<GcMapChunk>>, GsError> {
+    let maps = state.maps.read().await;
+    let map = maps.get(&map_id)
+        .ok_or_else(|| GsError::GsBadRequest(format!("地图不存在: {}", map_id)))?;
+    let chunks = map.header
+        .gc_chunks_around(&GcPosition::gc_new(query.x, query.y), query.radius.min(4))
+        .into_iter()
+        .filter_map(|coord| map.gc_chunk(coord).cloned())
+        .collect();
+    Ok(Json(chunks))
+}
+
+/// 遭遇生成参数
+#[derive(Deserialize)]
+pub struct GsEncounterQuery {
+    pub x: i32,
+    pub y: i32,
+    /// 玩家等级
+    pub level: u32,
+    /// 种子盐值 (与客户端 `generate_encounter` 的 salt 相同)
+    #[serde(default)]
+    pub salt: u64,
+    /// 大世界地形
+    #[serde(default)]
+    pub terrain: GcWorldTerrainType,
+    /// 存档难度
+    #[serde(default)]
+    pub difficulty: GcDifficulty,
+    /// 敌人等级随玩家缩放
+    #[serde(default)]
+    pub level_scaling: bool,
+}
+
+/// 生成位置上的遭遇 (与客户端同输入同结果，用于校验战斗配置)
+pub async fn gs_get_map_encounter(
+    State(state): State<GsAppState>,
+    Path(map_id): Path<String>,
+    Query(query): Query<GsEncounterQuery>,
+) -> Result<Json<GcEncounter>, GsError> {
+    let maps = state.maps.read().await;
+    let map = maps.get(&map_id)
+        .ok_or_else(|| GsError::GsBadRequest(format!("地图不存在: {}", map_id)))?;
+    let pos = GcPosition::gc_new(query.x, query.y);
+    let phase = state.world_clock.gc_phase_at(gs_now());
+    let region = map.header.spawn_regions.iter()
+        .find(|r| r.gc_contains(&pos) && r.gc_is_active(phase))
+        .ok_or_else(|| GsError::GsBadRequest(format!("该位置在{}没有可遭遇的刷怪区域", phase.gc_name())))?;
+    let elevation = map.gc_get_tile(&pos).map(|t| t.gc_elevation()).unwrap_or(0);
+    let seed = gc_encounter_seed(&map_id, &pos, query.salt);
+    let difficulty = GcDifficultySettings::gc_new(query.difficulty, query.level_scaling);
+    let encounter = match state.gs_region_weather(&map_id) {
+        Some(weather) => gc_generate_encounter_in(query.terrain, region, query.level, elevation, weather.weather, seed),
+        None => gc_generate_encounter_on(query.terrain, region, query.level, elevation, seed),
+    };
+    let encounter = encounter
+        .gc_with_difficulty(difficulty, query.level)
+        .gc_with_time_of_day(phase);
+    Ok(Json(encounter))
+}
+
 /// 房间列表响应
 #[derive(Serialize)]
 pub struct GsRoomListItem {
@@ -426,54 +1179,505 @@ pub async fn gs_get_room(
     })))
 }
 
+// =============================================================================
+// 内容 API
+// =============================================================================
+
+/// 获取当前赛季定义 (可用模式、怪兽/卡牌轮换与禁用列表)
+pub async fn gs_get_content_season(
+    State(state): State<GsAppState>,
+) -> Result<Json<GcSeason>, GsError> {
+    state.seasons.current_season()
+        .cloned()
+        .map(Json)
+        .ok_or_else(|| GsError::GsInternalError("没有激活的赛季".to_string()))
+}
+
+/// 获取服务器内容版本 (客户端据此计算本地版本并在登录时上报)
+pub async fn gs_get_content_version(
+    State(state): State<GsAppState>,
+) -> Json<GcContentVersion> {
+    Json(state.content_version.as_ref().clone())
+}
+
+/// 当前世界时间 (昼夜时段与下次切换时间)
+pub async fn gs_get_world_time(
+    State(state): State<GsAppState>,
+) -> Json<GcWorldTime> {
+    Json(state.world_clock.gc_time_at(gs_now()))
+}
+
+/// 各区域当前天气 (客户端渲染天气效果)
+pub async fn gs_get_world_weather(
+    State(state): State<GsAppState>,
+) -> Json<Vec<GcRegionWeather>> {
+    Json(state.gs_weather_now())
+}
+
+/// 单个区域当前天气
+pub async fn gs_get_region_weather(
+    State(state): State<GsAppState>,
+    Path(region_id): Path<String>,
+) -> Result<Json<GcRegionWeather>, GsError> {
+    state.gs_region_weather(&region_id)
+        .map(Json)
+        .ok_or_else(|| GsError::GsBadRequest(format!("天气区域不存在: {}", region_id)))
+}
+
+/// 本周挑战
+#[derive(Serialize)]
+pub struct GsWeeklyChallengeResponse {
+    pub week: u64,
+    pub ends_at: u64,
+    pub ruleset: Option<GcRuleset>,
+}
+
+/// 获取本周挑战规则集
+pub async fn gs_get_weekly_challenge(
+    State(state): State<GsAppState>,
+) -> Json<GsWeeklyChallengeResponse> {
+    let rotation = &state.weekly_rotation;
+    let week = rotation.gc_week_index(gs_now());
+    Json(GsWeeklyChallengeResponse {
+        week,
+        ends_at: rotation.epoch_start + (week + 1) * GC_WEEK_SECS,
+        ruleset: rotation.gc_ruleset_for_week(week).cloned(),
+    })
+}
+
+/// 获取本周挑战排行
+pub async fn gs_get_weekly_leaderboard(
+    State(state): State<GsAppState>,
+) -> Json<Vec<GcWeeklyEntry>> {
+    let week = state.weekly_rotation.gc_week_index(gs_now());
+    let leaderboard = state.weekly_leaderboard.read().await;
+    if leaderboard.week != week {
+        return Json(Vec::new());
+    }
+    Json(leaderboard.gc_ranking())
+}
+
 /// 资源列表项
 #[derive(Serialize)]
 pub struct GsAssetItem {
     pub name: String,
-    pub type_: String, // "model", "texture"
+    /// 资源类型 ("model" / "texture" / "prefab")
+    #[serde(rename = "type")]
+    pub asset_type: String,
     pub path: String,
+    /// 预制体分类 (仅预制体)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    /// 用户标签
+    pub tags: Vec<String>,
+    /// 缩略图与模型元数据 (仅上传的资源)
+    #[serde(flatten)]
+    pub preview: GsAssetPreview,
+}
+
+impl GsAssetItem {
+    fn gs_new(name: &str, asset_type: &str, path: String) -> Self {
+        Self {
+            name: name.to_string(),
+            asset_type: asset_type.to_string(),
+            path,
+            category: None,
+            tags: Vec::new(),
+            preview: GsAssetPreview::default(),
+        }
+    }
+}
+
+/// 资源列表参数
+#[derive(Deserialize)]
+pub struct GsAssetListQuery {
+    /// 搜索词 (名称、分类与标签)
+    #[serde(default)]
+    pub q: Option<String>,
+    /// 逗号分隔的标签，资源须带有全部标签
+    #[serde(default)]
+    pub tags: Option<String>,
 }
 
 /// 获取资源列表 (用于编辑器)
-pub async fn gs_list_assets() -> Result<Json<Vec<GsAssetItem>>, GsError> {
+///
+/// 带搜索词或标签时只返回匹配的资源，按匹配程度排序
+pub async fn gs_list_assets(
+    State(state): State<GsAppState>,
+    Query(query): Query<GsAssetListQuery>,
+) -> Result<Json<Vec<GsAssetItem>>, GsError> {
     let mut assets = Vec::new();
     
     // 1. 扫描上传目录
-    let upload_dir = PathBuf::from("../../assets/uploads");
+    let upload_dir = state.config.gs_upload_dir();
     if upload_dir.exists() {
-        let mut entries = fs::read_dir(upload_dir).await
+        let mut entries = fs::read_dir(&upload_dir).await
             .map_err(|e| GsError::GsInternalError(format!("Failed to read uploads dir: {}", e)))?;
             
         while let Ok(Some(entry)) = entries.next_entry().await {
             let path = entry.path();
             if let Some(ext) = path.extension() {
                 let ext_str = ext.to_string_lossy().to_lowercase();
-                if ext_str == "glb" || ext_str == "gltf" {
-                    if let Some(name) = path.file_name() {
-                        assets.push(GsAssetItem {
-                            name: name.to_string_lossy().to_string(),
-                            type_: "model".to_string(),
-                            path: format!("assets/uploads/{}", name.to_string_lossy()),
-                        });
-                    }
+                let asset_type = match ext_str.as_str() {
+                    "glb" | "gltf" => "model",
+                    "png" | "jpg" => "texture",
+                    _ => continue,
+                };
+                if let Some(name) = path.file_name() {
+                    let name = name.to_string_lossy();
+                    let mut item = GsAssetItem::gs_new(&name, asset_type, format!("assets/uploads/{}", name));
+                    item.preview = gs_read_preview(&upload_dir, &name).await;
+                    assets.push(item);
                 }
             }
         }
     }
     
     // 2. 添加内置资源 - 使用 nature/ 目录下已有的模型
-    assets.push(GsAssetItem {
-        name: "tree_pine".to_string(),
-        type_: "model".to_string(),
-        path: "assets/models/nature/Pine_1.gltf".to_string(),
-    });
-    assets.push(GsAssetItem {
-        name: "common_tree".to_string(),
-        type_: "model".to_string(),
-        path: "assets/models/nature/CommonTree_1.gltf".to_string(),
-    });
+    assets.push(GsAssetItem::gs_new("tree_pine", "model", "assets/models/nature/Pine_1.gltf".to_string()));
+    assets.push(GsAssetItem::gs_new("common_tree", "model", "assets/models/nature/CommonTree_1.gltf".to_string()));
     
-    Ok(Json(assets))
+    // 3. 预制体 (与 MCP list_prefabs 相同的注册表)
+    for (category, prefab_ids) in gs_load_prefab_registry(&state).await {
+        for prefab_id in prefab_ids {
+            let mut item = GsAssetItem::gs_new(&prefab_id, "prefab", format!("prefab:{}", prefab_id));
+            item.category = Some(category.clone());
+            assets.push(item);
+        }
+    }
+    
+    let registry = state.gs_asset_tag_registry().await;
+    for asset in &mut assets {
+        asset.tags = registry.gc_tags(&asset.name);
+    }
+    
+    let query = GcAssetQuery::gc_parse(query.q.as_deref(), query.tags.as_deref());
+    if query.gc_is_empty() {
+        return Ok(Json(assets));
+    }
+    let mut scored: Vec<(u32, GsAssetItem)> = assets
+        .into_iter()
+        .filter_map(|asset| {
+            let extra: Vec<&str> = asset.category.iter().map(|c| c.as_str()).collect();
+            Some((query.gc_score(&asset.name, &extra, &asset.tags)?, asset))
+        })
+        .collect();
+    scored.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.name.cmp(&b.1.name)));
+    Ok(Json(scored.into_iter().map(|(_, asset)| asset).collect()))
+}
+
+/// 全部资源标签及使用次数
+pub async fn gs_list_asset_tags(
+    State(state): State<GsAppState>,
+) -> Json<Vec<GcAssetTagCount>> {
+    Json(state.gs_asset_tag_registry().await.gc_tag_counts())
+}
+
+/// 设置资源标签请求
+#[derive(Deserialize)]
+pub struct GsAssetTagsRequest {
+    pub tags: Vec<String>,
+}
+
+/// 替换资源的标签 (空列表移除全部标签，需登录)
+pub async fn gs_set_asset_tags(
+    State(state): State<GsAppState>,
+    Path(asset): Path<String>,
+    headers: axum::http::HeaderMap,
+    GsJson(payload): GsJson<GsAssetTagsRequest>,
+) -> Result<Json<Value>, GsError> {
+    gs_bearer_claims(&headers)?;
+    let tags = state.gs_set_asset_tags(&asset, &payload.tags).await.map_err(GsError::GsBadRequest)?;
+    Ok(Json(json!({ "asset": asset, "tags": tags })))
+}
+
+/// 重命名标签请求
+#[derive(Deserialize)]
+pub struct GsRenameAssetTagRequest {
+    pub from: String,
+    pub to: String,
+}
+
+/// 重命名标签 (所有资源，需登录)
+pub async fn gs_rename_asset_tag(
+    State(state): State<GsAppState>,
+    headers: axum::http::HeaderMap,
+    GsJson(payload): GsJson<GsRenameAssetTagRequest>,
+) -> Result<Json<Value>, GsError> {
+    gs_bearer_claims(&headers)?;
+    let assets = state.gs_rename_asset_tag(&payload.from, &payload.to).await.map_err(GsError::GsBadRequest)?;
+    Ok(Json(json!({ "assets": assets })))
+}
+
+/// 删除标签 (所有资源，需登录)
+pub async fn gs_delete_asset_tag(
+    State(state): State<GsAppState>,
+    Path(tag): Path<String>,
+    headers: axum::http::HeaderMap,
+) -> Result<Json<Value>, GsError> {
+    gs_bearer_claims(&headers)?;
+    let assets = state.gs_delete_asset_tag(&tag).await.map_err(GsError::GsBadRequest)?;
+    Ok(Json(json!({ "assets": assets })))
+}
+
+// =============================================================================
+// 回放分享
+// =============================================================================
+
+/// 回放上传请求
+#[derive(Deserialize)]
+pub struct GsReplayUploadRequest {
+    pub replay: GcReplay,
+    /// 公开后出现在最近回放列表中 (默认公开)
+    #[serde(default = "gs_default_true")]
+    pub public: bool,
+}
+
+fn gs_default_true() -> bool {
+    true
+}
+
+/// 回放详情 (摘要 + 完整回放)
+#[derive(Serialize)]
+pub struct GsReplayResponse {
+    pub summary: GcReplaySummary,
+    pub replay: GcReplay,
+}
+
+/// 回放列表参数
+#[derive(Deserialize)]
+pub struct GsReplayListQuery {
+    #[serde(default)]
+    pub mode: Option<GcGameMode>,
+    /// 任一玩家的职业
+    #[serde(default)]
+    pub profession: Option<GcProfessionType>,
+    /// 返回条数 (最多 50)
+    #[serde(default = "gs_default_replay_limit")]
+    pub limit: usize,
+}
+
+fn gs_default_replay_limit() -> usize {
+    20
+}
+
+/// 上传已结束对局的回放，返回分享码
+///
+/// 先按原始请求体大小拒绝过大的回放，再重放事件校验元数据
+pub async fn gs_upload_replay(
+    State(state): State<GsAppState>,
+    body: Bytes,
+) -> Result<Json<GcReplaySummary>, GsError> {
+    let max_bytes = state.config.replay_policy.max_bytes;
+    if body.len() > max_bytes {
+        return Err(GcReplayError::GcTooLarge { size: body.len(), max: max_bytes }.into());
+    }
+    let text = std::str::from_utf8(&body)
+        .map_err(|_| GsError::GsBadRequest("请求体不是 UTF-8 文本".to_string()))?;
+    let request: GsReplayUploadRequest = gc_parse_json("body", text).map_err(GsError::GsInvalidJson)?;
+    for player in &request.replay.players {
+        gc_sanitize_text(&player.name, GcTextField::PlayerName, state.name_filter.as_ref())?;
+    }
+
+    let summary = state.replays.write().await.gc_upload(request.replay, body.len(), request.public, gs_now())?;
+    tracing::info!("🎞️ 回放已分享: {} ({} 个事件，{} 字节)", summary.code, summary.event_count, summary.size_bytes);
+    Ok(Json(summary))
+}
+
+/// 按分享码获取回放
+pub async fn gs_get_replay(
+    State(state): State<GsAppState>,
+    Path(code): Path<String>,
+) -> Result<Json<GsReplayResponse>, GsError> {
+    let replays = state.replays.read().await;
+    let (summary, replay) = replays.gc_get(&code, gs_now())
+        .ok_or_else(|| GsError::GsReplayNotFound(code.clone()))?;
+    Ok(Json(GsReplayResponse { summary: summary.clone(), replay: replay.clone() }))
+}
+
+/// 最近的公开回放 (可按模式、职业筛选)
+pub async fn gs_list_replays(
+    State(state): State<GsAppState>,
+    Query(query): Query<GsReplayListQuery>,
+) -> Json<Vec<GcReplaySummary>> {
+    let filter = GcReplayFilter { mode: query.mode, profession: query.profession };
+    Json(state.replays.read().await.gc_list_public(&filter, query.limit, gs_now()))
+}
+
+// =============================================================================
+// 对局审计
+// =============================================================================
+
+/// 审计复核结果
+#[derive(Serialize)]
+pub struct GsMatchAuditVerification {
+    pub battle_id: String,
+    /// 回放与审计记录是否一致
+    pub valid: bool,
+    /// 不一致的原因
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// 获取竞技对局的审计记录
+pub async fn gs_get_match_audit(
+    State(state): State<GsAppState>,
+    Path(battle_id): Path<String>,
+) -> Result<Json<GcMatchAudit>, GsError> {
+    state.gs_match_audit(&battle_id).await
+        .map(Json)
+        .ok_or(GsError::GsAuditNotFound(battle_id))
+}
+
+/// 凭回放复核审计记录 (争议处理、反作弊复核)
+pub async fn gs_verify_match_audit(
+    State(state): State<GsAppState>,
+    Path(battle_id): Path<String>,
+    GsJson(replay): GsJson<GcReplay>,
+) -> Result<Json<GsMatchAuditVerification>, GsError> {
+    let audit = state.gs_match_audit(&battle_id).await
+        .ok_or_else(|| GsError::GsAuditNotFound(battle_id.clone()))?;
+    let error = gc_verify_match(&audit, &replay).err().map(|e| e.to_string());
+    Ok(Json(GsMatchAuditVerification { battle_id, valid: error.is_none(), error }))
+}
+
+// =============================================================================
+// 使用统计
+// =============================================================================
+
+/// 使用统计查询参数
+#[derive(Deserialize)]
+pub struct GsUsageStatsQuery {
+    /// 段位 (省略为全部段位)
+    #[serde(default)]
+    pub bracket: Option<GcRankBracket>,
+    /// 返回条数 (最多 100)
+    #[serde(default = "gs_default_usage_limit")]
+    pub limit: usize,
+}
+
+fn gs_default_usage_limit() -> usize {
+    20
+}
+
+/// 使用统计响应
+#[derive(Serialize)]
+pub struct GsUsageStatsResponse {
+    /// 段位 (None 为全部段位)
+    pub bracket: Option<GcRankBracket>,
+    /// 该段位的样本数
+    pub samples: u64,
+    /// 选取率与胜率 (选取次数降序)
+    pub rates: Vec<GcUsageRate>,
+}
+
+/// 汇总表的统计响应
+async fn gs_usage_stats(state: &GsAppState, kind: GcUsageKind, query: &GsUsageStatsQuery) -> GsUsageStatsResponse {
+    let stats = state.usage_stats.read().await;
+    let table = stats.gc_table(kind);
+    GsUsageStatsResponse {
+        bracket: query.bracket,
+        samples: table.gc_games(query.bracket),
+        rates: table.gc_rates(query.bracket, query.limit),
+    }
+}
+
+/// 卡牌选取率与胜率
+pub async fn gs_get_card_stats(
+    State(state): State<GsAppState>,
+    Query(query): Query<GsUsageStatsQuery>,
+) -> Json<GsUsageStatsResponse> {
+    Json(gs_usage_stats(&state, GcUsageKind::Card, &query).await)
+}
+
+/// 酒馆单位选取率与胜率
+pub async fn gs_get_unit_stats(
+    State(state): State<GsAppState>,
+    Query(query): Query<GsUsageStatsQuery>,
+) -> Json<GsUsageStatsResponse> {
+    Json(gs_usage_stats(&state, GcUsageKind::Unit, &query).await)
+}
+
+// =============================================================================
+// 幽灵竞赛
+// =============================================================================
+
+/// 幽灵查询参数 (省略为酒馆)
+#[derive(Deserialize)]
+pub struct GsGhostQuery {
+    #[serde(default)]
+    pub mode: GcGhostMode,
+}
+
+/// 幽灵上传响应
+#[derive(Serialize)]
+pub struct GsGhostUploadResponse {
+    /// 本次得分
+    pub score: u32,
+    /// 保存的最高分
+    pub best_score: u32,
+    /// 本次是否刷新最高分
+    pub new_best: bool,
+}
+
+/// 幽灵保存上限 (满时淘汰得分最低的幽灵)
+pub const GS_MAX_GHOSTS: usize = 10_000;
+
+/// 上传已结束流程的幽灵 (每名玩家每种流程只保留最高分)
+///
+/// 玩家身份取自登录令牌，得分由服务器按回合数据重新计算。
+pub async fn gs_upload_ghost(
+    State(state): State<GsAppState>,
+    headers: axum::http::HeaderMap,
+    GsJson(mut run): GsJson<GcGhostRun>,
+) -> Result<Json<GsGhostUploadResponse>, GsError> {
+    let claims = gs_bearer_claims(&headers)?;
+    run.gc_validate()?;
+    run.player_id = claims.sub;
+    run.player_name = claims.username;
+    let score = run.gc_finish();
+    let mut ghosts = state.ghosts.write().await;
+    let key = (run.player_id.clone(), run.mode);
+    let best_score = ghosts.get(&key).and_then(|g| g.score).unwrap_or_default();
+    let new_best = !ghosts.contains_key(&key) || score > best_score;
+    if new_best {
+        if !ghosts.contains_key(&key) && ghosts.len() >= GS_MAX_GHOSTS {
+            let lowest = ghosts.iter()
+                .min_by_key(|(_, g)| g.score.unwrap_or_default())
+                .map(|(k, _)| k.clone());
+            if let Some(lowest) = lowest {
+                ghosts.remove(&lowest);
+            }
+        }
+        ghosts.insert(key, run);
+    }
+    Ok(Json(GsGhostUploadResponse { score, best_score: best_score.max(score), new_best }))
+}
+
+/// 获取玩家的幽灵
+pub async fn gs_get_ghost(
+    State(state): State<GsAppState>,
+    Path(player_id): Path<String>,
+    Query(query): Query<GsGhostQuery>,
+) -> Result<Json<GcGhostRun>, GsError> {
+    state.ghosts.read().await
+        .get(&(player_id.clone(), query.mode))
+        .cloned()
+        .map(Json)
+        .ok_or(GsError::GsGhostNotFound(player_id))
+}
+
+/// 与玩家的幽灵竞赛 (请求体为自己的流程，可尚未结束)
+pub async fn gs_race_ghost(
+    State(state): State<GsAppState>,
+    Path(player_id): Path<String>,
+    GsJson(run): GsJson<GcGhostRun>,
+) -> Result<Json<GcGhostRace>, GsError> {
+    run.gc_validate_progress()?;
+    let ghosts = state.ghosts.read().await;
+    let ghost = ghosts.get(&(player_id.clone(), run.mode))
+        .ok_or(GsError::GsGhostNotFound(player_id))?;
+    Ok(Json(gc_race_ghost(&run, ghost)))
 }
 
 // =============================================================================
@@ -501,7 +1705,7 @@ fn gs_get_log_dir() -> PathBuf {
 
 /// 保存前端日志
 pub async fn gs_save_client_logs(
-    Json(payload): Json<GsClientLogsRequest>,
+    GsJson(payload): GsJson<GsClientLogsRequest>,
 ) -> Result<Json<Value>, GsError> {
     let log_dir = gs_get_log_dir();
     
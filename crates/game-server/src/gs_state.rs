@@ -4,13 +4,16 @@
 //! 前缀: Gs
 //! 文档: 文档/03-game-server.md
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, VecDeque};
 use std::sync::Arc;
 use tokio::sync::{RwLock, broadcast};
 use game_core::*;
 
 use crate::gs_config::GsConfig;
 use crate::gs_db::GsDatabase;
+use crate::gs_backpressure::GsBroadcastStats;
+use crate::gs_chaos::GsChaos;
+use crate::gs_janitor::GsJanitorStats;
 
 // =============================================================================
 // 广播消息
@@ -31,6 +34,81 @@ pub struct GsBroadcastMessage {
 // 房间状态
 // =============================================================================
 
+/// 房间对战的系列赛局数 (BO3)
+pub const GS_SERIES_BEST_OF: u32 = 3;
+
+/// 广播给所有连接 (不限房间) 的房间 ID
+pub const GS_BROADCAST_ALL: &str = "*";
+
+/// 广播给订阅大厅的连接的房间 ID
+pub const GS_BROADCAST_LOBBY: &str = "#lobby";
+
+/// 使用统计汇总在汇总表中的名称
+const GS_USAGE_SUMMARY: &str = "usage";
+
+/// 汇总任务每批读取的样本数
+const GS_USAGE_BATCH: i64 = 1000;
+
+/// 当前 Unix 时间戳 (秒)
+pub fn gs_now() -> u64 {
+    chrono::Utc::now().timestamp().max(0) as u64
+}
+
+/// 载入每周挑战轮换 (文件缺失或无效时使用默认轮换)
+fn gs_load_weekly_rotation() -> GcWeeklyRotation {
+    let Ok(path) = std::env::var("WEEKLY_CHALLENGE_PATH") else {
+        return gc_default_weekly_rotation();
+    };
+    let parsed = std::fs::read_to_string(&path)
+        .map_err(|e| e.to_string())
+        .and_then(|text| serde_json::from_str::<GcWeeklyRotation>(&text).map_err(|e| e.to_string()))
+        .and_then(|rotation| rotation.gc_validate().map(|_| rotation));
+    match parsed {
+        Ok(rotation) => rotation,
+        Err(e) => {
+            tracing::warn!("⚠️ 每周挑战定义无效: {} ({}) - 使用默认轮换", path, e);
+            gc_default_weekly_rotation()
+        }
+    }
+}
+
+/// 载入名称屏蔽词表 (NAME_BLOCKLIST_PATH，每行一个词；未设置或读取失败时为空)
+fn gs_load_name_blocklist() -> GcWordBlocklist {
+    let Ok(path) = std::env::var("NAME_BLOCKLIST_PATH") else {
+        return GcWordBlocklist::default();
+    };
+    match std::fs::read_to_string(&path) {
+        Ok(text) => GcWordBlocklist::gc_new(&text.lines().collect::<Vec<_>>()),
+        Err(e) => {
+            tracing::warn!("⚠️ 屏蔽词表读取失败: {} ({}) - 不过滤角色名", path, e);
+            GcWordBlocklist::default()
+        }
+    }
+}
+
+/// 载入跨模式奖励换算表 (REWARD_TABLE_PATH，文件缺失或无效时使用默认表)
+fn gs_load_reward_table() -> GcRewardTable {
+    let Ok(path) = std::env::var("REWARD_TABLE_PATH") else {
+        return GcRewardTable::default();
+    };
+    let parsed = std::fs::read_to_string(&path)
+        .map_err(|e| e.to_string())
+        .and_then(|text| serde_json::from_str::<GcRewardTable>(&text).map_err(|e| e.to_string()))
+        .and_then(|table| table.gc_validate().map(|_| table));
+    match parsed {
+        Ok(table) => table,
+        Err(e) => {
+            tracing::warn!("⚠️ 奖励换算表无效: {} ({}) - 使用默认换算表", path, e);
+            GcRewardTable::default()
+        }
+    }
+}
+
+/// 生成商店刷新用随机数 (取 UUID v4 的随机字节)
+fn gs_random_rolls() -> Vec<u8> {
+    uuid::Uuid::new_v4().as_bytes().to_vec()
+}
+
 /// 玩家在房间中的状态
 #[derive(Clone, Debug, Default)]
 pub struct GsRoomPlayer {
@@ -40,6 +118,8 @@ pub struct GsRoomPlayer {
     pub name: String,
     /// 是否准备
     pub ready: bool,
+    /// 角色外观
+    pub appearance: Option<GcAppearance>,
 }
 
 /// 房间状态
@@ -53,20 +133,45 @@ pub struct GsRoom {
     pub owner_id: String,
     /// 玩家列表 (带准备状态)
     pub players: Vec<GsRoomPlayer>,
-    /// 战斗状态 (如果正在进行)
-    pub battle: Option<GcBattleState>,
-    /// 最大玩家数
+    /// 战斗 (如果正在进行，事件溯源)
+    pub battle: Option<GcEventSourcedBattle>,
+    /// 已广播的公开行动记录序号 (新一局战斗时归零)
+    pub feed_broadcast_seq: u64,
+    /// 最大玩家数 (由赛制决定)
     pub max_players: usize,
+    /// 战斗赛制 (决斗 / 自由混战 / 2v2)
+    pub format: GcBattleFormat,
+    /// 2v2 队伍选择 (玩家 ID -> 队伍；未选择的玩家开局时自动分配)
+    pub teams: HashMap<String, u32>,
     /// 游戏是否已开始
     pub game_started: bool,
+    /// 系列赛 (游戏开始后创建)
+    pub series: Option<GcSeries>,
+    /// 玩家卡组 (玩家 ID -> 卡组)
+    pub decks: HashMap<String, GcDeck>,
+    /// 酒馆对局 (酒馆模式开始后创建)
+    pub tavern: Option<GcTavernGame>,
+    /// 已结束购物阶段的玩家 (酒馆模式)
+    pub tavern_ready: Vec<String>,
+    /// 玩家组织加成 (游戏开始时记录)
+    pub guild_buffs: HashMap<String, GcGuildBuffs>,
+    /// 组队标记限流 (玩家 ID -> 限流器)
+    pub ping_limits: HashMap<String, GcPingLimiter>,
+    /// 对局中断线、保留座位的玩家 (玩家 ID -> 断线时间)
+    pub disconnected_at: HashMap<String, u64>,
+    /// 由机器人托管的座位 (断线超过宽限期)
+    pub bot_seats: Vec<String>,
+    /// 卡牌对战暂停状态 (额度按房间计，开始游戏时重置)
+    pub pause: GcPauseState,
 }
 
 impl GsRoom {
-    pub fn gs_new(id: String, name: String, owner_id: String, owner_name: String) -> Self {
+    pub fn gs_new(id: String, name: String, owner_id: String, owner_name: String, owner_appearance: Option<GcAppearance>) -> Self {
         let owner = GsRoomPlayer {
             id: owner_id.clone(),
             name: owner_name,
             ready: false,
+            appearance: owner_appearance,
         };
         Self {
             id,
@@ -74,8 +179,43 @@ impl GsRoom {
             owner_id,
             players: vec![owner],
             battle: None,
-            max_players: 2,
+            feed_broadcast_seq: 0,
+            max_players: GcBattleFormat::Duel.gc_max_players(),
+            format: GcBattleFormat::Duel,
+            teams: HashMap::new(),
             game_started: false,
+            series: None,
+            decks: HashMap::new(),
+            tavern: None,
+            tavern_ready: Vec::new(),
+            guild_buffs: HashMap::new(),
+            ping_limits: HashMap::new(),
+            disconnected_at: HashMap::new(),
+            bot_seats: Vec::new(),
+            pause: GcPauseState::default(),
+        }
+    }
+
+    /// 暂停额度用尽时自动继续，返回本次暂停时长 (断线托管计时随之顺延)
+    pub fn gs_tick_pause(&mut self, now: u64) -> Option<u64> {
+        let paused_secs = self.pause.gc_tick(now)?;
+        self.gs_shift_timers(paused_secs);
+        Some(paused_secs)
+    }
+
+    /// 拒绝暂停中的对局操作
+    fn gs_ensure_unpaused(&mut self, now: u64) -> Result<(), String> {
+        self.gs_tick_pause(now);
+        if self.pause.gc_is_paused() {
+            return Err(GcPauseError::GcAlreadyPaused.to_string());
+        }
+        Ok(())
+    }
+
+    /// 暂停不计入断线托管计时
+    fn gs_shift_timers(&mut self, paused_secs: u64) {
+        for since in self.disconnected_at.values_mut() {
+            *since = since.saturating_add(paused_secs);
         }
     }
 
@@ -83,23 +223,39 @@ impl GsRoom {
         self.players.len() >= self.max_players
     }
 
-    pub fn gs_add_player(&mut self, player_id: String, player_name: String) -> bool {
+    pub fn gs_add_player(&mut self, player_id: String, player_name: String, appearance: Option<GcAppearance>) -> bool {
         if self.gs_is_full() {
             return false;
         }
         if self.players.iter().any(|p| p.id == player_id) {
             return false;
         }
+        if self.format.gc_uses_teams() {
+            self.gs_balance_team(&player_id);
+        }
         self.players.push(GsRoomPlayer {
             id: player_id,
             name: player_name,
             ready: false,
+            appearance,
         });
         true
     }
+    
+    /// 把未分队的玩家分进人数较少的队伍 (人数相同时进队伍 0)
+    fn gs_balance_team(&mut self, player_id: &str) {
+        if self.teams.contains_key(player_id) {
+            return;
+        }
+        let team = (0..GC_BATTLE_TEAM_COUNT)
+            .min_by_key(|team| self.teams.values().filter(|t| *t == team).count())
+            .unwrap_or(0);
+        self.teams.insert(player_id.to_string(), team);
+    }
 
     pub fn gs_remove_player(&mut self, player_id: &str) {
         self.players.retain(|p| p.id != player_id);
+        self.teams.remove(player_id);
     }
     
     /// 设置玩家准备状态
@@ -121,6 +277,110 @@ impl GsRoom {
     pub fn gs_player_ids(&self) -> Vec<String> {
         self.players.iter().map(|p| p.id.clone()).collect()
     }
+    
+    /// 创建新一局战斗 (使用玩家卡组作为牌库，按赛制安排座位)
+    fn gs_new_battle(&self) -> Result<GcEventSourcedBattle, String> {
+        let game_number = self.series.as_ref().map(|s| s.game_number).unwrap_or(1);
+        let gc_players: Vec<GcPlayer> = self.players.iter()
+            .map(|p| {
+                let mut player = GcPlayer::gc_new(&p.id, &p.name);
+                if let Some(deck) = self.decks.get(&p.id) {
+                    player.deck = deck.gc_build_cards(&format!("{}_g{}", p.id, game_number));
+                }
+                if let Some(buffs) = self.guild_buffs.get(&p.id) {
+                    player.gc_set_guild_buffs(buffs.clone());
+                }
+                player.team = self.teams.get(&p.id).copied();
+                player
+            })
+            .collect();
+        let gc_players = gc_arrange_seats(self.format, gc_players).map_err(|e| e.to_string())?;
+        
+        let battle_id = format!("battle_{}", uuid::Uuid::new_v4());
+        let mut battle = GcBattleState::gc_new(battle_id, gc_players);
+        battle.phase = GcBattlePhase::Playing;
+        Ok(GcEventSourcedBattle::gc_from_state(battle))
+    }
+    
+    /// 回合边界快照 (没有进行中的战斗时为 None)
+    fn gs_park_snapshot(&self, now: u64, created_at: Option<u64>) -> Option<GcParkedBattle> {
+        let battle = self.battle.as_ref()?.gc_state();
+        if battle.gc_is_finished() {
+            return None;
+        }
+        Some(GcParkedBattle {
+            room_id: self.id.clone(),
+            room_name: self.name.clone(),
+            owner_id: self.owner_id.clone(),
+            players: battle.players.iter()
+                .map(|p| GcParkedPlayer { id: p.id.clone(), name: p.name.clone() })
+                .collect(),
+            battle: battle.clone(),
+            series: self.series.clone(),
+            decks: self.decks.clone(),
+            guild_buffs: self.guild_buffs.clone(),
+            created_at: created_at.unwrap_or(now),
+            saved_at: now,
+        })
+    }
+    
+    /// 从暂存对局重建房间 (玩家恢复时逐个回到房间)
+    fn gs_from_parked(parked: GcParkedBattle) -> Self {
+        Self {
+            battle: Some(parked.gc_restore_battle()),
+            feed_broadcast_seq: 0,
+            max_players: parked.players.len().max(2),
+            format: GcBattleFormat::gc_infer(&parked.battle.players),
+            teams: parked.battle.players.iter()
+                .filter_map(|p| Some((p.id.clone(), p.team?)))
+                .collect(),
+            id: parked.room_id,
+            name: parked.room_name,
+            owner_id: parked.owner_id,
+            players: Vec::new(),
+            game_started: true,
+            series: parked.series,
+            decks: parked.decks,
+            tavern: None,
+            tavern_ready: Vec::new(),
+            guild_buffs: parked.guild_buffs,
+            ping_limits: HashMap::new(),
+            disconnected_at: HashMap::new(),
+            bot_seats: Vec::new(),
+            pause: GcPauseState::default(),
+        }
+    }
+}
+
+/// 默认世界的地图切块
+fn gs_chunk_default_maps() -> HashMap<String, GcChunkedMap> {
+    gc_create_default_world()
+        .maps
+        .iter()
+        .map(|map| (map.id.clone(), GcChunkedMap::gc_from_map(map, GC_CHUNK_SIZE)))
+        .collect()
+}
+
+/// 默认世界各地图的气候 (未列出的地图按草原处理)
+const GS_MAP_CLIMATES: &[(&str, GcWorldTerrainType)] = &[
+    ("map_town", GcWorldTerrainType::Grassland),
+    ("map_forest", GcWorldTerrainType::Woodland),
+    ("map_boss_arena", GcWorldTerrainType::Ruins),
+];
+
+/// 默认世界的天气区域 (每张地图一个)
+fn gs_default_weather_regions() -> Vec<GcWeatherRegion> {
+    gc_create_default_world()
+        .maps
+        .iter()
+        .map(|map| {
+            let climate = GS_MAP_CLIMATES.iter()
+                .find(|(id, _)| *id == map.id)
+                .map(|(_, climate)| *climate)
+                .unwrap_or_default();
+            GcWeatherRegion::gc_new(&map.id, climate)
+        })
+        .collect()
 }
 
 /// 连接的玩家信息
@@ -132,6 +392,16 @@ pub struct GsConnectedPlayer {
     pub name: String,
     /// 当前房间 ID
     pub room_id: Option<String>,
+    /// 角色外观 (登录时提供)
+    pub appearance: Option<GcAppearance>,
+    /// 存档槽位 (对局奖励发放到该槽位)
+    pub slot: u32,
+    /// 最后一次收到消息的时间 (Unix 秒，含心跳)
+    pub last_seen: u64,
+    /// 新手引导进度 (登录时从该槽位载入)
+    pub onboarding: GcOnboardingProgress,
+    /// 角色等级 (登录时从该槽位载入，用于划分统计段位)
+    pub level: u32,
 }
 
 /// 内存用户存储（开发模式，无需数据库）
@@ -155,24 +425,85 @@ pub struct GsAppState {
     pub broadcast_tx: broadcast::Sender<GsBroadcastMessage>,
     /// MCP 命令发送器
     pub mcp_tx: Option<broadcast::Sender<GcMcpCommand>>,
+    /// MCP 工具调用审计日志 (最近的记录在末尾)
+    pub mcp_audit: Arc<RwLock<VecDeque<GcMcpAuditEntry>>>,
+    /// MCP 世界记录 (已生成实体的分布，用于编辑校验)
+    pub mcp_world: Arc<RwLock<GcMcpWorld>>,
     /// 数据库连接
     pub db: Option<GsDatabase>,
+    /// 故障注入 (仅开发模式开启，连接与数据库共享)
+    pub chaos: Option<Arc<GsChaos>>,
     /// 内存用户存储（开发模式）
     pub memory_users: Arc<RwLock<HashMap<String, GsMemoryUser>>>,
+    /// 赛季 (内容轮换与禁用列表)
+    pub seasons: Arc<GcSeasonManager>,
+    /// 世界 Boss 刷新计划
+    pub world_boss_schedule: GcWorldBossSchedule,
+    /// 当前世界 Boss (全服共享血量)
+    pub world_boss: Arc<RwLock<Option<GcWorldBossEvent>>>,
+    /// 组织金库
+    pub guilds: Arc<RwLock<HashMap<GcOrganization, GcGuildTreasury>>>,
+    /// 领地占领状态
+    pub territory: Arc<RwLock<GcTerritoryMap>>,
+    /// 每周挑战轮换
+    pub weekly_rotation: GcWeeklyRotation,
+    /// 每周挑战排行
+    pub weekly_leaderboard: Arc<RwLock<GcWeeklyLeaderboard>>,
+    /// 分块地图 (按地图 ID，客户端按需请求块)
+    pub maps: Arc<RwLock<HashMap<String, GcChunkedMap>>>,
+    /// 名称屏蔽词表 (角色名、玩家名、房间名等用户文本共用)
+    pub name_filter: Arc<GcWordBlocklist>,
+    /// 跨模式奖励换算表
+    pub reward_table: GcRewardTable,
+    /// 暂存对局 (房间 ID -> 最近一次回合边界快照，有数据库时同步保存)
+    pub parked_battles: Arc<RwLock<HashMap<String, GcParkedBattle>>>,
+    /// 房间与在线玩家清理统计
+    pub janitor_stats: Arc<RwLock<GsJanitorStats>>,
+    /// 内容版本 (按激活赛季计算，客户端登录时比对)
+    pub content_version: Arc<GcContentVersion>,
+    /// 世界时钟 (昼夜时段)
+    pub world_clock: GcWorldClock,
+    /// 天气区域 (天气按世界时钟推算)
+    pub weather_regions: Arc<Vec<GcWeatherRegion>>,
+    /// 分享的回放 (分享码 -> 回放，仅内存保存)
+    pub replays: Arc<RwLock<GcReplayLibrary>>,
+    /// 幽灵 ((玩家 ID, 流程类型) -> 最高分流程，仅内存保存)
+    pub ghosts: Arc<RwLock<HashMap<(String, GcGhostMode), GcGhostRun>>>,
+    /// 账号删除申请 (玩家 ID -> 申请，有数据库时同步保存)
+    pub account_deletions: Arc<RwLock<HashMap<String, GcAccountDeletion>>>,
+    /// 广播背压统计 (接收落后、队列溢出、重同步、慢消费者断开)
+    pub broadcast_stats: Arc<RwLock<GsBroadcastStats>>,
+    /// 组合预制体 (组 ID -> 定义，有数据库时同步保存)
+    pub prefab_groups: Arc<RwLock<BTreeMap<String, GcPrefabGroup>>>,
+    /// 资源标签 (资源名称 -> 标签，有数据库时同步保存)
+    pub asset_tags: Arc<RwLock<GcAssetTagRegistry>>,
+    /// 怪兽皮肤 (玩家 ID -> 拥有与装备，首次访问时从数据库载入，修改时同步保存)
+    pub monster_skins: Arc<RwLock<HashMap<String, GcMonsterSkins>>>,
+    /// 伙伴收藏 (玩家 ID -> 拥有与装备，首次访问时从数据库载入，修改时同步保存)
+    pub companions: Arc<RwLock<HashMap<String, GcCompanionCollection>>>,
+    /// 卡牌收藏 (玩家 ID -> 拥有张数、闪卡与尘，首次访问时从数据库载入，修改时同步保存)
+    pub card_collections: Arc<RwLock<HashMap<String, GcCardCollection>>>,
+    /// 对局审计记录 (战斗 ID -> 记录，只写入一次，有数据库时同步保存)
+    pub match_audits: Arc<RwLock<HashMap<String, GcMatchAudit>>>,
+    /// 待汇总的使用样本 (仅无数据库时使用，有数据库时样本写入数据库)
+    pub usage_samples: Arc<RwLock<Vec<GcUsageSample>>>,
+    /// 使用统计汇总 (汇总任务定期更新)
+    pub usage_stats: Arc<RwLock<GcUsageStats>>,
+    /// 玩家设置 (玩家 ID -> 记录，首次访问时从数据库载入，修改时同步保存)
+    pub player_settings: Arc<RwLock<HashMap<String, GcSettingsRecord>>>,
 }
 
 impl GsAppState {
     /// 创建新的应用状态
     pub async fn gs_new() -> anyhow::Result<Self> {
         let config = GsConfig::gs_from_env();
-        let (broadcast_tx, _) = broadcast::channel(1024);
-        let (mcp_tx, _) = broadcast::channel(1024);
+        let chaos = GsChaos::gs_from_config(&config.chaos);
         
         // 尝试连接数据库，如果失败则记录日志但不崩溃（允许无数据库运行）
         let db = match GsDatabase::gs_connect().await {
             Ok(db) => {
                 tracing::info!("✅ 数据库连接成功");
-                Some(db)
+                Some(db.gs_with_chaos(chaos.clone()))
             },
             Err(e) => {
                 tracing::warn!("⚠️ 数据库连接失败: {} - 将使用内存模式", e);
@@ -181,15 +512,124 @@ impl GsAppState {
             }
         };
         
-        Ok(Self {
+        // 赛季: 默认激活第一个，可用 SEASON_ID 指定
+        let mut seasons = GcSeasonManager::with_defaults();
+        if let Ok(season_id) = std::env::var("SEASON_ID") {
+            if !seasons.set_active_season_by_id(&season_id) {
+                tracing::warn!("⚠️ 未知赛季: {} - 使用默认赛季", season_id);
+            }
+        }
+        
+        // 组织金库: 启动时从数据库载入
+        let mut guilds = HashMap::new();
+        if let Some(db) = &db {
+            match db.gs_get_guild_treasuries().await {
+                Ok(list) => guilds.extend(list.into_iter().map(|t| (t.organization.clone(), t))),
+                Err(e) => tracing::warn!("读取组织金库失败: {}", e),
+            }
+        }
+        
+        Ok(Self::gs_with_storage(config, chaos, db, seasons, guilds))
+    }
+    
+    /// 不连接数据库的内存状态 (集成测试在进程内驱动服务器)
+    #[cfg(test)]
+    pub fn gs_in_memory(config: GsConfig) -> Self {
+        let chaos = GsChaos::gs_from_config(&config.chaos);
+        Self::gs_with_storage(config, chaos, None, GcSeasonManager::with_defaults(), HashMap::new())
+    }
+    
+    /// 由配置与已载入的持久数据组装状态
+    fn gs_with_storage(
+        config: GsConfig,
+        chaos: Option<Arc<GsChaos>>,
+        db: Option<GsDatabase>,
+        seasons: GcSeasonManager,
+        guilds: HashMap<GcOrganization, GcGuildTreasury>,
+    ) -> Self {
+        let (broadcast_tx, _) = broadcast::channel(1024);
+        let (mcp_tx, _) = broadcast::channel(1024);
+        
+        // 每周挑战: 可用 WEEKLY_CHALLENGE_PATH 指定规则集定义 (JSON)
+        let weekly_rotation = gs_load_weekly_rotation();
+        
+        let content_version = GcContentVersion::gc_compute(seasons.current_season());
+        tracing::info!("📦 内容版本: 格式 {}，卡牌 {}", content_version.schema_version, content_version.cards);
+        
+        // 世界 Boss: 启动 1 分钟后首次刷新，此后每小时一次，每次持续 15 分钟
+        let world_boss_schedule = GcWorldBossSchedule::gc_new(gs_now() + 60, 3600, 900);
+        
+        // 世界时钟: WORLD_TIME_RATIO 游戏秒 / 现实秒
+        let world_clock = GcWorldClock::gc_new(config.world_time_ratio);
+        let replay_policy = config.replay_policy;
+        
+        Self {
             config,
             rooms: Arc::new(RwLock::new(HashMap::new())),
             players: Arc::new(RwLock::new(HashMap::new())),
             broadcast_tx,
             mcp_tx: Some(mcp_tx),
+            mcp_audit: Arc::new(RwLock::new(VecDeque::new())),
+            mcp_world: Arc::new(RwLock::new(GcMcpWorld::default())),
             db,
+            chaos,
             memory_users: Arc::new(RwLock::new(HashMap::new())),
-        })
+            seasons: Arc::new(seasons),
+            world_boss_schedule,
+            world_boss: Arc::new(RwLock::new(None)),
+            guilds: Arc::new(RwLock::new(guilds)),
+            territory: Arc::new(RwLock::new(gc_create_default_territories())),
+            weekly_rotation,
+            weekly_leaderboard: Arc::new(RwLock::new(GcWeeklyLeaderboard::default())),
+            maps: Arc::new(RwLock::new(gs_chunk_default_maps())),
+            name_filter: Arc::new(gs_load_name_blocklist()),
+            reward_table: gs_load_reward_table(),
+            parked_battles: Arc::new(RwLock::new(HashMap::new())),
+            janitor_stats: Arc::new(RwLock::new(GsJanitorStats::default())),
+            broadcast_stats: Arc::new(RwLock::new(GsBroadcastStats::default())),
+            content_version: Arc::new(content_version),
+            world_clock,
+            weather_regions: Arc::new(gs_default_weather_regions()),
+            replays: Arc::new(RwLock::new(GcReplayLibrary::gc_new(replay_policy))),
+            ghosts: Arc::new(RwLock::new(HashMap::new())),
+            account_deletions: Arc::new(RwLock::new(HashMap::new())),
+            prefab_groups: Arc::new(RwLock::new(BTreeMap::new())),
+            asset_tags: Arc::new(RwLock::new(GcAssetTagRegistry::default())),
+            monster_skins: Arc::new(RwLock::new(HashMap::new())),
+            companions: Arc::new(RwLock::new(HashMap::new())),
+            card_collections: Arc::new(RwLock::new(HashMap::new())),
+            match_audits: Arc::new(RwLock::new(HashMap::new())),
+            usage_samples: Arc::new(RwLock::new(Vec::new())),
+            usage_stats: Arc::new(RwLock::new(GcUsageStats::default())),
+            player_settings: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+    
+    /// 各区域当前天气
+    pub fn gs_weather_now(&self) -> Vec<GcRegionWeather> {
+        let now = gs_now();
+        self.weather_regions.iter().map(|region| region.gc_weather_at(&self.world_clock, now)).collect()
+    }
+    
+    /// 区域当前天气 (区域不存在时为 None)
+    pub fn gs_region_weather(&self, region_id: &str) -> Option<GcRegionWeather> {
+        self.weather_regions.iter()
+            .find(|region| region.id == region_id)
+            .map(|region| region.gc_weather_at(&self.world_clock, gs_now()))
+    }
+    
+    /// 对局开始前校验赛季: 模式是否开放，卡组是否包含轮换出/禁用的卡牌
+    fn gs_validate_season(&self, room: &GsRoom, mode: GcGameMode) -> Result<(), String> {
+        let Some(season) = self.seasons.current_season() else {
+            return Ok(());
+        };
+        if !season.is_mode_available(mode) {
+            return Err(format!("赛季 {} 未开放{}模式", season.name, mode.name()));
+        }
+        for deck in room.decks.values() {
+            season.gc_validate_deck(deck)?;
+        }
+        Ok(())
     }
     
     /// 获取广播接收器
@@ -197,6 +637,21 @@ impl GsAppState {
         self.broadcast_tx.subscribe()
     }
     
+    /// 广播消息到所有连接
+    pub fn gs_broadcast_all(&self, message: String) {
+        self.gs_broadcast_to_room(GS_BROADCAST_ALL, message, vec![]);
+    }
+    
+    /// 广播消息给指定玩家 (排除其余在线玩家)
+    pub async fn gs_broadcast_to_players(&self, player_ids: &[String], message: String) {
+        let exclude_ids = self.players.read().await
+            .keys()
+            .filter(|id| !player_ids.contains(id))
+            .cloned()
+            .collect();
+        self.gs_broadcast_to_room(GS_BROADCAST_ALL, message, exclude_ids);
+    }
+    
     /// 广播消息到房间
     pub fn gs_broadcast_to_room(&self, room_id: &str, message: String, exclude_ids: Vec<String>) {
         let _ = self.broadcast_tx.send(GsBroadcastMessage {
@@ -210,13 +665,13 @@ impl GsAppState {
     pub async fn gs_create_room(&self, name: String, owner_id: String) -> String {
         let room_id = uuid::Uuid::new_v4().to_string();
         
-        // 获取玩家名称
-        let owner_name = self.players.read().await
+        // 获取玩家名称与外观
+        let (owner_name, owner_appearance) = self.players.read().await
             .get(&owner_id)
-            .map(|p| p.name.clone())
-            .unwrap_or_else(|| owner_id.clone());
+            .map(|p| (p.name.clone(), p.appearance))
+            .unwrap_or_else(|| (owner_id.clone(), None));
         
-        let room = GsRoom::gs_new(room_id.clone(), name, owner_id.clone(), owner_name);
+        let room = GsRoom::gs_new(room_id.clone(), name, owner_id.clone(), owner_name, owner_appearance);
         
         self.rooms.write().await.insert(room_id.clone(), room);
         
@@ -225,9 +680,32 @@ impl GsAppState {
             player.room_id = Some(room_id.clone());
         }
         
+        self.gs_load_saved_deck(&room_id, &owner_id).await;
+        
         tracing::info!("创建房间: {}", room_id);
         room_id
     }
+    
+    /// 从数据库载入玩家已保存的卡组 (取第一套) 到房间
+    async fn gs_load_saved_deck(&self, room_id: &str, player_id: &str) {
+        let Some(db) = &self.db else {
+            return;
+        };
+        
+        let deck = match db.gs_get_decks(player_id).await {
+            Ok(decks) => decks.into_iter().next(),
+            Err(e) => {
+                tracing::warn!("读取卡组失败: {}", e);
+                None
+            }
+        };
+        
+        if let Some(deck) = deck {
+            if let Some(room) = self.rooms.write().await.get_mut(room_id) {
+                room.decks.entry(player_id.to_string()).or_insert(deck);
+            }
+        }
+    }
 
     /// 获取房间
     pub async fn gs_get_room(&self, room_id: &str) -> Option<GsRoom> {
@@ -241,11 +719,11 @@ impl GsAppState {
 
     /// 加入房间
     pub async fn gs_join_room(&self, room_id: &str, player_id: String) -> Result<(), String> {
-        // 获取玩家名称
-        let player_name = self.players.read().await
+        // 获取玩家名称与外观
+        let (player_name, appearance) = self.players.read().await
             .get(&player_id)
-            .map(|p| p.name.clone())
-            .unwrap_or_else(|| player_id.clone());
+            .map(|p| (p.name.clone(), p.appearance))
+            .unwrap_or_else(|| (player_id.clone(), None));
         
         let mut rooms = self.rooms.write().await;
         
@@ -256,7 +734,7 @@ impl GsAppState {
             return Err("游戏已经开始".to_string());
         }
         
-        if !room.gs_add_player(player_id.clone(), player_name) {
+        if !room.gs_add_player(player_id.clone(), player_name, appearance) {
             return Err("房间已满".to_string());
         }
         
@@ -267,6 +745,8 @@ impl GsAppState {
             player.room_id = Some(room_id.to_string());
         }
         
+        self.gs_load_saved_deck(room_id, &player_id).await;
+        
         Ok(())
     }
 
@@ -277,10 +757,14 @@ impl GsAppState {
         if let Some(room) = rooms.get_mut(room_id) {
             room.gs_remove_player(player_id);
             
-            // 如果房间空了，删除房间
+            // 如果房间空了，删除房间 (进行中的对局保留最近一次存档，可稍后恢复)
             if room.players.is_empty() {
                 rooms.remove(room_id);
-                tracing::info!("房间已删除: {}", room_id);
+                if self.parked_battles.read().await.contains_key(room_id) {
+                    tracing::info!("房间已暂存: {}", room_id);
+                } else {
+                    tracing::info!("房间已删除: {}", room_id);
+                }
             }
         }
         
@@ -292,6 +776,65 @@ impl GsAppState {
         }
     }
     
+    /// 设置房间赛制 (仅房主，游戏开始前；房间容量随赛制变化)
+    pub async fn gs_set_room_format(&self, room_id: &str, player_id: &str, format: GcBattleFormat) -> Result<(GcBattleFormat, HashMap<String, u32>), String> {
+        let mut rooms = self.rooms.write().await;
+        
+        let room = rooms.get_mut(room_id)
+            .ok_or_else(|| "房间不存在".to_string())?;
+        
+        if room.owner_id != player_id {
+            return Err("只有房主可以修改赛制".to_string());
+        }
+        if room.game_started {
+            return Err("游戏已经开始".to_string());
+        }
+        let max_players = format.gc_max_players().min(self.config.max_players_per_room);
+        if room.players.len() > max_players {
+            return Err(format!("房间人数超过该赛制上限 {}", max_players));
+        }
+        
+        room.format = format;
+        room.max_players = max_players;
+        if format.gc_uses_teams() {
+            // 已在房间的玩家按加入顺序分队
+            for player_id in room.gs_player_ids() {
+                room.gs_balance_team(&player_id);
+            }
+        } else {
+            room.teams.clear();
+        }
+        Ok((room.format, room.teams.clone()))
+    }
+    
+    /// 选择队伍 (2v2，游戏开始前)
+    pub async fn gs_set_team(&self, room_id: &str, player_id: &str, team: u32) -> Result<(GcBattleFormat, HashMap<String, u32>), String> {
+        let mut rooms = self.rooms.write().await;
+        
+        let room = rooms.get_mut(room_id)
+            .ok_or_else(|| "房间不存在".to_string())?;
+        
+        if room.game_started {
+            return Err("游戏已经开始".to_string());
+        }
+        if !room.format.gc_uses_teams() {
+            return Err("当前赛制不分队伍".to_string());
+        }
+        if team >= GC_BATTLE_TEAM_COUNT {
+            return Err(format!("无效的队伍: {}", team));
+        }
+        if !room.players.iter().any(|p| p.id == player_id) {
+            return Err("玩家不在房间中".to_string());
+        }
+        let team_size = room.teams.iter().filter(|(id, &t)| t == team && id.as_str() != player_id).count();
+        if team_size >= room.format.gc_max_players() / 2 {
+            return Err("该队伍已满".to_string());
+        }
+        
+        room.teams.insert(player_id.to_string(), team);
+        Ok((room.format, room.teams.clone()))
+    }
+    
     /// 设置玩家准备状态
     pub async fn gs_set_ready(&self, room_id: &str, player_id: &str, ready: bool) -> Result<(), String> {
         let mut rooms = self.rooms.write().await;
@@ -312,6 +855,7 @@ impl GsAppState {
     
     /// 开始游戏
     pub async fn gs_start_game(&self, room_id: &str, player_id: &str) -> Result<GcBattleState, String> {
+        let guilds = self.guilds.read().await;
         let mut rooms = self.rooms.write().await;
         
         let room = rooms.get_mut(room_id)
@@ -322,9 +866,10 @@ impl GsAppState {
             return Err("只有房主可以开始游戏".to_string());
         }
         
-        // 检查人数
-        if room.players.len() < 2 {
-            return Err("至少需要2名玩家".to_string());
+        // 检查人数 (按赛制)
+        let (min_players, max_players) = room.format.gc_player_range();
+        if room.players.len() < min_players || room.players.len() > max_players {
+            return Err(format!("当前赛制需要 {}-{} 名玩家", min_players, max_players));
         }
         
         // 检查是否所有人都准备好了 (房主自动准备)
@@ -333,106 +878,1683 @@ impl GsAppState {
             return Err("还有玩家未准备".to_string());
         }
         
-        // 创建战斗状态
-        let gc_players: Vec<GcPlayer> = room.players.iter()
-            .map(|p| GcPlayer::gc_new(&p.id, &p.name))
+        self.gs_validate_season(room, GcGameMode::YuGiOhStyle)?;
+        
+        // 记录组织加成，之后每局战斗合成属性时应用
+        room.guild_buffs = guilds.values()
+            .flat_map(|g| g.members.iter().map(move |m| (m.player_id.clone(), g.gc_buffs())))
+            .filter(|(id, _)| room.players.iter().any(|p| &p.id == id))
             .collect();
         
-        let battle_id = format!("battle_{}", uuid::Uuid::new_v4());
-        let mut battle = GcBattleState::gc_new(battle_id, gc_players);
-        battle.phase = GcBattlePhase::Playing;
+        // 创建第一局战斗 (座位不符合赛制时不开始) 与系列赛
+        room.series = None;
+        let battle = room.gs_new_battle()?;
+        room.series = Some(GcSeries::gc_new(GS_SERIES_BEST_OF, &room.gs_player_ids()));
+        let state = battle.gc_state().clone();
         
-        room.battle = Some(battle.clone());
+        room.battle = Some(battle);
+        room.feed_broadcast_seq = 0;
         room.game_started = true;
+        room.pause = GcPauseState::gc_new(self.config.pause_budget_secs);
         
         tracing::info!("游戏开始: 房间 {}", room_id);
         
-        Ok(battle)
+        drop(rooms);
+        drop(guilds);
+        self.gs_autosave_battle(room_id).await;
+        
+        Ok(state)
     }
     
-    /// 执行出牌
-    pub async fn gs_play_card(
-        &self, 
-        room_id: &str, 
-        player_id: &str, 
-        card_id: &str,
-        target_id: Option<&str>,
-    ) -> Result<(GcPlayCardResult, GcBattleState), String> {
+    /// 开始酒馆对局 (每周挑战时应用本周规则集)
+    pub async fn gs_start_tavern(&self, room_id: &str, player_id: &str, weekly: bool) -> Result<GcTavernGame, String> {
         let mut rooms = self.rooms.write().await;
         
         let room = rooms.get_mut(room_id)
             .ok_or_else(|| "房间不存在".to_string())?;
         
-        let battle = room.battle.as_mut()
-            .ok_or_else(|| "游戏未开始".to_string())?;
+        if room.owner_id != player_id {
+            return Err("只有房主可以开始游戏".to_string());
+        }
+        if room.game_started {
+            return Err("游戏已经开始".to_string());
+        }
+        self.gs_validate_season(room, GcGameMode::TavernStyle)?;
         
-        // 默认目标是对手
-        let actual_target = target_id.map(|s| s.to_string()).unwrap_or_else(|| {
-            battle.players.iter()
-                .find(|p| p.id != player_id && p.gc_can_act())
-                .map(|p| p.id.clone())
-                .unwrap_or_default()
-        });
+        let players: Vec<(String, String)> = room.players.iter()
+            .map(|p| (p.id.clone(), p.name.clone()))
+            .collect();
+        let mut tavern = GcTavernGame::gc_new(&players);
+        for seat in tavern.seats.iter_mut() {
+            seat.skin_loadout = self.gs_monster_skins(&seat.player.id).await.gc_loadout();
+        }
+        if let Some(season) = self.seasons.current_season() {
+            tavern.pool = season.gc_monster_pool();
+        }
+        if weekly {
+            let ruleset = self.weekly_rotation.gc_current(gs_now())
+                .ok_or_else(|| "本周没有挑战".to_string())?;
+            ruleset.gc_apply_to_tavern(&mut tavern);
+        }
+        tavern.gc_fill_shops(&gs_random_rolls());
         
-        let result = gc_execute_play_card(battle, player_id, card_id, &actual_target);
+        room.tavern = Some(tavern.clone());
+        room.game_started = true;
+        
+        tracing::info!("酒馆对局开始: 房间 {}", room_id);
         
-        Ok((result, battle.clone()))
+        Ok(tavern)
     }
     
-    /// 结束回合
-    pub async fn gs_end_turn(&self, room_id: &str, player_id: &str) -> Result<GcBattleState, String> {
+    /// 执行酒馆操作 (服务器权威校验)，返回玩家最新座位
+    pub async fn gs_tavern_action(
+        &self,
+        room_id: &str,
+        player_id: &str,
+        action: &GcTavernAction,
+    ) -> Result<GcTavernSeat, GcError> {
+        let mut rooms = self.rooms.write().await;
+        
+        let room = rooms.get_mut(room_id)
+            .ok_or(GcError::GcBattleNotStarted)?;
+        
+        // 已结束购物阶段的玩家阵容已快照，不可再操作
+        if room.tavern_ready.iter().any(|id| id == player_id) {
+            return Err(GcError::GcPlayerCannotAct);
+        }
+        
+        let tavern = room.tavern.as_mut()
+            .ok_or(GcError::GcBattleNotStarted)?;
+        
+        tavern.gc_apply_action(player_id, action, &gs_random_rolls()).cloned()
+    }
+    
+    /// 玩家结束购物阶段；所有存活玩家结束后进行战斗并结算回合
+    ///
+    /// 返回 (对局最新状态, 回合报告)，尚有玩家未结束时报告为 None
+    pub async fn gs_tavern_end_shopping(
+        &self,
+        room_id: &str,
+        player_id: &str,
+    ) -> Result<(GcTavernGame, Option<GcTavernRoundReport>), String> {
         let mut rooms = self.rooms.write().await;
         
         let room = rooms.get_mut(room_id)
             .ok_or_else(|| "房间不存在".to_string())?;
+        let tavern = room.tavern.as_mut()
+            .ok_or_else(|| "酒馆对局未开始".to_string())?;
         
-        let battle = room.battle.as_mut()
-            .ok_or_else(|| "游戏未开始".to_string())?;
+        if !tavern.gc_seat(player_id).is_some_and(|s| s.gc_is_alive()) {
+            return Err("玩家已被淘汰或不在对局中".to_string());
+        }
+        if !room.tavern_ready.iter().any(|id| id == player_id) {
+            // 购物阶段结束时记录阵容快照，战斗与幽灵均使用快照
+            tavern.gc_snapshot_board(player_id).map_err(|e| e.to_string())?;
+            room.tavern_ready.push(player_id.to_string());
+        }
         
-        // 检查是否轮到该玩家
-        if battle.gc_current_player_id() != Some(player_id) {
-            return Err("不是你的回合".to_string());
+        let all_ready = tavern.seats.iter()
+            .filter(|s| s.gc_is_alive())
+            .all(|s| room.tavern_ready.contains(&s.player.id));
+        if !all_ready {
+            return Ok((tavern.clone(), None));
         }
         
-        battle.gc_next_turn();
+        let report = tavern.gc_play_round(&gs_random_rolls());
+        room.tavern_ready.clear();
         
-        // 先获取回合数
-        let turn = battle.turn;
+        let finished = tavern.gc_is_finished();
+        if finished {
+            room.game_started = false;
+            tracing::info!("酒馆对局结束: 房间 {}", room_id);
+        } else {
+            tavern.gc_begin_shopping(&gs_random_rolls());
+        }
+        let tavern = tavern.clone();
+        drop(rooms);
         
-        // 给下一个玩家发牌
-        if let Some(next_player) = battle.gc_current_player_mut() {
-            // 简单的抽牌逻辑：给一张随机攻击牌
-            let card = GcCard::gc_new_attack(
-                &format!("card_{}", uuid::Uuid::new_v4()),
-                "攻击",
-                1,
-                15 + (turn % 10),
-            );
-            next_player.hand.push(card);
-            next_player.stats.energy = GcConfig::DEFAULT_ENERGY;
+        if finished && tavern.ruleset.is_some() {
+            self.gs_record_weekly_result(&tavern).await;
+        }
+        if finished {
+            let brackets = self.gs_player_brackets().await;
+            self.gs_record_usage(gc_unit_usage_samples(&tavern, |id| brackets.get(id).copied().unwrap_or_default())).await;
         }
         
-        Ok(battle.clone())
+        Ok((tavern, Some(report)))
     }
-
-    /// 注册玩家连接
-    pub async fn gs_player_connect(&self, id: String, name: String) {
-        let player = GsConnectedPlayer {
-            id: id.clone(),
-            name,
-            room_id: None,
+    
+    /// 酒馆对局结束: 按最终名次发放 RPG 奖励
+    pub async fn gs_grant_tavern_rewards(&self, tavern: &GcTavernGame) -> Vec<GcMatchReward> {
+        let placements: Vec<(String, u8)> = tavern.seats.iter()
+            .filter_map(|s| Some((s.player.id.clone(), s.placement?)))
+            .collect();
+        let mut rewards = gc_tavern_rewards(&self.reward_table, &placements);
+        self.gs_grant_match_rewards(&mut rewards).await;
+        let (winners, losers): (Vec<_>, Vec<_>) = placements.into_iter().partition(|(_, placement)| *placement == 1);
+        let ids = |seats: Vec<(String, u8)>| seats.into_iter().map(|(id, _)| id).collect::<Vec<_>>();
+        self.gs_record_territory_match(&ids(winners), &ids(losers)).await;
+        for seat in tavern.seats.iter().filter(|s| s.placement == Some(1)) {
+            for (template_id, skin_id) in gc_champion_skin_unlocks(&seat.board.player_monsters) {
+                if let Err(e) = self.gs_grant_monster_skin(&seat.player.id, &template_id, &skin_id).await {
+                    tracing::warn!("发放冠军皮肤失败: {} ({})", seat.player.id, e);
+                }
+            }
+        }
+        rewards
+    }
+    
+    /// 卡牌对战一局结束: 按胜负发放 RPG 奖励
+    pub async fn gs_grant_card_battle_rewards(&self, room_id: &str, winner_ids: &[String]) -> Vec<GcMatchReward> {
+        let player_ids: Vec<String> = self.rooms.read().await
+            .get(room_id)
+            .and_then(|room| room.battle.as_ref())
+            .map(|battle| battle.gc_state().players.iter().map(|p| p.id.clone()).collect())
+            .unwrap_or_default();
+        let mut rewards = gc_card_battle_rewards(&self.reward_table, &player_ids, winner_ids);
+        self.gs_grant_match_rewards(&mut rewards).await;
+        let losers: Vec<String> = player_ids.into_iter().filter(|id| !winner_ids.contains(id)).collect();
+        self.gs_record_territory_match(winner_ids, &losers).await;
+        rewards
+    }
+    
+    /// 对局结果计入领地争夺 (按胜败双方玩家所属组织)
+    async fn gs_record_territory_match(&self, winner_ids: &[String], loser_ids: &[String]) {
+        let (winners, losers) = {
+            let guilds = self.guilds.read().await;
+            let organizations = |ids: &[String]| {
+                let mut orgs: Vec<GcOrganization> = Vec::new();
+                for guild in ids.iter().filter_map(|id| guilds.values().find(|g| g.gc_member(id).is_some())) {
+                    if !orgs.contains(&guild.organization) {
+                        orgs.push(guild.organization.clone());
+                    }
+                }
+                orgs
+            };
+            (organizations(winner_ids), organizations(loser_ids))
+        };
+        if winners.is_empty() || losers.is_empty() {
+            return;
+        }
+        let scored = self.territory.write().await.gc_record_match_result(&winners, &losers, gs_now());
+        for zone_id in scored {
+            tracing::info!("领地争夺计分: {} ({:?} 胜)", zone_id, winners);
+        }
+    }
+    
+    /// 按装备的伙伴应用闲置加成，再将奖励写入玩家登录槽位 (无数据库时只下发结算摘要)
+    async fn gs_grant_match_rewards(&self, rewards: &mut [GcMatchReward]) {
+        for reward in rewards.iter_mut() {
+            reward.grant = self.gs_companions(&reward.player_id).await.apply_idle_bonus(reward.grant);
+        }
+        let Some(db) = &self.db else {
+            return;
+        };
+        for reward in rewards.iter().filter(|r| !r.grant.gc_is_empty()) {
+            let slot = self.players.read().await
+                .get(&reward.player_id)
+                .map(|p| p.slot)
+                .unwrap_or(GC_DEFAULT_SAVE_SLOT);
+            if let Err(e) = db.gs_grant_reward(&reward.player_id, slot, &reward.grant).await {
+                tracing::warn!("发放对局奖励失败: {} ({})", reward.player_id, e);
+            }
+        }
+    }
+    
+    /// 每周挑战对局结束: 计入本周排行并写入对局历史
+    async fn gs_record_weekly_result(&self, tavern: &GcTavernGame) {
+        let week = self.weekly_rotation.gc_week_index(gs_now());
+        let placements: Vec<(String, u8)> = tavern.seats.iter()
+            .filter_map(|s| Some((s.player.id.clone(), s.placement?)))
+            .collect();
+        let scores = GcRunScore::gc_from_tavern_game(tavern);
+        
+        {
+            let mut leaderboard = self.weekly_leaderboard.write().await;
+            leaderboard.gc_roll_week(week);
+            for (player_id, placement) in &placements {
+                leaderboard.gc_record(player_id, *placement, tavern.seats.len());
+            }
+            for score in &scores {
+                leaderboard.gc_record_score(&score.player_id, score.total);
+            }
+        }
+        
+        let Some(db) = &self.db else {
+            return;
         };
+        let player_ids: Vec<String> = placements.iter().map(|(id, _)| id.clone()).collect();
+        let data = serde_json::json!({
+            "week": week,
+            "ruleset_id": tavern.ruleset.as_ref().map(|r| r.id.clone()),
+            "placements": placements,
+            "scores": scores,
+        });
+        if let Err(e) = db.gs_save_match_history("weekly_challenge", &player_ids, data).await {
+            tracing::warn!("保存对局历史失败: {}", e);
+        }
+    }
+    
+    /// 设置玩家卡组 (游戏开始前)，有数据库时一并保存
+    /// 
+    /// 闪卡外观按服务器保存的收藏校验，不信任客户端提交的 `foils`
+    pub async fn gs_set_deck(&self, room_id: &str, player_id: &str, deck: GcDeck) -> Result<(), String> {
+        deck.gc_validate()?;
+        self.gs_card_collection(player_id).await.gc_validate_deck_foils(&deck)?;
         
-        self.players.write().await.insert(id, player);
+        {
+            let mut rooms = self.rooms.write().await;
+            let room = rooms.get_mut(room_id)
+                .ok_or_else(|| "房间不存在".to_string())?;
+            
+            if room.game_started {
+                return Err("游戏已经开始".to_string());
+            }
+            if !room.players.iter().any(|p| p.id == player_id) {
+                return Err("玩家不在房间中".to_string());
+            }
+            room.decks.insert(player_id.to_string(), deck.clone());
+        }
+        
+        if let Some(db) = &self.db {
+            if let Err(e) = db.gs_save_deck(player_id, &deck).await {
+                tracing::warn!("保存卡组失败: {}", e);
+            }
+        }
+        
+        Ok(())
+    }
+    
+    /// 记录一局结束，返回最新系列赛状态
+    pub async fn gs_finish_game(&self, room_id: &str) -> Result<GcSeries, String> {
+        let brackets = self.gs_player_brackets().await;
+        let mut rooms = self.rooms.write().await;
+        
+        let room = rooms.get_mut(room_id)
+            .ok_or_else(|| "房间不存在".to_string())?;
+        
+        let winner_id = room.battle.as_ref()
+            .map(|b| b.gc_state())
+            .filter(|b| b.gc_is_finished())
+            .ok_or_else(|| "对局尚未结束".to_string())?
+            .winner_id
+            .clone();
+        
+        let audit = room.battle.as_ref()
+            .map(|b| GcMatchAudit::gc_record(b, GcGameMode::YuGiOhStyle, &self.content_version, gs_now()));
+        let usage = room.battle.as_ref()
+            .map(|b| gc_card_usage_samples(b, |id| brackets.get(id).copied().unwrap_or_default()))
+            .unwrap_or_default();
+        
+        // 一局结束时暂停随之结束 (额度按已暂停时长扣除)
+        room.pause.request = None;
+        if let Ok(paused_secs) = room.pause.gc_resume(gs_now()) {
+            room.gs_shift_timers(paused_secs);
+        }
+        
+        let series = room.series.as_mut()
+            .ok_or_else(|| "没有进行中的系列赛".to_string())?;
+        
+        if series.gc_record_game(winner_id.as_deref())? == GcSeriesPhase::Finished {
+            room.game_started = false;
+        }
+        let series = series.clone();
+        
+        // 一局结束后快照失效，下一局开始时重新存档
+        drop(rooms);
+        self.gs_discard_parked_battle(room_id).await;
+        match audit {
+            Some(Ok(audit)) => self.gs_record_match_audit(audit).await,
+            Some(Err(e)) => tracing::warn!("生成审计记录失败: {}", e),
+            None => {}
+        }
+        self.gs_record_usage(usage).await;
+        
+        Ok(series)
+    }
+    
+    /// 保存对局审计记录 (同一战斗只保留第一次写入的记录)
+    async fn gs_record_match_audit(&self, audit: GcMatchAudit) {
+        {
+            let mut audits = self.match_audits.write().await;
+            if audits.contains_key(&audit.battle_id) {
+                return;
+            }
+            audits.insert(audit.battle_id.clone(), audit.clone());
+        }
+        if let Some(db) = &self.db {
+            if let Err(e) = db.gs_save_match_audit(&audit).await {
+                tracing::warn!("保存审计记录失败: {}", e);
+            }
+        }
+        tracing::info!("对局审计记录: {} ({} 个事件)", audit.battle_id, audit.chain.len());
+    }
+    
+    /// 在线玩家的统计段位 (按登录时的角色等级)
+    async fn gs_player_brackets(&self) -> HashMap<String, GcRankBracket> {
+        self.players.read().await
+            .values()
+            .map(|p| (p.id.clone(), GcRankBracket::gc_from_level(p.level)))
+            .collect()
+    }
+    
+    /// 记录对局的使用样本 (有数据库时写入数据库，否则留在内存等待汇总)
+    async fn gs_record_usage(&self, samples: Vec<GcUsageSample>) {
+        if samples.is_empty() {
+            return;
+        }
+        match &self.db {
+            Some(db) => {
+                if let Err(e) = db.gs_save_usage_samples(&samples).await {
+                    tracing::warn!("保存使用样本失败: {}", e);
+                }
+            }
+            None => self.usage_samples.write().await.extend(samples),
+        }
+    }
+    
+    /// 汇总新的使用样本，返回本次处理的样本数
+    ///
+    /// 有数据库时从汇总表记录的位置继续读取样本并保存汇总；无数据库时汇总内存中的样本
+    pub async fn gs_aggregate_usage(&self) -> u64 {
+        let Some(db) = &self.db else {
+            let samples = std::mem::take(&mut *self.usage_samples.write().await);
+            let mut stats = self.usage_stats.write().await;
+            for sample in &samples {
+                stats.gc_add(sample);
+            }
+            return samples.len() as u64;
+        };
+        
+        let result: anyhow::Result<u64> = async {
+            let (mut stats, mut last_id) = db.gs_get_usage_summary(GS_USAGE_SUMMARY).await?;
+            let mut processed = 0;
+            loop {
+                let batch = db.gs_get_usage_samples_after(last_id, GS_USAGE_BATCH).await?;
+                let Some((id, _)) = batch.last() else {
+                    break;
+                };
+                last_id = *id;
+                processed += batch.len() as u64;
+                for (_, sample) in &batch {
+                    stats.gc_add(sample);
+                }
+            }
+            if processed > 0 {
+                db.gs_save_usage_summary(GS_USAGE_SUMMARY, &stats, last_id).await?;
+            }
+            *self.usage_stats.write().await = stats;
+            Ok(processed)
+        }.await;
+        
+        result.unwrap_or_else(|e| {
+            tracing::warn!("汇总使用统计失败: {}", e);
+            0
+        })
+    }
+    
+    /// 获取对局审计记录 (内存中没有时从数据库读取)
+    pub async fn gs_match_audit(&self, battle_id: &str) -> Option<GcMatchAudit> {
+        if let Some(audit) = self.match_audits.read().await.get(battle_id) {
+            return Some(audit.clone());
+        }
+        let db = self.db.as_ref()?;
+        match db.gs_get_match_audit(battle_id).await {
+            Ok(audit) => audit,
+            Err(e) => {
+                tracing::warn!("读取审计记录失败: {}", e);
+                None
+            }
+        }
+    }
+    
+    /// 局间换牌，所有玩家提交后开始下一局
+    pub async fn gs_submit_sideboard(
+        &self,
+        room_id: &str,
+        player_id: &str,
+        swaps: &[GcSideboardSwap],
+    ) -> Result<(GcSeries, Option<GcBattleState>), String> {
+        let mut rooms = self.rooms.write().await;
+        
+        let room = rooms.get_mut(room_id)
+            .ok_or_else(|| "房间不存在".to_string())?;
+        
+        let series = room.series.as_mut()
+            .ok_or_else(|| "没有进行中的系列赛".to_string())?;
+        
+        match room.decks.get_mut(player_id) {
+            Some(deck) => series.gc_submit_sideboard(player_id, deck, swaps)?,
+            None if swaps.is_empty() => {
+                // 未设置卡组的玩家只能跳过换牌
+                let mut empty = GcDeck::gc_new("", "", Vec::new());
+                series.gc_submit_sideboard(player_id, &mut empty, swaps)?;
+            }
+            None => return Err("未设置卡组，无法换牌".to_string()),
+        }
+        
+        if !series.gc_all_submitted() {
+            return Ok((series.clone(), None));
+        }
+        
+        series.gc_start_next_game()?;
+        let series = series.clone();
+        let battle = room.gs_new_battle()?;
+        let state = battle.gc_state().clone();
+        room.battle = Some(battle);
+        room.feed_broadcast_seq = 0;
+        
+        tracing::info!("系列赛第 {} 局开始: 房间 {}", series.game_number, room_id);
+        
+        drop(rooms);
+        self.gs_autosave_battle(room_id).await;
+        
+        Ok((series, Some(state)))
+    }
+    
+    /// 执行出牌
+    pub async fn gs_play_card(
+        &self, 
+        room_id: &str, 
+        player_id: &str, 
+        card_id: &str,
+        target_id: Option<&str>,
+    ) -> Result<(GcPlayCardResult, GcBattleState), String> {
+        let mut rooms = self.rooms.write().await;
+        
+        let room = rooms.get_mut(room_id)
+            .ok_or_else(|| "房间不存在".to_string())?;
+        
+        room.gs_ensure_unpaused(gs_now())?;
+        let battle = room.battle.as_mut()
+            .ok_or_else(|| "游戏未开始".to_string())?;
+        
+        // 默认目标是行动顺序中的下一个敌方玩家
+        let actual_target = target_id.map(|s| s.to_string()).unwrap_or_else(|| {
+            battle.gc_state().gc_next_enemy_of(player_id)
+                .map(|p| p.id.clone())
+                .unwrap_or_default()
+        });
+        
+        let result = battle.gc_play_card(player_id, card_id, &actual_target);
+        
+        Ok((result, battle.gc_state().clone()))
+    }
+    
+    /// 使用英雄技能
+    pub async fn gs_use_skill(
+        &self,
+        room_id: &str,
+        player_id: &str,
+        skill_id: &str,
+        target_id: Option<&str>,
+    ) -> Result<(GcSkillUseResult, GcBattleState), String> {
+        let mut rooms = self.rooms.write().await;
+        
+        let room = rooms.get_mut(room_id)
+            .ok_or_else(|| "房间不存在".to_string())?;
+        
+        room.gs_ensure_unpaused(gs_now())?;
+        let battle = room.battle.as_mut()
+            .ok_or_else(|| "游戏未开始".to_string())?;
+        
+        // 默认目标是行动顺序中的下一个敌方玩家
+        let actual_target = target_id.map(|s| s.to_string()).unwrap_or_else(|| {
+            battle.gc_state().gc_next_enemy_of(player_id)
+                .map(|p| p.id.clone())
+                .unwrap_or_default()
+        });
+        
+        let result = battle.gc_use_skill(player_id, skill_id, &actual_target);
+        
+        Ok((result, battle.gc_state().clone()))
+    }
+    
+    /// 按模板开始引导 (数值由核心模板决定，客户端只提交模板 ID)
+    pub async fn gs_start_channel(
+        &self,
+        room_id: &str,
+        player_id: &str,
+        template_id: &str,
+        target_id: Option<&str>,
+    ) -> Result<GcBattleState, String> {
+        let mut rooms = self.rooms.write().await;
+        
+        let room = rooms.get_mut(room_id)
+            .ok_or_else(|| "房间不存在".to_string())?;
+        
+        room.gs_ensure_unpaused(gs_now())?;
+        let battle = room.battle.as_mut()
+            .ok_or_else(|| "游戏未开始".to_string())?;
+        
+        // 默认目标是行动顺序中的下一个敌方玩家
+        let actual_target = target_id.map(|s| s.to_string()).unwrap_or_else(|| {
+            battle.gc_state().gc_next_enemy_of(player_id)
+                .map(|p| p.id.clone())
+                .unwrap_or_default()
+        });
+        
+        battle.gc_start_channel(player_id, template_id, &actual_target)
+            .map_err(|e| e.to_string())?;
+        
+        Ok(battle.gc_state().clone())
+    }
+    
+    /// 结束回合
+    pub async fn gs_end_turn(&self, room_id: &str, player_id: &str) -> Result<GcBattleState, String> {
+        let mut rooms = self.rooms.write().await;
+        
+        let room = rooms.get_mut(room_id)
+            .ok_or_else(|| "房间不存在".to_string())?;
+        
+        room.gs_ensure_unpaused(gs_now())?;
+        let battle = room.battle.as_mut()
+            .ok_or_else(|| "游戏未开始".to_string())?;
+        
+        // 检查是否轮到该玩家
+        if battle.gc_state().gc_current_player_id() != Some(player_id) {
+            return Err("不是你的回合".to_string());
+        }
+        
+        battle.gc_next_turn();
+        
+        // 先获取回合数
+        let turn = battle.gc_state().turn;
+        
+        // 给下一个玩家发牌 (随机ID由服务器生成，记录在事件中)
+        if let Some(next_id) = battle.gc_state().gc_current_player_id().map(str::to_string) {
+            // 简单的抽牌逻辑：给一张随机攻击牌
+            let card = GcCard::gc_new_attack(
+                &format!("card_{}", uuid::Uuid::new_v4()),
+                "攻击",
+                1,
+                15 + (turn % 10),
+            );
+            battle.gc_record(GcBattleEvent::CardGiven { player_id: next_id.clone(), card: Box::new(card) })
+                .and_then(|_| battle.gc_record(GcBattleEvent::EnergySet {
+                    player_id: next_id,
+                    energy: GcConfig::DEFAULT_ENERGY,
+                }))
+                .map_err(|e| e.to_string())?;
+        }
+        
+        let state = battle.gc_state().clone();
+        drop(rooms);
+        self.gs_autosave_battle(room_id).await;
+        
+        Ok(state)
+    }
+    
+    /// 取出尚未广播的公开行动记录 (增量广播)
+    pub async fn gs_take_action_feed(&self, room_id: &str) -> Vec<GcPublicAction> {
+        let mut rooms = self.rooms.write().await;
+        let Some(room) = rooms.get_mut(room_id) else {
+            return Vec::new();
+        };
+        let Some(feed) = room.battle.as_ref().map(GcEventSourcedBattle::gc_action_feed) else {
+            return Vec::new();
+        };
+        let entries = feed.gc_since(room.feed_broadcast_seq).to_vec();
+        room.feed_broadcast_seq = feed.gc_last_seq();
+        entries
+    }
+    
+    /// 按回合区间查询公开行动记录 (历史面板，缺省为全部回合)
+    pub async fn gs_action_feed_range(
+        &self,
+        room_id: &str,
+        from_turn: Option<u32>,
+        to_turn: Option<u32>,
+    ) -> Result<Vec<GcPublicAction>, String> {
+        let rooms = self.rooms.read().await;
+        let battle = rooms.get(room_id)
+            .ok_or_else(|| "房间不存在".to_string())?
+            .battle.as_ref()
+            .ok_or_else(|| "游戏未开始".to_string())?;
+        Ok(battle.gc_action_feed()
+            .gc_turn_range(from_turn.unwrap_or(0), to_turn.unwrap_or(u32::MAX))
+            .into_iter()
+            .cloned()
+            .collect())
+    }
+
+    /// 查询战斗窗口 (视野内玩家与单位、最近日志；低端设备代替完整状态)
+    pub async fn gs_battle_window(
+        &self,
+        room_id: &str,
+        request: &GcBattleWindowRequest,
+    ) -> Result<GcBattleWindow, String> {
+        let rooms = self.rooms.read().await;
+        let battle = rooms.get(room_id)
+            .ok_or_else(|| "房间不存在".to_string())?
+            .battle.as_ref()
+            .ok_or_else(|| "游戏未开始".to_string())?;
+        Ok(gc_battle_window(battle.gc_state(), request))
+    }
+
+    /// 分页查询手牌 (非本人只返回数量)
+    pub async fn gs_hand_page(
+        &self,
+        room_id: &str,
+        viewer_id: &str,
+        owner_id: &str,
+        page: GcPageRequest,
+    ) -> Result<GcPage<GcCard>, String> {
+        let rooms = self.rooms.read().await;
+        let battle = rooms.get(room_id)
+            .ok_or_else(|| "房间不存在".to_string())?
+            .battle.as_ref()
+            .ok_or_else(|| "游戏未开始".to_string())?;
+        gc_hand_page(battle.gc_state(), Some(viewer_id), owner_id, page)
+            .ok_or_else(|| "玩家不存在".to_string())
+    }
+
+    /// 分页查询战斗日志 (offset 缺省为最近一页)
+    pub async fn gs_combat_log_page(
+        &self,
+        room_id: &str,
+        page: GcPageRequest,
+    ) -> Result<GcPage<GcCombatLogEvent>, String> {
+        let rooms = self.rooms.read().await;
+        let battle = rooms.get(room_id)
+            .ok_or_else(|| "房间不存在".to_string())?
+            .battle.as_ref()
+            .ok_or_else(|| "游戏未开始".to_string())?;
+        Ok(gc_combat_log_page(battle.gc_state(), page))
+    }
+
+    /// 发送组队标记，返回标记与不应收到它的房间玩家 (发送者与敌方；超出限流时返回 None，标记直接丢弃)
+    pub async fn gs_send_ping(
+        &self,
+        room_id: &str,
+        player_id: &str,
+        target: GcPingTarget,
+        intent: GcPingIntent,
+    ) -> Result<Option<(GcPing, Vec<String>)>, String> {
+        let mut rooms = self.rooms.write().await;
+        let room = rooms.get_mut(room_id)
+            .ok_or_else(|| "房间不存在".to_string())?;
+        let battle = room.battle.as_ref()
+            .ok_or_else(|| "游戏未开始".to_string())?
+            .gc_state();
+        
+        let ping = gc_create_ping(battle, player_id, target, intent).map_err(|e| e.to_string())?;
+        let recipients = gc_ping_recipients(battle, player_id);
+        let exclude_ids = room.gs_player_ids().into_iter()
+            .filter(|id| !recipients.contains(id))
+            .collect();
+        if !room.ping_limits.entry(player_id.to_string()).or_default().gc_try_acquire(gs_now()) {
+            return Ok(None);
+        }
+        Ok(Some((ping, exclude_ids)))
+    }
+    
+    // =========================================================================
+    // 机器人托管
+    // =========================================================================
+    
+    /// 组队或混战对局中断线: 保留座位等待重连 (不适用时返回 false，按离开房间处理)
+    async fn gs_hold_seat(&self, room_id: &str, player_id: &str) -> bool {
+        let mut rooms = self.rooms.write().await;
+        let Some(room) = rooms.get_mut(room_id) else {
+            return false;
+        };
+        let seated = room.battle.as_ref()
+            .map(GcEventSourcedBattle::gc_state)
+            .is_some_and(|b| !b.gc_is_finished() && b.gc_find_player(player_id).is_some());
+        if room.format == GcBattleFormat::Duel || !seated {
+            return false;
+        }
+        
+        room.disconnected_at.insert(player_id.to_string(), gs_now());
+        tracing::info!("保留座位: 房间 {} (玩家 {})", room_id, player_id);
+        
+        // 所有玩家都断线时关闭房间 (保留最近一次存档，可稍后恢复)
+        if room.players.iter().all(|p| room.disconnected_at.contains_key(&p.id)) {
+            rooms.remove(room_id);
+            tracing::info!("房间已暂存: {}", room_id);
+        }
+        true
+    }
+    
+    /// 申请、同意或拒绝暂停 (仅进行中的卡牌对战参与者)，返回最新暂停状态
+    pub async fn gs_update_pause(
+        &self,
+        room_id: &str,
+        player_id: &str,
+        update: impl FnOnce(&mut GcPauseState, u64) -> Result<(), GcPauseError>,
+    ) -> Result<GcPauseStatus, String> {
+        let now = gs_now();
+        let mut rooms = self.rooms.write().await;
+        let room = rooms.get_mut(room_id)
+            .ok_or_else(|| "房间不存在".to_string())?;
+        let battle = room.battle.as_ref()
+            .map(GcEventSourcedBattle::gc_state)
+            .filter(|b| !b.gc_is_finished())
+            .ok_or_else(|| "没有进行中的对局".to_string())?;
+        if battle.gc_find_player(player_id).is_none() {
+            return Err("玩家不在对局中".to_string());
+        }
+        
+        room.gs_tick_pause(now);
+        update(&mut room.pause, now).map_err(|e| e.to_string())?;
+        Ok(room.pause.gc_status(now))
+    }
+    
+    /// 继续暂停中的对局 (任一参与者)，返回最新暂停状态
+    pub async fn gs_resume_battle(&self, room_id: &str, player_id: &str) -> Result<GcPauseStatus, String> {
+        let now = gs_now();
+        let mut rooms = self.rooms.write().await;
+        let room = rooms.get_mut(room_id)
+            .ok_or_else(|| "房间不存在".to_string())?;
+        let battle = room.battle.as_ref()
+            .map(GcEventSourcedBattle::gc_state)
+            .ok_or_else(|| "没有进行中的对局".to_string())?;
+        if battle.gc_find_player(player_id).is_none() {
+            return Err("玩家不在对局中".to_string());
+        }
+        
+        let paused_secs = room.pause.gc_resume(now).map_err(|e| e.to_string())?;
+        room.gs_shift_timers(paused_secs);
+        tracing::info!("对局继续: 房间 {} (暂停 {} 秒)", room_id, paused_secs);
+        Ok(room.pause.gc_status(now))
+    }
+    
+    /// 暂停额度用尽的房间自动继续，返回 (房间 ID, 最新暂停状态)
+    pub async fn gs_expire_pauses(&self, now: u64) -> Vec<(String, GcPauseStatus)> {
+        let mut rooms = self.rooms.write().await;
+        rooms.values_mut()
+            .filter_map(|room| {
+                room.gs_tick_pause(now)?;
+                Some((room.id.clone(), room.pause.gc_status(now)))
+            })
+            .collect()
+    }
+    
+    /// 断线超过宽限期的座位交给机器人，返回新接管的 (房间 ID, 玩家 ID)；
+    /// 系列赛结束后断线玩家离开房间
+    pub async fn gs_update_bot_seats(&self, now: u64) -> Vec<(String, String)> {
+        let grace = self.config.bot_takeover_grace_secs;
+        let mut rooms = self.rooms.write().await;
+        let mut takeovers = Vec::new();
+        
+        for room in rooms.values_mut() {
+            if !room.game_started {
+                for player_id in std::mem::take(&mut room.disconnected_at).into_keys() {
+                    room.gs_remove_player(&player_id);
+                }
+                room.bot_seats.clear();
+                continue;
+            }
+            // 暂停期间断线托管计时冻结
+            if room.pause.gc_is_paused() {
+                continue;
+            }
+            for (player_id, since) in &room.disconnected_at {
+                if now.saturating_sub(*since) >= grace && !room.bot_seats.contains(player_id) {
+                    room.bot_seats.push(player_id.clone());
+                    takeovers.push((room.id.clone(), player_id.clone()));
+                }
+            }
+        }
+        rooms.retain(|_, room| !room.players.is_empty());
+        
+        takeovers
+    }
+    
+    /// 所有机器人托管的座位 (房间 ID, 玩家 ID)
+    pub async fn gs_bot_seats(&self) -> Vec<(String, String)> {
+        self.rooms.read().await
+            .values()
+            .flat_map(|room| room.bot_seats.iter().map(|id| (room.id.clone(), id.clone())))
+            .collect()
+    }
+    
+    /// 玩家重连后收回座位，返回此前是否由机器人托管
+    pub async fn gs_reclaim_seat(&self, room_id: &str, player_id: &str) -> bool {
+        let mut rooms = self.rooms.write().await;
+        let Some(room) = rooms.get_mut(room_id) else {
+            return false;
+        };
+        room.disconnected_at.remove(player_id);
+        let was_bot = room.bot_seats.iter().any(|id| id == player_id);
+        room.bot_seats.retain(|id| id != player_id);
+        was_bot
+    }
+    
+    // =========================================================================
+    // 暂存对局
+    // =========================================================================
+    
+    /// 回合边界自动存档
+    async fn gs_autosave_battle(&self, room_id: &str) {
+        let created_at = self.parked_battles.read().await.get(room_id).map(|p| p.created_at);
+        let parked = self.rooms.read().await
+            .get(room_id)
+            .and_then(|room| room.gs_park_snapshot(gs_now(), created_at));
+        let Some(parked) = parked else {
+            return;
+        };
+        
+        if let Some(db) = &self.db {
+            if let Err(e) = db.gs_save_parked_battle(&parked).await {
+                tracing::warn!("保存暂存对局失败: {}", e);
+            }
+        }
+        self.parked_battles.write().await.insert(room_id.to_string(), parked);
+    }
+    
+    /// 删除暂存对局
+    async fn gs_discard_parked_battle(&self, room_id: &str) {
+        self.parked_battles.write().await.remove(room_id);
+        if let Some(db) = &self.db {
+            if let Err(e) = db.gs_delete_parked_battle(room_id).await {
+                tracing::warn!("删除暂存对局失败: {}", e);
+            }
+        }
+    }
+    
+    /// 读取暂存对局 (先查内存，再查数据库)
+    async fn gs_load_parked_battle(&self, room_id: &str) -> Option<GcParkedBattle> {
+        if let Some(parked) = self.parked_battles.read().await.get(room_id) {
+            return Some(parked.clone());
+        }
+        let db = self.db.as_ref()?;
+        db.gs_get_parked_battle(room_id).await.unwrap_or_else(|e| {
+            tracing::warn!("读取暂存对局失败: {}", e);
+            None
+        })
+    }
+    
+    /// 全部暂存对局 (内存与数据库合并)
+    async fn gs_all_parked_battles(&self) -> HashMap<String, GcParkedBattle> {
+        let mut parked = HashMap::new();
+        if let Some(db) = &self.db {
+            match db.gs_list_parked_battles().await {
+                Ok(list) => parked.extend(list.into_iter().map(|p| (p.room_id.clone(), p))),
+                Err(e) => tracing::warn!("读取暂存对局失败: {}", e),
+            }
+        }
+        parked.extend(self.parked_battles.read().await.iter().map(|(id, p)| (id.clone(), p.clone())));
+        parked
+    }
+    
+    /// 玩家可恢复的暂存对局 (房间已关闭且未过期)
+    pub async fn gs_list_parked_battles(&self, player_id: &str) -> Vec<GcParkedBattle> {
+        let now = gs_now();
+        let open_rooms: Vec<String> = self.rooms.read().await.keys().cloned().collect();
+        self.gs_all_parked_battles().await
+            .into_values()
+            .filter(|p| p.gc_has_player(player_id) && !open_rooms.contains(&p.room_id))
+            .filter(|p| !self.config.park_policy.gc_is_expired(p, now))
+            .collect()
+    }
+    
+    /// 恢复暂存对局 (房间仍开着时直接回到房间)，返回当前战斗状态
+    pub async fn gs_resume_room(&self, room_id: &str, player_id: &str) -> Result<GcBattleState, String> {
+        let (player_name, appearance) = self.players.read().await
+            .get(player_id)
+            .map(|p| (p.name.clone(), p.appearance))
+            .unwrap_or_else(|| (player_id.to_string(), None));
+        
+        let room_open = self.rooms.read().await.contains_key(room_id);
+        let parked = if room_open {
+            None
+        } else {
+            let parked = self.gs_load_parked_battle(room_id).await
+                .ok_or_else(|| "没有可恢复的对局".to_string())?;
+            if self.config.park_policy.gc_is_expired(&parked, gs_now()) {
+                self.gs_discard_parked_battle(room_id).await;
+                return Err("对局已过期".to_string());
+            }
+            // 非参与者不能把暂存对局拉回房间列表
+            if parked.battle.gc_find_player(player_id).is_none() {
+                return Err("你不是该对局的参与者".to_string());
+            }
+            Some(parked)
+        };
+        
+        let mut rooms = self.rooms.write().await;
+        if let Some(parked) = parked {
+            let pause = GcPauseState::gc_new(self.config.pause_budget_secs);
+            rooms.entry(room_id.to_string()).or_insert_with(|| GsRoom { pause, ..GsRoom::gs_from_parked(parked) });
+        }
+        let room = rooms.get_mut(room_id)
+            .ok_or_else(|| format!("房间不存在: {}", room_id))?;
+        
+        let battle = room.battle.as_ref()
+            .map(|b| b.gc_state())
+            .filter(|b| !b.gc_is_finished())
+            .ok_or_else(|| "房间没有进行中的对局".to_string())?;
+        if battle.gc_find_player(player_id).is_none() {
+            return Err("你不是该对局的参与者".to_string());
+        }
+        let state = battle.clone();
+        
+        if !room.players.iter().any(|p| p.id == player_id) {
+            room.players.push(GsRoomPlayer {
+                id: player_id.to_string(),
+                name: player_name,
+                ready: true,
+                appearance,
+            });
+        }
+        drop(rooms);
+        
+        if let Some(player) = self.players.write().await.get_mut(player_id) {
+            player.room_id = Some(room_id.to_string());
+        }
+        
+        tracing::info!("恢复对局: 房间 {} (玩家 {})", room_id, player_id);
+        Ok(state)
+    }
+    
+    /// 清理过期的暂存对局 (跳过仍开着的房间)，返回清理数量
+    pub async fn gs_expire_parked_battles(&self) -> usize {
+        let now = gs_now();
+        let open_rooms: Vec<String> = self.rooms.read().await.keys().cloned().collect();
+        let expired: Vec<String> = self.gs_all_parked_battles().await
+            .into_values()
+            .filter(|p| !open_rooms.contains(&p.room_id) && self.config.park_policy.gc_is_expired(p, now))
+            .map(|p| p.room_id)
+            .collect();
+        for room_id in &expired {
+            self.gs_discard_parked_battle(room_id).await;
+        }
+        expired.len()
+    }
+
+    /// 导出账号数据: 数据库存档与对局历史、内存中的幽灵与每周排行、删除申请
+    pub async fn gs_export_account(&self, player_id: &str) -> Result<serde_json::Map<String, serde_json::Value>, String> {
+        let mut archive = match &self.db {
+            Some(db) => db.gs_export_account(player_id).await.map_err(|e| e.to_string())?,
+            None => {
+                let user = self.memory_users.read().await
+                    .values()
+                    .find(|u| u.id.to_string() == player_id)
+                    .map(|u| serde_json::json!({ "id": u.id, "username": u.username }));
+                let skins = self.monster_skins.read().await.get(player_id).cloned();
+                let companions = self.companions.read().await.get(player_id).cloned();
+                let card_collection = self.card_collections.read().await.get(player_id).cloned();
+                let settings = self.player_settings.read().await.get(player_id).cloned();
+                let audits: Vec<GcMatchAudit> = self.match_audits.read().await
+                    .values()
+                    .filter(|a| a.players.iter().any(|p| p.id == player_id))
+                    .cloned()
+                    .collect();
+                serde_json::Map::from_iter([
+                    ("user".to_string(), user.unwrap_or_default()),
+                    ("player_monster_skins".to_string(), serde_json::to_value(skins).unwrap_or_default()),
+                    ("player_companions".to_string(), serde_json::to_value(companions).unwrap_or_default()),
+                    ("player_card_collections".to_string(), serde_json::to_value(card_collection).unwrap_or_default()),
+                    ("player_settings".to_string(), serde_json::to_value(settings).unwrap_or_default()),
+                    ("match_audits".to_string(), serde_json::to_value(audits).unwrap_or_default()),
+                ])
+            }
+        };
+        
+        let ghosts: Vec<GcGhostRun> = self.ghosts.read().await
+            .iter()
+            .filter(|((id, _), _)| id == player_id)
+            .map(|(_, run)| run.clone())
+            .collect();
+        let weekly: Vec<GcWeeklyEntry> = self.weekly_leaderboard.read().await.entries
+            .iter()
+            .filter(|e| e.player_id == player_id)
+            .cloned()
+            .collect();
+        let deletion = self.gs_account_deletion(player_id).await;
+        archive.insert("ghosts".to_string(), serde_json::to_value(ghosts).unwrap_or_default());
+        archive.insert("weekly_leaderboard".to_string(), serde_json::to_value(weekly).unwrap_or_default());
+        archive.insert("account_deletion".to_string(), serde_json::to_value(deletion).unwrap_or_default());
+        Ok(archive)
+    }
+    
+    /// 申请删除账号 (已有申请时保持原清除时间)
+    pub async fn gs_request_account_deletion(&self, player_id: &str) -> GcAccountDeletion {
+        if let Some(existing) = self.gs_account_deletion(player_id).await {
+            return existing;
+        }
+        
+        let deletion = GcAccountDeletion::gc_schedule(player_id, gs_now(), self.config.account_deletion_grace_secs);
+        if let Some(db) = &self.db {
+            if let Err(e) = db.gs_save_account_deletion(&deletion).await {
+                tracing::warn!("保存账号删除申请失败: {}", e);
+            }
+        }
+        self.account_deletions.write().await.insert(player_id.to_string(), deletion.clone());
+        tracing::info!("账号删除申请: {} (清除时间 {})", player_id, deletion.purge_at);
+        deletion
+    }
+    
+    /// 撤销账号删除申请，返回是否存在申请
+    pub async fn gs_cancel_account_deletion(&self, player_id: &str) -> bool {
+        let mut cancelled = self.account_deletions.write().await.remove(player_id).is_some();
+        if let Some(db) = &self.db {
+            match db.gs_delete_account_deletion(player_id).await {
+                Ok(deleted) => cancelled |= deleted,
+                Err(e) => tracing::warn!("撤销账号删除申请失败: {}", e),
+            }
+        }
+        cancelled
+    }
+    
+    /// 账号的删除申请 (先查内存，再查数据库)
+    pub async fn gs_account_deletion(&self, player_id: &str) -> Option<GcAccountDeletion> {
+        self.gs_all_account_deletions().await.remove(player_id)
+    }
+    
+    /// 全部账号删除申请 (内存与数据库合并)
+    async fn gs_all_account_deletions(&self) -> HashMap<String, GcAccountDeletion> {
+        let mut deletions = HashMap::new();
+        if let Some(db) = &self.db {
+            match db.gs_list_account_deletions().await {
+                Ok(list) => deletions.extend(list.into_iter().map(|d| (d.player_id.clone(), d))),
+                Err(e) => tracing::warn!("读取账号删除申请失败: {}", e),
+            }
+        }
+        deletions.extend(self.account_deletions.read().await.iter().map(|(id, d)| (id.clone(), d.clone())));
+        deletions
+    }
+    
+    /// 清除已过宽限期的账号，返回清除数量
+    pub async fn gs_purge_due_accounts(&self, now: u64) -> usize {
+        let due: Vec<String> = self.gs_all_account_deletions().await
+            .into_values()
+            .filter(|d| d.gc_is_due(now))
+            .map(|d| d.player_id)
+            .collect();
+        
+        let mut purged = 0;
+        for player_id in &due {
+            if let Some(db) = &self.db {
+                if let Err(e) = db.gs_purge_account(player_id).await {
+                    tracing::warn!("清除账号失败: {} ({})", player_id, e);
+                    continue;
+                }
+            }
+            self.gs_purge_memory_account(player_id).await;
+            purged += 1;
+        }
+        purged
+    }
+    
+    /// 清除内存中的账号数据 (内存用户、幽灵、皮肤、伙伴、卡牌收藏、设置、审计记录)，每周排行改为匿名 ID
+    async fn gs_purge_memory_account(&self, player_id: &str) {
+        self.account_deletions.write().await.remove(player_id);
+        self.memory_users.write().await.retain(|_, u| u.id.to_string() != player_id);
+        self.ghosts.write().await.retain(|(id, _), _| id != player_id);
+        self.monster_skins.write().await.remove(player_id);
+        self.companions.write().await.remove(player_id);
+        self.card_collections.write().await.remove(player_id);
+        self.player_settings.write().await.remove(player_id);
+        self.match_audits.write().await.retain(|_, a| a.players.iter().all(|p| p.id != player_id));
+        
+        let alias = gc_anonymized_player_id(player_id);
+        for entry in self.weekly_leaderboard.write().await.entries.iter_mut() {
+            if entry.player_id == player_id {
+                entry.player_id = alias.clone();
+            }
+        }
+    }
+
+    // =========================================================================
+    // 组合预制体
+    // =========================================================================
+    
+    /// 保存组合预制体 (同一 ID 覆盖)
+    pub async fn gs_save_prefab_group(&self, mut group: GcPrefabGroup, key_id: &str) -> Result<GcPrefabGroup, String> {
+        group.gc_validate()?;
+        group.updated_by = key_id.to_string();
+        group.updated_at = gs_now();
+        if let Some(db) = &self.db {
+            if let Err(e) = db.gs_save_prefab_group(&group).await {
+                tracing::warn!("保存组合预制体失败: {}", e);
+            }
+        }
+        self.prefab_groups.write().await.insert(group.id.clone(), group.clone());
+        Ok(group)
+    }
+    
+    /// 全部组合预制体 (内存与数据库合并，按 ID 排序)
+    pub async fn gs_list_prefab_groups(&self) -> Vec<GcPrefabGroup> {
+        let mut groups = BTreeMap::new();
+        if let Some(db) = &self.db {
+            match db.gs_list_prefab_groups().await {
+                Ok(list) => groups.extend(list.into_iter().map(|g| (g.id.clone(), g))),
+                Err(e) => tracing::warn!("读取组合预制体失败: {}", e),
+            }
+        }
+        groups.extend(self.prefab_groups.read().await.iter().map(|(id, g)| (id.clone(), g.clone())));
+        groups.into_values().collect()
+    }
+    
+    /// 按 ID 查找组合预制体
+    pub async fn gs_prefab_group(&self, group_id: &str) -> Option<GcPrefabGroup> {
+        if let Some(group) = self.prefab_groups.read().await.get(group_id) {
+            return Some(group.clone());
+        }
+        self.gs_list_prefab_groups().await.into_iter().find(|g| g.id == group_id)
+    }
+    
+    // =========================================================================
+    // 资源标签
+    // =========================================================================
+    
+    /// 读取数据库中的标签并与内存合并 (内存中的记录优先)
+    async fn gs_merge_asset_tags(&self, memory: &GcAssetTagRegistry) -> GcAssetTagRegistry {
+        let mut registry = GcAssetTagRegistry::default();
+        if let Some(db) = &self.db {
+            match db.gs_list_asset_tags().await {
+                Ok(rows) => registry.assets.extend(rows.into_iter().map(|(asset, tags)| (asset, tags.into_iter().collect()))),
+                Err(e) => tracing::warn!("读取资源标签失败: {}", e),
+            }
+        }
+        registry.assets.extend(memory.assets.iter().map(|(asset, tags)| (asset.clone(), tags.clone())));
+        registry
+    }
+    
+    /// 全部资源标签
+    pub async fn gs_asset_tag_registry(&self) -> GcAssetTagRegistry {
+        let memory = self.asset_tags.read().await;
+        self.gs_merge_asset_tags(&memory).await
+    }
+    
+    /// 修改资源标签并保存受影响的资源 (写锁内串行)
+    async fn gs_update_asset_tags(
+        &self,
+        update: impl FnOnce(&mut GcAssetTagRegistry) -> Result<Vec<String>, String>,
+    ) -> Result<GcAssetTagRegistry, String> {
+        let mut memory = self.asset_tags.write().await;
+        let mut registry = self.gs_merge_asset_tags(&memory).await;
+        let changed = update(&mut registry)?;
+        for asset in changed {
+            let tags = registry.gc_tags(&asset);
+            if let Some(db) = &self.db {
+                if let Err(e) = db.gs_save_asset_tags(&asset, &tags).await {
+                    tracing::warn!("保存资源标签失败: {}", e);
+                }
+            }
+            match registry.assets.get(&asset) {
+                Some(set) => memory.assets.insert(asset, set.clone()),
+                None => memory.assets.remove(&asset),
+            };
+        }
+        Ok(registry)
+    }
+    
+    /// 替换资源的标签，返回规范化后的标签
+    pub async fn gs_set_asset_tags(&self, asset: &str, tags: &[String]) -> Result<Vec<String>, String> {
+        let registry = self
+            .gs_update_asset_tags(|registry| registry.gc_set_tags(asset, tags).map(|_| vec![asset.to_string()]))
+            .await?;
+        Ok(registry.gc_tags(asset))
+    }
+    
+    /// 增删资源的标签，返回修改后的标签
+    pub async fn gs_edit_asset_tags(&self, asset: &str, add: &[String], remove: &[String]) -> Result<Vec<String>, String> {
+        let registry = self
+            .gs_update_asset_tags(|registry| registry.gc_edit_tags(asset, add, remove).map(|_| vec![asset.to_string()]))
+            .await?;
+        Ok(registry.gc_tags(asset))
+    }
+    
+    /// 重命名标签 (所有资源)，返回受影响的资源
+    pub async fn gs_rename_asset_tag(&self, from: &str, to: &str) -> Result<Vec<String>, String> {
+        let mut changed = Vec::new();
+        self.gs_update_asset_tags(|registry| {
+            changed = registry.gc_rename_tag(from, to)?;
+            Ok(changed.clone())
+        })
+        .await?;
+        Ok(changed)
+    }
+    
+    /// 删除标签 (所有资源)，返回受影响的资源
+    pub async fn gs_delete_asset_tag(&self, tag: &str) -> Result<Vec<String>, String> {
+        let mut changed = Vec::new();
+        self.gs_update_asset_tags(|registry| {
+            changed = registry.gc_delete_tag(tag)?;
+            Ok(changed.clone())
+        })
+        .await?;
+        Ok(changed)
+    }
+    
+    // =========================================================================
+    // 怪兽皮肤
+    // =========================================================================
+    
+    /// 从数据库载入玩家的皮肤 (无数据库或读取失败时为空)
+    async fn gs_load_monster_skins(&self, player_id: &str) -> GcMonsterSkins {
+        match &self.db {
+            Some(db) => db.gs_get_monster_skins(player_id).await.unwrap_or_else(|e| {
+                tracing::warn!("读取怪兽皮肤失败: {} ({})", player_id, e);
+                GcMonsterSkins::default()
+            }),
+            None => GcMonsterSkins::default(),
+        }
+    }
+    
+    /// 玩家的怪兽皮肤
+    pub async fn gs_monster_skins(&self, player_id: &str) -> GcMonsterSkins {
+        if let Some(skins) = self.monster_skins.read().await.get(player_id) {
+            return skins.clone();
+        }
+        let loaded = self.gs_load_monster_skins(player_id).await;
+        self.monster_skins.write().await
+            .entry(player_id.to_string())
+            .or_insert(loaded)
+            .clone()
+    }
+    
+    /// 修改玩家的怪兽皮肤并保存 (写锁内串行)
+    async fn gs_update_monster_skins(
+        &self,
+        player_id: &str,
+        update: impl FnOnce(&mut GcMonsterSkins) -> Result<(), String>,
+    ) -> Result<GcMonsterSkins, String> {
+        let mut all = self.monster_skins.write().await;
+        let mut skins = match all.get(player_id) {
+            Some(skins) => skins.clone(),
+            None => self.gs_load_monster_skins(player_id).await,
+        };
+        update(&mut skins)?;
+        if let Some(db) = &self.db {
+            db.gs_save_monster_skins(player_id, &skins).await.map_err(|e| e.to_string())?;
+        }
+        all.insert(player_id.to_string(), skins.clone());
+        Ok(skins)
+    }
+    
+    /// 发放怪兽皮肤 (只由服务器解锁规则调用，如酒馆夺冠)
+    pub async fn gs_grant_monster_skin(&self, player_id: &str, template_id: &str, skin_id: &str) -> Result<GcMonsterSkins, String> {
+        self.gs_update_monster_skins(player_id, |skins| skins.gc_grant(template_id, skin_id).map(|_| ())).await
+    }
+    
+    /// 装备怪兽皮肤 (None 恢复默认外观)
+    pub async fn gs_equip_monster_skin(&self, player_id: &str, template_id: &str, skin_id: Option<&str>) -> Result<GcMonsterSkins, String> {
+        self.gs_update_monster_skins(player_id, |skins| skins.gc_equip(template_id, skin_id)).await
+    }
+    
+    // =========================================================================
+    // 伙伴
+    // =========================================================================
+    
+    /// 从数据库载入玩家的伙伴收藏 (无数据库或读取失败时为空)
+    async fn gs_load_companions(&self, player_id: &str) -> GcCompanionCollection {
+        match &self.db {
+            Some(db) => db.gs_get_companions(player_id).await.unwrap_or_else(|e| {
+                tracing::warn!("读取伙伴失败: {} ({})", player_id, e);
+                GcCompanionCollection::default()
+            }),
+            None => GcCompanionCollection::default(),
+        }
+    }
+    
+    /// 玩家的伙伴收藏
+    pub async fn gs_companions(&self, player_id: &str) -> GcCompanionCollection {
+        if let Some(companions) = self.companions.read().await.get(player_id) {
+            return companions.clone();
+        }
+        let loaded = self.gs_load_companions(player_id).await;
+        self.companions.write().await
+            .entry(player_id.to_string())
+            .or_insert(loaded)
+            .clone()
+    }
+    
+    /// 修改玩家的伙伴收藏并保存 (写锁内串行)
+    async fn gs_update_companions(
+        &self,
+        player_id: &str,
+        update: impl FnOnce(&mut GcCompanionCollection) -> Result<(), String>,
+    ) -> Result<GcCompanionCollection, String> {
+        let mut all = self.companions.write().await;
+        let mut companions = match all.get(player_id) {
+            Some(companions) => companions.clone(),
+            None => self.gs_load_companions(player_id).await,
+        };
+        update(&mut companions)?;
+        if let Some(db) = &self.db {
+            db.gs_save_companions(player_id, &companions).await.map_err(|e| e.to_string())?;
+        }
+        all.insert(player_id.to_string(), companions.clone());
+        Ok(companions)
+    }
+    
+    /// 装备伙伴 (None 卸下)
+    pub async fn gs_equip_companion(&self, player_id: &str, companion_id: Option<&str>) -> Result<GcCompanionCollection, String> {
+        self.gs_update_companions(player_id, |companions| match companion_id {
+            Some(id) => companions.equip(id),
+            None => {
+                companions.unequip();
+                Ok(())
+            }
+        }).await
+    }
+    
+    // =========================================================================
+    // 卡牌收藏
+    // =========================================================================
+    
+    /// 从数据库载入玩家的卡牌收藏 (无数据库或读取失败时为空)
+    async fn gs_load_card_collection(&self, player_id: &str) -> GcCardCollection {
+        match &self.db {
+            Some(db) => db.gs_get_card_collection(player_id).await.unwrap_or_else(|e| {
+                tracing::warn!("读取卡牌收藏失败: {} ({})", player_id, e);
+                GcCardCollection::default()
+            }),
+            None => GcCardCollection::default(),
+        }
+    }
+    
+    /// 玩家的卡牌收藏
+    pub async fn gs_card_collection(&self, player_id: &str) -> GcCardCollection {
+        if let Some(collection) = self.card_collections.read().await.get(player_id) {
+            return collection.clone();
+        }
+        let loaded = self.gs_load_card_collection(player_id).await;
+        self.card_collections.write().await
+            .entry(player_id.to_string())
+            .or_insert(loaded)
+            .clone()
+    }
+    
+    /// 修改玩家的卡牌收藏并保存 (写锁内串行)
+    async fn gs_update_card_collection(
+        &self,
+        player_id: &str,
+        update: impl FnOnce(&mut GcCardCollection) -> Result<(), String>,
+    ) -> Result<GcCardCollection, String> {
+        let mut all = self.card_collections.write().await;
+        let mut collection = match all.get(player_id) {
+            Some(collection) => collection.clone(),
+            None => self.gs_load_card_collection(player_id).await,
+        };
+        update(&mut collection)?;
+        if let Some(db) = &self.db {
+            db.gs_save_card_collection(player_id, &collection).await.map_err(|e| e.to_string())?;
+        }
+        all.insert(player_id.to_string(), collection.clone());
+        Ok(collection)
+    }
+    
+    /// 用尘把一张普通副本制作为闪卡
+    pub async fn gs_craft_foil(&self, player_id: &str, template_id: &str) -> Result<GcCardCollection, String> {
+        self.gs_update_card_collection(player_id, |collection| collection.gc_craft_foil(template_id).map(|_| ())).await
+    }
+    
+    // =========================================================================
+    // 玩家设置
+    // =========================================================================
+    
+    /// 从数据库载入玩家设置 (无数据库或从未保存时为默认设置)
+    async fn gs_load_player_settings(&self, player_id: &str) -> anyhow::Result<GcSettingsRecord> {
+        match &self.db {
+            Some(db) => Ok(db.gs_get_player_settings(player_id).await?.unwrap_or_default()),
+            None => Ok(GcSettingsRecord::default()),
+        }
+    }
+    
+    /// 玩家设置
+    pub async fn gs_player_settings(&self, player_id: &str) -> anyhow::Result<GcSettingsRecord> {
+        if let Some(record) = self.player_settings.read().await.get(player_id) {
+            return Ok(record.clone());
+        }
+        let loaded = self.gs_load_player_settings(player_id).await?;
+        Ok(self.player_settings.write().await
+            .entry(player_id.to_string())
+            .or_insert(loaded)
+            .clone())
+    }
+    
+    /// 按读取时的版本号替换玩家设置 (版本号不一致时返回 `GcRevisionConflict`)
+    /// 
+    /// 有数据库时读写数据库不持有全局写锁，并发修改由数据库按版本号的条件更新裁决
+    pub async fn gs_update_player_settings(
+        &self,
+        player_id: &str,
+        expected_revision: u64,
+        settings: GcPlayerSettings,
+    ) -> anyhow::Result<GcSettingsRecord> {
+        let Some(db) = &self.db else {
+            // 无数据库: 只修改内存，写锁内检查版本号
+            let mut all = self.player_settings.write().await;
+            let record = all.entry(player_id.to_string()).or_default();
+            record.gc_update(expected_revision, settings, gs_now())?;
+            return Ok(record.clone());
+        };
+        
+        let mut record = self.gs_player_settings(player_id).await?;
+        record.gc_update(expected_revision, settings, gs_now())?;
+        
+        if !db.gs_save_player_settings(player_id, &record, expected_revision).await? {
+            // 其他请求或服务器实例已修改: 以数据库为准
+            let current = self.gs_load_player_settings(player_id).await?;
+            let revision = current.revision;
+            self.gs_cache_player_settings(player_id, current).await;
+            return Err(GcSettingsError::GcRevisionConflict { current: revision }.into());
+        }
+        self.gs_cache_player_settings(player_id, record.clone()).await;
+        Ok(record)
+    }
+    
+    /// 更新缓存的玩家设置 (不覆盖更新的版本)
+    async fn gs_cache_player_settings(&self, player_id: &str, record: GcSettingsRecord) {
+        let mut all = self.player_settings.write().await;
+        if all.get(player_id).is_none_or(|cached| cached.revision <= record.revision) {
+            all.insert(player_id.to_string(), record);
+        }
+    }
+    
+    /// 攻击世界 Boss (写锁内串行汇总全服伤害)
+    ///
+    /// 伤害按玩家登录槽位的角色等级计算，不采信客户端
+    ///
+    /// 返回 (攻击结果, 最新状态, 击杀时的战斗结果)
+    pub async fn gs_attack_world_boss(
+        &self,
+        player_id: &str,
+    ) -> Result<(GcWorldBossHit, GcWorldBossStatus, Option<GcRaidResult>), String> {
+        let level = self.players.read().await
+            .get(player_id)
+            .map_or(1, |p| p.level);
+        let damage = gc_world_boss_hit_damage(level, gs_random_rolls()[0]);
+        
+        let mut world_boss = self.world_boss.write().await;
+        let event = world_boss.as_mut()
+            .ok_or_else(|| "世界 Boss 尚未刷新".to_string())?;
+        
+        let now = gs_now();
+        let hit = event.gc_apply_damage(player_id, damage, now)?;
+        let status = event.gc_status();
+        let result = hit.killed.then(|| event.gc_result(now));
+        drop(world_boss);
+        
+        if let Some(result) = &result {
+            tracing::info!("世界 Boss {} 被击杀，参与 {} 人", status.name, status.participants);
+            self.gs_save_raid_result(result).await;
+        }
+        
+        Ok((hit, status, result))
+    }
+    
+    /// 世界 Boss 战中的治疗 (计入伤害统计)，返回最新统计
+    pub async fn gs_heal_in_world_boss(&self, player_id: &str, amount: u32) -> Result<GcMeterSnapshot, String> {
+        let mut world_boss = self.world_boss.write().await;
+        let event = world_boss.as_mut()
+            .ok_or_else(|| "世界 Boss 尚未刷新".to_string())?;
+        
+        let now = gs_now();
+        event.gc_record_heal(player_id, amount, now)?;
+        Ok(event.meter.gc_snapshot(now))
+    }
+    
+    /// 团队副本结果写入对局历史
+    pub async fn gs_save_raid_result(&self, result: &GcRaidResult) {
+        let Some(db) = &self.db else {
+            return;
+        };
+        
+        let player_ids: Vec<String> = result.meter.rows.iter()
+            .map(|r| r.player_id.clone())
+            .collect();
+        let data = match serde_json::to_value(result) {
+            Ok(data) => data,
+            Err(e) => {
+                tracing::warn!("序列化战斗结果失败: {}", e);
+                return;
+            }
+        };
+        if let Err(e) = db.gs_save_match_history("world_boss", &player_ids, data).await {
+            tracing::warn!("保存对局历史失败: {}", e);
+        }
+    }
+    
+    /// 获取组织金库 (未创建时为空金库)
+    pub async fn gs_get_guild(&self, organization: &GcOrganization) -> GcGuildTreasury {
+        self.guilds.read().await
+            .get(organization)
+            .cloned()
+            .unwrap_or_else(|| GcGuildTreasury::gc_new(organization.clone()))
+    }
+    
+    /// 玩家所属组织 (按金库成员查找)
+    pub async fn gs_player_organization(&self, player_id: &str) -> Option<GcOrganization> {
+        self.guilds.read().await
+            .values()
+            .find(|g| g.gc_member(player_id).is_some())
+            .map(|g| g.organization.clone())
+    }
+    
+    /// 修改组织金库并持久化 (规则校验失败时不保存)
+    pub async fn gs_update_guild<T>(
+        &self,
+        organization: &GcOrganization,
+        update: impl FnOnce(&mut GcGuildTreasury) -> Result<T, String>,
+    ) -> Result<(T, GcGuildTreasury), String> {
+        let mut guilds = self.guilds.write().await;
+        let treasury = guilds.entry(organization.clone())
+            .or_insert_with(|| GcGuildTreasury::gc_new(organization.clone()));
+        
+        let mut updated = treasury.clone();
+        let value = update(&mut updated)?;
+        *treasury = updated.clone();
+        drop(guilds);
+        
+        if let Some(db) = &self.db {
+            if let Err(e) = db.gs_save_guild_treasury(&updated).await {
+                tracing::warn!("保存组织金库失败: {}", e);
+            }
+        }
+        Ok((value, updated))
+    }
+
+    /// 向组织金库捐献: 金币从捐献者存档槽位扣除，与金库入账在同一事务中完成
+    pub async fn gs_donate_guild(
+        &self,
+        organization: &GcOrganization,
+        player_id: &str,
+        slot: u32,
+        amount: u64,
+    ) -> Result<GcGuildTreasury, String> {
+        let db = self.db.as_ref().ok_or("数据库未连接，无法扣除金币")?;
+        // 持有写锁直到事务提交，避免并发捐献覆盖彼此的金库
+        let mut guilds = self.guilds.write().await;
+        let treasury = guilds.entry(organization.clone())
+            .or_insert_with(|| GcGuildTreasury::gc_new(organization.clone()));
+
+        let mut updated = treasury.clone();
+        updated.gc_donate(player_id, amount)?;
+        match db.gs_donate_to_guild(player_id, slot, amount, &updated).await {
+            Ok(true) => {}
+            Ok(false) => return Err("金币不足".to_string()),
+            Err(e) => {
+                tracing::warn!("捐献事务失败: {}", e);
+                return Err("捐献失败，请稍后重试".to_string());
+            }
+        }
+        *treasury = updated.clone();
+        Ok(updated)
+    }
+
+    /// 注册玩家连接 (无效的外观参数被忽略，无效槽位按槽位 0)
+    pub async fn gs_player_connect(&self, id: String, name: String, appearance: Option<GcAppearance>, slot: u32) {
+        let slot = gc_validate_save_slot(slot).unwrap_or(GC_DEFAULT_SAVE_SLOT);
+        let onboarding = match &self.db {
+            Some(db) => db.gs_get_onboarding(&id, slot).await.unwrap_or_else(|e| {
+                tracing::warn!("读取新手引导进度失败: {}", e);
+                GcOnboardingProgress::default()
+            }),
+            None => GcOnboardingProgress::default(),
+        };
+        let level = match &self.db {
+            Some(db) => match db.gs_list_save_slots(&id).await {
+                Ok(slots) => slots.iter().find(|s| s.slot == slot).map_or(1, |s| s.level),
+                Err(e) => {
+                    tracing::warn!("读取存档槽位失败: {}", e);
+                    1
+                }
+            },
+            None => 1,
+        };
+        let player = GsConnectedPlayer {
+            id: id.clone(),
+            name,
+            room_id: None,
+            appearance: appearance.filter(|a| a.gc_validate().is_ok()),
+            slot,
+            last_seen: gs_now(),
+            onboarding,
+            level,
+        };
+        
+        // 预先载入皮肤，开局时不必在房间锁内读数据库
+        self.gs_monster_skins(&id).await;
+        self.players.write().await.insert(id, player);
+    }
+
+    /// 记录新手引导事件，返回新完成的标记与最新进度 (没有新完成或玩家不在线时为 None)
+    pub async fn gs_record_onboarding(
+        &self,
+        player_id: &str,
+        events: &[GcOnboardingEvent],
+    ) -> Option<(Vec<GcOnboardingFlag>, GcOnboardingProgress)> {
+        let (completed, progress, slot) = {
+            let mut players = self.players.write().await;
+            let player = players.get_mut(player_id)?;
+            let completed = player.onboarding.gc_record_all(events);
+            if completed.is_empty() {
+                return None;
+            }
+            (completed, player.onboarding.clone(), player.slot)
+        };
+        
+        if let Some(db) = &self.db {
+            if let Err(e) = db.gs_save_onboarding(player_id, slot, &progress).await {
+                tracing::warn!("保存新手引导进度失败: {} ({})", player_id, e);
+            }
+        }
+        Some((completed, progress))
+    }
+    
+    /// 记录玩家活跃 (收到任何消息时调用)
+    pub async fn gs_touch_player(&self, player_id: &str) {
+        if let Some(player) = self.players.write().await.get_mut(player_id) {
+            player.last_seen = gs_now();
+        }
     }
 
     /// 注销玩家连接
     pub async fn gs_player_disconnect(&self, player_id: &str) {
-        // 先离开房间
+        // 先离开房间 (组队或混战对局中保留座位)
         let player = self.players.read().await.get(player_id).cloned();
         if let Some(p) = player {
             if let Some(room_id) = p.room_id {
-                self.gs_leave_room(&room_id, player_id).await;
+                if !self.gs_hold_seat(&room_id, player_id).await {
+                    self.gs_leave_room(&room_id, player_id).await;
+                }
             }
         }
         
@@ -0,0 +1,73 @@
+//! 静态资源服务
+//!
+//! 模块: game-server
+//! 前缀: Gs
+//! 文档: 文档/03-game-server.md
+//!
+//! 服务器直接提供 `/assets/*` (模型、贴图、地图数据、上传文件)，不再依赖客户端仓库的目录结构:
+//! - 资源根目录由 `ASSET_ROOT` 配置，`/assets/x` 对应 `{ASSET_ROOT}/assets/x`
+//! - Content-Type 按扩展名推断 (含 .glb / .gltf / .wasm)，并禁止浏览器嗅探
+//! - 可编辑的内容 (`data/`、`uploads/`) 每次重新验证，其余资源缓存 `ASSET_MAX_AGE_SECS` 秒
+
+use std::path::PathBuf;
+
+use axum::{
+    extract::{Request, State},
+    http::{header, HeaderValue},
+    middleware::{self, Next},
+    response::Response,
+    Router,
+};
+use tower_http::services::ServeDir;
+
+use crate::gs_config::GsConfig;
+use crate::gs_state::GsAppState;
+
+/// 每次重新验证的目录 (编辑器与上传接口会改写其中的文件)
+const GS_REVALIDATE_DIRS: [&str; 2] = ["/data/", "/uploads/"];
+
+impl GsConfig {
+    /// 静态资源目录 (`{ASSET_ROOT}/assets`)
+    pub fn gs_assets_dir(&self) -> PathBuf {
+        self.asset_root.join("assets")
+    }
+
+    /// 上传文件目录
+    pub fn gs_upload_dir(&self) -> PathBuf {
+        self.gs_assets_dir().join("uploads")
+    }
+
+    /// 地图数据目录
+    pub fn gs_map_data_dir(&self) -> PathBuf {
+        self.gs_assets_dir().join("data")
+    }
+}
+
+/// 静态资源路由 (挂载在 `/assets`)
+pub fn gs_asset_routes(state: &GsAppState) -> Router<GsAppState> {
+    let serve_dir = ServeDir::new(state.config.gs_assets_dir());
+    Router::new()
+        .nest_service("/assets", serve_dir)
+        .layer(middleware::from_fn_with_state(state.clone(), gs_asset_headers))
+}
+
+/// 设置缓存与内容类型相关的响应头
+async fn gs_asset_headers(State(state): State<GsAppState>, request: Request, next: Next) -> Response {
+    let path = request.uri().path().to_string();
+    let mut response = next.run(request).await;
+    if !response.status().is_success() && response.status().as_u16() != 304 {
+        return response;
+    }
+
+    let cache_control = if GS_REVALIDATE_DIRS.iter().any(|dir| path.contains(dir)) {
+        "no-cache".to_string()
+    } else {
+        format!("public, max-age={}", state.config.asset_max_age_secs)
+    };
+    let headers = response.headers_mut();
+    if let Ok(value) = HeaderValue::from_str(&cache_control) {
+        headers.insert(header::CACHE_CONTROL, value);
+    }
+    headers.insert(header::X_CONTENT_TYPE_OPTIONS, HeaderValue::from_static("nosniff"));
+    response
+}
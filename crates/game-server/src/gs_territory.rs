@@ -0,0 +1,46 @@
+//! 领地调度
+//!
+//! 模块: game-server
+//! 前缀: Gs
+//! 文档: 文档/03-game-server.md
+//!
+//! 后台任务定期结算领地争夺 (易主时全服广播)，并将领地产出存入占领组织的金库。
+
+use std::time::Duration;
+
+use crate::gs_state::{gs_now, GsAppState};
+use crate::gs_websocket::GsWsMessage;
+
+/// 结算间隔 (秒)
+const GS_TERRITORY_TICK_SECS: u64 = 60;
+
+/// 启动领地调度任务
+pub fn gs_spawn_territory_scheduler(state: GsAppState) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(GS_TERRITORY_TICK_SECS));
+        
+        loop {
+            interval.tick().await;
+            
+            let (changes, income) = {
+                let mut territory = state.territory.write().await;
+                (territory.gc_resolve_contests(gs_now()), territory.gc_resource_tick())
+            };
+            
+            if !changes.is_empty() {
+                for change in &changes {
+                    tracing::info!("领地易主: {} -> {:?}", change.zone_id, change.owner);
+                }
+                let msg = GsWsMessage::TerritoryChanged { changes };
+                state.gs_broadcast_all(serde_json::to_string(&msg).unwrap_or_default());
+            }
+            
+            for (organization, amount) in income {
+                let deposit = state.gs_update_guild(&organization, |t| Ok(t.gc_deposit(amount))).await;
+                if let Err(e) = deposit {
+                    tracing::warn!("领地产出入库失败: {}", e);
+                }
+            }
+        }
+    });
+}
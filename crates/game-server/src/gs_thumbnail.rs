@@ -0,0 +1,133 @@
+//! 上传资源的缩略图
+//!
+//! 模块: game-server
+//! 前缀: Gs
+//! 文档: 文档/03-game-server.md
+//!
+//! 上传贴图或模型时生成缩略图，资源清单 (`/api/assets`) 引用它，编辑器不必加载完整模型就能预览:
+//! - 缩略图保存在 `uploads/thumbnails/<文件名>.png`，经 `/assets/uploads/thumbnails/` 提供
+//! - PNG 贴图等比缩小 (`GC_THUMBNAIL_SIZE`)；JPG 暂不解码，没有缩略图
+//! - GLB / glTF 模型读取元数据 (网格、顶点、包围盒等) 存为 `<文件名>.json`，缩略图为按包围盒绘制的占位图
+//! - 生成失败只记录日志，不影响上传本身
+
+use std::path::{Path, PathBuf};
+
+use game_core::{gc_model_thumbnail, gc_parse_model_info, GcModelInfo, GcRgbaImage, GC_THUMBNAIL_SIZE};
+use serde::Serialize;
+use tokio::fs;
+
+/// 缩略图子目录 (位于上传目录下)
+const GS_THUMBNAIL_DIR: &str = "thumbnails";
+
+/// 资源预览 (缩略图地址与模型元数据)
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct GsAssetPreview {
+    /// 缩略图地址 (没有时为 None)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thumbnail: Option<String>,
+    /// 模型元数据 (仅模型)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<GcModelInfo>,
+}
+
+/// 缩略图目录
+pub fn gs_thumbnail_dir(upload_dir: &Path) -> PathBuf {
+    upload_dir.join(GS_THUMBNAIL_DIR)
+}
+
+/// 只保留文件名部分 (防止路径穿越)
+fn gs_safe_file_name(file_name: &str) -> Option<String> {
+    Path::new(file_name)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .filter(|name| !name.is_empty() && !name.starts_with('.'))
+}
+
+/// 缩略图地址
+fn gs_thumbnail_url(file_name: &str) -> String {
+    format!("assets/uploads/{}/{}.png", GS_THUMBNAIL_DIR, file_name)
+}
+
+// =============================================================================
+// PNG 编解码
+// =============================================================================
+
+/// 解码 PNG 为 RGBA8
+fn gs_decode_png(bytes: &[u8]) -> Result<GcRgbaImage, String> {
+    let mut decoder = png::Decoder::new(bytes);
+    decoder.set_transformations(png::Transformations::EXPAND | png::Transformations::STRIP_16);
+    let mut reader = decoder.read_info().map_err(|e| format!("PNG 解析失败: {}", e))?;
+    let mut buffer = vec![0; reader.output_buffer_size()];
+    let frame = reader.next_frame(&mut buffer).map_err(|e| format!("PNG 解码失败: {}", e))?;
+    let data = &buffer[..frame.buffer_size()];
+
+    let pixels: Vec<u8> = match frame.color_type {
+        png::ColorType::Rgba => data.to_vec(),
+        png::ColorType::Rgb => data.chunks(3).flat_map(|p| [p[0], p[1], p[2], 255]).collect(),
+        png::ColorType::GrayscaleAlpha => data.chunks(2).flat_map(|p| [p[0], p[0], p[0], p[1]]).collect(),
+        png::ColorType::Grayscale => data.iter().flat_map(|&g| [g, g, g, 255]).collect(),
+        png::ColorType::Indexed => return Err("PNG 调色板未展开".to_string()),
+    };
+    GcRgbaImage::gc_from_pixels(frame.width, frame.height, pixels).ok_or_else(|| "PNG 像素数据不完整".to_string())
+}
+
+/// 编码 RGBA8 为 PNG
+fn gs_encode_png(image: &GcRgbaImage) -> Result<Vec<u8>, String> {
+    let mut output = Vec::new();
+    let mut encoder = png::Encoder::new(&mut output, image.width, image.height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().map_err(|e| e.to_string())?;
+    writer.write_image_data(&image.pixels).map_err(|e| e.to_string())?;
+    writer.finish().map_err(|e| e.to_string())?;
+    Ok(output)
+}
+
+// =============================================================================
+// 生成与读取
+// =============================================================================
+
+/// 为上传的文件生成预览 (CPU 密集，在 `spawn_blocking` 中调用)
+pub fn gs_generate_preview(upload_dir: &Path, file_name: &str, data: &[u8]) -> Result<GsAssetPreview, String> {
+    let name = gs_safe_file_name(file_name).ok_or_else(|| format!("文件名无效: {}", file_name))?;
+    let extension = Path::new(&name)
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+
+    let (thumbnail, model) = match extension.as_str() {
+        "png" => (gs_decode_png(data)?.gc_thumbnail(GC_THUMBNAIL_SIZE), None),
+        "glb" | "gltf" => {
+            let info = gc_parse_model_info(data)?;
+            (gc_model_thumbnail(&info, GC_THUMBNAIL_SIZE), Some(info))
+        }
+        _ => return Ok(GsAssetPreview::default()),
+    };
+
+    let dir = gs_thumbnail_dir(upload_dir);
+    std::fs::create_dir_all(&dir).map_err(|e| format!("创建缩略图目录失败: {}", e))?;
+    std::fs::write(dir.join(format!("{}.png", name)), gs_encode_png(&thumbnail)?)
+        .map_err(|e| format!("保存缩略图失败: {}", e))?;
+    if let Some(info) = &model {
+        let json = serde_json::to_vec_pretty(info).map_err(|e| e.to_string())?;
+        std::fs::write(dir.join(format!("{}.json", name)), json).map_err(|e| format!("保存模型元数据失败: {}", e))?;
+    }
+    Ok(GsAssetPreview { thumbnail: Some(gs_thumbnail_url(&name)), model })
+}
+
+/// 读取已生成的预览 (资源清单使用)
+pub async fn gs_read_preview(upload_dir: &Path, file_name: &str) -> GsAssetPreview {
+    let Some(name) = gs_safe_file_name(file_name) else {
+        return GsAssetPreview::default();
+    };
+    let dir = gs_thumbnail_dir(upload_dir);
+    let thumbnail = fs::try_exists(dir.join(format!("{}.png", name)))
+        .await
+        .unwrap_or(false)
+        .then(|| gs_thumbnail_url(&name));
+    let model = fs::read_to_string(dir.join(format!("{}.json", name)))
+        .await
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok());
+    GsAssetPreview { thumbnail, model }
+}
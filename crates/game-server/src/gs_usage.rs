@@ -0,0 +1,30 @@
+//! 使用统计汇总
+//!
+//! 模块: game-server
+//! 前缀: Gs
+//! 文档: 文档/03-game-server.md
+//!
+//! 后台任务定期把对局结束时记录的使用样本汇总为各段位的卡牌/单位选取率与胜率。
+
+use std::time::Duration;
+
+use crate::gs_state::GsAppState;
+
+/// 汇总间隔 (秒)
+const GS_USAGE_AGGREGATE_SECS: u64 = 300;
+
+/// 启动使用统计汇总任务 (启动时先汇总一次，载入已有汇总)
+pub fn gs_spawn_usage_aggregator(state: GsAppState) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(GS_USAGE_AGGREGATE_SECS));
+        
+        loop {
+            interval.tick().await;
+            
+            let processed = state.gs_aggregate_usage().await;
+            if processed > 0 {
+                tracing::info!("汇总使用样本: {} 条", processed);
+            }
+        }
+    });
+}
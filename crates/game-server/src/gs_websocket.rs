@@ -11,11 +11,26 @@ use axum::{
     },
     response::IntoResponse,
 };
-use futures_util::{SinkExt, StreamExt};
+use futures_util::StreamExt;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast::error::RecvError;
 
-use crate::gs_state::GsAppState;
-use game_core::GcMcpCommand;
+use crate::gs_backpressure::{GsOutbound, GsPush, GsResync};
+use crate::gs_heartbeat::GsHeartbeat;
+use crate::gs_lobby::gs_lobby_snapshot;
+use crate::gs_state::{gs_now, GsAppState, GsBroadcastMessage, GS_BROADCAST_ALL, GS_BROADCAST_LOBBY};
+use game_core::{
+    GcAppearance, GcMatchReward, GcBattleState, GcWinProbability, gc_win_probabilities, GcBenchOverflow, GcDeck, GcError, GcPlayerZones, gc_battle_zones,
+    gc_redact_battle_state, GcMcpCommand, GcSeries, GcSideboardSwap,
+    GcMeterSnapshot, GcOrganization, GcRaidResult, GcRunScore, GcTerritoryChange, GcTavernAction, GcTavernGame, GcTavernRoundReport,
+    GcTavernSeat, GcWorldBossHit, GcWorldBossStatus, GcJsonDiagnostic, gc_parse_json, GcContentCheck, GcContentVersion,
+    GcWorldTime, GcRegionWeather, GcPublicAction, GcBattleFormat, GcPlayerMatchStats, gc_match_stats,
+    GcPing, GcPingIntent, GcPingTarget, GcOnboardingEvent, GcOnboardingFlag, GcOnboardingProgress,
+    gc_sanitize_text, GcTextError, GcTextField, GcEventSourcedBattle,
+    GcBattleWindow, GcBattleWindowRequest, GcCard, GcCombatLogEvent, GcPage, GcPageRequest, GcPauseStatus,
+};
 
 /// WebSocket 消息类型
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -25,8 +40,19 @@ pub enum GsWsMessage {
     // 客户端 -> 服务器
     // =========================================================================
     
-    /// 登录
-    Login { player_id: String, name: String },
+    /// 登录 (外观随房间玩家信息下发给其他玩家)
+    Login {
+        player_id: String,
+        name: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        appearance: Option<GcAppearance>,
+        /// 存档槽位 (对局奖励发放到该槽位)
+        #[serde(default)]
+        slot: u32,
+        /// 客户端 WASM 的内容版本 (`gw_content_version`)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        content_version: Option<GcContentVersion>,
+    },
     
     /// 创建房间
     CreateRoom { name: String },
@@ -37,20 +63,119 @@ pub enum GsWsMessage {
     /// 离开房间
     LeaveRoom,
     
+    /// 恢复暂存对局 (参与者回到已暂存或仍开着的对局)
+    ResumeRoom { room_id: String },
+    
+    /// 查询可恢复的暂存对局
+    ListParkedRooms,
+    
+    /// 获取房间列表 (一次性)
+    GetRoomList,
+    
+    /// 订阅大厅 (先收到完整 RoomList，之后推送 LobbyDeltas；重新订阅即重新同步)
+    SubscribeLobby,
+    
+    /// 取消订阅大厅
+    UnsubscribeLobby,
+    
     /// 准备
     Ready,
     
     /// 取消准备
     Unready,
     
+    /// 设置房间赛制 (仅房主，游戏开始前)
+    SetRoomFormat { format: GcBattleFormat },
+    
+    /// 选择队伍 (2v2)
+    SetTeam { team: u32 },
+    
     /// 开始游戏
     StartGame,
     
     /// 出牌
     PlayCard { card_id: String, target_id: Option<String> },
     
+    /// 使用英雄技能
+    UseSkill { skill_id: String, target_id: Option<String> },
+    
+    /// 按模板开始引导 (多回合后结算)
+    StartChannel { template_id: String, target_id: Option<String> },
+    
     /// 结束回合
     EndTurn,
+    
+    /// 查询公开行动记录 (历史面板，回合区间缺省为全部)
+    GetActionFeed {
+        #[serde(default)]
+        from_turn: Option<u32>,
+        #[serde(default)]
+        to_turn: Option<u32>,
+    },
+    
+    /// 查询战斗窗口 (视野内玩家与单位、最近日志，代替完整状态)
+    GetBattleWindow {
+        #[serde(default)]
+        player_ids: Vec<String>,
+        #[serde(default)]
+        log_tail: usize,
+    },
+    
+    /// 分页查询手牌 (owner_id 缺省为自己；他人手牌只返回数量)
+    GetHandPage {
+        #[serde(default)]
+        owner_id: Option<String>,
+        #[serde(default)]
+        page: GcPageRequest,
+    },
+    
+    /// 分页查询战斗日志 (page.offset 缺省为最近一页)
+    GetCombatLog {
+        #[serde(default)]
+        page: GcPageRequest,
+    },
+    
+    /// 发送组队标记 (只转发给队友，限流)
+    SendPing { target: GcPingTarget, intent: GcPingIntent },
+    
+    /// 申请暂停对局 (其他玩家同意后生效)
+    RequestPause,
+    
+    /// 同意其他玩家的暂停申请
+    AcceptPause,
+    
+    /// 拒绝或撤回暂停申请
+    DeclinePause,
+    
+    /// 继续暂停中的对局
+    ResumeBattle,
+    
+    /// 设置卡组 (游戏开始前)
+    SetDeck { deck: GcDeck },
+    
+    /// 提交局间换牌 (可为空表示不换)
+    SubmitSideboard { swaps: Vec<GcSideboardSwap> },
+    
+    /// 开始酒馆对局
+    StartTavern,
+    
+    /// 开始每周挑战 (酒馆模式 + 本周规则集)
+    StartWeeklyChallenge,
+    
+    /// 酒馆操作 (购买/出售/部署/撤回等)
+    TavernAction { action: GcTavernAction },
+    
+    /// 结束购物阶段
+    EndShopping,
+    
+    /// 查询世界 Boss (在场时返回状态，否则返回刷新时间)
+    GetWorldBoss,
+    
+    /// 攻击世界 Boss (伤害由服务器按角色等级计算，每秒最多一次)
+    AttackWorldBoss,
+    
+    /// 世界 Boss 战中治疗队友 (计入伤害统计)
+    HealInWorldBoss { amount: u32 },
 
     // =========================================================================
     // 服务器 -> 客户端
@@ -59,17 +184,34 @@ pub enum GsWsMessage {
     /// 登录成功
     LoginSuccess { player_id: String },
     
+    /// 客户端内容版本过期 (登录成功后发送，客户端应提示刷新页面)
+    ContentVersionWarning { components: Vec<String>, server: GcContentVersion },
+    
     /// 房间创建成功
     RoomCreated { room_id: String },
     
     /// 加入房间成功
     RoomJoined { room_id: String, players: Vec<RoomPlayerInfo> },
     
-    /// 房间列表
+    /// 房间列表 (完整列表，订阅大厅时用于同步)
     RoomList { rooms: Vec<RoomInfo> },
     
+    /// 大厅房间增量 (节流窗口内的变化合并发送)
+    LobbyDeltas { deltas: Vec<GsLobbyDelta> },
+    
+    /// 恢复对局成功 (随后推送 GameStarted)
+    RoomResumed { room_id: String, players: Vec<RoomPlayerInfo> },
+    
+    /// 可恢复的暂存对局列表
+    ParkedRooms { rooms: Vec<ParkedRoomInfo> },
+    
     /// 玩家加入通知
-    PlayerJoined { player_id: String, name: String },
+    PlayerJoined {
+        player_id: String,
+        name: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        appearance: Option<GcAppearance>,
+    },
     
     /// 玩家离开通知
     PlayerLeft { player_id: String },
@@ -77,11 +219,31 @@ pub enum GsWsMessage {
     /// 玩家准备状态变化
     PlayerReady { player_id: String, ready: bool },
     
-    /// 游戏开始
-    GameStarted { battle_state: String },
+    /// 座位托管状态 (断线超过宽限期由机器人接管，重连后交还)
+    SeatControl { player_id: String, bot: bool },
+    
+    /// 房间赛制与队伍 (加入房间时下发，变化时广播)
+    RoomSettings { format: GcBattleFormat, teams: HashMap<String, u32> },
+    
+    /// 游戏开始 (战斗状态按接收者脱敏，牌区数量见 zones)
+    GameStarted {
+        battle_state: String,
+        #[serde(default)]
+        zones: Vec<GcPlayerZones>,
+        /// 胜率估算 (服务器开启 BROADCAST_WIN_PROBABILITY 时附带)
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        win_probability: Vec<GcWinProbability>,
+    },
     
-    /// 游戏状态更新
-    GameState { battle_state: String },
+    /// 游戏状态更新 (战斗状态按接收者脱敏，牌区数量见 zones)
+    GameState {
+        battle_state: String,
+        #[serde(default)]
+        zones: Vec<GcPlayerZones>,
+        /// 胜率估算 (服务器开启 BROADCAST_WIN_PROBABILITY 时附带)
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        win_probability: Vec<GcWinProbability>,
+    },
     
     /// 回合开始
     TurnStart { player_id: String },
@@ -89,14 +251,112 @@ pub enum GsWsMessage {
     /// 卡牌打出
     CardPlayed { player_id: String, card_id: String, result: String },
     
+    /// 英雄技能使用
+    SkillUsed { player_id: String, skill_id: String, result: String },
+    
     /// 回合结束
     TurnEnded { player_id: String },
     
-    /// 游戏结束
-    GameEnded { winner_id: Option<String> },
+    /// 公开行动记录 (行动后增量广播新条目；查询时返回区间内条目)
+    ActionFeed { entries: Vec<GcPublicAction> },
+    
+    /// 战斗窗口 (GetBattleWindow 的响应)
+    BattleWindow { window: GcBattleWindow },
+    
+    /// 手牌的一页 (GetHandPage 的响应)
+    HandPage { owner_id: String, page: GcPage<GcCard> },
+    
+    /// 战斗日志的一页 (GetCombatLog 的响应)
+    CombatLogPage { page: GcPage<GcCombatLogEvent> },
+    
+    /// 队友的标记 (只发给发送者的队友)
+    TeamPing { ping: GcPing },
+    
+    /// 对局暂停状态 (申请、暂停、继续时广播)
+    PauseStatus { status: GcPauseStatus },
+    
+    /// 游戏结束 (winner_ids 为获胜方全部玩家，组队时包含队友；stats 为对局统计)
+    GameEnded {
+        winner_id: Option<String>,
+        #[serde(default)]
+        winner_ids: Vec<String>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        stats: Vec<GcPlayerMatchStats>,
+    },
+    
+    /// 对局奖励结算摘要 (酒馆对局结束或卡牌对战每局结束)
+    MatchRewards { rewards: Vec<GcMatchReward> },
+    
+    /// 新手引导有新完成的步骤 (只发给该玩家)
+    OnboardingUpdated { completed: Vec<GcOnboardingFlag>, progress: GcOnboardingProgress },
+    
+    /// 卡组设置成功
+    DeckSet { deck_id: String },
+    
+    /// 系列赛状态更新 (局间换牌阶段、换牌提交、系列赛结束)
+    SeriesUpdate { series: GcSeries },
+    
+    /// 酒馆对局开始
+    TavernStarted { round: u32 },
+    
+    /// 玩家座位更新 (仅发给本人；手牌区超出容量时附带强制出售提示)
+    TavernSeat { seat: Box<GcTavernSeat>, overflow: Option<GcBenchOverflow> },
+    
+    /// 玩家已结束购物阶段
+    ShoppingEnded { player_id: String },
+    
+    /// 回合结算 (对战配对、伤害、淘汰名次、胜者)
+    TavernRoundResult { report: GcTavernRoundReport },
+    
+    /// 酒馆对局结算摘要 (对局结束时广播，按名次排序)
+    TavernRunSummary { scores: Vec<GcRunScore> },
+    
+    /// 世界 Boss 下次刷新时间 (全服广播)
+    WorldBossScheduled { next_spawn_at: u64 },
+    
+    /// 世界 Boss 刷新 (全服广播)
+    WorldBossSpawned { status: GcWorldBossStatus },
+    
+    /// 世界 Boss 血量与排行更新 (全服广播，定期)
+    WorldBossUpdate { status: GcWorldBossStatus },
+    
+    /// 世界 Boss 进入新阶段 (全服广播)
+    WorldBossPhaseChanged { phase: usize, phase_name: String },
+    
+    /// 本人攻击结果
+    WorldBossHit { hit: GcWorldBossHit },
     
-    /// 错误
-    Error { code: String, message: String },
+    /// 伤害统计快照 (发给参与者，定期)
+    DamageMeter { snapshot: GcMeterSnapshot },
+    
+    /// 世界 Boss 被击杀，含奖励与最终伤害统计 (全服广播)
+    WorldBossDefeated { result: GcRaidResult },
+    
+    /// 世界 Boss 超时离开，含最终伤害统计 (全服广播)
+    WorldBossEscaped { result: GcRaidResult },
+    
+    /// 领地发起争夺 (全服广播)
+    TerritoryContestStarted { zone_id: String, challenger: GcOrganization, ends_at: u64 },
+    
+    /// 领地易主 (全服广播)
+    TerritoryChanged { changes: Vec<GcTerritoryChange> },
+    
+    /// 世界时间 (登录后发送，昼夜时段切换时全服广播)
+    WorldTime { time: GcWorldTime },
+    
+    /// 区域天气 (登录后发送全部区域，天气变化时全服广播变化的区域)
+    Weather { regions: Vec<GcRegionWeather> },
+    
+    /// 连接丢失了消息 (广播接收落后或出站队列已满)；随后推送完整状态快照，客户端应整体刷新
+    Resync { dropped: u64 },
+    
+    /// 错误 (消息解析失败时附带字段路径诊断)
+    Error {
+        code: String,
+        message: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        diagnostic: Option<GcJsonDiagnostic>,
+    },
     
     /// 心跳
     Ping,
@@ -107,7 +367,7 @@ pub enum GsWsMessage {
 }
 
 /// 房间信息 (用于列表)
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct RoomInfo {
     pub id: String,
     pub name: String,
@@ -116,6 +376,32 @@ pub struct RoomInfo {
     pub game_started: bool,
 }
 
+/// 大厅房间增量
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "kind")]
+pub enum GsLobbyDelta {
+    /// 新房间
+    #[serde(rename = "RoomCreated")]
+    Created { room: RoomInfo },
+    /// 房间人数或状态变化
+    #[serde(rename = "RoomUpdated")]
+    Updated { room: RoomInfo },
+    /// 房间关闭
+    #[serde(rename = "RoomClosed")]
+    Closed { room_id: String },
+}
+
+/// 暂存对局信息 (用于"继续对局"列表)
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ParkedRoomInfo {
+    pub room_id: String,
+    pub name: String,
+    pub player_ids: Vec<String>,
+    pub turn: u32,
+    pub saved_at: u64,
+    pub expires_at: u64,
+}
+
 /// 房间内玩家信息
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct RoomPlayerInfo {
@@ -123,6 +409,8 @@ pub struct RoomPlayerInfo {
     pub name: String,
     pub ready: bool,
     pub is_owner: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub appearance: Option<GcAppearance>,
 }
 
 /// WebSocket 升级处理
@@ -133,12 +421,24 @@ pub async fn gs_websocket_handler(
     ws.on_upgrade(|socket| gs_handle_socket(socket, state))
 }
 
+/// 重同步检查间隔 (毫秒): 待重同步的连接在没有新消息时也会按此间隔重试
+const GS_RESYNC_RETRY_MS: u64 = 500;
+
 /// 处理单个 WebSocket 连接
 async fn gs_handle_socket(socket: WebSocket, state: GsAppState) {
-    let (mut sender, mut receiver) = socket.split();
+    let (sender, mut receiver) = socket.split();
+    
+    // 出站消息经有界队列由写任务发送，慢客户端不会阻塞本循环
+    let (mut outbound, writer) = GsOutbound::gs_spawn(
+        sender,
+        state.config.ws_outbound_queue_cap,
+        state.config.ws_max_resyncs,
+        state.chaos.clone(),
+    );
     
     let mut player_id: Option<String> = None;
     let mut current_room_id: Option<String> = None;
+    let mut lobby_subscribed = false;
     
     // 订阅广播
     let mut broadcast_rx = state.gs_subscribe();
@@ -150,12 +450,23 @@ async fn gs_handle_socket(socket: WebSocket, state: GsAppState) {
         None
     };
     
+    let mut resync_retry = tokio::time::interval(Duration::from_millis(GS_RESYNC_RETRY_MS));
+    
+    // 应用层心跳: 超时未收到任何消息的连接主动关闭
+    let mut heartbeat = GsHeartbeat::gs_new(state.config.heartbeat_timeout_secs, Instant::now());
+    let mut heartbeat_check = tokio::time::interval(Duration::from_secs(state.config.heartbeat_check_secs));
+    
     tracing::info!("新 WebSocket 连接");
     
     loop {
+        let mut queued = Vec::new();
+        
         tokio::select! {
             // 处理客户端消息
             result = receiver.next() => {
+                if let Some(Ok(_)) = &result {
+                    heartbeat.gs_beat(Instant::now());
+                }
                 let msg = match result {
                     Some(Ok(Message::Text(text))) => text,
                     Some(Ok(Message::Close(_))) | None => {
@@ -169,47 +480,49 @@ async fn gs_handle_socket(socket: WebSocket, state: GsAppState) {
                     _ => continue,
                 };
                 
+                if state.chaos.as_ref().is_some_and(|chaos| chaos.gs_drop_inbound()) {
+                    tracing::debug!("故障注入: 丢弃客户端消息 (玩家 {:?})", player_id);
+                    continue;
+                }
+                
+                if let Some(pid) = &player_id {
+                    state.gs_touch_player(pid).await;
+                }
+                
                 // 解析消息
-                let ws_msg: GsWsMessage = match serde_json::from_str(&msg) {
-                    Ok(m) => m,
+                match gc_parse_json::<GsWsMessage>("message", &msg) {
+                    Ok(ws_msg) => {
+                        // 处理消息
+                        let responses = gs_handle_message(&state, &mut player_id, &mut current_room_id, &mut lobby_subscribed, ws_msg).await;
+                        queued.extend(responses.iter().filter_map(|resp| serde_json::to_string(resp).ok()));
+                    }
                     Err(e) => {
                         let error = GsWsMessage::Error {
                             code: "PARSE_ERROR".to_string(),
-                            message: format!("消息解析失败: {}", e),
+                            message: e.to_string(),
+                            diagnostic: Some(e),
                         };
-                        let _ = sender.send(Message::Text(serde_json::to_string(&error).unwrap().into())).await;
-                        continue;
-                    }
-                };
-                
-                // 处理消息
-                let responses = gs_handle_message(&state, &mut player_id, &mut current_room_id, ws_msg).await;
-                
-                // 发送响应
-                for resp in responses {
-                    let text = serde_json::to_string(&resp).unwrap();
-                    if sender.send(Message::Text(text.into())).await.is_err() {
-                        break;
+                        queued.extend(serde_json::to_string(&error).ok());
                     }
                 }
             }
             
-            // 处理广播消息
+            // 处理广播消息 (接收落后时记录丢失数量，随后重同步)
             broadcast_result = broadcast_rx.recv() => {
-                if let Ok(broadcast) = broadcast_result {
-                    // 检查是否在同一房间
-                    if current_room_id.as_ref() == Some(&broadcast.room_id) {
-                        // 检查是否被排除
-                        let excluded = player_id.as_ref()
-                            .map(|pid| broadcast.exclude_ids.contains(pid))
-                            .unwrap_or(false);
-                        
-                        if !excluded {
-                            if sender.send(Message::Text(broadcast.message.into())).await.is_err() {
-                                break;
-                            }
+                match broadcast_result {
+                    Ok(broadcast) => {
+                        if gs_should_deliver(&broadcast, player_id.as_deref(), current_room_id.as_deref(), lobby_subscribed) {
+                            queued.push(broadcast.message);
                         }
                     }
+                    Err(RecvError::Lagged(count)) => {
+                        tracing::warn!("连接广播接收落后，丢失 {} 条消息 (玩家 {:?})", count, player_id);
+                        outbound.gs_mark_lagged(count);
+                        let mut stats = state.broadcast_stats.write().await;
+                        stats.lag_events += 1;
+                        stats.lagged_messages += count;
+                    }
+                    Err(RecvError::Closed) => break,
                 }
             }
             
@@ -224,15 +537,66 @@ async fn gs_handle_socket(socket: WebSocket, state: GsAppState) {
                 if let Ok(cmd) = mcp_result {
                     // 转发给客户端
                     let msg = GsWsMessage::McpCommand { command: cmd };
-                    let text = serde_json::to_string(&msg).unwrap();
-                    if sender.send(Message::Text(text.into())).await.is_err() {
-                        break;
-                    }
+                    queued.extend(serde_json::to_string(&msg).ok());
+                }
+            }
+            
+            // 待重同步时定期重试
+            _ = resync_retry.tick(), if outbound.gs_needs_resync() => {}
+            
+            // 心跳超时: 连接已无声断开，按断线清理
+            _ = heartbeat_check.tick() => {
+                let now = Instant::now();
+                if heartbeat.gs_is_expired(now) {
+                    tracing::warn!(
+                        "心跳超时 ({} 秒未收到消息)，关闭连接 (玩家 {:?})",
+                        heartbeat.gs_silence(now).as_secs(),
+                        player_id,
+                    );
+                    state.janitor_stats.write().await.heartbeat_timeouts += 1;
+                    break;
+                }
+            }
+        }
+        
+        let mut overflows = 0;
+        let mut closed = false;
+        for text in queued {
+            match outbound.gs_push(text) {
+                GsPush::Queued => {}
+                GsPush::Dropped => overflows += 1,
+                GsPush::Faulted => {}
+                GsPush::Closed => closed = true,
+            }
+        }
+        if overflows > 0 {
+            state.broadcast_stats.write().await.queue_overflows += overflows;
+        }
+        if closed || outbound.gs_is_closed() {
+            break;
+        }
+        
+        match outbound.gs_resync_state() {
+            GsResync::Idle | GsResync::Deferred => {}
+            GsResync::Due { dropped } => {
+                let snapshot = gs_resync_snapshot(&state, player_id.as_deref(), current_room_id.as_deref(), lobby_subscribed, dropped).await;
+                let texts = snapshot.iter().filter_map(|msg| serde_json::to_string(msg).ok()).collect();
+                if outbound.gs_push_resync(texts) {
+                    tracing::info!("连接已重同步，期间丢失 {} 条消息 (玩家 {:?})", dropped, player_id);
+                    state.broadcast_stats.write().await.resyncs += 1;
                 }
             }
+            GsResync::Disconnect => {
+                tracing::warn!("慢消费者，断开连接 (玩家 {:?})", player_id);
+                state.broadcast_stats.write().await.slow_consumer_disconnects += 1;
+                break;
+            }
         }
     }
     
+    // 写任务可能卡在慢客户端上，直接结束
+    writer.abort();
+    
     // 断开连接时清理
     if let Some(pid) = player_id {
         state.gs_player_disconnect(&pid).await;
@@ -240,24 +604,92 @@ async fn gs_handle_socket(socket: WebSocket, state: GsAppState) {
     }
 }
 
+/// 广播是否发给该连接: 同一房间、全服广播或已订阅的大厅推送，且未被排除
+pub(crate) fn gs_should_deliver(
+    broadcast: &GsBroadcastMessage,
+    player_id: Option<&str>,
+    current_room_id: Option<&str>,
+    lobby_subscribed: bool,
+) -> bool {
+    let lobby = lobby_subscribed && broadcast.room_id == GS_BROADCAST_LOBBY;
+    let addressed = lobby || broadcast.room_id == GS_BROADCAST_ALL || current_room_id == Some(broadcast.room_id.as_str());
+    let excluded = player_id.is_some_and(|pid| broadcast.exclude_ids.iter().any(|id| id == pid));
+    addressed && !excluded
+}
+
 /// 处理单个消息
-async fn gs_handle_message(
+pub(crate) async fn gs_handle_message(
     state: &GsAppState,
     player_id: &mut Option<String>,
     current_room_id: &mut Option<String>,
+    lobby_subscribed: &mut bool,
     msg: GsWsMessage,
 ) -> Vec<GsWsMessage> {
     match msg {
         GsWsMessage::Ping => vec![GsWsMessage::Pong],
         
+        // =================================================================
+        // 大厅房间列表
+        // =================================================================
+        GsWsMessage::GetRoomList => {
+            let rooms = gs_lobby_snapshot(state).await.into_values().collect();
+            vec![GsWsMessage::RoomList { rooms }]
+        }
+        
+        GsWsMessage::SubscribeLobby => {
+            *lobby_subscribed = true;
+            let rooms = gs_lobby_snapshot(state).await.into_values().collect();
+            vec![GsWsMessage::RoomList { rooms }]
+        }
+        
+        GsWsMessage::UnsubscribeLobby => {
+            *lobby_subscribed = false;
+            vec![]
+        }
+        
         // =================================================================
         // 登录
         // =================================================================
-        GsWsMessage::Login { player_id: pid, name } => {
-            state.gs_player_connect(pid.clone(), name).await;
+        GsWsMessage::Login { player_id: pid, name, appearance, slot, content_version } => {
+            let server_version = state.content_version.as_ref();
+            let check = content_version.as_ref().map(|v| server_version.gc_check(v));
+            let stale = match check {
+                Some(GcContentCheck::Incompatible { client_schema, server_schema }) => {
+                    return vec![GsWsMessage::Error {
+                        code: "CONTENT_VERSION_MISMATCH".to_string(),
+                        message: format!("客户端格式版本 {} 与服务器 {} 不兼容，请刷新页面", client_schema, server_schema),
+                        diagnostic: None,
+                    }];
+                }
+                Some(GcContentCheck::Stale { components }) => Some(components),
+                Some(GcContentCheck::Match) => None,
+                None => Some(Vec::new()),
+            };
+            if let Some(components) = &stale {
+                tracing::warn!("玩家 {} 的内容版本不一致: {:?} (未上报为空)", pid, components);
+                if state.config.strict_content_version {
+                    return vec![GsWsMessage::Error {
+                        code: "CONTENT_VERSION_MISMATCH".to_string(),
+                        message: "客户端内容已过期，请刷新页面".to_string(),
+                        diagnostic: None,
+                    }];
+                }
+            }
+            
+            let name = match gc_sanitize_text(&name, GcTextField::PlayerName, state.name_filter.as_ref()) {
+                Ok(name) => name,
+                Err(e) => return vec![gs_text_error("Login", ".name", &e)],
+            };
+            state.gs_player_connect(pid.clone(), name, appearance, slot).await;
             *player_id = Some(pid.clone());
             tracing::info!("玩家登录: {}", pid);
-            vec![GsWsMessage::LoginSuccess { player_id: pid }]
+            let mut responses = vec![GsWsMessage::LoginSuccess { player_id: pid }];
+            if let Some(components) = stale.filter(|c| !c.is_empty()) {
+                responses.push(GsWsMessage::ContentVersionWarning { components, server: server_version.clone() });
+            }
+            responses.push(GsWsMessage::WorldTime { time: state.world_clock.gc_time_at(gs_now()) });
+            responses.push(GsWsMessage::Weather { regions: state.gs_weather_now() });
+            responses
         }
         
         // =================================================================
@@ -269,9 +701,14 @@ async fn gs_handle_message(
                 None => return vec![GsWsMessage::Error {
                     code: "NOT_LOGGED_IN".to_string(),
                     message: "请先登录".to_string(),
+                    diagnostic: None,
                 }],
             };
             
+            let name = match gc_sanitize_text(&name, GcTextField::RoomName, state.name_filter.as_ref()) {
+                Ok(name) => name,
+                Err(e) => return vec![gs_text_error("CreateRoom", ".name", &e)],
+            };
             let room_id = state.gs_create_room(name, pid).await;
             *current_room_id = Some(room_id.clone());
             
@@ -287,6 +724,7 @@ async fn gs_handle_message(
                 None => return vec![GsWsMessage::Error {
                     code: "NOT_LOGGED_IN".to_string(),
                     message: "请先登录".to_string(),
+                    diagnostic: None,
                 }],
             };
             
@@ -295,39 +733,52 @@ async fn gs_handle_message(
                     *current_room_id = Some(room_id.clone());
                     
                     // 获取房间玩家信息
-                    let players = if let Some(room) = state.gs_get_room(&room_id).await {
-                        room.players.iter().map(|p| RoomPlayerInfo {
+                    let (players, settings) = if let Some(room) = state.gs_get_room(&room_id).await {
+                        let players = room.players.iter().map(|p| RoomPlayerInfo {
                             id: p.id.clone(),
                             name: p.name.clone(),
                             ready: p.ready,
                             is_owner: p.id == room.owner_id,
-                        }).collect()
+                            appearance: p.appearance,
+                        }).collect();
+                        (players, GsWsMessage::RoomSettings { format: room.format, teams: room.teams })
                     } else {
-                        vec![]
+                        (vec![], GsWsMessage::RoomSettings { format: GcBattleFormat::Duel, teams: HashMap::new() })
                     };
                     
-                    // 获取玩家名称
-                    let player_name = state.players.read().await
+                    // 获取玩家名称与外观
+                    let (player_name, appearance) = state.players.read().await
                         .get(&pid)
-                        .map(|p| p.name.clone())
-                        .unwrap_or_else(|| pid.clone());
+                        .map(|p| (p.name.clone(), p.appearance))
+                        .unwrap_or_else(|| (pid.clone(), None));
                     
                     // 广播给房间其他玩家
                     let broadcast_msg = GsWsMessage::PlayerJoined {
                         player_id: pid.clone(),
                         name: player_name,
+                        appearance,
                     };
                     state.gs_broadcast_to_room(
                         &room_id,
                         serde_json::to_string(&broadcast_msg).unwrap(),
-                        vec![pid],
+                        vec![pid.clone()],
                     );
                     
-                    vec![GsWsMessage::RoomJoined { room_id, players }]
+                    // 组队房间加入时自动分队，同步给其他玩家
+                    if matches!(&settings, GsWsMessage::RoomSettings { teams, .. } if !teams.is_empty()) {
+                        state.gs_broadcast_to_room(
+                            &room_id,
+                            serde_json::to_string(&settings).unwrap_or_default(),
+                            vec![pid],
+                        );
+                    }
+                    
+                    vec![GsWsMessage::RoomJoined { room_id, players }, settings]
                 }
                 Err(e) => vec![GsWsMessage::Error {
                     code: "JOIN_FAILED".to_string(),
                     message: e,
+                    diagnostic: None,
                 }],
             }
         }
@@ -358,6 +809,98 @@ async fn gs_handle_message(
             vec![]
         }
         
+        // =================================================================
+        // 恢复暂存对局
+        // =================================================================
+        GsWsMessage::ResumeRoom { room_id } => {
+            let pid = match player_id {
+                Some(id) => id.clone(),
+                None => return vec![GsWsMessage::Error {
+                    code: "NOT_LOGGED_IN".to_string(),
+                    message: "请先登录".to_string(),
+                    diagnostic: None,
+                }],
+            };
+            
+            match state.gs_resume_room(&room_id, &pid).await {
+                Ok(battle) => {
+                    *current_room_id = Some(room_id.clone());
+                    
+                    let players = if let Some(room) = state.gs_get_room(&room_id).await {
+                        room.players.iter().map(|p| RoomPlayerInfo {
+                            id: p.id.clone(),
+                            name: p.name.clone(),
+                            ready: p.ready,
+                            is_owner: p.id == room.owner_id,
+                            appearance: p.appearance,
+                        }).collect()
+                    } else {
+                        vec![]
+                    };
+                    
+                    // 通知已在房间的玩家
+                    if let Some(me) = players.iter().find(|p| p.id == pid) {
+                        let broadcast_msg = GsWsMessage::PlayerJoined {
+                            player_id: pid.clone(),
+                            name: me.name.clone(),
+                            appearance: me.appearance,
+                        };
+                        state.gs_broadcast_to_room(
+                            &room_id,
+                            serde_json::to_string(&broadcast_msg).unwrap_or_default(),
+                            vec![pid.clone()],
+                        );
+                    }
+                    
+                    // 重连收回机器人托管的座位
+                    if state.gs_reclaim_seat(&room_id, &pid).await {
+                        let control_msg = GsWsMessage::SeatControl { player_id: pid.clone(), bot: false };
+                        state.gs_broadcast_to_room(
+                            &room_id,
+                            serde_json::to_string(&control_msg).unwrap_or_default(),
+                            vec![],
+                        );
+                    }
+                    
+                    // 按玩家脱敏后推送 (本连接已进入房间，可收到广播)
+                    gs_send_battle_state(state, &room_id, &battle, true);
+                    
+                    vec![GsWsMessage::RoomResumed { room_id, players }]
+                }
+                Err(e) => vec![GsWsMessage::Error {
+                    code: "RESUME_FAILED".to_string(),
+                    message: e,
+                    diagnostic: None,
+                }],
+            }
+        }
+        
+        GsWsMessage::ListParkedRooms => {
+            let pid = match player_id {
+                Some(id) => id.clone(),
+                None => return vec![GsWsMessage::Error {
+                    code: "NOT_LOGGED_IN".to_string(),
+                    message: "请先登录".to_string(),
+                    diagnostic: None,
+                }],
+            };
+            
+            let policy = state.config.park_policy;
+            let rooms = state.gs_list_parked_battles(&pid).await
+                .into_iter()
+                .map(|p| ParkedRoomInfo {
+                    expires_at: policy.gc_expires_at(&p),
+                    player_ids: p.gc_player_ids(),
+                    turn: p.battle.turn,
+                    saved_at: p.saved_at,
+                    room_id: p.room_id,
+                    name: p.room_name,
+                })
+                .collect();
+            
+            vec![GsWsMessage::ParkedRooms { rooms }]
+        }
+        
         // =================================================================
         // 准备
         // =================================================================
@@ -367,6 +910,7 @@ async fn gs_handle_message(
                 None => return vec![GsWsMessage::Error {
                     code: "NOT_LOGGED_IN".to_string(),
                     message: "请先登录".to_string(),
+                    diagnostic: None,
                 }],
             };
             
@@ -375,6 +919,7 @@ async fn gs_handle_message(
                 None => return vec![GsWsMessage::Error {
                     code: "NOT_IN_ROOM".to_string(),
                     message: "请先加入房间".to_string(),
+                    diagnostic: None,
                 }],
             };
             
@@ -395,6 +940,7 @@ async fn gs_handle_message(
                 Err(e) => vec![GsWsMessage::Error {
                     code: "READY_FAILED".to_string(),
                     message: e,
+                    diagnostic: None,
                 }],
             }
         }
@@ -428,6 +974,58 @@ async fn gs_handle_message(
             vec![]
         }
         
+        // =================================================================
+        // 设置赛制
+        // =================================================================
+        GsWsMessage::SetRoomFormat { format } => {
+            let pid = match player_id {
+                Some(id) => id.clone(),
+                None => return vec![GsWsMessage::Error {
+                    code: "NOT_LOGGED_IN".to_string(),
+                    message: "请先登录".to_string(),
+                    diagnostic: None,
+                }],
+            };
+            
+            let room_id = match current_room_id {
+                Some(id) => id.clone(),
+                None => return vec![GsWsMessage::Error {
+                    code: "NOT_IN_ROOM".to_string(),
+                    message: "请先加入房间".to_string(),
+                    diagnostic: None,
+                }],
+            };
+            
+            let result = state.gs_set_room_format(&room_id, &pid, format).await;
+            gs_broadcast_room_settings(state, &room_id, result)
+        }
+        
+        // =================================================================
+        // 选择队伍
+        // =================================================================
+        GsWsMessage::SetTeam { team } => {
+            let pid = match player_id {
+                Some(id) => id.clone(),
+                None => return vec![GsWsMessage::Error {
+                    code: "NOT_LOGGED_IN".to_string(),
+                    message: "请先登录".to_string(),
+                    diagnostic: None,
+                }],
+            };
+            
+            let room_id = match current_room_id {
+                Some(id) => id.clone(),
+                None => return vec![GsWsMessage::Error {
+                    code: "NOT_IN_ROOM".to_string(),
+                    message: "请先加入房间".to_string(),
+                    diagnostic: None,
+                }],
+            };
+            
+            let result = state.gs_set_team(&room_id, &pid, team).await;
+            gs_broadcast_room_settings(state, &room_id, result)
+        }
+        
         // =================================================================
         // 开始游戏
         // =================================================================
@@ -437,6 +1035,7 @@ async fn gs_handle_message(
                 None => return vec![GsWsMessage::Error {
                     code: "NOT_LOGGED_IN".to_string(),
                     message: "请先登录".to_string(),
+                    diagnostic: None,
                 }],
             };
             
@@ -445,28 +1044,21 @@ async fn gs_handle_message(
                 None => return vec![GsWsMessage::Error {
                     code: "NOT_IN_ROOM".to_string(),
                     message: "请先加入房间".to_string(),
+                    diagnostic: None,
                 }],
             };
             
             match state.gs_start_game(&room_id, &pid).await {
                 Ok(battle) => {
-                    let battle_json = serde_json::to_string(&battle).unwrap();
-                    
-                    // 广播给房间所有玩家
-                    let broadcast_msg = GsWsMessage::GameStarted {
-                        battle_state: battle_json,
-                    };
-                    state.gs_broadcast_to_room(
-                        &room_id,
-                        serde_json::to_string(&broadcast_msg).unwrap(),
-                        vec![],
-                    );
+                    // 按玩家脱敏后推送
+                    gs_send_battle_state(state, &room_id, &battle, true);
                     
                     vec![]
                 }
                 Err(e) => vec![GsWsMessage::Error {
                     code: "START_FAILED".to_string(),
                     message: e,
+                    diagnostic: None,
                 }],
             }
         }
@@ -480,6 +1072,7 @@ async fn gs_handle_message(
                 None => return vec![GsWsMessage::Error {
                     code: "NOT_LOGGED_IN".to_string(),
                     message: "请先登录".to_string(),
+                    diagnostic: None,
                 }],
             };
             
@@ -488,13 +1081,13 @@ async fn gs_handle_message(
                 None => return vec![GsWsMessage::Error {
                     code: "NOT_IN_ROOM".to_string(),
                     message: "请先加入房间".to_string(),
+                    diagnostic: None,
                 }],
             };
             
             match state.gs_play_card(&room_id, &pid, &card_id, target_id.as_deref()).await {
                 Ok((result, battle)) => {
                     let result_json = serde_json::to_string(&result).unwrap();
-                    let battle_json = serde_json::to_string(&battle).unwrap();
                     
                     // 广播卡牌打出
                     let card_msg = GsWsMessage::CardPlayed {
@@ -507,27 +1100,15 @@ async fn gs_handle_message(
                         serde_json::to_string(&card_msg).unwrap(),
                         vec![],
                     );
+                    gs_push_onboarding(state, &pid, &[GcOnboardingEvent::CardPlayed]).await;
                     
-                    // 广播状态更新
-                    let state_msg = GsWsMessage::GameState {
-                        battle_state: battle_json,
-                    };
-                    state.gs_broadcast_to_room(
-                        &room_id,
-                        serde_json::to_string(&state_msg).unwrap(),
-                        vec![],
-                    );
+                    // 广播状态更新 (按玩家脱敏)
+                    gs_send_battle_state(state, &room_id, &battle, false);
+                    gs_broadcast_action_feed(state, &room_id).await;
                     
                     // 检查游戏是否结束
                     if battle.gc_is_finished() {
-                        let end_msg = GsWsMessage::GameEnded {
-                            winner_id: battle.winner_id,
-                        };
-                        state.gs_broadcast_to_room(
-                            &room_id,
-                            serde_json::to_string(&end_msg).unwrap(),
-                            vec![],
-                        );
+                        gs_broadcast_game_end(state, &room_id, &battle).await;
                     }
                     
                     vec![]
@@ -535,6 +1116,104 @@ async fn gs_handle_message(
                 Err(e) => vec![GsWsMessage::Error {
                     code: "PLAY_CARD_FAILED".to_string(),
                     message: e,
+                    diagnostic: None,
+                }],
+            }
+        }
+        
+        // =================================================================
+        // 使用英雄技能
+        // =================================================================
+        GsWsMessage::UseSkill { skill_id, target_id } => {
+            let pid = match player_id {
+                Some(id) => id.clone(),
+                None => return vec![GsWsMessage::Error {
+                    code: "NOT_LOGGED_IN".to_string(),
+                    message: "请先登录".to_string(),
+                    diagnostic: None,
+                }],
+            };
+            
+            let room_id = match current_room_id {
+                Some(id) => id.clone(),
+                None => return vec![GsWsMessage::Error {
+                    code: "NOT_IN_ROOM".to_string(),
+                    message: "请先加入房间".to_string(),
+                    diagnostic: None,
+                }],
+            };
+            
+            match state.gs_use_skill(&room_id, &pid, &skill_id, target_id.as_deref()).await {
+                Ok((result, battle)) if result.success => {
+                    // 广播技能使用
+                    let skill_msg = GsWsMessage::SkillUsed {
+                        player_id: pid.clone(),
+                        skill_id,
+                        result: serde_json::to_string(&result).unwrap_or_default(),
+                    };
+                    state.gs_broadcast_to_room(
+                        &room_id,
+                        serde_json::to_string(&skill_msg).unwrap_or_default(),
+                        vec![],
+                    );
+                    gs_push_onboarding(state, &pid, &[GcOnboardingEvent::SkillUsed]).await;
+                    
+                    // 广播状态更新 (按玩家脱敏)
+                    gs_send_battle_state(state, &room_id, &battle, false);
+                    gs_broadcast_action_feed(state, &room_id).await;
+                    
+                    // 检查游戏是否结束
+                    if battle.gc_is_finished() {
+                        gs_broadcast_game_end(state, &room_id, &battle).await;
+                    }
+                    
+                    vec![]
+                }
+                Ok((result, _)) => vec![GsWsMessage::Error {
+                    code: "USE_SKILL_FAILED".to_string(),
+                    message: result.error.unwrap_or_default(),
+                    diagnostic: None,
+                }],
+                Err(e) => vec![GsWsMessage::Error {
+                    code: "USE_SKILL_FAILED".to_string(),
+                    message: e,
+                    diagnostic: None,
+                }],
+            }
+        }
+        
+        // =================================================================
+        // 开始引导
+        // =================================================================
+        GsWsMessage::StartChannel { template_id, target_id } => {
+            let pid = match player_id {
+                Some(id) => id.clone(),
+                None => return vec![GsWsMessage::Error {
+                    code: "NOT_LOGGED_IN".to_string(),
+                    message: "请先登录".to_string(),
+                    diagnostic: None,
+                }],
+            };
+            
+            let room_id = match current_room_id {
+                Some(id) => id.clone(),
+                None => return vec![GsWsMessage::Error {
+                    code: "NOT_IN_ROOM".to_string(),
+                    message: "请先加入房间".to_string(),
+                    diagnostic: None,
+                }],
+            };
+            
+            match state.gs_start_channel(&room_id, &pid, &template_id, target_id.as_deref()).await {
+                Ok(battle) => {
+                    gs_send_battle_state(state, &room_id, &battle, false);
+                    gs_broadcast_action_feed(state, &room_id).await;
+                    vec![]
+                }
+                Err(e) => vec![GsWsMessage::Error {
+                    code: "START_CHANNEL_FAILED".to_string(),
+                    message: e,
+                    diagnostic: None,
                 }],
             }
         }
@@ -548,6 +1227,7 @@ async fn gs_handle_message(
                 None => return vec![GsWsMessage::Error {
                     code: "NOT_LOGGED_IN".to_string(),
                     message: "请先登录".to_string(),
+                    diagnostic: None,
                 }],
             };
             
@@ -556,6 +1236,7 @@ async fn gs_handle_message(
                 None => return vec![GsWsMessage::Error {
                     code: "NOT_IN_ROOM".to_string(),
                     message: "请先加入房间".to_string(),
+                    diagnostic: None,
                 }],
             };
             
@@ -571,16 +1252,9 @@ async fn gs_handle_message(
                         vec![],
                     );
                     
-                    // 广播状态更新
-                    let battle_json = serde_json::to_string(&battle).unwrap();
-                    let state_msg = GsWsMessage::GameState {
-                        battle_state: battle_json,
-                    };
-                    state.gs_broadcast_to_room(
-                        &room_id,
-                        serde_json::to_string(&state_msg).unwrap(),
-                        vec![],
-                    );
+                    // 广播状态更新 (按玩家脱敏)
+                    gs_send_battle_state(state, &room_id, &battle, false);
+                    gs_broadcast_action_feed(state, &room_id).await;
                     
                     // 广播新回合开始
                     if let Some(next_player) = battle.gc_current_player_id() {
@@ -599,6 +1273,486 @@ async fn gs_handle_message(
                 Err(e) => vec![GsWsMessage::Error {
                     code: "END_TURN_FAILED".to_string(),
                     message: e,
+                    diagnostic: None,
+                }],
+            }
+        }
+        
+        // =================================================================
+        // 查询公开行动记录
+        // =================================================================
+        GsWsMessage::GetActionFeed { from_turn, to_turn } => {
+            let room_id = match current_room_id {
+                Some(id) => id.clone(),
+                None => return vec![GsWsMessage::Error {
+                    code: "NOT_IN_ROOM".to_string(),
+                    message: "请先加入房间".to_string(),
+                    diagnostic: None,
+                }],
+            };
+            
+            match state.gs_action_feed_range(&room_id, from_turn, to_turn).await {
+                Ok(entries) => vec![GsWsMessage::ActionFeed { entries }],
+                Err(e) => vec![GsWsMessage::Error {
+                    code: "GAME_NOT_STARTED".to_string(),
+                    message: e,
+                    diagnostic: None,
+                }],
+            }
+        }
+        
+        // =================================================================
+        // 分页与窗口查询
+        // =================================================================
+        GsWsMessage::GetBattleWindow { player_ids, log_tail } => {
+            let room_id = match current_room_id {
+                Some(id) => id.clone(),
+                None => return vec![GsWsMessage::Error {
+                    code: "NOT_IN_ROOM".to_string(),
+                    message: "请先加入房间".to_string(),
+                    diagnostic: None,
+                }],
+            };
+            
+            let request = GcBattleWindowRequest { player_ids, log_tail };
+            match state.gs_battle_window(&room_id, &request).await {
+                Ok(window) => vec![GsWsMessage::BattleWindow { window }],
+                Err(e) => vec![GsWsMessage::Error {
+                    code: "GAME_NOT_STARTED".to_string(),
+                    message: e,
+                    diagnostic: None,
+                }],
+            }
+        }
+        
+        GsWsMessage::GetHandPage { owner_id, page } => {
+            let pid = match player_id {
+                Some(id) => id.clone(),
+                None => return vec![GsWsMessage::Error {
+                    code: "NOT_LOGGED_IN".to_string(),
+                    message: "请先登录".to_string(),
+                    diagnostic: None,
+                }],
+            };
+            
+            let room_id = match current_room_id {
+                Some(id) => id.clone(),
+                None => return vec![GsWsMessage::Error {
+                    code: "NOT_IN_ROOM".to_string(),
+                    message: "请先加入房间".to_string(),
+                    diagnostic: None,
+                }],
+            };
+            
+            let owner_id = owner_id.unwrap_or_else(|| pid.clone());
+            match state.gs_hand_page(&room_id, &pid, &owner_id, page).await {
+                Ok(page) => vec![GsWsMessage::HandPage { owner_id, page }],
+                Err(e) => vec![GsWsMessage::Error {
+                    code: "HAND_PAGE_FAILED".to_string(),
+                    message: e,
+                    diagnostic: None,
+                }],
+            }
+        }
+        
+        GsWsMessage::GetCombatLog { page } => {
+            let room_id = match current_room_id {
+                Some(id) => id.clone(),
+                None => return vec![GsWsMessage::Error {
+                    code: "NOT_IN_ROOM".to_string(),
+                    message: "请先加入房间".to_string(),
+                    diagnostic: None,
+                }],
+            };
+            
+            match state.gs_combat_log_page(&room_id, page).await {
+                Ok(page) => vec![GsWsMessage::CombatLogPage { page }],
+                Err(e) => vec![GsWsMessage::Error {
+                    code: "GAME_NOT_STARTED".to_string(),
+                    message: e,
+                    diagnostic: None,
+                }],
+            }
+        }
+        
+        // =================================================================
+        // 组队标记
+        // =================================================================
+        GsWsMessage::SendPing { target, intent } => {
+            let pid = match player_id {
+                Some(id) => id.clone(),
+                None => return vec![GsWsMessage::Error {
+                    code: "NOT_LOGGED_IN".to_string(),
+                    message: "请先登录".to_string(),
+                    diagnostic: None,
+                }],
+            };
+            
+            let room_id = match current_room_id {
+                Some(id) => id.clone(),
+                None => return vec![GsWsMessage::Error {
+                    code: "NOT_IN_ROOM".to_string(),
+                    message: "请先加入房间".to_string(),
+                    diagnostic: None,
+                }],
+            };
+            
+            match state.gs_send_ping(&room_id, &pid, target, intent).await {
+                Ok(Some((ping, exclude_ids))) => {
+                    let broadcast_msg = GsWsMessage::TeamPing { ping };
+                    state.gs_broadcast_to_room(
+                        &room_id,
+                        serde_json::to_string(&broadcast_msg).unwrap_or_default(),
+                        exclude_ids,
+                    );
+                    vec![]
+                }
+                // 超出限流: 直接丢弃
+                Ok(None) => vec![],
+                Err(e) => vec![GsWsMessage::Error {
+                    code: "INVALID_PING".to_string(),
+                    message: e,
+                    diagnostic: None,
+                }],
+            }
+        }
+        
+        // =================================================================
+        // 暂停与继续
+        // =================================================================
+        GsWsMessage::RequestPause | GsWsMessage::AcceptPause | GsWsMessage::DeclinePause | GsWsMessage::ResumeBattle => {
+            let pid = match player_id {
+                Some(id) => id.clone(),
+                None => return vec![GsWsMessage::Error {
+                    code: "NOT_LOGGED_IN".to_string(),
+                    message: "请先登录".to_string(),
+                    diagnostic: None,
+                }],
+            };
+            
+            let room_id = match current_room_id {
+                Some(id) => id.clone(),
+                None => return vec![GsWsMessage::Error {
+                    code: "NOT_IN_ROOM".to_string(),
+                    message: "请先加入房间".to_string(),
+                    diagnostic: None,
+                }],
+            };
+            
+            let result = match msg {
+                GsWsMessage::RequestPause => state.gs_update_pause(&room_id, &pid, |p, now| p.gc_request(&pid, now)).await,
+                GsWsMessage::AcceptPause => state.gs_update_pause(&room_id, &pid, |p, now| p.gc_accept(&pid, now)).await,
+                GsWsMessage::DeclinePause => state.gs_update_pause(&room_id, &pid, |p, now| p.gc_decline(now)).await,
+                _ => state.gs_resume_battle(&room_id, &pid).await,
+            };
+            match result {
+                Ok(status) => {
+                    gs_broadcast_pause(state, &room_id, status);
+                    vec![]
+                }
+                Err(e) => vec![GsWsMessage::Error {
+                    code: "PAUSE_FAILED".to_string(),
+                    message: e,
+                    diagnostic: None,
+                }],
+            }
+        }
+        
+        // =================================================================
+        // 设置卡组
+        // =================================================================
+        GsWsMessage::SetDeck { deck } => {
+            let pid = match player_id {
+                Some(id) => id.clone(),
+                None => return vec![GsWsMessage::Error {
+                    code: "NOT_LOGGED_IN".to_string(),
+                    message: "请先登录".to_string(),
+                    diagnostic: None,
+                }],
+            };
+            
+            let room_id = match current_room_id {
+                Some(id) => id.clone(),
+                None => return vec![GsWsMessage::Error {
+                    code: "NOT_IN_ROOM".to_string(),
+                    message: "请先加入房间".to_string(),
+                    diagnostic: None,
+                }],
+            };
+            
+            let deck_id = deck.id.clone();
+            match state.gs_set_deck(&room_id, &pid, deck).await {
+                Ok(()) => vec![GsWsMessage::DeckSet { deck_id }],
+                Err(e) => vec![GsWsMessage::Error {
+                    code: "SET_DECK_FAILED".to_string(),
+                    message: e,
+                    diagnostic: None,
+                }],
+            }
+        }
+        
+        // =================================================================
+        // 局间换牌
+        // =================================================================
+        GsWsMessage::SubmitSideboard { swaps } => {
+            let pid = match player_id {
+                Some(id) => id.clone(),
+                None => return vec![GsWsMessage::Error {
+                    code: "NOT_LOGGED_IN".to_string(),
+                    message: "请先登录".to_string(),
+                    diagnostic: None,
+                }],
+            };
+            
+            let room_id = match current_room_id {
+                Some(id) => id.clone(),
+                None => return vec![GsWsMessage::Error {
+                    code: "NOT_IN_ROOM".to_string(),
+                    message: "请先加入房间".to_string(),
+                    diagnostic: None,
+                }],
+            };
+            
+            match state.gs_submit_sideboard(&room_id, &pid, &swaps).await {
+                Ok((series, battle)) => {
+                    let series_msg = GsWsMessage::SeriesUpdate { series };
+                    state.gs_broadcast_to_room(
+                        &room_id,
+                        serde_json::to_string(&series_msg).unwrap_or_default(),
+                        vec![],
+                    );
+                    
+                    // 所有玩家提交后开始下一局
+                    if let Some(battle) = battle {
+                        gs_send_battle_state(state, &room_id, &battle, true);
+                    }
+                    
+                    vec![]
+                }
+                Err(e) => vec![GsWsMessage::Error {
+                    code: "SIDEBOARD_FAILED".to_string(),
+                    message: e,
+                    diagnostic: None,
+                }],
+            }
+        }
+        
+        // =================================================================
+        // 酒馆模式
+        // =================================================================
+        GsWsMessage::StartTavern | GsWsMessage::StartWeeklyChallenge => {
+            let weekly = matches!(msg, GsWsMessage::StartWeeklyChallenge);
+            let pid = match player_id {
+                Some(id) => id.clone(),
+                None => return vec![GsWsMessage::Error {
+                    code: "NOT_LOGGED_IN".to_string(),
+                    message: "请先登录".to_string(),
+                    diagnostic: None,
+                }],
+            };
+            
+            let room_id = match current_room_id {
+                Some(id) => id.clone(),
+                None => return vec![GsWsMessage::Error {
+                    code: "NOT_IN_ROOM".to_string(),
+                    message: "请先加入房间".to_string(),
+                    diagnostic: None,
+                }],
+            };
+            
+            match state.gs_start_tavern(&room_id, &pid, weekly).await {
+                Ok(tavern) => {
+                    let start_msg = GsWsMessage::TavernStarted { round: tavern.round };
+                    state.gs_broadcast_to_room(
+                        &room_id,
+                        serde_json::to_string(&start_msg).unwrap_or_default(),
+                        vec![],
+                    );
+                    
+                    match tavern.gc_seat(&pid) {
+                        Some(seat) => vec![GsWsMessage::TavernSeat {
+                            seat: Box::new(seat.clone()),
+                            overflow: None,
+                        }],
+                        None => vec![],
+                    }
+                }
+                Err(e) => vec![GsWsMessage::Error {
+                    code: "START_FAILED".to_string(),
+                    message: e,
+                    diagnostic: None,
+                }],
+            }
+        }
+        
+        GsWsMessage::TavernAction { action } => {
+            let pid = match player_id {
+                Some(id) => id.clone(),
+                None => return vec![GsWsMessage::Error {
+                    code: "NOT_LOGGED_IN".to_string(),
+                    message: "请先登录".to_string(),
+                    diagnostic: None,
+                }],
+            };
+            
+            let room_id = match current_room_id {
+                Some(id) => id.clone(),
+                None => return vec![GsWsMessage::Error {
+                    code: "NOT_IN_ROOM".to_string(),
+                    message: "请先加入房间".to_string(),
+                    diagnostic: None,
+                }],
+            };
+            
+            match state.gs_tavern_action(&room_id, &pid, &action).await {
+                Ok(seat) => {
+                    let events: Vec<GcOnboardingEvent> = GcOnboardingEvent::gc_from_tavern_action(&action).into_iter()
+                        .chain(GcOnboardingEvent::gc_from_tavern_seat(&seat))
+                        .collect();
+                    gs_push_onboarding(state, &pid, &events).await;
+                    let overflow = seat.gc_bench_overflow();
+                    vec![GsWsMessage::TavernSeat { seat: Box::new(seat), overflow }]
+                }
+                Err(e) => vec![GsWsMessage::Error {
+                    code: gs_tavern_error_code(&e).to_string(),
+                    message: e.to_string(),
+                    diagnostic: None,
+                }],
+            }
+        }
+        
+        GsWsMessage::EndShopping => {
+            let pid = match player_id {
+                Some(id) => id.clone(),
+                None => return vec![GsWsMessage::Error {
+                    code: "NOT_LOGGED_IN".to_string(),
+                    message: "请先登录".to_string(),
+                    diagnostic: None,
+                }],
+            };
+            
+            let room_id = match current_room_id {
+                Some(id) => id.clone(),
+                None => return vec![GsWsMessage::Error {
+                    code: "NOT_IN_ROOM".to_string(),
+                    message: "请先加入房间".to_string(),
+                    diagnostic: None,
+                }],
+            };
+            
+            match state.gs_tavern_end_shopping(&room_id, &pid).await {
+                Ok((tavern, report)) => {
+                    let ended_msg = GsWsMessage::ShoppingEnded { player_id: pid };
+                    state.gs_broadcast_to_room(
+                        &room_id,
+                        serde_json::to_string(&ended_msg).unwrap_or_default(),
+                        vec![],
+                    );
+                    
+                    if let Some(report) = report {
+                        let result_msg = GsWsMessage::TavernRoundResult { report };
+                        state.gs_broadcast_to_room(
+                            &room_id,
+                            serde_json::to_string(&result_msg).unwrap_or_default(),
+                            vec![],
+                        );
+                        gs_send_tavern_seats(state, &room_id, &tavern);
+                        
+                        if tavern.gc_is_finished() {
+                            for seat in &tavern.seats {
+                                gs_push_onboarding(state, &seat.player.id, &GcOnboardingEvent::gc_from_tavern_seat(seat)).await;
+                            }
+                            let summary_msg = GsWsMessage::TavernRunSummary {
+                                scores: GcRunScore::gc_from_tavern_game(&tavern),
+                            };
+                            state.gs_broadcast_to_room(
+                                &room_id,
+                                serde_json::to_string(&summary_msg).unwrap_or_default(),
+                                vec![],
+                            );
+                            let rewards = state.gs_grant_tavern_rewards(&tavern).await;
+                            let rewards_msg = GsWsMessage::MatchRewards { rewards };
+                            state.gs_broadcast_to_room(
+                                &room_id,
+                                serde_json::to_string(&rewards_msg).unwrap_or_default(),
+                                vec![],
+                            );
+                        }
+                    }
+                    
+                    vec![]
+                }
+                Err(e) => vec![GsWsMessage::Error {
+                    code: "END_SHOPPING_FAILED".to_string(),
+                    message: e,
+                    diagnostic: None,
+                }],
+            }
+        }
+        
+        // =================================================================
+        // 世界 Boss
+        // =================================================================
+        GsWsMessage::GetWorldBoss => {
+            match state.world_boss.read().await.as_ref() {
+                Some(event) if event.gc_is_active(gs_now()) => {
+                    vec![GsWsMessage::WorldBossUpdate { status: event.gc_status() }]
+                }
+                _ => vec![GsWsMessage::WorldBossScheduled {
+                    next_spawn_at: state.world_boss_schedule.gc_next_spawn_at(gs_now()),
+                }],
+            }
+        }
+        
+        GsWsMessage::AttackWorldBoss => {
+            let pid = match player_id {
+                Some(id) => id.clone(),
+                None => return vec![GsWsMessage::Error {
+                    code: "NOT_LOGGED_IN".to_string(),
+                    message: "请先登录".to_string(),
+                    diagnostic: None,
+                }],
+            };
+            
+            match state.gs_attack_world_boss(&pid).await {
+                Ok((hit, status, result)) => {
+                    if let Some(phase) = hit.new_phase {
+                        let phase_msg = GsWsMessage::WorldBossPhaseChanged {
+                            phase,
+                            phase_name: status.phase_name.clone(),
+                        };
+                        state.gs_broadcast_all(serde_json::to_string(&phase_msg).unwrap_or_default());
+                    }
+                    if let Some(result) = result {
+                        let defeated_msg = GsWsMessage::WorldBossDefeated { result };
+                        state.gs_broadcast_all(serde_json::to_string(&defeated_msg).unwrap_or_default());
+                    }
+                    vec![GsWsMessage::WorldBossHit { hit }]
+                }
+                Err(e) => vec![GsWsMessage::Error {
+                    code: "WORLD_BOSS_UNAVAILABLE".to_string(),
+                    message: e,
+                    diagnostic: None,
+                }],
+            }
+        }
+        
+        GsWsMessage::HealInWorldBoss { amount } => {
+            let pid = match player_id {
+                Some(id) => id.clone(),
+                None => return vec![GsWsMessage::Error {
+                    code: "NOT_LOGGED_IN".to_string(),
+                    message: "请先登录".to_string(),
+                    diagnostic: None,
+                }],
+            };
+            
+            match state.gs_heal_in_world_boss(&pid, amount).await {
+                Ok(snapshot) => vec![GsWsMessage::DamageMeter { snapshot }],
+                Err(e) => vec![GsWsMessage::Error {
+                    code: "WORLD_BOSS_UNAVAILABLE".to_string(),
+                    message: e,
+                    diagnostic: None,
                 }],
             }
         }
@@ -607,6 +1761,344 @@ async fn gs_handle_message(
         _ => vec![GsWsMessage::Error {
             code: "UNKNOWN_MESSAGE".to_string(),
             message: "未知的消息类型".to_string(),
+            diagnostic: None,
         }],
     }
 }
+
+/// 向每名玩家单独推送自己的座位 (排除其他玩家)
+fn gs_send_tavern_seats(state: &GsAppState, room_id: &str, tavern: &GcTavernGame) {
+    for seat in &tavern.seats {
+        let others: Vec<String> = tavern.seats.iter()
+            .filter(|s| s.player.id != seat.player.id)
+            .map(|s| s.player.id.clone())
+            .collect();
+        let seat_msg = GsWsMessage::TavernSeat {
+            seat: Box::new(seat.clone()),
+            overflow: seat.gc_bench_overflow(),
+        };
+        state.gs_broadcast_to_room(
+            room_id,
+            serde_json::to_string(&seat_msg).unwrap_or_default(),
+            others,
+        );
+    }
+}
+
+/// 向每名玩家单独推送脱敏后的战斗状态 (只包含自己的手牌/牌库/弃牌堆内容)
+fn gs_send_battle_state(state: &GsAppState, room_id: &str, battle: &GcBattleState, started: bool) {
+    let win_probability = if state.config.broadcast_win_probability {
+        gc_win_probabilities(battle)
+    } else {
+        Vec::new()
+    };
+    for player in &battle.players {
+        let others: Vec<String> = battle.players.iter()
+            .filter(|p| p.id != player.id)
+            .map(|p| p.id.clone())
+            .collect();
+        let msg = gs_battle_state_message(battle, &player.id, win_probability.clone(), started);
+        state.gs_broadcast_to_room(room_id, serde_json::to_string(&msg).unwrap_or_default(), others);
+    }
+}
+
+/// 某名玩家视角的战斗状态消息
+fn gs_battle_state_message(
+    battle: &GcBattleState,
+    viewer: &str,
+    win_probability: Vec<GcWinProbability>,
+    started: bool,
+) -> GsWsMessage {
+    let battle_state = serde_json::to_string(&gc_redact_battle_state(battle, Some(viewer))).unwrap_or_default();
+    let zones = gc_battle_zones(battle, Some(viewer));
+    if started {
+        GsWsMessage::GameStarted { battle_state, zones, win_probability }
+    } else {
+        GsWsMessage::GameState { battle_state, zones, win_probability }
+    }
+}
+
+/// 重同步快照: `Resync` 之后依次是大厅房间列表、房间成员与设置、系列赛、本人视角的战斗状态与酒馆座位
+pub(crate) async fn gs_resync_snapshot(
+    state: &GsAppState,
+    player_id: Option<&str>,
+    room_id: Option<&str>,
+    lobby_subscribed: bool,
+    dropped: u64,
+) -> Vec<GsWsMessage> {
+    let mut messages = vec![GsWsMessage::Resync { dropped }];
+    if lobby_subscribed {
+        let rooms = gs_lobby_snapshot(state).await.into_values().collect();
+        messages.push(GsWsMessage::RoomList { rooms });
+    }
+
+    let (Some(pid), Some(room_id)) = (player_id, room_id) else {
+        return messages;
+    };
+    let Some(room) = state.gs_get_room(room_id).await else {
+        return messages;
+    };
+    let players = room.players.iter().map(|p| RoomPlayerInfo {
+        id: p.id.clone(),
+        name: p.name.clone(),
+        ready: p.ready,
+        is_owner: p.id == room.owner_id,
+        appearance: p.appearance,
+    }).collect();
+    messages.push(GsWsMessage::RoomJoined { room_id: room.id.clone(), players });
+    messages.push(GsWsMessage::RoomSettings { format: room.format, teams: room.teams.clone() });
+    if let Some(series) = &room.series {
+        messages.push(GsWsMessage::SeriesUpdate { series: series.clone() });
+    }
+    if let Some(battle) = room.battle.as_ref().map(GcEventSourcedBattle::gc_state) {
+        if battle.gc_find_player(pid).is_some() {
+            let win_probability = if state.config.broadcast_win_probability {
+                gc_win_probabilities(battle)
+            } else {
+                Vec::new()
+            };
+            messages.push(gs_battle_state_message(battle, pid, win_probability, false));
+            messages.push(GsWsMessage::PauseStatus { status: room.pause.gc_status(gs_now()) });
+        }
+    }
+    if let Some(seat) = room.tavern.as_ref().and_then(|t| t.gc_seat(pid)) {
+        messages.push(GsWsMessage::TavernSeat { seat: Box::new(seat.clone()), overflow: seat.gc_bench_overflow() });
+    }
+    messages
+}
+
+/// 增量广播公开行动记录 (所有人看到相同的脱敏条目)
+async fn gs_broadcast_action_feed(state: &GsAppState, room_id: &str) {
+    let entries = state.gs_take_action_feed(room_id).await;
+    if entries.is_empty() {
+        return;
+    }
+    let msg = GsWsMessage::ActionFeed { entries };
+    state.gs_broadcast_to_room(room_id, serde_json::to_string(&msg).unwrap_or_default(), vec![]);
+}
+
+/// 酒馆操作错误码
+fn gs_tavern_error_code(error: &GcError) -> &'static str {
+    match error {
+        GcError::GcBenchFull => "BENCH_FULL",
+        GcError::GcShopSlotEmpty => "SHOP_SLOT_EMPTY",
+        GcError::GcNotEnoughGold => "NOT_ENOUGH_GOLD",
+        GcError::GcBattleNotStarted => "GAME_NOT_STARTED",
+        GcError::GcPlayerCannotAct => "SHOPPING_ENDED",
+        _ => "TAVERN_ACTION_FAILED",
+    }
+}
+
+/// 广播房间赛制与队伍变化 (失败时只回复操作者)
+fn gs_broadcast_room_settings(
+    state: &GsAppState,
+    room_id: &str,
+    result: Result<(GcBattleFormat, HashMap<String, u32>), String>,
+) -> Vec<GsWsMessage> {
+    match result {
+        Ok((format, teams)) => {
+            let broadcast_msg = GsWsMessage::RoomSettings { format, teams };
+            state.gs_broadcast_to_room(
+                room_id,
+                serde_json::to_string(&broadcast_msg).unwrap_or_default(),
+                vec![],
+            );
+            vec![]
+        }
+        Err(e) => vec![GsWsMessage::Error {
+            code: "ROOM_SETTINGS_FAILED".to_string(),
+            message: e,
+            diagnostic: None,
+        }],
+    }
+}
+
+/// 广播对局暂停状态
+pub(crate) fn gs_broadcast_pause(state: &GsAppState, room_id: &str, status: GcPauseStatus) {
+    let msg = GsWsMessage::PauseStatus { status };
+    state.gs_broadcast_to_room(room_id, serde_json::to_string(&msg).unwrap_or_default(), vec![]);
+}
+
+/// 用户文本校验失败的错误响应 (错误码见 `GcTextError::gc_code`，附带字段诊断)
+fn gs_text_error(input: &str, path: &str, error: &GcTextError) -> GsWsMessage {
+    GsWsMessage::Error {
+        code: error.gc_code().to_string(),
+        message: error.to_string(),
+        diagnostic: Some(error.gc_diagnostic(input, path)),
+    }
+}
+
+/// 记录新手引导事件，有新完成的步骤时推送给该玩家
+async fn gs_push_onboarding(state: &GsAppState, player_id: &str, events: &[GcOnboardingEvent]) {
+    let Some((completed, progress)) = state.gs_record_onboarding(player_id, events).await else {
+        return;
+    };
+    let msg = GsWsMessage::OnboardingUpdated { completed, progress };
+    state.gs_broadcast_to_players(&[player_id.to_string()], serde_json::to_string(&msg).unwrap_or_default()).await;
+}
+
+/// 广播对局结束，并推进系列赛 (进入换牌阶段或结束)
+async fn gs_broadcast_game_end(state: &GsAppState, room_id: &str, battle: &GcBattleState) {
+    let winner_ids = battle.gc_winner_ids();
+    let rewards = state.gs_grant_card_battle_rewards(room_id, &winner_ids).await;
+    let end_msg = GsWsMessage::GameEnded {
+        winner_id: battle.winner_id.clone(),
+        winner_ids,
+        stats: gc_match_stats(battle),
+    };
+    state.gs_broadcast_to_room(
+        room_id,
+        serde_json::to_string(&end_msg).unwrap_or_default(),
+        vec![],
+    );
+    let rewards_msg = GsWsMessage::MatchRewards { rewards };
+    state.gs_broadcast_to_room(
+        room_id,
+        serde_json::to_string(&rewards_msg).unwrap_or_default(),
+        vec![],
+    );
+    for player in &battle.players {
+        if let Some(event) = GcOnboardingEvent::gc_from_battle_end(battle, &player.id) {
+            gs_push_onboarding(state, &player.id, &[event]).await;
+        }
+    }
+    
+    match state.gs_finish_game(room_id).await {
+        Ok(series) => {
+            let series_msg = GsWsMessage::SeriesUpdate { series };
+            state.gs_broadcast_to_room(
+                room_id,
+                serde_json::to_string(&series_msg).unwrap_or_default(),
+                vec![],
+            );
+        }
+        Err(e) => tracing::warn!("系列赛推进失败: {}", e),
+    }
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use game_core::{gc_tavern_rewards, GcArenaPosition, GcRewardTable, GcZonePrivacy, GcZoneView};
+
+    /// 协议快照: 格式有意修改时运行 `UPDATE_GOLDEN=1 cargo test -p game-server golden` 并提交快照
+    #[test]
+    fn test_ws_messages_golden() {
+        let messages = vec![
+            GsWsMessage::Login { player_id: "p1".to_string(), name: "玩家1".to_string(), appearance: None, slot: 1, content_version: None },
+            GsWsMessage::PlayCard { card_id: "c1".to_string(), target_id: Some("p2".to_string()) },
+            GsWsMessage::EndTurn,
+            GsWsMessage::TavernAction { action: GcTavernAction::Buy { slot: 0 } },
+            GsWsMessage::TavernAction {
+                action: GcTavernAction::Deploy { monster_id: "m1".to_string(), position: GcArenaPosition::back(1) },
+            },
+            GsWsMessage::RoomJoined {
+                room_id: "r1".to_string(),
+                players: vec![RoomPlayerInfo {
+                    id: "p1".to_string(),
+                    name: "玩家1".to_string(),
+                    ready: true,
+                    is_owner: true,
+                    appearance: Some(GcAppearance::gc_new(2, 5, 1)),
+                }],
+            },
+            GsWsMessage::RoomList {
+                rooms: vec![RoomInfo { id: "r1".to_string(), name: "房间".to_string(), player_count: 1, max_players: 2, game_started: false }],
+            },
+            GsWsMessage::SubscribeLobby,
+            GsWsMessage::SetRoomFormat { format: GcBattleFormat::TwoVsTwo },
+            GsWsMessage::SeatControl { player_id: "p2".to_string(), bot: true },
+            GsWsMessage::RoomSettings {
+                format: GcBattleFormat::TwoVsTwo,
+                teams: HashMap::from([("p1".to_string(), 1)]),
+            },
+            GsWsMessage::LobbyDeltas {
+                deltas: vec![
+                    GsLobbyDelta::Updated {
+                        room: RoomInfo { id: "r1".to_string(), name: "房间".to_string(), player_count: 2, max_players: 2, game_started: true },
+                    },
+                    GsLobbyDelta::Closed { room_id: "r2".to_string() },
+                ],
+            },
+            GsWsMessage::ResumeRoom { room_id: "r1".to_string() },
+            GsWsMessage::ParkedRooms {
+                rooms: vec![ParkedRoomInfo {
+                    room_id: "r1".to_string(),
+                    name: "房间".to_string(),
+                    player_ids: vec!["p1".to_string(), "p2".to_string()],
+                    turn: 4,
+                    saved_at: 1000,
+                    expires_at: 260200,
+                }],
+            },
+            GsWsMessage::GameState {
+                battle_state: "{}".to_string(),
+                zones: vec![GcPlayerZones {
+                    player_id: "p2".to_string(),
+                    hand: GcZoneView { count: 3, cards: None },
+                    deck: GcZoneView::gc_new(&[], GcZonePrivacy::Owner),
+                    discard: GcZoneView::default(),
+                }],
+                win_probability: vec![GcWinProbability { player_id: "p2".to_string(), per_mille: 620 }],
+            },
+            GsWsMessage::CardPlayed { player_id: "p1".to_string(), card_id: "c1".to_string(), result: "{}".to_string() },
+            GsWsMessage::SendPing {
+                target: GcPingTarget::BattlefieldSlot { player_id: "p2".to_string(), slot: 1 },
+                intent: GcPingIntent::Attack,
+            },
+            GsWsMessage::TeamPing {
+                ping: GcPing {
+                    sender_id: "p1".to_string(),
+                    target: GcPingTarget::Player { player_id: "p2".to_string() },
+                    intent: GcPingIntent::Caution,
+                    turn: 3,
+                },
+            },
+            GsWsMessage::GameEnded { winner_id: None, winner_ids: Vec::new(), stats: Vec::new() },
+            GsWsMessage::GameEnded {
+                winner_id: Some("p1".to_string()),
+                winner_ids: vec!["p1".to_string(), "p3".to_string()],
+                stats: vec![GcPlayerMatchStats {
+                    player_id: "p1".to_string(),
+                    team: Some(0),
+                    damage_dealt: 120,
+                    healing_done: 15,
+                    kills: 1,
+                    deaths: 0,
+                    assists: 2,
+                }],
+            },
+            GsWsMessage::MatchRewards {
+                rewards: gc_tavern_rewards(&GcRewardTable::default(), &[("p1".to_string(), 1)]),
+            },
+            GsWsMessage::OnboardingUpdated {
+                completed: vec![GcOnboardingFlag::FirstCardPlayed],
+                progress: GcOnboardingProgress { completed: [GcOnboardingFlag::FirstCardPlayed].into_iter().collect() },
+            },
+            GsWsMessage::Resync { dropped: 3 },
+            GsWsMessage::Error { code: "NOT_YOUR_TURN".to_string(), message: "不是你的回合".to_string(), diagnostic: None },
+            GsWsMessage::Ping,
+        ];
+        let actual = serde_json::to_value(&messages).expect("序列化失败");
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/ws_messages.json");
+
+        if std::env::var_os("UPDATE_GOLDEN").is_some() {
+            let text = serde_json::to_string_pretty(&actual).expect("格式化失败");
+            std::fs::write(path, text + "\n").expect("写入快照失败");
+            return;
+        }
+
+        let expected: serde_json::Value = serde_json::from_str(
+            &std::fs::read_to_string(path).expect("缺少快照，请用 UPDATE_GOLDEN=1 生成"),
+        ).expect("快照不是合法 JSON");
+        assert!(
+            actual == expected,
+            "GsWsMessage 的序列化格式已改变。若为有意修改，请运行 UPDATE_GOLDEN=1 cargo test -p game-server golden 并提交快照。\n实际:\n{}",
+            serde_json::to_string_pretty(&actual).unwrap_or_default(),
+        );
+    }
+}
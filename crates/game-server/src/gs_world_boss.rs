@@ -0,0 +1,94 @@
+//! 世界 Boss 调度
+//!
+//! 模块: game-server
+//! 前缀: Gs
+//! 文档: 文档/03-game-server.md
+//!
+//! 后台任务按刷新计划生成/移除世界 Boss，并定期向所有连接广播血量与排行，
+//! 向参与者广播伤害统计；Boss 离开时最终统计写入对局历史。
+
+use std::time::Duration;
+
+use game_core::*;
+
+use crate::gs_state::{gs_now, GsAppState};
+use crate::gs_websocket::GsWsMessage;
+
+/// 调度间隔 (秒)
+const GS_WORLD_BOSS_TICK_SECS: u64 = 2;
+
+/// 伤害统计广播间隔 (调度次数)
+const GS_DAMAGE_METER_EVERY_TICKS: u64 = 3;
+
+/// 启动世界 Boss 调度任务
+pub fn gs_spawn_world_boss_scheduler(state: GsAppState) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(GS_WORLD_BOSS_TICK_SECS));
+        let mut last_hp: Option<u32> = None;
+        let mut ticks: u64 = 0;
+        
+        gs_broadcast(&state, &GsWsMessage::WorldBossScheduled {
+            next_spawn_at: state.world_boss_schedule.gc_next_spawn_at(gs_now()),
+        });
+        
+        loop {
+            interval.tick().await;
+            ticks += 1;
+            let now = gs_now();
+            let mut world_boss = state.world_boss.write().await;
+            let mut escaped = None;
+            let mut meter = None;
+            
+            match world_boss.as_ref() {
+                // 刷新窗口开始: 生成 Boss
+                None => {
+                    if let Some((start, end)) = state.world_boss_schedule.gc_active_window(now) {
+                        let event = gc_create_ember_wyrm_event(start, end);
+                        tracing::info!("世界 Boss 刷新: {}", event.boss.name);
+                        gs_broadcast(&state, &GsWsMessage::WorldBossSpawned { status: event.gc_status() });
+                        last_hp = Some(event.boss.current_hp);
+                        *world_boss = Some(event);
+                    }
+                }
+                // 窗口结束: 未击杀则离开，并公布下次刷新时间
+                Some(event) if event.gc_is_expired(now) => {
+                    if event.boss.gc_is_alive() {
+                        tracing::info!("世界 Boss 离开: {}", event.boss.name);
+                        let result = event.gc_result(now);
+                        gs_broadcast(&state, &GsWsMessage::WorldBossEscaped { result: result.clone() });
+                        escaped = Some(result);
+                    }
+                    *world_boss = None;
+                    last_hp = None;
+                    gs_broadcast(&state, &GsWsMessage::WorldBossScheduled {
+                        next_spawn_at: state.world_boss_schedule.gc_next_spawn_at(now),
+                    });
+                }
+                // 血量变化时广播最新状态
+                Some(event) => {
+                    if last_hp != Some(event.boss.current_hp) {
+                        last_hp = Some(event.boss.current_hp);
+                        gs_broadcast(&state, &GsWsMessage::WorldBossUpdate { status: event.gc_status() });
+                    }
+                    if event.boss.gc_is_alive() && ticks.is_multiple_of(GS_DAMAGE_METER_EVERY_TICKS) {
+                        meter = Some((event.meter.gc_participants(), event.meter.gc_snapshot(now)));
+                    }
+                }
+            }
+            drop(world_boss);
+            
+            if let Some(result) = escaped {
+                state.gs_save_raid_result(&result).await;
+            }
+            if let Some((participants, snapshot)) = meter.filter(|(ids, _)| !ids.is_empty()) {
+                let msg = GsWsMessage::DamageMeter { snapshot };
+                state.gs_broadcast_to_players(&participants, serde_json::to_string(&msg).unwrap_or_default()).await;
+            }
+        }
+    });
+}
+
+/// 全服广播
+fn gs_broadcast(state: &GsAppState, msg: &GsWsMessage) {
+    state.gs_broadcast_all(serde_json::to_string(msg).unwrap_or_default());
+}
@@ -0,0 +1,54 @@
+//! 世界时钟广播
+//!
+//! 模块: game-server
+//! 前缀: Gs
+//! 文档: 文档/03-game-server.md
+//!
+//! 游戏时间由 `GcWorldClock` 按现实时间推算，客户端登录时收到当前时间与倍率后可自行推算；
+//! 后台任务只在昼夜时段切换时全服广播一次 `WorldTime`，用于纠正客户端时钟与切换光照；
+//! 区域天气同样由时钟推算，天气周期切换后只广播天气发生变化的区域 (`Weather`)。
+
+use std::time::Duration;
+
+use crate::gs_state::{gs_now, GsAppState};
+use crate::gs_websocket::GsWsMessage;
+
+/// 检查间隔 (秒)
+const GS_WORLD_CLOCK_TICK_SECS: u64 = 1;
+
+/// 启动世界时钟广播任务
+pub fn gs_spawn_world_clock(state: GsAppState) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(GS_WORLD_CLOCK_TICK_SECS));
+        let mut last_phase = state.world_clock.gc_phase_at(gs_now());
+        let mut last_weather = state.gs_weather_now();
+        tracing::info!("🕒 世界时钟: 倍率 {}，当前时段 {}", state.world_clock.time_ratio, last_phase.gc_name());
+        
+        loop {
+            interval.tick().await;
+            
+            let time = state.world_clock.gc_time_at(gs_now());
+            if time.phase != last_phase {
+                last_phase = time.phase;
+                tracing::debug!("🕒 第 {} 天 {:02}:{:02} 进入{}", time.day, time.hour, time.minute, time.phase.gc_name());
+                let msg = GsWsMessage::WorldTime { time };
+                state.gs_broadcast_all(serde_json::to_string(&msg).unwrap_or_default());
+            }
+            
+            let weather = state.gs_weather_now();
+            let changed: Vec<_> = weather.iter()
+                .filter(|current| !last_weather.iter().any(|last| last.region_id == current.region_id && last.weather == current.weather))
+                .cloned()
+                .collect();
+            last_weather = weather;
+            if changed.is_empty() {
+                continue;
+            }
+            for region in &changed {
+                tracing::debug!("🌦️ {} 天气转为{}", region.region_id, region.weather.gc_name());
+            }
+            let msg = GsWsMessage::Weather { regions: changed };
+            state.gs_broadcast_all(serde_json::to_string(&msg).unwrap_or_default());
+        }
+    });
+}
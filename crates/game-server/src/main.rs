@@ -12,8 +12,26 @@ mod gs_websocket;
 mod gs_db;
 mod gs_mcp;
 mod gs_auth;
+mod gs_world_boss;
+mod gs_territory;
+mod gs_parked;
+mod gs_lobby;
+mod gs_janitor;
+mod gs_static;
+mod gs_world_clock;
+mod gs_bot;
+mod gs_account;
+mod gs_backpressure;
+mod gs_heartbeat;
+mod gs_thumbnail;
+mod gs_usage;
+mod gs_pause;
+mod gs_chaos;
 
-use axum::{routing::{get, post}, Router};
+#[cfg(test)]
+mod gs_integration_tests;
+
+use axum::{routing::{delete, get, post, put}, Router};
 use std::net::SocketAddr;
 use tower_http::{cors::CorsLayer, trace::TraceLayer};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, Layer};
@@ -80,6 +98,18 @@ async fn main() -> anyhow::Result<()> {
     // 创建应用状态
     let state = GsAppState::gs_new().await?;
     
+    // 世界 Boss 调度
+    gs_world_boss::gs_spawn_world_boss_scheduler(state.clone());
+    gs_territory::gs_spawn_territory_scheduler(state.clone());
+    gs_parked::gs_spawn_parked_battle_cleanup(state.clone());
+    gs_lobby::gs_spawn_lobby_publisher(state.clone());
+    gs_janitor::gs_spawn_janitor(state.clone());
+    gs_world_clock::gs_spawn_world_clock(state.clone());
+    gs_bot::gs_spawn_bot_driver(state.clone());
+    gs_account::gs_spawn_account_purge(state.clone());
+    gs_usage::gs_spawn_usage_aggregator(state.clone());
+    gs_pause::gs_spawn_pause_watch(state.clone());
+    
     // 构建路由
     let app = gs_create_router(state);
 
@@ -108,21 +138,79 @@ fn gs_create_router(state: GsAppState) -> Router {
         
         // API 路由
         .route("/api/version", get(gs_version))
+        .route("/api/metrics/janitor", get(gs_get_janitor_stats))
+        .route("/api/metrics/broadcast", get(gs_get_broadcast_stats))
+        .route("/api/metrics/chaos", get(gs_get_chaos_stats))
         .route("/api/rooms", get(gs_list_rooms))
         .route("/api/rooms/:id", get(gs_get_room))
         .route("/api/upload", post(gs_upload_file))
         .route("/api/maps", post(gs_save_map)) // 新增保存地图接口
+        .route("/api/maps/:map_id", get(gs_get_map_header))
+        .route("/api/maps/:map_id/chunks", get(gs_get_map_chunks_around))
+        .route("/api/maps/:map_id/chunks/:cx/:cy", get(gs_get_map_chunk))
+        .route("/api/maps/:map_id/encounter", get(gs_get_map_encounter))
         .route("/api/assets", get(gs_list_assets)) // 新增资源列表接口
+        .route("/api/assets/:name/tags", put(gs_set_asset_tags))
+        .route("/api/asset-tags", get(gs_list_asset_tags))
+        .route("/api/asset-tags/rename", post(gs_rename_asset_tag))
+        .route("/api/asset-tags/:tag", delete(gs_delete_asset_tag))
+        .route("/api/content/season", get(gs_get_content_season))
+        .route("/api/content/version", get(gs_get_content_version))
+        .route("/api/world/time", get(gs_get_world_time))
+        .route("/api/world/weather", get(gs_get_world_weather))
+        .route("/api/world/weather/:region_id", get(gs_get_region_weather))
+        .route("/api/replays", get(gs_list_replays).post(gs_upload_replay))
+        .route("/api/replays/:code", get(gs_get_replay))
+        .route("/api/match-audits/:battle_id", get(gs_get_match_audit))
+        .route("/api/match-audits/:battle_id/verify", post(gs_verify_match_audit))
+        .route("/api/stats/cards", get(gs_get_card_stats))
+        .route("/api/stats/units", get(gs_get_unit_stats))
+        .route("/api/ghosts", post(gs_upload_ghost))
+        .route("/api/ghosts/:player_id", get(gs_get_ghost))
+        .route("/api/ghosts/:player_id/race", post(gs_race_ghost))
+        .route("/api/weekly-challenge", get(gs_get_weekly_challenge))
+        .route("/api/weekly-challenge/leaderboard", get(gs_get_weekly_leaderboard))
         
         // 认证 API
         .route("/api/auth/register", post(gs_register))
         .route("/api/auth/login", post(gs_login))
         .route("/api/auth/me", get(gs_get_current_user))
         
+        // 账号数据 API (导出与删除)
+        .route("/api/account/export", get(gs_export_account))
+        .route(
+            "/api/account/deletion",
+            get(gs_get_account_deletion).post(gs_request_account_deletion).delete(gs_cancel_account_deletion),
+        )
+        
         // 玩家数据 API
         .route("/api/player/:id/inventory", get(gs_get_inventory).post(gs_save_inventory))
         .route("/api/player/:id/profession", get(gs_get_profession).post(gs_save_profession))
         .route("/api/player/:id/progress", get(gs_get_player_progress).post(gs_save_player_progress))
+        .route("/api/player/:id/onboarding", get(gs_get_onboarding))
+        .route("/api/player/:id/skins", get(gs_get_monster_skins))
+        .route("/api/player/:id/settings", get(gs_get_player_settings).put(gs_put_player_settings))
+        .route("/api/player/:id/skins/equip", put(gs_equip_monster_skin))
+        .route("/api/companions", get(gs_get_companions))
+        .route("/api/companions/equip", put(gs_equip_companion))
+        .route("/api/collection", get(gs_get_card_collection))
+        .route("/api/collection/foils", post(gs_craft_foil))
+        .route("/api/player/:id/character", get(gs_get_character).post(gs_create_character))
+        .route("/api/player/:id/character/rename", post(gs_rename_character))
+        .route("/api/player/:id/slots", get(gs_list_save_slots))
+        .route("/api/player/:id/slots/:slot", delete(gs_delete_save_slot))
+        .route("/api/player/:id/slots/:slot/copy", post(gs_copy_save_slot))
+        
+        // 组织金库 API
+        .route("/api/guild/:org", get(gs_get_guild))
+        .route("/api/guild/:org/join", post(gs_join_guild))
+        .route("/api/guild/:org/donate", post(gs_donate_guild))
+        .route("/api/guild/:org/upgrade", post(gs_purchase_guild_upgrade))
+        .route("/api/guild/:org/role", post(gs_set_guild_role))
+        
+        // 领地 API
+        .route("/api/territory/map/:map_id", get(gs_get_territory_overlay))
+        .route("/api/territory/:zone_id/claim", post(gs_claim_territory))
         
         // 日志接口 (供 AI 代理使用)
         .route("/api/logs/client", post(gs_save_client_logs))
@@ -134,6 +222,9 @@ fn gs_create_router(state: GsAppState) -> Router {
         
         // MCP 路由
         .nest("/mcp", crate::gs_mcp::gs_mcp_routes(state.clone()))
+        
+        // 静态资源 (模型、贴图、地图数据、上传文件)
+        .merge(gs_static::gs_asset_routes(&state))
 
         // 中间件
         .layer(TraceLayer::new_for_http())
@@ -0,0 +1,145 @@
+//! 终端命令解析
+//!
+//! 模块: game-tui
+//! 前缀: Gt
+//! 文档: 文档/13-game-tui.md
+//!
+//! 每行一条命令，第一个词为命令名 (不区分大小写)，其余为参数。
+//! 卡牌/怪兽既可以写 ID，也可以写 ASCII 视图中的序号 (`[0]`、`[1]` ...)。
+
+use game_core::{gc_parse_arena_position, GcArenaPosition, GcBattleState, GcMonster};
+
+/// 帮助文本
+pub const GT_HELP: &str = "\
+命令:
+  show                    重新显示当前状态
+  play <序号|卡牌ID> [目标] 出牌 (默认目标为对手)
+  end                     结束回合
+  deploy <序号|怪兽ID> <位置> 部署怪兽 (位置 f0~f4 前排 / b0~b4 后排)
+  recall <位置>           撤回怪兽
+  fight                   执行一轮自动战斗
+  create <房间名>         创建房间 (连接服务器时)
+  join <房间ID>           加入房间 (连接服务器时)
+  ready                   准备 (连接服务器时)
+  start                   开始游戏 (连接服务器时，房主)
+  help                    显示本帮助
+  quit                    退出";
+
+/// 终端命令
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GtCommand {
+    Help,
+    Show,
+    Play { card: String, target: Option<String> },
+    EndTurn,
+    Deploy { monster: String, position: GcArenaPosition },
+    Recall { position: GcArenaPosition },
+    Fight,
+    CreateRoom { name: String },
+    JoinRoom { room_id: String },
+    Ready,
+    StartGame,
+    Quit,
+}
+
+/// 解析一行命令 (空行返回 None)
+pub fn gt_parse_command(line: &str) -> Option<Result<GtCommand, String>> {
+    let mut words = line.split_whitespace();
+    let name = words.next()?.to_ascii_lowercase();
+    let args: Vec<&str> = words.collect();
+
+    let position = |text: Option<&&str>| {
+        let text = text.ok_or_else(|| "缺少位置 (f0~f4 / b0~b4)".to_string())?;
+        gc_parse_arena_position(text).ok_or_else(|| format!("无效位置: {}", text))
+    };
+
+    let command = match (name.as_str(), args.as_slice()) {
+        ("help" | "h" | "?", _) => Ok(GtCommand::Help),
+        ("show" | "s", _) => Ok(GtCommand::Show),
+        ("play" | "p", [card, rest @ ..]) if rest.len() <= 1 => Ok(GtCommand::Play {
+            card: card.to_string(),
+            target: rest.first().map(|t| t.to_string()),
+        }),
+        ("play" | "p", _) => Err("用法: play <序号|卡牌ID> [目标]".to_string()),
+        ("end" | "e", []) => Ok(GtCommand::EndTurn),
+        ("deploy" | "d", [monster, pos]) => position(Some(pos)).map(|position| GtCommand::Deploy {
+            monster: monster.to_string(),
+            position,
+        }),
+        ("deploy" | "d", _) => Err("用法: deploy <序号|怪兽ID> <位置>".to_string()),
+        ("recall" | "r", [pos]) => position(Some(pos)).map(|position| GtCommand::Recall { position }),
+        ("recall" | "r", _) => Err("用法: recall <位置>".to_string()),
+        ("fight" | "f", []) => Ok(GtCommand::Fight),
+        ("create", [_, ..]) => Ok(GtCommand::CreateRoom { name: args.join(" ") }),
+        ("join", [room_id]) => Ok(GtCommand::JoinRoom { room_id: room_id.to_string() }),
+        ("ready", []) => Ok(GtCommand::Ready),
+        ("start", []) => Ok(GtCommand::StartGame),
+        ("quit" | "q" | "exit", _) => Ok(GtCommand::Quit),
+        (other, _) => Err(format!("未知命令或参数错误: {} (输入 help 查看命令)", other)),
+    };
+    Some(command)
+}
+
+/// 把手牌序号解析为卡牌 ID (不是序号时原样返回)
+pub fn gt_resolve_card(state: &GcBattleState, player_id: &str, token: &str) -> String {
+    token.parse::<usize>().ok()
+        .and_then(|i| state.gc_find_player(player_id)?.hand.get(i))
+        .map(|card| card.id.clone())
+        .unwrap_or_else(|| token.to_string())
+}
+
+/// 把手牌区序号解析为怪兽 ID (不是序号时原样返回)
+pub fn gt_resolve_monster(bench: &[GcMonster], token: &str) -> String {
+    token.parse::<usize>().ok()
+        .and_then(|i| bench.get(i))
+        .map(|monster| monster.id.clone())
+        .unwrap_or_else(|| token.to_string())
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use game_core::{GcCard, GcPlayer};
+
+    fn parse(line: &str) -> Result<GtCommand, String> {
+        gt_parse_command(line).expect("非空行应有结果")
+    }
+
+    #[test]
+    fn test_parse_commands() {
+        assert!(gt_parse_command("   ").is_none());
+        assert_eq!(parse("PLAY 0"), Ok(GtCommand::Play { card: "0".to_string(), target: None }));
+        assert_eq!(
+            parse("play c1 p2"),
+            Ok(GtCommand::Play { card: "c1".to_string(), target: Some("p2".to_string()) })
+        );
+        assert_eq!(
+            parse("deploy 1 b3"),
+            Ok(GtCommand::Deploy { monster: "1".to_string(), position: GcArenaPosition::back(3) })
+        );
+        assert_eq!(parse("recall f0"), Ok(GtCommand::Recall { position: GcArenaPosition::front(0) }));
+        assert_eq!(parse("create 测试 房间"), Ok(GtCommand::CreateRoom { name: "测试 房间".to_string() }));
+        assert_eq!(parse("q"), Ok(GtCommand::Quit));
+
+        assert!(parse("play").is_err());
+        assert!(parse("deploy 1 z9").is_err());
+        assert!(parse("end now").is_err());
+        assert!(parse("dance").is_err());
+    }
+
+    #[test]
+    fn test_resolve_hand_index() {
+        let mut player = GcPlayer::gc_new("p1", "玩家1");
+        player.hand.push(GcCard::gc_new_attack("card_a", "攻击", 1, 10));
+        let state = GcBattleState::gc_new("b", vec![player]);
+
+        assert_eq!(gt_resolve_card(&state, "p1", "0"), "card_a");
+        assert_eq!(gt_resolve_card(&state, "p1", "5"), "5");
+        assert_eq!(gt_resolve_card(&state, "p1", "card_x"), "card_x");
+        assert_eq!(gt_resolve_card(&state, "p9", "0"), "0");
+    }
+}
@@ -0,0 +1,281 @@
+//! 本地会话 (不连接服务器，直接驱动 game-core)
+//!
+//! 模块: game-tui
+//! 前缀: Gt
+//! 文档: 文档/13-game-tui.md
+//!
+//! ## 会话
+//! - `GtLocalBattle`: 卡牌对战，两名玩家在同一终端轮流操作 (热座)，
+//!   回合结束发牌与能量规则与服务器 `gs_end_turn` 相同
+//! - `GtLocalArena`: 战斗沙盘，从手牌区部署默认怪兽池的 1 阶怪兽，对阵固定敌人
+
+use std::fmt::Write;
+
+use game_core::{
+    GcArenaSide, GcBattleArena, GcBattleEvent, GcBattlePhase, GcBattleState, GcCard, GcConfig,
+    GcEventSourcedBattle, GcMonster, GcMonsterPool, GcMonsterTier, GcPlayer, GcTerrainType,
+};
+
+use crate::gt_command::{gt_resolve_card, gt_resolve_monster, GtCommand};
+
+/// 本地会话 (执行命令并返回要打印的文本)
+pub trait GtSession {
+    /// 当前状态的 ASCII 视图
+    fn gt_render(&self) -> String;
+
+    /// 执行命令，返回结果说明
+    fn gt_execute(&mut self, command: &GtCommand) -> Result<String, String>;
+}
+
+// =============================================================================
+// 卡牌对战
+// =============================================================================
+
+/// 本地卡牌对战
+pub struct GtLocalBattle {
+    battle: GcEventSourcedBattle,
+    /// 已发出的卡牌数 (生成卡牌 ID)
+    cards_given: u32,
+}
+
+impl GtLocalBattle {
+    /// 创建两人对战，先手玩家获得第一张牌
+    pub fn gt_new() -> Self {
+        let players = vec![GcPlayer::gc_new("p1", "玩家1"), GcPlayer::gc_new("p2", "玩家2")];
+        let mut state = GcBattleState::gc_new("local", players);
+        state.phase = GcBattlePhase::Playing;
+
+        let mut session = Self { battle: GcEventSourcedBattle::gc_from_state(state), cards_given: 0 };
+        session.gt_deal_current();
+        session
+    }
+
+    /// 当前战斗状态
+    pub fn gt_state(&self) -> &GcBattleState {
+        self.battle.gc_state()
+    }
+
+    /// 给行动玩家发一张攻击牌并重置能量 (同服务器回合结束逻辑)
+    fn gt_deal_current(&mut self) {
+        let Some(player_id) = self.battle.gc_state().gc_current_player_id().map(str::to_string) else {
+            return;
+        };
+        self.cards_given += 1;
+        let turn = self.battle.gc_state().turn;
+        let card = GcCard::gc_new_attack(format!("card_{}", self.cards_given), "攻击", 1, 15 + (turn % 10));
+        let recorded = self.battle
+            .gc_record(GcBattleEvent::CardGiven { player_id: player_id.clone(), card: Box::new(card) })
+            .and_then(|_| self.battle.gc_record(GcBattleEvent::EnergySet {
+                player_id,
+                energy: GcConfig::DEFAULT_ENERGY,
+            }));
+        debug_assert!(recorded.is_ok(), "发牌事件应可应用");
+    }
+
+    fn gt_current_player(&self) -> Result<String, String> {
+        if self.gt_state().gc_is_finished() {
+            return Err("对局已结束".to_string());
+        }
+        self.gt_state().gc_current_player_id()
+            .map(str::to_string)
+            .ok_or_else(|| "没有行动玩家".to_string())
+    }
+}
+
+impl GtSession for GtLocalBattle {
+    fn gt_render(&self) -> String {
+        let state = self.gt_state();
+        state.gc_to_ascii(state.gc_current_player_id())
+    }
+
+    fn gt_execute(&mut self, command: &GtCommand) -> Result<String, String> {
+        match command {
+            GtCommand::Play { card, target } => {
+                let player_id = self.gt_current_player()?;
+                let card_id = gt_resolve_card(self.gt_state(), &player_id, card);
+                let target_id = target.clone().unwrap_or_else(|| {
+                    self.gt_state().players.iter()
+                        .find(|p| p.id != player_id && p.gc_can_act())
+                        .map(|p| p.id.clone())
+                        .unwrap_or_default()
+                });
+                let result = self.battle.gc_play_card(&player_id, &card_id, &target_id);
+                if !result.success {
+                    return Err(result.error.unwrap_or_else(|| "出牌失败".to_string()));
+                }
+                Ok(format!("{} 打出 {}，造成 {} 点伤害", player_id, card_id, result.damage_dealt))
+            }
+            GtCommand::EndTurn => {
+                let player_id = self.gt_current_player()?;
+                self.battle.gc_next_turn();
+                self.gt_deal_current();
+                Ok(format!("{} 结束回合", player_id))
+            }
+            _ => Err("本地卡牌对战只支持 play / end".to_string()),
+        }
+    }
+}
+
+// =============================================================================
+// 战斗沙盘
+// =============================================================================
+
+/// 本地战斗沙盘
+pub struct GtLocalArena {
+    arena: GcBattleArena,
+    /// 手牌区怪兽
+    bench: Vec<GcMonster>,
+    /// 已进行的战斗轮数
+    rounds: u32,
+    /// 双方累计受到的直接伤害 (我方, 敌方)
+    player_damage: (u32, u32),
+}
+
+impl GtLocalArena {
+    /// 创建沙盘: 手牌区为全部 1 阶怪兽，敌方前排放置相同阵容
+    pub fn gt_new() -> Self {
+        let pool = GcMonsterPool::with_defaults();
+        let templates = pool.templates_by_tier(GcMonsterTier::Tier1);
+        let spawn = |prefix: &str, i: usize| {
+            let t = templates[i];
+            GcMonster::new_with_template(
+                &format!("{}{}", prefix, i),
+                &t.template_id,
+                &t.name,
+                t.level,
+                t.attribute,
+                t.base_atk,
+                t.base_def,
+                t.base_hp,
+            )
+        };
+
+        let mut arena = GcBattleArena::new(GcTerrainType::Plain, GcTerrainType::Plain);
+        for (slot, monster) in arena.enemy_monsters.iter_mut().enumerate().take(templates.len()) {
+            let mut enemy = spawn("e", slot);
+            enemy.slot = Some(slot as u8);
+            *monster = Some(enemy);
+        }
+        let bench = (0..templates.len()).map(|i| spawn("m", i)).collect();
+
+        Self { arena, bench, rounds: 0, player_damage: (0, 0) }
+    }
+
+    /// 移除阵亡怪兽
+    fn gt_clear_dead(&mut self) {
+        for slot in self.arena.player_monsters.iter_mut()
+            .chain(self.arena.player_back_row.iter_mut())
+            .chain(self.arena.enemy_monsters.iter_mut())
+            .chain(self.arena.enemy_back_row.iter_mut())
+        {
+            slot.take_if(|m| !m.is_alive());
+        }
+    }
+}
+
+impl GtSession for GtLocalArena {
+    fn gt_render(&self) -> String {
+        let mut out = format!("== 沙盘 | 第 {} 轮 ==\n", self.rounds);
+        out.push_str(&self.arena.gc_to_ascii());
+        out.push_str("手牌区\n");
+        for (i, monster) in self.bench.iter().enumerate() {
+            let _ = writeln!(out, "  [{}] {} ★{} 攻{} 生命{} ({})", i, monster.name, monster.star, monster.base_atk, monster.max_hp, monster.id);
+        }
+        let _ = writeln!(out, "直接伤害: 我方受到 {} / 敌方受到 {}", self.player_damage.0, self.player_damage.1);
+        out
+    }
+
+    fn gt_execute(&mut self, command: &GtCommand) -> Result<String, String> {
+        match command {
+            GtCommand::Deploy { monster, position } => {
+                let monster_id = gt_resolve_monster(&self.bench, monster);
+                self.arena.deploy_from_bench_to(&mut self.bench, &monster_id, *position)?;
+                Ok(format!("部署 {}", monster_id))
+            }
+            GtCommand::Recall { position } => self.arena.recall_from(&mut self.bench, *position)
+                .map(|monster| format!("撤回 {}", monster.id))
+                .ok_or_else(|| "该位置没有怪兽".to_string()),
+            GtCommand::Fight => {
+                let result = self.arena.run_combat_round(None);
+                self.rounds += 1;
+                let mut out = String::new();
+                for tick in result.player_structures.iter().chain(&result.enemy_structures) {
+                    let _ = writeln!(out, "{}", tick.description);
+                }
+                for action in &result.actions {
+                    let outcome = &action.outcome;
+                    let side = match action.side {
+                        GcArenaSide::Player => "我方",
+                        GcArenaSide::Enemy => "敌方",
+                    };
+                    let target = outcome.target_name.as_deref().unwrap_or("玩家");
+                    let _ = write!(out, "{} {} → {} 伤害 {}", side, outcome.attacker_name, target, outcome.damage);
+                    if outcome.target_destroyed {
+                        out.push_str(" (击破)");
+                    }
+                    out.push('\n');
+                    match action.side {
+                        GcArenaSide::Player => self.player_damage.1 += outcome.player_damage,
+                        GcArenaSide::Enemy => self.player_damage.0 += outcome.player_damage,
+                    }
+                }
+                self.gt_clear_dead();
+                if result.actions.is_empty() {
+                    out.push_str("双方都没有可行动的怪兽\n");
+                }
+                Ok(out.trim_end().to_string())
+            }
+            _ => Err("本地沙盘只支持 deploy / recall / fight".to_string()),
+        }
+    }
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use game_core::GcArenaPosition;
+
+    #[test]
+    fn test_local_battle_hot_seat() {
+        let mut session = GtLocalBattle::gt_new();
+        assert_eq!(session.gt_state().gc_current_player_id(), Some("p1"));
+        assert!(session.gt_render().contains("[0] 攻击"));
+
+        let played = session.gt_execute(&GtCommand::Play { card: "0".to_string(), target: None });
+        assert!(played.is_ok(), "{:?}", played);
+        let p2 = session.gt_state().gc_find_player("p2").expect("p2");
+        assert!(p2.stats.hp < p2.stats.max_hp);
+        assert!(session.gt_execute(&GtCommand::Play { card: "0".to_string(), target: None }).is_err());
+
+        session.gt_execute(&GtCommand::EndTurn).expect("结束回合");
+        assert_eq!(session.gt_state().gc_current_player_id(), Some("p2"));
+        assert_eq!(session.gt_state().gc_find_player("p2").map(|p| p.hand.len()), Some(1));
+        assert!(session.gt_execute(&GtCommand::Fight).is_err());
+    }
+
+    #[test]
+    fn test_local_arena_deploy_and_fight() {
+        let mut session = GtLocalArena::gt_new();
+        let bench = session.bench.len();
+        assert!(bench > 0);
+
+        session.gt_execute(&GtCommand::Deploy { monster: "0".to_string(), position: GcArenaPosition::front(0) })
+            .expect("部署");
+        assert_eq!(session.bench.len(), bench - 1);
+        assert!(session.gt_execute(&GtCommand::Deploy { monster: "0".to_string(), position: GcArenaPosition::front(0) }).is_err());
+
+        let log = session.gt_execute(&GtCommand::Fight).expect("战斗");
+        assert!(log.contains("伤害"));
+        assert_eq!(session.rounds, 1);
+        assert!(session.gt_render().contains("第 1 轮"));
+
+        if session.arena.player_monsters[0].is_some() {
+            session.gt_execute(&GtCommand::Recall { position: GcArenaPosition::front(0) }).expect("撤回");
+        }
+        assert!(session.gt_execute(&GtCommand::Recall { position: GcArenaPosition::back(4) }).is_err());
+    }
+}
@@ -0,0 +1,146 @@
+//! 远程会话 (通过 WebSocket 连接 game-server)
+//!
+//! 模块: game-tui
+//! 前缀: Gt
+//! 文档: 文档/13-game-tui.md
+//!
+//! 连接后自动登录；终端命令转换为协议消息 (`{"type": ..., "data": ...}`) 发送，
+//! 收到 `GameStarted` / `GameState` 时以本人视角打印战斗状态，其余消息打印类型与内容。
+
+use futures_util::{SinkExt, StreamExt};
+use game_core::{gc_parse_json, GcBattleState};
+use serde_json::{json, Value};
+use tokio::sync::mpsc;
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+
+use crate::gt_command::{gt_parse_command, gt_resolve_card, GtCommand, GT_HELP};
+
+/// 命令转换为协议消息 (本地专用命令返回错误)
+pub fn gt_command_message(command: &GtCommand, player_id: &str, battle: Option<&GcBattleState>) -> Result<Value, String> {
+    let message = match command {
+        GtCommand::CreateRoom { name } => json!({ "type": "CreateRoom", "data": { "name": name } }),
+        GtCommand::JoinRoom { room_id } => json!({ "type": "JoinRoom", "data": { "room_id": room_id } }),
+        GtCommand::Ready => json!({ "type": "Ready" }),
+        GtCommand::StartGame => json!({ "type": "StartGame" }),
+        GtCommand::EndTurn => json!({ "type": "EndTurn" }),
+        GtCommand::Play { card, target } => {
+            let card_id = battle
+                .map(|state| gt_resolve_card(state, player_id, card))
+                .unwrap_or_else(|| card.clone());
+            json!({ "type": "PlayCard", "data": { "card_id": card_id, "target_id": target } })
+        }
+        _ => return Err("连接服务器时不支持该命令".to_string()),
+    };
+    Ok(message)
+}
+
+/// 连接服务器并进入命令循环
+pub async fn gt_run_remote(url: &str, player_id: &str, name: &str) -> anyhow::Result<()> {
+    let (socket, _) = connect_async(url).await?;
+    let (mut sender, mut receiver) = socket.split();
+    println!("已连接 {}", url);
+
+    let login = json!({ "type": "Login", "data": { "player_id": player_id, "name": name } });
+    sender.send(Message::Text(login.to_string())).await?;
+
+    // 标准输入在独立线程中阻塞读取
+    let (line_tx, mut line_rx) = mpsc::unbounded_channel::<String>();
+    std::thread::spawn(move || {
+        for line in std::io::stdin().lines() {
+            let Ok(line) = line else { break };
+            if line_tx.send(line).is_err() {
+                break;
+            }
+        }
+    });
+
+    let mut battle: Option<GcBattleState> = None;
+    loop {
+        tokio::select! {
+            line = line_rx.recv() => {
+                let Some(line) = line else { break };
+                let Some(command) = gt_parse_command(&line) else { continue };
+                match command {
+                    Ok(GtCommand::Quit) => break,
+                    Ok(GtCommand::Help) => println!("{}", GT_HELP),
+                    Ok(GtCommand::Show) => match &battle {
+                        Some(state) => print!("{}", state.gc_to_ascii(Some(player_id))),
+                        None => println!("尚未收到战斗状态"),
+                    },
+                    Ok(command) => match gt_command_message(&command, player_id, battle.as_ref()) {
+                        Ok(message) => sender.send(Message::Text(message.to_string())).await?,
+                        Err(e) => println!("错误: {}", e),
+                    },
+                    Err(e) => println!("错误: {}", e),
+                }
+            }
+            msg = receiver.next() => {
+                let Some(msg) = msg else {
+                    println!("服务器已断开");
+                    break;
+                };
+                match msg? {
+                    Message::Text(text) => gt_print_message(&text, player_id, &mut battle),
+                    Message::Close(_) => {
+                        println!("服务器已断开");
+                        break;
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// 打印服务器消息 (战斗状态以 ASCII 显示并缓存)
+fn gt_print_message(text: &str, player_id: &str, battle: &mut Option<GcBattleState>) {
+    let Ok(value) = serde_json::from_str::<Value>(text) else {
+        println!("<< {}", text);
+        return;
+    };
+    let kind = value["type"].as_str().unwrap_or("?");
+    let data = &value["data"];
+
+    match kind {
+        "GameStarted" | "GameState" => {
+            let parsed = data["battle_state"].as_str()
+                .ok_or_else(|| "缺少 battle_state".to_string())
+                .and_then(|s| gc_parse_json::<GcBattleState>("battle_state", s).map_err(|e| e.to_string()));
+            match parsed {
+                Ok(state) => {
+                    print!("{}", state.gc_to_ascii(Some(player_id)));
+                    *battle = Some(state);
+                }
+                Err(e) => println!("<< {} (无法解析战斗状态: {})", kind, e),
+            }
+        }
+        "Error" => println!("<< 错误 [{}] {}", data["code"].as_str().unwrap_or("?"), data["message"].as_str().unwrap_or("")),
+        _ if data.is_null() => println!("<< {}", kind),
+        _ => println!("<< {} {}", kind, data),
+    }
+}
+
+// =============================================================================
+// 测试
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use game_core::{GcCard, GcPlayer};
+
+    #[test]
+    fn test_command_messages() {
+        let mut player = GcPlayer::gc_new("p1", "玩家1");
+        player.hand.push(GcCard::gc_new_attack("card_a", "攻击", 1, 10));
+        let state = GcBattleState::gc_new("b", vec![player]);
+
+        let play = GtCommand::Play { card: "0".to_string(), target: None };
+        let message = gt_command_message(&play, "p1", Some(&state)).expect("出牌消息");
+        assert_eq!(message, json!({ "type": "PlayCard", "data": { "card_id": "card_a", "target_id": null } }));
+
+        assert_eq!(gt_command_message(&GtCommand::Ready, "p1", None), Ok(json!({ "type": "Ready" })));
+        assert!(gt_command_message(&GtCommand::Fight, "p1", None).is_err());
+    }
+}
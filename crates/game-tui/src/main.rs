@@ -0,0 +1,70 @@
+//! 终端调试客户端入口
+//!
+//! 模块: game-tui
+//! 前缀: gt_
+//! 文档: 文档/13-game-tui.md
+//!
+//! 用法:
+//! - `tui` / `tui battle`: 本地卡牌对战 (热座)
+//! - `tui arena`: 本地战斗沙盘
+//! - `tui remote <ws地址> <玩家ID> [名称]`: 连接服务器，如 `tui remote ws://localhost:3000/ws p1`
+
+mod gt_command;
+mod gt_local;
+mod gt_remote;
+
+use std::io::Write;
+
+use gt_command::{gt_parse_command, GtCommand, GT_HELP};
+use gt_local::{GtLocalArena, GtLocalBattle, GtSession};
+
+const GT_USAGE: &str = "用法: tui [battle | arena | remote <ws地址> <玩家ID> [名称]]";
+
+fn main() -> anyhow::Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    match args.as_slice() {
+        [] | ["battle"] => gt_run_local(GtLocalBattle::gt_new()),
+        ["arena"] => gt_run_local(GtLocalArena::gt_new()),
+        ["remote", url, player_id, rest @ ..] if rest.len() <= 1 => {
+            let name = rest.first().copied().unwrap_or(player_id);
+            tokio::runtime::Runtime::new()?.block_on(gt_remote::gt_run_remote(url, player_id, name))
+        }
+        _ => {
+            eprintln!("{}", GT_USAGE);
+            std::process::exit(2);
+        }
+    }
+}
+
+/// 本地会话命令循环
+fn gt_run_local(mut session: impl GtSession) -> anyhow::Result<()> {
+    println!("{}\n", GT_HELP);
+    print!("{}", session.gt_render());
+
+    let mut line = String::new();
+    loop {
+        print!("> ");
+        std::io::stdout().flush()?;
+        line.clear();
+        if std::io::stdin().read_line(&mut line)? == 0 {
+            return Ok(());
+        }
+
+        match gt_parse_command(&line) {
+            None => {}
+            Some(Ok(GtCommand::Quit)) => return Ok(()),
+            Some(Ok(GtCommand::Help)) => println!("{}", GT_HELP),
+            Some(Ok(GtCommand::Show)) => print!("{}", session.gt_render()),
+            Some(Ok(command)) => match session.gt_execute(&command) {
+                Ok(message) => {
+                    println!("{}", message);
+                    print!("{}", session.gt_render());
+                }
+                Err(e) => println!("错误: {}", e),
+            },
+            Some(Err(e)) => println!("错误: {}", e),
+        }
+    }
+}
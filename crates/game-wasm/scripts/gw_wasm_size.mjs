@@ -0,0 +1,49 @@
+/**
+ * WASM 包体积记录
+ *
+ * 模块: game-wasm
+ * 前缀: gw_
+ * 文档: 文档/02-game-wasm.md
+ *
+ * 用法: node scripts/gw_wasm_size.mjs <lite|full>
+ * 读取 pkg/game_wasm_bg.wasm，记录原始大小与 gzip 大小到 wasm-size.json，并与上次记录比较
+ */
+
+import { existsSync, readFileSync, writeFileSync } from 'node:fs';
+import { dirname, join } from 'node:path';
+import { fileURLToPath } from 'node:url';
+import { gzipSync } from 'node:zlib';
+
+const root = join(dirname(fileURLToPath(import.meta.url)), '..');
+const wasmPath = join(root, 'pkg', 'game_wasm_bg.wasm');
+const recordPath = join(root, 'wasm-size.json');
+
+const profile = process.argv[2];
+if (profile !== 'lite' && profile !== 'full') {
+    console.error('用法: node scripts/gw_wasm_size.mjs <lite|full>');
+    process.exit(1);
+}
+if (!existsSync(wasmPath)) {
+    console.error(`找不到 ${wasmPath}，请先运行 wasm-pack build`);
+    process.exit(1);
+}
+
+const wasm = readFileSync(wasmPath);
+const entry = {
+    bytes: wasm.length,
+    gzip_bytes: gzipSync(wasm, { level: 9 }).length,
+    measured_at: new Date().toISOString(),
+};
+
+const record = existsSync(recordPath) ? JSON.parse(readFileSync(recordPath, 'utf8')) : {};
+const previous = record[profile];
+record[profile] = entry;
+writeFileSync(recordPath, JSON.stringify(record, null, 2) + '\n');
+
+const kb = (bytes) => `${(bytes / 1024).toFixed(1)} KB`;
+const delta = (now, before) => {
+    if (before === undefined) return '';
+    const diff = now - before;
+    return ` (${diff >= 0 ? '+' : '-'}${kb(Math.abs(diff))})`;
+};
+console.log(`📦 ${profile}: ${kb(entry.bytes)}${delta(entry.bytes, previous?.bytes)}, gzip ${kb(entry.gzip_bytes)}${delta(entry.gzip_bytes, previous?.gzip_bytes)}`);
@@ -7,6 +7,14 @@
 use wasm_bindgen::prelude::*;
 use game_core::*;
 use crate::gw_utils::*;
+use crate::GwOperationResult;
+
+/// 沙盒最多保留的历史快照数
+const GW_SANDBOX_HISTORY_LIMIT: usize = 200;
+
+/// 调试时间线最多保留的步数
+#[cfg(feature = "dev-tools")]
+const GW_DEBUG_TIMELINE_LIMIT: usize = 2000;
 
 // =============================================================================
 // GwBattle - 战斗管理器
@@ -16,6 +24,11 @@ use crate::gw_utils::*;
 #[wasm_bindgen]
 pub struct GwBattle {
     state: GcBattleState,
+    /// 沙盒撤销历史
+    history: GcBattleHistory,
+    /// 调试时间线 (每个操作之后的事件与快照)
+    #[cfg(feature = "dev-tools")]
+    timeline: GcBattleTimeline,
 }
 
 #[wasm_bindgen]
@@ -25,28 +38,44 @@ impl GwBattle {
     pub fn new(battle_id: &str) -> Self {
         Self {
             state: GcBattleState::gc_new(battle_id.to_string(), Vec::new()),
+            history: GcBattleHistory::gc_new(GW_SANDBOX_HISTORY_LIMIT),
+            #[cfg(feature = "dev-tools")]
+            timeline: GcBattleTimeline::gc_new(GW_DEBUG_TIMELINE_LIMIT),
         }
     }
 
-    /// 添加玩家
+    /// 创建沙盒战斗 (允许通过 sandbox_* 方法任意编辑状态)
+    #[wasm_bindgen(js_name = newSandbox)]
+    pub fn new_sandbox(battle_id: &str) -> Self {
+        let mut battle = Self::new(battle_id);
+        battle.state.sandbox = true;
+        battle
+    }
+
+    /// 添加玩家 (最多 4 人，3 人以上为自由混战)
     pub fn add_player(&mut self, id: &str, name: &str) -> bool {
         let player = GcPlayer::gc_new(id.to_string(), name.to_string());
-        
-        if self.state.players.len() < 2 {
-            self.state.players.push(player);
-            gw_log(&format!("玩家 {} 加入战斗", name));
-            true
-        } else {
-            gw_warn("战斗已满员");
-            false
-        }
+        self.push_player(player)
     }
 
-    /// 开始战斗
+    /// 添加组队玩家 (4 人且带队伍时为 2v2，队伍为 0 或 1)
+    pub fn add_team_player(&mut self, id: &str, name: &str, team: u32) -> bool {
+        let mut player = GcPlayer::gc_new(id.to_string(), name.to_string());
+        player.team = Some(team);
+        self.push_player(player)
+    }
+
+    /// 开始战斗 (按人数与队伍推断赛制并安排座位)
     pub fn start(&mut self) -> bool {
-        if self.state.players.len() != 2 {
-            gw_warn("需要2名玩家才能开始");
-            return false;
+        let format = GcBattleFormat::gc_infer(&self.state.players);
+        let players = std::mem::take(&mut self.state.players);
+        match gc_arrange_seats(format, players.clone()) {
+            Ok(seats) => self.state.players = seats,
+            Err(e) => {
+                self.state.players = players;
+                gw_warn(&format!("无法开始战斗: {}", e));
+                return false;
+            }
         }
 
         self.state.phase = GcBattlePhase::Playing;
@@ -88,6 +117,7 @@ impl GwBattle {
             player.gc_draw_cards(5);
         }
         
+        self.debug_record(|state| GcBattleEvent::Genesis { state: Box::new(state.clone()) });
         gw_log("⚔️ 战斗开始!");
         true
     }
@@ -122,6 +152,21 @@ impl GwBattle {
         self.state.winner_id.clone()
     }
 
+    /// 获胜方全部玩家 ID (组队时包含队友)
+    pub fn winner_ids(&self) -> Vec<String> {
+        self.state.gc_winner_ids()
+    }
+
+    /// 各队伍概况 (不分队伍时为空数组)
+    pub fn get_teams(&self) -> Result<JsValue, JsValue> {
+        gw_to_js_value(&self.state.gc_teams())
+    }
+
+    /// 对局统计 (伤害、治疗、击杀、死亡、助攻，按座位顺序)
+    pub fn get_match_stats(&self) -> Result<JsValue, JsValue> {
+        gw_to_js_value(&gc_match_stats(&self.state))
+    }
+
     /// 出牌
     pub fn play_card(
         &mut self,
@@ -142,11 +187,70 @@ impl GwBattle {
             
             // 检查是否有人死亡
             self.check_battle_end();
+            self.debug_record(|_| GcBattleEvent::CardPlayed {
+                player_id: player_id.to_string(),
+                card_id: card_id.to_string(),
+                target_id: target_id.to_string(),
+            });
         }
         
         gw_to_json(&result)
     }
 
+    /// 使用英雄技能 (每回合一次)
+    pub fn use_skill(
+        &mut self,
+        player_id: &str,
+        skill_id: &str,
+        target_id: &str,
+    ) -> Result<String, JsValue> {
+        if let Err(e) = gc_validate_use_skill(&self.state, player_id, skill_id, target_id) {
+            return Err(JsValue::from_str(&e.to_string()));
+        }
+
+        let result = gc_execute_use_skill(&mut self.state, player_id, skill_id, target_id);
+
+        if result.success {
+            gw_log(&format!("玩家 {} 使用技能 {}", player_id, skill_id));
+            self.check_battle_end();
+            self.debug_record(|_| GcBattleEvent::SkillUsed {
+                player_id: player_id.to_string(),
+                skill_id: skill_id.to_string(),
+                target_id: target_id.to_string(),
+            });
+        }
+
+        gw_to_json(&result)
+    }
+
+    /// 按模板开始引导 (多回合后在引导者回合开始时结算)
+    pub fn start_channel(
+        &mut self,
+        player_id: &str,
+        template_id: &str,
+        target_id: &str,
+    ) -> Result<(), JsValue> {
+        match gc_start_channel_from_template(&mut self.state, player_id, template_id, target_id) {
+            Ok(()) => {
+                self.debug_record(|_| GcBattleEvent::ChannelStarted {
+                    player_id: player_id.to_string(),
+                    template_id: template_id.to_string(),
+                    target_id: target_id.to_string(),
+                });
+                gw_log(&format!("玩家 {} 开始引导 {}", player_id, template_id));
+                Ok(())
+            }
+            Err(e) => Err(JsValue::from_str(&e.to_string()))
+        }
+    }
+
+    /// 获取玩家英雄技能栏 (JSON)
+    pub fn get_hero_skills(&self, player_id: &str) -> Result<String, JsValue> {
+        let player = self.state.gc_find_player(player_id)
+            .ok_or_else(|| JsValue::from_str("玩家不存在"))?;
+        gw_to_json(&player.hero_skills)
+    }
+
     /// 结束回合 (会自动执行战场战斗)
     pub fn end_turn(&mut self, player_id: &str) -> Result<JsValue, JsValue> {
         // 验证是否是当前玩家
@@ -159,6 +263,9 @@ impl GwBattle {
         
         // 执行战场战斗
         let combat_result = self.state.gc_execute_turn_combat(player_id);
+        if combat_result.is_some() {
+            self.debug_record(|_| GcBattleEvent::TurnCombatResolved { player_id: player_id.to_string() });
+        }
         
         // 检查战斗是否因战斗结束
         if self.state.gc_is_finished() {
@@ -166,11 +273,21 @@ impl GwBattle {
             return gw_to_js_value(&combat_result);
         }
 
-        // 切换玩家
-        self.state.current_player_index = (self.state.current_player_index + 1) % self.state.players.len();
+        // 结束本回合的费用修正
+        gc_expire_cost_modifiers(&mut self.state, player_id);
+
+        // 切换到下一个可行动的玩家 (多人战斗跳过已淘汰的玩家)
+        let player_count = self.state.players.len();
+        let previous_index = self.state.current_player_index;
+        if let Some(next_index) = (1..=player_count)
+            .map(|i| (previous_index + i) % player_count)
+            .find(|&i| self.state.players[i].gc_can_act())
+        {
+            self.state.current_player_index = next_index;
+        }
         
-        // 如果回到第一个玩家，增加回合数
-        if self.state.current_player_index == 0 {
+        // 如果绕回行动顺序的开头，增加回合数
+        if self.state.current_player_index <= previous_index {
             self.state.turn += 1;
         }
 
@@ -181,6 +298,7 @@ impl GwBattle {
             player.gc_draw_cards(GcConfig::DRAW_PER_TURN);
         }
 
+        self.debug_record(|_| GcBattleEvent::TurnAdvanced);
         gw_log(&format!("回合结束，现在是玩家 {} 的回合", self.state.current_player_index));
         gw_to_js_value(&combat_result)
     }
@@ -204,6 +322,153 @@ impl GwBattle {
             .unwrap_or_else(|| Err(JsValue::from_str("玩家不存在")))
     }
     
+    // =========================================================================
+    // 牌区查看相关方法
+    // =========================================================================
+
+    /// 获取玩家牌区 (own_view 为 true 时含手牌/牌库/弃牌堆内容，否则只有数量)
+    pub fn get_player_zones(&self, player_id: &str, own_view: bool) -> Result<JsValue, JsValue> {
+        let player = self.state.gc_find_player(player_id)
+            .ok_or_else(|| JsValue::from_str("玩家不存在"))?;
+        let privacy = if own_view { GcZonePrivacy::Owner } else { GcZonePrivacy::Opponent };
+        gw_to_js_value(&GcPlayerZones::gc_new(player, privacy))
+    }
+
+    /// 获取查看者视角下全部玩家的牌区 (viewer_id 为空表示观战)
+    pub fn get_all_zones(&self, viewer_id: Option<String>) -> Result<JsValue, JsValue> {
+        gw_to_js_value(&gc_battle_zones(&self.state, viewer_id.as_deref()))
+    }
+
+    /// 获取手牌数量
+    pub fn get_hand_count(&self, player_id: &str) -> usize {
+        self.state.gc_find_player(player_id).map(|p| p.hand.len()).unwrap_or(0)
+    }
+
+    /// 获取牌库数量
+    pub fn get_deck_count(&self, player_id: &str) -> usize {
+        self.state.gc_find_player(player_id).map(|p| p.deck.len()).unwrap_or(0)
+    }
+
+    /// 获取弃牌堆数量
+    pub fn get_discard_count(&self, player_id: &str) -> usize {
+        self.state.gc_find_player(player_id).map(|p| p.discard.len()).unwrap_or(0)
+    }
+
+    // =========================================================================
+    // 局面评估相关方法
+    // =========================================================================
+
+    /// 获取玩家胜率估算 (0~1，观战动量条用)
+    pub fn get_win_probability(&self, player_id: &str) -> f64 {
+        gc_win_probability(&self.state, player_id) as f64 / GC_PER_MILLE as f64
+    }
+
+    /// 获取全部玩家胜率 ([{ player_id, per_mille }])
+    pub fn get_win_probabilities(&self) -> Result<JsValue, JsValue> {
+        gw_to_js_value(&gc_win_probabilities(&self.state))
+    }
+
+    /// 获取玩家视角的局面分 (正数为优势)
+    pub fn get_evaluation(&self, player_id: &str) -> f64 {
+        gc_evaluate_battle(&self.state, player_id) as f64
+    }
+
+    /// 获取行动提示 (按推荐程度排序，limit 为 0 时返回全部)
+    pub fn get_hint(&self, player_id: &str, limit: usize) -> Result<JsValue, JsValue> {
+        let mut hints = gc_suggest_action(&self.state, player_id);
+        if limit > 0 {
+            hints.truncate(limit);
+        }
+        gw_to_js_value(&hints)
+    }
+
+    /// 说明操作为何不合法 (action_json 为 GcAction，空数组表示合法)
+    pub fn explain_action(&self, action_json: &str) -> Result<JsValue, JsValue> {
+        let action: GcAction = gw_parse_json("action_json", action_json)?;
+        gw_to_js_value(&gc_explain_legality(&self.state, &action))
+    }
+
+    /// 预览操作的结果 (悬停预览，不修改战斗状态)，返回 GcActionPreview
+    pub fn preview_action(&self, action_json: &str) -> Result<JsValue, JsValue> {
+        let action: GcAction = gw_parse_json("action_json", action_json)?;
+        gw_to_js_value(&gc_preview_action(&self.state, &action))
+    }
+
+    /// 本回合能否斩杀 (界面 "斩杀!" 提示)，返回 GcLethalCheck (含斩杀序列与最大伤害)
+    pub fn find_lethal(&self, player_id: &str) -> Result<JsValue, JsValue> {
+        gw_to_js_value(&gc_find_lethal(&self.state, player_id))
+    }
+
+    // =========================================================================
+    // 分页与窗口相关方法 (低端设备不必一次反序列化完整状态)
+    // =========================================================================
+
+    /// 获取战斗窗口 (request_json: GcBattleWindowRequest，视野内玩家、单位与最近日志)
+    pub fn get_battle_window(&self, request_json: &str) -> Result<JsValue, JsValue> {
+        let request: GcBattleWindowRequest = gw_parse_json("request_json", request_json)?;
+        gw_to_js_value(&gc_battle_window(&self.state, &request))
+    }
+
+    /// 获取手牌的一页 (offset 为空表示最后 limit 张；非本人查看时只有数量)
+    pub fn get_hand_page(
+        &self,
+        owner_id: &str,
+        viewer_id: Option<String>,
+        offset: Option<usize>,
+        limit: usize,
+    ) -> Result<JsValue, JsValue> {
+        let page = gc_hand_page(&self.state, viewer_id.as_deref(), owner_id, GcPageRequest { offset, limit })
+            .ok_or_else(|| JsValue::from_str("玩家不存在"))?;
+        gw_to_js_value(&page)
+    }
+
+    /// 获取战斗日志的一页 (offset 为空表示最后 limit 条)
+    pub fn get_combat_log_page(&self, offset: Option<usize>, limit: usize) -> Result<JsValue, JsValue> {
+        gw_to_js_value(&gc_combat_log_page(&self.state, GcPageRequest { offset, limit }))
+    }
+
+    // =========================================================================
+    // 卡牌费用相关方法
+    // =========================================================================
+
+    /// 获取玩家手牌的实际费用 (含减费/加费修正)
+    pub fn get_hand_costs(&self, player_id: &str) -> Result<JsValue, JsValue> {
+        if self.state.gc_find_player(player_id).is_none() {
+            return Err(JsValue::from_str("玩家不存在"));
+        }
+        gw_to_js_value(&gc_hand_costs(&self.state, player_id))
+    }
+
+    /// 获取手牌中某张卡牌的实际费用
+    pub fn get_card_cost(&self, player_id: &str, card_id: &str) -> Option<u32> {
+        let card = self.state.gc_find_player(player_id)?.gc_find_card_in_hand(card_id)?;
+        Some(gc_effective_card_cost(&self.state, player_id, card))
+    }
+
+    /// 获取手牌中某张卡牌的描述 (数值按玩家当前属性代入)，返回 GcCardText
+    pub fn get_card_text(&self, player_id: &str, card_id: &str) -> Result<JsValue, JsValue> {
+        let player = self.state.gc_find_player(player_id).ok_or_else(|| JsValue::from_str("玩家不存在"))?;
+        let card = player.gc_find_card_in_hand(card_id).ok_or_else(|| JsValue::from_str("卡牌不在手牌中"))?;
+        gw_to_js_value(&gc_render_card_text(card, &GcCardTextContext::gc_for_player(player)))
+    }
+
+    /// 添加费用修正 (JSON 格式的 GcCostModifier)
+    pub fn add_cost_modifier(&mut self, modifier_json: &str) -> Result<(), JsValue> {
+        let modifier: GcCostModifier = gw_parse_json("modifier_json", modifier_json)?;
+        gc_add_cost_modifier(&mut self.state, modifier.clone()).map_err(|e| JsValue::from_str(&e.to_string()))?;
+        self.debug_record(|_| GcBattleEvent::CostModifierAdded { modifier });
+        Ok(())
+    }
+
+    /// 移除指定来源的费用修正 (光环结束)，返回移除数量
+    pub fn remove_cost_modifiers_from(&mut self, source: &str) -> usize {
+        let removed = gc_remove_cost_modifiers_from(&mut self.state, source);
+        if removed > 0 {
+            self.debug_record(|_| GcBattleEvent::CostModifiersRemoved { source: source.to_string() });
+        }
+        removed
+    }
+
     // =========================================================================
     // 公共卡池相关方法
     // =========================================================================
@@ -222,6 +487,11 @@ impl GwBattle {
     pub fn acquire_card(&mut self, player_id: &str, card_id: &str) -> Result<String, JsValue> {
         match self.state.gc_acquire_card_from_pool(player_id, card_id) {
             Ok(card) => {
+                self.debug_record(|state| GcBattleEvent::CardAcquired {
+                    player_id: player_id.to_string(),
+                    card_id: card_id.to_string(),
+                    card_pool: Box::new(state.card_pool.clone()),
+                });
                 gw_log(&format!("🃏 {} 获取了卡牌: {}", player_id, card.name));
                 gw_to_json(&card)
             }
@@ -233,6 +503,10 @@ impl GwBattle {
     pub fn refresh_pool(&mut self, player_id: &str) -> Result<(), JsValue> {
         match self.state.gc_refresh_pool(player_id) {
             Ok(()) => {
+                self.debug_record(|state| GcBattleEvent::PoolRefreshed {
+                    player_id: player_id.to_string(),
+                    card_pool: Box::new(state.card_pool.clone()),
+                });
                 gw_log(&format!("🔄 {} 刷新了卡池", player_id));
                 Ok(())
             }
@@ -277,6 +551,11 @@ impl GwBattle {
     ) -> Result<(), JsValue> {
         match self.state.gc_deploy_card(player_id, card_id, slot_index) {
             Ok(()) => {
+                self.debug_record(|_| GcBattleEvent::CardDeployed {
+                    player_id: player_id.to_string(),
+                    card_id: card_id.to_string(),
+                    slot_index,
+                });
                 gw_log(&format!("📦 {} 将卡牌部署到槽位 {}", player_id, slot_index));
                 Ok(())
             }
@@ -317,6 +596,7 @@ impl GwBattle {
     pub fn execute_turn_combat(&mut self, player_id: &str) -> Result<String, JsValue> {
         match self.state.gc_execute_turn_combat(player_id) {
             Some(result) => {
+                self.debug_record(|_| GcBattleEvent::TurnCombatResolved { player_id: player_id.to_string() });
                 gw_log(&format!(
                     "⚔️ 回合战斗: 对手受伤 {}, 己方受伤 {}",
                     result.opponent_damage_taken,
@@ -338,30 +618,174 @@ impl GwBattle {
     /// 执行回合结束战斗并返回 JS 对象
     pub fn execute_turn_combat_js(&mut self, player_id: &str) -> Result<JsValue, JsValue> {
         match self.state.gc_execute_turn_combat(player_id) {
-            Some(result) => gw_to_js_value(&result),
+            Some(result) => {
+                self.debug_record(|_| GcBattleEvent::TurnCombatResolved { player_id: player_id.to_string() });
+                gw_to_js_value(&result)
+            }
             None => Err(JsValue::from_str("无法执行战斗"))
         }
     }
+
+    // =========================================================================
+    // 沙盒模式 (仅 newSandbox 创建的战斗可用)
+    // =========================================================================
+
+    /// 是否为沙盒战斗
+    #[wasm_bindgen(getter)]
+    pub fn is_sandbox(&self) -> bool {
+        self.state.sandbox
+    }
+
+    /// 执行沙盒命令 (GcSandboxCommand JSON)，返回新的战斗状态 JSON
+    pub fn sandbox_apply(&mut self, command_json: &str) -> Result<String, JsValue> {
+        let command: GcSandboxCommand = gw_parse_json("command_json", command_json)?;
+        self.apply_sandbox(command)?;
+        gw_to_json(&self.state)
+    }
+
+    /// 设置玩家生命值
+    pub fn sandbox_set_hp(&mut self, player_id: &str, hp: u32) -> Result<(), JsValue> {
+        self.apply_sandbox(GcSandboxCommand::SetHp { player_id: player_id.to_string(), hp })
+    }
+
+    /// 将任意模板卡牌加入手牌
+    pub fn sandbox_add_card(&mut self, player_id: &str, template_id: &str) -> Result<(), JsValue> {
+        self.apply_sandbox(GcSandboxCommand::AddCardToHand {
+            player_id: player_id.to_string(),
+            template_id: template_id.to_string(),
+        })
+    }
+
+    /// 强制地形 (空字符串恢复默认)
+    pub fn sandbox_force_terrain(&mut self, terrain: &str) -> Result<(), JsValue> {
+        let terrain = (!terrain.is_empty()).then(|| crate::gw_terrain::string_to_terrain(terrain));
+        self.apply_sandbox(GcSandboxCommand::ForceTerrain { terrain })
+    }
+
+    /// 跳过回合 (不结算战场战斗)
+    pub fn sandbox_skip_turn(&mut self, count: u32) -> Result<(), JsValue> {
+        self.apply_sandbox(GcSandboxCommand::SkipTurn { count })
+    }
+
+    /// 撤销上一次沙盒编辑，返回是否成功
+    pub fn sandbox_undo(&mut self) -> bool {
+        match self.history.gc_undo() {
+            Some(state) => {
+                self.state = state;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// 重做被撤销的沙盒编辑，返回是否成功
+    pub fn sandbox_redo(&mut self) -> bool {
+        match self.history.gc_redo() {
+            Some(state) => {
+                self.state = state;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+// =============================================================================
+// 时间回溯调试 (dev-tools 功能)
+// =============================================================================
+
+#[cfg(feature = "dev-tools")]
+#[wasm_bindgen]
+impl GwBattle {
+    /// 调试时间线步数 (位置 0 为开局)
+    pub fn debug_timeline_len(&self) -> usize {
+        self.timeline.gc_len()
+    }
+
+    /// 调试面板当前查看的位置
+    pub fn debug_cursor(&self) -> usize {
+        self.timeline.gc_cursor()
+    }
+
+    /// 跳到指定位置 ({ index, len, event, state })，不修改当前战斗
+    pub fn debug_seek(&mut self, index: usize) -> Result<JsValue, JsValue> {
+        let frame = self.timeline.gc_seek(index)
+            .ok_or_else(|| JsValue::from_str("调试时间线为空"))?;
+        gw_to_js_value(&frame)
+    }
+
+    /// 从当前位置前进/后退若干步 (负数后退)
+    pub fn debug_step(&mut self, delta: i32) -> Result<JsValue, JsValue> {
+        let frame = self.timeline.gc_step(delta as i64)
+            .ok_or_else(|| JsValue::from_str("调试时间线为空"))?;
+        gw_to_js_value(&frame)
+    }
+
+    /// 导出指定位置的确切状态 (JSON，可用于复现问题)
+    pub fn debug_export(&self, index: usize) -> Result<String, JsValue> {
+        let state = self.timeline.gc_state_at(index)
+            .ok_or_else(|| JsValue::from_str("位置超出调试时间线"))?;
+        gw_to_json(&state)
+    }
+
+    /// 让战斗回到当前查看的位置继续 (之后的操作丢弃原来的后续步骤)
+    pub fn debug_rewind(&mut self) -> bool {
+        match self.timeline.gc_rewind() {
+            Some(state) => {
+                self.state = state;
+                gw_log(&format!("⏪ 调试: 回到第 {} 步", self.timeline.gc_cursor()));
+                true
+            }
+            None => false,
+        }
+    }
 }
 
 // 内部方法
 impl GwBattle {
-    /// 检查战斗是否结束
+    /// 记录调试时间线 (未启用 dev-tools 时不构造事件)
+    #[cfg_attr(not(feature = "dev-tools"), allow(unused_variables))]
+    fn debug_record(&mut self, event: impl FnOnce(&GcBattleState) -> GcBattleEvent) {
+        #[cfg(feature = "dev-tools")]
+        self.timeline.gc_record(event(&self.state), &self.state);
+    }
+
+    /// 执行沙盒命令并记录日志
+    fn apply_sandbox(&mut self, command: GcSandboxCommand) -> Result<(), JsValue> {
+        // 编辑前后各记录一次 (未变化的组件共享，撤销回到编辑前)
+        self.history.gc_record(&self.state);
+        gc_apply_sandbox_command(&mut self.state, &command)
+            .map_err(|e| JsValue::from_str(&e.to_string()))?;
+        self.history.gc_record(&self.state);
+        gw_log(&format!("🧪 沙盒: {:?}", command));
+        self.debug_record(|_| GcBattleEvent::SandboxEdited { command });
+        Ok(())
+    }
+
+    /// 添加玩家 (满员时拒绝)
+    fn push_player(&mut self, player: GcPlayer) -> bool {
+        if self.state.players.len() < GC_MAX_BATTLE_PLAYERS {
+            gw_log(&format!("玩家 {} 加入战斗", player.name));
+            self.state.players.push(player);
+            true
+        } else {
+            gw_warn("战斗已满员");
+            false
+        }
+    }
+
+    /// 检查战斗是否结束 (存活玩家只剩一方)
     fn check_battle_end(&mut self) {
-        let alive_players: Vec<_> = self.state.players
-            .iter()
-            .filter(|p| p.stats.gc_is_alive())
-            .collect();
+        self.state.gc_check_battle_end();
+        if !self.state.gc_is_finished() {
+            return;
+        }
 
-        if alive_players.len() <= 1 {
-            self.state.phase = GcBattlePhase::Finished;
-            
-            if let Some(winner) = alive_players.first() {
-                self.state.winner_id = Some(winner.id.clone());
-                gw_log(&format!("🏆 战斗结束! 获胜者: {}", winner.name));
-            } else {
-                gw_log("⚖️ 战斗结束! 平局");
-            }
+        let winners = self.state.gc_winner_ids();
+        if winners.is_empty() {
+            gw_log("⚖️ 战斗结束! 平局");
+        } else {
+            gw_log(&format!("🏆 战斗结束! 获胜者: {}", winners.join(", ")));
         }
     }
 }
@@ -391,3 +815,38 @@ pub fn gw_preview_damage(attacker_attack: u32, target_defense: u32, card_damage:
     let result = GcDamageResult::new(base_damage, defense_reduction, final_damage);
     gw_to_js_value(&result).unwrap_or(JsValue::NULL)
 }
+
+// =============================================================================
+// 卡牌收藏
+// =============================================================================
+
+/// 收藏统计 (拥有/闪卡完成度)
+#[wasm_bindgen]
+pub fn gw_collection_stats(collection_json: &str) -> JsValue {
+    let collection: Result<GcCardCollection, _> = gw_try_parse_json("collection_json", collection_json);
+    match collection {
+        Ok(c) => gw_to_js_or_null(&c.gc_stats()),
+        Err(_) => JsValue::NULL,
+    }
+}
+
+/// 用尘把一张普通副本制作为闪卡
+/// 返回: { success, error?, data: 更新后的收藏 JSON }
+#[wasm_bindgen]
+pub fn gw_craft_foil(collection_json: &str, template_id: &str) -> JsValue {
+    let collection: Result<GcCardCollection, _> = gw_try_parse_json("collection_json", collection_json);
+
+    let result = match collection {
+        Ok(mut c) => match c.gc_craft_foil(template_id) {
+            Ok(_) => GwOperationResult {
+                success: true,
+                error: None,
+                data: serde_json::to_string(&c).ok(),
+                diagnostic: None,
+            },
+            Err(e) => GwOperationResult { success: false, error: Some(e), data: None, diagnostic: None },
+        },
+        Err(diagnostic) => GwOperationResult::gw_json_failure(Some(diagnostic)),
+    };
+    gw_to_js_or_null(&result)
+}
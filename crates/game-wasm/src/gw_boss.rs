@@ -36,6 +36,14 @@ impl GwBoss {
         }
     }
     
+    /// 按存档难度缩放 Boss 属性与掉落 (GcDifficultySettings JSON)
+    #[wasm_bindgen(js_name = applyDifficulty)]
+    pub fn apply_difficulty(&mut self, difficulty_json: &str) -> Result<(), JsValue> {
+        let difficulty: GcDifficultySettings = gw_parse_json("difficulty_json", difficulty_json)?;
+        self.boss.gc_apply_difficulty(&difficulty);
+        Ok(())
+    }
+    
     // =========================================================================
     // 属性获取
     // =========================================================================
@@ -188,6 +196,50 @@ impl GwBoss {
         gw_log(&format!("🎯 {} 将攻击目标锁定为: {}", self.boss.name, org_id));
     }
     
+    // =========================================================================
+    // 仇恨
+    // =========================================================================
+    
+    /// 受到指定玩家伤害 (记录仇恨)
+    /// 返回 { actual_damage, died, threat }
+    #[wasm_bindgen(js_name = takeDamageFrom)]
+    pub fn take_damage_from(&mut self, player_id: &str, damage: u32) -> Result<JsValue, JsValue> {
+        let (actual_damage, died) = self.boss.gc_take_damage_from(player_id, damage);
+        gw_to_js_value(&serde_json::json!({
+            "actual_damage": actual_damage,
+            "died": died,
+            "threat": self.boss.threat.gc_threat_of(player_id),
+        }))
+    }
+    
+    /// 记录治疗仇恨
+    #[wasm_bindgen(js_name = recordHeal)]
+    pub fn record_heal(&mut self, player_id: &str, amount: u32) {
+        self.boss.gc_record_heal(player_id, amount);
+    }
+    
+    /// 嘲讽
+    #[wasm_bindgen]
+    pub fn taunt(&mut self, player_id: &str, turns: u32) {
+        self.boss.gc_taunt(player_id, turns);
+        gw_log(&format!("🛡️ {} 被 {} 嘲讽 ({} 回合)", self.boss.name, player_id, turns));
+    }
+    
+    /// 获取当前仇恨列表 (按仇恨降序)
+    #[wasm_bindgen(js_name = getThreat)]
+    pub fn get_threat(&self) -> Result<JsValue, JsValue> {
+        gw_to_js_value(&self.boss.threat.gc_inspect())
+    }
+    
+    /// 规划本回合行动 (按仇恨选择单体目标)
+    #[wasm_bindgen(js_name = planAction)]
+    pub fn plan_action(&self, alive_player_ids: Vec<String>) -> Result<JsValue, JsValue> {
+        match self.boss.gc_plan_action(&alive_player_ids) {
+            Some(action) => gw_to_js_value(&action),
+            None => Ok(JsValue::NULL),
+        }
+    }
+    
     /// 获取完整状态 JSON
     #[wasm_bindgen(js_name = getStateJson)]
     pub fn get_state_json(&self) -> Result<String, JsValue> {
@@ -244,6 +244,18 @@ pub fn gw_get_current_version() -> String {
     GC_SAVE_VERSION.to_string()
 }
 
+/// 计算本地内容版本 (JSON，登录时上报)
+/// 输入: 服务器激活的赛季 ID (来自 /api/content/version，空字符串为默认赛季)
+#[wasm_bindgen]
+pub fn gw_content_version(season_id: &str) -> String {
+    let mut seasons = GcSeasonManager::with_defaults();
+    if !season_id.is_empty() && !seasons.set_active_season_by_id(season_id) {
+        gw_set_last_error(format!("未知赛季: {}", season_id));
+    }
+    let version = GcContentVersion::gc_compute(seasons.current_season());
+    serde_json::to_string(&version).unwrap_or_default()
+}
+
 /// 检查存档版本兼容性
 /// 输入: 存档版本字符串如 "1.2.3"
 /// 返回: { current_version, save_version, is_compatible, needs_migration, needs_reset }
@@ -270,7 +282,7 @@ pub fn gw_check_save_version(save_version_str: &str) -> JsValue {
         needs_reset: save_version.needs_reset(&current),
     };
     
-    serde_wasm_bindgen::to_value(&result).unwrap_or(JsValue::NULL)
+    gw_to_js_or_null(&result)
 }
 
 /// 迁移存档数据 (简化版: 仅添加版本号)
@@ -278,8 +290,7 @@ pub fn gw_check_save_version(save_version_str: &str) -> JsValue {
 #[wasm_bindgen]
 pub fn gw_migrate_save(save_json: &str) -> Result<String, JsValue> {
     // 解析 JSON
-    let mut data: serde_json::Value = serde_json::from_str(save_json)
-        .map_err(|e| JsValue::from_str(&format!("JSON解析错误: {}", e)))?;
+    let mut data: serde_json::Value = gw_parse_json("save_json", save_json)?;
     
     // 获取旧版本号
     let old_version = data.get("version")
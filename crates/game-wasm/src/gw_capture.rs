@@ -0,0 +1,81 @@
+//! 怪兽捕获 WASM 绑定
+//!
+//! 提供捕获成功率预览、捕获操作的 JS 接口
+
+use wasm_bindgen::prelude::*;
+use game_core::*;
+
+use crate::{gw_to_js_or_null, gw_try_parse_json, GwOperationResult};
+
+/// 字符串转捕获道具类型
+fn string_to_capture_item(item: &str) -> Option<GcCaptureItemType> {
+    match item.to_lowercase().as_str() {
+        "basic" | "basic_net" => Some(GcCaptureItemType::BasicNet),
+        "great" | "great_net" => Some(GcCaptureItemType::GreatNet),
+        "master" | "master_net" => Some(GcCaptureItemType::MasterNet),
+        _ => None,
+    }
+}
+
+/// 预览捕获成功率 (%)
+/// 解析失败返回 0
+#[wasm_bindgen]
+pub fn gw_capture_chance(monster_json: &str, tier: u8, item: &str) -> u32 {
+    let monster: Result<GcMonster, _> = gw_try_parse_json("monster_json", monster_json);
+    match (monster, string_to_capture_item(item)) {
+        (Ok(m), Some(i)) => gc_capture_chance(&m, GcMonsterTier::from_u8(tier), i),
+        _ => 0,
+    }
+}
+
+/// 尝试捕获敌方怪兽
+/// 输入: arena_json, player_json, 敌方槽位, 稀有度(1-5), 道具("basic"/"great"/"master"), 随机数
+/// 返回: { success, error?, data: {arena, player, result} }
+#[wasm_bindgen]
+pub fn gw_attempt_capture(
+    arena_json: &str,
+    player_json: &str,
+    enemy_slot: u8,
+    tier: u8,
+    item: &str,
+    roll: u8,
+) -> JsValue {
+    let arena: Result<GcBattleArena, _> = gw_try_parse_json("arena_json", arena_json);
+    let player: Result<GcPlayer, _> = gw_try_parse_json("player_json", player_json);
+
+    let result = match (arena, player, string_to_capture_item(item)) {
+        (Ok(mut a), Ok(mut p), Some(i)) => {
+            let capture = gc_attempt_capture(&mut a, &mut p, enemy_slot, GcMonsterTier::from_u8(tier), i, roll);
+            match capture.error.clone() {
+                Some(e) => GwOperationResult {
+                    success: false,
+                    error: Some(e),
+                    data: None,
+                    diagnostic: None,
+                },
+                None => {
+                    let result_data = serde_json::json!({
+                        "arena": a,
+                        "player": p,
+                        "result": capture
+                    });
+                    GwOperationResult {
+                        success: true,
+                        error: None,
+                        data: Some(result_data.to_string()),
+                        diagnostic: None,
+                    }
+                }
+            }
+        }
+        (Ok(_), Ok(_), None) => GwOperationResult {
+            success: false,
+            error: Some("未知的捕获道具".to_string()),
+            data: None,
+            diagnostic: None,
+        },
+        (arena, player, _) => GwOperationResult::gw_json_failure(arena.err().or(player.err())),
+    };
+
+    gw_to_js_or_null(&result)
+}
@@ -0,0 +1,46 @@
+//! 伙伴 WASM 绑定
+//!
+//! 提供伙伴装备、卸下的 JS 接口 (伙伴由捕获成功时获得)
+
+use wasm_bindgen::prelude::*;
+use game_core::*;
+
+use crate::{gw_to_js_or_null, gw_try_parse_json, GwOperationResult};
+
+/// 装备伙伴 (companion_id 为空字符串时卸下)，装备后重新计算角色属性
+/// 返回: { success, error?, data: player }
+#[wasm_bindgen]
+pub fn gw_equip_companion(player_json: &str, companion_id: &str) -> JsValue {
+    let player: Result<GcPlayer, _> = gw_try_parse_json("player_json", player_json);
+
+    let result = match player {
+        Ok(mut p) => {
+            let equipped = if companion_id.is_empty() {
+                p.companions.unequip();
+                Ok(())
+            } else {
+                p.companions.equip(companion_id)
+            };
+            match equipped {
+                Ok(()) => {
+                    p.gc_update_rpg_stats();
+                    GwOperationResult {
+                        success: true,
+                        error: None,
+                        data: serde_json::to_string(&p).ok(),
+                        diagnostic: None,
+                    }
+                }
+                Err(e) => GwOperationResult {
+                    success: false,
+                    error: Some(e),
+                    data: None,
+                    diagnostic: None,
+                },
+            }
+        }
+        Err(diagnostic) => GwOperationResult::gw_json_failure(Some(diagnostic)),
+    };
+
+    gw_to_js_or_null(&result)
+}
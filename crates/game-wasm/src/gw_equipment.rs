@@ -16,6 +16,8 @@ use game_core::{
 };
 use serde::Serialize;
 
+use crate::gw_to_js_or_null;
+
 // =============================================================================
 // 背包管理器
 // =============================================================================
@@ -51,7 +53,7 @@ impl GwInventoryManager {
     /// 移除物品
     pub fn remove_item(&mut self, item_id: &str) -> JsValue {
         match self.inner.gc_remove_item(item_id) {
-            Some(item) => serde_wasm_bindgen::to_value(&item).unwrap_or(JsValue::NULL),
+            Some(item) => gw_to_js_or_null(&item),
             None => JsValue::NULL,
         }
     }
@@ -0,0 +1,64 @@
+//! 怪兽进化 WASM 绑定
+//!
+//! 提供进化路线查询、进化操作的 JS 接口
+
+use wasm_bindgen::prelude::*;
+use game_core::*;
+
+use crate::{gw_to_js_or_null, gw_try_parse_json, GwOperationResult};
+
+/// 查询怪兽可用的进化目标
+/// 输入: monster_json, pool_json, 玩家等级, inventory_json (可为空字符串)
+/// 返回: 目标模板ID数组，解析失败返回 null
+#[wasm_bindgen]
+pub fn gw_available_evolutions(
+    monster_json: &str,
+    pool_json: &str,
+    player_level: u32,
+    inventory_json: &str,
+) -> JsValue {
+    let monster: Result<GcMonster, _> = gw_try_parse_json("monster_json", monster_json);
+    let pool: Result<GcMonsterPool, _> = gw_try_parse_json("pool_json", pool_json);
+    let inventory: Option<GcInventory> = gw_try_parse_json("inventory_json", inventory_json).ok();
+
+    match (monster, pool) {
+        (Ok(m), Ok(p)) => {
+            let targets = gc_available_evolutions(&m, &p, player_level, inventory.as_ref());
+            gw_to_js_or_null(&targets)
+        }
+        _ => JsValue::NULL,
+    }
+}
+
+/// 进化手牌区中的怪兽
+/// 返回: { success, error?, data: player }
+#[wasm_bindgen]
+pub fn gw_evolve_monster(
+    player_json: &str,
+    monster_id: &str,
+    pool_json: &str,
+    target_template_id: &str,
+) -> JsValue {
+    let player: Result<GcPlayer, _> = gw_try_parse_json("player_json", player_json);
+    let pool: Result<GcMonsterPool, _> = gw_try_parse_json("pool_json", pool_json);
+
+    let result = match (player, pool) {
+        (Ok(mut p), Ok(pool)) => match gc_evolve_bench_monster(&mut p, monster_id, &pool, target_template_id) {
+            Ok(()) => GwOperationResult {
+                success: true,
+                error: None,
+                data: serde_json::to_string(&p).ok(),
+                diagnostic: None,
+            },
+            Err(e) => GwOperationResult {
+                success: false,
+                error: Some(e),
+                data: None,
+                diagnostic: None,
+            },
+        },
+        (player, pool) => GwOperationResult::gw_json_failure(player.err().or(pool.err())),
+    };
+
+    gw_to_js_or_null(&result)
+}
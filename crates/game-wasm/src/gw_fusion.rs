@@ -0,0 +1,105 @@
+//! 怪兽融合 WASM 绑定
+//!
+//! 提供融合界面所需的配方列表、配方查询、融合操作的 JS 接口
+
+use wasm_bindgen::prelude::*;
+use game_core::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{gw_to_js_or_null, gw_try_parse_json, GwOperationResult};
+
+/// 配方信息 (JS端，未发现的配方隐藏结果)
+#[derive(Serialize, Deserialize)]
+pub struct GwFusionRecipeInfo {
+    pub recipe_id: String,
+    pub parent_a: String,
+    pub parent_b: String,
+    pub result_template_id: Option<String>,
+    pub discovered: bool,
+}
+
+/// 获取融合配方列表 (融合界面)
+/// 输入: registry_json, discovery_json
+#[wasm_bindgen]
+pub fn gw_fusion_recipes(registry_json: &str, discovery_json: &str) -> JsValue {
+    let registry: Result<GcFusionRegistry, _> = gw_try_parse_json("registry_json", registry_json);
+    let discovery: GcFusionDiscovery = gw_try_parse_json("discovery_json", discovery_json).unwrap_or_default();
+
+    match registry {
+        Ok(r) => {
+            let infos: Vec<GwFusionRecipeInfo> = r.recipes().iter().map(|recipe| {
+                let discovered = discovery.is_discovered(&recipe.recipe_id);
+                GwFusionRecipeInfo {
+                    recipe_id: recipe.recipe_id.clone(),
+                    parent_a: recipe.parent_a.clone(),
+                    parent_b: recipe.parent_b.clone(),
+                    result_template_id: discovered.then(|| recipe.result_template_id.clone()),
+                    discovered,
+                }
+            }).collect();
+            gw_to_js_or_null(&infos)
+        }
+        Err(_) => JsValue::NULL,
+    }
+}
+
+/// 两个模板是否存在融合配方
+#[wasm_bindgen]
+pub fn gw_can_fuse(registry_json: &str, template_a: &str, template_b: &str) -> bool {
+    let registry: Result<GcFusionRegistry, _> = gw_try_parse_json("registry_json", registry_json);
+    match registry {
+        Ok(r) => r.find_recipe(template_a, template_b).is_some(),
+        Err(_) => false,
+    }
+}
+
+/// 融合手牌区中的两只怪兽
+/// 输入: player_json, 素材A ID, 素材B ID, registry_json, pool_json, discovery_json, random_rolls_json (3个随机数 0-255), 产物 ID
+/// 返回: { success, error?, data: {player, discovery, result} }
+#[wasm_bindgen]
+#[allow(clippy::too_many_arguments)]
+pub fn gw_fuse_monsters(
+    player_json: &str,
+    id_a: &str,
+    id_b: &str,
+    registry_json: &str,
+    pool_json: &str,
+    discovery_json: &str,
+    random_rolls_json: &str,
+    result_id: &str,
+) -> JsValue {
+    let player: Result<GcPlayer, _> = gw_try_parse_json("player_json", player_json);
+    let registry: Result<GcFusionRegistry, _> = gw_try_parse_json("registry_json", registry_json);
+    let pool: Result<GcMonsterPool, _> = gw_try_parse_json("pool_json", pool_json);
+    let discovery: Result<GcFusionDiscovery, _> = gw_try_parse_json("discovery_json", discovery_json);
+    let rolls: Result<Vec<u8>, _> = gw_try_parse_json("random_rolls_json", random_rolls_json);
+
+    let result = match (player, registry, pool, discovery, rolls) {
+        (Ok(mut p), Ok(reg), Ok(pool), Ok(mut d), Ok(r)) => {
+            match gc_fuse_bench_monsters(&mut p, id_a, id_b, &reg, &pool, &mut d, &r, result_id) {
+                Ok(fusion) => {
+                    let result_data = serde_json::json!({
+                        "player": p,
+                        "discovery": d,
+                        "result": fusion
+                    });
+                    GwOperationResult {
+                        success: true,
+                        error: None,
+                        data: Some(result_data.to_string()),
+                        diagnostic: None,
+                    }
+                }
+                Err(e) => GwOperationResult {
+                    success: false,
+                    error: Some(e),
+                    data: None,
+                    diagnostic: None,
+                },
+            }
+        }
+        (player, registry, pool, discovery, rolls) => GwOperationResult::gw_json_failure(player.err().or(registry.err()).or(pool.err()).or(discovery.err()).or(rolls.err())),
+    };
+
+    gw_to_js_or_null(&result)
+}
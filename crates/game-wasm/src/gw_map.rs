@@ -9,15 +9,42 @@
 //! - 玩家移动
 //! - 地图查询
 //! - 交互处理
+//! - 单人移动控制器 (GwMapPlayer)
 
 use wasm_bindgen::prelude::*;
 use game_core::{
-    GcWorld, GcPosition, GcDirection, GcMoveResult, GcInteraction,
-    gc_create_default_world, gc_create_town_map, gc_create_forest_map, gc_create_boss_arena_map,
+    GcWorld, GcMap, GcMapPlayer, GcPosition, GcDirection, GcMoveResult, GcInteraction, GcSpawnRegion, GcTile,
+    GcDayPhase, GcDifficulty, GcDifficultySettings, GcWeather,
+    gc_create_default_world, gc_tile_interaction, gc_encounter_seed, gc_generate_encounter_at, gc_generate_encounter_during, gc_generate_encounter_in, gc_create_town_map, gc_create_forest_map, gc_create_boss_arena_map,
 };
 use serde::{Serialize, Deserialize};
 
-use crate::gw_log;
+use crate::{gw_log, gw_parse_json, gw_to_js_or_null};
+use crate::gw_terrain::string_to_world_terrain;
+
+/// 移动历史最多保留的步数
+const GW_MAP_HISTORY_LIMIT: usize = 100;
+
+/// 解析方向字符串 ("up" / "down" / "left" / "right")
+fn gw_parse_direction(direction: &str) -> Option<GcDirection> {
+    match direction {
+        "up" => Some(GcDirection::Up),
+        "down" => Some(GcDirection::Down),
+        "left" => Some(GcDirection::Left),
+        "right" => Some(GcDirection::Right),
+        _ => None,
+    }
+}
+
+/// 方向转字符串 (与 gw_parse_direction 对应)
+fn gw_direction_name(direction: GcDirection) -> &'static str {
+    match direction {
+        GcDirection::Up => "up",
+        GcDirection::Down => "down",
+        GcDirection::Left => "left",
+        GcDirection::Right => "right",
+    }
+}
 
 // =============================================================================
 // JS 友好类型
@@ -98,6 +125,45 @@ pub struct GwTileInfo {
     pub interactable: bool,
     pub entity_id: Option<String>,
     pub color: (u8, u8, u8),
+    pub elevation: i16,
+    /// 移动消耗 (不可通行为 null)
+    pub movement_cost: Option<u32>,
+}
+
+impl From<&GcTile> for GwTileInfo {
+    fn from(tile: &GcTile) -> Self {
+        Self {
+            tile_type: format!("{:?}", tile.tile_type),
+            walkable: tile.gc_is_walkable(),
+            interactable: tile.gc_is_interactable(),
+            entity_id: tile.entity_id.clone(),
+            color: tile.tile_type.gc_color(),
+            elevation: tile.gc_elevation(),
+            movement_cost: tile.gc_movement_cost(),
+        }
+    }
+}
+
+/// 遭遇生成参数
+#[derive(Serialize, Deserialize)]
+pub struct GwEncounterOptions {
+    /// 大世界地形 ("grassland" / "woodland" ...)
+    #[serde(default)]
+    pub world_terrain: String,
+    pub player_level: u32,
+    /// 种子盐值 (与服务器请求相同)
+    #[serde(default)]
+    pub salt: u32,
+    #[serde(default)]
+    pub difficulty: GcDifficulty,
+    #[serde(default)]
+    pub level_scaling: bool,
+    /// 昼夜时段 (服务器 `WorldTime` 下发；为空时忽略时段)
+    #[serde(default)]
+    pub time_of_day: Option<GcDayPhase>,
+    /// 所在区域天气 (服务器 `Weather` 下发；为空时忽略天气)
+    #[serde(default)]
+    pub weather: Option<GcWeather>,
 }
 
 /// JS 友好的地图信息
@@ -110,6 +176,14 @@ pub struct GwMapInfo {
     pub spawn_point: GwPosition,
 }
 
+/// JS 友好的移动记录
+#[derive(Serialize, Deserialize)]
+pub struct GwMoveStep {
+    pub from: GwPosition,
+    pub to: GwPosition,
+    pub direction: String,
+}
+
 /// JS 友好的玩家信息
 #[derive(Serialize, Deserialize)]
 pub struct GwPlayerMapInfo {
@@ -151,25 +225,22 @@ impl GwWorldManager {
     /// 移动玩家
     #[wasm_bindgen]
     pub fn move_player(&mut self, player_id: &str, direction: &str) -> JsValue {
-        let dir = match direction {
-            "up" => GcDirection::Up,
-            "down" => GcDirection::Down,
-            "left" => GcDirection::Left,
-            "right" => GcDirection::Right,
-            _ => {
+        let dir = match gw_parse_direction(direction) {
+            Some(dir) => dir,
+            None => {
                 let result = GwMoveResult {
                     success: false,
                     new_position: GwPosition { x: 0, y: 0 },
                     interaction: None,
                 };
-                return serde_wasm_bindgen::to_value(&result).unwrap();
+                return gw_to_js_or_null(&result);
             }
         };
         
         match self.world.gc_move_player(player_id, dir) {
             Ok(result) => {
                 let gw_result: GwMoveResult = result.into();
-                serde_wasm_bindgen::to_value(&gw_result).unwrap()
+                gw_to_js_or_null(&gw_result)
             }
             Err(_) => {
                 let result = GwMoveResult {
@@ -177,7 +248,7 @@ impl GwWorldManager {
                     new_position: GwPosition { x: 0, y: 0 },
                     interaction: None,
                 };
-                serde_wasm_bindgen::to_value(&result).unwrap()
+                gw_to_js_or_null(&result)
             }
         }
     }
@@ -212,7 +283,7 @@ impl GwWorldManager {
                 position: player.position.into(),
                 direction: format!("{:?}", player.direction),
             };
-            serde_wasm_bindgen::to_value(&info).unwrap()
+            gw_to_js_or_null(&info)
         } else {
             JsValue::NULL
         }
@@ -222,7 +293,7 @@ impl GwWorldManager {
     #[wasm_bindgen]
     pub fn get_map_ids(&self) -> JsValue {
         let ids: Vec<&String> = self.world.maps.iter().map(|m| &m.id).collect();
-        serde_wasm_bindgen::to_value(&ids).unwrap()
+        gw_to_js_or_null(&ids)
     }
     
     /// 获取地图信息
@@ -236,7 +307,7 @@ impl GwWorldManager {
                 height: map.height,
                 spawn_point: map.spawn_point.into(),
             };
-            serde_wasm_bindgen::to_value(&info).unwrap()
+            gw_to_js_or_null(&info)
         } else {
             JsValue::NULL
         }
@@ -248,14 +319,7 @@ impl GwWorldManager {
         if let Some(map) = self.world.gc_get_map(map_id) {
             let pos = GcPosition::gc_new(x, y);
             if let Some(tile) = map.gc_get_tile(&pos) {
-                let info = GwTileInfo {
-                    tile_type: format!("{:?}", tile.tile_type),
-                    walkable: tile.gc_is_walkable(),
-                    interactable: tile.gc_is_interactable(),
-                    entity_id: tile.entity_id.clone(),
-                    color: tile.tile_type.gc_color(),
-                };
-                return serde_wasm_bindgen::to_value(&info).unwrap();
+                return gw_to_js_or_null(&GwTileInfo::from(tile));
             }
         }
         JsValue::NULL
@@ -286,21 +350,91 @@ impl GwWorldManager {
     pub fn get_map_tiles(&self, map_id: &str) -> JsValue {
         if let Some(map) = self.world.gc_get_map(map_id) {
             let tiles: Vec<Vec<GwTileInfo>> = map.tiles.iter().map(|row| {
-                row.iter().map(|tile| {
-                    GwTileInfo {
-                        tile_type: format!("{:?}", tile.tile_type),
-                        walkable: tile.gc_is_walkable(),
-                        interactable: tile.gc_is_interactable(),
-                        entity_id: tile.entity_id.clone(),
-                        color: tile.tile_type.gc_color(),
-                    }
-                }).collect()
+                row.iter().map(GwTileInfo::from).collect()
             }).collect();
-            serde_wasm_bindgen::to_value(&tiles).unwrap()
+            gw_to_js_or_null(&tiles)
         } else {
             JsValue::NULL
         }
     }
+    
+    /// 编辑器: 设置海拔 (elevation 为 undefined 时清除)
+    #[wasm_bindgen]
+    pub fn set_elevation(&mut self, map_id: &str, x: i32, y: i32, elevation: Option<i16>) -> bool {
+        self.world.gc_get_map_mut(map_id)
+            .is_some_and(|map| map.gc_set_elevation(&GcPosition::gc_new(x, y), elevation))
+    }
+    
+    /// 编辑器: 设置移动消耗 (cost 为 undefined 时恢复默认)
+    #[wasm_bindgen]
+    pub fn set_movement_cost(&mut self, map_id: &str, x: i32, y: i32, cost: Option<u32>) -> bool {
+        self.world.gc_get_map_mut(map_id)
+            .is_some_and(|map| map.gc_set_movement_cost(&GcPosition::gc_new(x, y), cost))
+    }
+    
+    /// 获取地图的刷怪区域
+    #[wasm_bindgen]
+    pub fn get_spawn_regions(&self, map_id: &str) -> JsValue {
+        match self.world.gc_get_map(map_id) {
+            Some(map) => gw_to_js_or_null(&map.spawn_regions),
+            None => JsValue::NULL,
+        }
+    }
+    
+    /// 编辑器: 添加或替换刷怪区域 (GcSpawnRegion JSON)
+    #[wasm_bindgen]
+    pub fn set_spawn_region(&mut self, map_id: &str, region_json: &str) -> Result<(), JsValue> {
+        let region: GcSpawnRegion = gw_parse_json("region_json", region_json)?;
+        let map = self.world.gc_get_map_mut(map_id)
+            .ok_or_else(|| JsValue::from_str("地图不存在"))?;
+        map.gc_set_spawn_region(region);
+        Ok(())
+    }
+    
+    /// 编辑器: 移除刷怪区域
+    #[wasm_bindgen]
+    pub fn remove_spawn_region(&mut self, map_id: &str, region_id: &str) -> bool {
+        self.world.gc_get_map_mut(map_id)
+            .is_some_and(|map| map.gc_remove_spawn_region(region_id))
+    }
+    
+    /// 在位置生成遭遇 (种子由地图、位置与 salt 派生，与服务器一致)，不在刷怪区域时为 null
+    /// options: `{ world_terrain, player_level, salt, difficulty?, level_scaling?, time_of_day?, weather? }`
+    #[wasm_bindgen]
+    pub fn generate_encounter(&self, map_id: &str, x: i32, y: i32, options: JsValue) -> JsValue {
+        let Some(map) = self.world.gc_get_map(map_id) else {
+            return JsValue::NULL;
+        };
+        let Ok(options) = serde_wasm_bindgen::from_value::<GwEncounterOptions>(options) else {
+            return JsValue::NULL;
+        };
+        let difficulty = GcDifficultySettings::gc_new(options.difficulty, options.level_scaling);
+        let pos = GcPosition::gc_new(x, y);
+        let seed = gc_encounter_seed(map_id, &pos, options.salt as u64);
+        let world_terrain = string_to_world_terrain(&options.world_terrain);
+        let encounter = match (options.time_of_day, options.weather) {
+            (Some(phase), weather) => {
+                gc_generate_encounter_during(map, &pos, world_terrain, options.player_level, phase, weather, seed)
+            }
+            (None, Some(weather)) => map.gc_spawn_region_at(&pos).map(|region| {
+                gc_generate_encounter_in(world_terrain, region, options.player_level, map.gc_elevation_at(&pos), weather, seed)
+            }),
+            (None, None) => gc_generate_encounter_at(map, &pos, world_terrain, options.player_level, seed),
+        };
+        encounter
+            .map(|encounter| encounter.gc_with_difficulty(difficulty, options.player_level))
+            .and_then(|encounter| serde_wasm_bindgen::to_value(&encounter).ok())
+            .unwrap_or(JsValue::NULL)
+    }
+    
+    /// 按移动消耗寻路，返回 { steps: [{x, y}], cost }，不可达为 null
+    #[wasm_bindgen]
+    pub fn find_path(&self, map_id: &str, from_x: i32, from_y: i32, to_x: i32, to_y: i32) -> JsValue {
+        self.world.gc_get_map(map_id)
+            .and_then(|map| map.gc_find_path(&GcPosition::gc_new(from_x, from_y), &GcPosition::gc_new(to_x, to_y)))
+            .and_then(|path| serde_wasm_bindgen::to_value(&path).ok())
+            .unwrap_or(JsValue::NULL)
+    }
 }
 
 impl Default for GwWorldManager {
@@ -309,6 +443,146 @@ impl Default for GwWorldManager {
     }
 }
 
+// =============================================================================
+// WASM 单人移动控制器
+// =============================================================================
+
+/// 大地图玩家控制器
+/// 持有一张地图和玩家状态，负责碰撞、进入交互格检测、朝向与移动历史
+#[wasm_bindgen]
+pub struct GwMapPlayer {
+    map: GcMap,
+    player: GcMapPlayer,
+    history: Vec<GwMoveStep>,
+}
+
+#[wasm_bindgen]
+impl GwMapPlayer {
+    /// 在默认世界的指定地图出生点创建玩家
+    #[wasm_bindgen(constructor)]
+    pub fn new(player_id: &str, map_id: &str) -> Result<GwMapPlayer, JsValue> {
+        let map = gc_create_default_world().maps.into_iter()
+            .find(|m| m.id == map_id)
+            .ok_or_else(|| JsValue::from_str("地图不存在"))?;
+        Ok(Self::with_map(player_id, map))
+    }
+
+    /// 从地图 JSON 创建玩家 (出生点为地图出生点)
+    #[wasm_bindgen(js_name = fromMapJson)]
+    pub fn from_map_json(player_id: &str, map_json: &str) -> Result<GwMapPlayer, JsValue> {
+        let map: GcMap = gw_parse_json("map_json", map_json)?;
+        Ok(Self::with_map(player_id, map))
+    }
+
+    /// 向指定方向移动一格，返回 GwMoveResult (进入交互格时带实体 ID 与类型)
+    #[wasm_bindgen(js_name = "move")]
+    pub fn step(&mut self, direction: &str) -> Result<JsValue, JsValue> {
+        let dir = gw_parse_direction(direction)
+            .ok_or_else(|| JsValue::from_str("未知方向"))?;
+        let from = self.player.position;
+        let result = self.player.gc_move(dir, &self.map);
+        if result.success {
+            if self.history.len() >= GW_MAP_HISTORY_LIMIT {
+                self.history.remove(0);
+            }
+            self.history.push(GwMoveStep {
+                from: from.into(),
+                to: result.new_position.into(),
+                direction: gw_direction_name(dir).to_string(),
+            });
+        }
+        let gw_result: GwMoveResult = result.into();
+        serde_wasm_bindgen::to_value(&gw_result).map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// 原地转向
+    pub fn face(&mut self, direction: &str) -> bool {
+        match gw_parse_direction(direction) {
+            Some(dir) => {
+                self.player.direction = dir;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// 面前格子的交互 (按键交互用)，没有时返回 null
+    pub fn facing_interaction(&self) -> JsValue {
+        let facing = self.player.gc_get_facing_position();
+        match gc_tile_interaction(&self.map, &facing) {
+            Some(interaction) => {
+                let gw_interaction: GwInteraction = interaction.into();
+                gw_to_js_or_null(&gw_interaction)
+            }
+            None => JsValue::NULL,
+        }
+    }
+
+    /// 传送到当前地图的指定位置 (目标不可通行时拒绝)
+    pub fn teleport(&mut self, x: i32, y: i32) -> bool {
+        let pos = GcPosition::gc_new(x, y);
+        if !self.map.gc_can_walk(&pos) {
+            return false;
+        }
+        let map_id = self.map.id.clone();
+        self.player.gc_teleport(&map_id, pos);
+        true
+    }
+
+    /// 切换地图 (放在新地图出生点，清空移动历史)
+    pub fn change_map(&mut self, map_json: &str) -> Result<(), JsValue> {
+        let map: GcMap = gw_parse_json("map_json", map_json)?;
+        self.player.gc_teleport(&map.id, map.spawn_point);
+        self.map = map;
+        self.history.clear();
+        Ok(())
+    }
+
+    /// 当前 X 坐标
+    #[wasm_bindgen(getter)]
+    pub fn x(&self) -> i32 {
+        self.player.position.x
+    }
+
+    /// 当前 Y 坐标
+    #[wasm_bindgen(getter)]
+    pub fn y(&self) -> i32 {
+        self.player.position.y
+    }
+
+    /// 当前朝向 ("up" / "down" / "left" / "right")
+    #[wasm_bindgen(getter)]
+    pub fn facing(&self) -> String {
+        gw_direction_name(self.player.direction).to_string()
+    }
+
+    /// 当前地图 ID
+    #[wasm_bindgen(getter)]
+    pub fn map_id(&self) -> String {
+        self.map.id.clone()
+    }
+
+    /// 移动历史 (从旧到新，最多保留 100 步)
+    pub fn history(&self) -> JsValue {
+        gw_to_js_or_null(&self.history)
+    }
+
+    /// 清空移动历史
+    pub fn clear_history(&mut self) {
+        self.history.clear();
+    }
+}
+
+impl GwMapPlayer {
+    fn with_map(player_id: &str, map: GcMap) -> Self {
+        Self {
+            player: GcMapPlayer::gc_new(player_id, &map.id, map.spawn_point),
+            map,
+            history: Vec::new(),
+        }
+    }
+}
+
 // =============================================================================
 // 独立函数
 // =============================================================================
@@ -10,6 +10,8 @@ use game_core::{
 };
 use serde::{Deserialize, Serialize};
 
+use crate::gw_to_js_or_null;
+
 /// 怪兽信息 (JS端)
 #[derive(Serialize, Deserialize)]
 pub struct GwMonsterInfo {
@@ -75,7 +77,7 @@ pub fn gw_create_test_monster(id: &str, name: &str, level: u8, attr: &str, atk:
     
     let monster = GcMonster::new(id, name, level, attribute, atk, def, hp);
     let info = GwMonsterInfo::from(&monster);
-    serde_wasm_bindgen::to_value(&info).unwrap_or(JsValue::NULL)
+    gw_to_js_or_null(&info)
 }
 
 // =============================================================================
@@ -96,7 +98,7 @@ pub fn gw_validate_normal_summon(level: u8, normal_summon_used: bool) -> JsValue
     match gc_validate_normal_summon(level, normal_summon_used) {
         Ok(()) => {
             let r = GwSummonValidation { valid: true, error: None };
-            serde_wasm_bindgen::to_value(&r).unwrap_or(JsValue::NULL)
+            gw_to_js_or_null(&r)
         }
         Err(e) => {
             let msg = match e {
@@ -105,7 +107,7 @@ pub fn gw_validate_normal_summon(level: u8, normal_summon_used: bool) -> JsValue
                 _ => format!("{:?}", e),
             };
             let r = GwSummonValidation { valid: false, error: Some(msg) };
-            serde_wasm_bindgen::to_value(&r).unwrap_or(JsValue::NULL)
+            gw_to_js_or_null(&r)
         }
     }
 }
@@ -124,7 +126,7 @@ pub fn gw_validate_tribute_summon(level: u8, sacrifice_slots: &[u8], occupied_sl
     match gc_validate_tribute_summon(level, sacrifice_slots, &slots) {
         Ok(()) => {
             let r = GwSummonValidation { valid: true, error: None };
-            serde_wasm_bindgen::to_value(&r).unwrap_or(JsValue::NULL)
+            gw_to_js_or_null(&r)
         }
         Err(e) => {
             let msg = match e {
@@ -135,7 +137,7 @@ pub fn gw_validate_tribute_summon(level: u8, sacrifice_slots: &[u8], occupied_sl
                 _ => format!("{:?}", e),
             };
             let r = GwSummonValidation { valid: false, error: Some(msg) };
-            serde_wasm_bindgen::to_value(&r).unwrap_or(JsValue::NULL)
+            gw_to_js_or_null(&r)
         }
     }
 }
@@ -220,7 +222,7 @@ pub fn gw_calculate_battle_damage(
         attacker_destroyed: result.attacker_destroyed,
     };
     
-    serde_wasm_bindgen::to_value(&gw_result).unwrap_or(JsValue::NULL)
+    gw_to_js_or_null(&gw_result)
 }
 
 /// 计算直接攻击伤害 (攻击玩家)
@@ -259,7 +261,7 @@ pub fn gw_validate_special_summon(
     match game_core::gc_validate_special_summon(is_main_phase, target_slot, &slots) {
         Ok(()) => {
             let r = GwSummonValidation { valid: true, error: None };
-            serde_wasm_bindgen::to_value(&r).unwrap_or(JsValue::NULL)
+            gw_to_js_or_null(&r)
         }
         Err(e) => {
             let msg = match e {
@@ -269,7 +271,7 @@ pub fn gw_validate_special_summon(
                 _ => format!("{:?}", e),
             };
             let r = GwSummonValidation { valid: false, error: Some(msg) };
-            serde_wasm_bindgen::to_value(&r).unwrap_or(JsValue::NULL)
+            gw_to_js_or_null(&r)
         }
     }
 }
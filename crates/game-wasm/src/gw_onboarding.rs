@@ -0,0 +1,70 @@
+//! 新手引导 WASM 绑定
+//!
+//! 单机对局由前端推进引导进度；标记判定全部在 game-core，前端只传事件或酒馆座位
+
+use wasm_bindgen::prelude::*;
+use game_core::*;
+
+use crate::{gw_to_js_or_null, gw_try_parse_json, GwOperationResult};
+
+/// 记录后的进度与本次新完成的标记
+fn gw_onboarding_result(progress: &GcOnboardingProgress, completed: &[GcOnboardingFlag]) -> GwOperationResult {
+    let result_data = serde_json::json!({
+        "progress": progress,
+        "completed": completed,
+    });
+    GwOperationResult {
+        success: true,
+        error: None,
+        data: Some(result_data.to_string()),
+        diagnostic: None,
+    }
+}
+
+/// 记录引导事件
+/// 输入: progress_json, events_json (GcOnboardingEvent 数组)
+/// 返回: { success, error?, data: {progress, completed} }
+#[wasm_bindgen]
+pub fn gw_onboarding_record(progress_json: &str, events_json: &str) -> JsValue {
+    let progress: Result<GcOnboardingProgress, _> = gw_try_parse_json("progress_json", progress_json);
+    let events: Result<Vec<GcOnboardingEvent>, _> = gw_try_parse_json("events_json", events_json);
+
+    let result = match (progress, events) {
+        (Ok(mut p), Ok(e)) => {
+            let completed = p.gc_record_all(&e);
+            gw_onboarding_result(&p, &completed)
+        }
+        (progress, events) => GwOperationResult::gw_json_failure(progress.err().or(events.err())),
+    };
+
+    gw_to_js_or_null(&result)
+}
+
+/// 按酒馆座位推进引导 (合成升星、对局名次)
+/// 输入: progress_json, seat_json
+/// 返回: { success, error?, data: {progress, completed} }
+#[wasm_bindgen]
+pub fn gw_onboarding_record_tavern_seat(progress_json: &str, seat_json: &str) -> JsValue {
+    let progress: Result<GcOnboardingProgress, _> = gw_try_parse_json("progress_json", progress_json);
+    let seat: Result<GcTavernSeat, _> = gw_try_parse_json("seat_json", seat_json);
+
+    let result = match (progress, seat) {
+        (Ok(mut p), Ok(s)) => {
+            let completed = p.gc_record_all(&GcOnboardingEvent::gc_from_tavern_seat(&s));
+            gw_onboarding_result(&p, &completed)
+        }
+        (progress, seat) => GwOperationResult::gw_json_failure(progress.err().or(seat.err())),
+    };
+
+    gw_to_js_or_null(&result)
+}
+
+/// 各模式的引导清单
+/// 解析失败返回 null (错误见 gw_last_error)
+#[wasm_bindgen]
+pub fn gw_onboarding_checklists(progress_json: &str) -> JsValue {
+    match gw_try_parse_json::<GcOnboardingProgress>("progress_json", progress_json) {
+        Ok(progress) => gw_to_js_or_null(&progress.gc_checklists()),
+        Err(_) => JsValue::NULL,
+    }
+}
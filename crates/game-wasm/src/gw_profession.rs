@@ -19,7 +19,7 @@ use game_core::{
 };
 use serde::{Serialize, Deserialize};
 
-use crate::gw_log;
+use crate::{gw_log, gw_to_js_or_null};
 
 // =============================================================================
 // JS 友好类型
@@ -65,7 +65,7 @@ impl GwProfessionManager {
             })
             .collect();
         
-        serde_wasm_bindgen::to_value(&professions).unwrap()
+        gw_to_js_or_null(&professions)
     }
     
     /// 获取职业详情
@@ -81,7 +81,7 @@ impl GwProfessionManager {
         };
         
         let profession = GcProfession::gc_new(profession_type);
-        serde_wasm_bindgen::to_value(&profession).unwrap()
+        gw_to_js_or_null(&profession)
     }
     
     /// 获取职业天赋树
@@ -96,7 +96,7 @@ impl GwProfessionManager {
             _ => return JsValue::NULL,
         };
         
-        serde_wasm_bindgen::to_value(&tree).unwrap()
+        gw_to_js_or_null(&tree)
     }
 }
 
@@ -139,7 +139,7 @@ impl GwPlayerTalentManager {
     /// 获取当前状态
     #[wasm_bindgen]
     pub fn get_state(&self) -> JsValue {
-        serde_wasm_bindgen::to_value(&self.talents).unwrap()
+        gw_to_js_or_null(&self.talents)
     }
     
     /// 升级天赋
@@ -0,0 +1,134 @@
+//! 战场建筑 WASM 绑定
+//!
+//! 提供建筑模板查询、放置建筑、遵循目标规则的攻击等 JS 接口
+
+use wasm_bindgen::prelude::*;
+use game_core::*;
+
+use crate::{gw_to_js_or_null, gw_try_parse_json, GwOperationResult};
+
+/// 获取默认建筑模板 (JSON 字符串)
+#[wasm_bindgen]
+pub fn gw_get_structure_templates() -> String {
+    serde_json::to_string(&gc_default_structure_templates()).unwrap_or_default()
+}
+
+/// 在沙盘放置建筑
+/// 输入: arena_json, 模板ID, 实例ID, 槽位, 是否敌方
+/// 返回: { success, error?, data: arena }
+#[wasm_bindgen]
+pub fn gw_place_structure(
+    arena_json: &str,
+    template_id: &str,
+    instance_id: &str,
+    slot: u8,
+    is_enemy: bool,
+) -> JsValue {
+    let arena: Result<GcBattleArena, _> = gw_try_parse_json("arena_json", arena_json);
+
+    let result = match (arena, gc_get_structure_template(template_id)) {
+        (Ok(mut ar), Some(template)) => {
+            let structure = template.gc_create_instance(instance_id);
+            let placed = if is_enemy {
+                ar.place_enemy_structure(slot, structure)
+            } else {
+                ar.place_player_structure(slot, structure)
+            };
+            match placed {
+                Ok(()) => GwOperationResult {
+                    success: true,
+                    error: None,
+                    data: serde_json::to_string(&ar).ok(),
+                    diagnostic: None,
+                },
+                Err(e) => GwOperationResult {
+                    success: false,
+                    error: Some(e),
+                    data: None,
+                    diagnostic: None,
+                },
+            }
+        }
+        (Ok(_), None) => GwOperationResult {
+            success: false,
+            error: Some("建筑模板不存在".to_string()),
+            data: None,
+            diagnostic: None,
+        },
+        (Err(e), _) => GwOperationResult::gw_json_failure(Some(e)),
+    };
+
+    gw_to_js_or_null(&result)
+}
+
+/// 获取可攻击的敌方槽位 (遵循城墙规则)
+#[wasm_bindgen]
+pub fn gw_enemy_targetable_slots(arena_json: &str) -> Vec<u8> {
+    let arena: Result<GcBattleArena, _> = gw_try_parse_json("arena_json", arena_json);
+    match arena {
+        Ok(ar) => ar.enemy_targetable_slots(),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// 玩家怪兽攻击
+/// 输入: arena_json, 攻击槽位, 目标槽位 (-1 = 直接攻击)
+/// 返回: { success, error?, data: {arena, outcome} }
+#[wasm_bindgen]
+pub fn gw_arena_attack(arena_json: &str, attacker_slot: u8, target_slot: i32) -> JsValue {
+    let arena: Result<GcBattleArena, _> = gw_try_parse_json("arena_json", arena_json);
+    let target = u8::try_from(target_slot).ok();
+
+    let result = match arena {
+        Ok(mut ar) => match ar.player_attack(attacker_slot, target) {
+            Ok(outcome) => {
+                let result_data = serde_json::json!({
+                    "arena": ar,
+                    "outcome": outcome
+                });
+                GwOperationResult {
+                    success: true,
+                    error: None,
+                    data: Some(result_data.to_string()),
+                    diagnostic: None,
+                }
+            }
+            Err(e) => GwOperationResult {
+                success: false,
+                error: Some(e),
+                data: None,
+                diagnostic: None,
+            },
+        },
+        Err(e) => GwOperationResult::gw_json_failure(Some(e)),
+    };
+
+    gw_to_js_or_null(&result)
+}
+
+/// 单独触发建筑每回合效果 (gw_run_combat_round 已在回合开始时结算建筑，勿重复调用)
+/// 返回: { success, error?, data: {arena, player_results, enemy_results} }
+#[wasm_bindgen]
+pub fn gw_tick_structures(arena_json: &str) -> JsValue {
+    let arena: Result<GcBattleArena, _> = gw_try_parse_json("arena_json", arena_json);
+
+    let result = match arena {
+        Ok(mut ar) => {
+            let (player_results, enemy_results) = ar.tick_structures();
+            let result_data = serde_json::json!({
+                "arena": ar,
+                "player_results": player_results,
+                "enemy_results": enemy_results
+            });
+            GwOperationResult {
+                success: true,
+                error: None,
+                data: Some(result_data.to_string()),
+                diagnostic: None,
+            }
+        }
+        Err(e) => GwOperationResult::gw_json_failure(Some(e)),
+    };
+
+    gw_to_js_or_null(&result)
+}
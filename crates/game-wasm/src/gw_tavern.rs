@@ -6,6 +6,8 @@ use wasm_bindgen::prelude::*;
 use game_core::*;
 use serde::{Deserialize, Serialize};
 
+use crate::{gw_to_js_or_null, gw_try_parse_json};
+
 // =============================================================================
 // 经济系统绑定
 // =============================================================================
@@ -52,6 +54,41 @@ pub fn gw_get_xp_cost() -> u32 {
     GC_LEVEL_UP_COST
 }
 
+/// 获取商店稀有度权重 ([T1%, T2%, T3%, T4%, T5%])
+#[wasm_bindgen]
+pub fn gw_get_tier_weights(level: u8) -> Vec<u8> {
+    gc_get_tier_weights(level).weights.to_vec()
+}
+
+/// 获取商店概率预览 (各稀有度概率、升级变化、期望值)
+#[wasm_bindgen]
+pub fn gw_get_shop_odds(level: u8) -> JsValue {
+    gw_to_js_or_null(&gc_get_shop_odds_preview(level))
+}
+
+/// 指定模板在下一次刷新中的概率 (按商店等级与未冻结槽位)
+/// 模板不存在或 JSON 无效时返回 null
+#[wasm_bindgen]
+pub fn gw_get_template_odds(shop_json: &str, pool_json: &str, template_id: &str) -> JsValue {
+    let shop: Result<GcTavernShop, _> = gw_try_parse_json("shop_json", shop_json);
+    let pool: Result<GcMonsterPool, _> = gw_try_parse_json("pool_json", pool_json);
+    match (shop, pool) {
+        (Ok(s), Ok(p)) => s.template_odds(&p, template_id).map_or(JsValue::NULL, |odds| gw_to_js_or_null(&odds)),
+        _ => JsValue::NULL,
+    }
+}
+
+/// 找怪兽: 按名称或模板ID搜索，返回各模板的刷新概率与期望刷新花费 (命中率高的在前)
+#[wasm_bindgen]
+pub fn gw_find_unit_odds(shop_json: &str, pool_json: &str, query: &str) -> JsValue {
+    let shop: Result<GcTavernShop, _> = gw_try_parse_json("shop_json", shop_json);
+    let pool: Result<GcMonsterPool, _> = gw_try_parse_json("pool_json", pool_json);
+    match (shop, pool) {
+        (Ok(s), Ok(p)) => gw_to_js_or_null(&gc_find_unit_odds(&p, query, s.shop_level, s.open_slot_count())),
+        _ => JsValue::NULL,
+    }
+}
+
 // =============================================================================
 // 商店槽位信息
 // =============================================================================
@@ -62,6 +99,8 @@ pub struct GwShopSlot {
     pub index: usize,
     pub monster: Option<GwTavernMonster>,
     pub frozen: bool,
+    /// 调整前的基础价格 (高于 `monster.buy_price` 时为折扣)
+    pub base_price: u32,
 }
 
 /// 酒馆怪兽信息
@@ -77,6 +116,9 @@ pub struct GwTavernMonster {
     pub hp: u32,
     pub buy_price: u32,
     pub sell_price: u32,
+    /// 皮肤 ID (纯装饰)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub skin: Option<String>,
 }
 
 impl From<&GcMonster> for GwTavernMonster {
@@ -92,6 +134,7 @@ impl From<&GcMonster> for GwTavernMonster {
             hp: m.current_hp,
             buy_price: m.buy_price(),
             sell_price: m.sell_price(),
+            skin: m.skin.clone(),
         }
     }
 }
@@ -99,22 +142,39 @@ impl From<&GcMonster> for GwTavernMonster {
 /// 获取商店信息
 #[wasm_bindgen]
 pub fn gw_get_shop_info(shop_json: &str) -> JsValue {
-    let shop: Result<GcTavernShop, _> = serde_json::from_str(shop_json);
+    let shop: Result<GcTavernShop, _> = gw_try_parse_json("shop_json", shop_json);
     match shop {
         Ok(s) => {
             let slots: Vec<GwShopSlot> = s.slots.iter().enumerate().map(|(i, opt)| {
+                // 商店内价格经定价管线计算 (促销、英雄技能等)
+                let price = s.slot_price(i);
                 GwShopSlot {
                     index: i,
-                    monster: opt.as_ref().map(GwTavernMonster::from),
+                    monster: opt.as_ref().map(|m| GwTavernMonster {
+                        buy_price: price.as_ref().map_or(m.buy_price(), |p| p.price),
+                        ..GwTavernMonster::from(m)
+                    }),
                     frozen: s.frozen[i],
+                    base_price: price.map_or(0, |p| p.base),
                 }
             }).collect();
-            serde_wasm_bindgen::to_value(&slots).unwrap_or(JsValue::NULL)
+            gw_to_js_or_null(&slots)
         }
         Err(_) => JsValue::NULL,
     }
 }
 
+/// 获取商店法术槽位 (空槽位为 null)
+/// 购买并结算法术使用 `gw_tavern_action` 的 `BuySpell { slot, target }`
+#[wasm_bindgen]
+pub fn gw_get_shop_spells(shop_json: &str) -> JsValue {
+    let shop: Result<GcTavernShop, _> = gw_try_parse_json("shop_json", shop_json);
+    match shop {
+        Ok(s) => gw_to_js_or_null(&s.spell_slots),
+        Err(_) => JsValue::NULL,
+    }
+}
+
 // =============================================================================
 // 游戏模式
 // =============================================================================
@@ -151,6 +211,21 @@ pub struct GwOperationResult {
     pub success: bool,
     pub error: Option<String>,
     pub data: Option<String>,
+    /// JSON 参数解析失败时的诊断 (字段路径、期望类型)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub diagnostic: Option<GcJsonDiagnostic>,
+}
+
+impl GwOperationResult {
+    /// JSON 参数解析失败 (传入第一个失败参数的诊断)
+    pub fn gw_json_failure(diagnostic: Option<GcJsonDiagnostic>) -> Self {
+        Self {
+            success: false,
+            error: Some(diagnostic.as_ref().map_or_else(|| "JSON 解析失败".to_string(), |d| d.to_string())),
+            data: None,
+            diagnostic,
+        }
+    }
 }
 
 /// 刷新商店 (消耗2金币)
@@ -163,10 +238,10 @@ pub fn gw_refresh_shop(
     pool_json: &str,
     random_rolls_json: &str,
 ) -> JsValue {
-    let economy: Result<GcEconomy, _> = serde_json::from_str(economy_json);
-    let shop: Result<GcTavernShop, _> = serde_json::from_str(shop_json);
-    let pool: Result<GcMonsterPool, _> = serde_json::from_str(pool_json);
-    let rolls: Result<Vec<u8>, _> = serde_json::from_str(random_rolls_json);
+    let economy: Result<GcEconomy, _> = gw_try_parse_json("economy_json", economy_json);
+    let shop: Result<GcTavernShop, _> = gw_try_parse_json("shop_json", shop_json);
+    let pool: Result<GcMonsterPool, _> = gw_try_parse_json("pool_json", pool_json);
+    let rolls: Result<Vec<u8>, _> = gw_try_parse_json("random_rolls_json", random_rolls_json);
     
     match (economy, shop, pool, rolls) {
         (Ok(mut eco), Ok(mut s), Ok(p), Ok(r)) => {
@@ -175,8 +250,9 @@ pub fn gw_refresh_shop(
                     success: false,
                     error: Some("金币不足".to_string()),
                     data: None,
+                    diagnostic: None,
                 };
-                return serde_wasm_bindgen::to_value(&result).unwrap_or(JsValue::NULL);
+                return gw_to_js_or_null(&result);
             }
             
             let result_data = serde_json::json!({
@@ -188,16 +264,13 @@ pub fn gw_refresh_shop(
                 success: true,
                 error: None,
                 data: Some(result_data.to_string()),
+                diagnostic: None,
             };
-            serde_wasm_bindgen::to_value(&result).unwrap_or(JsValue::NULL)
+            gw_to_js_or_null(&result)
         }
-        _ => {
-            let result = GwOperationResult {
-                success: false,
-                error: Some("JSON 解析失败".to_string()),
-                data: None,
-            };
-            serde_wasm_bindgen::to_value(&result).unwrap_or(JsValue::NULL)
+        (economy, shop, pool, rolls) => {
+            let result = GwOperationResult::gw_json_failure(economy.err().or(shop.err()).or(pool.err()).or(rolls.err()));
+            gw_to_js_or_null(&result)
         }
     }
 }
@@ -205,7 +278,7 @@ pub fn gw_refresh_shop(
 /// 冻结/解冻商店槽位
 #[wasm_bindgen]
 pub fn gw_toggle_freeze(shop_json: &str, slot_index: usize) -> JsValue {
-    let shop: Result<GcTavernShop, _> = serde_json::from_str(shop_json);
+    let shop: Result<GcTavernShop, _> = gw_try_parse_json("shop_json", shop_json);
     
     match shop {
         Ok(mut s) => {
@@ -214,16 +287,13 @@ pub fn gw_toggle_freeze(shop_json: &str, slot_index: usize) -> JsValue {
                 success: true,
                 error: None,
                 data: serde_json::to_string(&s).ok(),
+                diagnostic: None,
             };
-            serde_wasm_bindgen::to_value(&r).unwrap_or(JsValue::NULL)
+            gw_to_js_or_null(&r)
         }
-        Err(_) => {
-            let r = GwOperationResult {
-                success: false,
-                error: Some("JSON 解析失败".to_string()),
-                data: None,
-            };
-            serde_wasm_bindgen::to_value(&r).unwrap_or(JsValue::NULL)
+        Err(e) => {
+            let r = GwOperationResult::gw_json_failure(Some(e));
+            gw_to_js_or_null(&r)
         }
     }
 }
@@ -240,8 +310,8 @@ pub fn gw_buy_monster(
     shop_json: &str,
     slot_index: usize,
 ) -> JsValue {
-    let economy: Result<GcEconomy, _> = serde_json::from_str(economy_json);
-    let shop: Result<GcTavernShop, _> = serde_json::from_str(shop_json);
+    let economy: Result<GcEconomy, _> = gw_try_parse_json("economy_json", economy_json);
+    let shop: Result<GcTavernShop, _> = gw_try_parse_json("shop_json", shop_json);
     
     match (economy, shop) {
         (Ok(mut eco), Ok(mut s)) => {
@@ -256,26 +326,24 @@ pub fn gw_buy_monster(
                         success: true,
                         error: None,
                         data: Some(result_data.to_string()),
+                        diagnostic: None,
                     };
-                    serde_wasm_bindgen::to_value(&result).unwrap_or(JsValue::NULL)
+                    gw_to_js_or_null(&result)
                 }
                 None => {
                     let result = GwOperationResult {
                         success: false,
                         error: Some("购买失败: 槽位为空或金币不足".to_string()),
                         data: None,
+                        diagnostic: None,
                     };
-                    serde_wasm_bindgen::to_value(&result).unwrap_or(JsValue::NULL)
+                    gw_to_js_or_null(&result)
                 }
             }
         }
-        _ => {
-            let result = GwOperationResult {
-                success: false,
-                error: Some("JSON 解析失败".to_string()),
-                data: None,
-            };
-            serde_wasm_bindgen::to_value(&result).unwrap_or(JsValue::NULL)
+        (economy, shop) => {
+            let result = GwOperationResult::gw_json_failure(economy.err().or(shop.err()));
+            gw_to_js_or_null(&result)
         }
     }
 }
@@ -284,8 +352,8 @@ pub fn gw_buy_monster(
 /// 返回: { success, error?, data: {economy} }
 #[wasm_bindgen]
 pub fn gw_sell_monster(economy_json: &str, monster_json: &str) -> JsValue {
-    let economy: Result<GcEconomy, _> = serde_json::from_str(economy_json);
-    let monster: Result<GcMonster, _> = serde_json::from_str(monster_json);
+    let economy: Result<GcEconomy, _> = gw_try_parse_json("economy_json", economy_json);
+    let monster: Result<GcMonster, _> = gw_try_parse_json("monster_json", monster_json);
     
     match (economy, monster) {
         (Ok(mut eco), Ok(m)) => {
@@ -300,16 +368,13 @@ pub fn gw_sell_monster(economy_json: &str, monster_json: &str) -> JsValue {
                 success: true,
                 error: None,
                 data: Some(result_data.to_string()),
+                diagnostic: None,
             };
-            serde_wasm_bindgen::to_value(&result).unwrap_or(JsValue::NULL)
+            gw_to_js_or_null(&result)
         }
-        _ => {
-            let result = GwOperationResult {
-                success: false,
-                error: Some("JSON 解析失败".to_string()),
-                data: None,
-            };
-            serde_wasm_bindgen::to_value(&result).unwrap_or(JsValue::NULL)
+        (economy, monster) => {
+            let result = GwOperationResult::gw_json_failure(economy.err().or(monster.err()));
+            gw_to_js_or_null(&result)
         }
     }
 }
@@ -321,7 +386,7 @@ pub fn gw_sell_monster(economy_json: &str, monster_json: &str) -> JsValue {
 /// 购买经验 (4金 -> 4经验)
 #[wasm_bindgen]
 pub fn gw_buy_xp(economy_json: &str) -> JsValue {
-    let economy: Result<GcEconomy, _> = serde_json::from_str(economy_json);
+    let economy: Result<GcEconomy, _> = gw_try_parse_json("economy_json", economy_json);
     
     match economy {
         Ok(mut eco) => {
@@ -330,24 +395,22 @@ pub fn gw_buy_xp(economy_json: &str) -> JsValue {
                     success: true,
                     error: None,
                     data: serde_json::to_string(&eco).ok(),
+                    diagnostic: None,
                 };
-                serde_wasm_bindgen::to_value(&result).unwrap_or(JsValue::NULL)
+                gw_to_js_or_null(&result)
             } else {
                 let result = GwOperationResult {
                     success: false,
                     error: Some("金币不足".to_string()),
                     data: None,
+                    diagnostic: None,
                 };
-                serde_wasm_bindgen::to_value(&result).unwrap_or(JsValue::NULL)
+                gw_to_js_or_null(&result)
             }
         }
-        Err(_) => {
-            let result = GwOperationResult {
-                success: false,
-                error: Some("JSON 解析失败".to_string()),
-                data: None,
-            };
-            serde_wasm_bindgen::to_value(&result).unwrap_or(JsValue::NULL)
+        Err(e) => {
+            let result = GwOperationResult::gw_json_failure(Some(e));
+            gw_to_js_or_null(&result)
         }
     }
 }
@@ -355,7 +418,7 @@ pub fn gw_buy_xp(economy_json: &str) -> JsValue {
 /// 收取回合收入
 #[wasm_bindgen]
 pub fn gw_collect_income(economy_json: &str) -> JsValue {
-    let economy: Result<GcEconomy, _> = serde_json::from_str(economy_json);
+    let economy: Result<GcEconomy, _> = gw_try_parse_json("economy_json", economy_json);
     
     match economy {
         Ok(mut eco) => {
@@ -368,16 +431,13 @@ pub fn gw_collect_income(economy_json: &str) -> JsValue {
                 success: true,
                 error: None,
                 data: Some(result_data.to_string()),
+                diagnostic: None,
             };
-            serde_wasm_bindgen::to_value(&result).unwrap_or(JsValue::NULL)
+            gw_to_js_or_null(&result)
         }
-        Err(_) => {
-            let result = GwOperationResult {
-                success: false,
-                error: Some("JSON 解析失败".to_string()),
-                data: None,
-            };
-            serde_wasm_bindgen::to_value(&result).unwrap_or(JsValue::NULL)
+        Err(e) => {
+            let result = GwOperationResult::gw_json_failure(Some(e));
+            gw_to_js_or_null(&result)
         }
     }
 }
@@ -385,12 +445,12 @@ pub fn gw_collect_income(economy_json: &str) -> JsValue {
 /// 获取经济信息
 #[wasm_bindgen]
 pub fn gw_get_economy_info(economy_json: &str) -> JsValue {
-    let economy: Result<GcEconomy, _> = serde_json::from_str(economy_json);
+    let economy: Result<GcEconomy, _> = gw_try_parse_json("economy_json", economy_json);
     
     match economy {
         Ok(eco) => {
             let info = GwEconomyInfo::from(&eco);
-            serde_wasm_bindgen::to_value(&info).unwrap_or(JsValue::NULL)
+            gw_to_js_or_null(&info)
         }
         Err(_) => JsValue::NULL,
     }
@@ -412,13 +472,13 @@ pub struct GwMergeGroup {
 /// 查找可合并的组
 #[wasm_bindgen]
 pub fn gw_find_mergeable(board_json: &str, bench_json: &str) -> JsValue {
-    let board: Result<Vec<Option<GcMonster>>, _> = serde_json::from_str(board_json);
-    let bench: Result<Vec<GcMonster>, _> = serde_json::from_str(bench_json);
+    let board: Result<Vec<Option<GcMonster>>, _> = gw_try_parse_json("board_json", board_json);
+    let bench: Result<Vec<GcMonster>, _> = gw_try_parse_json("bench_json", bench_json);
     
     match (board, bench) {
         (Ok(b), Ok(be)) => {
             let groups = gc_find_mergeable_groups(&b, &be);
-            serde_wasm_bindgen::to_value(&groups).unwrap_or(JsValue::NULL)
+            gw_to_js_or_null(&groups)
         }
         _ => JsValue::NULL,
     }
@@ -428,8 +488,8 @@ pub fn gw_find_mergeable(board_json: &str, bench_json: &str) -> JsValue {
 /// 返回: { success, merged?, board, bench }
 #[wasm_bindgen]
 pub fn gw_auto_merge_once(board_json: &str, bench_json: &str) -> JsValue {
-    let board: Result<Vec<Option<GcMonster>>, _> = serde_json::from_str(board_json);
-    let bench: Result<Vec<GcMonster>, _> = serde_json::from_str(bench_json);
+    let board: Result<Vec<Option<GcMonster>>, _> = gw_try_parse_json("board_json", board_json);
+    let bench: Result<Vec<GcMonster>, _> = gw_try_parse_json("bench_json", bench_json);
     
     match (board, bench) {
         (Ok(mut b), Ok(mut be)) => {
@@ -443,16 +503,13 @@ pub fn gw_auto_merge_once(board_json: &str, bench_json: &str) -> JsValue {
                 success: true,
                 error: None,
                 data: Some(result_data.to_string()),
+                diagnostic: None,
             };
-            serde_wasm_bindgen::to_value(&result).unwrap_or(JsValue::NULL)
+            gw_to_js_or_null(&result)
         }
-        _ => {
-            let result = GwOperationResult {
-                success: false,
-                error: Some("JSON 解析失败".to_string()),
-                data: None,
-            };
-            serde_wasm_bindgen::to_value(&result).unwrap_or(JsValue::NULL)
+        (board, bench) => {
+            let result = GwOperationResult::gw_json_failure(board.err().or(bench.err()));
+            gw_to_js_or_null(&result)
         }
     }
 }
@@ -461,8 +518,8 @@ pub fn gw_auto_merge_once(board_json: &str, bench_json: &str) -> JsValue {
 /// 返回: { success, merge_count, board, bench }
 #[wasm_bindgen]
 pub fn gw_auto_merge_all(board_json: &str, bench_json: &str) -> JsValue {
-    let board: Result<Vec<Option<GcMonster>>, _> = serde_json::from_str(board_json);
-    let bench: Result<Vec<GcMonster>, _> = serde_json::from_str(bench_json);
+    let board: Result<Vec<Option<GcMonster>>, _> = gw_try_parse_json("board_json", board_json);
+    let bench: Result<Vec<GcMonster>, _> = gw_try_parse_json("bench_json", bench_json);
     
     match (board, bench) {
         (Ok(mut b), Ok(mut be)) => {
@@ -476,16 +533,13 @@ pub fn gw_auto_merge_all(board_json: &str, bench_json: &str) -> JsValue {
                 success: true,
                 error: None,
                 data: Some(result_data.to_string()),
+                diagnostic: None,
             };
-            serde_wasm_bindgen::to_value(&result).unwrap_or(JsValue::NULL)
+            gw_to_js_or_null(&result)
         }
-        _ => {
-            let result = GwOperationResult {
-                success: false,
-                error: Some("JSON 解析失败".to_string()),
-                data: None,
-            };
-            serde_wasm_bindgen::to_value(&result).unwrap_or(JsValue::NULL)
+        (board, bench) => {
+            let result = GwOperationResult::gw_json_failure(board.err().or(bench.err()));
+            gw_to_js_or_null(&result)
         }
     }
 }
@@ -503,8 +557,8 @@ pub fn gw_deploy_from_bench(
     monster_id: &str,
     slot: u8,
 ) -> JsValue {
-    let arena: Result<GcBattleArena, _> = serde_json::from_str(arena_json);
-    let bench: Result<Vec<GcMonster>, _> = serde_json::from_str(bench_json);
+    let arena: Result<GcBattleArena, _> = gw_try_parse_json("arena_json", arena_json);
+    let bench: Result<Vec<GcMonster>, _> = gw_try_parse_json("bench_json", bench_json);
     
     match (arena, bench) {
         (Ok(mut ar), Ok(mut be)) => {
@@ -518,26 +572,24 @@ pub fn gw_deploy_from_bench(
                         success: true,
                         error: None,
                         data: Some(result_data.to_string()),
+                        diagnostic: None,
                     };
-                    serde_wasm_bindgen::to_value(&result).unwrap_or(JsValue::NULL)
+                    gw_to_js_or_null(&result)
                 }
                 Err(e) => {
                     let result = GwOperationResult {
                         success: false,
                         error: Some(e),
                         data: None,
+                        diagnostic: None,
                     };
-                    serde_wasm_bindgen::to_value(&result).unwrap_or(JsValue::NULL)
+                    gw_to_js_or_null(&result)
                 }
             }
         }
-        _ => {
-            let result = GwOperationResult {
-                success: false,
-                error: Some("JSON 解析失败".to_string()),
-                data: None,
-            };
-            serde_wasm_bindgen::to_value(&result).unwrap_or(JsValue::NULL)
+        (arena, bench) => {
+            let result = GwOperationResult::gw_json_failure(arena.err().or(bench.err()));
+            gw_to_js_or_null(&result)
         }
     }
 }
@@ -550,8 +602,8 @@ pub fn gw_recall_to_bench(
     bench_json: &str,
     slot: u8,
 ) -> JsValue {
-    let arena: Result<GcBattleArena, _> = serde_json::from_str(arena_json);
-    let bench: Result<Vec<GcMonster>, _> = serde_json::from_str(bench_json);
+    let arena: Result<GcBattleArena, _> = gw_try_parse_json("arena_json", arena_json);
+    let bench: Result<Vec<GcMonster>, _> = gw_try_parse_json("bench_json", bench_json);
     
     match (arena, bench) {
         (Ok(mut ar), Ok(mut be)) => {
@@ -565,16 +617,13 @@ pub fn gw_recall_to_bench(
                 success: true,
                 error: None,
                 data: Some(result_data.to_string()),
+                diagnostic: None,
             };
-            serde_wasm_bindgen::to_value(&result).unwrap_or(JsValue::NULL)
+            gw_to_js_or_null(&result)
         }
-        _ => {
-            let result = GwOperationResult {
-                success: false,
-                error: Some("JSON 解析失败".to_string()),
-                data: None,
-            };
-            serde_wasm_bindgen::to_value(&result).unwrap_or(JsValue::NULL)
+        (arena, bench) => {
+            let result = GwOperationResult::gw_json_failure(arena.err().or(bench.err()));
+            gw_to_js_or_null(&result)
         }
     }
 }
@@ -582,7 +631,7 @@ pub fn gw_recall_to_bench(
 /// 战场内换位
 #[wasm_bindgen]
 pub fn gw_swap_positions(arena_json: &str, slot_a: u8, slot_b: u8) -> JsValue {
-    let arena: Result<GcBattleArena, _> = serde_json::from_str(arena_json);
+    let arena: Result<GcBattleArena, _> = gw_try_parse_json("arena_json", arena_json);
     
     match arena {
         Ok(mut ar) => {
@@ -592,26 +641,397 @@ pub fn gw_swap_positions(arena_json: &str, slot_a: u8, slot_b: u8) -> JsValue {
                         success: true,
                         error: None,
                         data: serde_json::to_string(&ar).ok(),
+                        diagnostic: None,
                     };
-                    serde_wasm_bindgen::to_value(&result).unwrap_or(JsValue::NULL)
+                    gw_to_js_or_null(&result)
                 }
                 Err(e) => {
                     let result = GwOperationResult {
                         success: false,
                         error: Some(e),
                         data: None,
+                        diagnostic: None,
                     };
-                    serde_wasm_bindgen::to_value(&result).unwrap_or(JsValue::NULL)
+                    gw_to_js_or_null(&result)
                 }
             }
         }
-        Err(_) => {
-            let result = GwOperationResult {
+        Err(e) => {
+            let result = GwOperationResult::gw_json_failure(Some(e));
+            gw_to_js_or_null(&result)
+        }
+    }
+}
+
+// =============================================================================
+// 前后排
+// =============================================================================
+
+/// 构造沙盘位置 (back_row = true 表示后排)
+fn gw_position(back_row: bool, slot: u8) -> GcArenaPosition {
+    if back_row {
+        GcArenaPosition::back(slot)
+    } else {
+        GcArenaPosition::front(slot)
+    }
+}
+
+/// 从手牌区部署到指定排
+/// 返回: { success, error?, data: {arena, bench} }
+#[wasm_bindgen]
+pub fn gw_deploy_to_row(
+    arena_json: &str,
+    bench_json: &str,
+    monster_id: &str,
+    back_row: bool,
+    slot: u8,
+) -> JsValue {
+    let arena: Result<GcBattleArena, _> = gw_try_parse_json("arena_json", arena_json);
+    let bench: Result<Vec<GcMonster>, _> = gw_try_parse_json("bench_json", bench_json);
+
+    let result = match (arena, bench) {
+        (Ok(mut ar), Ok(mut be)) => match ar.deploy_from_bench_to(&mut be, monster_id, gw_position(back_row, slot)) {
+            Ok(()) => {
+                let result_data = serde_json::json!({
+                    "arena": ar,
+                    "bench": be
+                });
+                GwOperationResult {
+                    success: true,
+                    error: None,
+                    data: Some(result_data.to_string()),
+                    diagnostic: None,
+                }
+            }
+            Err(e) => GwOperationResult {
                 success: false,
-                error: Some("JSON 解析失败".to_string()),
+                error: Some(e),
                 data: None,
-            };
-            serde_wasm_bindgen::to_value(&result).unwrap_or(JsValue::NULL)
+                diagnostic: None,
+            },
+        },
+        (arena, bench) => GwOperationResult::gw_json_failure(arena.err().or(bench.err())),
+    };
+
+    gw_to_js_or_null(&result)
+}
+
+/// 从指定排召回到手牌区
+/// 返回: { success, error?, data: {arena, bench, monster?} }
+#[wasm_bindgen]
+pub fn gw_recall_from_row(arena_json: &str, bench_json: &str, back_row: bool, slot: u8) -> JsValue {
+    let arena: Result<GcBattleArena, _> = gw_try_parse_json("arena_json", arena_json);
+    let bench: Result<Vec<GcMonster>, _> = gw_try_parse_json("bench_json", bench_json);
+
+    let result = match (arena, bench) {
+        (Ok(mut ar), Ok(mut be)) => {
+            let monster = ar.recall_from(&mut be, gw_position(back_row, slot));
+            let result_data = serde_json::json!({
+                "arena": ar,
+                "bench": be,
+                "monster": monster
+            });
+            GwOperationResult {
+                success: true,
+                error: None,
+                data: Some(result_data.to_string()),
+                diagnostic: None,
+            }
         }
+        (arena, bench) => GwOperationResult::gw_json_failure(arena.err().or(bench.err())),
+    };
+
+    gw_to_js_or_null(&result)
+}
+
+/// 跨排移动怪兽 (目标有怪兽时互换)
+/// 返回: { success, error?, data: arena }
+#[wasm_bindgen]
+pub fn gw_move_monster(
+    arena_json: &str,
+    from_back_row: bool,
+    from_slot: u8,
+    to_back_row: bool,
+    to_slot: u8,
+) -> JsValue {
+    let arena: Result<GcBattleArena, _> = gw_try_parse_json("arena_json", arena_json);
+
+    let result = match arena {
+        Ok(mut ar) => match ar.move_player_monster(gw_position(from_back_row, from_slot), gw_position(to_back_row, to_slot)) {
+            Ok(()) => GwOperationResult {
+                success: true,
+                error: None,
+                data: serde_json::to_string(&ar).ok(),
+                diagnostic: None,
+            },
+            Err(e) => GwOperationResult {
+                success: false,
+                error: Some(e),
+                data: None,
+                diagnostic: None,
+            },
+        },
+        Err(e) => GwOperationResult::gw_json_failure(Some(e)),
+    };
+
+    gw_to_js_or_null(&result)
+}
+
+/// 获取指定位置怪兽可攻击的敌方位置 (JSON 数组)
+#[wasm_bindgen]
+pub fn gw_targetable_positions(arena_json: &str, back_row: bool, slot: u8) -> String {
+    let arena: Result<GcBattleArena, _> = gw_try_parse_json("arena_json", arena_json);
+    let positions = arena.ok().and_then(|ar| {
+        let reach = ar.player_monster_at(gw_position(back_row, slot))?.reach;
+        Some(ar.enemy_targetable_positions(reach))
+    });
+    serde_json::to_string(&positions.unwrap_or_default()).unwrap_or_default()
+}
+
+/// 指定位置攻击
+/// 输入: arena_json, 攻击者位置, 目标位置 (target_slot = -1 表示直接攻击)
+/// 返回: { success, error?, data: {arena, outcome} }
+#[wasm_bindgen]
+pub fn gw_attack_position(
+    arena_json: &str,
+    attacker_back_row: bool,
+    attacker_slot: u8,
+    target_back_row: bool,
+    target_slot: i32,
+) -> JsValue {
+    let arena: Result<GcBattleArena, _> = gw_try_parse_json("arena_json", arena_json);
+    let target = u8::try_from(target_slot).ok().map(|slot| gw_position(target_back_row, slot));
+
+    let result = match arena {
+        Ok(mut ar) => match ar.player_attack_at(gw_position(attacker_back_row, attacker_slot), target) {
+            Ok(outcome) => {
+                let result_data = serde_json::json!({
+                    "arena": ar,
+                    "outcome": outcome
+                });
+                GwOperationResult {
+                    success: true,
+                    error: None,
+                    data: Some(result_data.to_string()),
+                    diagnostic: None,
+                }
+            }
+            Err(e) => GwOperationResult {
+                success: false,
+                error: Some(e),
+                data: None,
+                diagnostic: None,
+            },
+        },
+        Err(e) => GwOperationResult::gw_json_failure(Some(e)),
+    };
+
+    gw_to_js_or_null(&result)
+}
+
+/// 自动攻击 (遵循前后排规则自动选择目标)
+/// 返回: { success, error?, data: {arena, outcome} }
+#[wasm_bindgen]
+pub fn gw_auto_attack(arena_json: &str, back_row: bool, slot: u8) -> JsValue {
+    let arena: Result<GcBattleArena, _> = gw_try_parse_json("arena_json", arena_json);
+
+    let result = match arena {
+        Ok(mut ar) => match ar.player_auto_attack(gw_position(back_row, slot)) {
+            Ok(outcome) => {
+                let result_data = serde_json::json!({
+                    "arena": ar,
+                    "outcome": outcome
+                });
+                GwOperationResult {
+                    success: true,
+                    error: None,
+                    data: Some(result_data.to_string()),
+                    diagnostic: None,
+                }
+            }
+            Err(e) => GwOperationResult {
+                success: false,
+                error: Some(e),
+                data: None,
+                diagnostic: None,
+            },
+        },
+        Err(e) => GwOperationResult::gw_json_failure(Some(e)),
+    };
+
+    gw_to_js_or_null(&result)
+}
+
+// =============================================================================
+// 先攻
+// =============================================================================
+
+/// 解析可选的伙伴光环 (空字符串 = 无光环)
+fn gw_parse_aura(aura_json: &str) -> Option<GcCompanionAura> {
+    gc_parse_json("aura_json", aura_json).ok()
+}
+
+/// 获取先攻顺序 (JSON 数组)
+#[wasm_bindgen]
+pub fn gw_initiative_order(arena_json: &str, aura_json: &str) -> String {
+    let arena: Result<GcBattleArena, _> = gw_try_parse_json("arena_json", arena_json);
+    let aura = gw_parse_aura(aura_json);
+    match arena {
+        Ok(ar) => serde_json::to_string(&ar.initiative_order(aura.as_ref())).unwrap_or_default(),
+        Err(_) => "[]".to_string(),
+    }
+}
+
+/// 执行一轮自动战斗
+/// 返回: { success, error?, data: {arena, result: {initiative, actions}} }
+#[wasm_bindgen]
+pub fn gw_run_combat_round(arena_json: &str, aura_json: &str) -> JsValue {
+    let arena: Result<GcBattleArena, _> = gw_try_parse_json("arena_json", arena_json);
+    let aura = gw_parse_aura(aura_json);
+
+    let result = match arena {
+        Ok(mut ar) => {
+            let round = ar.run_combat_round(aura.as_ref());
+            let result_data = serde_json::json!({
+                "arena": ar,
+                "result": round
+            });
+            GwOperationResult {
+                success: true,
+                error: None,
+                data: Some(result_data.to_string()),
+                diagnostic: None,
+            }
+        }
+        Err(e) => GwOperationResult::gw_json_failure(Some(e)),
+    };
+
+    gw_to_js_or_null(&result)
+}
+
+// =============================================================================
+// 酒馆座位 (手牌区容量校验)
+// =============================================================================
+
+/// 执行酒馆操作 (购买/出售/部署/撤回/刷新/冻结/扩容)
+/// 输入: seat_json, pool_json, action_json, random_rolls_json
+/// 返回: { success, error?, data: {seat, overflow?} 或 {code} }
+#[wasm_bindgen]
+pub fn gw_tavern_action(
+    seat_json: &str,
+    pool_json: &str,
+    action_json: &str,
+    random_rolls_json: &str,
+) -> JsValue {
+    let seat: Result<GcTavernSeat, _> = gw_try_parse_json("seat_json", seat_json);
+    let pool: Result<GcMonsterPool, _> = gw_try_parse_json("pool_json", pool_json);
+    let action: Result<GcTavernAction, _> = gw_try_parse_json("action_json", action_json);
+    let rolls: Result<Vec<u8>, _> = gw_try_parse_json("random_rolls_json", random_rolls_json);
+
+    let result = match (seat, pool, action, rolls) {
+        (Ok(mut s), Ok(p), Ok(a), Ok(r)) => match s.gc_apply_action(&a, &p, &r) {
+            Ok(()) => {
+                let result_data = serde_json::json!({
+                    "seat": s,
+                    "overflow": s.gc_bench_overflow()
+                });
+                GwOperationResult {
+                    success: true,
+                    error: None,
+                    data: Some(result_data.to_string()),
+                    diagnostic: None,
+                }
+            }
+            Err(e) => GwOperationResult {
+                success: false,
+                error: Some(e.to_string()),
+                data: Some(serde_json::json!({ "code": e.gc_code() }).to_string()),
+                diagnostic: None,
+            },
+        },
+        (seat, pool, action, rolls) => GwOperationResult::gw_json_failure(seat.err().or(pool.err()).or(action.err()).or(rolls.err())),
+    };
+
+    gw_to_js_or_null(&result)
+}
+
+/// 获取手牌区强制出售提示 (未超出容量返回 "null")
+#[wasm_bindgen]
+pub fn gw_bench_overflow(seat_json: &str) -> String {
+    let seat: Result<GcTavernSeat, _> = gw_try_parse_json("seat_json", seat_json);
+    match seat {
+        Ok(s) => serde_json::to_string(&s.gc_bench_overflow()).unwrap_or_default(),
+        Err(_) => "null".to_string(),
+    }
+}
+
+// =============================================================================
+// 阵容分享码
+// =============================================================================
+
+/// 生成阵容分享码
+/// 输入: arena_json (玩家一侧为阵容), bench_json
+/// 返回: { success, error?, data: 分享码 }
+#[wasm_bindgen]
+pub fn gw_encode_board_code(arena_json: &str, bench_json: &str) -> JsValue {
+    let arena: Result<GcBattleArena, _> = gw_try_parse_json("arena_json", arena_json);
+    let bench: Result<Vec<GcMonster>, _> = gw_try_parse_json("bench_json", bench_json);
+
+    let result = match (arena, bench) {
+        (Ok(a), Ok(b)) => match GcBoardComposition::gc_from_board(&a, &b).gc_encode() {
+            Ok(code) => GwOperationResult { success: true, error: None, data: Some(code), diagnostic: None },
+            Err(e) => GwOperationResult { success: false, error: Some(e.to_string()), data: None, diagnostic: None },
+        },
+        (arena, bench) => GwOperationResult::gw_json_failure(arena.err().or(bench.err())),
+    };
+    gw_to_js_or_null(&result)
+}
+
+/// 解析阵容分享码并按怪兽池还原 (客户端仅凭分享码渲染阵容)
+/// 返回: { success, error?, data: {composition, arena, bench} }
+#[wasm_bindgen]
+pub fn gw_decode_board_code(code: &str, pool_json: &str) -> JsValue {
+    let pool: Result<GcMonsterPool, _> = gw_try_parse_json("pool_json", pool_json);
+
+    let result = match pool {
+        Ok(p) => match GcBoardComposition::gc_decode(code).and_then(|c| c.gc_to_board(&p).map(|board| (c, board))) {
+            Ok((composition, (arena, bench))) => {
+                let result_data = serde_json::json!({
+                    "composition": composition,
+                    "arena": arena,
+                    "bench": bench
+                });
+                GwOperationResult { success: true, error: None, data: Some(result_data.to_string()), diagnostic: None }
+            }
+            Err(e) => GwOperationResult { success: false, error: Some(e.to_string()), data: None, diagnostic: None },
+        },
+        Err(e) => GwOperationResult::gw_json_failure(Some(e)),
+    };
+    gw_to_js_or_null(&result)
+}
+
+// =============================================================================
+// 流程结算
+// =============================================================================
+
+/// 酒馆对局结算摘要 (结算界面使用，按名次排序)
+/// 返回: GcRunScore 数组；JSON 无效时返回 null
+#[wasm_bindgen]
+pub fn gw_get_tavern_run_summary(game_json: &str) -> JsValue {
+    let game: Result<GcTavernGame, _> = gw_try_parse_json("game_json", game_json);
+    match game {
+        Ok(g) => gw_to_js_or_null(&GcRunScore::gc_from_tavern_game(&g)),
+        Err(_) => JsValue::NULL,
+    }
+}
+
+/// 冒险/单人流程结算 (回合与胜场取自幽灵记录，花费与伤害由调用方累计)
+/// 返回: GcRunScore；JSON 无效时返回 null
+#[wasm_bindgen]
+pub fn gw_get_ghost_run_score(run_json: &str, gold_spent: u32, damage_dealt: u32) -> JsValue {
+    let run: Result<GcGhostRun, _> = gw_try_parse_json("run_json", run_json);
+    match run {
+        Ok(r) => gw_to_js_or_null(&GcRunScore::gc_from_ghost_run(&r, gold_spent, damage_dealt)),
+        Err(_) => JsValue::NULL,
     }
 }
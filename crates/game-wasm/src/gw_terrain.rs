@@ -5,12 +5,14 @@
 
 use wasm_bindgen::prelude::*;
 use game_core::{
-    GcTerrainType, GcMonsterAttribute,
+    GcTerrainType, GcMonsterAttribute, GcColorPalette,
     GcWorldTerrainType, GcEnemyType,
     gc_get_terrain_modifier, gc_generate_battle_terrain,
 };
 use serde::{Deserialize, Serialize};
 
+use crate::gw_to_js_or_null;
+
 // =============================================================================
 // 导出给 JS 的地形类型
 // =============================================================================
@@ -21,6 +23,18 @@ pub struct GwTerrainInfo {
     pub id: String,
     pub name: String,
     pub color: [u8; 4],
+    /// 图案 ID (色盲模式下叠加纹理)
+    pub pattern: String,
+}
+
+/// 怪兽属性信息（用于 JS 端）
+#[derive(Serialize, Deserialize)]
+pub struct GwAttributeInfo {
+    pub id: String,
+    pub name: String,
+    pub color: [u8; 4],
+    /// 图标 ID
+    pub pattern: String,
 }
 
 /// 地形修正信息（用于 JS 端）
@@ -43,6 +57,8 @@ pub struct GwBattleTerrainResult {
     pub enemy_terrain_name: String,
     pub player_color: [u8; 4],
     pub enemy_color: [u8; 4],
+    pub player_pattern: String,
+    pub enemy_pattern: String,
 }
 
 // =============================================================================
@@ -63,7 +79,7 @@ fn terrain_to_string(terrain: GcTerrainType) -> String {
     }
 }
 
-fn string_to_terrain(s: &str) -> GcTerrainType {
+pub(crate) fn string_to_terrain(s: &str) -> GcTerrainType {
     match s.to_lowercase().as_str() {
         "volcano" => GcTerrainType::Volcano,
         "glacier" => GcTerrainType::Glacier,
@@ -89,7 +105,7 @@ fn string_to_attribute(s: &str) -> GcMonsterAttribute {
     }
 }
 
-fn string_to_world_terrain(s: &str) -> GcWorldTerrainType {
+pub(crate) fn string_to_world_terrain(s: &str) -> GcWorldTerrainType {
     match s.to_lowercase().as_str() {
         "desert" => GcWorldTerrainType::Desert,
         "snowfield" => GcWorldTerrainType::Snowfield,
@@ -118,21 +134,54 @@ fn string_to_enemy_type(s: &str) -> GcEnemyType {
 // =============================================================================
 
 /// 获取所有地形类型信息
+/// 
+/// # Arguments
+/// * `palette` - 调色板（"standard" / "deuteranopia" / "protanopia"）
 #[wasm_bindgen]
-pub fn gw_get_all_terrains() -> JsValue {
+pub fn gw_get_all_terrains(palette: &str) -> JsValue {
+    let palette = GcColorPalette::gc_from_id(palette);
     let terrains: Vec<GwTerrainInfo> = GcTerrainType::all()
         .iter()
         .map(|t| {
-            let color = t.color();
+            let color = t.color(palette);
             GwTerrainInfo {
                 id: terrain_to_string(*t),
                 name: t.name().to_string(),
                 color: [color.0, color.1, color.2, color.3],
+                pattern: t.pattern().to_string(),
+            }
+        })
+        .collect();
+
+    gw_to_js_or_null(&terrains)
+}
+
+/// 获取所有怪兽属性信息 (颜色按调色板)
+#[wasm_bindgen]
+pub fn gw_get_all_attributes(palette: &str) -> JsValue {
+    let palette = GcColorPalette::gc_from_id(palette);
+    let attributes: Vec<GwAttributeInfo> = [
+        GcMonsterAttribute::None,
+        GcMonsterAttribute::Fire,
+        GcMonsterAttribute::Water,
+        GcMonsterAttribute::Wind,
+        GcMonsterAttribute::Earth,
+        GcMonsterAttribute::Light,
+        GcMonsterAttribute::Dark,
+    ]
+        .iter()
+        .map(|a| {
+            let color = a.color(palette);
+            GwAttributeInfo {
+                id: format!("{:?}", a).to_lowercase(),
+                name: a.name().to_string(),
+                color: [color.0, color.1, color.2, color.3],
+                pattern: a.pattern().to_string(),
             }
         })
         .collect();
 
-    serde_wasm_bindgen::to_value(&terrains).unwrap_or(JsValue::NULL)
+    gw_to_js_or_null(&attributes)
 }
 
 /// 获取地形对怪兽的修正效果
@@ -155,7 +204,7 @@ pub fn gw_get_terrain_modifier(terrain_id: &str, monster_attribute: &str) -> JsV
         healing_bonus_percent: modifier.healing_bonus_percent,
     };
 
-    serde_wasm_bindgen::to_value(&info).unwrap_or(JsValue::NULL)
+    gw_to_js_or_null(&info)
 }
 
 /// 生成战斗地形
@@ -164,15 +213,17 @@ pub fn gw_get_terrain_modifier(terrain_id: &str, monster_attribute: &str) -> JsV
 /// * `world_terrain` - 大世界地形类型（如 "grassland", "desert"）
 /// * `enemy_type` - 敌人类型（如 "normal", "fire", "boss"）
 /// * `random_seed` - 随机种子（由前端提供，如 Date.now()）
+/// * `palette` - 调色板（"standard" / "deuteranopia" / "protanopia"）
 #[wasm_bindgen]
-pub fn gw_generate_battle_terrain(world_terrain: &str, enemy_type: &str, random_seed: u32) -> JsValue {
+pub fn gw_generate_battle_terrain(world_terrain: &str, enemy_type: &str, random_seed: u32, palette: &str) -> JsValue {
     let world = string_to_world_terrain(world_terrain);
     let enemy = string_to_enemy_type(enemy_type);
+    let palette = GcColorPalette::gc_from_id(palette);
     
     let (player_terrain, enemy_terrain) = gc_generate_battle_terrain(world, enemy, random_seed as u64);
 
-    let player_color = player_terrain.color();
-    let enemy_color = enemy_terrain.color();
+    let player_color = player_terrain.color(palette);
+    let enemy_color = enemy_terrain.color(palette);
 
     let result = GwBattleTerrainResult {
         player_terrain: terrain_to_string(player_terrain),
@@ -181,9 +232,11 @@ pub fn gw_generate_battle_terrain(world_terrain: &str, enemy_type: &str, random_
         enemy_terrain_name: enemy_terrain.name().to_string(),
         player_color: [player_color.0, player_color.1, player_color.2, player_color.3],
         enemy_color: [enemy_color.0, enemy_color.1, enemy_color.2, enemy_color.3],
+        player_pattern: player_terrain.pattern().to_string(),
+        enemy_pattern: enemy_terrain.pattern().to_string(),
     };
 
-    serde_wasm_bindgen::to_value(&result).unwrap_or(JsValue::NULL)
+    gw_to_js_or_null(&result)
 }
 
 /// 应用地形修正到攻击力
@@ -3,10 +3,39 @@
 //! 模块: game-wasm
 //! 前缀: gw_
 //! 文档: 文档/02-game-wasm.md
+//!
+//! ## 错误约定
+//! - 导出函数不得 panic (panic 会使整个 WASM 实例失效)，失败时返回错误值或 null
+//! - 所有失败都记录到最近错误，JS 端可用 `gw_last_error()` 查询原因
+
+use std::cell::RefCell;
 
+use game_core::{gc_parse_json, GcJsonDiagnostic};
 use wasm_bindgen::prelude::*;
 use web_sys::console;
 
+thread_local! {
+    /// 最近一次错误
+    static GW_LAST_ERROR: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// 记录最近错误
+pub fn gw_set_last_error(message: impl Into<String>) {
+    GW_LAST_ERROR.with(|last| *last.borrow_mut() = Some(message.into()));
+}
+
+/// 最近一次错误 (没有错误时为 undefined)
+#[wasm_bindgen]
+pub fn gw_last_error() -> Option<String> {
+    GW_LAST_ERROR.with(|last| last.borrow().clone())
+}
+
+/// 清除最近错误
+#[wasm_bindgen]
+pub fn gw_clear_last_error() {
+    GW_LAST_ERROR.with(|last| *last.borrow_mut() = None);
+}
+
 /// 日志输出到浏览器 console
 #[wasm_bindgen]
 pub fn gw_log(message: &str) {
@@ -27,24 +56,62 @@ pub fn gw_error(message: &str) {
 
 /// 将 Rust 结构序列化为 JSON 字符串
 pub fn gw_to_json<T: serde::Serialize>(value: &T) -> Result<String, JsValue> {
-    serde_json::to_string(value)
-        .map_err(|e| JsValue::from_str(&format!("JSON 序列化失败: {}", e)))
+    serde_json::to_string(value).map_err(|e| {
+        let message = format!("JSON 序列化失败: {}", e);
+        gw_set_last_error(message.as_str());
+        JsValue::from_str(&message)
+    })
 }
 
 /// 将 JSON 字符串反序列化为 Rust 结构
 pub fn gw_from_json<T: serde::de::DeserializeOwned>(json: &str) -> Result<T, JsValue> {
-    serde_json::from_str(json)
-        .map_err(|e| JsValue::from_str(&format!("JSON 反序列化失败: {}", e)))
+    gw_parse_json("json", json)
+}
+
+/// 解析 JSON 参数 (input 为参数名)，失败时抛出带诊断的 JS Error
+pub fn gw_parse_json<T: serde::de::DeserializeOwned>(input: &str, json: &str) -> Result<T, JsValue> {
+    gw_try_parse_json(input, json).map_err(|d| gw_json_error(&d))
+}
+
+/// 解析 JSON 参数，失败时记录最近错误并返回诊断
+pub fn gw_try_parse_json<T: serde::de::DeserializeOwned>(input: &str, json: &str) -> Result<T, GcJsonDiagnostic> {
+    gc_parse_json(input, json).inspect_err(|d| gw_set_last_error(d.to_string()))
+}
+
+/// 诊断转换为 JS Error (`message` 为可读描述，`diagnostic` 为结构化诊断)
+pub fn gw_json_error(diagnostic: &GcJsonDiagnostic) -> JsValue {
+    let error = js_sys::Error::new(&diagnostic.to_string());
+    if let Ok(value) = serde_wasm_bindgen::to_value(diagnostic) {
+        let _ = js_sys::Reflect::set(&error, &JsValue::from_str("diagnostic"), &value);
+    }
+    error.into()
 }
 
 /// 将 Rust 结构转换为 JS 对象 (使用 serde-wasm-bindgen)
 pub fn gw_to_js_value<T: serde::Serialize>(value: &T) -> Result<JsValue, JsValue> {
-    serde_wasm_bindgen::to_value(value)
-        .map_err(|e| JsValue::from_str(&format!("转换为 JS 失败: {}", e)))
+    serde_wasm_bindgen::to_value(value).map_err(|e| {
+        let message = format!("转换为 JS 失败: {}", e);
+        gw_set_last_error(message.as_str());
+        JsValue::from_str(&message)
+    })
+}
+
+/// 将 Rust 结构转换为 JS 对象，失败时记录最近错误并返回 null
+pub fn gw_to_js_or_null<T: serde::Serialize>(value: &T) -> JsValue {
+    match serde_wasm_bindgen::to_value(value) {
+        Ok(js) => js,
+        Err(e) => {
+            gw_set_last_error(format!("转换为 JS 失败: {}", e));
+            JsValue::NULL
+        }
+    }
 }
 
 /// 将 JS 对象转换为 Rust 结构 (使用 serde-wasm-bindgen)
 pub fn gw_from_js_value<T: serde::de::DeserializeOwned>(value: JsValue) -> Result<T, JsValue> {
-    serde_wasm_bindgen::from_value(value)
-        .map_err(|e| JsValue::from_str(&format!("从 JS 转换失败: {}", e)))
+    serde_wasm_bindgen::from_value(value).map_err(|e| {
+        let message = format!("从 JS 转换失败: {}", e);
+        gw_set_last_error(message.as_str());
+        JsValue::from_str(&message)
+    })
 }
@@ -5,28 +5,73 @@
 //!
 //! 文档: 文档/02-game-wasm.md
 //! 前缀: gw_ (函数) / Gw (类型)
+//!
+//! ## 构建档位
+//! - 完整版 (默认): `wasm-pack build --target web`，包含地图、Boss、冒险系统
+//! - 精简版: `wasm-pack build --target web -- --no-default-features`，只含战斗与酒馆
+//! - 功能开关: `maps` (大地图) / `boss` (Boss 战) / `adventure` (职业、装备、捕获、进化、融合、建筑)
+//!
+//! ## 不 panic 约定
+//! 导出函数一旦 panic，整个 WASM 实例失效。边界代码禁止 unwrap / expect / panic (由下方 clippy 规则保证)，
+//! 失败时返回错误或 null，并记录到 `gw_last_error()`。
 
+#![deny(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+
+// 精简版 (lite): 战斗 + 酒馆
 mod gw_bridge;
 mod gw_battle;
-mod gw_boss;
 mod gw_utils;
-mod gw_map;
-mod gw_profession;
-mod gw_equipment;
 mod gw_terrain;
 mod gw_monster;
 mod gw_tavern;
+mod gw_onboarding;
+
+// 完整版 (full): 按功能开关注册
+#[cfg(feature = "boss")]
+mod gw_boss;
+#[cfg(feature = "maps")]
+mod gw_map;
+#[cfg(feature = "adventure")]
+mod gw_profession;
+#[cfg(feature = "adventure")]
+mod gw_equipment;
+#[cfg(feature = "adventure")]
+mod gw_capture;
+#[cfg(feature = "adventure")]
+mod gw_companion;
+#[cfg(feature = "adventure")]
+mod gw_evolution;
+#[cfg(feature = "adventure")]
+mod gw_fusion;
+#[cfg(feature = "adventure")]
+mod gw_structure;
 
 pub use gw_bridge::*;
 pub use gw_battle::*;
-pub use gw_boss::*;
 pub use gw_utils::*;
-pub use gw_map::*;
-pub use gw_profession::*;
-pub use gw_equipment::*;
 pub use gw_terrain::*;
 pub use gw_monster::*;
 pub use gw_tavern::*;
+pub use gw_onboarding::*;
+
+#[cfg(feature = "boss")]
+pub use gw_boss::*;
+#[cfg(feature = "maps")]
+pub use gw_map::*;
+#[cfg(feature = "adventure")]
+pub use gw_profession::*;
+#[cfg(feature = "adventure")]
+pub use gw_equipment::*;
+#[cfg(feature = "adventure")]
+pub use gw_capture::*;
+#[cfg(feature = "adventure")]
+pub use gw_companion::*;
+#[cfg(feature = "adventure")]
+pub use gw_evolution::*;
+#[cfg(feature = "adventure")]
+pub use gw_fusion::*;
+#[cfg(feature = "adventure")]
+pub use gw_structure::*;
 
 use wasm_bindgen::prelude::*;
 
@@ -34,8 +79,11 @@ use wasm_bindgen::prelude::*;
 /// 在 JS 端首次加载时调用
 #[wasm_bindgen(start)]
 pub fn gw_init() {
-    // 设置 panic hook，让 Rust panic 在浏览器 console 显示
-    console_error_panic_hook::set_once();
+    // 设置 panic hook，让 Rust panic 在浏览器 console 显示，并记录到最近错误
+    std::panic::set_hook(Box::new(|info| {
+        gw_set_last_error(format!("WASM panic: {}", info));
+        console_error_panic_hook::hook(info);
+    }));
     
     gw_log("🎮 Game WASM 模块已加载");
 }
@@ -51,3 +99,30 @@ pub fn gw_version() -> String {
 pub fn gw_health_check() -> bool {
     true
 }
+
+/// 构建包含的功能开关 (精简版为空)
+#[wasm_bindgen]
+pub fn gw_build_features() -> Vec<String> {
+    let mut features = Vec::new();
+    if cfg!(feature = "maps") {
+        features.push("maps".to_string());
+    }
+    if cfg!(feature = "boss") {
+        features.push("boss".to_string());
+    }
+    if cfg!(feature = "adventure") {
+        features.push("adventure".to_string());
+    }
+    features
+}
+
+/// 构建档位: "full" / "lite" / "custom" (部分功能)
+#[wasm_bindgen]
+pub fn gw_build_profile() -> String {
+    match gw_build_features().len() {
+        3 => "full",
+        0 => "lite",
+        _ => "custom",
+    }
+    .to_string()
+}
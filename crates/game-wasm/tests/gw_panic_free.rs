@@ -0,0 +1,74 @@
+//! WASM 边界不 panic 测试
+//!
+//! 用各种畸形输入调用导出函数，断言只返回错误值并记录到 `gw_last_error()`，不会 panic。
+//! 只覆盖返回纯 Rust 类型的函数 (返回 JsValue 的函数需要 JS 环境)。
+//! `cargo test -p game-wasm --test gw_panic_free`
+
+use std::panic::catch_unwind;
+
+use game_wasm::*;
+
+/// 畸形 JSON 输入
+fn gw_malformed_inputs() -> Vec<String> {
+    vec![
+        String::new(),
+        "{".to_string(),
+        "null".to_string(),
+        "[]".to_string(),
+        "-1".to_string(),
+        "\"arena\"".to_string(),
+        r#"{"player_slots":[1,2,3]}"#.to_string(),
+        r#"{"slots":[null,null,null,null,null,null,null,null,null]}"#.to_string(),
+        "1e999".to_string(),
+        "{} trailing".to_string(),
+        // 超过 serde_json 递归上限的嵌套
+        format!("{}{}", "[".repeat(10_000), "]".repeat(10_000)),
+    ]
+}
+
+#[test]
+fn test_malformed_json_never_panics() {
+    for input in gw_malformed_inputs() {
+        let result = catch_unwind(|| {
+            gw_clear_last_error();
+            let positions = gw_targetable_positions(&input, true, 9);
+            assert_eq!(positions, "[]");
+            assert!(gw_last_error().is_some_and(|e| e.starts_with("arena_json 解析失败")));
+
+            assert_eq!(gw_initiative_order(&input, &input), "[]");
+            assert_eq!(gw_bench_overflow(&input), "null");
+        });
+        assert!(result.is_ok(), "输入 {:?} 导致 panic", input.chars().take(40).collect::<String>());
+    }
+}
+
+#[cfg(feature = "adventure")]
+#[test]
+fn test_adventure_entry_points_never_panic() {
+    for input in gw_malformed_inputs() {
+        let result = catch_unwind(|| {
+            assert_eq!(gw_capture_chance(&input, 255, "master"), 0);
+            assert!(!gw_can_fuse(&input, "", ""));
+            assert!(gw_enemy_targetable_slots(&input).is_empty());
+        });
+        assert!(result.is_ok(), "输入 {:?} 导致 panic", input.chars().take(40).collect::<String>());
+    }
+}
+
+#[test]
+fn test_out_of_range_arguments_never_panic() {
+    let result = catch_unwind(|| {
+        gw_get_board_slots(u8::MAX);
+        gw_get_tier_weights(u8::MAX);
+        gw_game_mode_name(u8::MAX);
+        gw_tavern_phase_name(u8::MAX);
+        gw_required_tributes(u8::MAX);
+        gw_apply_terrain_atk(u32::MAX, "未知地形", "未知属性");
+        gw_apply_terrain_def(u32::MAX, "", "");
+        gw_calc_terrain_hp_change(u32::MAX, "lava", "fire");
+    });
+    assert!(result.is_ok());
+
+    gw_clear_last_error();
+    assert_eq!(gw_last_error(), None);
+}